@@ -7,7 +7,7 @@ pub async fn run(mut app: App) -> io::Result<()> {
 
     loop {
         match stdin.read_line(&mut buffer) {
-            Ok(0) => return Ok(()),
+            Ok(0) => return app.flush().await.map_err(io::Error::other),
             Ok(_) => match app.command(&buffer).await {
                 Ok(s) => println!("\n{}\n", s),
                 Err(e) => eprintln!("\n{}\n", e),
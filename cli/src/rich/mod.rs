@@ -63,7 +63,7 @@ pub async fn run(mut app: App) -> io::Result<()> {
                                 input.key(key, false);
                                 break command;
                             }
-                            Key::Ctrl('c') => return Ok(()),
+                            Key::Ctrl('c') => return app.flush().await.map_err(io::Error::other),
                             Key::Ctrl('h') => input.key(Key::Backspace, true),
                             Key::Ctrl(c) => input.key(Key::Char(c), true),
                             k => input.key(k, false),
@@ -85,7 +85,9 @@ pub async fn run(mut app: App) -> io::Result<()> {
                     screen.flush()?;
                 }
                 Err(mpsc::RecvTimeoutError::Timeout) => {}
-                Err(mpsc::RecvTimeoutError::Disconnected) => return Ok(()),
+                Err(mpsc::RecvTimeoutError::Disconnected) => {
+                    return app.flush().await.map_err(io::Error::other)
+                }
             }
         };
 
@@ -1,7 +1,7 @@
 use super::{
     Autocomplete, AutocompleteSuggestion, Command, CommandMatches, ContextAwareParse, Runnable,
 };
-use crate::app::AppMeta;
+use crate::app::{AppMeta, CommandError};
 use crate::utils::CaseInsensitiveStr;
 use async_trait::async_trait;
 use std::borrow::Cow;
@@ -46,6 +46,59 @@ impl CommandAlias {
             Self::StrictWildcard { command, .. } => command,
         }
     }
+
+    /// A copy of the alias that refers to a renamed thing by its new name, eg. the numbered
+    /// aliases listing a set of generated inns when one of them is renamed. Returns `None` if the
+    /// alias doesn't refer to the old name.
+    pub async fn renamed(&self, name: &str, new_name: &str, app_meta: &AppMeta) -> Option<Self> {
+        let Self::Literal {
+            term,
+            summary,
+            command,
+        } = self
+        else {
+            return None;
+        };
+
+        let input = replace_name(&command.get_type()?.to_string(), name, new_name)?;
+        let command = Command::parse_input_irrefutable(&input, app_meta).await;
+        command.get_type()?;
+
+        Some(Self::Literal {
+            term: term.clone(),
+            summary: replace_name(summary, name, new_name)
+                .map(Cow::from)
+                .unwrap_or_else(|| summary.clone()),
+            command: Box::new(command),
+        })
+    }
+}
+
+/// Replace each occurrence of `name` in the text that isn't part of a longer word, or return
+/// `None` if there aren't any.
+fn replace_name(text: &str, name: &str, new_name: &str) -> Option<String> {
+    let mut result = String::new();
+    let mut last = 0;
+
+    for (start, _) in text.match_indices(name) {
+        let end = start + name.len();
+
+        if start >= last
+            && !text[..start].ends_with(char::is_alphanumeric)
+            && !text[end..].starts_with(char::is_alphanumeric)
+        {
+            result.push_str(&text[last..start]);
+            result.push_str(new_name);
+            last = end;
+        }
+    }
+
+    if last == 0 {
+        None
+    } else {
+        result.push_str(&text[last..]);
+        Some(result)
+    }
 }
 
 impl Hash for CommandAlias {
@@ -80,9 +133,10 @@ impl PartialEq for CommandAlias {
 
 impl Eq for CommandAlias {}
 
-#[async_trait(?Send)]
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
 impl Runnable for CommandAlias {
-    async fn run(self, input: &str, app_meta: &mut AppMeta) -> Result<String, String> {
+    async fn run(self, input: &str, app_meta: &mut AppMeta) -> Result<String, CommandError> {
         match self {
             Self::Literal { command, .. } => {
                 let mut temp_aliases = mem::take(&mut app_meta.command_aliases);
@@ -113,7 +167,8 @@ impl Runnable for CommandAlias {
     }
 }
 
-#[async_trait(?Send)]
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
 impl ContextAwareParse for CommandAlias {
     async fn parse_input(input: &str, app_meta: &AppMeta) -> CommandMatches<Self> {
         app_meta
@@ -135,7 +190,8 @@ impl ContextAwareParse for CommandAlias {
     }
 }
 
-#[async_trait(?Send)]
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
 impl Autocomplete for CommandAlias {
     async fn autocomplete(input: &str, app_meta: &AppMeta) -> Vec<AutocompleteSuggestion> {
         app_meta
@@ -179,6 +235,20 @@ mod tests {
     use std::collections::HashSet;
     use tokio_test::block_on;
 
+    #[test]
+    fn replace_name_test() {
+        assert_eq!(
+            Some("load Carol".to_string()),
+            replace_name("load Alice", "Alice", "Carol"),
+        );
+        assert_eq!(
+            Some("Carol's mother is Carol".to_string()),
+            replace_name("Alice's mother is Alice", "Alice", "Carol"),
+        );
+        assert_eq!(None, replace_name("load Alicent", "Alice", "Carol"));
+        assert_eq!(None, replace_name("load Bob", "Alice", "Carol"));
+    }
+
     #[test]
     fn literal_constructor_test() {
         let alias = CommandAlias::literal(
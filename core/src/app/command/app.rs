@@ -1,24 +1,32 @@
+use crate::app::page::show_page;
 use crate::app::{
-    AppMeta, Autocomplete, AutocompleteSuggestion, CommandMatches, ContextAwareParse, Runnable,
+    Annotation, AppMeta, Autocomplete, AutocompleteSuggestion, CommandError, CommandMatches,
+    ContextAwareParse, Runnable,
 };
-use crate::utils::CaseInsensitiveStr;
+use crate::utils::{CaseInsensitiveStr, EscapedView};
 use async_trait::async_trait;
 use caith::Roller;
 use initiative_macros::changelog;
 use std::fmt;
+use std::mem;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum AppCommand {
     About,
     Changelog,
     Debug,
+    DebugEvents,
     Help,
+    HelpTopic(String),
+    Page(usize),
     Roll(String),
+    Rolls,
 }
 
-#[async_trait(?Send)]
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
 impl Runnable for AppCommand {
-    async fn run(self, _input: &str, app_meta: &mut AppMeta) -> Result<String, String> {
+    async fn run(self, _input: &str, app_meta: &mut AppMeta) -> Result<String, CommandError> {
         Ok(match self {
             Self::About => include_str!("../../../../data/about.md")
                 .trim_end()
@@ -28,42 +36,76 @@ impl Runnable for AppCommand {
                 app_meta,
                 app_meta.repository.journal().await,
             ),
+            Self::DebugEvents => app_meta.trace_log.display_log(),
             Self::Changelog => changelog!().to_string(),
             Self::Help => include_str!("../../../../data/help.md")
                 .trim_end()
                 .to_string(),
-            Self::Roll(s) => Roller::new(&s)
-                .ok()
-                .and_then(|r| r.roll_with(&mut app_meta.rng).ok())
-                .map(|result| {
-                    result
-                        .to_string()
-                        .trim_end()
-                        .replace('\n', "\\\n")
-                        .replace('`', "")
-                })
-                .ok_or_else(|| {
+            Self::HelpTopic(topic) => help_topic(&topic).ok_or_else(|| {
+                CommandError::parse(
                     format!(
-                        "\"{}\" is not a valid dice formula. See `help` for some examples.",
-                        s
-                    )
-                })?,
+                        "There is no help for \"{}\". See `help` for everything initiative.sh can do.",
+                        EscapedView::new(&topic),
+                    ),
+                    Vec::new(),
+                )
+            })?,
+            Self::Page(number) => show_page(number, app_meta)
+                .map_err(|message| CommandError::unknown_entity(&number.to_string(), message))?,
+            Self::Roll(s) => {
+                let result = Roller::new(&s)
+                    .ok()
+                    .and_then(|r| r.roll_with(&mut app_meta.rng).ok())
+                    .map(|result| result.to_string().trim_end().replace('`', ""))
+                    .ok_or_else(|| {
+                        CommandError::parse(
+                            format!(
+                                "\"{}\" is not a valid dice formula. See `help` for some examples.",
+                                s
+                            ),
+                            Vec::new(),
+                        )
+                    })?;
+
+                app_meta.rng.note(result.replace('\n', "; "));
+                Annotation::Roll
+                    .annotate(&result.replace('\n', "\\\n"))
+                    .to_string()
+            }
+            Self::Rolls => app_meta.rng.display_log(),
         })
     }
 }
 
-#[async_trait(?Send)]
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
 impl ContextAwareParse for AppCommand {
-    async fn parse_input(input: &str, _app_meta: &AppMeta) -> CommandMatches<Self> {
+    async fn parse_input(input: &str, app_meta: &AppMeta) -> CommandMatches<Self> {
         if input.eq_ci("about") {
             CommandMatches::new_canonical(Self::About)
         } else if input.eq_ci("changelog") {
             CommandMatches::new_canonical(Self::Changelog)
         } else if input.eq_ci("debug") {
             CommandMatches::new_canonical(Self::Debug)
+        } else if input.eq_ci("debug events") {
+            CommandMatches::new_canonical(Self::DebugEvents)
         } else if input.eq_ci("help") {
             CommandMatches::new_canonical(Self::Help)
-        } else if input.starts_with_ci("roll ") {
+        } else if let Some(topic) = input
+            .strip_prefix_ci("help ")
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+        {
+            CommandMatches::new_canonical(Self::HelpTopic(topic.to_lowercase()))
+        } else if let Some(number) = input
+            .strip_prefix_ci("page ")
+            .and_then(|s| s.parse().ok())
+            .filter(|n| (1..=app_meta.pages.len()).contains(n))
+        {
+            CommandMatches::new_canonical(Self::Page(number))
+        } else if input.eq_ci("rolls") {
+            CommandMatches::new_canonical(Self::Rolls)
+        } else if input.starts_with_ci("roll ") && !input.starts_with_ci("roll on ") {
             CommandMatches::new_canonical(Self::Roll(input[5..].to_string()))
         } else if !input.chars().all(|c| c.is_ascii_digit())
             && Roller::new(input).map_or(false, |r| r.roll().is_ok())
@@ -75,7 +117,8 @@ impl ContextAwareParse for AppCommand {
     }
 }
 
-#[async_trait(?Send)]
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
 impl Autocomplete for AppCommand {
     async fn autocomplete(input: &str, _app_meta: &AppMeta) -> Vec<AutocompleteSuggestion> {
         if input.is_empty() {
@@ -86,6 +129,7 @@ impl Autocomplete for AppCommand {
             AutocompleteSuggestion::new("about", "about initiative.sh"),
             AutocompleteSuggestion::new("changelog", "show latest updates"),
             AutocompleteSuggestion::new("help", "how to use initiative.sh"),
+            AutocompleteSuggestion::new("rolls", "show every roll made this session"),
         ]
         .into_iter()
         .filter(|suggestion| suggestion.term.starts_with_ci(input))
@@ -105,12 +149,61 @@ impl fmt::Display for AppCommand {
             Self::About => write!(f, "about"),
             Self::Changelog => write!(f, "changelog"),
             Self::Debug => write!(f, "debug"),
+            Self::DebugEvents => write!(f, "debug events"),
             Self::Help => write!(f, "help"),
+            Self::HelpTopic(topic) => write!(f, "help {}", topic),
+            Self::Page(number) => write!(f, "page {}", number),
             Self::Roll(s) => write!(f, "roll {}", s),
+            Self::Rolls => write!(f, "rolls"),
         }
     }
 }
 
+/// The entries of the help text that mention a command, eg. every bullet point with a code span
+/// beginning with `journal` for the topic "journal".
+pub(super) fn help_topic(topic: &str) -> Option<String> {
+    let mut entries = Vec::new();
+    let mut entry = String::new();
+
+    for line in include_str!("../../../../data/help.md").lines() {
+        if line.starts_with("  ") && !entry.is_empty() {
+            entry.push('\n');
+            entry.push_str(line);
+            continue;
+        }
+
+        if mentions(&entry, topic) {
+            entries.push(mem::take(&mut entry));
+        }
+
+        entry.clear();
+        if line.starts_with("* ") {
+            entry.push_str(line);
+        }
+    }
+
+    if mentions(&entry, topic) {
+        entries.push(entry);
+    }
+
+    if entries.is_empty() {
+        None
+    } else {
+        Some(format!(
+            "# Help: {}\n\n{}\n\n_See `help` for everything else initiative.sh can do._",
+            EscapedView::new(topic),
+            entries.join("\n"),
+        ))
+    }
+}
+
+fn mentions(entry: &str, topic: &str) -> bool {
+    entry.split('`').skip(1).step_by(2).any(|code| {
+        code.strip_prefix_ci(topic)
+            .map_or(false, |rest| rest.is_empty() || rest.starts_with(' '))
+    })
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -138,10 +231,36 @@ mod test {
             block_on(AppCommand::parse_input("d20", &app_meta)),
         );
 
+        assert_eq!(
+            CommandMatches::new_canonical(AppCommand::HelpTopic("journal".to_string())),
+            block_on(AppCommand::parse_input("help Journal", &app_meta)),
+        );
+
         assert_eq!(
             CommandMatches::default(),
             block_on(AppCommand::parse_input("potato", &app_meta)),
         );
+
+        assert_eq!(
+            CommandMatches::default(),
+            block_on(AppCommand::parse_input("page 2", &app_meta)),
+        );
+    }
+
+    #[test]
+    fn parse_input_page_test() {
+        let mut app_meta = app_meta();
+        app_meta.pages = vec!["one".to_string(), "two".to_string()];
+
+        assert_eq!(
+            CommandMatches::new_canonical(AppCommand::Page(2)),
+            block_on(AppCommand::parse_input("PAGE 2", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::default(),
+            block_on(AppCommand::parse_input("page 3", &app_meta)),
+        );
     }
 
     #[test]
@@ -177,7 +296,10 @@ mod test {
         );
 
         assert_autocomplete(
-            &[("roll [dice]", "roll eg. 8d6 or d20+3")][..],
+            &[
+                ("roll [dice]", "roll eg. 8d6 or d20+3"),
+                ("rolls", "show every roll made this session"),
+            ][..],
             block_on(AppCommand::autocomplete("roll", &app_meta)),
         );
 
@@ -186,6 +308,34 @@ mod test {
             Vec::<AutocompleteSuggestion>::new(),
             block_on(AppCommand::autocomplete("debug", &app_meta)),
         );
+        assert_eq!(
+            Vec::<AutocompleteSuggestion>::new(),
+            block_on(AppCommand::autocomplete("debug events", &app_meta)),
+        );
+    }
+
+    #[test]
+    fn help_topic_test() {
+        let output = help_topic("merge").unwrap();
+        assert!(output.starts_with("# Help: merge\n\n* after `a character named Roger` and `a character named Rodger`,\n  `merge Rodger into Roger` combines"), "{}", output);
+        assert!(
+            output.ends_with(
+                "deleting the duplicate.\n\n_See `help` for everything else initiative.sh can do._"
+            ),
+            "{}",
+            output
+        );
+
+        let output = help_topic("journal").unwrap();
+        assert!(
+            output.contains("\n* `journal where species = elf"),
+            "{}",
+            output
+        );
+        assert!(!output.contains("`delete [name]`"), "{}", output);
+
+        assert_eq!(None, help_topic("potato"));
+        assert_eq!(None, help_topic("jour"));
     }
 
     #[test]
@@ -196,7 +346,10 @@ mod test {
             AppCommand::About,
             AppCommand::Changelog,
             AppCommand::Debug,
+            AppCommand::DebugEvents,
             AppCommand::Help,
+            AppCommand::HelpTopic("journal".to_string()),
+            AppCommand::Rolls,
         ]
         .into_iter()
         .for_each(|command| {
@@ -0,0 +1,304 @@
+use super::{Autocomplete, AutocompleteSuggestion, Command, Runnable};
+use crate::app::{Annotation, AppMeta, CommandError};
+use crate::utils::{quoted_words, CaseInsensitiveStr};
+use async_trait::async_trait;
+use std::fmt;
+use std::ops::Range;
+
+/// Several commands entered at once, eg. `inn; npc; save Foo` or `inn and npc`, which are run in
+/// order. Any changes they make are undone together with a single `undo`, and if one of the
+/// commands fails, the changes made by those before it are reversed.
+///
+/// Each part is only parsed right before it runs, so that it can refer to things created earlier
+/// in the chain.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChainCommand {
+    /// Each command with its byte offset in the original input.
+    parts: Vec<(usize, String)>,
+}
+
+impl ChainCommand {
+    /// Split the input at each semicolon that isn't in quotes.
+    pub fn parse_semicolons(input: &str) -> Option<Self> {
+        let mut ranges = Vec::new();
+        let mut start = 0;
+        let mut in_quotes = false;
+
+        for (i, c) in input.char_indices() {
+            match c {
+                '"' => in_quotes = !in_quotes,
+                ';' if !in_quotes => {
+                    ranges.push(start..i);
+                    start = i + ';'.len_utf8();
+                }
+                _ => {}
+            }
+        }
+        ranges.push(start..input.len());
+
+        Self::from_ranges(input, ranges)
+    }
+
+    /// Split the input at each unquoted word "and". Since "and" is also common in names and
+    /// descriptions, this is only a match if every part makes sense as a command by itself. If
+    /// `strict`, every part must be a canonical match, such as `create inn and create inn`.
+    pub async fn parse_and(input: &str, app_meta: &AppMeta, strict: bool) -> Option<Self> {
+        let mut ranges = Vec::new();
+        let mut start = 0;
+
+        for word in quoted_words(input) {
+            if (&input[word.range().clone()]).eq_ci("and") {
+                ranges.push(start..word.range().start);
+                start = word.range().end;
+            }
+        }
+        ranges.push(start..input.len());
+
+        let chain = Self::from_ranges(input, ranges)?;
+
+        for (_, part) in chain.parts.iter() {
+            let command = Box::pin(Command::parse_input_irrefutable(part, app_meta)).await;
+
+            if strict && !command.is_canonical() {
+                return None;
+            }
+            command.get_type()?;
+        }
+
+        Some(chain)
+    }
+
+    fn from_ranges(input: &str, ranges: Vec<Range<usize>>) -> Option<Self> {
+        if ranges.len() < 2 {
+            return None;
+        }
+
+        let parts: Vec<(usize, String)> = ranges
+            .into_iter()
+            .filter_map(|range| {
+                let part = &input[range.clone()];
+                let trimmed = part.trim_start();
+                let offset = range.start + part.len() - trimmed.len();
+                let trimmed = trimmed.trim_end();
+
+                if trimmed.is_empty() {
+                    None
+                } else {
+                    Some((offset, trimmed.to_string()))
+                }
+            })
+            .collect();
+
+        if parts.len() < 2 {
+            None
+        } else {
+            Some(Self { parts })
+        }
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Runnable for ChainCommand {
+    async fn run(self, _input: &str, app_meta: &mut AppMeta) -> Result<String, CommandError> {
+        let mut output = String::new();
+
+        app_meta.repository.begin_batch();
+
+        for (offset, part) in self.parts {
+            if !output.is_empty() {
+                output.push_str("\n\n");
+            }
+            output.push_str(&format!("_Running `{}`._\n\n", part));
+
+            let command = Command::parse_input_irrefutable(&part, app_meta).await;
+
+            match command.run(&part, app_meta).await {
+                Ok(part_output) => output.push_str(&part_output),
+                Err(mut error) => {
+                    app_meta.repository.abort_batch().await;
+
+                    if let CommandError::Parse { unknown_words, .. } = &mut error {
+                        unknown_words.iter_mut().for_each(|range| {
+                            *range = range.start + offset..range.end + offset;
+                        });
+                    }
+
+                    output.push_str(error.message());
+                    output.push_str(&format!(
+                        "\n\n{}",
+                        Annotation::Warning.annotate(
+                            "_The remaining commands were not run, and the earlier ones were undone._",
+                        ),
+                    ));
+                    *error.message_mut() = output;
+
+                    return Err(error);
+                }
+            }
+        }
+
+        if app_meta.repository.end_batch().await.is_err() {
+            output.push_str(&format!(
+                "\n\n{}",
+                Annotation::Warning.annotate(
+                    "_The journal couldn't be saved, so all of the commands were undone._",
+                ),
+            ));
+            return Err(CommandError::storage(output));
+        }
+
+        Ok(output)
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Autocomplete for ChainCommand {
+    async fn autocomplete(input: &str, app_meta: &AppMeta) -> Vec<AutocompleteSuggestion> {
+        let chain = match Self::parse_semicolons(input) {
+            Some(chain) => chain,
+            None => return Vec::new(),
+        };
+
+        // Suggest completions for the command currently being typed.
+        let offset = chain.parts.last().unwrap().0;
+
+        Command::autocomplete(&input[offset..], app_meta)
+            .await
+            .into_iter()
+            .map(|suggestion| {
+                AutocompleteSuggestion::new(
+                    format!("{}{}", &input[..offset], suggestion.term),
+                    suggestion.summary,
+                )
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for ChainCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let mut parts = self.parts.iter().map(|(_, part)| part.as_str());
+
+        if let Some(part) = parts.next() {
+            write!(f, "{}", part)?;
+        }
+        parts.try_for_each(|part| write!(f, "; {}", part))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::storage::NullDataStore;
+    use crate::Event;
+    use tokio_test::block_on;
+
+    #[test]
+    fn parse_semicolons_test() {
+        assert_eq!(
+            Some(ChainCommand {
+                parts: vec![(0, "inn".to_string()), (5, "npc".to_string())],
+            }),
+            ChainCommand::parse_semicolons("inn; npc"),
+        );
+
+        assert_eq!(
+            Some(ChainCommand {
+                parts: vec![
+                    (0, r#"inn named "Bob; Sons""#.to_string()),
+                    (22, "save".to_string()),
+                ],
+            }),
+            ChainCommand::parse_semicolons(r#"inn named "Bob; Sons";save;"#),
+        );
+
+        assert_eq!(None, ChainCommand::parse_semicolons("inn"));
+        assert_eq!(None, ChainCommand::parse_semicolons("inn;"));
+        assert_eq!(None, ChainCommand::parse_semicolons("; ;"));
+    }
+
+    #[test]
+    fn parse_and_test() {
+        let app_meta = app_meta();
+
+        assert_eq!(
+            Some(ChainCommand {
+                parts: vec![(0, "inn".to_string()), (8, "npc".to_string())],
+            }),
+            block_on(ChainCommand::parse_and("inn AND npc", &app_meta, false)),
+        );
+
+        assert_eq!(
+            None,
+            block_on(ChainCommand::parse_and("inn and potato", &app_meta, false)),
+        );
+        assert_eq!(
+            None,
+            block_on(ChainCommand::parse_and(
+                r#"inn "and" npc"#,
+                &app_meta,
+                false
+            )),
+        );
+        assert_eq!(
+            None,
+            block_on(ChainCommand::parse_and("inn and", &app_meta, false))
+        );
+
+        assert_eq!(
+            Some(ChainCommand {
+                parts: vec![
+                    (0, "create inn".to_string()),
+                    (15, "create inn".to_string())
+                ],
+            }),
+            block_on(ChainCommand::parse_and(
+                "create inn and create inn",
+                &app_meta,
+                true,
+            )),
+        );
+        assert_eq!(
+            None,
+            block_on(ChainCommand::parse_and(
+                "inn named Foo and Sons",
+                &app_meta,
+                true
+            )),
+        );
+    }
+
+    #[test]
+    fn autocomplete_test() {
+        let app_meta = app_meta();
+
+        assert_eq!(
+            vec![AutocompleteSuggestion::new(
+                "inn; about",
+                "about initiative.sh",
+            )],
+            block_on(ChainCommand::autocomplete("inn; abo", &app_meta)),
+        );
+
+        assert!(block_on(ChainCommand::autocomplete("abo", &app_meta)).is_empty());
+        assert!(block_on(ChainCommand::autocomplete("inn; ", &app_meta)).is_empty());
+    }
+
+    #[test]
+    fn display_test() {
+        assert_eq!(
+            "inn; npc",
+            ChainCommand::parse_semicolons(" inn ;npc ")
+                .unwrap()
+                .to_string(),
+        );
+    }
+
+    fn event_dispatcher(_event: Event) {}
+
+    fn app_meta() -> AppMeta {
+        AppMeta::new(NullDataStore::default(), &event_dispatcher)
+    }
+}
@@ -0,0 +1,181 @@
+use super::app::help_topic;
+use super::{AppCommand, Autocomplete, AutocompleteSuggestion, Command, CommandAlias};
+use crate::app::{AppMeta, CommandError};
+use crate::utils::CaseInsensitiveStr;
+
+/// The most commands to suggest for an unknown command.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// The error shown when nothing understood the input at all. Commands resembling its first word
+/// are suggested, closest first, with their syntax as shown by autocomplete, and `?` is aliased
+/// to the help for that word if it's a command in its own right (eg. `at 25pm`), or for the
+/// closest command if not.
+pub async fn unknown_command(input: &str, app_meta: &mut AppMeta) -> CommandError {
+    let mut message = format!("Unknown command: \"{}\"", input);
+    let suggestions = nearest_commands(input, app_meta).await;
+
+    app_meta
+        .command_aliases
+        .retain(|alias| !matches!(alias, CommandAlias::Literal { term, .. } if term == "?"));
+
+    if !suggestions.is_empty() {
+        message.push_str("\n\nDid you mean:\n");
+        suggestions.iter().for_each(|suggestion| {
+            message.push_str(&format!(
+                "\n* `{}`: {}",
+                suggestion.term, suggestion.summary
+            ))
+        });
+    }
+
+    if let Some(topic) = input
+        .split_whitespace()
+        .next()
+        .map(str::to_lowercase)
+        .filter(|topic| help_topic(topic).is_some())
+        .or_else(|| {
+            suggestions
+                .first()
+                .map(|suggestion| keyword(suggestion).to_lowercase())
+                .filter(|topic| help_topic(topic).is_some())
+        })
+    {
+        message.push_str(&format!("\n\n_Type `?` for help with `{}`._", topic));
+
+        app_meta.command_aliases.insert(CommandAlias::literal(
+            "?",
+            format!("help with {}", topic),
+            AppCommand::HelpTopic(topic).into(),
+        ));
+    }
+
+    let whole_input = 0..input.len();
+    CommandError::parse(message, vec![whole_input])
+}
+
+/// Autocomplete suggestions whose first word is within a few typos of the first word of the input,
+/// eg. `journal` for "jurnal", with only the first suggestion for each word. Only suggestions
+/// beginning with the same letter are considered.
+async fn nearest_commands(input: &str, app_meta: &AppMeta) -> Vec<AutocompleteSuggestion> {
+    let Some(word) = input.split_whitespace().next() else {
+        return Vec::new();
+    };
+    let Some(first_char) = word.chars().next() else {
+        return Vec::new();
+    };
+    let word = word.to_lowercase();
+    let max_distance = (word.chars().count() / 3).max(1);
+
+    let mut candidates: Vec<(usize, AutocompleteSuggestion)> =
+        Command::autocomplete(&first_char.to_string(), app_meta)
+            .await
+            .into_iter()
+            .filter_map(|suggestion| {
+                let distance = edit_distance(&word, &keyword(&suggestion).to_lowercase());
+                if distance <= max_distance {
+                    Some((distance, suggestion))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+    candidates.sort_by(|(a_distance, a), (b_distance, b)| {
+        a_distance
+            .cmp(b_distance)
+            .then_with(|| a.term.cmp_ci(&b.term))
+    });
+    candidates.dedup_by(|(_, a), (_, b)| keyword(a).eq_ci(keyword(b)));
+
+    candidates
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, suggestion)| suggestion)
+        .collect()
+}
+
+/// The first word of a suggestion, eg. `journal` for `journal where [field] = [value]`.
+fn keyword(suggestion: &AutocompleteSuggestion) -> &str {
+    suggestion
+        .term
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+}
+
+/// The number of characters that must be inserted, deleted, or substituted to turn one word into
+/// the other.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(a_char != *b_char);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::storage::NullDataStore;
+    use crate::Event;
+    use tokio_test::block_on;
+
+    #[test]
+    fn edit_distance_test() {
+        assert_eq!(0, edit_distance("journal", "journal"));
+        assert_eq!(1, edit_distance("jurnal", "journal"));
+        assert_eq!(2, edit_distance("hepl", "help"));
+        assert_eq!(3, edit_distance("kitten", "sitting"));
+        assert_eq!(4, edit_distance("", "save"));
+    }
+
+    #[test]
+    fn unknown_command_test() {
+        let mut app_meta = app_meta();
+
+        let error = block_on(unknown_command("jurnal", &mut app_meta));
+        assert_eq!(
+            "Unknown command: \"jurnal\"\n\nDid you mean:\n\n* `journal`: list journal contents\n\n_Type `?` for help with `journal`._",
+            error.message(),
+        );
+        assert!(app_meta.command_aliases.contains(&CommandAlias::literal(
+            "?",
+            "",
+            AppCommand::HelpTopic("journal".to_string()).into(),
+        )));
+
+        let error = block_on(unknown_command("at 25pm", &mut app_meta));
+        assert!(
+            error
+                .message()
+                .ends_with("\n\n_Type `?` for help with `at`._"),
+            "{}",
+            error.message(),
+        );
+        assert!(app_meta.command_aliases.contains(&CommandAlias::literal(
+            "?",
+            "",
+            AppCommand::HelpTopic("at".to_string()).into(),
+        )));
+
+        let error = block_on(unknown_command("potato", &mut app_meta));
+        assert_eq!("Unknown command: \"potato\"", error.message());
+        assert!(app_meta.command_aliases.is_empty());
+    }
+
+    fn event_dispatcher(_event: Event) {}
+
+    fn app_meta() -> AppMeta {
+        AppMeta::new(NullDataStore, &event_dispatcher)
+    }
+}
@@ -1,22 +1,42 @@
 pub use alias::CommandAlias;
 pub use app::AppCommand;
+pub use chain::ChainCommand;
 pub use runnable::{
     Autocomplete, AutocompleteSuggestion, CommandMatches, ContextAwareParse, Runnable,
 };
-pub use tutorial::TutorialCommand;
+pub use tutorial::{TutorialCommand, TutorialProgress};
+pub use wizard::WizardCommand;
 
 #[cfg(test)]
 pub use runnable::assert_autocomplete;
 
 mod alias;
 mod app;
+mod chain;
+mod fallback;
 mod runnable;
 mod tutorial;
-
-use super::AppMeta;
+mod wizard;
+
+use super::{AppMeta, CommandError};
+use crate::campaign::CampaignCommand;
+use crate::clue::ClueCommand;
+use crate::config::ConfigCommand;
+use crate::history::HistoryCommand;
+use crate::homebrew::HomebrewCommand;
+use crate::portent::PortentCommand;
 use crate::reference::ReferenceCommand;
+use crate::religion::ReligionCommand;
+use crate::scene::SceneCommand;
+use crate::session::SessionCommand;
 use crate::storage::StorageCommand;
+use crate::table::TableCommand;
+use crate::template::TemplateCommand;
+use crate::thread::ThreadCommand;
 use crate::time::TimeCommand;
+use crate::treasure::TreasureCommand;
+use crate::treasury::TreasuryCommand;
+use crate::war::WarCommand;
 use crate::world::WorldCommand;
 use async_trait::async_trait;
 use futures::join;
@@ -44,15 +64,36 @@ impl Command {
         }
     }
 
+    /// Was the input claimed outright, rather than only being a possible interpretation?
+    fn is_canonical(&self) -> bool {
+        self.matches.canonical_match.is_some()
+    }
+
     pub async fn parse_input_irrefutable(input: &str, app_meta: &AppMeta) -> Self {
         let parse_results = join!(
             CommandAlias::parse_input(input, app_meta),
             AppCommand::parse_input(input, app_meta),
+            CampaignCommand::parse_input(input, app_meta),
+            ClueCommand::parse_input(input, app_meta),
+            ConfigCommand::parse_input(input, app_meta),
+            HistoryCommand::parse_input(input, app_meta),
+            HomebrewCommand::parse_input(input, app_meta),
+            PortentCommand::parse_input(input, app_meta),
             ReferenceCommand::parse_input(input, app_meta),
+            ReligionCommand::parse_input(input, app_meta),
+            SceneCommand::parse_input(input, app_meta),
+            SessionCommand::parse_input(input, app_meta),
             StorageCommand::parse_input(input, app_meta),
+            TableCommand::parse_input(input, app_meta),
+            TemplateCommand::parse_input(input, app_meta),
+            ThreadCommand::parse_input(input, app_meta),
             TimeCommand::parse_input(input, app_meta),
+            TreasureCommand::parse_input(input, app_meta),
+            TreasuryCommand::parse_input(input, app_meta),
             TutorialCommand::parse_input(input, app_meta),
+            WarCommand::parse_input(input, app_meta),
             WorldCommand::parse_input(input, app_meta),
+            WizardCommand::parse_input(input, app_meta),
         );
 
         // We deliberately skip parse_results.0 and handle it afterwards.
@@ -62,7 +103,52 @@ impl Command {
             .union(parse_results.3)
             .union(parse_results.4)
             .union(parse_results.5)
-            .union(parse_results.6);
+            .union(parse_results.6)
+            .union(parse_results.7)
+            .union(parse_results.8)
+            .union(parse_results.9)
+            .union(parse_results.10)
+            .union(parse_results.11)
+            .union(parse_results.12)
+            .union(parse_results.13)
+            .union(parse_results.14)
+            .union(parse_results.15)
+            .union(parse_results.16)
+            .union(parse_results.17)
+            .union(parse_results.18)
+            .union(parse_results.19)
+            .union(parse_results.20)
+            .union(parse_results.21)
+            .union(parse_results.22);
+
+        // `wizard npc` also reads as a description of a character, but anyone typing it is looking
+        // for the wizard rather than an NPC who happens to be called a wizard. Likewise, a homebrew
+        // definition such as `species add warforged like dwarf` isn't a request for a dwarf.
+        if matches!(
+            result.canonical_match,
+            Some(CommandType::Homebrew(_) | CommandType::Wizard(_)),
+        ) {
+            result.fuzzy_matches.clear();
+        }
+
+        // Only treat the input as a chain of commands if nothing has claimed it outright, so that
+        // eg. the spell "Detect Evil and Good" isn't split. A semicolon is a deliberate separator
+        // that takes precedence over fuzzy matches such as "inn named Foo; npc" being an inn
+        // with a strange name. "And" is used if the input doesn't make sense otherwise, or if it
+        // joins commands that are each unambiguous, as in "create inn and create inn".
+        if result.canonical_match.is_none() {
+            if let Some(chain) = ChainCommand::parse_semicolons(input) {
+                result.fuzzy_matches.clear();
+                result.push_canonical(chain.into());
+            } else if result.fuzzy_matches.is_empty() {
+                if let Some(chain) = ChainCommand::parse_and(input, app_meta, false).await {
+                    result.push_fuzzy(chain.into());
+                }
+            } else if let Some(chain) = ChainCommand::parse_and(input, app_meta, true).await {
+                result.fuzzy_matches.clear();
+                result.push_canonical(chain.into());
+            }
+        }
 
         // While it is normally a fatal error to encounter two command subtypes claiming canonical
         // matches on a given input, the exception is where aliases are present. In this case, we
@@ -79,9 +165,10 @@ impl From<CommandMatches<CommandType>> for Command {
     }
 }
 
-#[async_trait(?Send)]
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
 impl Runnable for Command {
-    async fn run(self, input: &str, app_meta: &mut AppMeta) -> Result<String, String> {
+    async fn run(self, input: &str, app_meta: &mut AppMeta) -> Result<String, CommandError> {
         if let Some(command) = &self.matches.canonical_match {
             let other_interpretations_message = if !self.matches.fuzzy_matches.is_empty()
                 && !matches!(
@@ -114,16 +201,16 @@ impl Runnable for Command {
                         s.push_str(&message);
                         s
                     })
-                    .map_err(|mut s| {
-                        s.push_str(&message);
-                        s
+                    .map_err(|mut e| {
+                        e.message_mut().push_str(&message);
+                        e
                     })
             } else {
                 result
             }
         } else {
             match &self.matches.fuzzy_matches.len() {
-                0 => Err(format!("Unknown command: \"{}\"", input)),
+                0 => Err(fallback::unknown_command(input, app_meta).await),
                 1 => {
                     let mut fuzzy_matches = self.matches.fuzzy_matches;
                     fuzzy_matches.pop().unwrap().run(input, app_meta).await
@@ -140,31 +227,50 @@ impl Runnable for Command {
                         .collect();
                     lines.sort();
                     lines.into_iter().for_each(|line| message.push_str(&line));
-                    Err(message)
+                    Err(CommandError::parse(message, Vec::new()))
                 }
             }
         }
     }
 }
 
-#[async_trait(?Send)]
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
 impl ContextAwareParse for Command {
     async fn parse_input(input: &str, app_meta: &AppMeta) -> CommandMatches<Self> {
         CommandMatches::new_canonical(Self::parse_input_irrefutable(input, app_meta).await)
     }
 }
 
-#[async_trait(?Send)]
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
 impl Autocomplete for Command {
     async fn autocomplete(input: &str, app_meta: &AppMeta) -> Vec<AutocompleteSuggestion> {
         let results = join!(
             CommandAlias::autocomplete(input, app_meta),
             AppCommand::autocomplete(input, app_meta),
+            ChainCommand::autocomplete(input, app_meta),
+            CampaignCommand::autocomplete(input, app_meta),
+            ClueCommand::autocomplete(input, app_meta),
+            ConfigCommand::autocomplete(input, app_meta),
+            HistoryCommand::autocomplete(input, app_meta),
+            HomebrewCommand::autocomplete(input, app_meta),
+            PortentCommand::autocomplete(input, app_meta),
             ReferenceCommand::autocomplete(input, app_meta),
+            ReligionCommand::autocomplete(input, app_meta),
+            SceneCommand::autocomplete(input, app_meta),
+            SessionCommand::autocomplete(input, app_meta),
             StorageCommand::autocomplete(input, app_meta),
+            TableCommand::autocomplete(input, app_meta),
+            TemplateCommand::autocomplete(input, app_meta),
+            ThreadCommand::autocomplete(input, app_meta),
             TimeCommand::autocomplete(input, app_meta),
+            TreasureCommand::autocomplete(input, app_meta),
+            TreasuryCommand::autocomplete(input, app_meta),
             TutorialCommand::autocomplete(input, app_meta),
+            WarCommand::autocomplete(input, app_meta),
             WorldCommand::autocomplete(input, app_meta),
+            WizardCommand::autocomplete(input, app_meta),
         );
 
         std::iter::empty()
@@ -175,6 +281,23 @@ impl Autocomplete for Command {
             .chain(results.4)
             .chain(results.5)
             .chain(results.6)
+            .chain(results.7)
+            .chain(results.8)
+            .chain(results.9)
+            .chain(results.10)
+            .chain(results.11)
+            .chain(results.12)
+            .chain(results.13)
+            .chain(results.14)
+            .chain(results.15)
+            .chain(results.16)
+            .chain(results.17)
+            .chain(results.18)
+            .chain(results.19)
+            .chain(results.20)
+            .chain(results.21)
+            .chain(results.22)
+            .chain(results.23)
             .collect()
     }
 }
@@ -183,26 +306,68 @@ impl Autocomplete for Command {
 pub enum CommandType {
     Alias(CommandAlias),
     App(AppCommand),
+    Campaign(CampaignCommand),
+    Chain(ChainCommand),
+    Clue(ClueCommand),
+    Config(ConfigCommand),
+    History(HistoryCommand),
+    Homebrew(HomebrewCommand),
+    Portent(PortentCommand),
     Reference(ReferenceCommand),
+    Religion(ReligionCommand),
+    Scene(SceneCommand),
+    Session(SessionCommand),
     Storage(StorageCommand),
+    Table(TableCommand),
+    Template(TemplateCommand),
+    Thread(ThreadCommand),
     Time(TimeCommand),
+    Treasure(TreasureCommand),
+    Treasury(TreasuryCommand),
     Tutorial(TutorialCommand),
+    War(WarCommand),
+    Wizard(WizardCommand),
     World(WorldCommand),
 }
 
 impl CommandType {
-    async fn run(self, input: &str, app_meta: &mut AppMeta) -> Result<String, String> {
-        if !matches!(self, Self::Alias(_) | Self::Tutorial(_)) {
+    async fn run(self, input: &str, app_meta: &mut AppMeta) -> Result<String, CommandError> {
+        // Renaming keeps the aliases from the previous output, pointing them at the new name, so
+        // that eg. the numbered list of generated inns can still be used after renaming one.
+        if !matches!(
+            self,
+            Self::Alias(_)
+                | Self::Tutorial(_)
+                | Self::Wizard(_)
+                | Self::World(WorldCommand::Rename { .. }),
+        ) {
             app_meta.command_aliases.clear();
         }
 
         match self {
             Self::Alias(c) => c.run(input, app_meta).await,
             Self::App(c) => c.run(input, app_meta).await,
+            Self::Campaign(c) => c.run(input, app_meta).await,
+            Self::Chain(c) => c.run(input, app_meta).await,
+            Self::Clue(c) => c.run(input, app_meta).await,
+            Self::Config(c) => c.run(input, app_meta).await,
+            Self::History(c) => c.run(input, app_meta).await,
+            Self::Homebrew(c) => c.run(input, app_meta).await,
+            Self::Portent(c) => c.run(input, app_meta).await,
             Self::Reference(c) => c.run(input, app_meta).await,
+            Self::Religion(c) => c.run(input, app_meta).await,
+            Self::Scene(c) => c.run(input, app_meta).await,
+            Self::Session(c) => c.run(input, app_meta).await,
             Self::Storage(c) => c.run(input, app_meta).await,
+            Self::Table(c) => c.run(input, app_meta).await,
+            Self::Template(c) => c.run(input, app_meta).await,
+            Self::Thread(c) => c.run(input, app_meta).await,
             Self::Time(c) => c.run(input, app_meta).await,
+            Self::Treasure(c) => c.run(input, app_meta).await,
+            Self::Treasury(c) => c.run(input, app_meta).await,
             Self::Tutorial(c) => c.run(input, app_meta).await,
+            Self::War(c) => c.run(input, app_meta).await,
+            Self::Wizard(c) => c.run(input, app_meta).await,
             Self::World(c) => c.run(input, app_meta).await,
         }
     }
@@ -213,10 +378,27 @@ impl fmt::Display for CommandType {
         match self {
             Self::Alias(c) => write!(f, "{}", c),
             Self::App(c) => write!(f, "{}", c),
+            Self::Campaign(c) => write!(f, "{}", c),
+            Self::Chain(c) => write!(f, "{}", c),
+            Self::Clue(c) => write!(f, "{}", c),
+            Self::Config(c) => write!(f, "{}", c),
+            Self::History(c) => write!(f, "{}", c),
+            Self::Homebrew(c) => write!(f, "{}", c),
+            Self::Portent(c) => write!(f, "{}", c),
             Self::Reference(c) => write!(f, "{}", c),
+            Self::Religion(c) => write!(f, "{}", c),
+            Self::Scene(c) => write!(f, "{}", c),
+            Self::Session(c) => write!(f, "{}", c),
             Self::Storage(c) => write!(f, "{}", c),
+            Self::Table(c) => write!(f, "{}", c),
+            Self::Template(c) => write!(f, "{}", c),
+            Self::Thread(c) => write!(f, "{}", c),
             Self::Time(c) => write!(f, "{}", c),
+            Self::Treasure(c) => write!(f, "{}", c),
+            Self::Treasury(c) => write!(f, "{}", c),
             Self::Tutorial(c) => write!(f, "{}", c),
+            Self::War(c) => write!(f, "{}", c),
+            Self::Wizard(c) => write!(f, "{}", c),
             Self::World(c) => write!(f, "{}", c),
         }
     }
@@ -242,30 +424,132 @@ impl From<CommandAlias> for CommandType {
     }
 }
 
+impl From<CampaignCommand> for CommandType {
+    fn from(c: CampaignCommand) -> CommandType {
+        CommandType::Campaign(c)
+    }
+}
+
+impl From<ChainCommand> for CommandType {
+    fn from(c: ChainCommand) -> CommandType {
+        CommandType::Chain(c)
+    }
+}
+
+impl From<ClueCommand> for CommandType {
+    fn from(c: ClueCommand) -> CommandType {
+        CommandType::Clue(c)
+    }
+}
+
+impl From<ConfigCommand> for CommandType {
+    fn from(c: ConfigCommand) -> CommandType {
+        CommandType::Config(c)
+    }
+}
+
+impl From<HistoryCommand> for CommandType {
+    fn from(c: HistoryCommand) -> CommandType {
+        CommandType::History(c)
+    }
+}
+
+impl From<HomebrewCommand> for CommandType {
+    fn from(c: HomebrewCommand) -> CommandType {
+        CommandType::Homebrew(c)
+    }
+}
+
+impl From<PortentCommand> for CommandType {
+    fn from(c: PortentCommand) -> CommandType {
+        CommandType::Portent(c)
+    }
+}
+
 impl From<ReferenceCommand> for CommandType {
     fn from(c: ReferenceCommand) -> CommandType {
         CommandType::Reference(c)
     }
 }
 
+impl From<ReligionCommand> for CommandType {
+    fn from(c: ReligionCommand) -> CommandType {
+        CommandType::Religion(c)
+    }
+}
+
+impl From<SceneCommand> for CommandType {
+    fn from(c: SceneCommand) -> CommandType {
+        CommandType::Scene(c)
+    }
+}
+
+impl From<SessionCommand> for CommandType {
+    fn from(c: SessionCommand) -> CommandType {
+        CommandType::Session(c)
+    }
+}
+
 impl From<StorageCommand> for CommandType {
     fn from(c: StorageCommand) -> CommandType {
         CommandType::Storage(c)
     }
 }
 
+impl From<TableCommand> for CommandType {
+    fn from(c: TableCommand) -> CommandType {
+        CommandType::Table(c)
+    }
+}
+
+impl From<TemplateCommand> for CommandType {
+    fn from(c: TemplateCommand) -> CommandType {
+        CommandType::Template(c)
+    }
+}
+
+impl From<ThreadCommand> for CommandType {
+    fn from(c: ThreadCommand) -> CommandType {
+        CommandType::Thread(c)
+    }
+}
+
 impl From<TimeCommand> for CommandType {
     fn from(c: TimeCommand) -> CommandType {
         CommandType::Time(c)
     }
 }
 
+impl From<TreasureCommand> for CommandType {
+    fn from(c: TreasureCommand) -> CommandType {
+        CommandType::Treasure(c)
+    }
+}
+
+impl From<TreasuryCommand> for CommandType {
+    fn from(c: TreasuryCommand) -> CommandType {
+        CommandType::Treasury(c)
+    }
+}
+
 impl From<TutorialCommand> for CommandType {
     fn from(c: TutorialCommand) -> CommandType {
         CommandType::Tutorial(c)
     }
 }
 
+impl From<WarCommand> for CommandType {
+    fn from(c: WarCommand) -> CommandType {
+        CommandType::War(c)
+    }
+}
+
+impl From<WizardCommand> for CommandType {
+    fn from(c: WizardCommand) -> CommandType {
+        CommandType::Wizard(c)
+    }
+}
+
 impl From<WorldCommand> for CommandType {
     fn from(c: WorldCommand) -> CommandType {
         CommandType::World(c)
@@ -313,11 +597,11 @@ mod test {
         assert_eq!(
             Command::from(CommandMatches::new_canonical(CommandType::World(
                 WorldCommand::Create {
-                    thing: ParsedThing {
+                    thing: Box::new(ParsedThing {
                         thing: Npc::default().into(),
                         unknown_words: Vec::new(),
                         word_count: 1,
-                    },
+                    }),
                 }
             ))),
             block_on(Command::parse_input("create npc", &app_meta))
@@ -334,20 +618,45 @@ mod test {
                 ("Darkness", "SRD spell"),
                 ("Darkvision", "SRD spell"),
                 ("date", "get the current time"),
+                (
+                    "dedicate [place] to [deity]",
+                    "set the deity worshipped at a place",
+                ),
+                ("deities", "list the deities of the pantheon"),
                 ("Daylight", "SRD spell"),
                 ("Death Ward", "SRD spell"),
                 ("Delayed Blast Fireball", "SRD spell"),
                 ("delete [name]", "remove an entry from journal"),
+                ("deliver clue [number] of [secret]", "mark a clue as found"),
                 ("Demiplane", "SRD spell"),
                 ("desert", "create desert"),
+                ("disband [army]", "disband an army"),
                 ("Detect Evil and Good", "SRD spell"),
                 ("Detect Magic", "SRD spell"),
                 ("Detect Poison and Disease", "SRD spell"),
+                ("diseases", "SRD index"),
                 ("distillery", "create distillery"),
                 ("district", "create district"),
                 ("domain", "create domain"),
+                (
+                    "downtime [name] crafting [item]",
+                    "spend downtime crafting equipment",
+                ),
+                (
+                    "downtime [name] crafting [number] days",
+                    "spend downtime crafting",
+                ),
+                (
+                    "downtime [name] training [number] days",
+                    "spend downtime learning a language or tool",
+                ),
+                (
+                    "downtime [name] working [number] days",
+                    "spend downtime practicing a profession",
+                ),
                 ("dragonborn", "create dragonborn"),
                 ("duchy", "create duchy"),
+                ("dungeon", "create dungeon"),
                 ("duty-house", "create duty-house"),
                 ("dwarf", "create dwarf"),
                 ("dwarvish", "create dwarvish person"),
@@ -375,18 +684,18 @@ mod test {
 
         assert_eq!(
             CommandType::World(WorldCommand::Create {
-                thing: ParsedThing {
+                thing: Box::new(ParsedThing {
                     thing: Npc::default().into(),
                     unknown_words: Vec::new(),
                     word_count: 1,
-                },
+                }),
             }),
             WorldCommand::Create {
-                thing: ParsedThing {
+                thing: Box::new(ParsedThing {
                     thing: Npc::default().into(),
                     unknown_words: Vec::new(),
                     word_count: 1,
-                },
+                }),
             }
             .into(),
         );
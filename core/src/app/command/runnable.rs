@@ -1,19 +1,22 @@
-use crate::app::AppMeta;
+use crate::app::{AppMeta, CommandError};
 use async_trait::async_trait;
 use serde::Serialize;
 use std::borrow::Cow;
 
-#[async_trait(?Send)]
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
 pub trait Runnable: Sized {
-    async fn run(self, input: &str, app_meta: &mut AppMeta) -> Result<String, String>;
+    async fn run(self, input: &str, app_meta: &mut AppMeta) -> Result<String, CommandError>;
 }
 
-#[async_trait(?Send)]
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
 pub trait ContextAwareParse: Sized {
     async fn parse_input(input: &str, app_meta: &AppMeta) -> CommandMatches<Self>;
 }
 
-#[async_trait(?Send)]
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
 pub trait Autocomplete {
     async fn autocomplete(input: &str, app_meta: &AppMeta) -> Vec<AutocompleteSuggestion>;
 }
@@ -1,16 +1,55 @@
 use super::CommandType;
 use crate::app::{
-    AppCommand, AppMeta, Autocomplete, AutocompleteSuggestion, Command, CommandAlias,
+    AppCommand, AppMeta, Autocomplete, AutocompleteSuggestion, Command, CommandAlias, CommandError,
     CommandMatches, ContextAwareParse, Runnable,
 };
+use crate::config::Config;
+use crate::locale::{Language, TutorialPage};
 use crate::reference::{ItemCategory, ReferenceCommand, Spell};
-use crate::storage::{Change, StorageCommand};
+use crate::storage::{Change, KeyValue, StorageCommand};
 use crate::time::TimeCommand;
 use crate::utils::CaseInsensitiveStr;
 use crate::world::npc::{Age, Ethnicity, Gender, Npc, Species};
 use crate::world::{ParsedThing, Thing, WorldCommand};
 use async_trait::async_trait;
 use std::fmt;
+use std::str::FromStr;
+
+/// The sections of the tutorial. Each can be started directly, eg. `tutorial combat`, and
+/// `tutorial skip` moves on to the start of the next one.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TutorialChapter {
+    Basics,
+    Journal,
+    Combat,
+}
+
+/// Every step at which the tutorial waits for input, in order, with the key used to save progress
+/// and the chapter that the step belongs to. See [`TutorialCommand::step`].
+const STEPS: [(&str, TutorialChapter); 15] = [
+    ("generating-locations", TutorialChapter::Basics),
+    ("saving-locations", TutorialChapter::Basics),
+    ("generating-characters", TutorialChapter::Basics),
+    ("generating-alternatives", TutorialChapter::Basics),
+    ("viewing-alternatives", TutorialChapter::Basics),
+    ("editing-characters", TutorialChapter::Basics),
+    ("the-journal", TutorialChapter::Basics),
+    ("loading-from-journal", TutorialChapter::Journal),
+    ("srd-reference", TutorialChapter::Journal),
+    ("srd-reference-lists", TutorialChapter::Combat),
+    ("rolling-dice", TutorialChapter::Combat),
+    ("deleting-things", TutorialChapter::Combat),
+    ("advancing-time", TutorialChapter::Combat),
+    ("checking-the-time", TutorialChapter::Combat),
+    ("conclusion", TutorialChapter::Combat),
+];
+
+/// The step of the tutorial waiting on the user, saved to the key-value store each time the
+/// tutorial advances so that `tutorial resume` can pick it up again, even in a later session.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TutorialProgress {
+    step: TutorialCommand,
+}
 
 /// An enum representing each possible state of the tutorial. The Introduction variant is mapped to
 /// the `tutorial` command, while each other variant is registered as a [`CommandAlias`] upon
@@ -27,9 +66,14 @@ use std::fmt;
 /// advances the tutorial to the next step and appends its own output to the end of the command
 /// output. If the user did something different, the command takes effect anyway, and a brief note
 /// about the tutorial still being active is appended to the output.
+///
+/// The steps are grouped into [`TutorialChapter`]s, described by the `STEPS` table. The user can
+/// `exit` the tutorial at any point without losing their place, since the current step is saved
+/// as [`TutorialProgress`] whenever it changes.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum TutorialCommand {
     Introduction,
+    Chapter(TutorialChapter),
     GeneratingLocations,
     SavingLocations,
     GeneratingCharacters {
@@ -92,6 +136,36 @@ pub enum TutorialCommand {
         inn_name: Option<String>,
         npc_name: Option<String>,
     },
+    Skip,
+    Exit,
+}
+
+impl TutorialChapter {
+    pub const ALL: [Self; 3] = [Self::Basics, Self::Journal, Self::Combat];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Basics => "basics",
+            Self::Journal => "journal",
+            Self::Combat => "combat",
+        }
+    }
+
+    fn summary(&self) -> &'static str {
+        match self {
+            Self::Basics => "tutorial: generating and editing",
+            Self::Journal => "tutorial: saving and loading",
+            Self::Combat => "tutorial: reference, dice, and time",
+        }
+    }
+
+    fn next(&self) -> Option<Self> {
+        match self {
+            Self::Basics => Some(Self::Journal),
+            Self::Journal => Some(Self::Combat),
+            Self::Combat => None,
+        }
+    }
 }
 
 impl TutorialCommand {
@@ -111,24 +185,27 @@ impl TutorialCommand {
     /// judgement of the reader.
     fn output(
         &self,
-        command_output: Option<Result<String, String>>,
+        command_output: Option<Result<String, CommandError>>,
+        language: Language,
         app_meta: &mut AppMeta,
-    ) -> Result<String, String> {
-        let is_ok = if let Some(r) = &command_output {
-            r.is_ok()
-        } else {
-            true
+    ) -> Result<String, CommandError> {
+        let (mut output, error) = match command_output {
+            Some(Ok(output)) => (output, None),
+            Some(Err(error)) => (error.to_string(), Some(error)),
+            None => (String::new(), None),
         };
-
-        let mut output = command_output
-            .unwrap_or_else(|| Ok(String::new()))
-            .unwrap_or_else(|e| e);
         if !output.is_empty() {
             output.push_str("\n\n#");
         }
 
         match self {
-            Self::Introduction | Self::Cancel { .. } | Self::Resume | Self::Restart { .. } => {}
+            Self::Introduction
+            | Self::Chapter(_)
+            | Self::Cancel { .. }
+            | Self::Resume
+            | Self::Restart { .. }
+            | Self::Skip
+            | Self::Exit => {}
             Self::GeneratingLocations => {
                 app_meta.command_aliases.insert(CommandAlias::literal(
                     "next",
@@ -136,11 +213,11 @@ impl TutorialCommand {
                     Self::GeneratingLocations.into(),
                 ));
 
-                output.push_str(include_str!("../../../../data/tutorial/00-introduction.md"));
+                output.push_str(TutorialPage::Introduction.text(language));
+            }
+            Self::SavingLocations => {
+                output.push_str(TutorialPage::GeneratingLocations.text(language))
             }
-            Self::SavingLocations => output.push_str(include_str!(
-                "../../../../data/tutorial/01-generating-locations.md"
-            )),
             Self::GeneratingCharacters { inn_name } => {
                 app_meta.command_aliases.insert(CommandAlias::literal(
                     "save",
@@ -151,14 +228,11 @@ impl TutorialCommand {
                     .into(),
                 ));
 
-                output.push_str(&format!(
-                    include_str!("../../../../data/tutorial/02-saving-locations.md"),
-                    inn_name = inn_name,
-                ));
+                output.push_str(&TutorialPage::SavingLocations.render(language, inn_name, ""));
+            }
+            Self::GeneratingAlternatives { .. } => {
+                output.push_str(TutorialPage::GeneratingCharacters.text(language))
             }
-            Self::GeneratingAlternatives { .. } => output.push_str(include_str!(
-                "../../../../data/tutorial/03-generating-characters.md"
-            )),
             Self::ViewingAlternatives { npc_name, .. } => {
                 let thing = Thing::from(Npc {
                     species: Species::Human.into(),
@@ -171,13 +245,15 @@ impl TutorialCommand {
                 app_meta.command_aliases.insert(CommandAlias::literal(
                     "more",
                     format!("create {}", thing.display_description()),
-                    WorldCommand::CreateMultiple { thing }.into(),
+                    WorldCommand::CreateMultiple {
+                        thing: Box::new(thing),
+                        count: None,
+                    }
+                    .into(),
                 ));
 
-                output.push_str(&format!(
-                    include_str!("../../../../data/tutorial/04-generating-alternatives.md"),
-                    npc_name = npc_name,
-                ));
+                output
+                    .push_str(&TutorialPage::GeneratingAlternatives.render(language, "", npc_name));
             }
             Self::EditingCharacters { npc_name, .. } => {
                 app_meta.command_aliases.insert(CommandAlias::literal(
@@ -189,10 +265,7 @@ impl TutorialCommand {
                     .into(),
                 ));
 
-                output.push_str(&format!(
-                    include_str!("../../../../data/tutorial/05-viewing-alternatives.md"),
-                    npc_name = npc_name,
-                ));
+                output.push_str(&TutorialPage::ViewingAlternatives.render(language, "", npc_name));
             }
             Self::TheJournal { npc_name, .. } => {
                 app_meta.command_aliases.insert(CommandAlias::literal(
@@ -204,48 +277,38 @@ impl TutorialCommand {
                     .into(),
                 ));
 
-                output.push_str(&format!(
-                    include_str!("../../../../data/tutorial/06-editing-characters.md"),
-                    npc_name = npc_name,
-                ));
+                output.push_str(&TutorialPage::EditingCharacters.render(language, "", npc_name));
+            }
+            Self::LoadingFromJournal { inn_name, .. } => {
+                output.push_str(&TutorialPage::TheJournal.render(language, inn_name, ""))
+            }
+            Self::SrdReference { npc_name, .. } => {
+                output.push_str(&TutorialPage::LoadingFromJournal.render(language, "", npc_name))
+            }
+            Self::SrdReferenceLists { .. } => {
+                output.push_str(TutorialPage::SrdReference.text(language))
+            }
+            Self::RollingDice { inn_name, npc_name } => output
+                .push_str(&TutorialPage::SrdReferenceLists.render(language, inn_name, npc_name)),
+            Self::DeletingThings { npc_name, .. } => {
+                output.push_str(&TutorialPage::RollingDice.render(language, "", npc_name))
+            }
+            Self::AdvancingTime { inn_name, npc_name } => {
+                output.push_str(&TutorialPage::DeletingThings.render(language, inn_name, npc_name))
+            }
+            Self::CheckingTheTime { .. } => {
+                output.push_str(TutorialPage::AdvancingTime.text(language))
+            }
+            Self::Conclusion { .. } => {
+                output.push_str(TutorialPage::CheckingTheTime.text(language))
             }
-            Self::LoadingFromJournal { inn_name, .. } => output.push_str(&format!(
-                include_str!("../../../../data/tutorial/07-the-journal.md"),
-                inn_name = inn_name,
-            )),
-            Self::SrdReference { npc_name, .. } => output.push_str(&format!(
-                include_str!("../../../../data/tutorial/08-loading-from-journal.md"),
-                npc_name = npc_name,
-            )),
-            Self::SrdReferenceLists { .. } => output.push_str(include_str!(
-                "../../../../data/tutorial/09-srd-reference.md"
-            )),
-            Self::RollingDice { inn_name, npc_name } => output.push_str(&format!(
-                include_str!("../../../../data/tutorial/10-srd-reference-lists.md"),
-                inn_name = inn_name,
-                npc_name = npc_name,
-            )),
-            Self::DeletingThings { npc_name, .. } => output.push_str(&format!(
-                include_str!("../../../../data/tutorial/11-rolling-dice.md"),
-                npc_name = npc_name,
-            )),
-            Self::AdvancingTime { inn_name, npc_name } => output.push_str(&format!(
-                include_str!("../../../../data/tutorial/12-deleting-things.md"),
-                inn_name = inn_name,
-                npc_name = npc_name,
-            )),
-            Self::CheckingTheTime { .. } => output.push_str(include_str!(
-                "../../../../data/tutorial/13-advancing-time.md"
-            )),
-            Self::Conclusion { .. } => output.push_str(include_str!(
-                "../../../../data/tutorial/14-checking-the-time.md"
-            )),
         }
 
-        if is_ok {
-            Ok(output)
+        if let Some(mut error) = error {
+            *error.message_mut() = output;
+            Err(error)
         } else {
-            Err(output)
+            Ok(output)
         }
     }
 
@@ -253,9 +316,12 @@ impl TutorialCommand {
     fn inn_name(&self) -> Option<String> {
         match self {
             Self::Introduction
+            | Self::Chapter(_)
             | Self::GeneratingLocations
             | Self::SavingLocations
-            | Self::Resume => None,
+            | Self::Resume
+            | Self::Skip
+            | Self::Exit => None,
 
             Self::GeneratingCharacters { inn_name }
             | Self::GeneratingAlternatives { inn_name }
@@ -281,9 +347,12 @@ impl TutorialCommand {
     fn npc_name(&self) -> Option<String> {
         match self {
             Self::Introduction
+            | Self::Chapter(_)
             | Self::GeneratingLocations
             | Self::SavingLocations
             | Self::Resume
+            | Self::Skip
+            | Self::Exit
             | Self::GeneratingCharacters { .. }
             | Self::GeneratingAlternatives { .. }
             | Self::ViewingAlternatives { .. } => None,
@@ -311,7 +380,9 @@ impl TutorialCommand {
     /// `CommandType::Time(TimeCommand::Now)`.
     fn is_correct_command(&self, command: Option<&CommandType>) -> bool {
         match self {
-            Self::Cancel { .. } | Self::Resume => false,
+            Self::Chapter(_) | Self::Cancel { .. } | Self::Resume | Self::Skip | Self::Exit => {
+                false
+            }
             Self::Introduction | Self::Restart { .. } => true,
             Self::GeneratingLocations => matches!(
                 command,
@@ -335,16 +406,8 @@ impl TutorialCommand {
                 }
             }
             Self::GeneratingAlternatives { .. } => {
-                if let Some(CommandType::World(WorldCommand::Create {
-                    thing:
-                        ParsedThing {
-                            thing,
-                            unknown_words: _,
-                            word_count: _,
-                        },
-                })) = command
-                {
-                    thing.npc()
+                if let Some(CommandType::World(WorldCommand::Create { thing })) = command {
+                    thing.thing.npc()
                         == Some(&Npc {
                             species: Species::Human.into(),
                             ethnicity: Ethnicity::Human.into(),
@@ -357,7 +420,9 @@ impl TutorialCommand {
                 }
             }
             Self::ViewingAlternatives { .. } => {
-                if let Some(CommandType::World(WorldCommand::CreateMultiple { thing })) = command {
+                if let Some(CommandType::World(WorldCommand::CreateMultiple { thing, .. })) =
+                    command
+                {
                     thing.npc()
                         == Some(&Npc {
                             species: Species::Human.into(),
@@ -378,18 +443,9 @@ impl TutorialCommand {
                 }
             }
             Self::TheJournal { npc_name, .. } => {
-                if let Some(CommandType::World(WorldCommand::Edit {
-                    name,
-                    diff:
-                        ParsedThing {
-                            thing,
-                            unknown_words: _,
-                            word_count: _,
-                        },
-                })) = command
-                {
+                if let Some(CommandType::World(WorldCommand::Edit { name, diff, .. })) = command {
                     name.eq_ci(npc_name)
-                        && thing.npc()
+                        && diff.thing.npc()
                             == Some(&Npc {
                                 species: Species::HalfElf.into(),
                                 ..Default::default()
@@ -440,288 +496,501 @@ impl TutorialCommand {
             Self::Conclusion { .. } => matches!(command, Some(CommandType::Time(TimeCommand::Now))),
         }
     }
-}
 
-#[async_trait(?Send)]
-impl Runnable for TutorialCommand {
-    async fn run(self, input: &str, app_meta: &mut AppMeta) -> Result<String, String> {
-        let input_command = Command::parse_input_irrefutable(input, app_meta).await;
+    /// The position of this step in [`STEPS`], if the tutorial can wait for input at this step.
+    ///
+    /// As with [`Self::output`], there is an off-by-one going on: each step is named after the
+    /// page that is displayed *after* the user has done what it asks, so eg. `LoadingFromJournal`
+    /// is the step showing "The Journal" page and waiting for the user to open their journal.
+    fn step(&self) -> Option<usize> {
+        match self {
+            Self::Introduction
+            | Self::Chapter(_)
+            | Self::Cancel { .. }
+            | Self::Resume
+            | Self::Restart { .. }
+            | Self::Skip
+            | Self::Exit => None,
+            Self::GeneratingLocations => Some(0),
+            Self::SavingLocations => Some(1),
+            Self::GeneratingCharacters { .. } => Some(2),
+            Self::GeneratingAlternatives { .. } => Some(3),
+            Self::ViewingAlternatives { .. } => Some(4),
+            Self::EditingCharacters { .. } => Some(5),
+            Self::TheJournal { .. } => Some(6),
+            Self::LoadingFromJournal { .. } => Some(7),
+            Self::SrdReference { .. } => Some(8),
+            Self::SrdReferenceLists { .. } => Some(9),
+            Self::RollingDice { .. } => Some(10),
+            Self::DeletingThings { .. } => Some(11),
+            Self::AdvancingTime { .. } => Some(12),
+            Self::CheckingTheTime { .. } => Some(13),
+            Self::Conclusion { .. } => Some(14),
+        }
+    }
 
-        if let Some(CommandType::Tutorial(
-            TutorialCommand::Cancel { inn_name, npc_name }
-            | TutorialCommand::Restart { inn_name, npc_name },
-        )) = input_command.get_type()
-        {
-            if let Some(inn_name) = inn_name {
-                app_meta
-                    .repository
-                    .modify(Change::Delete {
-                        name: inn_name.to_owned(),
-                        uuid: None,
-                    })
-                    .await
-                    .ok();
+    /// The inverse of [`Self::step`], returning `None` if a name needed at that step is missing.
+    fn from_step(step: usize, inn_name: Option<String>, npc_name: Option<String>) -> Option<Self> {
+        let names = || Some((inn_name.clone()?, npc_name.clone()?));
+
+        Some(match step {
+            0 => Self::GeneratingLocations,
+            1 => Self::SavingLocations,
+            2 => Self::GeneratingCharacters {
+                inn_name: inn_name.clone()?,
+            },
+            3 => Self::GeneratingAlternatives {
+                inn_name: inn_name.clone()?,
+            },
+            4 => {
+                let (inn_name, npc_name) = names()?;
+                Self::ViewingAlternatives { inn_name, npc_name }
+            }
+            5 => {
+                let (inn_name, npc_name) = names()?;
+                Self::EditingCharacters { inn_name, npc_name }
+            }
+            6 => {
+                let (inn_name, npc_name) = names()?;
+                Self::TheJournal { inn_name, npc_name }
             }
+            7 => {
+                let (inn_name, npc_name) = names()?;
+                Self::LoadingFromJournal { inn_name, npc_name }
+            }
+            8 => {
+                let (inn_name, npc_name) = names()?;
+                Self::SrdReference { inn_name, npc_name }
+            }
+            9 => {
+                let (inn_name, npc_name) = names()?;
+                Self::SrdReferenceLists { inn_name, npc_name }
+            }
+            10 => {
+                let (inn_name, npc_name) = names()?;
+                Self::RollingDice { inn_name, npc_name }
+            }
+            11 => {
+                let (inn_name, npc_name) = names()?;
+                Self::DeletingThings { inn_name, npc_name }
+            }
+            12 => {
+                let (inn_name, npc_name) = names()?;
+                Self::AdvancingTime { inn_name, npc_name }
+            }
+            13 => {
+                let (inn_name, npc_name) = names()?;
+                Self::CheckingTheTime { inn_name, npc_name }
+            }
+            14 => {
+                let (inn_name, npc_name) = names()?;
+                Self::Conclusion { inn_name, npc_name }
+            }
+            _ => return None,
+        })
+    }
+
+    fn chapter(&self) -> TutorialChapter {
+        self.step()
+            .map_or(TutorialChapter::Basics, |step| STEPS[step].1)
+    }
+
+    /// The first step of a chapter. Each chapter carries on the story of the inn and character
+    /// introduced in the first, so any that the tutorial hasn't got to yet are generated now.
+    async fn start_chapter(
+        chapter: TutorialChapter,
+        inn_name: Option<String>,
+        npc_name: Option<String>,
+        app_meta: &mut AppMeta,
+    ) -> Result<Self, CommandError> {
+        if chapter == TutorialChapter::Basics {
+            Self::clean_up(inn_name, npc_name, app_meta).await;
+            return Ok(Self::GeneratingLocations);
+        }
+
+        let inn_name = match inn_name {
+            Some(inn_name) => inn_name,
+            None => {
+                let inn = "inn".parse::<ParsedThing<Thing>>().unwrap().thing;
+                Self::generate(inn, app_meta).await?
+            }
+        };
+
+        let npc_name = match npc_name {
+            Some(npc_name) => npc_name,
+            None => {
+                let npc = Thing::from(Npc {
+                    species: Species::HalfElf.into(),
+                    age: Age::Adult.into(),
+                    gender: Gender::Feminine.into(),
+                    ..Default::default()
+                });
+                Self::generate(npc, app_meta).await?
+            }
+        };
+
+        let step = STEPS
+            .iter()
+            .position(|(_, step_chapter)| step_chapter == &chapter)
+            .unwrap();
+
+        Ok(Self::from_step(step, Some(inn_name), Some(npc_name)).unwrap())
+    }
+
+    /// Unsaved things don't outlast the session, so the inn and character named in saved progress
+    /// may no longer exist. If so, start the chapter over, generating them again if necessary.
+    async fn restore(self, app_meta: &mut AppMeta) -> Result<Self, CommandError> {
+        let mut names = [self.inn_name(), self.npc_name()];
+        let mut missing = false;
+
+        for name in names.iter_mut() {
+            if let Some(thing_name) = name {
+                if app_meta.repository.get_by_name(thing_name).await.is_err() {
+                    *name = None;
+                    missing = true;
+                }
+            }
+        }
+
+        if missing {
+            let [inn_name, npc_name] = names;
+            Self::start_chapter(self.chapter(), inn_name, npc_name, app_meta).await
+        } else {
+            Ok(self)
+        }
+    }
 
-            if let Some(npc_name) = npc_name {
-                app_meta
+    /// Generate and save a thing for use in the tutorial, returning its name.
+    async fn generate(thing: Thing, app_meta: &mut AppMeta) -> Result<String, CommandError> {
+        for _ in 0..10 {
+            let mut thing = thing.clone();
+            thing.regenerate(&mut app_meta.rng, &app_meta.demographics);
+
+            if let Some(name) = thing.name().value().cloned() {
+                if app_meta
                     .repository
-                    .modify(Change::Delete {
-                        name: npc_name.to_owned(),
-                        uuid: None,
-                    })
+                    .modify(Change::CreateAndSave { thing })
                     .await
-                    .ok();
+                    .is_ok()
+                {
+                    return Ok(name);
+                }
             }
         }
 
-        app_meta.command_aliases.clear();
+        Err(format!(
+            "Couldn't generate {} for the tutorial.",
+            thing.display_description(),
+        )
+        .into())
+    }
 
-        let (result, next_command) = if self.is_correct_command(input_command.get_type()) {
-            match self {
-                Self::Cancel { .. } | Self::Resume => unreachable!(),
-                Self::Introduction | Self::Restart { .. } => {
-                    let next = Self::GeneratingLocations;
-                    (next.output(None, app_meta), Some(next))
-                }
-                Self::GeneratingLocations => {
-                    let next = Self::SavingLocations;
-                    (next.output(None, app_meta), Some(next))
-                }
-                Self::SavingLocations => {
-                    let command_output = input_command.run(input, app_meta).await;
-
-                    if let Ok(output) = command_output {
-                        let inn_name = output
-                            .lines()
-                            .nth(2)
-                            .unwrap()
-                            .trim_start_matches(&[' ', '#'][..])
-                            .to_string();
-
-                        let next = Self::GeneratingCharacters { inn_name };
-                        (next.output(Some(Ok(output)), app_meta), Some(next))
-                    } else {
-                        (command_output, Some(self))
-                    }
-                }
-                Self::GeneratingCharacters { inn_name } => {
-                    let next = Self::GeneratingAlternatives { inn_name };
+    /// Delete the inn and character generated over the course of the tutorial.
+    async fn clean_up(inn_name: Option<String>, npc_name: Option<String>, app_meta: &mut AppMeta) {
+        for name in [inn_name, npc_name].into_iter().flatten() {
+            app_meta
+                .repository
+                .modify(Change::Delete { name, uuid: None })
+                .await
+                .ok();
+        }
+    }
 
+    /// Run the command that satisfied this step and move on to the next one.
+    async fn advance(
+        self,
+        input: &str,
+        input_command: Command,
+        language: Language,
+        app_meta: &mut AppMeta,
+    ) -> (Result<String, CommandError>, Option<Self>) {
+        match self {
+            Self::Chapter(_) | Self::Cancel { .. } | Self::Resume | Self::Skip | Self::Exit => {
+                unreachable!()
+            }
+            Self::Introduction | Self::Restart { .. } => {
+                let next = Self::GeneratingLocations;
+                (next.output(None, language, app_meta), Some(next))
+            }
+            Self::GeneratingLocations => {
+                let next = Self::SavingLocations;
+                (next.output(None, language, app_meta), Some(next))
+            }
+            Self::SavingLocations => {
+                let command_output = input_command.run(input, app_meta).await;
+
+                if let Ok(output) = command_output {
+                    let inn_name = output
+                        .lines()
+                        .nth(2)
+                        .unwrap()
+                        .trim_start_matches(&[' ', '#'][..])
+                        .to_string();
+
+                    let next = Self::GeneratingCharacters { inn_name };
                     (
-                        next.output(Some(input_command.run(input, app_meta).await), app_meta),
+                        next.output(Some(Ok(output)), language, app_meta),
                         Some(next),
                     )
+                } else {
+                    (command_output, Some(self))
                 }
-                Self::GeneratingAlternatives { inn_name } => {
-                    let command_output = input_command.run(input, app_meta).await;
-
-                    if let Ok(output) = command_output {
-                        if let Some(npc_name) = output
-                            .lines()
-                            .find(|s| s.starts_with('#'))
-                            .map(|s| s.trim_start_matches(&[' ', '#'][..]).to_string())
-                        {
-                            let next = Self::ViewingAlternatives { inn_name, npc_name };
-
-                            (next.output(Some(Ok(output)), app_meta), Some(next))
-                        } else {
-                            (Ok(output), Some(Self::GeneratingAlternatives { inn_name }))
-                        }
-                    } else {
+            }
+            Self::GeneratingAlternatives { inn_name } => {
+                let command_output = input_command.run(input, app_meta).await;
+
+                if let Ok(output) = command_output {
+                    if let Some(npc_name) = output
+                        .lines()
+                        .find(|s| s.starts_with('#'))
+                        .map(|s| s.trim_start_matches(&[' ', '#'][..]).to_string())
+                    {
+                        let next = Self::ViewingAlternatives { inn_name, npc_name };
+
                         (
-                            command_output,
-                            Some(Self::GeneratingAlternatives { inn_name }),
+                            next.output(Some(Ok(output)), language, app_meta),
+                            Some(next),
                         )
-                    }
-                }
-                Self::ViewingAlternatives { inn_name, npc_name } => {
-                    let command_output = input_command.run(input, app_meta).await;
-
-                    if let Ok(output) = command_output {
-                        if let Some(npc_name) = output
-                            .lines()
-                            .find(|s| s.starts_with("~2~"))
-                            .and_then(|s| s.find('(').map(|i| (i, s)))
-                            .map(|(i, s)| s[10..i - 2].to_string())
-                        {
-                            let next = Self::EditingCharacters { npc_name, inn_name };
-
-                            (next.output(Some(Ok(output)), app_meta), Some(next))
-                        } else {
-                            (
-                                Ok(output),
-                                Some(Self::ViewingAlternatives { inn_name, npc_name }),
-                            )
-                        }
                     } else {
-                        (
-                            command_output,
-                            Some(Self::ViewingAlternatives { inn_name, npc_name }),
-                        )
+                        (Ok(output), Some(Self::GeneratingAlternatives { inn_name }))
                     }
+                } else {
+                    (
+                        command_output,
+                        Some(Self::GeneratingAlternatives { inn_name }),
+                    )
                 }
-                Self::EditingCharacters { inn_name, npc_name } => {
-                    let command_output = input_command.run(input, app_meta).await;
+            }
+            Self::ViewingAlternatives { inn_name, npc_name } => {
+                let command_output = input_command.run(input, app_meta).await;
 
-                    if let Ok(output) = command_output {
-                        let next = Self::TheJournal { inn_name, npc_name };
+                if let Ok(output) = command_output {
+                    if let Some(npc_name) = output
+                        .lines()
+                        .find(|s| s.starts_with("~2~"))
+                        .and_then(|s| s.find('(').map(|i| (i, s)))
+                        .map(|(i, s)| s[10..i - 2].to_string())
+                    {
+                        let next = Self::EditingCharacters { npc_name, inn_name };
 
-                        (next.output(Some(Ok(output)), app_meta), Some(next))
+                        (
+                            next.output(Some(Ok(output)), language, app_meta),
+                            Some(next),
+                        )
                     } else {
                         (
-                            command_output,
-                            Some(Self::EditingCharacters { inn_name, npc_name }),
+                            Ok(output),
+                            Some(Self::ViewingAlternatives { inn_name, npc_name }),
                         )
                     }
-                }
-                Self::TheJournal { inn_name, npc_name } => {
-                    let next = Self::LoadingFromJournal { inn_name, npc_name };
-
+                } else {
                     (
-                        next.output(Some(input_command.run(input, app_meta).await), app_meta),
-                        Some(next),
+                        command_output,
+                        Some(Self::ViewingAlternatives { inn_name, npc_name }),
                     )
                 }
-                Self::LoadingFromJournal { inn_name, npc_name } => {
-                    let next = Self::SrdReference { inn_name, npc_name };
+            }
+            Self::EditingCharacters { inn_name, npc_name } => {
+                let command_output = input_command.run(input, app_meta).await;
 
-                    (
-                        next.output(Some(input_command.run(input, app_meta).await), app_meta),
-                        Some(next),
-                    )
-                }
-                Self::SrdReference { inn_name, npc_name } => {
-                    let next = Self::SrdReferenceLists { inn_name, npc_name };
+                if let Ok(output) = command_output {
+                    let next = Self::TheJournal { inn_name, npc_name };
 
                     (
-                        next.output(Some(input_command.run(input, app_meta).await), app_meta),
+                        next.output(Some(Ok(output)), language, app_meta),
                         Some(next),
                     )
-                }
-                Self::SrdReferenceLists { inn_name, npc_name } => {
-                    let next = Self::RollingDice { inn_name, npc_name };
-
+                } else {
                     (
-                        next.output(Some(input_command.run(input, app_meta).await), app_meta),
-                        Some(next),
+                        command_output,
+                        Some(Self::EditingCharacters { inn_name, npc_name }),
                     )
                 }
-                Self::RollingDice { inn_name, npc_name } => {
-                    let next = Self::DeletingThings { inn_name, npc_name };
+            }
+            Self::Conclusion { inn_name, npc_name } => {
+                Self::clean_up(Some(inn_name), Some(npc_name), app_meta).await;
 
-                    (
-                        next.output(Some(input_command.run(input, app_meta).await), app_meta),
-                        Some(next),
-                    )
-                }
-                Self::DeletingThings { inn_name, npc_name } => {
-                    let next = Self::AdvancingTime { inn_name, npc_name };
+                (
+                    input_command.run(input, app_meta).await.map(|mut output| {
+                        output.push_str("\n\n#");
+                        output.push_str(TutorialPage::Conclusion.text(language));
+                        output
+                    }),
+                    None,
+                )
+            }
 
-                    (
-                        next.output(Some(input_command.run(input, app_meta).await), app_meta),
-                        Some(next),
-                    )
-                }
-                Self::AdvancingTime {
-                    inn_name, npc_name, ..
-                } => {
-                    let next = Self::CheckingTheTime { inn_name, npc_name };
+            // The remaining steps move on whether or not the command succeeded.
+            _ => {
+                let next =
+                    Self::from_step(self.step().unwrap() + 1, self.inn_name(), self.npc_name())
+                        .unwrap();
 
-                    (
-                        next.output(Some(input_command.run(input, app_meta).await), app_meta),
-                        Some(next),
-                    )
-                }
-                Self::CheckingTheTime { inn_name, npc_name } => {
-                    let next = Self::Conclusion { inn_name, npc_name };
+                (
+                    next.output(
+                        Some(input_command.run(input, app_meta).await),
+                        language,
+                        app_meta,
+                    ),
+                    Some(next),
+                )
+            }
+        }
+    }
+}
 
-                    (
-                        next.output(Some(input_command.run(input, app_meta).await), app_meta),
-                        Some(next),
-                    )
-                }
-                Self::Conclusion { inn_name, npc_name } => {
-                    app_meta
-                        .repository
-                        .modify(Change::Delete {
-                            name: inn_name,
-                            uuid: None,
-                        })
-                        .await
-                        .ok();
-                    app_meta
-                        .repository
-                        .modify(Change::Delete {
-                            name: npc_name,
-                            uuid: None,
-                        })
-                        .await
-                        .ok();
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Runnable for TutorialCommand {
+    async fn run(self, input: &str, app_meta: &mut AppMeta) -> Result<String, CommandError> {
+        let language = Config::load(app_meta).await.language;
+
+        // While the tutorial is active, these commands are handled by the wildcard alias like any
+        // other input. Otherwise, they act on the saved progress as though it were the current step.
+        let (current, input_command) = match self {
+            Self::Chapter(_) | Self::Resume | Self::Skip => {
+                let progress = app_meta
+                    .repository
+                    .get_key_value(&KeyValue::Tutorial(None))
+                    .await
+                    .ok()
+                    .and_then(KeyValue::tutorial);
 
+                let current = match (progress, &self) {
+                    (Some(progress), _) => progress.step.restore(app_meta).await?,
+                    (None, Self::Chapter(_)) => Self::Introduction,
+                    (None, _) => {
+                        return Err(CommandError::invalid_state(
+                            "There is no tutorial in progress. Type `tutorial` to start one.",
+                        ))
+                    }
+                };
+
+                (current, Command::from(self))
+            }
+            Self::Exit => return Err(CommandError::invalid_state("The tutorial isn't running.")),
+            _ => (
+                self,
+                Command::parse_input_irrefutable(input, app_meta).await,
+            ),
+        };
+
+        if let Some(CommandType::Tutorial(
+            TutorialCommand::Cancel { inn_name, npc_name }
+            | TutorialCommand::Restart { inn_name, npc_name },
+        )) = input_command.get_type()
+        {
+            Self::clean_up(inn_name.clone(), npc_name.clone(), app_meta).await;
+        }
+
+        app_meta.command_aliases.clear();
+
+        let exited = matches!(
+            input_command.get_type(),
+            Some(CommandType::Tutorial(TutorialCommand::Exit)),
+        );
+
+        let (result, next_command) = match input_command.get_type() {
+            Some(CommandType::Tutorial(TutorialCommand::Chapter(chapter))) => {
+                match Self::start_chapter(
+                    *chapter,
+                    current.inn_name(),
+                    current.npc_name(),
+                    app_meta,
+                )
+                .await
+                {
+                    Ok(next) => (next.output(None, language, app_meta), Some(next)),
+                    Err(e) => (Err(e), current.step().map(|_| current)),
+                }
+            }
+            Some(CommandType::Tutorial(TutorialCommand::Restart { .. })) => {
+                let next = Self::GeneratingLocations;
+                (next.output(None, language, app_meta), Some(next))
+            }
+            Some(CommandType::Tutorial(TutorialCommand::Skip)) => {
+                if let Some(chapter) = current.chapter().next() {
+                    match Self::start_chapter(
+                        chapter,
+                        current.inn_name(),
+                        current.npc_name(),
+                        app_meta,
+                    )
+                    .await
+                    {
+                        Ok(next) => (next.output(None, language, app_meta), Some(next)),
+                        Err(e) => (Err(e), Some(current)),
+                    }
+                } else {
+                    Self::clean_up(current.inn_name(), current.npc_name(), app_meta).await;
                     (
-                        input_command.run(input, app_meta).await.map(|mut output| {
-                            output.push_str("\n\n#");
-                            output.push_str(include_str!(
-                                "../../../../data/tutorial/99-conclusion.md"
-                            ));
-                            output
-                        }),
+                        Ok(TutorialPage::Conclusion.text(language).to_string()),
                         None,
                     )
                 }
             }
-        } else if let Some(CommandType::Tutorial(TutorialCommand::Cancel { .. })) =
-            input_command.get_type()
-        {
-            (
-                Ok(include_str!("../../../../data/tutorial/xx-cancelled.md").to_string()),
-                None,
-            )
-        } else if let Some(CommandType::Tutorial(TutorialCommand::Resume)) =
-            input_command.get_type()
-        {
-            (self.output(None, app_meta), Some(self))
-        } else {
-            let result = {
-                let f = |mut s: String| {
-                    if !s.is_empty() {
-                        s.push_str("\n\n#");
+            Some(CommandType::Tutorial(TutorialCommand::Exit)) => {
+                (Ok(TutorialPage::Exited.text(language).to_string()), None)
+            }
+            command_type if current.is_correct_command(command_type) => {
+                current
+                    .advance(input, input_command, language, app_meta)
+                    .await
+            }
+            Some(CommandType::Tutorial(TutorialCommand::Cancel { .. })) => {
+                (Ok(TutorialPage::Cancelled.text(language).to_string()), None)
+            }
+            Some(CommandType::Tutorial(TutorialCommand::Resume)) => {
+                (current.output(None, language, app_meta), Some(current))
+            }
+            _ => {
+                let result = {
+                    let f = |mut s: String| {
+                        if !s.is_empty() {
+                            s.push_str("\n\n#");
+                        }
+                        s.push_str(TutorialPage::StillActive.text(language));
+                        s
+                    };
+
+                    if !matches!(
+                        input_command.get_type(),
+                        Some(CommandType::Tutorial(TutorialCommand::Introduction))
+                    ) {
+                        input_command
+                            .run(input, app_meta)
+                            .await
+                            .map(f)
+                            .map_err(|mut e| {
+                                *e.message_mut() = f(e.to_string());
+                                e
+                            })
+                    } else {
+                        Ok(f(String::new()))
                     }
-                    s.push_str(include_str!("../../../../data/tutorial/xx-still-active.md"));
-                    s
                 };
 
-                if !matches!(
-                    input_command.get_type(),
-                    Some(CommandType::Tutorial(TutorialCommand::Introduction))
-                ) {
-                    input_command.run(input, app_meta).await.map(f).map_err(f)
-                } else {
-                    Ok(f(String::new()))
-                }
-            };
-
-            app_meta.command_aliases.insert(CommandAlias::literal(
-                "resume",
-                "return to the tutorial",
-                Self::Resume.into(),
-            ));
+                app_meta.command_aliases.insert(CommandAlias::literal(
+                    "resume",
+                    "return to the tutorial",
+                    Self::Resume.into(),
+                ));
 
-            app_meta.command_aliases.insert(CommandAlias::literal(
-                "restart",
-                "restart the tutorial",
-                Self::Restart {
-                    inn_name: self.inn_name(),
-                    npc_name: self.npc_name(),
-                }
-                .into(),
-            ));
+                app_meta.command_aliases.insert(CommandAlias::literal(
+                    "restart",
+                    "restart the tutorial",
+                    Self::Restart {
+                        inn_name: current.inn_name(),
+                        npc_name: current.npc_name(),
+                    }
+                    .into(),
+                ));
 
-            (result, Some(self))
+                (result, Some(current))
+            }
         };
 
-        if let Some(command) = next_command {
+        if let Some(command) = &next_command {
             app_meta.command_aliases.insert(CommandAlias::literal(
                 "cancel",
                 "cancel the tutorial",
@@ -732,37 +1001,80 @@ impl Runnable for TutorialCommand {
                 .into(),
             ));
 
+            app_meta.command_aliases.insert(CommandAlias::literal(
+                "exit",
+                "leave the tutorial for now",
+                Self::Exit.into(),
+            ));
+
             app_meta
                 .command_aliases
-                .insert(CommandAlias::strict_wildcard(command.into()));
+                .insert(CommandAlias::strict_wildcard(command.clone().into()));
+        }
+
+        if !exited {
+            app_meta
+                .repository
+                .modify_without_undo(Change::SetKeyValue {
+                    key_value: KeyValue::Tutorial(
+                        next_command.map(|step| TutorialProgress { step }),
+                    ),
+                })
+                .await
+                .ok();
         }
 
         result
     }
 }
 
-#[async_trait(?Send)]
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
 impl ContextAwareParse for TutorialCommand {
     async fn parse_input(input: &str, _app_meta: &AppMeta) -> CommandMatches<Self> {
         if input.eq_ci("tutorial") {
             CommandMatches::new_canonical(TutorialCommand::Introduction)
+        } else if let Some(arg) = input.strip_prefix_ci("tutorial ") {
+            let arg = arg.trim();
+
+            if arg.eq_ci("resume") {
+                CommandMatches::new_canonical(TutorialCommand::Resume)
+            } else if arg.eq_ci("skip") {
+                CommandMatches::new_canonical(TutorialCommand::Skip)
+            } else if arg.eq_ci("exit") {
+                CommandMatches::new_canonical(TutorialCommand::Exit)
+            } else if let Some(chapter) = TutorialChapter::ALL
+                .into_iter()
+                .find(|chapter| chapter.as_str().eq_ci(arg))
+            {
+                CommandMatches::new_canonical(TutorialCommand::Chapter(chapter))
+            } else {
+                CommandMatches::default()
+            }
         } else {
             CommandMatches::default()
         }
     }
 }
 
-#[async_trait(?Send)]
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
 impl Autocomplete for TutorialCommand {
     async fn autocomplete(input: &str, _app_meta: &AppMeta) -> Vec<AutocompleteSuggestion> {
-        if "tutorial".starts_with_ci(input) {
-            vec![AutocompleteSuggestion::new(
-                "tutorial",
-                "feature walkthrough",
-            )]
-        } else {
-            Vec::new()
-        }
+        [
+            ("tutorial".to_string(), "feature walkthrough"),
+            ("tutorial resume".to_string(), "pick up where you left off"),
+            ("tutorial skip".to_string(), "skip to the next chapter"),
+        ]
+        .into_iter()
+        .chain(
+            TutorialChapter::ALL
+                .into_iter()
+                .map(|chapter| (format!("tutorial {}", chapter.as_str()), chapter.summary())),
+        )
+        .filter(|(term, _)| term.starts_with_ci(input))
+        .map(|(term, summary)| AutocompleteSuggestion::new(term, summary))
+        .collect()
     }
 }
 
@@ -770,7 +1082,144 @@ impl fmt::Display for TutorialCommand {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         match self {
             Self::Introduction => write!(f, "tutorial"),
+            Self::Chapter(chapter) => write!(f, "tutorial {}", chapter.as_str()),
+            Self::Resume => write!(f, "tutorial resume"),
+            Self::Skip => write!(f, "tutorial skip"),
+            Self::Exit => write!(f, "tutorial exit"),
             _ => Ok(()),
         }
     }
 }
+
+impl fmt::Display for TutorialProgress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        if let Some(step) = self.step.step() {
+            write!(f, "{}", STEPS[step].0)?;
+        }
+        if let Some(inn_name) = self.step.inn_name() {
+            write!(f, "\ninn={}", inn_name)?;
+        }
+        if let Some(npc_name) = self.step.npc_name() {
+            write!(f, "\nnpc={}", npc_name)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for TutorialProgress {
+    type Err = ();
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        let mut lines = raw.lines();
+        let key = lines.next().ok_or(())?;
+        let step = STEPS.iter().position(|(k, _)| k == &key).ok_or(())?;
+
+        let (mut inn_name, mut npc_name) = (None, None);
+        for line in lines {
+            match line.split_once('=') {
+                Some(("inn", name)) => inn_name = Some(name.to_string()),
+                Some(("npc", name)) => npc_name = Some(name.to_string()),
+                _ => {}
+            }
+        }
+
+        TutorialCommand::from_step(step, inn_name, npc_name)
+            .map(|step| Self { step })
+            .ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::storage::NullDataStore;
+    use crate::Event;
+    use tokio_test::block_on;
+
+    #[test]
+    fn step_test() {
+        for (step, (_, chapter)) in STEPS.iter().enumerate() {
+            let command = TutorialCommand::from_step(
+                step,
+                Some("Foo Inn".to_string()),
+                Some("Bar".to_string()),
+            )
+            .unwrap();
+
+            assert_eq!(Some(step), command.step());
+            assert_eq!(chapter, &command.chapter());
+        }
+
+        assert_eq!(None, TutorialCommand::from_step(STEPS.len(), None, None));
+        assert_eq!(None, TutorialCommand::from_step(2, None, None));
+    }
+
+    #[test]
+    fn progress_test() {
+        let progress = TutorialProgress {
+            step: TutorialCommand::RollingDice {
+                inn_name: "The Prancing Pony".to_string(),
+                npc_name: "Barliman".to_string(),
+            },
+        };
+
+        assert_eq!(
+            "rolling-dice\ninn=The Prancing Pony\nnpc=Barliman",
+            progress.to_string(),
+        );
+        assert_eq!(Ok(progress.clone()), progress.to_string().parse());
+
+        assert_eq!(
+            Ok(TutorialProgress {
+                step: TutorialCommand::SavingLocations,
+            }),
+            "saving-locations".parse(),
+        );
+        assert_eq!(Err(()), "rolling-dice".parse::<TutorialProgress>());
+        assert_eq!(Err(()), "potato".parse::<TutorialProgress>());
+    }
+
+    #[test]
+    fn parse_input_test() {
+        let app_meta = app_meta();
+
+        assert_eq!(
+            CommandMatches::new_canonical(TutorialCommand::Introduction),
+            block_on(TutorialCommand::parse_input("tutorial", &app_meta)),
+        );
+        assert_eq!(
+            CommandMatches::new_canonical(TutorialCommand::Chapter(TutorialChapter::Combat)),
+            block_on(TutorialCommand::parse_input("Tutorial COMBAT", &app_meta)),
+        );
+        assert_eq!(
+            CommandMatches::new_canonical(TutorialCommand::Skip),
+            block_on(TutorialCommand::parse_input("tutorial skip", &app_meta)),
+        );
+        assert_eq!(
+            CommandMatches::default(),
+            block_on(TutorialCommand::parse_input("tutorial potato", &app_meta)),
+        );
+
+        for command in [
+            TutorialCommand::Introduction,
+            TutorialCommand::Chapter(TutorialChapter::Journal),
+            TutorialCommand::Resume,
+            TutorialCommand::Skip,
+            TutorialCommand::Exit,
+        ] {
+            assert_eq!(
+                CommandMatches::new_canonical(command.clone()),
+                block_on(TutorialCommand::parse_input(
+                    &command.to_string(),
+                    &app_meta
+                )),
+            );
+        }
+    }
+
+    fn event_dispatcher(_event: Event) {}
+
+    fn app_meta() -> AppMeta {
+        AppMeta::new(NullDataStore::default(), &event_dispatcher)
+    }
+}
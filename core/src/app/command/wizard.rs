@@ -0,0 +1,367 @@
+use super::CommandType;
+use crate::app::{
+    AppMeta, Autocomplete, AutocompleteSuggestion, Command, CommandAlias, CommandError,
+    CommandMatches, ContextAwareParse, Runnable,
+};
+use crate::utils::{capitalize, CaseInsensitiveStr};
+use crate::world::npc::{Age, Npc, Species};
+use crate::world::{Field, ParsedThing, WorldCommand};
+use async_trait::async_trait;
+use std::fmt;
+use std::ops::ControlFlow;
+
+/// A guided alternative to typing out a description, for users who don't yet know the syntax.
+/// `wizard npc` asks a short series of questions, building up a [`ParsedThing<Npc>`] from the
+/// answers, then creates the character as though it had been described all at once.
+///
+/// Like the [`TutorialCommand`](super::TutorialCommand), each unanswered question is registered as
+/// a [`CommandAlias::StrictWildcard`] so that the next input is taken as its answer. An answer can
+/// cover more than one question (eg. `elderly elf` for the species), in which case the questions
+/// already answered are skipped.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum WizardCommand {
+    Npc,
+    Species { npc: Box<ParsedThing<Npc>> },
+    Age { npc: Box<ParsedThing<Npc>> },
+    Name { npc: Box<ParsedThing<Npc>> },
+    Cancel,
+}
+
+impl WizardCommand {
+    /// The question to display to the user while waiting for them to answer this step.
+    fn question(&self) -> &'static str {
+        match self {
+            Self::Species { .. } => "What species is your character? Try `human`, `elf`, or `dwarf`, or type `random` to leave it to chance.",
+            Self::Age { .. } => "How old are they? Try `child`, `adult`, or `elderly`, or type `random` to leave it to chance.",
+            Self::Name { .. } => "What is their name? Type it out, or type `random` to have one generated.",
+            Self::Npc | Self::Cancel => unreachable!(),
+        }
+    }
+
+    /// Register this step to receive the next input, returning the question to ask.
+    fn ask(self, app_meta: &mut AppMeta) -> String {
+        let output = format!(
+            "{}\n\n_Type `cancel` at any time to stop creating this character._",
+            self.question(),
+        );
+
+        app_meta.command_aliases.insert(CommandAlias::literal(
+            "cancel",
+            "stop creating this character",
+            Self::Cancel.into(),
+        ));
+        app_meta
+            .command_aliases
+            .insert(CommandAlias::strict_wildcard(self.into()));
+
+        output
+    }
+
+    /// Apply the user's answer to the character under construction. If the answer doesn't make
+    /// sense for the question, the character is left unchanged and an explanation is returned.
+    fn answer(&mut self, input: &str) -> Result<(), String> {
+        let input = input.trim();
+
+        if input.eq_ci("random") && !matches!(self, Self::Npc | Self::Cancel) {
+            return Ok(());
+        }
+
+        match self {
+            Self::Species { npc } => match input.parse::<ParsedThing<Npc>>() {
+                Ok(mut answer)
+                    if answer.thing.species.is_some() && answer.unknown_words.is_empty() =>
+                {
+                    npc.thing.apply_diff(&mut answer.thing);
+                    npc.word_count += answer.word_count;
+                    Ok(())
+                }
+                _ => Err(format!("\"{}\" is not a species that I know.", input)),
+            },
+            Self::Age { npc } => match input.parse::<ParsedThing<Npc>>() {
+                Ok(mut answer)
+                    if (answer.thing.age.is_some() || answer.thing.age_years.is_some())
+                        && answer.unknown_words.is_empty() =>
+                {
+                    npc.thing.apply_diff(&mut answer.thing);
+                    npc.word_count += answer.word_count;
+                    Ok(())
+                }
+                _ => Err(format!("\"{}\" is not an age that I understand.", input)),
+            },
+            Self::Name { npc } => {
+                if input.is_empty() {
+                    Err("The name can't be blank.".to_string())
+                } else {
+                    npc.thing.name = Field::new(capitalize(input));
+                    npc.word_count += 1;
+                    Ok(())
+                }
+            }
+            Self::Npc | Self::Cancel => unreachable!(),
+        }
+    }
+
+    /// Move on to the next question, skipping any that earlier answers already covered, or break
+    /// with the completed character if there are none left.
+    fn next(self) -> ControlFlow<ParsedThing<Npc>, Self> {
+        match self {
+            Self::Species { npc } if npc.thing.age.is_none() && npc.thing.age_years.is_none() => {
+                ControlFlow::Continue(Self::Age { npc })
+            }
+            Self::Species { npc } | Self::Age { npc } if npc.thing.name.is_none() => {
+                ControlFlow::Continue(Self::Name { npc })
+            }
+            Self::Species { npc } | Self::Age { npc } | Self::Name { npc } => {
+                ControlFlow::Break(*npc)
+            }
+            Self::Npc | Self::Cancel => unreachable!(),
+        }
+    }
+
+    /// The step currently awaiting an answer, if the wizard is running.
+    fn active(app_meta: &AppMeta) -> Option<&Self> {
+        app_meta
+            .command_aliases
+            .iter()
+            .find_map(|alias| match alias {
+                CommandAlias::StrictWildcard { command } => match command.get_type() {
+                    Some(CommandType::Wizard(step)) => Some(step),
+                    _ => None,
+                },
+                CommandAlias::Literal { .. } => None,
+            })
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Runnable for WizardCommand {
+    async fn run(self, input: &str, app_meta: &mut AppMeta) -> Result<String, CommandError> {
+        match self {
+            Self::Npc => {
+                app_meta.command_aliases.clear();
+
+                Ok(format!(
+                    "# New character\n\n{}",
+                    Self::Species {
+                        npc: Box::default(),
+                    }
+                    .ask(app_meta),
+                ))
+            }
+            Self::Cancel => {
+                app_meta.command_aliases.clear();
+                Ok("Stopped creating the character.".to_string())
+            }
+            Self::Species { .. } | Self::Age { .. } | Self::Name { .. } => {
+                let input_command = Command::parse_input_irrefutable(input, app_meta).await;
+                app_meta.command_aliases.clear();
+
+                if let Some(CommandType::Wizard(Self::Cancel)) = input_command.get_type() {
+                    return Self::Cancel.run(input, app_meta).await;
+                }
+
+                let mut step = self;
+
+                if let Err(message) = step.answer(input) {
+                    return Err(format!("{}\n\n{}", message, step.ask(app_meta)).into());
+                }
+
+                match step.next() {
+                    ControlFlow::Continue(next) => Ok(next.ask(app_meta)),
+                    ControlFlow::Break(npc) => {
+                        let command = WorldCommand::Create {
+                            thing: Box::new(npc.into_thing()),
+                        };
+                        let input = command.to_string();
+                        command.run(&input, app_meta).await
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl ContextAwareParse for WizardCommand {
+    async fn parse_input(input: &str, _app_meta: &AppMeta) -> CommandMatches<Self> {
+        if input.eq_ci("wizard npc") {
+            CommandMatches::new_canonical(Self::Npc)
+        } else {
+            CommandMatches::default()
+        }
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Autocomplete for WizardCommand {
+    async fn autocomplete(input: &str, app_meta: &AppMeta) -> Vec<AutocompleteSuggestion> {
+        let mut suggestions = Vec::new();
+
+        if "wizard npc".starts_with_ci(input) {
+            suggestions.push(AutocompleteSuggestion::new(
+                "wizard npc",
+                "create a character step by step",
+            ));
+        }
+
+        let (terms, summary): (Vec<&'static str>, _) = match Self::active(app_meta) {
+            Some(Self::Species { .. }) => (
+                Species::get_words()
+                    .filter(|word| word.parse::<Species>().map(|s| s.as_str()) == Ok(*word))
+                    .collect(),
+                "species",
+            ),
+            Some(Self::Age { .. }) => (
+                Age::get_words()
+                    .filter(|word| word.parse::<Age>().map(|a| a.as_str()) == Ok(*word))
+                    .collect(),
+                "age",
+            ),
+            Some(Self::Name { .. }) => (Vec::new(), ""),
+            Some(Self::Npc | Self::Cancel) | None => return suggestions,
+        };
+
+        suggestions.extend(
+            terms
+                .into_iter()
+                .filter(|term| term.starts_with_ci(input))
+                .map(|term| AutocompleteSuggestion::new(term, summary)),
+        );
+
+        if "random".starts_with_ci(input) {
+            suggestions.push(AutocompleteSuggestion::new("random", "leave it to chance"));
+        }
+
+        suggestions
+    }
+}
+
+impl fmt::Display for WizardCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::Npc => write!(f, "wizard npc"),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::storage::NullDataStore;
+    use crate::Event;
+    use tokio_test::block_on;
+
+    #[test]
+    fn parse_input_test() {
+        let app_meta = app_meta();
+
+        assert_eq!(
+            CommandMatches::new_canonical(WizardCommand::Npc),
+            block_on(WizardCommand::parse_input("Wizard NPC", &app_meta)),
+        );
+        assert_eq!(
+            CommandMatches::default(),
+            block_on(WizardCommand::parse_input("wizard", &app_meta)),
+        );
+    }
+
+    #[test]
+    fn answer_test() {
+        let species = WizardCommand::Species {
+            npc: Box::default(),
+        };
+
+        {
+            let mut step = species.clone();
+            assert_eq!(Ok(()), step.answer("elf"));
+
+            let ControlFlow::Continue(WizardCommand::Age { npc }) = step.next() else {
+                panic!();
+            };
+            assert_eq!(Some(&Species::Elf), npc.thing.species.value());
+        }
+
+        {
+            let mut step = species.clone();
+            assert_eq!(Ok(()), step.answer("elderly dwarf"));
+
+            let ControlFlow::Continue(WizardCommand::Name { npc }) = step.next() else {
+                panic!();
+            };
+            assert_eq!(Some(&Age::Elderly), npc.thing.age.value());
+        }
+
+        {
+            let mut step = species.clone();
+            assert_eq!(Ok(()), step.answer("gnome child named Tib"));
+
+            let ControlFlow::Break(npc) = step.next() else {
+                panic!();
+            };
+            assert_eq!(Some(&"Tib".to_string()), npc.thing.name.value());
+        }
+
+        {
+            let mut step = species.clone();
+            assert_eq!(Ok(()), step.answer("random"));
+            assert_eq!(species, step);
+
+            assert_eq!(
+                Err("\"potato\" is not a species that I know.".to_string()),
+                step.answer("potato"),
+            );
+            assert_eq!(species, step);
+        }
+
+        {
+            let mut step = WizardCommand::Name {
+                npc: Box::default(),
+            };
+            assert_eq!(Ok(()), step.answer("mabel"));
+
+            let ControlFlow::Break(npc) = step.next() else {
+                panic!();
+            };
+            assert_eq!(Some(&"Mabel".to_string()), npc.thing.name.value());
+        }
+    }
+
+    #[test]
+    fn autocomplete_test() {
+        let mut app_meta = app_meta();
+
+        assert_eq!(
+            vec![AutocompleteSuggestion::new(
+                "wizard npc",
+                "create a character step by step",
+            )],
+            block_on(WizardCommand::autocomplete("wiz", &app_meta)),
+        );
+
+        block_on(WizardCommand::Npc.run("wizard npc", &mut app_meta)).unwrap();
+
+        assert_eq!(
+            vec![
+                AutocompleteSuggestion::new("half-elf", "species"),
+                AutocompleteSuggestion::new("half-orc", "species"),
+                AutocompleteSuggestion::new("halfling", "species"),
+                AutocompleteSuggestion::new("human", "species"),
+            ],
+            block_on(WizardCommand::autocomplete("h", &app_meta)),
+        );
+        assert_eq!(
+            vec![AutocompleteSuggestion::new("random", "leave it to chance")],
+            block_on(WizardCommand::autocomplete("r", &app_meta)),
+        );
+    }
+
+    fn event_dispatcher(_event: Event) {}
+
+    fn app_meta() -> AppMeta {
+        AppMeta::new(NullDataStore::default(), &event_dispatcher)
+    }
+}
@@ -0,0 +1,173 @@
+use serde::Serialize;
+use std::fmt;
+use std::ops::Range;
+
+/// The reason a command failed. Every variant carries the message shown to the user, which is
+/// what [`fmt::Display`] writes, but frontends can style errors by kind and tests can match on the
+/// kind rather than the wording.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CommandError {
+    /// The input named an entity that doesn't exist, or isn't of the expected type, eg.
+    /// `load Potato Johnson`. This includes anything else looked up by name or number, such as a
+    /// campaign, an item in someone's inventory, or a page of output.
+    UnknownEntity { name: String, message: String },
+
+    /// An entity couldn't be saved or renamed because another already has the name.
+    NameConflict { name: String, message: String },
+
+    /// The journal or another part of local storage couldn't be read or written.
+    Storage { message: String },
+
+    /// The input was recognized as a command but couldn't be fully understood. The ranges are
+    /// byte offsets of any words in the input that weren't understood.
+    Parse {
+        message: String,
+        unknown_words: Vec<Range<usize>>,
+    },
+
+    /// The command doesn't make sense in the current state, eg. killing a character who's
+    /// already dead or starting a scene while another is in progress.
+    InvalidState { message: String },
+
+    /// Anything else, such as a generator that couldn't come up with a unique name.
+    Other { message: String },
+}
+
+impl CommandError {
+    pub fn unknown_entity(name: &str, message: impl Into<String>) -> Self {
+        Self::UnknownEntity {
+            name: name.to_string(),
+            message: message.into(),
+        }
+    }
+
+    pub fn name_conflict(name: &str, message: impl Into<String>) -> Self {
+        Self::NameConflict {
+            name: name.to_string(),
+            message: message.into(),
+        }
+    }
+
+    pub fn storage(message: impl Into<String>) -> Self {
+        Self::Storage {
+            message: message.into(),
+        }
+    }
+
+    pub fn invalid_state(message: impl Into<String>) -> Self {
+        Self::InvalidState {
+            message: message.into(),
+        }
+    }
+
+    pub fn parse(message: impl Into<String>, unknown_words: Vec<Range<usize>>) -> Self {
+        Self::Parse {
+            message: message.into(),
+            unknown_words,
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            Self::UnknownEntity { message, .. }
+            | Self::NameConflict { message, .. }
+            | Self::Storage { message }
+            | Self::Parse { message, .. }
+            | Self::InvalidState { message }
+            | Self::Other { message } => message,
+        }
+    }
+
+    /// Mutable access to the message, for adding context such as the command that was run.
+    pub fn message_mut(&mut self) -> &mut String {
+        match self {
+            Self::UnknownEntity { message, .. }
+            | Self::NameConflict { message, .. }
+            | Self::Storage { message }
+            | Self::Parse { message, .. }
+            | Self::InvalidState { message }
+            | Self::Other { message } => message,
+        }
+    }
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+impl From<String> for CommandError {
+    fn from(message: String) -> Self {
+        Self::Other { message }
+    }
+}
+
+impl From<&str> for CommandError {
+    fn from(message: &str) -> Self {
+        message.to_string().into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn display_test() {
+        assert_eq!(
+            r#"There is no entity named "Potato"."#,
+            CommandError::unknown_entity("Potato", r#"There is no entity named "Potato"."#)
+                .to_string(),
+        );
+        assert_eq!(
+            "Gottfried is already dead.",
+            CommandError::invalid_state("Gottfried is already dead.").to_string(),
+        );
+    }
+
+    #[test]
+    fn message_mut_test() {
+        let mut error = CommandError::storage("Couldn't save `Gottfried`.");
+        error.message_mut().insert_str(0, "Oops! ");
+
+        assert_eq!(
+            CommandError::storage("Oops! Couldn't save `Gottfried`."),
+            error,
+        );
+    }
+
+    #[test]
+    fn serialize_test() {
+        assert_eq!(
+            serde_json::json!({
+                "kind": "name_conflict",
+                "name": "Gottfried",
+                "message": "There is already an entity named Gottfried.",
+            }),
+            serde_json::to_value(CommandError::name_conflict(
+                "Gottfried",
+                "There is already an entity named Gottfried.",
+            ))
+            .unwrap(),
+        );
+        let potato = 0..6;
+        assert_eq!(
+            serde_json::json!({
+                "kind": "parse",
+                "message": "Unknown words.",
+                "unknown_words": [{"start": 0, "end": 6}],
+            }),
+            serde_json::to_value(CommandError::parse("Unknown words.", vec![potato])).unwrap(),
+        );
+        assert_eq!(
+            serde_json::json!({
+                "kind": "invalid_state",
+                "message": "Gottfried is already dead.",
+            }),
+            serde_json::to_value(CommandError::invalid_state("Gottfried is already dead."))
+                .unwrap(),
+        );
+    }
+}
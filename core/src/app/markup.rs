@@ -0,0 +1,176 @@
+use crate::world::Thing;
+use std::fmt;
+
+/// The meaning of a span of output, which frontends may use to style it, eg. by color.
+/// Annotations are emitted as `<span class="npc">…</span>` around inline Markdown, alongside the
+/// existing markup: backticks for links, `~` for temporary links, `!` for errors, and `#` for
+/// headings. Errors are already marked by `!`, so have no annotation of their own.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Annotation {
+    Npc,
+    Pc,
+    Place,
+    Roll,
+    Success,
+    Warning,
+}
+
+/// Inline Markdown wrapped in an annotation. Each paragraph is wrapped separately so that the
+/// annotation never spans a block boundary.
+pub struct AnnotatedView<'a>(Annotation, &'a str);
+
+impl Annotation {
+    pub const ALL: [Annotation; 6] = [
+        Self::Npc,
+        Self::Pc,
+        Self::Place,
+        Self::Roll,
+        Self::Success,
+        Self::Warning,
+    ];
+
+    pub fn for_thing(thing: &Thing) -> Self {
+        match thing {
+            Thing::Npc(_) if thing.is_pc() => Self::Pc,
+            Thing::Npc(_) => Self::Npc,
+            Thing::Place(_) => Self::Place,
+        }
+    }
+
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Npc => "npc",
+            Self::Pc => "pc",
+            Self::Place => "place",
+            Self::Roll => "roll",
+            Self::Success => "success",
+            Self::Warning => "warning",
+        }
+    }
+
+    pub fn annotate<'a>(&self, text: &'a str) -> AnnotatedView<'a> {
+        AnnotatedView(*self, text)
+    }
+}
+
+impl<'a> fmt::Display for AnnotatedView<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Self(annotation, text) = self;
+
+        for (i, paragraph) in text.split("\n\n").enumerate() {
+            if i > 0 {
+                write!(f, "\n\n")?;
+            }
+
+            if paragraph.is_empty() {
+                continue;
+            }
+
+            write!(
+                f,
+                "<span class=\"{}\">{}</span>",
+                annotation.as_str(),
+                paragraph,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Remove the annotations from a block of output, leaving the plain Markdown. Only the opening
+/// tags emitted by [`AnnotatedView`] are recognized, and closing tags are only removed while one
+/// of them is open, so stray markup in user-provided text is left alone.
+pub fn strip_annotations(output: &str) -> String {
+    let mut result = String::with_capacity(output.len());
+    let mut remaining = output;
+    let mut depth = 0;
+
+    while let Some(start) = remaining.find('<') {
+        result.push_str(&remaining[..start]);
+        remaining = &remaining[start..];
+
+        if let Some(opener) = Annotation::ALL
+            .iter()
+            .map(|annotation| format!("<span class=\"{}\">", annotation.as_str()))
+            .find(|opener| remaining.starts_with(opener.as_str()))
+        {
+            depth += 1;
+            remaining = &remaining[opener.len()..];
+        } else if depth > 0 && remaining.starts_with("</span>") {
+            depth -= 1;
+            remaining = &remaining["</span>".len()..];
+        } else {
+            result.push('<');
+            remaining = &remaining['<'.len_utf8()..];
+        }
+    }
+
+    result.push_str(remaining);
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::world::npc::PlayerCharacter;
+    use crate::world::{Npc, Place};
+
+    #[test]
+    fn annotate_test() {
+        assert_eq!(
+            "<span class=\"roll\">1d20 = 17</span>",
+            Annotation::Roll.annotate("1d20 = 17").to_string(),
+        );
+        assert_eq!(
+            "<span class=\"success\">Saved.</span>\n\n<span class=\"success\">_Use `undo`._</span>",
+            Annotation::Success
+                .annotate("Saved.\n\n_Use `undo`._")
+                .to_string(),
+        );
+        assert_eq!("", Annotation::Warning.annotate("").to_string());
+    }
+
+    #[test]
+    fn for_thing_test() {
+        assert_eq!(
+            Annotation::Npc,
+            Annotation::for_thing(&Npc::default().into()),
+        );
+        assert_eq!(
+            Annotation::Pc,
+            Annotation::for_thing(
+                &Npc {
+                    pc: PlayerCharacter::default().into(),
+                    ..Default::default()
+                }
+                .into()
+            ),
+        );
+        assert_eq!(
+            Annotation::Place,
+            Annotation::for_thing(&Place::default().into()),
+        );
+    }
+
+    #[test]
+    fn strip_annotations_test() {
+        assert_eq!(
+            "`Gottfried` rolled 17.",
+            strip_annotations(
+                "<span class=\"npc\">`Gottfried`</span> rolled <span class=\"roll\">17</span>."
+            ),
+        );
+        assert_eq!(
+            "Nested `Bree`",
+            strip_annotations(
+                "<span class=\"warning\">Nested <span class=\"place\">`Bree`</span></span>"
+            ),
+        );
+        assert_eq!(
+            "<span>a</span> < b </span>",
+            strip_annotations("<span>a</span> < b </span>"),
+        );
+        assert_eq!("no markup", strip_annotations("no markup"));
+    }
+}
@@ -1,31 +1,93 @@
-use super::{CommandAlias, Event};
-use crate::storage::{DataStore, Repository};
+use super::{AuditedRng, CommandAlias, Event, TraceEvent, TraceHook, TraceLog, Vocabulary};
+use crate::history::HistoryEntry;
+use crate::storage::{DataStore, KeyValue, Repository};
+use crate::time::TimeOfDay;
+use crate::utils::MaybeSync;
 use crate::world;
-use rand::prelude::*;
 use std::collections::HashSet;
 use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The callback through which the frontend is told about [`Event`]s. It must be shareable between
+/// threads if the `sync` feature is enabled.
+#[cfg(feature = "sync")]
+pub type EventDispatcher = dyn Fn(Event) + Send + Sync;
+
+/// The callback through which the frontend is told about [`Event`]s. It must be shareable between
+/// threads if the `sync` feature is enabled.
+#[cfg(not(feature = "sync"))]
+pub type EventDispatcher = dyn Fn(Event);
 
 pub struct AppMeta {
+    /// The wall-clock time in milliseconds since the Unix epoch. The system clock isn't available
+    /// in every environment (notably WebAssembly), so the frontend can provide its own with
+    /// [`App::set_clock`](super::App::set_clock).
+    pub clock: fn() -> u64,
     pub command_aliases: HashSet<CommandAlias>,
     pub demographics: world::Demographics,
-    pub event_dispatcher: &'static dyn Fn(Event),
-    pub rng: SmallRng,
+    pub event_dispatcher: &'static EventDispatcher,
+    /// When changes waiting to be written to the data store were last saved, see
+    /// [`App::flush`](super::App::flush).
+    pub flushed_at: u64,
+    pub history: Vec<HistoryEntry>,
+    pub pages: Vec<String>,
+    pub rng: AuditedRng,
     pub repository: Repository,
+    pub trace_hooks: Vec<Box<dyn TraceHook>>,
+    pub trace_log: TraceLog,
+    pub vocabulary: Vocabulary,
 }
 
 impl AppMeta {
-    pub fn new<F: Fn(Event)>(
+    pub fn new<F: Fn(Event) + MaybeSync>(
         data_store: impl DataStore + 'static,
         event_dispatcher: &'static F,
     ) -> Self {
         Self {
+            clock: system_clock,
             command_aliases: HashSet::default(),
             demographics: world::Demographics::default(),
             event_dispatcher,
+            flushed_at: 0,
+            history: Vec::new(),
+            pages: Vec::new(),
             repository: Repository::new(data_store),
-            rng: SmallRng::from_entropy(),
+            rng: AuditedRng::from_entropy(),
+            trace_hooks: Vec::new(),
+            trace_log: TraceLog::default(),
+            vocabulary: Vocabulary::new(),
         }
     }
+
+    /// The time of day in the game world, for generators that want to colour their output with
+    /// it (eg. describing a market as bustling at midday).
+    pub(crate) async fn time_of_day(&self) -> TimeOfDay {
+        self.repository
+            .get_key_value(&KeyValue::Time(None))
+            .await
+            .ok()
+            .and_then(KeyValue::time)
+            .unwrap_or_default()
+            .time_of_day()
+    }
+
+    /// The current wall-clock time in milliseconds since the Unix epoch.
+    pub fn now(&self) -> u64 {
+        (self.clock)()
+    }
+
+    /// Pass an event to every subscribed hook, then add it to the trace log.
+    pub fn trace(&mut self, event: TraceEvent) {
+        self.trace_hooks.iter().for_each(|hook| hook.trace(&event));
+        self.trace_log.push(event);
+    }
+}
+
+fn system_clock() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
 }
 
 impl fmt::Debug for AppMeta {
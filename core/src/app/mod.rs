@@ -1,19 +1,44 @@
 pub use command::{
     AppCommand, Autocomplete, AutocompleteSuggestion, Command, CommandAlias, CommandMatches,
-    ContextAwareParse, Runnable,
+    ContextAwareParse, Runnable, TutorialProgress,
 };
-pub use meta::AppMeta;
+pub use error::CommandError;
+pub use markup::{strip_annotations, AnnotatedView, Annotation};
+pub use meta::{AppMeta, EventDispatcher};
+pub use output::{CommandOutput, OutputBody};
+pub use ranking::Ranking;
+pub use rng::{AuditedRng, RollRecord};
+pub use trace::{TraceEvent, TraceHook, TraceLog};
+pub use vocabulary::Vocabulary;
 
 #[cfg(test)]
 pub use command::assert_autocomplete;
 
 mod command;
+mod error;
+mod markup;
 mod meta;
+mod output;
+mod page;
+mod ranking;
+mod rng;
+mod trace;
+mod vocabulary;
 
+use crate::config::Config;
+use crate::history::{self, HistoryEntry};
+use crate::reference::HomebrewReference;
 use crate::storage::backup::{import, BackupData};
+use crate::storage::KeyValue;
 use crate::utils::CaseInsensitiveStr;
+use crate::world::place::Ambience;
+use crate::world::{annotate_mentions, find_mentions, render_links};
 use initiative_macros::motd;
 
+/// How long after the last flush a command writes any changes still waiting for the data store,
+/// in milliseconds. Writes are batched so that a slow data store doesn't hold up every command.
+const FLUSH_INTERVAL_MS: u64 = 5_000;
+
 /// The application wrapper. Its inner [`AppMeta`] object holds metadata associated with the
 /// application, including ephemeral storage of journal entries and the object representing the
 /// underlying data storage.
@@ -33,6 +58,14 @@ pub enum Event {
 
     /// The user typed the `import` command and should be prompted to select a file to import.
     Import,
+
+    /// The user typed the `homebrew import` command and should be prompted to select a file of
+    /// homebrew reference content to import.
+    ImportHomebrew,
+
+    /// The user typed the `ambience` command. The frontend may use the audio cues to play
+    /// something suitable on a soundboard.
+    Ambience(Ambience),
 }
 
 impl App {
@@ -45,6 +78,11 @@ impl App {
     /// store to initialize, which may involve opening a database connection.
     pub async fn init(&mut self) -> &'static str {
         self.meta.repository.init().await;
+
+        if Config::load(&self.meta).await.history {
+            self.meta.history = self.meta.repository.history().await.unwrap_or_default();
+        }
+
         let (motd, motd_len) = motd!("! Local storage is not available in your browser. You will be able to use initiative.sh, but anything you save will not persist beyond this session.");
 
         if self.meta.repository.data_store_enabled() {
@@ -57,11 +95,165 @@ impl App {
     /// The user typed an input and pressed Enter. What happens?
     ///
     /// On success or failure, returns a String that can be displayed back to the user.
+    /// Link tokens (eg. `{Gottfried}`) in the output are resolved against the repository, see
+    /// [`render_links`], and annotations are removed, see [`strip_annotations`].
     pub async fn command(&mut self, input: &str) -> Result<String, String> {
-        Command::parse_input_irrefutable(input, &self.meta)
-            .await
-            .run(input, &mut self.meta)
+        self.command_structured(input).await.into_markdown()
+    }
+
+    /// As [`App::command`], but returns the entities mentioned in the output and the aliases
+    /// made available by the command alongside the Markdown, so that the UI can render them
+    /// however it likes.
+    ///
+    /// References to earlier commands (`!!` and `![number]`) are expanded before the input is
+    /// parsed, and every command run is recorded in the history.
+    pub async fn command_structured(&mut self, input: &str) -> CommandOutput {
+        let (input, expanded) = match history::expand(input, &self.meta.history) {
+            Some(Ok(command)) => (command.to_string(), true),
+            Some(Err(message)) => {
+                return CommandOutput::Failure(OutputBody {
+                    markdown: message.clone(),
+                    things: Vec::new(),
+                    aliases: Vec::new(),
+                    error: Some(message.into()),
+                })
+            }
+            None => (input.to_string(), false),
+        };
+
+        let command = Command::parse_input_irrefutable(&input, &self.meta).await;
+
+        if let Some(command_type) = command.get_type() {
+            self.meta.trace(TraceEvent::CommandParsed {
+                input: input.clone(),
+                command: command_type.to_string(),
+            });
+        }
+
+        let mut result = command.run(&input, &mut self.meta).await;
+
+        for change in self.meta.repository.take_modifications() {
+            self.meta.trace(TraceEvent::StorageModified { change });
+        }
+
+        if let Err(error) = &result {
+            self.meta.trace(TraceEvent::CommandFailed {
+                input: input.clone(),
+                error: error.to_string(),
+            });
+        }
+
+        self.meta.rng.record(&input);
+
+        if expanded {
+            let notice = format!("_Running `{}`._\n\n", input);
+            match &mut result {
+                Ok(output) => output.insert_str(0, &notice),
+                Err(error) => error.message_mut().insert_str(0, &notice),
+            }
+        }
+
+        if let Ok(output) = result {
+            result = Ok(page::paginate(output, &mut self.meta).await);
+        }
+
+        self.record_history(&input, result.is_ok()).await;
+
+        if self.meta.now().saturating_sub(self.meta.flushed_at) >= FLUSH_INTERVAL_MS {
+            let _ = self.flush().await;
+        }
+
+        let (output, error) = match result {
+            Ok(output) => (output, None),
+            Err(error) => (error.to_string(), Some(error)),
+        };
+        let markdown = render_links(&output, &self.meta.repository).await;
+
+        let mut aliases: Vec<AutocompleteSuggestion> = self
+            .meta
+            .command_aliases
+            .iter()
+            .filter_map(|alias| match alias {
+                CommandAlias::Literal { term, summary, .. } => {
+                    Some(AutocompleteSuggestion::new(term.clone(), summary.clone()))
+                }
+                CommandAlias::StrictWildcard { .. } => None,
+            })
+            .collect();
+        aliases.sort_by(|a, b| a.term.cmp_ci(&b.term));
+
+        let is_success = error.is_none();
+        let body = OutputBody {
+            things: find_mentions(&markdown, &self.meta.repository).await,
+            markdown: annotate_mentions(&markdown, &self.meta.repository).await,
+            aliases,
+            error,
+        };
+
+        if is_success {
+            CommandOutput::Success(body)
+        } else {
+            CommandOutput::Failure(body)
+        }
+    }
+
+    /// Add a command to the history, also saving it to the data store if the `history` setting is
+    /// on.
+    async fn record_history(&mut self, input: &str, success: bool) {
+        if input.trim().is_empty() {
+            return;
+        }
+
+        let entry = HistoryEntry {
+            input: input.trim().to_string(),
+            time: self
+                .meta
+                .repository
+                .get_key_value(&KeyValue::Time(None))
+                .await
+                .ok()
+                .and_then(KeyValue::time)
+                .unwrap_or_default(),
+            real_time: Some(self.meta.now()),
+            success,
+        };
+
+        if Config::load(&self.meta).await.history {
+            self.meta.repository.record_history(&entry).await;
+        }
+
+        self.meta.history.push(entry);
+    }
+
+    /// Write any changes that are still waiting to be saved to the data store. Commands run more
+    /// than a few seconds after the last flush do this on their own, but the frontend should also
+    /// call it on a timer and when the user leaves, such as when the page is closed, so that
+    /// nothing is left waiting while the app is idle.
+    pub async fn flush(&mut self) -> Result<(), String> {
+        self.meta.flushed_at = self.meta.now();
+        self.meta
+            .repository
+            .flush()
             .await
+            .map_err(|_| "Some of your changes couldn't be saved.".to_string())
+    }
+
+    /// Subscribe to the structured events emitted while commands run, such as a command being
+    /// parsed or the journal being modified. See [`TraceEvent`].
+    pub fn subscribe(&mut self, hook: impl TraceHook + 'static) {
+        self.meta.trace_hooks.push(Box::new(hook));
+    }
+
+    /// Replace the wall-clock time source, which returns milliseconds since the Unix epoch. It's
+    /// used to time play sessions, see `session start`.
+    pub fn set_clock(&mut self, clock: fn() -> u64) {
+        self.meta.clock = clock;
+    }
+
+    /// Replace the source of randomness, eg. with [`AuditedRng::from_seed`] so that generated
+    /// output can be reproduced.
+    pub fn set_rng(&mut self, rng: AuditedRng) {
+        self.meta.rng = rng;
     }
 
     /// The user has updated their input and a new set of suggestions should be populated. This
@@ -70,10 +262,10 @@ impl App {
     /// used here to allow either `String` or `&'static str`, whatever is appropriate to a given
     /// case.
     ///
-    /// Returns a maximum of 10 results.
+    /// Returns a maximum of 10 results, with the most likely completions first (see [`Ranking`]).
     pub async fn autocomplete(&self, input: &str) -> Vec<AutocompleteSuggestion> {
         let mut suggestions: Vec<_> = Command::autocomplete(input, &self.meta).await;
-        suggestions.sort_by(|a, b| a.term.cmp_ci(&b.term));
+        Ranking::new(&self.meta).sort(&mut suggestions);
         suggestions.truncate(10);
         suggestions
     }
@@ -86,4 +278,10 @@ impl App {
             .map(|stats| stats.to_string())
             .map_err(|_| "Failed to import.".to_string())
     }
+
+    /// The part of the homebrew import flow that occurs after the user selects a file in response
+    /// to the [`Event::ImportHomebrew`].
+    pub async fn import_homebrew(&mut self, data: serde_json::Value) -> Result<String, String> {
+        HomebrewReference::import(&mut self.meta.repository, data).await
+    }
 }
@@ -0,0 +1,138 @@
+use super::{strip_annotations, AutocompleteSuggestion, CommandError};
+use crate::world::Thing;
+use serde::Serialize;
+
+/// The structured result of running a command, for frontends that want to render more than plain
+/// Markdown (eg. a card for each character mentioned). [`App::command`](super::App::command) is
+/// one consumer of this structure, reducing it to its Markdown text.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(tag = "status", rename_all = "lowercase")]
+pub enum CommandOutput {
+    Success(OutputBody),
+    Failure(OutputBody),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct OutputBody {
+    /// The output as it would be shown in the terminal, with link tokens resolved. Spans of the
+    /// output may be annotated for styling, see [`Annotation`](super::Annotation).
+    pub markdown: String,
+
+    /// The journal entries and recent entities mentioned in the output, in the order they're
+    /// first mentioned.
+    pub things: Vec<Thing>,
+
+    /// The aliases made available by the command, such as `save` after generating a character.
+    pub aliases: Vec<AutocompleteSuggestion>,
+
+    /// Why the command failed, if it did. The message is also the Markdown of the body, before
+    /// link tokens are resolved.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<CommandError>,
+}
+
+impl CommandOutput {
+    pub fn body(&self) -> &OutputBody {
+        match self {
+            Self::Success(body) | Self::Failure(body) => body,
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        matches!(self, Self::Success(_))
+    }
+
+    pub fn error(&self) -> Option<&CommandError> {
+        self.body().error.as_ref()
+    }
+
+    /// Reduce the output to the plain Markdown returned by [`App::command`](super::App::command).
+    pub fn into_markdown(self) -> Result<String, String> {
+        match self {
+            Self::Success(body) => Ok(strip_annotations(&body.markdown)),
+            Self::Failure(body) => Err(strip_annotations(&body.markdown)),
+        }
+    }
+
+    /// As [`CommandOutput::into_markdown`], but keeping the annotations for frontends that can
+    /// style them.
+    pub fn into_annotated_markdown(self) -> Result<String, String> {
+        match self {
+            Self::Success(body) => Ok(body.markdown),
+            Self::Failure(body) => Err(body.markdown),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::world::Npc;
+
+    #[test]
+    fn into_markdown_test() {
+        assert_eq!(Ok("Hello".to_string()), body("Hello").into_markdown());
+        assert_eq!(
+            Err("Goodbye".to_string()),
+            CommandOutput::Failure(body("Goodbye").body().clone()).into_markdown(),
+        );
+
+        let annotated = "<span class=\"roll\">17</span>";
+        assert_eq!(Ok("17".to_string()), body(annotated).into_markdown());
+        assert_eq!(
+            Ok(annotated.to_string()),
+            body(annotated).into_annotated_markdown(),
+        );
+    }
+
+    #[test]
+    fn serialize_test() {
+        let output = CommandOutput::Success(OutputBody {
+            markdown: "# Gottfried".to_string(),
+            things: vec![Npc {
+                name: "Gottfried".into(),
+                ..Default::default()
+            }
+            .into()],
+            aliases: vec![AutocompleteSuggestion::new("save", "save to journal")],
+            error: None,
+        });
+
+        let value = serde_json::to_value(output).unwrap();
+        assert_eq!("success", value["status"]);
+        assert_eq!("# Gottfried", value["markdown"]);
+        assert_eq!("Gottfried", value["things"][0]["name"]);
+        assert_eq!(
+            serde_json::json!([["save", "save to journal"]]),
+            value["aliases"],
+        );
+        assert!(value.get("error").is_none());
+    }
+
+    #[test]
+    fn serialize_test_failure() {
+        let output = CommandOutput::Failure(OutputBody {
+            markdown: r#"There is no entity named "Potato"."#.to_string(),
+            things: Vec::new(),
+            aliases: Vec::new(),
+            error: Some(CommandError::unknown_entity(
+                "Potato",
+                r#"There is no entity named "Potato"."#,
+            )),
+        });
+
+        let value = serde_json::to_value(output).unwrap();
+        assert_eq!("failure", value["status"]);
+        assert_eq!("unknown_entity", value["error"]["kind"]);
+        assert_eq!("Potato", value["error"]["name"]);
+    }
+
+    fn body(markdown: &str) -> CommandOutput {
+        CommandOutput::Success(OutputBody {
+            markdown: markdown.to_string(),
+            things: Vec::new(),
+            aliases: Vec::new(),
+            error: None,
+        })
+    }
+}
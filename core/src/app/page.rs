@@ -0,0 +1,217 @@
+use super::{AppCommand, AppMeta, CommandAlias};
+use crate::config::Config;
+
+/// Lines reserved at the bottom of each page for the footer and the blank line preceding it.
+const FOOTER_LINES: usize = 2;
+
+/// Split long output into pages according to the configured page size, keeping the pages in
+/// `app_meta` so that they can be shown with `next page` and `prev page`. Returns the first page,
+/// or the output unchanged if it fits on a single page.
+pub async fn paginate(output: String, app_meta: &mut AppMeta) -> String {
+    let page_size = match Config::load(app_meta).await.page_size {
+        Some(page_size) => page_size as usize,
+        None => return output,
+    };
+
+    if output.lines().count() <= page_size {
+        return output;
+    }
+
+    app_meta.pages = split(&output, page_size.saturating_sub(FOOTER_LINES));
+    show_page(1, app_meta).unwrap_or(output)
+}
+
+/// Show a numbered page of the most recently paginated output, counting from 1.
+pub fn show_page(number: usize, app_meta: &mut AppMeta) -> Result<String, String> {
+    let page_count = app_meta.pages.len();
+    let page = number
+        .checked_sub(1)
+        .and_then(|i| app_meta.pages.get(i))
+        .ok_or_else(|| format!("There is no page {}.", number))?;
+
+    let footer = match (number > 1, number < page_count) {
+        (false, _) => "Use ~next page~ to continue.",
+        (true, true) => "Use ~next page~ to continue or ~prev page~ to go back.",
+        (true, false) => "Use ~prev page~ to go back.",
+    };
+
+    let output = format!(
+        "{}\n\n_Page {} of {}. {}_",
+        page, number, page_count, footer,
+    );
+
+    if number < page_count {
+        app_meta.command_aliases.insert(CommandAlias::literal(
+            "next page",
+            format!("show page {}", number + 1),
+            AppCommand::Page(number + 1).into(),
+        ));
+    } else {
+        remove_alias("next page", app_meta);
+    }
+
+    if number > 1 {
+        app_meta.command_aliases.insert(CommandAlias::literal(
+            "prev page",
+            format!("show page {}", number - 1),
+            AppCommand::Page(number - 1).into(),
+        ));
+    } else {
+        remove_alias("prev page", app_meta);
+    }
+
+    Ok(output)
+}
+
+fn remove_alias(alias_term: &str, app_meta: &mut AppMeta) {
+    app_meta
+        .command_aliases
+        .retain(|alias| !matches!(alias, CommandAlias::Literal { term, .. } if term == alias_term));
+}
+
+/// Split the output into pages of at most `lines_per_page` lines. A table split across pages has
+/// its header repeated at the top of each page, and pages don't begin with blank lines.
+fn split(output: &str, lines_per_page: usize) -> Vec<String> {
+    let lines_per_page = lines_per_page.max(1);
+    let lines: Vec<&str> = output.lines().collect();
+
+    let mut pages = Vec::new();
+    let mut page: Vec<&str> = Vec::new();
+    let mut table_start: Option<usize> = None;
+
+    for (i, line) in lines.iter().enumerate() {
+        if !line.starts_with('|') {
+            table_start = None;
+        } else if table_start.is_none() {
+            table_start = Some(i);
+        }
+
+        if page.len() >= lines_per_page {
+            pages.push(page.join("\n").trim_end().to_string());
+            page.clear();
+
+            // The header is the first two lines of the table: the column names and the separator.
+            if let Some(start) = table_start {
+                page.extend_from_slice(&lines[start..i.min(start + 2)]);
+            }
+        }
+
+        if !(page.is_empty() && line.trim().is_empty()) {
+            page.push(line);
+        }
+    }
+
+    if !page.is_empty() || pages.is_empty() {
+        pages.push(page.join("\n").trim_end().to_string());
+    }
+
+    pages
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::storage::{Change, KeyValue, MemoryDataStore};
+    use crate::Event;
+    use tokio_test::block_on;
+
+    #[test]
+    fn split_test() {
+        assert_eq!(vec!["one\ntwo"], split("one\ntwo", 5));
+        assert_eq!(
+            vec!["one\ntwo", "three\nfour", "five"],
+            split("one\ntwo\nthree\nfour\nfive", 2)
+        );
+        assert_eq!(vec!["one", "three"], split("one\n\nthree", 1));
+        assert_eq!(vec![""], split("", 5));
+    }
+
+    #[test]
+    fn split_table_test() {
+        assert_eq!(
+            vec![
+                "# Table\n\n| A |\n|---|",
+                "| A |\n|---|\n| 1 |\n| 2 |",
+                "| A |\n|---|\n| 3 |\nAfter",
+            ],
+            split("# Table\n\n| A |\n|---|\n| 1 |\n| 2 |\n| 3 |\nAfter", 4),
+        );
+    }
+
+    #[test]
+    fn split_table_header_test() {
+        // The header isn't repeated on the page where the table begins.
+        assert_eq!(
+            vec!["# Table\n\n| A |", "| A |\n|---|\n| 1 |"],
+            split("# Table\n\n| A |\n|---|\n| 1 |", 3),
+        );
+    }
+
+    #[test]
+    fn paginate_test() {
+        let mut app_meta = app_meta();
+        let output = (1..=50)
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        assert_eq!(output, block_on(paginate(output.clone(), &mut app_meta)));
+        assert!(app_meta.pages.is_empty());
+
+        block_on(app_meta.repository.modify(Change::SetKeyValue {
+            key_value: KeyValue::Config(Some(Config {
+                page_size: Some(40),
+                ..Default::default()
+            })),
+        }))
+        .unwrap();
+
+        let page = block_on(paginate(output.clone(), &mut app_meta));
+        assert!(page.starts_with("1\n2\n"), "{}", page);
+        assert!(
+            page.ends_with("38\n\n_Page 1 of 2. Use ~next page~ to continue._"),
+            "{}",
+            page,
+        );
+        assert_eq!(40, page.lines().count());
+        assert_eq!(2, app_meta.pages.len());
+
+        assert_eq!(
+            "Short output",
+            block_on(paginate("Short output".to_string(), &mut app_meta)),
+        );
+        assert_eq!(2, app_meta.pages.len());
+    }
+
+    #[test]
+    fn show_page_test() {
+        let mut app_meta = app_meta();
+        app_meta.pages = vec!["one".to_string(), "two".to_string(), "three".to_string()];
+
+        assert_eq!(
+            Ok(
+                "two\n\n_Page 2 of 3. Use ~next page~ to continue or ~prev page~ to go back._"
+                    .to_string()
+            ),
+            show_page(2, &mut app_meta),
+        );
+        assert_eq!(2, app_meta.command_aliases.len());
+
+        assert_eq!(
+            Ok("three\n\n_Page 3 of 3. Use ~prev page~ to go back._".to_string()),
+            show_page(3, &mut app_meta),
+        );
+        assert_eq!(1, app_meta.command_aliases.len());
+
+        assert_eq!(
+            Err("There is no page 4.".to_string()),
+            show_page(4, &mut app_meta),
+        );
+    }
+
+    fn event_dispatcher(_event: Event) {}
+
+    fn app_meta() -> AppMeta {
+        AppMeta::new(MemoryDataStore::default(), &event_dispatcher)
+    }
+}
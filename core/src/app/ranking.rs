@@ -0,0 +1,187 @@
+use super::{AppMeta, AutocompleteSuggestion, CommandAlias};
+use crate::utils::CaseInsensitiveStr;
+
+/// How many of the most recent successful commands count towards a suggestion's score.
+const HISTORY_WINDOW: usize = 20;
+
+/// How many of the most recently generated things count towards a suggestion's score.
+const RECENT_THINGS_WINDOW: usize = 10;
+
+/// Suggestions matching an alias registered for the current context, such as the tutorial's "next"
+/// or the pager's "next page", outrank everything else.
+const ALIAS_SCORE: usize = 1000;
+
+/// Weight given to each step of recency within the windows above.
+const RECENCY_SCORE: usize = 10;
+
+/// The signals used to put the most likely completions first. Every [`Autocomplete`] impl
+/// contributes suggestions unordered, and they are then ranked together by
+/// [`App::autocomplete`](crate::App::autocomplete).
+///
+/// [`Autocomplete`]: super::Autocomplete
+pub struct Ranking<'a> {
+    /// Terms of the literal aliases currently registered.
+    aliases: Vec<&'a str>,
+
+    /// The first word of each recent successful command, most recent first.
+    commands: Vec<&'a str>,
+
+    /// The names of recently generated things, most recent first.
+    names: Vec<&'a str>,
+}
+
+impl<'a> Ranking<'a> {
+    pub fn new(app_meta: &'a AppMeta) -> Self {
+        Self {
+            aliases: app_meta
+                .command_aliases
+                .iter()
+                .filter_map(|alias| match alias {
+                    CommandAlias::Literal { term, .. } => Some(term.as_ref()),
+                    CommandAlias::StrictWildcard { .. } => None,
+                })
+                .collect(),
+            commands: app_meta
+                .history
+                .iter()
+                .rev()
+                .filter(|entry| entry.success)
+                .filter_map(|entry| entry.input.split_whitespace().next())
+                .take(HISTORY_WINDOW)
+                .collect(),
+            names: app_meta
+                .repository
+                .recent()
+                .rev()
+                .filter_map(|thing| thing.name().value())
+                .map(|name| name.as_str())
+                .take(RECENT_THINGS_WINDOW)
+                .collect(),
+        }
+    }
+
+    pub fn score(&self, suggestion: &AutocompleteSuggestion) -> usize {
+        let term = suggestion.term.as_ref();
+        let mut score = 0;
+
+        if term.in_ci(&self.aliases) {
+            score += ALIAS_SCORE;
+        }
+
+        if let Some(first_word) = term.split_whitespace().next() {
+            if let Some(i) = self
+                .commands
+                .iter()
+                .position(|command| command.eq_ci(first_word))
+            {
+                score += (HISTORY_WINDOW - i) * RECENCY_SCORE;
+            }
+        }
+
+        let term_lower = term.to_lowercase();
+        if let Some(i) = self
+            .names
+            .iter()
+            .position(|name| term_lower.contains(&name.to_lowercase()))
+        {
+            score += (RECENT_THINGS_WINDOW - i) * RECENCY_SCORE;
+        }
+
+        score
+    }
+
+    /// Sort suggestions from most to least likely, falling back to alphabetical order between
+    /// suggestions with the same score.
+    pub fn sort(&self, suggestions: &mut [AutocompleteSuggestion]) {
+        suggestions.sort_by(|a, b| {
+            self.score(b)
+                .cmp(&self.score(a))
+                .then_with(|| a.term.cmp_ci(&b.term))
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::AppCommand;
+    use crate::history::HistoryEntry;
+    use crate::storage::{Change, NullDataStore};
+    use crate::time::Time;
+    use crate::world::Npc;
+    use crate::Event;
+    use tokio_test::block_on;
+
+    #[test]
+    fn score_test() {
+        let mut app_meta = app_meta();
+        app_meta.command_aliases.insert(CommandAlias::literal(
+            "next",
+            "continue the tutorial",
+            AppCommand::About.into(),
+        ));
+        app_meta.history = vec![entry("save Gandalf", true), entry("load Frodo", true)];
+        block_on(
+            app_meta.repository.modify(Change::Create {
+                thing: Npc {
+                    name: "Gottfried".into(),
+                    ..Default::default()
+                }
+                .into(),
+            }),
+        )
+        .unwrap();
+
+        let ranking = Ranking::new(&app_meta);
+
+        assert_eq!(ALIAS_SCORE, ranking.score(&suggestion("NEXT")));
+        assert_eq!(200, ranking.score(&suggestion("load [name]")));
+        assert_eq!(190, ranking.score(&suggestion("save [name]")));
+        assert_eq!(
+            100,
+            ranking.score(&suggestion("rename Gottfried to [new name]"))
+        );
+        assert_eq!(0, ranking.score(&suggestion("about")));
+    }
+
+    #[test]
+    fn sort_test() {
+        let mut app_meta = app_meta();
+        app_meta.history = vec![entry("roll d20", true), entry("date", false)];
+
+        let mut suggestions = vec![
+            suggestion("date"),
+            suggestion("delete [name]"),
+            suggestion("roll [dice]"),
+            suggestion("Darkness"),
+        ];
+        Ranking::new(&app_meta).sort(&mut suggestions);
+
+        assert_eq!(
+            vec!["roll [dice]", "Darkness", "date", "delete [name]"],
+            suggestions
+                .iter()
+                .map(|suggestion| suggestion.term.as_ref())
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    fn entry(input: &str, success: bool) -> HistoryEntry {
+        HistoryEntry {
+            input: input.to_string(),
+            time: Time::default(),
+            real_time: None,
+            success,
+        }
+    }
+
+    fn suggestion(term: &'static str) -> AutocompleteSuggestion {
+        AutocompleteSuggestion::new(term, "")
+    }
+
+    fn event_dispatcher(_event: Event) {}
+
+    fn app_meta() -> AppMeta {
+        AppMeta::new(NullDataStore::default(), &event_dispatcher)
+    }
+}
@@ -0,0 +1,237 @@
+use crate::utils::MaybeSync;
+use rand::prelude::*;
+use std::fmt;
+
+/// The number of entries shown by `rolls`.
+const ROLLS_SHOWN: usize = 20;
+
+/// The app's source of randomness. Any [`RngCore`] can be plugged in, such as a seeded generator
+/// for reproducible output. Every random value drawn is counted and attributed to the command
+/// that drew it, and dice results are kept verbatim, so that the log shown by `rolls` can prove
+/// that nothing was fudged.
+pub struct AuditedRng {
+    inner: Box<InnerRng>,
+    seed: Option<u64>,
+    draws: u64,
+    results: Vec<String>,
+    log: Vec<RollRecord>,
+}
+
+#[cfg(feature = "sync")]
+type InnerRng = dyn RngCore + Send + Sync;
+
+#[cfg(not(feature = "sync"))]
+type InnerRng = dyn RngCore;
+
+/// The random values drawn by a single command.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RollRecord {
+    pub input: String,
+    pub draws: u64,
+    pub results: Vec<String>,
+}
+
+impl AuditedRng {
+    /// Wrap an arbitrary generator. Its seed is unknown, so the log can't be used to reproduce
+    /// the session.
+    pub fn new(rng: impl RngCore + MaybeSync + 'static) -> Self {
+        Self {
+            inner: Box::new(rng),
+            seed: None,
+            draws: 0,
+            results: Vec::new(),
+            log: Vec::new(),
+        }
+    }
+
+    /// A generator whose output is fully determined by `seed`.
+    pub fn from_seed(seed: u64) -> Self {
+        Self {
+            seed: Some(seed),
+            ..Self::new(SmallRng::seed_from_u64(seed))
+        }
+    }
+
+    /// A generator with a random seed, which is kept so that the session can be reproduced.
+    pub fn from_entropy() -> Self {
+        Self::from_seed(SmallRng::from_entropy().gen())
+    }
+
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Note the outcome of a dice roll made by the current command.
+    pub fn note(&mut self, result: impl Into<String>) {
+        self.results.push(result.into());
+    }
+
+    /// Attribute everything drawn since the last call to the command `input`. Commands that
+    /// didn't draw anything aren't logged.
+    pub fn record(&mut self, input: &str) {
+        if self.draws == 0 && self.results.is_empty() {
+            return;
+        }
+
+        self.log.push(RollRecord {
+            input: input.trim().to_string(),
+            draws: self.draws,
+            results: std::mem::take(&mut self.results),
+        });
+        self.draws = 0;
+    }
+
+    pub fn log(&self) -> &[RollRecord] {
+        &self.log
+    }
+
+    /// The output of the `rolls` command.
+    pub fn display_log(&self) -> String {
+        let mut output = "# Rolls".to_string();
+
+        if let Some(seed) = self.seed {
+            output.push_str(&format!("\n\n_Random seed: {}_", seed));
+        }
+
+        if self.log.is_empty() {
+            output.push_str("\n\nNothing has been rolled yet.");
+        } else {
+            output.push('\n');
+
+            let skip = self.log.len().saturating_sub(ROLLS_SHOWN);
+            for record in self.log.iter().skip(skip) {
+                output.push_str(&format!("\n* {}", record));
+            }
+        }
+
+        output
+    }
+}
+
+impl Default for AuditedRng {
+    fn default() -> Self {
+        Self::from_entropy()
+    }
+}
+
+impl RngCore for AuditedRng {
+    fn next_u32(&mut self) -> u32 {
+        self.draws += 1;
+        self.inner.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.draws += 1;
+        self.inner.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.draws += 1;
+        self.inner.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.draws += 1;
+        self.inner.try_fill_bytes(dest)
+    }
+}
+
+impl fmt::Debug for AuditedRng {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "AuditedRng {{ seed: {:?}, draws: {}, log: {:?} }}",
+            self.seed, self.draws, self.log,
+        )
+    }
+}
+
+impl fmt::Display for RollRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "`{}`: ", self.input)?;
+
+        for result in &self.results {
+            write!(f, "{}; ", result)?;
+        }
+
+        write!(
+            f,
+            "{} random value{}",
+            self.draws,
+            if self.draws == 1 { "" } else { "s" },
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn from_seed_test() {
+        let mut rng = AuditedRng::from_seed(42);
+        let mut expected = SmallRng::seed_from_u64(42);
+
+        assert_eq!(Some(42), rng.seed());
+        assert_eq!(expected.gen::<u64>(), rng.gen::<u64>());
+        assert_eq!(None, AuditedRng::new(expected).seed());
+    }
+
+    #[test]
+    fn record_test() {
+        let mut rng = AuditedRng::from_seed(0);
+
+        rng.record("about");
+        assert!(rng.log().is_empty());
+
+        rng.gen::<u32>();
+        rng.gen::<u64>();
+        rng.note("d20 = 15");
+        rng.record(" roll d20 ");
+
+        rng.gen::<u32>();
+        rng.record("npc");
+
+        assert_eq!(
+            [
+                RollRecord {
+                    input: "roll d20".to_string(),
+                    draws: 2,
+                    results: vec!["d20 = 15".to_string()],
+                },
+                RollRecord {
+                    input: "npc".to_string(),
+                    draws: 1,
+                    results: Vec::new(),
+                },
+            ],
+            rng.log(),
+        );
+    }
+
+    #[test]
+    fn display_log_test() {
+        let mut rng = AuditedRng::from_seed(1234);
+        assert_eq!(
+            "# Rolls\n\n_Random seed: 1234_\n\nNothing has been rolled yet.",
+            rng.display_log(),
+        );
+
+        rng.gen::<u32>();
+        rng.note("d20 = 15");
+        rng.record("roll d20");
+        rng.gen::<u32>();
+        rng.gen::<u32>();
+        rng.record("npc");
+
+        assert_eq!(
+            "# Rolls\n\n_Random seed: 1234_\n\n* `roll d20`: d20 = 15; 1 random value\n* `npc`: 2 random values",
+            rng.display_log(),
+        );
+
+        let mut rng = AuditedRng::new(SmallRng::seed_from_u64(0));
+        rng.gen::<u32>();
+        rng.record("npc");
+        assert_eq!("# Rolls\n\n* `npc`: 1 random value", rng.display_log());
+    }
+}
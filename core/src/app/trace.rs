@@ -0,0 +1,200 @@
+use crate::utils::MaybeSync;
+use std::collections::VecDeque;
+use std::fmt;
+
+/// A structured record of something that happened while running a command. Frontends and tests
+/// can receive these as they happen with [`crate::App::subscribe`], and the most recent are kept
+/// in the [`TraceLog`] for the `debug events` command.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TraceEvent {
+    /// The input was understood as the given command.
+    CommandParsed { input: String, command: String },
+
+    /// The command run in response to the input failed with an error message.
+    CommandFailed { input: String, error: String },
+
+    /// A thing was generated, whether or not it was kept.
+    Generated { thing_type: String, name: String },
+
+    /// A change was applied to the repository, described as it would be by `redo`.
+    StorageModified { change: String },
+}
+
+/// A subscriber to trace events. Any closure accepting a `&TraceEvent` can be used.
+pub trait TraceHook: MaybeSync {
+    fn trace(&self, event: &TraceEvent);
+}
+
+impl<F: Fn(&TraceEvent) + MaybeSync> TraceHook for F {
+    fn trace(&self, event: &TraceEvent) {
+        self(event)
+    }
+}
+
+/// The built-in collector of trace events, holding only the most recent.
+#[derive(Debug, Default)]
+pub struct TraceLog {
+    events: VecDeque<TraceEvent>,
+}
+
+impl TraceLog {
+    pub const LEN: usize = 100;
+
+    pub fn push(&mut self, event: TraceEvent) {
+        while self.events.len() >= Self::LEN {
+            self.events.pop_front();
+        }
+
+        self.events.push_back(event);
+    }
+
+    /// The collected events, from oldest to newest.
+    pub fn events(&self) -> impl Iterator<Item = &TraceEvent> {
+        self.events.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    pub fn display_log(&self) -> String {
+        let mut output = "# Events".to_string();
+
+        if self.events.is_empty() {
+            output.push_str("\n\nNothing has happened yet.");
+        } else {
+            output.push('\n');
+
+            for event in self.events.iter() {
+                output.push_str(&format!("\n* {}", event));
+            }
+        }
+
+        output
+    }
+}
+
+impl fmt::Display for TraceEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::CommandParsed { input, command } if input == command => {
+                write!(f, "parsed `{}`", input)
+            }
+            Self::CommandParsed { input, command } => {
+                write!(f, "parsed `{}` as `{}`", input, command)
+            }
+            Self::CommandFailed { input, error } => write!(f, "`{}` failed: {}", input, error),
+            Self::Generated { thing_type, name } => {
+                write!(f, "generated {} {}", thing_type, name)
+            }
+            Self::StorageModified { change } => write!(f, "storage modified by {}", change),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn trace_log_test() {
+        let mut log = TraceLog::default();
+        assert!(log.is_empty());
+
+        for i in 0..=TraceLog::LEN {
+            log.push(TraceEvent::StorageModified {
+                change: i.to_string(),
+            });
+        }
+
+        assert_eq!(TraceLog::LEN, log.events().count());
+        assert_eq!(
+            Some(&TraceEvent::StorageModified {
+                change: "1".to_string(),
+            }),
+            log.events().next(),
+        );
+    }
+
+    #[test]
+    fn display_log_test() {
+        let mut log = TraceLog::default();
+        assert_eq!("# Events\n\nNothing has happened yet.", log.display_log());
+
+        log.push(TraceEvent::CommandParsed {
+            input: "npc".to_string(),
+            command: "npc".to_string(),
+        });
+        log.push(TraceEvent::Generated {
+            thing_type: "character".to_string(),
+            name: "Gottfried".to_string(),
+        });
+
+        assert_eq!(
+            "# Events\n\n* parsed `npc`\n* generated character Gottfried",
+            log.display_log(),
+        );
+    }
+
+    #[test]
+    fn trace_hook_test() {
+        use crate::utils::Shared;
+
+        let events = Shared::new(Vec::new());
+        let hook: Box<dyn TraceHook> = {
+            let events = events.clone();
+            Box::new(move |event: &TraceEvent| events.borrow_mut().push(event.clone()))
+        };
+
+        let event = TraceEvent::Generated {
+            thing_type: "character".to_string(),
+            name: "Gottfried".to_string(),
+        };
+        hook.trace(&event);
+
+        assert_eq!(vec![event], *events.borrow());
+    }
+
+    #[test]
+    fn display_test() {
+        assert_eq!(
+            "parsed `journal`",
+            TraceEvent::CommandParsed {
+                input: "journal".to_string(),
+                command: "journal".to_string(),
+            }
+            .to_string(),
+        );
+        assert_eq!(
+            "parsed `Gottfried` as `load Gottfried`",
+            TraceEvent::CommandParsed {
+                input: "Gottfried".to_string(),
+                command: "load Gottfried".to_string(),
+            }
+            .to_string(),
+        );
+        assert_eq!(
+            "`potato` failed: Unknown command: \"potato\"",
+            TraceEvent::CommandFailed {
+                input: "potato".to_string(),
+                error: "Unknown command: \"potato\"".to_string(),
+            }
+            .to_string(),
+        );
+        assert_eq!(
+            "generated character Gottfried",
+            TraceEvent::Generated {
+                thing_type: "character".to_string(),
+                name: "Gottfried".to_string(),
+            }
+            .to_string(),
+        );
+        assert_eq!(
+            "storage modified by creating Gottfried",
+            TraceEvent::StorageModified {
+                change: "creating Gottfried".to_string(),
+            }
+            .to_string(),
+        );
+    }
+}
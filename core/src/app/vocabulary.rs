@@ -0,0 +1,80 @@
+use crate::reference::{ReferenceCommand, Spell};
+use crate::utils::Trie;
+use crate::world;
+
+/// Static words that autocomplete draws from, indexed once when the app starts so that each
+/// keystroke only has to walk down a [`Trie`] rather than scan every word list. This matters most
+/// for the SRD, which runs to thousands of terms.
+#[derive(Debug)]
+pub struct Vocabulary {
+    /// Words that can start a character description, eg. "elderly" or "dwarf".
+    pub npc_terms: Trie<&'static str>,
+
+    /// Words that can start a place description, eg. "inn".
+    pub place_terms: Trie<&'static str>,
+
+    /// SRD entries and reference commands, with the summary shown alongside each suggestion.
+    pub reference: Trie<(&'static str, &'static str)>,
+
+    /// The canonical name of every SRD spell.
+    pub spells: Trie<&'static str>,
+}
+
+impl Vocabulary {
+    pub fn new() -> Self {
+        let mut spell_names: Vec<&'static str> = Spell::get_words()
+            .filter_map(|word| word.parse::<Spell>().ok())
+            .map(|spell| spell.get_name())
+            .collect();
+        spell_names.sort_unstable();
+        spell_names.dedup();
+
+        Self {
+            npc_terms: world::npc_terms().map(|term| (term, term)).collect(),
+            place_terms: world::place_terms().map(|term| (term, term)).collect(),
+            reference: ReferenceCommand::vocabulary()
+                .map(|entry| (entry.0, entry))
+                .collect(),
+            spells: spell_names.into_iter().map(|name| (name, name)).collect(),
+        }
+    }
+}
+
+impl Default for Vocabulary {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn new_test() {
+        let vocabulary = Vocabulary::new();
+
+        assert_eq!(
+            vec![&"inn"],
+            vocabulary
+                .place_terms
+                .starting_with("in")
+                .collect::<Vec<_>>(),
+        );
+        assert!(vocabulary
+            .npc_terms
+            .starting_with("dwa")
+            .any(|term| term == &"dwarf"));
+        assert_eq!(
+            vec![&("Fireball", "SRD spell")],
+            vocabulary
+                .reference
+                .starting_with("fireb")
+                .collect::<Vec<_>>(),
+        );
+        assert_eq!(
+            vec![&"Shield"],
+            vocabulary.spells.starting_with("SH").collect::<Vec<_>>(),
+        );
+    }
+}
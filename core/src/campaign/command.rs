@@ -0,0 +1,348 @@
+use crate::app::{
+    AppMeta, Autocomplete, AutocompleteSuggestion, CommandError, CommandMatches, ContextAwareParse,
+    Runnable,
+};
+use crate::storage::RepositoryError;
+use crate::utils::CaseInsensitiveStr;
+use async_trait::async_trait;
+use std::fmt;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CampaignCommand {
+    List,
+    New { name: String },
+    Switch { name: String },
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Runnable for CampaignCommand {
+    async fn run(self, _input: &str, app_meta: &mut AppMeta) -> Result<String, CommandError> {
+        let repository = &mut app_meta.repository;
+
+        match self {
+            Self::List => {
+                let mut output = "# Campaigns\n".to_string();
+
+                for campaign in repository.campaigns() {
+                    if campaign == repository.current_campaign() {
+                        output.push_str(&format!("\n* **{}** (current)", campaign.name));
+                    } else {
+                        output.push_str(&format!("\n* `campaign switch {}`", campaign.name));
+                    }
+                }
+
+                output.push_str("\n\n_Use `campaign new [name]` to start a new campaign._");
+
+                Ok(output)
+            }
+            Self::New { name } => {
+                let previous = repository.current_campaign().name.clone();
+
+                match repository.create_campaign(&name).await {
+                    Ok(campaign) => Ok(format!(
+                        "Started a new campaign, **{}**. Use `campaign switch {}` to return to the previous one.",
+                        campaign.name, previous,
+                    )),
+                    Err(RepositoryError::NameAlreadyExists) => Err(CommandError::name_conflict(
+                        &name,
+                        format!("There is already a campaign named \"{}\".", name),
+                    )),
+                    Err(
+                        RepositoryError::DataStoreFailed
+                        | RepositoryError::MissingName
+                        | RepositoryError::NotFound,
+                    ) => Err(CommandError::storage("Couldn't create the campaign.")),
+                }
+            }
+            Self::Switch { name } => {
+                if repository.current_campaign().name.eq_ci(&name) {
+                    return Err(CommandError::invalid_state(format!(
+                        "**{}** is already the current campaign.",
+                        repository.current_campaign().name,
+                    )));
+                }
+
+                match repository.switch_campaign(&name).await {
+                    Ok(campaign) => Ok(format!("Switched to the **{}** campaign.", campaign.name)),
+                    Err(RepositoryError::NotFound) => Err(CommandError::unknown_entity(
+                        &name,
+                        format!(
+                            "There is no campaign named \"{}\". Use `campaign` to list your campaigns.",
+                            name,
+                        ),
+                    )),
+                    Err(
+                        RepositoryError::DataStoreFailed
+                        | RepositoryError::MissingName
+                        | RepositoryError::NameAlreadyExists,
+                    ) => Err(CommandError::storage("Couldn't switch campaigns.")),
+                }
+            }
+        }
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl ContextAwareParse for CampaignCommand {
+    async fn parse_input(input: &str, _app_meta: &AppMeta) -> CommandMatches<Self> {
+        if input.eq_ci("campaign") || input.eq_ci("campaigns") {
+            CommandMatches::new_canonical(Self::List)
+        } else if let Some(name) = input
+            .strip_prefix_ci("campaign new ")
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+        {
+            CommandMatches::new_canonical(Self::New {
+                name: name.to_string(),
+            })
+        } else if let Some(name) = input
+            .strip_prefix_ci("campaign switch ")
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+        {
+            CommandMatches::new_canonical(Self::Switch {
+                name: name.to_string(),
+            })
+        } else {
+            CommandMatches::default()
+        }
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Autocomplete for CampaignCommand {
+    async fn autocomplete(input: &str, app_meta: &AppMeta) -> Vec<AutocompleteSuggestion> {
+        if input.is_empty() {
+            Vec::new()
+        } else if let Some(name) = input.strip_prefix_ci("campaign switch ") {
+            let repository = &app_meta.repository;
+
+            repository
+                .campaigns()
+                .iter()
+                .filter(|c| c != &repository.current_campaign() && c.name.starts_with_ci(name))
+                .map(|c| {
+                    AutocompleteSuggestion::new(
+                        format!("campaign switch {}", c.name),
+                        "switch campaigns",
+                    )
+                })
+                .collect()
+        } else {
+            [
+                ("campaign", "list campaigns"),
+                ("campaign new [name]", "start a new campaign"),
+                ("campaign switch [name]", "switch campaigns"),
+            ]
+            .into_iter()
+            .filter(|(term, _)| term.starts_with_ci(input))
+            .map(|(term, summary)| AutocompleteSuggestion::new(term, summary))
+            .collect()
+        }
+    }
+}
+
+impl fmt::Display for CampaignCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::List => write!(f, "campaign"),
+            Self::New { name } => write!(f, "campaign new {}", name),
+            Self::Switch { name } => write!(f, "campaign switch {}", name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::assert_autocomplete;
+    use crate::storage::MemoryDataStore;
+    use crate::Event;
+    use tokio_test::block_on;
+
+    #[test]
+    fn parse_input_test() {
+        let app_meta = app_meta();
+
+        assert_eq!(
+            CommandMatches::new_canonical(CampaignCommand::List),
+            block_on(CampaignCommand::parse_input("CAMPAIGNS", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(CampaignCommand::New {
+                name: "Curse of Strahd".to_string(),
+            }),
+            block_on(CampaignCommand::parse_input(
+                "campaign new Curse of Strahd",
+                &app_meta,
+            )),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(CampaignCommand::Switch {
+                name: "default".to_string(),
+            }),
+            block_on(CampaignCommand::parse_input(
+                "Campaign Switch default",
+                &app_meta,
+            )),
+        );
+
+        assert_eq!(
+            CommandMatches::default(),
+            block_on(CampaignCommand::parse_input("campaign new  ", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::default(),
+            block_on(CampaignCommand::parse_input("campaigner", &app_meta)),
+        );
+    }
+
+    #[test]
+    fn autocomplete_test() {
+        let mut app_meta = app_meta();
+
+        assert_autocomplete(
+            &[
+                ("campaign", "list campaigns"),
+                ("campaign new [name]", "start a new campaign"),
+                ("campaign switch [name]", "switch campaigns"),
+            ][..],
+            block_on(CampaignCommand::autocomplete("camp", &app_meta)),
+        );
+
+        assert_autocomplete(
+            &[][..],
+            block_on(CampaignCommand::autocomplete("", &app_meta)),
+        );
+
+        block_on(app_meta.repository.create_campaign("Curse of Strahd")).unwrap();
+        block_on(
+            app_meta
+                .repository
+                .create_campaign("Lost Mine of Phandelver"),
+        )
+        .unwrap();
+
+        assert_autocomplete(
+            &[
+                ("campaign switch Curse of Strahd", "switch campaigns"),
+                ("campaign switch Default", "switch campaigns"),
+            ][..],
+            block_on(CampaignCommand::autocomplete("campaign switch ", &app_meta)),
+        );
+
+        assert_autocomplete(
+            &[("campaign switch Curse of Strahd", "switch campaigns")][..],
+            block_on(CampaignCommand::autocomplete(
+                "CAMPAIGN SWITCH c",
+                &app_meta,
+            )),
+        );
+    }
+
+    #[test]
+    fn display_test() {
+        let app_meta = app_meta();
+
+        [
+            CampaignCommand::List,
+            CampaignCommand::New {
+                name: "Curse of Strahd".to_string(),
+            },
+            CampaignCommand::Switch {
+                name: "Default".to_string(),
+            },
+        ]
+        .into_iter()
+        .for_each(|command| {
+            let command_string = command.to_string();
+            assert_ne!("", command_string);
+            assert_eq!(
+                CommandMatches::new_canonical(command),
+                block_on(CampaignCommand::parse_input(&command_string, &app_meta)),
+                "{}",
+                command_string,
+            );
+        });
+    }
+
+    #[test]
+    fn run_test() {
+        let mut app_meta = app_meta();
+
+        assert_eq!(
+            Ok("Started a new campaign, **Curse of Strahd**. Use `campaign switch Default` to return to the previous one.".to_string()),
+            block_on(
+                CampaignCommand::New {
+                    name: "Curse of Strahd".to_string(),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+
+        assert_eq!(
+            Err(CommandError::name_conflict(
+                "curse of strahd",
+                "There is already a campaign named \"curse of strahd\".",
+            )),
+            block_on(
+                CampaignCommand::New {
+                    name: "curse of strahd".to_string(),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+
+        assert_eq!(
+            Ok("# Campaigns\n\n* `campaign switch Default`\n* **Curse of Strahd** (current)\n\n_Use `campaign new [name]` to start a new campaign._".to_string()),
+            block_on(CampaignCommand::List.run("", &mut app_meta)),
+        );
+
+        assert_eq!(
+            Err(CommandError::invalid_state(
+                "**Curse of Strahd** is already the current campaign."
+            )),
+            block_on(
+                CampaignCommand::Switch {
+                    name: "curse of strahd".to_string(),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+
+        assert_eq!(
+            Err(CommandError::unknown_entity(
+                "Tomb of Annihilation",
+                "There is no campaign named \"Tomb of Annihilation\". Use `campaign` to list your campaigns.",
+            )),
+            block_on(
+                CampaignCommand::Switch {
+                    name: "Tomb of Annihilation".to_string(),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+
+        assert_eq!(
+            Ok("Switched to the **Default** campaign.".to_string()),
+            block_on(
+                CampaignCommand::Switch {
+                    name: "default".to_string(),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+    }
+
+    fn event_dispatcher(_event: Event) {}
+
+    fn app_meta() -> AppMeta {
+        AppMeta::new(MemoryDataStore::default(), &event_dispatcher)
+    }
+}
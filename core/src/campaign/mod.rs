@@ -0,0 +1,107 @@
+pub use command::CampaignCommand;
+
+mod command;
+
+use crate::Uuid;
+use std::fmt;
+use std::str::FromStr;
+
+/// A campaign, whose things, time, and key-values are stored in a namespace of the data store
+/// separate from those of other campaigns.
+///
+/// The list of campaigns is persisted to the default namespace one per line, in the form
+/// `f81d4fae-7dec-11d0-a765-00a0c91e6bf6\tCurse of Strahd`. The default campaign has no UUID,
+/// and holds any data saved before campaigns were introduced.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Campaign {
+    pub name: String,
+    pub uuid: Option<Uuid>,
+}
+
+impl Campaign {
+    /// The name under which pre-existing data is listed once a second campaign is created.
+    pub const DEFAULT_NAME: &'static str = "Default";
+
+    pub fn namespace(&self) -> Option<String> {
+        self.uuid.map(|uuid| uuid.to_string())
+    }
+}
+
+impl Default for Campaign {
+    fn default() -> Self {
+        Self {
+            name: Self::DEFAULT_NAME.to_string(),
+            uuid: None,
+        }
+    }
+}
+
+impl fmt::Display for Campaign {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(uuid) = self.uuid {
+            write!(f, "{}", uuid)?;
+        }
+
+        write!(f, "\t{}", self.name)
+    }
+}
+
+impl FromStr for Campaign {
+    type Err = ();
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        let (uuid, name) = raw.split_once('\t').ok_or(())?;
+
+        if name.is_empty() {
+            return Err(());
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            uuid: if uuid.is_empty() {
+                None
+            } else {
+                Some(uuid.parse().map_err(|_| ())?)
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn display_from_str_test() {
+        let campaign = Campaign {
+            name: "Curse of Strahd".to_string(),
+            uuid: Some(Uuid::from_u128(1)),
+        };
+
+        assert_eq!(
+            "00000000-0000-0000-0000-000000000001\tCurse of Strahd",
+            campaign.to_string(),
+        );
+        assert_eq!(Ok(campaign.clone()), campaign.to_string().parse());
+
+        assert_eq!("\tDefault", Campaign::default().to_string());
+        assert_eq!(Ok(Campaign::default()), "\tDefault".parse());
+
+        assert_eq!(Err(()), "Curse of Strahd".parse::<Campaign>());
+        assert_eq!(Err(()), "potato\tCurse of Strahd".parse::<Campaign>());
+        assert_eq!(Err(()), "\t".parse::<Campaign>());
+    }
+
+    #[test]
+    fn namespace_test() {
+        assert_eq!(None, Campaign::default().namespace());
+        assert_eq!(
+            Some("00000000-0000-0000-0000-000000000001".to_string()),
+            Campaign {
+                name: "Curse of Strahd".to_string(),
+                uuid: Some(Uuid::from_u128(1)),
+            }
+            .namespace(),
+        );
+    }
+}
@@ -0,0 +1,591 @@
+use super::{Clue, Secret, Secrets, CLUES_PER_SECRET};
+use crate::app::{
+    AppMeta, Autocomplete, AutocompleteSuggestion, CommandError, CommandMatches, ContextAwareParse,
+    Runnable,
+};
+use crate::storage::{Change, KeyValue};
+use crate::utils::{split_at_last_word, CaseInsensitiveStr};
+use crate::Uuid;
+use async_trait::async_trait;
+use std::fmt;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ClueCommand {
+    AddClue {
+        secret: String,
+        text: String,
+        from: Option<String>,
+    },
+    AddSecret {
+        text: String,
+    },
+    Deliver {
+        secret: String,
+        clue: usize,
+    },
+    Secret {
+        secret: String,
+    },
+    Secrets,
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Runnable for ClueCommand {
+    async fn run(self, _input: &str, app_meta: &mut AppMeta) -> Result<String, CommandError> {
+        let mut secrets = Secrets::load(app_meta).await;
+
+        let response = match self {
+            Self::Secrets => return Ok(report(&secrets)),
+            Self::Secret { secret } => {
+                let secret = secrets.get(&secret).ok_or_else(|| not_found(&secret))?;
+                return Ok(show(secret, app_meta).await);
+            }
+            Self::AddSecret { text } => {
+                if let Some(existing) = secrets.iter().find(|secret| secret.text.eq_ci(&text)) {
+                    return Err(CommandError::name_conflict(
+                        &existing.text,
+                        format!("There is already a secret called \"{}\".", existing.text),
+                    ));
+                }
+
+                let response = format!(
+                    "Added the secret **{}**. Use `clue {}: [clue]` to give the players a way to find it out, or `undo` to reverse this.",
+                    text, text,
+                );
+                secrets.push(Secret::new(text));
+                response
+            }
+            Self::AddClue { secret, text, from } => {
+                // "Garlic missing from the market" is only a clue from someone if there is
+                // something called "the market" to be a clue from.
+                let (text, from) = match from {
+                    Some(name) => match app_meta.repository.get_by_name(&name).await {
+                        Ok(thing) => (
+                            text,
+                            Some(*thing.uuid().ok_or_else(|| {
+                                format!(
+                                    "{} hasn't been saved to your `journal` yet. Use `save {}` first.",
+                                    thing.name(),
+                                    thing.name(),
+                                )
+                            })?),
+                        ),
+                        Err(_) => (format!("{} from {}", text, name), None),
+                    },
+                    None => (text, None),
+                };
+
+                let secret = secrets.get_mut(&secret).ok_or_else(|| not_found(&secret))?;
+                secret.clues.push(Clue {
+                    text,
+                    from,
+                    delivered: false,
+                });
+
+                let mut response =
+                    format!("Added clue {} to **{}**.", secret.clues.len(), secret.text,);
+
+                match secret.clues_needed() {
+                    0 => {}
+                    1 => response.push_str(
+                        " Add at least one more to give the players a fair chance of finding it out.",
+                    ),
+                    n => response.push_str(&format!(
+                        " Add at least {} more to give the players a fair chance of finding it out.",
+                        n,
+                    )),
+                }
+
+                response.push_str(" Use `undo` to reverse this.");
+                response
+            }
+            Self::Deliver { secret, clue } => {
+                let secret = secrets.get_mut(&secret).ok_or_else(|| not_found(&secret))?;
+
+                let count = secret.clues.len();
+                let delivered = &mut secret
+                    .clues
+                    .get_mut(clue.wrapping_sub(1))
+                    .ok_or_else(|| {
+                        CommandError::unknown_entity(
+                            &clue.to_string(),
+                            format!("There is no clue {} of **{}**.", clue, secret.text),
+                        )
+                    })?
+                    .delivered;
+
+                if *delivered {
+                    return Err(CommandError::invalid_state(format!(
+                        "Clue {} of **{}** has already been delivered.",
+                        clue, secret.text,
+                    )));
+                }
+
+                *delivered = true;
+
+                format!(
+                    "Clue {} of **{}** has been delivered, for {} of {} so far. Use `undo` to reverse this.",
+                    clue,
+                    secret.text,
+                    secret.delivered(),
+                    count,
+                )
+            }
+        };
+
+        app_meta
+            .repository
+            .modify(Change::SetKeyValue {
+                key_value: KeyValue::Secrets(Some(secrets)),
+            })
+            .await
+            .map(|_| response)
+            .map_err(|_| CommandError::storage("Unable to save the secrets."))
+    }
+}
+
+/// The secrets that haven't reached the players yet, followed by the rest, with a reminder for
+/// any that have fewer clues than the three clue rule calls for.
+fn report(secrets: &Secrets) -> String {
+    if secrets.is_empty() {
+        return "# Secrets\n\n*There are no secrets yet. Use `secret add [secret]` to add one.*"
+            .to_string();
+    }
+
+    let line = |secret: &Secret| {
+        let mut line = format!(
+            "\n* **{}**: {} of {} {} delivered",
+            secret.text,
+            secret.delivered(),
+            secret.clues.len(),
+            if secret.clues.len() == 1 {
+                "clue"
+            } else {
+                "clues"
+            },
+        );
+
+        if secret.clues_needed() > 0 {
+            line.push_str(&format!(
+                " _(needs {} more to make {})_",
+                secret.clues_needed(),
+                CLUES_PER_SECRET,
+            ));
+        }
+
+        line
+    };
+
+    let mut output = "# Secrets".to_string();
+
+    let (hidden, hinted): (Vec<&Secret>, Vec<&Secret>) =
+        secrets.iter().partition(|secret| secret.delivered() == 0);
+
+    if !hidden.is_empty() {
+        output.push_str("\n\n## Not yet found out\n");
+        hidden
+            .into_iter()
+            .for_each(|secret| output.push_str(&line(secret)));
+    }
+
+    if !hinted.is_empty() {
+        output.push_str("\n\n## Clues delivered\n");
+        hinted
+            .into_iter()
+            .for_each(|secret| output.push_str(&line(secret)));
+    }
+
+    output
+}
+
+/// A secret with its clues, numbered so that they can be marked as delivered.
+async fn show(secret: &Secret, app_meta: &AppMeta) -> String {
+    let mut output = format!("# {}\n", secret.text);
+
+    if secret.clues.is_empty() {
+        output.push_str(&format!(
+            "\n*There are no clues to this secret yet. Use `clue {}: [clue]` to add one.*",
+            secret.text,
+        ));
+        return output;
+    }
+
+    for (i, clue) in secret.clues.iter().enumerate() {
+        output.push_str(&format!("\n{}. {}", i + 1, clue.text));
+
+        if let Some(name) = name_of(clue.from.as_ref(), app_meta).await {
+            output.push_str(&format!(" (from `{}`)", name));
+        }
+
+        if clue.delivered {
+            output.push_str(" _(delivered)_");
+        }
+    }
+
+    if let Some(clue) = secret.clues.iter().position(|clue| !clue.delivered) {
+        output.push_str(&format!(
+            "\n\n_Use `deliver clue {} of {}` once the players have found a clue._",
+            clue + 1,
+            secret.text,
+        ));
+    }
+
+    output
+}
+
+async fn name_of(uuid: Option<&Uuid>, app_meta: &AppMeta) -> Option<String> {
+    app_meta
+        .repository
+        .get_by_uuid(uuid?)
+        .await
+        .ok()
+        .map(|thing| thing.name().to_string())
+}
+
+fn not_found(secret: &str) -> CommandError {
+    CommandError::unknown_entity(
+        secret,
+        format!(
+            "There is no secret called \"{}\". Use `secrets` to list your secrets.",
+            secret,
+        ),
+    )
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl ContextAwareParse for ClueCommand {
+    async fn parse_input(input: &str, _app_meta: &AppMeta) -> CommandMatches<Self> {
+        let rest = |prefix: &str| {
+            input
+                .strip_prefix_ci(prefix)
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+        };
+
+        if input.eq_ci("secrets") {
+            CommandMatches::new_canonical(Self::Secrets)
+        } else if let Some(text) = rest("secret add ") {
+            CommandMatches::new_canonical(Self::AddSecret {
+                text: text.to_string(),
+            })
+        } else if let Some(secret) = rest("secret ") {
+            CommandMatches::new_canonical(Self::Secret {
+                secret: secret.to_string(),
+            })
+        } else if let Some((secret, clue)) = rest("clue ").and_then(|s| s.split_once(':')) {
+            let (text, from) = match split_at_last_word(clue, "from") {
+                Some((text, from)) => (text, Some(from.to_string())),
+                None => (clue.trim(), None),
+            };
+
+            if secret.trim().is_empty() || text.is_empty() {
+                CommandMatches::default()
+            } else {
+                CommandMatches::new_canonical(Self::AddClue {
+                    secret: secret.trim().to_string(),
+                    text: text.to_string(),
+                    from,
+                })
+            }
+        } else if let Some((clue, secret)) = rest("deliver clue ")
+            .and_then(|s| s.split_once(' '))
+            .filter(|(_, rest)| rest.starts_with_ci("of "))
+            .and_then(|(clue, rest)| Some((clue.parse().ok()?, &rest["of ".len()..])))
+            .filter(|(clue, secret)| *clue > 0 && !secret.trim().is_empty())
+        {
+            CommandMatches::new_canonical(Self::Deliver {
+                secret: secret.trim().to_string(),
+                clue,
+            })
+        } else {
+            CommandMatches::default()
+        }
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Autocomplete for ClueCommand {
+    async fn autocomplete(input: &str, app_meta: &AppMeta) -> Vec<AutocompleteSuggestion> {
+        if input.is_empty() {
+            return Vec::new();
+        }
+
+        let mut suggestions: Vec<AutocompleteSuggestion> = [
+            ("secrets", "list the secrets yet to be found out"),
+            ("secret [secret]", "show the clues to a secret"),
+            ("secret add [secret]", "add a secret"),
+            ("clue [secret]: [clue]", "add a clue to a secret"),
+            (
+                "clue [secret]: [clue] from [name]",
+                "add a clue found with a character or place",
+            ),
+            ("deliver clue [number] of [secret]", "mark a clue as found"),
+        ]
+        .into_iter()
+        .filter(|(term, _)| term.starts_with_ci(input))
+        .map(|(term, summary)| AutocompleteSuggestion::new(term, summary))
+        .collect();
+
+        for (prefix, suffix, summary) in [
+            ("secret ", "", "show the clues to the secret"),
+            ("clue ", ": [clue]", "add a clue to the secret"),
+        ] {
+            if let Some(partial) = input.strip_prefix_ci(prefix) {
+                Secrets::load(app_meta)
+                    .await
+                    .iter()
+                    .filter(|secret| secret.text.starts_with_ci(partial))
+                    .for_each(|secret| {
+                        suggestions.push(AutocompleteSuggestion::new(
+                            format!("{}{}{}", prefix, secret.text, suffix),
+                            summary,
+                        ))
+                    });
+                break;
+            }
+        }
+
+        suggestions
+    }
+}
+
+impl fmt::Display for ClueCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::AddClue {
+                secret,
+                text,
+                from: Some(from),
+            } => write!(f, "clue {}: {} from {}", secret, text, from),
+            Self::AddClue {
+                secret,
+                text,
+                from: None,
+            } => write!(f, "clue {}: {}", secret, text),
+            Self::AddSecret { text } => write!(f, "secret add {}", text),
+            Self::Deliver { secret, clue } => write!(f, "deliver clue {} of {}", clue, secret),
+            Self::Secret { secret } => write!(f, "secret {}", secret),
+            Self::Secrets => write!(f, "secrets"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::assert_autocomplete;
+    use crate::storage::MemoryDataStore;
+    use crate::world::Npc;
+    use crate::Event;
+    use tokio_test::block_on;
+
+    #[test]
+    fn parse_input_test() {
+        let app_meta = app_meta();
+
+        assert_eq!(
+            CommandMatches::new_canonical(ClueCommand::AddClue {
+                secret: "the mayor".to_string(),
+                text: "A letter from the Count".to_string(),
+                from: Some("Captain Vell".to_string()),
+            }),
+            block_on(ClueCommand::parse_input(
+                "CLUE the mayor: A letter from the Count from Captain Vell",
+                &app_meta,
+            )),
+        );
+        assert_eq!(
+            CommandMatches::new_canonical(ClueCommand::Deliver {
+                secret: "the mayor".to_string(),
+                clue: 2,
+            }),
+            block_on(ClueCommand::parse_input(
+                "deliver clue 2 of the mayor",
+                &app_meta
+            )),
+        );
+        assert_eq!(
+            CommandMatches::new_canonical(ClueCommand::Secret {
+                secret: "the mayor".to_string(),
+            }),
+            block_on(ClueCommand::parse_input("secret the mayor", &app_meta)),
+        );
+
+        [
+            "secret",
+            "clue the mayor",
+            "clue : bodies",
+            "clue the mayor: ",
+            "deliver clue 0 of the mayor",
+            "deliver clue two of the mayor",
+            "deliver clue 2",
+        ]
+        .into_iter()
+        .for_each(|input| {
+            assert_eq!(
+                CommandMatches::default(),
+                block_on(ClueCommand::parse_input(input, &app_meta)),
+                "{}",
+                input,
+            );
+        });
+    }
+
+    #[test]
+    fn autocomplete_test() {
+        let mut app_meta = app_meta();
+        block_on(
+            ClueCommand::AddSecret {
+                text: "The mayor is a vampire".to_string(),
+            }
+            .run("", &mut app_meta),
+        )
+        .unwrap();
+
+        assert_autocomplete(
+            &[
+                ("secrets", "list the secrets yet to be found out"),
+                ("secret [secret]", "show the clues to a secret"),
+                ("secret add [secret]", "add a secret"),
+            ][..],
+            block_on(ClueCommand::autocomplete("SEC", &app_meta)),
+        );
+
+        assert_autocomplete(
+            &[(
+                "clue The mayor is a vampire: [clue]",
+                "add a clue to the secret",
+            )][..],
+            block_on(ClueCommand::autocomplete("clue the m", &app_meta)),
+        );
+    }
+
+    #[test]
+    fn display_test() {
+        let app_meta = app_meta();
+
+        [
+            ClueCommand::AddClue {
+                secret: "The mayor".to_string(),
+                text: "Bloodless bodies in the crypt".to_string(),
+                from: None,
+            },
+            ClueCommand::AddClue {
+                secret: "The mayor".to_string(),
+                text: "A letter".to_string(),
+                from: Some("Captain Vell".to_string()),
+            },
+            ClueCommand::AddSecret {
+                text: "The mayor is a vampire".to_string(),
+            },
+            ClueCommand::Deliver {
+                secret: "The mayor".to_string(),
+                clue: 3,
+            },
+            ClueCommand::Secret {
+                secret: "The mayor".to_string(),
+            },
+            ClueCommand::Secrets,
+        ]
+        .into_iter()
+        .for_each(|command| {
+            let command_string = command.to_string();
+            assert_ne!("", command_string);
+            assert_eq!(
+                CommandMatches::new_canonical(command),
+                block_on(ClueCommand::parse_input(&command_string, &app_meta)),
+                "{}",
+                command_string,
+            );
+        });
+    }
+
+    #[test]
+    fn run_test() {
+        let mut app_meta = app_meta();
+
+        assert_eq!(
+            Ok(
+                "# Secrets\n\n*There are no secrets yet. Use `secret add [secret]` to add one.*"
+                    .to_string()
+            ),
+            block_on(ClueCommand::Secrets.run("", &mut app_meta)),
+        );
+
+        block_on(
+            app_meta.repository.modify(Change::CreateAndSave {
+                thing: Npc {
+                    name: "Captain Vell".into(),
+                    ..Default::default()
+                }
+                .into(),
+            }),
+        )
+        .unwrap();
+
+        block_on(run("secret add The mayor is a vampire", &mut app_meta)).unwrap();
+        block_on(run("secret add The baron's heir is missing", &mut app_meta)).unwrap();
+
+        assert_eq!(
+            Ok("Added clue 1 to **The mayor is a vampire**. Add at least 2 more to give the players a fair chance of finding it out. Use `undo` to reverse this.".to_string()),
+            block_on(run(
+                "clue the mayor: Bloodless bodies in the crypt",
+                &mut app_meta,
+            )),
+        );
+
+        assert_eq!(
+            Ok("Added clue 2 to **The mayor is a vampire**. Add at least one more to give the players a fair chance of finding it out. Use `undo` to reverse this.".to_string()),
+            block_on(run(
+                "clue the mayor: A letter to the Count from Captain Vell",
+                &mut app_meta,
+            )),
+        );
+
+        assert_eq!(
+            Ok("Clue 2 of **The mayor is a vampire** has been delivered, for 1 of 2 so far. Use `undo` to reverse this.".to_string()),
+            block_on(run("deliver clue 2 of the mayor", &mut app_meta)),
+        );
+        assert_eq!(
+            Err(CommandError::invalid_state(
+                "Clue 2 of **The mayor is a vampire** has already been delivered."
+            )),
+            block_on(run("deliver clue 2 of the mayor", &mut app_meta)),
+        );
+        assert_eq!(
+            Err(CommandError::unknown_entity(
+                "3",
+                "There is no clue 3 of **The mayor is a vampire**."
+            )),
+            block_on(run("deliver clue 3 of the mayor", &mut app_meta)),
+        );
+
+        assert_eq!(
+            Ok("# The mayor is a vampire\n\n1. Bloodless bodies in the crypt\n2. A letter to the Count (from `Captain Vell`) _(delivered)_\n\n_Use `deliver clue 1 of The mayor is a vampire` once the players have found a clue._".to_string()),
+            block_on(run("secret the mayor", &mut app_meta)),
+        );
+
+        assert_eq!(
+            Ok("# Secrets\n\n## Not yet found out\n\n* **The baron's heir is missing**: 0 of 0 clues delivered _(needs 3 more to make 3)_\n\n## Clues delivered\n\n* **The mayor is a vampire**: 1 of 2 clues delivered _(needs 1 more to make 3)_".to_string()),
+            block_on(run("secrets", &mut app_meta)),
+        );
+    }
+
+    async fn run(input: &str, app_meta: &mut AppMeta) -> Result<String, CommandError> {
+        ClueCommand::parse_input(input, app_meta)
+            .await
+            .take_best_match()
+            .unwrap()
+            .run(input, app_meta)
+            .await
+    }
+
+    fn event_dispatcher(_event: Event) {}
+
+    fn app_meta() -> AppMeta {
+        AppMeta::new(MemoryDataStore::default(), &event_dispatcher)
+    }
+}
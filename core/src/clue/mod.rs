@@ -0,0 +1,240 @@
+pub use command::ClueCommand;
+
+mod command;
+
+use crate::app::AppMeta;
+use crate::storage::KeyValue;
+use crate::utils::CaseInsensitiveStr;
+use crate::Uuid;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// The number of clues a secret should have for the players to have a fair chance at finding it,
+/// following the three clue rule: they'll miss the first, ignore the second, and misinterpret the
+/// third.
+pub const CLUES_PER_SECRET: usize = 3;
+
+/// The secrets of a campaign, in the order they were added.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct Secrets(Vec<Secret>);
+
+/// Something the players don't know yet, and the clues that might lead them to it.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Secret {
+    pub text: String,
+
+    #[serde(default)]
+    pub clues: Vec<Clue>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Clue {
+    pub text: String,
+
+    /// The character or place where the clue can be found.
+    #[serde(default)]
+    pub from: Option<Uuid>,
+
+    #[serde(default)]
+    pub delivered: bool,
+}
+
+impl Secrets {
+    /// Load the campaign's secrets from storage, or none if none have been added yet or the data
+    /// store is unavailable.
+    pub async fn load(app_meta: &AppMeta) -> Self {
+        app_meta
+            .repository
+            .get_key_value(&KeyValue::Secrets(None))
+            .await
+            .ok()
+            .and_then(KeyValue::secrets)
+            .unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Secret> {
+        self.0.iter()
+    }
+
+    pub fn push(&mut self, secret: Secret) {
+        self.0.push(secret);
+    }
+
+    /// Find a secret by its text, or by the start of its text if that is enough to tell it apart
+    /// from the others.
+    pub fn get(&self, text: &str) -> Option<&Secret> {
+        self.position(text).map(|i| &self.0[i])
+    }
+
+    pub fn get_mut(&mut self, text: &str) -> Option<&mut Secret> {
+        self.position(text).map(|i| &mut self.0[i])
+    }
+
+    /// The clues that can be found with the journal entry with the given UUID, along with the
+    /// secrets they lead to.
+    pub fn clues_from<'a>(
+        &'a self,
+        uuid: &'a Uuid,
+    ) -> impl Iterator<Item = (&'a Secret, &'a Clue)> {
+        self.0.iter().flat_map(move |secret| {
+            secret
+                .clues
+                .iter()
+                .filter(move |clue| clue.from.as_ref() == Some(uuid))
+                .map(move |clue| (secret, clue))
+        })
+    }
+
+    fn position(&self, text: &str) -> Option<usize> {
+        if let Some(i) = self.0.iter().position(|secret| secret.text.eq_ci(text)) {
+            return Some(i);
+        }
+
+        let mut matches = self
+            .0
+            .iter()
+            .enumerate()
+            .filter(|(_, secret)| secret.text.starts_with_ci(text))
+            .map(|(i, _)| i);
+
+        match (matches.next(), matches.next()) {
+            (Some(i), None) => Some(i),
+            _ => None,
+        }
+    }
+}
+
+impl Secret {
+    pub fn new(text: String) -> Self {
+        Self {
+            text,
+            clues: Vec::new(),
+        }
+    }
+
+    pub fn delivered(&self) -> usize {
+        self.clues.iter().filter(|clue| clue.delivered).count()
+    }
+
+    /// The number of clues still to be added to meet the three clue rule.
+    pub fn clues_needed(&self) -> usize {
+        CLUES_PER_SECRET.saturating_sub(self.clues.len())
+    }
+}
+
+impl fmt::Display for Secrets {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            serde_json::to_string(self).map_err(|_| fmt::Error)?,
+        )
+    }
+}
+
+impl FromStr for Secrets {
+    type Err = ();
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(raw).map_err(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn get_test() {
+        let mut secrets = secrets();
+
+        assert_eq!(
+            Some("The mayor is a vampire"),
+            secrets.get("the MAYOR").map(|s| s.text.as_str()),
+        );
+        assert_eq!(None, secrets.get("the"));
+        assert_eq!(None, secrets.get("the dragon"));
+
+        secrets.get_mut("the baron").unwrap().clues.push(clue(None));
+        assert_eq!(1, secrets.get("the baron").unwrap().clues.len());
+    }
+
+    #[test]
+    fn delivered_test() {
+        let mut secret = Secret::new("The mayor is a vampire".to_string());
+        assert_eq!((0, 3), (secret.delivered(), secret.clues_needed()));
+
+        secret.clues.push(clue(None));
+        secret.clues.push(Clue {
+            delivered: true,
+            ..clue(None)
+        });
+        assert_eq!((1, 1), (secret.delivered(), secret.clues_needed()));
+
+        secret.clues.push(clue(None));
+        secret.clues.push(clue(None));
+        assert_eq!((1, 0), (secret.delivered(), secret.clues_needed()));
+    }
+
+    #[test]
+    fn clues_from_test() {
+        let gimli = Uuid::new_v4();
+        let mut secrets = secrets();
+        secrets.0[0].clues.push(clue(None));
+        secrets.0[0].clues.push(clue(Some(gimli)));
+        secrets.0[1].clues.push(clue(Some(gimli)));
+
+        assert_eq!(
+            vec!["The mayor is a vampire", "The baron's heir is missing"],
+            secrets
+                .clues_from(&gimli)
+                .map(|(secret, _)| secret.text.as_str())
+                .collect::<Vec<_>>(),
+        );
+        assert_eq!(0, secrets.clues_from(&Uuid::nil()).count());
+    }
+
+    #[test]
+    fn serialize_deserialize_test() {
+        let mut secrets = Secrets::default();
+        assert!(secrets.is_empty());
+
+        secrets.push(Secret {
+            text: "The mayor is a vampire".to_string(),
+            clues: vec![clue(Some(Uuid::nil()))],
+        });
+
+        assert_eq!(
+            r#"[{"text":"The mayor is a vampire","clues":[{"text":"Bloodless bodies in the crypt","from":"00000000-0000-0000-0000-000000000000","delivered":false}]}]"#,
+            secrets.to_string(),
+        );
+        assert_eq!(Ok(secrets.clone()), secrets.to_string().parse());
+        assert_eq!(
+            Ok(Secret::new("The mayor is a vampire".to_string())),
+            r#"[{"text":"The mayor is a vampire"}]"#
+                .parse::<Secrets>()
+                .map(|secrets| secrets.0[0].clone()),
+        );
+    }
+
+    fn clue(from: Option<Uuid>) -> Clue {
+        Clue {
+            text: "Bloodless bodies in the crypt".to_string(),
+            from,
+            delivered: false,
+        }
+    }
+
+    fn secrets() -> Secrets {
+        let mut secrets = Secrets::default();
+        secrets.push(Secret::new("The mayor is a vampire".to_string()));
+        secrets.push(Secret::new("The baron's heir is missing".to_string()));
+        secrets
+    }
+}
@@ -0,0 +1,294 @@
+use crate::storage::JournalQuery;
+use crate::utils::{CaseInsensitiveStr, CodeView, EscapedView};
+use crate::world::place::PlaceType;
+use crate::world::{render, Npc, Place, Thing};
+use std::fmt;
+use std::str::FromStr;
+
+/// The placeholders understood by [`render`] other than the fields of a thing.
+const GRAMMAR_PLACEHOLDERS: &[&str] = &[
+    "a", "they", "them", "their", "theirs", "themself", "theyre", "theyve",
+];
+
+/// The user's templates for summarizing things in one line, set with
+/// `config template npc = "{name} — {age} {species}, {occupation}"`. Templates are persisted to
+/// the key-value store one per line, in the form `npc = {name} — {age} {species}, {occupation}`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CardTemplates(Vec<CardTemplate>);
+
+/// A template for the summaries of one type of thing: `npc`, `pc`, `place`, or a kind of place
+/// such as `inn`. Placeholders in braces are replaced with the thing's fields as shown by
+/// `journal where`, along with the pronouns and articles understood by [`render`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CardTemplate {
+    pub kind: String,
+    pub template: String,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CardTemplateError {
+    UnknownKind(String),
+    UnknownField(String),
+}
+
+impl CardTemplates {
+    pub fn get(&self, kind: &str) -> Option<&CardTemplate> {
+        self.0.iter().find(|template| template.kind.eq_ci(kind))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &CardTemplate> {
+        self.0.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Add a template, replacing any existing template for the same type.
+    pub fn insert(&mut self, template: CardTemplate) {
+        if let Some(existing) = self.0.iter_mut().find(|t| t.kind.eq_ci(&template.kind)) {
+            *existing = template;
+        } else {
+            self.0.push(template);
+        }
+    }
+
+    pub fn remove(&mut self, kind: &str) -> Option<CardTemplate> {
+        let index = self
+            .0
+            .iter()
+            .position(|template| template.kind.eq_ci(kind))?;
+        Some(self.0.remove(index))
+    }
+
+    /// Summarize a thing using the most specific template that applies to it, if any. Player
+    /// characters fall back on the `npc` template, and kinds of place on the `place` template.
+    pub fn render(&self, thing: &Thing) -> Option<String> {
+        let kind = JournalQuery::display_field(thing, "type").unwrap_or_default();
+        let fallback = match thing {
+            Thing::Npc(_) => "npc",
+            Thing::Place(_) => "place",
+        };
+
+        let template = self.get(&kind).or_else(|| self.get(fallback))?;
+        let values: Vec<(&str, String)> = fields()
+            .map(|field| {
+                let value = if field == "name" {
+                    thing
+                        .name()
+                        .value()
+                        .map(|name| CodeView::new(name).to_string())
+                } else {
+                    JournalQuery::display_field(thing, field)
+                        .map(|value| EscapedView::new(&value).to_string())
+                };
+
+                (field, value.unwrap_or_default())
+            })
+            .collect();
+        let vars: Vec<(&str, &str)> = values
+            .iter()
+            .map(|(field, value)| (*field, value.as_str()))
+            .collect();
+
+        let summary = render(&template.template, &thing.gender(), &vars);
+        Some(summary.split_whitespace().collect::<Vec<_>>().join(" "))
+    }
+}
+
+impl CardTemplate {
+    pub fn new(kind: &str, template: &str) -> Result<Self, CardTemplateError> {
+        let kind = kind.trim().to_lowercase();
+        let template = template.trim();
+        let template = template
+            .strip_prefix('"')
+            .and_then(|t| t.strip_suffix('"'))
+            .unwrap_or(template);
+
+        if !["npc", "pc", "place"].contains(&kind.as_str()) && kind.parse::<PlaceType>().is_err() {
+            return Err(CardTemplateError::UnknownKind(kind));
+        }
+
+        if let Some(placeholder) = placeholders(template).find(|placeholder| {
+            let placeholder = placeholder.to_lowercase();
+            !placeholder.contains('|')
+                && !GRAMMAR_PLACEHOLDERS.contains(&placeholder.as_str())
+                && !fields().any(|field| field == placeholder)
+        }) {
+            return Err(CardTemplateError::UnknownField(placeholder.to_string()));
+        }
+
+        Ok(Self {
+            kind,
+            template: template.replace('\n', " "),
+        })
+    }
+}
+
+/// The fields that can appear in a template: `type` and anything that can be set on a character
+/// or place.
+fn fields() -> impl Iterator<Item = &'static str> {
+    std::iter::once("type")
+        .chain(Npc::lockable_fields().iter().copied())
+        .chain(
+            Place::lockable_fields()
+                .iter()
+                .copied()
+                .filter(|field| !Npc::lockable_fields().contains(field)),
+        )
+}
+
+fn placeholders(template: &str) -> impl Iterator<Item = &str> {
+    template
+        .split('{')
+        .skip(1)
+        .filter_map(|part| part.split_once('}').map(|(placeholder, _)| placeholder))
+}
+
+impl fmt::Display for CardTemplates {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut templates = self.0.iter();
+
+        if let Some(template) = templates.next() {
+            write!(f, "{}", template)?;
+        }
+
+        templates.try_for_each(|template| write!(f, "\n{}", template))
+    }
+}
+
+impl FromStr for CardTemplates {
+    type Err = ();
+
+    /// Lines that can't be parsed are skipped rather than invalidating every template.
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        Ok(Self(
+            raw.lines().filter_map(|line| line.parse().ok()).collect(),
+        ))
+    }
+}
+
+impl fmt::Display for CardTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} = {}", self.kind, self.template)
+    }
+}
+
+impl FromStr for CardTemplate {
+    type Err = CardTemplateError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        let (kind, template) = raw
+            .split_once('=')
+            .ok_or_else(|| CardTemplateError::UnknownKind(raw.trim().to_string()))?;
+        Self::new(kind, template)
+    }
+}
+
+impl fmt::Display for CardTemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownKind(kind) => write!(
+                f,
+                "\"{}\" isn't a type of thing. Templates can be set for `npc`, `pc`, `place`, or a kind of place such as `inn`.",
+                kind,
+            ),
+            Self::UnknownField(field) => write!(
+                f,
+                "\"{}\" isn't a field that can be shown in a summary.",
+                field,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::world::npc::{Age, Gender, Species};
+
+    #[test]
+    fn card_template_from_str_test() {
+        let template: CardTemplate = "NPC = \"{name} — {age} {species}\"".parse().unwrap();
+        assert_eq!("npc", template.kind);
+        assert_eq!("{name} — {age} {species}", template.template);
+        assert_eq!("npc = {name} — {age} {species}", template.to_string());
+
+        assert!("inn = {name}, {They} {is|are} {a} {type}"
+            .parse::<CardTemplate>()
+            .is_ok());
+
+        assert_eq!(
+            Err(CardTemplateError::UnknownKind("potato".to_string())),
+            "potato = {name}".parse::<CardTemplate>(),
+        );
+        assert_eq!(
+            Err(CardTemplateError::UnknownField("mood".to_string())),
+            "npc = {name} ({mood})".parse::<CardTemplate>(),
+        );
+    }
+
+    #[test]
+    fn card_templates_round_trip_test() {
+        let mut templates = CardTemplates::default();
+        templates.insert("npc = {name}".parse().unwrap());
+        templates.insert("inn = {name}, {a} {type}".parse().unwrap());
+        templates.insert("NPC = {name} ({age})".parse().unwrap());
+
+        assert_eq!(
+            "npc = {name} ({age})\ninn = {name}, {a} {type}",
+            templates.to_string()
+        );
+        assert_eq!(Ok(templates.clone()), templates.to_string().parse());
+
+        assert_eq!(
+            Some("inn".to_string()),
+            templates.remove("Inn").map(|template| template.kind),
+        );
+        assert_eq!(None, templates.remove("inn"));
+    }
+
+    #[test]
+    fn render_test() {
+        let mut templates = CardTemplates::default();
+        let npc: Thing = Npc {
+            name: "Gottfried".into(),
+            age: Age::Adult.into(),
+            age_years: 40.into(),
+            gender: Gender::Masculine.into(),
+            species: Species::Dwarf.into(),
+            ..Default::default()
+        }
+        .into();
+        let inn: Thing = Place {
+            name: "The Prancing Pony".into(),
+            subtype: "inn".parse::<PlaceType>().unwrap().into(),
+            ..Default::default()
+        }
+        .into();
+
+        assert_eq!(None, templates.render(&npc));
+
+        templates.insert(
+            "npc = {name} — {age} {species}, {occupation}. {They} {is|are} {a} {demeanor} sort."
+                .parse()
+                .unwrap(),
+        );
+        templates.insert("place = {name} ({type})".parse().unwrap());
+
+        assert_eq!(
+            Some("`Gottfried` — 40 years dwarf, . He is a sort.".to_string()),
+            templates.render(&npc),
+        );
+        assert_eq!(
+            Some("`The Prancing Pony` (inn)".to_string()),
+            templates.render(&inn),
+        );
+
+        templates.insert("inn = {name}, {a} {type}".parse().unwrap());
+        assert_eq!(
+            Some("`The Prancing Pony`, an inn".to_string()),
+            templates.render(&inn),
+        );
+    }
+}
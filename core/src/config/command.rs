@@ -0,0 +1,472 @@
+use super::{CardTemplate, Config, Setting, When};
+use crate::app::{
+    AppMeta, Autocomplete, AutocompleteSuggestion, CommandError, CommandMatches, ContextAwareParse,
+    Runnable,
+};
+use crate::storage::{Change, KeyValue};
+use crate::time::ClockView;
+use crate::utils::CaseInsensitiveStr;
+use async_trait::async_trait;
+use std::fmt;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ConfigCommand {
+    RemoveTemplate { kind: String },
+    Set { setting: Setting },
+    SetTemplate { kind: String, template: String },
+    Show,
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Runnable for ConfigCommand {
+    async fn run(self, _input: &str, app_meta: &mut AppMeta) -> Result<String, CommandError> {
+        let mut config = Config::load(app_meta).await;
+
+        match self {
+            Self::Show => {
+                let mut output = format!(
+                    "# Configuration\n\n**Detail:** {}\\\n**Emoji:** {}\\\n**Icons:** {}\\\n**Date format:** {}\\\n**Show times:** {}\\\n**Suggestions:** {}\\\n**Days per year:** {}\\\n**Dawn:** {}\\\n**Dusk:** {}\\\n**Party Charisma:** {:+}\\\n**Save history:** {}\\\n**Page size:** {}\\\n**Autosave recent:** {}\\\n**Language:** {}\n\n_Use `config [setting] [value]` to change a setting, eg. `config emoji off`._",
+                    config.detail,
+                    if config.emoji { "on" } else { "off" },
+                    config.icons,
+                    config.date_format,
+                    match config.when {
+                        When::Game => "in the game",
+                        When::Real => "in the real world",
+                        When::Both => "in the game and the real world",
+                    },
+                    config.suggestions,
+                    config.year,
+                    ClockView::new(config.dawn, 0),
+                    ClockView::new(config.dusk, 0),
+                    config.charisma,
+                    if config.history { "on" } else { "off" },
+                    config
+                        .page_size
+                        .map_or_else(|| "off".to_string(), |n| format!("{} lines", n)),
+                    if config.autosave { "on" } else { "off" },
+                    config.language.get_name(),
+                );
+
+                if !config.cards.is_empty() {
+                    output.push_str("\n\n## Summary templates\n");
+
+                    for (i, card) in config.cards.iter().enumerate() {
+                        output.push_str(&format!(
+                            "{}**{}:** {}",
+                            if i == 0 { "\n" } else { "\\\n" },
+                            card.kind,
+                            escape_braces(&card.template),
+                        ));
+                    }
+                }
+
+                Ok(output)
+            }
+            Self::Set { setting } => {
+                let response = format!(
+                    "Configuration updated: `{}`. Use `undo` to reverse this.",
+                    setting,
+                );
+
+                config.apply(setting);
+
+                app_meta
+                    .repository
+                    .modify(Change::SetKeyValue {
+                        key_value: KeyValue::Config(Some(config)),
+                    })
+                    .await
+                    .map(|_| response)
+                    .map_err(|_| CommandError::storage("Unable to save the configuration."))
+            }
+            Self::SetTemplate { kind, template } => {
+                let card = CardTemplate::new(&kind, &template).map_err(|e| e.to_string())?;
+                let response = format!(
+                    "Summary template for `{}` updated. Use `undo` to reverse this.",
+                    card.kind,
+                );
+
+                config.cards.insert(card);
+                save_cards(config, app_meta).await.map(|_| response)
+            }
+            Self::RemoveTemplate { kind } => {
+                let card = config.cards.remove(&kind).ok_or_else(|| {
+                    CommandError::unknown_entity(
+                        &kind,
+                        format!(
+                            "There is no summary template for `{}`.",
+                            kind.to_lowercase()
+                        ),
+                    )
+                })?;
+                let response = format!(
+                    "Summary template for `{}` removed. Use `undo` to reverse this.",
+                    card.kind,
+                );
+
+                save_cards(config, app_meta).await.map(|_| response)
+            }
+        }
+    }
+}
+
+async fn save_cards(config: Config, app_meta: &mut AppMeta) -> Result<(), CommandError> {
+    app_meta
+        .repository
+        .modify(Change::SetKeyValue {
+            key_value: KeyValue::CardTemplates(Some(config.cards)),
+        })
+        .await
+        .map(|_| ())
+        .map_err(|_| CommandError::storage("Unable to save the summary template."))
+}
+
+/// Braces in output are read as link tokens (see [`crate::world::LinkView`]). A doubled brace
+/// leaves one brace either side of the token, which is then reduced to its plain text.
+fn escape_braces(template: &str) -> String {
+    template.replace('{', "{{").replace('}', "}}")
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl ContextAwareParse for ConfigCommand {
+    async fn parse_input(input: &str, _app_meta: &AppMeta) -> CommandMatches<Self> {
+        if input.eq_ci("config") {
+            CommandMatches::new_canonical(Self::Show)
+        } else if let Some(rest) = input.strip_prefix_ci("config template ") {
+            if let Some((kind, template)) = rest.split_once('=') {
+                let template = template.trim();
+
+                CommandMatches::new_canonical(Self::SetTemplate {
+                    kind: kind.trim().to_lowercase(),
+                    template: template
+                        .strip_prefix('"')
+                        .and_then(|t| t.strip_suffix('"'))
+                        .unwrap_or(template)
+                        .to_string(),
+                })
+            } else if let Some(kind) = rest.strip_suffix_ci(" off") {
+                CommandMatches::new_canonical(Self::RemoveTemplate {
+                    kind: kind.trim().to_lowercase(),
+                })
+            } else {
+                CommandMatches::default()
+            }
+        } else if let Some(Ok(setting)) = input
+            .strip_prefix_ci("config ")
+            .map(|s| s.parse::<Setting>())
+        {
+            CommandMatches::new_canonical(Self::Set { setting })
+        } else {
+            CommandMatches::default()
+        }
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Autocomplete for ConfigCommand {
+    async fn autocomplete(input: &str, _app_meta: &AppMeta) -> Vec<AutocompleteSuggestion> {
+        if input.is_empty() {
+            Vec::new()
+        } else if "config".starts_with_ci(input) {
+            vec![AutocompleteSuggestion::new("config", "show settings")]
+        } else {
+            [
+                ("config detail full", "show full details of new entries"),
+                ("config detail summary", "show summaries of new entries"),
+                ("config emoji on", "show emoji"),
+                ("config emoji off", "hide emoji"),
+                ("config icons person", "show characters as people"),
+                ("config icons species", "show characters by species"),
+                (
+                    "config date long",
+                    "show dates as eg. \"day 1 at 8:00:00 am\"",
+                ),
+                ("config date short", "show dates as eg. \"1:08:00:00\""),
+                ("config when game", "show when things happened in the game"),
+                (
+                    "config when real",
+                    "show when things happened in the real world",
+                ),
+                ("config when both", "show game and real-world times"),
+                (
+                    "config suggestions [number]",
+                    "set the number of suggestions",
+                ),
+                ("config year [days]", "set the number of days in a year"),
+                ("config dawn [hour]", "set the time of sunrise, eg. 6am"),
+                ("config dusk [hour]", "set the time of sunset, eg. 6pm"),
+                (
+                    "config charisma [modifier]",
+                    "set the party's Charisma for haggling",
+                ),
+                ("config history on", "save command history"),
+                ("config history off", "forget command history"),
+                ("config page [lines]", "split long output into pages"),
+                ("config page off", "show long output all at once"),
+                (
+                    "config autosave on",
+                    "keep unsaved entries between sessions",
+                ),
+                ("config autosave off", "forget unsaved entries"),
+                ("config language en", "show messages in English"),
+                ("config language es", "show messages in Spanish"),
+                (
+                    "config template [type] = [template]",
+                    "customize how summaries look",
+                ),
+                ("config template [type] off", "restore the default summary"),
+            ]
+            .into_iter()
+            .filter(|(term, _)| term.starts_with_ci(input))
+            .map(|(term, summary)| AutocompleteSuggestion::new(term, summary))
+            .collect()
+        }
+    }
+}
+
+impl fmt::Display for ConfigCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::RemoveTemplate { kind } => write!(f, "config template {} off", kind),
+            Self::Set { setting } => write!(f, "config {}", setting),
+            Self::SetTemplate { kind, template } => {
+                write!(f, "config template {} = \"{}\"", kind, template)
+            }
+            Self::Show => write!(f, "config"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::assert_autocomplete;
+    use crate::config::{DateFormat, Detail};
+    use crate::locale::Language;
+    use crate::storage::MemoryDataStore;
+    use crate::Event;
+    use tokio_test::block_on;
+
+    #[test]
+    fn parse_input_test() {
+        let app_meta = app_meta();
+
+        assert_eq!(
+            CommandMatches::new_canonical(ConfigCommand::Show),
+            block_on(ConfigCommand::parse_input("config", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(ConfigCommand::Set {
+                setting: Setting::Emoji(false),
+            }),
+            block_on(ConfigCommand::parse_input("CONFIG EMOJI OFF", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::default(),
+            block_on(ConfigCommand::parse_input("config emoji", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::default(),
+            block_on(ConfigCommand::parse_input("configure", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(ConfigCommand::SetTemplate {
+                kind: "npc".to_string(),
+                template: "{name} — {age} {species}, {occupation}".to_string(),
+            }),
+            block_on(ConfigCommand::parse_input(
+                "config template NPC = \"{name} — {age} {species}, {occupation}\"",
+                &app_meta,
+            )),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(ConfigCommand::RemoveTemplate {
+                kind: "inn".to_string(),
+            }),
+            block_on(ConfigCommand::parse_input(
+                "config template inn off",
+                &app_meta
+            )),
+        );
+    }
+
+    #[test]
+    fn autocomplete_test() {
+        let app_meta = app_meta();
+
+        assert_autocomplete(
+            &[("config", "show settings")][..],
+            block_on(ConfigCommand::autocomplete("con", &app_meta)),
+        );
+
+        assert_autocomplete(
+            &[
+                (
+                    "config date long",
+                    "show dates as eg. \"day 1 at 8:00:00 am\"",
+                ),
+                ("config date short", "show dates as eg. \"1:08:00:00\""),
+                ("config dawn [hour]", "set the time of sunrise, eg. 6am"),
+                ("config detail full", "show full details of new entries"),
+                ("config detail summary", "show summaries of new entries"),
+                ("config dusk [hour]", "set the time of sunset, eg. 6pm"),
+            ][..],
+            block_on(ConfigCommand::autocomplete("config d", &app_meta)),
+        );
+
+        assert_autocomplete(
+            &[][..],
+            block_on(ConfigCommand::autocomplete("", &app_meta)),
+        );
+    }
+
+    #[test]
+    fn display_test() {
+        let app_meta = app_meta();
+
+        [
+            ConfigCommand::Show,
+            ConfigCommand::Set {
+                setting: Setting::Detail(Detail::Summary),
+            },
+            ConfigCommand::Set {
+                setting: Setting::DateFormat(DateFormat::Short),
+            },
+            ConfigCommand::Set {
+                setting: Setting::Suggestions(5),
+            },
+            ConfigCommand::Set {
+                setting: Setting::Year(360),
+            },
+            ConfigCommand::Set {
+                setting: Setting::Dusk(20),
+            },
+            ConfigCommand::Set {
+                setting: Setting::Charisma(-2),
+            },
+            ConfigCommand::Set {
+                setting: Setting::Autosave(true),
+            },
+            ConfigCommand::Set {
+                setting: Setting::Language(Language::Spanish),
+            },
+            ConfigCommand::SetTemplate {
+                kind: "npc".to_string(),
+                template: "{name} — {age} {species}".to_string(),
+            },
+            ConfigCommand::RemoveTemplate {
+                kind: "inn".to_string(),
+            },
+        ]
+        .into_iter()
+        .for_each(|command| {
+            let command_string = command.to_string();
+            assert_ne!("", command_string);
+            assert_eq!(
+                CommandMatches::new_canonical(command),
+                block_on(ConfigCommand::parse_input(&command_string, &app_meta)),
+                "{}",
+                command_string,
+            );
+        });
+    }
+
+    #[test]
+    fn run_test() {
+        let mut app_meta = app_meta();
+
+        assert_eq!(
+            Ok("Configuration updated: `suggestions 3`. Use `undo` to reverse this.".to_string()),
+            block_on(
+                ConfigCommand::Set {
+                    setting: Setting::Suggestions(3),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+
+        assert_eq!(
+            Config {
+                suggestions: 3,
+                ..Default::default()
+            },
+            block_on(Config::load(&app_meta)),
+        );
+
+        let output = block_on(ConfigCommand::Show.run("", &mut app_meta)).unwrap();
+        assert!(output.contains("**Suggestions:** 3"), "{}", output);
+        assert!(output.contains("**Language:** English"), "{}", output);
+        assert!(!output.contains("Summary templates"), "{}", output);
+    }
+
+    #[test]
+    fn run_test_template() {
+        let mut app_meta = app_meta();
+
+        assert_eq!(
+            Ok("Summary template for `npc` updated. Use `undo` to reverse this.".to_string()),
+            block_on(
+                ConfigCommand::SetTemplate {
+                    kind: "NPC".to_string(),
+                    template: "{name}, {age} {species}".to_string(),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+
+        assert_eq!(
+            Err("\"mood\" isn't a field that can be shown in a summary.".into()),
+            block_on(
+                ConfigCommand::SetTemplate {
+                    kind: "npc".to_string(),
+                    template: "{name} ({mood})".to_string(),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+
+        let output = block_on(ConfigCommand::Show.run("", &mut app_meta)).unwrap();
+        assert!(
+            output.ends_with("## Summary templates\n\n**npc:** {{name}}, {{age}} {{species}}"),
+            "{}",
+            output,
+        );
+
+        assert_eq!(
+            Ok("Summary template for `npc` removed. Use `undo` to reverse this.".to_string()),
+            block_on(
+                ConfigCommand::RemoveTemplate {
+                    kind: "npc".to_string(),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+        assert_eq!(
+            Err(CommandError::unknown_entity(
+                "npc",
+                "There is no summary template for `npc`."
+            )),
+            block_on(
+                ConfigCommand::RemoveTemplate {
+                    kind: "npc".to_string(),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+    }
+
+    fn event_dispatcher(_event: Event) {}
+
+    fn app_meta() -> AppMeta {
+        AppMeta::new(MemoryDataStore::default(), &event_dispatcher)
+    }
+}
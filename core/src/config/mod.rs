@@ -0,0 +1,617 @@
+pub use card::{CardTemplate, CardTemplates};
+pub use command::ConfigCommand;
+
+mod card;
+mod command;
+
+use crate::app::AppMeta;
+use crate::locale::Language;
+use crate::storage::KeyValue;
+use crate::time::{ClockView, Moment, Time, UtcView};
+use crate::world::Thing;
+use std::fmt;
+use std::str::FromStr;
+
+/// User preferences affecting how output is displayed. The configuration is persisted to the
+/// key-value store as a single string of the form
+/// `detail=full;emoji=on;icons=person;date=long;when=game;suggestions=10;year=365;dawn=6am;dusk=6pm;charisma=+0;history=off;page=off;autosave=off;language=en`.
+/// Summary templates may contain any character, so they're kept under a key of their own.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    pub detail: Detail,
+    pub emoji: bool,
+
+    /// Whether characters' summaries show an emoji for their species rather than a person
+    /// reflecting their age and gender.
+    pub icons: Icons,
+
+    pub date_format: DateFormat,
+
+    /// Whether records of when something happened, such as the `history`, show the time in the
+    /// game, the real-world time, or both.
+    pub when: When,
+
+    pub suggestions: u8,
+
+    /// The number of days in a year of the campaign's calendar, used to place annual festivals.
+    pub year: u16,
+
+    /// The hours of the day at which the sun rises and sets, used when advancing time to a part
+    /// of the day such as `advance to dawn`.
+    pub dawn: u8,
+    pub dusk: u8,
+
+    /// The Charisma modifier of whichever member of the party does the talking, used when
+    /// haggling with shopkeepers.
+    pub charisma: i8,
+
+    /// Whether commands are saved to the data store as they're run, so that the `history`
+    /// outlasts the session.
+    pub history: bool,
+
+    /// The number of lines shown at a time before long output is split into pages, or `None` to
+    /// show everything at once.
+    pub page_size: Option<u16>,
+
+    /// Whether recent entries are saved to the data store as they change, so that they are
+    /// restored in the next session even if they haven't been saved to the journal.
+    pub autosave: bool,
+
+    /// The language used for messages and tutorial text that have been translated. See
+    /// [`crate::locale`].
+    pub language: Language,
+
+    /// The user's templates for summarizing things, set with `config template`.
+    pub cards: CardTemplates,
+}
+
+/// How much detail to show when a new thing is generated.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Detail {
+    Full,
+    Summary,
+}
+
+/// Which emoji characters are shown with when emoji are on.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Icons {
+    Person,
+    Species,
+}
+
+/// How times and dates are displayed, corresponding to [`crate::time::Time::display_long`] and
+/// [`crate::time::Time::display_short`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DateFormat {
+    Long,
+    Short,
+}
+
+/// Which clock a record of when something happened is shown by: the in-game clock, the real-world
+/// clock (in UTC), or both.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum When {
+    Game,
+    Real,
+    Both,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Setting {
+    Detail(Detail),
+    Emoji(bool),
+    Icons(Icons),
+    DateFormat(DateFormat),
+    When(When),
+    Suggestions(u8),
+    Year(u16),
+    Dawn(u8),
+    Dusk(u8),
+    Charisma(i8),
+    History(bool),
+    PageSize(Option<u16>),
+    Autosave(bool),
+    Language(Language),
+}
+
+impl Config {
+    /// The maximum number of suggestions offered by `more`, limited by the number of keys
+    /// available for numbered aliases.
+    pub const SUGGESTIONS_MAX: u8 = 10;
+
+    /// The longest year that can be configured, in days.
+    pub const YEAR_MAX: u16 = 1000;
+
+    /// The range of page sizes that can be configured, in lines.
+    pub const PAGE_SIZE_MIN: u16 = 10;
+    pub const PAGE_SIZE_MAX: u16 = 1000;
+
+    /// The range of Charisma modifiers that can be configured.
+    pub const CHARISMA_MIN: i8 = -5;
+    pub const CHARISMA_MAX: i8 = 10;
+
+    /// Load the current configuration from storage, falling back to the defaults if none has been
+    /// saved or if the data store is unavailable.
+    pub async fn load(app_meta: &AppMeta) -> Self {
+        let mut config: Self = app_meta
+            .repository
+            .get_key_value(&KeyValue::Config(None))
+            .await
+            .ok()
+            .and_then(KeyValue::config)
+            .unwrap_or_default();
+
+        config.cards = app_meta
+            .repository
+            .get_key_value(&KeyValue::CardTemplates(None))
+            .await
+            .ok()
+            .and_then(KeyValue::card_templates)
+            .unwrap_or_default();
+
+        config
+    }
+
+    /// Format a view according to the user's emoji preference. Views of things omit their emoji
+    /// when formatted with the alternate flag (`{:#}`).
+    pub fn render(&self, view: impl fmt::Display) -> String {
+        if self.emoji {
+            format!("{}", view)
+        } else {
+            format!("{:#}", view)
+        }
+    }
+
+    /// Summarize a thing in one line, using the user's template for its type if there is one.
+    /// The thing's emoji is prepended here rather than by the view so that templated summaries
+    /// get one too.
+    pub fn render_summary(&self, thing: &Thing) -> String {
+        let summary = self
+            .cards
+            .render(thing)
+            .unwrap_or_else(|| format!("{:#}", thing.display_summary()));
+
+        if self.emoji {
+            format!(
+                "{} {}",
+                thing.get_emoji(self.icons == Icons::Species),
+                summary
+            )
+        } else {
+            summary
+        }
+    }
+
+    pub fn display_time(&self, time: &Time) -> String {
+        match self.date_format {
+            DateFormat::Long => time.display_long().to_string(),
+            DateFormat::Short => time.display_short().to_string(),
+        }
+    }
+
+    /// Show when something happened, given the in-game time and the real-world time in
+    /// milliseconds since the Unix epoch, according to the `when` preference. Records made before
+    /// real-world times were kept only have the in-game time.
+    pub fn display_when(&self, time: &Time, real_time: Option<u64>) -> String {
+        match (self.when, real_time) {
+            (When::Real, Some(real_time)) => UtcView::new(real_time).to_string(),
+            (When::Both, Some(real_time)) => {
+                format!("{} ({})", self.display_time(time), UtcView::new(real_time),)
+            }
+            _ => self.display_time(time),
+        }
+    }
+
+    pub fn apply(&mut self, setting: Setting) {
+        match setting {
+            Setting::Detail(detail) => self.detail = detail,
+            Setting::Emoji(emoji) => self.emoji = emoji,
+            Setting::Icons(icons) => self.icons = icons,
+            Setting::DateFormat(date_format) => self.date_format = date_format,
+            Setting::When(when) => self.when = when,
+            Setting::Suggestions(suggestions) => self.suggestions = suggestions,
+            Setting::Year(year) => self.year = year,
+            Setting::Dawn(dawn) => self.dawn = dawn,
+            Setting::Dusk(dusk) => self.dusk = dusk,
+            Setting::Charisma(charisma) => self.charisma = charisma,
+            Setting::History(history) => self.history = history,
+            Setting::PageSize(page_size) => self.page_size = page_size,
+            Setting::Autosave(autosave) => self.autosave = autosave,
+            Setting::Language(language) => self.language = language,
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            detail: Detail::Full,
+            emoji: true,
+            icons: Icons::Person,
+            date_format: DateFormat::Long,
+            when: When::Game,
+            suggestions: Self::SUGGESTIONS_MAX,
+            year: 365,
+            dawn: 6,
+            dusk: 18,
+            charisma: 0,
+            history: false,
+            page_size: None,
+            autosave: false,
+            language: Language::default(),
+            cards: CardTemplates::default(),
+        }
+    }
+}
+
+impl fmt::Display for Config {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "detail={};emoji={};icons={};date={};when={};suggestions={};year={};dawn={};dusk={};charisma={:+};history={};page={};autosave={};language={}",
+            self.detail,
+            if self.emoji { "on" } else { "off" },
+            self.icons,
+            self.date_format,
+            self.when,
+            self.suggestions,
+            self.year,
+            ClockView::new(self.dawn, 0),
+            ClockView::new(self.dusk, 0),
+            self.charisma,
+            if self.history { "on" } else { "off" },
+            self.page_size
+                .map_or_else(|| "off".to_string(), |n| n.to_string()),
+            if self.autosave { "on" } else { "off" },
+            self.language,
+        )
+    }
+}
+
+impl FromStr for Config {
+    type Err = ();
+
+    /// Unrecognized keys are ignored and missing keys take their default values, so that settings
+    /// can be added and removed without invalidating a user's stored configuration.
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        let mut config = Config::default();
+
+        for (key, value) in raw.split(';').filter_map(|part| part.split_once('=')) {
+            if let Ok(setting) = format!("{} {}", key, value).parse() {
+                config.apply(setting);
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+impl fmt::Display for Detail {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Full => write!(f, "full"),
+            Self::Summary => write!(f, "summary"),
+        }
+    }
+}
+
+impl fmt::Display for Icons {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Person => write!(f, "person"),
+            Self::Species => write!(f, "species"),
+        }
+    }
+}
+
+impl fmt::Display for DateFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Long => write!(f, "long"),
+            Self::Short => write!(f, "short"),
+        }
+    }
+}
+
+impl fmt::Display for When {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Game => write!(f, "game"),
+            Self::Real => write!(f, "real"),
+            Self::Both => write!(f, "both"),
+        }
+    }
+}
+
+impl fmt::Display for Setting {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Detail(detail) => write!(f, "detail {}", detail),
+            Self::Emoji(true) => write!(f, "emoji on"),
+            Self::Emoji(false) => write!(f, "emoji off"),
+            Self::Icons(icons) => write!(f, "icons {}", icons),
+            Self::DateFormat(date_format) => write!(f, "date {}", date_format),
+            Self::When(when) => write!(f, "when {}", when),
+            Self::Suggestions(suggestions) => write!(f, "suggestions {}", suggestions),
+            Self::Year(year) => write!(f, "year {}", year),
+            Self::Dawn(dawn) => write!(f, "dawn {}", ClockView::new(*dawn, 0)),
+            Self::Dusk(dusk) => write!(f, "dusk {}", ClockView::new(*dusk, 0)),
+            Self::Charisma(charisma) => write!(f, "charisma {:+}", charisma),
+            Self::History(true) => write!(f, "history on"),
+            Self::History(false) => write!(f, "history off"),
+            Self::PageSize(Some(page_size)) => write!(f, "page {}", page_size),
+            Self::PageSize(None) => write!(f, "page off"),
+            Self::Autosave(true) => write!(f, "autosave on"),
+            Self::Autosave(false) => write!(f, "autosave off"),
+            Self::Language(language) => write!(f, "language {}", language),
+        }
+    }
+}
+
+impl FromStr for Setting {
+    type Err = ();
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        let (key, value) = raw.trim().split_once(' ').ok_or(())?;
+
+        match (
+            key.to_lowercase().as_str(),
+            value.trim().to_lowercase().as_str(),
+        ) {
+            ("detail", "full") => Ok(Self::Detail(Detail::Full)),
+            ("detail", "summary") => Ok(Self::Detail(Detail::Summary)),
+            ("emoji", "on") => Ok(Self::Emoji(true)),
+            ("emoji", "off") => Ok(Self::Emoji(false)),
+            ("icons", "person") => Ok(Self::Icons(Icons::Person)),
+            ("icons", "species") => Ok(Self::Icons(Icons::Species)),
+            ("history", "on") => Ok(Self::History(true)),
+            ("history", "off") => Ok(Self::History(false)),
+            ("autosave", "on") => Ok(Self::Autosave(true)),
+            ("autosave", "off") => Ok(Self::Autosave(false)),
+            ("date", "long") => Ok(Self::DateFormat(DateFormat::Long)),
+            ("date", "short") => Ok(Self::DateFormat(DateFormat::Short)),
+            ("when", "game") => Ok(Self::When(When::Game)),
+            ("when", "real") => Ok(Self::When(When::Real)),
+            ("when", "both") => Ok(Self::When(When::Both)),
+            ("suggestions", number) => match number.parse() {
+                Ok(n) if (1..=Config::SUGGESTIONS_MAX).contains(&n) => Ok(Self::Suggestions(n)),
+                _ => Err(()),
+            },
+            ("page", "off") => Ok(Self::PageSize(None)),
+            ("page", number) => match number.parse() {
+                Ok(n) if (Config::PAGE_SIZE_MIN..=Config::PAGE_SIZE_MAX).contains(&n) => {
+                    Ok(Self::PageSize(Some(n)))
+                }
+                _ => Err(()),
+            },
+            ("language", language) => language.parse().map(Self::Language),
+            ("year", number) => match number.parse() {
+                Ok(n) if (1..=Config::YEAR_MAX).contains(&n) => Ok(Self::Year(n)),
+                _ => Err(()),
+            },
+            ("dawn", hour) => parse_hour(hour).map(Self::Dawn),
+            ("dusk", hour) => parse_hour(hour).map(Self::Dusk),
+            ("charisma", modifier) => {
+                match modifier.strip_prefix('+').unwrap_or(modifier).parse() {
+                    Ok(n) if (Config::CHARISMA_MIN..=Config::CHARISMA_MAX).contains(&n) => {
+                        Ok(Self::Charisma(n))
+                    }
+                    _ => Err(()),
+                }
+            }
+            _ => Err(()),
+        }
+    }
+}
+
+/// An hour of the day, either on the 24-hour clock (eg. "18") or the 12-hour clock (eg. "6pm").
+fn parse_hour(raw: &str) -> Result<u8, ()> {
+    match raw.parse::<u8>() {
+        Ok(hour) if hour < 24 => Ok(hour),
+        Ok(_) => Err(()),
+        Err(_) => match raw.parse() {
+            Ok(Moment::Clock { hours, minutes: 0 }) => Ok(hours),
+            _ => Err(()),
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::world::npc::Species;
+    use crate::world::Npc;
+
+    #[test]
+    fn config_default_test() {
+        assert_eq!(
+            "detail=full;emoji=on;icons=person;date=long;when=game;suggestions=10;year=365;dawn=6am;dusk=6pm;charisma=+0;history=off;page=off;autosave=off;language=en",
+            Config::default().to_string(),
+        );
+    }
+
+    #[test]
+    fn config_from_str_test() {
+        assert_eq!(Ok(Config::default()), "".parse());
+        assert_eq!(Ok(Config::default()), "potato=johnson".parse());
+
+        assert_eq!(
+            Ok(Config {
+                detail: Detail::Summary,
+                emoji: false,
+                icons: Icons::Species,
+                date_format: DateFormat::Short,
+                when: When::Both,
+                suggestions: 3,
+                year: 360,
+                dawn: 5,
+                dusk: 20,
+                charisma: 3,
+                history: true,
+                page_size: Some(40),
+                autosave: true,
+                language: Language::Spanish,
+                cards: CardTemplates::default(),
+            }),
+            "detail=summary;emoji=off;icons=species;date=short;when=both;suggestions=3;year=360;dawn=5am;dusk=20;charisma=3;history=on;page=40;autosave=on;language=es".parse(),
+        );
+
+        assert_eq!(
+            Ok(Config {
+                emoji: false,
+                ..Default::default()
+            }),
+            "emoji=off;suggestions=11".parse(),
+        );
+    }
+
+    #[test]
+    fn config_round_trip_test() {
+        let config = Config {
+            detail: Detail::Summary,
+            emoji: false,
+            icons: Icons::Species,
+            date_format: DateFormat::Short,
+            when: When::Real,
+            suggestions: 1,
+            year: 12,
+            dawn: 0,
+            dusk: 12,
+            charisma: -1,
+            history: true,
+            page_size: Some(25),
+            autosave: true,
+            language: Language::Spanish,
+            cards: CardTemplates::default(),
+        };
+
+        assert_eq!(Ok(config.clone()), config.to_string().parse());
+    }
+
+    #[test]
+    fn config_display_time_test() {
+        let time = Time::try_new(2, 13, 4, 5).unwrap();
+
+        assert_eq!("day 2 at 1:04:05 pm", Config::default().display_time(&time));
+        assert_eq!(
+            "2:13:04:05",
+            Config {
+                date_format: DateFormat::Short,
+                ..Default::default()
+            }
+            .display_time(&time),
+        );
+    }
+
+    #[test]
+    fn config_display_when_test() {
+        let time = Time::try_new(2, 13, 4, 5).unwrap();
+        let real_time = Some(1_700_000_000_000);
+        let mut config = Config::default();
+
+        assert_eq!("day 2 at 1:04:05 pm", config.display_when(&time, real_time));
+
+        config.when = When::Real;
+        assert_eq!(
+            "2023-11-14 22:13 UTC",
+            config.display_when(&time, real_time)
+        );
+        assert_eq!("day 2 at 1:04:05 pm", config.display_when(&time, None));
+
+        config.when = When::Both;
+        config.date_format = DateFormat::Short;
+        assert_eq!(
+            "2:13:04:05 (2023-11-14 22:13 UTC)",
+            config.display_when(&time, real_time),
+        );
+    }
+
+    #[test]
+    fn config_render_summary_test() {
+        let npc: Thing = Npc {
+            name: "Gottfried".into(),
+            species: Species::Dwarf.into(),
+            ..Default::default()
+        }
+        .into();
+        let mut config = Config::default();
+
+        assert_eq!("🧑 `Gottfried` (dwarf)", config.render_summary(&npc));
+
+        config.icons = Icons::Species;
+        assert_eq!("⛏ `Gottfried` (dwarf)", config.render_summary(&npc));
+
+        config
+            .cards
+            .insert("npc = {name} the {species}".parse().unwrap());
+        assert_eq!("⛏ `Gottfried` the dwarf", config.render_summary(&npc));
+
+        config.emoji = false;
+        assert_eq!("`Gottfried` the dwarf", config.render_summary(&npc));
+    }
+
+    #[test]
+    fn setting_from_str_test() {
+        [
+            ("detail full", Setting::Detail(Detail::Full)),
+            ("detail summary", Setting::Detail(Detail::Summary)),
+            ("emoji on", Setting::Emoji(true)),
+            ("emoji off", Setting::Emoji(false)),
+            ("icons person", Setting::Icons(Icons::Person)),
+            ("icons species", Setting::Icons(Icons::Species)),
+            ("date long", Setting::DateFormat(DateFormat::Long)),
+            ("date short", Setting::DateFormat(DateFormat::Short)),
+            ("when game", Setting::When(When::Game)),
+            ("when real", Setting::When(When::Real)),
+            ("when both", Setting::When(When::Both)),
+            ("suggestions 1", Setting::Suggestions(1)),
+            ("suggestions 10", Setting::Suggestions(10)),
+            ("year 365", Setting::Year(365)),
+            ("year 1000", Setting::Year(1000)),
+            ("dawn 5am", Setting::Dawn(5)),
+            ("dusk 12am", Setting::Dusk(0)),
+            ("charisma +3", Setting::Charisma(3)),
+            ("charisma -1", Setting::Charisma(-1)),
+            ("charisma +0", Setting::Charisma(0)),
+            ("history on", Setting::History(true)),
+            ("history off", Setting::History(false)),
+            ("page 10", Setting::PageSize(Some(10))),
+            ("page off", Setting::PageSize(None)),
+            ("autosave on", Setting::Autosave(true)),
+            ("autosave off", Setting::Autosave(false)),
+            ("language en", Setting::Language(Language::English)),
+            ("language es", Setting::Language(Language::Spanish)),
+        ]
+        .into_iter()
+        .for_each(|(input, setting)| {
+            assert_eq!(input, setting.to_string());
+            assert_eq!(Ok(setting.clone()), input.parse::<Setting>(), "{}", input);
+            assert_eq!(
+                Ok(setting),
+                input.to_uppercase().parse::<Setting>(),
+                "{}",
+                input,
+            );
+        });
+
+        assert_eq!(Err(()), "suggestions 0".parse::<Setting>());
+        assert_eq!(Err(()), "suggestions 11".parse::<Setting>());
+        assert_eq!(Err(()), "year 0".parse::<Setting>());
+        assert_eq!(Err(()), "year 1001".parse::<Setting>());
+        assert_eq!(Ok(Setting::Dusk(19)), "dusk 19".parse::<Setting>());
+        assert_eq!(Err(()), "dawn 24".parse::<Setting>());
+        assert_eq!(Err(()), "dawn 5:30am".parse::<Setting>());
+        assert_eq!(Ok(Setting::Charisma(5)), "charisma 5".parse::<Setting>());
+        assert_eq!(Err(()), "charisma +11".parse::<Setting>());
+        assert_eq!(Err(()), "charisma -6".parse::<Setting>());
+        assert_eq!(Err(()), "page 9".parse::<Setting>());
+        assert_eq!(Err(()), "page 1001".parse::<Setting>());
+        assert_eq!(Err(()), "emoji".parse::<Setting>());
+        assert_eq!(
+            Ok(Setting::Language(Language::Spanish)),
+            "language spanish".parse::<Setting>(),
+        );
+        assert_eq!(Err(()), "language klingon".parse::<Setting>());
+        assert_eq!(Err(()), "potato johnson".parse::<Setting>());
+    }
+}
@@ -0,0 +1,298 @@
+use super::{is_history_command, HistoryEntry};
+use crate::app::{
+    AppMeta, Autocomplete, AutocompleteSuggestion, Command, CommandError, CommandMatches,
+    ContextAwareParse, Runnable,
+};
+use crate::config::Config;
+use crate::utils::CaseInsensitiveStr;
+use crate::world::count;
+use async_trait::async_trait;
+use std::fmt;
+
+/// The number of commands shown by `history`. Older commands can still be re-run by number.
+const HISTORY_SHOWN: usize = 20;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum HistoryCommand {
+    Replay { name: String },
+    Save { name: String },
+    Show,
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Runnable for HistoryCommand {
+    async fn run(self, _input: &str, app_meta: &mut AppMeta) -> Result<String, CommandError> {
+        match self {
+            Self::Show => {
+                if app_meta.history.is_empty() {
+                    return Ok("There are no commands in the history yet.".to_string());
+                }
+
+                let config = Config::load(app_meta).await;
+                let skip = app_meta.history.len().saturating_sub(HISTORY_SHOWN);
+                let mut output = "# History\n".to_string();
+
+                for (i, entry) in app_meta.history.iter().enumerate().skip(skip) {
+                    output.push_str(&format!("\n* {}", entry.display(i + 1, &config)));
+                }
+
+                output.push_str("\n\n_Use `!!` to repeat the last command or `![number]` to repeat an earlier one. Use `history save [name]` to save this session for `replay`._");
+
+                Ok(output)
+            }
+            Self::Save { name } => {
+                let entries: Vec<HistoryEntry> = replayable(&app_meta.history).cloned().collect();
+
+                if entries.is_empty() {
+                    return Err(CommandError::invalid_state(
+                        "There are no commands to save.",
+                    ));
+                }
+
+                app_meta
+                    .repository
+                    .save_history_log(&name, &entries)
+                    .await
+                    .map(|_| {
+                        format!(
+                            "Saved {} as `{}`. Use `replay {}` to run them again.",
+                            count(entries.len() as u32, "command"),
+                            name,
+                            name,
+                        )
+                    })
+                    .map_err(|_| CommandError::storage("Unable to save the history."))
+            }
+            Self::Replay { name } => {
+                let entries = match app_meta.repository.history_log(&name).await {
+                    Ok(Some(entries)) => entries,
+                    Ok(None) => {
+                        return Err(CommandError::unknown_entity(
+                            &name,
+                            format!(r#"There is no saved history named "{}"."#, name),
+                        ))
+                    }
+                    Err(_) => return Err(CommandError::storage("Unable to load the history.")),
+                };
+
+                let mut failures = Vec::new();
+                let mut replayed = 0;
+
+                for entry in replayable(&entries) {
+                    let result = Command::parse_input_irrefutable(&entry.input, app_meta)
+                        .await
+                        .run(&entry.input, app_meta)
+                        .await;
+
+                    if result.is_err() {
+                        failures.push(&entry.input);
+                    }
+
+                    replayed += 1;
+                }
+
+                let mut output =
+                    format!("Replayed {} from `{}`.", count(replayed, "command"), name);
+
+                if !failures.is_empty() {
+                    output.push_str("\n\n! The following commands failed:\n");
+
+                    for input in failures {
+                        output.push_str(&format!("\n* `{}`", input));
+                    }
+                }
+
+                Ok(output)
+            }
+        }
+    }
+}
+
+/// The commands worth replaying: those that succeeded and aren't themselves history commands.
+fn replayable(entries: &[HistoryEntry]) -> impl Iterator<Item = &HistoryEntry> {
+    entries
+        .iter()
+        .filter(|entry| entry.success && !is_history_command(&entry.input))
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl ContextAwareParse for HistoryCommand {
+    async fn parse_input(input: &str, _app_meta: &AppMeta) -> CommandMatches<Self> {
+        if input.eq_ci("history") {
+            CommandMatches::new_canonical(Self::Show)
+        } else if let Some(name) = input
+            .strip_prefix_ci("history save ")
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+        {
+            CommandMatches::new_canonical(Self::Save {
+                name: name.to_string(),
+            })
+        } else if let Some(name) = input
+            .strip_prefix_ci("replay ")
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+        {
+            CommandMatches::new_canonical(Self::Replay {
+                name: name.to_string(),
+            })
+        } else {
+            CommandMatches::default()
+        }
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Autocomplete for HistoryCommand {
+    async fn autocomplete(input: &str, app_meta: &AppMeta) -> Vec<AutocompleteSuggestion> {
+        if input.is_empty() {
+            return Vec::new();
+        }
+
+        let mut suggestions: Vec<AutocompleteSuggestion> = [
+            ("history", "show recent commands"),
+            ("history save [name]", "save this session's commands"),
+            ("replay [name]", "run saved commands again"),
+        ]
+        .into_iter()
+        .filter(|(term, _)| term.starts_with_ci(input))
+        .map(|(term, summary)| AutocompleteSuggestion::new(term, summary))
+        .collect();
+
+        if "!!".starts_with(input) {
+            if let Some(entry) = app_meta.history.last() {
+                suggestions.push(AutocompleteSuggestion::new(
+                    "!!",
+                    format!("repeat `{}`", entry.input),
+                ));
+            }
+        }
+
+        suggestions
+    }
+}
+
+impl fmt::Display for HistoryCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::Replay { name } => write!(f, "replay {}", name),
+            Self::Save { name } => write!(f, "history save {}", name),
+            Self::Show => write!(f, "history"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::assert_autocomplete;
+    use crate::storage::MemoryDataStore;
+    use crate::time::Time;
+    use crate::Event;
+    use tokio_test::block_on;
+
+    #[test]
+    fn parse_input_test() {
+        let app_meta = app_meta();
+
+        assert_eq!(
+            CommandMatches::new_canonical(HistoryCommand::Show),
+            block_on(HistoryCommand::parse_input("HISTORY", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(HistoryCommand::Save {
+                name: "session.log".to_string(),
+            }),
+            block_on(HistoryCommand::parse_input(
+                "history save session.log",
+                &app_meta
+            )),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(HistoryCommand::Replay {
+                name: "session.log".to_string(),
+            }),
+            block_on(HistoryCommand::parse_input("replay session.log", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::default(),
+            block_on(HistoryCommand::parse_input("replay ", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::default(),
+            block_on(HistoryCommand::parse_input("historian", &app_meta)),
+        );
+    }
+
+    #[test]
+    fn autocomplete_test() {
+        let mut app_meta = app_meta();
+
+        assert_autocomplete(
+            &[
+                ("history", "show recent commands"),
+                ("history save [name]", "save this session's commands"),
+            ][..],
+            block_on(HistoryCommand::autocomplete("hist", &app_meta)),
+        );
+
+        assert_autocomplete(
+            &[("replay [name]", "run saved commands again")][..],
+            block_on(HistoryCommand::autocomplete("REP", &app_meta)),
+        );
+
+        assert!(block_on(HistoryCommand::autocomplete("!", &app_meta)).is_empty());
+
+        app_meta.history.push(HistoryEntry {
+            input: "npc".to_string(),
+            time: Time::default(),
+            real_time: None,
+            success: true,
+        });
+
+        assert_autocomplete(
+            &[("!!", "repeat `npc`")][..],
+            block_on(HistoryCommand::autocomplete("!", &app_meta)),
+        );
+    }
+
+    #[test]
+    fn display_test() {
+        let app_meta = app_meta();
+
+        [
+            HistoryCommand::Show,
+            HistoryCommand::Save {
+                name: "session.log".to_string(),
+            },
+            HistoryCommand::Replay {
+                name: "session.log".to_string(),
+            },
+        ]
+        .into_iter()
+        .for_each(|command| {
+            let command_string = command.to_string();
+            assert_ne!("", command_string);
+
+            assert_eq!(
+                CommandMatches::new_canonical(command),
+                block_on(HistoryCommand::parse_input(&command_string, &app_meta)),
+                "{}",
+                command_string,
+            );
+        });
+    }
+
+    fn event_dispatcher(_event: Event) {}
+
+    fn app_meta() -> AppMeta {
+        AppMeta::new(MemoryDataStore::default(), &event_dispatcher)
+    }
+}
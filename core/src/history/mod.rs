@@ -0,0 +1,255 @@
+pub use command::HistoryCommand;
+
+mod command;
+
+use crate::config::Config;
+use crate::time::Time;
+use crate::utils::CaseInsensitiveStr;
+use std::fmt;
+use std::str::FromStr;
+
+/// A command entered by the user, recorded at the in-game and real-world times when it was run.
+///
+/// Entries are persisted to the key-value store one per line, in the form
+/// `1:08:00:00@1700000000000\tok\telf named Gottfried`. Entries recorded before real-world times
+/// were kept have only the in-game time, eg. `1:08:00:00\tok\telf named Gottfried`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HistoryEntry {
+    pub input: String,
+    pub time: Time,
+
+    /// The real-world time in milliseconds since the Unix epoch, if known.
+    pub real_time: Option<u64>,
+
+    pub success: bool,
+}
+
+pub struct HistoryEntryView<'a> {
+    entry: &'a HistoryEntry,
+    number: usize,
+    config: &'a Config,
+}
+
+impl HistoryEntry {
+    /// Show the entry as it appears in the `history` list, numbered so that it can be re-run with
+    /// `![number]`.
+    pub fn display<'a>(&'a self, number: usize, config: &'a Config) -> HistoryEntryView<'a> {
+        HistoryEntryView {
+            entry: self,
+            number,
+            config,
+        }
+    }
+}
+
+/// Is the input a command that reads or replays the history? Such commands are never replayed
+/// themselves, since a log replaying itself would never end.
+pub fn is_history_command(input: &str) -> bool {
+    input.starts_with('!')
+        || input.eq_ci("history")
+        || input.starts_with_ci("history ")
+        || input.starts_with_ci("replay ")
+}
+
+/// Expand a reference to an earlier command: `!!` for the previous command, or `![number]` for
+/// the command with that number in the `history` list. Returns `None` if the input isn't a
+/// reference at all.
+pub fn expand<'a>(input: &str, history: &'a [HistoryEntry]) -> Option<Result<&'a str, String>> {
+    let reference = input.trim().strip_prefix('!')?;
+
+    let entry = if reference == "!" {
+        history
+            .last()
+            .ok_or_else(|| "There are no commands in the history yet.".to_string())
+    } else {
+        let number: usize = reference.parse().ok()?;
+
+        number
+            .checked_sub(1)
+            .and_then(|i| history.get(i))
+            .ok_or_else(|| format!("There is no command numbered {} in the `history`.", number))
+    };
+
+    Some(entry.map(|entry| entry.input.as_str()))
+}
+
+impl fmt::Display for HistoryEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.time.display_short())?;
+
+        if let Some(real_time) = self.real_time {
+            write!(f, "@{}", real_time)?;
+        }
+
+        write!(
+            f,
+            "\t{}\t{}",
+            if self.success { "ok" } else { "err" },
+            self.input,
+        )
+    }
+}
+
+impl FromStr for HistoryEntry {
+    type Err = ();
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        let mut parts = raw.splitn(3, '\t');
+
+        let times = parts.next().ok_or(())?;
+        let (time, real_time) = match times.split_once('@') {
+            Some((time, real_time)) => (time.parse()?, Some(real_time.parse().map_err(|_| ())?)),
+            None => (times.parse()?, None),
+        };
+        let success = match parts.next() {
+            Some("ok") => true,
+            Some("err") => false,
+            _ => return Err(()),
+        };
+        let input = parts.next().filter(|s| !s.is_empty()).ok_or(())?;
+
+        Ok(Self {
+            input: input.to_string(),
+            time,
+            real_time,
+            success,
+        })
+    }
+}
+
+impl<'a> fmt::Display for HistoryEntryView<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let entry = self.entry;
+
+        write!(f, "`!{}` `{}`", self.number, entry.input)?;
+
+        if !entry.success {
+            write!(f, " (failed)")?;
+        }
+
+        write!(
+            f,
+            ", {}",
+            self.config.display_when(&entry.time, entry.real_time),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::When;
+
+    #[test]
+    fn round_trip_test() {
+        [
+            entry("elf named Gottfried", true),
+            entry("potato\tjohnson", false),
+            HistoryEntry {
+                real_time: Some(1_700_000_000_000),
+                ..entry("npc", true)
+            },
+        ]
+        .into_iter()
+        .for_each(|entry| {
+            assert_eq!(Ok(entry.clone()), entry.to_string().parse(), "{}", entry);
+        });
+    }
+
+    #[test]
+    fn from_str_test() {
+        assert_eq!(
+            Ok(entry("elf named Gottfried", true)),
+            "1:08:00:00\tok\telf named Gottfried".parse(),
+        );
+        assert_eq!(
+            Ok(HistoryEntry {
+                real_time: Some(1_700_000_000_000),
+                ..entry("npc", false)
+            }),
+            "1:08:00:00@1700000000000\terr\tnpc".parse(),
+        );
+        assert_eq!(Err(()), "1:08:00:00\tmaybe\tnpc".parse::<HistoryEntry>());
+        assert_eq!(Err(()), "1:08:00:00@now\tok\tnpc".parse::<HistoryEntry>());
+        assert_eq!(Err(()), "1:08:00:00\tok\t".parse::<HistoryEntry>());
+        assert_eq!(Err(()), "potato".parse::<HistoryEntry>());
+    }
+
+    #[test]
+    fn display_test() {
+        assert_eq!(
+            "`!3` `npc`, day 1 at 8:00:00 am",
+            entry("npc", true)
+                .display(3, &Config::default())
+                .to_string(),
+        );
+        assert_eq!(
+            "`!12` `potato` (failed), day 1 at 8:00:00 am",
+            entry("potato", false)
+                .display(12, &Config::default())
+                .to_string(),
+        );
+        assert_eq!(
+            "`!1` `npc`, day 1 at 8:00:00 am (2023-11-14 22:13 UTC)",
+            HistoryEntry {
+                real_time: Some(1_700_000_000_000),
+                ..entry("npc", true)
+            }
+            .display(
+                1,
+                &Config {
+                    when: When::Both,
+                    ..Default::default()
+                }
+            )
+            .to_string(),
+        );
+    }
+
+    #[test]
+    fn expand_test() {
+        let history = [entry("npc", true), entry("potato", false)];
+
+        assert_eq!(Some(Ok("potato")), expand("!!", &history));
+        assert_eq!(Some(Ok("npc")), expand("!1", &history));
+        assert_eq!(Some(Ok("potato")), expand(" !2 ", &history));
+        assert_eq!(
+            Some(Err(
+                "There is no command numbered 3 in the `history`.".to_string()
+            )),
+            expand("!3", &history),
+        );
+        assert_eq!(
+            Some(Err(
+                "There is no command numbered 0 in the `history`.".to_string()
+            )),
+            expand("!0", &history),
+        );
+        assert_eq!(
+            Some(Err("There are no commands in the history yet.".to_string())),
+            expand("!!", &[]),
+        );
+        assert_eq!(None, expand("npc", &history));
+        assert_eq!(None, expand("!potato", &history));
+    }
+
+    #[test]
+    fn is_history_command_test() {
+        assert!(is_history_command("!!"));
+        assert!(is_history_command("!3"));
+        assert!(is_history_command("History"));
+        assert!(is_history_command("history save session.log"));
+        assert!(is_history_command("replay session.log"));
+        assert!(!is_history_command("npc"));
+        assert!(!is_history_command("historian"));
+    }
+
+    fn entry(input: &str, success: bool) -> HistoryEntry {
+        HistoryEntry {
+            input: input.to_string(),
+            time: Time::default(),
+            real_time: None,
+            success,
+        }
+    }
+}
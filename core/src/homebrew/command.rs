@@ -0,0 +1,527 @@
+use super::{Homebrew, HomebrewBase, HomebrewError, HomebrewType};
+use crate::app::{
+    AppMeta, Autocomplete, AutocompleteSuggestion, CommandError, CommandMatches, ContextAwareParse,
+    Runnable,
+};
+use crate::reference::{HomebrewEntry, HomebrewEntryKind, HomebrewReference};
+use crate::storage::{Change, KeyValue};
+use crate::utils::{capitalize, CaseInsensitiveStr};
+use crate::Event;
+use async_trait::async_trait;
+use std::fmt;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum HomebrewCommand {
+    Add {
+        kind: HomebrewKind,
+        definition: String,
+    },
+    Import,
+    List,
+    Remove {
+        kind: HomebrewKind,
+        name: String,
+    },
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HomebrewKind {
+    Species,
+    PlaceType,
+}
+
+impl HomebrewKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Species => "species",
+            Self::PlaceType => "place type",
+        }
+    }
+
+    fn matches(&self, homebrew: &HomebrewType) -> bool {
+        homebrew.is_species() == (self == &Self::Species)
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Runnable for HomebrewCommand {
+    async fn run(self, _input: &str, app_meta: &mut AppMeta) -> Result<String, CommandError> {
+        let mut homebrew = Homebrew::load(app_meta).await;
+
+        let response = match self {
+            Self::Import => {
+                (app_meta.event_dispatcher)(Event::ImportHomebrew);
+                return Ok("The file upload popup should appear momentarily. Please select a JSON file containing lists of `spells`, `equipment`, `magic-items`, or `monsters` in the format of the D&D 5e API.".to_string());
+            }
+            Self::List => {
+                let reference = HomebrewReference::load(app_meta).await;
+
+                if homebrew.is_empty() && reference.is_empty() {
+                    return Ok("# Homebrew\n\n*You haven't added any homebrew yet. Use `species add [name] like [species]` or `place type add [name] like [place type]` to add a species or place type, eg. `species add warforged like dwarf`, or `homebrew import` to import spells, items, and monsters.*".to_string());
+                }
+
+                let mut output = "# Homebrew".to_string();
+
+                for (title, kind) in [
+                    ("Species", HomebrewKind::Species),
+                    ("Place types", HomebrewKind::PlaceType),
+                ] {
+                    let mut list: Vec<&HomebrewType> =
+                        homebrew.iter().filter(|h| kind.matches(h)).collect();
+
+                    if list.is_empty() {
+                        continue;
+                    }
+
+                    list.sort_by(|a, b| a.name.cmp_ci(&b.name));
+
+                    output.push_str(&format!("\n\n## {}\n", title));
+                    list.into_iter().for_each(|homebrew_type| {
+                        output.push_str(&format!(
+                            "\n* `{}`: like {}",
+                            homebrew_type.name, homebrew_type.base,
+                        ));
+
+                        if let Some(share) = homebrew_type.share {
+                            output.push_str(&format!(", {}% of the population", share));
+                        }
+
+                        if !homebrew_type.names.is_empty() {
+                            output.push_str(&format!(
+                                ", with names like {}",
+                                homebrew_type.names.join(", "),
+                            ));
+                        }
+                    });
+                }
+
+                for kind in [
+                    HomebrewEntryKind::Spell,
+                    HomebrewEntryKind::Equipment,
+                    HomebrewEntryKind::MagicItem,
+                    HomebrewEntryKind::Monster,
+                ] {
+                    let mut list: Vec<&HomebrewEntry> =
+                        reference.iter().filter(|e| e.kind == kind).collect();
+
+                    if list.is_empty() {
+                        continue;
+                    }
+
+                    list.sort_by(|a, b| a.name().cmp_ci(b.name()));
+
+                    output.push_str(&format!("\n\n## {}\n", capitalize(kind.plural())));
+                    list.into_iter().for_each(|entry| {
+                        output.push_str(&format!("\n* {}", entry.summary()));
+                    });
+                }
+
+                return Ok(output);
+            }
+            Self::Add { kind, definition } => {
+                let homebrew_type: HomebrewType = format!("{} {}", kind.as_str(), definition)
+                    .parse()
+                    .map_err(|e: HomebrewError| CommandError::parse(e.to_string(), Vec::new()))?;
+
+                let response = format!(
+                    "{} the **{name}** {}. Use `create {name}` to use it, or `undo` to reverse this.",
+                    if homebrew.get(&homebrew_type.name).is_some() {
+                        "Updated"
+                    } else {
+                        "Added"
+                    },
+                    kind.as_str(),
+                    name = homebrew_type.name,
+                );
+
+                homebrew.insert(homebrew_type);
+                response
+            }
+            Self::Remove { kind, name } => {
+                if !homebrew.get(&name).is_some_and(|h| kind.matches(h)) {
+                    return Err(CommandError::unknown_entity(
+                        &name,
+                        format!(
+                            "There is no homebrew {} named \"{}\". Use `homebrew list` to list your homebrew species and place types.",
+                            kind.as_str(),
+                            name,
+                        ),
+                    ));
+                }
+
+                let homebrew_type = homebrew.remove(&name).unwrap();
+
+                format!(
+                    "Removed the **{}** {}. Anything already created with it is unaffected. Use `undo` to reverse this.",
+                    homebrew_type.name,
+                    kind.as_str(),
+                )
+            }
+        };
+
+        app_meta
+            .repository
+            .modify(Change::SetKeyValue {
+                key_value: KeyValue::Homebrew(Some(homebrew)),
+            })
+            .await
+            .map(|_| response)
+            .map_err(|_| CommandError::storage("Unable to save the homebrew types."))
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl ContextAwareParse for HomebrewCommand {
+    async fn parse_input(input: &str, _app_meta: &AppMeta) -> CommandMatches<Self> {
+        if input.eq_ci("homebrew") || input.eq_ci("homebrew list") {
+            return CommandMatches::new_canonical(Self::List);
+        } else if input.eq_ci("homebrew import") {
+            return CommandMatches::new_canonical(Self::Import);
+        }
+
+        for kind in [HomebrewKind::Species, HomebrewKind::PlaceType] {
+            let rest = match input
+                .strip_prefix_ci(kind.as_str())
+                .and_then(|s| s.strip_prefix(' '))
+            {
+                Some(rest) => rest,
+                None => continue,
+            };
+
+            if let Some(definition) = rest
+                .strip_prefix_ci("add ")
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+            {
+                return CommandMatches::new_canonical(Self::Add {
+                    kind,
+                    definition: definition.to_string(),
+                });
+            } else if let Some(name) = rest
+                .strip_prefix_ci("remove ")
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+            {
+                return CommandMatches::new_canonical(Self::Remove {
+                    kind,
+                    name: name.to_string(),
+                });
+            }
+        }
+
+        CommandMatches::default()
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Autocomplete for HomebrewCommand {
+    async fn autocomplete(input: &str, app_meta: &AppMeta) -> Vec<AutocompleteSuggestion> {
+        if input.is_empty() {
+            return Vec::new();
+        }
+
+        let homebrew = Homebrew::load(app_meta).await;
+        let mut suggestions: Vec<AutocompleteSuggestion> = [
+            (
+                "homebrew import",
+                "import homebrew spells, items, and monsters",
+            ),
+            ("homebrew list", "list your homebrew"),
+            (
+                "place type add [name] like [place type]",
+                "add a homebrew place type",
+            ),
+            ("place type remove [name]", "remove a homebrew place type"),
+            (
+                "species add [name] like [species]",
+                "add a homebrew species",
+            ),
+            ("species remove [name]", "remove a homebrew species"),
+        ]
+        .into_iter()
+        .filter(|(term, _)| term.starts_with_ci(input))
+        .map(|(term, summary)| AutocompleteSuggestion::new(term, summary))
+        .collect();
+
+        for (prefix, kind) in [
+            ("species remove ", Some(HomebrewKind::Species)),
+            ("place type remove ", Some(HomebrewKind::PlaceType)),
+            ("create ", None),
+            ("", None),
+        ] {
+            if let Some(partial) = input.strip_prefix_ci(prefix) {
+                homebrew
+                    .iter()
+                    .filter(|h| kind.is_none_or(|kind| kind.matches(h)))
+                    .filter(|h| h.name.starts_with_ci(partial))
+                    .for_each(|h| {
+                        suggestions.push(AutocompleteSuggestion::new(
+                            format!("{}{}", prefix, h.name),
+                            match (kind, h.base) {
+                                (Some(kind), _) => format!("remove homebrew {}", kind.as_str()),
+                                (None, HomebrewBase::Species(_)) => {
+                                    "create homebrew species".to_string()
+                                }
+                                (None, HomebrewBase::PlaceType(_)) => {
+                                    "create homebrew place type".to_string()
+                                }
+                            },
+                        ))
+                    });
+                break;
+            }
+        }
+
+        suggestions
+    }
+}
+
+impl fmt::Display for HomebrewCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::Add { kind, definition } => write!(f, "{} add {}", kind.as_str(), definition),
+            Self::Import => write!(f, "homebrew import"),
+            Self::List => write!(f, "homebrew list"),
+            Self::Remove { kind, name } => write!(f, "{} remove {}", kind.as_str(), name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::assert_autocomplete;
+    use crate::storage::MemoryDataStore;
+    use crate::Event;
+    use tokio_test::block_on;
+
+    #[test]
+    fn parse_input_test() {
+        let app_meta = app_meta();
+
+        assert_eq!(
+            CommandMatches::new_canonical(HomebrewCommand::List),
+            block_on(HomebrewCommand::parse_input("HOMEBREW", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(HomebrewCommand::Add {
+                kind: HomebrewKind::Species,
+                definition: "warforged like dwarf".to_string(),
+            }),
+            block_on(HomebrewCommand::parse_input(
+                "Species Add warforged like dwarf",
+                &app_meta,
+            )),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(HomebrewCommand::Remove {
+                kind: HomebrewKind::PlaceType,
+                name: "teahouse".to_string(),
+            }),
+            block_on(HomebrewCommand::parse_input(
+                "place type remove teahouse",
+                &app_meta,
+            )),
+        );
+
+        assert_eq!(
+            CommandMatches::default(),
+            block_on(HomebrewCommand::parse_input("species add ", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::default(),
+            block_on(HomebrewCommand::parse_input("speciesadd elf", &app_meta)),
+        );
+    }
+
+    #[test]
+    fn autocomplete_test() {
+        let mut app_meta = app_meta();
+
+        assert_autocomplete(
+            &[
+                (
+                    "species add [name] like [species]",
+                    "add a homebrew species",
+                ),
+                ("species remove [name]", "remove a homebrew species"),
+            ][..],
+            block_on(HomebrewCommand::autocomplete("spec", &app_meta)),
+        );
+
+        block_on(
+            HomebrewCommand::Add {
+                kind: HomebrewKind::Species,
+                definition: "warforged like dwarf".to_string(),
+            }
+            .run("", &mut app_meta),
+        )
+        .unwrap();
+
+        assert_autocomplete(
+            &[("warforged", "create homebrew species")][..],
+            block_on(HomebrewCommand::autocomplete("WAR", &app_meta)),
+        );
+
+        assert_autocomplete(
+            &[("create warforged", "create homebrew species")][..],
+            block_on(HomebrewCommand::autocomplete("create w", &app_meta)),
+        );
+
+        assert_autocomplete(
+            &[
+                ("species remove [name]", "remove a homebrew species"),
+                ("species remove warforged", "remove homebrew species"),
+            ][..],
+            block_on(HomebrewCommand::autocomplete("species remove ", &app_meta)),
+        );
+
+        assert_autocomplete(
+            &[][..],
+            block_on(HomebrewCommand::autocomplete(
+                "place type remove w",
+                &app_meta,
+            )),
+        );
+
+        assert_autocomplete(
+            &[][..],
+            block_on(HomebrewCommand::autocomplete("", &app_meta)),
+        );
+    }
+
+    #[test]
+    fn display_test() {
+        let app_meta = app_meta();
+
+        [
+            HomebrewCommand::Add {
+                kind: HomebrewKind::Species,
+                definition: "warforged like dwarf".to_string(),
+            },
+            HomebrewCommand::Add {
+                kind: HomebrewKind::PlaceType,
+                definition: "teahouse like inn".to_string(),
+            },
+            HomebrewCommand::List,
+            HomebrewCommand::Remove {
+                kind: HomebrewKind::Species,
+                name: "warforged".to_string(),
+            },
+            HomebrewCommand::Remove {
+                kind: HomebrewKind::PlaceType,
+                name: "teahouse".to_string(),
+            },
+        ]
+        .into_iter()
+        .for_each(|command| {
+            let command_string = command.to_string();
+            assert_ne!("", command_string);
+            assert_eq!(
+                CommandMatches::new_canonical(command),
+                block_on(HomebrewCommand::parse_input(&command_string, &app_meta)),
+                "{}",
+                command_string,
+            );
+        });
+    }
+
+    #[test]
+    fn run_test() {
+        let mut app_meta = app_meta();
+
+        assert_eq!(
+            Ok("# Homebrew\n\n*You haven't added any homebrew yet. Use `species add [name] like [species]` or `place type add [name] like [place type]` to add a species or place type, eg. `species add warforged like dwarf`, or `homebrew import` to import spells, items, and monsters.*".to_string()),
+            block_on(HomebrewCommand::List.run("", &mut app_meta)),
+        );
+
+        assert_eq!(
+            Ok("Added the **warforged** species. Use `create warforged` to use it, or `undo` to reverse this.".to_string()),
+            block_on(
+                HomebrewCommand::Add {
+                    kind: HomebrewKind::Species,
+                    definition: "warforged like dwarf".to_string(),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+
+        assert_eq!(
+            Ok("Updated the **Warforged** species. Use `create Warforged` to use it, or `undo` to reverse this.".to_string()),
+            block_on(
+                HomebrewCommand::Add {
+                    kind: HomebrewKind::Species,
+                    definition: "Warforged like dwarf as 5% of the population with names like Anvil, Bolt".to_string(),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+
+        assert_eq!(
+            Ok("Added the **teahouse** place type. Use `create teahouse` to use it, or `undo` to reverse this.".to_string()),
+            block_on(
+                HomebrewCommand::Add {
+                    kind: HomebrewKind::PlaceType,
+                    definition: "teahouse like inn".to_string(),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+
+        assert_eq!(
+            Err(CommandError::parse(
+                "\"elf\" is already built in.",
+                Vec::new()
+            )),
+            block_on(
+                HomebrewCommand::Add {
+                    kind: HomebrewKind::Species,
+                    definition: "elf like human".to_string(),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+
+        assert_eq!(
+            Ok("# Homebrew\n\n## Species\n\n* `Warforged`: like dwarf, 5% of the population, with names like Anvil, Bolt\n\n## Place types\n\n* `teahouse`: like inn".to_string()),
+            block_on(HomebrewCommand::List.run("", &mut app_meta)),
+        );
+
+        assert_eq!(
+            Err(CommandError::unknown_entity(
+                "teahouse",
+                "There is no homebrew species named \"teahouse\". Use `homebrew list` to list your homebrew species and place types.",
+            )),
+            block_on(
+                HomebrewCommand::Remove {
+                    kind: HomebrewKind::Species,
+                    name: "teahouse".to_string(),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+
+        assert_eq!(
+            Ok("Removed the **teahouse** place type. Anything already created with it is unaffected. Use `undo` to reverse this.".to_string()),
+            block_on(
+                HomebrewCommand::Remove {
+                    kind: HomebrewKind::PlaceType,
+                    name: "TEAHOUSE".to_string(),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+    }
+
+    fn event_dispatcher(_event: Event) {}
+
+    fn app_meta() -> AppMeta {
+        AppMeta::new(MemoryDataStore::default(), &event_dispatcher)
+    }
+}
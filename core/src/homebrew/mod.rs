@@ -0,0 +1,542 @@
+pub use command::HomebrewCommand;
+
+mod command;
+
+use crate::app::AppMeta;
+use crate::storage::KeyValue;
+use crate::utils::CaseInsensitiveStr;
+use crate::world::npc::Species;
+use crate::world::place::PlaceType;
+use crate::world::{Demographics, Field, Generate, ParsedThing, Thing};
+use rand::prelude::*;
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+/// The user's homebrew species and place types. They're persisted to the key-value store one per
+/// line, each in the same form used to define them, eg.
+/// `species warforged like dwarf with names like Anvil, Bolt`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Homebrew(Vec<HomebrewType>);
+
+/// A species or place type added by the user, eg. `warforged`. Each is based on a built-in species
+/// or place type, which is used to generate everything the homebrew type doesn't specify.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HomebrewType {
+    pub name: String,
+    pub base: HomebrewBase,
+
+    /// Names to choose from when generating one, in place of those of the base.
+    pub names: Vec<String>,
+
+    /// The percentage of randomly generated characters who are of this species.
+    pub share: Option<u8>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HomebrewBase {
+    Species(Species),
+    PlaceType(PlaceType),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum HomebrewError {
+    MissingName,
+    BuiltIn(String),
+    UnknownBase(String),
+    InvalidShare(String),
+}
+
+impl Homebrew {
+    /// Load the user's homebrew types from storage, or an empty set if none have been defined or
+    /// the data store is unavailable.
+    pub async fn load(app_meta: &AppMeta) -> Self {
+        app_meta
+            .repository
+            .get_key_value(&KeyValue::Homebrew(None))
+            .await
+            .ok()
+            .and_then(KeyValue::homebrew)
+            .unwrap_or_default()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&HomebrewType> {
+        self.0.iter().find(|homebrew| homebrew.name.eq_ci(name))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &HomebrewType> {
+        self.0.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Add a homebrew type, replacing any existing one with the same name.
+    pub fn insert(&mut self, homebrew: HomebrewType) {
+        if let Some(existing) = self.0.iter_mut().find(|h| h.name.eq_ci(&homebrew.name)) {
+            *existing = homebrew;
+        } else {
+            self.0.push(homebrew);
+        }
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<HomebrewType> {
+        let index = self
+            .0
+            .iter()
+            .position(|homebrew| homebrew.name.eq_ci(name))?;
+        Some(self.0.remove(index))
+    }
+
+    /// Find the homebrew type named in the input as a whole word, eg. `adult warforged named
+    /// Bolt`, returning it along with where its name appears. The longest matching name wins.
+    pub fn find(&self, input: &str) -> Option<(&HomebrewType, Range<usize>)> {
+        self.0
+            .iter()
+            .filter_map(|homebrew| {
+                input
+                    .char_indices()
+                    .map(|(i, _)| i)
+                    .filter(|&i| {
+                        input[..i]
+                            .chars()
+                            .next_back()
+                            .is_none_or(char::is_whitespace)
+                    })
+                    .find(|&i| {
+                        let rest = &input[i..];
+
+                        rest.starts_with_ci(&homebrew.name)
+                            && rest.get(homebrew.name.len()..).is_some_and(|after| {
+                                after.is_empty() || after.starts_with(char::is_whitespace)
+                            })
+                    })
+                    .map(|i| (homebrew, i..i + homebrew.name.len()))
+            })
+            .max_by_key(|(homebrew, _)| homebrew.name.len())
+    }
+
+    /// Apply the homebrew types to a freshly generated thing. A character whose species wasn't
+    /// specified may turn out to be of a homebrew species, according to its share of the
+    /// population, and anything of a homebrew type with names of its own is given one of them.
+    ///
+    /// Only unlocked fields are replaced, so anything specified by the user is left untouched.
+    pub fn apply(&self, rng: &mut impl Rng, demographics: &Demographics, thing: &mut Thing) {
+        if let Thing::Npc(npc) = thing {
+            if npc.species.is_unlocked() && npc.homebrew.is_unlocked() && npc.homebrew.is_none() {
+                let roll = rng.gen_range(0..100u16);
+                let mut total = 0u16;
+
+                let rolled =
+                    self.0
+                        .iter()
+                        .find_map(|homebrew| match (homebrew.base, homebrew.share) {
+                            (HomebrewBase::Species(species), Some(share)) => {
+                                total += u16::from(share);
+                                (roll < total).then_some((homebrew, species))
+                            }
+                            _ => None,
+                        });
+
+                if let Some((homebrew, species)) = rolled {
+                    // Generate the character again as its base species, keeping the species
+                    // unlocked afterwards since it wasn't chosen by the user.
+                    npc.species = Field::new(species);
+                    npc.ethnicity.clear();
+                    npc.regenerate(rng, demographics);
+                    npc.species.unlock();
+                    npc.homebrew.replace(homebrew.name.clone());
+                }
+            }
+        }
+
+        let (homebrew, name) = match thing {
+            Thing::Npc(npc) => (&npc.homebrew, &mut npc.name),
+            Thing::Place(place) => (&place.homebrew, &mut place.name),
+        };
+
+        if let Some(value) = homebrew
+            .value()
+            .and_then(|homebrew| self.get(homebrew))
+            .and_then(|homebrew| homebrew.names.choose(rng))
+        {
+            name.replace(value.clone());
+        }
+    }
+}
+
+impl HomebrewType {
+    pub fn new(
+        name: &str,
+        base: HomebrewBase,
+        names: Vec<String>,
+        share: Option<u8>,
+    ) -> Result<Self, HomebrewError> {
+        let name = name.trim();
+
+        if name.is_empty() || name.contains('\n') {
+            Err(HomebrewError::MissingName)
+        } else if name.parse::<Species>().is_ok() || name.parse::<PlaceType>().is_ok() {
+            Err(HomebrewError::BuiltIn(name.to_string()))
+        } else if share.is_some_and(|share| !(1..=100).contains(&share)) {
+            Err(HomebrewError::InvalidShare(format!("{}%", share.unwrap())))
+        } else {
+            Ok(Self {
+                name: name.to_string(),
+                base,
+                names,
+                share,
+            })
+        }
+    }
+
+    pub fn is_species(&self) -> bool {
+        matches!(self.base, HomebrewBase::Species(_))
+    }
+
+    /// Parse a description naming the homebrew type at the given range, as found by
+    /// [`Homebrew::find`], as though the base had been named instead. Unknown words are reported
+    /// relative to the original description.
+    pub fn instantiate(&self, input: &str, range: Range<usize>) -> Result<ParsedThing<Thing>, ()> {
+        let base = self.base.to_string();
+        let mut parsed: ParsedThing<Thing> =
+            format!("{}{}{}", &input[..range.start], base, &input[range.end..]).parse()?;
+
+        // Words following the base move back to where they were in the original description.
+        let base_end = range.start + base.len();
+        parsed.unknown_words = parsed
+            .unknown_words
+            .into_iter()
+            .map(|unknown| {
+                if unknown.start >= base_end {
+                    unknown.start - base_end + range.end..unknown.end - base_end + range.end
+                } else {
+                    unknown
+                }
+            })
+            .collect();
+
+        match &mut parsed.thing {
+            Thing::Npc(npc) => npc.homebrew = Field::new(self.name.clone()),
+            Thing::Place(place) => place.homebrew = Field::new(self.name.clone()),
+        }
+
+        Ok(parsed)
+    }
+
+    /// Parse the part of a definition following `species` or `place type`, eg.
+    /// `warforged like dwarf as 5% of the population with names like Anvil, Bolt`.
+    fn parse_definition(input: &str, is_species: bool) -> Result<Self, HomebrewError> {
+        let (input, names) = match split_once_ci(input, " with names like ") {
+            Some((input, names)) => (
+                input,
+                names
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+            ),
+            None => (input, Vec::new()),
+        };
+
+        let (input, share) = match split_once_ci(input, " as ") {
+            Some((input, share)) if is_species => {
+                let share = share.trim();
+                let raw = share
+                    .strip_suffix_ci(" of the population")
+                    .unwrap_or(share)
+                    .trim();
+
+                let share = raw
+                    .strip_suffix('%')
+                    .and_then(|s| s.trim().parse::<u8>().ok())
+                    .ok_or_else(|| HomebrewError::InvalidShare(raw.to_string()))?;
+
+                (input, Some(share))
+            }
+            _ => (input, None),
+        };
+
+        let (name, base) = match split_once_ci(input, " like ") {
+            Some((name, base)) => {
+                let base = base.trim();
+
+                (
+                    name,
+                    if is_species {
+                        base.parse().map(HomebrewBase::Species)
+                    } else {
+                        base.parse().map(HomebrewBase::PlaceType)
+                    }
+                    .map_err(|_| HomebrewError::UnknownBase(base.to_string()))?,
+                )
+            }
+            None if is_species => (input, HomebrewBase::Species(Species::Human)),
+            None => (input, HomebrewBase::PlaceType(PlaceType::Any)),
+        };
+
+        Self::new(name, base, names, share)
+    }
+}
+
+/// Split the input at the first occurrence of the separator, ignoring case.
+fn split_once_ci<'a>(input: &'a str, separator: &str) -> Option<(&'a str, &'a str)> {
+    input
+        .char_indices()
+        .find(|(i, _)| (&input[*i..]).starts_with_ci(separator))
+        .map(|(i, _)| (&input[..i], &input[i + separator.len()..]))
+}
+
+impl fmt::Display for Homebrew {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut homebrew = self.0.iter();
+
+        if let Some(homebrew_type) = homebrew.next() {
+            write!(f, "{}", homebrew_type)?;
+        }
+
+        homebrew.try_for_each(|homebrew_type| write!(f, "\n{}", homebrew_type))
+    }
+}
+
+impl FromStr for Homebrew {
+    type Err = ();
+
+    /// Lines that can't be parsed are skipped rather than invalidating every homebrew type.
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        Ok(Self(
+            raw.lines().filter_map(|line| line.parse().ok()).collect(),
+        ))
+    }
+}
+
+impl fmt::Display for HomebrewType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.base {
+            HomebrewBase::Species(_) => write!(f, "species {} like {}", self.name, self.base)?,
+            HomebrewBase::PlaceType(_) => write!(f, "place type {} like {}", self.name, self.base)?,
+        }
+
+        if let Some(share) = self.share {
+            write!(f, " as {}% of the population", share)?;
+        }
+
+        if !self.names.is_empty() {
+            write!(f, " with names like {}", self.names.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for HomebrewType {
+    type Err = HomebrewError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        if let Some(definition) = raw.strip_prefix_ci("species ") {
+            Self::parse_definition(definition, true)
+        } else if let Some(definition) = raw.strip_prefix_ci("place type ") {
+            Self::parse_definition(definition, false)
+        } else {
+            Err(HomebrewError::MissingName)
+        }
+    }
+}
+
+impl fmt::Display for HomebrewBase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Species(species) => write!(f, "{}", species),
+            Self::PlaceType(place_type) => write!(f, "{}", place_type),
+        }
+    }
+}
+
+impl fmt::Display for HomebrewError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::MissingName => write!(f, "The homebrew type must have a name."),
+            Self::BuiltIn(name) => write!(f, "\"{}\" is already built in.", name),
+            Self::UnknownBase(base) => write!(
+                f,
+                "\"{}\" isn't a species or place type that the homebrew type can be based on.",
+                base,
+            ),
+            Self::InvalidShare(share) => write!(
+                f,
+                "\"{}\" isn't a valid share of the population. Use a percentage from 1% to 100%.",
+                share,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::world::npc::Age;
+    use crate::world::{Npc, Place};
+
+    #[test]
+    fn homebrew_type_from_str_test() {
+        let homebrew: HomebrewType =
+            "species Warforged like DWARF as 5% of the population with names like Anvil, Bolt ,"
+                .parse()
+                .unwrap();
+        assert_eq!(
+            HomebrewType {
+                name: "Warforged".to_string(),
+                base: HomebrewBase::Species(Species::Dwarf),
+                names: vec!["Anvil".to_string(), "Bolt".to_string()],
+                share: Some(5),
+            },
+            homebrew,
+        );
+        assert_eq!(
+            "species Warforged like dwarf as 5% of the population with names like Anvil, Bolt",
+            homebrew.to_string(),
+        );
+
+        assert_eq!(
+            Ok(HomebrewType {
+                name: "kenku".to_string(),
+                base: HomebrewBase::Species(Species::Human),
+                names: Vec::new(),
+                share: None,
+            }),
+            "species kenku".parse(),
+        );
+
+        assert_eq!(
+            Ok(HomebrewType {
+                name: "teahouse".to_string(),
+                base: HomebrewBase::PlaceType("inn".parse().unwrap()),
+                names: vec!["The Jade Cup".to_string()],
+                share: None,
+            }),
+            "place type teahouse like inn with names like The Jade Cup".parse(),
+        );
+
+        assert_eq!(
+            Err(HomebrewError::BuiltIn("elf".to_string())),
+            "species elf like human".parse::<HomebrewType>(),
+        );
+        assert_eq!(
+            Err(HomebrewError::UnknownBase("potato".to_string())),
+            "species warforged like potato".parse::<HomebrewType>(),
+        );
+        assert_eq!(
+            Err(HomebrewError::InvalidShare("lots".to_string())),
+            "species warforged as lots".parse::<HomebrewType>(),
+        );
+        assert_eq!(
+            Err(HomebrewError::InvalidShare("0%".to_string())),
+            "species warforged as 0%".parse::<HomebrewType>(),
+        );
+        assert_eq!(
+            Err(HomebrewError::MissingName),
+            "species  like dwarf".parse::<HomebrewType>(),
+        );
+    }
+
+    #[test]
+    fn instantiate_test() {
+        let homebrew: HomebrewType = "species warforged like dwarf".parse().unwrap();
+
+        let parsed = homebrew
+            .instantiate("adult warforged potato", 6..15)
+            .unwrap();
+        assert_eq!(vec![16..22], parsed.unknown_words);
+
+        if let Thing::Npc(Npc {
+            species,
+            age,
+            homebrew,
+            ..
+        }) = parsed.thing
+        {
+            assert_eq!(Some(&Species::Dwarf), species.value());
+            assert_eq!(Some(&Age::Adult), age.value());
+            assert_eq!(Some("warforged"), homebrew.value().map(String::as_str));
+        } else {
+            panic!("{:?}", parsed.thing);
+        }
+    }
+
+    #[test]
+    fn apply_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let demographics = Demographics::default();
+        let mut homebrew = Homebrew::default();
+        homebrew.insert(
+            "species warforged like dwarf as 100% of the population with names like Anvil"
+                .parse()
+                .unwrap(),
+        );
+        homebrew.insert(
+            "place type teahouse like inn with names like The Jade Cup"
+                .parse()
+                .unwrap(),
+        );
+
+        let mut thing: Thing = Npc::default().into();
+        thing.regenerate(&mut rng, &demographics);
+        homebrew.apply(&mut rng, &demographics, &mut thing);
+
+        if let Thing::Npc(npc) = &thing {
+            assert_eq!(Some(&Species::Dwarf), npc.species.value());
+            assert!(npc.species.is_unlocked());
+            assert_eq!(Some("warforged"), npc.homebrew.value().map(String::as_str));
+            assert_eq!(Some("Anvil"), npc.name.value().map(String::as_str));
+        } else {
+            panic!("{:?}", thing);
+        }
+
+        // The user asked for an elf, so that's what they get.
+        let mut thing: Thing = Npc {
+            species: Species::Elf.into(),
+            ..Default::default()
+        }
+        .into();
+        thing.regenerate(&mut rng, &demographics);
+        homebrew.apply(&mut rng, &demographics, &mut thing);
+        assert_eq!(Some(&Species::Elf), thing.npc().unwrap().species.value());
+        assert!(thing.npc().unwrap().homebrew.is_none());
+
+        let (teahouse, range) = homebrew.find("teahouse").unwrap();
+        let mut thing = teahouse.instantiate("teahouse", range).unwrap().thing;
+        thing.regenerate(&mut rng, &demographics);
+        homebrew.apply(&mut rng, &demographics, &mut thing);
+        assert_eq!("The Jade Cup", thing.name().to_string());
+        assert!(matches!(thing, Thing::Place(Place { .. })));
+    }
+
+    #[test]
+    fn homebrew_test() {
+        let mut homebrew = Homebrew::default();
+        homebrew.insert("species warforged like dwarf".parse().unwrap());
+        homebrew.insert("place type teahouse like inn".parse().unwrap());
+        homebrew.insert("species Warforged like human".parse().unwrap());
+
+        assert_eq!(
+            "species Warforged like human\nplace type teahouse like inn",
+            homebrew.to_string(),
+        );
+        assert_eq!(Ok(homebrew.clone()), homebrew.to_string().parse());
+
+        assert_eq!(
+            Some(("Warforged", 6..15)),
+            homebrew
+                .find("adult warforged named Bolt")
+                .map(|(h, range)| (h.name.as_str(), range)),
+        );
+        assert!(homebrew.find("warforgeds").is_none());
+        assert!(homebrew.find("unwarforged").is_none());
+
+        assert!(homebrew.remove("WARFORGED").is_some());
+        assert!(homebrew.remove("warforged").is_none());
+        assert_eq!("place type teahouse like inn", homebrew.to_string());
+    }
+}
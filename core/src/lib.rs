@@ -8,22 +8,42 @@
 
 pub mod app;
 
-pub use app::{App, Event};
+pub use app::{App, Event, TraceEvent, TraceHook};
 pub use storage::backup::BackupData;
-pub use storage::{DataStore, MemoryDataStore, NullDataStore};
+pub use storage::{normalize, normalize_prefix, DataStore, MemoryDataStore, NullDataStore};
+pub use utils::MaybeSync;
 pub use uuid::Uuid;
 pub use world::Thing;
 
+mod campaign;
+mod clue;
+mod config;
+mod history;
+mod homebrew;
+mod locale;
+mod portent;
 mod reference;
+mod religion;
+mod scene;
+mod session;
 mod storage;
+mod table;
+mod template;
+mod thread;
 mod time;
+mod treasure;
+mod treasury;
 mod utils;
+mod war;
 mod world;
 
 /// Creates a new instance of the application wrapper. The `data_store` is used to save and load
 /// data from storage, and the `event_dispatcher` is a callback function invoked whenever an
 /// event occurs in-app that may require special handling by the UI. See [`Event`] for details.
-pub fn app<F: Fn(Event)>(
+///
+/// With the `sync` feature enabled, both must be `Send + Sync`, and in exchange the app can be
+/// moved between threads.
+pub fn app<F: Fn(Event) + MaybeSync>(
     data_store: impl DataStore + 'static,
     event_dispatcher: &'static F,
 ) -> app::App {
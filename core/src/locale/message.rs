@@ -0,0 +1,166 @@
+use super::Language;
+use crate::utils::{CodeView, EscapedView};
+
+/// A short message shown in response to a command, such as an error or a note that an entry was
+/// changed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Message<'a> {
+    NoEntityNamed { name: &'a str },
+    NoCharacterNamed { name: &'a str },
+    NoCharacterOrPlaceNamed { name: &'a str },
+    NameInUse,
+    NameInUseBy { summary: &'a str },
+    NameAlreadyInUse { name: &'a str, summary: &'a str },
+    BlankName,
+    UnknownError,
+    CouldNotEdit { name: &'a str },
+    CouldNotSave { name: &'a str },
+    CouldNotRename { name: &'a str },
+    Edited { name: &'a str },
+    EditedAndSaved { name: &'a str },
+    Renamed { name: &'a str, new_name: &'a str },
+    RenamedAndSaved { name: &'a str, new_name: &'a str },
+}
+
+impl<'a> Message<'a> {
+    pub fn localize(&self, language: Language) -> String {
+        match language {
+            Language::English => self.english(),
+            Language::Spanish => self.spanish(),
+        }
+    }
+
+    fn english(&self) -> String {
+        match self {
+            Self::NoEntityNamed { name } => format!(r#"There is no entity named "{}"."#, EscapedView::new(name)),
+            Self::NoCharacterNamed { name } => {
+                format!(r#"There is no character named "{}"."#, EscapedView::new(name))
+            }
+            Self::NoCharacterOrPlaceNamed { name } => {
+                format!(r#"There is no character or place named "{}"."#, EscapedView::new(name))
+            }
+            Self::NameInUse => "That name is already in use.".to_string(),
+            Self::NameInUseBy { summary } => format!("That name is already in use by {}.", summary),
+            Self::NameAlreadyInUse { name, summary } => {
+                format!(r#""{}" is already in use by {}."#, EscapedView::new(name), summary)
+            }
+            Self::BlankName => "The new name can't be blank.".to_string(),
+            Self::UnknownError => "An error occurred.".to_string(),
+            Self::CouldNotEdit { name } => format!("Couldn't edit {}.", CodeView::new(name)),
+            Self::CouldNotSave { name } => format!("Couldn't save {}.", CodeView::new(name)),
+            Self::CouldNotRename { name } => format!("Couldn't rename {}.", CodeView::new(name)),
+            Self::Edited { name } => format!(
+                "_{} was successfully edited. Use `undo` to reverse this._",
+                EscapedView::new(name),
+            ),
+            Self::EditedAndSaved { name } => format!(
+                "_{} was successfully edited and automatically saved to your `journal`. Use `undo` to reverse this._",
+                EscapedView::new(name),
+            ),
+            Self::Renamed { name, new_name } => format!(
+                "_{} was successfully renamed to {}. Use `undo` to reverse this._",
+                EscapedView::new(name),
+                EscapedView::new(new_name),
+            ),
+            Self::RenamedAndSaved { name, new_name } => format!(
+                "_{} was successfully renamed to {} and automatically saved to your `journal`. Use `undo` to reverse this._",
+                EscapedView::new(name),
+                EscapedView::new(new_name),
+            ),
+        }
+    }
+
+    fn spanish(&self) -> String {
+        match self {
+            Self::NoEntityNamed { name } => format!(r#"No hay ninguna entrada llamada "{}"."#, EscapedView::new(name)),
+            Self::NoCharacterNamed { name } => {
+                format!(r#"No hay ningún personaje llamado "{}"."#, EscapedView::new(name))
+            }
+            Self::NoCharacterOrPlaceNamed { name } => {
+                format!(r#"No hay ningún personaje ni lugar llamado "{}"."#, EscapedView::new(name))
+            }
+            Self::NameInUse => "Ese nombre ya está en uso.".to_string(),
+            Self::NameInUseBy { summary } => format!("Ese nombre ya lo usa {}.", summary),
+            Self::NameAlreadyInUse { name, summary } => {
+                format!(r#""{}" ya lo usa {}."#, EscapedView::new(name), summary)
+            }
+            Self::BlankName => "El nuevo nombre no puede estar vacío.".to_string(),
+            Self::UnknownError => "Se produjo un error.".to_string(),
+            Self::CouldNotEdit { name } => format!("No se pudo editar {}.", CodeView::new(name)),
+            Self::CouldNotSave { name } => format!("No se pudo guardar {}.", CodeView::new(name)),
+            Self::CouldNotRename { name } => format!("No se pudo renombrar {}.", CodeView::new(name)),
+            Self::Edited { name } => format!(
+                "_{} se editó correctamente. Usa `undo` para deshacerlo._",
+                EscapedView::new(name),
+            ),
+            Self::EditedAndSaved { name } => format!(
+                "_{} se editó correctamente y se guardó automáticamente en tu `journal`. Usa `undo` para deshacerlo._",
+                EscapedView::new(name),
+            ),
+            Self::Renamed { name, new_name } => format!(
+                "_{} pasó a llamarse {}. Usa `undo` para deshacerlo._",
+                EscapedView::new(name),
+                EscapedView::new(new_name),
+            ),
+            Self::RenamedAndSaved { name, new_name } => format!(
+                "_{} pasó a llamarse {} y se guardó automáticamente en tu `journal`. Usa `undo` para deshacerlo._",
+                EscapedView::new(name),
+                EscapedView::new(new_name),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn localize_test() {
+        let message = Message::NoEntityNamed { name: "Potato" };
+
+        assert_eq!(
+            r#"There is no entity named "Potato"."#,
+            message.localize(Language::English),
+        );
+        assert_eq!(
+            r#"No hay ninguna entrada llamada "Potato"."#,
+            message.localize(Language::Spanish),
+        );
+    }
+
+    #[test]
+    fn localize_test_name_already_in_use() {
+        let message = Message::NameAlreadyInUse {
+            name: "Éowyn",
+            summary: "`Éowyn` (human)",
+        };
+
+        assert_eq!(
+            r#""Éowyn" is already in use by `Éowyn` (human)."#,
+            message.localize(Language::English),
+        );
+        assert_eq!(
+            r#""Éowyn" ya lo usa `Éowyn` (human)."#,
+            message.localize(Language::Spanish),
+        );
+    }
+
+    #[test]
+    fn localize_test_escaped() {
+        assert_eq!(
+            "_\\~delete Gottfried\\~ was successfully edited. Use `undo` to reverse this._",
+            Message::Edited {
+                name: "~delete Gottfried~",
+            }
+            .localize(Language::English),
+        );
+        assert_eq!(
+            "Couldn't save ``Potato`Johnson``.",
+            Message::CouldNotSave {
+                name: "Potato`Johnson",
+            }
+            .localize(Language::English),
+        );
+    }
+}
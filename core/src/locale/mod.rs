@@ -0,0 +1,79 @@
+//! User-facing text that has been translated out of English. Output is still predominantly
+//! English, but messages are gradually being moved behind [`Message`] and [`TutorialPage`] so that
+//! they can be rendered in the language selected with `config language`. Anything without a
+//! translation falls back to English.
+//!
+//! Commands themselves are always entered in English, so translated text continues to refer to
+//! them by their English names.
+
+pub use message::Message;
+pub use tutorial::TutorialPage;
+
+mod message;
+mod tutorial;
+
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+}
+
+impl Language {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Self::English => "English",
+            Self::Spanish => "Español",
+        }
+    }
+}
+
+/// Written as the ISO 639-1 code, which is how the language is persisted in the configuration.
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::English => write!(f, "en"),
+            Self::Spanish => write!(f, "es"),
+        }
+    }
+}
+
+impl FromStr for Language {
+    type Err = ();
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        match raw.trim().to_lowercase().as_str() {
+            "en" | "english" => Ok(Self::English),
+            "es" | "spanish" | "español" | "espanol" => Ok(Self::Spanish),
+            _ => Err(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn language_from_str_test() {
+        assert_eq!(Ok(Language::English), "en".parse());
+        assert_eq!(Ok(Language::English), "English".parse());
+        assert_eq!(Ok(Language::Spanish), "ES".parse());
+        assert_eq!(Ok(Language::Spanish), "español".parse());
+        assert_eq!(Err(()), "klingon".parse::<Language>());
+    }
+
+    #[test]
+    fn language_display_test() {
+        [Language::English, Language::Spanish]
+            .into_iter()
+            .for_each(|language| {
+                assert_eq!(Ok(language), language.to_string().parse());
+            });
+
+        assert_eq!("Español", Language::Spanish.get_name());
+    }
+}
@@ -0,0 +1,121 @@
+use super::Language;
+
+/// A page of the tutorial. Some pages refer to the inn and character generated earlier in the
+/// tutorial, which are substituted by [`TutorialPage::render`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TutorialPage {
+    Introduction,
+    GeneratingLocations,
+    SavingLocations,
+    GeneratingCharacters,
+    GeneratingAlternatives,
+    ViewingAlternatives,
+    EditingCharacters,
+    TheJournal,
+    LoadingFromJournal,
+    SrdReference,
+    SrdReferenceLists,
+    RollingDice,
+    DeletingThings,
+    AdvancingTime,
+    CheckingTheTime,
+    Conclusion,
+    Cancelled,
+    Exited,
+    StillActive,
+}
+
+impl TutorialPage {
+    /// The page text in the given language, with any `{inn_name}` and `{npc_name}` placeholders
+    /// filled in.
+    pub fn render(&self, language: Language, inn_name: &str, npc_name: &str) -> String {
+        self.text(language)
+            .replace("{inn_name}", inn_name)
+            .replace("{npc_name}", npc_name)
+    }
+
+    /// The raw page text, falling back to English for pages that haven't been translated.
+    pub fn text(&self, language: Language) -> &'static str {
+        match (language, self) {
+            (Language::Spanish, Self::Conclusion) => {
+                include_str!("../../../data/locale/es/tutorial/99-conclusion.md")
+            }
+            (Language::Spanish, Self::Cancelled) => {
+                include_str!("../../../data/locale/es/tutorial/xx-cancelled.md")
+            }
+            (Language::Spanish, Self::Exited) => {
+                include_str!("../../../data/locale/es/tutorial/xx-exited.md")
+            }
+            (Language::Spanish, Self::StillActive) => {
+                include_str!("../../../data/locale/es/tutorial/xx-still-active.md")
+            }
+            (_, page) => page.english(),
+        }
+    }
+
+    fn english(&self) -> &'static str {
+        match self {
+            Self::Introduction => include_str!("../../../data/tutorial/00-introduction.md"),
+            Self::GeneratingLocations => {
+                include_str!("../../../data/tutorial/01-generating-locations.md")
+            }
+            Self::SavingLocations => include_str!("../../../data/tutorial/02-saving-locations.md"),
+            Self::GeneratingCharacters => {
+                include_str!("../../../data/tutorial/03-generating-characters.md")
+            }
+            Self::GeneratingAlternatives => {
+                include_str!("../../../data/tutorial/04-generating-alternatives.md")
+            }
+            Self::ViewingAlternatives => {
+                include_str!("../../../data/tutorial/05-viewing-alternatives.md")
+            }
+            Self::EditingCharacters => {
+                include_str!("../../../data/tutorial/06-editing-characters.md")
+            }
+            Self::TheJournal => include_str!("../../../data/tutorial/07-the-journal.md"),
+            Self::LoadingFromJournal => {
+                include_str!("../../../data/tutorial/08-loading-from-journal.md")
+            }
+            Self::SrdReference => include_str!("../../../data/tutorial/09-srd-reference.md"),
+            Self::SrdReferenceLists => {
+                include_str!("../../../data/tutorial/10-srd-reference-lists.md")
+            }
+            Self::RollingDice => include_str!("../../../data/tutorial/11-rolling-dice.md"),
+            Self::DeletingThings => include_str!("../../../data/tutorial/12-deleting-things.md"),
+            Self::AdvancingTime => include_str!("../../../data/tutorial/13-advancing-time.md"),
+            Self::CheckingTheTime => include_str!("../../../data/tutorial/14-checking-the-time.md"),
+            Self::Conclusion => include_str!("../../../data/tutorial/99-conclusion.md"),
+            Self::Cancelled => include_str!("../../../data/tutorial/xx-cancelled.md"),
+            Self::Exited => include_str!("../../../data/tutorial/xx-exited.md"),
+            Self::StillActive => include_str!("../../../data/tutorial/xx-still-active.md"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn render_test() {
+        let page = TutorialPage::SavingLocations.render(Language::English, "Potato Inn", "");
+        assert!(page.contains("Potato Inn"), "{}", page);
+        assert!(!page.contains("{inn_name}"), "{}", page);
+    }
+
+    #[test]
+    fn text_test() {
+        assert_eq!(
+            "The tutorial has been cancelled.\n",
+            TutorialPage::Cancelled.text(Language::English),
+        );
+        assert_eq!(
+            "El tutorial ha sido cancelado.\n",
+            TutorialPage::Cancelled.text(Language::Spanish),
+        );
+        assert_eq!(
+            TutorialPage::Introduction.text(Language::English),
+            TutorialPage::Introduction.text(Language::Spanish),
+        );
+    }
+}
@@ -0,0 +1,445 @@
+use super::{Kind, Portent, Portents, Subject};
+use crate::app::{
+    AppMeta, Autocomplete, AutocompleteSuggestion, CommandError, CommandMatches, ContextAwareParse,
+    Runnable,
+};
+use crate::storage::{Change, KeyValue};
+use crate::utils::{capitalize, quoted_words, CaseInsensitiveStr};
+use crate::world::Thing;
+use async_trait::async_trait;
+use std::fmt;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PortentCommand {
+    Create {
+        kind: Kind,
+        subject: Option<String>,
+        item: Option<String>,
+    },
+    Portents,
+}
+
+impl PortentCommand {
+    /// Parse "curse Gimli" or "curse longsword held by Gimli", following the kind of portent.
+    /// Only curses can be laid on items, while prophecies and omens are "about" something.
+    fn parse_create(kind: Kind, input: &str) -> Option<Self> {
+        let (subject, item) = match kind {
+            Kind::Curse => match split_at_held_by(input) {
+                Some((item, subject)) => (subject, Some(item)),
+                None => (input.trim(), None),
+            },
+            Kind::Omen | Kind::Prophecy => (input.strip_prefix_ci("about ")?.trim(), None),
+        };
+
+        if subject.is_empty() {
+            None
+        } else {
+            Some(Self::Create {
+                kind,
+                subject: Some(subject.to_string()),
+                item: item.map(str::to_string),
+            })
+        }
+    }
+
+    /// Lay the portent on its subject, if any, and record it.
+    async fn create(
+        kind: Kind,
+        subject: Option<String>,
+        item: Option<String>,
+        app_meta: &mut AppMeta,
+    ) -> Result<String, CommandError> {
+        let subject = if let Some(name) = subject {
+            let thing = app_meta.repository.get_by_name(&name).await.map_err(|_| {
+                CommandError::unknown_entity(&name, format!(r#"No matches for "{}""#, name))
+            })?;
+
+            let uuid = *thing.uuid().ok_or_else(|| {
+                format!(
+                    "{} hasn't been saved to your `journal` yet. Use `save {}` first.",
+                    thing.name(),
+                    thing.name(),
+                )
+            })?;
+
+            let item = if let Some(item) = item {
+                let inventory = match &thing {
+                    Thing::Npc(npc) => npc.inventory.value(),
+                    Thing::Place(place) => place.inventory.value(),
+                };
+
+                Some(
+                    inventory
+                        .and_then(|inventory| inventory.items.iter().find(|i| i.name.eq_ci(&item)))
+                        .map(|i| i.name.clone())
+                        .ok_or_else(|| {
+                            CommandError::unknown_entity(
+                                &item,
+                                format!("There is no {} in {}'s inventory.", item, thing.name()),
+                            )
+                        })?,
+                )
+            } else {
+                None
+            };
+
+            Some((thing.name().to_string(), Subject { uuid, item }))
+        } else {
+            None
+        };
+
+        let mut portent = Portent::generate(
+            kind,
+            subject.as_ref().map(|(name, _)| name.as_str()),
+            &mut app_meta.rng,
+        );
+
+        let mut output = format!(
+            "# {}\n\n*{}*\n\n**Trigger:** {}\\\n**Effect:** {}\\\n**Escape:** {}",
+            capitalize(kind.as_str()),
+            portent,
+            portent.trigger,
+            portent.effect,
+            portent.escape,
+        );
+
+        output.push_str(&match &subject {
+            Some((name, Subject { item: Some(item), .. })) => format!(
+                "\n\n_The {} has been laid on {}'s {}, and is shown with `{}` in your `journal`. Use `undo` to lift it._",
+                kind, name, item, name,
+            ),
+            Some((name, Subject { item: None, .. })) => format!(
+                "\n\n_The {} has been attached to `{}` in your `journal`. Use `undo` to remove it._",
+                kind, name,
+            ),
+            None => format!(
+                "\n\n_The {} has been recorded. Use `portents` to see it again, or `undo` to remove it._",
+                kind,
+            ),
+        });
+
+        portent.subject = subject.map(|(_, subject)| subject);
+
+        let mut portents = super::load_portents(app_meta).await;
+        portents.push(portent);
+        save(portents, app_meta).await?;
+
+        Ok(output)
+    }
+}
+
+/// Split "longsword held by Gimli" into the item and its holder.
+fn split_at_held_by(input: &str) -> Option<(&str, &str)> {
+    let words: Vec<_> = quoted_words(input).collect();
+
+    words
+        .windows(2)
+        .skip(1)
+        .rfind(|pair| pair[0].as_str().eq_ci("held") && pair[1].as_str().eq_ci("by"))
+        .map(|pair| {
+            (
+                input[..pair[0].range().start].trim(),
+                input[pair[1].range().end..].trim(),
+            )
+        })
+        .filter(|(item, holder)| !item.is_empty() && !holder.is_empty())
+}
+
+async fn save(portents: Portents, app_meta: &mut AppMeta) -> Result<(), CommandError> {
+    app_meta
+        .repository
+        .modify(Change::SetKeyValue {
+            key_value: KeyValue::Portents(Some(portents)),
+        })
+        .await
+        .map(|_| ())
+        .map_err(|_| CommandError::storage("Unable to save the portent."))
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Runnable for PortentCommand {
+    async fn run(self, _input: &str, app_meta: &mut AppMeta) -> Result<String, CommandError> {
+        match self {
+            Self::Create {
+                kind,
+                subject,
+                item,
+            } => Self::create(kind, subject, item, app_meta).await,
+            Self::Portents => {
+                let portents = super::load_portents(app_meta).await;
+
+                if portents.is_empty() {
+                    return Ok("# Portents\n\n*There are no curses, prophecies, or omens yet. Use `curse [name]`, `prophecy`, or `omen` to create one.*".to_string());
+                }
+
+                let mut output = "# Portents\n".to_string();
+
+                for portent in portents.iter() {
+                    output.push_str(&format!("\n* **{}**", capitalize(portent.kind.as_str())));
+
+                    if let Some(subject) = &portent.subject {
+                        if let Ok(thing) = app_meta.repository.get_by_uuid(&subject.uuid).await {
+                            output.push_str(&format!(" on `{}`", thing.name()));
+
+                            if let Some(item) = &subject.item {
+                                output.push_str(&format!(" ({})", item));
+                            }
+                        }
+                    }
+
+                    output.push_str(&format!(": {}", portent));
+                }
+
+                Ok(output)
+            }
+        }
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl ContextAwareParse for PortentCommand {
+    async fn parse_input(input: &str, _app_meta: &AppMeta) -> CommandMatches<Self> {
+        if input.eq_ci("portents") {
+            return CommandMatches::new_canonical(Self::Portents);
+        }
+
+        let (kind, rest) = input.split_once(' ').unwrap_or((input, ""));
+
+        let kind = match kind.parse::<Kind>() {
+            Ok(kind) => kind,
+            Err(()) => return CommandMatches::default(),
+        };
+
+        if rest.trim().is_empty() {
+            CommandMatches::new_canonical(Self::Create {
+                kind,
+                subject: None,
+                item: None,
+            })
+        } else {
+            Self::parse_create(kind, rest)
+                .map(CommandMatches::new_canonical)
+                .unwrap_or_default()
+        }
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Autocomplete for PortentCommand {
+    async fn autocomplete(input: &str, _app_meta: &AppMeta) -> Vec<AutocompleteSuggestion> {
+        if input.is_empty() {
+            return Vec::new();
+        }
+
+        [
+            ("curse", "generate a curse"),
+            ("curse [name]", "lay a curse on a character or place"),
+            ("curse [item] held by [name]", "lay a curse on an item"),
+            ("omen", "generate an omen"),
+            (
+                "omen about [name]",
+                "generate an omen about a character or place",
+            ),
+            ("portents", "list the curses, prophecies, and omens"),
+            ("prophecy", "generate a prophecy"),
+            (
+                "prophecy about [name]",
+                "generate a prophecy about a character or place",
+            ),
+        ]
+        .into_iter()
+        .filter(|(term, _)| term.starts_with_ci(input))
+        .map(|(term, summary)| AutocompleteSuggestion::new(term, summary))
+        .collect()
+    }
+}
+
+impl fmt::Display for PortentCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::Create {
+                kind,
+                subject,
+                item,
+            } => {
+                write!(f, "{}", kind)?;
+
+                match (kind, subject, item) {
+                    (_, Some(subject), Some(item)) => write!(f, " {} held by {}", item, subject),
+                    (Kind::Curse, Some(subject), None) => write!(f, " {}", subject),
+                    (_, Some(subject), None) => write!(f, " about {}", subject),
+                    (_, None, _) => Ok(()),
+                }
+            }
+            Self::Portents => write!(f, "portents"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::assert_autocomplete;
+    use crate::storage::MemoryDataStore;
+    use crate::world::inventory::{Inventory, Item};
+    use crate::world::Npc;
+    use crate::Event;
+    use tokio_test::block_on;
+
+    #[test]
+    fn parse_input_test() {
+        let app_meta = app_meta();
+
+        assert_eq!(
+            CommandMatches::new_canonical(create(
+                Kind::Curse,
+                Some("Gimli"),
+                Some("Ring of Protection"),
+            )),
+            block_on(PortentCommand::parse_input(
+                "CURSE Ring of Protection held by Gimli",
+                &app_meta,
+            )),
+        );
+        assert_eq!(
+            CommandMatches::new_canonical(create(Kind::Prophecy, Some("Bree"), None)),
+            block_on(PortentCommand::parse_input(
+                "prophecy about Bree",
+                &app_meta
+            )),
+        );
+        assert_eq!(
+            CommandMatches::new_canonical(create(Kind::Omen, None, None)),
+            block_on(PortentCommand::parse_input("omen", &app_meta)),
+        );
+
+        ["prophecy Bree", "omen about", "cursed", "curses"]
+            .into_iter()
+            .for_each(|input| {
+                assert_eq!(
+                    CommandMatches::default(),
+                    block_on(PortentCommand::parse_input(input, &app_meta)),
+                    "{}",
+                    input,
+                );
+            });
+    }
+
+    #[test]
+    fn autocomplete_test() {
+        let app_meta = app_meta();
+
+        assert_autocomplete(
+            &[
+                ("portents", "list the curses, prophecies, and omens"),
+                ("prophecy", "generate a prophecy"),
+                (
+                    "prophecy about [name]",
+                    "generate a prophecy about a character or place",
+                ),
+            ][..],
+            block_on(PortentCommand::autocomplete("P", &app_meta)),
+        );
+    }
+
+    #[test]
+    fn display_test() {
+        let app_meta = app_meta();
+
+        [
+            create(Kind::Curse, None, None),
+            create(Kind::Curse, Some("Gimli"), None),
+            create(Kind::Curse, Some("Gimli"), Some("Longsword")),
+            create(Kind::Omen, Some("Bree"), None),
+            create(Kind::Prophecy, None, None),
+            PortentCommand::Portents,
+        ]
+        .into_iter()
+        .for_each(|command| {
+            let command_string = command.to_string();
+            assert_ne!("", command_string);
+            assert_eq!(
+                CommandMatches::new_canonical(command),
+                block_on(PortentCommand::parse_input(&command_string, &app_meta)),
+                "{}",
+                command_string,
+            );
+        });
+    }
+
+    #[test]
+    fn run_test() {
+        let mut app_meta = app_meta();
+
+        block_on(
+            app_meta.repository.modify(Change::CreateAndSave {
+                thing: Npc {
+                    name: "Gimli".into(),
+                    inventory: Inventory {
+                        items: vec![Item {
+                            name: "Longsword".to_string(),
+                            quantity: 1,
+                            index: None,
+                        }],
+                        ..Default::default()
+                    }
+                    .into(),
+                    ..Default::default()
+                }
+                .into(),
+            }),
+        )
+        .unwrap();
+
+        assert_eq!(
+            Err(CommandError::unknown_entity(
+                "axe",
+                "There is no axe in Gimli's inventory."
+            )),
+            block_on(create(Kind::Curse, Some("Gimli"), Some("axe")).run("", &mut app_meta)),
+        );
+
+        let output =
+            block_on(create(Kind::Curse, Some("gimli"), Some("LONGSWORD")).run("", &mut app_meta))
+                .unwrap();
+        assert!(output.starts_with("# Curse\n\n*"), "{}", output);
+        assert!(output.contains("\n\n**Trigger:** "), "{}", output);
+        assert!(
+            output.ends_with("_The curse has been laid on Gimli's Longsword, and is shown with `Gimli` in your `journal`. Use `undo` to lift it._"),
+            "{}",
+            output,
+        );
+
+        let output = block_on(create(Kind::Prophecy, None, None).run("", &mut app_meta)).unwrap();
+        assert!(
+            output.ends_with("_The prophecy has been recorded. Use `portents` to see it again, or `undo` to remove it._"),
+            "{}",
+            output,
+        );
+
+        let output = block_on(PortentCommand::Portents.run("", &mut app_meta)).unwrap();
+        assert!(
+            output.starts_with("# Portents\n\n* **Curse** on `Gimli` (Longsword): "),
+            "{}",
+            output,
+        );
+        assert!(output.contains("\n* **Prophecy**: "), "{}", output);
+    }
+
+    fn create(kind: Kind, subject: Option<&str>, item: Option<&str>) -> PortentCommand {
+        PortentCommand::Create {
+            kind,
+            subject: subject.map(str::to_string),
+            item: item.map(str::to_string),
+        }
+    }
+
+    fn event_dispatcher(_event: Event) {}
+
+    fn app_meta() -> AppMeta {
+        AppMeta::new(MemoryDataStore::default(), &event_dispatcher)
+    }
+}
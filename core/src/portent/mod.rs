@@ -0,0 +1,312 @@
+pub use command::PortentCommand;
+
+mod command;
+
+use crate::app::AppMeta;
+use crate::storage::KeyValue;
+use crate::Uuid;
+use initiative_macros::WordList;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Stand-ins for the subject of a prophecy or omen that isn't about anything in the journal.
+const STAND_INS: &[&str] = &["the realm", "the old capital", "the valley", "the city"];
+
+const CURSE_TRIGGERS: &[&str] = &[
+    "Whoever spills blood in anger",
+    "The first to break bread after dusk",
+    "Any who speak a falsehood",
+    "Whoever takes what is not freely given",
+    "Whoever turns away a beggar",
+    "The next to cross the threshold at midnight",
+];
+
+const CURSE_EFFECTS: &[&str] = &[
+    "will age a year with every sunset",
+    "will hear the dead whispering their name",
+    "will cast neither reflection nor shadow",
+    "will find that all food turns to ash in their mouth",
+    "will be followed by a murder of crows wherever they go",
+    "will slowly turn to stone, starting with the fingertips",
+];
+
+const CURSE_ESCAPES: &[&str] = &[
+    "unless they make amends to the one they wronged",
+    "until they are forgiven by one they have harmed",
+    "unless the curse is spoken aloud before a holy altar",
+    "until three strangers show them kindness",
+    "unless they bathe in the light of a full moon",
+    "until what was taken is returned",
+];
+
+const PROPHECY_TRIGGERS: &[&str] = &[
+    "When the red star rises over {subject}",
+    "When the last bell of {subject} falls silent",
+    "When a child born at midwinter comes to {subject}",
+    "When the river runs backwards past {subject}",
+    "When three kings break bread with {subject}",
+];
+
+const PROPHECY_EFFECTS: &[&str] = &[
+    "the old blood will wake",
+    "a crown will pass to a stranger's hand",
+    "the dead will walk for a single night",
+    "a great fire will cleanse the land",
+    "the sleeper beneath the hills will rise",
+];
+
+const PROPHECY_ESCAPES: &[&str] = &[
+    "unless the seventh seal holds",
+    "unless one of true heart stands in the doorway",
+    "unless the bell is rung once more",
+    "unless a sacrifice is freely given",
+];
+
+const OMEN_TRIGGERS: &[&str] = &[
+    "Crows gather in silence around {subject}",
+    "The wells near {subject} run red for a day",
+    "A two-headed calf is born within sight of {subject}",
+    "Every candle near {subject} gutters at once",
+    "A white stag is seen near {subject}",
+];
+
+const OMEN_EFFECTS: &[&str] = &[
+    "foretelling a death before the new moon",
+    "foretelling a bountiful harvest",
+    "foretelling the return of an old enemy",
+    "foretelling a betrayal by a trusted friend",
+];
+
+const OMEN_ESCAPES: &[&str] = &[
+    "unless salt is laid on every threshold",
+    "unless the sign is answered with an offering",
+    "unless a priest keeps vigil through the night",
+];
+
+/// The curses, prophecies, and omens of a campaign, in the order they were created.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct Portents(Vec<Portent>);
+
+/// A curse, prophecy, or omen, made up of what sets it off, what comes of it, and how it can be
+/// escaped.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Portent {
+    pub kind: Kind,
+    pub trigger: String,
+    pub effect: String,
+    pub escape: String,
+
+    /// The character, place, or item the portent is attached to, if any.
+    #[serde(default)]
+    pub subject: Option<Subject>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Subject {
+    pub uuid: Uuid,
+
+    /// The name of an item in the inventory of the character or place, for a portent attached to
+    /// the item rather than its holder.
+    #[serde(default)]
+    pub item: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, WordList)]
+#[serde(into = "&'static str", try_from = "&str")]
+pub enum Kind {
+    Curse,
+    Omen,
+    Prophecy,
+}
+
+/// Load the campaign's portents from storage, or none if none have been created yet or the data
+/// store is unavailable.
+pub async fn load_portents(app_meta: &AppMeta) -> Portents {
+    app_meta
+        .repository
+        .get_key_value(&KeyValue::Portents(None))
+        .await
+        .ok()
+        .and_then(KeyValue::portents)
+        .unwrap_or_default()
+}
+
+impl Portents {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Portent> {
+        self.0.iter()
+    }
+
+    pub fn push(&mut self, portent: Portent) {
+        self.0.push(portent);
+    }
+
+    /// The portents attached to the thing with the given UUID, or to any of its items.
+    pub fn about<'a>(&'a self, uuid: &'a Uuid) -> impl Iterator<Item = &'a Portent> {
+        self.0.iter().filter(move |portent| {
+            portent
+                .subject
+                .as_ref()
+                .map_or(false, |subject| &subject.uuid == uuid)
+        })
+    }
+}
+
+impl Portent {
+    /// Generate a portent of the given kind. `subject` is the name of what the portent is about,
+    /// which prophecies and omens weave into their wording.
+    pub fn generate(kind: Kind, subject: Option<&str>, rng: &mut impl Rng) -> Self {
+        let (triggers, effects, escapes) = match kind {
+            Kind::Curse => (CURSE_TRIGGERS, CURSE_EFFECTS, CURSE_ESCAPES),
+            Kind::Omen => (OMEN_TRIGGERS, OMEN_EFFECTS, OMEN_ESCAPES),
+            Kind::Prophecy => (PROPHECY_TRIGGERS, PROPHECY_EFFECTS, PROPHECY_ESCAPES),
+        };
+
+        let subject = subject.unwrap_or_else(|| *STAND_INS.choose(rng).unwrap());
+
+        Self {
+            kind,
+            trigger: triggers.choose(rng).unwrap().replace("{subject}", subject),
+            effect: effects.choose(rng).unwrap().to_string(),
+            escape: escapes.choose(rng).unwrap().to_string(),
+            subject: None,
+        }
+    }
+}
+
+impl fmt::Display for Portents {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            serde_json::to_string(self).map_err(|_| fmt::Error)?,
+        )
+    }
+}
+
+impl FromStr for Portents {
+    type Err = ();
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(raw).map_err(|_| ())
+    }
+}
+
+/// The portent as a single sentence, eg. "Whoever spills blood in anger will age a year with
+/// every sunset, until what was taken is returned."
+impl fmt::Display for Portent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            Kind::Curse => write!(f, "{} {}, {}.", self.trigger, self.effect, self.escape),
+            Kind::Omen | Kind::Prophecy => {
+                write!(f, "{}, {}, {}.", self.trigger, self.effect, self.escape)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn generate_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        for _ in 0..20 {
+            let prophecy = Portent::generate(Kind::Prophecy, Some("Bree"), &mut rng);
+            assert!(prophecy.trigger.contains("Bree"), "{:?}", prophecy);
+            assert!(PROPHECY_EFFECTS.contains(&prophecy.effect.as_str()));
+            assert!(PROPHECY_ESCAPES.contains(&prophecy.escape.as_str()));
+
+            let omen = Portent::generate(Kind::Omen, None, &mut rng);
+            assert!(!omen.trigger.contains('{'), "{:?}", omen);
+
+            let curse = Portent::generate(Kind::Curse, Some("Gimli"), &mut rng);
+            assert!(CURSE_TRIGGERS.contains(&curse.trigger.as_str()));
+            assert_eq!(None, curse.subject);
+        }
+    }
+
+    #[test]
+    fn about_test() {
+        let gimli = Uuid::new_v4();
+        let mut portents = Portents::default();
+        assert!(portents.is_empty());
+
+        portents.push(curse(None));
+        portents.push(curse(Some(Subject {
+            uuid: gimli,
+            item: Some("Longsword".to_string()),
+        })));
+        portents.push(curse(Some(Subject {
+            uuid: Uuid::nil(),
+            item: None,
+        })));
+
+        assert_eq!(3, portents.iter().count());
+        assert_eq!(
+            vec![Some("Longsword")],
+            portents
+                .about(&gimli)
+                .map(|p| p.subject.as_ref().unwrap().item.as_deref())
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn display_test() {
+        let mut portent = curse(None);
+
+        assert_eq!(
+            "Whoever spills blood in anger will age a year with every sunset, until what was taken is returned.",
+            portent.to_string(),
+        );
+
+        portent.kind = Kind::Prophecy;
+        portent.trigger = "When the red star rises over Bree".to_string();
+        portent.effect = "the old blood will wake".to_string();
+        portent.escape = "unless the seventh seal holds".to_string();
+        assert_eq!(
+            "When the red star rises over Bree, the old blood will wake, unless the seventh seal holds.",
+            portent.to_string(),
+        );
+    }
+
+    #[test]
+    fn serialize_deserialize_test() {
+        let portents = Portents(vec![curse(Some(Subject {
+            uuid: Uuid::nil(),
+            item: None,
+        }))]);
+
+        assert_eq!(
+            r#"[{"kind":"curse","trigger":"Whoever spills blood in anger","effect":"will age a year with every sunset","escape":"until what was taken is returned","subject":{"uuid":"00000000-0000-0000-0000-000000000000","item":null}}]"#,
+            portents.to_string(),
+        );
+        assert_eq!(Ok(portents.clone()), portents.to_string().parse());
+        assert_eq!(Ok(Portents::default()), "[]".parse());
+    }
+
+    fn curse(subject: Option<Subject>) -> Portent {
+        Portent {
+            kind: Kind::Curse,
+            trigger: "Whoever spills blood in anger".to_string(),
+            effect: "will age a year with every sunset".to_string(),
+            escape: "until what was taken is returned".to_string(),
+            subject,
+        }
+    }
+}
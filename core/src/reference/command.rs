@@ -1,38 +1,193 @@
-use super::{Condition, Item, ItemCategory, MagicItem, Spell, Trait};
+use super::search::search;
+use super::{
+    ChallengeRating, Class, Condition, Disease, HomebrewEntry, HomebrewEntryKind,
+    HomebrewReference, Item, ItemCategory, Madness, MagicItem, Spell, SpellFilter, Trait,
+};
 use crate::app::{
-    AppMeta, Autocomplete, AutocompleteSuggestion, CommandMatches, ContextAwareParse, Runnable,
+    AppMeta, Autocomplete, AutocompleteSuggestion, CommandError, CommandMatches, ContextAwareParse,
+    Runnable,
 };
-use crate::utils::CaseInsensitiveStr;
+use crate::storage::{Change, KeyValue};
+use crate::utils::{capitalize, CaseInsensitiveStr};
+use crate::world::inventory::Coins;
+use crate::world::party_location;
+use crate::world::place::economy_at;
 use async_trait::async_trait;
 use caith::Roller;
 use std::fmt;
 use std::iter::repeat;
 
+/// The rarities of magic items in the SRD, from least to most rare.
+const RARITIES: &[&str] = &[
+    "common",
+    "uncommon",
+    "rare",
+    "very rare",
+    "legendary",
+    "artifact",
+];
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ReferenceCommand {
+    Class(Class),
     Condition(Condition),
+    Conditions,
+    Disease(Disease),
+    Diseases,
+    FilteredSpells(SpellFilter),
+    Homebrew(HomebrewEntry),
     Item(Item),
     ItemCategory(ItemCategory),
     MagicItem(MagicItem),
+    MagicItems(Option<&'static str>),
+    Madness(Madness),
+    Monster(HomebrewEntry),
     OpenGameLicense,
+    ScaleMonster {
+        name: String,
+        /// The challenge rating as typed if it isn't one, eg. "100", to be reported when run.
+        challenge_rating: Result<ChallengeRating, String>,
+        save_as: Option<String>,
+    },
+    Search(String),
     Spell(Spell),
     Spells,
     Trait(Trait),
 }
 
-#[async_trait(?Send)]
+/// The price of an item where the party is, if the local economy changes it, eg.
+/// "**Price in `Waterdeep`:** 18 gp (+20%)".
+async fn local_price(item: &Item, app_meta: &AppMeta) -> Option<String> {
+    let cost = Coins::from_cp(item.get_cost_cp()? as u64);
+    let economy = economy_at(&party_location(app_meta).await?, app_meta).await?;
+    let modifier = economy.price_modifier(item);
+
+    if modifier == 0 {
+        None
+    } else {
+        Some(format!(
+            "\n\n**Price in {:#}:** {} ({:+}%)",
+            economy.display_name(),
+            economy.local_price(item, cost),
+            modifier,
+        ))
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
 impl Runnable for ReferenceCommand {
-    async fn run(self, _input: &str, _app_meta: &mut AppMeta) -> Result<String, String> {
+    async fn run(self, _input: &str, app_meta: &mut AppMeta) -> Result<String, CommandError> {
         let (output, name) = match self {
+            Self::Class(class) => (format!("{}", class), class.get_name()),
             Self::Condition(condition) => (format!("{}", condition), condition.get_name()),
-            Self::Item(item) => (format!("{}", item), item.get_name()),
+            Self::Conditions => (condition_list(), "This listing"),
+            Self::Disease(disease) => (disease.to_string(), disease.get_name()),
+            Self::Diseases => (Disease::get_list(), "This listing"),
+            Self::FilteredSpells(filter) => {
+                if filter.spells().is_empty() {
+                    return Err(format!("There are no SRD spells matching \"{}\".", filter).into());
+                }
+                (filter.display_table().to_string(), "This listing")
+            }
+            Self::Homebrew(entry) => {
+                return Ok(format!(
+                    "{}\n\n*{} is homebrew content. Use `homebrew list` to see the rest of your homebrew.*",
+                    linkify_dice(&entry.details()),
+                    entry.name(),
+                ));
+            }
+            Self::Item(item) => {
+                let mut output = format!("{}", item);
+
+                if let Some(note) = local_price(&item, app_meta).await {
+                    output.push_str(&note);
+                }
+
+                (output, item.get_name())
+            }
             Self::ItemCategory(category) => (format!("{}", category), "This listing"),
             Self::MagicItem(magic_item) => (format!("{}", magic_item), magic_item.get_name()),
+            Self::MagicItems(rarity) => (magic_item_list(rarity)?, "This listing"),
+            Self::Madness(madness) => (madness.to_string(), madness.get_name()),
+            Self::Monster(monster) => {
+                return Ok(format!(
+                    "{}\n\n*{} is Open Game Content subject to the `Open Game License`.*",
+                    linkify_dice(&monster.details()),
+                    monster.name(),
+                ));
+            }
             Self::OpenGameLicense => {
                 return Ok(include_str!("../../../data/ogl-1.0a.md")
                     .trim_end()
                     .to_string());
             }
+            Self::ScaleMonster {
+                name,
+                challenge_rating,
+                save_as,
+            } => {
+                let challenge_rating = challenge_rating.map_err(|raw| {
+                    format!(
+                        "\"{}\" isn't a challenge rating. Challenge ratings range from 0 to 30, including 1/8, 1/4, and 1/2.",
+                        raw,
+                    )
+                })?;
+
+                let mut homebrew = HomebrewReference::load(app_meta).await;
+
+                // Homebrew monsters take precedence over SRD monsters of the same name.
+                let monster = match homebrew
+                    .get(&name, Some(HomebrewEntryKind::Monster))
+                    .filter(|entry| entry.kind == HomebrewEntryKind::Monster)
+                    .cloned()
+                    .or_else(|| HomebrewEntry::srd_monster(&name))
+                {
+                    Some(monster) => monster,
+                    None => {
+                        return Err(CommandError::unknown_entity(
+                            &name,
+                            format!("There is no monster named \"{}\".", name),
+                        ));
+                    }
+                };
+
+                let scaled = monster.scale(challenge_rating, save_as.as_deref())?;
+                let details = linkify_dice(&scaled.details());
+
+                let save_as = match save_as {
+                    Some(save_as) => save_as,
+                    None => {
+                        return Ok(format!(
+                            "{}\n\n_Use `scale {} to cr {} as [name]` to save this as a homebrew monster._",
+                            details,
+                            monster.name(),
+                            challenge_rating,
+                        ));
+                    }
+                };
+
+                homebrew.merge(vec![scaled]);
+
+                return app_meta
+                    .repository
+                    .modify(Change::SetKeyValue {
+                        key_value: KeyValue::HomebrewReference(Some(homebrew)),
+                    })
+                    .await
+                    .map(|_| {
+                        format!(
+                            "{}\n\n_{} was saved as a homebrew monster. Use `undo` to reverse this._",
+                            details, save_as,
+                        )
+                    })
+                    .map_err(|_| CommandError::storage("Unable to save the homebrew content."));
+            }
+            Self::Search(query) => (
+                search(&query)
+                    .ok_or_else(|| format!("Nothing in the SRD matches \"{}\".", query.trim()))?,
+                "This listing",
+            ),
             Self::Spell(spell) => (format!("{}", spell), spell.get_name()),
             Self::Spells => (Spell::get_list().to_string(), "This listing"),
             Self::Trait(t) => (t.to_string(), t.get_name()),
@@ -46,18 +201,47 @@ impl Runnable for ReferenceCommand {
     }
 }
 
-#[async_trait(?Send)]
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
 impl ContextAwareParse for ReferenceCommand {
-    async fn parse_input(input: &str, _app_meta: &AppMeta) -> CommandMatches<Self> {
+    async fn parse_input(input: &str, app_meta: &AppMeta) -> CommandMatches<Self> {
+        let homebrew = HomebrewReference::load(app_meta).await;
+
         let mut matches = if input.eq_ci("Open Game License") {
             CommandMatches::new_canonical(Self::OpenGameLicense)
+        } else if let Some(rarity) = input.strip_prefix_ci("srd ").and_then(parse_magic_items) {
+            CommandMatches::new_canonical(Self::MagicItems(rarity))
+        } else if let Some(query) = input
+            .strip_prefix_ci("srd search ")
+            .filter(|s| !s.trim().is_empty())
+        {
+            CommandMatches::new_canonical(Self::Search(query.trim().to_lowercase()))
         } else if input.eq_ci("srd spells") {
             CommandMatches::new_canonical(Self::Spells)
+        } else if let Some(filter) = input
+            .strip_prefix_ci("srd spells ")
+            .and_then(SpellFilter::parse)
+        {
+            CommandMatches::new_canonical(Self::FilteredSpells(filter))
+        } else if input.eq_ci("srd conditions") {
+            CommandMatches::new_canonical(Self::Conditions)
+        } else if input.eq_ci("srd diseases") {
+            CommandMatches::new_canonical(Self::Diseases)
+        } else if let Some(class) = input
+            .strip_prefix_ci("srd class ")
+            .and_then(|s| s.parse().ok())
+        {
+            CommandMatches::new_canonical(Self::Class(class))
         } else if let Some(condition) = input
             .strip_prefix_ci("srd condition ")
             .and_then(|s| s.parse().ok())
         {
             CommandMatches::new_canonical(Self::Condition(condition))
+        } else if let Some(disease) = input
+            .strip_prefix_ci("srd disease ")
+            .and_then(|s| s.parse().ok())
+        {
+            CommandMatches::new_canonical(Self::Disease(disease))
         } else if let Some(item_category) = input
             .strip_prefix_ci("srd item category ")
             .and_then(|s| s.parse().ok())
@@ -73,6 +257,11 @@ impl ContextAwareParse for ReferenceCommand {
             .and_then(|s| s.parse().ok())
         {
             CommandMatches::new_canonical(Self::MagicItem(magic_item))
+        } else if let Some(madness) = input
+            .strip_prefix_ci("srd madness ")
+            .and_then(|s| s.parse().ok())
+        {
+            CommandMatches::new_canonical(Self::Madness(madness))
         } else if let Some(spell) = input
             .strip_prefix_ci("srd spell ")
             .and_then(|s| s.parse().ok())
@@ -83,23 +272,54 @@ impl ContextAwareParse for ReferenceCommand {
             .and_then(|s| s.parse().ok())
         {
             CommandMatches::new_canonical(Self::Trait(character_trait))
+        } else if let Some(monster) = input
+            .strip_prefix_ci("srd monster ")
+            .and_then(HomebrewEntry::srd_monster)
+        {
+            CommandMatches::new_canonical(Self::Monster(monster))
+        } else if let Some(command) = parse_scale_monster(input) {
+            CommandMatches::new_canonical(command)
+        } else if let Some(entry) = input.strip_prefix_ci("homebrew ").and_then(|rest| {
+            homebrew.iter().find(|entry| {
+                rest.strip_prefix_ci(entry.kind.to_string())
+                    .and_then(|name| name.strip_prefix(' '))
+                    .is_some_and(|name| name.eq_ci(entry.name()))
+            })
+        }) {
+            CommandMatches::new_canonical(Self::Homebrew(entry.clone()))
         } else {
             CommandMatches::default()
         };
 
+        // Homebrew content takes the place of any SRD spell or item of the same name. The SRD
+        // version remains available with the "srd" prefix, eg. "srd spell fireball".
+        let homebrew_entry = homebrew.get(input, None);
+        if let Some(entry) = homebrew_entry {
+            matches.push_fuzzy(Self::Homebrew(entry.clone()));
+        }
+
+        if let Ok(class) = input.parse() {
+            matches.push_fuzzy(Self::Class(class));
+        }
         if let Ok(condition) = input.parse() {
             matches.push_fuzzy(Self::Condition(condition));
         }
-        if let Ok(item) = input.parse() {
+        if let Ok(disease) = input.parse() {
+            matches.push_fuzzy(Self::Disease(disease));
+        }
+        if let Some(item) = input.parse().ok().filter(|_| homebrew_entry.is_none()) {
             matches.push_fuzzy(Self::Item(item));
         }
         if let Ok(category) = input.parse() {
             matches.push_fuzzy(Self::ItemCategory(category));
         }
-        if let Ok(magic_item) = input.parse() {
+        if let Some(magic_item) = input.parse().ok().filter(|_| homebrew_entry.is_none()) {
             matches.push_fuzzy(Self::MagicItem(magic_item));
         }
-        if let Ok(spell) = input.parse() {
+        if let Ok(madness) = input.parse() {
+            matches.push_fuzzy(Self::Madness(madness));
+        }
+        if let Some(spell) = input.parse().ok().filter(|_| homebrew_entry.is_none()) {
             matches.push_fuzzy(Self::Spell(spell));
         }
         if let Ok(character_trait) = input.parse() {
@@ -108,40 +328,168 @@ impl ContextAwareParse for ReferenceCommand {
         if input.eq_ci("spells") {
             matches.push_fuzzy(Self::Spells);
         }
+        if let Some(filter) = input
+            .strip_prefix_ci("spells ")
+            .and_then(SpellFilter::parse)
+        {
+            matches.push_fuzzy(Self::FilteredSpells(filter));
+        }
+        if input.eq_ci("conditions") {
+            matches.push_fuzzy(Self::Conditions);
+        }
+        if input.eq_ci("diseases") {
+            matches.push_fuzzy(Self::Diseases);
+        }
+        if let Some(rarity) = parse_magic_items(input) {
+            matches.push_fuzzy(Self::MagicItems(rarity));
+        }
 
         matches
     }
 }
 
-#[async_trait(?Send)]
-impl Autocomplete for ReferenceCommand {
-    async fn autocomplete(input: &str, _app_meta: &AppMeta) -> Vec<AutocompleteSuggestion> {
+impl ReferenceCommand {
+    /// Every fixed term that can be autocompleted, with its summary. This is indexed once when the
+    /// app starts up; see [`Vocabulary`](crate::app::Vocabulary).
+    pub fn vocabulary() -> impl Iterator<Item = (&'static str, &'static str)> {
         [
             ("Open Game License", "SRD license"),
             ("spells", "SRD index"),
+            ("spells [class] [school] [level]", "filter SRD spells"),
+            ("conditions", "SRD index"),
+            ("diseases", "SRD index"),
+            ("magic items", "SRD index"),
+            ("srd search [terms]", "search the SRD by keyword"),
+            ("scale [monster] to cr [n]", "adjust a monster's challenge"),
+            ("srd monster [name]", "SRD monster"),
+            ("magic items common", "SRD index by rarity"),
+            ("magic items uncommon", "SRD index by rarity"),
+            ("magic items rare", "SRD index by rarity"),
+            ("magic items very rare", "SRD index by rarity"),
+            ("magic items legendary", "SRD index by rarity"),
+            ("magic items artifact", "SRD index by rarity"),
         ]
         .into_iter()
         .chain(Spell::get_words().zip(repeat("SRD spell")))
+        .chain(Class::get_words().zip(repeat("SRD class")))
         .chain(Condition::get_words().zip(repeat("SRD condition")))
+        .chain(Disease::get_words().zip(repeat("SRD disease")))
+        .chain(Madness::get_words().zip(repeat("SRD madness")))
         .chain(Item::get_words().zip(repeat("SRD item")))
         .chain(ItemCategory::get_words().zip(repeat("SRD item category")))
         .chain(MagicItem::get_words().zip(repeat("SRD magic item")))
         .chain(Trait::get_words().zip(repeat("SRD trait")))
-        .filter(|(term, _)| term.starts_with_ci(input))
-        .take(10)
-        .map(|(term, summary)| AutocompleteSuggestion::new(term, summary))
-        .collect()
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Autocomplete for ReferenceCommand {
+    async fn autocomplete(input: &str, app_meta: &AppMeta) -> Vec<AutocompleteSuggestion> {
+        let homebrew = HomebrewReference::load(app_meta).await;
+
+        let mut suggestions: Vec<AutocompleteSuggestion> = if input.is_empty() {
+            Vec::new()
+        } else if let Some(partial) = input.strip_prefix_ci("srd monster ") {
+            HomebrewEntry::srd_monsters()
+                .filter(|monster| monster.name().starts_with_ci(partial))
+                .map(|monster| {
+                    AutocompleteSuggestion::new(
+                        format!("srd monster {}", monster.name()),
+                        "SRD monster",
+                    )
+                })
+                .collect()
+        } else if let Some(partial) = input.strip_prefix_ci("scale ") {
+            homebrew
+                .iter()
+                .filter(|entry| entry.kind == HomebrewEntryKind::Monster)
+                .map(|entry| entry.name().to_string())
+                .chain(
+                    HomebrewEntry::srd_monsters()
+                        .map(|monster| monster.name().to_string())
+                        .filter(|name| {
+                            homebrew
+                                .get(name, Some(HomebrewEntryKind::Monster))
+                                .is_none()
+                        }),
+                )
+                .filter(|name| name.starts_with_ci(partial))
+                .map(|name| {
+                    AutocompleteSuggestion::new(
+                        format!("scale {} to cr [n]", name),
+                        "adjust a monster's challenge",
+                    )
+                })
+                .collect()
+        } else {
+            homebrew
+                .iter()
+                .filter(|entry| entry.name().starts_with_ci(input))
+                .map(|entry| {
+                    AutocompleteSuggestion::new(
+                        entry.name().to_string(),
+                        format!("homebrew {}", entry.kind),
+                    )
+                })
+                .collect()
+        };
+
+        suggestions.extend(
+            app_meta
+                .vocabulary
+                .reference
+                .starting_with(input)
+                // Only suggest "Wizard 5" and the like once the class name has been typed out.
+                .filter(|(term, summary)| {
+                    input.contains(' ')
+                        || *summary != "SRD class"
+                        || !term.ends_with(|c: char| c.is_ascii_digit())
+                })
+                // Homebrew content replaces SRD entries of the same name.
+                .filter(|(term, _)| homebrew.get(term, None).is_none())
+                .take(10)
+                .map(|(term, summary)| AutocompleteSuggestion::new(*term, *summary)),
+        );
+
+        suggestions.truncate(10);
+        suggestions
     }
 }
 
 impl fmt::Display for ReferenceCommand {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         match self {
+            Self::Class(class) => write!(f, "srd class {}", class.get_name()),
             Self::Condition(condition) => write!(f, "srd condition {}", condition.get_name()),
+            Self::Conditions => write!(f, "srd conditions"),
+            Self::Disease(disease) => write!(f, "srd disease {}", disease.get_name()),
+            Self::Diseases => write!(f, "srd diseases"),
+            Self::FilteredSpells(filter) => write!(f, "srd spells {}", filter),
+            Self::Homebrew(entry) => write!(f, "homebrew {} {}", entry.kind, entry.name()),
             Self::Item(item) => write!(f, "srd item {}", item.get_name()),
             Self::ItemCategory(category) => write!(f, "srd item category {}", category.get_name()),
             Self::MagicItem(item) => write!(f, "srd magic item {}", item.get_name()),
+            Self::MagicItems(Some(rarity)) => write!(f, "srd magic items {}", rarity),
+            Self::MagicItems(None) => write!(f, "srd magic items"),
+            Self::Madness(madness) => write!(f, "srd madness {}", madness.get_name()),
+            Self::Monster(monster) => write!(f, "srd monster {}", monster.name()),
             Self::OpenGameLicense => write!(f, "Open Game License"),
+            Self::ScaleMonster {
+                name,
+                challenge_rating,
+                save_as,
+            } => {
+                match challenge_rating {
+                    Ok(challenge_rating) => write!(f, "scale {} to cr {}", name, challenge_rating)?,
+                    Err(raw) => write!(f, "scale {} to cr {}", name, raw)?,
+                }
+                if let Some(save_as) = save_as {
+                    write!(f, " as {}", save_as)?;
+                }
+                Ok(())
+            }
+            Self::Search(query) => write!(f, "srd search {}", query),
             Self::Spell(spell) => write!(f, "srd spell {}", spell.get_name()),
             Self::Spells => write!(f, "srd spells"),
             Self::Trait(species_trait) => write!(f, "srd trait {}", species_trait.get_name()),
@@ -149,6 +497,109 @@ impl fmt::Display for ReferenceCommand {
     }
 }
 
+/// Parse "scale [monster] to cr [challenge rating]", optionally followed by "as [name]", eg.
+/// "scale goblin to cr 3 as goblin captain".
+fn parse_scale_monster(input: &str) -> Option<ReferenceCommand> {
+    let rest = input.strip_prefix_ci("scale ")?;
+
+    // The monster name may itself contain " to cr ", so split at the last occurrence.
+    let (name, rest) = rest
+        .char_indices()
+        .rev()
+        .find(|(i, _)| (&rest[*i..]).starts_with_ci(" to cr "))
+        .map(|(i, _)| (&rest[..i], &rest[i + " to cr ".len()..]))?;
+
+    let (challenge_rating, save_as) = match rest
+        .char_indices()
+        .find(|(i, _)| (&rest[*i..]).starts_with_ci(" as "))
+    {
+        Some((i, _)) => {
+            let save_as = rest[i + " as ".len()..].trim();
+            if save_as.is_empty() {
+                return None;
+            }
+            (&rest[..i], Some(save_as))
+        }
+        None => (rest, None),
+    };
+
+    if name.trim().is_empty() || challenge_rating.trim().is_empty() {
+        return None;
+    }
+
+    Some(ReferenceCommand::ScaleMonster {
+        name: name.trim().to_string(),
+        challenge_rating: challenge_rating
+            .parse()
+            .map_err(|_| challenge_rating.trim().to_string()),
+        save_as: save_as.map(str::to_string),
+    })
+}
+
+/// Parse "magic items" optionally followed by a rarity, eg. "magic items very rare".
+fn parse_magic_items(input: &str) -> Option<Option<&'static str>> {
+    let rest = input.strip_prefix_ci("magic items")?;
+
+    if rest.is_empty() {
+        Some(None)
+    } else {
+        let rarity = rest.strip_prefix(' ')?;
+        RARITIES.iter().find(|r| r.eq_ci(rarity)).map(|r| Some(*r))
+    }
+}
+
+fn condition_list() -> String {
+    let mut conditions: Vec<&str> = Condition::get_words().collect();
+    conditions.sort_unstable();
+
+    conditions
+        .into_iter()
+        .fold("# Conditions".to_string(), |mut output, name| {
+            output.push_str(&format!("\n* `{}`", name));
+            output
+        })
+}
+
+fn magic_item_list(rarity: Option<&str>) -> Result<String, String> {
+    let mut magic_items: Vec<MagicItem> = MagicItem::get_all()
+        .filter(|magic_item| rarity.is_none() || magic_item.get_rarity() == rarity)
+        .collect();
+
+    if magic_items.is_empty() {
+        return Err(format!(
+            "There are no {} magic items in the SRD.",
+            rarity.unwrap_or_default(),
+        ));
+    }
+
+    magic_items.sort_by_key(|magic_item| magic_item.get_name());
+
+    let mut output = match rarity {
+        Some(rarity) => format!("# {} magic items", capitalize(rarity)),
+        None => "# Magic items".to_string(),
+    };
+
+    for magic_item in magic_items {
+        let mut notes = Vec::new();
+
+        if rarity.is_none() {
+            if let Some(item_rarity) = magic_item.get_rarity() {
+                notes.push(item_rarity);
+            }
+        }
+        if magic_item.requires_attunement() {
+            notes.push("requires attunement");
+        }
+
+        output.push_str(&format!("\n* `{}`", magic_item.get_name()));
+        if !notes.is_empty() {
+            output.push_str(&format!(" ({})", notes.join(", ")));
+        }
+    }
+
+    Ok(output)
+}
+
 fn linkify_dice(input: &str) -> String {
     let mut result = String::with_capacity(input.len());
     let mut input_offset = 0;
@@ -225,7 +676,18 @@ mod test {
             ReferenceCommand::Item(Item::Shield),
             ReferenceCommand::ItemCategory(ItemCategory::Shields),
             ReferenceCommand::MagicItem(MagicItem::DeckOfManyThings),
+            ReferenceCommand::MagicItems(None),
+            ReferenceCommand::Class(Class::Wizard5),
+            ReferenceCommand::Conditions,
+            ReferenceCommand::Disease(Disease::SewerPlague),
+            ReferenceCommand::Diseases,
+            ReferenceCommand::Madness(Madness::ShortTerm),
+            ReferenceCommand::MagicItems(Some("very rare")),
             ReferenceCommand::OpenGameLicense,
+            ReferenceCommand::Search("fire".to_string()),
+            ReferenceCommand::FilteredSpells(
+                SpellFilter::default().with_class("Wizard").with_level(3),
+            ),
         ]
         .into_iter()
         .for_each(|command| {
@@ -251,6 +713,51 @@ mod test {
         });
     }
 
+    #[test]
+    fn parse_scale_monster_test() {
+        assert_eq!(
+            Some(ReferenceCommand::ScaleMonster {
+                name: "Goblin to cr Chief".to_string(),
+                challenge_rating: Ok("1/4".parse().unwrap()),
+                save_as: Some("Goblin Captain".to_string()),
+            }),
+            parse_scale_monster("Scale Goblin to cr Chief TO CR 1/4 AS Goblin Captain"),
+        );
+
+        [
+            ReferenceCommand::ScaleMonster {
+                name: "Goblin".to_string(),
+                challenge_rating: Ok("1/2".parse().unwrap()),
+                save_as: None,
+            },
+            ReferenceCommand::ScaleMonster {
+                name: "Goblin".to_string(),
+                challenge_rating: Ok("3".parse().unwrap()),
+                save_as: Some("Goblin Captain".to_string()),
+            },
+        ]
+        .into_iter()
+        .for_each(|command| {
+            assert_eq!(
+                Some(command.clone()),
+                parse_scale_monster(&command.to_string())
+            );
+        });
+
+        assert_eq!(
+            Some(ReferenceCommand::ScaleMonster {
+                name: "goblin".to_string(),
+                challenge_rating: Err("31".to_string()),
+                save_as: None,
+            }),
+            parse_scale_monster("scale goblin to cr 31"),
+        );
+        assert_eq!(None, parse_scale_monster("scale goblin to cr "));
+        assert_eq!(None, parse_scale_monster("scale goblin to cr 3 as "));
+        assert_eq!(None, parse_scale_monster("scale  to cr 3"));
+        assert_eq!(None, parse_scale_monster("scale goblin"));
+    }
+
     fn event_dispatcher(_event: Event) {}
 
     fn app_meta() -> AppMeta {
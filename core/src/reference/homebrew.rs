@@ -0,0 +1,540 @@
+use super::scale::{scale_monster, ChallengeRating};
+use crate::app::AppMeta;
+use crate::storage::{Change, KeyValue, Repository};
+use crate::utils::CaseInsensitiveStr;
+use initiative_reference::srd_5e;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::str::FromStr;
+
+/// Spells, equipment, magic items, and monsters imported by the user in the format of the D&D 5e
+/// API. They're consulted alongside the bundled SRD data, and take precedence over any SRD entry of
+/// the same name. The original JSON of each entry is kept so that it can be rendered with the same
+/// views as the SRD.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct HomebrewReference(Vec<HomebrewEntry>);
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct HomebrewEntry {
+    pub kind: HomebrewEntryKind,
+    data: Value,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HomebrewEntryKind {
+    Spell,
+    Equipment,
+    MagicItem,
+    Monster,
+}
+
+/// The number of entries added and replaced by a homebrew import.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct ImportStats {
+    added: Vec<(HomebrewEntryKind, usize)>,
+    replaced: usize,
+}
+
+impl HomebrewReference {
+    /// Load the user's homebrew reference content from storage, or an empty set if none has been
+    /// imported or the data store is unavailable.
+    pub async fn load(app_meta: &AppMeta) -> Self {
+        app_meta
+            .repository
+            .get_key_value(&KeyValue::HomebrewReference(None))
+            .await
+            .ok()
+            .and_then(KeyValue::homebrew_reference)
+            .unwrap_or_default()
+    }
+
+    /// Read an imported file, merge its contents into the stored homebrew, and describe the result.
+    /// This is the counterpart of [`backup::import`](crate::storage::backup::import) for reference
+    /// content.
+    pub async fn import(repository: &mut Repository, data: Value) -> Result<String, String> {
+        let entries = Self::parse(data)?;
+
+        let mut homebrew = repository
+            .get_key_value(&KeyValue::HomebrewReference(None))
+            .await
+            .ok()
+            .and_then(KeyValue::homebrew_reference)
+            .unwrap_or_default();
+
+        let stats = homebrew.merge(entries);
+
+        repository
+            .modify(Change::SetKeyValue {
+                key_value: KeyValue::HomebrewReference(Some(homebrew)),
+            })
+            .await
+            .map(|_| stats.to_string())
+            .map_err(|_| "Unable to save the homebrew content.".to_string())
+    }
+
+    /// Parse the contents of a homebrew file: an object whose keys are the names of the 5e API
+    /// endpoints (`spells`, `equipment`, `magic-items`, and `monsters`), each containing a list of
+    /// entries.
+    pub fn parse(data: Value) -> Result<Vec<HomebrewEntry>, String> {
+        let Value::Object(sections) = data else {
+            return Err("The homebrew file must contain an object with lists of `spells`, `equipment`, `magic-items`, or `monsters`.".to_string());
+        };
+
+        let mut entries = Vec::new();
+
+        for (section, values) in sections {
+            let kind = match section.as_str() {
+                "spells" => HomebrewEntryKind::Spell,
+                "equipment" => HomebrewEntryKind::Equipment,
+                "magic-items" | "magic_items" => HomebrewEntryKind::MagicItem,
+                "monsters" => HomebrewEntryKind::Monster,
+                _ => {
+                    return Err(format!(
+                        "The homebrew file contains an unrecognized section, \"{}\". Recognized sections are `spells`, `equipment`, `magic-items`, and `monsters`.",
+                        section,
+                    ))
+                }
+            };
+
+            let Value::Array(values) = values else {
+                return Err(format!("The `{}` section must be a list.", section));
+            };
+
+            for (i, data) in values.into_iter().enumerate() {
+                let entry = HomebrewEntry { kind, data };
+
+                if let Err(e) = entry.validate() {
+                    return Err(format!(
+                        "The {} {} couldn't be read: {}.",
+                        kind,
+                        entry
+                            .data
+                            .get("name")
+                            .and_then(Value::as_str)
+                            .map(|name| format!("\"{}\"", name))
+                            .unwrap_or_else(|| format!("at position {}", i + 1)),
+                        e,
+                    ));
+                }
+
+                entries.push(entry);
+            }
+        }
+
+        if entries.is_empty() {
+            Err("The homebrew file doesn't contain any entries.".to_string())
+        } else {
+            Ok(entries)
+        }
+    }
+
+    /// Add the entries, replacing any existing entry of the same kind and name.
+    pub fn merge(&mut self, entries: Vec<HomebrewEntry>) -> ImportStats {
+        let mut stats = ImportStats::default();
+
+        for entry in entries {
+            if let Some(existing) = self
+                .0
+                .iter_mut()
+                .find(|e| e.kind == entry.kind && e.name().eq_ci(entry.name()))
+            {
+                *existing = entry.clone();
+                stats.replaced += 1;
+            } else {
+                self.0.push(entry.clone());
+            }
+
+            if let Some((_, count)) = stats.added.iter_mut().find(|(k, _)| k == &entry.kind) {
+                *count += 1;
+            } else {
+                stats.added.push((entry.kind, 1));
+            }
+        }
+
+        stats
+    }
+
+    /// Find an entry by name, preferring one of the given kind if there are several.
+    pub fn get(&self, name: &str, kind: Option<HomebrewEntryKind>) -> Option<&HomebrewEntry> {
+        self.0
+            .iter()
+            .filter(|entry| entry.name().eq_ci(name))
+            .min_by_key(|entry| Some(entry.kind) != kind)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &HomebrewEntry> {
+        self.0.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl HomebrewEntry {
+    /// The monsters of the SRD in the same form as imported ones, so that they can be scaled and
+    /// saved as homebrew.
+    pub fn srd_monsters() -> impl Iterator<Item = Self> {
+        srd_5e::monsters()
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|monster| serde_json::to_value(monster).ok())
+            .map(|data| HomebrewEntry {
+                kind: HomebrewEntryKind::Monster,
+                data,
+            })
+    }
+
+    /// A monster from the SRD by name, eg. "goblin".
+    pub fn srd_monster(name: &str) -> Option<Self> {
+        Self::srd_monsters().find(|monster| monster.name().eq_ci(name))
+    }
+
+    pub fn name(&self) -> &str {
+        self.data
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+    }
+
+    /// A monster's challenge rating, rounded down to a whole number.
+    pub fn challenge_rating(&self) -> Option<u8> {
+        self.data
+            .get("challenge_rating")
+            .and_then(Value::as_f64)
+            .map(|cr| cr.clamp(0., 30.) as u8)
+    }
+
+    /// A monster's type, eg. "undead".
+    pub fn monster_type(&self) -> Option<&str> {
+        self.data.get("type").and_then(Value::as_str)
+    }
+
+    /// A one-line description for listings, eg. "`Frostbolt` (1st-level evocation)".
+    pub fn summary(&self) -> String {
+        match self.kind {
+            HomebrewEntryKind::Spell => self
+                .deserialize::<srd_5e::Spell>()
+                .map(|spell| spell.display_summary().to_string()),
+            HomebrewEntryKind::Equipment => self
+                .deserialize::<srd_5e::Item>()
+                .map(|item| format!("`{}` ({})", self.name(), item.get_category().to_lowercase())),
+            HomebrewEntryKind::MagicItem => {
+                self.deserialize::<srd_5e::MagicItem>().map(|magic_item| {
+                    if let Some(rarity) = magic_item.rarity() {
+                        format!("`{}` ({})", self.name(), rarity)
+                    } else {
+                        format!("`{}`", self.name())
+                    }
+                })
+            }
+            HomebrewEntryKind::Monster => self
+                .deserialize::<srd_5e::Monster>()
+                .map(|monster| monster.display_summary().to_string()),
+        }
+        .unwrap_or_else(|_| format!("`{}`", self.name()))
+    }
+
+    /// The full entry, rendered the same way as its SRD counterparts.
+    pub fn details(&self) -> String {
+        match self.kind {
+            HomebrewEntryKind::Spell => self
+                .deserialize::<srd_5e::Spell>()
+                .map(|spell| spell.display_details().to_string()),
+            HomebrewEntryKind::Equipment => self
+                .deserialize::<srd_5e::Item>()
+                .map(|item| item.display_details().to_string()),
+            HomebrewEntryKind::MagicItem => self
+                .deserialize::<srd_5e::MagicItem>()
+                .map(|magic_item| magic_item.display_details().to_string()),
+            HomebrewEntryKind::Monster => self
+                .deserialize::<srd_5e::Monster>()
+                .map(|monster| monster.display_details().to_string()),
+        }
+        .map(|details| details.trim_end().to_string())
+        .unwrap_or_else(|_| format!("# {}", self.name()))
+    }
+
+    /// A copy of a monster adjusted to the given challenge rating, renamed if a name is given.
+    pub fn scale(
+        &self,
+        challenge_rating: ChallengeRating,
+        name: Option<&str>,
+    ) -> Result<HomebrewEntry, String> {
+        if self.kind != HomebrewEntryKind::Monster {
+            return Err(format!("{} isn't a monster.", self.name()));
+        }
+
+        let mut data = scale_monster(&self.data, challenge_rating)?;
+
+        if let Some(name) = name {
+            data["name"] = name.into();
+            data["index"] = name
+                .split_whitespace()
+                .map(str::to_lowercase)
+                .collect::<Vec<_>>()
+                .join("-")
+                .into();
+        }
+
+        Ok(HomebrewEntry {
+            kind: self.kind,
+            data,
+        })
+    }
+
+    fn validate(&self) -> Result<(), serde_json::Error> {
+        if self.data.get("name").and_then(Value::as_str).is_none() {
+            return Err(serde::de::Error::missing_field("name"));
+        }
+
+        match self.kind {
+            HomebrewEntryKind::Spell => self.deserialize::<srd_5e::Spell>().map(|_| ()),
+            HomebrewEntryKind::Equipment => self.deserialize::<srd_5e::Item>().map(|_| ()),
+            HomebrewEntryKind::MagicItem => self.deserialize::<srd_5e::MagicItem>().map(|_| ()),
+            HomebrewEntryKind::Monster => self.deserialize::<srd_5e::Monster>().map(|_| ()),
+        }
+    }
+
+    fn deserialize<T: serde::de::DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        serde_json::from_value(self.data.clone())
+    }
+}
+
+impl HomebrewEntryKind {
+    /// The plural form, used as a heading in the `homebrew list` output.
+    pub fn plural(&self) -> &'static str {
+        match self {
+            Self::Spell => "spells",
+            Self::Equipment => "equipment",
+            Self::MagicItem => "magic items",
+            Self::Monster => "monsters",
+        }
+    }
+}
+
+impl fmt::Display for HomebrewEntryKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Spell => write!(f, "spell"),
+            Self::Equipment => write!(f, "equipment"),
+            Self::MagicItem => write!(f, "magic item"),
+            Self::Monster => write!(f, "monster"),
+        }
+    }
+}
+
+impl fmt::Display for HomebrewReference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            serde_json::to_string(self).map_err(|_| fmt::Error)?,
+        )
+    }
+}
+
+impl FromStr for HomebrewReference {
+    type Err = ();
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(raw).map_err(|_| ())
+    }
+}
+
+impl fmt::Display for ImportStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut added = self.added.clone();
+        added.sort();
+
+        let counts: Vec<String> = added
+            .iter()
+            .map(|(kind, count)| match (kind, count) {
+                (HomebrewEntryKind::Equipment, count) => format!("{} equipment", count),
+                (kind, 1) => format!("1 {}", kind),
+                (kind, count) => format!("{} {}", count, kind.plural()),
+            })
+            .collect();
+
+        write!(f, "Imported ")?;
+
+        match counts.as_slice() {
+            [] => write!(f, "nothing")?,
+            [one] => write!(f, "{}", one)?,
+            [init @ .., last] => write!(f, "{} and {}", init.join(", "), last)?,
+        }
+
+        match self.replaced {
+            0 => write!(f, ".")?,
+            1 => write!(f, ", replacing 1 existing entry.")?,
+            n => write!(f, ", replacing {} existing entries.", n)?,
+        }
+
+        write!(
+            f,
+            " Use `homebrew list` to see your homebrew content, or `undo` to reverse this.",
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_test() {
+        let entries = HomebrewReference::parse(json!({
+            "spells": [frostbolt()],
+            "monsters": [bog_lurker()],
+        }))
+        .unwrap();
+
+        assert_eq!(
+            vec![
+                ("Bog Lurker", HomebrewEntryKind::Monster),
+                ("Frostbolt", HomebrewEntryKind::Spell),
+            ],
+            entries
+                .iter()
+                .map(|entry| (entry.name(), entry.kind))
+                .collect::<Vec<_>>(),
+        );
+
+        assert_eq!(
+            Err("The spell \"Frostbolt\" couldn't be read: missing field `level`.".to_string()),
+            HomebrewReference::parse(
+                json!({"spells": [{"index": "frostbolt", "name": "Frostbolt"}]})
+            ),
+        );
+
+        assert_eq!(
+            Err("The monster at position 1 couldn't be read: missing field `name`.".to_string()),
+            HomebrewReference::parse(json!({"monsters": [{}]})),
+        );
+
+        assert_eq!(
+            Err("The homebrew file contains an unrecognized section, \"feats\". Recognized sections are `spells`, `equipment`, `magic-items`, and `monsters`.".to_string()),
+            HomebrewReference::parse(json!({"feats": []})),
+        );
+
+        assert_eq!(
+            Err("The homebrew file doesn't contain any entries.".to_string()),
+            HomebrewReference::parse(json!({"spells": []})),
+        );
+
+        assert!(HomebrewReference::parse(json!([frostbolt()])).is_err());
+    }
+
+    #[test]
+    fn merge_test() {
+        let mut homebrew = HomebrewReference::default();
+
+        assert_eq!(
+            "Imported 1 spell and 1 monster. Use `homebrew list` to see your homebrew content, or `undo` to reverse this.",
+            homebrew
+                .merge(
+                    HomebrewReference::parse(json!({
+                        "monsters": [bog_lurker()],
+                        "spells": [frostbolt()],
+                    }))
+                    .unwrap(),
+                )
+                .to_string(),
+        );
+
+        let mut frostbolt_2 = frostbolt();
+        frostbolt_2["name"] = json!("FROSTBOLT");
+        frostbolt_2["level"] = json!(2);
+
+        let mut frostbolt_3 = frostbolt();
+        frostbolt_3["name"] = json!("Greater Frostbolt");
+
+        assert_eq!(
+            "Imported 2 spells, replacing 1 existing entry. Use `homebrew list` to see your homebrew content, or `undo` to reverse this.",
+            homebrew
+                .merge(
+                    HomebrewReference::parse(json!({"spells": [frostbolt_2, frostbolt_3]}))
+                        .unwrap(),
+                )
+                .to_string(),
+        );
+
+        assert_eq!(3, homebrew.iter().count());
+        assert_eq!(
+            "`FROSTBOLT` (2nd-level evocation)",
+            homebrew.get("frostbolt", None).unwrap().summary(),
+        );
+        assert_eq!(
+            HomebrewEntryKind::Monster,
+            homebrew
+                .get("bog lurker", Some(HomebrewEntryKind::Spell))
+                .unwrap()
+                .kind,
+        );
+        assert!(homebrew.get("fireball", None).is_none());
+    }
+
+    #[test]
+    fn details_test() {
+        let entries = HomebrewReference::parse(json!({
+            "magic-items": [{
+                "index": "lantern-of-echoes",
+                "name": "Lantern of Echoes",
+                "desc": ["Wondrous item, uncommon", "It repeats the last thing said near it."],
+                "rarity": {"name": "Uncommon"},
+            }],
+        }))
+        .unwrap();
+
+        assert_eq!("`Lantern of Echoes` (uncommon)", entries[0].summary());
+        assert_eq!(
+            "# Lantern of Echoes\n\n*Wondrous item, uncommon*\n\nIt repeats the last thing said near it.",
+            entries[0].details(),
+        );
+    }
+
+    #[test]
+    fn serialize_test() {
+        let mut homebrew = HomebrewReference::default();
+        homebrew.merge(HomebrewReference::parse(json!({"spells": [frostbolt()]})).unwrap());
+
+        assert_eq!(Ok(homebrew.clone()), homebrew.to_string().parse());
+        assert_eq!(Err(()), "potato".parse::<HomebrewReference>());
+    }
+
+    fn frostbolt() -> Value {
+        json!({
+            "index": "frostbolt",
+            "name": "Frostbolt",
+            "level": 1,
+            "school": {"index": "evocation", "name": "Evocation", "url": "/api/magic-schools/evocation"},
+            "casting_time": "1 action",
+            "range": "60 feet",
+            "components": ["V", "S"],
+            "duration": "Instantaneous",
+            "desc": ["A shard of ice deals 2d8 cold damage."],
+        })
+    }
+
+    fn bog_lurker() -> Value {
+        json!({
+            "index": "bog-lurker",
+            "name": "Bog Lurker",
+            "size": "Large",
+            "type": "monstrosity",
+            "alignment": "neutral evil",
+            "armor_class": 14,
+            "hit_points": 59,
+            "strength": 18,
+            "dexterity": 9,
+            "constitution": 16,
+            "intelligence": 5,
+            "wisdom": 12,
+            "charisma": 6,
+            "challenge_rating": 2,
+        })
+    }
+}
@@ -1,9 +1,20 @@
 pub use command::ReferenceCommand;
+pub use homebrew::{HomebrewEntry, HomebrewEntryKind, HomebrewReference};
+pub use rules::{Disease, Madness};
+pub use scale::ChallengeRating;
+pub use spells::SpellFilter;
 
 mod command;
+mod homebrew;
+mod rules;
+mod scale;
+mod search;
+mod spells;
 
 use initiative_macros::reference_enum;
 
+reference_enum!(Class);
+
 reference_enum!(Condition);
 
 reference_enum!(Item);
@@ -0,0 +1,128 @@
+use initiative_macros::WordList;
+use std::fmt;
+
+/// Diseases from the SRD's Gamemastering section, which isn't covered by the D&D 5e API.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, WordList)]
+pub enum Disease {
+    #[term = "Cackle Fever"]
+    CackleFever,
+
+    #[term = "Sewer Plague"]
+    SewerPlague,
+
+    #[term = "Sight Rot"]
+    SightRot,
+}
+
+/// The SRD's rules for madness, along with its tables of madness effects.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, WordList)]
+pub enum Madness {
+    #[term = "Madness"]
+    Rules,
+
+    #[term = "Short-Term Madness"]
+    ShortTerm,
+
+    #[term = "Long-Term Madness"]
+    LongTerm,
+
+    #[term = "Indefinite Madness"]
+    Indefinite,
+}
+
+impl Disease {
+    pub fn get_name(&self) -> &'static str {
+        self.as_str()
+    }
+
+    pub fn get_output(&self) -> &'static str {
+        match self {
+            Self::CackleFever => include_str!("../../../data/rules/cackle-fever.md"),
+            Self::SewerPlague => include_str!("../../../data/rules/sewer-plague.md"),
+            Self::SightRot => include_str!("../../../data/rules/sight-rot.md"),
+        }
+        .trim_end()
+    }
+
+    pub fn get_list() -> String {
+        Self::get_words().fold("# Diseases".to_string(), |mut output, name| {
+            output.push_str(&format!("\n* `{}`", name));
+            output
+        })
+    }
+}
+
+impl Madness {
+    pub fn get_name(&self) -> &'static str {
+        self.as_str()
+    }
+
+    pub fn get_output(&self) -> &'static str {
+        match self {
+            Self::Rules => include_str!("../../../data/rules/madness.md"),
+            Self::ShortTerm => include_str!("../../../data/rules/short-term-madness.md"),
+            Self::LongTerm => include_str!("../../../data/rules/long-term-madness.md"),
+            Self::Indefinite => include_str!("../../../data/rules/indefinite-madness.md"),
+        }
+        .trim_end()
+    }
+}
+
+impl fmt::Display for Disease {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.get_output())
+    }
+}
+
+impl fmt::Display for Madness {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.get_output())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        assert_eq!(Ok(Disease::CackleFever), "cackle fever".parse());
+        assert_eq!(Ok(Disease::SightRot), "Sight Rot".parse());
+        assert_eq!(Ok(Madness::ShortTerm), "short-term madness".parse());
+        assert_eq!(Ok(Madness::Rules), "MADNESS".parse());
+        assert_eq!(Err(()), "the sniffles".parse::<Disease>());
+    }
+
+    #[test]
+    fn get_output_test() {
+        Disease::get_words().for_each(|word| {
+            let disease: Disease = word.parse().unwrap();
+            assert!(
+                disease
+                    .get_output()
+                    .starts_with(&format!("# {}\n\n", disease.get_name())),
+                "{}",
+                word,
+            );
+        });
+
+        Madness::get_words().for_each(|word| {
+            let madness: Madness = word.parse().unwrap();
+            assert!(
+                madness
+                    .get_output()
+                    .starts_with(&format!("# {}\n\n", madness.get_name())),
+                "{}",
+                word,
+            );
+        });
+    }
+
+    #[test]
+    fn get_list_test() {
+        assert_eq!(
+            "# Diseases\n* `Cackle Fever`\n* `Sewer Plague`\n* `Sight Rot`",
+            Disease::get_list(),
+        );
+    }
+}
@@ -0,0 +1,423 @@
+use serde_json::Value;
+use std::fmt;
+use std::str::FromStr;
+
+/// A challenge rating from 0 to 30, eg. "1/4" or "12".
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ChallengeRating(usize);
+
+/// The expected statistics of a monster of a given challenge rating, per the usual monster
+/// creation guidelines. Hit points and damage are given as ranges; scaling uses their midpoints.
+struct Guideline {
+    name: &'static str,
+    value: f64,
+    proficiency_bonus: i64,
+    hit_points: (i64, i64),
+    attack_bonus: i64,
+    damage_per_round: (i64, i64),
+    save_dc: i64,
+    xp: u64,
+}
+
+#[rustfmt::skip]
+const GUIDELINES: &[Guideline] = &[
+    Guideline { name: "0", value: 0., proficiency_bonus: 2, hit_points: (1, 6), attack_bonus: 3, damage_per_round: (0, 1), save_dc: 13, xp: 10 },
+    Guideline { name: "1/8", value: 0.125, proficiency_bonus: 2, hit_points: (7, 35), attack_bonus: 3, damage_per_round: (2, 3), save_dc: 13, xp: 25 },
+    Guideline { name: "1/4", value: 0.25, proficiency_bonus: 2, hit_points: (36, 49), attack_bonus: 3, damage_per_round: (4, 5), save_dc: 13, xp: 50 },
+    Guideline { name: "1/2", value: 0.5, proficiency_bonus: 2, hit_points: (50, 70), attack_bonus: 3, damage_per_round: (6, 8), save_dc: 13, xp: 100 },
+    Guideline { name: "1", value: 1., proficiency_bonus: 2, hit_points: (71, 85), attack_bonus: 3, damage_per_round: (9, 14), save_dc: 13, xp: 200 },
+    Guideline { name: "2", value: 2., proficiency_bonus: 2, hit_points: (86, 100), attack_bonus: 3, damage_per_round: (15, 20), save_dc: 13, xp: 450 },
+    Guideline { name: "3", value: 3., proficiency_bonus: 2, hit_points: (101, 115), attack_bonus: 4, damage_per_round: (21, 26), save_dc: 13, xp: 700 },
+    Guideline { name: "4", value: 4., proficiency_bonus: 2, hit_points: (116, 130), attack_bonus: 5, damage_per_round: (27, 32), save_dc: 14, xp: 1100 },
+    Guideline { name: "5", value: 5., proficiency_bonus: 3, hit_points: (131, 145), attack_bonus: 6, damage_per_round: (33, 38), save_dc: 15, xp: 1800 },
+    Guideline { name: "6", value: 6., proficiency_bonus: 3, hit_points: (146, 160), attack_bonus: 6, damage_per_round: (39, 44), save_dc: 15, xp: 2300 },
+    Guideline { name: "7", value: 7., proficiency_bonus: 3, hit_points: (161, 175), attack_bonus: 6, damage_per_round: (45, 50), save_dc: 15, xp: 2900 },
+    Guideline { name: "8", value: 8., proficiency_bonus: 3, hit_points: (176, 190), attack_bonus: 7, damage_per_round: (51, 56), save_dc: 16, xp: 3900 },
+    Guideline { name: "9", value: 9., proficiency_bonus: 4, hit_points: (191, 205), attack_bonus: 7, damage_per_round: (57, 62), save_dc: 16, xp: 5000 },
+    Guideline { name: "10", value: 10., proficiency_bonus: 4, hit_points: (206, 220), attack_bonus: 7, damage_per_round: (63, 68), save_dc: 16, xp: 5900 },
+    Guideline { name: "11", value: 11., proficiency_bonus: 4, hit_points: (221, 235), attack_bonus: 8, damage_per_round: (69, 74), save_dc: 17, xp: 7200 },
+    Guideline { name: "12", value: 12., proficiency_bonus: 4, hit_points: (236, 250), attack_bonus: 8, damage_per_round: (75, 80), save_dc: 17, xp: 8400 },
+    Guideline { name: "13", value: 13., proficiency_bonus: 5, hit_points: (251, 265), attack_bonus: 8, damage_per_round: (81, 86), save_dc: 18, xp: 10000 },
+    Guideline { name: "14", value: 14., proficiency_bonus: 5, hit_points: (266, 280), attack_bonus: 8, damage_per_round: (87, 92), save_dc: 18, xp: 11500 },
+    Guideline { name: "15", value: 15., proficiency_bonus: 5, hit_points: (281, 295), attack_bonus: 8, damage_per_round: (93, 98), save_dc: 18, xp: 13000 },
+    Guideline { name: "16", value: 16., proficiency_bonus: 5, hit_points: (296, 310), attack_bonus: 9, damage_per_round: (99, 104), save_dc: 18, xp: 15000 },
+    Guideline { name: "17", value: 17., proficiency_bonus: 6, hit_points: (311, 325), attack_bonus: 10, damage_per_round: (105, 110), save_dc: 19, xp: 18000 },
+    Guideline { name: "18", value: 18., proficiency_bonus: 6, hit_points: (326, 340), attack_bonus: 10, damage_per_round: (111, 116), save_dc: 19, xp: 20000 },
+    Guideline { name: "19", value: 19., proficiency_bonus: 6, hit_points: (341, 355), attack_bonus: 10, damage_per_round: (117, 122), save_dc: 19, xp: 22000 },
+    Guideline { name: "20", value: 20., proficiency_bonus: 6, hit_points: (356, 400), attack_bonus: 10, damage_per_round: (123, 140), save_dc: 19, xp: 25000 },
+    Guideline { name: "21", value: 21., proficiency_bonus: 7, hit_points: (401, 445), attack_bonus: 11, damage_per_round: (141, 158), save_dc: 20, xp: 33000 },
+    Guideline { name: "22", value: 22., proficiency_bonus: 7, hit_points: (446, 490), attack_bonus: 11, damage_per_round: (159, 176), save_dc: 20, xp: 41000 },
+    Guideline { name: "23", value: 23., proficiency_bonus: 7, hit_points: (491, 535), attack_bonus: 11, damage_per_round: (177, 194), save_dc: 20, xp: 50000 },
+    Guideline { name: "24", value: 24., proficiency_bonus: 7, hit_points: (536, 580), attack_bonus: 12, damage_per_round: (195, 212), save_dc: 21, xp: 62000 },
+    Guideline { name: "25", value: 25., proficiency_bonus: 8, hit_points: (581, 625), attack_bonus: 12, damage_per_round: (213, 230), save_dc: 21, xp: 75000 },
+    Guideline { name: "26", value: 26., proficiency_bonus: 8, hit_points: (626, 670), attack_bonus: 12, damage_per_round: (231, 248), save_dc: 21, xp: 90000 },
+    Guideline { name: "27", value: 27., proficiency_bonus: 8, hit_points: (671, 715), attack_bonus: 13, damage_per_round: (249, 266), save_dc: 22, xp: 105000 },
+    Guideline { name: "28", value: 28., proficiency_bonus: 8, hit_points: (716, 760), attack_bonus: 13, damage_per_round: (267, 284), save_dc: 22, xp: 120000 },
+    Guideline { name: "29", value: 29., proficiency_bonus: 9, hit_points: (761, 805), attack_bonus: 13, damage_per_round: (285, 302), save_dc: 22, xp: 135000 },
+    Guideline { name: "30", value: 30., proficiency_bonus: 9, hit_points: (806, 850), attack_bonus: 14, damage_per_round: (303, 320), save_dc: 23, xp: 155000 },
+];
+
+/// The fields of a monster whose descriptions contain attack bonuses, damage, and save DCs.
+const ABILITY_FIELDS: &[&str] = &[
+    "special_abilities",
+    "actions",
+    "reactions",
+    "legendary_actions",
+];
+
+impl ChallengeRating {
+    /// A whole-number challenge rating, capped at 30.
+    pub fn whole(cr: u8) -> Self {
+        Self::from_value(cr.min(30).into()).unwrap()
+    }
+
+    /// The save DC expected of a monster's abilities at this challenge rating.
+    pub fn save_dc(&self) -> i64 {
+        self.guideline().save_dc
+    }
+
+    fn from_value(value: f64) -> Option<Self> {
+        GUIDELINES
+            .iter()
+            .position(|guideline| guideline.value == value)
+            .map(Self)
+    }
+
+    fn guideline(&self) -> &'static Guideline {
+        &GUIDELINES[self.0]
+    }
+}
+
+/// Adjust the JSON of a monster in the format of the D&D 5e API to the given challenge rating. Hit
+/// points and damage are scaled by the ratio between the expected values at the old and new
+/// challenge ratings, while attack bonuses, save DCs, and proficiencies shift by the difference.
+pub fn scale_monster(data: &Value, target: ChallengeRating) -> Result<Value, String> {
+    let source = data
+        .get("challenge_rating")
+        .and_then(Value::as_f64)
+        .and_then(ChallengeRating::from_value)
+        .ok_or_else(|| "The monster doesn't have a valid challenge rating.".to_string())?;
+
+    let (from, to) = (source.guideline(), target.guideline());
+    let mut data = data.clone();
+
+    let midpoint = |(min, max): (i64, i64)| (min + max) as f64 / 2.;
+    let hp_ratio = midpoint(to.hit_points) / midpoint(from.hit_points);
+    let damage_ratio = midpoint(to.damage_per_round) / midpoint(from.damage_per_round).max(1.);
+    let attack_delta = to.attack_bonus - from.attack_bonus;
+    let dc_delta = to.save_dc - from.save_dc;
+    let proficiency_delta = to.proficiency_bonus - from.proficiency_bonus;
+
+    if let Some(hit_points) = data.get("hit_points").and_then(Value::as_i64) {
+        let hit_points = ((hit_points as f64 * hp_ratio).round() as i64).max(1);
+        let constitution = data
+            .get("constitution")
+            .and_then(Value::as_i64)
+            .unwrap_or(10);
+
+        let hit_dice = ["hit_points_roll", "hit_dice"]
+            .iter()
+            .find_map(|field| data.get(field).and_then(Value::as_str))
+            .and_then(parse_dice);
+
+        // Recalculate the hit dice so that they still add up to the hit points.
+        if let Some((_, sides, _)) = hit_dice {
+            let modifier = (constitution - 10).div_euclid(2);
+            let per_die = (sides as f64 + 1.) / 2. + modifier as f64;
+            let count = if per_die > 0. {
+                ((hit_points as f64 / per_die).round() as i64).max(1)
+            } else {
+                1
+            };
+
+            data["hit_points"] = ((count * (sides + 1) / 2 + count * modifier).max(1)).into();
+            if data.get("hit_points_roll").is_some() {
+                data["hit_points_roll"] = format_roll(count, sides, count * modifier).into();
+            }
+            if data.get("hit_dice").is_some() {
+                data["hit_dice"] = format!("{}d{}", count, sides).into();
+            }
+        } else {
+            data["hit_points"] = hit_points.into();
+        }
+    }
+
+    for field in ABILITY_FIELDS {
+        if let Some(Value::Array(abilities)) = data.get_mut(*field) {
+            for ability in abilities.iter_mut() {
+                if let Some(desc) = ability.get("desc").and_then(Value::as_str) {
+                    let desc = scale_damage(desc, damage_ratio);
+                    let desc = shift_attack_bonuses(&desc, attack_delta);
+                    ability["desc"] = shift_save_dcs(&desc, dc_delta).into();
+                }
+            }
+        }
+    }
+
+    if let Some(Value::Array(proficiencies)) = data.get_mut("proficiencies") {
+        for proficiency in proficiencies.iter_mut() {
+            if let Some(value) = proficiency.get("value").and_then(Value::as_i64) {
+                proficiency["value"] = (value + proficiency_delta).into();
+            }
+        }
+    }
+
+    data["challenge_rating"] = to.value.into();
+    data["proficiency_bonus"] = to.proficiency_bonus.into();
+    data["xp"] = to.xp.into();
+
+    Ok(data)
+}
+
+/// Parse a roll such as "2d8 + 4" or "7d10+21" into the number of dice, the sides per die, and
+/// the modifier.
+fn parse_dice(input: &str) -> Option<(i64, i64, i64)> {
+    let input: String = input.chars().filter(|c| !c.is_whitespace()).collect();
+    let (count, rest) = input.split_once('d')?;
+
+    let (sides, modifier) = if let Some((sides, modifier)) = rest.split_once('+') {
+        (sides, modifier.parse().ok()?)
+    } else if let Some((sides, modifier)) = rest.split_once('-') {
+        (sides, -modifier.parse::<i64>().ok()?)
+    } else {
+        (rest, 0)
+    };
+
+    Some((count.parse().ok()?, sides.parse().ok()?, modifier))
+}
+
+fn format_roll(count: i64, sides: i64, modifier: i64) -> String {
+    match modifier {
+        0 => format!("{}d{}", count, sides),
+        m if m > 0 => format!("{}d{}+{}", count, sides, m),
+        m => format!("{}d{}{}", count, sides, m),
+    }
+}
+
+/// Scale damage written as an average followed by the roll, eg. "13 (2d8 + 4)", by multiplying
+/// the number of dice.
+fn scale_damage(text: &str, ratio: f64) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(open) = rest.find('(') {
+        let Some(close) = rest[open..].find(')').map(|i| open + i) else {
+            break;
+        };
+
+        let before = &rest[..open];
+        let average_start = before
+            .strip_suffix(' ')
+            .map(|s| s.trim_end_matches(|c: char| c.is_ascii_digit()).len());
+
+        match (
+            average_start.filter(|&start| start < open - 1),
+            parse_dice(&rest[open + 1..close]),
+        ) {
+            (Some(start), Some((count, sides, modifier))) => {
+                let count = ((count as f64 * ratio).round() as i64).max(1);
+                let average = (count * (sides + 1) / 2 + modifier).max(1);
+
+                output.push_str(&rest[..start]);
+                output.push_str(&format!("{} ({}d{}", average, count, sides));
+                match modifier {
+                    0 => output.push(')'),
+                    m if m > 0 => output.push_str(&format!(" + {})", m)),
+                    m => output.push_str(&format!(" - {})", -m)),
+                }
+            }
+            _ => output.push_str(&rest[..=close]),
+        }
+
+        rest = &rest[close + 1..];
+    }
+
+    output.push_str(rest);
+    output
+}
+
+/// Shift attack bonuses written as eg. "+4 to hit".
+fn shift_attack_bonuses(text: &str, delta: i64) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(pos) = rest.find(" to hit") {
+        let before = &rest[..pos];
+        let digits_start = before.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+
+        match (
+            before[..digits_start].strip_suffix(['+', '-']),
+            before[digits_start..].parse::<i64>(),
+        ) {
+            (Some(prefix), Ok(bonus)) => {
+                let bonus = if before[..digits_start].ends_with('-') {
+                    -bonus
+                } else {
+                    bonus
+                };
+
+                output.push_str(prefix);
+                output.push_str(&format!("{:+}", bonus + delta));
+            }
+            _ => output.push_str(before),
+        }
+
+        output.push_str(" to hit");
+        rest = &rest[pos + " to hit".len()..];
+    }
+
+    output.push_str(rest);
+    output
+}
+
+/// Shift save DCs written as eg. "DC 13".
+fn shift_save_dcs(text: &str, delta: i64) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(pos) = rest.find("DC ") {
+        output.push_str(&rest[..pos + "DC ".len()]);
+        rest = &rest[pos + "DC ".len()..];
+
+        let digits_len = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        if let Ok(dc) = rest[..digits_len].parse::<i64>() {
+            output.push_str(&(dc + delta).to_string());
+            rest = &rest[digits_len..];
+        }
+    }
+
+    output.push_str(rest);
+    output
+}
+
+impl FromStr for ChallengeRating {
+    type Err = ();
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        GUIDELINES
+            .iter()
+            .position(|guideline| guideline.name == raw.trim())
+            .or_else(|| {
+                raw.trim()
+                    .parse::<f64>()
+                    .ok()
+                    .and_then(ChallengeRating::from_value)
+                    .map(|cr| cr.0)
+            })
+            .map(Self)
+            .ok_or(())
+    }
+}
+
+impl fmt::Display for ChallengeRating {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.guideline().name)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn challenge_rating_test() {
+        assert_eq!(
+            Ok("1/4".to_string()),
+            "1/4".parse().map(|cr: ChallengeRating| cr.to_string())
+        );
+        assert_eq!(
+            Ok("1/2".to_string()),
+            "0.5".parse().map(|cr: ChallengeRating| cr.to_string())
+        );
+        assert_eq!(
+            Ok("30".to_string()),
+            "30".parse().map(|cr: ChallengeRating| cr.to_string())
+        );
+        assert_eq!(Err(()), "31".parse::<ChallengeRating>());
+        assert_eq!(Err(()), "1/3".parse::<ChallengeRating>());
+    }
+
+    #[test]
+    fn whole_test() {
+        assert_eq!("14", ChallengeRating::whole(14).to_string());
+        assert_eq!("30", ChallengeRating::whole(40).to_string());
+        assert_eq!(18, ChallengeRating::whole(14).save_dc());
+        assert_eq!(13, ChallengeRating::whole(0).save_dc());
+    }
+
+    #[test]
+    fn scale_monster_test() {
+        let goblin = json!({
+            "name": "Goblin",
+            "constitution": 10,
+            "hit_points": 7,
+            "hit_points_roll": "2d6",
+            "challenge_rating": 0.25,
+            "xp": 50,
+            "proficiencies": [{"value": 6, "proficiency": {"index": "skill-stealth", "name": "Skill: Stealth", "url": ""}}],
+            "actions": [{
+                "name": "Scimitar",
+                "desc": "Melee Weapon Attack: +4 to hit, reach 5 ft., one target. Hit: 5 (1d6 + 2) slashing damage.",
+            }, {
+                "name": "Shriek",
+                "desc": "Each creature within 10 ft. must succeed on a DC 10 Wisdom saving throw.",
+            }],
+        });
+
+        let scaled = scale_monster(&goblin, "3".parse().unwrap()).unwrap();
+
+        assert_eq!(json!(3.), scaled["challenge_rating"]);
+        assert_eq!(json!(700), scaled["xp"]);
+        assert_eq!(json!(2), scaled["proficiency_bonus"]);
+        assert_eq!(json!(6), scaled["proficiencies"][0]["value"]);
+        assert_eq!(json!("5d6"), scaled["hit_points_roll"]);
+        assert_eq!(json!(17), scaled["hit_points"]);
+        assert_eq!(
+            json!("Melee Weapon Attack: +5 to hit, reach 5 ft., one target. Hit: 19 (5d6 + 2) slashing damage."),
+            scaled["actions"][0]["desc"],
+        );
+        assert_eq!(
+            json!("Each creature within 10 ft. must succeed on a DC 10 Wisdom saving throw."),
+            scaled["actions"][1]["desc"],
+        );
+
+        let scaled = scale_monster(&goblin, "5".parse().unwrap()).unwrap();
+        assert_eq!(json!(3), scaled["proficiency_bonus"]);
+        assert_eq!(json!(7), scaled["proficiencies"][0]["value"]);
+        assert_eq!(
+            json!("Each creature within 10 ft. must succeed on a DC 12 Wisdom saving throw."),
+            scaled["actions"][1]["desc"],
+        );
+
+        assert_eq!(
+            Err("The monster doesn't have a valid challenge rating.".to_string()),
+            scale_monster(&json!({"name": "Goblin"}), "3".parse().unwrap()),
+        );
+    }
+
+    #[test]
+    fn scale_damage_test() {
+        assert_eq!("Hit: 10 (3d6).", scale_damage("Hit: 3 (1d6).", 3.));
+        assert_eq!(
+            "Hit: 1 (1d4 - 1) and (not dice).",
+            scale_damage("Hit: 1 (1d4 - 1) and (not dice).", 0.1),
+        );
+        assert_eq!("Unclosed (1d6", scale_damage("Unclosed (1d6", 2.));
+    }
+
+    #[test]
+    fn shift_attack_bonuses_test() {
+        assert_eq!("+7 to hit", shift_attack_bonuses("+4 to hit", 3));
+        assert_eq!("+1 to hit", shift_attack_bonuses("-1 to hit", 2));
+        assert_eq!("easy to hit", shift_attack_bonuses("easy to hit", 2));
+    }
+
+    #[test]
+    fn parse_dice_test() {
+        assert_eq!(Some((2, 8, 4)), parse_dice("2d8 + 4"));
+        assert_eq!(Some((7, 10, -7)), parse_dice("7d10-7"));
+        assert_eq!(Some((1, 6, 0)), parse_dice("1d6"));
+        assert_eq!(None, parse_dice("not dice"));
+    }
+}
@@ -0,0 +1,240 @@
+use super::{
+    Class, Condition, Disease, HomebrewEntry, Item, ItemCategory, Madness, MagicItem, Spell, Trait,
+};
+use std::collections::HashSet;
+
+/// The number of results shown for each category before the rest are summarized.
+const RESULTS_PER_CATEGORY: usize = 10;
+
+struct Category {
+    title: &'static str,
+
+    /// Prepended to each result to make a command, eg. "srd monster " for "srd monster Goblin".
+    prefix: &'static str,
+
+    results: Vec<(u32, String)>,
+}
+
+/// Search the names and text of every SRD reference entry for all of the given keywords,
+/// returning the matches grouped by category. Matches in an entry's name rank above matches in
+/// its text, and categories are ordered by their best match.
+pub fn search(query: &str) -> Option<String> {
+    let query = query.trim().to_lowercase();
+    let terms: Vec<&str> = query.split_whitespace().collect();
+
+    if terms.is_empty() {
+        return None;
+    }
+
+    let mut categories: Vec<Category> = [
+        category(
+            "Spells",
+            &terms,
+            Spell::get_words()
+                .filter_map(|word| word.parse::<Spell>().ok())
+                .map(|spell| (spell.get_name(), spell.get_output())),
+        ),
+        category(
+            "Classes",
+            &terms,
+            Class::get_words()
+                .filter(|word| !word.ends_with(|c: char| c.is_ascii_digit()))
+                .filter_map(|word| word.parse::<Class>().ok())
+                .map(|class| (class.get_name(), class.get_output())),
+        ),
+        category(
+            "Equipment",
+            &terms,
+            Item::get_words()
+                .filter_map(|word| word.parse::<Item>().ok())
+                .map(|item| (item.get_name(), item.get_output())),
+        ),
+        category(
+            "Equipment categories",
+            &terms,
+            ItemCategory::get_words()
+                .filter_map(|word| word.parse::<ItemCategory>().ok())
+                .map(|category| (category.get_name(), category.get_output())),
+        ),
+        category(
+            "Magic items",
+            &terms,
+            MagicItem::get_all().map(|item| (item.get_name(), item.get_output())),
+        ),
+        category(
+            "Monsters",
+            &terms,
+            HomebrewEntry::srd_monsters()
+                .map(|monster| (monster.name().to_string(), monster.details())),
+        )
+        .with_prefix("srd monster "),
+        category(
+            "Conditions",
+            &terms,
+            Condition::get_words()
+                .filter_map(|word| word.parse::<Condition>().ok())
+                .map(|condition| (condition.get_name(), condition.get_output())),
+        ),
+        category(
+            "Diseases",
+            &terms,
+            Disease::get_words()
+                .filter_map(|word| word.parse::<Disease>().ok())
+                .map(|disease| (disease.get_name(), disease.get_output())),
+        ),
+        category(
+            "Madness",
+            &terms,
+            Madness::get_words()
+                .filter_map(|word| word.parse::<Madness>().ok())
+                .map(|madness| (madness.get_name(), madness.get_output())),
+        ),
+        category(
+            "Traits",
+            &terms,
+            Trait::get_words()
+                .filter_map(|word| word.parse::<Trait>().ok())
+                .map(|t| (t.get_name(), t.get_output())),
+        ),
+    ]
+    .into_iter()
+    .filter(|category| !category.results.is_empty())
+    .collect();
+
+    if categories.is_empty() {
+        return None;
+    }
+
+    // Stable sort, so categories with equally good matches keep their order above.
+    categories.sort_by_key(|category| std::cmp::Reverse(category.results[0].0));
+
+    let mut output = format!("# Search results for \"{}\"", query);
+
+    for category in categories {
+        output.push_str(&format!("\n\n## {}\n", category.title));
+
+        for (_, name) in category.results.iter().take(RESULTS_PER_CATEGORY) {
+            output.push_str(&format!("\n* `{}{}`", category.prefix, name));
+        }
+
+        if category.results.len() > RESULTS_PER_CATEGORY {
+            output.push_str(&format!(
+                "\n* ...and {} more",
+                category.results.len() - RESULTS_PER_CATEGORY,
+            ));
+        }
+    }
+
+    Some(output)
+}
+
+/// Score and sort the entries of a category, dropping those that don't match every term. The
+/// same entry may be listed more than once under different aliases, so duplicates are skipped.
+fn category<N: AsRef<str>, O: AsRef<str>>(
+    title: &'static str,
+    terms: &[&str],
+    entries: impl Iterator<Item = (N, O)>,
+) -> Category {
+    let mut seen = HashSet::new();
+
+    let mut results: Vec<(u32, String)> = entries
+        .filter(|(name, _)| seen.insert(name.as_ref().to_string()))
+        .filter_map(|(name, output)| {
+            Some((
+                score(terms, name.as_ref(), output.as_ref())?,
+                name.as_ref().to_string(),
+            ))
+        })
+        .collect();
+
+    results.sort_by(|(a_score, a_name), (b_score, b_name)| {
+        b_score.cmp(a_score).then_with(|| a_name.cmp(b_name))
+    });
+
+    Category {
+        title,
+        prefix: "",
+        results,
+    }
+}
+
+impl Category {
+    fn with_prefix(mut self, prefix: &'static str) -> Self {
+        self.prefix = prefix;
+        self
+    }
+}
+
+/// The relevance of an entry to a search, or None if any of the terms appears in neither its
+/// name nor its text.
+fn score(terms: &[&str], name: &str, output: &str) -> Option<u32> {
+    let name = name.to_lowercase();
+    let output = output.to_lowercase();
+    let query = terms.join(" ");
+
+    let mut score = if name == query {
+        1000
+    } else if name.starts_with(&query) {
+        100
+    } else {
+        0
+    };
+
+    for term in terms {
+        if name
+            .split(|c: char| !c.is_alphanumeric())
+            .any(|word| word.starts_with(term))
+        {
+            score += 10;
+        } else {
+            match output.matches(term).count() {
+                0 => return None,
+                count => score += count.min(5) as u32,
+            }
+        }
+    }
+
+    Some(score)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn score_test() {
+        assert_eq!(Some(1010), score(&["prone"], "Prone", "# Prone"));
+        assert_eq!(
+            Some(1020),
+            score(&["sight", "rot"], "Sight Rot", "# Sight Rot"),
+        );
+        assert_eq!(Some(110), score(&["sight"], "Sight Rot", "# Sight Rot"));
+        assert_eq!(Some(10), score(&["rot"], "Sight Rot", "# Sight Rot"));
+        assert_eq!(
+            Some(2),
+            score(&["blind"], "Sight Rot", "blinds the victim... blinded"),
+        );
+        assert_eq!(Some(5), score(&["the"], "Sight Rot", &"the ".repeat(20)));
+        assert_eq!(None, score(&["rot", "fever"], "Sight Rot", "# Sight Rot"));
+    }
+
+    #[test]
+    fn search_test() {
+        assert_eq!(None, search(""));
+        assert_eq!(None, search("xyzzyplugh"));
+
+        let output = search("Madness").unwrap();
+        assert!(
+            output.starts_with(
+                "# Search results for \"madness\"\n\n## Madness\n\n* `Madness`\n* `Indefinite Madness`\n* `Long-Term Madness`\n* `Short-Term Madness`",
+            ),
+            "{}",
+            output,
+        );
+        assert!(
+            output.contains("\n\n## Diseases\n\n* `Cackle Fever`"),
+            "{}",
+            output,
+        );
+    }
+}
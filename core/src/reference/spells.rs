@@ -0,0 +1,281 @@
+use super::{Class, Spell};
+use crate::utils::{capitalize, CaseInsensitiveStr};
+use std::fmt;
+
+const SCHOOLS: &[&str] = &[
+    "abjuration",
+    "conjuration",
+    "divination",
+    "enchantment",
+    "evocation",
+    "illusion",
+    "necromancy",
+    "transmutation",
+];
+
+/// A query against the SRD spell list, eg. "3rd-level wizard spells" or "necromancy cantrips".
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SpellFilter {
+    class: Option<&'static str>,
+    level: Option<u8>,
+    school: Option<&'static str>,
+}
+
+pub struct TableView<'a>(&'a SpellFilter);
+
+impl SpellFilter {
+    pub fn with_class(mut self, class: &'static str) -> Self {
+        self.class = Some(class);
+        self
+    }
+
+    pub fn with_level(mut self, level: u8) -> Self {
+        self.level = Some(level);
+        self
+    }
+
+    pub fn with_school(mut self, school: &'static str) -> Self {
+        self.school = Some(school);
+        self
+    }
+
+    pub fn matches(&self, spell: &Spell) -> bool {
+        self.class.map_or(true, |class| {
+            spell.get_classes().iter().any(|c| c.eq_ci(class))
+        }) && self.level.map_or(true, |level| spell.get_level() == level)
+            && self
+                .school
+                .map_or(true, |school| spell.get_school() == school)
+    }
+
+    /// The matching spells, ordered by level and then by name.
+    pub fn spells(&self) -> Vec<Spell> {
+        let mut spells: Vec<Spell> = Spell::get_words()
+            .filter_map(|word| word.parse::<Spell>().ok())
+            .filter(|spell| self.matches(spell))
+            .collect();
+
+        spells.sort_by(|a, b| {
+            a.get_level()
+                .cmp(&b.get_level())
+                .then_with(|| a.get_name().cmp(b.get_name()))
+        });
+
+        spells
+    }
+
+    pub fn display_table(&self) -> TableView {
+        TableView(self)
+    }
+
+    /// Parse the terms following "spells", eg. "wizard 3" or "necromancy cantrips". Every word
+    /// must be a class, a school of magic, or a spell level; an empty filter isn't a match.
+    pub fn parse(input: &str) -> Option<Self> {
+        let mut filter = Self::default();
+
+        for word in input.split_whitespace() {
+            if let Some(class) = Class::get_words()
+                .filter(|c| !c.ends_with(|c: char| c.is_ascii_digit()))
+                .find(|c| c.eq_ci(word))
+            {
+                if filter.class.replace(class).is_some() {
+                    return None;
+                }
+            } else if let Some(school) = SCHOOLS.iter().find(|s| s.eq_ci(word)) {
+                if filter.school.replace(school).is_some() {
+                    return None;
+                }
+            } else if let Some(level) = parse_level(word) {
+                if filter.level.replace(level).is_some() {
+                    return None;
+                }
+            } else {
+                return None;
+            }
+        }
+
+        if filter == Self::default() {
+            None
+        } else {
+            Some(filter)
+        }
+    }
+
+    fn title(&self) -> String {
+        let mut words = Vec::new();
+
+        match self.level {
+            Some(0) | None => {}
+            Some(level) => words.push(format!("{}-level", ordinal(level))),
+        }
+
+        if let Some(school) = self.school {
+            words.push(school.to_string());
+        }
+
+        if let Some(class) = self.class {
+            words.push(class.to_lowercase());
+        }
+
+        words.push(if self.level == Some(0) {
+            "cantrips".to_string()
+        } else {
+            "spells".to_string()
+        });
+
+        capitalize(&words.join(" "))
+    }
+}
+
+fn parse_level(word: &str) -> Option<u8> {
+    if word.eq_ci("cantrip") || word.eq_ci("cantrips") {
+        return Some(0);
+    }
+
+    let word = word.strip_suffix_ci("-level").unwrap_or(word);
+    let digits = word
+        .strip_suffix_ci("st")
+        .or_else(|| word.strip_suffix_ci("nd"))
+        .or_else(|| word.strip_suffix_ci("rd"))
+        .or_else(|| word.strip_suffix_ci("th"))
+        .unwrap_or(word);
+
+    digits.parse().ok().filter(|level| (1..=9).contains(level))
+}
+
+fn ordinal(level: u8) -> String {
+    match level {
+        1 => "1st".to_string(),
+        2 => "2nd".to_string(),
+        3 => "3rd".to_string(),
+        n => format!("{}th", n),
+    }
+}
+
+impl fmt::Display for SpellFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut words = Vec::new();
+
+        if let Some(class) = self.class {
+            words.push(class.to_lowercase());
+        }
+
+        if let Some(school) = self.school {
+            words.push(school.to_string());
+        }
+
+        match self.level {
+            Some(0) => words.push("cantrips".to_string()),
+            Some(level) => words.push(level.to_string()),
+            None => {}
+        }
+
+        write!(f, "{}", words.join(" "))
+    }
+}
+
+impl<'a> fmt::Display for TableView<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let filter = self.0;
+
+        write!(
+            f,
+            "# {}\n\n| Name | Level | School |\n|---|--:|---|",
+            filter.title(),
+        )?;
+
+        filter.spells().iter().try_for_each(|spell| {
+            write!(
+                f,
+                "\n| `{}` | {} | {} |",
+                spell.get_name(),
+                match spell.get_level() {
+                    0 => "cantrip".to_string(),
+                    level => ordinal(level),
+                },
+                capitalize(spell.get_school()),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        assert_eq!(
+            Some(SpellFilter::default().with_class("Wizard").with_level(3)),
+            SpellFilter::parse("wizard 3"),
+        );
+        assert_eq!(
+            Some(
+                SpellFilter::default()
+                    .with_school("necromancy")
+                    .with_level(0)
+            ),
+            SpellFilter::parse("Necromancy cantrips"),
+        );
+        assert_eq!(
+            Some(SpellFilter::default().with_level(2)),
+            SpellFilter::parse("2nd-level"),
+        );
+        assert_eq!(
+            Some(SpellFilter::default().with_level(9)),
+            SpellFilter::parse("9th"),
+        );
+
+        assert_eq!(None, SpellFilter::parse(""));
+        assert_eq!(None, SpellFilter::parse("10"));
+        assert_eq!(None, SpellFilter::parse("wizard wizard"));
+        assert_eq!(None, SpellFilter::parse("3 4"));
+        assert_eq!(None, SpellFilter::parse("wizard potato"));
+    }
+
+    #[test]
+    fn display_test() {
+        ["wizard", "necromancy cantrips", "wizard evocation 3", "1"]
+            .into_iter()
+            .for_each(|input| {
+                assert_eq!(
+                    input,
+                    SpellFilter::parse(input).unwrap().to_string(),
+                    "{}",
+                    input,
+                )
+            });
+    }
+
+    #[test]
+    fn title_test() {
+        assert_eq!(
+            "3rd-level wizard spells",
+            SpellFilter::parse("wizard 3").unwrap().title(),
+        );
+        assert_eq!(
+            "Necromancy cantrips",
+            SpellFilter::parse("necromancy cantrips").unwrap().title(),
+        );
+        assert_eq!(
+            "Wizard spells",
+            SpellFilter::parse("wizard").unwrap().title(),
+        );
+    }
+
+    #[test]
+    fn matches_test() {
+        let spell = Spell::Fireball;
+
+        assert!(SpellFilter::default().matches(&spell));
+        assert!(SpellFilter::default().with_class("Wizard").matches(&spell));
+        assert!(SpellFilter::default().with_level(3).matches(&spell));
+        assert!(SpellFilter::default()
+            .with_school("evocation")
+            .matches(&spell));
+        assert!(!SpellFilter::default().with_level(2).matches(&spell));
+        assert!(!SpellFilter::default()
+            .with_school("necromancy")
+            .matches(&spell));
+    }
+}
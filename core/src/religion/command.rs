@@ -0,0 +1,480 @@
+use super::generate::{generate_deity, generate_pantheon, name};
+use super::{Domain, Pantheon};
+use crate::app::{
+    AppMeta, Autocomplete, AutocompleteSuggestion, CommandError, CommandMatches, ContextAwareParse,
+    Runnable,
+};
+use crate::storage::{Change, KeyValue};
+use crate::utils::{quoted_words, CaseInsensitiveStr};
+use crate::world::npc::Ethnicity;
+use crate::world::{Place, Thing};
+use async_trait::async_trait;
+use std::fmt;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ReligionCommand {
+    CreateDeity {
+        domain: Option<Domain>,
+        people: Option<Ethnicity>,
+    },
+    CreatePantheon,
+    Dedicate {
+        place: String,
+        deity: String,
+    },
+    Pantheon,
+}
+
+impl ReligionCommand {
+    /// Parse "dwarvish deity of the forge", following "create". The people and domain are
+    /// both optional.
+    fn parse_create_deity(input: &str) -> Option<Self> {
+        let (people, rest) = if input.starts_with_ci("deity") {
+            (None, input)
+        } else {
+            let (people, rest) = input.split_once(' ')?;
+            (Some(people.parse().ok()?), rest)
+        };
+        let rest = rest.strip_prefix_ci("deity")?;
+
+        let domain = if rest.is_empty() {
+            None
+        } else {
+            let rest = rest.strip_prefix_ci(" of ")?;
+            Some(rest.strip_prefix_ci("the ").unwrap_or(rest).parse().ok()?)
+        };
+
+        Some(Self::CreateDeity { domain, people })
+    }
+
+    /// Parse "The Glowing Altar to Aurelos", following "dedicate".
+    fn parse_dedicate(input: &str) -> Option<Self> {
+        let word = quoted_words(input)
+            .skip(1)
+            .filter(|word| word.as_str().eq_ci("to"))
+            .last()?;
+        let (place, deity) = (
+            input[..word.range().start].trim(),
+            input[word.range().end..].trim(),
+        );
+
+        if place.is_empty() || deity.is_empty() {
+            None
+        } else {
+            Some(Self::Dedicate {
+                place: place.to_string(),
+                deity: deity.to_string(),
+            })
+        }
+    }
+}
+
+fn no_deity(name: &str) -> CommandError {
+    CommandError::unknown_entity(
+        name,
+        format!(
+            r#"There is no deity named "{}". Use `pantheon` to see the deities of your campaign."#,
+            name,
+        ),
+    )
+}
+
+async fn save(pantheon: Pantheon, app_meta: &mut AppMeta) -> Result<(), CommandError> {
+    app_meta
+        .repository
+        .modify(Change::SetKeyValue {
+            key_value: KeyValue::Pantheon(Some(pantheon)),
+        })
+        .await
+        .map(|_| ())
+        .map_err(|_| CommandError::storage("Unable to save the pantheon."))
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Runnable for ReligionCommand {
+    async fn run(self, _input: &str, app_meta: &mut AppMeta) -> Result<String, CommandError> {
+        let mut pantheon = super::load_pantheon(app_meta).await;
+
+        match self {
+            Self::CreateDeity { domain, people } => {
+                let mut deity =
+                    generate_deity(&mut app_meta.rng, &app_meta.demographics, domain, people);
+
+                while pantheon.get(&deity.name).is_some() {
+                    deity.name = name(&mut app_meta.rng);
+                }
+
+                let output = format!(
+                    "{}\n\n_{} has been added to your campaign's `pantheon`. Use `undo` to remove them._",
+                    deity, deity.name,
+                );
+
+                pantheon.add(deity);
+                save(pantheon, app_meta).await?;
+                Ok(output)
+            }
+            Self::CreatePantheon => {
+                let new_pantheon = generate_pantheon(&mut app_meta.rng, &app_meta.demographics);
+                let mut output = "# Pantheon".to_string();
+
+                for deity in new_pantheon.iter() {
+                    output.push_str(&format!("\n\n{}", deity));
+                }
+
+                output.push_str(if pantheon.is_empty() {
+                    "\n\n_The pantheon has been saved, and the temples and shrines you create will be dedicated to its deities. Use `undo` to remove it._"
+                } else {
+                    "\n\n_The pantheon has replaced your previous one, and the temples and shrines you create will be dedicated to its deities. Use `undo` to restore the previous pantheon._"
+                });
+
+                save(new_pantheon, app_meta).await?;
+                Ok(output)
+            }
+            Self::Dedicate { place, deity } => {
+                let deity = pantheon.get(&deity).ok_or_else(|| no_deity(&deity))?;
+
+                let place = match app_meta.repository.get_by_name(&place).await {
+                    Ok(Thing::Place(place)) => place,
+                    _ => {
+                        return Err(CommandError::unknown_entity(
+                            &place,
+                            format!(r#"There is no place named "{}"."#, place),
+                        ))
+                    }
+                };
+
+                app_meta
+                    .repository
+                    .modify(Change::Edit {
+                        name: place.name.to_string(),
+                        uuid: place.uuid.as_ref().map(|uuid| uuid.as_ref().to_owned()),
+                        diff: Place {
+                            worship: deity.name.clone().into(),
+                            ..Default::default()
+                        }
+                        .into(),
+                    })
+                    .await
+                    .map_err(|_| CommandError::storage("Unable to save the place."))?;
+
+                Ok(format!(
+                    "`{}` is now dedicated to **{}**, {}. Use `undo` to reverse this.",
+                    place.name, deity.name, deity.title,
+                ))
+            }
+            Self::Pantheon => {
+                if pantheon.is_empty() {
+                    return Ok("# Pantheon\n\n*Your campaign has no deities yet. Use `create pantheon` to create a whole pantheon, or `create deity` to create them one at a time.*".to_string());
+                }
+
+                let mut output = "# Pantheon\n".to_string();
+
+                for (i, (deity, share)) in pantheon
+                    .prevalence(&app_meta.demographics)
+                    .into_iter()
+                    .enumerate()
+                {
+                    output.push_str(&format!(
+                        "{}**{}**, {} ({}): {:.0}% of the faithful",
+                        if i == 0 { "\n" } else { "\\\n" },
+                        deity.name,
+                        deity.title,
+                        deity
+                            .domains
+                            .iter()
+                            .map(|domain| domain.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                        share * 100.,
+                    ));
+
+                    if let Some(people) = &deity.people {
+                        output.push_str(&format!(", chiefly {}", people));
+                    }
+                }
+
+                Ok(output)
+            }
+        }
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl ContextAwareParse for ReligionCommand {
+    async fn parse_input(input: &str, _app_meta: &AppMeta) -> CommandMatches<Self> {
+        if input.in_ci(&["pantheon", "deities"]) {
+            CommandMatches::new_canonical(Self::Pantheon)
+        } else if input.eq_ci("create pantheon") {
+            CommandMatches::new_canonical(Self::CreatePantheon)
+        } else if let Some(input) = input.strip_prefix_ci("create ") {
+            Self::parse_create_deity(input)
+                .map(CommandMatches::new_canonical)
+                .unwrap_or_default()
+        } else if let Some(input) = input.strip_prefix_ci("dedicate ") {
+            Self::parse_dedicate(input)
+                .map(CommandMatches::new_canonical)
+                .unwrap_or_default()
+        } else {
+            CommandMatches::default()
+        }
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Autocomplete for ReligionCommand {
+    async fn autocomplete(input: &str, _app_meta: &AppMeta) -> Vec<AutocompleteSuggestion> {
+        if input.is_empty() {
+            return Vec::new();
+        }
+
+        [
+            ("create deity", "generate a deity for the pantheon"),
+            (
+                "create deity of [domain]",
+                "generate a deity of war, the forge, etc.",
+            ),
+            ("create pantheon", "generate a pantheon of deities"),
+            (
+                "dedicate [place] to [deity]",
+                "set the deity worshipped at a place",
+            ),
+            ("deities", "list the deities of the pantheon"),
+            ("pantheon", "list the deities of the pantheon"),
+        ]
+        .into_iter()
+        .filter(|(term, _)| term.starts_with_ci(input))
+        .map(|(term, summary)| AutocompleteSuggestion::new(term, summary))
+        .collect()
+    }
+}
+
+impl fmt::Display for ReligionCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::CreateDeity { domain, people } => {
+                write!(f, "create ")?;
+
+                if let Some(people) = people {
+                    write!(f, "{} ", people.as_str())?;
+                }
+
+                write!(f, "deity")?;
+
+                if let Some(domain) = domain {
+                    write!(f, " of {}", domain)?;
+                }
+
+                Ok(())
+            }
+            Self::CreatePantheon => write!(f, "create pantheon"),
+            Self::Dedicate { place, deity } => write!(f, "dedicate {} to {}", place, deity),
+            Self::Pantheon => write!(f, "pantheon"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::assert_autocomplete;
+    use crate::storage::MemoryDataStore;
+    use crate::Event;
+    use tokio_test::block_on;
+
+    #[test]
+    fn parse_input_test() {
+        let app_meta = app_meta();
+
+        assert_eq!(
+            CommandMatches::new_canonical(ReligionCommand::CreateDeity {
+                domain: Some(Domain::Forge),
+                people: Some(Ethnicity::Dwarvish),
+            }),
+            block_on(ReligionCommand::parse_input(
+                "create Dwarvish deity of the forge",
+                &app_meta,
+            )),
+        );
+        assert_eq!(
+            CommandMatches::new_canonical(ReligionCommand::CreateDeity {
+                domain: Some(Domain::Tempest),
+                people: None,
+            }),
+            block_on(ReligionCommand::parse_input(
+                "CREATE DEITY OF STORM",
+                &app_meta
+            )),
+        );
+        assert_eq!(
+            CommandMatches::new_canonical(ReligionCommand::Dedicate {
+                place: "Shrine to the Sun".to_string(),
+                deity: "Aurelos".to_string(),
+            }),
+            block_on(ReligionCommand::parse_input(
+                "dedicate Shrine to the Sun to Aurelos",
+                &app_meta,
+            )),
+        );
+        assert_eq!(
+            CommandMatches::new_canonical(ReligionCommand::Pantheon),
+            block_on(ReligionCommand::parse_input("deities", &app_meta)),
+        );
+
+        [
+            "create deity of potatoes",
+            "create potato deity",
+            "create deityship",
+            "create inn",
+            "dedicate Bree",
+            "dedicate to Aurelos",
+        ]
+        .into_iter()
+        .for_each(|input| {
+            assert_eq!(
+                CommandMatches::default(),
+                block_on(ReligionCommand::parse_input(input, &app_meta)),
+                "{}",
+                input,
+            );
+        });
+    }
+
+    #[test]
+    fn autocomplete_test() {
+        let app_meta = app_meta();
+
+        assert_autocomplete(
+            &[
+                ("create deity", "generate a deity for the pantheon"),
+                (
+                    "create deity of [domain]",
+                    "generate a deity of war, the forge, etc.",
+                ),
+            ][..],
+            block_on(ReligionCommand::autocomplete("create d", &app_meta)),
+        );
+
+        assert_autocomplete(
+            &[
+                (
+                    "dedicate [place] to [deity]",
+                    "set the deity worshipped at a place",
+                ),
+                ("deities", "list the deities of the pantheon"),
+            ][..],
+            block_on(ReligionCommand::autocomplete("De", &app_meta)),
+        );
+    }
+
+    #[test]
+    fn display_test() {
+        let app_meta = app_meta();
+
+        [
+            ReligionCommand::CreateDeity {
+                domain: Some(Domain::Forge),
+                people: Some(Ethnicity::Dwarvish),
+            },
+            ReligionCommand::CreateDeity {
+                domain: None,
+                people: None,
+            },
+            ReligionCommand::CreatePantheon,
+            ReligionCommand::Dedicate {
+                place: "The Glowing Altar".to_string(),
+                deity: "Aurelos".to_string(),
+            },
+            ReligionCommand::Pantheon,
+        ]
+        .into_iter()
+        .for_each(|command| {
+            let command_string = command.to_string();
+            assert_ne!("", command_string);
+            assert_eq!(
+                CommandMatches::new_canonical(command),
+                block_on(ReligionCommand::parse_input(&command_string, &app_meta)),
+                "{}",
+                command_string,
+            );
+        });
+    }
+
+    #[test]
+    fn run_test() {
+        let mut app_meta = app_meta();
+
+        assert_eq!(
+            Ok("# Pantheon\n\n*Your campaign has no deities yet. Use `create pantheon` to create a whole pantheon, or `create deity` to create them one at a time.*".to_string()),
+            block_on(ReligionCommand::Pantheon.run("", &mut app_meta)),
+        );
+
+        let output = block_on(
+            ReligionCommand::CreateDeity {
+                domain: Some(Domain::War),
+                people: None,
+            }
+            .run("", &mut app_meta),
+        )
+        .unwrap();
+        assert!(output.contains("\n*Domains: war"), "{}", output);
+
+        let pantheon = block_on(crate::religion::load_pantheon(&app_meta));
+        let deity = pantheon.iter().next().unwrap().clone();
+
+        block_on(
+            app_meta.repository.modify(Change::CreateAndSave {
+                thing: Place {
+                    name: "The Glowing Altar".into(),
+                    ..Default::default()
+                }
+                .into(),
+            }),
+        )
+        .unwrap();
+
+        assert_eq!(
+            Err(no_deity("Moradin")),
+            block_on(dedicate("The Glowing Altar", "Moradin").run("", &mut app_meta)),
+        );
+        assert_eq!(
+            Ok(format!(
+                "`The Glowing Altar` is now dedicated to **{}**, {}. Use `undo` to reverse this.",
+                deity.name, deity.title,
+            )),
+            block_on(dedicate("the glowing altar", &deity.name).run("", &mut app_meta)),
+        );
+
+        match block_on(app_meta.repository.get_by_name("The Glowing Altar")) {
+            Ok(Thing::Place(place)) => assert_eq!(Some(&deity.name), place.worship.value()),
+            result => panic!("{:?}", result),
+        }
+
+        let output = block_on(ReligionCommand::CreatePantheon.run("", &mut app_meta)).unwrap();
+        assert!(output.starts_with("# Pantheon\n\n## "), "{}", output);
+        assert!(
+            output.ends_with("Use `undo` to restore the previous pantheon._"),
+            "{}",
+            output,
+        );
+
+        let output = block_on(ReligionCommand::Pantheon.run("", &mut app_meta)).unwrap();
+        assert!(output.starts_with("# Pantheon\n\n**"), "{}", output);
+        assert!(output.contains("% of the faithful"), "{}", output);
+    }
+
+    fn dedicate(place: &str, deity: &str) -> ReligionCommand {
+        ReligionCommand::Dedicate {
+            place: place.to_string(),
+            deity: deity.to_string(),
+        }
+    }
+
+    fn event_dispatcher(_event: Event) {}
+
+    fn app_meta() -> AppMeta {
+        AppMeta::new(MemoryDataStore::default(), &event_dispatcher)
+    }
+}
@@ -0,0 +1,280 @@
+//! Deities built from their domains: the domains decide how the deity is known, the symbol of
+//! their faith, and what their worshippers do, while the name is assembled from syllables.
+
+use super::{Deity, Domain, Pantheon};
+use crate::world::npc::Ethnicity;
+use crate::world::Demographics;
+use rand::prelude::*;
+
+/// The words that go with a domain.
+struct Words {
+    titles: &'static [&'static str],
+    symbols: &'static [&'static str],
+    worship: &'static [&'static str],
+}
+
+const PREFIXES: &[&str] = &[
+    "Ae", "Bal", "Cor", "Dra", "Eir", "Gal", "Hel", "Ior", "Kel", "Lath", "Mor", "Nym", "Ost",
+    "Pel", "Sel", "Tyr", "Ul", "Vor", "Xan", "Zar",
+];
+
+const MIDDLES: &[&str] = &["", "", "a", "e", "i", "o", "an", "ar", "en", "or"];
+
+const SUFFIXES: &[&str] = &[
+    "ara", "ath", "dor", "el", "ia", "ion", "is", "ix", "os", "oth", "und", "une", "ys",
+];
+
+/// Generate a deity, optionally of a given domain and people. A deity without a given people is
+/// as likely to be worshipped by all as to belong to one of the peoples of the region.
+pub fn generate_deity(
+    rng: &mut impl Rng,
+    demographics: &Demographics,
+    domain: Option<Domain>,
+    people: Option<Ethnicity>,
+) -> Deity {
+    let primary = domain.unwrap_or_else(|| *Domain::ALL.choose(rng).unwrap());
+    let mut domains = vec![primary];
+
+    if rng.gen_bool(0.5) {
+        if let Some(secondary) = Domain::ALL
+            .iter()
+            .filter(|domain| **domain != primary)
+            .choose(rng)
+        {
+            domains.push(*secondary);
+        }
+    }
+
+    let words = words(&primary);
+
+    Deity {
+        name: name(rng),
+        title: words.titles.choose(rng).unwrap().to_string(),
+        domains,
+        symbol: words.symbols.choose(rng).unwrap().to_string(),
+        worship: words.worship.choose(rng).unwrap().to_string(),
+        people: people.or_else(|| {
+            if rng.gen_bool(0.5) {
+                Some(demographics.gen_species_ethnicity(rng).1)
+            } else {
+                None
+            }
+        }),
+    }
+}
+
+/// Generate a pantheon of five to eight deities, each with a different primary domain.
+pub fn generate_pantheon(rng: &mut impl Rng, demographics: &Demographics) -> Pantheon {
+    let count = rng.gen_range(5..=8);
+    let mut pantheon = Pantheon::default();
+
+    for domain in Domain::ALL.choose_multiple(rng, count) {
+        let mut deity = generate_deity(rng, demographics, Some(*domain), None);
+
+        while pantheon.get(&deity.name).is_some() {
+            deity.name = name(rng);
+        }
+
+        pantheon.add(deity);
+    }
+
+    pantheon
+}
+
+pub fn name(rng: &mut impl Rng) -> String {
+    format!(
+        "{}{}{}",
+        PREFIXES.choose(rng).unwrap(),
+        MIDDLES.choose(rng).unwrap(),
+        SUFFIXES.choose(rng).unwrap(),
+    )
+}
+
+fn words(domain: &Domain) -> Words {
+    match domain {
+        Domain::Arcana => Words {
+            titles: &[
+                "the Keeper of Secrets",
+                "the Weaver of Spells",
+                "the First Mage",
+            ],
+            symbols: &[
+                "an eye within a seven-pointed star",
+                "a staff crowned with a crescent",
+            ],
+            worship: &[
+                "Apprentices burn their first failed spell scroll as an offering.",
+                "The faithful trace sigils in the air before any act of magic.",
+            ],
+        },
+        Domain::Death => Words {
+            titles: &["the Pale Lord", "the Last Door", "the Gatherer"],
+            symbols: &["a skull with a coin in its teeth", "a black candle, unlit"],
+            worship: &[
+                "The dead are buried facing the east, with a coin for the journey.",
+                "Names of the departed are spoken aloud once a year at midnight.",
+            ],
+        },
+        Domain::Forge => Words {
+            titles: &[
+                "the Worldsmith",
+                "the Forgefather",
+                "the Hammer of Creation",
+            ],
+            symbols: &["an anvil wreathed in flame", "a hammer crossed with tongs"],
+            worship: &[
+                "Smiths offer the first blade of every forging at the altar.",
+                "The faithful keep a hearth burning that is never allowed to go out.",
+            ],
+        },
+        Domain::Knowledge => Words {
+            titles: &["the Lorekeeper", "the All-Seeing", "the Scribe of Ages"],
+            symbols: &["an open book beneath a lamp", "a quill and a key"],
+            worship: &[
+                "Each of the faithful copies out a page of scripture every day.",
+                "Questions are written on slips of paper and left at the altar to be answered.",
+            ],
+        },
+        Domain::Life => Words {
+            titles: &["the Mother of Mercy", "the Healer", "the Giver of Breath"],
+            symbols: &[
+                "a sheaf of wheat bound with a red cord",
+                "an open hand over a heart",
+            ],
+            worship: &[
+                "The sick are brought to the temple steps at dawn to be blessed.",
+                "The faithful share bread with a stranger on holy days.",
+            ],
+        },
+        Domain::Light => Words {
+            titles: &["the Morninglord", "the Radiant One", "the Dawnbringer"],
+            symbols: &["a rising sun", "a golden disc with twelve rays"],
+            worship: &[
+                "Hymns are sung at sunrise from the highest point in town.",
+                "Lamps are lit in every window on the longest night of the year.",
+            ],
+        },
+        Domain::Nature => Words {
+            titles: &["the Green Mother", "the Horned Lord", "the Oakfather"],
+            symbols: &["an oak leaf", "a stag's antlers entwined with ivy"],
+            worship: &[
+                "The faithful plant a tree for every child born to them.",
+                "Offerings of the first harvest are left in the forest for the beasts.",
+            ],
+        },
+        Domain::Order => Words {
+            titles: &["the Lawgiver", "the Just", "the Crowned Judge"],
+            symbols: &["a balanced set of scales", "a gauntlet clasping a scroll"],
+            worship: &[
+                "Oaths sworn before the altar are recorded and kept for a hundred years.",
+                "Disputes are brought to the priests to be judged on holy days.",
+            ],
+        },
+        Domain::Peace => Words {
+            titles: &["the Peacemaker", "the Quiet Heart", "the Dove"],
+            symbols: &["a white dove", "two hands clasped together"],
+            worship: &[
+                "No blade may be carried past the temple doors.",
+                "Old enemies share a cup of wine before the altar to end their feud.",
+            ],
+        },
+        Domain::Tempest => Words {
+            titles: &["the Stormlord", "the Lady of Storms", "the Wavemother"],
+            symbols: &[
+                "a bolt of lightning splitting a cloud",
+                "a trident rising from the waves",
+            ],
+            worship: &[
+                "Sailors pour a measure of their rum into the sea before setting out.",
+                "The faithful stand bareheaded in the rain of the first storm of the year.",
+            ],
+        },
+        Domain::Trickery => Words {
+            titles: &["the Laughing One", "the Masked God", "the Thousand Faces"],
+            symbols: &["a grinning mask", "a pair of loaded dice"],
+            worship: &[
+                "Pranks played on the priests are considered a form of prayer.",
+                "The faithful wear masks to every service so that none knows who prays.",
+            ],
+        },
+        Domain::Twilight => Words {
+            titles: &[
+                "the Watcher at Dusk",
+                "the Keeper of Dreams",
+                "the Lantern in the Dark",
+            ],
+            symbols: &[
+                "a lantern beneath a crescent moon",
+                "a closed eye ringed with stars",
+            ],
+            worship: &[
+                "The faithful keep a vigil from dusk until the first star appears.",
+                "Dreams are told to the priests each morning to be interpreted.",
+            ],
+        },
+        Domain::War => Words {
+            titles: &["the Red Hand", "the Battlelord", "the Shield of the People"],
+            symbols: &[
+                "a sword planted point-down in the earth",
+                "a bloodied shield",
+            ],
+            worship: &[
+                "Soldiers hang their first shield on the temple wall.",
+                "The names of the fallen are carved into the temple's pillars after every war.",
+            ],
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn generate_deity_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let demographics = Demographics::default();
+
+        for _ in 0..20 {
+            let deity = generate_deity(&mut rng, &demographics, Some(Domain::Forge), None);
+            assert_eq!(Domain::Forge, deity.domains[0]);
+            assert!(deity.domains.len() <= 2);
+            assert!(!deity.domains[1..].contains(&Domain::Forge), "{:?}", deity);
+            assert!(words(&Domain::Forge)
+                .symbols
+                .contains(&deity.symbol.as_str()));
+        }
+
+        let people = Some(Ethnicity::Dwarvish);
+        assert_eq!(
+            people,
+            generate_deity(&mut rng, &demographics, None, people).people,
+        );
+    }
+
+    #[test]
+    fn generate_pantheon_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let demographics = Demographics::default();
+
+        for _ in 0..10 {
+            let pantheon = generate_pantheon(&mut rng, &demographics);
+            let primaries: Vec<Domain> = pantheon.iter().map(|deity| deity.domains[0]).collect();
+            assert!((5..=8).contains(&primaries.len()), "{:?}", pantheon);
+
+            for (i, domain) in primaries.iter().enumerate() {
+                assert!(!primaries[i + 1..].contains(domain), "{:?}", pantheon);
+            }
+        }
+    }
+
+    #[test]
+    fn words_test() {
+        for domain in Domain::ALL {
+            let words = words(&domain);
+            assert!(!words.titles.is_empty(), "{}", domain);
+            assert!(!words.symbols.is_empty(), "{}", domain);
+            assert!(!words.worship.is_empty(), "{}", domain);
+        }
+    }
+}
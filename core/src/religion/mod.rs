@@ -0,0 +1,381 @@
+pub use command::ReligionCommand;
+
+mod command;
+mod generate;
+
+use crate::app::AppMeta;
+use crate::storage::KeyValue;
+use crate::utils::CaseInsensitiveStr;
+use crate::world::npc::Ethnicity;
+use crate::world::{Demographics, Place};
+use initiative_macros::WordList;
+use rand::distributions::WeightedIndex;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// The kinds of place that are dedicated to a deity, by their canonical term.
+const PLACES_OF_WORSHIP: &[&str] = &["abbey", "monastery", "shrine", "temple"];
+
+/// The deities of a campaign, in the order they were created.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct Pantheon(Vec<Deity>);
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Deity {
+    pub name: String,
+
+    /// How the deity is known to their faithful, eg. "the Lady of Storms".
+    pub title: String,
+
+    pub domains: Vec<Domain>,
+    pub symbol: String,
+
+    /// A practice observed by the faithful, as a sentence.
+    pub worship: String,
+
+    /// The people who hold the deity as their own, or `None` if they're worshipped by all.
+    #[serde(default)]
+    pub people: Option<Ethnicity>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, WordList)]
+#[serde(into = "&'static str", try_from = "&str")]
+pub enum Domain {
+    #[alias = "magic"]
+    Arcana,
+
+    Death,
+    Forge,
+    Knowledge,
+
+    #[alias = "healing"]
+    Life,
+
+    #[alias = "sun"]
+    Light,
+
+    Nature,
+    Order,
+    Peace,
+
+    #[alias = "storm"]
+    #[alias = "sea"]
+    Tempest,
+
+    #[alias = "trickster"]
+    Trickery,
+
+    Twilight,
+    War,
+}
+
+/// Load the campaign's pantheon from storage, or an empty one if no deities have been created yet
+/// or the data store is unavailable.
+pub async fn load_pantheon(app_meta: &AppMeta) -> Pantheon {
+    app_meta
+        .repository
+        .get_key_value(&KeyValue::Pantheon(None))
+        .await
+        .ok()
+        .and_then(KeyValue::pantheon)
+        .unwrap_or_default()
+}
+
+/// Dedicate a newly generated temple or shrine to a deity from the pantheon, favouring the
+/// deities of the peoples who live in the region. Places that aren't places of worship, or that
+/// are already dedicated, are left as they are.
+pub fn assign_worship(
+    place: &mut Place,
+    pantheon: &Pantheon,
+    demographics: &Demographics,
+    rng: &mut impl Rng,
+) {
+    let is_place_of_worship = place.subtype.value().map_or(false, |subtype| {
+        PLACES_OF_WORSHIP.contains(&subtype.as_str())
+    });
+
+    if is_place_of_worship && place.worship.is_none() {
+        if let Some(deity) = pantheon.choose(demographics, rng) {
+            place.worship.replace_with(|_| deity.name.clone());
+        }
+    }
+}
+
+impl Pantheon {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Deity> {
+        self.0.iter()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Deity> {
+        self.0.iter().find(|deity| deity.name.eq_ci(name))
+    }
+
+    /// Add a deity, replacing any deity of the same name.
+    pub fn add(&mut self, deity: Deity) {
+        if let Some(existing) = self.0.iter_mut().find(|d| d.name.eq_ci(&deity.name)) {
+            *existing = deity;
+        } else {
+            self.0.push(deity);
+        }
+    }
+
+    /// The share of the region's faithful who worship each deity, in the order of the pantheon.
+    /// Deities worshipped by all are honoured everywhere, while a people's own deities are
+    /// honoured in proportion to how many of that people live there.
+    pub fn prevalence(&self, demographics: &Demographics) -> Vec<(&Deity, f64)> {
+        let weights: Vec<f64> = self
+            .0
+            .iter()
+            .map(|deity| deity.weight(demographics))
+            .collect();
+        let total: f64 = weights.iter().sum();
+
+        self.0
+            .iter()
+            .zip(weights)
+            .map(|(deity, weight)| (deity, if total > 0. { weight / total } else { 0. }))
+            .collect()
+    }
+
+    /// Pick a deity at random, weighted by their prevalence in the region.
+    pub fn choose(&self, demographics: &Demographics, rng: &mut impl Rng) -> Option<&Deity> {
+        let weights = self.0.iter().map(|deity| deity.weight(demographics));
+
+        WeightedIndex::new(weights)
+            .ok()
+            .map(|dist| &self.0[dist.sample(rng)])
+    }
+}
+
+impl Domain {
+    pub const ALL: [Self; 13] = [
+        Self::Arcana,
+        Self::Death,
+        Self::Forge,
+        Self::Knowledge,
+        Self::Life,
+        Self::Light,
+        Self::Nature,
+        Self::Order,
+        Self::Peace,
+        Self::Tempest,
+        Self::Trickery,
+        Self::Twilight,
+        Self::War,
+    ];
+}
+
+impl Deity {
+    fn weight(&self, demographics: &Demographics) -> f64 {
+        if let Some(people) = &self.people {
+            3. * demographics.ethnicity_share(people)
+        } else {
+            1.
+        }
+    }
+}
+
+impl fmt::Display for Pantheon {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            serde_json::to_string(self).map_err(|_| fmt::Error)?,
+        )
+    }
+}
+
+impl FromStr for Pantheon {
+    type Err = ();
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(raw).map_err(|_| ())
+    }
+}
+
+/// The deity as shown when they're created, eg.
+///
+/// ```text
+/// ## Aurelos, the Worldsmith
+/// *Domains: forge, light · worshipped by the dwarvish people*
+///
+/// **Symbol:** an anvil wreathed in flame
+///
+/// **Worship:** Smiths offer the first blade of every forging at the altar.
+/// ```
+impl fmt::Display for Deity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "## {}, {}\n*Domains: {}",
+            self.name,
+            self.title,
+            self.domains
+                .iter()
+                .map(|domain| domain.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+        )?;
+
+        if let Some(people) = &self.people {
+            write!(f, " · worshipped by the {} people", people)?;
+        }
+
+        write!(
+            f,
+            "*\n\n**Symbol:** {}\n\n**Worship:** {}",
+            self.symbol, self.worship,
+        )
+    }
+}
+
+impl fmt::Display for Domain {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::world::npc::Species;
+    use std::collections::HashMap;
+
+    #[test]
+    fn pantheon_test() {
+        let mut pantheon = Pantheon::default();
+        assert!(pantheon.is_empty());
+
+        pantheon.add(deity("Aurelos", None));
+        pantheon.add(deity("Moradin", Some(Ethnicity::Dwarvish)));
+        assert_eq!(2, pantheon.iter().count());
+        assert_eq!(
+            Some(Some(Ethnicity::Dwarvish)),
+            pantheon.get("MORADIN").map(|deity| deity.people),
+        );
+
+        pantheon.add(deity("moradin", None));
+        assert_eq!(2, pantheon.iter().count());
+        assert_eq!(
+            Some(None),
+            pantheon.get("Moradin").map(|deity| deity.people)
+        );
+    }
+
+    #[test]
+    fn prevalence_test() {
+        let pantheon = Pantheon(vec![
+            deity("Aurelos", None),
+            deity("Moradin", Some(Ethnicity::Dwarvish)),
+            deity("Corellon", Some(Ethnicity::Elvish)),
+        ]);
+
+        let mut groups = HashMap::new();
+        groups.insert((Species::Dwarf, Ethnicity::Dwarvish), 50);
+        groups.insert((Species::Human, Ethnicity::Human), 50);
+        let demographics = Demographics::new(groups);
+
+        assert_eq!(
+            vec![("Aurelos", 0.4), ("Moradin", 0.6), ("Corellon", 0.)],
+            pantheon
+                .prevalence(&demographics)
+                .into_iter()
+                .map(|(deity, share)| (deity.name.as_str(), share))
+                .collect::<Vec<_>>(),
+        );
+
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..20 {
+            assert_ne!(
+                Some("Corellon"),
+                pantheon
+                    .choose(&demographics, &mut rng)
+                    .map(|deity| deity.name.as_str()),
+            );
+        }
+
+        assert_eq!(None, Pantheon::default().choose(&demographics, &mut rng),);
+    }
+
+    #[test]
+    fn assign_worship_test() {
+        let pantheon = Pantheon(vec![deity("Aurelos", None)]);
+        let demographics = Demographics::default();
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        let mut shrine = place("shrine");
+        assign_worship(&mut shrine, &pantheon, &demographics, &mut rng);
+        assert_eq!(Some("Aurelos"), shrine.worship.value().map(|s| s.as_str()));
+
+        let mut inn = place("inn");
+        assign_worship(&mut inn, &pantheon, &demographics, &mut rng);
+        assert!(inn.worship.is_none());
+
+        let mut temple = Place {
+            worship: "Moradin".into(),
+            ..place("temple")
+        };
+        assign_worship(&mut temple, &pantheon, &demographics, &mut rng);
+        assert_eq!(Some("Moradin"), temple.worship.value().map(|s| s.as_str()));
+    }
+
+    #[test]
+    fn display_test() {
+        let mut deity = deity("Aurelos", Some(Ethnicity::Dwarvish));
+
+        assert_eq!(
+            "## Aurelos, the Worldsmith
+*Domains: forge, light · worshipped by the dwarvish people*
+
+**Symbol:** an anvil wreathed in flame
+
+**Worship:** Smiths offer the first blade of every forging at the altar.",
+            deity.to_string(),
+        );
+
+        deity.people = None;
+        assert!(deity
+            .to_string()
+            .starts_with("## Aurelos, the Worldsmith\n*Domains: forge, light*\n\n"));
+    }
+
+    #[test]
+    fn serialize_deserialize_test() {
+        let pantheon = Pantheon(vec![deity("Aurelos", Some(Ethnicity::Dwarvish))]);
+
+        assert_eq!(
+            r#"[{"name":"Aurelos","title":"the Worldsmith","domains":["forge","light"],"symbol":"an anvil wreathed in flame","worship":"Smiths offer the first blade of every forging at the altar.","people":"dwarvish"}]"#,
+            pantheon.to_string(),
+        );
+        assert_eq!(Ok(pantheon.clone()), pantheon.to_string().parse());
+        assert_eq!(Ok(Domain::Tempest), "storm".parse());
+    }
+
+    fn deity(name: &str, people: Option<Ethnicity>) -> Deity {
+        Deity {
+            name: name.to_string(),
+            title: "the Worldsmith".to_string(),
+            domains: vec![Domain::Forge, Domain::Light],
+            symbol: "an anvil wreathed in flame".to_string(),
+            worship: "Smiths offer the first blade of every forging at the altar.".to_string(),
+            people,
+        }
+    }
+
+    fn place(subtype: &str) -> Place {
+        Place {
+            subtype: subtype
+                .parse::<crate::world::place::PlaceType>()
+                .unwrap()
+                .into(),
+            ..Default::default()
+        }
+    }
+}
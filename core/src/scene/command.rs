@@ -0,0 +1,438 @@
+use super::Scene;
+use crate::app::{
+    AppMeta, Autocomplete, AutocompleteSuggestion, CommandError, CommandMatches, ContextAwareParse,
+    Runnable,
+};
+use crate::storage::{Change, KeyValue};
+use crate::utils::CaseInsensitiveStr;
+use async_trait::async_trait;
+use std::fmt;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SceneCommand {
+    End,
+    List,
+    Recap(usize),
+    Show,
+    Start { name: String },
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Runnable for SceneCommand {
+    async fn run(self, _input: &str, app_meta: &mut AppMeta) -> Result<String, CommandError> {
+        let mut scenes = super::load(app_meta).await;
+
+        let response = match self {
+            Self::Show => {
+                return if let Some(scene) = &scenes.current {
+                    let recap = scene.recap(
+                        scenes.next_number(),
+                        game_time(app_meta).await,
+                        commands_since(scene, app_meta),
+                        created_since(scene, app_meta).await,
+                        rolls_since(scene, app_meta),
+                    );
+
+                    Ok(format!(
+                        "{}\n\n_Use `scene end` to end the scene and save a recap._",
+                        recap,
+                    ))
+                } else if scenes.recaps.is_empty() {
+                    Ok("*No scene is in progress. Use `scene start [name]` to keep track of what happens in a scene.*".to_string())
+                } else {
+                    Ok("*No scene is in progress. Use `scene start [name]` to start one, or `scenes` to see the recaps of earlier scenes.*".to_string())
+                };
+            }
+            Self::List => {
+                return if scenes.is_empty() {
+                    Ok("# Scenes\n\n*You haven't recorded any scenes yet. Use `scene start [name]` to keep track of what happens in a scene.*".to_string())
+                } else {
+                    let mut output = "# Scenes\n".to_string();
+
+                    for (i, recap) in scenes.recaps.iter().enumerate() {
+                        output.push_str(&format!(
+                            "{}`scene {}`: {}",
+                            if i == 0 { "\n" } else { "\\\n" },
+                            recap.number,
+                            recap.name,
+                        ));
+                    }
+
+                    if let Some(scene) = &scenes.current {
+                        output.push_str(&format!(
+                            "{}`scene`: {} (in progress)",
+                            if scenes.recaps.is_empty() {
+                                "\n"
+                            } else {
+                                "\\\n"
+                            },
+                            scene.name,
+                        ));
+                    }
+
+                    Ok(output)
+                };
+            }
+            Self::Recap(number) => {
+                return scenes
+                    .recaps
+                    .iter()
+                    .find(|recap| recap.number == number)
+                    .map(|recap| recap.to_string())
+                    .ok_or_else(|| {
+                        CommandError::unknown_entity(
+                            &number.to_string(),
+                            format!(
+                                "There is no recap of scene {}. Use `scenes` to see the scenes you've recorded.",
+                                number,
+                            ),
+                        )
+                    });
+            }
+            Self::Start { name } => {
+                if let Some(scene) = &scenes.current {
+                    return Err(CommandError::invalid_state(format!(
+                        "Scene {}, \"{}\", is still in progress. Use `scene end` to end it before starting another.",
+                        scenes.next_number(),
+                        scene.name,
+                    )));
+                }
+
+                let game_time = game_time(app_meta).await;
+                let sessions = crate::session::load(app_meta).await;
+                let journal = app_meta
+                    .repository
+                    .journal()
+                    .await
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(|thing| thing.uuid().copied())
+                    .collect();
+
+                let response = format!(
+                    "Scene {}, \"{}\", has started. Anything you create or roll until `scene end` will be part of it. Use `undo` to reverse this.",
+                    scenes.next_number(),
+                    name,
+                );
+
+                scenes.current = Some(Scene {
+                    name,
+                    game_time,
+                    session: sessions.current.as_ref().map(|_| sessions.next_number()),
+                    history_len: app_meta.history.len() + 1,
+                    rolls_len: app_meta.rng.log().len(),
+                    journal,
+                });
+
+                response
+            }
+            Self::End => {
+                let scene = scenes.current.take().ok_or_else(|| {
+                    CommandError::invalid_state(
+                        "No scene is in progress. Use `scene start [name]` to start one.",
+                    )
+                })?;
+
+                let recap = scene.recap(
+                    scenes.next_number(),
+                    game_time(app_meta).await,
+                    commands_since(&scene, app_meta),
+                    created_since(&scene, app_meta).await,
+                    rolls_since(&scene, app_meta),
+                );
+
+                let response = format!(
+                    "{}\n\n_The recap has been saved. Use `scene {}` to see it again, or `undo` to reverse this._",
+                    recap, recap.number,
+                );
+
+                scenes.recaps.push(recap);
+
+                response
+            }
+        };
+
+        app_meta
+            .repository
+            .modify(Change::SetKeyValue {
+                key_value: KeyValue::Scenes(Some(scenes)),
+            })
+            .await
+            .map(|_| response)
+            .map_err(|_| CommandError::storage("Unable to save the scene."))
+    }
+}
+
+async fn game_time(app_meta: &AppMeta) -> crate::time::Time {
+    app_meta
+        .repository
+        .get_key_value(&KeyValue::Time(None))
+        .await
+        .ok()
+        .and_then(KeyValue::time)
+        .unwrap_or_default()
+}
+
+fn commands_since(scene: &Scene, app_meta: &AppMeta) -> usize {
+    app_meta.history.len().saturating_sub(scene.history_len)
+}
+
+async fn created_since(scene: &Scene, app_meta: &AppMeta) -> Vec<String> {
+    app_meta
+        .repository
+        .journal()
+        .await
+        .unwrap_or_default()
+        .iter()
+        .filter(|thing| {
+            thing
+                .uuid()
+                .is_some_and(|uuid| !scene.journal.contains(uuid))
+        })
+        .map(|thing| thing.name().to_string())
+        .collect()
+}
+
+/// The dice rolled during the scene, as shown by `rolls`. The log isn't kept between visits, so
+/// if the app has been reloaded since the scene started, only the rolls since then are known.
+fn rolls_since(scene: &Scene, app_meta: &AppMeta) -> Vec<String> {
+    let log = app_meta.rng.log();
+
+    log.get(scene.rolls_len..)
+        .unwrap_or(log)
+        .iter()
+        .filter(|record| !record.results.is_empty())
+        .map(|record| format!("`{}`: {}", record.input, record.results.join("; ")))
+        .collect()
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl ContextAwareParse for SceneCommand {
+    async fn parse_input(input: &str, _app_meta: &AppMeta) -> CommandMatches<Self> {
+        if input.eq_ci("scene") {
+            CommandMatches::new_canonical(Self::Show)
+        } else if input.eq_ci("scenes") {
+            CommandMatches::new_canonical(Self::List)
+        } else if input.eq_ci("scene end") {
+            CommandMatches::new_canonical(Self::End)
+        } else if let Some(name) = input
+            .strip_prefix_ci("scene start ")
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+        {
+            CommandMatches::new_canonical(Self::Start {
+                name: name.to_string(),
+            })
+        } else if let Some(number) = input
+            .strip_prefix_ci("scene ")
+            .and_then(|s| s.trim().parse().ok())
+        {
+            CommandMatches::new_canonical(Self::Recap(number))
+        } else {
+            CommandMatches::default()
+        }
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Autocomplete for SceneCommand {
+    async fn autocomplete(input: &str, _app_meta: &AppMeta) -> Vec<AutocompleteSuggestion> {
+        if input.is_empty() {
+            return Vec::new();
+        }
+
+        [
+            ("scene", "show the scene in progress"),
+            ("scene [number]", "show the recap of a scene"),
+            ("scene end", "end the scene with a recap"),
+            ("scene start [name]", "start keeping track of a scene"),
+            ("scenes", "list the recorded scenes"),
+        ]
+        .into_iter()
+        .filter(|(term, _)| term.starts_with_ci(input))
+        .map(|(term, summary)| AutocompleteSuggestion::new(term, summary))
+        .collect()
+    }
+}
+
+impl fmt::Display for SceneCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::End => write!(f, "scene end"),
+            Self::List => write!(f, "scenes"),
+            Self::Recap(number) => write!(f, "scene {}", number),
+            Self::Show => write!(f, "scene"),
+            Self::Start { name } => write!(f, "scene start {}", name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::assert_autocomplete;
+    use crate::storage::MemoryDataStore;
+    use crate::world::{Npc, Thing};
+    use crate::Event;
+    use tokio_test::block_on;
+
+    #[test]
+    fn parse_input_test() {
+        let app_meta = app_meta();
+
+        assert_eq!(
+            CommandMatches::new_canonical(SceneCommand::Show),
+            block_on(SceneCommand::parse_input("SCENE", &app_meta)),
+        );
+        assert_eq!(
+            CommandMatches::new_canonical(SceneCommand::Start {
+                name: "Ambush at the bridge".to_string(),
+            }),
+            block_on(SceneCommand::parse_input(
+                "scene start Ambush at the bridge",
+                &app_meta,
+            )),
+        );
+        assert_eq!(
+            CommandMatches::new_canonical(SceneCommand::Recap(3)),
+            block_on(SceneCommand::parse_input("scene 3", &app_meta)),
+        );
+        assert_eq!(
+            CommandMatches::default(),
+            block_on(SceneCommand::parse_input("scene start", &app_meta)),
+        );
+    }
+
+    #[test]
+    fn autocomplete_test() {
+        let app_meta = app_meta();
+
+        assert_autocomplete(
+            &[
+                ("scene", "show the scene in progress"),
+                ("scene [number]", "show the recap of a scene"),
+                ("scene end", "end the scene with a recap"),
+                ("scene start [name]", "start keeping track of a scene"),
+                ("scenes", "list the recorded scenes"),
+            ][..],
+            block_on(SceneCommand::autocomplete("sce", &app_meta)),
+        );
+
+        assert_autocomplete(
+            &[("scene start [name]", "start keeping track of a scene")][..],
+            block_on(SceneCommand::autocomplete("scene s", &app_meta)),
+        );
+    }
+
+    #[test]
+    fn display_test() {
+        let app_meta = app_meta();
+
+        [
+            SceneCommand::End,
+            SceneCommand::List,
+            SceneCommand::Recap(2),
+            SceneCommand::Show,
+            SceneCommand::Start {
+                name: "Parley".to_string(),
+            },
+        ]
+        .into_iter()
+        .for_each(|command| {
+            let command_string = command.to_string();
+            assert_ne!("", command_string);
+            assert_eq!(
+                CommandMatches::new_canonical(command),
+                block_on(SceneCommand::parse_input(&command_string, &app_meta)),
+                "{}",
+                command_string,
+            );
+        });
+    }
+
+    #[test]
+    fn run_test() {
+        let mut app_meta = app_meta();
+
+        assert_eq!(
+            Err(CommandError::invalid_state(
+                "No scene is in progress. Use `scene start [name]` to start one."
+            )),
+            block_on(SceneCommand::End.run("", &mut app_meta)),
+        );
+
+        assert_eq!(
+            Ok("Scene 1, \"Ambush at the bridge\", has started. Anything you create or roll until `scene end` will be part of it. Use `undo` to reverse this.".to_string()),
+            block_on(
+                SceneCommand::Start {
+                    name: "Ambush at the bridge".to_string(),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+
+        assert_eq!(
+            Err(CommandError::invalid_state(
+                "Scene 1, \"Ambush at the bridge\", is still in progress. Use `scene end` to end it before starting another."
+            )),
+            block_on(
+                SceneCommand::Start {
+                    name: "Parley".to_string(),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+
+        block_on(app_meta.repository.modify(Change::CreateAndSave {
+            thing: Thing::from(Npc {
+                name: "Gottfried".into(),
+                ..Default::default()
+            }),
+        }))
+        .unwrap();
+
+        app_meta.rng.note("d20 = 15");
+        app_meta.rng.record("roll d20");
+
+        assert_eq!(
+            Ok("# Scene 1: Ambush at the bridge
+
+**Game time:** day 1 at 8:00:00 am (no time passed)\\
+**Commands run:** 0
+
+## Added to the Journal
+
+`Gottfried`
+
+## Rolls
+
+* `roll d20`: d20 = 15
+
+_The recap has been saved. Use `scene 1` to see it again, or `undo` to reverse this._"
+                .to_string()),
+            block_on(SceneCommand::End.run("", &mut app_meta)),
+        );
+
+        assert_eq!(
+            Ok("# Scenes\n\n`scene 1`: Ambush at the bridge".to_string()),
+            block_on(SceneCommand::List.run("", &mut app_meta)),
+        );
+
+        assert_eq!(
+            Err(CommandError::unknown_entity(
+                "2",
+                "There is no recap of scene 2. Use `scenes` to see the scenes you've recorded."
+            )),
+            block_on(SceneCommand::Recap(2).run("", &mut app_meta)),
+        );
+    }
+
+    fn event_dispatcher(_event: Event) {}
+
+    fn app_meta() -> AppMeta {
+        AppMeta::new(MemoryDataStore::default(), &event_dispatcher)
+    }
+}
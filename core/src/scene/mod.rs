@@ -0,0 +1,243 @@
+pub use command::SceneCommand;
+
+mod command;
+
+use crate::app::AppMeta;
+use crate::storage::KeyValue;
+use crate::time::{Interval, Time};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// The named scenes of a campaign, such as "Ambush at the bridge": the scene being played, if
+/// any, and a recap of every scene that has ended. Scenes are smaller than sessions, and a
+/// session usually contains several of them.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct Scenes {
+    pub current: Option<Scene>,
+    pub recaps: Vec<Recap>,
+}
+
+/// A scene in progress, with enough of the state at its start to sum it up when it ends.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Scene {
+    pub name: String,
+    pub game_time: Time,
+
+    /// The session in progress when the scene started, if any.
+    pub session: Option<usize>,
+
+    /// The number of commands in the history when the scene started, counting `scene start`
+    /// itself.
+    pub history_len: usize,
+
+    /// The number of entries in the log shown by `rolls` when the scene started.
+    pub rolls_len: usize,
+
+    /// The journal entries that already existed, so that the ones added since can be listed.
+    pub journal: Vec<Uuid>,
+}
+
+/// A summary of a scene, shown when it ends and kept for the `scene [number]` recap.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Recap {
+    pub number: usize,
+    pub name: String,
+    pub session: Option<usize>,
+    pub game_start: Time,
+    pub game_end: Time,
+    pub commands: usize,
+    pub created: Vec<String>,
+    pub rolls: Vec<String>,
+}
+
+/// Load the campaign's scenes from storage, or an empty record if no scene has been started yet
+/// or the data store is unavailable.
+pub async fn load(app_meta: &AppMeta) -> Scenes {
+    app_meta
+        .repository
+        .get_key_value(&KeyValue::Scenes(None))
+        .await
+        .ok()
+        .and_then(KeyValue::scenes)
+        .unwrap_or_default()
+}
+
+impl Scenes {
+    pub fn is_empty(&self) -> bool {
+        self.current.is_none() && self.recaps.is_empty()
+    }
+
+    /// The number of the scene in progress, or of the next scene to be started.
+    pub fn next_number(&self) -> usize {
+        self.recaps.len() + 1
+    }
+}
+
+impl Scene {
+    /// Sum up the scene as of the given moment.
+    pub fn recap(
+        &self,
+        number: usize,
+        game_end: Time,
+        commands: usize,
+        created: Vec<String>,
+        rolls: Vec<String>,
+    ) -> Recap {
+        Recap {
+            number,
+            name: self.name.clone(),
+            session: self.session,
+            game_start: self.game_time.clone(),
+            game_end,
+            commands,
+            created,
+            rolls,
+        }
+    }
+}
+
+impl fmt::Display for Scenes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            serde_json::to_string(self).map_err(|_| fmt::Error)?,
+        )
+    }
+}
+
+impl FromStr for Scenes {
+    type Err = ();
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(raw).map_err(|_| ())
+    }
+}
+
+/// A recap as shown by `scene end` and `scene [number]`, or the scene so far as shown by `scene`.
+impl fmt::Display for Recap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let elapsed = self.game_end.since(&self.game_start);
+
+        write!(
+            f,
+            "# Scene {}: {}\n\n**Game time:** {}",
+            self.number,
+            self.name,
+            self.game_start.display_long(),
+        )?;
+
+        if elapsed == Interval::default() {
+            write!(f, " (no time passed)")?;
+        } else {
+            write!(
+                f,
+                " to {} ({})",
+                self.game_end.display_long(),
+                elapsed.display_long(),
+            )?;
+        }
+
+        if let Some(session) = self.session {
+            write!(f, "\\\n**Session:** `session {}`", session)?;
+        }
+
+        write!(f, "\\\n**Commands run:** {}", self.commands)?;
+
+        if !self.created.is_empty() {
+            write!(f, "\n\n## Added to the Journal\n")?;
+
+            for (i, name) in self.created.iter().enumerate() {
+                write!(f, "{}`{}`", if i == 0 { "\n" } else { "\\\n" }, name)?;
+            }
+        }
+
+        if !self.rolls.is_empty() {
+            write!(f, "\n\n## Rolls\n")?;
+
+            for roll in &self.rolls {
+                write!(f, "\n* {}", roll)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn recap_display_test() {
+        let mut recap = recap();
+
+        assert_eq!(
+            "# Scene 2: Ambush at the bridge
+
+**Game time:** day 1 at 8:00:00 am to day 1 at 8:01:00 am (1 minute)\\
+**Session:** `session 3`\\
+**Commands run:** 4
+
+## Added to the Journal
+
+`Gottfried`
+
+## Rolls
+
+* `roll d20`: d20 = 15",
+            recap.to_string(),
+        );
+
+        recap.game_end = recap.game_start.clone();
+        recap.session = None;
+        recap.created.clear();
+        recap.rolls.clear();
+
+        assert_eq!(
+            "# Scene 2: Ambush at the bridge
+
+**Game time:** day 1 at 8:00:00 am (no time passed)\\
+**Commands run:** 4",
+            recap.to_string(),
+        );
+    }
+
+    #[test]
+    fn serialize_deserialize_test() {
+        let scenes = Scenes {
+            current: Some(Scene {
+                name: "Parley".to_string(),
+                game_time: Time::default(),
+                session: None,
+                history_len: 3,
+                rolls_len: 1,
+                journal: vec![Uuid::nil()],
+            }),
+            recaps: vec![recap()],
+        };
+
+        assert_eq!(
+            r#"{"current":{"name":"Parley","game_time":"1:08:00:00","session":null,"history_len":3,"rolls_len":1,"journal":["00000000-0000-0000-0000-000000000000"]},"recaps":[{"number":2,"name":"Ambush at the bridge","session":3,"game_start":"1:08:00:00","game_end":"1:08:01:00","commands":4,"created":["Gottfried"],"rolls":["`roll d20`: d20 = 15"]}]}"#,
+            scenes.to_string(),
+        );
+        assert_eq!(Ok(scenes.clone()), scenes.to_string().parse());
+        assert_eq!(Ok(Scenes::default()), "{}".parse());
+    }
+
+    fn recap() -> Recap {
+        Recap {
+            number: 2,
+            name: "Ambush at the bridge".to_string(),
+            session: Some(3),
+            game_start: Time::default(),
+            game_end: "1:08:01:00".parse().unwrap(),
+            commands: 4,
+            created: vec!["Gottfried".to_string()],
+            rolls: vec!["`roll d20`: d20 = 15".to_string()],
+        }
+    }
+}
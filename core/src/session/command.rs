@@ -0,0 +1,387 @@
+use super::{describe_duration, Session};
+use crate::app::{
+    AppMeta, Autocomplete, AutocompleteSuggestion, CommandError, CommandMatches, ContextAwareParse,
+    Runnable,
+};
+use crate::storage::{Change, KeyValue};
+use crate::utils::CaseInsensitiveStr;
+use async_trait::async_trait;
+use std::fmt;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SessionCommand {
+    End,
+    List,
+    Recap(usize),
+    Show,
+    Start,
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Runnable for SessionCommand {
+    async fn run(self, _input: &str, app_meta: &mut AppMeta) -> Result<String, CommandError> {
+        let mut sessions = super::load(app_meta).await;
+
+        let response = match self {
+            Self::Show => {
+                return if let Some(session) = &sessions.current {
+                    let recap = session.recap(
+                        sessions.next_number(),
+                        app_meta.now(),
+                        game_time(app_meta).await,
+                        commands_since(session, app_meta),
+                        created_since(session, app_meta).await,
+                    );
+
+                    Ok(format!(
+                        "{}\n\n_Use `session end` to end the session and save a recap._",
+                        recap.display_in_progress(),
+                    ))
+                } else if sessions.recaps.is_empty() {
+                    Ok("*No session is in progress. Use `session start` when you sit down to play to keep track of the session.*".to_string())
+                } else {
+                    Ok("*No session is in progress. Use `session start` to start one, or `sessions` to see the recaps of earlier sessions.*".to_string())
+                };
+            }
+            Self::List => {
+                return if sessions.is_empty() {
+                    Ok("# Sessions\n\n*You haven't recorded any sessions yet. Use `session start` when you sit down to play to keep track of the session.*".to_string())
+                } else {
+                    let mut output = "# Sessions\n".to_string();
+
+                    for (i, recap) in sessions.recaps.iter().enumerate() {
+                        output.push_str(&format!(
+                            "{}`session {}` ({})",
+                            if i == 0 { "\n" } else { "\\\n" },
+                            recap.number,
+                            recap.duration(),
+                        ));
+                    }
+
+                    if let Some(session) = &sessions.current {
+                        output.push_str(&format!(
+                            "{}`session` (in progress for {})",
+                            if sessions.recaps.is_empty() {
+                                "\n"
+                            } else {
+                                "\\\n"
+                            },
+                            describe_duration(app_meta.now().saturating_sub(session.started_at)),
+                        ));
+                    }
+
+                    Ok(output)
+                };
+            }
+            Self::Recap(number) => {
+                return sessions
+                    .recaps
+                    .iter()
+                    .find(|recap| recap.number == number)
+                    .map(|recap| recap.display().to_string())
+                    .ok_or_else(|| {
+                        CommandError::unknown_entity(
+                            &number.to_string(),
+                            format!(
+                                "There is no recap of session {}. Use `sessions` to see the sessions you've recorded.",
+                                number,
+                            ),
+                        )
+                    });
+            }
+            Self::Start => {
+                if let Some(session) = &sessions.current {
+                    return Err(CommandError::invalid_state(format!(
+                        "Session {} has been in progress for {}. Use `session end` to end it before starting another.",
+                        sessions.next_number(),
+                        describe_duration(app_meta.now().saturating_sub(session.started_at)),
+                    )));
+                }
+
+                let game_time = game_time(app_meta).await;
+                let journal = app_meta
+                    .repository
+                    .journal()
+                    .await
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(|thing| thing.uuid().copied())
+                    .collect();
+
+                let response = format!(
+                    "Session {} has started. It's {} in the game world. Use `session end` when you're done to get a recap, or `undo` to reverse this.",
+                    sessions.next_number(),
+                    game_time.display_long(),
+                );
+
+                sessions.current = Some(Session {
+                    started_at: app_meta.now(),
+                    game_time,
+                    history_len: app_meta.history.len() + 1,
+                    journal,
+                });
+
+                response
+            }
+            Self::End => {
+                let session = sessions.current.take().ok_or_else(|| {
+                    CommandError::invalid_state(
+                        "No session is in progress. Use `session start` to start one.",
+                    )
+                })?;
+
+                let recap = session.recap(
+                    sessions.next_number(),
+                    app_meta.now(),
+                    game_time(app_meta).await,
+                    commands_since(&session, app_meta),
+                    created_since(&session, app_meta).await,
+                );
+
+                let response = format!(
+                    "{}\n\n_The recap has been saved. Use `session {}` to see it again, or `undo` to reverse this._",
+                    recap.display(),
+                    recap.number,
+                );
+
+                sessions.recaps.push(recap);
+
+                response
+            }
+        };
+
+        app_meta
+            .repository
+            .modify(Change::SetKeyValue {
+                key_value: KeyValue::Sessions(Some(sessions)),
+            })
+            .await
+            .map(|_| response)
+            .map_err(|_| CommandError::storage("Unable to save the session."))
+    }
+}
+
+async fn game_time(app_meta: &AppMeta) -> crate::time::Time {
+    app_meta
+        .repository
+        .get_key_value(&KeyValue::Time(None))
+        .await
+        .ok()
+        .and_then(KeyValue::time)
+        .unwrap_or_default()
+}
+
+fn commands_since(session: &Session, app_meta: &AppMeta) -> usize {
+    app_meta.history.len().saturating_sub(session.history_len)
+}
+
+async fn created_since(session: &Session, app_meta: &AppMeta) -> Vec<String> {
+    app_meta
+        .repository
+        .journal()
+        .await
+        .unwrap_or_default()
+        .iter()
+        .filter(|thing| {
+            thing
+                .uuid()
+                .is_some_and(|uuid| !session.journal.contains(uuid))
+        })
+        .map(|thing| thing.name().to_string())
+        .collect()
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl ContextAwareParse for SessionCommand {
+    async fn parse_input(input: &str, _app_meta: &AppMeta) -> CommandMatches<Self> {
+        if input.eq_ci("session") {
+            CommandMatches::new_canonical(Self::Show)
+        } else if input.eq_ci("sessions") {
+            CommandMatches::new_canonical(Self::List)
+        } else if input.eq_ci("session start") {
+            CommandMatches::new_canonical(Self::Start)
+        } else if input.eq_ci("session end") {
+            CommandMatches::new_canonical(Self::End)
+        } else if let Some(number) = input
+            .strip_prefix_ci("session ")
+            .and_then(|s| s.trim().parse().ok())
+        {
+            CommandMatches::new_canonical(Self::Recap(number))
+        } else {
+            CommandMatches::default()
+        }
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Autocomplete for SessionCommand {
+    async fn autocomplete(input: &str, _app_meta: &AppMeta) -> Vec<AutocompleteSuggestion> {
+        if input.is_empty() {
+            return Vec::new();
+        }
+
+        [
+            ("session", "show the session in progress"),
+            ("session [number]", "show the recap of a session"),
+            ("session end", "end the session with a recap"),
+            ("session start", "start keeping track of a session"),
+            ("sessions", "list the recorded sessions"),
+        ]
+        .into_iter()
+        .filter(|(term, _)| term.starts_with_ci(input))
+        .map(|(term, summary)| AutocompleteSuggestion::new(term, summary))
+        .collect()
+    }
+}
+
+impl fmt::Display for SessionCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::End => write!(f, "session end"),
+            Self::List => write!(f, "sessions"),
+            Self::Recap(number) => write!(f, "session {}", number),
+            Self::Show => write!(f, "session"),
+            Self::Start => write!(f, "session start"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::assert_autocomplete;
+    use crate::storage::MemoryDataStore;
+    use crate::Event;
+    use std::cell::Cell;
+    use tokio_test::block_on;
+
+    thread_local! {
+        static NOW: Cell<u64> = const { Cell::new(0) };
+    }
+
+    #[test]
+    fn parse_input_test() {
+        let app_meta = app_meta();
+
+        assert_eq!(
+            CommandMatches::new_canonical(SessionCommand::Show),
+            block_on(SessionCommand::parse_input("SESSION", &app_meta)),
+        );
+        assert_eq!(
+            CommandMatches::new_canonical(SessionCommand::Recap(3)),
+            block_on(SessionCommand::parse_input("session 3", &app_meta)),
+        );
+        assert_eq!(
+            CommandMatches::default(),
+            block_on(SessionCommand::parse_input("session zero", &app_meta)),
+        );
+    }
+
+    #[test]
+    fn autocomplete_test() {
+        let app_meta = app_meta();
+
+        assert_autocomplete(
+            &[
+                ("session", "show the session in progress"),
+                ("session [number]", "show the recap of a session"),
+                ("session end", "end the session with a recap"),
+                ("session start", "start keeping track of a session"),
+                ("sessions", "list the recorded sessions"),
+            ][..],
+            block_on(SessionCommand::autocomplete("sess", &app_meta)),
+        );
+
+        assert_autocomplete(
+            &[("session start", "start keeping track of a session")][..],
+            block_on(SessionCommand::autocomplete("session s", &app_meta)),
+        );
+    }
+
+    #[test]
+    fn display_test() {
+        let app_meta = app_meta();
+
+        [
+            SessionCommand::End,
+            SessionCommand::List,
+            SessionCommand::Recap(2),
+            SessionCommand::Show,
+            SessionCommand::Start,
+        ]
+        .into_iter()
+        .for_each(|command| {
+            let command_string = command.to_string();
+            assert_ne!("", command_string);
+            assert_eq!(
+                CommandMatches::new_canonical(command),
+                block_on(SessionCommand::parse_input(&command_string, &app_meta)),
+                "{}",
+                command_string,
+            );
+        });
+    }
+
+    #[test]
+    fn run_test() {
+        let mut app_meta = app_meta();
+        NOW.with(|now| now.set(1_000_000));
+
+        assert_eq!(
+            Err(CommandError::invalid_state(
+                "No session is in progress. Use `session start` to start one."
+            )),
+            block_on(SessionCommand::End.run("", &mut app_meta)),
+        );
+
+        assert_eq!(
+            Ok("Session 1 has started. It's day 1 at 8:00:00 am in the game world. Use `session end` when you're done to get a recap, or `undo` to reverse this.".to_string()),
+            block_on(SessionCommand::Start.run("", &mut app_meta)),
+        );
+
+        NOW.with(|now| now.set(1_000_000 + 5_400_000));
+
+        assert_eq!(
+            Err(CommandError::invalid_state(
+                "Session 1 has been in progress for 1 hour, 30 minutes. Use `session end` to end it before starting another."
+            )),
+            block_on(SessionCommand::Start.run("", &mut app_meta)),
+        );
+
+        assert_eq!(
+            Ok("# Session 1
+
+**Duration:** 1 hour, 30 minutes\\
+**Game time:** day 1 at 8:00:00 am (no time passed)\\
+**Commands run:** 0
+
+_The recap has been saved. Use `session 1` to see it again, or `undo` to reverse this._"
+                .to_string()),
+            block_on(SessionCommand::End.run("", &mut app_meta)),
+        );
+
+        assert_eq!(
+            Ok("# Sessions\n\n`session 1` (1 hour, 30 minutes)".to_string()),
+            block_on(SessionCommand::List.run("", &mut app_meta)),
+        );
+
+        assert_eq!(
+            Err(CommandError::unknown_entity(
+                "2",
+                "There is no recap of session 2. Use `sessions` to see the sessions you've recorded."
+            )),
+            block_on(SessionCommand::Recap(2).run("", &mut app_meta)),
+        );
+    }
+
+    fn event_dispatcher(_event: Event) {}
+
+    fn app_meta() -> AppMeta {
+        let mut app_meta = AppMeta::new(MemoryDataStore::default(), &event_dispatcher);
+        app_meta.clock = || NOW.with(Cell::get);
+        app_meta
+    }
+}
@@ -0,0 +1,269 @@
+pub use command::SessionCommand;
+
+mod command;
+
+use crate::app::AppMeta;
+use crate::storage::KeyValue;
+use crate::time::{Interval, Time};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// The play sessions of a campaign, timed by the wall clock alongside the time in the game world:
+/// the session in progress, if any, and a recap of every session that has ended.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct Sessions {
+    pub current: Option<Session>,
+    pub recaps: Vec<Recap>,
+}
+
+/// A session in progress, with enough of the state at its start to sum it up when it ends.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Session {
+    /// The wall-clock time when the session started, in milliseconds since the Unix epoch.
+    pub started_at: u64,
+    pub game_time: Time,
+
+    /// The number of commands in the history when the session started, counting `session start`
+    /// itself.
+    pub history_len: usize,
+
+    /// The journal entries that already existed, so that the ones added since can be listed.
+    pub journal: Vec<Uuid>,
+}
+
+/// A summary of a session, shown when it ends and kept for a post-session recap.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Recap {
+    pub number: usize,
+    pub started_at: u64,
+    pub ended_at: u64,
+    pub game_start: Time,
+    pub game_end: Time,
+    pub commands: usize,
+    pub created: Vec<String>,
+}
+
+/// A recap as shown by `session end` and `session [number]`, or the session so far as shown by
+/// `session`.
+pub struct RecapView<'a> {
+    recap: &'a Recap,
+    in_progress: bool,
+}
+
+/// Load the campaign's sessions from storage, or an empty record if no session has been started
+/// yet or the data store is unavailable.
+pub async fn load(app_meta: &AppMeta) -> Sessions {
+    app_meta
+        .repository
+        .get_key_value(&KeyValue::Sessions(None))
+        .await
+        .ok()
+        .and_then(KeyValue::sessions)
+        .unwrap_or_default()
+}
+
+/// Describe a wall-clock duration in hours and minutes, eg. "3 hours, 12 minutes".
+pub fn describe_duration(milliseconds: u64) -> String {
+    let minutes = milliseconds / 60_000;
+
+    if minutes == 0 {
+        "less than a minute".to_string()
+    } else {
+        Interval::new(0, (minutes / 60) as i32, (minutes % 60) as i32, 0, 0)
+            .display_long()
+            .to_string()
+    }
+}
+
+impl Sessions {
+    pub fn is_empty(&self) -> bool {
+        self.current.is_none() && self.recaps.is_empty()
+    }
+
+    /// The number of the session in progress, or of the next session to be started.
+    pub fn next_number(&self) -> usize {
+        self.recaps.len() + 1
+    }
+}
+
+impl Session {
+    /// Sum up the session as of the given moment.
+    pub fn recap(
+        &self,
+        number: usize,
+        ended_at: u64,
+        game_end: Time,
+        commands: usize,
+        created: Vec<String>,
+    ) -> Recap {
+        Recap {
+            number,
+            started_at: self.started_at,
+            ended_at: ended_at.max(self.started_at),
+            game_start: self.game_time.clone(),
+            game_end,
+            commands,
+            created,
+        }
+    }
+}
+
+impl Recap {
+    pub fn duration(&self) -> String {
+        describe_duration(self.ended_at - self.started_at)
+    }
+
+    pub fn display(&self) -> RecapView {
+        RecapView {
+            recap: self,
+            in_progress: false,
+        }
+    }
+
+    pub fn display_in_progress(&self) -> RecapView {
+        RecapView {
+            recap: self,
+            in_progress: true,
+        }
+    }
+}
+
+impl fmt::Display for Sessions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            serde_json::to_string(self).map_err(|_| fmt::Error)?,
+        )
+    }
+}
+
+impl FromStr for Sessions {
+    type Err = ();
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(raw).map_err(|_| ())
+    }
+}
+
+impl<'a> fmt::Display for RecapView<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let recap = self.recap;
+        let elapsed = recap.game_end.since(&recap.game_start);
+
+        write!(
+            f,
+            "# Session {}\n\n**{}:** {}\\\n**Game time:** {}",
+            recap.number,
+            if self.in_progress {
+                "Elapsed"
+            } else {
+                "Duration"
+            },
+            recap.duration(),
+            recap.game_start.display_long(),
+        )?;
+
+        if elapsed == Interval::default() {
+            write!(f, " (no time passed)")?;
+        } else {
+            write!(
+                f,
+                " to {} ({})",
+                recap.game_end.display_long(),
+                elapsed.display_long(),
+            )?;
+        }
+
+        write!(f, "\\\n**Commands run:** {}", recap.commands)?;
+
+        if !recap.created.is_empty() {
+            write!(f, "\n\n## Added to the Journal\n")?;
+
+            for (i, name) in recap.created.iter().enumerate() {
+                write!(f, "{}`{}`", if i == 0 { "\n" } else { "\\\n" }, name)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn describe_duration_test() {
+        assert_eq!("less than a minute", describe_duration(59_999));
+        assert_eq!("1 minute", describe_duration(60_000));
+        assert_eq!("3 hours, 12 minutes", describe_duration(11_520_000));
+        assert_eq!("2 hours", describe_duration(7_200_000));
+    }
+
+    #[test]
+    fn recap_display_test() {
+        let mut recap = recap();
+
+        assert_eq!(
+            "# Session 2
+
+**Duration:** 3 hours, 12 minutes\\
+**Game time:** day 1 at 8:00:00 am to day 2 at 10:30:00 am (1 day, 2 hours, 30 minutes)\\
+**Commands run:** 45
+
+## Added to the Journal
+
+`Gottfried`\\
+`The Prancing Pony`",
+            recap.display().to_string(),
+        );
+
+        recap.game_end = recap.game_start.clone();
+        recap.created.clear();
+
+        assert_eq!(
+            "# Session 2
+
+**Elapsed:** 3 hours, 12 minutes\\
+**Game time:** day 1 at 8:00:00 am (no time passed)\\
+**Commands run:** 45",
+            recap.display_in_progress().to_string(),
+        );
+    }
+
+    #[test]
+    fn serialize_deserialize_test() {
+        let sessions = Sessions {
+            current: Some(Session {
+                started_at: 1_000,
+                game_time: Time::default(),
+                history_len: 3,
+                journal: vec![Uuid::nil()],
+            }),
+            recaps: vec![recap()],
+        };
+
+        assert_eq!(
+            r#"{"current":{"started_at":1000,"game_time":"1:08:00:00","history_len":3,"journal":["00000000-0000-0000-0000-000000000000"]},"recaps":[{"number":2,"started_at":0,"ended_at":11520000,"game_start":"1:08:00:00","game_end":"2:10:30:00","commands":45,"created":["Gottfried","The Prancing Pony"]}]}"#,
+            sessions.to_string(),
+        );
+        assert_eq!(Ok(sessions.clone()), sessions.to_string().parse());
+        assert_eq!(Ok(Sessions::default()), "{}".parse());
+    }
+
+    fn recap() -> Recap {
+        Recap {
+            number: 2,
+            started_at: 0,
+            ended_at: 11_520_000,
+            game_start: Time::default(),
+            game_end: "2:10:30:00".parse().unwrap(),
+            commands: 45,
+            created: vec!["Gottfried".to_string(), "The Prancing Pony".to_string()],
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! A reverse index of the journal, recording which entries refer to which others, so that an
+//! entry's details can list everything that points back at it.
+//!
+//! An entry refers to another if it's located there, or if its description mentions the other
+//! entry by name.
+
+use crate::utils::CaseInsensitiveStr;
+use crate::world::Thing;
+use crate::Uuid;
+use std::collections::HashMap;
+
+#[derive(Debug, Default)]
+pub struct Backlinks {
+    /// The names of the entries referring to each entry, by the UUID of the entry referred to.
+    referrers: HashMap<Uuid, Vec<String>>,
+}
+
+impl Backlinks {
+    /// Index the references between entries of the journal. Archived entries can be referred
+    /// to, but their own references are left out.
+    pub fn build(journal: &[Thing]) -> Self {
+        let mut referrers: HashMap<Uuid, Vec<String>> = HashMap::new();
+
+        for referrer in journal.iter().filter(|thing| !thing.is_archived()) {
+            let location_uuid = referrer.location_uuid();
+            let description = match referrer {
+                Thing::Place(place) => place.description.value(),
+                Thing::Npc(_) => None,
+            };
+
+            for thing in journal {
+                let Some(uuid) = thing.uuid() else {
+                    continue;
+                };
+
+                if referrer.uuid() == Some(uuid) {
+                    continue;
+                }
+
+                let is_referenced = location_uuid == Some(uuid)
+                    || description.is_some_and(|description| {
+                        thing
+                            .name()
+                            .value()
+                            .is_some_and(|name| mentions(description, name))
+                    });
+
+                if is_referenced {
+                    referrers
+                        .entry(*uuid)
+                        .or_default()
+                        .push(referrer.name().to_string());
+                }
+            }
+        }
+
+        referrers
+            .values_mut()
+            .for_each(|names| names.sort_by(|a, b| a.cmp_ci(b)));
+
+        Self { referrers }
+    }
+
+    /// The names of the entries referring to the one with this UUID, in alphabetical order.
+    pub fn get(&self, uuid: &Uuid) -> &[String] {
+        self.referrers
+            .get(uuid)
+            .map_or(&[], |names| names.as_slice())
+    }
+}
+
+/// Does the text mention the name as a whole word or phrase, ignoring case? "Bob" is mentioned
+/// in "Bob's Tavern" but not in "Bobby's Tavern".
+fn mentions(text: &str, name: &str) -> bool {
+    if name.is_empty() {
+        return false;
+    }
+
+    text.char_indices()
+        .filter(|(i, _)| {
+            text[..*i]
+                .chars()
+                .next_back()
+                .is_none_or(|c| !c.is_alphanumeric())
+        })
+        .any(|(i, _)| {
+            let rest = &text[i..];
+
+            rest.starts_with_ci(name)
+                && rest
+                    .get(name.len()..)
+                    .and_then(|after| after.chars().next())
+                    .is_none_or(|c| !c.is_alphanumeric())
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::world::{Npc, Place, PlaceUuid};
+
+    #[test]
+    fn mentions_test() {
+        assert!(mentions("Run by Bob.", "Bob"));
+        assert!(mentions("Bob's Tavern", "bob"));
+        assert!(mentions("The Silver Eel is nearby", "The Silver Eel"));
+        assert!(!mentions("Bobby's Tavern", "Bob"));
+        assert!(!mentions("Kabob", "Bob"));
+        assert!(!mentions("Anything", ""));
+    }
+
+    #[test]
+    fn build_test() {
+        let [inn, temple, npc] = [1, 2, 3].map(Uuid::from_u128);
+        let journal: Vec<Thing> = vec![
+            Place {
+                uuid: Some(inn.into()),
+                name: "The Silver Eel".into(),
+                description: "Run by Gottfried, who prays at the Temple of Light.".into(),
+                ..Default::default()
+            }
+            .into(),
+            Place {
+                uuid: Some(temple.into()),
+                name: "Temple of Light".into(),
+                archived: true.into(),
+                description: "Visited by Gottfried.".into(),
+                ..Default::default()
+            }
+            .into(),
+            Npc {
+                uuid: Some(npc.into()),
+                name: "Gottfried".into(),
+                location_uuid: PlaceUuid::from(inn).into(),
+                ..Default::default()
+            }
+            .into(),
+        ];
+
+        let backlinks = Backlinks::build(&journal);
+
+        assert_eq!(&["Gottfried".to_string()][..], backlinks.get(&inn));
+        assert_eq!(&["The Silver Eel".to_string()][..], backlinks.get(&temple));
+        assert_eq!(&["The Silver Eel".to_string()][..], backlinks.get(&npc));
+        assert!(backlinks.get(&Uuid::from_u128(4)).is_empty());
+    }
+}
@@ -1,11 +1,16 @@
 use super::backup::export;
-use super::{Change, RepositoryError};
+use super::{Change, JournalOrder, JournalQuery, RepositoryError, WorldStats};
 use crate::app::{
-    AppMeta, Autocomplete, AutocompleteSuggestion, CommandAlias, CommandMatches, ContextAwareParse,
-    Event, Runnable,
+    Annotation, AppMeta, Autocomplete, AutocompleteSuggestion, CommandAlias, CommandError,
+    CommandMatches, ContextAwareParse, Event, Runnable,
 };
-use crate::utils::CaseInsensitiveStr;
-use crate::world::Thing;
+use crate::clue::Secrets;
+use crate::config::Config;
+use crate::portent::load_portents;
+use crate::thread::Threads;
+use crate::utils::{capitalize, CaseInsensitiveStr, CodeView, EscapedView};
+use crate::world::{find_connections, list, LinkView, Npc, Place, PlaceUuid, Thing};
+use crate::Uuid;
 use async_trait::async_trait;
 use futures::join;
 use std::cmp::Ordering;
@@ -14,118 +19,265 @@ use std::iter::repeat;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum StorageCommand {
+    Archive { name: String },
     Delete { name: String },
+    DeleteConfirmed { name: String, uuid: Option<Uuid> },
+    Exit,
     Export,
     Import,
     Journal,
+    JournalArchived,
+    JournalOrdered { order: JournalOrder },
+    JournalWhere { query: JournalQuery },
     Load { name: String },
+    LoadExact { name: String, uuid: Option<Uuid> },
+    Merge { from: String, into: String },
+    Pin { name: String },
     Redo,
+    Report,
     Save { name: String },
+    Stats,
+    Unarchive { name: String },
     Undo,
+    Unpin { name: String },
 }
 
-#[async_trait(?Send)]
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
 impl Runnable for StorageCommand {
-    async fn run(self, _input: &str, app_meta: &mut AppMeta) -> Result<String, String> {
+    async fn run(self, _input: &str, app_meta: &mut AppMeta) -> Result<String, CommandError> {
         match self {
-            Self::Journal => {
-                let mut output = "# Journal".to_string();
-                let [mut npcs, mut places] = [Vec::new(), Vec::new()];
+            Self::Journal | Self::JournalArchived => {
+                let archived = matches!(self, Self::JournalArchived);
+                let config = Config::load(app_meta).await;
+                let mut output = if archived {
+                    "# Archive".to_string()
+                } else {
+                    "# Journal".to_string()
+                };
+                let [mut pinned, mut pcs, mut npcs, mut places] =
+                    [Vec::new(), Vec::new(), Vec::new(), Vec::new()];
 
-                let record_count = app_meta
+                let (archived_things, things): (Vec<Thing>, Vec<Thing>) = app_meta
                     .repository
                     .journal()
                     .await
-                    .map_err(|_| "Couldn't access the journal.".to_string())?
+                    .map_err(|_| CommandError::storage("Couldn't access the journal."))?
+                    .into_iter()
+                    .partition(|thing| thing.is_archived());
+                let archived_count = archived_things.len();
+
+                let record_count = if archived { archived_things } else { things }
                     .into_iter()
                     .map(|thing| match thing {
+                        _ if !archived && thing.is_pinned() => pinned.push(thing),
+                        Thing::Npc(_) if thing.is_pc() => pcs.push(thing),
                         Thing::Npc(_) => npcs.push(thing),
                         Thing::Place(_) => places.push(thing),
                     })
                     .count();
 
-                let mut add_section = |title: &str, mut things: Vec<Thing>| {
-                    if !things.is_empty() {
-                        output.push_str("\n\n## ");
-                        output.push_str(title);
-
-                        things.sort_unstable_by(|a, b| {
-                            if let (Some(a), Some(b)) = (a.name().value(), b.name().value()) {
-                                a.cmp_ci(b)
-                            } else {
-                                // This shouldn't happen.
-                                Ordering::Equal
-                            }
-                        });
+                // With autosave on, recent entries outlast the session, so they're listed too.
+                let unsaved: Vec<Thing> = if !archived && config.autosave {
+                    app_meta.repository.recent().cloned().collect()
+                } else {
+                    Vec::new()
+                };
+                let unsaved_count = unsaved.len();
 
-                        things.into_iter().enumerate().for_each(|(i, thing)| {
-                            if i > 0 {
-                                output.push('\\');
-                            }
+                let mut add_section = |title: &str, mut things: Vec<Thing>| {
+                    things.sort_unstable_by(|a, b| {
+                        if let (Some(a), Some(b)) = (a.name().value(), b.name().value()) {
+                            a.cmp_ci(b)
+                        } else {
+                            // This shouldn't happen.
+                            Ordering::Equal
+                        }
+                    });
 
-                            output.push_str(&format!("\n{}", thing.display_summary()));
-                        });
-                    }
+                    push_section(&mut output, title, &things, &config);
                 };
 
+                add_section("Pinned", pinned);
+                add_section("Player Characters", pcs);
                 add_section("NPCs", npcs);
                 add_section("Places", places);
+                add_section("Unsaved", unsaved);
 
-                if record_count == 0 {
+                if archived {
+                    if record_count == 0 {
+                        output.push_str("\n\n*There are no archived entries.*");
+                    } else {
+                        output.push_str(
+                            "\n\n*To restore an entry to your journal, use `unarchive [name]`.*",
+                        );
+                    }
+                } else if record_count == 0 {
                     output.push_str("\n\n*Your journal is currently empty.*");
                 } else {
                     output.push_str("\n\n*To export the contents of your journal, use `export`.*");
                 }
 
+                if unsaved_count > 0 {
+                    output.push_str("\n\n*Unsaved entries are kept by autosave between sessions, but aren't part of your journal. To keep one, use `save [name]`.*");
+                }
+
+                if !archived && archived_count > 0 {
+                    output.push_str(&format!(
+                        "\n\n*{} archived {} not shown. To list {}, use `journal archived`.*",
+                        archived_count,
+                        if archived_count == 1 { "entry is" } else { "entries are" },
+                        if archived_count == 1 { "it" } else { "them" },
+                    ));
+                }
+
                 Ok(output)
             }
-            Self::Delete { name } => {
-                let name = app_meta
-                        .repository
-                        .get_by_name(&name)
-                        .await
-                        .map(|t| t.name().value().map(|s| s.to_string()))
-                        .unwrap_or(None)
-                        .unwrap_or(name);
+            Self::JournalOrdered { order } => {
+                let config = Config::load(app_meta).await;
+                let journal = app_meta
+                    .repository
+                    .journal()
+                    .await
+                    .map_err(|_| CommandError::storage("Couldn't access the journal."))?;
+                let timeline = if order == JournalOrder::Recent {
+                    app_meta.repository.timeline().await.unwrap_or_default()
+                } else {
+                    Vec::new()
+                };
 
-                app_meta
-                        .repository
-                        .modify(Change::Delete { name: name.clone(), uuid: None })
-                        .await
-                        .map(|_| format!("{} was successfully deleted. Use `undo` to reverse this.", name))
-                        .map_err(|(_, e)| match e {
-                            RepositoryError::NotFound => {
-                                format!("There is no entity named \"{}\".", name)
-                            }
-                            RepositoryError::DataStoreFailed
-                            | RepositoryError::MissingName
-                            | RepositoryError::NameAlreadyExists => {
-                                format!("Couldn't delete `{}`.", name)
-                            }
-                        })
+                let (mut pinned, things): (Vec<Thing>, Vec<Thing>) = journal
+                    .iter()
+                    .filter(|thing| !thing.is_archived())
+                    .cloned()
+                    .partition(|thing| thing.is_pinned());
+                let mut output = "# Journal".to_string();
+
+                if pinned.is_empty() && things.is_empty() {
+                    output.push_str("\n\n*Your journal is currently empty.*");
+                    return Ok(output);
+                }
+
+                // Pinned entries stay at the top, however the rest are arranged.
+                pinned.sort_by(|a, b| a.name().to_string().cmp_ci(b.name().to_string()));
+                push_section(&mut output, "Pinned", &pinned, &config);
+
+                for (title, things) in order.sections(things, &journal, &timeline) {
+                    push_section(&mut output, &title, &things, &config);
+                }
+
+                output.push_str("\n\n*To export the contents of your journal, use `export`.*");
+
+                Ok(output)
             }
-            Self::Save { name } => {
-                let name = app_meta
+            Self::JournalWhere { query } => {
+                let mut things: Vec<Thing> = app_meta
                     .repository
-                    .get_by_name(&name)
+                    .journal()
                     .await
-                    .map(|t| t.name().value().map(|s| s.to_string()))
-                    .unwrap_or(None)
-                    .unwrap_or(name);
+                    .map_err(|_| CommandError::storage("Couldn't access the journal."))?
+                    .into_iter()
+                    .filter(|thing| !thing.is_archived() && query.matches(thing))
+                    .collect();
+
+                let mut output = format!("# Journal
+
+*Entries where {}*", query);
+
+                if things.is_empty() {
+                    output.push_str("\n\n*No entries match. To list everything, use `journal`.*");
+                    return Ok(output);
+                }
+
+                things.sort_by(|a, b| a.name().to_string().cmp_ci(b.name().to_string()));
+
+                let fields: Vec<&str> = query
+                    .fields()
+                    .into_iter()
+                    .filter(|field| *field != "name")
+                    .collect();
+
+                output.push_str("\n\n| Name |");
+                fields
+                    .iter()
+                    .for_each(|field| output.push_str(&format!(" {} |", capitalize(field))));
+                output.push_str("\n|---|");
+                fields.iter().for_each(|_| output.push_str("---|"));
+
+                for thing in &things {
+                    output.push_str(&format!("\n| {} |", LinkView::new(&thing.name().to_string())));
+
+                    for field in &fields {
+                        output.push_str(&format!(
+                            " {} |",
+                            JournalQuery::display_field(thing, field)
+                                .unwrap_or_else(|| "-".to_string()),
+                        ));
+                    }
+                }
+
+                output.push_str(&format!(
+                    "\n\n*{} {}.*",
+                    things.len(),
+                    if things.len() == 1 {
+                        "entry matches"
+                    } else {
+                        "entries match"
+                    },
+                ));
+
+                Ok(output)
+            }
+            Self::Archive { name } => set_archived(&name, true, app_meta).await,
+            Self::Unarchive { name } => set_archived(&name, false, app_meta).await,
+            Self::Merge { from, into } => merge_things(&from, &into, app_meta).await,
+            Self::Pin { name } => set_pinned(&name, true, app_meta).await,
+            Self::Unpin { name } => set_pinned(&name, false, app_meta).await,
+            Self::Delete { name } => match suggest_archive(&name, app_meta).await {
+                Some(output) => Ok(output),
+                None => delete_thing(name, None, app_meta).await,
+            },
+            Self::DeleteConfirmed { name, uuid } => delete_thing(name, uuid, app_meta).await,
+            Self::Exit => app_meta
+                .repository
+                .flush()
+                .await
+                .map(|_| "All of your changes have been saved. You can safely close this page.".to_string())
+                .map_err(|_| CommandError::storage("Some of your changes couldn't be saved.")),
+            Self::Save { name } => {
+                let (name, uuid) = match app_meta.repository.get_by_name(&name).await {
+                    Ok(thing) => (
+                        thing.name().value().map(|s| s.to_string()).unwrap_or(name),
+                        thing.uuid().cloned(),
+                    ),
+                    Err(_) if name.eq_ci("both") => return save_both(app_meta).await,
+                    Err(_) => (name, None),
+                };
 
-                 app_meta
+                app_meta
                     .repository
-                    .modify(Change::Save { name: name.clone() })
+                    .modify(Change::Save {
+                        name: name.clone(),
+                        uuid,
+                    })
                     .await
-                    .map(|_| format!("{} was successfully saved. Use `undo` to reverse this.", name))
+                    .map(|_| {
+                        Annotation::Success
+                            .annotate(&format!(
+                                "{} was successfully saved. Use `undo` to reverse this.",
+                                EscapedView::new(&name),
+                            ))
+                            .to_string()
+                    })
                     .map_err(|(_, e)| match e {
-                        RepositoryError::NotFound => {
-                            format!("There is no entity named \"{}\".", name)
-                        }
+                        RepositoryError::NotFound => CommandError::unknown_entity(
+                            &name,
+                            format!("There is no entity named \"{}\".", EscapedView::new(&name)),
+                        ),
                         RepositoryError::DataStoreFailed
                         | RepositoryError::MissingName
                         | RepositoryError::NameAlreadyExists => {
-                            format!("Couldn't save `{}`.", name)
+                            CommandError::storage(format!("Couldn't save `{}`.", name))
                         }
                     })
             }
@@ -138,34 +290,118 @@ impl Runnable for StorageCommand {
                 Ok("The file upload popup should appear momentarily. Please select a compatible JSON file, such as that produced by the `export` command.".to_string())
             }
             Self::Load { name } => {
-                let thing = app_meta.repository.get_by_name(&name).await;
-                let mut save_command = None;
-                let output = if let Ok(thing) = thing {
-                    if thing.uuid().is_none() {
-                        save_command = Some(CommandAlias::literal(
-                            "save",
-                            format!("save {}", name),
-                            StorageCommand::Save { name }.into(),
+                let mut things = app_meta
+                    .repository
+                    .get_all_by_name(&name)
+                    .await
+                    .unwrap_or_default();
+
+                if things.len() > 1 {
+                    let config = Config::load(app_meta).await;
+                    let mut output = format!(
+                        "# There are several entries named \"{}\"",
+                        things[0].name(),
+                    );
+
+                    for (i, thing) in things.into_iter().enumerate() {
+                        output.push_str(&format!(
+                            "{}~{}~ {}",
+                            if i == 0 { "\n\n" } else { "\\\n" },
+                            i + 1,
+                            config.render_summary(&thing),
                         ));
 
-                        Ok(format!(
-                            "{}\n\n_{} has not yet been saved. Use ~save~ to save {} to your `journal`._",
-                            thing.display_details(app_meta.repository.load_relations(&thing).await.unwrap_or_default()),
-                            thing.name(),
-                            thing.gender().them(),
-                        ))
-                    } else {
-                        Ok(format!("{}", thing.display_details(app_meta.repository.load_relations(&thing).await.unwrap_or_default())))
+                        app_meta.command_aliases.insert(CommandAlias::literal(
+                            (i + 1).to_string(),
+                            format!("load {} ({})", thing.name(), thing.as_str()),
+                            StorageCommand::LoadExact {
+                                name: thing.name().to_string(),
+                                uuid: thing.uuid().cloned(),
+                            }
+                            .into(),
+                        ));
                     }
+
+                    output.push_str("\n\n_Type the number of the entry you want to load._");
+
+                    Ok(output)
+                } else if let Some(thing) = things.pop() {
+                    Ok(load_thing(thing, app_meta).await)
                 } else {
-                    Err(format!("No matches for \"{}\"", name))
+                    Err(CommandError::unknown_entity(
+                        &name,
+                        format!("No matches for \"{}\"", name),
+                    ))
+                }
+            }
+            Self::LoadExact { name, uuid } => {
+                let thing = if let Some(uuid) = uuid {
+                    app_meta.repository.get_by_uuid(&uuid).await.ok()
+                } else {
+                    app_meta
+                        .repository
+                        .recent()
+                        .find(|t| t.name().value().map_or(false, |s| s.eq_ci(&name)))
+                        .cloned()
                 };
 
-                if let Some(save_command) = save_command {
-                    app_meta.command_aliases.insert(save_command);
+                if let Some(thing) = thing {
+                    Ok(load_thing(thing, app_meta).await)
+                } else {
+                    Err(CommandError::unknown_entity(
+                        &name,
+                        format!("No matches for \"{}\"", name),
+                    ))
                 }
+            }
+            Self::Stats => {
+                let journal = app_meta
+                    .repository
+                    .journal()
+                    .await
+                    .map_err(|_| CommandError::storage("Couldn't access the journal."))?;
 
-                output
+                Ok(WorldStats::new(&journal, &app_meta.demographics).to_string())
+            }
+            Self::Report => {
+                let quarantined = app_meta
+                    .repository
+                    .quarantined()
+                    .await
+                    .map_err(|_| CommandError::storage("Couldn't access the journal."))?;
+
+                let mut output = "# Storage report".to_string();
+
+                if quarantined.is_empty() {
+                    output.push_str("\n\n*No problems were found with your journal.*");
+                } else {
+                    output.push_str(&format!(
+                        "\n\n{} couldn't be read, and {} been set aside so that the rest of your journal could be loaded:\n",
+                        if quarantined.len() == 1 {
+                            "1 journal entry".to_string()
+                        } else {
+                            format!("{} journal entries", quarantined.len())
+                        },
+                        if quarantined.len() == 1 { "has" } else { "have" },
+                    ));
+
+                    for record in quarantined.iter() {
+                        output.push_str(&format!(
+                            "\n* **{}** ({}): {}",
+                            record.name().unwrap_or("Unnamed entry"),
+                            match record.record_type() {
+                                Some("Npc") => "character",
+                                Some("Place") => "place",
+                                _ => "unknown type",
+                            },
+                            record.error,
+                        ));
+                    }
+
+                    output.push_str("\n\n_Nothing has been deleted. These entries will be restored automatically if a future update is able to read them._");
+                }
+
+                Ok(output)
             }
             Self::Redo => match app_meta.repository.redo().await {
                 Some(Ok(thing)) => {
@@ -178,19 +414,24 @@ impl Runnable for StorageCommand {
 
                     if let Some(thing) = thing {
                         Ok(format!(
-                            "{}\n\n_Successfully redid {}. Use `undo` to reverse this._",
-                            thing.display_details(app_meta.repository.load_relations(&thing).await.unwrap_or_default()),
-                            action,
+                            "{}\n\n{}",
+                            Config::load(app_meta).await.render(thing.display_details(app_meta.repository.load_relations(&thing).await.unwrap_or_default())),
+                            Annotation::Success.annotate(&format!(
+                                "_Successfully redid {}. Use `undo` to reverse this._",
+                                action,
+                            )),
                         ))
                     } else {
-                        Ok(format!(
-                            "Successfully redid {}. Use `undo` to reverse this.",
-                            action,
-                        ))
+                        Ok(Annotation::Success
+                            .annotate(&format!(
+                                "Successfully redid {}. Use `undo` to reverse this.",
+                                action,
+                            ))
+                            .to_string())
                     }
                 }
-                Some(Err(_)) => Err("Failed to redo.".to_string()),
-                None => Err("Nothing to redo.".to_string()),
+                Some(Err(_)) => Err(CommandError::storage("Failed to redo.")),
+                None => Err(CommandError::invalid_state("Nothing to redo.")),
             },
             Self::Undo => match app_meta.repository.undo().await {
                 Some(Ok(thing)) => {
@@ -198,19 +439,24 @@ impl Runnable for StorageCommand {
 
                     if let Some(thing) = thing {
                         Ok(format!(
-                            "{}\n\n_Successfully undid {}. Use `redo` to reverse this._",
-                            thing.display_details(app_meta.repository.load_relations(&thing).await.unwrap_or_default()),
-                            action,
+                            "{}\n\n{}",
+                            Config::load(app_meta).await.render(thing.display_details(app_meta.repository.load_relations(&thing).await.unwrap_or_default())),
+                            Annotation::Success.annotate(&format!(
+                                "_Successfully undid {}. Use `redo` to reverse this._",
+                                action,
+                            )),
                         ))
                     } else {
-                        Ok(format!(
-                            "Successfully undid {}. Use `redo` to reverse this.",
-                            action,
-                        ))
+                        Ok(Annotation::Success
+                            .annotate(&format!(
+                                "Successfully undid {}. Use `redo` to reverse this.",
+                                action,
+                            ))
+                            .to_string())
                     }
                 }
-                Some(Err(_)) => Err("Failed to undo.".to_string()),
-                None => Err("Nothing to undo.".to_string()),
+                Some(Err(_)) => Err(CommandError::storage("Failed to undo.")),
+                None => Err(CommandError::invalid_state("Nothing to undo.")),
             },
         }
         .map(|mut s| {
@@ -222,7 +468,575 @@ impl Runnable for StorageCommand {
     }
 }
 
-#[async_trait(?Send)]
+/// Delete a thing from the journal or recent entries.
+/// Add a titled list of things to the journal output, unless there aren't any.
+/// Save the two most recently generated entries, as in `inn; npc; save both`.
+async fn save_both(app_meta: &mut AppMeta) -> Result<String, CommandError> {
+    let mut names: Vec<String> = app_meta
+        .repository
+        .recent()
+        .rev()
+        .filter_map(|thing| thing.name().value().map(|s| s.to_string()))
+        .take(2)
+        .collect();
+    names.reverse();
+
+    if names.len() < 2 {
+        return Err(CommandError::unknown_entity(
+            "both",
+            "There is no entity named \"both\", and there aren't two unsaved entries to save.",
+        ));
+    }
+
+    let changes = names
+        .iter()
+        .map(|name| Change::Save {
+            name: name.clone(),
+            uuid: None,
+        })
+        .collect();
+
+    app_meta
+        .repository
+        .transaction(changes)
+        .await
+        .map(|_| {
+            Annotation::Success
+                .annotate(&format!(
+                    "{} and {} were successfully saved. Use `undo` to reverse this.",
+                    EscapedView::new(&names[0]),
+                    EscapedView::new(&names[1]),
+                ))
+                .to_string()
+        })
+        .map_err(|_| {
+            CommandError::storage(format!("Couldn't save `{}` and `{}`.", names[0], names[1]))
+        })
+}
+
+fn push_section(output: &mut String, title: &str, things: &[Thing], config: &Config) {
+    if things.is_empty() {
+        return;
+    }
+
+    output.push_str("\n\n## ");
+    output.push_str(title);
+
+    things.iter().enumerate().for_each(|(i, thing)| {
+        if i > 0 {
+            output.push('\\');
+        }
+
+        // Characters who are dead or retired are struck out. The Markdown syntax for this is
+        // already used for command aliases.
+        if thing.fate().is_some() {
+            output.push_str(&format!("\n<s>{}</s>", config.render_summary(thing),));
+        } else {
+            output.push_str(&format!("\n{}", config.render_summary(thing)));
+        }
+    });
+}
+
+/// Delete a journal entry, by its UUID if it was resolved when the command was parsed, so that
+/// another entry that has since taken the same name isn't deleted in its place.
+async fn delete_thing(
+    name: String,
+    uuid: Option<Uuid>,
+    app_meta: &mut AppMeta,
+) -> Result<String, CommandError> {
+    let thing = if let Some(uuid) = &uuid {
+        app_meta.repository.get_by_uuid(uuid).await
+    } else {
+        app_meta.repository.get_by_name(&name).await
+    };
+
+    let (name, change) = match thing {
+        Ok(thing) => (thing.name().to_string(), Change::delete(&thing)),
+        Err(_) => (name.clone(), Change::Delete { name, uuid }),
+    };
+
+    app_meta
+        .repository
+        .modify(change)
+        .await
+        .map(|_| {
+            Annotation::Success
+                .annotate(&format!(
+                    "{} was successfully deleted. Use `undo` to reverse this.",
+                    EscapedView::new(&name),
+                ))
+                .to_string()
+        })
+        .map_err(|(_, e)| match e {
+            RepositoryError::NotFound => CommandError::unknown_entity(
+                &name,
+                format!("There is no entity named \"{}\".", EscapedView::new(&name)),
+            ),
+            RepositoryError::DataStoreFailed
+            | RepositoryError::MissingName
+            | RepositoryError::NameAlreadyExists => {
+                CommandError::storage(format!("Couldn't delete {}.", CodeView::new(&name)))
+            }
+        })
+}
+
+/// Deleting a journal entry that other entries refer to would leave them pointing at nothing, so
+/// suggest archiving it instead. Returns `None` if the thing can be deleted without asking.
+async fn suggest_archive(name: &str, app_meta: &mut AppMeta) -> Option<String> {
+    let thing = app_meta.repository.get_by_name(name).await.ok()?;
+
+    if thing.uuid().is_none() || thing.is_archived() {
+        return None;
+    }
+
+    let connections: Vec<String> = find_connections(&thing, 1, &app_meta.repository)
+        .await
+        .iter()
+        .filter_map(|path| path.first())
+        .map(|(_, other)| LinkView::new(&other.name().to_string()).to_string())
+        .collect();
+
+    if connections.is_empty() {
+        return None;
+    }
+
+    let name = thing.name().to_string();
+
+    app_meta.command_aliases.insert(CommandAlias::literal(
+        "archive",
+        format!("archive {}", name),
+        StorageCommand::Archive { name: name.clone() }.into(),
+    ));
+
+    app_meta.command_aliases.insert(CommandAlias::literal(
+        "delete",
+        format!("delete {}", name),
+        StorageCommand::DeleteConfirmed {
+            name: name.clone(),
+            uuid: thing.uuid().cloned(),
+        }
+        .into(),
+    ));
+
+    Some(format!(
+        "{} is connected to {}. Rather than deleting {}, you can ~archive~ {}, which hides {} from your journal without breaking those connections.\n\n_Use ~delete~ if you really want to delete {}._",
+        name,
+        list(&connections),
+        thing.gender().them(),
+        thing.gender().them(),
+        thing.gender().them(),
+        name,
+    ))
+}
+
+/// Archive a journal entry, or restore an archived one, as an edit that can be undone.
+async fn set_archived(
+    name: &str,
+    archived: bool,
+    app_meta: &mut AppMeta,
+) -> Result<String, CommandError> {
+    let thing = app_meta.repository.get_by_name(name).await.map_err(|_| {
+        CommandError::unknown_entity(
+            name,
+            format!("There is no entity named \"{}\".", EscapedView::new(name)),
+        )
+    })?;
+    let name = thing.name().to_string();
+
+    if thing.uuid().is_none() {
+        return Err(CommandError::invalid_state(format!(
+            "{} isn't in your journal, so there's nothing to {}.",
+            name,
+            if archived { "archive" } else { "restore" },
+        )));
+    } else if thing.is_archived() == archived {
+        return Err(CommandError::invalid_state(format!(
+            "{} is {}archived.",
+            name,
+            if archived { "already " } else { "not " },
+        )));
+    }
+
+    // An unarchived thing is left with nothing in the field, just like one that was never
+    // archived.
+    let field = if archived { Some(true) } else { None };
+    let diff: Thing = match thing {
+        Thing::Npc(_) => Npc {
+            archived: field.into(),
+            ..Default::default()
+        }
+        .into(),
+        Thing::Place(_) => Place {
+            archived: field.into(),
+            ..Default::default()
+        }
+        .into(),
+    };
+
+    match app_meta
+        .repository
+        .modify(Change::Edit {
+            name: name.clone(),
+            uuid: thing.uuid().cloned(),
+            diff,
+        })
+        .await
+    {
+        Ok(_) if archived => Ok(format!(
+            "{} was archived. It no longer appears in your journal or suggestions, but can be listed with `journal archived` and restored with `unarchive {}`. Use `undo` to reverse this.",
+            name, name,
+        )),
+        Ok(_) => Ok(format!(
+            "{} was restored to your journal. Use `undo` to reverse this.",
+            name,
+        )),
+        Err(_) => Err(CommandError::storage(format!(
+            "Couldn't {} `{}`.",
+            if archived { "archive" } else { "unarchive" },
+            name,
+        ))),
+    }
+}
+
+/// Merge a duplicate into another entry, filling in the other entry's fields from the
+/// duplicate, moving anything located in the duplicate over to the other entry, and deleting the
+/// duplicate. All of this is undone together.
+async fn merge_things(
+    from: &str,
+    into: &str,
+    app_meta: &mut AppMeta,
+) -> Result<String, CommandError> {
+    let [from_thing, into_thing] = {
+        let (from_result, into_result) = join!(
+            app_meta.repository.get_by_name(from),
+            app_meta.repository.get_by_name(into),
+        );
+
+        [(from, from_result), (into, into_result)].map(|(name, result)| {
+            result.map_err(|_| {
+                CommandError::unknown_entity(
+                    name,
+                    format!("There is no entity named \"{}\".", EscapedView::new(name)),
+                )
+            })
+        })
+    };
+    let (from_thing, into_thing) = (from_thing?, into_thing?);
+    let (from, into) = (from_thing.name().to_string(), into_thing.name().to_string());
+
+    if from_thing.uuid() == into_thing.uuid() && from.eq_ci(&into) {
+        return Err(CommandError::invalid_state(format!(
+            "{} can't be merged into itself.",
+            from,
+        )));
+    }
+
+    let diff = into_thing.merge_diff(&from_thing).ok_or_else(|| {
+        CommandError::unknown_entity(
+            &into,
+            format!(
+                "{} is a {} and {} is a {}, so they can't be merged.",
+                from,
+                from_thing.as_str(),
+                into,
+                into_thing.as_str(),
+            ),
+        )
+    })?;
+
+    // Anything located in the duplicate moves to the entry it's merged into.
+    let occupants: Vec<Thing> = if let Some(uuid) = from_thing.uuid() {
+        let mut things = app_meta.repository.journal().await.unwrap_or_default();
+        things.extend(app_meta.repository.recent().cloned());
+        things
+            .into_iter()
+            .filter(|thing| thing.location_uuid() == Some(uuid))
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let merged = apply_merge(&from_thing, &into_thing, diff, &occupants, app_meta).await?;
+
+    let mut output = format!("{} was merged into {}.", from, LinkView::new(&into),);
+
+    if !occupants.is_empty() {
+        output.push_str(&format!(
+            " {} {} now located there.",
+            list(
+                &occupants
+                    .iter()
+                    .map(|thing| LinkView::new(&thing.name().to_string()).to_string())
+                    .collect::<Vec<_>>(),
+            ),
+            if occupants.len() == 1 { "is" } else { "are" },
+        ));
+    }
+
+    output.push_str(&format!(
+        "\n\n{}\n\n_Use `undo` to reverse this._",
+        load_thing(merged, app_meta).await,
+    ));
+
+    Ok(output)
+}
+
+/// Make the changes for [`merge_things`] in a single transaction, returning the merged entry.
+async fn apply_merge(
+    from_thing: &Thing,
+    into_thing: &Thing,
+    diff: Thing,
+    occupants: &[Thing],
+    app_meta: &mut AppMeta,
+) -> Result<Thing, CommandError> {
+    let error = || {
+        CommandError::storage(format!(
+            "Couldn't merge `{}` into `{}`.",
+            from_thing.name(),
+            into_thing.name(),
+        ))
+    };
+
+    let into_name = into_thing.name().to_string();
+    let mut changes = Vec::new();
+
+    // The occupants need the uuid of the merged entry, so an unsaved entry is saved first.
+    let uuid = if let Some(uuid) = into_thing.uuid() {
+        *uuid
+    } else {
+        let uuid = Uuid::new_v4();
+        changes.push(Change::Save {
+            name: into_name.clone(),
+            uuid: Some(uuid),
+        });
+        uuid
+    };
+
+    changes.push(Change::Edit {
+        name: into_name.clone(),
+        uuid: Some(uuid),
+        diff,
+    });
+
+    for thing in occupants {
+        let diff: Thing = match thing {
+            Thing::Npc(_) => Npc {
+                location_uuid: PlaceUuid::from(uuid).into(),
+                ..Default::default()
+            }
+            .into(),
+            Thing::Place(_) => Place {
+                location_uuid: PlaceUuid::from(uuid).into(),
+                ..Default::default()
+            }
+            .into(),
+        };
+
+        changes.push(Change::Edit {
+            name: thing.name().to_string(),
+            uuid: thing.uuid().cloned(),
+            diff,
+        });
+    }
+
+    changes.push(Change::Delete {
+        name: from_thing.name().to_string(),
+        uuid: from_thing.uuid().cloned(),
+    });
+
+    app_meta
+        .repository
+        .transaction(changes)
+        .await
+        .map_err(|_| error())?;
+
+    app_meta
+        .repository
+        .get_by_uuid(&uuid)
+        .await
+        .map_err(|_| error())
+}
+
+/// Pin a journal entry to the top of the journal, or unpin it, as an edit that can be undone.
+async fn set_pinned(
+    name: &str,
+    pinned: bool,
+    app_meta: &mut AppMeta,
+) -> Result<String, CommandError> {
+    let thing = app_meta.repository.get_by_name(name).await.map_err(|_| {
+        CommandError::unknown_entity(
+            name,
+            format!("There is no entity named \"{}\".", EscapedView::new(name)),
+        )
+    })?;
+    let name = thing.name().to_string();
+
+    if thing.uuid().is_none() {
+        return Err(CommandError::invalid_state(format!(
+            "{} isn't in your journal, so there's nothing to {}. Use `save {}` to add {} first.",
+            name,
+            if pinned { "pin" } else { "unpin" },
+            name,
+            thing.gender().them(),
+        )));
+    } else if thing.is_pinned() == pinned {
+        return Err(CommandError::invalid_state(format!(
+            "{} is {}pinned.",
+            name,
+            if pinned { "already " } else { "not " },
+        )));
+    }
+
+    let field = if pinned { Some(true) } else { None };
+    let diff: Thing = match thing {
+        Thing::Npc(_) => Npc {
+            pinned: field.into(),
+            ..Default::default()
+        }
+        .into(),
+        Thing::Place(_) => Place {
+            pinned: field.into(),
+            ..Default::default()
+        }
+        .into(),
+    };
+
+    match app_meta
+        .repository
+        .modify(Change::Edit {
+            name: name.clone(),
+            uuid: thing.uuid().cloned(),
+            diff,
+        })
+        .await
+    {
+        Ok(_) if pinned => Ok(format!(
+            "{} was pinned to the top of your journal, and can be loaded with just `{}`. Use `undo` to reverse this.",
+            name,
+            first_name(&name),
+        )),
+        Ok(_) => Ok(format!(
+            "{} was unpinned. Use `undo` to reverse this.",
+            name,
+        )),
+        Err(_) => Err(CommandError::storage(format!(
+            "Couldn't {} `{}`.",
+            if pinned { "pin" } else { "unpin" },
+            name,
+        ))),
+    }
+}
+
+/// The first word of a name, which serves as a shortcut to load a pinned entry.
+fn first_name(name: &str) -> &str {
+    name.split_whitespace().next().unwrap_or(name)
+}
+
+/// Pinned journal entries whose first name is `input`, for use as a shortcut to load them.
+async fn pinned_by_first_name(input: &str, app_meta: &AppMeta) -> Vec<Thing> {
+    if input.is_empty() || input.contains(char::is_whitespace) {
+        return Vec::new();
+    }
+
+    app_meta
+        .repository
+        .journal()
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|thing| {
+            thing.is_pinned()
+                && !thing.is_archived()
+                && first_name(&thing.name().to_string()).eq_ci(input)
+        })
+        .collect()
+}
+
+/// Display the details of a thing that the user has asked to load, registering a `save` alias if
+/// it hasn't yet been saved to the journal.
+async fn load_thing(thing: Thing, app_meta: &mut AppMeta) -> String {
+    let mut details = Config::load(app_meta).await.render(
+        thing.display_details(
+            app_meta
+                .repository
+                .load_relations(&thing)
+                .await
+                .unwrap_or_default(),
+        ),
+    );
+
+    let backlinks = app_meta.repository.backlinks(&thing).await;
+    if !backlinks.is_empty() {
+        details.push_str(&format!(
+            "\n\n**Referenced by:** {}",
+            backlinks
+                .iter()
+                .map(|name| LinkView::new(name).to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+        ));
+    }
+
+    if let Some(uuid) = thing.uuid() {
+        for portent in load_portents(app_meta).await.about(uuid) {
+            details.push_str(&format!("\n\n**{}", capitalize(portent.kind.as_str())));
+
+            if let Some(item) = portent.subject.as_ref().and_then(|s| s.item.as_ref()) {
+                details.push_str(&format!(" ({})", item));
+            }
+
+            details.push_str(&format!(":** {}", portent));
+        }
+
+        for (secret, clue) in Secrets::load(app_meta).await.clues_from(uuid) {
+            details.push_str(&format!("\n\n**Clue ({}):** {}", secret.text, clue.text));
+
+            if clue.delivered {
+                details.push_str(" _(delivered)_");
+            }
+        }
+
+        let threads: Vec<String> = Threads::load(app_meta)
+            .await
+            .involving(uuid)
+            .map(|thread| format!("{} ({})", thread.title, thread.status))
+            .collect();
+
+        if !threads.is_empty() {
+            details.push_str(&format!("\n\n**Threads:** {}", threads.join(", ")));
+        }
+    }
+
+    if thing.is_archived() {
+        format!(
+            "{}\n\n_{} is archived. Use `unarchive {}` to restore {} to your `journal`._",
+            details,
+            thing.name(),
+            thing.name(),
+            thing.gender().them(),
+        )
+    } else if thing.uuid().is_none() {
+        let name = thing.name().to_string();
+
+        app_meta.command_aliases.insert(CommandAlias::literal(
+            "save",
+            format!("save {}", name),
+            StorageCommand::Save { name: name.clone() }.into(),
+        ));
+
+        format!(
+            "{}\n\n_{} has not yet been saved. Use ~save~ to save {} to your `journal`._",
+            details,
+            name,
+            thing.gender().them(),
+        )
+    } else {
+        details
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
 impl ContextAwareParse for StorageCommand {
     async fn parse_input(input: &str, app_meta: &AppMeta) -> CommandMatches<Self> {
         let mut matches = CommandMatches::default();
@@ -231,9 +1045,50 @@ impl ContextAwareParse for StorageCommand {
             matches.push_fuzzy(Self::Load {
                 name: input.to_string(),
             });
+        } else {
+            let pinned = pinned_by_first_name(input, app_meta).await;
+            let unique = pinned.len() == 1;
+
+            for thing in pinned {
+                let command = Self::LoadExact {
+                    name: thing.name().to_string(),
+                    uuid: thing.uuid().cloned(),
+                };
+
+                if unique {
+                    matches.push_canonical(command);
+                } else {
+                    matches.push_fuzzy(command);
+                }
+            }
         }
 
-        if let Some(name) = input.strip_prefix_ci("delete ") {
+        if let Some(name) = input.strip_prefix_ci("archive ") {
+            matches.push_canonical(Self::Archive {
+                name: name.to_string(),
+            });
+        } else if let Some(name) = input.strip_prefix_ci("unarchive ") {
+            matches.push_canonical(Self::Unarchive {
+                name: name.to_string(),
+            });
+        } else if let Some((from, into)) = input.strip_prefix_ci("merge ").and_then(|rest| {
+            rest.char_indices()
+                .find(|(i, _)| (&rest[*i..]).starts_with_ci(" into "))
+                .map(|(i, _)| (&rest[..i], &rest[i + 6..]))
+        }) {
+            matches.push_canonical(Self::Merge {
+                from: from.to_string(),
+                into: into.to_string(),
+            });
+        } else if let Some(name) = input.strip_prefix_ci("pin ") {
+            matches.push_canonical(Self::Pin {
+                name: name.to_string(),
+            });
+        } else if let Some(name) = input.strip_prefix_ci("unpin ") {
+            matches.push_canonical(Self::Unpin {
+                name: name.to_string(),
+            });
+        } else if let Some(name) = input.strip_prefix_ci("delete ") {
             matches.push_canonical(Self::Delete {
                 name: name.to_string(),
             });
@@ -247,10 +1102,28 @@ impl ContextAwareParse for StorageCommand {
             });
         } else if input.eq_ci("journal") {
             matches.push_canonical(Self::Journal);
+        } else if input.eq_ci("journal archived") {
+            matches.push_canonical(Self::JournalArchived);
+        } else if let Some(order) = input
+            .strip_prefix_ci("journal ")
+            .and_then(|order| order.parse().ok())
+        {
+            matches.push_canonical(Self::JournalOrdered { order });
+        } else if let Some(query) = input
+            .strip_prefix_ci("journal where ")
+            .and_then(|query| query.parse().ok())
+        {
+            matches.push_canonical(Self::JournalWhere { query });
+        } else if input.eq_ci("stats world") {
+            matches.push_canonical(Self::Stats);
+        } else if input.eq_ci("storage report") {
+            matches.push_canonical(Self::Report);
         } else if input.eq_ci("undo") {
             matches.push_canonical(Self::Undo);
         } else if input.eq_ci("redo") {
             matches.push_canonical(Self::Redo);
+        } else if input.eq_ci("exit") {
+            matches.push_canonical(Self::Exit);
         } else if input.eq_ci("export") {
             matches.push_canonical(Self::Export);
         } else if input.eq_ci("import") {
@@ -261,16 +1134,71 @@ impl ContextAwareParse for StorageCommand {
     }
 }
 
-#[async_trait(?Send)]
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
 impl Autocomplete for StorageCommand {
     async fn autocomplete(input: &str, app_meta: &AppMeta) -> Vec<AutocompleteSuggestion> {
         let mut suggestions: Vec<AutocompleteSuggestion> = [
+            ("archive", "archive [name]", "set an entry aside"),
             ("delete", "delete [name]", "remove an entry from journal"),
+            ("exit", "exit", "save any pending changes"),
             ("export", "export", "export the journal contents"),
             ("import", "import", "import a journal backup"),
             ("journal", "journal", "list journal contents"),
+            (
+                "journal archived",
+                "journal archived",
+                "list archived entries",
+            ),
+            (
+                "journal alphabetical",
+                "journal alphabetical",
+                "list journal contents by name",
+            ),
+            (
+                "journal by location",
+                "journal by location",
+                "list journal contents by location",
+            ),
+            (
+                "journal by type",
+                "journal by type",
+                "list journal contents by type",
+            ),
+            (
+                "journal recent",
+                "journal recent",
+                "list recently created entries first",
+            ),
+            (
+                "journal where",
+                "journal where [field] = [value]",
+                "search the journal",
+            ),
             ("load", "load [name]", "load an entry"),
+            (
+                "merge",
+                "merge [name] into [name]",
+                "combine duplicate entries",
+            ),
+            (
+                "pin",
+                "pin [name]",
+                "keep an entry at the top of the journal",
+            ),
             ("save", "save [name]", "save an entry to journal"),
+            (
+                "stats world",
+                "stats world",
+                "summarize your world and spot gaps",
+            ),
+            (
+                "storage report",
+                "storage report",
+                "list journal entries that couldn't be read",
+            ),
+            ("unarchive", "unarchive [name]", "restore an archived entry"),
+            ("unpin", "unpin [name]", "unpin an entry"),
         ]
         .into_iter()
         .filter(|(s, _, _)| s.starts_with_ci(input))
@@ -302,7 +1230,7 @@ impl Autocomplete for StorageCommand {
         .collect();
 
         let ((full_matches, partial_matches), prefix) = if let Some((prefix, name)) =
-            ["delete ", "load ", "save "]
+            ["archive ", "delete ", "load ", "pin ", "save ", "unpin "]
                 .iter()
                 .find_map(|prefix| input.strip_prefix_ci(prefix).map(|name| (*prefix, name)))
         {
@@ -336,7 +1264,10 @@ impl Autocomplete for StorageCommand {
         {
             if matches!(
                 (prefix, thing.uuid()),
-                ("save ", Some(_)) | ("delete ", None)
+                ("save ", Some(_)) | ("archive " | "delete " | "pin " | "unpin ", None)
+            ) || matches!(
+                (prefix, thing.is_pinned()),
+                ("pin ", true) | ("unpin ", false)
             ) {
                 continue;
             }
@@ -348,7 +1279,10 @@ impl Autocomplete for StorageCommand {
                 suggestions.push(AutocompleteSuggestion::new(
                     suggestion_term,
                     match command {
+                        Self::Archive { .. } => format!("set {} aside", thing.as_str()),
                         Self::Delete { .. } => format!("remove {} from journal", thing.as_str()),
+                        Self::Pin { .. } => format!("pin {} to the top of journal", thing.as_str()),
+                        Self::Unpin { .. } => format!("unpin {}", thing.as_str()),
                         Self::Save { .. } => format!("save {} to journal", thing.as_str()),
                         Self::Load { .. } => {
                             if thing.uuid().is_some() {
@@ -363,6 +1297,51 @@ impl Autocomplete for StorageCommand {
             }
         }
 
+        // Pinned entries can be loaded by their first name alone.
+        if !input.is_empty() && !input.contains(char::is_whitespace) {
+            for thing in app_meta
+                .repository
+                .journal()
+                .await
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|thing| thing.is_pinned() && !thing.is_archived())
+            {
+                let name = thing.name().to_string();
+                let first_name = first_name(&name);
+
+                if first_name != name && first_name.starts_with_ci(input) {
+                    suggestions.push(AutocompleteSuggestion::new(
+                        first_name.to_string(),
+                        format!("{} (pinned)", thing.display_description()),
+                    ));
+                }
+            }
+        }
+
+        if let Some(name) = input.strip_prefix_ci("unarchive ") {
+            for thing in app_meta
+                .repository
+                .journal()
+                .await
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|thing| {
+                    thing.is_archived()
+                        && thing
+                            .name()
+                            .value()
+                            .map_or(false, |s| s.starts_with_ci(name))
+                })
+                .take(10)
+            {
+                suggestions.push(AutocompleteSuggestion::new(
+                    format!("unarchive {}", thing.name()),
+                    format!("restore archived {}", thing.as_str()),
+                ));
+            }
+        }
+
         suggestions
     }
 }
@@ -370,13 +1349,26 @@ impl Autocomplete for StorageCommand {
 impl fmt::Display for StorageCommand {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         match self {
-            Self::Delete { name } => write!(f, "delete {}", name),
+            Self::Archive { name } => write!(f, "archive {}", name),
+            Self::Merge { from, into } => write!(f, "merge {} into {}", from, into),
+            Self::Pin { name } => write!(f, "pin {}", name),
+            Self::Delete { name } | Self::DeleteConfirmed { name, .. } => {
+                write!(f, "delete {}", name)
+            }
+            Self::Exit => write!(f, "exit"),
             Self::Export => write!(f, "export"),
             Self::Import => write!(f, "import"),
             Self::Journal => write!(f, "journal"),
-            Self::Load { name } => write!(f, "load {}", name),
+            Self::JournalArchived => write!(f, "journal archived"),
+            Self::JournalOrdered { order } => write!(f, "journal {}", order),
+            Self::JournalWhere { query } => write!(f, "journal where {}", query),
+            Self::Load { name } | Self::LoadExact { name, .. } => write!(f, "load {}", name),
             Self::Redo => write!(f, "redo"),
+            Self::Report => write!(f, "storage report"),
+            Self::Stats => write!(f, "stats world"),
             Self::Save { name } => write!(f, "save {}", name),
+            Self::Unarchive { name } => write!(f, "unarchive {}", name),
+            Self::Unpin { name } => write!(f, "unpin {}", name),
             Self::Undo => write!(f, "undo"),
         }
     }
@@ -474,6 +1466,35 @@ mod test {
             block_on(StorageCommand::parse_input("JOURNAL", &app_meta)),
         );
 
+        assert_eq!(
+            CommandMatches::new_canonical(StorageCommand::JournalWhere {
+                query: "species = elf".parse().unwrap(),
+            }),
+            block_on(StorageCommand::parse_input(
+                "Journal WHERE species = elf",
+                &app_meta
+            )),
+        );
+
+        assert_eq!(
+            CommandMatches::default(),
+            block_on(StorageCommand::parse_input(
+                "journal where potato",
+                &app_meta
+            )),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(StorageCommand::Merge {
+                from: "Gandalf".to_string(),
+                into: "Gandalf the Grey".to_string(),
+            }),
+            block_on(StorageCommand::parse_input(
+                "MERGE Gandalf INTO Gandalf the Grey",
+                &app_meta
+            )),
+        );
+
         assert_eq!(
             CommandMatches::default(),
             block_on(StorageCommand::parse_input("potato", &app_meta)),
@@ -572,32 +1593,68 @@ mod test {
         );
 
         assert_autocomplete(
-            &[("save [name]", "save an entry to journal")][..],
+            &[
+                ("save [name]", "save an entry to journal"),
+                ("stats world", "summarize your world and spot gaps"),
+                (
+                    "storage report",
+                    "list journal entries that couldn't be read",
+                ),
+            ][..],
             block_on(StorageCommand::autocomplete("s", &app_meta)),
         );
 
         assert_autocomplete(
-            &[("save [name]", "save an entry to journal")][..],
+            &[
+                ("save [name]", "save an entry to journal"),
+                ("stats world", "summarize your world and spot gaps"),
+                (
+                    "storage report",
+                    "list journal entries that couldn't be read",
+                ),
+            ][..],
             block_on(StorageCommand::autocomplete("S", &app_meta)),
         );
 
         assert_autocomplete(
-            &[("journal", "list journal contents")][..],
+            &[
+                ("journal", "list journal contents"),
+                ("journal alphabetical", "list journal contents by name"),
+                ("journal archived", "list archived entries"),
+                ("journal by location", "list journal contents by location"),
+                ("journal by type", "list journal contents by type"),
+                ("journal recent", "list recently created entries first"),
+                ("journal where [field] = [value]", "search the journal"),
+            ][..],
             block_on(StorageCommand::autocomplete("j", &app_meta)),
         );
 
         assert_autocomplete(
-            &[("journal", "list journal contents")][..],
+            &[
+                ("journal", "list journal contents"),
+                ("journal alphabetical", "list journal contents by name"),
+                ("journal archived", "list archived entries"),
+                ("journal by location", "list journal contents by location"),
+                ("journal by type", "list journal contents by type"),
+                ("journal recent", "list recently created entries first"),
+                ("journal where [field] = [value]", "search the journal"),
+            ][..],
             block_on(StorageCommand::autocomplete("J", &app_meta)),
         );
 
         assert_autocomplete(
-            &[("export", "export the journal contents")][..],
+            &[
+                ("exit", "save any pending changes"),
+                ("export", "export the journal contents"),
+            ][..],
             block_on(StorageCommand::autocomplete("e", &app_meta)),
         );
 
         assert_autocomplete(
-            &[("export", "export the journal contents")][..],
+            &[
+                ("exit", "save any pending changes"),
+                ("export", "export the journal contents"),
+            ][..],
             block_on(StorageCommand::autocomplete("E", &app_meta)),
         );
 
@@ -615,6 +1672,7 @@ mod test {
             &[
                 ("Potato & Meat", "inn (unsaved)"),
                 ("Potato Johnson", "adult elf, they/them (unsaved)"),
+                ("pin [name]", "keep an entry at the top of the journal"),
                 ("potato can be lowercase", "person (unsaved)"),
             ][..],
             block_on(StorageCommand::autocomplete("p", &app_meta)),
@@ -672,9 +1730,44 @@ mod test {
             StorageCommand::Save {
                 name: "Potato Johnson".to_string(),
             },
+            StorageCommand::Archive {
+                name: "Potato Johnson".to_string(),
+            },
+            StorageCommand::Unarchive {
+                name: "Potato Johnson".to_string(),
+            },
+            StorageCommand::Pin {
+                name: "Potato Johnson".to_string(),
+            },
+            StorageCommand::Merge {
+                from: "Potato Johnson".to_string(),
+                into: "Mr. Potato Head".to_string(),
+            },
+            StorageCommand::Unpin {
+                name: "Potato Johnson".to_string(),
+            },
+            StorageCommand::Exit,
             StorageCommand::Export,
             StorageCommand::Import,
             StorageCommand::Journal,
+            StorageCommand::JournalArchived,
+            StorageCommand::JournalOrdered {
+                order: JournalOrder::Alphabetical,
+            },
+            StorageCommand::JournalOrdered {
+                order: JournalOrder::ByLocation,
+            },
+            StorageCommand::JournalOrdered {
+                order: JournalOrder::ByType,
+            },
+            StorageCommand::JournalOrdered {
+                order: JournalOrder::Recent,
+            },
+            StorageCommand::JournalWhere {
+                query: "species = elf and age > 100".parse().unwrap(),
+            },
+            StorageCommand::Report,
+            StorageCommand::Stats,
             StorageCommand::Load {
                 name: "Potato Johnson".to_string(),
             },
@@ -692,6 +1785,59 @@ mod test {
         });
     }
 
+    #[test]
+    fn delete_confirmed_test() {
+        let mut app_meta = app_meta();
+        block_on(app_meta.repository.init());
+
+        let gimli = block_on(
+            app_meta.repository.modify(Change::CreateAndSave {
+                thing: Npc {
+                    name: "Gimli".into(),
+                    ..Default::default()
+                }
+                .into(),
+            }),
+        )
+        .unwrap()
+        .unwrap();
+        let command = StorageCommand::DeleteConfirmed {
+            name: "Gimli".to_string(),
+            uuid: gimli.uuid().cloned(),
+        };
+
+        // Another entry taking the name doesn't change which one is deleted.
+        block_on(
+            app_meta.repository.modify(Change::Edit {
+                name: "Gimli".to_string(),
+                uuid: None,
+                diff: Npc {
+                    name: "Gimli the Elder".into(),
+                    ..Default::default()
+                }
+                .into(),
+            }),
+        )
+        .unwrap();
+        block_on(
+            app_meta.repository.modify(Change::CreateAndSave {
+                thing: Npc {
+                    name: "Gimli".into(),
+                    ..Default::default()
+                }
+                .into(),
+            }),
+        )
+        .unwrap();
+
+        assert_eq!(
+            Ok("<span class=\"success\">Gimli the Elder was successfully deleted. Use `undo` to reverse this.</span>".to_string()),
+            block_on(command.run("", &mut app_meta)),
+        );
+        assert!(block_on(app_meta.repository.get_by_name("Gimli")).is_ok());
+        assert!(block_on(app_meta.repository.get_by_name("Gimli the Elder")).is_err());
+    }
+
     fn event_dispatcher(_event: Event) {}
 
     fn app_meta() -> AppMeta {
@@ -1,4 +1,4 @@
-use crate::utils::CaseInsensitiveStr;
+use crate::utils::{MaybeSync, Shared};
 use crate::{Thing, Uuid};
 use async_trait::async_trait;
 use std::collections::HashMap;
@@ -6,13 +6,46 @@ use std::collections::HashMap;
 #[derive(Default)]
 pub struct NullDataStore;
 
+/// The `things` and `key_values` fields hold the data of the default namespace. Other namespaces
+/// are stored separately, shared between clones of the data store.
 #[derive(Clone, Default)]
 pub struct MemoryDataStore {
-    pub things: std::rc::Rc<std::cell::RefCell<HashMap<Uuid, Thing>>>,
-    pub key_values: std::rc::Rc<std::cell::RefCell<std::collections::HashMap<String, String>>>,
+    pub things: Shared<HashMap<Uuid, Thing>>,
+    pub key_values: Shared<HashMap<String, String>>,
+    namespace: Option<String>,
+    namespaces: Shared<HashMap<String, MemoryDataStore>>,
 }
 
-#[async_trait(?Send)]
+impl MemoryDataStore {
+    fn scoped_things(&self) -> Shared<HashMap<Uuid, Thing>> {
+        if let Some(namespace) = &self.namespace {
+            let mut namespaces = self.namespaces.borrow_mut();
+            namespaces
+                .entry(namespace.clone())
+                .or_default()
+                .things
+                .clone()
+        } else {
+            self.things.clone()
+        }
+    }
+
+    fn scoped_key_values(&self) -> Shared<HashMap<String, String>> {
+        if let Some(namespace) = &self.namespace {
+            let mut namespaces = self.namespaces.borrow_mut();
+            namespaces
+                .entry(namespace.clone())
+                .or_default()
+                .key_values
+                .clone()
+        } else {
+            self.key_values.clone()
+        }
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
 impl DataStore for NullDataStore {
     async fn health_check(&self) -> Result<(), ()> {
         Err(())
@@ -61,21 +94,28 @@ impl DataStore for NullDataStore {
     async fn delete_value(&mut self, _key: &str) -> Result<(), ()> {
         Err(())
     }
+
+    fn set_namespace(&mut self, _namespace: Option<&str>) {}
 }
 
-#[async_trait(?Send)]
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
 impl DataStore for MemoryDataStore {
     async fn health_check(&self) -> Result<(), ()> {
         Ok(())
     }
 
     async fn delete_thing_by_uuid(&mut self, uuid: &Uuid) -> Result<(), ()> {
-        self.things.borrow_mut().remove(uuid).map(|_| ()).ok_or(())
+        self.scoped_things()
+            .borrow_mut()
+            .remove(uuid)
+            .map(|_| ())
+            .ok_or(())
     }
 
     async fn edit_thing(&mut self, thing: &Thing) -> Result<(), ()> {
         if let Some(uuid) = thing.uuid() {
-            self.things
+            self.scoped_things()
                 .borrow_mut()
                 .entry(*uuid)
                 .and_modify(|t| *t = thing.clone())
@@ -87,19 +127,19 @@ impl DataStore for MemoryDataStore {
     }
 
     async fn get_all_the_things(&self) -> Result<Vec<Thing>, ()> {
-        Ok(self.things.borrow().values().cloned().collect())
+        Ok(self.scoped_things().borrow().values().cloned().collect())
     }
 
     async fn get_thing_by_uuid(&self, uuid: &Uuid) -> Result<Option<Thing>, ()> {
-        Ok(self.things.borrow().get(uuid).cloned())
+        Ok(self.scoped_things().borrow().get(uuid).cloned())
     }
 
     async fn get_thing_by_name(&self, name: &str) -> Result<Option<Thing>, ()> {
         Ok(self
-            .things
+            .scoped_things()
             .borrow()
             .values()
-            .find(|thing| thing.name().value().map_or(false, |s| s.eq_ci(name)))
+            .find(|thing| thing.is_named(name))
             .cloned())
     }
 
@@ -109,15 +149,10 @@ impl DataStore for MemoryDataStore {
         limit: Option<usize>,
     ) -> Result<Vec<Thing>, ()> {
         Ok(self
-            .things
+            .scoped_things()
             .borrow()
             .values()
-            .filter(|thing| {
-                thing
-                    .name()
-                    .value()
-                    .map_or(false, |s| s.starts_with_ci(name))
-            })
+            .filter(|thing| thing.is_named_starting_with(name))
             .take(limit.unwrap_or(usize::MAX))
             .cloned()
             .collect())
@@ -125,7 +160,8 @@ impl DataStore for MemoryDataStore {
 
     async fn save_thing(&mut self, thing: &Thing) -> Result<(), ()> {
         if let Some(uuid) = thing.uuid() {
-            let mut things = self.things.borrow_mut();
+            let things = self.scoped_things();
+            let mut things = things.borrow_mut();
 
             if things.contains_key(uuid) {
                 Err(())
@@ -139,25 +175,33 @@ impl DataStore for MemoryDataStore {
     }
 
     async fn set_value(&mut self, key: &str, value: &str) -> Result<(), ()> {
-        let mut key_values = self.key_values.borrow_mut();
+        let key_values = self.scoped_key_values();
+        let mut key_values = key_values.borrow_mut();
         key_values.insert(key.to_string(), value.to_string());
         Ok(())
     }
 
     async fn get_value(&self, key: &str) -> Result<Option<String>, ()> {
-        let key_values = self.key_values.borrow();
+        let key_values = self.scoped_key_values();
+        let key_values = key_values.borrow();
         Ok(key_values.get(key).cloned())
     }
 
     async fn delete_value(&mut self, key: &str) -> Result<(), ()> {
-        let mut key_values = self.key_values.borrow_mut();
+        let key_values = self.scoped_key_values();
+        let mut key_values = key_values.borrow_mut();
         key_values.remove(key);
         Ok(())
     }
+
+    fn set_namespace(&mut self, namespace: Option<&str>) {
+        self.namespace = namespace.map(|s| s.to_string());
+    }
 }
 
-#[async_trait(?Send)]
-pub trait DataStore {
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+pub trait DataStore: MaybeSync {
     async fn health_check(&self) -> Result<(), ()>;
 
     async fn delete_thing_by_uuid(&mut self, uuid: &Uuid) -> Result<(), ()>;
@@ -166,6 +210,16 @@ pub trait DataStore {
 
     async fn get_all_the_things(&self) -> Result<Vec<Thing>, ()>;
 
+    /// The raw JSON of every thing, for use by migrations. Data stores that may hold things in an
+    /// outdated layout should override this, since those things can't be deserialized as-is.
+    async fn get_all_the_thing_values(&self) -> Result<Vec<serde_json::Value>, ()> {
+        self.get_all_the_things()
+            .await?
+            .iter()
+            .map(|thing| serde_json::to_value(thing).map_err(|_| ()))
+            .collect()
+    }
+
     async fn get_thing_by_uuid(&self, uuid: &Uuid) -> Result<Option<Thing>, ()>;
 
     async fn get_thing_by_name(&self, name: &str) -> Result<Option<Thing>, ()>;
@@ -183,6 +237,11 @@ pub trait DataStore {
     async fn get_value(&self, key: &str) -> Result<Option<String>, ()>;
 
     async fn delete_value(&mut self, key: &str) -> Result<(), ()>;
+
+    /// Scope all subsequent operations on things and key-values to the given namespace, such as
+    /// a campaign. `None` refers to the default namespace, which holds any data stored before
+    /// namespaces were introduced.
+    fn set_namespace(&mut self, namespace: Option<&str>);
 }
 
 #[cfg(test)]
@@ -231,9 +290,13 @@ mod test {
         assert_eq!(Ok(None), block_on(ds.get_thing_by_name("gANDALF THE gREY")));
         assert_eq!(Ok(()), block_on(ds.save_thing(&gandalf_the_grey)));
         assert_eq!(
-            Ok(Some(gandalf_the_grey)),
+            Ok(Some(gandalf_the_grey.clone())),
             block_on(ds.get_thing_by_name("gANDALF THE gREY")),
         );
+        assert_eq!(
+            Ok(Some(gandalf_the_grey)),
+            block_on(ds.get_thing_by_name("Gandâlf  the grey")),
+        );
     }
 
     #[test]
@@ -353,6 +416,41 @@ mod test {
         assert_eq!(Ok(None), block_on(ds.get_value("somekey")));
     }
 
+    #[test]
+    fn memory_namespace_test() {
+        let mut ds = MemoryDataStore::default();
+
+        block_on(ds.save_thing(&person(Uuid::from_u128(1)))).unwrap();
+        block_on(ds.set_value("somekey", "abc")).unwrap();
+
+        ds.set_namespace(Some("strahd"));
+        assert_eq!(Ok(0), block_on(ds.get_all_the_things()).map(|v| v.len()));
+        assert_eq!(Ok(None), block_on(ds.get_value("somekey")));
+        assert_eq!(Ok(()), block_on(ds.save_thing(&person(TEST_UUID))));
+        assert_eq!(Ok(()), block_on(ds.set_value("somekey", "def")));
+
+        let mut other = ds.clone();
+        other.set_namespace(None);
+        assert_eq!(
+            Ok(Some(Uuid::from_u128(1))),
+            block_on(other.get_all_the_things()).map(|v| v[0].uuid().copied()),
+        );
+        assert_eq!(
+            Ok(Some("abc".to_string())),
+            block_on(other.get_value("somekey")),
+        );
+
+        other.set_namespace(Some("strahd"));
+        assert_eq!(
+            Ok(Some(person(TEST_UUID))),
+            block_on(other.get_thing_by_uuid(&TEST_UUID)),
+        );
+        assert_eq!(
+            Ok(Some("def".to_string())),
+            block_on(other.get_value("somekey")),
+        );
+    }
+
     fn person(uuid: Uuid) -> Thing {
         Npc {
             uuid: Some(uuid.into()),
@@ -0,0 +1,304 @@
+//! Alternative arrangements of the journal listing, as in `journal by location` or
+//! `journal recent`. Each arrangement divides the entries into titled sections, in the order
+//! they're to be shown.
+
+use super::{normalize, TimelineEntry, TimelineEvent};
+use crate::utils::{capitalize, CaseInsensitiveStr};
+use crate::world::Thing;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum JournalOrder {
+    Alphabetical,
+    ByLocation,
+    ByType,
+    Recent,
+}
+
+impl JournalOrder {
+    /// Divide the things into sections. `journal` is every thing in the journal, archived or not,
+    /// so that locations can be looked up, and `timeline` is used to find when things were
+    /// created.
+    pub fn sections(
+        &self,
+        mut things: Vec<Thing>,
+        journal: &[Thing],
+        timeline: &[TimelineEntry],
+    ) -> Vec<(String, Vec<Thing>)> {
+        things.sort_by(cmp_name);
+
+        match self {
+            Self::Alphabetical => group_by(things, |thing| {
+                thing
+                    .name()
+                    .value()
+                    .and_then(|name| name.chars().next())
+                    .filter(|c| c.is_alphabetic())
+                    .map_or_else(|| "#".to_string(), |c| c.to_uppercase().to_string())
+            }),
+            Self::ByLocation => {
+                let mut sections = group_by(things, |thing| {
+                    thing
+                        .location_uuid()
+                        .and_then(|uuid| journal.iter().find(|t| t.uuid() == Some(uuid)))
+                        .map_or_else(String::new, |location| location.name().to_string())
+                });
+
+                // Things without a location are listed last.
+                sections.sort_by(|(a, _), (b, _)| {
+                    a.is_empty().cmp(&b.is_empty()).then_with(|| a.cmp_ci(b))
+                });
+
+                if let Some((title, _)) = sections.last_mut().filter(|(title, _)| title.is_empty())
+                {
+                    *title = "No Location".to_string();
+                }
+
+                sections
+            }
+            Self::ByType => {
+                let mut sections = group_by(things, |thing| match thing {
+                    Thing::Npc(_) if thing.is_pc() => "Player Characters".to_string(),
+                    Thing::Npc(_) => "NPCs".to_string(),
+                    Thing::Place(place) => match (place.homebrew.value(), place.subtype.value()) {
+                        (Some(homebrew), _) => capitalize(homebrew),
+                        (None, Some(subtype)) => capitalize(subtype.as_str()),
+                        (None, None) => "Places".to_string(),
+                    },
+                });
+
+                // Characters come first, followed by the places in alphabetical order.
+                let rank = |title: &str| match title {
+                    "Player Characters" => 0,
+                    "NPCs" => 1,
+                    _ => 2,
+                };
+                sections.sort_by(|(a, _), (b, _)| rank(a).cmp(&rank(b)).then_with(|| a.cmp_ci(b)));
+
+                sections
+            }
+            Self::Recent => {
+                // The position of each thing's creation in the timeline. Later is more recent.
+                let created: HashMap<String, (usize, i32)> = timeline
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, entry)| match &entry.event {
+                        TimelineEvent::Create { name, .. } => {
+                            Some((normalize(name), (i, entry.time.days())))
+                        }
+                        _ => None,
+                    })
+                    .collect();
+                let created =
+                    |thing: &Thing| created.get(&normalize(&thing.name().to_string())).copied();
+
+                things.sort_by(|a, b| match (created(a), created(b)) {
+                    (Some(a), Some(b)) => b.cmp(&a),
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => Ordering::Equal,
+                });
+
+                group_by(things, |thing| {
+                    created(thing).map_or_else(
+                        || "Earlier".to_string(),
+                        |(_, day)| format!("Created on Day {}", day),
+                    )
+                })
+            }
+        }
+    }
+}
+
+/// Group the things by title, with sections in the order their titles first appear.
+fn group_by(things: Vec<Thing>, title: impl Fn(&Thing) -> String) -> Vec<(String, Vec<Thing>)> {
+    let mut sections: Vec<(String, Vec<Thing>)> = Vec::new();
+
+    for thing in things {
+        let title = title(&thing);
+
+        match sections.iter_mut().find(|(t, _)| t == &title) {
+            Some((_, section)) => section.push(thing),
+            None => sections.push((title, vec![thing])),
+        }
+    }
+
+    sections
+}
+
+fn cmp_name(a: &Thing, b: &Thing) -> Ordering {
+    if let (Some(a), Some(b)) = (a.name().value(), b.name().value()) {
+        a.cmp_ci(b)
+    } else {
+        // This shouldn't happen.
+        Ordering::Equal
+    }
+}
+
+impl FromStr for JournalOrder {
+    type Err = ();
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.to_lowercase().as_str() {
+            "alphabetical" => Ok(Self::Alphabetical),
+            "by location" => Ok(Self::ByLocation),
+            "by type" => Ok(Self::ByType),
+            "recent" => Ok(Self::Recent),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for JournalOrder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::Alphabetical => write!(f, "alphabetical"),
+            Self::ByLocation => write!(f, "by location"),
+            Self::ByType => write!(f, "by type"),
+            Self::Recent => write!(f, "recent"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::time::Time;
+    use crate::world::npc::Npc;
+    use crate::world::place::{Place, PlaceType};
+    use crate::world::PlaceUuid;
+    use crate::Uuid;
+
+    #[test]
+    fn from_str_display_test() {
+        for order in [
+            JournalOrder::Alphabetical,
+            JournalOrder::ByLocation,
+            JournalOrder::ByType,
+            JournalOrder::Recent,
+        ] {
+            assert_eq!(Ok(order), order.to_string().parse());
+        }
+
+        assert_eq!(Ok(JournalOrder::ByType), "BY TYPE".parse());
+        assert_eq!(Err(()), "by potato".parse::<JournalOrder>());
+    }
+
+    #[test]
+    fn sections_test() {
+        let inn_uuid = PlaceUuid::from(Uuid::from_u128(1));
+        let things: Vec<Thing> = vec![
+            Place {
+                uuid: Some(inn_uuid.clone()),
+                name: "The Silver Eel".into(),
+                subtype: "inn".parse::<PlaceType>().unwrap().into(),
+                ..Default::default()
+            }
+            .into(),
+            Npc {
+                name: "Gottfried".into(),
+                location_uuid: inn_uuid.clone().into(),
+                ..Default::default()
+            }
+            .into(),
+            Npc {
+                name: "Aerendyl".into(),
+                ..Default::default()
+            }
+            .into(),
+            Npc {
+                name: "Gimli".into(),
+                location_uuid: inn_uuid.clone().into(),
+                ..Default::default()
+            }
+            .into(),
+        ];
+        let timeline = vec![
+            create_entry(1, "Gimli"),
+            create_entry(1, "The Silver Eel"),
+            create_entry(2, "Aerendyl"),
+        ];
+
+        let sections = |order: JournalOrder| -> Vec<(String, Vec<String>)> {
+            order
+                .sections(things.clone(), &things, &timeline)
+                .into_iter()
+                .map(|(title, things)| {
+                    (
+                        title,
+                        things
+                            .iter()
+                            .map(|thing| thing.name().to_string())
+                            .collect(),
+                    )
+                })
+                .collect()
+        };
+
+        assert_eq!(
+            vec![
+                ("A".to_string(), vec!["Aerendyl".to_string()]),
+                (
+                    "G".to_string(),
+                    vec!["Gimli".to_string(), "Gottfried".to_string()],
+                ),
+                ("T".to_string(), vec!["The Silver Eel".to_string()]),
+            ],
+            sections(JournalOrder::Alphabetical),
+        );
+
+        assert_eq!(
+            vec![
+                (
+                    "The Silver Eel".to_string(),
+                    vec!["Gimli".to_string(), "Gottfried".to_string()],
+                ),
+                (
+                    "No Location".to_string(),
+                    vec!["Aerendyl".to_string(), "The Silver Eel".to_string()],
+                ),
+            ],
+            sections(JournalOrder::ByLocation),
+        );
+
+        assert_eq!(
+            vec![
+                (
+                    "NPCs".to_string(),
+                    vec![
+                        "Aerendyl".to_string(),
+                        "Gimli".to_string(),
+                        "Gottfried".to_string(),
+                    ],
+                ),
+                ("Inn".to_string(), vec!["The Silver Eel".to_string()]),
+            ],
+            sections(JournalOrder::ByType),
+        );
+
+        assert_eq!(
+            vec![
+                ("Created on Day 2".to_string(), vec!["Aerendyl".to_string()]),
+                (
+                    "Created on Day 1".to_string(),
+                    vec!["The Silver Eel".to_string(), "Gimli".to_string()],
+                ),
+                ("Earlier".to_string(), vec!["Gottfried".to_string()]),
+            ],
+            sections(JournalOrder::Recent),
+        );
+    }
+
+    fn create_entry(days: i32, name: &str) -> TimelineEntry {
+        TimelineEntry {
+            time: Time::try_new(days, 8, 0, 0).unwrap(),
+            event: TimelineEvent::Create {
+                name: name.to_string(),
+                thing_type: "thing".to_string(),
+            },
+        }
+    }
+}
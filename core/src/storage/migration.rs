@@ -0,0 +1,240 @@
+//! Stored things are upgraded to the current layout when the repository is initialized. Each
+//! namespace records the schema version of its things, and any migrations newer than that
+//! version are applied to the raw JSON of every thing before it is deserialized and saved again.
+
+use super::DataStore;
+use crate::world::Thing;
+use serde_json::{Map, Value};
+
+/// The schema version of things saved by this build. Data stored before versioning was
+/// introduced is considered to be version 0.
+pub const SCHEMA_VERSION: u32 = MIGRATIONS.len() as u32;
+
+const SCHEMA_VERSION_KEY: &str = "schema";
+
+type Migration = fn(&mut Map<String, Value>);
+
+/// The migration at index `n` upgrades a thing from version `n` to version `n + 1`. Since some of
+/// these changes were previously performed by the browser's database upgrades, migrations must
+/// leave things that are already in the expected layout unchanged.
+const MIGRATIONS: &[Migration] = &[legacy_layout, location_uuid];
+
+/// Upgrade the things in the data store's current namespace to the current schema version, if
+/// they were stored by an older version. Only things changed by a migration are saved again, and
+/// things that can't be read even after migrating are left untouched.
+pub async fn migrate(data_store: &mut dyn DataStore) -> Result<(), ()> {
+    let version = data_store
+        .get_value(SCHEMA_VERSION_KEY)
+        .await?
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0);
+
+    if version >= SCHEMA_VERSION {
+        return Ok(());
+    }
+
+    for value in data_store.get_all_the_thing_values().await? {
+        let mut upgraded = value.clone();
+        apply_migrations(&mut upgraded, version);
+
+        if upgraded != value {
+            if let Ok(thing) = deserialize(upgraded) {
+                if thing.uuid().is_some() {
+                    data_store.edit_thing(&thing).await?;
+                }
+            }
+        }
+    }
+
+    data_store
+        .set_value(SCHEMA_VERSION_KEY, &SCHEMA_VERSION.to_string())
+        .await
+}
+
+/// Apply all migrations newer than `version` to the raw JSON of a thing, then deserialize it.
+pub fn upgrade(mut value: Value, version: u32) -> Result<Thing, serde_json::Error> {
+    apply_migrations(&mut value, version);
+    deserialize(value)
+}
+
+fn apply_migrations(value: &mut Value, version: u32) {
+    if let Value::Object(thing) = value {
+        MIGRATIONS
+            .iter()
+            .skip(version as usize)
+            .for_each(|migration| migration(thing));
+    }
+}
+
+fn deserialize(value: Value) -> Result<Thing, serde_json::Error> {
+    // Several enums deserialize from borrowed strings, which a `Value` can't provide.
+    serde_json::from_str(&value.to_string())
+}
+
+/// Places were once called locations with a nested subtype, NPC ages were stored together with
+/// their numeric value, and enum values were stored by variant name rather than by term.
+fn legacy_layout(thing: &mut Map<String, Value>) {
+    if thing.get("type").and_then(Value::as_str) == Some("Location") {
+        thing.insert("type".to_string(), "Place".into());
+
+        if let Some(subtype) = thing
+            .get_mut("subtype")
+            .and_then(|subtype| subtype.get_mut("subtype"))
+            .map(Value::take)
+        {
+            thing.insert("subtype".to_string(), subtype);
+        }
+    }
+
+    if let Some(Value::Object(age)) = thing.get("age") {
+        let (years, age_type) = (age.get("value").cloned(), age.get("type").cloned());
+
+        if let Some(years) = years {
+            thing.insert("age_years".to_string(), years);
+        }
+
+        if let Some(age_type) = age_type {
+            thing.insert("age".to_string(), age_type);
+        }
+    }
+
+    map_str(thing, "age", |s| match s {
+        "YoungAdult" => "young-adult".to_string(),
+        "MiddleAged" => "middle-aged".to_string(),
+        s => s.to_lowercase(),
+    });
+
+    map_str(thing, "gender", |s| match s {
+        "Trans" | "NonBinaryThey" => "non-binary".to_string(),
+        s => s.to_lowercase(),
+    });
+
+    map_str(thing, "species", |s| match s {
+        "HalfElf" => "half-elf".to_string(),
+        "HalfOrc" => "half-orc".to_string(),
+        s => s.to_lowercase(),
+    });
+
+    map_str(thing, "ethnicity", str::to_lowercase);
+    map_str(thing, "subtype", str::to_lowercase);
+}
+
+/// The parent of a place was renamed to its location when NPCs gained locations of their own.
+fn location_uuid(thing: &mut Map<String, Value>) {
+    if let Some(parent_uuid) = thing.remove("parent_uuid") {
+        if thing.get("location_uuid").map_or(true, Value::is_null) {
+            thing.insert("location_uuid".to_string(), parent_uuid);
+        }
+    }
+}
+
+fn map_str(thing: &mut Map<String, Value>, key: &str, f: impl Fn(&str) -> String) {
+    if let Some(value) = thing.get_mut(key) {
+        if let Some(s) = value.as_str() {
+            *value = f(s).into();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::storage::MemoryDataStore;
+    use crate::world::npc::{Age, Gender, Species};
+    use crate::world::place::PlaceType;
+    use crate::world::Npc;
+    use crate::Uuid;
+    use serde_json::json;
+    use tokio_test::block_on;
+
+    #[test]
+    fn upgrade_test_location() {
+        let thing = upgrade(
+            json!({
+                "type": "Location",
+                "uuid": "00000000-0000-0000-0000-000000000001",
+                "parent_uuid": "00000000-0000-0000-0000-000000000002",
+                "subtype": {"type": "Building", "subtype": "Inn"},
+                "name": "The Prancing Pony",
+                "description": null,
+            }),
+            0,
+        )
+        .unwrap();
+
+        let place = thing.place().unwrap();
+        assert_eq!(
+            Some(&"inn".parse::<PlaceType>().unwrap()),
+            place.subtype.value()
+        );
+        assert_eq!(
+            Some("00000000-0000-0000-0000-000000000002".to_string()),
+            place.location_uuid.value().map(|uuid| uuid.to_string()),
+        );
+        assert_eq!("The Prancing Pony", thing.name().to_string());
+    }
+
+    #[test]
+    fn upgrade_test_npc() {
+        let thing = upgrade(
+            json!({
+                "type": "Npc",
+                "uuid": "00000000-0000-0000-0000-000000000001",
+                "name": "Barliman Butterbur",
+                "gender": "Trans",
+                "age": {"type": "MiddleAged", "value": 50},
+                "species": "HalfElf",
+                "ethnicity": "Elvish",
+            }),
+            0,
+        )
+        .unwrap();
+
+        let npc = thing.npc().unwrap();
+        assert_eq!(Some(&Gender::NonBinaryThey), npc.gender.value());
+        assert_eq!(Some(&Age::MiddleAged), npc.age.value());
+        assert_eq!(Some(&50), npc.age_years.value());
+        assert_eq!(Some(&Species::HalfElf), npc.species.value());
+    }
+
+    #[test]
+    fn upgrade_test_current() {
+        let json = json!({
+            "type": "Place",
+            "uuid": "00000000-0000-0000-0000-000000000001",
+            "location_uuid": null,
+            "subtype": {"unlocked": "inn"},
+            "name": "The Prancing Pony",
+            "description": null,
+        });
+
+        assert_eq!(
+            serde_json::from_str::<Thing>(&json.to_string()).unwrap(),
+            upgrade(json.clone(), 0).unwrap(),
+        );
+        assert_eq!(
+            serde_json::from_str::<Thing>(&json.to_string()).unwrap(),
+            upgrade(json, SCHEMA_VERSION).unwrap(),
+        );
+    }
+
+    #[test]
+    fn migrate_test() {
+        let mut ds = MemoryDataStore::default();
+        let mut thing: Thing = Npc {
+            uuid: Some(Uuid::from_u128(1).into()),
+            name: "Barliman Butterbur".into(),
+            ..Default::default()
+        }
+        .into();
+        thing.lock_all();
+
+        block_on(ds.save_thing(&thing)).unwrap();
+        assert_eq!(Ok(()), block_on(migrate(&mut ds)));
+        assert_eq!(
+            Ok(Some(SCHEMA_VERSION.to_string())),
+            block_on(ds.get_value(SCHEMA_VERSION_KEY)),
+        );
+        assert_eq!(Ok(vec![thing]), block_on(ds.get_all_the_things()));
+    }
+}
@@ -1,9 +1,22 @@
 pub mod backup;
+pub mod migration;
+pub mod recovery;
 
 pub use command::StorageCommand;
 pub use data_store::{DataStore, MemoryDataStore, NullDataStore};
+pub use journal::JournalOrder;
+pub use normalize::{name_starts_with, names_match, normalize, normalize_prefix};
+pub use query::JournalQuery;
 pub use repository::{Change, Error as RepositoryError, KeyValue, Repository};
+pub use stats::WorldStats;
+pub use timeline::{TimelineEntry, TimelineEvent};
 
+mod backlinks;
 mod command;
 mod data_store;
+mod journal;
+mod normalize;
+mod query;
 mod repository;
+mod stats;
+mod timeline;
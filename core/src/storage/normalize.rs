@@ -0,0 +1,91 @@
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// Reduce a name to the form used to compare it with other names: case folded, with accents
+/// stripped and runs of whitespace collapsed to a single space, so that "Éowyn" and " eowyn "
+/// are the same name.
+pub fn normalize(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+
+    for word in name.split_whitespace() {
+        if !result.is_empty() {
+            result.push(' ');
+        }
+
+        word.nfkd()
+            .filter(|c| !is_combining_mark(*c))
+            .flat_map(char::to_lowercase)
+            .for_each(|c| match c {
+                // The lowercase sharp S has no single-character uppercase, so it folds to "ss".
+                'ß' => result.push_str("ss"),
+                c => result.push(c),
+            });
+    }
+
+    result
+}
+
+/// Whether two names are the same once normalized.
+pub fn names_match(a: &str, b: &str) -> bool {
+    a == b || normalize(a) == normalize(b)
+}
+
+/// Normalize the start of a name, as typed so far. Unlike [`normalize`], a trailing space is
+/// kept, so that "Gandalf " matches "Gandalf the Grey" but not "Gandalfo".
+pub fn normalize_prefix(prefix: &str) -> String {
+    let mut result = normalize(prefix);
+
+    if !result.is_empty() && prefix.ends_with(char::is_whitespace) {
+        result.push(' ');
+    }
+
+    result
+}
+
+/// Whether a name starts with the given prefix once both are normalized.
+pub fn name_starts_with(name: &str, prefix: &str) -> bool {
+    normalize(name).starts_with(&normalize_prefix(prefix))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn normalize_test() {
+        assert_eq!("eowyn", normalize("Éowyn"));
+        assert_eq!("eowyn", normalize("EOWYN"));
+        assert_eq!("the yawning portal", normalize("  The \t Yawning\nPortal "));
+        assert_eq!("strasse", normalize("Straße"));
+        assert_eq!("zoe", normalize("Zoë"));
+        assert_eq!("p🥔tat🥔", normalize("P🥔TAT🥔"));
+        assert_eq!("", normalize("   "));
+    }
+
+    #[test]
+    fn normalize_prefix_test() {
+        assert_eq!("gandalf ", normalize_prefix("Gandalf  "));
+        assert_eq!("gandalf", normalize_prefix("Gandalf"));
+        assert_eq!("", normalize_prefix(" "));
+    }
+
+    #[test]
+    fn names_match_test() {
+        assert!(names_match("Éowyn", "eowyn"));
+        assert!(names_match("Gandalf the Grey", "gandalf  THE grey"));
+        assert!(names_match("Straße", "STRASSE"));
+
+        assert!(!names_match("Éowyn", "Eowy"));
+        assert!(!names_match("Gandalf the Grey", "Gandalf the White"));
+    }
+
+    #[test]
+    fn name_starts_with_test() {
+        assert!(name_starts_with("Éowyn", "eow"));
+        assert!(name_starts_with("Gandalf the Grey", "gandalf "));
+        assert!(name_starts_with("Gandalf the Grey", ""));
+
+        assert!(!name_starts_with("Gandalfo", "gandalf "));
+        assert!(!name_starts_with("Éowyn", "owyn"));
+    }
+}
@@ -0,0 +1,294 @@
+//! A small expression language for filtering the journal, as in
+//! `journal where species = elf and age > 100`.
+//!
+//! A query is one or more conditions joined by `and`. Each condition compares a field, as shown
+//! by [`Thing::display_field`], against a value. If both sides start with a number (eg. an age of
+//! "120 years" and a value of `100`), they're compared numerically; otherwise they're compared as
+//! case-insensitive text.
+
+use crate::utils::CaseInsensitiveStr;
+use crate::world::{Npc, Place, Thing};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JournalQuery {
+    conditions: Vec<Condition>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Condition {
+    field: String,
+    operator: Operator,
+    value: String,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Operator {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl JournalQuery {
+    /// Does the thing satisfy every condition of the query?
+    pub fn matches(&self, thing: &Thing) -> bool {
+        self.conditions
+            .iter()
+            .all(|condition| condition.matches(thing))
+    }
+
+    /// The fields named by the query, in the order they first appear, without duplicates.
+    pub fn fields(&self) -> Vec<&str> {
+        let mut fields: Vec<&str> = Vec::new();
+
+        for condition in &self.conditions {
+            if !fields.contains(&condition.field.as_str()) {
+                fields.push(&condition.field);
+            }
+        }
+
+        fields
+    }
+
+    /// The value of a field for display in the results, including the pseudo-field `type`.
+    pub fn display_field(thing: &Thing, field: &str) -> Option<String> {
+        if field == "type" {
+            Some(match thing {
+                Thing::Place(place) => place.homebrew.value().cloned().unwrap_or_else(|| {
+                    place
+                        .subtype
+                        .value()
+                        .map(|subtype| subtype.as_str())
+                        .unwrap_or("place")
+                        .to_string()
+                }),
+                Thing::Npc(_) if thing.is_pc() => "pc".to_string(),
+                Thing::Npc(_) => "npc".to_string(),
+            })
+        } else {
+            thing.display_field(field)
+        }
+    }
+}
+
+impl Condition {
+    fn matches(&self, thing: &Thing) -> bool {
+        let Some(actual) = JournalQuery::display_field(thing, &self.field) else {
+            return self.operator == Operator::Ne;
+        };
+
+        let ordering = match (leading_number(&actual), leading_number(&self.value)) {
+            (Some(actual), Some(value)) => actual.cmp(&value),
+            _ => actual.cmp_ci(&self.value),
+        };
+
+        match self.operator {
+            Operator::Eq => ordering == Ordering::Equal,
+            Operator::Ne => ordering != Ordering::Equal,
+            Operator::Lt => ordering == Ordering::Less,
+            Operator::Le => ordering != Ordering::Greater,
+            Operator::Gt => ordering == Ordering::Greater,
+            Operator::Ge => ordering != Ordering::Less,
+        }
+    }
+}
+
+impl Operator {
+    /// Longer operators come first so that `<=` isn't mistaken for `<`.
+    const ALL: [Self; 6] = [Self::Ne, Self::Le, Self::Ge, Self::Eq, Self::Lt, Self::Gt];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Eq => "=",
+            Self::Ne => "!=",
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+        }
+    }
+}
+
+/// The number at the start of the string, if any, as in "120 years".
+fn leading_number(input: &str) -> Option<u64> {
+    let digits = input.trim().split(|c: char| !c.is_ascii_digit()).next()?;
+    digits.parse().ok()
+}
+
+/// Is the field one that can be queried?
+fn is_queryable(field: &str) -> bool {
+    field == "type"
+        || Npc::lockable_fields().contains(&field)
+        || Place::lockable_fields().contains(&field)
+}
+
+impl FromStr for JournalQuery {
+    type Err = ();
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut conditions = Vec::new();
+        let mut remaining = input.trim();
+
+        loop {
+            let (condition, rest) = match remaining
+                .char_indices()
+                .find(|(i, _)| (&remaining[*i..]).starts_with_ci(" and "))
+            {
+                Some((i, _)) => (&remaining[..i], Some(&remaining[i + 5..])),
+                None => (remaining, None),
+            };
+
+            conditions.push(condition.parse()?);
+
+            if let Some(rest) = rest {
+                remaining = rest;
+            } else {
+                break;
+            }
+        }
+
+        Ok(Self { conditions })
+    }
+}
+
+impl FromStr for Condition {
+    type Err = ();
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let (index, operator) = input
+            .char_indices()
+            .find_map(|(i, _)| {
+                Operator::ALL
+                    .into_iter()
+                    .find(|operator| input[i..].starts_with(operator.as_str()))
+                    .map(|operator| (i, operator))
+            })
+            .ok_or(())?;
+
+        let field = input[..index].trim().to_lowercase();
+        let value = input[index + operator.as_str().len()..].trim();
+
+        if is_queryable(&field) && !value.is_empty() {
+            Ok(Self {
+                field,
+                operator,
+                value: value.to_string(),
+            })
+        } else {
+            Err(())
+        }
+    }
+}
+
+impl fmt::Display for JournalQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        for (i, condition) in self.conditions.iter().enumerate() {
+            if i > 0 {
+                write!(f, " and ")?;
+            }
+
+            write!(
+                f,
+                "{} {} {}",
+                condition.field,
+                condition.operator.as_str(),
+                condition.value,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::world::npc::Species;
+
+    #[test]
+    fn from_str_test() {
+        assert_eq!(
+            Ok(JournalQuery {
+                conditions: vec![
+                    Condition {
+                        field: "species".to_string(),
+                        operator: Operator::Eq,
+                        value: "elf".to_string(),
+                    },
+                    Condition {
+                        field: "age".to_string(),
+                        operator: Operator::Ge,
+                        value: "100".to_string(),
+                    },
+                ],
+            }),
+            "Species = elf AND age>=100".parse(),
+        );
+
+        assert_eq!(Err(()), "".parse::<JournalQuery>());
+        assert_eq!(Err(()), "species".parse::<JournalQuery>());
+        assert_eq!(Err(()), "species =".parse::<JournalQuery>());
+        assert_eq!(Err(()), "potato = elf".parse::<JournalQuery>());
+        assert_eq!(Err(()), "species = elf and potato".parse::<JournalQuery>(),);
+    }
+
+    #[test]
+    fn display_test() {
+        for query in [
+            "species = elf and age > 100",
+            "type != inn",
+            "name <= m and name >= c",
+        ] {
+            assert_eq!(query, query.parse::<JournalQuery>().unwrap().to_string());
+        }
+    }
+
+    #[test]
+    fn matches_test() {
+        let elf: Thing = Npc {
+            name: "Aerendyl".into(),
+            species: Species::Elf.into(),
+            age_years: 120.into(),
+            ..Default::default()
+        }
+        .into();
+        let dwarf: Thing = Npc {
+            name: "Gimli".into(),
+            species: Species::Dwarf.into(),
+            age_years: 90.into(),
+            ..Default::default()
+        }
+        .into();
+        let inn: Thing = Place {
+            name: "The Silver Eel".into(),
+            subtype: "inn"
+                .parse::<crate::world::place::PlaceType>()
+                .unwrap()
+                .into(),
+            ..Default::default()
+        }
+        .into();
+
+        let matches = |query: &str| -> Vec<&str> {
+            let query: JournalQuery = query.parse().unwrap();
+            [&elf, &dwarf, &inn]
+                .into_iter()
+                .filter(|thing| query.matches(thing))
+                .map(|thing| thing.name().value().unwrap().as_str())
+                .collect()
+        };
+
+        assert_eq!(vec!["Aerendyl"], matches("species = Elf and age > 100"));
+        assert_eq!(vec!["Aerendyl", "Gimli"], matches("age >= 90"));
+        assert_eq!(vec!["Gimli"], matches("age < 100"));
+        assert_eq!(vec!["Gimli", "The Silver Eel"], matches("species != elf"));
+        assert_eq!(vec!["The Silver Eel"], matches("type = inn"));
+        assert_eq!(vec!["Aerendyl", "Gimli"], matches("type = npc"));
+        assert_eq!(vec!["Gimli", "The Silver Eel"], matches("name > b"));
+    }
+}
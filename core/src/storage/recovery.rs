@@ -0,0 +1,252 @@
+//! A stored thing that can't be read, such as one damaged by a failed write or saved by a newer
+//! version of the app, shouldn't prevent the rest of the journal from loading. Such records are
+//! moved out of the journal into a quarantine kept alongside it, where they can be listed with
+//! `storage report` and are retried every time the repository is initialized.
+
+use super::{migration, DataStore};
+use crate::Uuid;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+const QUARANTINE_KEY: &str = "quarantine";
+
+/// A stored record that couldn't be read as a thing, along with the reason why.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct QuarantinedRecord {
+    pub record: Value,
+    pub error: String,
+}
+
+impl QuarantinedRecord {
+    /// The name of the record, if one can be found in its data.
+    pub fn name(&self) -> Option<&str> {
+        let name = self.record.get("name")?;
+        name.as_str()
+            .or_else(|| name.get("unlocked").and_then(Value::as_str))
+    }
+
+    /// The type of the record, such as "Npc" or "Place".
+    pub fn record_type(&self) -> Option<&str> {
+        self.record.get("type").and_then(Value::as_str)
+    }
+}
+
+/// Move any unreadable things in the data store's current namespace into the quarantine, and
+/// restore any quarantined records that have since become readable.
+pub async fn quarantine(data_store: &mut dyn DataStore) -> Result<(), ()> {
+    let mut changed = false;
+    let mut quarantined = Vec::new();
+
+    for entry in get_quarantined(data_store).await? {
+        match migration::upgrade(entry.record.clone(), 0) {
+            Ok(thing) if thing.uuid().is_some() && data_store.save_thing(&thing).await.is_ok() => {
+                changed = true;
+            }
+            _ => quarantined.push(entry),
+        }
+    }
+
+    for record in data_store.get_all_the_thing_values().await? {
+        if let Err(e) = migration::upgrade(record.clone(), migration::SCHEMA_VERSION) {
+            if let Some(uuid) = record
+                .get("uuid")
+                .and_then(Value::as_str)
+                .and_then(|s| s.parse::<Uuid>().ok())
+            {
+                data_store.delete_thing_by_uuid(&uuid).await?;
+            }
+
+            // A record without a UUID can't be removed, so it will be found again next time.
+            if !quarantined.iter().any(|entry| entry.record == record) {
+                quarantined.push(QuarantinedRecord {
+                    record,
+                    error: e.to_string(),
+                });
+                changed = true;
+            }
+        }
+    }
+
+    if !changed {
+        Ok(())
+    } else if quarantined.is_empty() {
+        data_store.delete_value(QUARANTINE_KEY).await
+    } else {
+        let raw = serde_json::to_string(&quarantined).map_err(|_| ())?;
+        data_store.set_value(QUARANTINE_KEY, &raw).await
+    }
+}
+
+/// The records currently in the quarantine of the data store's current namespace.
+pub async fn get_quarantined(data_store: &dyn DataStore) -> Result<Vec<QuarantinedRecord>, ()> {
+    match data_store.get_value(QUARANTINE_KEY).await? {
+        Some(raw) => serde_json::from_str(&raw).map_err(|_| ()),
+        None => Ok(Vec::new()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::storage::MemoryDataStore;
+    use crate::world::{Npc, Thing};
+    use async_trait::async_trait;
+    use serde_json::json;
+    use tokio_test::block_on;
+
+    /// Holds raw records alongside the things of a `MemoryDataStore`, as a data store whose
+    /// contents have been damaged would.
+    #[derive(Default)]
+    struct DamagedDataStore {
+        data_store: MemoryDataStore,
+        records: Vec<Value>,
+    }
+
+    #[cfg_attr(feature = "sync", async_trait)]
+    #[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+    impl DataStore for DamagedDataStore {
+        async fn health_check(&self) -> Result<(), ()> {
+            self.data_store.health_check().await
+        }
+
+        async fn delete_thing_by_uuid(&mut self, uuid: &Uuid) -> Result<(), ()> {
+            let uuid_str = uuid.to_string();
+            let len = self.records.len();
+            self.records
+                .retain(|record| record.get("uuid").and_then(Value::as_str) != Some(&uuid_str));
+
+            if self.records.len() < len {
+                Ok(())
+            } else {
+                self.data_store.delete_thing_by_uuid(uuid).await
+            }
+        }
+
+        async fn edit_thing(&mut self, thing: &Thing) -> Result<(), ()> {
+            self.data_store.edit_thing(thing).await
+        }
+
+        async fn get_all_the_things(&self) -> Result<Vec<Thing>, ()> {
+            self.data_store.get_all_the_things().await
+        }
+
+        async fn get_all_the_thing_values(&self) -> Result<Vec<Value>, ()> {
+            let mut values: Vec<Value> = self
+                .data_store
+                .get_all_the_things()
+                .await?
+                .iter()
+                .map(|thing| serde_json::to_value(thing).unwrap())
+                .collect();
+            values.extend(self.records.iter().cloned());
+            Ok(values)
+        }
+
+        async fn get_thing_by_uuid(&self, uuid: &Uuid) -> Result<Option<Thing>, ()> {
+            self.data_store.get_thing_by_uuid(uuid).await
+        }
+
+        async fn get_thing_by_name(&self, name: &str) -> Result<Option<Thing>, ()> {
+            self.data_store.get_thing_by_name(name).await
+        }
+
+        async fn get_things_by_name_start(
+            &self,
+            name: &str,
+            limit: Option<usize>,
+        ) -> Result<Vec<Thing>, ()> {
+            self.data_store.get_things_by_name_start(name, limit).await
+        }
+
+        async fn save_thing(&mut self, thing: &Thing) -> Result<(), ()> {
+            self.data_store.save_thing(thing).await
+        }
+
+        async fn set_value(&mut self, key: &str, value: &str) -> Result<(), ()> {
+            self.data_store.set_value(key, value).await
+        }
+
+        async fn get_value(&self, key: &str) -> Result<Option<String>, ()> {
+            self.data_store.get_value(key).await
+        }
+
+        async fn delete_value(&mut self, key: &str) -> Result<(), ()> {
+            self.data_store.delete_value(key).await
+        }
+
+        fn set_namespace(&mut self, namespace: Option<&str>) {
+            self.data_store.set_namespace(namespace);
+        }
+    }
+
+    #[test]
+    fn quarantine_test() {
+        let mut ds = DamagedDataStore::default();
+        let thing: Thing = Npc {
+            uuid: Some(Uuid::from_u128(1).into()),
+            name: "Gandalf".into(),
+            ..Default::default()
+        }
+        .into();
+        let damaged = json!({
+            "type": "Npc",
+            "uuid": "00000000-0000-0000-0000-000000000002",
+            "name": "Saruman",
+            "species": "istari",
+        });
+
+        block_on(ds.save_thing(&thing)).unwrap();
+        ds.records.push(damaged.clone());
+
+        assert_eq!(Ok(()), block_on(quarantine(&mut ds)));
+        assert!(ds.records.is_empty());
+        assert_eq!(Ok(1), block_on(ds.get_all_the_things()).map(|v| v.len()));
+
+        let quarantined = block_on(get_quarantined(&ds)).unwrap();
+        assert_eq!(1, quarantined.len());
+        assert_eq!(damaged, quarantined[0].record);
+        assert_eq!(Some("Saruman"), quarantined[0].name());
+        assert_eq!(Some("Npc"), quarantined[0].record_type());
+        assert_eq!("unrecognized value", quarantined[0].error);
+
+        // Nothing changes the second time around.
+        assert_eq!(Ok(()), block_on(quarantine(&mut ds)));
+        assert_eq!(Ok(quarantined), block_on(get_quarantined(&ds)));
+    }
+
+    #[test]
+    fn quarantine_test_restore() {
+        let mut ds = DamagedDataStore::default();
+        let record = QuarantinedRecord {
+            record: json!({
+                "type": "Npc",
+                "uuid": "00000000-0000-0000-0000-000000000002",
+                "name": "Radagast",
+                "species": "human",
+            }),
+            error: "unknown variant".to_string(),
+        };
+
+        block_on(ds.set_value(
+            QUARANTINE_KEY,
+            &serde_json::to_string(&vec![record]).unwrap(),
+        ))
+        .unwrap();
+
+        assert_eq!(Ok(()), block_on(quarantine(&mut ds)));
+        assert_eq!(Ok(Vec::new()), block_on(get_quarantined(&ds)));
+        assert_eq!(Ok(None), block_on(ds.get_value(QUARANTINE_KEY)));
+        assert_eq!(
+            Ok(Some("Radagast".to_string())),
+            block_on(ds.get_all_the_things()).map(|v| v[0].name().value().cloned()),
+        );
+    }
+
+    #[test]
+    fn get_quarantined_test_empty() {
+        assert_eq!(
+            Ok(Vec::new()),
+            block_on(get_quarantined(&MemoryDataStore::default())),
+        );
+    }
+}
@@ -1,18 +1,57 @@
-use crate::storage::{DataStore, MemoryDataStore};
+use crate::app::TutorialProgress;
+use crate::campaign::Campaign;
+use crate::clue::Secrets;
+use crate::config::{CardTemplates, Config};
+use crate::history::HistoryEntry;
+use crate::homebrew::Homebrew;
+use crate::portent::Portents;
+use crate::reference::HomebrewReference;
+use crate::religion::Pantheon;
+use crate::scene::Scenes;
+use crate::session::Sessions;
+use crate::storage::backlinks::Backlinks;
+use crate::storage::recovery::{self, QuarantinedRecord};
+use crate::storage::{
+    migration, names_match, normalize, DataStore, MemoryDataStore, TimelineEntry, TimelineEvent,
+};
+use crate::table::Tables;
+use crate::template::Templates;
+use crate::thread::Threads;
 use crate::time::Time;
-use crate::utils::CaseInsensitiveStr;
-use crate::world::{Npc, NpcRelations, Place, PlaceRelations, Thing, ThingRelations};
+use crate::war::{Armies, Battles};
+use crate::world::inventory::Coins;
+use crate::world::{
+    list, Npc, NpcRelations, Place, PlaceRelations, PlaceUuid, Thing, ThingRelations,
+};
 use crate::Uuid;
 use futures::join;
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 use std::fmt;
 
+const CAMPAIGNS_KEY: &str = "campaigns";
+const CURRENT_CAMPAIGN_KEY: &str = "campaign";
+const HISTORY_KEY: &str = "history";
+const HISTORY_LOG_PREFIX: &str = "history:";
+const RECENT_KEY: &str = "recent";
 const RECENT_MAX_LEN: usize = 100;
+const TIMELINE_KEY: &str = "timeline";
 const UNDO_HISTORY_LEN: usize = 10;
+const WRITE_BEHIND_LEN: usize = 10;
 
 pub struct Repository {
+    autosave_recent: bool,
+    /// Built when first needed, and discarded whenever the journal changes.
+    backlinks: Option<Backlinks>,
+    batch_start: Option<usize>,
+    batch_depth: usize,
+    campaigns: Vec<Campaign>,
+    current_campaign: Campaign,
     data_store: Box<dyn DataStore>,
     data_store_enabled: bool,
+    modifications: Vec<String>,
+    pending_things: HashMap<Uuid, Thing>,
+    pending_writes: HashMap<&'static str, Option<String>>,
+    pending_write_count: usize,
     recent: VecDeque<Thing>,
     redo_change: Option<Change>,
     undo_history: VecDeque<Change>,
@@ -53,10 +92,12 @@ pub enum Change {
         diff: Thing,
     },
 
-    /// Transfer a thing from recent to journal.
+    /// Transfer a thing from recent to journal. A uuid can be given so that other changes made in
+    /// the same [`Repository::transaction`] can refer to the saved thing; otherwise one is
+    /// generated.
     ///
     /// Reverse: Unsave
-    Save { name: String },
+    Save { name: String, uuid: Option<Uuid> },
 
     /// Transfer a thing from journal to recent. Only triggerable as the reverse to Save.
     ///
@@ -67,6 +108,22 @@ pub enum Change {
     ///
     /// Reverse: SetKeyValue
     SetKeyValue { key_value: KeyValue },
+
+    /// Lock or unlock a field on a Thing, so that it is kept or regenerated respectively.
+    ///
+    /// Reverse: SetLocked
+    SetLocked {
+        name: String,
+        uuid: Option<Uuid>,
+        field: String,
+        locked: bool,
+    },
+
+    /// Several changes made by a single input, such as `inn; save`, which are undone together.
+    /// The changes are applied in order.
+    ///
+    /// Reverse: Batch, with each change reversed and the order flipped
+    Batch { changes: Vec<Change> },
 }
 
 pub struct DisplayUndo<'a>(&'a Change);
@@ -83,14 +140,41 @@ pub enum Error {
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum KeyValue {
+    Armies(Option<Armies>),
+    Battles(Option<Battles>),
+    CardTemplates(Option<CardTemplates>),
+    Config(Option<Config>),
+    Homebrew(Option<Homebrew>),
+    HomebrewReference(Option<HomebrewReference>),
+    Pantheon(Option<Pantheon>),
+    PartyLocation(Option<PlaceUuid>),
+    Portents(Option<Portents>),
+    Scenes(Option<Scenes>),
+    Secrets(Option<Secrets>),
+    Sessions(Option<Sessions>),
+    Tables(Option<Tables>),
+    Templates(Option<Templates>),
+    Threads(Option<Threads>),
     Time(Option<Time>),
+    Treasury(Option<Coins>),
+    Tutorial(Option<TutorialProgress>),
 }
 
 impl Repository {
     pub fn new(data_store: impl DataStore + 'static) -> Self {
         Self {
+            autosave_recent: false,
+            backlinks: None,
+            batch_start: None,
+            batch_depth: 0,
+            campaigns: vec![Campaign::default()],
+            current_campaign: Campaign::default(),
             data_store: Box::new(data_store),
             data_store_enabled: false,
+            modifications: Vec::new(),
+            pending_things: HashMap::default(),
+            pending_writes: HashMap::default(),
+            pending_write_count: 0,
             recent: VecDeque::default(),
             redo_change: None,
             undo_history: VecDeque::default(),
@@ -103,6 +187,185 @@ impl Repository {
         } else {
             self.data_store = Box::<MemoryDataStore>::default();
         }
+
+        self.load_campaigns().await;
+        self.migrate().await;
+        self.restore_recent().await;
+    }
+
+    /// Every campaign in the data store, starting with the default campaign.
+    pub fn campaigns(&self) -> &[Campaign] {
+        &self.campaigns
+    }
+
+    pub fn current_campaign(&self) -> &Campaign {
+        &self.current_campaign
+    }
+
+    /// Create a new campaign and make it the current one. If this is the first campaign to be
+    /// created, any existing data remains accessible as the default campaign.
+    pub async fn create_campaign(&mut self, name: &str) -> Result<Campaign, Error> {
+        let name = name.trim();
+
+        if name.is_empty() {
+            return Err(Error::MissingName);
+        } else if self.campaigns.iter().any(|c| names_match(&c.name, name)) {
+            return Err(Error::NameAlreadyExists);
+        }
+
+        let campaign = Campaign {
+            name: name.to_string(),
+            uuid: Some(Uuid::new_v4()),
+        };
+
+        let mut campaigns = self.campaigns.clone();
+        campaigns.push(campaign.clone());
+        self.save_campaigns(&campaigns, &campaign).await?;
+        self.campaigns = campaigns;
+        self.enter_campaign(campaign.clone());
+        self.restore_recent().await;
+
+        Ok(campaign)
+    }
+
+    /// Make the named campaign the current one. Recent entries and the undo history belong to
+    /// the previous campaign, and are discarded unless autosave is on for that campaign.
+    pub async fn switch_campaign(&mut self, name: &str) -> Result<Campaign, Error> {
+        let campaign = self
+            .campaigns
+            .iter()
+            .find(|c| names_match(&c.name, name))
+            .cloned()
+            .ok_or(Error::NotFound)?;
+
+        self.save_campaigns(&self.campaigns.clone(), &campaign)
+            .await?;
+        self.enter_campaign(campaign.clone());
+        self.restore_recent().await;
+
+        Ok(campaign)
+    }
+
+    /// The campaign registry is stored in the default namespace, regardless of which campaign is
+    /// current.
+    async fn load_campaigns(&mut self) {
+        if let Ok(Some(raw)) = self.data_store.get_value(CAMPAIGNS_KEY).await {
+            let campaigns: Vec<Campaign> = raw.lines().filter_map(|l| l.parse().ok()).collect();
+
+            if !campaigns.is_empty() {
+                self.campaigns = campaigns;
+            }
+        }
+
+        if let Ok(Some(raw)) = self.data_store.get_value(CURRENT_CAMPAIGN_KEY).await {
+            if let Some(campaign) = self
+                .campaigns
+                .iter()
+                .find(|c| c.namespace().as_deref() == Some(raw.as_str()))
+            {
+                self.current_campaign = campaign.clone();
+            }
+        }
+
+        self.data_store
+            .set_namespace(self.current_campaign.namespace().as_deref());
+    }
+
+    async fn save_campaigns(
+        &mut self,
+        campaigns: &[Campaign],
+        current_campaign: &Campaign,
+    ) -> Result<(), Error> {
+        let raw = campaigns
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        // Pending writes belong to the namespace that is current when they're flushed.
+        let _ = self.flush().await;
+        self.data_store.set_namespace(None);
+
+        let result = match self.data_store.set_value(CAMPAIGNS_KEY, &raw).await {
+            Ok(()) => match current_campaign.namespace() {
+                Some(namespace) => {
+                    self.data_store
+                        .set_value(CURRENT_CAMPAIGN_KEY, &namespace)
+                        .await
+                }
+                None => self.data_store.delete_value(CURRENT_CAMPAIGN_KEY).await,
+            },
+            Err(()) => Err(()),
+        };
+
+        self.data_store
+            .set_namespace(self.current_campaign.namespace().as_deref());
+
+        result.map_err(|_| Error::DataStoreFailed)
+    }
+
+    /// Upgrade the things of every campaign to the current schema version, setting aside any that
+    /// can't be read.
+    async fn migrate(&mut self) {
+        for campaign in self.campaigns.clone() {
+            self.data_store
+                .set_namespace(campaign.namespace().as_deref());
+            let _ = migration::migrate(self.data_store.as_mut()).await;
+            let _ = recovery::quarantine(self.data_store.as_mut()).await;
+        }
+
+        self.data_store
+            .set_namespace(self.current_campaign.namespace().as_deref());
+    }
+
+    /// Restore the recent entries of the current campaign from the previous session, if the
+    /// `autosave` setting is on. Entries that have since been saved to the journal under the same
+    /// name are skipped.
+    async fn restore_recent(&mut self) {
+        self.autosave_recent = self
+            .get_key_value(&KeyValue::Config(None))
+            .await
+            .ok()
+            .and_then(KeyValue::config)
+            .map_or(false, |config| config.autosave);
+
+        if !self.autosave_recent {
+            return;
+        }
+
+        if let Ok(Some(raw)) = self.get_deferred_value(RECENT_KEY).await {
+            for thing in serde_json::from_str::<Vec<Thing>>(&raw).unwrap_or_default() {
+                if let Some(name) = thing.name().value() {
+                    if self.get_by_name(name).await.is_err() {
+                        self.push_recent(thing);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Save the recent entries to the data store if the `autosave` setting is on. Since this
+    /// happens on every change, the write is deferred like those of the history.
+    async fn autosave_recent(&mut self) {
+        if !self.autosave_recent {
+            return;
+        }
+
+        if self.recent.is_empty() {
+            self.defer_value(RECENT_KEY, None).await;
+        } else if let Ok(raw) = serde_json::to_string(&self.recent) {
+            self.defer_value(RECENT_KEY, Some(raw)).await;
+        }
+    }
+
+    fn enter_campaign(&mut self, campaign: Campaign) {
+        self.data_store
+            .set_namespace(campaign.namespace().as_deref());
+        self.current_campaign = campaign;
+        self.backlinks = None;
+        self.recent.clear();
+        self.redo_change = None;
+        self.undo_history.clear();
     }
 
     pub async fn get_by_change(&self, change: &Change) -> Result<Thing, Error> {
@@ -120,11 +383,15 @@ impl Repository {
             }
             | Change::Edit {
                 uuid: Some(uuid), ..
-            } => (None, Some(uuid)),
-            Change::Delete { name, .. } | Change::Edit { name, .. } | Change::Save { name } => {
-                (Some(name), None)
             }
-            Change::SetKeyValue { .. } => (None, None),
+            | Change::SetLocked {
+                uuid: Some(uuid), ..
+            } => (None, Some(uuid)),
+            Change::Delete { name, .. }
+            | Change::Edit { name, .. }
+            | Change::Save { name, .. }
+            | Change::SetLocked { name, .. } => (Some(name), None),
+            Change::SetKeyValue { .. } | Change::Batch { .. } => (None, None),
         };
 
         if let Some(uuid) = uuid {
@@ -183,10 +450,27 @@ impl Repository {
         };
 
         match thing {
-            Thing::Npc(Npc { .. }) => Ok(NpcRelations {
-                location: locations,
+            Thing::Npc(Npc { relationships, .. }) => {
+                let mut related = Vec::new();
+
+                for relationship in relationships.value().iter().flat_map(|r| r.iter()) {
+                    match self
+                        .get_by_uuid(relationship.uuid().as_ref())
+                        .await
+                        .and_then(|thing| thing.into_npc().map_err(|_| Error::NotFound))
+                    {
+                        Ok(npc) => related.push((relationship.clone(), npc)),
+                        Err(Error::NotFound) => {}
+                        Err(e) => return Err(e),
+                    }
+                }
+
+                Ok(NpcRelations {
+                    location: locations,
+                    relationships: related,
+                }
+                .into())
             }
-            .into()),
             Thing::Place(Place { .. }) => Ok(PlaceRelations {
                 location: locations,
             }
@@ -194,19 +478,49 @@ impl Repository {
         }
     }
 
+    /// The names of the journal entries that refer to the thing, either by being located there or
+    /// by mentioning it in their description. Unsaved things can't be referred to.
+    pub async fn backlinks(&mut self, thing: &Thing) -> Vec<String> {
+        let Some(uuid) = thing.uuid() else {
+            return Vec::new();
+        };
+
+        if self.backlinks.is_none() {
+            let journal = self.journal().await.unwrap_or_default();
+            self.backlinks = Some(Backlinks::build(&journal));
+        }
+
+        self.backlinks
+            .as_ref()
+            .map(|backlinks| backlinks.get(uuid).to_vec())
+            .unwrap_or_default()
+    }
+
+    /// Things whose names start with the given string, for autocompletion. Archived things are
+    /// left out.
     pub async fn get_by_name_start(
         &self,
         name: &str,
         limit: Option<usize>,
     ) -> Result<Vec<Thing>, Error> {
-        let mut things = self
+        let mut things: Vec<Thing> = self
             .data_store
-            .get_things_by_name_start(name, limit)
+            .get_things_by_name_start(name, None)
             .await
-            .map_err(|_| Error::DataStoreFailed)?;
+            .map_err(|_| Error::DataStoreFailed)?
+            .into_iter()
+            .chain(
+                self.pending_things
+                    .values()
+                    .filter(|t| t.is_named_starting_with(name))
+                    .cloned(),
+            )
+            .filter(|thing| !thing.is_archived())
+            .take(limit.unwrap_or(usize::MAX))
+            .collect();
 
         self.recent()
-            .filter(|t| t.name().value().map_or(false, |s| s.starts_with_ci(name)))
+            .filter(|t| t.is_named_starting_with(name))
             .take(
                 limit
                     .unwrap_or(usize::MAX)
@@ -218,22 +532,32 @@ impl Repository {
         Ok(things)
     }
 
-    pub fn recent(&self) -> impl Iterator<Item = &Thing> {
+    pub fn recent(&self) -> impl DoubleEndedIterator<Item = &Thing> {
         let (a, b) = self.recent.as_slices();
         a.iter().chain(b.iter())
     }
 
     pub async fn journal(&self) -> Result<Vec<Thing>, Error> {
-        self.data_store
+        let mut things = self
+            .data_store
             .get_all_the_things()
+            .await
+            .map_err(|_| Error::DataStoreFailed)?;
+
+        things.extend(self.pending_things.values().cloned());
+        Ok(things)
+    }
+
+    /// Stored records of the current campaign that couldn't be read, and have been set aside.
+    pub async fn quarantined(&self) -> Result<Vec<QuarantinedRecord>, Error> {
+        recovery::get_quarantined(self.data_store.as_ref())
             .await
             .map_err(|_| Error::DataStoreFailed)
     }
 
     pub async fn get_by_name(&self, name: &str) -> Result<Thing, Error> {
-        let (saved_thing, recent_thing) = join!(self.data_store.get_thing_by_name(name), async {
-            self.recent()
-                .find(|t| t.name().value().map_or(false, |s| s.eq_ci(name)))
+        let (saved_thing, recent_thing) = join!(self.get_saved_thing_by_name(name), async {
+            self.recent().find(|t| t.is_named(name))
         });
 
         if let Some(thing) = recent_thing {
@@ -247,8 +571,42 @@ impl Repository {
         }
     }
 
+    /// Like [`Repository::get_by_name`], but returns every thing matching the name rather than
+    /// just the first, for use when the name is ambiguous. Recent entries are listed first.
+    pub async fn get_all_by_name(&self, name: &str) -> Result<Vec<Thing>, Error> {
+        let mut things: Vec<Thing> = self
+            .recent()
+            .filter(|t| t.is_named(name))
+            .cloned()
+            .collect();
+
+        things.extend(
+            self.pending_things
+                .values()
+                .filter(|t| t.is_named(name))
+                .cloned(),
+        );
+
+        // The whole name is looked up as a prefix, so surrounding spaces mustn't be taken as part
+        // of it.
+        match self
+            .data_store
+            .get_things_by_name_start(&normalize(name), None)
+            .await
+        {
+            Ok(saved_things) => saved_things
+                .into_iter()
+                .filter(|t| t.is_named(name))
+                .for_each(|t| things.push(t)),
+            Err(()) if things.is_empty() => return Err(Error::DataStoreFailed),
+            Err(()) => {}
+        }
+
+        Ok(things)
+    }
+
     pub async fn get_by_uuid(&self, uuid: &Uuid) -> Result<Thing, Error> {
-        match self.data_store.get_thing_by_uuid(uuid).await {
+        match self.get_saved_thing_by_uuid(uuid).await {
             Ok(Some(thing)) => Ok(thing),
             Ok(None) => Err(Error::NotFound),
             Err(()) => Err(Error::DataStoreFailed),
@@ -259,18 +617,150 @@ impl Repository {
         let undo_change = self.modify_without_undo(change).await?;
         let thing = self.get_by_change(&undo_change).await.ok();
 
-        while self.undo_history.len() >= UNDO_HISTORY_LEN {
-            self.undo_history.pop_front();
+        if let Change::Batch { changes } = &undo_change {
+            // The undo changes are in reverse order, so flip them back to log events in the order
+            // they happened.
+            for change in changes.clone().iter().rev() {
+                let thing = self.get_by_change(change).await.ok();
+                self.log_event(change, thing.as_ref()).await;
+            }
+        } else {
+            self.log_event(&undo_change, thing.as_ref()).await;
         }
-        self.undo_history.push_back(undo_change);
+
+        self.push_undo(undo_change);
 
         Ok(thing)
     }
 
+    /// Make several changes as one, for operations that would leave things in a muddle if only
+    /// some of their changes were made. The data store is checked before anything is written, and
+    /// if any change fails, those already made are reversed, so that either every change is made
+    /// or none of them are. The changes are undone together in a single step.
+    ///
+    /// On failure, the changes are returned as they were given along with the error.
+    pub async fn transaction(&mut self, changes: Vec<Change>) -> Result<(), (Vec<Change>, Error)> {
+        if changes.is_empty() {
+            return Ok(());
+        }
+
+        if self.data_store.health_check().await.is_err() {
+            return Err((changes, Error::DataStoreFailed));
+        }
+
+        let change = if changes.len() == 1 {
+            changes.into_iter().next().unwrap()
+        } else {
+            Change::Batch { changes }
+        };
+
+        match self.modify(change).await {
+            Ok(_) => Ok(()),
+            Err((Change::Batch { changes }, e)) => Err((changes, e)),
+            Err((change, e)) => Err((vec![change], e)),
+        }
+    }
+
+    /// Start collecting changes into a single [`Change::Batch`], so that a chain of commands can be
+    /// undone in one step. Finish with [`Repository::end_batch`]. Batches can be nested, in which
+    /// case everything is combined when the outermost batch ends.
+    pub fn begin_batch(&mut self) {
+        if self.batch_depth == 0 {
+            self.batch_start = Some(self.undo_history.len());
+        }
+        self.batch_depth += 1;
+    }
+
+    /// Combine the changes made since [`Repository::begin_batch`] into a single undo step, and
+    /// write the things saved during the batch. If they can't be written, the whole batch is
+    /// reversed as by [`Repository::abort_batch`] and the error is returned.
+    pub async fn end_batch(&mut self) -> Result<(), Error> {
+        self.batch_depth = self.batch_depth.saturating_sub(1);
+
+        if self.batch_depth > 0 {
+            return Ok(());
+        }
+
+        if !self.pending_things.is_empty() {
+            if let Err(e) = self.flush().await {
+                self.reverse_batch().await;
+                return Err(e);
+            }
+        }
+
+        if let Some(start) = self.batch_start.take() {
+            let start = start.min(self.undo_history.len());
+
+            if self.undo_history.len() - start > 1 {
+                let changes = self.undo_history.drain(start..).rev().collect();
+                self.undo_history.push_back(Change::Batch { changes });
+            }
+
+            while self.undo_history.len() > UNDO_HISTORY_LEN {
+                self.undo_history.pop_front();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// End a batch by reversing the changes made since [`Repository::begin_batch`] instead of
+    /// keeping them, so that a chain of commands that fails partway through leaves nothing
+    /// behind. A nested batch is left for the outermost one to reverse.
+    pub async fn abort_batch(&mut self) {
+        self.batch_depth = self.batch_depth.saturating_sub(1);
+
+        if self.batch_depth > 0 {
+            return;
+        }
+
+        self.reverse_batch().await;
+    }
+
+    /// Reverse the changes made since the outermost [`Repository::begin_batch`]. Things saved
+    /// during the batch haven't been written yet, so reversing their saves returns them to the
+    /// recent entries.
+    async fn reverse_batch(&mut self) {
+        if let Some(start) = self.batch_start.take() {
+            let start = start.min(self.undo_history.len());
+
+            for change in self.undo_history.drain(start..).rev().collect::<Vec<_>>() {
+                let logged_count = Self::logged_count(&change);
+
+                if self.modify_without_undo(change).await.is_ok() {
+                    for _ in 0..logged_count {
+                        self.unlog_event().await;
+                    }
+                }
+            }
+
+            while self.undo_history.len() > UNDO_HISTORY_LEN {
+                self.undo_history.pop_front();
+            }
+        }
+    }
+
+    fn push_undo(&mut self, undo_change: Change) {
+        // While batching, the history may temporarily run over its length so that none of the
+        // batch is lost before it is combined.
+        if self.batch_start.is_none() {
+            while self.undo_history.len() >= UNDO_HISTORY_LEN {
+                self.undo_history.pop_front();
+            }
+        }
+        self.undo_history.push_back(undo_change);
+    }
+
     pub async fn undo(&mut self) -> Option<Result<Option<Thing>, Error>> {
         if let Some(change) = self.undo_history.pop_back() {
+            let logged_count = Self::logged_count(&change);
+
             match self.modify_without_undo(change).await {
                 Ok(redo_change) => {
+                    for _ in 0..logged_count {
+                        self.unlog_event().await;
+                    }
+
                     let thing = self.get_by_change(&redo_change).await.ok();
                     self.redo_change = Some(redo_change);
                     Some(Ok(thing))
@@ -303,12 +793,31 @@ impl Repository {
         }
     }
 
+    /// Descriptions of the changes successfully applied since this was last called, in order.
+    pub fn take_modifications(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.modifications)
+    }
+
     pub fn get_redo(&self) -> Option<&Change> {
         self.redo_change.as_ref()
     }
 
     pub async fn modify_without_undo(&mut self, change: Change) -> Result<Change, (Change, Error)> {
+        let description = change.display_redo().to_string();
+        let result = self.apply_change(change).await;
+
+        if result.is_ok() {
+            self.modifications.push(description);
+            self.backlinks = None;
+            self.autosave_recent().await;
+        }
+
+        result
+    }
+
+    async fn apply_change(&mut self, change: Change) -> Result<Change, (Change, Error)> {
         match change {
+            Change::Batch { changes } => self.apply_batch(changes).await,
             Change::Create { thing } => self
                 .create_thing(thing)
                 .await
@@ -419,7 +928,7 @@ impl Repository {
                     Err((diff, e)) => Err((Change::EditAndUnsave { name, uuid, diff }, e)),
                 }
             }
-            Change::Save { name } => match self.save_thing_by_name(&name).await {
+            Change::Save { name, uuid } => match self.save_thing_by_name(&name, uuid).await {
                 Ok(uuid) => Ok(Change::Unsave {
                     uuid,
                     name: self
@@ -429,83 +938,434 @@ impl Repository {
                         .unwrap_or(None)
                         .unwrap_or(name),
                 }),
-                Err(e) => Err((Change::Save { name }, e)),
+                Err(e) => Err((Change::Save { name, uuid }, e)),
             },
             Change::Unsave { name, uuid } => self
                 .unsave_thing_by_uuid(&uuid)
                 .await
-                .map(|name| Change::Save { name })
+                .map(|name| Change::Save { name, uuid: None })
                 .map_err(|(_, e)| (Change::Unsave { name, uuid }, e)),
             Change::SetKeyValue { key_value } => self
                 .set_key_value(&key_value)
                 .await
                 .map(|old_kv| Change::SetKeyValue { key_value: old_kv })
                 .map_err(|e| (Change::SetKeyValue { key_value }, e)),
+            Change::SetLocked {
+                name,
+                uuid,
+                field,
+                locked,
+            } => match self.set_locked(&name, uuid.as_ref(), &field, locked).await {
+                Ok(was_locked) => Ok(Change::SetLocked {
+                    name,
+                    uuid,
+                    field,
+                    locked: was_locked,
+                }),
+                Err(e) => Err((
+                    Change::SetLocked {
+                        name,
+                        uuid,
+                        field,
+                        locked,
+                    },
+                    e,
+                )),
+            },
+        }
+    }
+
+    /// Apply each change in turn. If one fails, those already applied are reversed so that the
+    /// batch is all or nothing.
+    async fn apply_batch(&mut self, changes: Vec<Change>) -> Result<Change, (Change, Error)> {
+        let mut reverse_changes = Vec::with_capacity(changes.len());
+        let mut changes = changes.into_iter();
+
+        while let Some(change) = changes.next() {
+            match Box::pin(self.apply_change(change)).await {
+                Ok(reverse_change) => reverse_changes.push(reverse_change),
+                Err((change, e)) => {
+                    let mut unapplied = Vec::with_capacity(reverse_changes.len() + 1);
+                    for reverse_change in reverse_changes.into_iter().rev() {
+                        match Box::pin(self.apply_change(reverse_change)).await {
+                            Ok(change) | Err((change, _)) => unapplied.push(change),
+                        }
+                    }
+                    unapplied.reverse();
+                    unapplied.push(change);
+                    unapplied.extend(changes);
+
+                    return Err((Change::Batch { changes: unapplied }, e));
+                }
+            }
         }
+
+        reverse_changes.reverse();
+        Ok(Change::Batch {
+            changes: reverse_changes,
+        })
     }
 
     pub async fn get_key_value(&self, key: &KeyValue) -> Result<KeyValue, Error> {
         let value_str = self.data_store.get_value(key.key_raw()).await;
 
         match key {
+            KeyValue::Armies(_) => value_str
+                .and_then(|o| o.map(|s| s.parse()).transpose())
+                .map(KeyValue::Armies),
+            KeyValue::Battles(_) => value_str
+                .and_then(|o| o.map(|s| s.parse()).transpose())
+                .map(KeyValue::Battles),
+            KeyValue::CardTemplates(_) => value_str
+                .and_then(|o| o.map(|s| s.parse()).transpose())
+                .map(KeyValue::CardTemplates),
+            KeyValue::Config(_) => value_str
+                .and_then(|o| o.map(|s| s.parse()).transpose())
+                .map(KeyValue::Config),
+            KeyValue::Homebrew(_) => value_str
+                .and_then(|o| o.map(|s| s.parse()).transpose())
+                .map(KeyValue::Homebrew),
+            KeyValue::HomebrewReference(_) => value_str
+                .and_then(|o| o.map(|s| s.parse()).transpose())
+                .map(KeyValue::HomebrewReference),
+            KeyValue::Pantheon(_) => value_str
+                .and_then(|o| o.map(|s| s.parse()).transpose())
+                .map(KeyValue::Pantheon),
+            KeyValue::PartyLocation(_) => value_str
+                .and_then(|o| {
+                    o.map(|s| s.parse::<Uuid>().map(PlaceUuid::from).map_err(|_| ()))
+                        .transpose()
+                })
+                .map(KeyValue::PartyLocation),
+            KeyValue::Portents(_) => value_str
+                .and_then(|o| o.map(|s| s.parse()).transpose())
+                .map(KeyValue::Portents),
+            KeyValue::Scenes(_) => value_str
+                .and_then(|o| o.map(|s| s.parse()).transpose())
+                .map(KeyValue::Scenes),
+            KeyValue::Secrets(_) => value_str
+                .and_then(|o| o.map(|s| s.parse()).transpose())
+                .map(KeyValue::Secrets),
+            KeyValue::Sessions(_) => value_str
+                .and_then(|o| o.map(|s| s.parse()).transpose())
+                .map(KeyValue::Sessions),
+            KeyValue::Tables(_) => value_str
+                .and_then(|o| o.map(|s| s.parse()).transpose())
+                .map(KeyValue::Tables),
+            KeyValue::Templates(_) => value_str
+                .and_then(|o| o.map(|s| s.parse()).transpose())
+                .map(KeyValue::Templates),
+            KeyValue::Threads(_) => value_str
+                .and_then(|o| o.map(|s| s.parse()).transpose())
+                .map(KeyValue::Threads),
             KeyValue::Time(_) => value_str
                 .and_then(|o| o.map(|s| s.parse()).transpose())
                 .map(KeyValue::Time),
+            KeyValue::Treasury(_) => value_str
+                .and_then(|o| o.map(|s| s.parse()).transpose())
+                .map(KeyValue::Treasury),
+            KeyValue::Tutorial(_) => value_str
+                .and_then(|o| o.map(|s| s.parse()).transpose())
+                .map(KeyValue::Tutorial),
         }
         .map_err(|_| Error::DataStoreFailed)
     }
 
-    pub fn data_store_enabled(&self) -> bool {
-        self.data_store_enabled
-    }
+    /// Get the notable events of the campaign, ordered by the in-game time when they occurred.
+    /// Events that occurred at the same time are listed in the order they were recorded.
+    pub async fn timeline(&self) -> Result<Vec<TimelineEntry>, Error> {
+        let mut timeline: Vec<TimelineEntry> = self
+            .get_deferred_value(TIMELINE_KEY)
+            .await
+            .map_err(|_| Error::DataStoreFailed)?
+            .map(|raw| raw.lines().filter_map(|line| line.parse().ok()).collect())
+            .unwrap_or_default();
 
-    async fn set_key_value(&mut self, key_value: &KeyValue) -> Result<KeyValue, Error> {
-        let old_key_value = self.get_key_value(key_value).await?;
+        timeline.sort_by(|a, b| a.time.cmp(&b.time));
 
-        match key_value.key_value_raw() {
-            (key, Some(value)) => self.data_store.set_value(key, &value).await,
-            (key, None) => self.data_store.delete_value(key).await,
-        }
-        .map(|_| old_key_value)
-        .map_err(|_| Error::DataStoreFailed)
+        Ok(timeline)
     }
 
-    fn push_recent(&mut self, thing: Thing) {
-        while self.recent.len() >= RECENT_MAX_LEN {
-            self.recent.pop_front();
-        }
+    /// Get the commands recorded in the data store, in the order they were run. Commands are
+    /// only recorded while the `history` setting is on.
+    pub async fn history(&self) -> Result<Vec<HistoryEntry>, Error> {
+        self.get_history(HISTORY_KEY).await
+    }
 
-        self.recent.push_back(thing);
+    /// Append a command to the history in the data store. As with the timeline, failing to record
+    /// it doesn't cause the command itself to fail.
+    pub async fn record_history(&mut self, entry: &HistoryEntry) {
+        let raw = match self.get_deferred_value(HISTORY_KEY).await {
+            Ok(Some(raw)) => format!("{}\n{}", raw, entry),
+            Ok(None) => entry.to_string(),
+            Err(()) => return,
+        };
+
+        self.defer_value(HISTORY_KEY, Some(raw)).await;
     }
 
-    fn take_recent<F>(&mut self, f: F) -> Option<Thing>
-    where
-        F: Fn(&Thing) -> bool,
-    {
-        if let Some(index) =
-            self.recent
-                .iter()
-                .enumerate()
-                .find_map(|(i, t)| if f(t) { Some(i) } else { None })
-        {
-            self.recent.remove(index)
-        } else {
-            None
+    /// Get a sequence of commands saved under a name with `history save [name]`, or `None` if
+    /// there is no such log.
+    pub async fn history_log(&self, name: &str) -> Result<Option<Vec<HistoryEntry>>, Error> {
+        let key = format!("{}{}", HISTORY_LOG_PREFIX, name.to_lowercase());
+
+        match self.data_store.get_value(&key).await {
+            Ok(Some(_)) => self.get_history(&key).await.map(Some),
+            Ok(None) => Ok(None),
+            Err(()) => Err(Error::DataStoreFailed),
         }
     }
 
-    async fn create_thing(&mut self, thing: Thing) -> Result<String, (Thing, Error)> {
-        if let Some(name) = thing.name().value() {
-            if self.get_by_name(name).await.is_ok() {
-                Err((thing, Error::NameAlreadyExists))
-            } else {
-                let name = name.to_string();
-                self.push_recent(thing);
-                Ok(name)
-            }
-        } else {
-            Err((thing, Error::MissingName))
-        }
+    /// Save a sequence of commands under a name, replacing any log previously saved under it.
+    pub async fn save_history_log(
+        &mut self,
+        name: &str,
+        entries: &[HistoryEntry],
+    ) -> Result<(), Error> {
+        let raw = entries
+            .iter()
+            .map(|entry| entry.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        self.data_store
+            .set_value(
+                &format!("{}{}", HISTORY_LOG_PREFIX, name.to_lowercase()),
+                &raw,
+            )
+            .await
+            .map_err(|_| Error::DataStoreFailed)
+    }
+
+    async fn get_history(&self, key: &str) -> Result<Vec<HistoryEntry>, Error> {
+        Ok(self
+            .get_deferred_value(key)
+            .await
+            .map_err(|_| Error::DataStoreFailed)?
+            .map(|raw| raw.lines().filter_map(|line| line.parse().ok()).collect())
+            .unwrap_or_default())
+    }
+
+    pub fn data_store_enabled(&self) -> bool {
+        self.data_store_enabled
+    }
+
+    /// Write any deferred changes to the data store, such as before the user leaves. Anything that
+    /// can't be written stays queued, to be tried again on the next flush.
+    pub async fn flush(&mut self) -> Result<(), Error> {
+        let mut result = Ok(());
+
+        for (uuid, thing) in self.pending_things.drain().collect::<Vec<_>>() {
+            if self.data_store.save_thing(&thing).await.is_err() {
+                self.pending_things.insert(uuid, thing);
+                result = Err(Error::DataStoreFailed);
+            }
+        }
+
+        for (key, value) in self.pending_writes.drain().collect::<Vec<_>>() {
+            if match &value {
+                Some(value) => self.data_store.set_value(key, value).await,
+                None => self.data_store.delete_value(key).await,
+            }
+            .is_err()
+            {
+                self.pending_writes.insert(key, value);
+                result = Err(Error::DataStoreFailed);
+            }
+        }
+
+        self.pending_write_count = self.pending_things.len() + self.pending_writes.len();
+        result
+    }
+
+    /// Whether any changes are waiting to be written to the data store.
+    pub fn has_pending_writes(&self) -> bool {
+        self.pending_write_count > 0
+    }
+
+    /// Queue a write to the data store, replacing any write already pending for the same key.
+    /// Values that are rewritten on every command, such as the history and timeline, are written
+    /// this way so that a slow data store doesn't hold up every command. The queue is flushed
+    /// after every `WRITE_BEHIND_LEN` writes.
+    async fn defer_value(&mut self, key: &'static str, value: Option<String>) {
+        self.pending_writes.insert(key, value);
+        self.count_pending_write().await;
+    }
+
+    async fn count_pending_write(&mut self) {
+        self.pending_write_count += 1;
+
+        if self.pending_write_count >= WRITE_BEHIND_LEN {
+            let _ = self.flush().await;
+        }
+    }
+
+    /// Get a value from the data store, or the value waiting to be written if there is one.
+    async fn get_deferred_value(&self, key: &str) -> Result<Option<String>, ()> {
+        if let Some(value) = self.pending_writes.get(key) {
+            Ok(value.clone())
+        } else {
+            self.data_store.get_value(key).await
+        }
+    }
+
+    async fn set_key_value(&mut self, key_value: &KeyValue) -> Result<KeyValue, Error> {
+        let old_key_value = self.get_key_value(key_value).await?;
+
+        match key_value.key_value_raw() {
+            (key, Some(value)) => self.data_store.set_value(key, &value).await,
+            (key, None) => self.data_store.delete_value(key).await,
+        }
+        .map_err(|_| Error::DataStoreFailed)?;
+
+        // Recent entries saved by autosave are forgotten as soon as it's turned off.
+        if let KeyValue::Config(config) = key_value {
+            let autosave = config.as_ref().map_or(false, |c| c.autosave);
+
+            if self.autosave_recent && !autosave {
+                self.defer_value(RECENT_KEY, None).await;
+            }
+
+            self.autosave_recent = autosave;
+        }
+
+        Ok(old_key_value)
+    }
+
+    /// Does applying the change (as an undo) reverse an event recorded in the timeline?
+    fn is_logged(undo_change: &Change) -> bool {
+        matches!(
+            undo_change,
+            Change::Create { .. }
+                | Change::CreateAndSave { .. }
+                | Change::Delete { .. }
+                | Change::SetKeyValue {
+                    key_value: KeyValue::Battles(_) | KeyValue::Time(_),
+                },
+        )
+    }
+
+    /// How many events in the timeline are reversed by applying the change as an undo.
+    fn logged_count(undo_change: &Change) -> usize {
+        if let Change::Batch { changes } = undo_change {
+            changes.iter().filter(|c| Self::is_logged(c)).count()
+        } else {
+            usize::from(Self::is_logged(undo_change))
+        }
+    }
+
+    /// Record the event corresponding to a change in the timeline. Since the timeline is purely
+    /// informational, failing to record an event doesn't cause the change itself to fail.
+    async fn log_event(&mut self, undo_change: &Change, thing: Option<&Thing>) {
+        if !Self::is_logged(undo_change) {
+            return;
+        }
+
+        let current_time = self
+            .get_key_value(&KeyValue::Time(None))
+            .await
+            .ok()
+            .and_then(KeyValue::time)
+            .unwrap_or_default();
+
+        let entry = match undo_change {
+            Change::Delete { name, .. } => TimelineEntry {
+                time: current_time,
+                event: TimelineEvent::Create {
+                    name: thing.map_or_else(|| name.to_string(), |t| t.name().to_string()),
+                    thing_type: thing.map_or("thing", |t| t.as_str()).to_string(),
+                },
+            },
+            Change::Create { thing } | Change::CreateAndSave { thing } => TimelineEntry {
+                time: current_time,
+                event: TimelineEvent::Delete {
+                    name: thing.name().to_string(),
+                    thing_type: thing.as_str().to_string(),
+                },
+            },
+            Change::SetKeyValue {
+                key_value: KeyValue::Time(time),
+            } => TimelineEntry {
+                time: time.clone().unwrap_or_default(),
+                event: TimelineEvent::TimeJump { to: current_time },
+            },
+            // Battles are only ever added, so the battle being recorded is the last one.
+            Change::SetKeyValue {
+                key_value: KeyValue::Battles(_),
+            } => match self
+                .get_key_value(&KeyValue::Battles(None))
+                .await
+                .ok()
+                .and_then(KeyValue::battles)
+                .as_ref()
+                .and_then(|battles| battles.last())
+            {
+                Some(battle) => TimelineEntry {
+                    time: current_time,
+                    event: TimelineEvent::Battle {
+                        summary: battle.to_string(),
+                    },
+                },
+                None => return,
+            },
+            _ => unreachable!(),
+        };
+
+        let raw = match self.get_deferred_value(TIMELINE_KEY).await {
+            Ok(Some(raw)) => format!("{}\n{}", raw, entry),
+            Ok(None) => entry.to_string(),
+            Err(()) => return,
+        };
+
+        self.defer_value(TIMELINE_KEY, Some(raw)).await;
+    }
+
+    /// Remove the most recently recorded event from the timeline, when its change is undone.
+    async fn unlog_event(&mut self) {
+        if let Ok(Some(raw)) = self.get_deferred_value(TIMELINE_KEY).await {
+            let remainder = raw
+                .rsplit_once('\n')
+                .map(|(remainder, _)| remainder.to_string());
+            self.defer_value(TIMELINE_KEY, remainder).await;
+        }
+    }
+
+    fn push_recent(&mut self, thing: Thing) {
+        while self.recent.len() >= RECENT_MAX_LEN {
+            self.recent.pop_front();
+        }
+
+        self.recent.push_back(thing);
+    }
+
+    fn take_recent<F>(&mut self, f: F) -> Option<Thing>
+    where
+        F: Fn(&Thing) -> bool,
+    {
+        if let Some(index) =
+            self.recent
+                .iter()
+                .enumerate()
+                .find_map(|(i, t)| if f(t) { Some(i) } else { None })
+        {
+            self.recent.remove(index)
+        } else {
+            None
+        }
+    }
+
+    async fn create_thing(&mut self, thing: Thing) -> Result<String, (Thing, Error)> {
+        if let Some(name) = thing.name().value() {
+            if self.get_by_name(name).await.is_ok() {
+                Err((thing, Error::NameAlreadyExists))
+            } else {
+                let name = name.to_string();
+                self.push_recent(thing);
+                Ok(name)
+            }
+        } else {
+            Err((thing, Error::MissingName))
+        }
     }
 
     async fn create_and_save_thing(&mut self, thing: Thing) -> Result<Uuid, (Thing, Error)> {
@@ -528,9 +1388,7 @@ impl Repository {
             .and_then(|t| t.uuid().cloned())
         {
             self.delete_thing_by_uuid(&uuid).await.map_err(|(_, e)| e)
-        } else if let Some(thing) =
-            self.take_recent(|t| t.name().value().map_or(false, |s| s.eq_ci(name)))
-        {
+        } else if let Some(thing) = self.take_recent(|t| t.is_named(name)) {
             Ok(thing)
         } else {
             Err(Error::NotFound)
@@ -538,6 +1396,11 @@ impl Repository {
     }
 
     async fn delete_thing_by_uuid(&mut self, uuid: &Uuid) -> Result<Thing, (Option<Thing>, Error)> {
+        // A thing that hasn't been written yet need never be.
+        if let Some(thing) = self.pending_things.remove(uuid) {
+            return Ok(thing);
+        }
+
         match (
             self.data_store.get_thing_by_uuid(uuid).await,
             self.data_store.delete_thing_by_uuid(uuid).await,
@@ -549,9 +1412,12 @@ impl Repository {
         }
     }
 
-    async fn save_thing_by_name(&mut self, name: &str) -> Result<Uuid, Error> {
-        if let Some(thing) = self.take_recent(|t| t.name().value().map_or(false, |s| s.eq_ci(name)))
-        {
+    async fn save_thing_by_name(&mut self, name: &str, uuid: Option<Uuid>) -> Result<Uuid, Error> {
+        if let Some(mut thing) = self.take_recent(|t| t.is_named(name)) {
+            if let Some(uuid) = uuid {
+                thing.set_uuid(uuid);
+            }
+
             self.save_thing(thing).await.map_err(|(thing, e)| {
                 self.push_recent(thing);
                 e
@@ -570,12 +1436,54 @@ impl Repository {
             uuid
         };
 
-        match self.data_store.save_thing(&thing).await {
-            Ok(()) => Ok(uuid),
-            Err(()) => {
-                thing.clear_uuid();
-                Err((thing, Error::DataStoreFailed))
-            }
+        // Everything in the journal is kept as-is unless the user explicitly unlocks it.
+        let mut locked_thing = thing.clone();
+        locked_thing.lock_all();
+
+        // Things saved together, such as by a chain of commands, are written together when the
+        // batch ends. Otherwise the save is written straight away, so that the user hears about it
+        // if it fails.
+        if self.batch_depth == 0 || !self.data_store_enabled {
+            return match self.data_store.save_thing(&locked_thing).await {
+                Ok(()) => Ok(uuid),
+                Err(()) => {
+                    thing.clear_uuid();
+                    Err((thing, Error::DataStoreFailed))
+                }
+            };
+        }
+
+        self.pending_things.insert(uuid, locked_thing);
+        self.count_pending_write().await;
+        Ok(uuid)
+    }
+
+    /// Write an edited thing, updating the pending save instead if it hasn't been written yet.
+    async fn write_thing(&mut self, thing: &Thing) -> Result<(), ()> {
+        if let Some(pending_thing) = thing
+            .uuid()
+            .and_then(|uuid| self.pending_things.get_mut(uuid))
+        {
+            *pending_thing = thing.clone();
+            Ok(())
+        } else {
+            self.data_store.edit_thing(thing).await
+        }
+    }
+
+    async fn get_saved_thing_by_uuid(&self, uuid: &Uuid) -> Result<Option<Thing>, ()> {
+        if let Some(thing) = self.pending_things.get(uuid) {
+            Ok(Some(thing.clone()))
+        } else {
+            self.data_store.get_thing_by_uuid(uuid).await
+        }
+    }
+
+    async fn get_saved_thing_by_name(&self, name: &str) -> Result<Option<Thing>, ()> {
+        if let Some(thing) = self.pending_things.values().find(|t| t.is_named(name)) {
+            Ok(Some(thing.clone()))
+        } else {
+            self.data_store.get_thing_by_name(name).await
         }
     }
 
@@ -600,12 +1508,42 @@ impl Repository {
         }
     }
 
+    /// Lock or unlock a field on a thing in the journal (by UUID) or in recent (by name),
+    /// returning whether the field was locked beforehand.
+    async fn set_locked(
+        &mut self,
+        name: &str,
+        uuid: Option<&Uuid>,
+        field: &str,
+        locked: bool,
+    ) -> Result<bool, Error> {
+        if let Some(uuid) = uuid {
+            let mut thing = self.get_by_uuid(uuid).await?;
+            let was_locked = thing
+                .set_field_locked(field, locked)
+                .ok_or(Error::NotFound)?;
+
+            self.write_thing(&thing)
+                .await
+                .map_err(|_| Error::DataStoreFailed)?;
+
+            Ok(was_locked)
+        } else {
+            self.recent
+                .iter_mut()
+                .find(|thing| thing.is_named(name))
+                .ok_or(Error::NotFound)?
+                .set_field_locked(field, locked)
+                .ok_or(Error::NotFound)
+        }
+    }
+
     async fn edit_thing_by_uuid(
         &mut self,
         uuid: &Uuid,
         mut diff: Thing,
     ) -> Result<Thing, (Thing, Error)> {
-        match self.data_store.get_thing_by_uuid(uuid).await {
+        match self.get_saved_thing_by_uuid(uuid).await {
             Ok(Some(mut thing)) => {
                 if thing.try_apply_diff(&mut diff).is_err() {
                     // This fails when the thing types don't match, eg. applying an Npc diff to a
@@ -613,7 +1551,7 @@ impl Repository {
                     return Err((diff, Error::NotFound));
                 }
 
-                match self.data_store.edit_thing(&thing).await {
+                match self.write_thing(&thing).await {
                     Ok(()) => Ok(diff),
                     Err(()) => Err((diff, Error::DataStoreFailed)),
                 }
@@ -628,13 +1566,13 @@ impl Repository {
         name: &str,
         mut diff: Thing,
     ) -> Result<Change, (Thing, Error)> {
-        let data_store_failed = match self.data_store.get_thing_by_name(name).await {
+        let data_store_failed = match self.get_saved_thing_by_name(name).await {
             Ok(Some(mut thing)) => {
                 if thing.try_apply_diff(&mut diff).is_err() {
                     return Err((diff, Error::NotFound));
                 }
 
-                return match self.data_store.edit_thing(&thing).await {
+                return match self.write_thing(&thing).await {
                     Ok(()) => Ok(Change::Edit {
                         name: thing.name().to_string(),
                         uuid: thing.uuid().cloned(),
@@ -647,9 +1585,9 @@ impl Repository {
             Err(()) => true,
         };
 
-        if let Some(mut thing) = self.take_recent(|thing| {
-            thing.name().value().map_or(false, |s| s.eq_ci(name)) && thing.as_str() == diff.as_str()
-        }) {
+        if let Some(mut thing) =
+            self.take_recent(|thing| thing.is_named(name) && thing.as_str() == diff.as_str())
+        {
             thing.try_apply_diff(&mut diff).unwrap();
 
             let name = thing.name().to_string();
@@ -686,7 +1624,24 @@ impl Repository {
 impl KeyValue {
     pub const fn key_raw(&self) -> &'static str {
         match self {
+            Self::Armies(_) => "armies",
+            Self::Battles(_) => "battles",
+            Self::CardTemplates(_) => "card_templates",
+            Self::Config(_) => "config",
+            Self::Homebrew(_) => "homebrew",
+            Self::HomebrewReference(_) => "homebrew_reference",
+            Self::Pantheon(_) => "pantheon",
+            Self::PartyLocation(_) => "party_location",
+            Self::Portents(_) => "portents",
+            Self::Scenes(_) => "scenes",
+            Self::Secrets(_) => "secrets",
+            Self::Sessions(_) => "sessions",
+            Self::Tables(_) => "tables",
+            Self::Templates(_) => "templates",
+            Self::Threads(_) => "threads",
             Self::Time(_) => "time",
+            Self::Treasury(_) => "treasury",
+            Self::Tutorial(_) => "tutorial",
         }
     }
 
@@ -694,22 +1649,234 @@ impl KeyValue {
         (
             self.key_raw(),
             match self {
+                Self::Armies(armies) => armies
+                    .as_ref()
+                    .filter(|a| !a.is_empty())
+                    .map(|a| a.to_string()),
+                Self::Battles(battles) => battles
+                    .as_ref()
+                    .filter(|b| !b.is_empty())
+                    .map(|b| b.to_string()),
+                Self::CardTemplates(templates) => templates
+                    .as_ref()
+                    .filter(|t| !t.is_empty())
+                    .map(|t| t.to_string()),
+                Self::Config(config) => config.as_ref().map(|c| c.to_string()),
+                Self::Homebrew(homebrew) => homebrew
+                    .as_ref()
+                    .filter(|h| !h.is_empty())
+                    .map(|h| h.to_string()),
+                Self::HomebrewReference(homebrew) => homebrew
+                    .as_ref()
+                    .filter(|h| !h.is_empty())
+                    .map(|h| h.to_string()),
+                Self::Pantheon(pantheon) => pantheon
+                    .as_ref()
+                    .filter(|p| !p.is_empty())
+                    .map(|p| p.to_string()),
+                Self::PartyLocation(uuid) => uuid.as_ref().map(|u| u.to_string()),
+                Self::Portents(portents) => portents
+                    .as_ref()
+                    .filter(|p| !p.is_empty())
+                    .map(|p| p.to_string()),
+                Self::Scenes(scenes) => scenes
+                    .as_ref()
+                    .filter(|s| !s.is_empty())
+                    .map(|s| s.to_string()),
+                Self::Secrets(secrets) => secrets
+                    .as_ref()
+                    .filter(|s| !s.is_empty())
+                    .map(|s| s.to_string()),
+                Self::Sessions(sessions) => sessions
+                    .as_ref()
+                    .filter(|s| !s.is_empty())
+                    .map(|s| s.to_string()),
+                Self::Tables(tables) => tables
+                    .as_ref()
+                    .filter(|t| !t.is_empty())
+                    .map(|t| t.to_string()),
+                Self::Templates(templates) => templates
+                    .as_ref()
+                    .filter(|t| !t.is_empty())
+                    .map(|t| t.to_string()),
+                Self::Threads(threads) => threads
+                    .as_ref()
+                    .filter(|t| !t.is_empty())
+                    .map(|t| t.to_string()),
                 Self::Time(time) => time.as_ref().map(|t| t.display_short().to_string()),
+                Self::Treasury(coins) => coins
+                    .as_ref()
+                    .filter(|c| !c.is_empty())
+                    .map(|c| c.to_string()),
+                Self::Tutorial(progress) => progress.as_ref().map(|p| p.to_string()),
             },
         )
     }
 
-    pub const fn time(self) -> Option<Time> {
-        #[allow(irrefutable_let_patterns)]
+    pub fn armies(self) -> Option<Armies> {
+        if let Self::Armies(armies) = self {
+            armies
+        } else {
+            None
+        }
+    }
+
+    pub fn battles(self) -> Option<Battles> {
+        if let Self::Battles(battles) = self {
+            battles
+        } else {
+            None
+        }
+    }
+
+    pub fn card_templates(self) -> Option<CardTemplates> {
+        if let Self::CardTemplates(templates) = self {
+            templates
+        } else {
+            None
+        }
+    }
+
+    pub fn config(self) -> Option<Config> {
+        if let Self::Config(config) = self {
+            config
+        } else {
+            None
+        }
+    }
+
+    pub fn homebrew(self) -> Option<Homebrew> {
+        if let Self::Homebrew(homebrew) = self {
+            homebrew
+        } else {
+            None
+        }
+    }
+
+    pub fn homebrew_reference(self) -> Option<HomebrewReference> {
+        if let Self::HomebrewReference(homebrew) = self {
+            homebrew
+        } else {
+            None
+        }
+    }
+
+    pub fn pantheon(self) -> Option<Pantheon> {
+        if let Self::Pantheon(pantheon) = self {
+            pantheon
+        } else {
+            None
+        }
+    }
+
+    pub fn party_location(self) -> Option<PlaceUuid> {
+        if let Self::PartyLocation(uuid) = self {
+            uuid
+        } else {
+            None
+        }
+    }
+
+    pub fn portents(self) -> Option<Portents> {
+        if let Self::Portents(portents) = self {
+            portents
+        } else {
+            None
+        }
+    }
+
+    pub fn scenes(self) -> Option<Scenes> {
+        if let Self::Scenes(scenes) = self {
+            scenes
+        } else {
+            None
+        }
+    }
+
+    pub fn secrets(self) -> Option<Secrets> {
+        if let Self::Secrets(secrets) = self {
+            secrets
+        } else {
+            None
+        }
+    }
+
+    pub fn sessions(self) -> Option<Sessions> {
+        if let Self::Sessions(sessions) = self {
+            sessions
+        } else {
+            None
+        }
+    }
+
+    pub fn tables(self) -> Option<Tables> {
+        if let Self::Tables(tables) = self {
+            tables
+        } else {
+            None
+        }
+    }
+
+    pub fn templates(self) -> Option<Templates> {
+        if let Self::Templates(templates) = self {
+            templates
+        } else {
+            None
+        }
+    }
+
+    pub fn threads(self) -> Option<Threads> {
+        if let Self::Threads(threads) = self {
+            threads
+        } else {
+            None
+        }
+    }
+
+    pub fn time(self) -> Option<Time> {
         if let Self::Time(time) = self {
             time
         } else {
             None
         }
     }
+
+    pub fn treasury(self) -> Option<Coins> {
+        if let Self::Treasury(coins) = self {
+            coins
+        } else {
+            None
+        }
+    }
+
+    pub fn tutorial(self) -> Option<TutorialProgress> {
+        if let Self::Tutorial(progress) = self {
+            progress
+        } else {
+            None
+        }
+    }
 }
 
 impl Change {
+    /// Edit the given thing, by its UUID if it has been saved to the journal or its name if not.
+    pub fn edit(thing: &Thing, diff: Thing) -> Self {
+        Change::Edit {
+            name: thing.name().to_string(),
+            uuid: thing.uuid().cloned(),
+            diff,
+        }
+    }
+
+    /// Delete the given thing, by its UUID if it has been saved to the journal or its name if
+    /// not.
+    pub fn delete(thing: &Thing) -> Self {
+        Change::Delete {
+            name: thing.name().to_string(),
+            uuid: thing.uuid().cloned(),
+        }
+    }
+
     pub fn display_undo(&self) -> DisplayUndo {
         DisplayUndo(self)
     }
@@ -724,9 +1891,15 @@ impl Change {
             Self::Delete { name, .. }
             | Self::Edit { name, .. }
             | Self::EditAndUnsave { name, .. }
-            | Self::Save { name }
-            | Self::Unsave { name, .. } => name.to_owned(),
+            | Self::Save { name, .. }
+            | Self::Unsave { name, .. }
+            | Self::SetLocked { name, .. } => name.to_owned(),
             Self::SetKeyValue { key_value } => key_value.key_raw().to_string(),
+            Self::Batch { changes } => changes
+                .iter()
+                .map(Self::name)
+                .collect::<Vec<_>>()
+                .join(", "),
         }
     }
 }
@@ -742,13 +1915,38 @@ impl<'a> fmt::Display for DisplayUndo<'a> {
                 write!(f, "deleting {}", thing.name())
             }
             Change::Delete { name, .. } => write!(f, "creating {}", name),
-            Change::Save { name } => write!(f, "removing {} from journal", name),
+            Change::Save { name, .. } => write!(f, "removing {} from journal", name),
             Change::Unsave { name, .. } => write!(f, "saving {} to journal", name),
+            Change::SetLocked {
+                name,
+                field,
+                locked: true,
+                ..
+            } => write!(f, "unlocking {}'s {}", name, field),
+            Change::SetLocked {
+                name,
+                field,
+                locked: false,
+                ..
+            } => write!(f, "locking {}'s {}", name, field),
 
             // These changes are symmetric, so we can provide the same output in both cases.
             Change::Edit { .. } | Change::EditAndUnsave { .. } | Change::SetKeyValue { .. } => {
                 write!(f, "{}", DisplayRedo(change))
             }
+
+            // The reversed changes are stored last first.
+            Change::Batch { changes } => write!(
+                f,
+                "{}",
+                list(
+                    &changes
+                        .iter()
+                        .rev()
+                        .map(|change| change.display_undo().to_string())
+                        .collect::<Vec<_>>()
+                ),
+            ),
         }
     }
 }
@@ -764,11 +1962,50 @@ impl<'a> fmt::Display for DisplayRedo<'a> {
             Change::Edit { name, .. } | Change::EditAndUnsave { name, .. } => {
                 write!(f, "editing {}", name)
             }
-            Change::Save { name } => write!(f, "saving {} to journal", name),
+            Change::Save { name, .. } => write!(f, "saving {} to journal", name),
             Change::Unsave { name, .. } => write!(f, "removing {} from journal", name),
             Change::SetKeyValue { key_value } => match key_value {
+                KeyValue::Armies(_) => write!(f, "changing the armies"),
+                KeyValue::Battles(_) => write!(f, "fighting a battle"),
+                KeyValue::CardTemplates(_) => write!(f, "changing the summary templates"),
+                KeyValue::Config(_) => write!(f, "changing the configuration"),
+                KeyValue::Homebrew(_) => write!(f, "changing the homebrew types"),
+                KeyValue::HomebrewReference(_) => write!(f, "importing homebrew content"),
+                KeyValue::Pantheon(_) => write!(f, "changing the pantheon"),
+                KeyValue::PartyLocation(_) => write!(f, "moving the party"),
+                KeyValue::Portents(_) => write!(f, "changing the portents"),
+                KeyValue::Scenes(_) => write!(f, "changing the scenes"),
+                KeyValue::Secrets(_) => write!(f, "changing the secrets"),
+                KeyValue::Sessions(_) => write!(f, "changing the sessions"),
+                KeyValue::Tables(_) => write!(f, "changing the tables"),
+                KeyValue::Templates(_) => write!(f, "changing the templates"),
+                KeyValue::Threads(_) => write!(f, "changing the threads"),
                 KeyValue::Time(_) => write!(f, "changing the time"),
+                KeyValue::Treasury(_) => write!(f, "changing the treasury"),
+                KeyValue::Tutorial(_) => write!(f, "changing the tutorial progress"),
             },
+            Change::SetLocked {
+                name,
+                field,
+                locked: true,
+                ..
+            } => write!(f, "locking {}'s {}", name, field),
+            Change::SetLocked {
+                name,
+                field,
+                locked: false,
+                ..
+            } => write!(f, "unlocking {}'s {}", name, field),
+            Change::Batch { changes } => write!(
+                f,
+                "{}",
+                list(
+                    &changes
+                        .iter()
+                        .map(|change| change.display_redo().to_string())
+                        .collect::<Vec<_>>()
+                ),
+            ),
         }
     }
 }
@@ -787,11 +2024,10 @@ impl fmt::Debug for Repository {
 mod test {
     use super::*;
     use crate::storage::data_store::{MemoryDataStore, NullDataStore};
+    use crate::utils::Shared;
     use crate::world::npc::{Npc, Species};
     use crate::world::{Place, PlaceUuid};
     use async_trait::async_trait;
-    use std::cell::RefCell;
-    use std::rc::Rc;
     use tokio_test::block_on;
 
     const OLYMPUS_UUID: Uuid = Uuid::from_u128(1);
@@ -877,11 +2113,90 @@ mod test {
         );
     }
 
+    #[test]
+    fn get_by_name_test_normalized() {
+        let mut repo = repo();
+        block_on(
+            repo.modify(Change::Create {
+                thing: Npc {
+                    name: "Éowyn".into(),
+                    ..Default::default()
+                }
+                .into(),
+            }),
+        )
+        .unwrap();
+
+        assert_eq!(
+            "Éowyn",
+            block_on(repo.get_by_name("eowyn"))
+                .map(|thing| thing.name().to_string())
+                .unwrap(),
+        );
+        assert_eq!(
+            "Odysseus",
+            block_on(repo.get_by_name(" odysseus "))
+                .map(|thing| thing.name().to_string())
+                .unwrap(),
+        );
+        assert_eq!(
+            Err(Error::NameAlreadyExists),
+            block_on(
+                repo.modify(Change::Create {
+                    thing: Npc {
+                        name: "EOWYN".into(),
+                        ..Default::default()
+                    }
+                    .into(),
+                })
+            )
+            .map_err(|(_, e)| e),
+        );
+    }
+
     #[test]
     fn get_by_name_test_not_found() {
         assert_eq!(Err(Error::NotFound), block_on(repo().get_by_name("NOBODY")));
     }
 
+    #[test]
+    fn get_all_by_name_test() {
+        let mut repo = repo();
+
+        repo.push_recent(
+            Npc {
+                name: "Olympus".into(),
+                ..Default::default()
+            }
+            .into(),
+        );
+
+        let things = block_on(repo.get_all_by_name("OLYMPUS")).unwrap();
+        assert_eq!(2, things.len());
+        assert!(things[0].uuid().is_none());
+        assert_eq!(Some(&OLYMPUS_UUID), things[1].uuid());
+
+        assert!(block_on(repo.get_all_by_name("Nobody")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn get_all_by_name_test_data_store_failed() {
+        let mut repo = null_repo();
+        assert_eq!(
+            Err(Error::DataStoreFailed),
+            block_on(repo.get_all_by_name("Odysseus")),
+        );
+
+        repo.push_recent(
+            Npc {
+                name: "Odysseus".into(),
+                ..Default::default()
+            }
+            .into(),
+        );
+        assert_eq!(1, block_on(repo.get_all_by_name("Odysseus")).unwrap().len());
+    }
+
     #[test]
     fn get_by_uuid_test_from_journal() {
         assert_eq!(
@@ -1691,7 +3006,7 @@ mod test {
 
     #[test]
     fn change_test_edit_and_unsave_data_store_failed() {
-        let mut repo = Repository::new(TimeBombDataStore::new(7));
+        let mut repo = Repository::new(TimeBombDataStore::new(15));
         populate_repo(&mut repo);
 
         let change = Change::EditAndUnsave {
@@ -1770,6 +3085,28 @@ mod test {
         }
     }
 
+    #[test]
+    fn take_modifications_test() {
+        let mut repo = empty_repo();
+        let change = Change::Create {
+            thing: Npc {
+                name: "Odysseus".into(),
+                ..Default::default()
+            }
+            .into(),
+        };
+
+        block_on(repo.modify(change.clone())).unwrap();
+        assert!(block_on(repo.modify(change)).is_err());
+        block_on(repo.undo()).unwrap().unwrap();
+
+        assert_eq!(
+            vec!["creating Odysseus", "deleting Odysseus"],
+            repo.take_modifications(),
+        );
+        assert!(repo.take_modifications().is_empty());
+    }
+
     #[test]
     fn change_test_create_already_exists_in_journal() {
         let (mut repo, data_store) = repo_data_store();
@@ -1814,6 +3151,7 @@ mod test {
         let (mut repo, data_store) = repo_data_store();
         let change = Change::Save {
             name: "ODYSSEUS".to_string(),
+            uuid: None,
         };
         assert_eq!(
             "saving ODYSSEUS to journal",
@@ -1853,6 +3191,7 @@ mod test {
             assert_eq!(
                 Some(Change::Save {
                     name: "Odysseus".to_string(),
+                    uuid: None,
                 }),
                 repo.redo_change,
             );
@@ -1881,6 +3220,7 @@ mod test {
 
         let change = Change::Save {
             name: "ODYSSEUS".to_string(),
+            uuid: None,
         };
         assert_eq!(
             block_on(repo.modify(change.clone())),
@@ -1890,12 +3230,99 @@ mod test {
         assert_eq!(1, repo.recent().count());
     }
 
+    #[test]
+    fn change_test_set_locked_journal_success() {
+        let (mut repo, data_store) = repo_data_store();
+        let change = Change::SetLocked {
+            name: "Olympus".to_string(),
+            uuid: Some(OLYMPUS_UUID),
+            field: "name".to_string(),
+            locked: false,
+        };
+        assert_eq!(
+            "unlocking Olympus's name",
+            change.display_redo().to_string(),
+        );
+
+        {
+            let thing = block_on(repo.modify(change)).unwrap().unwrap();
+            assert_eq!(Some(false), thing.is_field_locked("name"));
+
+            let result = repo.undo_history().next().unwrap();
+            assert_eq!(
+                &Change::SetLocked {
+                    name: "Olympus".to_string(),
+                    uuid: Some(OLYMPUS_UUID),
+                    field: "name".to_string(),
+                    locked: true,
+                },
+                result,
+            );
+            assert_eq!(
+                "unlocking Olympus's name",
+                result.display_undo().to_string(),
+            );
+            assert_eq!(
+                Some(false),
+                block_on(data_store.get_thing_by_uuid(&OLYMPUS_UUID))
+                    .unwrap()
+                    .unwrap()
+                    .is_field_locked("name"),
+            );
+        }
+
+        {
+            let thing = block_on(repo.undo()).unwrap().unwrap().unwrap();
+            assert_eq!(Some(true), thing.is_field_locked("name"));
+        }
+    }
+
+    #[test]
+    fn change_test_set_locked_recent_success() {
+        let mut repo = repo();
+        let change = Change::SetLocked {
+            name: "ODYSSEUS".to_string(),
+            uuid: None,
+            field: "age".to_string(),
+            locked: false,
+        };
+
+        let thing = block_on(repo.modify(change)).unwrap().unwrap();
+        assert_eq!(Some(false), thing.is_field_locked("age"));
+        assert_eq!(
+            Some(false),
+            repo.recent().next().unwrap().is_field_locked("age"),
+        );
+    }
+
+    #[test]
+    fn change_test_set_locked_not_found() {
+        let mut repo = repo();
+
+        [("NOBODY", "age"), ("Odysseus", "potato")]
+            .into_iter()
+            .for_each(|(name, field)| {
+                let change = Change::SetLocked {
+                    name: name.to_string(),
+                    uuid: None,
+                    field: field.to_string(),
+                    locked: true,
+                };
+
+                assert_eq!(
+                    block_on(repo.modify(change.clone())),
+                    Err((change, Error::NotFound)),
+                );
+            });
+    }
+
     #[test]
     fn change_test_save_already_saved() {
         let mut repo = repo();
 
         let change = Change::Save {
             name: "OLYMPUS".to_string(),
+            uuid: None,
         };
         assert_eq!(
             block_on(repo.modify(change.clone())),
@@ -1908,6 +3335,7 @@ mod test {
         let mut repo = repo();
         let change = Change::Save {
             name: "NOBODY".to_string(),
+            uuid: None,
         };
 
         assert_eq!(
@@ -1943,6 +3371,7 @@ mod test {
             assert_eq!(
                 &Change::Save {
                     name: "Olympus".to_string(),
+                    uuid: None,
                 },
                 result,
             );
@@ -2025,14 +3454,16 @@ mod test {
                 .unwrap();
             assert_eq!(Some(Ok(None)), block_on(repo.undo()));
 
+            let mut odysseus = Npc {
+                uuid: Some(uuid.into()),
+                name: "Odysseus".into(),
+                ..Default::default()
+            };
+            odysseus.lock_all();
+
             assert_eq!(
-                Some(Change::CreateAndSave {
-                    thing: Npc {
-                        uuid: Some(uuid.into()),
-                        name: "Odysseus".into(),
-                        ..Default::default()
-                    }
-                    .into(),
+                Some(Change::CreateAndSave {
+                    thing: odysseus.into(),
                 }),
                 repo.redo_change,
             );
@@ -2188,6 +3619,7 @@ mod test {
         match block_on(repo.load_relations(&odysseus)) {
             Ok(ThingRelations::Npc(NpcRelations {
                 location: Some((parent, None)),
+                ..
             })) => {
                 assert_eq!("River Styx", parent.name.value().unwrap());
             }
@@ -2211,6 +3643,286 @@ mod test {
         }
     }
 
+    #[test]
+    fn timeline_test() {
+        let mut repo = repo();
+        assert_eq!(Ok(Vec::new()), block_on(repo.timeline()));
+
+        block_on(repo.modify(Change::Delete {
+            name: "Olympus".into(),
+            uuid: None,
+        }))
+        .unwrap();
+
+        block_on(repo.modify(Change::SetKeyValue {
+            key_value: KeyValue::Time(Some(Time::try_new(0, 0, 0, 0).unwrap())),
+        }))
+        .unwrap();
+
+        block_on(
+            repo.modify(Change::Create {
+                thing: Npc {
+                    name: "Medusa".into(),
+                    ..Default::default()
+                }
+                .into(),
+            }),
+        )
+        .unwrap();
+
+        block_on(repo.modify(Change::Save {
+            name: "Medusa".into(),
+            uuid: None,
+        }))
+        .unwrap();
+
+        assert_eq!(
+            Ok(vec![
+                TimelineEntry {
+                    time: Time::try_new(0, 0, 0, 0).unwrap(),
+                    event: TimelineEvent::Create {
+                        name: "Medusa".into(),
+                        thing_type: "character".into(),
+                    },
+                },
+                TimelineEntry {
+                    time: Time::default(),
+                    event: TimelineEvent::Delete {
+                        name: "Olympus".into(),
+                        thing_type: "place".into(),
+                    },
+                },
+                TimelineEntry {
+                    time: Time::default(),
+                    event: TimelineEvent::TimeJump {
+                        to: Time::try_new(0, 0, 0, 0).unwrap(),
+                    },
+                },
+            ]),
+            block_on(repo.timeline()),
+        );
+
+        block_on(repo.undo()).unwrap().unwrap();
+        block_on(repo.undo()).unwrap().unwrap();
+        block_on(repo.undo()).unwrap().unwrap();
+
+        assert_eq!(
+            Ok(vec![TimelineEntry {
+                time: Time::default(),
+                event: TimelineEvent::Delete {
+                    name: "Olympus".into(),
+                    thing_type: "place".into(),
+                },
+            }]),
+            block_on(repo.timeline()),
+        );
+
+        block_on(repo.undo()).unwrap().unwrap();
+        assert_eq!(Ok(Vec::new()), block_on(repo.timeline()));
+    }
+
+    #[test]
+    fn batch_test() {
+        let mut repo = repo();
+
+        repo.begin_batch();
+        block_on(repo.modify(Change::Create { thing: penelope() })).unwrap();
+        block_on(repo.modify(Change::Save {
+            name: "Penelope".into(),
+            uuid: None,
+        }))
+        .unwrap();
+        block_on(repo.modify(Change::Delete {
+            name: "Olympus".into(),
+            uuid: None,
+        }))
+        .unwrap();
+        block_on(repo.end_batch()).unwrap();
+
+        assert_eq!(1, repo.undo_history().count());
+        assert_eq!(
+            "creating Penelope, saving Penelope to journal, and deleting Olympus",
+            repo.undo_history()
+                .next()
+                .unwrap()
+                .display_undo()
+                .to_string(),
+        );
+        assert_eq!(2, block_on(repo.timeline()).unwrap().len());
+
+        assert_eq!(Some(Ok(None)), block_on(repo.undo()));
+        assert_eq!(Err(Error::NotFound), block_on(repo.get_by_name("Penelope")));
+        assert!(block_on(repo.get_by_name("Olympus")).is_ok());
+        assert_eq!(Ok(Vec::new()), block_on(repo.timeline()));
+        assert_eq!(
+            "creating Penelope, saving Penelope to journal, and deleting Olympus",
+            repo.get_redo().unwrap().display_redo().to_string(),
+        );
+
+        assert_eq!(Some(Ok(None)), block_on(repo.redo()));
+        assert!(block_on(repo.get_by_name("Penelope")).is_ok());
+        assert_eq!(Err(Error::NotFound), block_on(repo.get_by_name("Olympus")));
+        assert_eq!(2, block_on(repo.timeline()).unwrap().len());
+    }
+
+    #[test]
+    fn batch_save_test() {
+        let (mut repo, data_store) = repo_data_store();
+
+        repo.begin_batch();
+        block_on(repo.modify(Change::CreateAndSave { thing: penelope() })).unwrap();
+
+        assert!(block_on(repo.get_by_name("Penelope")).is_ok());
+        assert_eq!(5, block_on(repo.journal()).unwrap().len());
+        assert_eq!(4, block_on(data_store.get_all_the_things()).unwrap().len());
+
+        block_on(repo.end_batch()).unwrap();
+
+        assert_eq!(5, block_on(data_store.get_all_the_things()).unwrap().len());
+        assert!(!repo.has_pending_writes());
+    }
+
+    #[test]
+    fn batch_save_failure_test() {
+        let mut repo = Repository::new(UnsavableDataStore::default());
+        block_on(repo.init());
+        block_on(repo.modify(Change::Create { thing: penelope() })).unwrap();
+
+        repo.begin_batch();
+        block_on(repo.modify(Change::Save {
+            name: "Penelope".into(),
+            uuid: None,
+        }))
+        .unwrap();
+
+        assert_eq!(Err(Error::DataStoreFailed), block_on(repo.end_batch()));
+        assert_eq!(1, repo.undo_history().count());
+        assert_eq!(Ok(Vec::new()), block_on(repo.journal()));
+        assert!(repo
+            .recent()
+            .any(|thing| thing.name().value() == Some(&"Penelope".to_string())));
+    }
+
+    #[test]
+    fn flush_failure_test() {
+        let mut repo = Repository::new(UnsavableDataStore::default());
+        block_on(repo.init());
+
+        repo.begin_batch();
+        block_on(repo.modify(Change::CreateAndSave { thing: penelope() })).unwrap();
+
+        assert_eq!(Err(Error::DataStoreFailed), block_on(repo.flush()));
+        assert!(repo.has_pending_writes());
+        assert!(block_on(repo.get_by_name("Penelope")).is_ok());
+    }
+
+    #[test]
+    fn batch_single_change_test() {
+        let mut repo = repo();
+
+        repo.begin_batch();
+        block_on(repo.modify(Change::Create { thing: penelope() })).unwrap();
+        block_on(repo.end_batch()).unwrap();
+
+        assert!(matches!(
+            repo.undo_history().next(),
+            Some(Change::Delete { .. }),
+        ));
+    }
+
+    #[test]
+    fn batch_nested_test() {
+        let mut repo = repo();
+
+        repo.begin_batch();
+        block_on(repo.modify(Change::Create { thing: penelope() })).unwrap();
+        repo.begin_batch();
+        block_on(repo.modify(Change::Save {
+            name: "Penelope".into(),
+            uuid: None,
+        }))
+        .unwrap();
+        block_on(repo.end_batch()).unwrap();
+        block_on(repo.modify(Change::Delete {
+            name: "Olympus".into(),
+            uuid: None,
+        }))
+        .unwrap();
+        block_on(repo.end_batch()).unwrap();
+
+        assert_eq!(1, repo.undo_history().count());
+        assert!(matches!(
+            repo.undo_history().next(),
+            Some(Change::Batch { changes }) if changes.len() == 3,
+        ));
+    }
+
+    #[test]
+    fn transaction_test() {
+        let mut repo = repo();
+
+        assert_eq!(
+            Ok(()),
+            block_on(repo.transaction(vec![
+                Change::Create { thing: penelope() },
+                Change::Delete {
+                    name: "Olympus".into(),
+                    uuid: None,
+                },
+            ])),
+        );
+
+        assert!(block_on(repo.get_by_name("Penelope")).is_ok());
+        assert_eq!(Err(Error::NotFound), block_on(repo.get_by_name("Olympus")));
+        assert_eq!(
+            vec!["creating Penelope and deleting Olympus"],
+            repo.undo_history()
+                .map(|change| change.display_undo().to_string())
+                .collect::<Vec<_>>(),
+        );
+
+        assert_eq!(Some(Ok(None)), block_on(repo.undo()));
+        assert_eq!(Err(Error::NotFound), block_on(repo.get_by_name("Penelope")));
+        assert!(block_on(repo.get_by_name("Olympus")).is_ok());
+
+        assert_eq!(Ok(()), block_on(repo.transaction(Vec::new())));
+        assert_eq!(0, repo.undo_history().count());
+    }
+
+    #[test]
+    fn transaction_rollback_test() {
+        let mut repo = repo();
+        let changes = vec![
+            Change::Create { thing: penelope() },
+            Change::Delete {
+                name: "Olympus".into(),
+                uuid: None,
+            },
+            Change::Delete {
+                name: "Nobody".into(),
+                uuid: None,
+            },
+        ];
+
+        assert!(matches!(
+            block_on(repo.transaction(changes)),
+            Err((changes, Error::NotFound)) if changes.len() == 3,
+        ));
+
+        assert_eq!(Err(Error::NotFound), block_on(repo.get_by_name("Penelope")));
+        assert!(block_on(repo.get_by_name("Olympus")).is_ok());
+        assert_eq!(0, repo.undo_history().count());
+        assert_eq!(Ok(Vec::new()), block_on(repo.timeline()));
+
+        let changes = vec![Change::SetKeyValue {
+            key_value: KeyValue::Time(Some(Time::try_new(2, 0, 0, 0).unwrap())),
+        }];
+        assert_eq!(
+            Err((changes.clone(), Error::DataStoreFailed)),
+            block_on(null_repo().transaction(changes)),
+        );
+    }
+
     #[test]
     fn debug_test() {
         assert_eq!(
@@ -2233,6 +3945,126 @@ mod test {
         assert_eq!(false, repo.data_store_enabled());
     }
 
+    #[test]
+    fn create_campaign_test() {
+        let (mut repo, data_store) = repo_data_store();
+        block_on(repo.init());
+        block_on(repo.modify(Change::Create { thing: penelope() })).unwrap();
+
+        let campaign = block_on(repo.create_campaign("Curse of Strahd")).unwrap();
+        assert_eq!("Curse of Strahd", campaign.name);
+        assert_eq!(&campaign, repo.current_campaign());
+        assert_eq!(
+            vec![Campaign::default(), campaign.clone()],
+            repo.campaigns(),
+        );
+
+        assert_eq!(0, repo.recent().count());
+        assert_eq!(0, repo.undo_history().count());
+        assert_eq!(Ok(Vec::new()), block_on(repo.journal()));
+        assert_eq!(Err(Error::NotFound), block_on(repo.get_by_name("Olympus")));
+
+        assert_eq!(
+            Err(Error::NameAlreadyExists),
+            block_on(repo.create_campaign("DEFAULT")),
+        );
+        assert_eq!(Err(Error::MissingName), block_on(repo.create_campaign(" ")));
+
+        // The registry itself lives in the default namespace.
+        assert_eq!(
+            Some(&format!("\tDefault\n{}", campaign)),
+            data_store.key_values.borrow().get(CAMPAIGNS_KEY),
+        );
+    }
+
+    #[test]
+    fn switch_campaign_test() {
+        let (mut repo, data_store) = repo_data_store();
+        block_on(repo.init());
+
+        let campaign = block_on(repo.create_campaign("Curse of Strahd")).unwrap();
+        block_on(repo.modify(Change::CreateAndSave { thing: penelope() })).unwrap();
+        block_on(repo.modify(Change::SetKeyValue {
+            key_value: KeyValue::Time(Some(Time::try_new(2, 0, 0, 0).unwrap())),
+        }))
+        .unwrap();
+
+        assert_eq!(
+            Ok(Campaign::default()),
+            block_on(repo.switch_campaign("default")),
+        );
+        assert!(block_on(repo.get_by_name("Olympus")).is_ok());
+        assert_eq!(Err(Error::NotFound), block_on(repo.get_by_name("Penelope")));
+        assert_eq!(
+            Ok(KeyValue::Time(None)),
+            block_on(repo.get_key_value(&KeyValue::Time(None))),
+        );
+
+        assert_eq!(
+            Err(Error::NotFound),
+            block_on(repo.switch_campaign("Tomb of Annihilation")),
+        );
+
+        // The current campaign is restored when the repository is reinitialized.
+        block_on(repo.switch_campaign("curse of strahd")).unwrap();
+        let mut repo = Repository::new(data_store);
+        block_on(repo.init());
+        assert_eq!(&campaign, repo.current_campaign());
+        assert!(block_on(repo.get_by_name("Penelope")).is_ok());
+        assert_eq!(
+            Ok(KeyValue::Time(Some(Time::try_new(2, 0, 0, 0).unwrap()))),
+            block_on(repo.get_key_value(&KeyValue::Time(None))),
+        );
+    }
+
+    #[test]
+    fn campaign_test_without_registry() {
+        let mut repo = repo();
+        block_on(repo.init());
+
+        assert_eq!(&Campaign::default(), repo.current_campaign());
+        assert_eq!(vec![Campaign::default()], repo.campaigns());
+        assert!(block_on(repo.get_by_name("Olympus")).is_ok());
+    }
+
+    #[test]
+    fn defer_value_test() {
+        let (mut repo, data_store) = empty_repo_data_store();
+
+        block_on(repo.defer_value(TIMELINE_KEY, Some("a".to_string())));
+        assert_eq!(
+            Ok(Some("a".to_string())),
+            block_on(repo.get_deferred_value(TIMELINE_KEY)),
+        );
+        assert_eq!(None, data_store.key_values.borrow().get(TIMELINE_KEY));
+
+        assert_eq!(Ok(()), block_on(repo.flush()));
+        assert_eq!(
+            Some(&"a".to_string()),
+            data_store.key_values.borrow().get(TIMELINE_KEY),
+        );
+
+        for i in 1..WRITE_BEHIND_LEN {
+            block_on(repo.defer_value(HISTORY_KEY, Some(i.to_string())));
+        }
+        assert_eq!(None, data_store.key_values.borrow().get(HISTORY_KEY));
+
+        block_on(repo.defer_value(TIMELINE_KEY, None));
+        assert_eq!(
+            Some(&(WRITE_BEHIND_LEN - 1).to_string()),
+            data_store.key_values.borrow().get(HISTORY_KEY),
+        );
+        assert_eq!(None, data_store.key_values.borrow().get(TIMELINE_KEY));
+    }
+
+    fn penelope() -> Thing {
+        Npc {
+            name: "Penelope".into(),
+            ..Default::default()
+        }
+        .into()
+    }
+
     fn repo() -> Repository {
         repo_data_store().0
     }
@@ -2319,14 +4151,14 @@ mod test {
     }
 
     struct TimeBombDataStore {
-        t_minus: Rc<RefCell<usize>>,
+        t_minus: Shared<usize>,
         data_store: MemoryDataStore,
     }
 
     impl TimeBombDataStore {
         pub fn new(t_minus: usize) -> Self {
             Self {
-                t_minus: Rc::new(t_minus.into()),
+                t_minus: Shared::new(t_minus),
                 data_store: MemoryDataStore::default(),
             }
         }
@@ -2335,13 +4167,14 @@ mod test {
             if *self.t_minus.borrow() == 0 {
                 Err(())
             } else {
-                self.t_minus.replace_with(|&mut i| i - 1);
+                *self.t_minus.borrow_mut() -= 1;
                 Ok(())
             }
         }
     }
 
-    #[async_trait(?Send)]
+    #[cfg_attr(feature = "sync", async_trait)]
+    #[cfg_attr(not(feature = "sync"), async_trait(?Send))]
     impl DataStore for TimeBombDataStore {
         async fn health_check(&self) -> Result<(), ()> {
             if *self.t_minus.borrow() == 0 {
@@ -2404,5 +4237,71 @@ mod test {
             self.tick()?;
             self.data_store.delete_value(key).await
         }
+
+        fn set_namespace(&mut self, namespace: Option<&str>) {
+            self.data_store.set_namespace(namespace);
+        }
+    }
+
+    /// A data store that works normally except that things can't be saved to it.
+    #[derive(Default)]
+    struct UnsavableDataStore {
+        data_store: MemoryDataStore,
+    }
+
+    #[cfg_attr(feature = "sync", async_trait)]
+    #[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+    impl DataStore for UnsavableDataStore {
+        async fn health_check(&self) -> Result<(), ()> {
+            Ok(())
+        }
+
+        async fn delete_thing_by_uuid(&mut self, uuid: &Uuid) -> Result<(), ()> {
+            self.data_store.delete_thing_by_uuid(uuid).await
+        }
+
+        async fn edit_thing(&mut self, thing: &Thing) -> Result<(), ()> {
+            self.data_store.edit_thing(thing).await
+        }
+
+        async fn get_all_the_things(&self) -> Result<Vec<Thing>, ()> {
+            self.data_store.get_all_the_things().await
+        }
+
+        async fn get_thing_by_uuid(&self, uuid: &Uuid) -> Result<Option<Thing>, ()> {
+            self.data_store.get_thing_by_uuid(uuid).await
+        }
+
+        async fn get_thing_by_name(&self, name: &str) -> Result<Option<Thing>, ()> {
+            self.data_store.get_thing_by_name(name).await
+        }
+
+        async fn get_things_by_name_start(
+            &self,
+            name: &str,
+            limit: Option<usize>,
+        ) -> Result<Vec<Thing>, ()> {
+            self.data_store.get_things_by_name_start(name, limit).await
+        }
+
+        async fn save_thing(&mut self, _thing: &Thing) -> Result<(), ()> {
+            Err(())
+        }
+
+        async fn set_value(&mut self, key: &str, value: &str) -> Result<(), ()> {
+            self.data_store.set_value(key, value).await
+        }
+
+        async fn get_value(&self, key: &str) -> Result<Option<String>, ()> {
+            self.data_store.get_value(key).await
+        }
+
+        async fn delete_value(&mut self, key: &str) -> Result<(), ()> {
+            self.data_store.delete_value(key).await
+        }
+
+        fn set_namespace(&mut self, namespace: Option<&str>) {
+            self.data_store.set_namespace(namespace);
+        }
     }
 }
@@ -0,0 +1,367 @@
+//! A summary of the world as recorded in the journal, shown by `stats world`. It counts what has
+//! been created and points out gaps, such as places without descriptions or characters located
+//! in places that no longer exist.
+
+use crate::utils::CaseInsensitiveStr;
+use crate::world::{list, Demographics, LinkView, Thing};
+use crate::Uuid;
+use std::collections::HashMap;
+use std::fmt;
+
+pub struct WorldStats<'a> {
+    things: Vec<&'a Thing>,
+    journal: &'a [Thing],
+    demographics: &'a Demographics,
+}
+
+impl<'a> WorldStats<'a> {
+    /// Summarize the journal. Archived entries aren't counted, but can still be the location of
+    /// other entries.
+    pub fn new(journal: &'a [Thing], demographics: &'a Demographics) -> Self {
+        let mut things: Vec<&Thing> = journal.iter().filter(|t| !t.is_archived()).collect();
+        things.sort_by(|a, b| a.name().to_string().cmp_ci(b.name().to_string()));
+
+        Self {
+            things,
+            journal,
+            demographics,
+        }
+    }
+
+    /// The things located in the place, directly or within other places there.
+    fn residents(&self, uuid: &Uuid) -> impl Iterator<Item = &&'a Thing> {
+        let uuid = *uuid;
+
+        self.things.iter().filter(move |thing| {
+            let mut location = thing.location_uuid();
+
+            // The depth is limited in case locations somehow form a loop.
+            for _ in 0..10 {
+                match location {
+                    Some(location_uuid) if location_uuid == &uuid => return true,
+                    Some(location_uuid) => {
+                        location = self
+                            .journal
+                            .iter()
+                            .find(|t| t.uuid() == Some(location_uuid))
+                            .and_then(|t| t.location_uuid());
+                    }
+                    None => return false,
+                }
+            }
+
+            false
+        })
+    }
+}
+
+/// Count the values, listing them from most to least common, as in "3 human, 1 elf".
+fn tally(values: impl Iterator<Item = String>) -> String {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for value in values {
+        *counts.entry(value).or_default() += 1;
+    }
+
+    let mut counts: Vec<(String, usize)> = counts.into_iter().collect();
+    counts.sort_by(|(a_value, a), (b_value, b)| b.cmp(a).then_with(|| a_value.cmp(b_value)));
+
+    counts
+        .into_iter()
+        .map(|(value, count)| format!("{} {}", count, value))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn percent(share: f64) -> String {
+    format!("{}%", (share * 100.).round())
+}
+
+impl<'a> fmt::Display for WorldStats<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let npcs: Vec<_> = self
+            .things
+            .iter()
+            .filter_map(|thing| match thing {
+                Thing::Npc(npc) => Some(npc),
+                Thing::Place(_) => None,
+            })
+            .collect();
+        let places: Vec<_> = self
+            .things
+            .iter()
+            .filter_map(|thing| match thing {
+                Thing::Place(place) => Some(place),
+                Thing::Npc(_) => None,
+            })
+            .collect();
+
+        write!(f, "# World statistics")?;
+
+        if self.things.is_empty() {
+            return write!(
+                f,
+                "\n\n*Your journal is empty. Statistics will appear here once you've saved some characters and places.*",
+            );
+        }
+
+        write!(
+            f,
+            "\n\n*{} journal {}: {} {} and {} {}.*",
+            self.things.len(),
+            if self.things.len() == 1 {
+                "entry"
+            } else {
+                "entries"
+            },
+            npcs.len(),
+            if npcs.len() == 1 {
+                "character"
+            } else {
+                "characters"
+            },
+            places.len(),
+            if places.len() == 1 { "place" } else { "places" },
+        )?;
+
+        if !npcs.is_empty() {
+            write!(f, "\n\n## Characters")?;
+
+            for (i, (title, values)) in [
+                (
+                    "Species",
+                    tally(npcs.iter().filter_map(|npc| npc.display_field("species"))),
+                ),
+                (
+                    "Gender",
+                    tally(
+                        npcs.iter()
+                            .filter_map(|npc| npc.gender.value())
+                            .map(|g| g.to_string()),
+                    ),
+                ),
+                (
+                    "Age",
+                    tally(
+                        npcs.iter()
+                            .filter_map(|npc| npc.age.value())
+                            .map(|a| a.to_string()),
+                    ),
+                ),
+            ]
+            .iter()
+            .filter(|(_, values)| !values.is_empty())
+            .enumerate()
+            {
+                write!(
+                    f,
+                    "{}**{}:** {}",
+                    if i == 0 { "\n\n" } else { "\\\n" },
+                    title,
+                    values,
+                )?;
+            }
+        }
+
+        if !places.is_empty() {
+            write!(
+                f,
+                "\n\n## Places\n\n**Type:** {}",
+                tally(
+                    places
+                        .iter()
+                        .map(|place| place.display_description().to_string())
+                ),
+            )?;
+        }
+
+        let settlements: Vec<_> = places
+            .iter()
+            .filter(|place| place.is_settlement())
+            .collect();
+
+        if !settlements.is_empty() {
+            write!(f, "\n\n## Settlements")?;
+
+            let expected = self.demographics.species_shares();
+            if !expected.is_empty() {
+                write!(
+                    f,
+                    "\n\nYour demographics expect {}.",
+                    expected
+                        .iter()
+                        .take(5)
+                        .map(|(species, share)| format!("{} {}", percent(*share), species))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                )?;
+            }
+
+            writeln!(f)?;
+
+            for place in settlements {
+                let residents: Vec<_> = place
+                    .uuid
+                    .as_ref()
+                    .map(|uuid| {
+                        let uuid: &Uuid = uuid.as_ref();
+                        self.residents(uuid)
+                            .filter_map(|thing| match thing {
+                                Thing::Npc(npc) => Some(npc),
+                                Thing::Place(_) => None,
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+
+                write!(
+                    f,
+                    "\n* {}: {} {}",
+                    LinkView::new(&place.name.to_string()),
+                    residents.len(),
+                    if residents.len() == 1 {
+                        "resident"
+                    } else {
+                        "residents"
+                    },
+                )?;
+
+                let species = tally(
+                    residents
+                        .iter()
+                        .filter_map(|npc| npc.species.value())
+                        .map(|s| s.to_string()),
+                );
+                if !species.is_empty() {
+                    write!(f, " ({})", species)?;
+                }
+            }
+        }
+
+        let orphaned: Vec<String> = self
+            .things
+            .iter()
+            .filter(|thing| {
+                thing.location_uuid().is_some_and(|location_uuid| {
+                    !self.journal.iter().any(|t| t.uuid() == Some(location_uuid))
+                })
+            })
+            .map(|thing| LinkView::new(&thing.name().to_string()).to_string())
+            .collect();
+        let undescribed: Vec<String> = places
+            .iter()
+            .filter(|place| place.description.is_none())
+            .map(|place| LinkView::new(&place.name.to_string()).to_string())
+            .collect();
+
+        write!(f, "\n\n## Gaps")?;
+
+        if orphaned.is_empty() && undescribed.is_empty() {
+            write!(f, "\n\n*No gaps were found.*")?;
+        } else {
+            writeln!(f)?;
+
+            if !orphaned.is_empty() {
+                write!(
+                    f,
+                    "\n* **Missing locations:** {} {} no longer in your journal.",
+                    list(&orphaned),
+                    if orphaned.len() == 1 {
+                        "is located in a place that is"
+                    } else {
+                        "are located in places that are"
+                    },
+                )?;
+            }
+
+            if !undescribed.is_empty() {
+                write!(f, "\n* **Missing descriptions:** {}", list(&undescribed),)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::world::npc::{Age, Ethnicity, Gender, Npc, Species};
+    use crate::world::place::{Place, PlaceType};
+    use crate::world::PlaceUuid;
+
+    #[test]
+    fn display_test() {
+        let [town, inn, ruins] = [1, 2, 3].map(|n| PlaceUuid::from(Uuid::from_u128(n)));
+        let journal: Vec<Thing> = vec![
+            Place {
+                uuid: Some(town.clone()),
+                name: "Dunwich".into(),
+                subtype: "town".parse::<PlaceType>().unwrap().into(),
+                description: "A quiet town.".into(),
+                ..Default::default()
+            }
+            .into(),
+            Place {
+                uuid: Some(inn.clone()),
+                location_uuid: town.clone().into(),
+                name: "The Silver Eel".into(),
+                subtype: "inn".parse::<PlaceType>().unwrap().into(),
+                ..Default::default()
+            }
+            .into(),
+            Npc {
+                name: "Gottfried".into(),
+                species: Species::Human.into(),
+                gender: Gender::Masculine.into(),
+                age: Age::Adult.into(),
+                location_uuid: inn.clone().into(),
+                ..Default::default()
+            }
+            .into(),
+            Npc {
+                name: "Aerendyl".into(),
+                species: Species::Elf.into(),
+                gender: Gender::Feminine.into(),
+                age: Age::Adult.into(),
+                location_uuid: ruins.clone().into(),
+                ..Default::default()
+            }
+            .into(),
+            Npc {
+                name: "Gimli".into(),
+                species: Species::Dwarf.into(),
+                archived: true.into(),
+                location_uuid: town.clone().into(),
+                ..Default::default()
+            }
+            .into(),
+        ];
+        let demographics = Demographics::new(HashMap::from([
+            ((Species::Human, Ethnicity::Human), 3),
+            ((Species::Elf, Ethnicity::Elvish), 1),
+        ]));
+
+        assert_eq!(
+            "# World statistics\n\n\
+            *4 journal entries: 2 characters and 2 places.*\n\n\
+            ## Characters\n\n\
+            **Species:** 1 elf, 1 human\\\n\
+            **Gender:** 1 feminine (she/her), 1 masculine (he/him)\\\n\
+            **Age:** 2 adult\n\n\
+            ## Places\n\n\
+            **Type:** 1 inn, 1 town\n\n\
+            ## Settlements\n\n\
+            Your demographics expect 75% human, 25% elf.\n\n\
+            * {Dunwich}: 1 resident (1 human)\n\n\
+            ## Gaps\n\n\
+            * **Missing locations:** {Aerendyl} is located in a place that is no longer in your journal.\n\
+            * **Missing descriptions:** {The Silver Eel}",
+            WorldStats::new(&journal, &demographics).to_string(),
+        );
+
+        assert_eq!(
+            "# World statistics\n\n*Your journal is empty. Statistics will appear here once you've saved some characters and places.*",
+            WorldStats::new(&[], &demographics).to_string(),
+        );
+    }
+}
@@ -0,0 +1,208 @@
+use crate::config::Config;
+use crate::time::Time;
+use std::fmt;
+use std::str::FromStr;
+
+/// A notable event in the campaign, recorded at the in-game time when it occurred.
+///
+/// Entries are persisted to the key-value store one per line, in the form
+/// `1:08:00:00\tcreate\tcharacter\tGottfried`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TimelineEntry {
+    pub time: Time,
+    pub event: TimelineEvent,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TimelineEvent {
+    Battle { summary: String },
+    Create { name: String, thing_type: String },
+    Delete { name: String, thing_type: String },
+    TimeJump { to: Time },
+}
+
+pub struct TimelineEntryView<'a>(&'a TimelineEntry, &'a Config);
+
+impl TimelineEntry {
+    pub fn display<'a>(&'a self, config: &'a Config) -> TimelineEntryView<'a> {
+        TimelineEntryView(self, config)
+    }
+}
+
+impl fmt::Display for TimelineEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}\t", self.time.display_short())?;
+
+        match &self.event {
+            TimelineEvent::Battle { summary } => write!(f, "battle\t{}", summary),
+            TimelineEvent::Create { name, thing_type } => {
+                write!(f, "create\t{}\t{}", thing_type, name)
+            }
+            TimelineEvent::Delete { name, thing_type } => {
+                write!(f, "delete\t{}\t{}", thing_type, name)
+            }
+            TimelineEvent::TimeJump { to } => write!(f, "time\t{}", to.display_short()),
+        }
+    }
+}
+
+impl FromStr for TimelineEntry {
+    type Err = ();
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        let mut parts = raw.splitn(4, '\t');
+        let time = parts.next().ok_or(())?.parse()?;
+
+        let event = match (parts.next(), parts.next(), parts.next()) {
+            (Some("battle"), Some(summary), None) => TimelineEvent::Battle {
+                summary: summary.to_string(),
+            },
+            (Some("create"), Some(thing_type), Some(name)) => TimelineEvent::Create {
+                name: name.to_string(),
+                thing_type: thing_type.to_string(),
+            },
+            (Some("delete"), Some(thing_type), Some(name)) => TimelineEvent::Delete {
+                name: name.to_string(),
+                thing_type: thing_type.to_string(),
+            },
+            (Some("time"), Some(to), None) => TimelineEvent::TimeJump { to: to.parse()? },
+            _ => return Err(()),
+        };
+
+        Ok(Self { time, event })
+    }
+}
+
+impl<'a> fmt::Display for TimelineEntryView<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (entry, config) = (self.0, self.1);
+
+        write!(f, "**{}:** ", config.display_time(&entry.time))?;
+
+        match &entry.event {
+            TimelineEvent::Battle { summary } => write!(f, "{}", summary),
+            TimelineEvent::Create { name, thing_type } => {
+                write!(f, "created `{}` ({})", name, thing_type)
+            }
+            TimelineEvent::Delete { name, thing_type } => {
+                write!(f, "deleted `{}` ({})", name, thing_type)
+            }
+            TimelineEvent::TimeJump { to } if to < &entry.time => {
+                write!(f, "time rewound to {}", config.display_time(to))
+            }
+            TimelineEvent::TimeJump { to } => {
+                write!(f, "time advanced to {}", config.display_time(to))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip_test() {
+        [
+            entry(TimelineEvent::Battle {
+                summary: "The Iron Legion defeated The Black Host".to_string(),
+            }),
+            entry(TimelineEvent::Create {
+                name: "Gottfried".to_string(),
+                thing_type: "character".to_string(),
+            }),
+            entry(TimelineEvent::Delete {
+                name: "The Prancing\tPony".to_string(),
+                thing_type: "place".to_string(),
+            }),
+            entry(TimelineEvent::TimeJump {
+                to: Time::try_new(2, 0, 0, 0).unwrap(),
+            }),
+        ]
+        .into_iter()
+        .for_each(|entry| {
+            assert_eq!(Ok(entry.clone()), entry.to_string().parse(), "{}", entry);
+        });
+    }
+
+    #[test]
+    fn from_str_test() {
+        assert_eq!(
+            Ok(entry(TimelineEvent::TimeJump {
+                to: Time::try_new(1, 16, 0, 0).unwrap(),
+            })),
+            "1:08:00:00\ttime\t1:16:00:00".parse(),
+        );
+
+        assert_eq!(Err(()), "".parse::<TimelineEntry>());
+        assert_eq!(Err(()), "1:08:00:00".parse::<TimelineEntry>());
+        assert_eq!(
+            Err(()),
+            "1:08:00:00\tcreate\tcharacter".parse::<TimelineEntry>()
+        );
+        assert_eq!(
+            Err(()),
+            "1:08:00:00\tpotato\tjohnson".parse::<TimelineEntry>()
+        );
+        assert_eq!(Err(()), "potato\ttime\t1:16:00:00".parse::<TimelineEntry>());
+    }
+
+    #[test]
+    fn display_test() {
+        let config = Config::default();
+
+        assert_eq!(
+            "**day 1 at 8:00:00 am:** created `Gottfried` (character)",
+            entry(TimelineEvent::Create {
+                name: "Gottfried".to_string(),
+                thing_type: "character".to_string(),
+            })
+            .display(&config)
+            .to_string(),
+        );
+
+        assert_eq!(
+            "**day 1 at 8:00:00 am:** deleted `The Prancing Pony` (place)",
+            entry(TimelineEvent::Delete {
+                name: "The Prancing Pony".to_string(),
+                thing_type: "place".to_string(),
+            })
+            .display(&config)
+            .to_string(),
+        );
+
+        assert_eq!(
+            "**day 1 at 8:00:00 am:** time advanced to day 1 at 4:00:00 pm",
+            entry(TimelineEvent::TimeJump {
+                to: Time::try_new(1, 16, 0, 0).unwrap(),
+            })
+            .display(&config)
+            .to_string(),
+        );
+
+        assert_eq!(
+            "**day 1 at 8:00:00 am:** The Iron Legion defeated The Black Host at `Bree`",
+            entry(TimelineEvent::Battle {
+                summary: "The Iron Legion defeated The Black Host at `Bree`".to_string(),
+            })
+            .display(&config)
+            .to_string(),
+        );
+
+        assert_eq!(
+            "**day 1 at 8:00:00 am:** time rewound to day 1 at 7:00:00 am",
+            entry(TimelineEvent::TimeJump {
+                to: Time::try_new(1, 7, 0, 0).unwrap(),
+            })
+            .display(&config)
+            .to_string(),
+        );
+    }
+
+    fn entry(event: TimelineEvent) -> TimelineEntry {
+        TimelineEntry {
+            time: Time::default(),
+            event,
+        }
+    }
+}
@@ -0,0 +1,453 @@
+use super::{Table, Tables};
+use crate::app::{
+    AppMeta, Autocomplete, AutocompleteSuggestion, CommandError, CommandMatches, ContextAwareParse,
+    Runnable,
+};
+use crate::storage::{Change, KeyValue};
+use crate::utils::CaseInsensitiveStr;
+use async_trait::async_trait;
+use std::fmt;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TableCommand {
+    Add { definition: String },
+    Delete { name: String },
+    Edit { definition: String },
+    List,
+    Roll { name: String },
+    Show { name: String },
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Runnable for TableCommand {
+    async fn run(self, _input: &str, app_meta: &mut AppMeta) -> Result<String, CommandError> {
+        let mut tables = Tables::load(app_meta).await;
+
+        let response = match self {
+            Self::List => {
+                let mut names: Vec<&Table> = tables.iter().collect();
+
+                if names.is_empty() {
+                    return Ok("# Tables\n\n*You haven't defined any tables yet. Use `table add [name]: [entries]` to create one, eg. `table add weather: 1-4 Rain, 5-6 Fog`.*".to_string());
+                }
+
+                names.sort_by(|a, b| a.name.cmp_ci(&b.name));
+
+                let mut output = "# Tables\n".to_string();
+                names.into_iter().for_each(|table| {
+                    output.push_str(&format!("\n* `table {}` (d{})", table.name, table.die()))
+                });
+
+                return Ok(output);
+            }
+            Self::Show { name } => {
+                let table = tables.get(&name).ok_or_else(|| not_found(&name))?;
+
+                let mut output = format!(
+                    "# {}\n\n| d{} | Result |\n|--:|---|",
+                    table.name,
+                    table.die(),
+                );
+
+                for entry in &table.entries {
+                    let range = if entry.range.start() == entry.range.end() {
+                        entry.range.start().to_string()
+                    } else {
+                        format!("{}-{}", entry.range.start(), entry.range.end())
+                    };
+
+                    output.push_str(&format!("\n| {} | {} |", range, entry.text));
+                }
+
+                output.push_str(&format!(
+                    "\n\n_Use `roll on {}` to roll on this table._",
+                    table.name,
+                ));
+
+                return Ok(output);
+            }
+            Self::Roll { name } => {
+                let table = tables.get(&name).ok_or_else(|| not_found(&name))?;
+                let (roll, entry) = table.roll(&mut app_meta.rng);
+
+                return Ok(format!(
+                    "**{}** (rolled {} on a d{}): {}",
+                    table.name,
+                    roll,
+                    table.die(),
+                    entry.text,
+                ));
+            }
+            Self::Add { definition } => {
+                let table = definition
+                    .parse::<Table>()
+                    .map_err(|e| CommandError::parse(e.to_string(), Vec::new()))?;
+
+                if let Some(existing) = tables.get(&table.name) {
+                    return Err(CommandError::name_conflict(
+                        &existing.name,
+                        format!(
+                            "There is already a table named \"{}\". Use `table edit {}: [entries]` to replace it.",
+                            existing.name, existing.name,
+                        ),
+                    ));
+                }
+
+                let response = format!(
+                    "Added the **{name}** table. Use `roll on {name}` to roll on it, or `undo` to reverse this.",
+                    name = table.name,
+                );
+                tables.insert(table);
+                response
+            }
+            Self::Edit { definition } => {
+                let table = definition
+                    .parse::<Table>()
+                    .map_err(|e| CommandError::parse(e.to_string(), Vec::new()))?;
+
+                if tables.get(&table.name).is_none() {
+                    return Err(not_found(&table.name));
+                }
+
+                let response = format!(
+                    "Updated the **{}** table. Use `undo` to reverse this.",
+                    table.name,
+                );
+                tables.insert(table);
+                response
+            }
+            Self::Delete { name } => {
+                let table = tables.remove(&name).ok_or_else(|| not_found(&name))?;
+                format!(
+                    "Deleted the **{}** table. Use `undo` to reverse this.",
+                    table.name,
+                )
+            }
+        };
+
+        app_meta
+            .repository
+            .modify(Change::SetKeyValue {
+                key_value: KeyValue::Tables(Some(tables)),
+            })
+            .await
+            .map(|_| response)
+            .map_err(|_| CommandError::storage("Unable to save the tables."))
+    }
+}
+
+fn not_found(name: &str) -> CommandError {
+    CommandError::unknown_entity(
+        name,
+        format!(
+            "There is no table named \"{}\". Use `tables` to list your tables.",
+            name,
+        ),
+    )
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl ContextAwareParse for TableCommand {
+    async fn parse_input(input: &str, _app_meta: &AppMeta) -> CommandMatches<Self> {
+        let name = |prefix: &str| {
+            input
+                .strip_prefix_ci(prefix)
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string())
+        };
+
+        if input.eq_ci("tables") {
+            CommandMatches::new_canonical(Self::List)
+        } else if let Some(definition) = name("table add ") {
+            CommandMatches::new_canonical(Self::Add { definition })
+        } else if let Some(definition) = name("table edit ") {
+            CommandMatches::new_canonical(Self::Edit { definition })
+        } else if let Some(name) = name("table delete ") {
+            CommandMatches::new_canonical(Self::Delete { name })
+        } else if let Some(name) = name("roll on ") {
+            CommandMatches::new_canonical(Self::Roll { name })
+        } else if let Some(name) = name("table ") {
+            CommandMatches::new_canonical(Self::Show { name })
+        } else {
+            CommandMatches::default()
+        }
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Autocomplete for TableCommand {
+    async fn autocomplete(input: &str, app_meta: &AppMeta) -> Vec<AutocompleteSuggestion> {
+        if input.is_empty() {
+            return Vec::new();
+        }
+
+        let tables = Tables::load(app_meta).await;
+        let mut suggestions: Vec<AutocompleteSuggestion> = [
+            ("tables", "list your tables"),
+            ("table [name]", "show a table"),
+            ("table add [name]: [entries]", "create a table"),
+            ("table edit [name]: [entries]", "replace a table"),
+            ("table delete [name]", "delete a table"),
+            ("roll on [table]", "roll on a table"),
+        ]
+        .into_iter()
+        .filter(|(term, _)| term.starts_with_ci(input))
+        .map(|(term, summary)| AutocompleteSuggestion::new(term, summary))
+        .collect();
+
+        for (prefix, summary) in [
+            ("roll on ", "roll on table"),
+            ("table delete ", "delete table"),
+            ("table ", "show table"),
+        ] {
+            if let Some(partial) = input.strip_prefix_ci(prefix) {
+                tables
+                    .iter()
+                    .filter(|table| table.name.starts_with_ci(partial))
+                    .for_each(|table| {
+                        suggestions.push(AutocompleteSuggestion::new(
+                            format!("{}{}", prefix, table.name),
+                            summary,
+                        ))
+                    });
+                break;
+            }
+        }
+
+        suggestions
+    }
+}
+
+impl fmt::Display for TableCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::Add { definition } => write!(f, "table add {}", definition),
+            Self::Delete { name } => write!(f, "table delete {}", name),
+            Self::Edit { definition } => write!(f, "table edit {}", definition),
+            Self::List => write!(f, "tables"),
+            Self::Roll { name } => write!(f, "roll on {}", name),
+            Self::Show { name } => write!(f, "table {}", name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::assert_autocomplete;
+    use crate::storage::MemoryDataStore;
+    use crate::Event;
+    use tokio_test::block_on;
+
+    #[test]
+    fn parse_input_test() {
+        let app_meta = app_meta();
+
+        assert_eq!(
+            CommandMatches::new_canonical(TableCommand::List),
+            block_on(TableCommand::parse_input("TABLES", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(TableCommand::Add {
+                definition: "rumors: 1-3 The mill is haunted".to_string(),
+            }),
+            block_on(TableCommand::parse_input(
+                "table add rumors: 1-3 The mill is haunted",
+                &app_meta,
+            )),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(TableCommand::Roll {
+                name: "rumors".to_string(),
+            }),
+            block_on(TableCommand::parse_input("Roll On rumors", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::default(),
+            block_on(TableCommand::parse_input("table ", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::default(),
+            block_on(TableCommand::parse_input("roll d20", &app_meta)),
+        );
+    }
+
+    #[test]
+    fn autocomplete_test() {
+        let mut app_meta = app_meta();
+
+        assert_autocomplete(
+            &[
+                ("table [name]", "show a table"),
+                ("table add [name]: [entries]", "create a table"),
+                ("table delete [name]", "delete a table"),
+                ("table edit [name]: [entries]", "replace a table"),
+                ("tables", "list your tables"),
+            ][..],
+            block_on(TableCommand::autocomplete("tab", &app_meta)),
+        );
+
+        block_on(
+            TableCommand::Add {
+                definition: "rumors: The mill is haunted".to_string(),
+            }
+            .run("", &mut app_meta),
+        )
+        .unwrap();
+
+        assert_autocomplete(
+            &[("roll on rumors", "roll on table")][..],
+            block_on(TableCommand::autocomplete("roll on r", &app_meta)),
+        );
+
+        assert_autocomplete(&[][..], block_on(TableCommand::autocomplete("", &app_meta)));
+    }
+
+    #[test]
+    fn display_test() {
+        let app_meta = app_meta();
+
+        [
+            TableCommand::Add {
+                definition: "rumors: 1 The mill is haunted".to_string(),
+            },
+            TableCommand::Delete {
+                name: "rumors".to_string(),
+            },
+            TableCommand::Edit {
+                definition: "rumors: 1 Wolves".to_string(),
+            },
+            TableCommand::List,
+            TableCommand::Roll {
+                name: "rumors".to_string(),
+            },
+            TableCommand::Show {
+                name: "rumors".to_string(),
+            },
+        ]
+        .into_iter()
+        .for_each(|command| {
+            let command_string = command.to_string();
+            assert_ne!("", command_string);
+            assert_eq!(
+                CommandMatches::new_canonical(command),
+                block_on(TableCommand::parse_input(&command_string, &app_meta)),
+                "{}",
+                command_string,
+            );
+        });
+    }
+
+    #[test]
+    fn run_test() {
+        let mut app_meta = app_meta();
+
+        assert_eq!(
+            Ok("Added the **rumors** table. Use `roll on rumors` to roll on it, or `undo` to reverse this.".to_string()),
+            block_on(
+                TableCommand::Add {
+                    definition: "rumors: 1-3 The mill is haunted, 4-6 Wolves".to_string(),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+
+        assert_eq!(
+            Err(CommandError::name_conflict(
+                "rumors",
+                "There is already a table named \"rumors\". Use `table edit rumors: [entries]` to replace it.",
+            )),
+            block_on(
+                TableCommand::Add {
+                    definition: "Rumors: 1 Bandits".to_string(),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+
+        assert_eq!(
+            Ok("# rumors\n\n| d6 | Result |\n|--:|---|\n| 1-3 | The mill is haunted |\n| 4-6 | Wolves |\n\n_Use `roll on rumors` to roll on this table._".to_string()),
+            block_on(
+                TableCommand::Show {
+                    name: "RUMORS".to_string(),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+
+        assert_eq!(
+            Ok("Updated the **rumors** table. Use `undo` to reverse this.".to_string()),
+            block_on(
+                TableCommand::Edit {
+                    definition: "rumors: 1 Bandits".to_string(),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+
+        assert_eq!(
+            Ok("**rumors** (rolled 1 on a d1): Bandits".to_string()),
+            block_on(
+                TableCommand::Roll {
+                    name: "rumors".to_string(),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+
+        assert_eq!(
+            Ok("# Tables\n\n* `table rumors` (d1)".to_string()),
+            block_on(TableCommand::List.run("", &mut app_meta)),
+        );
+
+        assert_eq!(
+            Err(CommandError::parse(
+                "The entries must cover every number in order from 1, but 2 is missing.",
+                Vec::new(),
+            )),
+            block_on(
+                TableCommand::Edit {
+                    definition: "rumors: 1 Bandits, 3 Wolves".to_string(),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+
+        assert_eq!(
+            Ok("Deleted the **rumors** table. Use `undo` to reverse this.".to_string()),
+            block_on(
+                TableCommand::Delete {
+                    name: "rumors".to_string(),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+
+        assert_eq!(
+            Err(CommandError::unknown_entity(
+                "rumors",
+                "There is no table named \"rumors\". Use `tables` to list your tables.",
+            )),
+            block_on(
+                TableCommand::Roll {
+                    name: "rumors".to_string(),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+    }
+
+    fn event_dispatcher(_event: Event) {}
+
+    fn app_meta() -> AppMeta {
+        AppMeta::new(MemoryDataStore::default(), &event_dispatcher)
+    }
+}
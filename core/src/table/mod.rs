@@ -0,0 +1,519 @@
+pub use command::TableCommand;
+
+mod command;
+
+use crate::app::AppMeta;
+use crate::storage::KeyValue;
+use crate::utils::CaseInsensitiveStr;
+use crate::world::npc::Occupation;
+use crate::world::{Npc, Place, Thing, WordRegistry};
+use rand::Rng;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// The user's custom roll tables. Tables are persisted to the key-value store one per line, each
+/// in the same form used to define them, eg. `rumors: 1-3 The mill is haunted, 4-6 Wolves`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Tables(Vec<Table>);
+
+/// A roll table, rolled on using a die with as many sides as the highest value of its entries.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Table {
+    pub name: String,
+    pub entries: Vec<TableEntry>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TableEntry {
+    pub range: RangeInclusive<u32>,
+    pub text: String,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TableError {
+    MissingName,
+    NoEntries,
+    InvalidRange(String),
+    Gap(u32),
+}
+
+impl Tables {
+    /// Load the user's tables from storage, or an empty set if none have been defined or the data
+    /// store is unavailable.
+    pub async fn load(app_meta: &AppMeta) -> Self {
+        app_meta
+            .repository
+            .get_key_value(&KeyValue::Tables(None))
+            .await
+            .ok()
+            .and_then(KeyValue::tables)
+            .unwrap_or_default()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Table> {
+        self.0.iter().find(|table| table.name.eq_ci(name))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Table> {
+        self.0.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Add a table, replacing any existing table with the same name.
+    pub fn insert(&mut self, table: Table) {
+        if let Some(existing) = self.0.iter_mut().find(|t| t.name.eq_ci(&table.name)) {
+            *existing = table;
+        } else {
+            self.0.push(table);
+        }
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<Table> {
+        let index = self.0.iter().position(|table| table.name.eq_ci(name))?;
+        Some(self.0.remove(index))
+    }
+
+    /// Override the built-in generators with the user's tables, where any have been defined. Names
+    /// are drawn from a table named after the type of thing, eg. `inn names` or `elf names`,
+    /// falling back to `place names` or `npc names`. NPC occupations are drawn from `occupations`.
+    ///
+    /// Only unlocked fields are replaced, so anything specified by the user is left untouched.
+    pub fn apply(&self, rng: &mut impl Rng, thing: &mut Thing) {
+        let (name_tables, name) = match thing {
+            Thing::Npc(Npc {
+                name,
+                species,
+                occupation,
+                ..
+            }) => {
+                if let Some(value) = self
+                    .roll_on("occupations", rng)
+                    .and_then(|s| s.parse::<Occupation>().ok())
+                {
+                    occupation.replace(value);
+                }
+
+                (
+                    [
+                        species.value().map(|species| format!("{} names", species)),
+                        Some("npc names".to_string()),
+                    ],
+                    name,
+                )
+            }
+            Thing::Place(Place { name, subtype, .. }) => (
+                [
+                    subtype.value().map(|subtype| format!("{} names", subtype)),
+                    Some("place names".to_string()),
+                ],
+                name,
+            ),
+        };
+
+        if let Some(value) = name_tables
+            .iter()
+            .flatten()
+            .find_map(|table| self.roll_on(table, rng))
+        {
+            name.replace(value.to_string());
+        }
+    }
+
+    /// Words to add to the pools that generators draw from, from tables named after a pool, eg.
+    /// `animal words: Capybara, Axolotl`. Each entry is one word, however many numbers it covers.
+    pub fn words(&self) -> WordRegistry {
+        let mut words = WordRegistry::default();
+
+        for table in &self.0 {
+            if let Some(pool) = table.name.to_lowercase().strip_suffix(" words") {
+                words.add(pool, table.entries.iter().map(|entry| entry.text.as_str()));
+            }
+        }
+
+        words
+    }
+
+    fn roll_on(&self, name: &str, rng: &mut impl Rng) -> Option<&str> {
+        self.get(name).map(|table| table.roll(rng).1.text.as_str())
+    }
+}
+
+impl Table {
+    pub fn die(&self) -> u32 {
+        self.entries.last().map_or(0, |entry| *entry.range.end())
+    }
+
+    /// Roll on the table, returning the number rolled and the corresponding entry.
+    pub fn roll(&self, rng: &mut impl Rng) -> (u32, &TableEntry) {
+        let roll = rng.gen_range(1..=self.die());
+        let entry = self
+            .entries
+            .iter()
+            .find(|entry| entry.range.contains(&roll))
+            .unwrap();
+        (roll, entry)
+    }
+
+    /// Parse the entries of a table, eg. `1-3 The mill is haunted, 4-6 Wolves`. Entries may
+    /// contain commas, since a new entry only begins where a comma is followed by a number. If no
+    /// numbers are given at all, eg. `Wolves, Bandits, Ghosts`, each entry is equally likely.
+    pub fn parse_entries(name: &str, raw: &str) -> Result<Self, TableError> {
+        let name = name.trim();
+
+        if name.is_empty() || name.contains('\n') {
+            return Err(TableError::MissingName);
+        }
+
+        let parts: Vec<&str> = raw
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .collect();
+
+        if parts.is_empty() {
+            return Err(TableError::NoEntries);
+        }
+
+        let entries = if parts.iter().all(|part| split_range(part).is_none()) {
+            parts
+                .iter()
+                .zip(1..)
+                .map(|(text, i)| TableEntry {
+                    range: i..=i,
+                    text: text.to_string(),
+                })
+                .collect()
+        } else {
+            let mut entries: Vec<TableEntry> = Vec::new();
+
+            for part in parts {
+                match (split_range(part), entries.last_mut()) {
+                    (Some((range, text)), _) => entries.push(TableEntry {
+                        range,
+                        text: text.to_string(),
+                    }),
+                    (None, Some(entry)) => {
+                        entry.text.push_str(", ");
+                        entry.text.push_str(part);
+                    }
+                    (None, None) => return Err(TableError::InvalidRange(part.to_string())),
+                }
+            }
+
+            let mut next = 1;
+            for entry in &entries {
+                if entry.range.is_empty() {
+                    return Err(TableError::InvalidRange(entry.to_string()));
+                } else if *entry.range.start() != next {
+                    return Err(TableError::Gap(next));
+                }
+                next = entry
+                    .range
+                    .end()
+                    .checked_add(1)
+                    .ok_or_else(|| TableError::InvalidRange(entry.to_string()))?;
+            }
+
+            entries
+        };
+
+        Ok(Self {
+            name: name.to_string(),
+            entries,
+        })
+    }
+}
+
+/// Split a part such as `1-3 The mill is haunted` into its range and text.
+fn split_range(part: &str) -> Option<(RangeInclusive<u32>, &str)> {
+    let (range, text) = part.split_once(' ')?;
+    let text = text.trim();
+
+    let range = if let Some((start, end)) = range.split_once('-') {
+        start.parse().ok()?..=end.parse().ok()?
+    } else {
+        let value = range.parse().ok()?;
+        value..=value
+    };
+
+    if text.is_empty() {
+        None
+    } else {
+        Some((range, text))
+    }
+}
+
+impl fmt::Display for Tables {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut tables = self.0.iter();
+
+        if let Some(table) = tables.next() {
+            write!(f, "{}", table)?;
+        }
+
+        tables.try_for_each(|table| write!(f, "\n{}", table))
+    }
+}
+
+impl FromStr for Tables {
+    type Err = ();
+
+    /// Lines that can't be parsed are skipped rather than invalidating every table.
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        Ok(Self(
+            raw.lines().filter_map(|line| line.parse().ok()).collect(),
+        ))
+    }
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:", self.name)?;
+
+        for (i, entry) in self.entries.iter().enumerate() {
+            write!(f, "{} {}", if i == 0 { "" } else { "," }, entry)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Table {
+    type Err = TableError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        let (name, entries) = raw.split_once(':').ok_or(TableError::MissingName)?;
+        Self::parse_entries(name, entries)
+    }
+}
+
+impl fmt::Display for TableEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.range.start() == self.range.end() {
+            write!(f, "{} {}", self.range.start(), self.text)
+        } else {
+            write!(
+                f,
+                "{}-{} {}",
+                self.range.start(),
+                self.range.end(),
+                self.text,
+            )
+        }
+    }
+}
+
+impl fmt::Display for TableError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::MissingName => write!(f, "The table must have a name."),
+            Self::NoEntries => write!(f, "The table must have at least one entry."),
+            Self::InvalidRange(part) => {
+                write!(
+                    f,
+                    "\"{}\" doesn't begin with a valid number or range.",
+                    part
+                )
+            }
+            Self::Gap(n) => write!(
+                f,
+                "The entries must cover every number in order from 1, but {} is missing.",
+                n,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::world::npc::Species;
+    use crate::world::place::PlaceType;
+    use crate::world::Field;
+    use rand::prelude::*;
+
+    #[test]
+    fn table_from_str_test() {
+        let table: Table = "rumors: 1-3 The mill is haunted, 4 Wolves, or worse, 5-6 Bandits"
+            .parse()
+            .unwrap();
+
+        assert_eq!("rumors", table.name);
+        assert_eq!(6, table.die());
+        assert_eq!(
+            vec![
+                TableEntry {
+                    range: 1..=3,
+                    text: "The mill is haunted".to_string(),
+                },
+                TableEntry {
+                    range: 4..=4,
+                    text: "Wolves, or worse".to_string(),
+                },
+                TableEntry {
+                    range: 5..=6,
+                    text: "Bandits".to_string(),
+                },
+            ],
+            table.entries,
+        );
+
+        assert_eq!(
+            "rumors: 1-3 The mill is haunted, 4 Wolves, or worse, 5-6 Bandits",
+            table.to_string(),
+        );
+    }
+
+    #[test]
+    fn table_from_str_test_unnumbered() {
+        let table: Table = "weather: Rain, Fog, Sunshine".parse().unwrap();
+        assert_eq!(3, table.die());
+        assert_eq!("weather: 1 Rain, 2 Fog, 3 Sunshine", table.to_string());
+    }
+
+    #[test]
+    fn table_from_str_test_invalid() {
+        assert_eq!(Err(TableError::MissingName), "1-3 Rain".parse::<Table>());
+        assert_eq!(Err(TableError::MissingName), ": 1-3 Rain".parse::<Table>());
+        assert_eq!(Err(TableError::NoEntries), "weather: ,".parse::<Table>());
+        assert_eq!(
+            Err(TableError::Gap(3)),
+            "weather: 1-2 Rain, 4 Fog".parse::<Table>(),
+        );
+        assert_eq!(
+            Err(TableError::Gap(1)),
+            "weather: 2-3 Rain".parse::<Table>(),
+        );
+        assert_eq!(
+            Err(TableError::InvalidRange("Rain".to_string())),
+            "weather: Rain, 2 Fog".parse::<Table>(),
+        );
+        assert_eq!(
+            Err(TableError::InvalidRange("5-3 Rain".to_string())),
+            "weather: 5-3 Rain".parse::<Table>(),
+        );
+        assert_eq!(
+            Err(TableError::InvalidRange("3-2 Fog".to_string())),
+            "weather: 1-2 Rain, 3-2 Fog".parse::<Table>(),
+        );
+        assert_eq!(
+            Err(TableError::InvalidRange("1-4294967295 Rain".to_string())),
+            "weather: 1-4294967295 Rain".parse::<Table>(),
+        );
+    }
+
+    #[test]
+    fn table_roll_test() {
+        let table: Table = "weather: 1-5 Rain, 6 Fog".parse().unwrap();
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut fog = 0;
+
+        for _ in 0..600 {
+            let (roll, entry) = table.roll(&mut rng);
+            assert!(entry.range.contains(&roll));
+
+            if entry.text == "Fog" {
+                fog += 1;
+            }
+        }
+
+        assert!((50..150).contains(&fog), "{}", fog);
+    }
+
+    #[test]
+    fn tables_test() {
+        let mut tables = Tables::default();
+        tables.insert("rumors: 1 The mill is haunted".parse().unwrap());
+        tables.insert("weather: 1 Rain".parse().unwrap());
+        tables.insert("Rumors: 1 Wolves".parse().unwrap());
+
+        assert_eq!("Rumors: 1 Wolves\nweather: 1 Rain", tables.to_string());
+        assert_eq!(Ok(tables.clone()), tables.to_string().parse());
+        assert_eq!(
+            Some("Rumors"),
+            tables.get("RUMORS").map(|t| t.name.as_str())
+        );
+
+        assert!(tables.remove("rumors").is_some());
+        assert!(tables.remove("rumors").is_none());
+        assert_eq!("weather: 1 Rain", tables.to_string());
+    }
+
+    #[test]
+    fn words_test() {
+        let mut tables = Tables::default();
+        tables.insert("rumors: 1 The mill is haunted".parse().unwrap());
+        tables.insert("potato words: Russet".parse().unwrap());
+        assert!(tables.words().is_empty());
+
+        tables.insert("Gem Words: Moonstone, Bloodstone".parse().unwrap());
+
+        let mut expected = WordRegistry::default();
+        expected.add("gem", ["Moonstone", "Bloodstone"]);
+        assert_eq!(expected, tables.words());
+    }
+
+    #[test]
+    fn apply_test() {
+        let mut tables = Tables::default();
+        tables.insert("elf names: Legolas".parse().unwrap());
+        tables.insert("npc names: Bob".parse().unwrap());
+        tables.insert("inn names: The Prancing Pony".parse().unwrap());
+        tables.insert("occupations: cultist".parse().unwrap());
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        let mut elf: Thing = Npc {
+            species: Species::Elf.into(),
+            ..Default::default()
+        }
+        .into();
+        tables.apply(&mut rng, &mut elf);
+        assert_eq!(Some("Legolas"), elf.name().value().map(String::as_str));
+
+        if let Thing::Npc(npc) = &elf {
+            assert_eq!(Some(&Occupation::Cultist), npc.occupation.value());
+            assert!(!npc.name.is_locked());
+        } else {
+            unreachable!();
+        }
+
+        let mut dwarf: Thing = Npc {
+            species: Species::Dwarf.into(),
+            ..Default::default()
+        }
+        .into();
+        tables.apply(&mut rng, &mut dwarf);
+        assert_eq!(Some("Bob"), dwarf.name().value().map(String::as_str));
+
+        let mut gimli: Thing = Npc {
+            name: Field::new("Gimli".to_string()),
+            ..Default::default()
+        }
+        .into();
+        tables.apply(&mut rng, &mut gimli);
+        assert_eq!(Some("Gimli"), gimli.name().value().map(String::as_str));
+
+        let mut inn: Thing = Place {
+            subtype: "inn".parse::<PlaceType>().unwrap().into(),
+            ..Default::default()
+        }
+        .into();
+        tables.apply(&mut rng, &mut inn);
+        assert_eq!(
+            Some("The Prancing Pony"),
+            inn.name().value().map(String::as_str),
+        );
+
+        let mut temple: Thing = Place {
+            subtype: "temple".parse::<PlaceType>().unwrap().into(),
+            ..Default::default()
+        }
+        .into();
+        tables.apply(&mut rng, &mut temple);
+        assert_eq!(None, temple.name().value());
+    }
+}
@@ -0,0 +1,376 @@
+use super::{Template, Templates};
+use crate::app::{
+    AppMeta, Autocomplete, AutocompleteSuggestion, CommandError, CommandMatches, ContextAwareParse,
+    Runnable,
+};
+use crate::storage::{Change, KeyValue};
+use crate::utils::CaseInsensitiveStr;
+use crate::world::{append_unknown_words_notice, ParsedThing, Thing};
+use async_trait::async_trait;
+use std::fmt;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TemplateCommand {
+    Delete { name: String },
+    List,
+    Save { name: String, description: String },
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Runnable for TemplateCommand {
+    async fn run(self, _input: &str, app_meta: &mut AppMeta) -> Result<String, CommandError> {
+        let mut templates = Templates::load(app_meta).await;
+
+        let response = match self {
+            Self::List => {
+                let mut list: Vec<&Template> = templates.iter().collect();
+
+                if list.is_empty() {
+                    return Ok("# Templates\n\n*You haven't saved any templates yet. Use `template save [name] = [description]` to create one, eg. `template save city-guard = human adult guard`.*".to_string());
+                }
+
+                list.sort_by(|a, b| a.name.cmp_ci(&b.name));
+
+                let mut output = "# Templates\n".to_string();
+                list.into_iter().for_each(|template| {
+                    output.push_str(&format!(
+                        "\n* `create {}`: {}",
+                        template.name, template.description,
+                    ))
+                });
+
+                return Ok(output);
+            }
+            Self::Save { name, description } => {
+                let template = Template::new(&name, &description)
+                    .map_err(|e| CommandError::parse(e.to_string(), Vec::new()))?;
+                let unknown_words = template
+                    .description
+                    .parse::<ParsedThing<Thing>>()
+                    .map(|parsed| parsed.unknown_words)
+                    .unwrap_or_default();
+
+                let response = format!(
+                    "{} the **{name}** template. Use `create {name}` to use it, or `undo` to reverse this.",
+                    if templates.get(&template.name).is_some() {
+                        "Updated"
+                    } else {
+                        "Saved"
+                    },
+                    name = template.name,
+                );
+                let response =
+                    append_unknown_words_notice(response, &template.description, unknown_words);
+
+                templates.insert(template);
+                response
+            }
+            Self::Delete { name } => {
+                let template = templates.remove(&name).ok_or_else(|| {
+                    CommandError::unknown_entity(
+                        &name,
+                        format!(
+                            "There is no template named \"{}\". Use `templates` to list your templates.",
+                            name,
+                        ),
+                    )
+                })?;
+
+                format!(
+                    "Deleted the **{}** template. Use `undo` to reverse this.",
+                    template.name,
+                )
+            }
+        };
+
+        app_meta
+            .repository
+            .modify(Change::SetKeyValue {
+                key_value: KeyValue::Templates(Some(templates)),
+            })
+            .await
+            .map(|_| response)
+            .map_err(|_| CommandError::storage("Unable to save the templates."))
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl ContextAwareParse for TemplateCommand {
+    async fn parse_input(input: &str, _app_meta: &AppMeta) -> CommandMatches<Self> {
+        if input.eq_ci("templates") {
+            CommandMatches::new_canonical(Self::List)
+        } else if let Some((name, description)) = input
+            .strip_prefix_ci("template save ")
+            .and_then(|s| s.split_once('='))
+            .map(|(name, description)| (name.trim(), description.trim()))
+            .filter(|(name, description)| !name.is_empty() && !description.is_empty())
+        {
+            CommandMatches::new_canonical(Self::Save {
+                name: name.to_string(),
+                description: description.to_string(),
+            })
+        } else if let Some(name) = input
+            .strip_prefix_ci("template delete ")
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+        {
+            CommandMatches::new_canonical(Self::Delete {
+                name: name.to_string(),
+            })
+        } else {
+            CommandMatches::default()
+        }
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Autocomplete for TemplateCommand {
+    async fn autocomplete(input: &str, app_meta: &AppMeta) -> Vec<AutocompleteSuggestion> {
+        if input.is_empty() {
+            return Vec::new();
+        }
+
+        let templates = Templates::load(app_meta).await;
+        let mut suggestions: Vec<AutocompleteSuggestion> = [
+            ("templates", "list your templates"),
+            ("template save [name] = [description]", "save a template"),
+            ("template delete [name]", "delete a template"),
+        ]
+        .into_iter()
+        .filter(|(term, _)| term.starts_with_ci(input))
+        .map(|(term, summary)| AutocompleteSuggestion::new(term, summary))
+        .collect();
+
+        for (prefix, summary) in [
+            ("template delete ", "delete template"),
+            ("create ", "create from template"),
+            ("", "create from template"),
+        ] {
+            if let Some(partial) = input.strip_prefix_ci(prefix) {
+                templates
+                    .iter()
+                    .filter(|template| template.name.starts_with_ci(partial))
+                    .for_each(|template| {
+                        suggestions.push(AutocompleteSuggestion::new(
+                            format!("{}{}", prefix, template.name),
+                            summary,
+                        ))
+                    });
+                break;
+            }
+        }
+
+        suggestions
+    }
+}
+
+impl fmt::Display for TemplateCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::Delete { name } => write!(f, "template delete {}", name),
+            Self::List => write!(f, "templates"),
+            Self::Save { name, description } => {
+                write!(f, "template save {} = {}", name, description)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::assert_autocomplete;
+    use crate::storage::MemoryDataStore;
+    use crate::Event;
+    use tokio_test::block_on;
+
+    #[test]
+    fn parse_input_test() {
+        let app_meta = app_meta();
+
+        assert_eq!(
+            CommandMatches::new_canonical(TemplateCommand::List),
+            block_on(TemplateCommand::parse_input("TEMPLATES", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(TemplateCommand::Save {
+                name: "city-guard".to_string(),
+                description: "human adult guard".to_string(),
+            }),
+            block_on(TemplateCommand::parse_input(
+                "template save city-guard=human adult guard",
+                &app_meta,
+            )),
+        );
+
+        assert_eq!(
+            CommandMatches::default(),
+            block_on(TemplateCommand::parse_input(
+                "template save city-guard = ",
+                &app_meta,
+            )),
+        );
+
+        assert_eq!(
+            CommandMatches::default(),
+            block_on(TemplateCommand::parse_input("template delete ", &app_meta)),
+        );
+    }
+
+    #[test]
+    fn autocomplete_test() {
+        let mut app_meta = app_meta();
+
+        assert_autocomplete(
+            &[
+                ("template delete [name]", "delete a template"),
+                ("template save [name] = [description]", "save a template"),
+                ("templates", "list your templates"),
+            ][..],
+            block_on(TemplateCommand::autocomplete("temp", &app_meta)),
+        );
+
+        block_on(
+            TemplateCommand::Save {
+                name: "city-guard".to_string(),
+                description: "human adult guard".to_string(),
+            }
+            .run("", &mut app_meta),
+        )
+        .unwrap();
+
+        assert_autocomplete(
+            &[("city-guard", "create from template")][..],
+            block_on(TemplateCommand::autocomplete("CIT", &app_meta)),
+        );
+
+        assert_autocomplete(
+            &[("create city-guard", "create from template")][..],
+            block_on(TemplateCommand::autocomplete("create c", &app_meta)),
+        );
+
+        assert_autocomplete(
+            &[
+                ("template delete [name]", "delete a template"),
+                ("template delete city-guard", "delete template"),
+            ][..],
+            block_on(TemplateCommand::autocomplete("template delete ", &app_meta)),
+        );
+
+        assert_autocomplete(
+            &[][..],
+            block_on(TemplateCommand::autocomplete("", &app_meta)),
+        );
+    }
+
+    #[test]
+    fn display_test() {
+        let app_meta = app_meta();
+
+        [
+            TemplateCommand::Delete {
+                name: "city-guard".to_string(),
+            },
+            TemplateCommand::List,
+            TemplateCommand::Save {
+                name: "city-guard".to_string(),
+                description: "human adult guard".to_string(),
+            },
+        ]
+        .into_iter()
+        .for_each(|command| {
+            let command_string = command.to_string();
+            assert_ne!("", command_string);
+            assert_eq!(
+                CommandMatches::new_canonical(command),
+                block_on(TemplateCommand::parse_input(&command_string, &app_meta)),
+                "{}",
+                command_string,
+            );
+        });
+    }
+
+    #[test]
+    fn run_test() {
+        let mut app_meta = app_meta();
+
+        assert_eq!(
+            Ok("# Templates\n\n*You haven't saved any templates yet. Use `template save [name] = [description]` to create one, eg. `template save city-guard = human adult guard`.*".to_string()),
+            block_on(TemplateCommand::List.run("", &mut app_meta)),
+        );
+
+        assert_eq!(
+            Ok("Saved the **city-guard** template. Use `create city-guard` to use it, or `undo` to reverse this.".to_string()),
+            block_on(
+                TemplateCommand::Save {
+                    name: "city-guard".to_string(),
+                    description: "human adult guard".to_string(),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+
+        assert_eq!(
+            Ok("Updated the **City-Guard** template. Use `create City-Guard` to use it, or `undo` to reverse this.".to_string()),
+            block_on(
+                TemplateCommand::Save {
+                    name: "City-Guard".to_string(),
+                    description: "elf adult guard".to_string(),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+
+        assert_eq!(
+            Err(CommandError::parse(
+                "\"potato\" isn't a description of a character or place.",
+                Vec::new(),
+            )),
+            block_on(
+                TemplateCommand::Save {
+                    name: "spud".to_string(),
+                    description: "potato".to_string(),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+
+        assert_eq!(
+            Ok("# Templates\n\n* `create City-Guard`: elf adult guard".to_string()),
+            block_on(TemplateCommand::List.run("", &mut app_meta)),
+        );
+
+        assert_eq!(
+            Ok("Deleted the **City-Guard** template. Use `undo` to reverse this.".to_string()),
+            block_on(
+                TemplateCommand::Delete {
+                    name: "city-guard".to_string(),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+
+        assert_eq!(
+            Err(CommandError::unknown_entity(
+                "city-guard",
+                "There is no template named \"city-guard\". Use `templates` to list your templates.",
+            )),
+            block_on(
+                TemplateCommand::Delete {
+                    name: "city-guard".to_string(),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+    }
+
+    fn event_dispatcher(_event: Event) {}
+
+    fn app_meta() -> AppMeta {
+        AppMeta::new(MemoryDataStore::default(), &event_dispatcher)
+    }
+}
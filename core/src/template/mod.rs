@@ -0,0 +1,254 @@
+pub use command::TemplateCommand;
+
+mod command;
+
+use crate::app::AppMeta;
+use crate::storage::KeyValue;
+use crate::utils::CaseInsensitiveStr;
+use crate::world::{ParsedThing, Thing};
+use std::fmt;
+use std::str::FromStr;
+
+/// The user's saved generation templates. Templates are persisted to the key-value store one per
+/// line, each in the same form used to define them, eg. `city-guard = human adult guard`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Templates(Vec<Template>);
+
+/// A partial description of a character or place, instantiated using `create [name]`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Template {
+    pub name: String,
+    pub description: String,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TemplateError {
+    MissingName,
+    InvalidDescription(String),
+}
+
+impl Templates {
+    /// Load the user's templates from storage, or an empty set if none have been defined or the
+    /// data store is unavailable.
+    pub async fn load(app_meta: &AppMeta) -> Self {
+        app_meta
+            .repository
+            .get_key_value(&KeyValue::Templates(None))
+            .await
+            .ok()
+            .and_then(KeyValue::templates)
+            .unwrap_or_default()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Template> {
+        self.0.iter().find(|template| template.name.eq_ci(name))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Template> {
+        self.0.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Add a template, replacing any existing template with the same name.
+    pub fn insert(&mut self, template: Template) {
+        if let Some(existing) = self.0.iter_mut().find(|t| t.name.eq_ci(&template.name)) {
+            *existing = template;
+        } else {
+            self.0.push(template);
+        }
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<Template> {
+        let index = self
+            .0
+            .iter()
+            .position(|template| template.name.eq_ci(name))?;
+        Some(self.0.remove(index))
+    }
+
+    /// Find the template whose name begins the input, eg. `city-guard named Bob`, returning it
+    /// along with the remainder of the input. The longest matching name wins.
+    pub fn find_prefix<'a>(&self, input: &'a str) -> Option<(&Template, &'a str)> {
+        self.0
+            .iter()
+            .filter(|template| input.starts_with_ci(&template.name))
+            .filter_map(|template| {
+                input
+                    .get(template.name.len()..)
+                    .filter(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+                    .map(|rest| (template, rest))
+            })
+            .max_by_key(|(template, _)| template.name.len())
+    }
+}
+
+impl Template {
+    pub fn new(name: &str, description: &str) -> Result<Self, TemplateError> {
+        let (name, description) = (name.trim(), description.trim());
+
+        if name.is_empty() || name.contains(&['=', '\n'][..]) {
+            Err(TemplateError::MissingName)
+        } else if description.contains('\n') || description.parse::<ParsedThing<Thing>>().is_err() {
+            Err(TemplateError::InvalidDescription(description.to_string()))
+        } else {
+            Ok(Self {
+                name: name.to_string(),
+                description: description.to_string(),
+            })
+        }
+    }
+
+    /// Parse the template followed by any further description, eg. ` named Bob`. Unknown words
+    /// are only reported for the further description, as the template's own were reported when
+    /// it was saved. Their ranges are relative to the further description.
+    pub fn instantiate(&self, rest: &str) -> Result<ParsedThing<Thing>, ()> {
+        let offset = self.description.len();
+        let mut parsed: ParsedThing<Thing> = format!("{}{}", self.description, rest).parse()?;
+
+        parsed.unknown_words = parsed
+            .unknown_words
+            .into_iter()
+            .filter(|range| range.start >= offset)
+            .map(|range| range.start - offset..range.end - offset)
+            .collect();
+
+        Ok(parsed)
+    }
+}
+
+impl fmt::Display for Templates {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut templates = self.0.iter();
+
+        if let Some(template) = templates.next() {
+            write!(f, "{}", template)?;
+        }
+
+        templates.try_for_each(|template| write!(f, "\n{}", template))
+    }
+}
+
+impl FromStr for Templates {
+    type Err = ();
+
+    /// Lines that can't be parsed are skipped rather than invalidating every template.
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        Ok(Self(
+            raw.lines().filter_map(|line| line.parse().ok()).collect(),
+        ))
+    }
+}
+
+impl fmt::Display for Template {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} = {}", self.name, self.description)
+    }
+}
+
+impl FromStr for Template {
+    type Err = TemplateError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        let (name, description) = raw.split_once('=').ok_or(TemplateError::MissingName)?;
+        Self::new(name, description)
+    }
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::MissingName => write!(f, "The template must have a name."),
+            Self::InvalidDescription(description) => write!(
+                f,
+                "\"{}\" isn't a description of a character or place.",
+                description,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::world::npc::{Age, Species};
+    use crate::world::Npc;
+
+    #[test]
+    fn template_from_str_test() {
+        let template: Template = "city-guard = human adult guard".parse().unwrap();
+        assert_eq!("city-guard", template.name);
+        assert_eq!("human adult guard", template.description);
+        assert_eq!("city-guard = human adult guard", template.to_string());
+
+        assert_eq!(
+            Err(TemplateError::MissingName),
+            "human adult guard".parse::<Template>(),
+        );
+        assert_eq!(
+            Err(TemplateError::MissingName),
+            " = human adult guard".parse::<Template>(),
+        );
+        assert_eq!(
+            Err(TemplateError::InvalidDescription("potato".to_string())),
+            "spud = potato".parse::<Template>(),
+        );
+    }
+
+    #[test]
+    fn instantiate_test() {
+        let template: Template = "city-guard = human adult potato".parse().unwrap();
+
+        let parsed = template.instantiate(" named Bob").unwrap();
+        assert!(parsed.unknown_words.is_empty());
+
+        if let Thing::Npc(Npc {
+            name, species, age, ..
+        }) = parsed.thing
+        {
+            assert_eq!(Some("Bob"), name.value().map(String::as_str));
+            assert_eq!(Some(&Species::Human), species.value());
+            assert_eq!(Some(&Age::Adult), age.value());
+        } else {
+            panic!("{:?}", parsed.thing);
+        }
+
+        let parsed = template.instantiate(" elderly banana").unwrap();
+        assert_eq!(vec![9..15], parsed.unknown_words);
+    }
+
+    #[test]
+    fn templates_test() {
+        let mut templates = Templates::default();
+        templates.insert("city-guard = human adult".parse().unwrap());
+        templates.insert("city = town".parse().unwrap());
+        templates.insert("City-Guard = elf adult".parse().unwrap());
+
+        assert_eq!("City-Guard = elf adult\ncity = town", templates.to_string(),);
+        assert_eq!(Ok(templates.clone()), templates.to_string().parse());
+        assert_eq!(
+            Some("City-Guard"),
+            templates.get("CITY-GUARD").map(|t| t.name.as_str()),
+        );
+
+        assert_eq!(
+            Some(("City-Guard", " named Bob")),
+            templates
+                .find_prefix("city-guard named Bob")
+                .map(|(t, rest)| (t.name.as_str(), rest)),
+        );
+        assert_eq!(
+            Some(("city", "")),
+            templates
+                .find_prefix("City")
+                .map(|(t, rest)| (t.name.as_str(), rest)),
+        );
+        assert!(templates.find_prefix("citys").is_none());
+
+        assert!(templates.remove("city-guard").is_some());
+        assert!(templates.remove("city-guard").is_none());
+        assert_eq!("city = town", templates.to_string());
+    }
+}
@@ -0,0 +1,429 @@
+use super::{Status, Thread, Threads};
+use crate::app::{
+    AppMeta, Autocomplete, AutocompleteSuggestion, CommandError, CommandMatches, ContextAwareParse,
+    Runnable,
+};
+use crate::session::describe_duration;
+use crate::storage::{Change, KeyValue};
+use crate::utils::{split_at_last_word, CaseInsensitiveStr};
+use crate::world::list;
+use async_trait::async_trait;
+use std::fmt;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ThreadCommand {
+    Add { title: String },
+    Involve { thread: String, name: String },
+    SetStatus { thread: String, status: Status },
+    Threads,
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Runnable for ThreadCommand {
+    async fn run(self, _input: &str, app_meta: &mut AppMeta) -> Result<String, CommandError> {
+        let mut threads = Threads::load(app_meta).await;
+        let now = app_meta.now();
+
+        let response = match self {
+            Self::Threads => return Ok(dashboard(&threads, app_meta).await),
+            Self::Add { title } => {
+                if let Some(existing) = threads.iter().find(|thread| thread.title.eq_ci(&title)) {
+                    return Err(CommandError::name_conflict(
+                        &existing.title,
+                        format!("There is already a thread called \"{}\".", existing.title),
+                    ));
+                }
+
+                let response = format!(
+                    "Added the thread **{}**. Use `thread {} involves [name]` to link it to your `journal`, or `undo` to reverse this.",
+                    title, title,
+                );
+                threads.add(Thread::new(title, now));
+                response
+            }
+            Self::Involve { thread, name } => {
+                let thing = app_meta.repository.get_by_name(&name).await.map_err(|_| {
+                    CommandError::unknown_entity(&name, format!(r#"No matches for "{}""#, name))
+                })?;
+
+                let uuid = *thing.uuid().ok_or_else(|| {
+                    format!(
+                        "{} hasn't been saved to your `journal` yet. Use `save {}` first.",
+                        thing.name(),
+                        thing.name(),
+                    )
+                })?;
+
+                let thread = threads
+                    .touch(&thread, now)
+                    .ok_or_else(|| not_found(&thread))?;
+
+                if thread.involves.contains(&uuid) {
+                    return Err(CommandError::invalid_state(format!(
+                        "`{}` is already involved in **{}**.",
+                        thing.name(),
+                        thread.title,
+                    )));
+                }
+
+                thread.involves.push(uuid);
+                format!(
+                    "`{}` is now involved in **{}**. Use `undo` to reverse this.",
+                    thing.name(),
+                    thread.title,
+                )
+            }
+            Self::SetStatus { thread, status } => {
+                let thread = threads
+                    .touch(&thread, now)
+                    .ok_or_else(|| not_found(&thread))?;
+                thread.status = status;
+
+                format!(
+                    "**{}** is now {}. Use `undo` to reverse this.",
+                    thread.title, status,
+                )
+            }
+        };
+
+        app_meta
+            .repository
+            .modify(Change::SetKeyValue {
+                key_value: KeyValue::Threads(Some(threads)),
+            })
+            .await
+            .map(|_| response)
+            .map_err(|_| CommandError::storage("Unable to save the threads."))
+    }
+}
+
+/// List the threads, most recently touched first, with their status and who and what is involved.
+async fn dashboard(threads: &Threads, app_meta: &AppMeta) -> String {
+    if threads.is_empty() {
+        return "# Threads\n\n*There are no plot threads yet. Use `thread add [description]` to start one.*".to_string();
+    }
+
+    let now = app_meta.now();
+    let mut output = "# Threads\n".to_string();
+
+    for thread in threads.iter() {
+        output.push_str(&format!("\n* **{}** ({})", thread.title, thread.status));
+
+        let mut involved = Vec::new();
+        for uuid in &thread.involves {
+            if let Ok(thing) = app_meta.repository.get_by_uuid(uuid).await {
+                involved.push(format!("`{}`", thing.name()));
+            }
+        }
+
+        if !involved.is_empty() {
+            output.push_str(&format!(", involving {}", list(&involved)));
+        }
+
+        output.push_str(&format!(
+            ", _touched {} ago_",
+            describe_duration(now.saturating_sub(thread.touched_at)),
+        ));
+    }
+
+    output
+}
+
+fn not_found(title: &str) -> CommandError {
+    CommandError::unknown_entity(
+        title,
+        format!(
+            "There is no thread called \"{}\". Use `threads` to list your threads.",
+            title,
+        ),
+    )
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl ContextAwareParse for ThreadCommand {
+    async fn parse_input(input: &str, _app_meta: &AppMeta) -> CommandMatches<Self> {
+        if input.eq_ci("threads") {
+            return CommandMatches::new_canonical(Self::Threads);
+        }
+
+        let rest = match input
+            .strip_prefix_ci("thread ")
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+        {
+            Some(rest) => rest,
+            None => return CommandMatches::default(),
+        };
+
+        if let Some(title) = rest
+            .strip_prefix_ci("add ")
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+        {
+            CommandMatches::new_canonical(Self::Add {
+                title: title.to_string(),
+            })
+        } else if let Some((thread, status)) = split_at_last_word(rest, "is")
+            .and_then(|(thread, status)| Some((thread, status.parse::<Status>().ok()?)))
+        {
+            CommandMatches::new_canonical(Self::SetStatus {
+                thread: thread.to_string(),
+                status,
+            })
+        } else if let Some((thread, name)) = split_at_last_word(rest, "involves") {
+            CommandMatches::new_canonical(Self::Involve {
+                thread: thread.to_string(),
+                name: name.to_string(),
+            })
+        } else {
+            CommandMatches::default()
+        }
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Autocomplete for ThreadCommand {
+    async fn autocomplete(input: &str, app_meta: &AppMeta) -> Vec<AutocompleteSuggestion> {
+        if input.is_empty() {
+            return Vec::new();
+        }
+
+        let mut suggestions: Vec<AutocompleteSuggestion> = [
+            ("threads", "list the plot threads"),
+            ("thread add [description]", "start a plot thread"),
+            (
+                "thread [title] involves [name]",
+                "link a plot thread to your journal",
+            ),
+            (
+                "thread [title] is [open/advancing/resolved]",
+                "change the status of a plot thread",
+            ),
+        ]
+        .into_iter()
+        .filter(|(term, _)| term.starts_with_ci(input))
+        .map(|(term, summary)| AutocompleteSuggestion::new(term, summary))
+        .collect();
+
+        if let Some(partial) = input.strip_prefix_ci("thread ") {
+            Threads::load(app_meta)
+                .await
+                .iter()
+                .filter(|thread| thread.title.starts_with_ci(partial))
+                .for_each(|thread| {
+                    suggestions.push(AutocompleteSuggestion::new(
+                        format!("thread {} is [status]", thread.title),
+                        "change the status of the thread",
+                    ))
+                });
+        }
+
+        suggestions
+    }
+}
+
+impl fmt::Display for ThreadCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::Add { title } => write!(f, "thread add {}", title),
+            Self::Involve { thread, name } => write!(f, "thread {} involves {}", thread, name),
+            Self::SetStatus { thread, status } => write!(f, "thread {} is {}", thread, status),
+            Self::Threads => write!(f, "threads"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::assert_autocomplete;
+    use crate::storage::MemoryDataStore;
+    use crate::world::Npc;
+    use crate::Event;
+    use tokio_test::block_on;
+
+    #[test]
+    fn parse_input_test() {
+        let app_meta = app_meta();
+
+        assert_eq!(
+            CommandMatches::new_canonical(ThreadCommand::Add {
+                title: "The cult is infiltrating the guard".to_string(),
+            }),
+            block_on(ThreadCommand::parse_input(
+                "THREAD ADD The cult is infiltrating the guard",
+                &app_meta,
+            )),
+        );
+        assert_eq!(
+            CommandMatches::new_canonical(ThreadCommand::SetStatus {
+                thread: "The cult is infiltrating the guard".to_string(),
+                status: Status::Advancing,
+            }),
+            block_on(ThreadCommand::parse_input(
+                "thread The cult is infiltrating the guard is advancing",
+                &app_meta,
+            )),
+        );
+        assert_eq!(
+            CommandMatches::new_canonical(ThreadCommand::Involve {
+                thread: "the cult".to_string(),
+                name: "Captain Vell".to_string(),
+            }),
+            block_on(ThreadCommand::parse_input(
+                "thread the cult involves Captain Vell",
+                &app_meta,
+            )),
+        );
+
+        [
+            "thread",
+            "thread add",
+            "thread the cult is",
+            "thread the cult is infiltrating",
+            "thread involves Gimli",
+            "threadbare",
+        ]
+        .into_iter()
+        .for_each(|input| {
+            assert_eq!(
+                CommandMatches::default(),
+                block_on(ThreadCommand::parse_input(input, &app_meta)),
+                "{}",
+                input,
+            );
+        });
+    }
+
+    #[test]
+    fn autocomplete_test() {
+        let mut app_meta = app_meta();
+        block_on(
+            ThreadCommand::Add {
+                title: "The cult is infiltrating the guard".to_string(),
+            }
+            .run("", &mut app_meta),
+        )
+        .unwrap();
+
+        assert_autocomplete(
+            &[
+                ("thread add [description]", "start a plot thread"),
+                (
+                    "thread [title] involves [name]",
+                    "link a plot thread to your journal",
+                ),
+                (
+                    "thread [title] is [open/advancing/resolved]",
+                    "change the status of a plot thread",
+                ),
+                ("threads", "list the plot threads"),
+            ][..],
+            block_on(ThreadCommand::autocomplete("thread", &app_meta)),
+        );
+
+        assert_autocomplete(
+            &[(
+                "thread The cult is infiltrating the guard is [status]",
+                "change the status of the thread",
+            )][..],
+            block_on(ThreadCommand::autocomplete("thread the c", &app_meta)),
+        );
+    }
+
+    #[test]
+    fn display_test() {
+        let app_meta = app_meta();
+
+        [
+            ThreadCommand::Add {
+                title: "The cult is infiltrating the guard".to_string(),
+            },
+            ThreadCommand::Involve {
+                thread: "The cult".to_string(),
+                name: "Gimli".to_string(),
+            },
+            ThreadCommand::SetStatus {
+                thread: "The cult".to_string(),
+                status: Status::Resolved,
+            },
+            ThreadCommand::Threads,
+        ]
+        .into_iter()
+        .for_each(|command| {
+            let command_string = command.to_string();
+            assert_ne!("", command_string);
+            assert_eq!(
+                CommandMatches::new_canonical(command),
+                block_on(ThreadCommand::parse_input(&command_string, &app_meta)),
+                "{}",
+                command_string,
+            );
+        });
+    }
+
+    #[test]
+    fn run_test() {
+        let mut app_meta = app_meta();
+
+        assert_eq!(
+            Ok("# Threads\n\n*There are no plot threads yet. Use `thread add [description]` to start one.*".to_string()),
+            block_on(ThreadCommand::Threads.run("", &mut app_meta)),
+        );
+
+        block_on(
+            app_meta.repository.modify(Change::CreateAndSave {
+                thing: Npc {
+                    name: "Gimli".into(),
+                    ..Default::default()
+                }
+                .into(),
+            }),
+        )
+        .unwrap();
+
+        [
+            "thread add The cult is infiltrating the guard",
+            "thread add The baron's heir is missing",
+            "thread the cult involves gimli",
+            "thread the baron is resolved",
+        ]
+        .into_iter()
+        .for_each(|input| {
+            block_on(
+                block_on(ThreadCommand::parse_input(input, &app_meta))
+                    .take_best_match()
+                    .unwrap()
+                    .run(input, &mut app_meta),
+            )
+            .unwrap();
+        });
+
+        assert_eq!(
+            Err(CommandError::invalid_state(
+                "`Gimli` is already involved in **The cult is infiltrating the guard**."
+            )),
+            block_on(
+                ThreadCommand::Involve {
+                    thread: "the cult".to_string(),
+                    name: "Gimli".to_string(),
+                }
+                .run("", &mut app_meta),
+            ),
+        );
+
+        assert_eq!(
+            Ok("# Threads\n\n* **The baron's heir is missing** (resolved), _touched less than a minute ago_\n* **The cult is infiltrating the guard** (open), involving `Gimli`, _touched less than a minute ago_".to_string()),
+            block_on(ThreadCommand::Threads.run("", &mut app_meta)),
+        );
+    }
+
+    fn event_dispatcher(_event: Event) {}
+
+    fn app_meta() -> AppMeta {
+        AppMeta::new(MemoryDataStore::default(), &event_dispatcher)
+    }
+}
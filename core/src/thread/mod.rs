@@ -0,0 +1,232 @@
+pub use command::ThreadCommand;
+
+mod command;
+
+use crate::app::AppMeta;
+use crate::storage::KeyValue;
+use crate::utils::CaseInsensitiveStr;
+use crate::Uuid;
+use initiative_macros::WordList;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// The plot threads of a campaign, with the most recently touched first. Threads are a lighter
+/// weight way of keeping track of what's going on than writing up a full quest: a sentence, a
+/// status, and the characters and places involved.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct Threads(Vec<Thread>);
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Thread {
+    pub title: String,
+    pub status: Status,
+
+    /// The journal entries involved in the thread.
+    #[serde(default)]
+    pub involves: Vec<Uuid>,
+
+    /// The wall-clock time when the thread was added or last changed, in milliseconds since the
+    /// Unix epoch.
+    pub touched_at: u64,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, WordList)]
+#[serde(into = "&'static str", try_from = "&str")]
+pub enum Status {
+    Open,
+    Advancing,
+    Resolved,
+}
+
+impl Threads {
+    /// Load the campaign's threads from storage, or none if none have been added yet or the data
+    /// store is unavailable.
+    pub async fn load(app_meta: &AppMeta) -> Self {
+        app_meta
+            .repository
+            .get_key_value(&KeyValue::Threads(None))
+            .await
+            .ok()
+            .and_then(KeyValue::threads)
+            .unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Thread> {
+        self.0.iter()
+    }
+
+    /// Find a thread by its title, or by the start of its title if that is enough to tell it
+    /// apart from the others.
+    pub fn get(&self, title: &str) -> Option<&Thread> {
+        self.position(title).map(|i| &self.0[i])
+    }
+
+    /// Add a thread as the most recently touched.
+    pub fn add(&mut self, thread: Thread) {
+        self.0.insert(0, thread);
+    }
+
+    /// Find a thread as for [`Threads::get`] and mark it as touched at the given time, moving it
+    /// to the top of the list.
+    pub fn touch(&mut self, title: &str, now: u64) -> Option<&mut Thread> {
+        let index = self.position(title)?;
+        let mut thread = self.0.remove(index);
+        thread.touched_at = now;
+        self.add(thread);
+        self.0.first_mut()
+    }
+
+    /// The threads involving the journal entry with the given UUID.
+    pub fn involving<'a>(&'a self, uuid: &'a Uuid) -> impl Iterator<Item = &'a Thread> {
+        self.0
+            .iter()
+            .filter(move |thread| thread.involves.contains(uuid))
+    }
+
+    fn position(&self, title: &str) -> Option<usize> {
+        if let Some(i) = self.0.iter().position(|thread| thread.title.eq_ci(title)) {
+            return Some(i);
+        }
+
+        let mut matches = self
+            .0
+            .iter()
+            .enumerate()
+            .filter(|(_, thread)| thread.title.starts_with_ci(title))
+            .map(|(i, _)| i);
+
+        match (matches.next(), matches.next()) {
+            (Some(i), None) => Some(i),
+            _ => None,
+        }
+    }
+}
+
+impl Thread {
+    pub fn new(title: String, now: u64) -> Self {
+        Self {
+            title,
+            status: Status::Open,
+            involves: Vec::new(),
+            touched_at: now,
+        }
+    }
+}
+
+impl fmt::Display for Threads {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            serde_json::to_string(self).map_err(|_| fmt::Error)?,
+        )
+    }
+}
+
+impl FromStr for Threads {
+    type Err = ();
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(raw).map_err(|_| ())
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn get_test() {
+        let threads = threads();
+
+        assert_eq!(
+            Some("The cult is infiltrating the guard"),
+            threads
+                .get("THE CULT IS INFILTRATING THE GUARD")
+                .map(|t| t.title.as_str()),
+        );
+        assert_eq!(
+            Some("The cult is infiltrating the guard"),
+            threads.get("the cult").map(|t| t.title.as_str()),
+        );
+        assert_eq!(None, threads.get("the"));
+        assert_eq!(None, threads.get("a dragon"));
+    }
+
+    #[test]
+    fn touch_test() {
+        let mut threads = threads();
+
+        let thread = threads.touch("the cult", 30).unwrap();
+        thread.status = Status::Advancing;
+
+        assert_eq!(
+            vec![
+                ("The cult is infiltrating the guard", Status::Advancing, 30),
+                ("The baron's heir is missing", Status::Open, 20),
+            ],
+            threads
+                .iter()
+                .map(|t| (t.title.as_str(), t.status, t.touched_at))
+                .collect::<Vec<_>>(),
+        );
+        assert!(threads.touch("the dragon", 40).is_none());
+    }
+
+    #[test]
+    fn involving_test() {
+        let gimli = Uuid::new_v4();
+        let mut threads = threads();
+        threads.0[1].involves.push(gimli);
+
+        assert_eq!(
+            vec!["The cult is infiltrating the guard"],
+            threads
+                .involving(&gimli)
+                .map(|t| t.title.as_str())
+                .collect::<Vec<_>>(),
+        );
+        assert_eq!(0, threads.involving(&Uuid::nil()).count());
+    }
+
+    #[test]
+    fn serialize_deserialize_test() {
+        let mut threads = Threads::default();
+        assert!(threads.is_empty());
+
+        threads.add(Thread {
+            title: "The cult is infiltrating the guard".to_string(),
+            status: Status::Advancing,
+            involves: vec![Uuid::nil()],
+            touched_at: 10,
+        });
+
+        assert_eq!(
+            r#"[{"title":"The cult is infiltrating the guard","status":"advancing","involves":["00000000-0000-0000-0000-000000000000"],"touched_at":10}]"#,
+            threads.to_string(),
+        );
+        assert_eq!(Ok(threads.clone()), threads.to_string().parse());
+    }
+
+    fn threads() -> Threads {
+        let mut threads = Threads::default();
+        threads.add(Thread::new(
+            "The cult is infiltrating the guard".to_string(),
+            10,
+        ));
+        threads.add(Thread::new("The baron's heir is missing".to_string(), 20));
+        threads
+    }
+}
@@ -1,43 +1,120 @@
-use super::Interval;
+use super::{Interval, Moment};
 use crate::app::{
-    AppMeta, Autocomplete, AutocompleteSuggestion, CommandMatches, ContextAwareParse, Runnable,
+    AppMeta, Autocomplete, AutocompleteSuggestion, CommandError, CommandMatches, ContextAwareParse,
+    Runnable,
 };
+use crate::config::Config;
 use crate::storage::{Change, KeyValue};
 use crate::utils::CaseInsensitiveStr;
+use crate::world::{
+    expire_statuses, festival_notices, party_location_notice, skipped_festival_notices,
+};
 use async_trait::async_trait;
 use std::fmt;
 use std::iter;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum TimeCommand {
-    Add { interval: Interval },
+    Add {
+        interval: Interval,
+    },
+
+    /// Advance to the next time the clock reaches a moment, as in `advance to dawn` or `at 6pm`,
+    /// or to that moment on the following day, as in `next morning`.
+    Advance {
+        to: Moment,
+        next_day: bool,
+    },
+
     Now,
-    Sub { interval: Interval },
+    Sub {
+        interval: Interval,
+    },
+    Timeline,
 }
 
-#[async_trait(?Send)]
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
 impl Runnable for TimeCommand {
-    async fn run(self, _input: &str, app_meta: &mut AppMeta) -> Result<String, String> {
-        let time = {
-            let current_time = app_meta
+    async fn run(self, _input: &str, app_meta: &mut AppMeta) -> Result<String, CommandError> {
+        let config = Config::load(app_meta).await;
+
+        if self == Self::Timeline {
+            let timeline = app_meta
                 .repository
-                .get_key_value(&KeyValue::Time(None))
+                .timeline()
                 .await
-                .map_err(|_| "Storage error.".to_string())?
-                .time()
-                .unwrap_or_default();
+                .map_err(|_| CommandError::storage("Couldn't access the timeline."))?;
 
-            match &self {
-                Self::Add { interval } => current_time.checked_add(interval),
-                Self::Sub { interval } => current_time.checked_sub(interval),
-                Self::Now => {
-                    return Ok(format!("It is currently {}.", current_time.display_long()))
+            return Ok(if timeline.is_empty() {
+                "# Timeline\n\n*The timeline is currently empty. Creating and deleting entries and advancing time will add events to it.*".to_string()
+            } else {
+                format!(
+                    "# Timeline\n\n{}",
+                    timeline
+                        .iter()
+                        .map(|entry| entry.display(&config).to_string())
+                        .collect::<Vec<_>>()
+                        .join("\\\n"),
+                )
+            });
+        }
+
+        let current_time = app_meta
+            .repository
+            .get_key_value(&KeyValue::Time(None))
+            .await
+            .map_err(|_| CommandError::storage("Storage error."))?
+            .time()
+            .unwrap_or_default();
+
+        let time = match &self {
+            Self::Add { interval } => current_time.checked_add(interval),
+            Self::Sub { interval } => current_time.checked_sub(interval),
+            Self::Advance { to, next_day } => {
+                let (hours, minutes) = to.clock(&config);
+                current_time.next_at(hours, minutes, *next_day)
+            }
+            Self::Now => {
+                let mut output = format!("It is currently {}.", config.display_time(&current_time));
+
+                if let Some(notice) = party_location_notice(app_meta).await {
+                    output.push_str(&format!("\n\n{}", notice));
                 }
+
+                return Ok(output);
             }
+            Self::Timeline => unreachable!(),
         };
 
         if let Some(time) = time {
-            let response = format!("It is now {}. Use `undo` to reverse.", time.display_long());
+            let mut response = if matches!(self, Self::Advance { .. }) {
+                format!(
+                    "It is now {}, {} later. Use `undo` to reverse.",
+                    config.display_time(&time),
+                    time.since(&current_time).display_long(),
+                )
+            } else {
+                format!(
+                    "It is now {}. Use `undo` to reverse.",
+                    config.display_time(&time),
+                )
+            };
+
+            // Statuses are expired before the time is changed so that `undo` reverses the latter.
+            if matches!(self, Self::Add { .. } | Self::Advance { .. }) {
+                let mut notices = expire_statuses(&time, app_meta).await;
+
+                if time.days() != current_time.days() {
+                    notices.extend(skipped_festival_notices(&current_time, &time, app_meta).await);
+                    notices.extend(festival_notices(&time, app_meta).await);
+                }
+
+                if !notices.is_empty() {
+                    response.push_str("\n\n");
+                    response.push_str(&notices.join("\\\n"));
+                }
+            }
 
             app_meta
                 .repository
@@ -50,23 +127,30 @@ impl Runnable for TimeCommand {
         } else {
             Err(())
         }
-        .map_err(|_| match &self {
-            Self::Add { interval } => {
-                format!("Unable to advance time by {}.", interval.display_long())
-            }
-            Self::Sub { interval } => {
-                format!("Unable to rewind time by {}.", interval.display_long())
+        .map_err(|_| {
+            match &self {
+                Self::Add { interval } => {
+                    format!("Unable to advance time by {}.", interval.display_long())
+                }
+                Self::Sub { interval } => {
+                    format!("Unable to rewind time by {}.", interval.display_long())
+                }
+                Self::Advance { to, .. } => format!("Unable to advance time to {}.", to),
+                Self::Now | Self::Timeline => unreachable!(),
             }
-            Self::Now => unreachable!(),
+            .into()
         })
     }
 }
 
-#[async_trait(?Send)]
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
 impl ContextAwareParse for TimeCommand {
     async fn parse_input(input: &str, _app_meta: &AppMeta) -> CommandMatches<Self> {
         if input.eq_ci("now") {
             CommandMatches::new_canonical(Self::Now)
+        } else if input.eq_ci("timeline") {
+            CommandMatches::new_canonical(Self::Timeline)
         } else if input.in_ci(&["time", "date"]) {
             CommandMatches::new_fuzzy(Self::Now)
         } else if let Some(canonical_match) = input
@@ -81,13 +165,22 @@ impl ContextAwareParse for TimeCommand {
             })
         {
             CommandMatches::new_canonical(canonical_match)
+        } else if let Some((to, next_day)) = input
+            .strip_prefix_ci("advance to ")
+            .or_else(|| input.strip_prefix_ci("at "))
+            .map(|s| (s, false))
+            .or_else(|| input.strip_prefix_ci("next ").map(|s| (s, true)))
+            .and_then(|(s, next_day)| s.parse().ok().map(|to| (to, next_day)))
+        {
+            CommandMatches::new_canonical(Self::Advance { to, next_day })
         } else {
             CommandMatches::default()
         }
     }
 }
 
-#[async_trait(?Send)]
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
 impl Autocomplete for TimeCommand {
     async fn autocomplete(input: &str, _app_meta: &AppMeta) -> Vec<AutocompleteSuggestion> {
         if input.starts_with(&['+', '-'][..]) {
@@ -126,12 +219,37 @@ impl Autocomplete for TimeCommand {
                 .collect(),
                 _ => suggest_all().collect(),
             }
-        } else if !input.is_empty() {
-            ["now", "time", "date"]
+        } else if let Some((prefix, rest)) = ["advance to ", "next "]
+            .into_iter()
+            .find_map(|prefix| input.strip_prefix_ci(prefix).map(|rest| (prefix, rest)))
+        {
+            Moment::DAY_PARTS
                 .into_iter()
-                .filter(|term| term.starts_with_ci(input))
-                .map(|term| AutocompleteSuggestion::new(term, "get the current time"))
+                .filter(|moment| moment.to_string().starts_with_ci(rest))
+                .map(|moment| {
+                    AutocompleteSuggestion::new(
+                        format!("{}{}", &input[..prefix.len()], moment),
+                        if prefix == "next " {
+                            format!("advance to {} tomorrow", moment)
+                        } else {
+                            format!("advance to the next {}", moment)
+                        },
+                    )
+                })
                 .collect()
+        } else if !input.is_empty() {
+            [
+                ("advance to [time]", "advance to eg. dawn or 6pm"),
+                ("next [time]", "advance to eg. morning tomorrow"),
+                ("now", "get the current time"),
+                ("time", "get the current time"),
+                ("date", "get the current time"),
+                ("timeline", "list events in chronological order"),
+            ]
+            .into_iter()
+            .filter(|(term, _)| term.starts_with_ci(input))
+            .map(|(term, summary)| AutocompleteSuggestion::new(term, summary))
+            .collect()
         } else {
             Vec::new()
         }
@@ -142,8 +260,14 @@ impl fmt::Display for TimeCommand {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         match self {
             Self::Add { interval } => write!(f, "+{}", interval.display_short()),
+            Self::Advance {
+                to,
+                next_day: false,
+            } => write!(f, "advance to {}", to),
+            Self::Advance { to, next_day: true } => write!(f, "next {}", to),
             Self::Now => write!(f, "now"),
             Self::Sub { interval } => write!(f, "-{}", interval.display_short()),
+            Self::Timeline => write!(f, "timeline"),
         }
     }
 }
@@ -180,6 +304,50 @@ mod test {
             block_on(TimeCommand::parse_input("-10h", &app_meta)),
         );
 
+        assert_eq!(
+            CommandMatches::new_canonical(TimeCommand::Timeline),
+            block_on(TimeCommand::parse_input("timeline", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(TimeCommand::Add {
+                interval: Interval::new(7, 0, 0, 0, 0),
+            }),
+            block_on(TimeCommand::parse_input("+1 week", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(TimeCommand::Advance {
+                to: Moment::Dawn,
+                next_day: false,
+            }),
+            block_on(TimeCommand::parse_input("advance to dawn", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(TimeCommand::Advance {
+                to: Moment::Morning,
+                next_day: true,
+            }),
+            block_on(TimeCommand::parse_input("next morning", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(TimeCommand::Advance {
+                to: Moment::Clock {
+                    hours: 18,
+                    minutes: 0
+                },
+                next_day: false,
+            }),
+            block_on(TimeCommand::parse_input("at 6pm", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::default(),
+            block_on(TimeCommand::parse_input("next page", &app_meta)),
+        );
+
         assert_eq!(
             CommandMatches::default(),
             block_on(TimeCommand::parse_input("1d2h", &app_meta)),
@@ -195,6 +363,38 @@ mod test {
             block_on(TimeCommand::autocomplete("", &app_meta)),
         );
 
+        assert_autocomplete(
+            &[
+                ("time", "get the current time"),
+                ("timeline", "list events in chronological order"),
+            ][..],
+            block_on(TimeCommand::autocomplete("ti", &app_meta)),
+        );
+
+        assert_autocomplete(
+            &[
+                ("next [time]", "advance to eg. morning tomorrow"),
+                ("now", "get the current time"),
+            ][..],
+            block_on(TimeCommand::autocomplete("n", &app_meta)),
+        );
+
+        assert_autocomplete(
+            &[
+                ("advance to midnight", "advance to the next midnight"),
+                ("advance to morning", "advance to the next morning"),
+            ][..],
+            block_on(TimeCommand::autocomplete("advance to m", &app_meta)),
+        );
+
+        assert_autocomplete(
+            &[
+                ("Next dawn", "advance to dawn tomorrow"),
+                ("Next dusk", "advance to dusk tomorrow"),
+            ][..],
+            block_on(TimeCommand::autocomplete("Next d", &app_meta)),
+        );
+
         assert_autocomplete(
             &[
                 ("+[number]", "advance time"),
@@ -311,10 +511,22 @@ mod test {
             TimeCommand::Add {
                 interval: Interval::new(2, 3, 4, 5, 6),
             },
+            TimeCommand::Advance {
+                to: Moment::Dusk,
+                next_day: false,
+            },
+            TimeCommand::Advance {
+                to: Moment::Clock {
+                    hours: 6,
+                    minutes: 30,
+                },
+                next_day: true,
+            },
             TimeCommand::Now,
             TimeCommand::Sub {
                 interval: Interval::new(2, 3, 4, 5, 6),
             },
+            TimeCommand::Timeline,
         ]
         .into_iter()
         .for_each(|command| {
@@ -73,51 +73,98 @@ impl FromStr for Interval {
         match raw.trim() {
             "" => Err(()),
             "0" => Ok(Interval::default()),
-            s => {
-                let mut used_chars = HashSet::new();
-                let mut interval = Interval::default();
-
-                s.split_inclusive(|c: char| !c.is_ascii_digit())
-                    .enumerate()
-                    .try_for_each(|(raw_index, s)| {
-                        let part = s.trim();
-
-                        if part.is_empty() {
-                            Ok(())
-                        } else if let Some((part_index, c)) = part.char_indices().last() {
-                            if !used_chars.insert(c.to_ascii_lowercase()) {
-                                return Err(());
-                            }
-
-                            let value = if part_index == 0 && raw_index == 0 {
-                                // Interpret input like "d" as "1d"
-                                1
-                            } else if part.starts_with(|c: char| c.is_ascii_digit()) {
-                                part[..part_index].parse().map_err(|_| ())?
-                            } else {
-                                // Don't accept "-1d", that's handled by the command parser
-                                return Err(());
-                            };
-
-                            match c {
-                                'd' | 'D' => interval += Self::new_days(value),
-                                'h' | 'H' => interval += Self::new_hours(value),
-                                'm' | 'M' => interval += Self::new_minutes(value),
-                                's' | 'S' => interval += Self::new_seconds(value),
-                                'r' | 'R' => interval += Self::new_rounds(value),
-                                _ => return Err(()),
-                            }
-
-                            Ok(())
-                        } else {
-                            Err(())
-                        }
-                    })?;
-
-                Ok(interval)
+            s => parse_short(s).or_else(|_| parse_long(s)),
+        }
+    }
+}
+
+/// Parse an interval in the form "2d3h" or "2d 3h".
+fn parse_short(s: &str) -> Result<Interval, ()> {
+    let mut used_chars = HashSet::new();
+    let mut interval = Interval::default();
+
+    s.split_inclusive(|c: char| !c.is_ascii_digit())
+        .enumerate()
+        .try_for_each(|(raw_index, s)| {
+            let part = s.trim();
+
+            if part.is_empty() {
+                Ok(())
+            } else if let Some((part_index, c)) = part.char_indices().last() {
+                if !used_chars.insert(c.to_ascii_lowercase()) {
+                    return Err(());
+                }
+
+                let value: i32 = if part_index == 0 && raw_index == 0 {
+                    // Interpret input like "d" as "1d"
+                    1
+                } else if part.starts_with(|c: char| c.is_ascii_digit()) {
+                    part[..part_index].parse().map_err(|_| ())?
+                } else {
+                    // Don't accept "-1d", that's handled by the command parser
+                    return Err(());
+                };
+
+                match c {
+                    'w' | 'W' => interval += Interval::new_days(value.checked_mul(7).ok_or(())?),
+                    'd' | 'D' => interval += Interval::new_days(value),
+                    'h' | 'H' => interval += Interval::new_hours(value),
+                    'm' | 'M' => interval += Interval::new_minutes(value),
+                    's' | 'S' => interval += Interval::new_seconds(value),
+                    'r' | 'R' => interval += Interval::new_rounds(value),
+                    _ => return Err(()),
+                }
+
+                Ok(())
+            } else {
+                Err(())
+            }
+        })?;
+
+    Ok(interval)
+}
+
+/// Parse an interval written out in words, such as "1 week" or "2 days, 3 hours".
+fn parse_long(s: &str) -> Result<Interval, ()> {
+    let words: Vec<&str> = s
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|word| !word.is_empty() && !word.eq_ignore_ascii_case("and"))
+        .collect();
+
+    if words.is_empty() || !words.len().is_multiple_of(2) {
+        return Err(());
+    }
+
+    let mut used_units = HashSet::new();
+    let mut interval = Interval::default();
+
+    for pair in words.chunks(2) {
+        let value: i32 = match pair[0].to_lowercase().as_str() {
+            "a" | "an" => 1,
+            number if number.chars().all(|c| c.is_ascii_digit()) => {
+                number.parse().map_err(|_| ())?
             }
+            _ => return Err(()),
+        };
+
+        let unit = pair[1].to_lowercase();
+        let unit = unit.strip_suffix('s').unwrap_or(&unit);
+        if !used_units.insert(unit.to_string()) {
+            return Err(());
         }
+
+        interval += match unit {
+            "week" => Interval::new_days(value.checked_mul(7).ok_or(())?),
+            "day" => Interval::new_days(value),
+            "hour" | "hr" => Interval::new_hours(value),
+            "minute" | "min" => Interval::new_minutes(value),
+            "second" | "sec" => Interval::new_seconds(value),
+            "round" => Interval::new_rounds(value),
+            _ => return Err(()),
+        };
     }
+
+    Ok(interval)
 }
 
 impl<'a> fmt::Display for IntervalShortView<'a> {
@@ -242,7 +289,18 @@ mod test {
         assert_eq!(Ok(i(2, 3, 4, 5, 6)), "2d3h4m5s6r".parse());
         assert_eq!(Ok(i(2, 3, 4, 5, 6)), "2d 3h 4m 5s 6r".parse());
 
+        assert_eq!(Ok(days(14)), "2w".parse());
+        assert_eq!(Ok(days(7)), "1 week".parse());
+        assert_eq!(Ok(days(7)), "a week".parse());
+        assert_eq!(Ok(i(2, 3, 0, 0, 0)), "2 days, 3 hours".parse());
+        assert_eq!(Ok(i(0, 1, 30, 0, 0)), "1 Hour and 30 mins".parse());
+        assert_eq!(Ok(rounds(5)), "5 rounds".parse());
+
         assert_eq!(Err(()), format!("{}d", i64::MAX).parse::<Interval>());
+        assert_eq!(Err(()), format!("{}w", i32::MAX).parse::<Interval>());
+        assert_eq!(Err(()), "1 fortnight".parse::<Interval>());
+        assert_eq!(Err(()), "1 day 2 days".parse::<Interval>());
+        assert_eq!(Err(()), "week".parse::<Interval>());
         assert_eq!(Err(()), "".parse::<Interval>());
         assert_eq!(Err(()), "1 d".parse::<Interval>());
         assert_eq!(Err(()), "1a".parse::<Interval>());
@@ -1,13 +1,19 @@
 pub use command::TimeCommand;
 pub use interval::Interval;
+pub use moment::{ClockView, Moment};
+pub use utc::UtcView;
 
 mod command;
 mod interval;
+mod moment;
+mod utc;
 
+use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::str::FromStr;
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(into = "String", try_from = "&str")]
 pub struct Time {
     days: i32,
     hours: u8,
@@ -15,6 +21,17 @@ pub struct Time {
     seconds: u8,
 }
 
+/// A coarse division of the day, used to colour generated content (eg. an inn that is quiet in
+/// the small hours).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TimeOfDay {
+    Night,
+    Morning,
+    Midday,
+    Afternoon,
+    Evening,
+}
+
 pub struct TimeShortView<'a>(&'a Time);
 
 pub struct TimeLongView<'a>(&'a Time);
@@ -33,6 +50,21 @@ impl Time {
         }
     }
 
+    /// The day number, counting from day 1 at the start of a campaign.
+    pub fn days(&self) -> i32 {
+        self.days
+    }
+
+    pub fn time_of_day(&self) -> TimeOfDay {
+        match self.hours {
+            6..=10 => TimeOfDay::Morning,
+            11..=13 => TimeOfDay::Midday,
+            14..=17 => TimeOfDay::Afternoon,
+            18..=21 => TimeOfDay::Evening,
+            _ => TimeOfDay::Night,
+        }
+    }
+
     pub fn checked_add(&self, interval: &Interval) -> Option<Self> {
         let (mut days, mut hours, mut minutes, mut seconds) = (
             (self.days as i64) + (interval.days as i64),
@@ -97,6 +129,38 @@ impl Time {
         }
     }
 
+    /// The first time after this one at which the clock reads the given hour and minute, or that
+    /// time on the following day if `next_day` is set, as in "next morning".
+    pub fn next_at(&self, hours: u8, minutes: u8, next_day: bool) -> Option<Self> {
+        let today = Self::try_new(self.days, hours, minutes, 0).ok()?;
+
+        if next_day || today <= *self {
+            Self::try_new(self.days.checked_add(1)?, hours, minutes, 0).ok()
+        } else {
+            Some(today)
+        }
+    }
+
+    /// The interval that has passed since an earlier time, in days, hours, minutes, and seconds.
+    /// If the earlier time is actually later, no time has passed.
+    pub fn since(&self, earlier: &Time) -> Interval {
+        let seconds = |time: &Time| {
+            time.days as i64 * 86400
+                + time.hours as i64 * 3600
+                + time.minutes as i64 * 60
+                + time.seconds as i64
+        };
+        let elapsed = (seconds(self) - seconds(earlier)).max(0);
+
+        Interval::new(
+            (elapsed / 86400) as i32,
+            (elapsed / 3600 % 24) as i32,
+            (elapsed / 60 % 60) as i32,
+            (elapsed % 60) as i32,
+            0,
+        )
+    }
+
     pub fn display_short(&self) -> TimeShortView {
         TimeShortView(self)
     }
@@ -136,6 +200,20 @@ impl FromStr for Time {
     }
 }
 
+impl From<Time> for String {
+    fn from(input: Time) -> String {
+        input.display_short().to_string()
+    }
+}
+
+impl TryFrom<&str> for Time {
+    type Error = &'static str;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        input.parse().map_err(|_| "Not a valid time.")
+    }
+}
+
 impl<'a> fmt::Display for TimeShortView<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let time = self.0;
@@ -184,6 +262,17 @@ mod test {
         assert_eq!(t(1, 8, 0, 0), Time::default());
     }
 
+    #[test]
+    fn time_of_day_test() {
+        assert_eq!(TimeOfDay::Night, t(1, 2, 0, 0).time_of_day());
+        assert_eq!(TimeOfDay::Morning, t(1, 6, 0, 0).time_of_day());
+        assert_eq!(TimeOfDay::Morning, Time::default().time_of_day());
+        assert_eq!(TimeOfDay::Midday, t(1, 12, 30, 0).time_of_day());
+        assert_eq!(TimeOfDay::Afternoon, t(1, 17, 59, 59).time_of_day());
+        assert_eq!(TimeOfDay::Evening, t(1, 18, 0, 0).time_of_day());
+        assert_eq!(TimeOfDay::Night, t(1, 23, 0, 0).time_of_day());
+    }
+
     #[test]
     fn time_checked_add_test() {
         assert_eq!(
@@ -302,6 +391,29 @@ mod test {
         assert!(t0().checked_sub(&Interval::new_seconds(i32::MIN)).is_none());
     }
 
+    #[test]
+    fn time_next_at_test() {
+        assert_eq!(Some(t(1, 18, 0, 0)), t(1, 8, 0, 0).next_at(18, 0, false));
+        assert_eq!(Some(t(2, 6, 0, 0)), t(1, 8, 0, 0).next_at(6, 0, false));
+        assert_eq!(Some(t(2, 8, 0, 0)), t(1, 8, 0, 0).next_at(8, 0, false));
+        assert_eq!(Some(t(2, 18, 30, 0)), t(1, 8, 0, 0).next_at(18, 30, true));
+        assert_eq!(None, t(1, 8, 0, 0).next_at(24, 0, false));
+        assert_eq!(None, t(i32::MAX, 8, 0, 0).next_at(6, 0, false));
+    }
+
+    #[test]
+    fn time_since_test() {
+        assert_eq!(
+            Interval::new(1, 2, 3, 4, 0),
+            t(2, 10, 3, 4).since(&t(1, 8, 0, 0)),
+        );
+        assert_eq!(
+            Interval::new(0, 23, 59, 59, 0),
+            t(2, 0, 0, 0).since(&t(1, 0, 0, 1)),
+        );
+        assert_eq!(Interval::default(), t(1, 8, 0, 0).since(&t(2, 8, 0, 0)));
+    }
+
     #[test]
     fn time_display_short_test() {
         assert_eq!("1:02:03:04", t(1, 2, 3, 4).display_short().to_string());
@@ -339,6 +451,19 @@ mod test {
         assert_eq!(Ok(t(1, 23, 59, 59)), "1:23:59:59".parse());
     }
 
+    #[test]
+    fn time_serialize_deserialize_test() {
+        assert_eq!(
+            r#""1:02:03:04""#,
+            serde_json::to_string(&t(1, 2, 3, 4)).unwrap(),
+        );
+        assert_eq!(
+            t(1, 2, 3, 4),
+            serde_json::from_str::<Time>(r#""1:02:03:04""#).unwrap(),
+        );
+        assert!(serde_json::from_str::<Time>(r#""1:24:00:00""#).is_err());
+    }
+
     fn t(days: i32, hours: u8, minutes: u8, seconds: u8) -> Time {
         Time {
             days,
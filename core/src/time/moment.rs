@@ -0,0 +1,194 @@
+use crate::config::Config;
+use std::fmt;
+use std::str::FromStr;
+
+/// A time of day that time can be advanced to, as in `advance to dawn`, `next morning`, or
+/// `at 6pm`. Dawn and dusk fall at the hours set with `config dawn` and `config dusk`; morning
+/// and evening follow two hours after each.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Moment {
+    Dawn,
+    Morning,
+    Noon,
+    Afternoon,
+    Dusk,
+    Evening,
+    Midnight,
+    Clock { hours: u8, minutes: u8 },
+}
+
+/// An hour and minute of the day formatted as on a 12-hour clock, eg. "6pm" or "6:30pm".
+pub struct ClockView(u8, u8);
+
+impl Moment {
+    pub const DAY_PARTS: [Moment; 7] = [
+        Self::Dawn,
+        Self::Morning,
+        Self::Noon,
+        Self::Afternoon,
+        Self::Dusk,
+        Self::Evening,
+        Self::Midnight,
+    ];
+
+    /// The hour and minute of the day at which the moment falls.
+    pub fn clock(&self, config: &Config) -> (u8, u8) {
+        match self {
+            Self::Dawn => (config.dawn, 0),
+            Self::Morning => ((config.dawn + 2) % 24, 0),
+            Self::Noon => (12, 0),
+            Self::Afternoon => (15, 0),
+            Self::Dusk => (config.dusk, 0),
+            Self::Evening => ((config.dusk + 2) % 24, 0),
+            Self::Midnight => (0, 0),
+            Self::Clock { hours, minutes } => (*hours, *minutes),
+        }
+    }
+}
+
+impl ClockView {
+    pub fn new(hours: u8, minutes: u8) -> Self {
+        Self(hours, minutes)
+    }
+}
+
+impl FromStr for Moment {
+    type Err = ();
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        let raw = raw.trim().to_lowercase();
+
+        match raw.as_str() {
+            "dawn" | "sunrise" => return Ok(Self::Dawn),
+            "morning" => return Ok(Self::Morning),
+            "noon" | "midday" => return Ok(Self::Noon),
+            "afternoon" => return Ok(Self::Afternoon),
+            "dusk" | "sunset" | "nightfall" => return Ok(Self::Dusk),
+            "evening" => return Ok(Self::Evening),
+            "midnight" => return Ok(Self::Midnight),
+            _ => {}
+        }
+
+        let (number, pm) = if let Some(number) = raw.strip_suffix("am") {
+            (number.trim_end(), Some(false))
+        } else if let Some(number) = raw.strip_suffix("pm") {
+            (number.trim_end(), Some(true))
+        } else {
+            (raw.as_str(), None)
+        };
+
+        // A bare number such as "6" could be morning or evening, so a 24-hour time needs minutes.
+        let (hours, minutes): (u8, u8) = match (number.split_once(':'), pm) {
+            (Some((hours, minutes)), _) if minutes.len() == 2 => (
+                hours.parse().map_err(|_| ())?,
+                minutes.parse().map_err(|_| ())?,
+            ),
+            (None, Some(_)) => (number.parse().map_err(|_| ())?, 0),
+            _ => return Err(()),
+        };
+
+        let hours = match pm {
+            Some(pm) if (1..=12).contains(&hours) => hours % 12 + if pm { 12 } else { 0 },
+            Some(_) => return Err(()),
+            None if hours < 24 => hours,
+            None => return Err(()),
+        };
+
+        if minutes < 60 {
+            Ok(Self::Clock { hours, minutes })
+        } else {
+            Err(())
+        }
+    }
+}
+
+impl fmt::Display for Moment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Dawn => write!(f, "dawn"),
+            Self::Morning => write!(f, "morning"),
+            Self::Noon => write!(f, "noon"),
+            Self::Afternoon => write!(f, "afternoon"),
+            Self::Dusk => write!(f, "dusk"),
+            Self::Evening => write!(f, "evening"),
+            Self::Midnight => write!(f, "midnight"),
+            Self::Clock { hours, minutes } => write!(f, "{}", ClockView::new(*hours, *minutes)),
+        }
+    }
+}
+
+impl fmt::Display for ClockView {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Self(hours, minutes) = self;
+        let am_pm = if *hours < 12 { "am" } else { "pm" };
+        let hours = match hours % 12 {
+            0 => 12,
+            hours => hours,
+        };
+
+        if *minutes == 0 {
+            write!(f, "{}{}", hours, am_pm)
+        } else {
+            write!(f, "{}:{:02}{}", hours, minutes, am_pm)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn from_str_test() {
+        assert_eq!(Ok(Moment::Dawn), "Dawn".parse());
+        assert_eq!(Ok(Moment::Dusk), "sunset".parse());
+        assert_eq!(Ok(Moment::Noon), "midday".parse());
+
+        assert_eq!(Ok(clock(18, 0)), "6pm".parse());
+        assert_eq!(Ok(clock(18, 30)), "6:30 PM".parse());
+        assert_eq!(Ok(clock(0, 0)), "12am".parse());
+        assert_eq!(Ok(clock(12, 0)), "12pm".parse());
+        assert_eq!(Ok(clock(7, 5)), "07:05".parse());
+
+        assert_eq!(Err(()), "6".parse::<Moment>());
+        assert_eq!(Err(()), "13pm".parse::<Moment>());
+        assert_eq!(Err(()), "0am".parse::<Moment>());
+        assert_eq!(Err(()), "24:00".parse::<Moment>());
+        assert_eq!(Err(()), "6:60pm".parse::<Moment>());
+        assert_eq!(Err(()), "6:5pm".parse::<Moment>());
+        assert_eq!(Err(()), "page".parse::<Moment>());
+    }
+
+    #[test]
+    fn display_test() {
+        Moment::DAY_PARTS
+            .into_iter()
+            .chain([clock(18, 0), clock(0, 30), clock(12, 0), clock(9, 15)])
+            .for_each(|moment| {
+                assert_eq!(Ok(moment), moment.to_string().parse(), "{}", moment);
+            });
+
+        assert_eq!("12:30am", clock(0, 30).to_string());
+        assert_eq!("6pm", clock(18, 0).to_string());
+    }
+
+    #[test]
+    fn clock_test() {
+        let config = Config {
+            dawn: 5,
+            dusk: 21,
+            ..Default::default()
+        };
+
+        assert_eq!((6, 0), Moment::Dawn.clock(&Config::default()));
+        assert_eq!((5, 0), Moment::Dawn.clock(&config));
+        assert_eq!((7, 0), Moment::Morning.clock(&config));
+        assert_eq!((21, 0), Moment::Dusk.clock(&config));
+        assert_eq!((23, 0), Moment::Evening.clock(&config));
+        assert_eq!((18, 30), clock(18, 30).clock(&config));
+    }
+
+    fn clock(hours: u8, minutes: u8) -> Moment {
+        Moment::Clock { hours, minutes }
+    }
+}
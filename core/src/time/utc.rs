@@ -0,0 +1,72 @@
+use std::fmt;
+
+/// A real-world time, given in milliseconds since the Unix epoch (as returned by
+/// [`AppMeta::now`](crate::app::AppMeta::now)), shown as a UTC date and time to the minute, eg.
+/// "2023-11-14 22:13 UTC".
+pub struct UtcView(u64);
+
+impl UtcView {
+    pub fn new(millis: u64) -> Self {
+        Self(millis)
+    }
+}
+
+impl fmt::Display for UtcView {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let minutes = self.0 / 60_000;
+        let (year, month, day) = civil_from_days((minutes / 1440) as i64);
+
+        write!(
+            f,
+            "{}-{:02}-{:02} {:02}:{:02} UTC",
+            year,
+            month,
+            day,
+            minutes / 60 % 24,
+            minutes % 60,
+        )
+    }
+}
+
+/// The Gregorian calendar date of a number of days since 1970-01-01, as year, month, and day.
+/// See Howard Hinnant's "chrono-Compatible Low-Level Date Algorithms".
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn display_test() {
+        assert_eq!("1970-01-01 00:00 UTC", UtcView::new(0).to_string());
+        assert_eq!(
+            "2023-11-14 22:13 UTC",
+            UtcView::new(1_700_000_000_000).to_string(),
+        );
+        assert_eq!(
+            "2000-02-29 23:59 UTC",
+            UtcView::new(951_868_799_999).to_string(),
+        );
+        assert_eq!(
+            "2000-03-01 00:00 UTC",
+            UtcView::new(951_868_800_000).to_string(),
+        );
+    }
+}
@@ -0,0 +1,334 @@
+use super::Treasure;
+use crate::app::{
+    AppMeta, Autocomplete, AutocompleteSuggestion, CommandError, CommandMatches, ContextAwareParse,
+    Runnable,
+};
+use crate::storage::Change;
+use crate::utils::{quoted_words, CaseInsensitiveStr};
+use crate::world::{Npc, Place, Thing};
+use async_trait::async_trait;
+use std::fmt;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TreasureCommand {
+    Individual { cr: u8, recipient: Option<String> },
+    Hoard { cr: u8, recipient: Option<String> },
+}
+
+impl TreasureCommand {
+    /// Parse the part of the command following "loot" or "hoard", eg. "cr 5 for Thorin".
+    fn parse_cr_recipient(input: &str) -> Option<(u8, Option<String>)> {
+        let input = input.strip_prefix_ci("cr ")?;
+
+        let (cr, recipient) = if let Some(word) = quoted_words(input)
+            .skip(1)
+            .find(|word| word.as_str().eq_ci("for"))
+        {
+            let recipient = input[word.range().end..].trim();
+
+            if recipient.is_empty() {
+                return None;
+            }
+
+            (
+                input[..word.range().start].trim(),
+                Some(recipient.to_string()),
+            )
+        } else {
+            (input.trim(), None)
+        };
+
+        let cr = match cr {
+            "1/8" | "1/4" | "1/2" => 0,
+            cr => cr.parse().ok().filter(|&cr| cr <= 30)?,
+        };
+
+        Some((cr, recipient))
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Runnable for TreasureCommand {
+    async fn run(self, _input: &str, app_meta: &mut AppMeta) -> Result<String, CommandError> {
+        let (treasure, recipient, mut output) = match self {
+            Self::Individual { cr, recipient } => (
+                Treasure::individual(&mut app_meta.rng, cr),
+                recipient,
+                format!("# Individual Treasure (CR {})\n\n", cr),
+            ),
+            Self::Hoard { cr, recipient } => (
+                Treasure::hoard(&mut app_meta.rng, cr),
+                recipient,
+                format!("# Treasure Hoard (CR {})\n\n", cr),
+            ),
+        };
+
+        output.push_str(&treasure.to_string());
+
+        let recipient = if let Some(recipient) = recipient {
+            recipient
+        } else {
+            output.push_str("\n\n_Add `for [name]` to the end of the command to give the treasure to a character or place._");
+            return Ok(output);
+        };
+
+        let thing = app_meta
+            .repository
+            .get_by_name(&recipient)
+            .await
+            .map_err(|_| {
+                CommandError::unknown_entity(
+                    &recipient,
+                    format!(r#"There is no character or place named "{}"."#, recipient),
+                )
+            })?;
+
+        let mut inventory = match &thing {
+            Thing::Npc(npc) => npc.inventory.value(),
+            Thing::Place(place) => place.inventory.value(),
+        }
+        .cloned()
+        .unwrap_or_default();
+
+        inventory.add(treasure.into_inventory());
+
+        let diff = match &thing {
+            Thing::Npc(_) => Npc {
+                inventory: inventory.into(),
+                ..Default::default()
+            }
+            .into(),
+            Thing::Place(_) => Place {
+                inventory: inventory.into(),
+                ..Default::default()
+            }
+            .into(),
+        };
+
+        match app_meta.repository.modify(Change::edit(&thing, diff)).await {
+            Ok(Some(thing)) => {
+                output.push_str(&format!(
+                    "\n\n_The treasure was added to the inventory of **{}**. Use `undo` to reverse this._",
+                    thing.name(),
+                ));
+                Ok(output)
+            }
+            _ => Err(CommandError::storage(format!(
+                "Couldn't give the treasure to `{}`.",
+                recipient,
+            ))),
+        }
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl ContextAwareParse for TreasureCommand {
+    async fn parse_input(input: &str, _app_meta: &AppMeta) -> CommandMatches<Self> {
+        if let Some((cr, recipient)) = input
+            .strip_prefix_ci("loot ")
+            .and_then(Self::parse_cr_recipient)
+        {
+            CommandMatches::new_canonical(Self::Individual { cr, recipient })
+        } else if let Some((cr, recipient)) = input
+            .strip_prefix_ci("hoard ")
+            .and_then(Self::parse_cr_recipient)
+        {
+            CommandMatches::new_canonical(Self::Hoard { cr, recipient })
+        } else {
+            CommandMatches::default()
+        }
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Autocomplete for TreasureCommand {
+    async fn autocomplete(input: &str, app_meta: &AppMeta) -> Vec<AutocompleteSuggestion> {
+        if input.is_empty() {
+            return Vec::new();
+        }
+
+        match Self::parse_input(input, app_meta).await.canonical_match {
+            Some(Self::Individual { .. }) => vec![AutocompleteSuggestion::new(
+                input.to_string(),
+                "roll individual treasure",
+            )],
+            Some(Self::Hoard { .. }) => {
+                vec![AutocompleteSuggestion::new(
+                    input.to_string(),
+                    "roll a treasure hoard",
+                )]
+            }
+            None => [
+                ("hoard cr [cr]", "roll a treasure hoard"),
+                ("loot cr [cr]", "roll individual treasure"),
+            ]
+            .into_iter()
+            .filter(|(term, _)| term.starts_with_ci(input))
+            .map(|(term, summary)| AutocompleteSuggestion::new(term, summary))
+            .collect(),
+        }
+    }
+}
+
+impl fmt::Display for TreasureCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let (command, cr, recipient) = match self {
+            Self::Individual { cr, recipient } => ("loot", cr, recipient),
+            Self::Hoard { cr, recipient } => ("hoard", cr, recipient),
+        };
+
+        write!(f, "{} cr {}", command, cr)?;
+
+        if let Some(recipient) = recipient {
+            write!(f, " for {}", recipient)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::assert_autocomplete;
+    use crate::storage::MemoryDataStore;
+    use crate::Event;
+    use tokio_test::block_on;
+
+    #[test]
+    fn parse_input_test() {
+        let app_meta = app_meta();
+
+        assert_eq!(
+            CommandMatches::new_canonical(TreasureCommand::Individual {
+                cr: 5,
+                recipient: None,
+            }),
+            block_on(TreasureCommand::parse_input("loot cr 5", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(TreasureCommand::Hoard {
+                cr: 0,
+                recipient: Some("Thorin Oakenshield".to_string()),
+            }),
+            block_on(TreasureCommand::parse_input(
+                "HOARD CR 1/4 FOR Thorin Oakenshield",
+                &app_meta,
+            )),
+        );
+
+        assert_eq!(
+            CommandMatches::default(),
+            block_on(TreasureCommand::parse_input("loot cr 31", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::default(),
+            block_on(TreasureCommand::parse_input("hoard cr 5 for ", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::default(),
+            block_on(TreasureCommand::parse_input("loot", &app_meta)),
+        );
+    }
+
+    #[test]
+    fn autocomplete_test() {
+        let app_meta = app_meta();
+
+        assert_autocomplete(
+            &[
+                ("hoard cr [cr]", "roll a treasure hoard"),
+                ("loot cr [cr]", "roll individual treasure"),
+            ][..],
+            [
+                block_on(TreasureCommand::autocomplete("h", &app_meta)),
+                block_on(TreasureCommand::autocomplete("L", &app_meta)),
+            ]
+            .concat(),
+        );
+
+        assert_autocomplete(
+            &[("hoard cr 11", "roll a treasure hoard")][..],
+            block_on(TreasureCommand::autocomplete("hoard cr 11", &app_meta)),
+        );
+
+        assert_autocomplete(
+            &[][..],
+            block_on(TreasureCommand::autocomplete("", &app_meta)),
+        );
+    }
+
+    #[test]
+    fn display_test() {
+        let app_meta = app_meta();
+
+        [
+            TreasureCommand::Individual {
+                cr: 5,
+                recipient: None,
+            },
+            TreasureCommand::Hoard {
+                cr: 17,
+                recipient: Some("The Prancing Pony".to_string()),
+            },
+        ]
+        .into_iter()
+        .for_each(|command| {
+            let command_string = command.to_string();
+            assert_ne!("", command_string);
+            assert_eq!(
+                CommandMatches::new_canonical(command),
+                block_on(TreasureCommand::parse_input(&command_string, &app_meta)),
+                "{}",
+                command_string,
+            );
+        });
+    }
+
+    #[test]
+    fn run_test_no_recipient() {
+        let mut app_meta = app_meta();
+
+        let output = block_on(
+            TreasureCommand::Hoard {
+                cr: 11,
+                recipient: None,
+            }
+            .run("", &mut app_meta),
+        )
+        .unwrap();
+
+        assert!(
+            output.starts_with("# Treasure Hoard (CR 11)\n\n**Coins:** "),
+            "{}",
+            output,
+        );
+        assert!(output.contains("**Total Value:** "), "{}", output);
+
+        assert_eq!(
+            Err(CommandError::unknown_entity(
+                "Thorin",
+                r#"There is no character or place named "Thorin"."#,
+            )),
+            block_on(
+                TreasureCommand::Individual {
+                    cr: 0,
+                    recipient: Some("Thorin".to_string()),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+    }
+
+    fn event_dispatcher(_event: Event) {}
+
+    fn app_meta() -> AppMeta {
+        AppMeta::new(MemoryDataStore::default(), &event_dispatcher)
+    }
+}
@@ -0,0 +1,664 @@
+pub use command::TreasureCommand;
+
+mod command;
+
+use crate::reference::MagicItem;
+use crate::utils::capitalize;
+use crate::world::inventory::{Coins, Item};
+use crate::world::Inventory;
+use rand::prelude::*;
+use std::fmt;
+
+/// Loot rolled on the treasure tables of the Dungeon Master's Guide, either the coins carried by
+/// an individual monster or the contents of a hoard.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Treasure {
+    pub coins: Coins,
+    pub gems: Vec<Valuable>,
+    pub art_objects: Vec<Valuable>,
+    pub magic_items: Vec<Item>,
+}
+
+/// A gem or art object with a fixed value in gold pieces.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Valuable {
+    pub name: String,
+    pub value: u32,
+    pub quantity: u32,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Denomination {
+    Cp,
+    Sp,
+    Ep,
+    Gp,
+    Pp,
+}
+
+use Denomination::*;
+
+/// A number of coins, eg. `(4, 6, 100, Cp)` is 4d6 × 100 cp.
+type CoinRoll = (u32, u32, u32, Denomination);
+
+/// A row of a d100 table of individual treasure, up to and including the given roll.
+type IndividualRow = (u8, &'static [CoinRoll]);
+
+/// A row of a d100 hoard table, up to and including the given roll. The valuables are rolled as
+/// `(count, sides, value)`, where the value in gp determines whether they are gems or art
+/// objects. The magic items are rolled as `(count, sides, table)`.
+type HoardRow = (u8, Option<(u32, u32, u32)>, &'static [(u32, u32, char)]);
+
+impl Treasure {
+    /// Roll the coins carried by a single creature of the given challenge rating.
+    pub fn individual(rng: &mut impl Rng, cr: u8) -> Self {
+        let table: &[IndividualRow] = match cr {
+            0..=4 => &[
+                (30, &[(5, 6, 1, Cp)]),
+                (60, &[(4, 6, 1, Sp)]),
+                (70, &[(3, 6, 1, Ep)]),
+                (95, &[(3, 6, 1, Gp)]),
+                (100, &[(1, 6, 1, Pp)]),
+            ],
+            5..=10 => &[
+                (30, &[(4, 6, 100, Cp), (1, 6, 10, Ep)]),
+                (60, &[(6, 6, 10, Sp), (2, 6, 10, Gp)]),
+                (70, &[(3, 6, 10, Ep), (2, 6, 10, Gp)]),
+                (95, &[(4, 6, 10, Gp)]),
+                (100, &[(2, 6, 10, Gp), (3, 6, 1, Pp)]),
+            ],
+            11..=16 => &[
+                (20, &[(4, 6, 100, Sp), (1, 6, 100, Gp)]),
+                (35, &[(1, 6, 100, Ep), (1, 6, 100, Gp)]),
+                (75, &[(2, 6, 100, Gp), (1, 6, 10, Pp)]),
+                (100, &[(2, 6, 100, Gp), (2, 6, 10, Pp)]),
+            ],
+            _ => &[
+                (15, &[(2, 6, 1000, Ep), (8, 6, 100, Gp)]),
+                (55, &[(1, 6, 1000, Gp), (1, 6, 100, Pp)]),
+                (100, &[(1, 6, 1000, Gp), (2, 6, 100, Pp)]),
+            ],
+        };
+
+        let roll = rng.gen_range(1..=100);
+        let (_, coin_rolls) = table.iter().find(|(max, _)| roll <= *max).unwrap();
+
+        let mut treasure = Self::default();
+        treasure.add_coins(rng, coin_rolls);
+        treasure
+    }
+
+    /// Roll the contents of a hoard guarded by a creature of the given challenge rating.
+    pub fn hoard(rng: &mut impl Rng, cr: u8) -> Self {
+        let (coin_rolls, table): (&[CoinRoll], &[HoardRow]) = match cr {
+            0..=4 => (
+                &[(6, 6, 100, Cp), (3, 6, 100, Sp), (2, 6, 10, Gp)],
+                &[
+                    (6, None, &[]),
+                    (16, Some((2, 6, 10)), &[]),
+                    (26, Some((2, 4, 25)), &[]),
+                    (36, Some((2, 6, 50)), &[]),
+                    (44, Some((2, 6, 10)), &[(1, 6, 'A')]),
+                    (52, Some((2, 4, 25)), &[(1, 6, 'A')]),
+                    (60, Some((2, 6, 50)), &[(1, 6, 'A')]),
+                    (65, Some((2, 6, 10)), &[(1, 4, 'B')]),
+                    (70, Some((2, 4, 25)), &[(1, 4, 'B')]),
+                    (75, Some((2, 6, 50)), &[(1, 4, 'B')]),
+                    (78, Some((2, 6, 10)), &[(1, 4, 'C')]),
+                    (80, Some((2, 4, 25)), &[(1, 4, 'C')]),
+                    (85, Some((2, 6, 50)), &[(1, 4, 'C')]),
+                    (92, Some((2, 4, 25)), &[(1, 4, 'F')]),
+                    (97, Some((2, 6, 50)), &[(1, 4, 'F')]),
+                    (99, Some((2, 4, 25)), &[(1, 1, 'G')]),
+                    (100, Some((2, 6, 50)), &[(1, 1, 'G')]),
+                ],
+            ),
+            5..=10 => (
+                &[
+                    (2, 6, 100, Cp),
+                    (2, 6, 1000, Sp),
+                    (6, 6, 100, Gp),
+                    (3, 6, 10, Pp),
+                ],
+                &[
+                    (4, None, &[]),
+                    (10, Some((2, 4, 25)), &[]),
+                    (16, Some((3, 6, 50)), &[]),
+                    (22, Some((3, 6, 100)), &[]),
+                    (28, Some((2, 4, 250)), &[]),
+                    (32, Some((2, 4, 25)), &[(1, 6, 'A')]),
+                    (36, Some((3, 6, 50)), &[(1, 6, 'A')]),
+                    (40, Some((3, 6, 100)), &[(1, 6, 'A')]),
+                    (44, Some((2, 4, 250)), &[(1, 6, 'A')]),
+                    (49, Some((2, 4, 25)), &[(1, 4, 'B')]),
+                    (54, Some((3, 6, 50)), &[(1, 4, 'B')]),
+                    (59, Some((3, 6, 100)), &[(1, 4, 'B')]),
+                    (63, Some((2, 4, 250)), &[(1, 4, 'B')]),
+                    (66, Some((2, 4, 25)), &[(1, 4, 'C')]),
+                    (69, Some((3, 6, 50)), &[(1, 4, 'C')]),
+                    (72, Some((3, 6, 100)), &[(1, 4, 'C')]),
+                    (74, Some((2, 4, 250)), &[(1, 4, 'C')]),
+                    (76, Some((2, 4, 25)), &[(1, 1, 'D')]),
+                    (78, Some((3, 6, 50)), &[(1, 1, 'D')]),
+                    (79, Some((3, 6, 100)), &[(1, 1, 'D')]),
+                    (80, Some((2, 4, 250)), &[(1, 1, 'D')]),
+                    (84, Some((2, 4, 25)), &[(1, 4, 'F')]),
+                    (88, Some((3, 6, 50)), &[(1, 4, 'F')]),
+                    (91, Some((3, 6, 100)), &[(1, 4, 'F')]),
+                    (94, Some((2, 4, 250)), &[(1, 4, 'F')]),
+                    (96, Some((3, 6, 100)), &[(1, 4, 'G')]),
+                    (98, Some((2, 4, 250)), &[(1, 6, 'G')]),
+                    (99, Some((3, 6, 100)), &[(1, 1, 'H')]),
+                    (100, Some((2, 4, 250)), &[(1, 1, 'H')]),
+                ],
+            ),
+            11..=16 => (
+                &[(4, 6, 1000, Gp), (5, 6, 100, Pp)],
+                &[
+                    (3, None, &[]),
+                    (6, Some((2, 4, 250)), &[]),
+                    (9, Some((2, 4, 750)), &[]),
+                    (12, Some((3, 6, 500)), &[]),
+                    (15, Some((3, 6, 1000)), &[]),
+                    (19, Some((2, 4, 250)), &[(1, 4, 'A'), (1, 6, 'B')]),
+                    (23, Some((2, 4, 750)), &[(1, 4, 'A'), (1, 6, 'B')]),
+                    (26, Some((3, 6, 500)), &[(1, 4, 'A'), (1, 6, 'B')]),
+                    (29, Some((3, 6, 1000)), &[(1, 4, 'A'), (1, 6, 'B')]),
+                    (35, Some((2, 4, 250)), &[(1, 6, 'C')]),
+                    (40, Some((2, 4, 750)), &[(1, 6, 'C')]),
+                    (45, Some((3, 6, 500)), &[(1, 6, 'C')]),
+                    (50, Some((3, 6, 1000)), &[(1, 6, 'C')]),
+                    (54, Some((2, 4, 250)), &[(1, 4, 'D')]),
+                    (58, Some((2, 4, 750)), &[(1, 4, 'D')]),
+                    (62, Some((3, 6, 500)), &[(1, 4, 'D')]),
+                    (66, Some((3, 6, 1000)), &[(1, 4, 'D')]),
+                    (68, Some((2, 4, 250)), &[(1, 1, 'E')]),
+                    (70, Some((2, 4, 750)), &[(1, 1, 'E')]),
+                    (72, Some((3, 6, 500)), &[(1, 1, 'E')]),
+                    (74, Some((3, 6, 1000)), &[(1, 1, 'E')]),
+                    (76, Some((2, 4, 250)), &[(1, 1, 'F'), (1, 4, 'G')]),
+                    (78, Some((2, 4, 750)), &[(1, 1, 'F'), (1, 4, 'G')]),
+                    (80, Some((3, 6, 500)), &[(1, 1, 'F'), (1, 4, 'G')]),
+                    (82, Some((3, 6, 1000)), &[(1, 1, 'F'), (1, 4, 'G')]),
+                    (85, Some((2, 4, 250)), &[(1, 4, 'H')]),
+                    (88, Some((2, 4, 750)), &[(1, 4, 'H')]),
+                    (90, Some((3, 6, 500)), &[(1, 4, 'H')]),
+                    (92, Some((3, 6, 1000)), &[(1, 4, 'H')]),
+                    (94, Some((2, 4, 250)), &[(1, 1, 'I')]),
+                    (96, Some((2, 4, 750)), &[(1, 1, 'I')]),
+                    (98, Some((3, 6, 500)), &[(1, 1, 'I')]),
+                    (100, Some((3, 6, 1000)), &[(1, 1, 'I')]),
+                ],
+            ),
+            _ => (
+                &[(12, 6, 1000, Gp), (8, 6, 1000, Pp)],
+                &[
+                    (2, None, &[]),
+                    (5, Some((3, 6, 1000)), &[(1, 8, 'C')]),
+                    (8, Some((1, 10, 2500)), &[(1, 8, 'C')]),
+                    (11, Some((1, 4, 7500)), &[(1, 8, 'C')]),
+                    (14, Some((1, 8, 5000)), &[(1, 8, 'C')]),
+                    (22, Some((3, 6, 1000)), &[(1, 6, 'D')]),
+                    (30, Some((1, 10, 2500)), &[(1, 6, 'D')]),
+                    (38, Some((1, 4, 7500)), &[(1, 6, 'D')]),
+                    (46, Some((1, 8, 5000)), &[(1, 6, 'D')]),
+                    (52, Some((3, 6, 1000)), &[(1, 6, 'E')]),
+                    (58, Some((1, 10, 2500)), &[(1, 6, 'E')]),
+                    (63, Some((1, 4, 7500)), &[(1, 6, 'E')]),
+                    (68, Some((1, 8, 5000)), &[(1, 6, 'E')]),
+                    (69, Some((3, 6, 1000)), &[(1, 4, 'G')]),
+                    (70, Some((1, 10, 2500)), &[(1, 4, 'G')]),
+                    (71, Some((1, 4, 7500)), &[(1, 4, 'G')]),
+                    (72, Some((1, 8, 5000)), &[(1, 4, 'G')]),
+                    (74, Some((3, 6, 1000)), &[(1, 4, 'H')]),
+                    (76, Some((1, 10, 2500)), &[(1, 4, 'H')]),
+                    (78, Some((1, 4, 7500)), &[(1, 4, 'H')]),
+                    (80, Some((1, 8, 5000)), &[(1, 4, 'H')]),
+                    (85, Some((3, 6, 1000)), &[(1, 4, 'I')]),
+                    (90, Some((1, 10, 2500)), &[(1, 4, 'I')]),
+                    (95, Some((1, 4, 7500)), &[(1, 4, 'I')]),
+                    (100, Some((1, 8, 5000)), &[(1, 4, 'I')]),
+                ],
+            ),
+        };
+
+        let mut treasure = Self::default();
+        treasure.add_coins(rng, coin_rolls);
+
+        let roll = rng.gen_range(1..=100);
+        let (_, valuables, magic_items) = table.iter().find(|(max, _, _)| roll <= *max).unwrap();
+
+        if let Some((count, sides, value)) = valuables {
+            for _ in 0..roll_dice(rng, *count, *sides) {
+                treasure.add_valuable(rng, *value);
+            }
+        }
+
+        for (count, sides, table) in magic_items.iter() {
+            for _ in 0..roll_dice(rng, *count, *sides) {
+                treasure.add_magic_item(rng, *table);
+            }
+        }
+
+        treasure
+    }
+
+    /// The combined value of the coins, gems, and art objects in copper pieces. Magic items
+    /// aren't included, since they have no fixed price.
+    pub fn value_cp(&self) -> u64 {
+        self.coins.value_cp()
+            + self
+                .gems
+                .iter()
+                .chain(self.art_objects.iter())
+                .map(|valuable| valuable.value as u64 * valuable.quantity as u64 * 100)
+                .sum::<u64>()
+    }
+
+    pub fn into_inventory(self) -> Inventory {
+        let mut inventory = Inventory {
+            coins: self.coins,
+            items: Vec::new(),
+        };
+
+        self.gems
+            .iter()
+            .chain(self.art_objects.iter())
+            .for_each(|valuable| {
+                inventory.add_item(Item::new(&valuable.to_string(), valuable.quantity))
+            });
+
+        self.magic_items
+            .into_iter()
+            .for_each(|item| inventory.add_item(item));
+
+        inventory
+    }
+
+    fn add_coins(&mut self, rng: &mut impl Rng, coin_rolls: &[CoinRoll]) {
+        for (count, sides, multiplier, denomination) in coin_rolls {
+            let amount = roll_dice(rng, *count, *sides) * multiplier;
+
+            match denomination {
+                Cp => self.coins.cp += amount,
+                Sp => self.coins.sp += amount,
+                Ep => self.coins.ep += amount,
+                Gp => self.coins.gp += amount,
+                Pp => self.coins.pp += amount,
+            }
+        }
+    }
+
+    fn add_valuable(&mut self, rng: &mut impl Rng, value: u32) {
+        let (valuables, names): (&mut Vec<Valuable>, &[&str]) = match value {
+            10 => (
+                &mut self.gems,
+                &[
+                    "azurite",
+                    "banded agate",
+                    "blue quartz",
+                    "eye agate",
+                    "hematite",
+                    "lapis lazuli",
+                    "malachite",
+                    "moss agate",
+                    "obsidian",
+                    "rhodochrosite",
+                    "tiger eye",
+                    "turquoise",
+                ],
+            ),
+            50 => (
+                &mut self.gems,
+                &[
+                    "bloodstone",
+                    "carnelian",
+                    "chalcedony",
+                    "chrysoprase",
+                    "citrine",
+                    "jasper",
+                    "moonstone",
+                    "onyx",
+                    "quartz",
+                    "sardonyx",
+                    "star rose quartz",
+                    "zircon",
+                ],
+            ),
+            100 => (
+                &mut self.gems,
+                &[
+                    "amber",
+                    "amethyst",
+                    "chrysoberyl",
+                    "coral",
+                    "garnet",
+                    "jade",
+                    "jet",
+                    "pearl",
+                    "spinel",
+                    "tourmaline",
+                ],
+            ),
+            500 => (
+                &mut self.gems,
+                &[
+                    "alexandrite",
+                    "aquamarine",
+                    "black pearl",
+                    "blue spinel",
+                    "peridot",
+                    "topaz",
+                ],
+            ),
+            1000 => (
+                &mut self.gems,
+                &[
+                    "black opal",
+                    "blue sapphire",
+                    "emerald",
+                    "fire opal",
+                    "opal",
+                    "star ruby",
+                    "star sapphire",
+                    "yellow sapphire",
+                ],
+            ),
+            5000 => (
+                &mut self.gems,
+                &["black sapphire", "diamond", "jacinth", "ruby"],
+            ),
+            25 => (
+                &mut self.art_objects,
+                &[
+                    "silver ewer",
+                    "carved bone statuette",
+                    "small gold bracelet",
+                    "cloth-of-gold vestments",
+                    "black velvet mask stitched with silver thread",
+                    "copper chalice with silver filigree",
+                    "pair of engraved bone dice",
+                    "small mirror set in a painted wooden frame",
+                    "embroidered silk handkerchief",
+                    "gold locket with a painted portrait inside",
+                ],
+            ),
+            250 => (
+                &mut self.art_objects,
+                &[
+                    "gold ring set with bloodstones",
+                    "carved ivory statuette",
+                    "large gold bracelet",
+                    "silver necklace with a gemstone pendant",
+                    "bronze crown",
+                    "silk robe with gold embroidery",
+                    "large well-made tapestry",
+                    "brass mug with jade inlay",
+                    "box of turquoise animal figurines",
+                    "gold bird cage with electrum filigree",
+                ],
+            ),
+            750 => (
+                &mut self.art_objects,
+                &[
+                    "silver chalice set with moonstones",
+                    "silver-plated steel longsword with jet set in the hilt",
+                    "carved harp of exotic wood with ivory inlay",
+                    "small gold idol",
+                    "gold dragon comb set with red garnets as eyes",
+                    "bottle stopper cork embossed with gold leaf",
+                    "ceremonial electrum dagger with a black pearl in the pommel",
+                    "silver and gold brooch",
+                    "obsidian statuette with gold fittings and inlay",
+                    "painted gold war mask",
+                ],
+            ),
+            2500 => (
+                &mut self.art_objects,
+                &[
+                    "fine gold chain set with a fire opal",
+                    "old masterpiece painting",
+                    "embroidered silk and velvet mantle set with moonstones",
+                    "platinum bracelet set with a sapphire",
+                    "embroidered glove set with jewel chips",
+                    "jeweled anklet",
+                    "gold music box",
+                    "gold circlet set with four aquamarines",
+                    "eye patch with a mock eye of blue sapphire and moonstone",
+                    "necklace string of small pink pearls",
+                ],
+            ),
+            7500 => (
+                &mut self.art_objects,
+                &[
+                    "jeweled gold crown",
+                    "jeweled platinum ring",
+                    "small gold statuette set with rubies",
+                    "gold cup set with emeralds",
+                    "gold jewelry box with platinum filigree",
+                    "painted gold child's sarcophagus",
+                    "jade game board with solid gold playing pieces",
+                    "bejeweled ivory drinking horn with gold filigree",
+                ],
+            ),
+            _ => unreachable!("No valuables are worth {} gp.", value),
+        };
+
+        let name = names.choose(rng).unwrap();
+
+        if let Some(valuable) = valuables.iter_mut().find(|v| v.name == *name) {
+            valuable.quantity += 1;
+        } else {
+            valuables.push(Valuable {
+                name: name.to_string(),
+                value,
+                quantity: 1,
+            });
+        }
+    }
+
+    /// Pick a random item from the SRD of the rarity corresponding to a magic item table. If
+    /// the SRD has no items of that rarity, a placeholder is used instead.
+    fn add_magic_item(&mut self, rng: &mut impl Rng, table: char) {
+        let rarity = match table {
+            'A' => "common",
+            'B' | 'F' => "uncommon",
+            'C' | 'G' => "rare",
+            'D' | 'H' => "very rare",
+            _ => "legendary",
+        };
+
+        let candidates: Vec<MagicItem> = MagicItem::get_all()
+            .filter(|item| item.get_rarity() == Some(rarity))
+            .collect();
+
+        let name = candidates
+            .choose(rng)
+            .map(|item| item.get_name().to_string())
+            .unwrap_or_else(|| format!("{} magic item", capitalize(rarity)));
+
+        if let Some(item) = self.magic_items.iter_mut().find(|item| item.name == name) {
+            item.quantity += 1;
+        } else {
+            self.magic_items.push(Item::new(&name, 1));
+        }
+    }
+}
+
+impl fmt::Display for Treasure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "**Coins:** {}", self.coins)?;
+
+        if !self.gems.is_empty() {
+            write!(f, "\\\n**Gems:** {}", list(&self.gems))?;
+        }
+
+        if !self.art_objects.is_empty() {
+            write!(f, "\\\n**Art Objects:** {}", list(&self.art_objects))?;
+        }
+
+        if !self.magic_items.is_empty() {
+            write!(
+                f,
+                "\\\n**Magic Items:** {}",
+                self.magic_items
+                    .iter()
+                    .map(|item| {
+                        if item.quantity == 1 {
+                            format!("`{}`", item.name)
+                        } else {
+                            format!("`{}` ×{}", item.name, item.quantity)
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            )?;
+        }
+
+        let value_cp = self.value_cp();
+        write!(f, "\\\n**Total Value:** {}", value_cp / 100)?;
+
+        let remainder_cp = value_cp % 100;
+        if remainder_cp > 0 {
+            write!(f, ".{:02}", remainder_cp)?;
+        }
+
+        write!(f, " gp")?;
+
+        if !self.magic_items.is_empty() {
+            write!(f, " plus magic items")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Valuable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({} gp)", self.name, self.value)
+    }
+}
+
+fn list(valuables: &[Valuable]) -> String {
+    valuables
+        .iter()
+        .map(|valuable| {
+            if valuable.quantity == 1 {
+                valuable.to_string()
+            } else {
+                format!("{} ×{}", valuable, valuable.quantity)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn roll_dice(rng: &mut impl Rng, count: u32, sides: u32) -> u32 {
+    (0..count).map(|_| rng.gen_range(1..=sides)).sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn individual_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        for _ in 0..100 {
+            let treasure = Treasure::individual(&mut rng, 0);
+            assert!(!treasure.coins.is_empty());
+            assert!(treasure.value_cp() <= 6000, "{:?}", treasure);
+            assert!(treasure.gems.is_empty());
+            assert!(treasure.art_objects.is_empty());
+            assert!(treasure.magic_items.is_empty());
+
+            let treasure = Treasure::individual(&mut rng, 30);
+            assert!(treasure.value_cp() >= 100_000, "{:?}", treasure);
+        }
+    }
+
+    #[test]
+    fn hoard_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let (mut gems, mut art_objects, mut magic_items) = (0, 0, 0);
+
+        for cr in [0, 5, 11, 17] {
+            for _ in 0..100 {
+                let treasure = Treasure::hoard(&mut rng, cr);
+                assert!(treasure.coins.gp > 0, "{:?}", treasure);
+
+                gems += treasure.gems.len();
+                art_objects += treasure.art_objects.len();
+                magic_items += treasure.magic_items.len();
+            }
+        }
+
+        assert!(gems > 0);
+        assert!(art_objects > 0);
+        assert!(magic_items > 0);
+    }
+
+    #[test]
+    fn into_inventory_test() {
+        let treasure = Treasure {
+            coins: Coins {
+                gp: 120,
+                ..Default::default()
+            },
+            gems: vec![Valuable {
+                name: "onyx".to_string(),
+                value: 50,
+                quantity: 2,
+            }],
+            art_objects: vec![Valuable {
+                name: "bronze crown".to_string(),
+                value: 250,
+                quantity: 1,
+            }],
+            magic_items: vec![Item::new("Potion of Healing", 3)],
+        };
+
+        assert_eq!(47000, treasure.value_cp());
+
+        assert_eq!(
+            "**Coins:** 120 gp\\\n**Items:** onyx (50 gp) ×2, bronze crown (250 gp), Potion of Healing ×3",
+            treasure.into_inventory().display().to_string(),
+        );
+    }
+
+    #[test]
+    fn display_test() {
+        let mut treasure = Treasure {
+            coins: Coins {
+                cp: 17,
+                sp: 3,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(
+            "**Coins:** 17 cp, 3 sp\\\n**Total Value:** 0.47 gp",
+            treasure.to_string(),
+        );
+
+        treasure.coins.cp = 0;
+        treasure.gems.push(Valuable {
+            name: "onyx".to_string(),
+            value: 50,
+            quantity: 2,
+        });
+        treasure.art_objects.push(Valuable {
+            name: "bronze crown".to_string(),
+            value: 250,
+            quantity: 1,
+        });
+        treasure.magic_items.push(Item::new("Flame Tongue", 1));
+
+        assert_eq!(
+            "**Coins:** 3 sp\\\n**Gems:** onyx (50 gp) ×2\\\n**Art Objects:** bronze crown (250 gp)\\\n**Magic Items:** `Flame Tongue`\\\n**Total Value:** 350.30 gp plus magic items",
+            treasure.to_string(),
+        );
+    }
+}
@@ -0,0 +1,947 @@
+use super::haggle::Haggle;
+use crate::app::{
+    AppMeta, Autocomplete, AutocompleteSuggestion, CommandError, CommandMatches, ContextAwareParse,
+    Runnable,
+};
+use crate::config::Config;
+use crate::reference::Item as Equipment;
+use crate::storage::{Change, KeyValue};
+use crate::utils::{quoted_words, CaseInsensitiveStr};
+use crate::world::inventory::{Coins, Item};
+use crate::world::place::economy_at;
+use crate::world::{parse_equipment, party_location, Inventory, Npc, Place, Thing};
+use async_trait::async_trait;
+use std::fmt;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TreasuryCommand {
+    Add(Coins),
+    Buy {
+        item: Equipment,
+        quantity: u32,
+        shop: Option<String>,
+    },
+    Haggle {
+        item: Equipment,
+        quantity: u32,
+        shop: String,
+    },
+    Show,
+    Spend(Coins),
+}
+
+impl TreasuryCommand {
+    /// Parse the part of the command following "buy", eg. "2 daggers from Ye Olde Shoppe".
+    async fn parse_buy(input: &str, app_meta: &AppMeta) -> Option<Self> {
+        parse_purchase(input, "from", app_meta)
+            .await
+            .or_else(|| parse_equipment(input).map(|(quantity, item)| (item, quantity, None)))
+            .map(|(item, quantity, shop)| Self::Buy {
+                item,
+                quantity,
+                shop,
+            })
+    }
+
+    /// Parse the part of the command following "haggle", eg. "a shield at Ye Olde Shoppe".
+    async fn parse_haggle(input: &str, app_meta: &AppMeta) -> Option<Self> {
+        parse_purchase(input, "at", app_meta)
+            .await
+            .and_then(|(item, quantity, shop)| {
+                shop.map(|shop| Self::Haggle {
+                    item,
+                    quantity,
+                    shop,
+                })
+            })
+    }
+
+    /// Haggle with the shopkeeper over the price of an item in their shop, then buy it at the
+    /// agreed price. The purchase and the shopkeeper's change of heart are undone together.
+    async fn haggle(
+        item: Equipment,
+        quantity: u32,
+        shop: String,
+        mut treasury: Coins,
+        app_meta: &mut AppMeta,
+    ) -> Result<String, CommandError> {
+        let bought = Item::equipment(&item, quantity);
+        let (place, inventory) = take_from_shop(&shop, &item, quantity, app_meta).await?;
+        let (asking, economy) = local_cost(&item, quantity, Some(&place), app_meta).await?;
+
+        let shopkeeper = find_shopkeeper(&place, app_meta).await.ok_or_else(|| {
+            CommandError::invalid_state(
+                format!("There's nobody at {} to haggle with.", place.name,),
+            )
+        })?;
+        let name = shopkeeper.name.to_string();
+
+        let charisma = Config::load(app_meta).await.charisma;
+        let haggle = Haggle::roll(&mut app_meta.rng, charisma, &shopkeeper);
+        let price = haggle.price(asking);
+        let attitude = haggle.attitude(shopkeeper.attitude.value().copied());
+
+        treasury.spend(price).map_err(|_| {
+            CommandError::invalid_state(format!(
+                "{} The treasury {}, which isn't enough to buy {} for {}.",
+                haggle.describe(&name),
+                describe_holdings(&treasury),
+                bought,
+                price,
+            ))
+        })?;
+
+        app_meta
+            .repository
+            .transaction(vec![
+                Change::Edit {
+                    name: place.name.to_string(),
+                    uuid: place.uuid.as_ref().map(|uuid| uuid.as_ref().to_owned()),
+                    diff: Place {
+                        inventory: inventory.into(),
+                        ..Default::default()
+                    }
+                    .into(),
+                },
+                Change::Edit {
+                    name: name.clone(),
+                    uuid: shopkeeper
+                        .uuid
+                        .as_ref()
+                        .map(|uuid| uuid.as_ref().to_owned()),
+                    diff: Npc {
+                        attitude: attitude.into(),
+                        ..Default::default()
+                    }
+                    .into(),
+                },
+                Change::SetKeyValue {
+                    key_value: KeyValue::Treasury(Some(treasury)),
+                },
+            ])
+            .await
+            .map_err(|_| CommandError::storage(format!("Couldn't buy from `{}`.", place.name)))?;
+
+        Ok(format!(
+            "# Haggling with {name}\n\n**Asking price:** {}{}\\\n**Party:** {}\\\n**{name}:** {}\n\n{} Bought {} from **{}** for {}. The treasury now holds {}.\n\n_{name} is {} toward the party. Use `undo` to reverse this._",
+            asking,
+            describe_economy(&item, economy.as_ref()),
+            haggle.party,
+            haggle.shopkeeper,
+            haggle.describe(&name),
+            bought,
+            place.name,
+            price,
+            treasury,
+            attitude,
+            name = name,
+        ))
+    }
+}
+
+/// Split a purchase such as "2 daggers from Ye Olde Shoppe" at the given word into the item and
+/// the shop. A shop that exists is preferred if the word appears more than once.
+async fn parse_purchase(
+    input: &str,
+    separator: &str,
+    app_meta: &AppMeta,
+) -> Option<(Equipment, u32, Option<String>)> {
+    let mut purchase = None;
+
+    for word in quoted_words(input)
+        .skip(1)
+        .filter(|word| word.as_str().eq_ci(separator))
+    {
+        let (item, shop) = (
+            &input[..word.range().start],
+            input[word.range().end..].trim(),
+        );
+
+        if let (Some((quantity, item)), false) = (parse_equipment(item), shop.is_empty()) {
+            if let Ok(thing) = app_meta.repository.get_by_name(shop).await {
+                purchase = Some((item, quantity, Some(thing.name().to_string())));
+                break;
+            } else if purchase.is_none() {
+                purchase = Some((item, quantity, Some(shop.to_string())));
+            }
+        }
+    }
+
+    purchase
+}
+
+fn cost(item: &Equipment, quantity: u32) -> Result<Coins, CommandError> {
+    item.get_cost_cp()
+        .map(|cost| Coins::from_cp(cost as u64 * quantity as u64))
+        .ok_or_else(|| format!("{} doesn't have a price in the SRD.", item.get_name()).into())
+}
+
+/// The price of the items where they're bought: at the shop if there is one, otherwise wherever
+/// the party is. Also returns the place whose economy changed the price, if any.
+async fn local_cost(
+    item: &Equipment,
+    quantity: u32,
+    shop: Option<&Place>,
+    app_meta: &AppMeta,
+) -> Result<(Coins, Option<Place>), CommandError> {
+    let cost = cost(item, quantity)?;
+
+    let place = if let Some(shop) = shop {
+        Some(shop.clone())
+    } else {
+        party_location(app_meta).await
+    };
+
+    let economy = if let Some(place) = place {
+        economy_at(&place, app_meta)
+            .await
+            .filter(|economy| economy.price_modifier(item) != 0)
+    } else {
+        None
+    };
+
+    Ok(if let Some(economy) = economy {
+        (economy.local_price(item, cost), Some(economy))
+    } else {
+        (cost, None)
+    })
+}
+
+/// A note on how the local economy changed the price, eg. " (+20% in **Waterdeep**)".
+fn describe_economy(item: &Equipment, economy: Option<&Place>) -> String {
+    economy
+        .map(|economy| {
+            format!(
+                " ({:+}% in **{}**)",
+                economy.price_modifier(item),
+                economy.name,
+            )
+        })
+        .unwrap_or_default()
+}
+
+/// Check that a shop has the items in stock, returning the shop and its inventory without them.
+async fn take_from_shop(
+    shop: &str,
+    item: &Equipment,
+    quantity: u32,
+    app_meta: &AppMeta,
+) -> Result<(Place, Inventory), CommandError> {
+    let place = match app_meta.repository.get_by_name(shop).await {
+        Ok(Thing::Place(place)) => place,
+        _ => {
+            return Err(CommandError::unknown_entity(
+                shop,
+                format!(r#"There is no shop named "{}"."#, shop),
+            ))
+        }
+    };
+
+    let mut inventory = place.inventory.value().cloned().unwrap_or_default();
+
+    match inventory.remove_item(item.get_name(), quantity) {
+        Ok(_) => Ok((place, inventory)),
+        Err(0) => Err(CommandError::unknown_entity(
+            item.get_name(),
+            format!("{} doesn't have any {}.", place.name, item.get_name()),
+        )),
+        Err(held) => Err(CommandError::invalid_state(format!(
+            "{} only has {}.",
+            place.name,
+            Item::equipment(item, held),
+        ))),
+    }
+}
+
+/// The first character found working at a place, among both the journal and recent entries.
+async fn find_shopkeeper(place: &Place, app_meta: &AppMeta) -> Option<Npc> {
+    let uuid = place.uuid.as_ref()?;
+    let journal = app_meta.repository.journal().await.unwrap_or_default();
+
+    let shopkeeper = journal
+        .iter()
+        .chain(app_meta.repository.recent())
+        .filter(|thing| !thing.is_archived() && thing.fate().is_none() && !thing.is_pc())
+        .find_map(|thing| match thing {
+            Thing::Npc(npc) if npc.location_uuid.value() == Some(uuid) => Some(npc.clone()),
+            _ => None,
+        });
+
+    shopkeeper
+}
+
+fn describe_holdings(treasury: &Coins) -> String {
+    if treasury.is_empty() {
+        "is empty".to_string()
+    } else {
+        format!("only holds {}", treasury)
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Runnable for TreasuryCommand {
+    async fn run(self, _input: &str, app_meta: &mut AppMeta) -> Result<String, CommandError> {
+        let mut treasury = super::load(app_meta).await;
+
+        let response = match self {
+            Self::Haggle {
+                item,
+                quantity,
+                shop,
+            } => return Self::haggle(item, quantity, shop, treasury, app_meta).await,
+            Self::Show => {
+                return if treasury.is_empty() {
+                    Ok("# Treasury\n\n*The treasury is empty. Use `treasury add [amount]` to add coins, eg. `treasury add 150 gp`.*".to_string())
+                } else {
+                    Ok(format!(
+                        "# Treasury\n\n**Coins:** {}\\\n**Total Value:** {}",
+                        treasury,
+                        Coins::from_cp(treasury.value_cp()),
+                    ))
+                };
+            }
+            Self::Add(coins) => {
+                treasury.add(coins);
+
+                format!(
+                    "Added {} to the treasury, which now holds {}. Use `undo` to reverse this.",
+                    coins, treasury,
+                )
+            }
+            Self::Spend(coins) => {
+                treasury.spend(coins).map_err(|_| {
+                    CommandError::invalid_state(format!(
+                        "The treasury {}, which isn't enough to spend {}.",
+                        describe_holdings(&treasury),
+                        coins,
+                    ))
+                })?;
+
+                format!(
+                    "Spent {} from the treasury, which now holds {}. Use `undo` to reverse this.",
+                    coins, treasury,
+                )
+            }
+            Self::Buy {
+                item,
+                quantity,
+                shop,
+            } => {
+                let bought = Item::equipment(&item, quantity);
+
+                // Check the shop's stock before touching the treasury.
+                let shop = if let Some(shop) = shop {
+                    Some(take_from_shop(&shop, &item, quantity, app_meta).await?)
+                } else {
+                    None
+                };
+
+                let (cost, economy) = local_cost(
+                    &item,
+                    quantity,
+                    shop.as_ref().map(|(place, _)| place),
+                    app_meta,
+                )
+                .await?;
+                let note = describe_economy(&item, economy.as_ref());
+
+                treasury.spend(cost).map_err(|_| {
+                    CommandError::invalid_state(format!(
+                        "The treasury {}, which isn't enough to buy {} for {}.",
+                        describe_holdings(&treasury),
+                        bought,
+                        cost,
+                    ))
+                })?;
+
+                if let Some((place, inventory)) = shop {
+                    let name = place.name.to_string();
+
+                    app_meta
+                        .repository
+                        .modify(Change::Edit {
+                            name: name.clone(),
+                            uuid: place.uuid.as_ref().map(|uuid| uuid.as_ref().to_owned()),
+                            diff: Place {
+                                inventory: inventory.into(),
+                                ..Default::default()
+                            }
+                            .into(),
+                        })
+                        .await
+                        .map_err(|_| {
+                            CommandError::storage(format!("Couldn't buy from `{}`.", name))
+                        })?;
+
+                    format!(
+                        "Bought {} from **{}** for {}{}. The treasury now holds {}. Use `undo` twice to reverse this.",
+                        bought, name, cost, note, treasury,
+                    )
+                } else {
+                    format!(
+                        "Bought {} for {}{}. The treasury now holds {}. Use `undo` to reverse this.",
+                        bought, cost, note, treasury,
+                    )
+                }
+            }
+        };
+
+        app_meta
+            .repository
+            .modify(Change::SetKeyValue {
+                key_value: KeyValue::Treasury(Some(treasury)),
+            })
+            .await
+            .map(|_| response)
+            .map_err(|_| CommandError::storage("Unable to save the treasury."))
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl ContextAwareParse for TreasuryCommand {
+    async fn parse_input(input: &str, app_meta: &AppMeta) -> CommandMatches<Self> {
+        let parse_coins = |s: &str| s.parse::<Coins>().ok().filter(|c| !c.is_empty());
+
+        if input.eq_ci("treasury") {
+            CommandMatches::new_canonical(Self::Show)
+        } else if let Some(coins) = input.strip_prefix_ci("treasury add ").and_then(parse_coins) {
+            CommandMatches::new_canonical(Self::Add(coins))
+        } else if let Some(coins) = input
+            .strip_prefix_ci("treasury spend ")
+            .and_then(parse_coins)
+        {
+            CommandMatches::new_canonical(Self::Spend(coins))
+        } else if let Some(input) = input.strip_prefix_ci("buy ") {
+            Self::parse_buy(input, app_meta)
+                .await
+                .map(CommandMatches::new_canonical)
+                .unwrap_or_default()
+        } else if let Some(input) = input.strip_prefix_ci("haggle ") {
+            Self::parse_haggle(input, app_meta)
+                .await
+                .map(CommandMatches::new_canonical)
+                .unwrap_or_default()
+        } else {
+            CommandMatches::default()
+        }
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Autocomplete for TreasuryCommand {
+    async fn autocomplete(input: &str, _app_meta: &AppMeta) -> Vec<AutocompleteSuggestion> {
+        if input.is_empty() {
+            return Vec::new();
+        }
+
+        [
+            ("buy [item]", "buy equipment with the treasury"),
+            ("buy [item] from [shop]", "buy equipment from a shop"),
+            (
+                "haggle [item] at [shop]",
+                "haggle with a shopkeeper over a price",
+            ),
+            ("treasury", "show the party's coins"),
+            ("treasury add [amount]", "add coins to the treasury"),
+            ("treasury spend [amount]", "spend coins from the treasury"),
+        ]
+        .into_iter()
+        .filter(|(term, _)| term.starts_with_ci(input))
+        .map(|(term, summary)| AutocompleteSuggestion::new(term, summary))
+        .collect()
+    }
+}
+
+impl fmt::Display for TreasuryCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::Add(coins) => write!(f, "treasury add {}", coins),
+            Self::Buy {
+                item,
+                quantity,
+                shop,
+            } => {
+                write!(f, "buy {} {}", quantity, item.get_name())?;
+
+                if let Some(shop) = shop {
+                    write!(f, " from {}", shop)?;
+                }
+
+                Ok(())
+            }
+            Self::Haggle {
+                item,
+                quantity,
+                shop,
+            } => write!(f, "haggle {} {} at {}", quantity, item.get_name(), shop),
+            Self::Show => write!(f, "treasury"),
+            Self::Spend(coins) => write!(f, "treasury spend {}", coins),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::assert_autocomplete;
+    use crate::storage::MemoryDataStore;
+    use crate::world::{Inventory, PlaceUuid};
+    use crate::Event;
+    use tokio_test::block_on;
+
+    #[test]
+    fn parse_input_test() {
+        let app_meta = app_meta();
+
+        assert_eq!(
+            CommandMatches::new_canonical(TreasuryCommand::Show),
+            block_on(TreasuryCommand::parse_input("TREASURY", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(TreasuryCommand::Add(Coins {
+                gp: 150,
+                ..Default::default()
+            })),
+            block_on(TreasuryCommand::parse_input(
+                "treasury add 150 gp",
+                &app_meta
+            )),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(TreasuryCommand::Spend(Coins {
+                sp: 5,
+                pp: 3,
+                ..Default::default()
+            })),
+            block_on(TreasuryCommand::parse_input(
+                "treasury spend 3 pp, 5 sp",
+                &app_meta
+            )),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(TreasuryCommand::Buy {
+                item: Equipment::Longsword,
+                quantity: 2,
+                shop: None,
+            }),
+            block_on(TreasuryCommand::parse_input("buy 2 longswords", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(TreasuryCommand::Buy {
+                item: Equipment::Shield,
+                quantity: 1,
+                shop: Some("Ye Olde Shoppe".to_string()),
+            }),
+            block_on(TreasuryCommand::parse_input(
+                "buy a shield from Ye Olde Shoppe",
+                &app_meta
+            )),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(TreasuryCommand::Haggle {
+                item: Equipment::Shield,
+                quantity: 1,
+                shop: "Ye Olde Shoppe".to_string(),
+            }),
+            block_on(TreasuryCommand::parse_input(
+                "haggle a shield at Ye Olde Shoppe",
+                &app_meta
+            )),
+        );
+
+        assert_eq!(
+            CommandMatches::default(),
+            block_on(TreasuryCommand::parse_input("haggle a shield", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::default(),
+            block_on(TreasuryCommand::parse_input("treasury add 0 gp", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::default(),
+            block_on(TreasuryCommand::parse_input(
+                "treasury spend lots",
+                &app_meta
+            )),
+        );
+
+        assert_eq!(
+            CommandMatches::default(),
+            block_on(TreasuryCommand::parse_input("buy a potato", &app_meta)),
+        );
+    }
+
+    #[test]
+    fn autocomplete_test() {
+        let app_meta = app_meta();
+
+        assert_autocomplete(
+            &[
+                ("treasury", "show the party's coins"),
+                ("treasury add [amount]", "add coins to the treasury"),
+                ("treasury spend [amount]", "spend coins from the treasury"),
+            ][..],
+            block_on(TreasuryCommand::autocomplete("TREAS", &app_meta)),
+        );
+
+        assert_autocomplete(
+            &[
+                ("buy [item]", "buy equipment with the treasury"),
+                ("buy [item] from [shop]", "buy equipment from a shop"),
+            ][..],
+            block_on(TreasuryCommand::autocomplete("bu", &app_meta)),
+        );
+
+        assert_autocomplete(
+            &[(
+                "haggle [item] at [shop]",
+                "haggle with a shopkeeper over a price",
+            )][..],
+            block_on(TreasuryCommand::autocomplete("HAG", &app_meta)),
+        );
+
+        assert_autocomplete(
+            &[][..],
+            block_on(TreasuryCommand::autocomplete("", &app_meta)),
+        );
+    }
+
+    #[test]
+    fn display_test() {
+        let app_meta = app_meta();
+
+        [
+            TreasuryCommand::Add(Coins {
+                cp: 5,
+                gp: 150,
+                ..Default::default()
+            }),
+            TreasuryCommand::Buy {
+                item: Equipment::Longsword,
+                quantity: 2,
+                shop: None,
+            },
+            TreasuryCommand::Buy {
+                item: Equipment::Shield,
+                quantity: 1,
+                shop: Some("Ye Olde Shoppe".to_string()),
+            },
+            TreasuryCommand::Haggle {
+                item: Equipment::Dagger,
+                quantity: 3,
+                shop: "Ye Olde Shoppe".to_string(),
+            },
+            TreasuryCommand::Show,
+            TreasuryCommand::Spend(Coins {
+                pp: 3,
+                ..Default::default()
+            }),
+        ]
+        .into_iter()
+        .for_each(|command| {
+            let command_string = command.to_string();
+            assert_ne!("", command_string);
+            assert_eq!(
+                CommandMatches::new_canonical(command),
+                block_on(TreasuryCommand::parse_input(&command_string, &app_meta)),
+                "{}",
+                command_string,
+            );
+        });
+    }
+
+    #[test]
+    fn run_test() {
+        let mut app_meta = app_meta();
+
+        assert_eq!(
+            Ok("# Treasury\n\n*The treasury is empty. Use `treasury add [amount]` to add coins, eg. `treasury add 150 gp`.*".to_string()),
+            block_on(TreasuryCommand::Show.run("", &mut app_meta)),
+        );
+
+        assert_eq!(
+            Ok("Added 5 gp, 2 pp to the treasury, which now holds 5 gp, 2 pp. Use `undo` to reverse this.".to_string()),
+            block_on(
+                TreasuryCommand::Add(Coins {
+                    gp: 5,
+                    pp: 2,
+                    ..Default::default()
+                })
+                .run("", &mut app_meta)
+            ),
+        );
+
+        assert_eq!(
+            Ok("Bought Longsword for 15 gp. The treasury now holds 1 pp. Use `undo` to reverse this.".to_string()),
+            block_on(
+                TreasuryCommand::Buy {
+                    item: Equipment::Longsword,
+                    quantity: 1,
+                    shop: None,
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+
+        assert_eq!(
+            Ok("Spent 3 sp from the treasury, which now holds 7 sp, 9 gp. Use `undo` to reverse this.".to_string()),
+            block_on(
+                TreasuryCommand::Spend(Coins {
+                    sp: 3,
+                    ..Default::default()
+                })
+                .run("", &mut app_meta)
+            ),
+        );
+
+        assert_eq!(
+            Ok("# Treasury\n\n**Coins:** 7 sp, 9 gp\\\n**Total Value:** 7 sp, 9 gp".to_string()),
+            block_on(TreasuryCommand::Show.run("", &mut app_meta)),
+        );
+
+        assert_eq!(
+            Err(CommandError::invalid_state(
+                "The treasury only holds 7 sp, 9 gp, which isn't enough to spend 1 pp."
+            )),
+            block_on(
+                TreasuryCommand::Spend(Coins {
+                    pp: 1,
+                    ..Default::default()
+                })
+                .run("", &mut app_meta)
+            ),
+        );
+
+        assert_eq!(
+            Err(CommandError::invalid_state(
+                "The treasury only holds 7 sp, 9 gp, which isn't enough to buy Longsword for 15 gp."
+            )),
+            block_on(
+                TreasuryCommand::Buy {
+                    item: Equipment::Longsword,
+                    quantity: 1,
+                    shop: None,
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+
+        assert_eq!(
+            Err(CommandError::unknown_entity(
+                "Ye Olde Shoppe",
+                r#"There is no shop named "Ye Olde Shoppe"."#,
+            )),
+            block_on(
+                TreasuryCommand::Buy {
+                    item: Equipment::Shield,
+                    quantity: 1,
+                    shop: Some("Ye Olde Shoppe".to_string()),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+
+        let mut inventory = Inventory::default();
+        inventory.add_item(Item::equipment(&Equipment::Shield, 1));
+
+        block_on(
+            app_meta.repository.modify(Change::Create {
+                thing: Place {
+                    name: "Ye Olde Shoppe".into(),
+                    inventory: inventory.into(),
+                    ..Default::default()
+                }
+                .into(),
+            }),
+        )
+        .unwrap();
+
+        block_on(
+            TreasuryCommand::Add(Coins {
+                gp: 20,
+                ..Default::default()
+            })
+            .run("", &mut app_meta),
+        )
+        .unwrap();
+
+        assert_eq!(
+            Err(CommandError::invalid_state(
+                "Ye Olde Shoppe only has Shield."
+            )),
+            block_on(
+                TreasuryCommand::Buy {
+                    item: Equipment::Shield,
+                    quantity: 2,
+                    shop: Some("Ye Olde Shoppe".to_string()),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+
+        assert_eq!(
+            Ok("Bought Shield from **Ye Olde Shoppe** for 10 gp. The treasury now holds 7 sp, 19 gp. Use `undo` twice to reverse this.".to_string()),
+            block_on(
+                TreasuryCommand::Buy {
+                    item: Equipment::Shield,
+                    quantity: 1,
+                    shop: Some("Ye Olde Shoppe".to_string()),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+
+        assert_eq!(
+            Err(CommandError::unknown_entity(
+                "Shield",
+                "Ye Olde Shoppe doesn't have any Shield."
+            )),
+            block_on(
+                TreasuryCommand::Buy {
+                    item: Equipment::Shield,
+                    quantity: 1,
+                    shop: Some("Ye Olde Shoppe".to_string()),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+    }
+
+    #[test]
+    fn run_test_haggle() {
+        let mut app_meta = app_meta();
+
+        let mut inventory = Inventory::default();
+        inventory.add_item(Item::equipment(&Equipment::Shield, 1));
+
+        block_on(
+            app_meta.repository.modify(Change::CreateAndSave {
+                thing: Place {
+                    name: "Ye Olde Shoppe".into(),
+                    inventory: inventory.into(),
+                    ..Default::default()
+                }
+                .into(),
+            }),
+        )
+        .unwrap();
+
+        let haggle = TreasuryCommand::Haggle {
+            item: Equipment::Shield,
+            quantity: 1,
+            shop: "Ye Olde Shoppe".to_string(),
+        };
+
+        assert_eq!(
+            Err(CommandError::invalid_state(
+                "There's nobody at Ye Olde Shoppe to haggle with."
+            )),
+            block_on(haggle.clone().run("", &mut app_meta)),
+        );
+
+        let uuid = block_on(app_meta.repository.get_by_name("Ye Olde Shoppe"))
+            .unwrap()
+            .uuid()
+            .copied()
+            .unwrap();
+
+        block_on(
+            app_meta.repository.modify(Change::Create {
+                thing: Npc {
+                    name: "Gottfried".into(),
+                    location_uuid: PlaceUuid::from(uuid).into(),
+                    ..Default::default()
+                }
+                .into(),
+            }),
+        )
+        .unwrap();
+
+        let error = block_on(haggle.clone().run("", &mut app_meta)).unwrap_err();
+        assert!(
+            error
+                .message()
+                .contains(" The treasury is empty, which isn't enough to buy Shield for "),
+            "{}",
+            error.message(),
+        );
+
+        block_on(
+            TreasuryCommand::Add(Coins {
+                gp: 10,
+                ..Default::default()
+            })
+            .run("", &mut app_meta),
+        )
+        .unwrap();
+
+        let output = block_on(haggle.clone().run("", &mut app_meta)).unwrap();
+        assert!(
+            output.starts_with(
+                "# Haggling with Gottfried\n\n**Asking price:** 10 gp\\\n**Party:** `d20` ("
+            ),
+            "{}",
+            output,
+        );
+        assert!(
+            output.contains("Bought Shield from **Ye Olde Shoppe** for "),
+            "{}",
+            output
+        );
+        assert!(
+            output.ends_with(" toward the party. Use `undo` to reverse this._"),
+            "{}",
+            output,
+        );
+
+        let npc = match block_on(app_meta.repository.get_by_name("Gottfried")) {
+            Ok(Thing::Npc(npc)) => npc,
+            thing => panic!("{:?}", thing),
+        };
+        assert!(
+            output.contains(&format!(
+                "_Gottfried is {} toward the party.",
+                npc.attitude.value().unwrap(),
+            )),
+            "{}",
+            output,
+        );
+
+        assert_eq!(
+            Err(CommandError::unknown_entity(
+                "Shield",
+                "Ye Olde Shoppe doesn't have any Shield."
+            )),
+            block_on(haggle.run("", &mut app_meta)),
+        );
+
+        block_on(app_meta.repository.undo());
+        assert_eq!(
+            Coins {
+                gp: 10,
+                ..Default::default()
+            },
+            block_on(crate::treasury::load(&app_meta)),
+        );
+        assert!(matches!(
+            block_on(app_meta.repository.get_by_name("Gottfried")),
+            Ok(Thing::Npc(Npc { attitude, .. })) if attitude.is_none(),
+        ));
+    }
+
+    fn event_dispatcher(_event: Event) {}
+
+    fn app_meta() -> AppMeta {
+        AppMeta::new(MemoryDataStore::default(), &event_dispatcher)
+    }
+}
@@ -0,0 +1,275 @@
+//! Haggling with a shopkeeper over the price of equipment. The party's Charisma check is opposed
+//! by the shopkeeper's, and the difference between them decides how much is knocked off the
+//! asking price and how the shopkeeper feels about the party afterward.
+
+use crate::world::inventory::Coins;
+use crate::world::npc::{Attitude, Demeanor};
+use crate::world::Npc;
+use rand::Rng;
+use std::fmt;
+
+/// The outcome of an opposed Charisma check between the party and a shopkeeper.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Haggle {
+    pub party: Check,
+    pub shopkeeper: Check,
+}
+
+/// A single `d20` roll with its modifier.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Check {
+    pub roll: u8,
+    pub modifier: i8,
+}
+
+impl Haggle {
+    /// Roll the party's check with the configured `charisma` against the shopkeeper's. NPCs don't
+    /// have ability scores, so the shopkeeper's modifier comes from their demeanor and attitude.
+    pub fn roll(rng: &mut impl Rng, charisma: i8, shopkeeper: &Npc) -> Self {
+        Self {
+            party: Check::roll(rng, charisma),
+            shopkeeper: Check::roll(rng, shopkeeper_modifier(shopkeeper)),
+        }
+    }
+
+    /// How far the party's check beat the shopkeeper's, or a negative number if it fell short.
+    pub fn margin(&self) -> i8 {
+        self.party.total() - self.shopkeeper.total()
+    }
+
+    /// The percentage of the asking price that the party ends up paying.
+    pub fn percent(&self) -> u64 {
+        match self.margin() {
+            10.. => 70,
+            5..=9 => 80,
+            1..=4 => 90,
+            _ => 100,
+        }
+    }
+
+    /// The agreed price, rounded to the nearest copper piece.
+    pub fn price(&self, asking: Coins) -> Coins {
+        Coins::from_cp((asking.value_cp() * self.percent() + 50) / 100)
+    }
+
+    /// The shopkeeper's attitude toward the party after the deal. A narrow loss leaves them
+    /// pleased with the sale, while being fleeced or pestered with a lowball offer sours them.
+    pub fn attitude(&self, attitude: Option<Attitude>) -> Attitude {
+        let attitude = attitude.unwrap_or(Attitude::Indifferent);
+
+        match self.margin() {
+            10.. | i8::MIN..=-5 => worse(attitude),
+            -4..=0 => better(attitude),
+            _ => attitude,
+        }
+    }
+
+    /// A line describing how the haggling went, eg. "Gottfried grudgingly agrees to 80% of the
+    /// asking price."
+    pub fn describe(&self, name: &str) -> String {
+        match self.margin() {
+            10.. => format!(
+                "{} throws up their hands and agrees to {}% of the asking price.",
+                name,
+                self.percent(),
+            ),
+            1..=9 => format!(
+                "{} grudgingly agrees to {}% of the asking price.",
+                name,
+                self.percent(),
+            ),
+            -4..=0 => format!("{} won't budge, but enjoyed the haggling.", name),
+            _ => format!("{} won't budge, and is insulted by the offer.", name),
+        }
+    }
+}
+
+impl Check {
+    fn roll(rng: &mut impl Rng, modifier: i8) -> Self {
+        Self {
+            roll: rng.gen_range(1..=20),
+            modifier,
+        }
+    }
+
+    pub fn total(&self) -> i8 {
+        self.roll as i8 + self.modifier
+    }
+}
+
+/// Shopkeepers who are difficult to deal with drive a harder bargain, and those who like the
+/// party an easier one.
+fn shopkeeper_modifier(npc: &Npc) -> i8 {
+    let demeanor = match npc.demeanor.value() {
+        Some(Demeanor::Arrogant | Demeanor::Stern | Demeanor::Suspicious) => 2,
+        Some(Demeanor::Grumpy) => 1,
+        Some(Demeanor::Cheerful | Demeanor::Jovial) => -1,
+        Some(Demeanor::Lazy | Demeanor::Nervous | Demeanor::Shy) => -2,
+        Some(Demeanor::Curious | Demeanor::Pious) | None => 0,
+    };
+
+    demeanor - Attitude::reaction_modifier(npc.attitude.value()) / 2
+}
+
+fn worse(attitude: Attitude) -> Attitude {
+    match attitude {
+        Attitude::Hostile | Attitude::Unfriendly => Attitude::Hostile,
+        Attitude::Indifferent => Attitude::Unfriendly,
+        Attitude::Friendly => Attitude::Indifferent,
+        Attitude::Helpful => Attitude::Friendly,
+    }
+}
+
+fn better(attitude: Attitude) -> Attitude {
+    match attitude {
+        Attitude::Hostile => Attitude::Unfriendly,
+        Attitude::Unfriendly => Attitude::Indifferent,
+        Attitude::Indifferent => Attitude::Friendly,
+        Attitude::Friendly | Attitude::Helpful => Attitude::Helpful,
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "`d20` ({})", self.roll)?;
+
+        match self.modifier {
+            0 => {}
+            m if m > 0 => write!(f, " + {}", m)?,
+            m => write!(f, " - {}", -m)?,
+        }
+
+        write!(f, " = {}", self.total())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::prelude::*;
+
+    #[test]
+    fn roll_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let npc = Npc {
+            demeanor: Demeanor::Stern.into(),
+            attitude: Attitude::Helpful.into(),
+            ..Default::default()
+        };
+
+        for _ in 0..100 {
+            let haggle = Haggle::roll(&mut rng, 3, &npc);
+            assert_eq!(3, haggle.party.modifier);
+            assert_eq!(0, haggle.shopkeeper.modifier);
+            assert!((1..=20).contains(&haggle.party.roll), "{:?}", haggle);
+            assert!((1..=20).contains(&haggle.shopkeeper.roll), "{:?}", haggle);
+        }
+
+        assert_eq!(
+            3,
+            shopkeeper_modifier(&Npc {
+                demeanor: Demeanor::Suspicious.into(),
+                attitude: Attitude::Unfriendly.into(),
+                ..Default::default()
+            }),
+        );
+        assert_eq!(0, shopkeeper_modifier(&Npc::default()));
+    }
+
+    #[test]
+    fn outcome_test() {
+        let asking = Coins {
+            gp: 10,
+            ..Default::default()
+        };
+
+        [
+            (20, 2, 70, Attitude::Unfriendly),
+            (12, 5, 80, Attitude::Indifferent),
+            (8, 5, 90, Attitude::Indifferent),
+            (5, 5, 100, Attitude::Friendly),
+            (3, 10, 100, Attitude::Unfriendly),
+        ]
+        .into_iter()
+        .for_each(|(party, shopkeeper, percent, attitude)| {
+            let haggle = haggle(party, shopkeeper);
+            assert_eq!(percent, haggle.percent(), "{:?}", haggle);
+            assert_eq!(attitude, haggle.attitude(None), "{:?}", haggle);
+            assert_eq!(
+                Coins::from_cp(percent * 10),
+                haggle.price(asking),
+                "{:?}",
+                haggle,
+            );
+        });
+
+        assert_eq!(
+            Attitude::Hostile,
+            haggle(1, 20).attitude(Some(Attitude::Hostile))
+        );
+        assert_eq!(
+            Attitude::Helpful,
+            haggle(5, 5).attitude(Some(Attitude::Helpful))
+        );
+        assert_eq!(
+            Coins {
+                cp: 4,
+                ..Default::default()
+            },
+            haggle(15, 5).price(Coins {
+                cp: 6,
+                ..Default::default()
+            }),
+        );
+    }
+
+    #[test]
+    fn display_test() {
+        assert_eq!(
+            "`d20` (14) + 3 = 17",
+            Check {
+                roll: 14,
+                modifier: 3,
+            }
+            .to_string(),
+        );
+        assert_eq!(
+            "`d20` (1) - 2 = -1",
+            Check {
+                roll: 1,
+                modifier: -2,
+            }
+            .to_string(),
+        );
+        assert_eq!(
+            "`d20` (9) = 9",
+            Check {
+                roll: 9,
+                modifier: 0
+            }
+            .to_string()
+        );
+
+        assert_eq!(
+            "Gottfried grudgingly agrees to 80% of the asking price.",
+            haggle(12, 5).describe("Gottfried"),
+        );
+        assert_eq!(
+            "Gottfried won't budge, and is insulted by the offer.",
+            haggle(3, 10).describe("Gottfried"),
+        );
+    }
+
+    fn haggle(party: u8, shopkeeper: u8) -> Haggle {
+        Haggle {
+            party: Check {
+                roll: party,
+                modifier: 0,
+            },
+            shopkeeper: Check {
+                roll: shopkeeper,
+                modifier: 0,
+            },
+        }
+    }
+}
@@ -0,0 +1,20 @@
+pub use command::TreasuryCommand;
+
+mod command;
+mod haggle;
+
+use crate::app::AppMeta;
+use crate::storage::KeyValue;
+use crate::world::inventory::Coins;
+
+/// Load the party's shared coins from storage, or an empty treasury if nothing has been added
+/// yet or the data store is unavailable.
+pub async fn load(app_meta: &AppMeta) -> Coins {
+    app_meta
+        .repository
+        .get_key_value(&KeyValue::Treasury(None))
+        .await
+        .ok()
+        .and_then(KeyValue::treasury)
+        .unwrap_or_default()
+}
@@ -0,0 +1,103 @@
+use std::fmt;
+
+/// Characters that have a meaning in the output markup wherever they appear in plain text:
+/// Markdown emphasis and links, backticks for code, `~` for temporary links, and `<` for
+/// annotations.
+const SPECIAL_CHARS: &[char] = &['\\', '`', '*', '_', '~', '[', ']', '<'];
+
+/// Characters that only have a meaning at the start of a line: headings, `!` for errors,
+/// blockquotes, and lists.
+const LEADING_CHARS: &[char] = &['#', '!', '>', '-', '+'];
+
+/// User-provided text, such as a name or description, escaped so that it can be interpolated into
+/// plain Markdown without being mistaken for markup. Line breaks are collapsed into spaces.
+pub struct EscapedView<'a>(&'a str);
+
+/// User-provided text formatted as a code span, eg. `` `Gottfried` ``, which the web frontend
+/// renders as a link. The span is fenced with enough backticks that any backticks in the text are
+/// shown literally, and line breaks are collapsed into spaces.
+pub struct CodeView<'a>(&'a str);
+
+impl<'a> EscapedView<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self(text)
+    }
+}
+
+impl<'a> CodeView<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self(text)
+    }
+}
+
+impl<'a> fmt::Display for EscapedView<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.0.starts_with(LEADING_CHARS) {
+            write!(f, "\\")?;
+        }
+
+        self.0.chars().try_for_each(|c| match c {
+            '\n' | '\r' => write!(f, " "),
+            c if SPECIAL_CHARS.contains(&c) => write!(f, "\\{}", c),
+            c => write!(f, "{}", c),
+        })
+    }
+}
+
+impl<'a> fmt::Display for CodeView<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = self.0.replace(['\n', '\r'], " ");
+        let longest_run = text
+            .split(|c| c != '`')
+            .map(str::len)
+            .max()
+            .unwrap_or_default();
+        let fence = "`".repeat(longest_run + 1);
+
+        // A space is needed to separate a backtick at either end from the fence. One space is
+        // stripped from each end when rendered.
+        if text.starts_with('`') || text.ends_with('`') {
+            write!(f, "{} {} {}", fence, text, fence)
+        } else {
+            write!(f, "{}{}{}", fence, text, fence)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn escaped_view_test() {
+        assert_eq!("Gottfried", EscapedView::new("Gottfried").to_string());
+        assert_eq!(
+            "\\~delete Gottfried\\~",
+            EscapedView::new("~delete Gottfried~").to_string(),
+        );
+        assert_eq!(
+            "\\# The \\*Prancing\\* Pony \\`\\`",
+            EscapedView::new("# The *Prancing* Pony ``").to_string(),
+        );
+        assert_eq!("Room #3!", EscapedView::new("Room #3!").to_string());
+        assert_eq!("\\! Danger", EscapedView::new("! Danger").to_string());
+        assert_eq!(
+            "Potato Johnson",
+            EscapedView::new("Potato\nJohnson").to_string()
+        );
+    }
+
+    #[test]
+    fn code_view_test() {
+        assert_eq!("`Gottfried`", CodeView::new("Gottfried").to_string());
+        assert_eq!("``Gott`fried``", CodeView::new("Gott`fried").to_string());
+        assert_eq!(
+            "``` ``Gottfried ```",
+            CodeView::new("``Gottfried").to_string()
+        );
+        assert_eq!(
+            "`Potato Johnson`",
+            CodeView::new("Potato\nJohnson").to_string()
+        );
+    }
+}
@@ -1,8 +1,14 @@
 pub use case_insensitive_str::CaseInsensitiveStr;
+pub use markdown::{CodeView, EscapedView};
 pub use quoted_word_iter::quoted_words;
+pub use sync::{MaybeSync, Shared};
+pub use trie::Trie;
 
 mod case_insensitive_str;
+mod markdown;
 mod quoted_word_iter;
+mod sync;
+mod trie;
 
 use std::iter::Iterator;
 use std::ops::Range;
@@ -19,19 +25,20 @@ pub fn capitalize(input: &str) -> String {
     result
 }
 
-pub fn pluralize(word: &str) -> (&str, &str) {
-    match word {
-        "Goose" => ("Geese", ""),
-        "Beef" | "Carp" | "Cod" | "Deer" | "Perch" | "Potatoes" | "Sheep" | "Squid" => (word, ""),
-        s if s.ends_with('f') => (&word[..(word.len() - 1)], "ves"),
-        s if s.ends_with("ey") => (&word[..(word.len() - 2)], "ies"),
-        s if s.ends_with('y') => (&word[..(word.len() - 1)], "ies"),
-        s if s.ends_with(&['s', 'x', 'z'][..]) => (word, "es"),
-        s if s.ends_with("ch") => (word, "es"),
-        s if s.ends_with("sh") => (word, "es"),
-        s if s.ends_with(&['s', 'x'][..]) => (word, "es"),
-        _ => (word, "s"),
-    }
+/// Split a phrase at the last occurrence of the given word, eg. "The cult involves Gimli" at
+/// "involves", as long as there is something on either side of it.
+pub fn split_at_last_word<'a>(phrase: &'a str, word: &str) -> Option<(&'a str, &'a str)> {
+    quoted_words(phrase)
+        .skip(1)
+        .filter(|w| w.as_str().eq_ci(word))
+        .last()
+        .map(|w| {
+            (
+                phrase[..w.range().start].trim(),
+                phrase[w.range().end..].trim(),
+            )
+        })
+        .filter(|(before, after)| !before.is_empty() && !after.is_empty())
 }
 
 pub struct Word<'a> {
@@ -0,0 +1,79 @@
+//! With the `sync` feature enabled, the app can be shared between threads, eg. by a server running
+//! a separate world for each channel. Without it, nothing needs to be `Send` or `Sync`, which is
+//! what allows the web version to hold JavaScript objects.
+
+#[cfg(not(feature = "sync"))]
+use std::cell::{Ref, RefCell, RefMut};
+#[cfg(not(feature = "sync"))]
+use std::rc::Rc;
+#[cfg(feature = "sync")]
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Implemented for every type that is `Send + Sync` if the `sync` feature is enabled, or for
+/// every type at all if it isn't.
+#[cfg(feature = "sync")]
+pub trait MaybeSync: Send + Sync {}
+
+#[cfg(feature = "sync")]
+impl<T: Send + Sync> MaybeSync for T {}
+
+/// Implemented for every type that is `Send + Sync` if the `sync` feature is enabled, or for
+/// every type at all if it isn't.
+#[cfg(not(feature = "sync"))]
+pub trait MaybeSync {}
+
+#[cfg(not(feature = "sync"))]
+impl<T> MaybeSync for T {}
+
+/// A value shared between clones of its owner: an `Rc<RefCell<T>>`, or an `Arc<Mutex<T>>` if the
+/// `sync` feature is enabled.
+#[derive(Default)]
+pub struct Shared<T> {
+    #[cfg(not(feature = "sync"))]
+    inner: Rc<RefCell<T>>,
+
+    #[cfg(feature = "sync")]
+    inner: Arc<Mutex<T>>,
+}
+
+#[cfg(not(feature = "sync"))]
+impl<T> Shared<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            inner: Rc::new(RefCell::new(value)),
+        }
+    }
+
+    pub fn borrow(&self) -> Ref<'_, T> {
+        self.inner.borrow()
+    }
+
+    pub fn borrow_mut(&self) -> RefMut<'_, T> {
+        self.inner.borrow_mut()
+    }
+}
+
+#[cfg(feature = "sync")]
+impl<T> Shared<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(value)),
+        }
+    }
+
+    pub fn borrow(&self) -> MutexGuard<'_, T> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn borrow_mut(&self) -> MutexGuard<'_, T> {
+        self.borrow()
+    }
+}
+
+impl<T> Clone for Shared<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use std::collections::BTreeMap;
+
+/// A prefix tree keyed case-insensitively, used to look up everything starting with a partial
+/// input without scanning a whole word list. Values come back in (lowercase) key order.
+#[derive(Debug)]
+pub struct Trie<V> {
+    root: TrieNode<V>,
+    len: usize,
+}
+
+#[derive(Debug)]
+struct TrieNode<V> {
+    children: BTreeMap<char, TrieNode<V>>,
+    values: Vec<V>,
+}
+
+/// A position within a [`Trie`], corresponding to some prefix. Cursors can be moved further down
+/// the tree as more input arrives rather than starting again from the root.
+#[derive(Debug)]
+pub struct TrieCursor<'a, V> {
+    node: &'a TrieNode<V>,
+}
+
+impl<V> Trie<V> {
+    pub fn insert(&mut self, key: &str, value: V) {
+        let mut node = &mut self.root;
+        for c in key.chars().flat_map(char::to_lowercase) {
+            node = node.children.entry(c).or_default();
+        }
+        node.values.push(value);
+        self.len += 1;
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn cursor(&self) -> TrieCursor<'_, V> {
+        TrieCursor { node: &self.root }
+    }
+
+    /// Every value whose key starts with the prefix, ignoring case.
+    pub fn starting_with<'a>(&'a self, prefix: &str) -> impl Iterator<Item = &'a V> + 'a {
+        self.cursor()
+            .descend(prefix)
+            .into_iter()
+            .flat_map(|c| c.iter())
+    }
+}
+
+impl<'a, V> TrieCursor<'a, V> {
+    /// Move down the tree by the given input, returning `None` if no key continues that way.
+    pub fn descend(self, input: &str) -> Option<Self> {
+        let mut node = self.node;
+        for c in input.chars().flat_map(char::to_lowercase) {
+            node = node.children.get(&c)?;
+        }
+        Some(Self { node })
+    }
+
+    /// Lazily walk the values beneath this point, so that taking the first few results doesn't
+    /// visit the rest of the tree.
+    pub fn iter(self) -> impl Iterator<Item = &'a V> + 'a {
+        let mut stack = vec![self.node];
+
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(node.children.values().rev());
+            Some(node.values.iter())
+        })
+        .flatten()
+    }
+}
+
+impl<V> Clone for TrieCursor<'_, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<V> Copy for TrieCursor<'_, V> {}
+
+impl<V> Default for Trie<V> {
+    fn default() -> Self {
+        Self {
+            root: TrieNode::default(),
+            len: 0,
+        }
+    }
+}
+
+impl<V> Default for TrieNode<V> {
+    fn default() -> Self {
+        Self {
+            children: BTreeMap::new(),
+            values: Vec::new(),
+        }
+    }
+}
+
+impl<K: AsRef<str>, V> FromIterator<(K, V)> for Trie<V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut trie = Self::default();
+        iter.into_iter()
+            .for_each(|(key, value)| trie.insert(key.as_ref(), value));
+        trie
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn starting_with_test() {
+        let trie: Trie<&str> = ["Shield", "shillelagh", "Fireball", "shield", "Shatter"]
+            .into_iter()
+            .map(|word| (word, word))
+            .collect();
+
+        assert_eq!(5, trie.len());
+        assert_eq!(
+            vec!["Shatter", "Shield", "shield", "shillelagh"],
+            trie.starting_with("SH").copied().collect::<Vec<_>>(),
+        );
+        assert_eq!(
+            vec!["Fireball"],
+            trie.starting_with("fireball").copied().collect::<Vec<_>>(),
+        );
+        assert_eq!(0, trie.starting_with("fireballs").count());
+        assert_eq!(5, trie.starting_with("").count());
+    }
+
+    #[test]
+    fn cursor_test() {
+        let trie: Trie<u8> = [("a", 1), ("ab", 2), ("abc", 3), ("b", 4)]
+            .into_iter()
+            .collect();
+
+        let cursor = trie.cursor().descend("a").unwrap();
+        assert_eq!(vec![1, 2, 3], cursor.iter().copied().collect::<Vec<_>>());
+
+        let cursor = cursor.descend("B").unwrap();
+        assert_eq!(vec![2, 3], cursor.iter().copied().collect::<Vec<_>>());
+
+        assert!(cursor.descend("d").is_none());
+        assert!(Trie::<u8>::default().is_empty());
+    }
+}
@@ -0,0 +1,220 @@
+//! A round of battle between two armies, resolved with an opposed roll. Each side adds the
+//! quality of its soldiers, an advantage for outnumbering the enemy, and a bonus for being led by
+//! a commander, and the difference between the totals decides how many fall on each side and
+//! whether the field is won.
+
+use super::{Army, Quality};
+use rand::Rng;
+use std::fmt;
+
+/// The rolls of both sides in a round of battle.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Round {
+    pub attacker: Strength,
+    pub defender: Strength,
+}
+
+/// A single `d20` roll with the modifiers that went into it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Strength {
+    pub roll: u8,
+    pub quality: Quality,
+    pub numbers: i8,
+    pub commander: i8,
+}
+
+impl Round {
+    /// Roll both sides' strength. `attacker_led` and `defender_led` are whether each army has a
+    /// living commander at its head.
+    pub fn roll(
+        rng: &mut impl Rng,
+        attacker: &Army,
+        defender: &Army,
+        attacker_led: bool,
+        defender_led: bool,
+    ) -> Self {
+        Self {
+            attacker: Strength::roll(rng, attacker, defender, attacker_led),
+            defender: Strength::roll(rng, defender, attacker, defender_led),
+        }
+    }
+
+    /// How far the attacker's total beat the defender's, or a negative number if it fell short.
+    pub fn margin(&self) -> i8 {
+        self.attacker.total() - self.defender.total()
+    }
+
+    /// A margin of 5 or more carries the field, and with it any place being fought over.
+    pub fn is_decisive(&self) -> bool {
+        self.margin().abs() >= 5
+    }
+
+    /// The percentage of each side's soldiers lost, attacker first. The winner loses a few, and
+    /// the loser more the worse they were beaten.
+    pub fn loss_percent(&self) -> (u32, u32) {
+        let beaten = (10 + 2 * self.margin().unsigned_abs() as u32).min(50);
+
+        match self.margin() {
+            0 => (5, 5),
+            1.. => (5, beaten),
+            _ => (beaten, 5),
+        }
+    }
+}
+
+impl Strength {
+    fn roll(rng: &mut impl Rng, army: &Army, enemy: &Army, led: bool) -> Self {
+        Self {
+            roll: rng.gen_range(1..=20),
+            quality: army.quality,
+            numbers: if army.size >= enemy.size.saturating_mul(2) {
+                4
+            } else if army.size > enemy.size {
+                2
+            } else {
+                0
+            },
+            commander: if led { 2 } else { 0 },
+        }
+    }
+
+    pub fn total(&self) -> i8 {
+        self.roll as i8 + self.quality.modifier() + self.numbers + self.commander
+    }
+}
+
+/// The number of soldiers lost from an army of `size`, rounded to the nearest soldier.
+pub fn losses(size: u32, percent: u32) -> u32 {
+    ((size as u64 * percent as u64 + 50) / 100) as u32
+}
+
+impl fmt::Display for Strength {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "`d20` ({})", self.roll)?;
+
+        for (modifier, reason) in [
+            (self.quality.modifier(), self.quality.as_str()),
+            (self.numbers, "numbers"),
+            (self.commander, "commander"),
+        ] {
+            match modifier {
+                0 => {}
+                m if m > 0 => write!(f, " + {} ({})", m, reason)?,
+                m => write!(f, " - {} ({})", -m, reason)?,
+            }
+        }
+
+        write!(f, " = {}", self.total())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::prelude::*;
+
+    #[test]
+    fn roll_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let (legion, host) = (army(500, Quality::Elite), army(200, Quality::Levy));
+
+        for _ in 0..100 {
+            let round = Round::roll(&mut rng, &legion, &host, true, false);
+            assert_eq!(4, round.attacker.numbers);
+            assert_eq!(2, round.attacker.commander);
+            assert_eq!(0, round.defender.numbers);
+            assert_eq!(0, round.defender.commander);
+            assert!((1..=20).contains(&round.attacker.roll), "{:?}", round);
+            assert!((1..=20).contains(&round.defender.roll), "{:?}", round);
+        }
+
+        let round = Round::roll(&mut rng, &host, &army(150, Quality::Trained), false, false);
+        assert_eq!(2, round.attacker.numbers);
+    }
+
+    #[test]
+    fn outcome_test() {
+        [
+            (10, 10, (5, 5), false),
+            (12, 10, (5, 14), false),
+            (15, 10, (5, 20), true),
+            (1, 20, (48, 5), true),
+            (20, 1, (5, 48), true),
+        ]
+        .into_iter()
+        .for_each(|(attacker, defender, percent, decisive)| {
+            let round = round(attacker, defender);
+            assert_eq!(percent, round.loss_percent(), "{:?}", round);
+            assert_eq!(decisive, round.is_decisive(), "{:?}", round);
+        });
+
+        let rout = Round {
+            attacker: Strength {
+                roll: 20,
+                quality: Quality::Elite,
+                numbers: 4,
+                commander: 2,
+            },
+            defender: Strength {
+                roll: 1,
+                quality: Quality::Levy,
+                numbers: 0,
+                commander: 0,
+            },
+        };
+        assert_eq!((5, 50), rout.loss_percent());
+
+        assert_eq!(25, losses(500, 5));
+        assert_eq!(1, losses(10, 5));
+        assert_eq!(0, losses(0, 50));
+    }
+
+    #[test]
+    fn display_test() {
+        assert_eq!(
+            "`d20` (14) + 4 (elite) + 2 (numbers) + 2 (commander) = 22",
+            Strength {
+                roll: 14,
+                quality: Quality::Elite,
+                numbers: 2,
+                commander: 2,
+            }
+            .to_string(),
+        );
+        assert_eq!(
+            "`d20` (3) - 2 (levy) = 1",
+            Strength {
+                roll: 3,
+                quality: Quality::Levy,
+                numbers: 0,
+                commander: 0,
+            }
+            .to_string(),
+        );
+    }
+
+    fn army(size: u32, quality: Quality) -> Army {
+        Army {
+            name: "Army".to_string(),
+            size,
+            quality,
+            commander: None,
+            territory: Vec::new(),
+        }
+    }
+
+    /// A round with trained armies of equal size, so that the totals are just the rolls.
+    fn round(attacker: u8, defender: u8) -> Round {
+        let strength = |roll| Strength {
+            roll,
+            quality: Quality::Trained,
+            numbers: 0,
+            commander: 0,
+        };
+
+        Round {
+            attacker: strength(attacker),
+            defender: strength(defender),
+        }
+    }
+}
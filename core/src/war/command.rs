@@ -0,0 +1,762 @@
+use super::battle::{losses, Round};
+use super::{Armies, Army, Battle, Quality};
+use crate::app::{
+    AppMeta, Autocomplete, AutocompleteSuggestion, CommandError, CommandMatches, ContextAwareParse,
+    Runnable,
+};
+use crate::config::Config;
+use crate::locale::Message;
+use crate::storage::{Change, KeyValue};
+use crate::utils::{quoted_words, CaseInsensitiveStr};
+use crate::world::{Npc, Thing};
+use async_trait::async_trait;
+use std::fmt;
+
+/// The number of battles shown by `armies`. The rest can be found in the timeline.
+const RECENT_BATTLES_LEN: usize = 5;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum WarCommand {
+    Armies,
+    Battle {
+        attacker: String,
+        defender: String,
+        place: Option<String>,
+    },
+    Disband {
+        name: String,
+    },
+    Muster {
+        name: String,
+        size: u32,
+        quality: Quality,
+        commander: Option<String>,
+    },
+}
+
+impl WarCommand {
+    /// Parse "The Iron Legion of 500 veterans led by Gimli". The quality defaults to trained
+    /// soldiers, and the commander is optional.
+    fn parse_muster(input: &str) -> Option<Self> {
+        let led_by = split_at_word(input, "led");
+        let (input, commander) = match &led_by {
+            Some((input, rest)) => (*input, Some(rest.strip_prefix_ci("by ")?.trim())),
+            None => (input, None),
+        };
+
+        let (name, force) = split_at_word(input, "of")?;
+        let mut words = force.split_whitespace();
+        // Sizes of zero or less are still recognized, so that running them can explain what's
+        // wrong rather than the input not being understood at all.
+        let size: i64 = words.next()?.parse().ok()?;
+        let size = u32::try_from(size.max(0)).ok()?;
+
+        let quality = match words.next() {
+            Some(word) if word.in_ci(&["soldiers", "troops"]) => Quality::Trained,
+            Some(word) => word.parse().ok()?,
+            None => Quality::Trained,
+        };
+
+        match words.next() {
+            Some(word) if word.in_ci(&["soldiers", "troops"]) => {}
+            Some(_) => return None,
+            None => {}
+        }
+
+        if words.next().is_some() {
+            return None;
+        }
+
+        Some(Self::Muster {
+            name: name.to_string(),
+            size,
+            quality,
+            commander: commander.map(str::to_string),
+        })
+    }
+
+    /// Parse "The Iron Legion vs The Black Host at Bree". The place is optional.
+    fn parse_battle(input: &str) -> Option<Self> {
+        let (attacker, rest) = ["vs", "vs.", "versus"]
+            .into_iter()
+            .find_map(|separator| split_at_word(input, separator))?;
+
+        let (defender, place) = match split_at_word(rest, "at") {
+            Some((defender, place)) => (defender, Some(place.to_string())),
+            None => (rest, None),
+        };
+
+        Some(Self::Battle {
+            attacker: attacker.to_string(),
+            defender: defender.to_string(),
+            place,
+        })
+    }
+
+    /// Fight a round of battle, updating the armies and recording the outcome in the timeline.
+    /// Both are undone together.
+    async fn battle(
+        attacker: String,
+        defender: String,
+        place: Option<String>,
+        mut armies: Armies,
+        app_meta: &mut AppMeta,
+    ) -> Result<String, CommandError> {
+        let attacker = armies
+            .get(&attacker)
+            .cloned()
+            .ok_or_else(|| no_army(&attacker))?;
+        let defender = armies
+            .get(&defender)
+            .cloned()
+            .ok_or_else(|| no_army(&defender))?;
+
+        if attacker.name == defender.name {
+            return Err(CommandError::invalid_state(format!(
+                "{} can't fight itself.",
+                attacker.name,
+            )));
+        }
+
+        let place = if let Some(name) = place {
+            match app_meta.repository.get_by_name(&name).await {
+                Ok(Thing::Place(place)) => {
+                    let uuid = place.uuid.clone().ok_or_else(|| {
+                        format!(
+                            "{} hasn't been saved to your `journal` yet. Use `save {}` first.",
+                            place.name, place.name,
+                        )
+                    })?;
+                    Some((place.name.to_string(), uuid))
+                }
+                _ => {
+                    return Err(CommandError::unknown_entity(
+                        &name,
+                        format!(r#"There is no place named "{}"."#, name),
+                    ))
+                }
+            }
+        } else {
+            None
+        };
+
+        let attacker_led = is_led(&attacker, app_meta).await;
+        let defender_led = is_led(&defender, app_meta).await;
+        let round = Round::roll(
+            &mut app_meta.rng,
+            &attacker,
+            &defender,
+            attacker_led,
+            defender_led,
+        );
+
+        let (attacker_percent, defender_percent) = round.loss_percent();
+        let attacker_losses = losses(attacker.size, attacker_percent);
+        let defender_losses = losses(defender.size, defender_percent);
+
+        let victor = match round.margin() {
+            0 => None,
+            1.. => Some(&attacker),
+            _ => Some(&defender),
+        };
+
+        let captured = match (victor, &place) {
+            (Some(victor), Some((_, uuid))) if round.is_decisive() => {
+                !victor.territory.contains(uuid)
+            }
+            _ => false,
+        };
+
+        for (army, lost) in [(&attacker, attacker_losses), (&defender, defender_losses)] {
+            if let Some(army) = armies.get_mut(&army.name) {
+                army.size = army.size.saturating_sub(lost);
+            }
+        }
+
+        if let (true, Some(victor), Some((_, uuid))) = (captured, victor, &place) {
+            armies.capture(&victor.name, uuid.clone());
+        }
+
+        let battle = Battle {
+            attacker: attacker.name.clone(),
+            defender: defender.name.clone(),
+            place: place.as_ref().map(|(name, _)| name.clone()),
+            attacker_losses,
+            defender_losses,
+            victor: victor.map(|army| army.name.clone()),
+            captured,
+        };
+
+        let mut output = if let Some((name, _)) = &place {
+            format!("# Battle of {}", name)
+        } else {
+            format!("# {} vs. {}", attacker.name, defender.name)
+        };
+
+        output.push_str(&format!(
+            "\n\n**{}:** {}\\\n**{}:** {}\n\n{}.\n\n",
+            attacker.name, round.attacker, defender.name, round.defender, battle,
+        ));
+
+        for (i, name) in [&attacker.name, &defender.name].into_iter().enumerate() {
+            if let Some(army) = armies.get(name) {
+                output.push_str(&format!(
+                    "{}**{}:** {}",
+                    if i == 0 { "" } else { "\\\n" },
+                    army.name,
+                    describe(army, app_meta).await,
+                ));
+            }
+        }
+
+        output.push_str(
+            "\n\n_The battle has been recorded in the `timeline`. Use `undo` to reverse this._",
+        );
+
+        let mut battles = super::load_battles(app_meta).await;
+        battles.push(battle);
+
+        app_meta
+            .repository
+            .transaction(vec![
+                Change::SetKeyValue {
+                    key_value: KeyValue::Armies(Some(armies)),
+                },
+                Change::SetKeyValue {
+                    key_value: KeyValue::Battles(Some(battles)),
+                },
+            ])
+            .await
+            .map(|_| output)
+            .map_err(|_| CommandError::storage("Unable to save the battle."))
+    }
+}
+
+/// Split the input at the last occurrence of the word `separator`, eg. "Army of the Dead of 500"
+/// at "of". Both sides must be non-empty.
+fn split_at_word<'a>(input: &'a str, separator: &str) -> Option<(&'a str, &'a str)> {
+    quoted_words(input)
+        .skip(1)
+        .filter(|word| word.as_str().eq_ci(separator))
+        .last()
+        .map(|word| {
+            (
+                input[..word.range().start].trim(),
+                input[word.range().end..].trim(),
+            )
+        })
+        .filter(|(before, after)| !before.is_empty() && !after.is_empty())
+}
+
+fn no_army(name: &str) -> CommandError {
+    CommandError::unknown_entity(
+        name,
+        format!(
+            r#"There is no army named "{}". Use `armies` to see the armies you've mustered."#,
+            name,
+        ),
+    )
+}
+
+async fn commander(army: &Army, app_meta: &AppMeta) -> Option<Npc> {
+    match app_meta
+        .repository
+        .get_by_uuid(army.commander.as_ref()?.as_ref())
+        .await
+    {
+        Ok(Thing::Npc(npc)) => Some(npc),
+        _ => None,
+    }
+}
+
+/// Is the army led by a commander who is still able to lead it?
+async fn is_led(army: &Army, app_meta: &AppMeta) -> bool {
+    commander(army, app_meta)
+        .await
+        .map_or(false, |npc| npc.fate.is_none())
+}
+
+/// The army's strength and holdings, eg. "500 veteran soldiers led by `Gimli`, holding `Bree`".
+async fn describe(army: &Army, app_meta: &AppMeta) -> String {
+    let mut output = format!("{} {} soldiers", army.size, army.quality);
+
+    if let Some(npc) = commander(army, app_meta).await {
+        output.push_str(&format!(" led by `{}`", npc.name));
+
+        if let Some(fate) = npc.fate.value() {
+            output.push_str(&format!(" ({})", fate.as_str()));
+        }
+    }
+
+    let mut territory = Vec::new();
+
+    for uuid in &army.territory {
+        if let Ok(thing) = app_meta.repository.get_by_uuid(uuid.as_ref()).await {
+            territory.push(format!("`{}`", thing.name()));
+        }
+    }
+
+    if !territory.is_empty() {
+        output.push_str(&format!(", holding {}", territory.join(", ")));
+    }
+
+    output
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Runnable for WarCommand {
+    async fn run(self, _input: &str, app_meta: &mut AppMeta) -> Result<String, CommandError> {
+        let mut armies = super::load_armies(app_meta).await;
+
+        let response = match self {
+            Self::Armies => {
+                if armies.is_empty() {
+                    return Ok("# Armies\n\n*There are no armies yet. Use `army [name] of [size] [quality]` to muster one, eg. `army The Iron Legion of 500 veterans led by Gimli`.*".to_string());
+                }
+
+                let mut output = "# Armies\n".to_string();
+
+                for (i, army) in armies.iter().enumerate() {
+                    output.push_str(&format!(
+                        "{}**{}:** {}",
+                        if i == 0 { "\n" } else { "\\\n" },
+                        army.name,
+                        describe(army, app_meta).await,
+                    ));
+                }
+
+                let battles = super::load_battles(app_meta).await;
+
+                if !battles.is_empty() {
+                    output.push_str("\n\n## Recent Battles\n");
+
+                    let skip = battles.iter().count().saturating_sub(RECENT_BATTLES_LEN);
+                    for battle in battles.iter().skip(skip) {
+                        output.push_str(&format!("\n* {}", battle));
+                    }
+                }
+
+                return Ok(output);
+            }
+            Self::Battle {
+                attacker,
+                defender,
+                place,
+            } => return Self::battle(attacker, defender, place, armies, app_meta).await,
+            Self::Disband { name } => {
+                let army = armies.disband(&name).ok_or_else(|| no_army(&name))?;
+                format!(
+                    "{} has been disbanded. Use `undo` to reverse this.",
+                    army.name
+                )
+            }
+            Self::Muster {
+                name,
+                size,
+                quality,
+                commander,
+            } => {
+                if size == 0 {
+                    return Err("An army needs at least one soldier.".into());
+                }
+
+                let commander = if let Some(commander) = commander {
+                    let npc = match app_meta.repository.get_by_name(&commander).await {
+                        Ok(Thing::Npc(npc)) => npc,
+                        _ => {
+                            let config = Config::load(app_meta).await;
+
+                            return Err(CommandError::unknown_entity(
+                                &commander,
+                                Message::NoCharacterNamed { name: &commander }
+                                    .localize(config.language),
+                            ));
+                        }
+                    };
+
+                    Some(npc.uuid.clone().ok_or_else(|| {
+                        format!(
+                            "{} hasn't been saved to your `journal` yet. Use `save {}` first.",
+                            npc.name, npc.name,
+                        )
+                    })?)
+                } else {
+                    None
+                };
+
+                armies.muster(Army {
+                    name: name.clone(),
+                    size,
+                    quality,
+                    commander,
+                    territory: Vec::new(),
+                });
+
+                let army = armies.get(&name).unwrap();
+                format!(
+                    "**{}** has been mustered: {}. Use `undo` to reverse this.",
+                    army.name,
+                    describe(army, app_meta).await,
+                )
+            }
+        };
+
+        app_meta
+            .repository
+            .modify(Change::SetKeyValue {
+                key_value: KeyValue::Armies(Some(armies)),
+            })
+            .await
+            .map(|_| response)
+            .map_err(|_| CommandError::storage("Unable to save the armies."))
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl ContextAwareParse for WarCommand {
+    async fn parse_input(input: &str, _app_meta: &AppMeta) -> CommandMatches<Self> {
+        if input.eq_ci("armies") {
+            CommandMatches::new_canonical(Self::Armies)
+        } else if let Some(name) = input
+            .strip_prefix_ci("disband ")
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+        {
+            CommandMatches::new_canonical(Self::Disband {
+                name: name.to_string(),
+            })
+        } else if let Some(input) = input.strip_prefix_ci("battle ") {
+            Self::parse_battle(input)
+                .map(CommandMatches::new_canonical)
+                .unwrap_or_default()
+        } else if let Some(input) = input.strip_prefix_ci("army ") {
+            Self::parse_muster(input)
+                .map(CommandMatches::new_canonical)
+                .unwrap_or_default()
+        } else {
+            CommandMatches::default()
+        }
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Autocomplete for WarCommand {
+    async fn autocomplete(input: &str, _app_meta: &AppMeta) -> Vec<AutocompleteSuggestion> {
+        if input.is_empty() {
+            return Vec::new();
+        }
+
+        [
+            ("armies", "list the armies and the places they hold"),
+            ("army [name] of [size] [quality]", "muster an army"),
+            (
+                "battle [army] vs [army] at [place]",
+                "fight a round of battle between two armies",
+            ),
+            ("disband [army]", "disband an army"),
+        ]
+        .into_iter()
+        .filter(|(term, _)| term.starts_with_ci(input))
+        .map(|(term, summary)| AutocompleteSuggestion::new(term, summary))
+        .collect()
+    }
+}
+
+impl fmt::Display for WarCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::Armies => write!(f, "armies"),
+            Self::Battle {
+                attacker,
+                defender,
+                place,
+            } => {
+                write!(f, "battle {} vs {}", attacker, defender)?;
+
+                if let Some(place) = place {
+                    write!(f, " at {}", place)?;
+                }
+
+                Ok(())
+            }
+            Self::Disband { name } => write!(f, "disband {}", name),
+            Self::Muster {
+                name,
+                size,
+                quality,
+                commander,
+            } => {
+                write!(f, "army {} of {} {} soldiers", name, size, quality)?;
+
+                if let Some(commander) = commander {
+                    write!(f, " led by {}", commander)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::assert_autocomplete;
+    use crate::storage::MemoryDataStore;
+    use crate::world::Place;
+    use crate::Event;
+    use tokio_test::block_on;
+
+    #[test]
+    fn parse_input_test() {
+        let app_meta = app_meta();
+
+        assert_eq!(
+            CommandMatches::new_canonical(WarCommand::Muster {
+                name: "Army of the Dead".to_string(),
+                size: 1000,
+                quality: Quality::Elite,
+                commander: Some("Isildur".to_string()),
+            }),
+            block_on(WarCommand::parse_input(
+                "army Army of the Dead of 1000 elite troops led by Isildur",
+                &app_meta,
+            )),
+        );
+        assert_eq!(
+            CommandMatches::new_canonical(WarCommand::Muster {
+                name: "The Iron Legion".to_string(),
+                size: 500,
+                quality: Quality::Trained,
+                commander: None,
+            }),
+            block_on(WarCommand::parse_input(
+                "ARMY The Iron Legion of 500",
+                &app_meta
+            )),
+        );
+        assert_eq!(
+            CommandMatches::new_canonical(WarCommand::Battle {
+                attacker: "The Iron Legion".to_string(),
+                defender: "The Black Host".to_string(),
+                place: Some("Bree".to_string()),
+            }),
+            block_on(WarCommand::parse_input(
+                "battle The Iron Legion versus The Black Host at Bree",
+                &app_meta,
+            )),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(WarCommand::Muster {
+                name: "The Red".to_string(),
+                size: 0,
+                quality: Quality::Levy,
+                commander: None,
+            }),
+            block_on(WarCommand::parse_input(
+                "army The Red of -5 levy",
+                &app_meta
+            )),
+        );
+
+        [
+            "army The Iron Legion",
+            "army The Iron Legion of many",
+            "army The Iron Legion of 500 potatoes",
+            "army The Iron Legion of 500 led by",
+            "battle The Iron Legion",
+            "disband",
+        ]
+        .into_iter()
+        .for_each(|input| {
+            assert_eq!(
+                CommandMatches::default(),
+                block_on(WarCommand::parse_input(input, &app_meta)),
+                "{}",
+                input,
+            );
+        });
+    }
+
+    #[test]
+    fn autocomplete_test() {
+        let app_meta = app_meta();
+
+        assert_autocomplete(
+            &[
+                ("armies", "list the armies and the places they hold"),
+                ("army [name] of [size] [quality]", "muster an army"),
+            ][..],
+            block_on(WarCommand::autocomplete("ar", &app_meta)),
+        );
+
+        assert_autocomplete(
+            &[(
+                "battle [army] vs [army] at [place]",
+                "fight a round of battle between two armies",
+            )][..],
+            block_on(WarCommand::autocomplete("BAT", &app_meta)),
+        );
+    }
+
+    #[test]
+    fn display_test() {
+        let app_meta = app_meta();
+
+        [
+            WarCommand::Armies,
+            WarCommand::Battle {
+                attacker: "The Iron Legion".to_string(),
+                defender: "The Black Host".to_string(),
+                place: Some("Bree".to_string()),
+            },
+            WarCommand::Battle {
+                attacker: "The Iron Legion".to_string(),
+                defender: "The Black Host".to_string(),
+                place: None,
+            },
+            WarCommand::Disband {
+                name: "The Black Host".to_string(),
+            },
+            WarCommand::Muster {
+                name: "The Iron Legion".to_string(),
+                size: 500,
+                quality: Quality::Veteran,
+                commander: Some("Gimli".to_string()),
+            },
+            WarCommand::Muster {
+                name: "The Black Host".to_string(),
+                size: 300,
+                quality: Quality::Levy,
+                commander: None,
+            },
+        ]
+        .into_iter()
+        .for_each(|command| {
+            let command_string = command.to_string();
+            assert_ne!("", command_string);
+            assert_eq!(
+                CommandMatches::new_canonical(command),
+                block_on(WarCommand::parse_input(&command_string, &app_meta)),
+                "{}",
+                command_string,
+            );
+        });
+    }
+
+    #[test]
+    fn run_test() {
+        let mut app_meta = app_meta();
+
+        assert_eq!(
+            Err(CommandError::unknown_entity(
+                "Gimli",
+                r#"There is no character named "Gimli"."#,
+            )),
+            block_on(muster("The Iron Legion", 500, Some("Gimli")).run("", &mut app_meta)),
+        );
+
+        block_on(
+            app_meta.repository.modify(Change::CreateAndSave {
+                thing: Npc {
+                    name: "Gimli".into(),
+                    ..Default::default()
+                }
+                .into(),
+            }),
+        )
+        .unwrap();
+
+        block_on(
+            app_meta.repository.modify(Change::CreateAndSave {
+                thing: Place {
+                    name: "Bree".into(),
+                    ..Default::default()
+                }
+                .into(),
+            }),
+        )
+        .unwrap();
+
+        assert_eq!(
+            Ok("**The Iron Legion** has been mustered: 500 veteran soldiers led by `Gimli`. Use `undo` to reverse this.".to_string()),
+            block_on(muster("The Iron Legion", 500, Some("Gimli")).run("", &mut app_meta)),
+        );
+
+        block_on(muster("The Black Host", 300, None).run("", &mut app_meta)).unwrap();
+
+        assert_eq!(
+            Err("An army needs at least one soldier.".into()),
+            block_on(muster("The Red", 0, None).run("", &mut app_meta)),
+        );
+
+        assert_eq!(
+            Err(no_army("The Grey Company")),
+            block_on(battle("The Iron Legion", "The Grey Company").run("", &mut app_meta)),
+        );
+
+        let output =
+            block_on(battle("The Iron Legion", "the black host").run("", &mut app_meta)).unwrap();
+        assert!(output.starts_with("# Battle of Bree\n\n"), "{}", output);
+        assert!(output.contains(" (commander) = "), "{}", output);
+        assert!(
+            output.ends_with(
+                "\n\n_The battle has been recorded in the `timeline`. Use `undo` to reverse this._"
+            ),
+            "{}",
+            output,
+        );
+
+        let timeline = block_on(app_meta.repository.timeline()).unwrap();
+        assert!(
+            timeline.last().unwrap().to_string().contains("\tbattle\t"),
+            "{:?}",
+            timeline,
+        );
+
+        let output = block_on(WarCommand::Armies.run("", &mut app_meta)).unwrap();
+        assert!(
+            output.starts_with("# Armies\n\n**The Iron Legion:** "),
+            "{}",
+            output,
+        );
+        assert!(output.contains("\n\n## Recent Battles\n\n* "), "{}", output);
+
+        assert_eq!(
+            Ok("The Black Host has been disbanded. Use `undo` to reverse this.".to_string()),
+            block_on(
+                WarCommand::Disband {
+                    name: "THE BLACK HOST".to_string(),
+                }
+                .run("", &mut app_meta)
+            ),
+        );
+    }
+
+    fn muster(name: &str, size: u32, commander: Option<&str>) -> WarCommand {
+        WarCommand::Muster {
+            name: name.to_string(),
+            size,
+            quality: Quality::Veteran,
+            commander: commander.map(str::to_string),
+        }
+    }
+
+    fn battle(attacker: &str, defender: &str) -> WarCommand {
+        WarCommand::Battle {
+            attacker: attacker.to_string(),
+            defender: defender.to_string(),
+            place: Some("Bree".to_string()),
+        }
+    }
+
+    fn event_dispatcher(_event: Event) {}
+
+    fn app_meta() -> AppMeta {
+        AppMeta::new(MemoryDataStore::default(), &event_dispatcher)
+    }
+}
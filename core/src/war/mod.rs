@@ -0,0 +1,339 @@
+pub use command::WarCommand;
+
+mod battle;
+mod command;
+
+use crate::app::AppMeta;
+use crate::storage::KeyValue;
+use crate::utils::CaseInsensitiveStr;
+use crate::world::npc::Uuid as NpcUuid;
+use crate::world::PlaceUuid;
+use initiative_macros::WordList;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// The armies of a campaign, in the order they were mustered.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct Armies(Vec<Army>);
+
+/// A body of soldiers, abstracted to a head count and how well they fight.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Army {
+    pub name: String,
+    pub size: u32,
+    pub quality: Quality,
+
+    /// The character leading the army, who is worth a bonus in battle while they're alive.
+    #[serde(default)]
+    pub commander: Option<NpcUuid>,
+
+    /// The places the army has taken control of in battle.
+    #[serde(default)]
+    pub territory: Vec<PlaceUuid>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, WordList)]
+#[serde(into = "&'static str", try_from = "&str")]
+pub enum Quality {
+    #[alias = "levies"]
+    #[alias = "militia"]
+    Levy,
+
+    Trained,
+
+    #[alias = "veterans"]
+    Veteran,
+
+    Elite,
+}
+
+/// The battles fought so far, in the order they were fought. Each one is also recorded in the
+/// timeline.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct Battles(Vec<Battle>);
+
+/// The outcome of a round of battle between two armies.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Battle {
+    pub attacker: String,
+    pub defender: String,
+    pub place: Option<String>,
+    pub attacker_losses: u32,
+    pub defender_losses: u32,
+
+    /// The name of the winning army, or `None` if neither side gained the upper hand.
+    pub victor: Option<String>,
+
+    /// Did the victor take control of the place where the battle was fought?
+    pub captured: bool,
+}
+
+/// Load the campaign's armies from storage, or none if none have been mustered yet or the data
+/// store is unavailable.
+pub async fn load_armies(app_meta: &AppMeta) -> Armies {
+    app_meta
+        .repository
+        .get_key_value(&KeyValue::Armies(None))
+        .await
+        .ok()
+        .and_then(KeyValue::armies)
+        .unwrap_or_default()
+}
+
+/// Load the campaign's battles from storage, or none if none have been fought yet or the data
+/// store is unavailable.
+pub async fn load_battles(app_meta: &AppMeta) -> Battles {
+    app_meta
+        .repository
+        .get_key_value(&KeyValue::Battles(None))
+        .await
+        .ok()
+        .and_then(KeyValue::battles)
+        .unwrap_or_default()
+}
+
+impl Armies {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Army> {
+        self.0.iter()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Army> {
+        self.0.iter().find(|army| army.name.eq_ci(name))
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Army> {
+        self.0.iter_mut().find(|army| army.name.eq_ci(name))
+    }
+
+    /// Add an army, replacing any army of the same name but keeping the territory it holds.
+    pub fn muster(&mut self, mut army: Army) {
+        if let Some(existing) = self.get_mut(&army.name) {
+            army.territory = std::mem::take(&mut existing.territory);
+            *existing = army;
+        } else {
+            self.0.push(army);
+        }
+    }
+
+    pub fn disband(&mut self, name: &str) -> Option<Army> {
+        let index = self.0.iter().position(|army| army.name.eq_ci(name))?;
+        Some(self.0.remove(index))
+    }
+
+    /// Hand control of a place to the named army, taking it from whoever held it before.
+    pub fn capture(&mut self, name: &str, place: PlaceUuid) {
+        self.0
+            .iter_mut()
+            .for_each(|army| army.territory.retain(|uuid| uuid != &place));
+
+        if let Some(army) = self.get_mut(name) {
+            army.territory.push(place);
+        }
+    }
+}
+
+impl Quality {
+    /// The bonus or penalty to the army's roll in battle.
+    pub fn modifier(&self) -> i8 {
+        match self {
+            Self::Levy => -2,
+            Self::Trained => 0,
+            Self::Veteran => 2,
+            Self::Elite => 4,
+        }
+    }
+}
+
+impl Battles {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Battle> {
+        self.0.iter()
+    }
+
+    pub fn last(&self) -> Option<&Battle> {
+        self.0.last()
+    }
+
+    pub fn push(&mut self, battle: Battle) {
+        self.0.push(battle);
+    }
+}
+
+impl fmt::Display for Armies {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            serde_json::to_string(self).map_err(|_| fmt::Error)?,
+        )
+    }
+}
+
+impl FromStr for Armies {
+    type Err = ();
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(raw).map_err(|_| ())
+    }
+}
+
+impl fmt::Display for Battles {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            serde_json::to_string(self).map_err(|_| fmt::Error)?,
+        )
+    }
+}
+
+impl FromStr for Battles {
+    type Err = ();
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(raw).map_err(|_| ())
+    }
+}
+
+impl fmt::Display for Quality {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A one-line summary of the battle, as recorded in the timeline, eg. "The Iron Legion defeated
+/// The Black Host at `Bree` and took control of it (losses: 25 and 150)".
+impl fmt::Display for Battle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let losses = if self.victor.as_ref() == Some(&self.defender) {
+            write!(f, "{} held off {}", self.defender, self.attacker)?;
+            (self.defender_losses, self.attacker_losses)
+        } else if let Some(victor) = &self.victor {
+            write!(f, "{} defeated {}", victor, self.defender)?;
+            (self.attacker_losses, self.defender_losses)
+        } else {
+            write!(
+                f,
+                "{} and {} fought to a standstill",
+                self.attacker, self.defender,
+            )?;
+            (self.attacker_losses, self.defender_losses)
+        };
+
+        if let Some(place) = &self.place {
+            write!(f, " at `{}`", place)?;
+
+            if self.captured {
+                write!(f, " and took control of it")?;
+            }
+        }
+
+        write!(f, " (losses: {} and {})", losses.0, losses.1)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn armies_test() {
+        let mut armies = Armies::default();
+        assert!(armies.is_empty());
+
+        armies.muster(army("The Iron Legion", 500));
+        armies.muster(army("The Black Host", 300));
+        assert_eq!(
+            Some(300),
+            armies.get("the black host").map(|army| army.size)
+        );
+
+        let bree: PlaceUuid = uuid::Uuid::nil().into();
+        armies.capture("The Black Host", bree.clone());
+        assert_eq!(
+            vec![bree.clone()],
+            armies.get("The Black Host").unwrap().territory
+        );
+
+        armies.capture("The Iron Legion", bree.clone());
+        assert!(armies.get("The Black Host").unwrap().territory.is_empty());
+
+        armies.muster(army("the iron legion", 200));
+        assert_eq!(2, armies.iter().count());
+        assert_eq!(vec![bree], armies.get("The Iron Legion").unwrap().territory);
+
+        assert_eq!(
+            Some("The Black Host".to_string()),
+            armies.disband("THE BLACK HOST").map(|army| army.name),
+        );
+        assert_eq!(None, armies.disband("The Black Host"));
+    }
+
+    #[test]
+    fn battle_display_test() {
+        let mut battle = Battle {
+            attacker: "The Iron Legion".to_string(),
+            defender: "The Black Host".to_string(),
+            place: Some("Bree".to_string()),
+            attacker_losses: 25,
+            defender_losses: 150,
+            victor: Some("The Iron Legion".to_string()),
+            captured: true,
+        };
+
+        assert_eq!(
+            "The Iron Legion defeated The Black Host at `Bree` and took control of it (losses: 25 and 150)",
+            battle.to_string(),
+        );
+
+        battle.victor = Some("The Black Host".to_string());
+        battle.captured = false;
+        assert_eq!(
+            "The Black Host held off The Iron Legion at `Bree` (losses: 150 and 25)",
+            battle.to_string(),
+        );
+
+        battle.victor = None;
+        battle.place = None;
+        assert_eq!(
+            "The Iron Legion and The Black Host fought to a standstill (losses: 25 and 150)",
+            battle.to_string(),
+        );
+    }
+
+    #[test]
+    fn serialize_deserialize_test() {
+        let armies = Armies(vec![Army {
+            commander: Some(uuid::Uuid::nil().into()),
+            ..army("The Iron Legion", 500)
+        }]);
+
+        assert_eq!(
+            r#"[{"name":"The Iron Legion","size":500,"quality":"veteran","commander":"00000000-0000-0000-0000-000000000000","territory":[]}]"#,
+            armies.to_string(),
+        );
+        assert_eq!(Ok(armies.clone()), armies.to_string().parse());
+        assert_eq!(Ok(Battles::default()), "[]".parse());
+        assert_eq!(Ok(Quality::Levy), "militia".parse());
+    }
+
+    fn army(name: &str, size: u32) -> Army {
+        Army {
+            name: name.to_string(),
+            size,
+            quality: Quality::Veteran,
+            commander: None,
+            territory: Vec::new(),
+        }
+    }
+}
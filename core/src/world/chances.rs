@@ -0,0 +1,127 @@
+//! The odds behind generated content. Generators that pick from weighted tables keep those tables
+//! in data rather than in `match` arms, so that `chances` can show what they're likely to come
+//! up with.
+
+use rand::Rng;
+use std::fmt;
+
+/// Outcomes that a generator picks between, each with its relative weight. Outcomes are
+/// described in the terms of the template they produce, eg. "The [adjective] [thing]".
+pub type WeightTable = &'static [(&'static str, u32)];
+
+/// Something whose generation can be previewed as a list of weighted tables.
+pub trait Chances {
+    fn chances(&self) -> Vec<ChanceTable>;
+}
+
+/// One part of a generated thing (eg. its name) and the outcomes that can decide it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChanceTable {
+    pub label: &'static str,
+    pub outcomes: Vec<(String, u32)>,
+}
+
+impl ChanceTable {
+    pub fn new(label: &'static str, table: WeightTable) -> Self {
+        Self {
+            label,
+            outcomes: table
+                .iter()
+                .map(|(outcome, weight)| (outcome.to_string(), *weight))
+                .collect(),
+        }
+    }
+
+    /// A table where every outcome is as likely as every other.
+    pub fn uniform(label: &'static str, outcomes: &[&str]) -> Self {
+        Self {
+            label,
+            outcomes: outcomes
+                .iter()
+                .map(|outcome| (outcome.to_string(), 1))
+                .collect(),
+        }
+    }
+}
+
+/// Pick an outcome from a table. This rolls exactly as `match rng.gen_range(0..total)` over the
+/// table's total weight would, so a generator can move its odds into a table without changing
+/// what a given seed produces.
+pub fn pick(rng: &mut impl Rng, table: WeightTable) -> &'static str {
+    let total: u32 = table.iter().map(|(_, weight)| weight).sum();
+    let target = rng.gen_range(0..total);
+    let mut acc = 0;
+
+    for (outcome, weight) in table {
+        acc += weight;
+        if acc > target {
+            return outcome;
+        }
+    }
+
+    unreachable!();
+}
+
+impl fmt::Display for ChanceTable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let total: u32 = self.outcomes.iter().map(|(_, weight)| weight).sum();
+
+        write!(
+            f,
+            "## {}\n\n| Outcome | Weight | Chance |\n|---|--:|--:|",
+            self.label,
+        )?;
+
+        for (outcome, weight) in &self.outcomes {
+            write!(
+                f,
+                "\n| {} | {} | {:.1}% |",
+                outcome,
+                weight,
+                f64::from(*weight) * 100. / f64::from(total.max(1)),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    const TABLE: WeightTable = &[("one", 1), ("two or three", 2), ("four", 1)];
+
+    #[test]
+    fn pick_test() {
+        let mut rng1 = SmallRng::seed_from_u64(0);
+        let mut rng2 = SmallRng::seed_from_u64(0);
+
+        for _ in 0..20 {
+            assert_eq!(
+                match rng1.gen_range(0..4) {
+                    0 => "one",
+                    1..=2 => "two or three",
+                    3 => "four",
+                    _ => unreachable!(),
+                },
+                pick(&mut rng2, TABLE),
+            );
+        }
+    }
+
+    #[test]
+    fn display_test() {
+        assert_eq!(
+            "## name\n\n| Outcome | Weight | Chance |\n|---|--:|--:|\n| one | 1 | 25.0% |\n| two or three | 2 | 50.0% |\n| four | 1 | 25.0% |",
+            ChanceTable::new("name", TABLE).to_string(),
+        );
+
+        assert_eq!(
+            vec![("a".to_string(), 1), ("b".to_string(), 1)],
+            ChanceTable::uniform("name", &["a", "b"]).outcomes,
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use super::ParsedThing;
 use crate::app::{AppMeta, Autocomplete, AutocompleteSuggestion};
-use crate::utils::{quoted_words, CaseInsensitiveStr};
-use crate::world::npc::{Age, Ethnicity, Gender, Npc, Species};
+use crate::utils::{quoted_words, CaseInsensitiveStr, Trie};
+use crate::world::npc::{Age, Ethnicity, Gender, Npc, Occupation, Species};
 use crate::world::place::{Place, PlaceType};
 use crate::world::Thing;
 use async_trait::async_trait;
@@ -114,6 +114,7 @@ fn autocomplete_terms<T: Default + FromStr + Into<Thing>>(
     input: &str,
     basic_terms: &[&str],
     vocabulary: &[(&str, &str, &[&str])],
+    index: &Trie<&'static str>,
 ) -> Vec<AutocompleteSuggestion> {
     if let Some(result) = autocomplete_trailing_name::<T>(input) {
         return vec![result];
@@ -215,11 +216,8 @@ fn autocomplete_terms<T: Default + FromStr + Into<Thing>>(
         }
     } else {
         // First word, autocomplete all known vocabulary
-        vocabulary
-            .iter()
-            .flat_map(|(_, _, terms)| terms.iter())
-            .chain(basic_terms.iter())
-            .filter(|s| s.starts_with_ci(parsed.partial))
+        index
+            .starting_with(parsed.partial)
             .filter_map(|term| {
                 let suggestion = parsed.suggestion(term);
                 suggestion.parse::<T>().ok().map(|thing| {
@@ -235,9 +233,25 @@ fn autocomplete_terms<T: Default + FromStr + Into<Thing>>(
     }
 }
 
-#[async_trait(?Send)]
+/// Every word that can begin a description of a place, indexed once when the app starts up.
+pub fn place_terms() -> impl Iterator<Item = &'static str> {
+    PlaceType::get_words().chain(["place"])
+}
+
+/// Every word that can begin a description of a character, indexed once when the app starts up.
+pub fn npc_terms() -> impl Iterator<Item = &'static str> {
+    Age::get_words()
+        .chain(Ethnicity::get_words())
+        .chain(Gender::get_words())
+        .chain(Occupation::get_words())
+        .chain(Species::get_words())
+        .chain(["character", "npc", "person"])
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
 impl Autocomplete for Place {
-    async fn autocomplete(input: &str, _app_meta: &AppMeta) -> Vec<AutocompleteSuggestion> {
+    async fn autocomplete(input: &str, app_meta: &AppMeta) -> Vec<AutocompleteSuggestion> {
         autocomplete_terms::<ParsedThing<Place>>(
             input,
             &["place"],
@@ -246,13 +260,15 @@ impl Autocomplete for Place {
                 "specify a place type (eg. inn)",
                 &PlaceType::get_words().collect::<Vec<_>>(),
             )],
+            &app_meta.vocabulary.place_terms,
         )
     }
 }
 
-#[async_trait(?Send)]
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
 impl Autocomplete for Npc {
-    async fn autocomplete(input: &str, _app_meta: &AppMeta) -> Vec<AutocompleteSuggestion> {
+    async fn autocomplete(input: &str, app_meta: &AppMeta) -> Vec<AutocompleteSuggestion> {
         if let Some(word) = quoted_words(input).last().filter(|w| {
             let s = w.as_str();
             s.starts_with(|c: char| c.is_ascii_digit())
@@ -303,12 +319,18 @@ impl Autocomplete for Npc {
                         "specify a gender",
                         &Gender::get_words().collect::<Vec<_>>(),
                     ),
+                    (
+                        "occupation",
+                        "specify an occupation (eg. \"blacksmith\")",
+                        &Occupation::get_words().collect::<Vec<_>>(),
+                    ),
                     (
                         "species",
                         "specify a species (eg. \"dwarf\")",
                         &Species::get_words().collect::<Vec<_>>(),
                     ),
                 ],
+                &app_meta.vocabulary.npc_terms,
             )
         }
     }
@@ -483,6 +505,10 @@ mod test {
                 ("elf [age]", "specify an age (eg. \"elderly\")"),
                 ("elf [ethnicity]", "specify an ethnicity (eg. \"elvish\")"),
                 ("elf [gender]", "specify a gender"),
+                (
+                    "elf [occupation]",
+                    "specify an occupation (eg. \"blacksmith\")",
+                ),
                 ("elf named [name]", "specify a name"),
             ][..],
             block_on(Npc::autocomplete("elf ", &app_meta())),
@@ -492,6 +518,10 @@ mod test {
             &[
                 ("human [age]", "specify an age (eg. \"elderly\")"),
                 ("human [gender]", "specify a gender"),
+                (
+                    "human [occupation]",
+                    "specify an occupation (eg. \"blacksmith\")",
+                ),
                 ("human named [name]", "specify a name"),
             ][..],
             block_on(Npc::autocomplete("human ", &app_meta())),
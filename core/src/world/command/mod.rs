@@ -1,29 +1,217 @@
-use super::{Field, Npc, Place, Thing};
+use super::chances::Chances;
+use super::connections::{find_connections, PathView, DEFAULT_HOPS, MAX_HOPS};
+use super::contact::create_contact;
+use super::downtime::{downtime, Activity};
+use super::grammar::{count, list, plural, render};
+use super::inventory::{Inventory, Item};
+use super::npc::{
+    ordinal, ContactKind, Ethnicity, Fate, PlayerCharacter, Reaction, Resource, Resources, Rest,
+    Species,
+};
+use super::party::{move_party, party_location_notice, place_with_party};
+use super::place::{
+    apply_flavor, generate_features, generate_lair, generate_settlement_name, Ambience, Dungeon,
+    LairMonster, PlaceType,
+};
+use super::prep::{prep, DEFAULT_PREP_DAYS};
+use super::random::pick_random;
+use super::rest::party_rest;
+use super::status::Status;
+use super::{
+    expire_statuses, rumor, talk, upcoming_festivals, Field, LinkView, Npc, Place, Statuses, Thing,
+};
 use crate::app::{
-    AppMeta, Autocomplete, AutocompleteSuggestion, CommandAlias, CommandMatches, ContextAwareParse,
-    Runnable,
+    AppMeta, Autocomplete, AutocompleteSuggestion, CommandAlias, CommandError, CommandMatches,
+    ContextAwareParse, Event, Runnable, TraceEvent,
 };
-use crate::storage::{Change, RepositoryError, StorageCommand};
-use crate::utils::{quoted_words, CaseInsensitiveStr};
+use crate::config::{Config, Detail};
+use crate::homebrew::Homebrew;
+use crate::locale::Message;
+use crate::reference::{HomebrewEntryKind, HomebrewReference, Item as Equipment, Spell};
+use crate::religion::{assign_worship, load_pantheon};
+use crate::storage::{names_match, Change, KeyValue, RepositoryError, StorageCommand};
+use crate::table::Tables;
+use crate::template::Templates;
+use crate::time::Interval;
+use crate::utils::{capitalize, quoted_words, CaseInsensitiveStr, EscapedView};
+use crate::Uuid;
 use async_trait::async_trait;
 use futures::join;
+use parse::{npc_meanings, split_quantity};
 use std::fmt;
 use std::ops::Range;
 
+/// Descriptions with at least this many understood words are spelled out after the thing is
+/// created, so that it's clear which word set what.
+const MEANINGS_SHOWN_MIN: usize = 4;
+
+/// The number of settlement names suggested by `name`.
+const NAMES_SHOWN: usize = 10;
+
+/// The number of alternatives offered by `suggest`, and how many times to try to come up with
+/// that many distinct values before settling for fewer.
+const SUGGESTIONS_SHOWN: usize = 3;
+const SUGGESTION_ATTEMPTS: usize = 20;
+
+pub use autocomplete::{npc_terms, place_terms};
+
 mod autocomplete;
 mod parse;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum WorldCommand {
+    AddAlias {
+        name: String,
+        alias: String,
+    },
+    AddStatus {
+        name: String,
+        status: String,
+        duration: Option<Interval>,
+    },
+    Ambience {
+        name: String,
+    },
+    Chances {
+        subtype: PlaceType,
+    },
+    Connections {
+        name: String,
+        hops: usize,
+    },
     Create {
-        thing: ParsedThing<Thing>,
+        thing: Box<ParsedThing<Thing>>,
     },
     CreateMultiple {
-        thing: Thing,
+        thing: Box<Thing>,
+        /// How many to create, if it was specified (eg. `create three dwarves`). Otherwise, the
+        /// configured number of suggestions are shown.
+        count: Option<u8>,
+    },
+    CreateContact {
+        name: String,
+        /// The part the contact plays, if it was specified (eg. `create mentor for Aragorn`).
+        /// Otherwise, one is picked at random.
+        kind: Option<ContactKind>,
+    },
+    CreateLair {
+        monster: String,
+    },
+    Crew {
+        name: String,
+    },
+    Downtime {
+        name: String,
+        activity: Activity,
     },
     Edit {
         name: String,
-        diff: ParsedThing<Thing>,
+
+        /// The UUID of the thing being edited, if it has been saved to the journal, resolved when
+        /// the command is parsed so that the edit doesn't land on another entry of the same name.
+        uuid: Option<Uuid>,
+        diff: Box<ParsedThing<Thing>>,
+    },
+    Festivals,
+    Forget {
+        name: String,
+        spell: Spell,
+    },
+    Give {
+        name: String,
+        item: Equipment,
+        quantity: u32,
+    },
+    Kill {
+        name: String,
+    },
+    Learn {
+        name: String,
+        spells: Vec<Spell>,
+    },
+    Lock {
+        name: String,
+        field: String,
+        locked: bool,
+    },
+    Menu {
+        name: String,
+    },
+    Name {
+        ethnicity: Option<Ethnicity>,
+    },
+    Party,
+    PartyAt {
+        name: String,
+    },
+    PartyRest {
+        rest: Rest,
+        encounters: bool,
+    },
+    Pc {
+        name: String,
+        pc: PlayerCharacter,
+    },
+    Prep {
+        days: u16,
+    },
+    Random {
+        thing: Box<Thing>,
+        location: Option<String>,
+    },
+    RemoveStatus {
+        name: String,
+        status: String,
+    },
+    Reaction {
+        name: String,
+    },
+    Rename {
+        name: String,
+        new_name: String,
+    },
+    Rest {
+        name: String,
+        rest: Rest,
+    },
+    Retire {
+        name: String,
+    },
+    Room {
+        name: String,
+        number: usize,
+    },
+    Rumor {
+        about: Option<String>,
+    },
+    SetSpellSlots {
+        name: String,
+        slots: Vec<u8>,
+    },
+    Suggest {
+        name: String,
+        field: String,
+    },
+    Take {
+        name: String,
+        item: Equipment,
+        quantity: u32,
+    },
+    Talk {
+        name: String,
+    },
+    TrackFeature {
+        name: String,
+        feature: String,
+        max: u8,
+        recovery: Rest,
+    },
+    Travel {
+        name: String,
+    },
+    UseResource {
+        name: String,
+        resource: Resource,
     },
 }
 
@@ -34,47 +222,240 @@ pub struct ParsedThing<T> {
     pub word_count: usize,
 }
 
-#[async_trait(?Send)]
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
 impl Runnable for WorldCommand {
-    async fn run(self, input: &str, app_meta: &mut AppMeta) -> Result<String, String> {
+    async fn run(self, input: &str, app_meta: &mut AppMeta) -> Result<String, CommandError> {
+        let language = Config::load(app_meta).await.language;
+
         match self {
+            Self::AddAlias { name, alias } => {
+                let thing = match app_meta.repository.get_by_name(&name).await {
+                    Ok(thing) => thing,
+                    Err(_) => {
+                        let mut message = Message::NoEntityNamed { name: &name }.localize(language);
+
+                        // The names may simply be the wrong way around, so say who has the alias.
+                        if let Ok(other_thing) = app_meta.repository.get_by_name(&alias).await {
+                            message.push(' ');
+                            message.push_str(
+                                &Message::NameAlreadyInUse {
+                                    name: &alias,
+                                    summary: &other_thing.display_summary().to_string(),
+                                }
+                                .localize(language),
+                            );
+                        }
+
+                        return Err(CommandError::unknown_entity(&name, message));
+                    }
+                };
+
+                if thing.is_named(&alias) {
+                    // Echo the alias as it was saved rather than as it was typed this time.
+                    let known_as = thing
+                        .aliases()
+                        .iter()
+                        .find(|known_as| names_match(known_as, &alias))
+                        .unwrap_or(&alias);
+
+                    return Err(CommandError::invalid_state(format!(
+                        "{} is already known as {}.",
+                        thing.name(),
+                        known_as,
+                    )));
+                } else if let Ok(other_thing) = app_meta.repository.get_by_name(&alias).await {
+                    return Err(CommandError::name_conflict(
+                        &alias,
+                        Message::NameInUseBy {
+                            summary: &other_thing.display_summary().to_string(),
+                        }
+                        .localize(language),
+                    ));
+                }
+
+                let mut aliases = thing.aliases().to_vec();
+                aliases.push(alias.clone());
+
+                let diff = match thing {
+                    Thing::Npc(_) => Npc {
+                        aliases: aliases.into(),
+                        ..Default::default()
+                    }
+                    .into(),
+                    Thing::Place(_) => Place {
+                        aliases: aliases.into(),
+                        ..Default::default()
+                    }
+                    .into(),
+                };
+
+                match app_meta.repository.modify(Change::edit(&thing, diff)).await {
+                    Ok(Some(thing)) => Ok(format!(
+                        "{}\n\n_{} is now also known as {}. Use `undo` to reverse this._",
+                        Config::load(app_meta).await.render_summary(&thing),
+                        thing.name(),
+                        alias,
+                    )),
+                    _ => Err(CommandError::storage(
+                        Message::CouldNotEdit { name: &name }.localize(language),
+                    )),
+                }
+            }
+            Self::AddStatus {
+                name,
+                status,
+                duration,
+            } => {
+                if duration.as_ref() == Some(&Interval::default()) {
+                    return Err("A status has to last for some time, such as `for 1 hour`.".into());
+                }
+
+                let (thing, mut statuses) = get_statuses(&name, app_meta).await?;
+
+                let expires = if let Some(duration) = &duration {
+                    Some(
+                        app_meta
+                            .repository
+                            .get_key_value(&KeyValue::Time(None))
+                            .await
+                            .ok()
+                            .and_then(KeyValue::time)
+                            .unwrap_or_default()
+                            .checked_add(duration)
+                            .ok_or_else(|| {
+                                format!("Unable to add a duration of {}.", duration.display_long())
+                            })?,
+                    )
+                } else {
+                    None
+                };
+
+                statuses.add(Status {
+                    name: status.clone(),
+                    expires: expires.clone(),
+                });
+
+                let output = edit_statuses(&thing, statuses, app_meta).await?;
+
+                Ok(if let Some(expires) = expires {
+                    format!(
+                        "{}\n\n_{} is now {} until {}. Use `undo` to reverse this._",
+                        output,
+                        thing.name(),
+                        status,
+                        Config::load(app_meta).await.display_time(&expires),
+                    )
+                } else {
+                    format!(
+                        "{}\n\n_{} is now {}. Use `undo` to reverse this._",
+                        output,
+                        thing.name(),
+                        status,
+                    )
+                })
+            }
             Self::Create {
                 thing: parsed_thing,
             } => {
-                let diff = parsed_thing.thing;
+                let mut diff = parsed_thing.thing;
+                place_with_party(&mut diff, app_meta).await;
                 let unknown_words = parsed_thing.unknown_words.to_owned();
+                let meanings = match diff {
+                    Thing::Npc(_) => {
+                        npc_meanings(input.strip_prefix_ci("create ").unwrap_or(input))
+                    }
+                    Thing::Place(_) => Vec::new(),
+                };
+                let (config, tables, homebrew, time_of_day, pantheon) = join!(
+                    Config::load(app_meta),
+                    Tables::load(app_meta),
+                    Homebrew::load(app_meta),
+                    app_meta.time_of_day(),
+                    load_pantheon(app_meta),
+                );
+                let words = tables.words();
                 let mut output = None;
 
                 for _ in 0..10 {
                     let mut thing = diff.clone();
-                    thing.regenerate(&mut app_meta.rng, &app_meta.demographics);
-                    let mut temp_output = format!(
-                        "{}",
-                        thing.display_details(
-                            app_meta
-                                .repository
-                                .load_relations(&thing)
-                                .await
-                                .unwrap_or_default()
-                        )
-                    );
+                    words.scope(|| thing.regenerate(&mut app_meta.rng, &app_meta.demographics));
+                    tables.apply(&mut app_meta.rng, &mut thing);
+                    homebrew.apply(&mut app_meta.rng, &app_meta.demographics, &mut thing);
+                    if let Thing::Place(place) = &mut thing {
+                        apply_flavor(place, time_of_day, &mut app_meta.rng);
+                        assign_worship(place, &pantheon, &app_meta.demographics, &mut app_meta.rng);
+                    }
+                    app_meta.trace(TraceEvent::Generated {
+                        thing_type: thing.as_str().to_string(),
+                        name: thing.name().to_string(),
+                    });
+                    let mut temp_output = match config.detail {
+                        Detail::Full => config.render(
+                            thing.display_details(
+                                app_meta
+                                    .repository
+                                    .load_relations(&thing)
+                                    .await
+                                    .unwrap_or_default(),
+                            ),
+                        ),
+                        Detail::Summary => config.render_summary(&thing),
+                    };
                     let mut command_alias = None;
 
+                    if meanings.len() >= MEANINGS_SHOWN_MIN {
+                        temp_output.push_str(&format!(
+                            "\n\n_Understood {}._",
+                            list(
+                                &meanings
+                                    .iter()
+                                    .map(|(word, meaning)| format!("**{}** as {}", word, meaning))
+                                    .collect::<Vec<_>>(),
+                            ),
+                        ));
+                    }
+
+                    let has_features =
+                        matches!(&thing, Thing::Place(place) if place.has_features());
+                    let mut region_name = None;
+
                     let change = match thing.name() {
-                        Field::Locked(Some(name)) => {
+                        Field::Locked(Some(name)) | Field::Unlocked(Some(name)) if has_features => {
                             temp_output.push_str(&format!(
+                                "\n\n_{} has been automatically added to your `journal`, along with the places within it._",
+                                name,
+                            ));
+
+                            if thing.name().is_unlocked() {
+                                app_meta.command_aliases.insert(CommandAlias::literal(
+                                    "more",
+                                    format!("create {}", diff.display_description()),
+                                    WorldCommand::CreateMultiple {
+                                        thing: Box::new(diff.clone()),
+                                        count: None,
+                                    }
+                                    .into(),
+                                ));
+                            }
+
+                            region_name = Some(name.to_string());
+                            Change::CreateAndSave { thing }
+                        }
+                        Field::Locked(Some(name)) => {
+                            temp_output.push_str(&render(
                                     "\n\n_Because you specified a name, {name} has been automatically added to your `journal`. Use `undo` to remove {them}._",
-                                    name = name,
-                                    them = thing.gender().them(),
+                                    &thing.gender(),
+                                    &[("name", &EscapedView::new(name).to_string())],
                                 ));
 
                             Change::CreateAndSave { thing }
                         }
                         Field::Unlocked(Some(name)) => {
-                            temp_output.push_str(&format!(
+                            temp_output.push_str(&render(
                                     "\n\n_{name} has not yet been saved. Use ~save~ to save {them} to your `journal`. For more suggestions, type ~more~._",
-                                    name = name,
-                                    them = thing.gender().them(),
+                                    &thing.gender(),
+                                    &[("name", &EscapedView::new(name).to_string())],
                                 ));
 
                             command_alias = Some(CommandAlias::literal(
@@ -90,7 +471,8 @@ impl Runnable for WorldCommand {
                                 "more",
                                 format!("create {}", diff.display_description()),
                                 WorldCommand::CreateMultiple {
-                                    thing: diff.clone(),
+                                    thing: Box::new(diff.clone()),
+                                    count: None,
                                 }
                                 .into(),
                             ));
@@ -100,14 +482,71 @@ impl Runnable for WorldCommand {
                         _ => Change::Create { thing },
                     };
 
-                    match app_meta.repository.modify(change).await {
-                        Ok(_) => {
+                    let dungeon = match &change {
+                        Change::Create {
+                            thing: Thing::Place(place),
+                        }
+                        | Change::CreateAndSave {
+                            thing: Thing::Place(place),
+                        } => place
+                            .dungeon
+                            .value()
+                            .cloned()
+                            .zip(place.name.value().cloned()),
+                        _ => None,
+                    };
+
+                    // A region is saved in one transaction with the places within it, so that
+                    // they're undone together and none are saved if any can't be.
+                    let (change, features) = match change {
+                        Change::CreateAndSave {
+                            thing: Thing::Place(mut region),
+                        } if region_name.is_some() => {
+                            region.uuid = Some(Uuid::new_v4().into());
+                            let features = region_features(&region, app_meta).await;
+                            (
+                                Change::CreateAndSave {
+                                    thing: region.into(),
+                                },
+                                features,
+                            )
+                        }
+                        change => (change, Vec::new()),
+                    };
+
+                    let result = if features.is_empty() {
+                        app_meta.repository.modify(change).await.map(|_| ())
+                    } else {
+                        let mut changes = vec![change];
+                        changes.extend(features.iter().map(|place| Change::CreateAndSave {
+                            thing: place.clone().into(),
+                        }));
+
+                        app_meta
+                            .repository
+                            .transaction(changes)
+                            .await
+                            .map_err(|(changes, e)| (changes.into_iter().next().unwrap(), e))
+                    };
+
+                    match result {
+                        Ok(()) => {
+                            if let Some(name) = region_name {
+                                temp_output.push_str(
+                                    &describe_features(&name, features, app_meta).await,
+                                );
+                            }
+
                             output = Some(temp_output);
 
                             if let Some(alias) = command_alias {
                                 app_meta.command_aliases.insert(alias);
                             }
 
+                            if let Some((dungeon, name)) = dungeon {
+                                insert_room_aliases(&name, &dungeon, app_meta);
+                            }
+
                             break;
                         }
                         Err((Change::Create { thing }, RepositoryError::NameAlreadyExists))
@@ -121,17 +560,23 @@ impl Runnable for WorldCommand {
                                     .get_by_name(thing.name().value().unwrap())
                                     .await
                                 {
-                                    return Err(format!(
-                                        "That name is already in use by {}.",
-                                        other_thing.display_summary(),
+                                    return Err(CommandError::name_conflict(
+                                        thing.name().value().unwrap(),
+                                        Message::NameInUseBy {
+                                            summary: &other_thing.display_summary().to_string(),
+                                        }
+                                        .localize(language),
                                     ));
                                 } else {
-                                    return Err("That name is already in use.".to_string());
+                                    return Err(CommandError::name_conflict(
+                                        thing.name().value().unwrap(),
+                                        Message::NameInUse.localize(language),
+                                    ));
                                 }
                             }
                         }
-                        Err((Change::Create { thing }, RepositoryError::MissingName)) => return Err(format!("There is no name generator implemented for that type. You must specify your own name using `{} named [name]`.", thing.display_description())),
-                        Err(_) => return Err("An error occurred.".to_string()),
+                        Err((Change::Create { thing }, RepositoryError::MissingName)) => return Err(format!("There is no name generator implemented for that type. You must specify your own name using `{} named [name]`.", thing.display_description()).into()),
+                        Err(_) => return Err(CommandError::storage(Message::UnknownError.localize(language))),
                     }
                 }
 
@@ -141,26 +586,61 @@ impl Runnable for WorldCommand {
                     Err(format!(
                         "Couldn't create a unique {} name.",
                         diff.display_description(),
-                    ))
+                    )
+                    .into())
                 }
             }
-            Self::CreateMultiple { thing } => {
-                let mut output = format!(
-                    "# Alternative suggestions for \"{}\"",
-                    thing.display_description(),
+            Self::CreateMultiple { mut thing, count } => {
+                place_with_party(&mut thing, app_meta).await;
+                let (config, tables, homebrew, time_of_day, pantheon) = join!(
+                    Config::load(app_meta),
+                    Tables::load(app_meta),
+                    Homebrew::load(app_meta),
+                    app_meta.time_of_day(),
+                    load_pantheon(app_meta),
                 );
+                let words = tables.words();
+                let mut output = if let Some(count) = count {
+                    format!(
+                        "# {}",
+                        capitalize(&super::grammar::count(
+                            count.into(),
+                            &thing.display_description().to_string(),
+                        )),
+                    )
+                } else {
+                    format!(
+                        "# Alternative suggestions for \"{}\"",
+                        thing.display_description(),
+                    )
+                };
 
-                for i in 1..=10 {
+                for i in 1..=count.unwrap_or(config.suggestions) {
                     let mut thing_output = None;
 
                     for _ in 0..10 {
-                        let mut thing = thing.clone();
-                        thing.regenerate(&mut app_meta.rng, &app_meta.demographics);
+                        let mut thing = Thing::clone(&thing);
+                        words.scope(|| thing.regenerate(&mut app_meta.rng, &app_meta.demographics));
+                        tables.apply(&mut app_meta.rng, &mut thing);
+                        homebrew.apply(&mut app_meta.rng, &app_meta.demographics, &mut thing);
+                        if let Thing::Place(place) = &mut thing {
+                            apply_flavor(place, time_of_day, &mut app_meta.rng);
+                            assign_worship(
+                                place,
+                                &pantheon,
+                                &app_meta.demographics,
+                                &mut app_meta.rng,
+                            );
+                        }
+                        app_meta.trace(TraceEvent::Generated {
+                            thing_type: thing.as_str().to_string(),
+                            name: thing.name().to_string(),
+                        });
                         let temp_thing_output = format!(
                             "{}~{}~ {}",
                             if i == 1 { "\n\n" } else { "\\\n" },
                             i % 10,
-                            thing.display_summary(),
+                            config.render_summary(&thing),
                         );
                         let command_alias = CommandAlias::literal(
                             (i % 10).to_string(),
@@ -178,7 +658,11 @@ impl Runnable for WorldCommand {
                                 break;
                             }
                             Err((_, RepositoryError::NameAlreadyExists)) => {}
-                            Err(_) => return Err("An error occurred.".to_string()),
+                            Err(_) => {
+                                return Err(CommandError::storage(
+                                    Message::UnknownError.localize(language),
+                                ))
+                            }
                         }
                     }
 
@@ -192,475 +676,4642 @@ impl Runnable for WorldCommand {
 
                 app_meta.command_aliases.insert(CommandAlias::literal(
                     "more",
-                    format!("create {}", thing.display_description()),
-                    Self::CreateMultiple { thing }.into(),
+                    if let Some(count) = count {
+                        format!(
+                            "create {} {}",
+                            count,
+                            plural(&thing.display_description().to_string())
+                        )
+                    } else {
+                        format!("create {}", thing.display_description())
+                    },
+                    Self::CreateMultiple { thing, count }.into(),
                 ));
 
                 output.push_str("\n\n_For even more suggestions, type ~more~._");
 
                 Ok(output)
             }
-            Self::Edit { name, diff } => {
-                let ParsedThing {
-                    thing: diff,
-                    unknown_words,
-                    word_count: _,
-                } = diff;
+            Self::CreateContact { name, kind } => create_contact(&name, kind, app_meta).await,
+            Self::CreateLair { monster } => {
+                let homebrew = HomebrewReference::load(app_meta).await;
+                let monster = homebrew
+                    .get(&monster, Some(HomebrewEntryKind::Monster))
+                    .filter(|entry| entry.kind == HomebrewEntryKind::Monster)
+                    .map(|entry| {
+                        LairMonster::homebrew(
+                            entry.name(),
+                            entry.challenge_rating().unwrap_or_default(),
+                            entry.monster_type(),
+                        )
+                    })
+                    .or_else(|| LairMonster::srd(&monster))
+                    .ok_or_else(|| {
+                        CommandError::unknown_entity(
+                            &monster,
+                            format!(
+                                "There's no lair for \"{}\". Lairs can be created for legendary monsters like the adult black dragon, or for monsters added with `homebrew import`.",
+                                monster,
+                            ),
+                        )
+                    })?;
 
-                let thing_type = diff.as_str();
+                let config = Config::load(app_meta).await;
 
-                match app_meta.repository.modify(Change::Edit {
+                for _ in 0..10 {
+                    let mut place = Place::default();
+                    generate_lair(&mut place, &mut app_meta.rng, &monster);
+
+                    let name = place.name.to_string();
+                    let dungeon = place.dungeon.value().cloned().unwrap();
+                    let thing: Thing = place.into();
+                    app_meta.trace(TraceEvent::Generated {
+                        thing_type: thing.as_str().to_string(),
                         name: name.clone(),
-                        uuid: None,
-                        diff,
-                    }).await {
-                    Ok(Some(thing)) if matches!(app_meta.repository.undo_history().next(), Some(Change::EditAndUnsave { .. })) => Ok(format!(
-                        "{}\n\n_{} was successfully edited and automatically saved to your `journal`. Use `undo` to reverse this._",
-                        thing.display_details(app_meta.repository.load_relations(&thing).await.unwrap_or_default()),
-                        name,
-                    )),
-                    Ok(Some(thing)) => Ok(format!(
-                        "{}\n\n_{} was successfully edited. Use `undo` to reverse this._",
-                        thing.display_details(app_meta.repository.load_relations(&thing).await.unwrap_or_default()),
-                        name,
-                    )),
-                    Err((_, RepositoryError::NotFound)) => Err(format!(r#"There is no {} named "{}"."#, thing_type, name)),
-                    _ => Err(format!("Couldn't edit `{}`.", name)),
+                    });
+
+                    let output = format!(
+                        "{}\n\n_The lair of the {} has been automatically added to your `journal`, with its hoard in the lair's inventory. Use `undo` to remove it._",
+                        config.render(thing.display_details(Default::default())),
+                        monster.name(),
+                    );
+
+                    match app_meta
+                        .repository
+                        .modify(Change::CreateAndSave { thing })
+                        .await
+                    {
+                        Ok(_) => {
+                            insert_room_aliases(&name, &dungeon, app_meta);
+                            return Ok(output);
+                        }
+                        Err((_, RepositoryError::NameAlreadyExists)) => {}
+                        Err(_) => {
+                            return Err(CommandError::storage(
+                                Message::UnknownError.localize(language),
+                            ))
+                        }
+                    }
                 }
-                .map(|s| append_unknown_words_notice(s, input, unknown_words))
+
+                Err(format!(
+                    "Couldn't create a unique name for the lair of the {}.",
+                    monster.name()
+                )
+                .into())
             }
-        }
-    }
-}
+            Self::Downtime { name, activity } => downtime(&name, activity, app_meta).await,
+            Self::Crew { name } => {
+                let place = match app_meta.repository.get_by_name(&name).await {
+                    Ok(Thing::Place(place)) if place.vehicle_type().is_some() => place,
+                    Ok(thing) => {
+                        return Err(CommandError::unknown_entity(
+                            &thing.name().to_string(),
+                            format!("{} doesn't have a crew.", thing.name()),
+                        ))
+                    }
+                    Err(_) => {
+                        return Err(CommandError::unknown_entity(
+                            &name,
+                            Message::NoEntityNamed { name: &name }.localize(language),
+                        ))
+                    }
+                };
 
-#[async_trait(?Send)]
-impl ContextAwareParse for WorldCommand {
-    async fn parse_input(input: &str, app_meta: &AppMeta) -> CommandMatches<Self> {
-        let mut matches = CommandMatches::default();
+                let name = place.name.to_string();
+                let mut output = format!("# Crew of {}", name);
+                let mut crew = Vec::new();
 
-        if let Some(Ok(thing)) = input
-            .strip_prefix_ci("create ")
-            .map(|s| s.parse::<ParsedThing<Thing>>())
-        {
-            if thing.unknown_words.is_empty() {
-                matches.push_canonical(Self::Create { thing });
-            } else {
-                matches.push_fuzzy(Self::Create { thing });
-            }
-        } else if let Ok(thing) = input.parse::<ParsedThing<Thing>>() {
-            matches.push_fuzzy(Self::Create { thing });
-        }
+                if let Some(uuid) = &place.uuid {
+                    let mut things = app_meta.repository.journal().await.unwrap_or_default();
+                    things.extend(app_meta.repository.recent().cloned());
 
-        if let Some(word) = quoted_words(input)
-            .skip(1)
-            .find(|word| word.as_str().eq_ci("is"))
-        {
-            let (name, description) = (
-                input[..word.range().start].trim(),
-                input[word.range().end..].trim(),
-            );
+                    crew.extend(things.into_iter().filter(|thing| {
+                        matches!(thing, Thing::Npc(npc) if npc.location_uuid.value() == Some(uuid))
+                    }));
+                }
 
-            let (diff, thing) = if let Ok(thing) = app_meta.repository.get_by_name(name).await {
-                (
-                    match thing {
-                        Thing::Npc(_) => description
-                            .parse::<ParsedThing<Npc>>()
-                            .map(|npc| npc.into_thing()),
-                        Thing::Place(_) => description
-                            .parse::<ParsedThing<Place>>()
-                            .map(|npc| npc.into_thing()),
-                    }
-                    .or_else(|_| description.parse()),
-                    Some(thing),
-                )
-            } else {
-                // This will be an error when we try to run the command, but for now we'll pretend
-                // it's valid so that we can provide a more coherent message.
-                (description.parse(), None)
-            };
+                // Crew members need somewhere to be, so the vehicle is saved before they are
+                // generated aboard it.
+                if crew.is_empty() {
+                    let uuid = if let Some(uuid) = &place.uuid {
+                        uuid.clone()
+                    } else {
+                        let uuid = app_meta
+                            .repository
+                            .modify(Change::Save {
+                                name: name.clone(),
+                                uuid: None,
+                            })
+                            .await
+                            .ok()
+                            .flatten()
+                            .and_then(|thing| thing.uuid().copied())
+                            .ok_or_else(|| {
+                                CommandError::storage(
+                                    Message::CouldNotSave { name: &name }.localize(language),
+                                )
+                            })?;
 
-            if let Ok(mut diff) = diff {
-                let name = thing
-                    .map(|t| t.name().to_string())
-                    .unwrap_or_else(|| name.to_string());
+                        output.push_str(&format!(
+                            "\n\n_{} was automatically saved to your `journal` so that its crew can be aboard._",
+                            name,
+                        ));
 
-                diff.unknown_words.iter_mut().for_each(|range| {
-                    *range = range.start + word.range().end + 1..range.end + word.range().end + 1
-                });
+                        uuid.into()
+                    };
 
-                matches.push_fuzzy(Self::Edit { name, diff });
-            }
-        }
+                    let (tables, homebrew) =
+                        join!(Tables::load(app_meta), Homebrew::load(app_meta));
+                    let words = tables.words();
 
-        matches
-    }
-}
+                    for occupation in place.vehicle_type().unwrap().crew() {
+                        for _ in 0..10 {
+                            let mut thing: Thing = Npc {
+                                location_uuid: uuid.clone().into(),
+                                occupation: (*occupation).into(),
+                                ..Default::default()
+                            }
+                            .into();
+                            words.scope(|| {
+                                thing.regenerate(&mut app_meta.rng, &app_meta.demographics)
+                            });
+                            tables.apply(&mut app_meta.rng, &mut thing);
+                            homebrew.apply(&mut app_meta.rng, &app_meta.demographics, &mut thing);
+                            app_meta.trace(TraceEvent::Generated {
+                                thing_type: thing.as_str().to_string(),
+                                name: thing.name().to_string(),
+                            });
 
-#[async_trait(?Send)]
-impl Autocomplete for WorldCommand {
-    async fn autocomplete(input: &str, app_meta: &AppMeta) -> Vec<AutocompleteSuggestion> {
-        let mut suggestions = Vec::new();
+                            match app_meta.repository.modify(Change::Create { thing }).await {
+                                Ok(Some(thing)) => {
+                                    crew.push(thing);
+                                    break;
+                                }
+                                Ok(None) | Err((_, RepositoryError::NameAlreadyExists)) => {}
+                                Err(_) => {
+                                    return Err(CommandError::storage(
+                                        Message::UnknownError.localize(language),
+                                    ))
+                                }
+                            }
+                        }
+                    }
+                }
 
-        let (mut place_suggestions, mut npc_suggestions) = join!(
-            Place::autocomplete(input, app_meta),
-            Npc::autocomplete(input, app_meta),
-        );
+                if crew.is_empty() {
+                    return Ok(format!("{} has no crew.", name));
+                }
 
-        suggestions.append(&mut place_suggestions);
-        suggestions.append(&mut npc_suggestions);
+                let config = Config::load(app_meta).await;
 
-        let mut input_words = quoted_words(input).skip(1);
+                for (i, thing) in crew.iter().enumerate().take(10) {
+                    let i = i + 1;
 
-        if let Some((is_word, next_word)) = input_words
-            .find(|word| word.as_str().eq_ci("is"))
-            .and_then(|word| input_words.next().map(|next_word| (word, next_word)))
-        {
-            if let Ok(thing) = app_meta
-                .repository
-                .get_by_name(input[..is_word.range().start].trim())
-                .await
-            {
-                let split_pos = input.len() - input[is_word.range().end..].trim_start().len();
-
-                let edit_suggestions = match thing {
-                    Thing::Npc(_) => Npc::autocomplete(input[split_pos..].trim_start(), app_meta),
-                    Thing::Place(_) => {
-                        Place::autocomplete(input[split_pos..].trim_start(), app_meta)
-                    }
-                }
-                .await;
-
-                suggestions.extend(edit_suggestions.into_iter().map(|suggestion| {
-                    AutocompleteSuggestion::new(
-                        format!("{}{}", &input[..split_pos], suggestion.term),
-                        format!("edit {}", thing.as_str()),
-                    )
-                }));
+                    output.push_str(&format!(
+                        "{}~{}~ {}",
+                        if i == 1 { "\n\n" } else { "\\\n" },
+                        i % 10,
+                        config.render_summary(thing),
+                    ));
 
-                if next_word.as_str().in_ci(&["named", "called"]) && input_words.next().is_some() {
-                    suggestions.push(AutocompleteSuggestion::new(
-                        input.to_string(),
-                        format!("rename {}", thing.as_str()),
+                    app_meta.command_aliases.insert(CommandAlias::literal(
+                        (i % 10).to_string(),
+                        format!("load {}", thing.name()),
+                        StorageCommand::Load {
+                            name: thing.name().to_string(),
+                        }
+                        .into(),
                     ));
                 }
+
+                Ok(output)
             }
-        }
+            Self::Edit { name, uuid, diff } => {
+                let ParsedThing {
+                    thing: diff,
+                    unknown_words,
+                    word_count: _,
+                } = *diff;
 
-        if let Ok(thing) = app_meta.repository.get_by_name(input.trim_end()).await {
-            suggestions.push(AutocompleteSuggestion::new(
-                if input.ends_with(char::is_whitespace) {
-                    format!("{}is [{} description]", input, thing.as_str())
-                } else {
-                    format!("{} is [{} description]", input, thing.as_str())
-                },
-                format!("edit {}", thing.as_str()),
-            ));
-        } else if let Some((last_word_index, last_word)) =
-            quoted_words(input).enumerate().skip(1).last()
-        {
-            if "is".starts_with_ci(last_word.as_str()) {
-                if let Ok(thing) = app_meta
+                let thing_type = diff.as_str();
+
+                match app_meta
                     .repository
-                    .get_by_name(input[..last_word.range().start].trim())
+                    .modify(Change::Edit {
+                        name: name.clone(),
+                        uuid,
+                        diff,
+                    })
                     .await
                 {
-                    suggestions.push(AutocompleteSuggestion::new(
-                        if last_word.range().end == input.len() {
-                            format!(
-                                "{}is [{} description]",
-                                &input[..last_word.range().start],
-                                thing.as_str(),
+                    Ok(Some(thing))
+                        if matches!(
+                            app_meta.repository.undo_history().next(),
+                            Some(Change::EditAndUnsave { .. })
+                        ) =>
+                    {
+                        Ok(format!(
+                            "{}\n\n{}",
+                            Config::load(app_meta).await.render(
+                                thing.display_details(
+                                    app_meta
+                                        .repository
+                                        .load_relations(&thing)
+                                        .await
+                                        .unwrap_or_default()
+                                )
+                            ),
+                            Message::EditedAndSaved { name: &name }.localize(language),
+                        ))
+                    }
+                    Ok(Some(thing)) => Ok(format!(
+                        "{}\n\n{}",
+                        Config::load(app_meta).await.render(
+                            thing.display_details(
+                                app_meta
+                                    .repository
+                                    .load_relations(&thing)
+                                    .await
+                                    .unwrap_or_default()
                             )
-                        } else {
-                            format!("{}[{} description]", &input, thing.as_str())
+                        ),
+                        Message::Edited { name: &name }.localize(language),
+                    )),
+                    Err((_, RepositoryError::NotFound)) => Err(CommandError::unknown_entity(
+                        &name,
+                        format!(
+                            r#"There is no {} named "{}"."#,
+                            thing_type,
+                            EscapedView::new(&name)
+                        ),
+                    )),
+                    _ => Err(CommandError::storage(
+                        Message::CouldNotEdit { name: &name }.localize(language),
+                    )),
+                }
+                .map(|s| append_unknown_words_notice(s, input, unknown_words))
+            }
+            Self::Festivals => {
+                let now = app_meta
+                    .repository
+                    .get_key_value(&KeyValue::Time(None))
+                    .await
+                    .ok()
+                    .and_then(KeyValue::time)
+                    .unwrap_or_default();
+
+                let upcoming = upcoming_festivals(&now, app_meta).await;
+
+                if upcoming.is_empty() {
+                    Ok("There are no festivals coming up. Settlements and realms, such as a `town` or a `kingdom`, celebrate festivals of their own.".to_string())
+                } else {
+                    Ok(upcoming.iter().fold(
+                        "# Upcoming festivals\n".to_string(),
+                        |mut output, upcoming| {
+                            output.push_str(&format!("\n* {}", upcoming.display()));
+                            output
                         },
-                        format!("edit {}", thing.as_str()),
                     ))
                 }
-            } else if let Some(suggestion) = ["named", "called"]
-                .iter()
-                .find(|s| s.starts_with_ci(last_word.as_str()))
-            {
-                let second_last_word = quoted_words(input).nth(last_word_index - 1).unwrap();
+            }
+            Self::Give {
+                name,
+                item,
+                quantity,
+            } => {
+                let (mut inventory, capacity) = get_inventory(&name, app_meta).await?;
+                let item = Item::equipment(&item, quantity);
+                inventory.add_item(item.clone());
+                let weight = inventory.weight();
 
-                if second_last_word.as_str().eq_ci("is") {
-                    if let Ok(thing) = app_meta
+                let mut output = edit_npc(
+                    &name,
+                    Npc {
+                        inventory: inventory.into(),
+                        ..Default::default()
+                    },
+                    app_meta,
+                )
+                .await?;
+
+                output.push_str(&format!(
+                    "\n\n_{} was given {}. Use `undo` to reverse this._",
+                    name, item,
+                ));
+
+                if weight > capacity as f32 {
+                    output.push_str(&format!(
+                        "\n\n! {} is now carrying {} lbs, exceeding a carrying capacity of {} lbs.",
+                        name,
+                        (weight * 10.).round() / 10.,
+                        capacity,
+                    ));
+                }
+
+                Ok(output)
+            }
+            Self::Kill { name } => set_fate(&name, true, app_meta).await,
+            Self::Learn { name, spells } => {
+                let mut known = get_spells(&name, app_meta).await?;
+                let mut learned = Vec::new();
+
+                for spell in spells {
+                    let spell_name = spell.get_name().to_string();
+
+                    if !known.contains(&spell_name) && !learned.contains(&spell_name) {
+                        learned.push(spell_name);
+                    }
+                }
+
+                if learned.is_empty() {
+                    return Err(CommandError::invalid_state(format!(
+                        "{} already knows all of those spells.",
+                        name,
+                    )));
+                }
+
+                known.extend(learned.iter().cloned());
+
+                let mut output = edit_npc(
+                    &name,
+                    Npc {
+                        spells: known.into(),
+                        ..Default::default()
+                    },
+                    app_meta,
+                )
+                .await?;
+
+                output.push_str(&format!(
+                    "\n\n_{} learned {}. Use `undo` to reverse this._",
+                    name,
+                    list(&learned),
+                ));
+
+                Ok(output)
+            }
+            Self::Forget { name, spell } => {
+                let mut known = get_spells(&name, app_meta).await?;
+                let len = known.len();
+                known.retain(|spell_name| spell_name != spell.get_name());
+
+                if known.len() == len {
+                    return Err(CommandError::unknown_entity(
+                        spell.get_name(),
+                        format!("{} doesn't know {}.", name, spell.get_name()),
+                    ));
+                }
+
+                let mut output = edit_npc(
+                    &name,
+                    Npc {
+                        spells: known.into(),
+                        ..Default::default()
+                    },
+                    app_meta,
+                )
+                .await?;
+
+                output.push_str(&format!(
+                    "\n\n_{} forgot {}. Use `undo` to reverse this._",
+                    name,
+                    spell.get_name(),
+                ));
+
+                Ok(output)
+            }
+            Self::Party => Ok(party(app_meta).await),
+            Self::PartyAt { name } => move_party(&name, false, app_meta).await,
+            Self::Pc { name, pc } => set_pc(&name, pc, app_meta).await,
+            Self::PartyRest { rest, encounters } => party_rest(rest, encounters, app_meta).await,
+            Self::Prep { days } => Ok(prep(days, app_meta).await),
+            Self::Random { thing, location } => pick_random(*thing, location, app_meta).await,
+            Self::Retire { name } => set_fate(&name, false, app_meta).await,
+            Self::RemoveStatus { name, status } => {
+                let (thing, mut statuses) = get_statuses(&name, app_meta).await?;
+
+                let status = statuses
+                    .remove(&status)
+                    .ok_or_else(|| format!("{} isn't {}.", thing.name(), status))?;
+
+                edit_statuses(&thing, statuses, app_meta)
+                    .await
+                    .map(|output| {
+                        format!(
+                            "{}\n\n_{} is no longer {}. Use `undo` to reverse this._",
+                            output,
+                            thing.name(),
+                            status.name,
+                        )
+                    })
+            }
+            Self::Rename { name, new_name } => {
+                let thing = if let Ok(thing) = app_meta.repository.get_by_name(&name).await {
+                    thing
+                } else {
+                    return Err(CommandError::unknown_entity(
+                        &name,
+                        Message::NoEntityNamed { name: &name }.localize(language),
+                    ));
+                };
+
+                if new_name.trim().is_empty() {
+                    return Err(Message::BlankName.localize(language).into());
+                } else if !new_name.eq_ci(&name) {
+                    // Taking one of its own aliases as its name is fine.
+                    if let Some(other_thing) = app_meta
                         .repository
-                        .get_by_name(input[..second_last_word.range().start].trim())
+                        .get_by_name(&new_name)
                         .await
+                        .ok()
+                        .filter(|other_thing| other_thing != &thing)
                     {
-                        suggestions.push(AutocompleteSuggestion::new(
-                            if last_word.range().end == input.len() {
-                                format!(
-                                    "{}{} [name]",
-                                    &input[..last_word.range().start],
-                                    suggestion,
-                                )
-                            } else {
-                                format!("{}[name]", input)
-                            },
-                            format!("rename {}", thing.as_str()),
+                        return Err(CommandError::name_conflict(
+                            &new_name,
+                            Message::NameInUseBy {
+                                summary: &other_thing.display_summary().to_string(),
+                            }
+                            .localize(language),
                         ));
                     }
                 }
+
+                // Relations are stored by UUID, so changing the name doesn't affect them, but
+                // aliases refer to things by name.
+                let old_name = thing.name().to_string();
+                let diff: Thing = match thing {
+                    Thing::Npc(_) => Npc {
+                        name: new_name.as_str().into(),
+                        ..Default::default()
+                    }
+                    .into(),
+                    Thing::Place(_) => Place {
+                        name: new_name.as_str().into(),
+                        ..Default::default()
+                    }
+                    .into(),
+                };
+
+                let result = app_meta
+                    .repository
+                    .modify(Change::Edit {
+                        name: old_name.clone(),
+                        uuid: thing.uuid().cloned(),
+                        diff,
+                    })
+                    .await;
+
+                if result.is_ok() {
+                    rename_aliases(&old_name, &new_name, app_meta).await;
+                }
+
+                match result {
+                    Ok(Some(thing))
+                        if matches!(
+                            app_meta.repository.undo_history().next(),
+                            Some(Change::EditAndUnsave { .. })
+                        ) =>
+                    {
+                        Ok(format!(
+                            "{}\n\n{}",
+                            Config::load(app_meta).await.render(
+                                thing.display_details(
+                                    app_meta
+                                        .repository
+                                        .load_relations(&thing)
+                                        .await
+                                        .unwrap_or_default()
+                                )
+                            ),
+                            Message::RenamedAndSaved {
+                                name: &name,
+                                new_name: &new_name
+                            }
+                            .localize(language),
+                        ))
+                    }
+                    Ok(Some(thing)) => Ok(format!(
+                        "{}\n\n{}",
+                        Config::load(app_meta).await.render(
+                            thing.display_details(
+                                app_meta
+                                    .repository
+                                    .load_relations(&thing)
+                                    .await
+                                    .unwrap_or_default()
+                            )
+                        ),
+                        Message::Renamed {
+                            name: &name,
+                            new_name: &new_name
+                        }
+                        .localize(language),
+                    )),
+                    Err((_, RepositoryError::NotFound)) => Err(CommandError::unknown_entity(
+                        &name,
+                        Message::NoEntityNamed { name: &name }.localize(language),
+                    )),
+                    _ => Err(CommandError::storage(
+                        Message::CouldNotRename { name: &name }.localize(language),
+                    )),
+                }
             }
-        }
+            Self::Ambience { name } => {
+                let place = match app_meta.repository.get_by_name(&name).await {
+                    Ok(Thing::Place(place)) => place,
+                    Ok(thing) => {
+                        return Err(CommandError::unknown_entity(
+                            &thing.name().to_string(),
+                            format!("{} isn't a place.", thing.name()),
+                        ))
+                    }
+                    Err(_) => {
+                        return Err(CommandError::unknown_entity(
+                            &name,
+                            Message::NoEntityNamed { name: &name }.localize(language),
+                        ))
+                    }
+                };
 
-        suggestions
-    }
-}
+                let time_of_day = app_meta.time_of_day().await;
+                let ambience = Ambience::generate(&place, time_of_day, &mut app_meta.rng);
+                let output = ambience.to_string();
 
-impl fmt::Display for WorldCommand {
-    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        match self {
-            Self::Create { thing } => write!(f, "create {}", thing.thing.display_description()),
-            Self::CreateMultiple { thing } => {
-                write!(f, "create  multiple {}", thing.display_description())
+                (app_meta.event_dispatcher)(Event::Ambience(ambience));
+
+                Ok(output)
             }
-            Self::Edit { name, diff } => {
-                write!(f, "{} is {}", name, diff.thing.display_description())
+            Self::Chances { subtype } => {
+                let tables = subtype.chances();
+
+                if tables.is_empty() {
+                    Err(format!(
+                        "Nothing about generating {} is left to chance.",
+                        plural(subtype.as_str()),
+                    )
+                    .into())
+                } else {
+                    Ok(format!(
+                        "# Chances: {}\n\n{}",
+                        subtype,
+                        tables
+                            .iter()
+                            .map(|table| table.to_string())
+                            .collect::<Vec<_>>()
+                            .join("\n\n"),
+                    ))
+                }
             }
-        }
-    }
-}
+            Self::Connections { name, hops } => {
+                let thing = match app_meta.repository.get_by_name(&name).await {
+                    Ok(thing) => thing,
+                    Err(_) => {
+                        return Err(CommandError::unknown_entity(
+                            &name,
+                            Message::NoEntityNamed { name: &name }.localize(language),
+                        ))
+                    }
+                };
 
-impl<T: Into<Thing>> ParsedThing<T> {
-    pub fn into_thing(self) -> ParsedThing<Thing> {
-        ParsedThing {
-            thing: self.thing.into(),
-            unknown_words: self.unknown_words,
-            word_count: self.word_count,
-        }
-    }
-}
+                let paths = find_connections(&thing, hops, &app_meta.repository).await;
 
-impl<T: Default> Default for ParsedThing<T> {
-    fn default() -> Self {
-        Self {
-            thing: T::default(),
-            unknown_words: Vec::default(),
-            word_count: 0,
-        }
-    }
-}
+                if paths.is_empty() {
+                    Ok(format!("{} has no known connections.", thing.name()))
+                } else {
+                    Ok(paths.iter().fold(
+                        format!("# Connections of {}\n", thing.name()),
+                        |mut output, path| {
+                            output.push_str(&format!("\n* {}", PathView::new(&thing, path)));
+                            output
+                        },
+                    ))
+                }
+            }
+            Self::Lock {
+                name,
+                field,
+                locked,
+            } => {
+                let thing = match app_meta.repository.get_by_name(&name).await {
+                    Ok(thing) => thing,
+                    Err(_) => {
+                        return Err(CommandError::unknown_entity(
+                            &name,
+                            Message::NoEntityNamed { name: &name }.localize(language),
+                        ))
+                    }
+                };
 
-impl<T: Into<Thing>> From<ParsedThing<T>> for Thing {
-    fn from(input: ParsedThing<T>) -> Self {
-        input.thing.into()
-    }
-}
+                if thing.is_field_locked(&field).is_none() {
+                    return Err(CommandError::unknown_entity(
+                        &field,
+                        format!(
+                            "{} has no field called \"{}\". Its fields are {}.",
+                            thing.name(),
+                            field,
+                            list(
+                                &thing
+                                    .lockable_fields()
+                                    .iter()
+                                    .map(|field| format!("`{}`", field))
+                                    .collect::<Vec<_>>(),
+                            ),
+                        ),
+                    ));
+                }
 
-fn append_unknown_words_notice(
-    mut output: String,
-    input: &str,
-    unknown_words: Vec<Range<usize>>,
-) -> String {
-    if !unknown_words.is_empty() {
-        output.push_str(
-            "\n\n! initiative.sh doesn't know some of those words, but it did its best.\n\n\\> ",
-        );
+                let name = thing.name().to_string();
+
+                match app_meta
+                    .repository
+                    .modify(Change::SetLocked {
+                        name: name.clone(),
+                        uuid: thing.uuid().cloned(),
+                        field: field.clone(),
+                        locked,
+                    })
+                    .await
+                {
+                    Ok(Some(thing)) => Ok(format!(
+                        "{}\n\n_{}'s {} is now {}. Use `undo` to reverse this._",
+                        Config::load(app_meta).await.render(
+                            thing.display_details(
+                                app_meta
+                                    .repository
+                                    .load_relations(&thing)
+                                    .await
+                                    .unwrap_or_default(),
+                            ),
+                        ),
+                        name,
+                        field,
+                        if locked {
+                            "locked and will be kept if it's regenerated"
+                        } else {
+                            "unlocked and may change if it's regenerated"
+                        },
+                    )),
+                    _ => Err(CommandError::storage(format!(
+                        "Couldn't {} `{}`'s {}.",
+                        if locked { "lock" } else { "unlock" },
+                        name,
+                        field,
+                    ))),
+                }
+            }
+            Self::Menu { name } => {
+                let place = match app_meta.repository.get_by_name(&name).await {
+                    Ok(Thing::Place(place)) if place.has_menu() => place,
+                    Ok(thing) => {
+                        return Err(CommandError::unknown_entity(
+                            &thing.name().to_string(),
+                            format!("{} doesn't have a menu.", thing.name()),
+                        ))
+                    }
+                    Err(_) => {
+                        return Err(CommandError::unknown_entity(
+                            &name,
+                            Message::NoEntityNamed { name: &name }.localize(language),
+                        ))
+                    }
+                };
+
+                // Any unlocked items are regenerated and then locked, so the menu stays the same
+                // once it has been seen.
+                let mut menu = place.menu.value().cloned().unwrap_or_default();
+                menu.regenerate(&mut app_meta.rng);
+                menu.lock_all();
+
+                let mut output = format!("# Menu at {}\n\n{}", name, menu.display());
+
+                if place.menu.value() != Some(&menu) {
+                    app_meta
+                        .repository
+                        .modify(Change::Edit {
+                            name: name.clone(),
+                            uuid: place.uuid.as_ref().map(|uuid| uuid.as_ref().to_owned()),
+                            diff: Place {
+                                menu: menu.into(),
+                                ..Default::default()
+                            }
+                            .into(),
+                        })
+                        .await
+                        .map_err(|_| format!("Couldn't save the menu of `{}`.", name))?;
+
+                    if place.uuid.is_none() {
+                        output.push_str(&format!(
+                            "\n\n_{} was automatically saved to your `journal` to preserve its menu. Use `undo` to reverse this._",
+                            name,
+                        ));
+                    }
+                }
+
+                Ok(output)
+            }
+            Self::Name { ethnicity } => {
+                let ethnicity =
+                    ethnicity.unwrap_or_else(|| app_meta.demographics.dominant_ethnicity());
+
+                Ok((0..NAMES_SHOWN).fold(
+                    format!("# Settlement names ({})\n", ethnicity),
+                    |mut output, _| {
+                        output.push_str(&format!(
+                            "\n* {}",
+                            generate_settlement_name(&mut app_meta.rng, &ethnicity),
+                        ));
+                        output
+                    },
+                ))
+            }
+            Self::Suggest { name, field } => {
+                let thing = match app_meta.repository.get_by_name(&name).await {
+                    Ok(thing) => thing,
+                    Err(_) => {
+                        return Err(CommandError::unknown_entity(
+                            &name,
+                            Message::NoEntityNamed { name: &name }.localize(language),
+                        ))
+                    }
+                };
+                let name = thing.name().to_string();
+
+                if thing.field_diff(&field).is_none() {
+                    return Err(format!(
+                        "There are no suggestions for {}'s {}. Suggestions are available for {}.",
+                        name,
+                        field,
+                        list(
+                            &thing
+                                .lockable_fields()
+                                .iter()
+                                .filter(|field| thing.field_diff(field).is_some())
+                                .map(|field| format!("`{}`", field))
+                                .collect::<Vec<_>>(),
+                        ),
+                    )
+                    .into());
+                }
+
+                // Regenerate only the one field, keeping everything else as it is.
+                let current = thing.display_field(&field);
+                let mut suggestions: Vec<(String, Thing)> = Vec::new();
+
+                for _ in 0..SUGGESTION_ATTEMPTS {
+                    let mut candidate = thing.clone();
+                    candidate.lock_all();
+                    candidate.set_field_locked(&field, false);
+                    candidate.regenerate(&mut app_meta.rng, &app_meta.demographics);
+
+                    let (value, diff) = match (
+                        candidate.display_field(&field),
+                        candidate.field_diff(&field),
+                    ) {
+                        (Some(value), Some(diff)) => (value, diff),
+                        _ => continue,
+                    };
+
+                    if current.as_ref() == Some(&value)
+                        || suggestions.iter().any(|(other, _)| other == &value)
+                        || (field == "name"
+                            && app_meta.repository.get_by_name(&value).await.is_ok())
+                    {
+                        continue;
+                    }
+
+                    suggestions.push((value, diff));
+
+                    if suggestions.len() == SUGGESTIONS_SHOWN {
+                        break;
+                    }
+                }
+
+                if suggestions.is_empty() {
+                    return Err(format!(
+                        "Couldn't come up with any suggestions for {}'s {}.",
+                        name, field,
+                    )
+                    .into());
+                }
+
+                let mut output = format!("# Suggested {} for {}\n", field, name);
+
+                for (i, (value, diff)) in suggestions.into_iter().enumerate() {
+                    output.push_str(&format!(
+                        "{}~{}~ {}",
+                        if i == 0 { "\n" } else { "\\\n" },
+                        i + 1,
+                        value,
+                    ));
+
+                    app_meta.command_aliases.insert(CommandAlias::literal(
+                        (i + 1).to_string(),
+                        format!("use this {} for {}", field, name),
+                        WorldCommand::Edit {
+                            name: name.clone(),
+                            uuid: thing.uuid().cloned(),
+                            diff: Box::new(ParsedThing {
+                                thing: diff,
+                                unknown_words: Vec::new(),
+                                word_count: 0,
+                            }),
+                        }
+                        .into(),
+                    ));
+                }
+
+                output.push_str(&format!(
+                    "\n\n_{}'s {} hasn't been changed. Type the number of a suggestion to use it._",
+                    name, field,
+                ));
+
+                Ok(output)
+            }
+            Self::Rumor { about } => {
+                let about = if let Some(name) = about {
+                    match app_meta.repository.get_by_name(&name).await {
+                        Ok(thing) => Some(thing),
+                        Err(_) => {
+                            return Err(CommandError::unknown_entity(
+                                &name,
+                                Message::NoEntityNamed { name: &name }.localize(language),
+                            ))
+                        }
+                    }
+                } else {
+                    None
+                };
+
+                // Characters who are dead or retired aren't up to anything worth gossiping about,
+                // unless the gossip is specifically about them. The players' own characters are
+                // left out too, since the players already know what they've been up to.
+                let mut journal = app_meta.repository.journal().await.unwrap_or_default();
+                journal.retain(|thing| {
+                    !thing.is_archived() && thing.fate().is_none() && !thing.is_pc()
+                });
+
+                let time_of_day = app_meta.time_of_day().await;
+
+                Ok(format!(
+                    "# Rumor\n\n\"{}\"",
+                    rumor::generate(
+                        &mut app_meta.rng,
+                        &app_meta.demographics,
+                        &journal,
+                        about.as_ref(),
+                        time_of_day,
+                    ),
+                ))
+            }
+            Self::Room { name, number } => {
+                let place = match app_meta.repository.get_by_name(&name).await {
+                    Ok(Thing::Place(place)) if place.dungeon.is_some() => place,
+                    Ok(thing) => {
+                        return Err(CommandError::unknown_entity(
+                            &thing.name().to_string(),
+                            format!("{} isn't a dungeon.", thing.name()),
+                        ))
+                    }
+                    Err(_) => {
+                        return Err(CommandError::unknown_entity(
+                            &name,
+                            Message::NoEntityNamed { name: &name }.localize(language),
+                        ))
+                    }
+                };
+
+                let name = place.name.to_string();
+                let dungeon = place.dungeon.value().unwrap();
+                let room = number
+                    .checked_sub(1)
+                    .and_then(|i| dungeon.rooms.get(i))
+                    .ok_or_else(|| {
+                        format!(
+                            "{} has no room {}. Its rooms are numbered 1 to {}.",
+                            name,
+                            number,
+                            dungeon.rooms.len(),
+                        )
+                    })?;
+
+                let output = room.display(number, &name).to_string();
+                insert_room_aliases(&name, dungeon, app_meta);
+
+                Ok(output)
+            }
+            Self::Reaction { name } => {
+                let npc = match app_meta.repository.get_by_name(&name).await {
+                    Ok(Thing::Npc(npc)) => npc,
+                    _ => {
+                        return Err(CommandError::unknown_entity(
+                            &name,
+                            Message::NoCharacterNamed { name: &name }.localize(language),
+                        ))
+                    }
+                };
+
+                let reaction = Reaction::roll(&mut app_meta.rng, npc.attitude.value());
+
+                edit_npc(
+                    &name,
+                    Npc {
+                        attitude: reaction.attitude.into(),
+                        ..Default::default()
+                    },
+                    app_meta,
+                )
+                .await
+                .map(|output| {
+                    format!(
+                        "{}
+
+_Reaction roll: {}. {} is {} toward the party. Use `undo` to reverse this._",
+                        output, reaction, name, reaction.attitude,
+                    )
+                })
+            }
+            Self::Rest { name, rest } => {
+                let mut resources = get_resources(&name, app_meta).await?;
+                resources.rest(rest);
+
+                let time = app_meta
+                    .repository
+                    .get_key_value(&KeyValue::Time(None))
+                    .await
+                    .ok()
+                    .and_then(KeyValue::time)
+                    .unwrap_or_default()
+                    .checked_add(&rest.interval())
+                    .ok_or_else(|| "Unable to advance time.".to_string())?;
+
+                app_meta
+                    .repository
+                    .modify(Change::SetKeyValue {
+                        key_value: KeyValue::Time(Some(time.clone())),
+                    })
+                    .await
+                    .map_err(|_| "Unable to advance time.".to_string())?;
+
+                let notices = expire_statuses(&time, app_meta).await;
+                let mut output = edit_resources(&name, resources, app_meta).await?;
+
+                output.push_str(&format!(
+                    "\n\n_{} finished a {} rest. It is now {}._",
+                    name,
+                    rest,
+                    Config::load(app_meta).await.display_time(&time),
+                ));
+
+                if !notices.is_empty() {
+                    output.push_str("\\\n");
+                    output.push_str(&notices.join("\\\n"));
+                }
+
+                Ok(output)
+            }
+            Self::SetSpellSlots { name, slots } => {
+                let mut resources = get_resources(&name, app_meta).await?;
+                resources.set_spell_slots(&slots);
+
+                edit_resources(&name, resources, app_meta)
+                    .await
+                    .map(|output| {
+                        format!(
+                            "{}\n\n_{}'s spell slots were updated. Use `undo` to reverse this._",
+                            output, name,
+                        )
+                    })
+            }
+            Self::Take {
+                name,
+                item,
+                quantity,
+            } => {
+                let (mut inventory, _) = get_inventory(&name, app_meta).await?;
+
+                let item = match inventory.remove_item(item.get_name(), quantity) {
+                    Ok(item) => item,
+                    Err(0) => {
+                        return Err(CommandError::unknown_entity(
+                            item.get_name(),
+                            format!("{} doesn't have any {}.", name, item.get_name()),
+                        ));
+                    }
+                    Err(held) => {
+                        return Err(CommandError::invalid_state(format!(
+                            "{} only has {}.",
+                            name,
+                            Item::equipment(&item, held),
+                        )));
+                    }
+                };
+
+                edit_npc(
+                    &name,
+                    Npc {
+                        inventory: inventory.into(),
+                        ..Default::default()
+                    },
+                    app_meta,
+                )
+                .await
+                .map(|output| {
+                    format!(
+                        "{}\n\n_{} was taken from {}. Use `undo` to reverse this._",
+                        output, item, name,
+                    )
+                })
+            }
+            Self::Talk { name } => {
+                let thing = match app_meta.repository.get_by_name(&name).await {
+                    Ok(thing @ Thing::Npc(_)) => thing,
+                    _ => {
+                        return Err(CommandError::unknown_entity(
+                            &name,
+                            Message::NoCharacterNamed { name: &name }.localize(language),
+                        ))
+                    }
+                };
+
+                let connected: Vec<Thing> = find_connections(&thing, 2, &app_meta.repository)
+                    .await
+                    .into_iter()
+                    .flatten()
+                    .map(|(_, thing)| thing)
+                    .filter(|thing| !thing.is_archived() && !thing.is_pc())
+                    .collect();
+
+                // As with rumors, the players' own characters and anyone who is dead or retired
+                // are left out of the gossip.
+                let mut journal = app_meta.repository.journal().await.unwrap_or_default();
+                journal.retain(|other| {
+                    !other.is_archived()
+                        && other.fate().is_none()
+                        && !other.is_pc()
+                        && other.uuid() != thing.uuid()
+                });
+
+                let time_of_day = app_meta.time_of_day().await;
+                let npc = match thing {
+                    Thing::Npc(npc) => npc,
+                    Thing::Place(_) => unreachable!(),
+                };
+                let name = npc.name.to_string();
+
+                let mut output = format!(
+                    "# Talking to {}\n\n{}",
+                    name,
+                    talk::generate(
+                        &mut app_meta.rng,
+                        &app_meta.demographics,
+                        &npc,
+                        &connected,
+                        &journal,
+                        time_of_day,
+                    ),
+                );
+
+                if let Some(attitude) = npc.attitude.value() {
+                    output.push_str(&format!("\n\n_{} is {} toward the party._", name, attitude,));
+                } else {
+                    output.push_str(&format!(
+                        "\n\n_{} hasn't formed an opinion of the party yet. Use `reaction {}` to roll one._",
+                        name, name,
+                    ));
+                }
+
+                Ok(output)
+            }
+            Self::TrackFeature {
+                name,
+                feature,
+                max,
+                recovery,
+            } => {
+                let mut resources = get_resources(&name, app_meta).await?;
+                resources.track_feature(&feature, max, recovery);
+
+                edit_resources(&name, resources, app_meta).await.map(|output| {
+                    format!(
+                        "{}\n\n_{}'s uses of {} are now being tracked. Use `undo` to reverse this._",
+                        output, name, feature,
+                    )
+                })
+            }
+            Self::Travel { name } => move_party(&name, true, app_meta).await,
+            Self::UseResource { name, resource } => {
+                let mut resources = get_resources(&name, app_meta).await?;
+
+                let (used, exhausted, missing) = match &resource {
+                    Resource::SpellSlot(level) => {
+                        let slot = format!("{}-level spell slot", ordinal(*level));
+                        (
+                            count(1, &slot),
+                            count(0, &slot),
+                            format!("any {}", plural(&slot)),
+                        )
+                    }
+                    Resource::Feature(feature) => {
+                        let feature = resources
+                            .features
+                            .iter()
+                            .find(|f| f.name.eq_ci(feature))
+                            .map_or(feature, |f| &f.name);
+
+                        (
+                            feature.to_string(),
+                            format!("no uses of {}", feature),
+                            feature.to_string(),
+                        )
+                    }
+                };
+
+                let pool = match resources.expend(&resource) {
+                    Ok(pool) => pool,
+                    Err(()) if resources.get(&resource).is_some() => {
+                        return Err(CommandError::invalid_state(format!(
+                            "{} has {} remaining.",
+                            name, exhausted,
+                        )));
+                    }
+                    Err(()) => {
+                        return Err(CommandError::unknown_entity(
+                            &used,
+                            format!("{} doesn't have {}.", name, missing),
+                        ))
+                    }
+                };
+
+                edit_resources(&name, resources, app_meta).await?;
+
+                Ok(format!(
+                    "{} used {}, leaving {} of {}. Use `undo` to reverse this.",
+                    name,
+                    used,
+                    pool.remaining(),
+                    pool.max,
+                ))
+            }
+        }
+    }
+}
+
+/// Split input of the form `[command] for [name]`, preferring a split where the name refers to an
+/// existing entry.
+async fn split_for<'a>(input: &'a str, app_meta: &AppMeta) -> Option<(&'a str, String)> {
+    let mut result = None;
+
+    for word in quoted_words(input)
+        .skip(1)
+        .filter(|word| word.as_str().eq_ci("for"))
+    {
+        let (command, name) = (
+            input[..word.range().start].trim(),
+            input[word.range().end..].trim(),
+        );
+
+        if name.is_empty() {
+            continue;
+        } else if let Ok(thing) = app_meta.repository.get_by_name(name).await {
+            return Some((command, thing.name().to_string()));
+        } else {
+            result = Some((command, name.to_string()));
+        }
+    }
+
+    result
+}
+
+/// Generate the places within a new region, leaving out any whose names are already taken.
+async fn region_features(region: &Place, app_meta: &mut AppMeta) -> Vec<Place> {
+    let mut features = Vec::new();
+
+    for place in generate_features(region, &mut app_meta.rng, &app_meta.demographics) {
+        let name = place.name.to_string();
+
+        if features
+            .iter()
+            .any(|feature: &Place| feature.name.to_string().eq_ci(&name))
+            || app_meta.repository.get_by_name(&name).await.is_ok()
+        {
+            continue;
+        }
+
+        features.push(place);
+    }
+
+    features
+}
+
+/// A numbered list of the places within a newly created region, which can be loaded by number.
+async fn describe_features(name: &str, features: Vec<Place>, app_meta: &mut AppMeta) -> String {
+    let config = Config::load(app_meta).await;
+    let mut output = String::new();
+
+    for (i, place) in features.into_iter().enumerate() {
+        let i = i + 1;
+        let thing: Thing = place.into();
+
+        output.push_str(&format!(
+            "{}~{}~ {}",
+            if i == 1 { "\n\n" } else { "\\\n" },
+            i % 10,
+            config.render_summary(&thing),
+        ));
+
+        app_meta.command_aliases.insert(CommandAlias::literal(
+            (i % 10).to_string(),
+            format!("load {}", thing.name()),
+            StorageCommand::Load {
+                name: thing.name().to_string(),
+            }
+            .into(),
+        ));
+    }
+
+    if output.is_empty() {
+        output
+    } else {
+        format!("\n\n## Places within {}{}", name, output)
+    }
+}
+
+/// Point any aliases from the previous output that refer to a renamed thing at its new name.
+async fn rename_aliases(name: &str, new_name: &str, app_meta: &mut AppMeta) {
+    let aliases: Vec<CommandAlias> = app_meta.command_aliases.iter().cloned().collect();
+
+    for alias in aliases {
+        if let Some(alias) = alias.renamed(name, new_name, app_meta).await {
+            app_meta.command_aliases.replace(alias);
+        }
+    }
+}
+
+/// Get the resources of the named character, or an error message if the name doesn't refer to a
+/// character.
+async fn get_resources(name: &str, app_meta: &AppMeta) -> Result<Resources, CommandError> {
+    let language = Config::load(app_meta).await.language;
+
+    match app_meta.repository.get_by_name(name).await {
+        Ok(Thing::Npc(npc)) => Ok(npc.resources.value().cloned().unwrap_or_default()),
+        _ => Err(CommandError::unknown_entity(
+            name,
+            Message::NoCharacterNamed { name }.localize(language),
+        )),
+    }
+}
+
+/// Parse a quantity of SRD equipment, eg. "a longsword" or "3 daggers".
+pub(crate) fn parse_equipment(input: &str) -> Option<(u32, Equipment)> {
+    let input = input.trim();
+
+    let (quantity, item) = match input.split_once(' ') {
+        Some((article, item)) if article.eq_ci("a") || article.eq_ci("an") => (1, item),
+        Some((quantity, item)) => quantity.parse().map_or((1, input), |q| (q, item)),
+        None => (1, input),
+    };
+
+    if quantity == 0 {
+        return None;
+    }
+
+    let item = item.trim();
+
+    item.parse()
+        .ok()
+        .or_else(|| item.strip_suffix_ci("s").and_then(|s| s.parse().ok()))
+        .or_else(|| item.strip_suffix_ci("es").and_then(|s| s.parse().ok()))
+        .map(|item| (quantity, item))
+}
+
+/// Get the inventory and carrying capacity of the named character, or an error message if the
+/// name doesn't refer to a character.
+async fn get_inventory(name: &str, app_meta: &AppMeta) -> Result<(Inventory, u16), CommandError> {
+    let language = Config::load(app_meta).await.language;
+
+    match app_meta.repository.get_by_name(name).await {
+        Ok(Thing::Npc(npc)) => Ok((
+            npc.inventory.value().cloned().unwrap_or_default(),
+            npc.carrying_capacity(),
+        )),
+        _ => Err(CommandError::unknown_entity(
+            name,
+            Message::NoCharacterNamed { name }.localize(language),
+        )),
+    }
+}
+
+/// Get the names of the spells known by the named character, or an error message if the name
+/// doesn't refer to a character.
+async fn get_spells(name: &str, app_meta: &AppMeta) -> Result<Vec<String>, CommandError> {
+    let language = Config::load(app_meta).await.language;
+
+    match app_meta.repository.get_by_name(name).await {
+        Ok(Thing::Npc(npc)) => Ok(npc.spells.value().cloned().unwrap_or_default()),
+        _ => Err(CommandError::unknown_entity(
+            name,
+            Message::NoCharacterNamed { name }.localize(language),
+        )),
+    }
+}
+
+/// Parse a list of SRD spells, eg. "Fireball, Shield, and Protection from Evil and Good". Returns
+/// `None` if any of the spells isn't recognized.
+fn parse_spells(input: &str) -> Option<Vec<Spell>> {
+    let mut spells = Vec::new();
+
+    for item in input.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        let item = item.strip_prefix_ci("and ").unwrap_or(item).trim();
+
+        // Some spell names contain "and", so the whole item is tried first.
+        if let Ok(spell) = item.parse() {
+            spells.push(spell);
+        } else {
+            let (a, b) = item.split_once(" and ")?;
+            spells.push(a.trim().parse().ok()?);
+            spells.push(b.trim().parse().ok()?);
+        }
+    }
+
+    Some(spells).filter(|spells| !spells.is_empty())
+}
+
+/// Replace the resources of the named character, returning the updated character details.
+async fn edit_resources(
+    name: &str,
+    resources: Resources,
+    app_meta: &mut AppMeta,
+) -> Result<String, CommandError> {
+    edit_npc(
+        name,
+        Npc {
+            resources: resources.into(),
+            ..Default::default()
+        },
+        app_meta,
+    )
+    .await
+}
+
+/// Get the named character or place along with its current statuses, or an error message if the
+/// name doesn't refer to anything.
+async fn get_statuses(name: &str, app_meta: &AppMeta) -> Result<(Thing, Statuses), CommandError> {
+    let language = Config::load(app_meta).await.language;
+
+    match app_meta.repository.get_by_name(name).await {
+        Ok(thing) => {
+            let statuses = match &thing {
+                Thing::Npc(npc) => npc.statuses.value(),
+                Thing::Place(place) => place.statuses.value(),
+            }
+            .cloned()
+            .unwrap_or_default();
+
+            Ok((thing, statuses))
+        }
+        Err(_) => Err(CommandError::unknown_entity(
+            name,
+            Message::NoCharacterOrPlaceNamed { name }.localize(language),
+        )),
+    }
+}
+
+/// Replace the statuses of a character or place, returning its updated summary.
+async fn edit_statuses(
+    thing: &Thing,
+    statuses: Statuses,
+    app_meta: &mut AppMeta,
+) -> Result<String, CommandError> {
+    let language = Config::load(app_meta).await.language;
+
+    let diff = match thing {
+        Thing::Npc(_) => Npc {
+            statuses: statuses.into(),
+            ..Default::default()
+        }
+        .into(),
+        Thing::Place(_) => Place {
+            statuses: statuses.into(),
+            ..Default::default()
+        }
+        .into(),
+    };
+
+    match app_meta.repository.modify(Change::edit(thing, diff)).await {
+        Ok(Some(thing)) => Ok(Config::load(app_meta).await.render_summary(&thing)),
+        _ => Err(CommandError::storage(
+            Message::CouldNotEdit {
+                name: &thing.name().to_string(),
+            }
+            .localize(language),
+        )),
+    }
+}
+
+/// Mark a character as dead or retired as of the current game time, as an edit that can be undone.
+async fn set_fate(name: &str, dead: bool, app_meta: &mut AppMeta) -> Result<String, CommandError> {
+    let language = Config::load(app_meta).await.language;
+
+    let thing = match app_meta.repository.get_by_name(name).await {
+        Ok(thing @ Thing::Npc(_)) => thing,
+        Ok(thing) => {
+            return Err(CommandError::unknown_entity(
+                &thing.name().to_string(),
+                format!("{} isn't a character.", thing.name()),
+            ))
+        }
+        Err(_) => {
+            return Err(CommandError::unknown_entity(
+                name,
+                Message::NoCharacterNamed { name }.localize(language),
+            ))
+        }
+    };
+    let name = thing.name().to_string();
+
+    if let Some(fate) = thing.fate() {
+        return Err(CommandError::invalid_state(format!(
+            "{} is already {}.",
+            name, fate,
+        )));
+    }
+
+    let time = app_meta
+        .repository
+        .get_key_value(&KeyValue::Time(None))
+        .await
+        .ok()
+        .and_then(KeyValue::time)
+        .unwrap_or_default();
+    let fate = if dead {
+        Fate::Dead { time }
+    } else {
+        Fate::Retired { time }
+    };
+
+    match app_meta
+        .repository
+        .modify(Change::Edit {
+            name: name.clone(),
+            uuid: thing.uuid().cloned(),
+            diff: Npc {
+                fate: fate.clone().into(),
+                ..Default::default()
+            }
+            .into(),
+        })
+        .await
+    {
+        Ok(Some(thing)) => {
+            let config = Config::load(app_meta).await;
+
+            Ok(format!(
+                "{}\n\n_{} {} on {}. Use `undo` to reverse this._",
+                config.render_summary(&thing),
+                name,
+                if dead { "died" } else { "retired" },
+                config.display_time(fate.time()),
+            ))
+        }
+        _ => Err(CommandError::storage(
+            Message::CouldNotEdit { name: &name }.localize(language),
+        )),
+    }
+}
+
+/// Add a player character to the journal, or fill in the statistics of an existing character,
+/// making them a player character if they weren't already.
+async fn set_pc(
+    name: &str,
+    pc: PlayerCharacter,
+    app_meta: &mut AppMeta,
+) -> Result<String, CommandError> {
+    let language = Config::load(app_meta).await.language;
+
+    let change = match app_meta.repository.get_by_name(name).await {
+        Ok(Thing::Npc(npc)) => {
+            let mut merged = npc.pc.value().cloned().unwrap_or_default();
+            merged.merge(pc);
+
+            Change::Edit {
+                name: npc.name.to_string(),
+                uuid: npc.uuid.as_ref().map(|uuid| uuid.as_ref().to_owned()),
+                diff: Npc {
+                    pc: merged.into(),
+                    ..Default::default()
+                }
+                .into(),
+            }
+        }
+        Ok(thing) => {
+            return Err(CommandError::unknown_entity(
+                &thing.name().to_string(),
+                format!("{} isn't a character.", thing.name()),
+            ))
+        }
+        Err(_) => {
+            let mut npc = Npc::default();
+            npc.name.replace(name.to_string());
+            npc.pc.replace(pc);
+
+            Change::CreateAndSave { thing: npc.into() }
+        }
+    };
+    let created = matches!(change, Change::CreateAndSave { .. });
+
+    match app_meta.repository.modify(change).await {
+        Ok(Some(thing)) => {
+            let config = Config::load(app_meta).await;
+
+            Ok(format!(
+                "{}\n\n_{} was {} your journal as a player character. Use `undo` to reverse this._",
+                config.render_summary(&thing),
+                thing.name(),
+                if created { "added to" } else { "updated in" },
+            ))
+        }
+        Err((_, RepositoryError::NameAlreadyExists)) => Err(CommandError::name_conflict(
+            name,
+            format!(
+                "There is already an entry named \"{}\".",
+                EscapedView::new(name)
+            ),
+        )),
+        _ => Err(CommandError::storage(
+            Message::CouldNotSave { name }.localize(language),
+        )),
+    }
+}
+
+/// A table of the player characters in the journal, with the statistics needed at the table.
+async fn party(app_meta: &AppMeta) -> String {
+    let mut pcs: Vec<(String, PlayerCharacter)> = app_meta
+        .repository
+        .journal()
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .chain(app_meta.repository.recent().cloned())
+        .filter(|thing| !thing.is_archived())
+        .filter_map(|thing| match thing {
+            Thing::Npc(npc) => Some((npc.name.value()?.to_string(), npc.pc.value()?.clone())),
+            Thing::Place(_) => None,
+        })
+        .collect();
+
+    let location_notice = party_location_notice(app_meta).await;
+
+    if pcs.is_empty() {
+        let mut output = "# Party\n\n*There are no player characters in your journal. To add one, use `pc [name]`.*".to_string();
+
+        if let Some(notice) = location_notice {
+            output.push_str(&format!("\n\n{}", notice));
+        }
+
+        return output;
+    }
+
+    pcs.sort_by(|(a, _), (b, _)| a.cmp_ci(b));
+
+    let dash = || "-".to_string();
+    let mut output = "# Party\n\n| Name | Class | Level | AC | Passive Perception | Player |\n|---|---|---|---|---|---|".to_string();
+
+    for (name, pc) in pcs.iter() {
+        output.push_str(&format!(
+            "\n| {} | {} | {} | {} | {} | {} |",
+            LinkView::new(name),
+            pc.class.clone().unwrap_or_else(dash),
+            pc.level.map_or_else(dash, |level| level.to_string()),
+            pc.armor_class.map_or_else(dash, |ac| ac.to_string()),
+            pc.passive_perception.map_or_else(dash, |pp| pp.to_string()),
+            pc.player.clone().unwrap_or_else(dash),
+        ));
+    }
+
+    let levels: Vec<u32> = pcs
+        .iter()
+        .filter_map(|(_, pc)| pc.level.map(u32::from))
+        .collect();
+
+    if !levels.is_empty() {
+        output.push_str(&format!(
+            "\n\n*Average party level: {}*",
+            (levels.iter().sum::<u32>() as f32 / levels.len() as f32 * 10.).round() / 10.,
+        ));
+    }
+
+    if let Some(notice) = location_notice {
+        output.push_str(&format!("\n\n{}", notice));
+    }
+
+    output
+}
+
+/// Parse the new value of a single field, as in "Gottfried's age is 40y" or "The Silver Eel's
+/// description is a dive", returning the edit to make. Unknown words are relative to `value`.
+///
+/// Returns `None` if `field` isn't a field of `thing`, or if `value` says nothing about it.
+fn parse_field_edit(thing: &Thing, field: &str, value: &str) -> Option<WorldCommand> {
+    let name = thing.name().to_string();
+    let field = field.to_lowercase();
+
+    if value.is_empty() {
+        return None;
+    }
+
+    let diff: ParsedThing<Thing> = match (thing, field.as_str()) {
+        (_, "name") => {
+            return Some(WorldCommand::Rename {
+                name,
+                new_name: value.to_string(),
+            })
+        }
+        (Thing::Place(_), "description") => ParsedThing {
+            thing: Place {
+                description: Field::new(value.to_string()),
+                ..Default::default()
+            }
+            .into(),
+            unknown_words: Vec::new(),
+            word_count: 1,
+        },
+        (Thing::Npc(npc), "age") if parse_age_years(value).is_some() => {
+            let age_years = parse_age_years(value);
+            let mut diff = Npc {
+                age_years: age_years.into(),
+                ..Default::default()
+            };
+
+            // Keep the age category in step with the new age, if the species is known.
+            if let Some(age) = npc
+                .species
+                .value()
+                .zip(age_years)
+                .map(|(species, years)| species.age_from_years(years))
+            {
+                diff.age = Field::new(age);
+            }
+
+            ParsedThing {
+                thing: diff.into(),
+                unknown_words: Vec::new(),
+                word_count: 1,
+            }
+        }
+        (Thing::Npc(_), _) if thing.lockable_fields().contains(&field.as_str()) => {
+            let parsed = value.parse::<ParsedThing<Npc>>().ok()?;
+            ParsedThing {
+                thing: parsed.thing.field_diff(&field)?.into(),
+                unknown_words: parsed.unknown_words,
+                word_count: parsed.word_count,
+            }
+        }
+        _ => return None,
+    };
+
+    if diff.thing.display_field(&field).is_some() {
+        Some(WorldCommand::Edit {
+            name,
+            uuid: thing.uuid().cloned(),
+            diff: Box::new(diff),
+        })
+    } else {
+        None
+    }
+}
+
+/// Parse an age in years, eg. "40", "40y", or "40 years old".
+fn parse_age_years(input: &str) -> Option<u16> {
+    let input = input.trim().to_lowercase();
+    let input = input.strip_suffix(" old").unwrap_or(&input);
+    let input = ["years", "year", "yrs", "y"]
+        .iter()
+        .find_map(|suffix| input.strip_suffix(suffix))
+        .unwrap_or(input);
+
+    input.trim().parse().ok()
+}
+
+/// Parse a status with an optional duration, eg. "poisoned for 1 hour" or "on fire for 3 rounds".
+fn parse_status(input: &str) -> Option<(String, Option<Interval>)> {
+    let input = input.trim();
+
+    if let Some(word) = quoted_words(input)
+        .skip(1)
+        .filter(|word| word.as_str().eq_ci("for"))
+        .last()
+    {
+        if let Some(duration) = parse_duration(&input[word.range().end..]) {
+            let status = input[..word.range().start].trim();
+            let is_description = status
+                .split_whitespace()
+                .next()
+                .is_some_and(|word| word.in_ci(&["a", "an", "the"]));
+
+            return Some((status.to_string(), Some(duration)))
+                .filter(|_| !status.is_empty() && !is_description);
+        }
+    }
+
+    Some((input.to_string(), None)).filter(|_| Status::is_known(input))
+}
+
+/// Parse a duration, either in the short form used by the time commands (eg. "8h") or spelled out
+/// (eg. "3 rounds"). A duration of zero is still parsed, so that it can be rejected when the
+/// command runs rather than being mistaken for part of the status.
+fn parse_duration(input: &str) -> Option<Interval> {
+    let input = input.trim();
+
+    if let Ok(interval) = input.parse::<Interval>() {
+        return Some(interval);
+    }
+
+    let (quantity, unit) = input.split_once(' ')?;
+    let quantity = if quantity.in_ci(&["a", "an", "one"]) {
+        1
+    } else {
+        quantity.parse().ok().filter(|&n: &i32| n >= 0)?
+    };
+
+    match unit.trim().to_lowercase().trim_end_matches('s') {
+        "round" => Some(Interval::new_rounds(quantity)),
+        "second" => Some(Interval::new_seconds(quantity)),
+        "minute" => Some(Interval::new_minutes(quantity)),
+        "hour" => Some(Interval::new_hours(quantity)),
+        "day" => Some(Interval::new_days(quantity)),
+        _ => None,
+    }
+}
+
+/// Split an optional hop count off the end of a name, eg. "Gottfried 2 hops". The count is capped
+/// at [`MAX_HOPS`].
+fn parse_hops(input: &str) -> (&str, usize) {
+    input
+        .rsplit_once(' ')
+        .filter(|(_, unit)| unit.in_ci(&["hop", "hops"]))
+        .and_then(|(s, _)| s.trim_end().rsplit_once(' '))
+        .and_then(|(name, hops)| {
+            hops.parse::<usize>()
+                .ok()
+                .filter(|&hops| hops > 0)
+                .map(|hops| (name.trim_end(), hops.min(MAX_HOPS)))
+        })
+        .unwrap_or((input, DEFAULT_HOPS))
+}
+
+/// Split input of the form `[number] of [dungeon]`, eg. "3 of The Sunken Crypt".
+fn parse_room(input: &str) -> Option<(usize, &str)> {
+    let (number, input) = input.split_once(' ')?;
+    let (of, name) = input.trim_start().split_once(' ')?;
+
+    if of.eq_ci("of") && !name.trim().is_empty() {
+        Some((number.parse().ok()?, name.trim()))
+    } else {
+        None
+    }
+}
+
+/// Offer `room 1`, `room 2`, etc. as shortcuts to the rooms of the most recently viewed dungeon.
+fn insert_room_aliases(name: &str, dungeon: &Dungeon, app_meta: &mut AppMeta) {
+    for number in 1..=dungeon.rooms.len() {
+        app_meta.command_aliases.insert(CommandAlias::literal(
+            format!("room {}", number),
+            format!("show room {} of {}", number, name),
+            WorldCommand::Room {
+                name: name.to_string(),
+                number,
+            }
+            .into(),
+        ));
+    }
+}
+
+/// Apply a diff to the named character, returning the updated character details.
+async fn edit_npc(name: &str, diff: Npc, app_meta: &mut AppMeta) -> Result<String, CommandError> {
+    let language = Config::load(app_meta).await.language;
+
+    let change = match app_meta.repository.get_by_name(name).await {
+        Ok(thing) => Change::edit(&thing, diff.into()),
+        Err(_) => {
+            return Err(CommandError::storage(
+                Message::CouldNotEdit { name }.localize(language),
+            ))
+        }
+    };
+
+    match app_meta.repository.modify(change).await {
+        Ok(Some(thing)) => Ok(Config::load(app_meta).await.render(
+            thing.display_details(
+                app_meta
+                    .repository
+                    .load_relations(&thing)
+                    .await
+                    .unwrap_or_default(),
+            ),
+        )),
+        _ => Err(CommandError::storage(
+            Message::CouldNotEdit { name }.localize(language),
+        )),
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl ContextAwareParse for WorldCommand {
+    async fn parse_input(input: &str, app_meta: &AppMeta) -> CommandMatches<Self> {
+        let mut matches = CommandMatches::default();
+
+        let (create_input, is_create) = if let Some(s) = input.strip_prefix_ci("create ") {
+            (s, true)
+        } else {
+            (input, false)
+        };
+
+        let (templates, homebrew) = join!(Templates::load(app_meta), Homebrew::load(app_meta));
+
+        // "three dwarves" or "a party of halflings", resolved as the singular would be.
+        if let Some((count, description)) = split_quantity(create_input) {
+            if let Some(thing) = templates
+                .find_prefix(&description)
+                .and_then(|(template, rest)| template.instantiate(rest).ok())
+                .or_else(|| {
+                    homebrew.find(&description).and_then(|(homebrew, range)| {
+                        homebrew.instantiate(&description, range).ok()
+                    })
+                })
+                .or_else(|| description.parse::<ParsedThing<Thing>>().ok())
+                .filter(|thing| thing.unknown_words.is_empty())
+            {
+                let command = Self::CreateMultiple {
+                    thing: Box::new(thing.thing),
+                    count: Some(count),
+                };
+
+                if is_create {
+                    matches.push_canonical(command);
+                } else {
+                    matches.push_fuzzy(command);
+                }
+            }
+        }
+
+        // Templates and homebrew types take precedence over the built-in vocabulary, eg.
+        // `create city-guard` or `create warforged`.
+        if let Some(thing) = templates
+            .find_prefix(create_input)
+            .and_then(|(template, rest)| {
+                Some((template.instantiate(rest).ok()?, input.len() - rest.len()))
+            })
+            .or_else(|| {
+                homebrew.find(create_input).and_then(|(homebrew, range)| {
+                    Some((
+                        homebrew.instantiate(create_input, range).ok()?,
+                        input.len() - create_input.len(),
+                    ))
+                })
+            })
+            .map(|(mut thing, offset)| {
+                thing.unknown_words.iter_mut().for_each(|range| {
+                    *range = range.start + offset..range.end + offset;
+                });
+
+                thing
+            })
+        {
+            if is_create && thing.unknown_words.is_empty() {
+                matches.push_canonical(Self::Create {
+                    thing: Box::new(thing),
+                });
+            } else {
+                matches.push_fuzzy(Self::Create {
+                    thing: Box::new(thing),
+                });
+            }
+        } else if let Some(Ok(thing)) = input
+            .strip_prefix_ci("create ")
+            .map(|s| s.parse::<ParsedThing<Thing>>())
+        {
+            if thing.unknown_words.is_empty() {
+                matches.push_canonical(Self::Create {
+                    thing: Box::new(thing),
+                });
+            } else {
+                matches.push_fuzzy(Self::Create {
+                    thing: Box::new(thing),
+                });
+            }
+        } else if let Ok(thing) = input.parse::<ParsedThing<Thing>>() {
+            matches.push_fuzzy(Self::Create {
+                thing: Box::new(thing),
+            });
+        }
+
+        if let Some(input) = input.strip_prefix_ci("rename ") {
+            let mut rename = None;
+
+            for word in quoted_words(input)
+                .skip(1)
+                .filter(|word| word.as_str().eq_ci("to"))
+            {
+                let (name, new_name) = (
+                    input[..word.range().start].trim(),
+                    input[word.range().end..].trim(),
+                );
+
+                if new_name.is_empty() {
+                    continue;
+                } else if let Ok(thing) = app_meta.repository.get_by_name(name).await {
+                    rename = Some((thing.name().to_string(), new_name.to_string()));
+                    break;
+                } else if rename.is_none() {
+                    rename = Some((name.to_string(), new_name.to_string()));
+                }
+            }
+
+            if let Some((name, new_name)) = rename {
+                matches.push_canonical(Self::Rename { name, new_name });
+            }
+        }
+
+        if let Some(input) = input.strip_prefix_ci("connections ") {
+            let (name, hops) = parse_hops(input.trim());
+
+            if !name.is_empty() {
+                let name = if let Ok(thing) = app_meta.repository.get_by_name(name).await {
+                    thing.name().to_string()
+                } else {
+                    name.to_string()
+                };
+
+                matches.push_canonical(Self::Connections { name, hops });
+            }
+        }
+
+        if let Some(name) = input.strip_prefix_ci("menu ") {
+            let name = if let Ok(thing) = app_meta.repository.get_by_name(name).await {
+                thing.name().to_string()
+            } else {
+                name.to_string()
+            };
+
+            matches.push_canonical(Self::Menu { name });
+        }
+
+        if let Some(name) = input.strip_prefix_ci("ambience ") {
+            let name = if let Ok(thing) = app_meta.repository.get_by_name(name).await {
+                thing.name().to_string()
+            } else {
+                name.to_string()
+            };
+
+            matches.push_canonical(Self::Ambience { name });
+        }
+
+        if let Some(Ok(subtype)) = input
+            .strip_prefix_ci("chances ")
+            .map(|s| s.trim().parse::<PlaceType>())
+        {
+            matches.push_canonical(Self::Chances { subtype });
+        }
+
+        if input.eq_ci("festivals") {
+            matches.push_canonical(Self::Festivals);
+        }
+
+        if input.in_ci(&["party", "pcs"]) {
+            matches.push_canonical(Self::Party);
+        }
+
+        if let Some((travel, name)) = input
+            .strip_prefix_ci("party is at ")
+            .or_else(|| input.strip_prefix_ci("the party is at "))
+            .map(|s| (false, s))
+            .or_else(|| input.strip_prefix_ci("travel to ").map(|s| (true, s)))
+            .map(|(travel, s)| (travel, s.trim()))
+            .filter(|(_, s)| !s.is_empty())
+        {
+            let name = if let Ok(thing) = app_meta.repository.get_by_name(name).await {
+                thing.name().to_string()
+            } else {
+                name.to_string()
+            };
+
+            if travel {
+                matches.push_canonical(Self::Travel { name });
+            } else {
+                matches.push_canonical(Self::PartyAt { name });
+            }
+        }
+
+        if let Some(input) = input.strip_prefix_ci("pc ") {
+            let (name, clauses) = input.split_once(',').unwrap_or((input, ""));
+            let name = name.trim();
+
+            // "pc named Aragorn", as in "npc named Aragorn"
+            let name = match name.split_once(' ') {
+                Some((word, rest)) if word.in_ci(&["named", "called"]) => rest.trim(),
+                _ => name,
+            };
+
+            if let (false, Ok(pc)) = (name.is_empty(), PlayerCharacter::parse_clauses(clauses)) {
+                let name = if let Ok(thing) = app_meta.repository.get_by_name(name).await {
+                    thing.name().to_string()
+                } else {
+                    name.to_string()
+                };
+
+                matches.push_canonical(Self::Pc { name, pc });
+            }
+        }
+
+        if input.eq_ci("prep") {
+            matches.push_canonical(Self::Prep {
+                days: DEFAULT_PREP_DAYS,
+            });
+        } else if let Some(days) = input
+            .strip_prefix_ci("prep ")
+            .and_then(|s| s.trim().split_once(' '))
+            .filter(|(_, unit)| unit.in_ci(&["day", "days"]))
+            .and_then(|(days, _)| days.parse().ok())
+        {
+            matches.push_canonical(Self::Prep { days });
+        }
+
+        if let Some((locked, input)) = input
+            .strip_prefix_ci("lock ")
+            .map(|s| (true, s))
+            .or_else(|| input.strip_prefix_ci("unlock ").map(|s| (false, s)))
+        {
+            if let Some((name, field)) = input.trim().rsplit_once("'s ") {
+                let name = if let Ok(thing) = app_meta.repository.get_by_name(name).await {
+                    thing.name().to_string()
+                } else {
+                    name.to_string()
+                };
+
+                matches.push_canonical(Self::Lock {
+                    name,
+                    field: field.trim().to_lowercase(),
+                    locked,
+                });
+            }
+        }
+
+        if input.eq_ci("name") {
+            matches.push_canonical(Self::Name { ethnicity: None });
+        } else if let Some(ethnicity) = input.strip_prefix_ci("name ").and_then(|word| {
+            word.parse::<Ethnicity>()
+                .ok()
+                .or_else(|| word.parse::<Species>().ok().map(|s| s.default_ethnicity()))
+        }) {
+            matches.push_canonical(Self::Name {
+                ethnicity: Some(ethnicity),
+            });
+        }
+
+        // `create contact for Aragorn`, `contact for Aragorn`, or `create mentor for Aragorn`.
+        if let Some((kind, name)) = input
+            .strip_prefix_ci("create ")
+            .or_else(|| Some(input).filter(|input| input.starts_with_ci("contact ")))
+            .and_then(|input| input.split_once(' '))
+            .filter(|(_, rest)| rest.starts_with_ci("for "))
+            .and_then(|(kind, rest)| {
+                let kind = if kind.eq_ci("contact") {
+                    None
+                } else {
+                    Some(kind.parse::<ContactKind>().ok()?)
+                };
+
+                Some((kind, rest["for ".len()..].trim()))
+            })
+            .filter(|(_, name)| !name.is_empty())
+        {
+            let name = if let Ok(thing) = app_meta.repository.get_by_name(name).await {
+                thing.name().to_string()
+            } else {
+                name.to_string()
+            };
+
+            matches.push_canonical(Self::CreateContact { name, kind });
+        }
+
+        if let Some(monster) = input
+            .strip_prefix_ci("create lair for ")
+            .or_else(|| input.strip_prefix_ci("lair for "))
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+        {
+            let homebrew = HomebrewReference::load(app_meta).await;
+            let monster = homebrew
+                .get(monster, Some(HomebrewEntryKind::Monster))
+                .map(|entry| entry.name())
+                .unwrap_or(monster)
+                .to_string();
+
+            matches.push_canonical(Self::CreateLair { monster });
+        }
+
+        if let Some(name) = input.strip_prefix_ci("crew ") {
+            let name = if let Ok(thing) = app_meta.repository.get_by_name(name).await {
+                thing.name().to_string()
+            } else {
+                name.to_string()
+            };
+
+            matches.push_canonical(Self::Crew { name });
+        }
+
+        if let Some(input) = input.strip_prefix_ci("downtime ") {
+            // The character's name may contain spaces, so try each word as the start of the
+            // activity, eg. "Aria Moonwhisper crafting 5 days".
+            if let Some((name, activity)) = quoted_words(input).skip(1).find_map(|word| {
+                Some((
+                    input[..word.range().start].trim(),
+                    input[word.range().start..].parse::<Activity>().ok()?,
+                ))
+            }) {
+                let name = if let Ok(thing) = app_meta.repository.get_by_name(name).await {
+                    thing.name().to_string()
+                } else {
+                    name.to_string()
+                };
+
+                matches.push_canonical(Self::Downtime { name, activity });
+            }
+        }
+
+        if let Some((number, name)) = input.strip_prefix_ci("room ").and_then(parse_room) {
+            let name = if let Ok(thing) = app_meta.repository.get_by_name(name).await {
+                thing.name().to_string()
+            } else {
+                name.to_string()
+            };
+
+            matches.push_canonical(Self::Room { name, number });
+        }
+
+        if let Some((name, field)) = input
+            .strip_prefix_ci("suggest ")
+            .and_then(|s| s.trim().rsplit_once(' '))
+        {
+            let name = name.strip_suffix("'s").unwrap_or(name);
+            let name = if let Ok(thing) = app_meta.repository.get_by_name(name).await {
+                thing.name().to_string()
+            } else {
+                name.to_string()
+            };
+
+            matches.push_canonical(Self::Suggest {
+                name,
+                field: field.to_lowercase(),
+            });
+        }
+
+        if input.eq_ci("rumor") {
+            matches.push_canonical(Self::Rumor { about: None });
+        } else if let Some(name) = input.strip_prefix_ci("rumor about ") {
+            let name = if let Ok(thing) = app_meta.repository.get_by_name(name).await {
+                thing.name().to_string()
+            } else {
+                name.to_string()
+            };
+
+            matches.push_canonical(Self::Rumor { about: Some(name) });
+        }
+
+        if let Some(input) = input.strip_prefix_ci("random ") {
+            let mut random = None;
+
+            // The description ends at the first "in" that leaves a description that makes sense,
+            // so that "random person in the Inn of the Fallen Star" picks from within the inn.
+            for word in quoted_words(input).skip(1) {
+                if !word.as_str().eq_ci("in") {
+                    continue;
+                }
+
+                let (description, location) = (
+                    input[..word.range().start].trim(),
+                    input[word.range().end..].trim(),
+                );
+
+                if location.is_empty() {
+                    continue;
+                }
+
+                if let Ok(parsed) = description.parse::<ParsedThing<Thing>>() {
+                    if parsed.unknown_words.is_empty() {
+                        let location =
+                            if let Ok(thing) = app_meta.repository.get_by_name(location).await {
+                                thing.name().to_string()
+                            } else {
+                                location.to_string()
+                            };
+
+                        random = Some(Self::Random {
+                            thing: Box::new(parsed.thing),
+                            location: Some(location),
+                        });
+                        break;
+                    }
+                }
+            }
+
+            if let Some(random) = random.or_else(|| {
+                input
+                    .parse::<ParsedThing<Thing>>()
+                    .ok()
+                    .filter(|parsed| parsed.unknown_words.is_empty())
+                    .map(|parsed| Self::Random {
+                        thing: Box::new(parsed.thing),
+                        location: None,
+                    })
+            }) {
+                matches.push_canonical(random);
+            }
+        }
+
+        if let Some(input) = input.strip_prefix_ci("give ") {
+            let mut give = None;
+
+            for word in quoted_words(input).skip(1) {
+                let (name, item) = (
+                    input[..word.range().start].trim(),
+                    &input[word.range().start..],
+                );
+
+                if let Some((quantity, item)) = parse_equipment(item) {
+                    if let Ok(thing) = app_meta.repository.get_by_name(name).await {
+                        give = Some((thing.name().to_string(), item, quantity));
+                        break;
+                    } else if give.is_none() {
+                        give = Some((name.to_string(), item, quantity));
+                    }
+                }
+            }
+
+            if let Some((name, item, quantity)) = give {
+                matches.push_canonical(Self::Give {
+                    name,
+                    item,
+                    quantity,
+                });
+            }
+        } else if let Some(input) = input.strip_prefix_ci("take ") {
+            let mut take = None;
+
+            for word in quoted_words(input)
+                .skip(1)
+                .filter(|word| word.as_str().eq_ci("from"))
+            {
+                let (item, name) = (
+                    &input[..word.range().start],
+                    input[word.range().end..].trim(),
+                );
+
+                if let (Some((quantity, item)), false) = (parse_equipment(item), name.is_empty()) {
+                    if let Ok(thing) = app_meta.repository.get_by_name(name).await {
+                        take = Some((thing.name().to_string(), item, quantity));
+                        break;
+                    } else if take.is_none() {
+                        take = Some((name.to_string(), item, quantity));
+                    }
+                }
+            }
+
+            if let Some((name, item, quantity)) = take {
+                matches.push_canonical(Self::Take {
+                    name,
+                    item,
+                    quantity,
+                });
+            }
+        }
+
+        if let Some(name) = input.strip_prefix_ci("reaction ") {
+            let name = if let Ok(thing) = app_meta.repository.get_by_name(name).await {
+                thing.name().to_string()
+            } else {
+                name.to_string()
+            };
+
+            matches.push_canonical(Self::Reaction { name });
+        }
+
+        if let Some(name) = input
+            .strip_prefix_ci("talk to ")
+            .or_else(|| input.strip_prefix_ci("talk "))
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+        {
+            let name = if let Ok(thing) = app_meta.repository.get_by_name(name).await {
+                thing.name().to_string()
+            } else {
+                name.to_string()
+            };
+
+            matches.push_canonical(Self::Talk { name });
+        }
+
+        if let Some((name, dead)) = input
+            .strip_prefix_ci("kill ")
+            .map(|name| (name, true))
+            .or_else(|| input.strip_prefix_ci("retire ").map(|name| (name, false)))
+        {
+            let name = if let Ok(thing) = app_meta.repository.get_by_name(name).await {
+                thing.name().to_string()
+            } else {
+                name.to_string()
+            };
+
+            if dead {
+                matches.push_canonical(Self::Kill { name });
+            } else {
+                matches.push_canonical(Self::Retire { name });
+            }
+        }
+
+        if let Some((rest, encounters)) = match input.to_lowercase().as_str() {
+            "rest short" => Some((Rest::Short, false)),
+            "rest long" => Some((Rest::Long, false)),
+            "rest long with encounters" => Some((Rest::Long, true)),
+            _ => None,
+        } {
+            matches.push_canonical(Self::PartyRest { rest, encounters });
+        } else if let Some((rest, name)) = [
+            ("long rest ", Rest::Long),
+            ("short rest ", Rest::Short),
+            ("rest ", Rest::Long),
+        ]
+        .into_iter()
+        .find_map(|(prefix, rest)| input.strip_prefix_ci(prefix).map(|name| (rest, name)))
+        {
+            let name = if let Ok(thing) = app_meta.repository.get_by_name(name).await {
+                thing.name().to_string()
+            } else {
+                name.to_string()
+            };
+
+            matches.push_canonical(Self::Rest { name, rest });
+        } else if input.starts_with_ci("use ")
+            || input.starts_with_ci("set slots ")
+            || input.starts_with_ci("track ")
+        {
+            if let Some((command, name)) = split_for(input, app_meta).await {
+                if let Some(Ok(resource)) = command.strip_prefix_ci("use ").map(|s| s.parse()) {
+                    matches.push_canonical(Self::UseResource { name, resource });
+                } else if let Some(Some(slots)) = command.strip_prefix_ci("set slots ").map(|s| {
+                    s.split_whitespace()
+                        .map(|n| n.parse().ok())
+                        .collect::<Option<Vec<u8>>>()
+                        .filter(|slots| (1..=9).contains(&slots.len()))
+                }) {
+                    matches.push_canonical(Self::SetSpellSlots { name, slots });
+                } else if let Some((max, feature, recovery)) =
+                    command.strip_prefix_ci("track ").and_then(|s| {
+                        let (max, s) = s.split_once(' ')?;
+                        let (feature, recovery) = s.strip_suffix_ci(" rest")?.rsplit_once(' ')?;
+                        let feature = feature.strip_suffix_ci(" per")?.trim();
+
+                        if feature.is_empty() {
+                            None
+                        } else {
+                            Some((
+                                max.parse().ok()?,
+                                feature.to_string(),
+                                recovery.parse().ok()?,
+                            ))
+                        }
+                    })
+                {
+                    matches.push_canonical(Self::TrackFeature {
+                        name,
+                        feature,
+                        max,
+                        recovery,
+                    });
+                }
+            }
+        }
+
+        if let Some(word) = quoted_words(input)
+            .skip(1)
+            .find(|word| word.as_str().in_ci(&["knows", "forgets"]))
+        {
+            let (name, list) = (
+                input[..word.range().start].trim(),
+                input[word.range().end..].trim(),
+            );
+
+            if let Some(mut spells) = parse_spells(list) {
+                let name = if let Ok(thing) = app_meta.repository.get_by_name(name).await {
+                    thing.name().to_string()
+                } else {
+                    name.to_string()
+                };
+
+                if word.as_str().eq_ci("knows") {
+                    matches.push_canonical(Self::Learn { name, spells });
+                } else if spells.len() == 1 {
+                    matches.push_canonical(Self::Forget {
+                        name,
+                        spell: spells.remove(0),
+                    });
+                }
+            }
+        }
+
+        // "Hilda who is grumpy" describes a new character rather than editing one called "Hilda
+        // who".
+        if let Some(word) = quoted_words(input)
+            .skip(1)
+            .find(|word| {
+                word.as_str().eq_ci("is")
+                    && !input[..word.range().start].trim_end().ends_with_ci(" who")
+            })
+            .filter(|_| !matches!(matches.canonical_match, Some(Self::PartyAt { .. })))
+        {
+            let (name, description) = (
+                input[..word.range().start].trim(),
+                input[word.range().end..].trim(),
+            );
+            let description_offset = input.len() - input[word.range().end..].trim_start().len();
+
+            // "Gottfried's age is 40y" edits a single field.
+            let field_edit = if let Some((owner, field)) = name.rsplit_once("'s ") {
+                app_meta
+                    .repository
+                    .get_by_name(owner.trim())
+                    .await
+                    .ok()
+                    .and_then(|thing| parse_field_edit(&thing, field.trim(), description))
+            } else {
+                None
+            };
+
+            if let Some(mut command) = field_edit {
+                if let Self::Edit { diff, .. } = &mut command {
+                    diff.unknown_words.iter_mut().for_each(|range| {
+                        *range = range.start + description_offset..range.end + description_offset
+                    });
+
+                    if diff.unknown_words.is_empty() {
+                        matches.push_canonical(command);
+                    } else {
+                        matches.push_fuzzy(command);
+                    }
+                } else {
+                    matches.push_canonical(command);
+                }
+            } else if let Some(status) = description
+                .strip_prefix_ci("no longer ")
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+            {
+                let name = if let Ok(thing) = app_meta.repository.get_by_name(name).await {
+                    thing.name().to_string()
+                } else {
+                    name.to_string()
+                };
+
+                matches.push_canonical(Self::RemoveStatus {
+                    name,
+                    status: status.to_string(),
+                });
+            } else if let Some(alias) = description
+                .strip_prefix_ci("also known as ")
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+            {
+                let name = if let Ok(thing) = app_meta.repository.get_by_name(name).await {
+                    thing.name().to_string()
+                } else {
+                    name.to_string()
+                };
+
+                matches.push_canonical(Self::AddAlias {
+                    name,
+                    alias: alias.to_string(),
+                });
+            } else {
+                let (diff, thing) = if let Ok(thing) = app_meta.repository.get_by_name(name).await {
+                    (
+                        match thing {
+                            Thing::Npc(_) => description
+                                .parse::<ParsedThing<Npc>>()
+                                .map(|npc| npc.into_thing()),
+                            Thing::Place(_) => description
+                                .parse::<ParsedThing<Place>>()
+                                .map(|npc| npc.into_thing()),
+                        }
+                        .or_else(|_| description.parse()),
+                        Some(thing),
+                    )
+                } else {
+                    // This will be an error when we try to run the command, but for now we'll
+                    // pretend it's valid so that we can provide a more coherent message.
+                    (description.parse(), None)
+                };
+
+                if let Ok(mut diff) = diff {
+                    // Long descriptions such as "Gimli is stocky red-haired scarred" can also be
+                    // read as creating a new character, so an edit of an existing thing that
+                    // understood every word takes precedence.
+                    let is_canonical = thing.is_some() && diff.unknown_words.is_empty();
+
+                    let uuid = thing.as_ref().and_then(|t| t.uuid().cloned());
+                    let name = thing
+                        .map(|t| t.name().to_string())
+                        .unwrap_or_else(|| name.to_string());
+
+                    diff.unknown_words.iter_mut().for_each(|range| {
+                        *range =
+                            range.start + word.range().end + 1..range.end + word.range().end + 1
+                    });
+
+                    if is_canonical {
+                        matches.push_canonical(Self::Edit {
+                            name,
+                            uuid,
+                            diff: Box::new(diff),
+                        });
+                    } else {
+                        matches.push_fuzzy(Self::Edit {
+                            name,
+                            uuid,
+                            diff: Box::new(diff),
+                        });
+                    }
+                } else if let (Some(thing), Some((status, duration))) =
+                    (thing, parse_status(description))
+                {
+                    // Anything that can't be parsed as a description of an existing character or
+                    // place is treated as a transient status, eg. "Gottfried is poisoned".
+                    matches.push_fuzzy(Self::AddStatus {
+                        name: thing.name().to_string(),
+                        status,
+                        duration,
+                    });
+                }
+            }
+        }
+
+        matches
+    }
+}
+
+#[cfg_attr(feature = "sync", async_trait)]
+#[cfg_attr(not(feature = "sync"), async_trait(?Send))]
+impl Autocomplete for WorldCommand {
+    async fn autocomplete(input: &str, app_meta: &AppMeta) -> Vec<AutocompleteSuggestion> {
+        let mut suggestions = Vec::new();
+
+        let (mut place_suggestions, mut npc_suggestions) = join!(
+            Place::autocomplete(input, app_meta),
+            Npc::autocomplete(input, app_meta),
+        );
+
+        // Some occupations double as businesses (eg. "blacksmith"), in which case the input will
+        // resolve to the place.
+        npc_suggestions.retain(|npc| !place_suggestions.iter().any(|place| place.term == npc.term));
+
+        suggestions.append(&mut place_suggestions);
+        suggestions.append(&mut npc_suggestions);
+
+        if "rename ".starts_with_ci(input) {
+            suggestions.push(AutocompleteSuggestion::new(
+                "rename [name] to [new name]",
+                "rename an entry",
+            ));
+        } else if let Some(name) = input.strip_prefix_ci("rename ") {
+            for thing in app_meta
+                .repository
+                .get_by_name_start(name, Some(10))
+                .await
+                .unwrap_or_default()
+            {
+                suggestions.push(AutocompleteSuggestion::new(
+                    format!("rename {} to [new name]", thing.name()),
+                    format!("rename {}", thing.as_str()),
+                ));
+            }
+        }
+
+        suggestions.extend(
+            [
+                (
+                    "ambience [name]",
+                    "describe the sounds and smells of a place",
+                ),
+                (
+                    "chances [place type]",
+                    "show the odds behind generating a place",
+                ),
+                ("connections [name]", "show how an entry is connected"),
+                (
+                    "create contact for [name]",
+                    "generate a contact for a player character",
+                ),
+                ("create lair for [monster]", "generate a monster's lair"),
+                ("crew [name]", "show the crew of a ship or caravan"),
+                (
+                    "downtime [name] crafting [number] days",
+                    "spend downtime crafting",
+                ),
+                (
+                    "downtime [name] crafting [item]",
+                    "spend downtime crafting equipment",
+                ),
+                (
+                    "downtime [name] training [number] days",
+                    "spend downtime learning a language or tool",
+                ),
+                (
+                    "downtime [name] working [number] days",
+                    "spend downtime practicing a profession",
+                ),
+                ("festivals", "list upcoming festivals"),
+                ("give [name] [item]", "give equipment to a character"),
+                ("kill [name]", "mark a character as dead"),
+                ("lock [name]'s [field]", "keep a field if regenerated"),
+                ("long rest [name]", "recover all resources"),
+                ("menu [name]", "show the menu of an inn or bar"),
+                ("name", "suggest names for a settlement"),
+                ("party", "list the player characters"),
+                ("party is at [place]", "set where the party is"),
+                ("pc [name]", "add a player character"),
+                (
+                    "pc [name], level [level] [class], AC [armor class]",
+                    "add a player character's statistics",
+                ),
+                ("prep", "get ready for the next session"),
+                ("prep [number] days", "get ready, looking further ahead"),
+                (
+                    "name [ethnicity]",
+                    "suggest settlement names of an ethnicity",
+                ),
+                ("random [description]", "pick a journal entry at random"),
+                (
+                    "random [description] in [place]",
+                    "pick a journal entry from a place at random",
+                ),
+                ("reaction [name]", "roll a character's reaction"),
+                ("rest [name]", "take a long rest"),
+                ("rest long", "take a long rest as a party"),
+                (
+                    "rest long with encounters",
+                    "rest, rolling for a night encounter",
+                ),
+                ("rest short", "take a short rest as a party"),
+                ("retire [name]", "mark a character as retired"),
+                ("room [number] of [dungeon]", "show a room of a dungeon"),
+                ("rumor", "hear some tavern gossip"),
+                ("rumor about [name]", "hear gossip about an entry"),
+                ("set slots [1st] [2nd] ... for [name]", "set spell slots"),
+                ("short rest [name]", "recover short rest resources"),
+                ("suggest [name] [field]", "suggest alternatives for a field"),
+                ("take [item] from [name]", "take equipment from a character"),
+                ("talk [name]", "improvise a conversation with a character"),
+                ("travel to [place]", "move the party to a place"),
+                (
+                    "track [number] [feature] per [short|long] rest for [name]",
+                    "track a limited-use feature",
+                ),
+                ("unlock [name]'s [field]", "allow a field to be regenerated"),
+                ("use slot [level] for [name]", "expend a spell slot"),
+            ]
+            .into_iter()
+            .filter(|(term, _)| term.starts_with_ci(input))
+            .map(|(term, summary)| AutocompleteSuggestion::new(term, summary)),
+        );
+
+        if let Some(word) = quoted_words(input)
+            .skip(1)
+            .find(|word| word.as_str().in_ci(&["knows", "forgets"]))
+        {
+            let name = input[..word.range().start].trim();
+
+            if let Ok(Thing::Npc(npc)) = app_meta.repository.get_by_name(name).await {
+                // Complete the last spell in the list, eg. "Fireball, Sh".
+                let partial_start = input[word.range().end..]
+                    .rfind(',')
+                    .map_or(word.range().end, |i| word.range().end + i + 1);
+                let partial = input[partial_start..].trim_start();
+                let partial = partial.strip_prefix_ci("and ").unwrap_or(partial);
+                let prefix = &input[..input.len() - partial.len()];
+                let prefix = if prefix.ends_with(' ') {
+                    prefix.to_string()
+                } else {
+                    format!("{} ", prefix)
+                };
+
+                let spell_names: Vec<String> = if word.as_str().eq_ci("knows") {
+                    app_meta
+                        .vocabulary
+                        .spells
+                        .starting_with(partial)
+                        .map(|spell_name| spell_name.to_string())
+                        .filter(|spell_name| {
+                            !npc.spells
+                                .value()
+                                .map_or(false, |spells| spells.contains(spell_name))
+                        })
+                        .collect()
+                } else {
+                    npc.spells.value().cloned().unwrap_or_default()
+                };
+
+                let mut spell_names: Vec<String> = spell_names
+                    .into_iter()
+                    .filter(|spell_name| spell_name.starts_with_ci(partial))
+                    .collect();
+                spell_names.sort();
+                spell_names.dedup();
+
+                for spell_name in spell_names.into_iter().take(10) {
+                    suggestions.push(AutocompleteSuggestion::new(
+                        format!("{}{}", prefix, spell_name),
+                        if word.as_str().eq_ci("knows") {
+                            "learn an SRD spell"
+                        } else {
+                            "forget a spell"
+                        },
+                    ));
+                }
+            }
+        }
+
+        if let Some(name) = input.strip_prefix_ci("connections ") {
+            for thing in app_meta
+                .repository
+                .get_by_name_start(name, Some(10))
+                .await
+                .unwrap_or_default()
+            {
+                suggestions.push(AutocompleteSuggestion::new(
+                    format!("connections {}", thing.name()),
+                    "show how this entry is connected",
+                ));
+            }
+        }
+
+        if let Some(name) = input.strip_prefix_ci("crew ") {
+            for thing in app_meta
+                .repository
+                .get_by_name_start(name, Some(10))
+                .await
+                .unwrap_or_default()
+                .into_iter()
+                .filter(
+                    |thing| matches!(thing, Thing::Place(place) if place.vehicle_type().is_some()),
+                )
+            {
+                suggestions.push(AutocompleteSuggestion::new(
+                    format!("crew {}", thing.name()),
+                    "show the crew",
+                ));
+            }
+        }
+
+        if let Some((prefix, name)) = ["party is at ", "travel to "]
+            .into_iter()
+            .find_map(|prefix| Some((prefix, input.strip_prefix_ci(prefix)?)))
+        {
+            for thing in app_meta
+                .repository
+                .get_by_name_start(name, Some(10))
+                .await
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|thing| matches!(thing, Thing::Place(_)))
+            {
+                suggestions.push(AutocompleteSuggestion::new(
+                    format!("{}{}", prefix, thing.name()),
+                    "move the party here",
+                ));
+            }
+        }
+
+        if let Some(name) = input.strip_prefix_ci("ambience ") {
+            for thing in app_meta
+                .repository
+                .get_by_name_start(name, Some(10))
+                .await
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|thing| matches!(thing, Thing::Place(_)))
+            {
+                suggestions.push(AutocompleteSuggestion::new(
+                    format!("ambience {}", thing.name()),
+                    "describe the atmosphere",
+                ));
+            }
+        }
+
+        if let Some(name) = input.strip_prefix_ci("menu ") {
+            for thing in app_meta
+                .repository
+                .get_by_name_start(name, Some(10))
+                .await
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|thing| matches!(thing, Thing::Place(place) if place.has_menu()))
+            {
+                suggestions.push(AutocompleteSuggestion::new(
+                    format!("menu {}", thing.name()),
+                    "show the menu",
+                ));
+            }
+        }
+
+        if let Some((number, name)) = input.strip_prefix_ci("room ").and_then(parse_room) {
+            for thing in app_meta
+                .repository
+                .get_by_name_start(name, Some(10))
+                .await
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|thing| matches!(thing, Thing::Place(place) if place.dungeon.is_some()))
+            {
+                suggestions.push(AutocompleteSuggestion::new(
+                    format!("room {} of {}", number, thing.name()),
+                    format!("show room {}", number),
+                ));
+            }
+        }
+
+        if let Some(rest) = input.strip_prefix_ci("random ") {
+            if let Some(word) = quoted_words(rest)
+                .skip(1)
+                .find(|word| word.as_str().eq_ci("in"))
+            {
+                let (description, name) = (
+                    &rest[..word.range().end],
+                    rest[word.range().end..].trim_start(),
+                );
+
+                if !name.is_empty() {
+                    for thing in app_meta
+                        .repository
+                        .get_by_name_start(name, Some(10))
+                        .await
+                        .unwrap_or_default()
+                        .into_iter()
+                        .filter(|thing| matches!(thing, Thing::Place(_)))
+                    {
+                        suggestions.push(AutocompleteSuggestion::new(
+                            format!("random {} {}", description, thing.name()),
+                            "pick a journal entry from here at random",
+                        ));
+                    }
+                }
+            }
+        }
+
+        if let Some(name) = input.strip_prefix_ci("rumor about ") {
+            for thing in app_meta
+                .repository
+                .get_by_name_start(name, Some(10))
+                .await
+                .unwrap_or_default()
+            {
+                suggestions.push(AutocompleteSuggestion::new(
+                    format!("rumor about {}", thing.name()),
+                    format!("hear gossip about the {}", thing.as_str()),
+                ));
+            }
+        }
+
+        if let Some((prefix, name)) = ["kill ", "retire "]
+            .iter()
+            .find_map(|prefix| input.strip_prefix_ci(prefix).map(|name| (*prefix, name)))
+        {
+            for thing in app_meta
+                .repository
+                .get_by_name_start(name, Some(10))
+                .await
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|thing| matches!(thing, Thing::Npc(_)) && thing.fate().is_none())
+            {
+                suggestions.push(AutocompleteSuggestion::new(
+                    format!("{}{}", prefix, thing.name()),
+                    if prefix == "kill " {
+                        "mark as dead"
+                    } else {
+                        "mark as retired"
+                    },
+                ));
+            }
+        }
+
+        if let Some(name) = input.strip_prefix_ci("reaction ") {
+            for thing in app_meta
+                .repository
+                .get_by_name_start(name, Some(10))
+                .await
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|thing| matches!(thing, Thing::Npc(_)))
+            {
+                suggestions.push(AutocompleteSuggestion::new(
+                    format!("reaction {}", thing.name()),
+                    "roll a reaction",
+                ));
+            }
+        }
+
+        if let Some(name) = input.strip_prefix_ci("talk ") {
+            let name = name.strip_prefix_ci("to ").unwrap_or(name);
+
+            for thing in app_meta
+                .repository
+                .get_by_name_start(name, Some(10))
+                .await
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|thing| matches!(thing, Thing::Npc(_)))
+            {
+                suggestions.push(AutocompleteSuggestion::new(
+                    format!("talk {}", thing.name()),
+                    "improvise a conversation",
+                ));
+            }
+        }
+
+        if let Some((prefix, rest, name)) = [
+            ("long rest ", Rest::Long),
+            ("short rest ", Rest::Short),
+            ("rest ", Rest::Long),
+        ]
+        .into_iter()
+        .find_map(|(prefix, rest)| {
+            input
+                .strip_prefix_ci(prefix)
+                .map(|name| (prefix, rest, name))
+        }) {
+            for thing in app_meta
+                .repository
+                .get_by_name_start(name, Some(10))
+                .await
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|thing| matches!(thing, Thing::Npc(_)))
+            {
+                suggestions.push(AutocompleteSuggestion::new(
+                    format!("{}{}", prefix, thing.name()),
+                    format!("take a {} rest", rest),
+                ));
+            }
+        }
+
+        let mut input_words = quoted_words(input).skip(1);
+
+        if let Some((is_word, next_word)) = input_words
+            .find(|word| word.as_str().eq_ci("is"))
+            .and_then(|word| input_words.next().map(|next_word| (word, next_word)))
+        {
+            if let Ok(thing) = app_meta
+                .repository
+                .get_by_name(input[..is_word.range().start].trim())
+                .await
+            {
+                let split_pos = input.len() - input[is_word.range().end..].trim_start().len();
+
+                let edit_suggestions = match thing {
+                    Thing::Npc(_) => Npc::autocomplete(input[split_pos..].trim_start(), app_meta),
+                    Thing::Place(_) => {
+                        Place::autocomplete(input[split_pos..].trim_start(), app_meta)
+                    }
+                }
+                .await;
+                let has_edit_suggestions = !edit_suggestions.is_empty();
+
+                suggestions.extend(edit_suggestions.into_iter().map(|suggestion| {
+                    AutocompleteSuggestion::new(
+                        format!("{}{}", &input[..split_pos], suggestion.term),
+                        format!("edit {}", thing.as_str()),
+                    )
+                }));
+
+                if next_word.as_str().in_ci(&["named", "called"]) && input_words.next().is_some() {
+                    suggestions.push(AutocompleteSuggestion::new(
+                        input.to_string(),
+                        format!("rename {}", thing.as_str()),
+                    ));
+                }
+
+                let description = input[split_pos..].trim_start();
+
+                if "also known as ".starts_with_ci(description)
+                    || description.starts_with_ci("also known as ")
+                {
+                    suggestions.push(AutocompleteSuggestion::new(
+                        match description
+                            .strip_prefix_ci("also known as ")
+                            .map(str::trim)
+                            .filter(|s| !s.is_empty())
+                        {
+                            Some(alias) => format!("{} is also known as {}", thing.name(), alias),
+                            None => format!("{} is also known as [other name]", thing.name()),
+                        },
+                        format!("give {} another name", thing.as_str()),
+                    ));
+                }
+
+                if let Some(status) = description.strip_prefix_ci("no longer ") {
+                    let statuses = match &thing {
+                        Thing::Npc(npc) => npc.statuses.value(),
+                        Thing::Place(place) => place.statuses.value(),
+                    };
+
+                    suggestions.extend(
+                        statuses
+                            .iter()
+                            .flat_map(|statuses| statuses.iter())
+                            .filter(|s| s.name.starts_with_ci(status.trim_start()))
+                            .map(|s| {
+                                AutocompleteSuggestion::new(
+                                    format!("{} is no longer {}", thing.name(), s.name),
+                                    "remove status",
+                                )
+                            }),
+                    );
+                } else if !has_edit_suggestions
+                    && Self::parse_input(input, app_meta)
+                        .await
+                        .fuzzy_matches
+                        .iter()
+                        .any(|command| matches!(command, Self::AddStatus { .. }))
+                {
+                    suggestions.push(AutocompleteSuggestion::new(
+                        input.to_string(),
+                        format!("add {} status", thing.as_str()),
+                    ));
+                }
+            }
+        }
+
+        if let Ok(thing) = app_meta.repository.get_by_name(input.trim_end()).await {
+            suggestions.push(AutocompleteSuggestion::new(
+                if input.ends_with(char::is_whitespace) {
+                    format!("{}is [{} description]", input, thing.as_str())
+                } else {
+                    format!("{} is [{} description]", input, thing.as_str())
+                },
+                format!("edit {}", thing.as_str()),
+            ));
+        } else if let Some((last_word_index, last_word)) =
+            quoted_words(input).enumerate().skip(1).last()
+        {
+            if "is".starts_with_ci(last_word.as_str()) {
+                if let Ok(thing) = app_meta
+                    .repository
+                    .get_by_name(input[..last_word.range().start].trim())
+                    .await
+                {
+                    suggestions.push(AutocompleteSuggestion::new(
+                        if last_word.range().end == input.len() {
+                            format!(
+                                "{}is [{} description]",
+                                &input[..last_word.range().start],
+                                thing.as_str(),
+                            )
+                        } else {
+                            format!("{}[{} description]", &input, thing.as_str())
+                        },
+                        format!("edit {}", thing.as_str()),
+                    ))
+                }
+            } else if let Some(suggestion) = ["named", "called"]
+                .iter()
+                .find(|s| s.starts_with_ci(last_word.as_str()))
+            {
+                let second_last_word = quoted_words(input).nth(last_word_index - 1).unwrap();
+
+                if second_last_word.as_str().eq_ci("is") {
+                    if let Ok(thing) = app_meta
+                        .repository
+                        .get_by_name(input[..second_last_word.range().start].trim())
+                        .await
+                    {
+                        suggestions.push(AutocompleteSuggestion::new(
+                            if last_word.range().end == input.len() {
+                                format!(
+                                    "{}{} [name]",
+                                    &input[..last_word.range().start],
+                                    suggestion,
+                                )
+                            } else {
+                                format!("{}[name]", input)
+                            },
+                            format!("rename {}", thing.as_str()),
+                        ));
+                    }
+                }
+            }
+        }
+
+        suggestions
+    }
+}
+
+impl fmt::Display for WorldCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::AddAlias { name, alias } => write!(f, "{} is also known as {}", name, alias),
+            Self::AddStatus {
+                name,
+                status,
+                duration,
+            } => {
+                write!(f, "{} is {}", name, status)?;
+
+                if let Some(duration) = duration {
+                    write!(f, " for {}", duration.display_short())?;
+                }
+
+                Ok(())
+            }
+            Self::Connections { name, hops } => {
+                write!(f, "connections {}", name)?;
+
+                if *hops != DEFAULT_HOPS {
+                    write!(f, " {} {}", hops, if *hops == 1 { "hop" } else { "hops" })?;
+                }
+
+                Ok(())
+            }
+            Self::Ambience { name } => write!(f, "ambience {}", name),
+            Self::Chances { subtype } => write!(f, "chances {}", subtype),
+            Self::Create { thing } => write!(f, "create {}", thing.thing.display_description()),
+            Self::CreateMultiple { thing, count: None } => {
+                write!(f, "create  multiple {}", thing.display_description())
+            }
+            Self::CreateMultiple {
+                thing,
+                count: Some(count),
+            } => write!(
+                f,
+                "create {} {}",
+                count,
+                plural(&thing.display_description().to_string()),
+            ),
+            Self::CreateContact { name, kind: None } => write!(f, "create contact for {}", name),
+            Self::CreateContact {
+                name,
+                kind: Some(kind),
+            } => write!(f, "create {} for {}", kind, name),
+            Self::CreateLair { monster } => write!(f, "create lair for {}", monster),
+            Self::Crew { name } => write!(f, "crew {}", name),
+            Self::Downtime { name, activity } => write!(f, "downtime {} {}", name, activity),
+            Self::Festivals => write!(f, "festivals"),
+            Self::Edit { name, diff, .. } => {
+                write!(f, "{} is {}", name, diff.thing.display_description())
+            }
+            Self::Give {
+                name,
+                item,
+                quantity,
+            } => write!(f, "give {} {} {}", name, quantity, item.get_name()),
+            Self::Kill { name } => write!(f, "kill {}", name),
+            Self::Learn { name, spells } => write!(
+                f,
+                "{} knows {}",
+                name,
+                spells
+                    .iter()
+                    .map(|spell| spell.get_name())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ),
+            Self::Forget { name, spell } => write!(f, "{} forgets {}", name, spell.get_name()),
+            Self::Lock {
+                name,
+                field,
+                locked: true,
+            } => write!(f, "lock {}'s {}", name, field),
+            Self::Lock {
+                name,
+                field,
+                locked: false,
+            } => write!(f, "unlock {}'s {}", name, field),
+            Self::Menu { name } => write!(f, "menu {}", name),
+            Self::Name { ethnicity: None } => write!(f, "name"),
+            Self::Name {
+                ethnicity: Some(ethnicity),
+            } => write!(f, "name {}", ethnicity),
+            Self::Party => write!(f, "party"),
+            Self::PartyAt { name } => write!(f, "party is at {}", name),
+            Self::PartyRest {
+                rest,
+                encounters: false,
+            } => write!(f, "rest {}", rest),
+            Self::PartyRest {
+                rest,
+                encounters: true,
+            } => write!(f, "rest {} with encounters", rest),
+            Self::Pc { name, pc } if pc == &PlayerCharacter::default() => write!(f, "pc {}", name),
+            Self::Pc { name, pc } => write!(f, "pc {}, {}", name, pc),
+            Self::Prep {
+                days: DEFAULT_PREP_DAYS,
+            } => write!(f, "prep"),
+            Self::Prep { days: 1 } => write!(f, "prep 1 day"),
+            Self::Prep { days } => write!(f, "prep {} days", days),
+            Self::Random {
+                thing,
+                location: None,
+            } => write!(f, "random {}", thing.display_description()),
+            Self::Random {
+                thing,
+                location: Some(location),
+            } => write!(f, "random {} in {}", thing.display_description(), location),
+            Self::RemoveStatus { name, status } => write!(f, "{} is no longer {}", name, status),
+            Self::Rename { name, new_name } => write!(f, "rename {} to {}", name, new_name),
+            Self::Reaction { name } => write!(f, "reaction {}", name),
+            Self::Rest { name, rest } => write!(f, "{} rest {}", rest, name),
+            Self::Retire { name } => write!(f, "retire {}", name),
+            Self::Room { name, number } => write!(f, "room {} of {}", number, name),
+            Self::Rumor { about: None } => write!(f, "rumor"),
+            Self::Rumor { about: Some(name) } => write!(f, "rumor about {}", name),
+            Self::SetSpellSlots { name, slots } => {
+                write!(f, "set slots")?;
+                for slot in slots {
+                    write!(f, " {}", slot)?;
+                }
+                write!(f, " for {}", name)
+            }
+            Self::Suggest { name, field } => write!(f, "suggest {} {}", name, field),
+            Self::Take {
+                name,
+                item,
+                quantity,
+            } => write!(f, "take {} {} from {}", quantity, item.get_name(), name),
+            Self::Talk { name } => write!(f, "talk {}", name),
+            Self::TrackFeature {
+                name,
+                feature,
+                max,
+                recovery,
+            } => write!(
+                f,
+                "track {} {} per {} rest for {}",
+                max, feature, recovery, name,
+            ),
+            Self::Travel { name } => write!(f, "travel to {}", name),
+            Self::UseResource { name, resource } => write!(f, "use {} for {}", resource, name),
+        }
+    }
+}
+
+impl<T: Into<Thing>> ParsedThing<T> {
+    pub fn into_thing(self) -> ParsedThing<Thing> {
+        ParsedThing {
+            thing: self.thing.into(),
+            unknown_words: self.unknown_words,
+            word_count: self.word_count,
+        }
+    }
+}
+
+impl<T: Default> Default for ParsedThing<T> {
+    fn default() -> Self {
+        Self {
+            thing: T::default(),
+            unknown_words: Vec::default(),
+            word_count: 0,
+        }
+    }
+}
+
+impl<T: Into<Thing>> From<ParsedThing<T>> for Thing {
+    fn from(input: ParsedThing<T>) -> Self {
+        input.thing.into()
+    }
+}
+
+pub(crate) fn append_unknown_words_notice(
+    mut output: String,
+    input: &str,
+    unknown_words: Vec<Range<usize>>,
+) -> String {
+    if !unknown_words.is_empty() {
+        output.push_str(
+            "\n\n! initiative.sh doesn't know some of those words, but it did its best.\n\n\\> ",
+        );
+
+        {
+            let mut pos = 0;
+            for word_range in unknown_words.iter() {
+                output.push_str(&input[pos..word_range.start]);
+                pos = word_range.end;
+                output.push_str("**");
+                output.push_str(&input[word_range.clone()]);
+                output.push_str("**");
+            }
+            output.push_str(&input[pos..]);
+        }
+
+        output.push_str("\\\n\u{a0}\u{a0}");
+
+        {
+            let mut words = unknown_words.into_iter();
+            let mut unknown_word = words.next();
+            for (i, _) in input.char_indices() {
+                if unknown_word.as_ref().map_or(false, |word| i >= word.end) {
+                    unknown_word = words.next();
+                }
+
+                if let Some(word) = &unknown_word {
+                    output.push(if i >= word.start { '^' } else { '\u{a0}' });
+                } else {
+                    break;
+                }
+            }
+        }
+
+        output.push_str("\\\nWant to help improve its vocabulary? Join us [on Discord](https://discord.gg/ZrqJPpxXVZ) and suggest your new words!");
+    }
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::assert_autocomplete;
+    use crate::storage::NullDataStore;
+    use crate::world::npc::{Age, Gender, Species};
+    use crate::Event;
+    use tokio_test::block_on;
+
+    #[test]
+    fn parse_input_test() {
+        let mut app_meta = app_meta();
+
+        assert_eq!(
+            CommandMatches::new_fuzzy(create(Npc::default())),
+            block_on(WorldCommand::parse_input("npc", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(create(Npc::default())),
+            block_on(WorldCommand::parse_input("create npc", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::new_fuzzy(create(Npc {
+                species: Species::Elf.into(),
+                ..Default::default()
+            })),
+            block_on(WorldCommand::parse_input("elf", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::default(),
+            block_on(WorldCommand::parse_input("potato", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(WorldCommand::Menu {
+                name: "The Prancing Pony".into(),
+            }),
+            block_on(WorldCommand::parse_input(
+                "menu The Prancing Pony",
+                &app_meta,
+            )),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(WorldCommand::Ambience {
+                name: "The Prancing Pony".into(),
+            }),
+            block_on(WorldCommand::parse_input(
+                "ambience The Prancing Pony",
+                &app_meta,
+            )),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(WorldCommand::Crew {
+                name: "The Salty Gull".into(),
+            }),
+            block_on(WorldCommand::parse_input("crew The Salty Gull", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(WorldCommand::PartyAt {
+                name: "The Silver Eel".into(),
+            }),
+            block_on(WorldCommand::parse_input(
+                "the party is at The Silver Eel",
+                &app_meta
+            )),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(WorldCommand::Travel {
+                name: "Waterdeep".into(),
+            }),
+            block_on(WorldCommand::parse_input("travel to Waterdeep", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(WorldCommand::Room {
+                name: "The Sunken Crypt".into(),
+                number: 3,
+            }),
+            block_on(WorldCommand::parse_input(
+                "room 3 of The Sunken Crypt",
+                &app_meta,
+            )),
+        );
+
+        assert_eq!(
+            CommandMatches::default(),
+            block_on(WorldCommand::parse_input(
+                "room three of The Sunken Crypt",
+                &app_meta
+            )),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(WorldCommand::Connections {
+                name: "Gottfried".into(),
+                hops: 3,
+            }),
+            block_on(WorldCommand::parse_input(
+                "connections Gottfried",
+                &app_meta
+            )),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(WorldCommand::Connections {
+                name: "Gottfried".into(),
+                hops: 1,
+            }),
+            block_on(WorldCommand::parse_input(
+                "connections Gottfried 1 hop",
+                &app_meta,
+            )),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(WorldCommand::Rename {
+                name: "Went".into(),
+                new_name: "to Town to Stayed Home".into(),
+            }),
+            block_on(WorldCommand::parse_input(
+                "rename Went to to Town to Stayed Home",
+                &app_meta,
+            )),
+        );
+
+        block_on(
+            app_meta.repository.modify(Change::Create {
+                thing: Place {
+                    name: "Went to Town".into(),
+                    ..Default::default()
+                }
+                .into(),
+            }),
+        )
+        .unwrap();
+
+        assert_eq!(
+            CommandMatches::new_canonical(WorldCommand::Rename {
+                name: "Went to Town".into(),
+                new_name: "Stayed Home".into(),
+            }),
+            block_on(WorldCommand::parse_input(
+                "rename Went to Town to Stayed Home",
+                &app_meta,
+            )),
+        );
+
+        {
+            block_on(
+                app_meta.repository.modify(Change::Create {
+                    thing: Npc {
+                        name: "Spot".into(),
+                        ..Default::default()
+                    }
+                    .into(),
+                }),
+            )
+            .unwrap();
+
+            assert_eq!(
+                CommandMatches::new_fuzzy(WorldCommand::Edit {
+                    name: "Spot".into(),
+                    uuid: None,
+                    diff: Box::new(ParsedThing {
+                        thing: Npc {
+                            age: Age::Child.into(),
+                            gender: Gender::Masculine.into(),
+                            ..Default::default()
+                        }
+                        .into(),
+                        unknown_words: vec![10..14],
+                        word_count: 2,
+                    }),
+                }),
+                block_on(WorldCommand::parse_input("Spot is a good boy", &app_meta)),
+            );
+
+            assert_eq!(
+                CommandMatches::new_canonical(WorldCommand::Rename {
+                    name: "Spot".into(),
+                    new_name: "Rover".into(),
+                }),
+                block_on(WorldCommand::parse_input("rename spot to Rover", &app_meta)),
+            );
+        }
+
+        {
+            block_on(app_meta.repository.init());
+
+            let gimli = block_on(
+                app_meta.repository.modify(Change::CreateAndSave {
+                    thing: Npc {
+                        name: "Gimli".into(),
+                        ..Default::default()
+                    }
+                    .into(),
+                }),
+            )
+            .unwrap()
+            .unwrap();
+
+            assert!(matches!(
+                block_on(WorldCommand::parse_input("gimli is a dwarf", &app_meta)).take_best_match(),
+                Some(WorldCommand::Edit { name, uuid, .. })
+                    if name == "Gimli" && uuid.as_ref() == gimli.uuid(),
+            ));
+            assert!(gimli.uuid().is_some());
+        }
+    }
+
+    #[test]
+    fn autocomplete_test() {
+        let mut app_meta = app_meta();
+
+        block_on(
+            app_meta.repository.modify(Change::Create {
+                thing: Npc {
+                    name: "Potato Johnson".into(),
+                    species: Species::Elf.into(),
+                    gender: Gender::NonBinaryThey.into(),
+                    age: Age::Adult.into(),
+                    ..Default::default()
+                }
+                .into(),
+            }),
+        )
+        .unwrap();
+
+        [
+            ("npc", "create person"),
+            // Species
+            ("dragonborn", "create dragonborn"),
+            ("dwarf", "create dwarf"),
+            ("elf", "create elf"),
+            ("gnome", "create gnome"),
+            ("half-elf", "create half-elf"),
+            ("half-orc", "create half-orc"),
+            ("halfling", "create halfling"),
+            ("human", "create human"),
+            ("tiefling", "create tiefling"),
+            // Occupation
+            ("cultist", "create cultist"),
+            // PlaceType
+            ("tavern", "create inn"),
+        ]
+        .into_iter()
+        .for_each(|(word, summary)| {
+            assert_eq!(
+                vec![AutocompleteSuggestion::new(word, summary)],
+                block_on(WorldCommand::autocomplete(word, &app_meta)),
+            );
+
+            assert_eq!(
+                vec![AutocompleteSuggestion::new(word, summary)],
+                block_on(WorldCommand::autocomplete(&word.to_uppercase(), &app_meta)),
+            );
+        });
+
+        assert_autocomplete(
+            &[("inn", "create inn"), ("innkeeper", "create innkeeper")][..],
+            block_on(WorldCommand::autocomplete("inn", &app_meta)),
+        );
+
+        assert_autocomplete(
+            &[
+                ("baby", "create infant"),
+                ("baker", "create baker"),
+                ("bakery", "create bakery"),
+                ("bank", "create bank"),
+                ("bar", "create bar"),
+                ("barony", "create barony"),
+                ("barracks", "create barracks"),
+                ("barrens", "create barrens"),
+                ("base", "create base"),
+                ("bathhouse", "create bathhouse"),
+                ("beach", "create beach"),
+                ("blacksmith", "create blacksmith"),
+                ("boat", "create ship"),
+                ("boy", "create child, he/him"),
+                ("brewery", "create brewery"),
+                ("bridge", "create bridge"),
+                ("building", "create building"),
+                ("business", "create business"),
+                ("butcher", "create butcher"),
+            ][..],
+            block_on(WorldCommand::autocomplete("b", &app_meta)),
+        );
+
+        assert_autocomplete(
+            &[(
+                "Potato Johnson is [character description]",
+                "edit character",
+            )][..],
+            block_on(WorldCommand::autocomplete("Potato Johnson", &app_meta)),
+        );
+
+        assert_autocomplete(
+            &[(
+                "Potato Johnson is a [character description]",
+                "edit character",
+            )][..],
+            block_on(WorldCommand::autocomplete(
+                "Potato Johnson is a ",
+                &app_meta,
+            )),
+        );
+
+        assert_autocomplete(
+            &[
+                ("Potato Johnson is an elderly", "edit character"),
+                ("Potato Johnson is an elf", "edit character"),
+                ("Potato Johnson is an elvish", "edit character"),
+                ("Potato Johnson is an enby", "edit character"),
+            ][..],
+            block_on(WorldCommand::autocomplete(
+                "Potato Johnson is an e",
+                &app_meta,
+            )),
+        );
+
+        assert_autocomplete(
+            &[("rename [name] to [new name]", "rename an entry")][..],
+            block_on(WorldCommand::autocomplete("ren", &app_meta)),
+        );
+    }
+
+    #[test]
+    fn parse_input_name_test() {
+        let app_meta = app_meta();
+
+        assert_eq!(
+            Some(WorldCommand::Name { ethnicity: None }),
+            block_on(WorldCommand::parse_input("Name", &app_meta)).canonical_match,
+        );
+        assert_eq!(
+            Some(WorldCommand::Name {
+                ethnicity: Some(Ethnicity::Dwarvish),
+            }),
+            block_on(WorldCommand::parse_input("name dwarvish", &app_meta)).canonical_match,
+        );
+        assert_eq!(
+            Some(WorldCommand::Name {
+                ethnicity: Some(Ethnicity::Elvish),
+            }),
+            block_on(WorldCommand::parse_input("name elf", &app_meta)).canonical_match,
+        );
+        assert_eq!(
+            None,
+            block_on(WorldCommand::parse_input("name potato", &app_meta)).canonical_match,
+        );
+    }
+
+    #[test]
+    fn parse_input_resources_test() {
+        let app_meta = app_meta();
+
+        assert_eq!(
+            CommandMatches::new_canonical(WorldCommand::Rest {
+                name: "Gottfried".into(),
+                rest: Rest::Long,
+            }),
+            block_on(WorldCommand::parse_input("rest Gottfried", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(WorldCommand::Rest {
+                name: "Gottfried".into(),
+                rest: Rest::Short,
+            }),
+            block_on(WorldCommand::parse_input("SHORT REST Gottfried", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(WorldCommand::Reaction {
+                name: "Gottfried".into(),
+            }),
+            block_on(WorldCommand::parse_input("reaction Gottfried", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(WorldCommand::Talk {
+                name: "Gottfried".into(),
+            }),
+            block_on(WorldCommand::parse_input("talk to Gottfried", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(WorldCommand::Rumor {
+                about: Some("Gottfried".into()),
+            }),
+            block_on(WorldCommand::parse_input(
+                "rumor about Gottfried",
+                &app_meta
+            )),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(WorldCommand::UseResource {
+                name: "Gottfried".into(),
+                resource: Resource::SpellSlot(3),
+            }),
+            block_on(WorldCommand::parse_input(
+                "use slot 3 for Gottfried",
+                &app_meta
+            )),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(WorldCommand::UseResource {
+                name: "Gottfried".into(),
+                resource: Resource::Feature("Lay on Hands for Real".into()),
+            }),
+            block_on(WorldCommand::parse_input(
+                "use Lay on Hands for Real for Gottfried",
+                &app_meta,
+            )),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(WorldCommand::SetSpellSlots {
+                name: "Gottfried".into(),
+                slots: vec![4, 3, 2],
+            }),
+            block_on(WorldCommand::parse_input(
+                "set slots 4 3 2 for Gottfried",
+                &app_meta,
+            )),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(WorldCommand::TrackFeature {
+                name: "Gottfried".into(),
+                feature: "Channel Divinity".into(),
+                max: 2,
+                recovery: Rest::Short,
+            }),
+            block_on(WorldCommand::parse_input(
+                "track 2 Channel Divinity per short rest for Gottfried",
+                &app_meta,
+            )),
+        );
+
+        [
+            "use slot 10 for Gottfried",
+            "use slot 3",
+            "set slots 4 three for Gottfried",
+            "track two Rage per long rest for Gottfried",
+            "track 2 Rage per day for Gottfried",
+        ]
+        .into_iter()
+        .for_each(|input| {
+            assert_eq!(
+                CommandMatches::default(),
+                block_on(WorldCommand::parse_input(input, &app_meta)),
+                "{}",
+                input,
+            );
+        });
+    }
+
+    #[test]
+    fn parse_input_inventory_test() {
+        let app_meta = app_meta();
+
+        assert_eq!(
+            CommandMatches::new_canonical(WorldCommand::Give {
+                name: "Gottfried".into(),
+                item: Equipment::Longsword,
+                quantity: 1,
+            }),
+            block_on(WorldCommand::parse_input(
+                "give Gottfried a longsword",
+                &app_meta,
+            )),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(WorldCommand::Give {
+                name: "Gottfried the Bold".into(),
+                item: Equipment::Longsword,
+                quantity: 3,
+            }),
+            block_on(WorldCommand::parse_input(
+                "GIVE Gottfried the Bold 3 Longswords",
+                &app_meta,
+            )),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(WorldCommand::Take {
+                name: "Gottfried".into(),
+                item: Equipment::Shield,
+                quantity: 1,
+            }),
+            block_on(WorldCommand::parse_input(
+                "take a shield from Gottfried",
+                &app_meta,
+            )),
+        );
+
+        [
+            "give Gottfried",
+            "give Gottfried a potato",
+            "take a longsword",
+            "take a longsword from ",
+        ]
+        .into_iter()
+        .for_each(|input| {
+            assert_eq!(
+                CommandMatches::default(),
+                block_on(WorldCommand::parse_input(input, &app_meta)),
+                "{}",
+                input,
+            );
+        });
+
+        [
+            WorldCommand::Give {
+                name: "Gottfried".into(),
+                item: Equipment::Longsword,
+                quantity: 2,
+            },
+            WorldCommand::Take {
+                name: "Gottfried".into(),
+                item: Equipment::Shield,
+                quantity: 1,
+            },
+        ]
+        .into_iter()
+        .for_each(|command| {
+            let command_string = command.to_string();
+
+            assert_eq!(
+                CommandMatches::new_canonical(command),
+                block_on(WorldCommand::parse_input(&command_string, &app_meta)),
+                "{}",
+                command_string,
+            );
+        });
+    }
+
+    #[test]
+    fn parse_input_status_test() {
+        let mut app_meta = app_meta();
+
+        block_on(
+            app_meta.repository.modify(Change::Create {
+                thing: Npc {
+                    name: "Gottfried".into(),
+                    ..Default::default()
+                }
+                .into(),
+            }),
+        )
+        .unwrap();
+
+        assert_eq!(
+            CommandMatches::new_fuzzy(WorldCommand::AddStatus {
+                name: "Gottfried".into(),
+                status: "poisoned".into(),
+                duration: None,
+            }),
+            block_on(WorldCommand::parse_input(
+                "gottfried is poisoned",
+                &app_meta
+            )),
+        );
+
+        assert_eq!(
+            CommandMatches::new_fuzzy(WorldCommand::AddStatus {
+                name: "Gottfried".into(),
+                status: "on fire".into(),
+                duration: Some(Interval::new_rounds(3)),
+            }),
+            block_on(WorldCommand::parse_input(
+                "Gottfried is on fire for 3 rounds",
+                &app_meta,
+            )),
+        );
+
+        assert_eq!(
+            CommandMatches::new_fuzzy(WorldCommand::AddStatus {
+                name: "Gottfried".into(),
+                status: "hunted".into(),
+                duration: Some(Interval::new_days(3)),
+            }),
+            block_on(WorldCommand::parse_input(
+                "Gottfried is hunted for 3 days",
+                &app_meta,
+            )),
+        );
+
+        assert_eq!(
+            CommandMatches::new_fuzzy(WorldCommand::AddStatus {
+                name: "Gottfried".into(),
+                status: "poisoned".into(),
+                duration: Some(Interval::new_minutes(0)),
+            }),
+            block_on(WorldCommand::parse_input(
+                "Gottfried is poisoned for 0 minutes",
+                &app_meta,
+            )),
+        );
+
+        // Anything else is left for the other commands to make sense of.
+        [
+            "Gottfried is a potato",
+            "Gottfried is hunted",
+            "Gottfried is cursed for eternity",
+            "Gottfried is a potato for 3 days",
+        ]
+        .into_iter()
+        .for_each(|input| {
+            let matches = block_on(WorldCommand::parse_input(input, &app_meta));
+            assert!(
+                !matches
+                    .canonical_match
+                    .iter()
+                    .chain(matches.fuzzy_matches.iter())
+                    .any(|command| matches!(command, WorldCommand::AddStatus { .. })),
+                "{}",
+                input,
+            )
+        });
+
+        assert_eq!(
+            CommandMatches::new_canonical(WorldCommand::RemoveStatus {
+                name: "Gottfried".into(),
+                status: "poisoned".into(),
+            }),
+            block_on(WorldCommand::parse_input(
+                "GOTTFRIED IS NO LONGER poisoned",
+                &app_meta,
+            )),
+        );
+
+        // Statuses can only be added to things that exist.
+        assert_eq!(
+            CommandMatches::default(),
+            block_on(WorldCommand::parse_input("Frodo is poisoned", &app_meta)),
+        );
+
+        [
+            WorldCommand::AddStatus {
+                name: "Gottfried".into(),
+                status: "asleep".into(),
+                duration: Some(Interval::new_hours(8)),
+            },
+            WorldCommand::RemoveStatus {
+                name: "Gottfried".into(),
+                status: "asleep".into(),
+            },
+        ]
+        .into_iter()
+        .for_each(|command| {
+            let command_string = command.to_string();
+            let matches = block_on(WorldCommand::parse_input(&command_string, &app_meta));
+
+            assert!(
+                matches.canonical_match.as_ref() == Some(&command)
+                    || matches.fuzzy_matches.contains(&command),
+                "{}",
+                command_string,
+            );
+        });
+    }
+
+    #[test]
+    fn parse_field_edit_test() {
+        let npc = Thing::from(Npc {
+            name: "Gottfried".into(),
+            ..Default::default()
+        });
+        let place = Thing::from(Place {
+            name: "The Silver Eel".into(),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            Some(WorldCommand::Edit {
+                name: "Gottfried".into(),
+                uuid: None,
+                diff: Box::new(ParsedThing {
+                    thing: Npc {
+                        age_years: 40.into(),
+                        ..Default::default()
+                    }
+                    .into(),
+                    unknown_words: Vec::new(),
+                    word_count: 1,
+                }),
+            }),
+            parse_field_edit(&npc, "age", "40y"),
+        );
+
+        assert_eq!(
+            Some(WorldCommand::Edit {
+                name: "Gottfried".into(),
+                uuid: None,
+                diff: Box::new(ParsedThing {
+                    thing: Npc {
+                        species: Species::Elf.into(),
+                        ..Default::default()
+                    }
+                    .into(),
+                    unknown_words: Vec::new(),
+                    word_count: 1,
+                }),
+            }),
+            parse_field_edit(&npc, "Species", "elf"),
+        );
+
+        assert_eq!(
+            Some(WorldCommand::Edit {
+                name: "The Silver Eel".into(),
+                uuid: None,
+                diff: Box::new(ParsedThing {
+                    thing: Place {
+                        description: "A dive".into(),
+                        ..Default::default()
+                    }
+                    .into(),
+                    unknown_words: Vec::new(),
+                    word_count: 1,
+                }),
+            }),
+            parse_field_edit(&place, "description", "A dive"),
+        );
+
+        assert_eq!(
+            Some(WorldCommand::Rename {
+                name: "Gottfried".into(),
+                new_name: "Godfrey".into(),
+            }),
+            parse_field_edit(&npc, "name", "Godfrey"),
+        );
+
+        assert_eq!(None, parse_field_edit(&npc, "species", "elderly"));
+        assert_eq!(None, parse_field_edit(&npc, "description", "tall"));
+        assert_eq!(None, parse_field_edit(&npc, "potato", "elf"));
+        assert_eq!(None, parse_field_edit(&place, "menu", "stew"));
+        assert_eq!(None, parse_field_edit(&place, "name", ""));
+    }
+
+    #[test]
+    fn parse_age_years_test() {
+        assert_eq!(Some(40), parse_age_years("40"));
+        assert_eq!(Some(40), parse_age_years("40y"));
+        assert_eq!(Some(40), parse_age_years("40 Years Old"));
+        assert_eq!(Some(1), parse_age_years("1 year"));
+        assert_eq!(None, parse_age_years("elderly"));
+        assert_eq!(None, parse_age_years("-3"));
+    }
+
+    #[test]
+    fn parse_duration_test() {
+        assert_eq!(Some(Interval::new_hours(8)), parse_duration("8h"));
+        assert_eq!(Some(Interval::new_rounds(3)), parse_duration("3 rounds"));
+        assert_eq!(Some(Interval::new_minutes(1)), parse_duration("a minute"));
+        assert_eq!(Some(Interval::new_days(2)), parse_duration("2 Days"));
+        assert_eq!(Some(Interval::new_days(0)), parse_duration("0 days"));
+        assert_eq!(None, parse_duration("-1 days"));
+        assert_eq!(None, parse_duration("eternity"));
+    }
+
+    #[test]
+    fn display_test_connections() {
+        let app_meta = app_meta();
+
+        [
+            ("connections Gottfried", DEFAULT_HOPS),
+            ("connections Gottfried 1 hop", 1),
+            ("connections Gottfried 5 hops", 5),
+        ]
+        .into_iter()
+        .for_each(|(command_string, hops)| {
+            let command = WorldCommand::Connections {
+                name: "Gottfried".into(),
+                hops,
+            };
+
+            assert_eq!(command_string, command.to_string());
+            assert_eq!(
+                CommandMatches::new_canonical(command),
+                block_on(WorldCommand::parse_input(command_string, &app_meta)),
+            );
+        });
+    }
+
+    #[test]
+    fn parse_hops_test() {
+        assert_eq!(("Gottfried", DEFAULT_HOPS), parse_hops("Gottfried"));
+        assert_eq!(("Gottfried", 1), parse_hops("Gottfried 1 hop"));
+        assert_eq!(("The Silver Eel", 5), parse_hops("The Silver Eel 5 HOPS"));
+        assert_eq!(("Gottfried", MAX_HOPS), parse_hops("Gottfried 99 hops"));
+        assert_eq!(
+            ("Gottfried 0 hops", DEFAULT_HOPS),
+            parse_hops("Gottfried 0 hops")
+        );
+        assert_eq!(("Hops", DEFAULT_HOPS), parse_hops("Hops"));
+    }
+
+    #[test]
+    fn parse_room_test() {
+        assert_eq!(
+            Some((3, "The Sunken Crypt")),
+            parse_room("3 of The Sunken Crypt")
+        );
+        assert_eq!(Some((1, "Tomb")), parse_room("1 OF Tomb"));
+        assert_eq!(None, parse_room("3 The Sunken Crypt"));
+        assert_eq!(None, parse_room("3 of "));
+        assert_eq!(None, parse_room("three of Tomb"));
+    }
+
+    #[test]
+    fn display_test_party_rest() {
+        let app_meta = app_meta();
+
+        [
+            ("rest short", Rest::Short, false),
+            ("rest long", Rest::Long, false),
+            ("rest long with encounters", Rest::Long, true),
+        ]
+        .into_iter()
+        .for_each(|(command_string, rest, encounters)| {
+            let command = WorldCommand::PartyRest { rest, encounters };
+
+            assert_eq!(command_string, command.to_string());
+            assert_eq!(
+                CommandMatches::new_canonical(command),
+                block_on(WorldCommand::parse_input(
+                    &command_string.to_uppercase(),
+                    &app_meta,
+                )),
+            );
+        });
+    }
+
+    #[test]
+    fn display_test_create_contact() {
+        let app_meta = app_meta();
+
+        [
+            None,
+            Some(ContactKind::Fence),
+            Some(ContactKind::Mentor),
+            Some(ContactKind::Relative),
+            Some(ContactKind::Rival),
+        ]
+        .into_iter()
+        .for_each(|kind| {
+            let command = WorldCommand::CreateContact {
+                name: "Aria Moonwhisper".to_string(),
+                kind,
+            };
+            let command_string = command.to_string();
+
+            assert_eq!(
+                CommandMatches::new_canonical(command),
+                block_on(WorldCommand::parse_input(&command_string, &app_meta)),
+                "{}",
+                command_string,
+            );
+        });
+
+        assert_eq!(
+            CommandMatches::new_canonical(WorldCommand::CreateContact {
+                name: "Aria".to_string(),
+                kind: None,
+            }),
+            block_on(WorldCommand::parse_input("contact for Aria", &app_meta)),
+        );
+
+        assert_eq!(
+            CommandMatches::new_canonical(WorldCommand::CreateContact {
+                name: "Aria".to_string(),
+                kind: Some(ContactKind::Mentor),
+            }),
+            block_on(WorldCommand::parse_input(
+                "create teacher for Aria",
+                &app_meta
+            )),
+        );
+    }
+
+    #[test]
+    fn display_test_downtime() {
+        let app_meta = app_meta();
+
+        [
+            Activity::Crafting { days: 5 },
+            Activity::CraftingItem {
+                item: Equipment::Shield,
+                quantity: 2,
+            },
+            Activity::Training { days: 1 },
+            Activity::Working { days: 30 },
+        ]
+        .into_iter()
+        .for_each(|activity| {
+            let command = WorldCommand::Downtime {
+                name: "Aria Moonwhisper".to_string(),
+                activity,
+            };
+            let command_string = command.to_string();
+
+            assert_eq!(
+                CommandMatches::new_canonical(command),
+                block_on(WorldCommand::parse_input(&command_string, &app_meta)),
+                "{}",
+                command_string,
+            );
+        });
+
+        assert_eq!(
+            CommandMatches::new_canonical(WorldCommand::Downtime {
+                name: "Aria".to_string(),
+                activity: Activity::CraftingItem {
+                    item: Equipment::Longsword,
+                    quantity: 1,
+                },
+            }),
+            block_on(WorldCommand::parse_input(
+                "DOWNTIME Aria crafting a longsword",
+                &app_meta,
+            )),
+        );
+
+        assert_eq!(
+            CommandMatches::default(),
+            block_on(WorldCommand::parse_input(
+                "downtime Aria carousing 5 days",
+                &app_meta,
+            )),
+        );
+    }
 
-        {
-            let mut pos = 0;
-            for word_range in unknown_words.iter() {
-                output.push_str(&input[pos..word_range.start]);
-                pos = word_range.end;
-                output.push_str("**");
-                output.push_str(&input[word_range.clone()]);
-                output.push_str("**");
-            }
-            output.push_str(&input[pos..]);
-        }
+    #[test]
+    fn display_test_room() {
+        let command = WorldCommand::Room {
+            name: "The Sunken Crypt".into(),
+            number: 3,
+        };
 
-        output.push_str("\\\n\u{a0}\u{a0}");
+        assert_eq!("room 3 of The Sunken Crypt", command.to_string());
+        assert_eq!(
+            CommandMatches::new_canonical(command),
+            block_on(WorldCommand::parse_input(
+                "room 3 of The Sunken Crypt",
+                &app_meta()
+            )),
+        );
+    }
 
-        {
-            let mut words = unknown_words.into_iter();
-            let mut unknown_word = words.next();
-            for (i, _) in input.char_indices() {
-                if unknown_word.as_ref().map_or(false, |word| i >= word.end) {
-                    unknown_word = words.next();
-                }
+    #[test]
+    fn display_test_lock() {
+        let app_meta = app_meta();
 
-                if let Some(word) = &unknown_word {
-                    output.push(if i >= word.start { '^' } else { '\u{a0}' });
-                } else {
-                    break;
-                }
-            }
-        }
+        [
+            ("lock Gottfried's age", "Gottfried", "age", true),
+            (
+                "unlock The Silver Eel's description",
+                "The Silver Eel",
+                "description",
+                false,
+            ),
+        ]
+        .into_iter()
+        .for_each(|(command_string, name, field, locked)| {
+            let command = WorldCommand::Lock {
+                name: name.into(),
+                field: field.into(),
+                locked,
+            };
 
-        output.push_str("\\\nWant to help improve its vocabulary? Join us [on Discord](https://discord.gg/ZrqJPpxXVZ) and suggest your new words!");
-    }
-    output
-}
+            assert_eq!(command_string, command.to_string());
+            assert_eq!(
+                Some(command),
+                block_on(WorldCommand::parse_input(command_string, &app_meta)).canonical_match,
+            );
+        });
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use crate::app::assert_autocomplete;
-    use crate::storage::NullDataStore;
-    use crate::world::npc::{Age, Gender, Species};
-    use crate::world::place::PlaceType;
-    use crate::Event;
-    use tokio_test::block_on;
+        assert_eq!(
+            None,
+            block_on(WorldCommand::parse_input("lock Gottfried", &app_meta)).canonical_match,
+        );
+    }
 
     #[test]
-    fn parse_input_test() {
-        let mut app_meta = app_meta();
+    fn display_test_suggest() {
+        let app_meta = app_meta();
+        let command = WorldCommand::Suggest {
+            name: "The Silver Eel".into(),
+            field: "description".into(),
+        };
 
+        assert_eq!("suggest The Silver Eel description", command.to_string());
         assert_eq!(
-            CommandMatches::new_fuzzy(create(Npc::default())),
-            block_on(WorldCommand::parse_input("npc", &app_meta)),
+            Some(command.clone()),
+            block_on(WorldCommand::parse_input(
+                "suggest The Silver Eel description",
+                &app_meta,
+            ))
+            .canonical_match,
         );
-
         assert_eq!(
-            CommandMatches::new_canonical(create(Npc::default())),
-            block_on(WorldCommand::parse_input("create npc", &app_meta)),
+            Some(command),
+            block_on(WorldCommand::parse_input(
+                "SUGGEST The Silver Eel's Description",
+                &app_meta,
+            ))
+            .canonical_match,
+        );
+        assert_eq!(
+            None,
+            block_on(WorldCommand::parse_input("suggest Gottfried", &app_meta)).canonical_match,
         );
+    }
+
+    #[test]
+    fn display_test_fate() {
+        let app_meta = app_meta();
+
+        [
+            (
+                "kill",
+                WorldCommand::Kill {
+                    name: "Gottfried".into(),
+                },
+            ),
+            (
+                "retire",
+                WorldCommand::Retire {
+                    name: "Gottfried".into(),
+                },
+            ),
+        ]
+        .into_iter()
+        .for_each(|(verb, command)| {
+            assert_eq!(format!("{} Gottfried", verb), command.to_string());
+            assert_eq!(
+                Some(command.clone()),
+                block_on(WorldCommand::parse_input(&command.to_string(), &app_meta))
+                    .canonical_match,
+            );
+            assert_eq!(
+                Some(command),
+                block_on(WorldCommand::parse_input(
+                    &format!("{} Gottfried", verb.to_uppercase()),
+                    &app_meta,
+                ))
+                .canonical_match,
+            );
+        });
+    }
+
+    #[test]
+    fn display_test_alias() {
+        let app_meta = app_meta();
+        let command = WorldCommand::AddAlias {
+            name: "The Yawning Portal".into(),
+            alias: "the Portal".into(),
+        };
 
         assert_eq!(
-            CommandMatches::new_fuzzy(create(Npc {
-                species: Species::Elf.into(),
-                ..Default::default()
-            })),
-            block_on(WorldCommand::parse_input("elf", &app_meta)),
+            "The Yawning Portal is also known as the Portal",
+            command.to_string(),
         );
+        assert_eq!(
+            Some(command),
+            block_on(WorldCommand::parse_input(
+                "The Yawning Portal is ALSO KNOWN AS the Portal",
+                &app_meta,
+            ))
+            .canonical_match,
+        );
+    }
 
+    #[test]
+    fn display_test_chances() {
+        let command = WorldCommand::Chances {
+            subtype: "inn".parse().unwrap(),
+        };
+
+        assert_eq!("chances inn", command.to_string());
         assert_eq!(
-            CommandMatches::default(),
-            block_on(WorldCommand::parse_input("potato", &app_meta)),
+            Some(command),
+            block_on(WorldCommand::parse_input("CHANCES TAVERN", &app_meta())).canonical_match,
         );
+    }
 
-        {
-            block_on(
-                app_meta.repository.modify(Change::Create {
-                    thing: Npc {
-                        name: "Spot".into(),
-                        ..Default::default()
-                    }
-                    .into(),
-                }),
-            )
-            .unwrap();
+    #[test]
+    fn display_test_random() {
+        let app_meta = app_meta();
 
-            assert_eq!(
-                CommandMatches::new_fuzzy(WorldCommand::Edit {
-                    name: "Spot".into(),
-                    diff: ParsedThing {
-                        thing: Npc {
-                            age: Age::Child.into(),
-                            gender: Gender::Masculine.into(),
+        [
+            (
+                "random inn",
+                WorldCommand::Random {
+                    thing: Box::new(
+                        Place {
+                            subtype: "inn".parse::<PlaceType>().ok().into(),
                             ..Default::default()
                         }
                         .into(),
-                        unknown_words: vec![10..14],
-                        word_count: 2,
-                    },
-                }),
-                block_on(WorldCommand::parse_input("Spot is a good boy", &app_meta)),
+                    ),
+                    location: None,
+                },
+            ),
+            (
+                "random dwarf in Greenest",
+                WorldCommand::Random {
+                    thing: Box::new(
+                        Npc {
+                            species: Some(Species::Dwarf).into(),
+                            ..Default::default()
+                        }
+                        .into(),
+                    ),
+                    location: Some("Greenest".into()),
+                },
+            ),
+        ]
+        .into_iter()
+        .for_each(|(command_string, command)| {
+            assert_eq!(command_string, command.to_string());
+            assert_eq!(
+                Some(command),
+                block_on(WorldCommand::parse_input(command_string, &app_meta)).canonical_match,
             );
-        }
+        });
+
+        assert_eq!(
+            None,
+            block_on(WorldCommand::parse_input("random potato", &app_meta)).canonical_match,
+        );
     }
 
     #[test]
-    fn autocomplete_test() {
-        let mut app_meta = app_meta();
-
-        block_on(
-            app_meta.repository.modify(Change::Create {
-                thing: Npc {
-                    name: "Potato Johnson".into(),
-                    species: Species::Elf.into(),
-                    gender: Gender::NonBinaryThey.into(),
-                    age: Age::Adult.into(),
-                    ..Default::default()
-                }
-                .into(),
-            }),
-        )
-        .unwrap();
+    fn display_test_spells() {
+        let app_meta = app_meta();
 
         [
-            ("npc", "create person"),
-            // Species
-            ("dragonborn", "create dragonborn"),
-            ("dwarf", "create dwarf"),
-            ("elf", "create elf"),
-            ("gnome", "create gnome"),
-            ("half-elf", "create half-elf"),
-            ("half-orc", "create half-orc"),
-            ("halfling", "create halfling"),
-            ("human", "create human"),
-            ("tiefling", "create tiefling"),
-            // PlaceType
-            ("inn", "create inn"),
+            (
+                "Gottfried knows Fireball, Shield",
+                WorldCommand::Learn {
+                    name: "Gottfried".into(),
+                    spells: vec![Spell::Fireball, Spell::Shield],
+                },
+            ),
+            (
+                "Gottfried forgets Magic Missile",
+                WorldCommand::Forget {
+                    name: "Gottfried".into(),
+                    spell: Spell::MagicMissile,
+                },
+            ),
         ]
         .into_iter()
-        .for_each(|(word, summary)| {
+        .for_each(|(command_string, command)| {
+            assert_eq!(command_string, command.to_string());
             assert_eq!(
-                vec![AutocompleteSuggestion::new(word, summary)],
-                block_on(WorldCommand::autocomplete(word, &app_meta)),
+                Some(command.clone()),
+                block_on(WorldCommand::parse_input(command_string, &app_meta)).canonical_match,
             );
-
             assert_eq!(
-                vec![AutocompleteSuggestion::new(word, summary)],
-                block_on(WorldCommand::autocomplete(&word.to_uppercase(), &app_meta)),
+                Some(command),
+                block_on(WorldCommand::parse_input(
+                    &command_string
+                        .to_lowercase()
+                        .replacen("gottfried", "Gottfried", 1),
+                    &app_meta,
+                ))
+                .canonical_match,
             );
         });
 
-        assert_autocomplete(
-            &[
-                ("baby", "create infant"),
-                ("bakery", "create bakery"),
-                ("bank", "create bank"),
-                ("bar", "create bar"),
-                ("barony", "create barony"),
-                ("barracks", "create barracks"),
-                ("barrens", "create barrens"),
-                ("base", "create base"),
-                ("bathhouse", "create bathhouse"),
-                ("beach", "create beach"),
-                ("blacksmith", "create blacksmith"),
-                ("boy", "create child, he/him"),
-                ("brewery", "create brewery"),
-                ("bridge", "create bridge"),
-                ("building", "create building"),
-                ("business", "create business"),
-            ][..],
-            block_on(WorldCommand::autocomplete("b", &app_meta)),
+        assert_eq!(
+            Some(WorldCommand::Learn {
+                name: "Gottfried".into(),
+                spells: vec![Spell::Fireball, Spell::Shield, Spell::Light],
+            }),
+            block_on(WorldCommand::parse_input(
+                "Gottfried knows fireball, shield and light",
+                &app_meta,
+            ))
+            .canonical_match,
         );
-
-        assert_autocomplete(
-            &[(
-                "Potato Johnson is [character description]",
-                "edit character",
-            )][..],
-            block_on(WorldCommand::autocomplete("Potato Johnson", &app_meta)),
+        assert_eq!(
+            None,
+            block_on(WorldCommand::parse_input(
+                "Gottfried knows potatoes",
+                &app_meta
+            ))
+            .canonical_match,
         );
+    }
 
-        assert_autocomplete(
-            &[(
-                "Potato Johnson is a [character description]",
-                "edit character",
-            )][..],
-            block_on(WorldCommand::autocomplete(
-                "Potato Johnson is a ",
-                &app_meta,
-            )),
+    #[test]
+    fn display_test_pc() {
+        let app_meta = app_meta();
+
+        [
+            (
+                "pc Aragorn",
+                WorldCommand::Pc {
+                    name: "Aragorn".into(),
+                    pc: PlayerCharacter::default(),
+                },
+            ),
+            (
+                "pc Aragorn, level 5 ranger, AC 16, passive perception 14, played by Sam",
+                WorldCommand::Pc {
+                    name: "Aragorn".into(),
+                    pc: PlayerCharacter {
+                        player: Some("Sam".to_string()),
+                        class: Some("ranger".to_string()),
+                        level: Some(5),
+                        armor_class: Some(16),
+                        passive_perception: Some(14),
+                    },
+                },
+            ),
+        ]
+        .into_iter()
+        .for_each(|(command_string, command)| {
+            assert_eq!(command_string, command.to_string());
+            assert_eq!(
+                Some(command.clone()),
+                block_on(WorldCommand::parse_input(command_string, &app_meta)).canonical_match,
+            );
+            assert_eq!(
+                Some(command),
+                block_on(WorldCommand::parse_input(
+                    &command_string.replacen("pc", "PC", 1),
+                    &app_meta,
+                ))
+                .canonical_match,
+            );
+        });
+
+        assert_eq!(
+            None,
+            block_on(WorldCommand::parse_input(
+                "pc Aragorn, +1 longsword",
+                &app_meta
+            ))
+            .canonical_match,
         );
 
-        assert_autocomplete(
-            &[
-                ("Potato Johnson is an elderly", "edit character"),
-                ("Potato Johnson is an elf", "edit character"),
-                ("Potato Johnson is an elvish", "edit character"),
-                ("Potato Johnson is an enby", "edit character"),
-            ][..],
-            block_on(WorldCommand::autocomplete(
-                "Potato Johnson is an e",
-                &app_meta,
-            )),
+        assert_eq!(
+            Some(WorldCommand::Pc {
+                name: "Aragorn".into(),
+                pc: PlayerCharacter::default(),
+            }),
+            block_on(WorldCommand::parse_input("pc named Aragorn", &app_meta)).canonical_match,
+        );
+        assert_eq!(
+            Some(WorldCommand::Pc {
+                name: "Aragorn".into(),
+                pc: PlayerCharacter {
+                    level: Some(5),
+                    ..Default::default()
+                },
+            }),
+            block_on(WorldCommand::parse_input(
+                "PC called Aragorn, level 5",
+                &app_meta
+            ))
+            .canonical_match,
         );
     }
 
@@ -678,6 +5329,15 @@ mod test {
                 species: Some(Species::Elf).into(),
                 ..Default::default()
             }),
+            WorldCommand::Festivals,
+            WorldCommand::Name { ethnicity: None },
+            WorldCommand::Party,
+            WorldCommand::Prep {
+                days: DEFAULT_PREP_DAYS,
+            },
+            WorldCommand::Prep { days: 1 },
+            WorldCommand::Prep { days: 30 },
+            WorldCommand::Rumor { about: None },
         ]
         .into_iter()
         .for_each(|command| {
@@ -705,11 +5365,11 @@ mod test {
 
     fn create(thing: impl Into<Thing>) -> WorldCommand {
         WorldCommand::Create {
-            thing: ParsedThing {
+            thing: Box::new(ParsedThing {
                 thing: thing.into(),
                 unknown_words: Vec::new(),
                 word_count: 1,
-            },
+            }),
         }
     }
 
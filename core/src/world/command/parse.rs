@@ -1,5 +1,10 @@
 use crate::utils::{capitalize, quoted_words, CaseInsensitiveStr};
 use crate::world::command::ParsedThing;
+use crate::world::npc::{
+    Age, Attitude, Build, Clothing, Demeanor, Ethnicity, Eyes, Gender, Hair, Mark, Occupation,
+    Species,
+};
+use crate::world::place::{PlaceType, Scarcity, WELL_SUPPLIED};
 use crate::world::{Field, Npc, Place};
 use std::str::FromStr;
 
@@ -46,6 +51,264 @@ fn split_name(input: &str) -> Option<(&str, &str)> {
     }
 }
 
+/// A word from an NPC description, classified by the field that it describes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum NpcWord {
+    Ignored,
+    Gender(Gender, Option<Age>),
+    Age(Age),
+    AgeYears(u16),
+    Species(Species, Option<Ethnicity>),
+    Ethnicity(Ethnicity),
+    Occupation(Occupation),
+    Build(Build),
+    Hair(Hair),
+    Eyes(Eyes),
+    Mark(Mark),
+    Clothing(Clothing),
+    Attitude(Attitude),
+    Demeanor(Demeanor),
+}
+
+impl NpcWord {
+    fn classify(word: &str) -> Option<Self> {
+        if word.in_ci(&["character", "npc", "person"]) {
+            Some(Self::Ignored)
+        } else if let Ok(gender) = word.parse() {
+            // Terms like "boy" and "woman" imply both age and gender.
+            Some(Self::Gender(gender, word.parse().ok()))
+        } else if let Ok(age) = word.parse() {
+            Some(Self::Age(age))
+        } else if let Ok(species) = word.parse() {
+            Some(Self::Species(species, word.parse().ok()))
+        } else if let Ok(ethnicity) = word.parse() {
+            Some(Self::Ethnicity(ethnicity))
+        } else if let Ok(occupation) = word.parse() {
+            Some(Self::Occupation(occupation))
+        } else if let Ok(build) = word.parse() {
+            Some(Self::Build(build))
+        } else if let Ok(hair) = word.parse() {
+            Some(Self::Hair(hair))
+        } else if let Ok(eyes) = word.parse() {
+            Some(Self::Eyes(eyes))
+        } else if let Ok(mark) = word.parse() {
+            Some(Self::Mark(mark))
+        } else if let Ok(clothing) = word.parse() {
+            Some(Self::Clothing(clothing))
+        } else if let Ok(attitude) = word.parse() {
+            Some(Self::Attitude(attitude))
+        } else if let Ok(demeanor) = word.parse() {
+            Some(Self::Demeanor(demeanor))
+        } else if let Some(Ok(age_years)) = word.strip_suffix_ci("-year-old").map(|s| s.parse()) {
+            Some(Self::AgeYears(age_years))
+        } else {
+            None
+        }
+    }
+
+    /// What the word was understood to mean, as reported back to the user.
+    fn meaning(&self) -> Option<&'static str> {
+        match self {
+            Self::Ignored => None,
+            Self::Gender(_, Some(_)) => Some("gender and age"),
+            Self::Gender(_, None) => Some("gender"),
+            Self::Age(_) | Self::AgeYears(_) => Some("age"),
+            Self::Species(_, Some(_)) => Some("species and ethnicity"),
+            Self::Species(_, None) => Some("species"),
+            Self::Ethnicity(_) => Some("ethnicity"),
+            Self::Occupation(_) => Some("occupation"),
+            Self::Build(_) | Self::Hair(_) | Self::Eyes(_) | Self::Mark(_) | Self::Clothing(_) => {
+                Some("appearance")
+            }
+            Self::Attitude(_) => Some("attitude"),
+            Self::Demeanor(_) => Some("trait"),
+        }
+    }
+
+    fn apply(self, npc: &mut Npc) {
+        match self {
+            Self::Ignored => {}
+            Self::Gender(gender, age) => {
+                npc.gender = Field::new(gender);
+
+                if let Some(age) = age {
+                    // Let's treat the age implied by the gender as secondary to other specifiers.
+                    // "Old boy" and "baby woman" sound a bit odd but are presumably elderly and
+                    // infant, respectively.
+                    npc.age.replace(age);
+                    npc.age.lock();
+                }
+            }
+            Self::Age(age) => npc.age = Field::new(age),
+            Self::AgeYears(age_years) => npc.age_years = Field::new(age_years),
+            Self::Species(species, ethnicity) => {
+                npc.species = Field::new(species);
+
+                if let Some(ethnicity) = ethnicity {
+                    npc.ethnicity.replace(ethnicity);
+                    npc.ethnicity.lock();
+                }
+            }
+            Self::Ethnicity(ethnicity) => npc.ethnicity = Field::new(ethnicity),
+            Self::Occupation(occupation) => npc.occupation = Field::new(occupation),
+            Self::Build(build) => npc.build = Field::new(build),
+            Self::Hair(hair) => npc.hair = Field::new(hair),
+            Self::Eyes(eyes) => npc.eyes = Field::new(eyes),
+            Self::Mark(mark) => npc.mark = Field::new(mark),
+            Self::Clothing(clothing) => npc.clothing = Field::new(clothing),
+            Self::Attitude(attitude) => npc.attitude = Field::new(attitude),
+            Self::Demeanor(demeanor) => npc.demeanor = Field::new(demeanor),
+        }
+    }
+}
+
+/// Split off a trailing "who is" clause, as in "a dwarf named Hilda who is grumpy", returning the
+/// rest of the input along with the clause and its offset.
+fn split_clause(input: &str) -> (&str, Option<(usize, &str)>) {
+    let mut words = quoted_words(input).peekable();
+
+    while let Some(word) = words.next() {
+        if word.as_str().eq_ci("who") {
+            if let Some(verb) = words
+                .peek()
+                .filter(|verb| verb.as_str().in_ci(&["is", "are"]))
+            {
+                let offset = verb.range().end;
+                return (
+                    &input[..word.range().start],
+                    Some((offset, &input[offset..])),
+                );
+            }
+        }
+    }
+
+    (input, None)
+}
+
+/// The position of `inner` within `outer`, of which it must be a slice.
+fn offset_in(outer: &str, inner: &str) -> usize {
+    inner.as_ptr() as usize - outer.as_ptr() as usize
+}
+
+/// The most things that can be created at once, since each is given a numbered alias (`~1~`
+/// through `~0~`).
+pub const QUANTITY_MAX: u8 = 10;
+
+/// How many are in "a party of halflings" and the like.
+const GROUP_SIZE: u8 = 5;
+
+/// Split a leading quantity off of a plural description, eg. "three dwarves" or "a party of
+/// halflings", returning the quantity and the description with its plurals made singular
+/// ("dwarf", "halfling").
+pub fn split_quantity(input: &str) -> Option<(u8, String)> {
+    let mut words = quoted_words(input);
+    let first = words.next()?;
+
+    let (quantity, rest) = match first.as_str().to_lowercase().as_str() {
+        "a" | "an" => {
+            let collective = words.next()?;
+
+            if collective.as_str().eq_ci("few") {
+                (3, &input[collective.range().end..])
+            } else {
+                let quantity = match collective.as_str().to_lowercase().as_str() {
+                    "pair" | "couple" => 2,
+                    "band" | "crowd" | "gang" | "group" | "handful" | "party" => GROUP_SIZE,
+                    _ => return None,
+                };
+                let of = words.next().filter(|word| word.as_str().eq_ci("of"))?;
+
+                (quantity, &input[of.range().end..])
+            }
+        }
+        "several" | "some" => (GROUP_SIZE, &input[first.range().end..]),
+        word => {
+            let quantity = match word {
+                "two" => 2,
+                "three" => 3,
+                "four" => 4,
+                "five" => 5,
+                "six" => 6,
+                "seven" => 7,
+                "eight" => 8,
+                "nine" => 9,
+                "ten" => 10,
+                number => number.parse().ok()?,
+            };
+
+            (quantity, &input[first.range().end..])
+        }
+    };
+
+    if !(2..=QUANTITY_MAX).contains(&quantity) || rest.trim().is_empty() {
+        return None;
+    }
+
+    let description = quoted_words(rest)
+        .map(|word| singular(word.as_str()).unwrap_or_else(|| word.as_str().to_string()))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    Some((quantity, description))
+}
+
+/// The singular form of a plural noun, preferring a form that is in the vocabulary. Words that
+/// are already known in their own right (eg. "boss", "dwarf") are left alone.
+fn singular(word: &str) -> Option<String> {
+    if is_known_word(word) {
+        return None;
+    }
+
+    let lower = word.to_lowercase();
+    let irregular = match lower.as_str() {
+        "children" => Some("child"),
+        "men" => Some("man"),
+        "women" => Some("woman"),
+        "people" | "folk" => Some("person"),
+        "geese" => Some("goose"),
+        _ => None,
+    };
+
+    if let Some(irregular) = irregular {
+        return Some(irregular.to_string());
+    }
+
+    let mut candidates = Vec::new();
+
+    if let Some(stem) = word.strip_suffix_ci("ves") {
+        candidates.push(format!("{}f", stem));
+        candidates.push(format!("{}fe", stem));
+    }
+    if let Some(stem) = word.strip_suffix_ci("ies") {
+        candidates.push(format!("{}y", stem));
+    }
+    if let Some(stem) = word.strip_suffix_ci("es").filter(|stem| {
+        stem.ends_with_ci("s")
+            || stem.ends_with_ci("x")
+            || stem.ends_with_ci("z")
+            || stem.ends_with_ci("ch")
+            || stem.ends_with_ci("sh")
+    }) {
+        candidates.push(stem.to_string());
+    }
+    if let Some(stem) = word
+        .strip_suffix_ci("s")
+        .filter(|stem| !stem.ends_with_ci("s"))
+    {
+        candidates.push(stem.to_string());
+    }
+
+    candidates
+        .iter()
+        .find(|candidate| is_known_word(candidate))
+        .or_else(|| candidates.first())
+        .cloned()
+}
+
+fn is_known_word(word: &str) -> bool {
+    NpcWord::classify(word).is_some() || word.parse::<PlaceType>().is_ok()
+}
+
 impl FromStr for ParsedThing<Place> {
     type Err = ();
 
@@ -69,6 +332,16 @@ impl FromStr for ParsedThing<Place> {
                 word_count -= 1;
             } else if let Ok(place_type) = word_str.parse() {
                 place.subtype = Field::new(place_type);
+            } else if let Ok(wealth) = word_str.parse() {
+                place.wealth = Field::new(wealth);
+            } else if let Ok(scarcity) = word_str.parse::<Scarcity>() {
+                let mut scarcities = place.scarcity.value().cloned().unwrap_or_default();
+                if !scarcities.contains(&scarcity) {
+                    scarcities.push(scarcity);
+                }
+                place.scarcity = Field::new(scarcities);
+            } else if word_str.eq_ci(WELL_SUPPLIED) {
+                place.scarcity = Field::new(Vec::new());
             } else {
                 unknown_words.push(word.range().to_owned());
             }
@@ -90,72 +363,88 @@ impl FromStr for ParsedThing<Npc> {
     type Err = ();
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let mut npc = Npc::default();
-        let mut unknown_words = Vec::new();
-        let mut word_count = 0;
+        parse_npc(input).0
+    }
+}
 
-        let description = if let Some((name, description)) = split_name(input) {
-            npc.name = Field::new(capitalize(name));
-            description
-        } else {
-            input
-        };
+/// What each understood word of an NPC description was taken to mean.
+type Meanings = Vec<(String, &'static str)>;
 
-        for word in quoted_words(description) {
-            let word_str = &word.as_str();
-            word_count += 1;
+/// The words of an NPC description that were understood, in the order that they appear, each
+/// with what it was taken to mean, eg. `("dwarvish", "ethnicity")`.
+pub fn npc_meanings(input: &str) -> Meanings {
+    parse_npc(input).1
+}
 
-            if word_str.in_ci(&["a", "an"]) {
-                word_count -= 1;
-            } else if word_str.in_ci(&["character", "npc", "person"]) {
-                // ignore
-            } else if let Ok(gender) = word_str.parse() {
-                npc.gender = Field::new(gender);
+fn parse_npc(input: &str) -> (Result<ParsedThing<Npc>, ()>, Meanings) {
+    let mut npc = Npc::default();
+    let mut unknown_words = Vec::new();
+    let mut meanings = Vec::new();
+    let mut word_count = 0;
 
-                if let Ok(age) = word_str.parse() {
-                    // Terms like "boy" and "woman" imply both age and gender, although let's treat
-                    // them as secondary to other specifiers. "Old boy" and "baby woman" sound a
-                    // bit odd but are presumably elderly and infant, respectively.
-                    npc.age.replace(age);
-                    npc.age.lock();
-                }
-            } else if let Ok(age) = word_str.parse() {
-                npc.age = Field::new(age);
-            } else if let Ok(species) = word_str.parse() {
-                npc.species = Field::new(species);
+    let (main, clause) = split_clause(input);
 
-                if let Ok(ethnicity) = word_str.parse() {
-                    npc.ethnicity.replace(ethnicity);
-                    npc.ethnicity.lock();
-                }
-            } else if let Ok(ethnicity) = word_str.parse() {
-                npc.ethnicity = Field::new(ethnicity);
-            } else if let Some(Ok(age_years)) =
-                word_str.strip_suffix_ci("-year-old").map(|s| s.parse())
-            {
-                npc.age_years = Field::new(age_years);
-            } else {
-                unknown_words.push(word.range().to_owned());
+    let description = if let Some((name, description)) = split_name(main) {
+        npc.name = Field::new(capitalize(name));
+        meanings.push((offset_in(input, name), name.to_string(), "name"));
+        description
+    } else {
+        main
+    };
+
+    // Unknown words in the clause are offset to line up with those in the description.
+    let words = quoted_words(description)
+        .map(|word| (offset_in(input, description), 0, word))
+        .chain(
+            clause
+                .into_iter()
+                .flat_map(|(offset, clause)| {
+                    quoted_words(clause).map(move |word| (offset, offset, word))
+                })
+                .filter(|(_, _, word)| !word.as_str().eq_ci("and")),
+        );
+
+    for (position, offset, word) in words {
+        let word_str = &word.as_str();
+        word_count += 1;
+
+        if word_str.in_ci(&["a", "an"]) {
+            word_count -= 1;
+        } else if let Some(npc_word) = NpcWord::classify(word_str) {
+            if let Some(meaning) = npc_word.meaning() {
+                meanings.push((position + word.range().start, word_str.to_string(), meaning));
             }
+
+            npc_word.apply(&mut npc);
+        } else {
+            let range = word.range();
+            unknown_words.push(range.start + offset..range.end + offset);
         }
+    }
 
-        if unknown_words.is_empty() || unknown_words.len() <= word_count / 2 {
+    meanings.sort_by_key(|(position, _, _)| *position);
+    let meanings = meanings
+        .into_iter()
+        .map(|(_, word, meaning)| (word, meaning))
+        .collect();
+
+    if unknown_words.is_empty() || unknown_words.len() <= word_count / 2 {
+        (
             Ok(ParsedThing {
                 thing: npc,
                 unknown_words,
                 word_count,
-            })
-        } else {
-            Err(())
-        }
+            }),
+            meanings,
+        )
+    } else {
+        (Err(()), meanings)
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::world::npc::{Age, Gender, Species};
-    use crate::world::place::PlaceType;
 
     #[test]
     fn place_from_str_test() {
@@ -280,8 +569,155 @@ mod test {
                 .unwrap(),
         );
 
+        {
+            let npc: ParsedThing<Npc> = "elderly dwarf blacksmith".parse().unwrap();
+            assert_eq!(Field::Locked(Some(Age::Elderly)), npc.thing.age);
+            assert_eq!(Field::Locked(Some(Species::Dwarf)), npc.thing.species);
+            assert_eq!(
+                Field::Locked(Some(Occupation::Blacksmith)),
+                npc.thing.occupation,
+            );
+            assert_eq!(0, npc.unknown_words.len());
+            assert_eq!(3, npc.word_count);
+        }
+
+        {
+            let npc: ParsedThing<Npc> = "stocky red-haired blue-eyed scarred tattered dwarf"
+                .parse()
+                .unwrap();
+            assert_eq!(Field::Locked(Some(Build::Stocky)), npc.thing.build);
+            assert_eq!(Field::Locked(Some(Hair::RedHaired)), npc.thing.hair);
+            assert_eq!(Field::Locked(Some(Eyes::Blue)), npc.thing.eyes);
+            assert_eq!(Field::Locked(Some(Mark::Scarred)), npc.thing.mark);
+            assert_eq!(Field::Locked(Some(Clothing::Ragged)), npc.thing.clothing);
+            assert_eq!(0, npc.unknown_words.len());
+        }
+
+        {
+            let npc: ParsedThing<Npc> = "unfriendly dwarf".parse().unwrap();
+            assert_eq!(
+                Field::Locked(Some(Attitude::Unfriendly)),
+                npc.thing.attitude
+            );
+            assert_eq!(0, npc.unknown_words.len());
+        }
+
         {
             assert!("potato".parse::<ParsedThing<Npc>>().is_err());
         }
     }
+
+    #[test]
+    fn npc_from_str_clause_test() {
+        {
+            let npc: ParsedThing<Npc> =
+                "middle-aged dwarvish woman blacksmith named Hilda who is grumpy"
+                    .parse()
+                    .unwrap();
+            assert_eq!(Field::Locked(Some("Hilda".to_string())), npc.thing.name);
+            assert_eq!(Field::Locked(Some(Age::MiddleAged)), npc.thing.age);
+            assert_eq!(
+                Field::Locked(Some(Ethnicity::Dwarvish)),
+                npc.thing.ethnicity,
+            );
+            assert_eq!(Field::Locked(Some(Gender::Feminine)), npc.thing.gender);
+            assert_eq!(
+                Field::Locked(Some(Occupation::Blacksmith)),
+                npc.thing.occupation,
+            );
+            assert_eq!(Field::Locked(Some(Demeanor::Grumpy)), npc.thing.demeanor);
+            assert_eq!(0, npc.unknown_words.len());
+            assert_eq!(5, npc.word_count);
+        }
+
+        {
+            let npc: ParsedThing<Npc> = "Hilda, a dwarf who is grumpy and potato".parse().unwrap();
+            assert_eq!(Field::Locked(Some("Hilda".to_string())), npc.thing.name);
+            assert_eq!(Field::Locked(Some(Demeanor::Grumpy)), npc.thing.demeanor);
+            assert_eq!(vec![33..39], npc.unknown_words);
+        }
+
+        {
+            let npc: ParsedThing<Npc> = "a boy named Doctor Who".parse().unwrap();
+            assert_eq!(
+                Field::Locked(Some("Doctor Who".to_string())),
+                npc.thing.name
+            );
+        }
+    }
+
+    #[test]
+    fn npc_meanings_test() {
+        assert_eq!(
+            vec![
+                ("middle-aged".to_string(), "age"),
+                ("dwarvish".to_string(), "ethnicity"),
+                ("woman".to_string(), "gender and age"),
+                ("blacksmith".to_string(), "occupation"),
+                ("Hilda".to_string(), "name"),
+                ("grumpy".to_string(), "trait"),
+            ],
+            npc_meanings("middle-aged dwarvish woman blacksmith named Hilda who is grumpy"),
+        );
+
+        assert_eq!(
+            vec![
+                ("Potato Johnson".to_string(), "name"),
+                ("elf".to_string(), "species"),
+                ("stocky".to_string(), "appearance"),
+            ],
+            npc_meanings("Potato Johnson, an elf npc who is stocky"),
+        );
+
+        assert!(npc_meanings("potato").is_empty());
+    }
+
+    #[test]
+    fn split_quantity_test() {
+        assert_eq!(
+            Some((3, "dwarf".to_string())),
+            split_quantity("three dwarves")
+        );
+        assert_eq!(Some((2, "Elf".to_string())), split_quantity("2 Elves"));
+        assert_eq!(
+            Some((4, "elderly halfling".to_string())),
+            split_quantity("Four elderly halflings"),
+        );
+        assert_eq!(
+            Some((GROUP_SIZE, "goblin".to_string())),
+            split_quantity("a party of goblins"),
+        );
+        assert_eq!(
+            Some((2, "inn".to_string())),
+            split_quantity("a pair of inns")
+        );
+        assert_eq!(
+            Some((3, "woman".to_string())),
+            split_quantity("a few women")
+        );
+        assert_eq!(
+            Some((5, "thief".to_string())),
+            split_quantity("five thieves")
+        );
+        assert_eq!(
+            Some((6, "church".to_string())),
+            split_quantity("six churches")
+        );
+        assert_eq!(
+            Some((7, "blacksmith".to_string())),
+            split_quantity("seven blacksmiths"),
+        );
+
+        assert_eq!(
+            Some((3, "dwarf".to_string())),
+            split_quantity("three dwarf")
+        );
+
+        assert_eq!(None, split_quantity("dwarves"));
+        assert_eq!(None, split_quantity("one dwarf"));
+        assert_eq!(None, split_quantity("eleven dwarves"));
+        assert_eq!(None, split_quantity("a dwarf"));
+        assert_eq!(None, split_quantity("a party"));
+        assert_eq!(None, split_quantity("three"));
+    }
 }
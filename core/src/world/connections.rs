@@ -0,0 +1,235 @@
+use super::{LinkView, Thing};
+use crate::storage::Repository;
+use crate::utils::CaseInsensitiveStr;
+use std::fmt;
+
+/// The number of hops followed by `connections` unless otherwise specified.
+pub const DEFAULT_HOPS: usize = 3;
+
+/// The most hops that `connections` will follow, to keep the output readable.
+pub const MAX_HOPS: usize = 10;
+
+/// A directed edge in the relationship graph between two things.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Link {
+    /// The thing is located in another place.
+    LocatedIn,
+
+    /// The place is the location of another thing.
+    Contains,
+}
+
+/// A route through the relationship graph, starting from (but not including) the thing whose
+/// connections were requested.
+pub type Path = Vec<(Link, Thing)>;
+
+/// A path rendered as a chain of links, eg. "{Gottfried} → located in → {The Silver Eel}".
+pub struct PathView<'a> {
+    origin: &'a Thing,
+    path: &'a [(Link, Thing)],
+}
+
+/// Walk the relationship graph outward from a thing, up to a given number of hops, returning every
+/// maximal path. Each thing appears at most once, at the shortest distance from the origin.
+pub async fn find_connections(
+    origin: &Thing,
+    max_hops: usize,
+    repository: &Repository,
+) -> Vec<Path> {
+    let mut things = repository.journal().await.unwrap_or_default();
+    things.extend(
+        repository
+            .recent()
+            .filter(|thing| thing.uuid().is_none())
+            .cloned(),
+    );
+
+    let mut visited = vec![origin.name().to_string()];
+    let mut paths = Vec::new();
+    let mut queue: Vec<Path> = vec![Vec::new()];
+
+    while !queue.is_empty() {
+        let mut next_queue = Vec::new();
+
+        for path in queue {
+            let node = path.last().map_or(origin, |(_, thing)| thing);
+
+            let mut neighbors = if path.len() < max_hops {
+                neighbors(node, &things, repository).await
+            } else {
+                Vec::new()
+            };
+
+            neighbors.retain(|(_, thing)| {
+                let name = thing.name().to_string();
+
+                if visited.iter().any(|visited| visited.eq_ci(&name)) {
+                    false
+                } else {
+                    visited.push(name);
+                    true
+                }
+            });
+
+            if neighbors.is_empty() {
+                if !path.is_empty() {
+                    paths.push(path);
+                }
+            } else {
+                for neighbor in neighbors {
+                    let mut next_path = path.clone();
+                    next_path.push(neighbor);
+                    next_queue.push(next_path);
+                }
+            }
+        }
+
+        queue = next_queue;
+    }
+
+    paths
+}
+
+async fn neighbors(node: &Thing, things: &[Thing], repository: &Repository) -> Vec<(Link, Thing)> {
+    let mut neighbors = Vec::new();
+
+    if let Some(location_uuid) = node.location_uuid() {
+        if let Ok(location) = repository.get_by_uuid(location_uuid).await {
+            neighbors.push((Link::LocatedIn, location));
+        }
+    }
+
+    if let (Thing::Place(_), Some(uuid)) = (node, node.uuid()) {
+        let mut contents: Vec<&Thing> = things
+            .iter()
+            .filter(|thing| thing.location_uuid() == Some(uuid))
+            .collect();
+
+        contents.sort_by_cached_key(|thing| thing.name().to_string().to_lowercase());
+
+        neighbors.extend(
+            contents
+                .into_iter()
+                .map(|thing| (Link::Contains, thing.clone())),
+        );
+    }
+
+    neighbors
+}
+
+impl<'a> PathView<'a> {
+    pub fn new(origin: &'a Thing, path: &'a [(Link, Thing)]) -> Self {
+        Self { origin, path }
+    }
+}
+
+impl fmt::Display for Link {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::LocatedIn => write!(f, "located in"),
+            Self::Contains => write!(f, "contains"),
+        }
+    }
+}
+
+impl<'a> fmt::Display for PathView<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", LinkView::new(&self.origin.name().to_string()))?;
+
+        for (link, thing) in self.path {
+            write!(
+                f,
+                " → {} → {}",
+                link,
+                LinkView::new(&thing.name().to_string()),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::storage::{Change, MemoryDataStore};
+    use crate::world::{Npc, Place, PlaceUuid};
+    use tokio_test::block_on;
+    use uuid::Uuid;
+
+    #[test]
+    fn find_connections_test() {
+        let repository = repository();
+        let gottfried = block_on(repository.get_by_name("Gottfried")).unwrap();
+
+        assert_eq!(
+            vec![
+                "{Gottfried} → located in → {The Silver Eel} → located in → {Waterdeep}",
+                "{Gottfried} → located in → {The Silver Eel} → contains → {Marta}",
+            ],
+            block_on(find_connections(&gottfried, DEFAULT_HOPS, &repository))
+                .iter()
+                .map(|path| PathView::new(&gottfried, path).to_string())
+                .collect::<Vec<_>>(),
+        );
+
+        assert_eq!(
+            vec!["{Gottfried} → located in → {The Silver Eel}"],
+            block_on(find_connections(&gottfried, 1, &repository))
+                .iter()
+                .map(|path| PathView::new(&gottfried, path).to_string())
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn find_connections_test_unconnected() {
+        let repository = repository();
+        let neverwinter = block_on(repository.get_by_name("Neverwinter")).unwrap();
+
+        assert!(block_on(find_connections(&neverwinter, DEFAULT_HOPS, &repository)).is_empty());
+    }
+
+    fn repository() -> Repository {
+        let mut repository = Repository::new(MemoryDataStore::default());
+
+        [
+            Place {
+                uuid: Some(Uuid::from_u128(1).into()),
+                name: "Waterdeep".into(),
+                ..Default::default()
+            }
+            .into(),
+            Place {
+                uuid: Some(Uuid::from_u128(2).into()),
+                location_uuid: PlaceUuid::from(Uuid::from_u128(1)).into(),
+                name: "The Silver Eel".into(),
+                ..Default::default()
+            }
+            .into(),
+            Place {
+                name: "Neverwinter".into(),
+                ..Default::default()
+            }
+            .into(),
+            Npc {
+                name: "Marta".into(),
+                location_uuid: PlaceUuid::from(Uuid::from_u128(2)).into(),
+                ..Default::default()
+            }
+            .into(),
+            Npc {
+                name: "Gottfried".into(),
+                location_uuid: PlaceUuid::from(Uuid::from_u128(2)).into(),
+                ..Default::default()
+            }
+            .into(),
+        ]
+        .into_iter()
+        .for_each(|thing: Thing| {
+            block_on(repository.modify(Change::CreateAndSave { thing })).unwrap();
+        });
+
+        repository
+    }
+}
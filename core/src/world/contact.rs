@@ -0,0 +1,319 @@
+//! Contacts from a player character's past: a mentor, rival, fence, or relative, generated with a
+//! reason for the party to seek them out and a secret for the GM to reveal in time.
+
+use super::grammar::render;
+use super::npc::{Age, ContactKind, Occupation, Relationship, Relationships};
+use super::party::place_with_party;
+use super::{Npc, Thing};
+use crate::app::{AppMeta, CommandError, TraceEvent};
+use crate::config::Config;
+use crate::homebrew::Homebrew;
+use crate::locale::Message;
+use crate::storage::{Change, RepositoryError};
+use crate::table::Tables;
+use crate::Uuid;
+use futures::join;
+use rand::prelude::*;
+
+fn hooks(kind: ContactKind) -> &'static [&'static str] {
+    match kind {
+        ContactKind::Fence => &[
+            "Has a buyer lined up for something {pc} hasn't stolen yet.",
+            "Needs a shipment moved past the city watch, and trusts {pc} to do it.",
+            "Is being squeezed by a rival guild and wants {pc} to lean on them.",
+        ],
+        ContactKind::Mentor => &[
+            "Has sent word to {pc}, asking {them} to come at once.",
+            "Wants {pc} to finish a task {they} abandoned long ago.",
+            "Has taken on a new student, and wants {pc} to test them.",
+        ],
+        ContactKind::Relative => &[
+            "Is getting married, and expects {pc} to be there.",
+            "Needs help keeping the family's land out of a creditor's hands.",
+            "Has come looking for {pc} with news of home.",
+        ],
+        ContactKind::Rival => &[
+            "Has beaten {pc} to a prize, and wants everyone to know it.",
+            "Is after the same treasure as the party, and is a step ahead.",
+            "Needs {pc}'s help, and would rather die than ask for it.",
+        ],
+    }
+}
+
+fn secrets(kind: ContactKind) -> &'static [&'static str] {
+    match kind {
+        ContactKind::Fence => &[
+            "Sells information about {their} clients to the city watch.",
+            "Owes a dangerous sum to a crime lord, and is running out of time.",
+            "Has kept back the best of what {pc} brought {them}, and sold it for a fortune.",
+        ],
+        ContactKind::Mentor => &[
+            "Is dying, and hasn't told anyone.",
+            "Once served the villain {pc} is now hunting.",
+            "Taught {pc} only half of what {they} {knows|know}, on purpose.",
+        ],
+        ContactKind::Relative => &[
+            "Isn't related to {pc} by blood, and knows it.",
+            "Is the one who sold out the family years ago.",
+            "Has been secretly sending {pc}'s letters to someone else.",
+        ],
+        ContactKind::Rival => &[
+            "Secretly admires {pc}, and envies {their} friends.",
+            "Is being blackmailed into opposing {pc}.",
+            "Once saved {pc}'s life, and never told {them}.",
+        ],
+    }
+}
+
+/// A hook and a secret for a contact of `pc_name`, with pronouns referring to the contact.
+pub fn generate_hook_secret(
+    rng: &mut impl Rng,
+    kind: ContactKind,
+    contact: &Npc,
+    pc_name: &str,
+) -> (String, String) {
+    let vars = [("pc", pc_name)];
+
+    (
+        render(hooks(kind).choose(rng).unwrap(), &contact.gender(), &vars),
+        render(secrets(kind).choose(rng).unwrap(), &contact.gender(), &vars),
+    )
+}
+
+/// The starting point for a contact of `pc` before generation. Relatives share the player
+/// character's ancestry, mentors are older, and fences tend to keep a respectable trade.
+fn seed(rng: &mut impl Rng, kind: ContactKind, pc: &Npc) -> Npc {
+    let mut npc = Npc::default();
+
+    match kind {
+        ContactKind::Fence => {
+            npc.occupation = [Occupation::Merchant, Occupation::Thief]
+                .choose(rng)
+                .copied()
+                .unwrap()
+                .into();
+        }
+        ContactKind::Mentor => {
+            npc.age = [Age::MiddleAged, Age::Elderly]
+                .choose(rng)
+                .copied()
+                .unwrap()
+                .into();
+        }
+        ContactKind::Relative => {
+            if let Some(species) = pc.species.value() {
+                npc.species = (*species).into();
+            }
+            if let Some(ethnicity) = pc.ethnicity.value() {
+                npc.ethnicity = (*ethnicity).into();
+            }
+            if let Some(homebrew) = pc.homebrew.value() {
+                npc.homebrew = homebrew.clone().into();
+            }
+        }
+        ContactKind::Rival => {}
+    }
+
+    npc
+}
+
+/// Generate a contact for the player character `name` and save them to the journal, recording
+/// the relationship on both characters. If `kind` isn't given, one is picked at random.
+pub async fn create_contact(
+    name: &str,
+    kind: Option<ContactKind>,
+    app_meta: &mut AppMeta,
+) -> Result<String, CommandError> {
+    let config = Config::load(app_meta).await;
+
+    let pc = match app_meta.repository.get_by_name(name).await {
+        Ok(Thing::Npc(npc)) if npc.pc.is_some() => npc,
+        Ok(Thing::Npc(npc)) => {
+            return Err(CommandError::unknown_entity(
+                name,
+                format!(
+                    "{} isn't a player character. Use `pc {}` to make them one.",
+                    npc.name, npc.name,
+                ),
+            ))
+        }
+        _ => {
+            return Err(CommandError::unknown_entity(
+                name,
+                Message::NoCharacterNamed { name }.localize(config.language),
+            ))
+        }
+    };
+
+    let pc_name = pc.name.to_string();
+    let pc_uuid = pc.uuid.clone().ok_or_else(|| {
+        format!(
+            "{} hasn't been saved to your `journal` yet. Use `save {}` first.",
+            pc_name, pc_name,
+        )
+    })?;
+
+    let kind = kind.unwrap_or_else(|| {
+        *[
+            ContactKind::Fence,
+            ContactKind::Mentor,
+            ContactKind::Relative,
+            ContactKind::Rival,
+        ]
+        .choose(&mut app_meta.rng)
+        .unwrap()
+    });
+
+    let mut diff: Thing = seed(&mut app_meta.rng, kind, &pc).into();
+    place_with_party(&mut diff, app_meta).await;
+    let (tables, homebrew) = join!(Tables::load(app_meta), Homebrew::load(app_meta));
+    let words = tables.words();
+
+    for _ in 0..10 {
+        let mut thing = diff.clone();
+        words.scope(|| thing.regenerate(&mut app_meta.rng, &app_meta.demographics));
+        tables.apply(&mut app_meta.rng, &mut thing);
+        homebrew.apply(&mut app_meta.rng, &app_meta.demographics, &mut thing);
+        app_meta.trace(TraceEvent::Generated {
+            thing_type: thing.as_str().to_string(),
+            name: thing.name().to_string(),
+        });
+
+        let mut npc = thing.into_npc().unwrap();
+        let (hook, secret) = generate_hook_secret(&mut app_meta.rng, kind, &npc, &pc_name);
+        npc.relationships = Relationships::from(vec![Relationship::ContactOf {
+            uuid: pc_uuid.clone(),
+            kind,
+            hook,
+            secret,
+        }])
+        .into();
+
+        // The contact's uuid is chosen up front so that the character can refer to it, and the
+        // two are saved together so that neither is left without the other.
+        let contact_uuid = Uuid::new_v4();
+        npc.uuid = Some(contact_uuid.into());
+
+        let mut relationships = pc.relationships.value().cloned().unwrap_or_default();
+        relationships.push(Relationship::Contact {
+            uuid: contact_uuid.into(),
+            kind,
+        });
+
+        let result = app_meta
+            .repository
+            .transaction(vec![
+                Change::CreateAndSave { thing: npc.into() },
+                Change::Edit {
+                    name: pc_name.clone(),
+                    uuid: pc.uuid.as_ref().map(|uuid| uuid.as_ref().to_owned()),
+                    diff: Npc {
+                        relationships: relationships.into(),
+                        ..Default::default()
+                    }
+                    .into(),
+                },
+            ])
+            .await;
+
+        match result {
+            Ok(()) => {}
+            Err((_, RepositoryError::NameAlreadyExists)) => continue,
+            Err(_) => {
+                return Err(CommandError::storage(
+                    Message::CouldNotEdit { name: &pc_name }.localize(config.language),
+                ))
+            }
+        }
+
+        let contact = match app_meta.repository.get_by_uuid(&contact_uuid).await {
+            Ok(Thing::Npc(contact)) => contact,
+            _ => {
+                return Err(CommandError::storage(
+                    Message::UnknownError.localize(config.language),
+                ))
+            }
+        };
+
+        let gender = contact.gender();
+        let contact_name = contact.name.to_string();
+        let thing: Thing = contact.into();
+        let relations = app_meta
+            .repository
+            .load_relations(&thing)
+            .await
+            .unwrap_or_default();
+
+        return Ok(format!(
+            "{}\n\n{}",
+            config.render(thing.display_details(relations)),
+            render(
+                "_{name} has been automatically added to your `journal` as {pc}'s {kind}. Use `undo` to remove {them}._",
+                &gender,
+                &[
+                    ("name", &contact_name),
+                    ("pc", &pc_name),
+                    ("kind", kind.as_str()),
+                ],
+            ),
+        ));
+    }
+
+    Err(format!("Couldn't create a unique name for {}'s {}.", pc_name, kind).into())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::world::npc::{Ethnicity, Gender, Species};
+
+    #[test]
+    fn generate_hook_secret_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let contact = Npc {
+            gender: Gender::Feminine.into(),
+            ..Default::default()
+        };
+
+        for kind in [
+            ContactKind::Fence,
+            ContactKind::Mentor,
+            ContactKind::Relative,
+            ContactKind::Rival,
+        ] {
+            for _ in 0..20 {
+                let (hook, secret) = generate_hook_secret(&mut rng, kind, &contact, "Aragorn");
+
+                assert!(!hook.contains('{'), "{}", hook);
+                assert!(!secret.contains('{'), "{}", secret);
+            }
+        }
+    }
+
+    #[test]
+    fn seed_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let pc = Npc {
+            species: Species::Dwarf.into(),
+            ethnicity: Ethnicity::Dwarvish.into(),
+            ..Default::default()
+        };
+
+        let relative = seed(&mut rng, ContactKind::Relative, &pc);
+        assert_eq!(Some(&Species::Dwarf), relative.species.value());
+        assert_eq!(Some(&Ethnicity::Dwarvish), relative.ethnicity.value());
+        assert!(relative.species.is_locked());
+
+        let mentor = seed(&mut rng, ContactKind::Mentor, &pc);
+        assert!(matches!(
+            mentor.age.value(),
+            Some(Age::MiddleAged | Age::Elderly),
+        ));
+        assert!(mentor.species.is_none());
+
+        let fence = seed(&mut rng, ContactKind::Fence, &pc);
+        assert!(fence.occupation.is_some());
+
+        assert_eq!(Npc::default(), seed(&mut rng, ContactKind::Rival, &pc));
+    }
+}
@@ -3,7 +3,6 @@ use rand::distributions::WeightedIndex;
 use rand::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::iter;
 
 type GroupMap = HashMap<(Species, Ethnicity), u64>;
 type GroupMapSerialized = Vec<(Species, Ethnicity, u64)>;
@@ -25,11 +24,13 @@ impl Demographics {
     }
 
     pub fn shift_species(&self, species: &Species, amount: f64) -> Self {
-        self.shift_by(
-            |s, _| s == species,
-            amount,
-            (*species, species.default_ethnicity()),
-        )
+        let defaults: Vec<_> = species
+            .ethnicity_weights()
+            .iter()
+            .map(|(ethnicity, weight)| ((*species, *ethnicity), *weight))
+            .collect();
+
+        self.shift_by(|s, _| s == species, amount, &defaults)
     }
 
     pub fn only_species(&self, species: &Species) -> Self {
@@ -40,7 +41,7 @@ impl Demographics {
         self.shift_by(
             |_, e| e == ethnicity,
             amount,
-            (ethnicity.default_species(), *ethnicity),
+            &[((ethnicity.default_species(), *ethnicity), 1)],
         )
     }
 
@@ -57,7 +58,7 @@ impl Demographics {
         self.shift_by(
             |s, e| s == species && e == ethnicity,
             amount,
-            (*species, *ethnicity),
+            &[((*species, *ethnicity), 1)],
         )
     }
 
@@ -76,11 +77,69 @@ impl Demographics {
         }
     }
 
+    /// The ethnicity with the largest population, counted across all species. Used to give
+    /// places a regional flavour, such as dwarvish town names in a dwarvish region.
+    pub fn dominant_ethnicity(&self) -> Ethnicity {
+        let mut populations: HashMap<Ethnicity, u64> = HashMap::new();
+        for ((_, ethnicity), population) in self.groups() {
+            *populations.entry(*ethnicity).or_default() += population;
+        }
+
+        populations
+            .into_iter()
+            .max_by_key(|(ethnicity, population)| (*population, ethnicity.as_str()))
+            .map(|(ethnicity, _)| ethnicity)
+            .unwrap_or(Ethnicity::Human)
+    }
+
+    /// The proportion of the population belonging to the ethnicity, counted across all species.
+    pub fn ethnicity_share(&self, ethnicity: &Ethnicity) -> f64 {
+        let total: u64 = self.groups().values().sum();
+
+        if total == 0 {
+            0.
+        } else {
+            let population: u64 = self
+                .groups()
+                .iter()
+                .filter(|((_, e), _)| e == ethnicity)
+                .map(|(_, population)| population)
+                .sum();
+
+            population as f64 / total as f64
+        }
+    }
+
+    /// The proportion of the population belonging to each species, largest first.
+    pub fn species_shares(&self) -> Vec<(Species, f64)> {
+        let mut populations: HashMap<Species, u64> = HashMap::new();
+        for ((species, _), population) in self.groups() {
+            *populations.entry(*species).or_default() += population;
+        }
+
+        let total: u64 = populations.values().sum();
+        let mut shares: Vec<(Species, f64)> = populations
+            .into_iter()
+            .filter(|(_, population)| *population > 0)
+            .map(|(species, population)| (species, population as f64 / total as f64))
+            .collect();
+
+        shares.sort_by(|(a_species, a), (b_species, b)| {
+            b.total_cmp(a)
+                .then_with(|| a_species.as_str().cmp(b_species.as_str()))
+        });
+
+        shares
+    }
+
+    /// Shift the given proportion of the population into the groups matching `f`. If no groups
+    /// match, the population is instead shifted into the `defaults`, divided according to their
+    /// weights.
     fn shift_by<F: Fn(&Species, &Ethnicity) -> bool>(
         &self,
         f: F,
         amount: f64,
-        default: (Species, Ethnicity),
+        defaults: &[((Species, Ethnicity), u64)],
     ) -> Self {
         if !(0. ..=1.).contains(&amount) {
             panic!("Invalid input: {}", amount);
@@ -112,13 +171,18 @@ impl Demographics {
                 .filter(|(_, v)| *v > 0)
                 .collect()
         } else {
+            let total_weight: u64 = defaults.iter().map(|(_, weight)| weight).sum();
+
             self.groups()
                 .iter()
                 .map(|(&k, &v)| (k, (v as f64 * (1. - amount)).round() as u64))
-                .chain(iter::once((
-                    default,
-                    (population as f64 * amount).round() as u64,
-                )))
+                .chain(defaults.iter().map(|(group, weight)| {
+                    (
+                        *group,
+                        (population as f64 * amount * *weight as f64 / total_weight as f64).round()
+                            as u64,
+                    )
+                }))
                 .filter(|(_, v)| *v > 0)
                 .collect()
         };
@@ -134,7 +198,16 @@ impl Demographics {
 impl Default for Demographics {
     fn default() -> Self {
         let mut groups = HashMap::new();
-        groups.insert((Species::Human, Ethnicity::Human), 1_020_000);
+
+        let human_weights = Species::Human.ethnicity_weights();
+        let human_total: u64 = human_weights.iter().map(|(_, weight)| weight).sum();
+        for (ethnicity, weight) in human_weights {
+            groups.insert(
+                (Species::Human, *ethnicity),
+                1_020_000 * weight / human_total,
+            );
+        }
+
         groups.insert((Species::HalfElf, Ethnicity::Elvish), 320_000);
         groups.insert((Species::Elf, Ethnicity::Elvish), 220_000);
         groups.insert((Species::Gnome, Ethnicity::Gnomish), 220_000);
@@ -174,6 +247,17 @@ impl From<GroupMapWrapper> for GroupMapSerialized {
 mod test {
     use super::*;
 
+    #[test]
+    fn species_shares_test() {
+        assert_eq!(
+            vec![(Species::Gnome, 0.5), (Species::Human, 0.5)],
+            demographics().species_shares(),
+        );
+        assert!(Demographics::new(HashMap::new())
+            .species_shares()
+            .is_empty());
+    }
+
     #[test]
     fn shift_species_test_existing() {
         let demographics = demographics().shift_species(&Species::Human, 0.3);
@@ -277,6 +361,33 @@ mod test {
         );
     }
 
+    #[test]
+    fn shift_species_test_new_weighted() {
+        let mut groups = HashMap::with_capacity(1);
+        groups.insert((Species::Gnome, Ethnicity::Gnomish), 100);
+        let demographics = Demographics::new(groups).shift_species(&Species::HalfElf, 0.6);
+
+        assert_eq!(3, demographics.groups().len());
+        assert_eq!(
+            Some(&40),
+            demographics
+                .groups()
+                .get(&(Species::Gnome, Ethnicity::Gnomish))
+        );
+        assert_eq!(
+            Some(&40),
+            demographics
+                .groups()
+                .get(&(Species::HalfElf, Ethnicity::Human))
+        );
+        assert_eq!(
+            Some(&20),
+            demographics
+                .groups()
+                .get(&(Species::HalfElf, Ethnicity::Elvish))
+        );
+    }
+
     #[test]
     fn shift_ethnicity_test_new() {
         let mut groups = HashMap::with_capacity(1);
@@ -395,6 +506,36 @@ mod test {
         assert_eq!(Some(&5), counts.get(&(Species::Gnome, Ethnicity::Gnomish)));
     }
 
+    #[test]
+    fn dominant_ethnicity_test() {
+        assert_eq!(Ethnicity::Gnomish, demographics().dominant_ethnicity());
+        assert_eq!(
+            Ethnicity::Human,
+            Demographics::default().dominant_ethnicity()
+        );
+        assert_eq!(
+            Ethnicity::Dwarvish,
+            Demographics::default()
+                .only_ethnicity(&Ethnicity::Dwarvish)
+                .dominant_ethnicity(),
+        );
+        assert_eq!(
+            Ethnicity::Human,
+            Demographics::new(HashMap::new()).dominant_ethnicity(),
+        );
+    }
+
+    #[test]
+    fn ethnicity_share_test() {
+        assert_eq!(0.7, demographics().ethnicity_share(&Ethnicity::Gnomish));
+        assert_eq!(0.3, demographics().ethnicity_share(&Ethnicity::Human));
+        assert_eq!(0., demographics().ethnicity_share(&Ethnicity::Elvish));
+        assert_eq!(
+            0.,
+            Demographics::new(HashMap::new()).ethnicity_share(&Ethnicity::Human),
+        );
+    }
+
     #[test]
     fn demographics_serialize_deserialize_test() {
         let demographics = demographics();
@@ -0,0 +1,518 @@
+//! Downtime activities from the SRD, spent by a player character between adventures. Each one
+//! takes a number of days of game time, and most cost or earn coins from the party's treasury.
+
+use super::grammar::render;
+use super::inventory::{Coins, Item};
+use super::{festival_notices, parse_equipment, status_expiries, Npc, Thing};
+use crate::app::{AppMeta, CommandError};
+use crate::config::Config;
+use crate::locale::Message;
+use crate::reference::Item as Equipment;
+use crate::storage::{Change, KeyValue};
+use crate::time::Interval;
+use crate::utils::CaseInsensitiveStr;
+use std::fmt;
+use std::str::FromStr;
+
+/// The market value of the items a character can craft in a day, in copper pieces. Raw materials
+/// cost half as much.
+const CRAFTING_CP_PER_DAY: u64 = 500;
+
+/// The cost of training per day, in copper pieces.
+const TRAINING_CP_PER_DAY: u64 = 100;
+
+/// The number of days of training needed to learn a new language or tool proficiency.
+const TRAINING_DAYS: u16 = 250;
+
+/// The wages earned per day by practicing a profession, in copper pieces: enough to maintain a
+/// modest lifestyle.
+const WORKING_CP_PER_DAY: u64 = 100;
+
+/// The longest stretch of downtime that can be taken at once, in days.
+const DAYS_MAX: u16 = 365;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Activity {
+    /// Crafting for a number of days without a particular item in mind.
+    Crafting { days: u16 },
+
+    /// Crafting equipment from the SRD, taking as many days as its price requires.
+    CraftingItem { item: Equipment, quantity: u32 },
+
+    /// Training toward a new language or tool proficiency.
+    Training { days: u16 },
+
+    /// Practicing a profession, or "working".
+    Working { days: u16 },
+}
+
+impl Activity {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Crafting { .. } | Self::CraftingItem { .. } => "crafting",
+            Self::Training { .. } => "training",
+            Self::Working { .. } => "working",
+        }
+    }
+
+    /// The number of days spent on the activity.
+    pub fn days(&self) -> Option<u16> {
+        match self {
+            Self::Crafting { days } | Self::Training { days } | Self::Working { days } => {
+                Some(*days)
+            }
+            Self::CraftingItem { item, quantity } => {
+                let value = item.get_cost_cp()? as u64 * *quantity as u64;
+                u16::try_from(value.div_ceil(CRAFTING_CP_PER_DAY).max(1)).ok()
+            }
+        }
+    }
+}
+
+/// Spend `activity` as the downtime of the named player character. Time advances by the days
+/// spent, and the treasury pays or is paid accordingly.
+///
+/// All of the changes are batched, so a single `undo` reverses the whole stretch of downtime.
+pub async fn downtime(
+    name: &str,
+    activity: Activity,
+    app_meta: &mut AppMeta,
+) -> Result<String, CommandError> {
+    let config = Config::load(app_meta).await;
+
+    let npc = match app_meta.repository.get_by_name(name).await {
+        Ok(Thing::Npc(npc)) if npc.pc.is_some() => npc,
+        Ok(Thing::Npc(npc)) => {
+            return Err(CommandError::unknown_entity(
+                name,
+                format!(
+                    "{} isn't a player character. Use `pc {}` to make them one.",
+                    npc.name, npc.name,
+                ),
+            ))
+        }
+        _ => {
+            return Err(CommandError::unknown_entity(
+                name,
+                Message::NoCharacterNamed { name }.localize(config.language),
+            ))
+        }
+    };
+
+    let name = npc.name.to_string();
+    let gender = npc.gender();
+
+    let days = activity
+        .days()
+        .filter(|days| (1..=DAYS_MAX).contains(days))
+        .ok_or_else(|| match &activity {
+            Activity::CraftingItem { item, .. } if item.get_cost_cp().is_none() => {
+                format!("{} doesn't have a price in the SRD.", item.get_name())
+            }
+            _ => format!("Downtime can last at most {} days.", DAYS_MAX),
+        })?;
+
+    let now = app_meta
+        .repository
+        .get_key_value(&KeyValue::Time(None))
+        .await
+        .ok()
+        .and_then(KeyValue::time)
+        .unwrap_or_default();
+    let time = now
+        .checked_add(&Interval::new_days(days.into()))
+        .ok_or_else(|| "Unable to advance time.".to_string())?;
+
+    let mut treasury = crate::treasury::load(app_meta).await;
+    let mut inventory = None;
+
+    let summary = match &activity {
+        Activity::Crafting { .. } => {
+            let progress = Coins::from_cp(CRAFTING_CP_PER_DAY * days as u64);
+            let materials = Coins::from_cp(CRAFTING_CP_PER_DAY * days as u64 / 2);
+            spend(&mut treasury, materials, "raw materials")?;
+
+            render(
+                "{name} makes {progress} worth of progress on {their} work, spending {materials} on raw materials.",
+                &gender,
+                &[
+                    ("name", &format!("`{}`", name)),
+                    ("progress", &progress.to_string()),
+                    ("materials", &materials.to_string()),
+                ],
+            )
+        }
+        Activity::CraftingItem { item, quantity } => {
+            let crafted = Item::equipment(item, *quantity);
+            let materials = Coins::from_cp(
+                item.get_cost_cp().unwrap_or_default() as u64 * *quantity as u64 / 2,
+            );
+            spend(&mut treasury, materials, "raw materials")?;
+
+            let mut npc_inventory = npc.inventory.value().cloned().unwrap_or_default();
+            npc_inventory.add_item(crafted.clone());
+            inventory = Some(npc_inventory);
+
+            render(
+                "{name} crafts {item}, spending {materials} on raw materials. {They} {adds|add} it to {their} inventory.",
+                &gender,
+                &[
+                    ("name", &format!("`{}`", name)),
+                    ("item", &crafted.to_string()),
+                    ("materials", &materials.to_string()),
+                ],
+            )
+        }
+        Activity::Training { .. } => {
+            let cost = Coins::from_cp(TRAINING_CP_PER_DAY * days as u64);
+            spend(&mut treasury, cost, "training")?;
+
+            format!(
+                "`{}` spends {} on training. Learning a new language or tool proficiency takes {} days in all.",
+                name, cost, TRAINING_DAYS,
+            )
+        }
+        Activity::Working { .. } => {
+            let wages = Coins::from_cp(WORKING_CP_PER_DAY * days as u64);
+            treasury.add(wages);
+
+            render(
+                "{name} practices {their} profession, earning {wages}: enough to maintain a modest lifestyle.",
+                &gender,
+                &[("name", &format!("`{}`", name)), ("wages", &wages.to_string())],
+            )
+        }
+    };
+
+    // Statuses are expired before the time is changed, as with `+[time]`.
+    let (mut changes, mut notices) = status_expiries(&time, app_meta).await;
+
+    if let Some(inventory) = inventory {
+        changes.push(Change::Edit {
+            name: name.clone(),
+            uuid: npc.uuid.as_ref().map(|uuid| uuid.as_ref().to_owned()),
+            diff: Npc {
+                inventory: inventory.into(),
+                ..Default::default()
+            }
+            .into(),
+        });
+    }
+
+    changes.push(Change::SetKeyValue {
+        key_value: KeyValue::Treasury(Some(treasury)),
+    });
+    changes.push(Change::SetKeyValue {
+        key_value: KeyValue::Time(Some(time.clone())),
+    });
+
+    // Everything changes together, so that `undo` reverses the activity in one step and nothing
+    // changes if any of it can't be saved.
+    app_meta
+        .repository
+        .transaction(changes)
+        .await
+        .map_err(|_| {
+            CommandError::storage(Message::CouldNotEdit { name: &name }.localize(config.language))
+        })?;
+
+    if time.days() != now.days() {
+        notices.extend(festival_notices(&time, app_meta).await);
+    }
+
+    let mut output = format!(
+        "# Downtime: {}\n\n**Activity:** {}\\\n**Days:** {}\n\n{}\n\nThe treasury now holds {}. It is now {}.",
+        name,
+        activity.name(),
+        days,
+        summary,
+        treasury,
+        config.display_time(&time),
+    );
+
+    if !notices.is_empty() {
+        output.push_str("\n\n");
+        output.push_str(&notices.join("\\\n"));
+    }
+
+    output.push_str("\n\n_Use `undo` to reverse this._");
+
+    Ok(output)
+}
+
+fn spend(treasury: &mut Coins, cost: Coins, purpose: &str) -> Result<(), CommandError> {
+    let holdings = if treasury.is_empty() {
+        "is empty".to_string()
+    } else {
+        format!("only holds {}", treasury)
+    };
+
+    treasury.spend(cost).map_err(|_| {
+        CommandError::invalid_state(format!(
+            "The treasury {}, which isn't enough to pay {} for {}.",
+            holdings, cost, purpose,
+        ))
+    })
+}
+
+/// A number of days, eg. "5 days", "1 day", or "a day".
+fn parse_days(input: &str) -> Option<u16> {
+    let (number, unit) = input.trim().split_once(' ')?;
+
+    let days = if number.in_ci(&["a", "an", "one"]) {
+        1
+    } else {
+        number.parse().ok()?
+    };
+
+    if unit.in_ci(&["day", "days"]) && days > 0 {
+        Some(days)
+    } else {
+        None
+    }
+}
+
+impl FromStr for Activity {
+    type Err = ();
+
+    /// Parse an activity and its duration, eg. "crafting 5 days" or "crafting a longsword".
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        let (activity, rest) = raw.trim().split_once(' ').ok_or(())?;
+
+        match activity.to_lowercase().as_str() {
+            "crafting" | "craft" => parse_days(rest)
+                .map(|days| Self::Crafting { days })
+                .or_else(|| {
+                    parse_equipment(rest)
+                        .filter(|(quantity, _)| *quantity > 0)
+                        .map(|(quantity, item)| Self::CraftingItem { item, quantity })
+                })
+                .ok_or(()),
+            "training" | "train" => parse_days(rest)
+                .map(|days| Self::Training { days })
+                .ok_or(()),
+            "working" | "work" => parse_days(rest)
+                .map(|days| Self::Working { days })
+                .ok_or(()),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for Activity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::CraftingItem { item, quantity } => {
+                write!(f, "crafting {} {}", quantity, item.get_name())
+            }
+            _ => match self.days() {
+                Some(1) => write!(f, "{} 1 day", self.name()),
+                Some(days) => write!(f, "{} {} days", self.name(), days),
+                None => write!(f, "{}", self.name()),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::storage::MemoryDataStore;
+    use crate::world::npc::{Gender, PlayerCharacter};
+    use crate::Event;
+    use tokio_test::block_on;
+
+    #[test]
+    fn activity_from_str_test() {
+        [
+            ("crafting 5 days", Activity::Crafting { days: 5 }),
+            ("training 1 day", Activity::Training { days: 1 }),
+            ("working 30 days", Activity::Working { days: 30 }),
+            (
+                "crafting 2 Shield",
+                Activity::CraftingItem {
+                    item: Equipment::Shield,
+                    quantity: 2,
+                },
+            ),
+        ]
+        .into_iter()
+        .for_each(|(input, activity)| {
+            assert_eq!(input, activity.to_string());
+            assert_eq!(Ok(activity.clone()), input.parse(), "{}", input);
+            assert_eq!(Ok(activity), input.to_uppercase().parse(), "{}", input);
+        });
+
+        assert_eq!(Ok(Activity::Working { days: 1 }), "work a day".parse());
+        assert_eq!(
+            Ok(Activity::CraftingItem {
+                item: Equipment::Longsword,
+                quantity: 1,
+            }),
+            "craft a longsword".parse(),
+        );
+        assert_eq!(Err(()), "training 0 days".parse::<Activity>());
+        assert_eq!(Err(()), "training a longsword".parse::<Activity>());
+        assert_eq!(Err(()), "carousing 5 days".parse::<Activity>());
+        assert_eq!(Err(()), "crafting".parse::<Activity>());
+    }
+
+    #[test]
+    fn activity_days_test() {
+        assert_eq!(Some(5), Activity::Crafting { days: 5 }.days());
+        assert_eq!(
+            Some(2),
+            Activity::CraftingItem {
+                item: Equipment::Shield,
+                quantity: 1,
+            }
+            .days(),
+        );
+        assert_eq!(
+            Some(1),
+            Activity::CraftingItem {
+                item: Equipment::Dagger,
+                quantity: 1,
+            }
+            .days(),
+        );
+        assert_eq!(
+            Some(6),
+            Activity::CraftingItem {
+                item: Equipment::Longsword,
+                quantity: 2,
+            }
+            .days(),
+        );
+    }
+
+    #[test]
+    fn downtime_test() {
+        let mut app_meta = app_meta();
+
+        block_on(
+            app_meta.repository.modify(Change::CreateAndSave {
+                thing: Npc {
+                    name: "Aria".into(),
+                    gender: Gender::Feminine.into(),
+                    pc: PlayerCharacter::default().into(),
+                    ..Default::default()
+                }
+                .into(),
+            }),
+        )
+        .unwrap();
+
+        block_on(
+            app_meta.repository.modify(Change::CreateAndSave {
+                thing: Npc {
+                    name: "Gottfried".into(),
+                    ..Default::default()
+                }
+                .into(),
+            }),
+        )
+        .unwrap();
+
+        assert_eq!(
+            "Gottfried isn't a player character. Use `pc Gottfried` to make them one.",
+            block_on(downtime(
+                "Gottfried",
+                Activity::Working { days: 1 },
+                &mut app_meta
+            ))
+            .unwrap_err()
+            .message(),
+        );
+
+        assert_eq!(
+            "The treasury is empty, which isn't enough to pay 5 gp for training.",
+            block_on(downtime(
+                "Aria",
+                Activity::Training { days: 5 },
+                &mut app_meta
+            ))
+            .unwrap_err()
+            .message(),
+        );
+
+        assert_eq!(
+            "# Downtime: Aria
+
+**Activity:** working\\
+**Days:** 10
+
+`Aria` practices her profession, earning 10 gp: enough to maintain a modest lifestyle.
+
+The treasury now holds 10 gp. It is now day 11 at 8:00:00 am.
+
+_Use `undo` to reverse this._",
+            block_on(downtime(
+                "aria",
+                Activity::Working { days: 10 },
+                &mut app_meta
+            ))
+            .unwrap(),
+        );
+
+        assert_eq!(
+            "# Downtime: Aria
+
+**Activity:** crafting\\
+**Days:** 2
+
+`Aria` crafts Shield, spending 5 gp on raw materials. She adds it to her inventory.
+
+The treasury now holds 5 gp. It is now day 13 at 8:00:00 am.
+
+_Use `undo` to reverse this._",
+            block_on(downtime(
+                "Aria",
+                Activity::CraftingItem {
+                    item: Equipment::Shield,
+                    quantity: 1,
+                },
+                &mut app_meta
+            ))
+            .unwrap(),
+        );
+
+        match block_on(app_meta.repository.get_by_name("Aria")) {
+            Ok(Thing::Npc(npc)) => assert!(
+                npc.inventory.value().map_or(false, |inventory| inventory
+                    .items
+                    .iter()
+                    .any(|item| item.name == "Shield")),
+                "{:?}",
+                npc,
+            ),
+            thing => panic!("{:?}", thing),
+        }
+
+        block_on(app_meta.repository.undo());
+
+        assert_eq!(
+            Coins::from_cp(1000),
+            block_on(crate::treasury::load(&app_meta)),
+        );
+
+        assert_eq!(
+            "`Aria` makes 10 gp worth of progress on her work, spending 5 gp on raw materials.",
+            block_on(downtime(
+                "Aria",
+                Activity::Crafting { days: 2 },
+                &mut app_meta
+            ))
+            .unwrap()
+            .split("\n\n")
+            .nth(2)
+            .unwrap(),
+        );
+    }
+
+    fn event_dispatcher(_event: Event) {}
+
+    fn app_meta() -> AppMeta {
+        AppMeta::new(MemoryDataStore::default(), &event_dispatcher)
+    }
+}
@@ -1,14 +1,31 @@
+use serde::ser::SerializeMap;
 use serde::{Deserialize, Serialize, Serializer};
 use std::fmt;
 use std::mem;
 
+/// A value that is either locked, having been specified by the user, or unlocked, in which case
+/// it's free to be regenerated.
+///
+/// Locked values are serialized as the bare value. Unlocked values are serialized in the form
+/// `{"unlocked":value}`, so that a field the user unlocked stays that way.
 #[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
-#[serde(from = "Option<T>")]
+#[serde(from = "FieldSerialized<T>")]
 pub enum Field<T> {
     Locked(Option<T>),
     Unlocked(Option<T>),
 }
 
+#[derive(Deserialize)]
+#[serde(transparent)]
+struct FieldSerialized<T>(Option<FieldValue<T>>);
+
+#[derive(Deserialize)]
+#[serde(untagged, deny_unknown_fields, expecting = "unrecognized value")]
+enum FieldValue<T> {
+    Unlocked { unlocked: T },
+    Locked(T),
+}
+
 impl<T> Field<T> {
     pub fn new(value: T) -> Self {
         Self::Locked(Some(value))
@@ -47,6 +64,19 @@ impl<T> Field<T> {
         }
     }
 
+    /// Lock or unlock the field, returning whether it was locked beforehand.
+    pub fn set_locked(&mut self, locked: bool) -> bool {
+        let was_locked = self.is_locked();
+
+        if locked {
+            self.lock();
+        } else {
+            self.unlock();
+        }
+
+        was_locked
+    }
+
     #[cfg(test)]
     pub fn unlocked(mut self) -> Self {
         self.unlock();
@@ -100,6 +130,20 @@ impl<T> Field<T> {
             other.lock();
         }
     }
+
+    /// A diff that merges the value of a duplicate field into this one. The duplicate's value is
+    /// taken if this field is empty, or if the duplicate is locked and this isn't. Otherwise the
+    /// diff is empty, leaving this field as it is.
+    pub fn merged_from(&self, other: &Self) -> Self
+    where
+        T: Clone,
+    {
+        if other.is_some() && (self.is_none() || (other.is_locked() && self.is_unlocked())) {
+            Self::Locked(other.value().cloned())
+        } else {
+            Self::default()
+        }
+    }
 }
 
 impl<T> Default for Field<T> {
@@ -114,6 +158,16 @@ impl<T> From<T> for Field<T> {
     }
 }
 
+impl<T> From<FieldSerialized<T>> for Field<T> {
+    fn from(value: FieldSerialized<T>) -> Field<T> {
+        match value.0 {
+            Some(FieldValue::Unlocked { unlocked }) => Field::Unlocked(Some(unlocked)),
+            Some(FieldValue::Locked(value)) => Field::Locked(Some(value)),
+            None => Field::Locked(None),
+        }
+    }
+}
+
 impl<T> From<Option<T>> for Field<T> {
     fn from(value: Option<T>) -> Field<T> {
         Field::Locked(value)
@@ -149,9 +203,14 @@ impl<T: Serialize> Serialize for Field<T> {
     where
         S: Serializer,
     {
-        match self.value() {
-            Some(v) => serializer.serialize_some(v),
-            None => serializer.serialize_none(),
+        match self {
+            Self::Unlocked(Some(v)) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry("unlocked", v)?;
+                map.end()
+            }
+            Self::Locked(Some(v)) => serializer.serialize_some(v),
+            Self::Locked(None) | Self::Unlocked(None) => serializer.serialize_none(),
         }
     }
 }
@@ -160,6 +219,23 @@ impl<T: Serialize> Serialize for Field<T> {
 mod test {
     use super::Field;
 
+    #[test]
+    fn merged_from_test() {
+        let empty: Field<u8> = Field::default();
+        let generated = Field::new_generated(1);
+        let locked = Field::new(2);
+
+        assert_eq!(Field::new(1), empty.merged_from(&generated));
+        assert_eq!(Field::new(2), generated.merged_from(&locked));
+        assert_eq!(Field::default(), locked.merged_from(&generated));
+        assert_eq!(Field::default(), locked.merged_from(&Field::new(3)));
+        assert_eq!(
+            Field::default(),
+            generated.merged_from(&Field::new_generated(3))
+        );
+        assert_eq!(Field::default(), generated.merged_from(&empty));
+    }
+
     #[test]
     fn default_test() {
         let field: Field<bool> = Field::default();
@@ -240,6 +316,12 @@ mod test {
 
         let field: Field<bool> = Field::default();
         assert_eq!("null", serde_json::to_string(&field).unwrap());
+
+        let field: Field<_> = Field::new_generated("hello");
+        assert_eq!(
+            r#"{"unlocked":"hello"}"#,
+            serde_json::to_string(&field).unwrap(),
+        );
     }
 
     #[test]
@@ -249,6 +331,22 @@ mod test {
 
         let field: Field<u8> = serde_json::from_str("null").unwrap();
         assert_eq!(Field::Locked(None), field);
+
+        let field: Field<u8> = serde_json::from_str(r#"{"unlocked":123}"#).unwrap();
+        assert_eq!(Field::Unlocked(Some(123)), field);
+
+        assert!(serde_json::from_str::<Field<u8>>(r#"{"unlocked":123,"potato":1}"#).is_err());
+    }
+
+    #[test]
+    fn set_locked_test() {
+        let mut field = Field::new_generated(1);
+
+        assert!(!field.set_locked(true));
+        assert!(field.is_locked());
+        assert!(field.set_locked(true));
+        assert!(field.set_locked(false));
+        assert!(field.is_unlocked());
     }
 
     #[test]
@@ -0,0 +1,277 @@
+//! Helpers for generating grammatical English, so that generators don't need to special-case
+//! articles, plurals, and pronouns themselves.
+
+use super::npc::Gender;
+use crate::utils::{capitalize, CaseInsensitiveStr};
+
+/// The indefinite article to use before a word or number, eg. "an" for "inn" or "8th", and "a" for
+/// "unicorn" or "1st".
+pub fn article(word: &str) -> &'static str {
+    let digits = word.find(|c: char| !c.is_ascii_digit() && c != ',');
+    let number = &word[..digits.unwrap_or(word.len())];
+
+    if !number.is_empty() {
+        // "Eight", "eleven", and "eighteen" are the only numbers pronounced with a leading vowel,
+        // although "eleven" and "eighteen" also lead "eleven thousand" and "eighteen million".
+        let digit_count = number.chars().filter(char::is_ascii_digit).count();
+
+        return if number.starts_with('8')
+            || (digit_count % 3 == 2 && (number.starts_with("11") || number.starts_with("18")))
+        {
+            "an"
+        } else {
+            "a"
+        };
+    }
+
+    if ["hour", "honest", "honor", "honour", "heir"]
+        .iter()
+        .any(|prefix| word.starts_with_ci(prefix))
+    {
+        "an"
+    } else if [
+        "uni", "use", "usu", "ure", "uti", "eu", "ewe", "one", "once",
+    ]
+    .iter()
+    .any(|prefix| word.starts_with_ci(prefix))
+    {
+        "a"
+    } else if word.starts_with(|c: char| "aeiouAEIOU".contains(c)) {
+        "an"
+    } else {
+        "a"
+    }
+}
+
+/// A word preceded by its indefinite article, eg. "an inn".
+pub fn with_article(word: &str) -> String {
+    format!("{} {}", article(word), word)
+}
+
+/// The plural form of a noun, eg. "wolves" for "wolf".
+pub fn plural(word: &str) -> String {
+    match word {
+        "Goose" => "Geese".to_string(),
+        "Beef" | "Carp" | "Cod" | "Deer" | "Perch" | "Potatoes" | "Sheep" | "Squid" => {
+            word.to_string()
+        }
+        // Peoples and monsters whose names don't change in the plural, eg. "4 lizardfolk".
+        s if ["folk", "aarakocra", "sahuagin"]
+            .iter()
+            .any(|suffix| s.ends_with_ci(suffix)) =>
+        {
+            word.to_string()
+        }
+        s if s.ends_with("person") => format!("{}ople", &word[..(word.len() - 4)]),
+        s if s.ends_with("ff") => format!("{}s", word),
+        s if s.ends_with('f') => format!("{}ves", &word[..(word.len() - 1)]),
+        s if s.ends_with("ey") => format!("{}ies", &word[..(word.len() - 2)]),
+        s if s.ends_with('y') => format!("{}ies", &word[..(word.len() - 1)]),
+        s if s.ends_with(&['s', 'x', 'z'][..]) => format!("{}es", word),
+        s if s.ends_with("ch") || s.ends_with("sh") => format!("{}es", word),
+        _ => format!("{}s", word),
+    }
+}
+
+/// A quantity of a noun, eg. "a wolf", "3 wolves", or "no wolves".
+pub fn count(n: u32, word: &str) -> String {
+    match n {
+        0 => format!("no {}", plural(word)),
+        1 => with_article(word),
+        n => format!("{} {}", n, plural(word)),
+    }
+}
+
+/// Join several items into a list, eg. "red, green, and blue".
+pub fn list(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [item] => item.clone(),
+        [first, second] => format!("{} and {}", first, second),
+        [rest @ .., last] => format!("{}, and {}", rest.join(", "), last),
+    }
+}
+
+/// Render a sentence template about a subject. Placeholders in braces are replaced as follows:
+///
+/// * `{they}`, `{them}`, `{their}`, `{theirs}`, `{themself}`, `{theyre}`, and `{theyve}` become
+///   the subject's pronouns, capitalized if the placeholder is (eg. `{They}`).
+/// * `{is|are}` becomes whichever form of the verb agrees with the subject.
+/// * `{a}` becomes the article agreeing with the word that follows it.
+/// * Anything else is looked up in `vars`, eg. `{occupation}`.
+///
+/// Unrecognized placeholders are left as they are.
+pub fn render(template: &str, subject: &Gender, vars: &[(&str, &str)]) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut pending_article = None;
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        push_text(&mut result, &rest[..start], &mut pending_article);
+
+        let end = if let Some(end) = rest[start..].find('}') {
+            start + end
+        } else {
+            rest = &rest[start..];
+            break;
+        };
+
+        let placeholder = &rest[start + 1..end];
+        rest = &rest[end + 1..];
+
+        if placeholder.eq_ci("a") {
+            pending_article = Some((result.len(), placeholder.starts_with('A')));
+            continue;
+        }
+
+        let replacement = pronoun(placeholder, subject)
+            .map(str::to_string)
+            .or_else(|| {
+                placeholder
+                    .split_once('|')
+                    .map(|(singular, plural)| subject.conjugate(singular, plural).to_string())
+            })
+            .or_else(|| {
+                vars.iter()
+                    .find(|(name, _)| *name == placeholder)
+                    .map(|(_, value)| value.to_string())
+            })
+            .unwrap_or_else(|| format!("{{{}}}", placeholder));
+
+        push_text(&mut result, &replacement, &mut pending_article);
+    }
+
+    push_text(&mut result, rest, &mut pending_article);
+    result
+}
+
+fn pronoun(placeholder: &str, subject: &Gender) -> Option<&'static str> {
+    let (pronoun, pronoun_cap) = match placeholder.to_lowercase().as_str() {
+        "they" => (subject.they(), subject.they_cap()),
+        "them" => (subject.them(), subject.them_cap()),
+        "their" => (subject.their(), subject.their_cap()),
+        "theirs" => (subject.theirs(), subject.theirs_cap()),
+        "themself" => (subject.themself(), subject.themself_cap()),
+        "theyre" => (subject.theyre(), subject.theyre_cap()),
+        "theyve" => (subject.theyve(), subject.theyve_cap()),
+        _ => return None,
+    };
+
+    Some(if placeholder.starts_with(char::is_uppercase) {
+        pronoun_cap
+    } else {
+        pronoun
+    })
+}
+
+/// Append text to the result, resolving any article waiting on the next word.
+fn push_text(result: &mut String, text: &str, pending_article: &mut Option<(usize, bool)>) {
+    if let Some((pos, capitalized)) = *pending_article {
+        let next_word = text.trim_start();
+
+        if !next_word.is_empty() {
+            let article = article(next_word);
+            result.insert_str(
+                pos,
+                &if capitalized {
+                    capitalize(article)
+                } else {
+                    article.to_string()
+                },
+            );
+            *pending_article = None;
+        }
+    }
+
+    result.push_str(text);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn article_test() {
+        [
+            ("a", "bar"),
+            ("an", "inn"),
+            ("an", "Inn"),
+            ("a", "unicorn"),
+            ("an", "umbrella"),
+            ("a", "European"),
+            ("an", "hour"),
+            ("a", "house"),
+            ("a", "1st"),
+            ("an", "8th"),
+            ("an", "11th"),
+            ("a", "111th"),
+            ("an", "18,000"),
+            ("a", "2nd"),
+        ]
+        .into_iter()
+        .for_each(|(expected, word)| assert_eq!(expected, article(word), "{}", word));
+    }
+
+    #[test]
+    fn plural_test() {
+        assert_eq!(
+            vec!["Geese", "Sheep", "Wolves", "Ladies", "Foxes", "Witches", "Bushes", "Cats"],
+            ["Goose", "Sheep", "Wolf", "Lady", "Fox", "Witch", "Bush", "Cat"]
+                .iter()
+                .map(|word| plural(word))
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn count_test() {
+        assert_eq!("no wolves", count(0, "wolf"));
+        assert_eq!("an owl", count(1, "owl"));
+        assert_eq!("3 cats", count(3, "cat"));
+        assert_eq!("2 old people", count(2, "old person"));
+        assert_eq!("4 lizardfolk", count(4, "lizardfolk"));
+        assert_eq!("2 hippogriffs", count(2, "hippogriff"));
+    }
+
+    #[test]
+    fn list_test() {
+        let items: Vec<String> = ["red", "green", "blue"].map(String::from).to_vec();
+
+        assert_eq!("", list(&[]));
+        assert_eq!("red", list(&items[..1]));
+        assert_eq!("red and green", list(&items[..2]));
+        assert_eq!("red, green, and blue", list(&items));
+    }
+
+    #[test]
+    fn render_test() {
+        let template =
+            "{They} {is|are} {a} {occupation}. {A} {adjective} soul, {theyre} {a} {age}.";
+        let vars = [
+            ("occupation", "innkeeper"),
+            ("adjective", "honest"),
+            ("age", "elf"),
+        ];
+
+        assert_eq!(
+            "She is an innkeeper. An honest soul, she's an elf.",
+            render(template, &Gender::Feminine, &vars),
+        );
+        assert_eq!(
+            "They are an innkeeper. An honest soul, they're an elf.",
+            render(template, &Gender::NonBinaryThey, &vars),
+        );
+        assert_eq!(
+            "He is a {occupation} in a {place}",
+            render(
+                "{They} {is|are} {a} {occupation} in {a} {place}",
+                &Gender::Masculine,
+                &[],
+            ),
+        );
+        assert_eq!(
+            "Unclosed {brace",
+            render("Unclosed {brace", &Gender::Neuter, &[])
+        );
+    }
+}
@@ -0,0 +1,550 @@
+use crate::reference::Item as Equipment;
+use crate::utils::CaseInsensitiveStr;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// The value of each denomination in copper pieces, from smallest to largest.
+const DENOMINATIONS: [(&str, u64); 5] =
+    [("cp", 1), ("sp", 10), ("ep", 50), ("gp", 100), ("pp", 1000)];
+
+/// Coins and other valuables carried by a character or stashed at a place.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Inventory {
+    pub coins: Coins,
+    pub items: Vec<Item>,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Coins {
+    pub cp: u32,
+    pub sp: u32,
+    pub ep: u32,
+    pub gp: u32,
+    pub pp: u32,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Item {
+    pub name: String,
+    pub quantity: u32,
+
+    /// The SRD index of a piece of mundane equipment, eg. `longsword`, used to look up its weight.
+    #[serde(default)]
+    pub index: Option<String>,
+}
+
+pub struct InventoryView<'a>(&'a Inventory);
+
+impl Inventory {
+    pub fn is_empty(&self) -> bool {
+        self.coins.is_empty() && self.items.is_empty()
+    }
+
+    /// Add an item, stacking it with any existing item of the same name.
+    pub fn add_item(&mut self, item: Item) {
+        if let Some(existing) = self.items.iter_mut().find(|i| i.name.eq_ci(&item.name)) {
+            existing.quantity += item.quantity;
+        } else {
+            self.items.push(item);
+        }
+    }
+
+    /// Remove some quantity of the named item, returning what was removed. If there aren't
+    /// enough of the item, the quantity actually held is returned as an error instead.
+    pub fn remove_item(&mut self, name: &str, quantity: u32) -> Result<Item, u32> {
+        let i = self
+            .items
+            .iter()
+            .position(|item| item.name.eq_ci(name))
+            .ok_or(0u32)?;
+
+        match self.items[i].quantity {
+            held if held < quantity => Err(held),
+            held if held == quantity => Ok(self.items.remove(i)),
+            _ => {
+                self.items[i].quantity -= quantity;
+
+                Ok(Item {
+                    quantity,
+                    ..self.items[i].clone()
+                })
+            }
+        }
+    }
+
+    /// Combine the contents of another inventory into this one.
+    pub fn add(&mut self, other: Inventory) {
+        self.coins.add(other.coins);
+
+        other.items.into_iter().for_each(|item| self.add_item(item));
+    }
+
+    /// The total weight of the inventory in pounds. Items that aren't SRD equipment are
+    /// considered weightless, while coins weigh a pound per fifty.
+    pub fn weight(&self) -> f32 {
+        self.coins.count() as f32 / 50.
+            + self
+                .items
+                .iter()
+                .filter_map(|item| {
+                    item.index
+                        .as_deref()
+                        .and_then(Equipment::from_index)
+                        .and_then(|equipment| equipment.get_weight())
+                        .map(|weight| weight * item.quantity as f32)
+                })
+                .sum::<f32>()
+    }
+
+    pub fn display(&self) -> InventoryView {
+        InventoryView(self)
+    }
+}
+
+impl Coins {
+    /// The given value in gold, silver, and copper pieces, using as few coins as possible. Only
+    /// the common denominations are used, since that's how change is usually given.
+    pub fn from_cp(value: u64) -> Self {
+        Self {
+            cp: (value % 10) as u32,
+            sp: (value / 10 % 10) as u32,
+            gp: (value / 100) as u32,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    pub fn add(&mut self, other: Coins) {
+        self.cp += other.cp;
+        self.sp += other.sp;
+        self.ep += other.ep;
+        self.gp += other.gp;
+        self.pp += other.pp;
+    }
+
+    pub fn count(&self) -> u32 {
+        self.cp + self.sp + self.ep + self.gp + self.pp
+    }
+
+    /// The combined value of the coins in copper pieces.
+    pub fn value_cp(&self) -> u64 {
+        self.cp as u64
+            + self.sp as u64 * 10
+            + self.ep as u64 * 50
+            + self.gp as u64 * 100
+            + self.pp as u64 * 1000
+    }
+
+    /// Remove coins worth the given amount. Coins of the denominations named in the amount are
+    /// used first, then the largest coins that don't overpay. If the exact amount can't be paid,
+    /// the smallest remaining coin is broken and the change returned. If there isn't enough money,
+    /// the coins are left untouched.
+    pub fn spend(&mut self, mut amount: Coins) -> Result<(), ()> {
+        if self.value_cp() < amount.value_cp() {
+            return Err(());
+        }
+
+        let mut remaining = 0;
+
+        for (i, (_, value)) in DENOMINATIONS.iter().enumerate() {
+            let wanted = *amount.denomination_mut(i);
+            let count = self.denomination_mut(i);
+            let used = (*count).min(wanted);
+
+            *count -= used;
+            remaining += (wanted - used) as u64 * value;
+        }
+
+        for (i, (_, value)) in DENOMINATIONS.iter().enumerate().rev() {
+            let count = self.denomination_mut(i);
+            let used = (*count as u64).min(remaining / value);
+
+            *count -= used as u32;
+            remaining -= used * value;
+        }
+
+        if remaining > 0 {
+            // Every coin left is worth more than the remainder, since it would have been spent
+            // otherwise, and there's enough money in total, so there must be one to break.
+            let i = (0..DENOMINATIONS.len())
+                .find(|&i| *self.denomination_mut(i) > 0)
+                .ok_or(())?;
+
+            *self.denomination_mut(i) -= 1;
+            self.add(Self::from_cp(DENOMINATIONS[i].1 - remaining));
+        }
+
+        Ok(())
+    }
+
+    fn denomination_mut(&mut self, i: usize) -> &mut u32 {
+        match i {
+            0 => &mut self.cp,
+            1 => &mut self.sp,
+            2 => &mut self.ep,
+            3 => &mut self.gp,
+            _ => &mut self.pp,
+        }
+    }
+}
+
+impl FromStr for Coins {
+    type Err = ();
+
+    /// Parse a list of amounts, eg. "150 gp" or "12 gp, 3 sp". The same denomination may appear
+    /// more than once, in which case the amounts are combined.
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        if raw.trim().eq_ci("none") {
+            return Ok(Self::default());
+        }
+
+        let words: Vec<&str> = raw
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|word| !word.is_empty())
+            .collect();
+
+        if words.is_empty() || !words.len().is_multiple_of(2) {
+            return Err(());
+        }
+
+        let mut coins = Self::default();
+
+        for pair in words.chunks(2) {
+            let amount: u32 = pair[0].parse().map_err(|_| ())?;
+            let i = DENOMINATIONS
+                .iter()
+                .position(|(denomination, _)| denomination.eq_ci(pair[1]))
+                .ok_or(())?;
+
+            *coins.denomination_mut(i) += amount;
+        }
+
+        Ok(coins)
+    }
+}
+
+impl fmt::Display for Coins {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let coins: Vec<String> = [
+            (self.cp, "cp"),
+            (self.sp, "sp"),
+            (self.ep, "ep"),
+            (self.gp, "gp"),
+            (self.pp, "pp"),
+        ]
+        .into_iter()
+        .filter(|(amount, _)| *amount > 0)
+        .map(|(amount, denomination)| format!("{} {}", amount, denomination))
+        .collect();
+
+        if coins.is_empty() {
+            write!(f, "none")
+        } else {
+            write!(f, "{}", coins.join(", "))
+        }
+    }
+}
+
+impl Item {
+    pub fn new(name: &str, quantity: u32) -> Self {
+        Self {
+            name: name.to_string(),
+            quantity,
+            index: None,
+        }
+    }
+
+    pub fn equipment(equipment: &Equipment, quantity: u32) -> Self {
+        Self {
+            name: equipment.get_name().to_string(),
+            quantity,
+            index: Some(equipment.get_index().to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Item {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.quantity == 1 {
+            write!(f, "{}", self.name)
+        } else {
+            write!(f, "{} ×{}", self.name, self.quantity)
+        }
+    }
+}
+
+impl<'a> fmt::Display for InventoryView<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let inventory = self.0;
+        let mut lines = Vec::new();
+
+        if !inventory.coins.is_empty() {
+            lines.push(format!("**Coins:** {}", inventory.coins));
+        }
+
+        if !inventory.items.is_empty() {
+            lines.push(format!(
+                "**Items:** {}",
+                inventory
+                    .items
+                    .iter()
+                    .map(|item| item.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ));
+        }
+
+        write!(f, "{}", lines.join("\\\n"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn add_test() {
+        let mut inventory = Inventory::default();
+        assert!(inventory.is_empty());
+
+        inventory.add_item(Item::new("bloodstone (50 gp)", 2));
+        inventory.add(Inventory {
+            coins: Coins {
+                gp: 12,
+                sp: 5,
+                ..Default::default()
+            },
+            items: vec![
+                Item::new("Bloodstone (50 gp)", 1),
+                Item::new("Flame Tongue", 1),
+            ],
+        });
+        inventory.add(Inventory {
+            coins: Coins {
+                gp: 3,
+                ..Default::default()
+            },
+            items: Vec::new(),
+        });
+
+        assert!(!inventory.is_empty());
+        assert_eq!(
+            Coins {
+                sp: 5,
+                gp: 15,
+                ..Default::default()
+            },
+            inventory.coins,
+        );
+        assert_eq!(
+            vec![
+                Item::new("bloodstone (50 gp)", 3),
+                Item::new("Flame Tongue", 1),
+            ],
+            inventory.items,
+        );
+    }
+
+    #[test]
+    fn remove_item_test() {
+        let mut inventory = Inventory::default();
+        inventory.add_item(Item::equipment(&Equipment::Longsword, 3));
+        inventory.add_item(Item::new("Flame Tongue", 1));
+
+        assert_eq!(
+            Ok(Item::equipment(&Equipment::Longsword, 2)),
+            inventory.remove_item("longsword", 2),
+        );
+        assert_eq!(Err(1), inventory.remove_item("Longsword", 2));
+        assert_eq!(Err(0), inventory.remove_item("Shield", 1));
+        assert_eq!(
+            Ok(Item::new("Flame Tongue", 1)),
+            inventory.remove_item("Flame Tongue", 1),
+        );
+
+        assert_eq!(
+            vec![Item::equipment(&Equipment::Longsword, 1)],
+            inventory.items
+        );
+    }
+
+    #[test]
+    fn weight_test() {
+        let mut inventory = Inventory::default();
+        assert_eq!(0., inventory.weight());
+
+        inventory.coins.gp = 75;
+        inventory.add_item(Item::equipment(&Equipment::Longsword, 2));
+        inventory.add_item(Item::new("Flame Tongue", 1));
+
+        assert_eq!(7.5, inventory.weight());
+    }
+
+    #[test]
+    fn value_cp_test() {
+        assert_eq!(0, Coins::default().value_cp());
+        assert_eq!(
+            12345,
+            Coins {
+                cp: 5,
+                sp: 4,
+                ep: 8,
+                gp: 9,
+                pp: 11,
+            }
+            .value_cp(),
+        );
+    }
+
+    #[test]
+    fn from_cp_test() {
+        assert_eq!(Coins::default(), Coins::from_cp(0));
+        assert_eq!(
+            Coins {
+                cp: 5,
+                sp: 4,
+                gp: 123,
+                ..Default::default()
+            },
+            Coins::from_cp(12345),
+        );
+    }
+
+    #[test]
+    fn spend_test() {
+        let mut coins = Coins {
+            gp: 10,
+            ..Default::default()
+        };
+
+        assert_eq!(Ok(()), coins.spend(Coins::from_cp(350)));
+        assert_eq!(
+            Coins {
+                sp: 5,
+                gp: 6,
+                ..Default::default()
+            },
+            coins,
+        );
+
+        let mut coins = Coins {
+            cp: 20,
+            gp: 12,
+            pp: 1,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            Ok(()),
+            coins.spend(Coins {
+                pp: 2,
+                ..Default::default()
+            }),
+        );
+        assert_eq!(
+            Coins {
+                cp: 20,
+                gp: 2,
+                ..Default::default()
+            },
+            coins,
+        );
+
+        assert_eq!(
+            Ok(()),
+            coins.spend(Coins {
+                sp: 1,
+                ..Default::default()
+            }),
+        );
+        assert_eq!(
+            Coins {
+                cp: 10,
+                gp: 2,
+                ..Default::default()
+            },
+            coins,
+        );
+
+        assert_eq!(Ok(()), coins.spend(Coins::from_cp(15)));
+        assert_eq!(
+            Coins {
+                cp: 5,
+                sp: 9,
+                gp: 1,
+                ..Default::default()
+            },
+            coins,
+        );
+
+        assert_eq!(Err(()), coins.spend(Coins::from_cp(1000)));
+        assert_eq!(
+            Coins {
+                cp: 5,
+                sp: 9,
+                gp: 1,
+                ..Default::default()
+            },
+            coins,
+        );
+    }
+
+    #[test]
+    fn from_str_test() {
+        assert_eq!(
+            Ok(Coins {
+                gp: 150,
+                ..Default::default()
+            }),
+            "150 gp".parse::<Coins>(),
+        );
+        assert_eq!(
+            Ok(Coins {
+                cp: 30,
+                gp: 125,
+                pp: 4,
+                ..Default::default()
+            }),
+            "30 cp, 120 GP, 4 pp 5 gp".parse::<Coins>(),
+        );
+        assert_eq!(Ok(Coins::default()), "none".parse::<Coins>());
+
+        assert_eq!(Err(()), "".parse::<Coins>());
+        assert_eq!(Err(()), "150".parse::<Coins>());
+        assert_eq!(Err(()), "150 doubloons".parse::<Coins>());
+        assert_eq!(Err(()), "-3 gp".parse::<Coins>());
+    }
+
+    #[test]
+    fn display_test() {
+        assert_eq!("", Inventory::default().display().to_string());
+        assert_eq!("none", Coins::default().to_string());
+
+        let mut inventory = Inventory {
+            coins: Coins {
+                cp: 30,
+                gp: 120,
+                pp: 4,
+                ..Default::default()
+            },
+            items: Vec::new(),
+        };
+
+        assert_eq!(
+            "**Coins:** 30 cp, 120 gp, 4 pp",
+            inventory.display().to_string()
+        );
+
+        inventory.add_item(Item::new("bloodstone (50 gp)", 2));
+        inventory.add_item(Item::new("Potion of Healing", 1));
+
+        assert_eq!(
+            "**Coins:** 30 cp, 120 gp, 4 pp\\\n**Items:** bloodstone (50 gp) ×2, Potion of Healing",
+            inventory.display().to_string(),
+        );
+    }
+}
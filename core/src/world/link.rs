@@ -0,0 +1,283 @@
+use super::Thing;
+use crate::app::Annotation;
+use crate::storage::Repository;
+use crate::utils::{CodeView, EscapedView};
+use std::fmt;
+
+/// A mention of a named entity, emitted as a link token of the form `{Gottfried}`. Tokens are
+/// resolved against the repository by [`render_links`] before output reaches the user.
+pub struct LinkView<'a>(&'a str);
+
+impl<'a> LinkView<'a> {
+    pub fn new(name: &'a str) -> Self {
+        Self(name)
+    }
+}
+
+impl<'a> fmt::Display for LinkView<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{{}}}", self.0)
+    }
+}
+
+/// Resolve the link tokens in a block of output. Tokens naming an entity that exists in the
+/// journal or recent history become clickable (eg. `` `Gottfried` ``), loading that entity when
+/// clicked. Tokens that don't resolve are reduced to their plain text.
+pub async fn render_links(output: &str, repository: &Repository) -> String {
+    let mut result = String::with_capacity(output.len());
+    let mut remaining = output;
+
+    while let Some((before, name, after)) = next_token(remaining) {
+        result.push_str(before);
+
+        let link = if let Ok(thing) = repository.get_by_name(name).await {
+            CodeView::new(thing.name().value().map_or(name, String::as_str)).to_string()
+        } else {
+            EscapedView::new(name).to_string()
+        };
+        result.push_str(&link);
+
+        remaining = after;
+    }
+
+    result.push_str(remaining);
+    result
+}
+
+/// Find the entities mentioned in a block of rendered output, in the order they're first
+/// mentioned. Mentions are headings (eg. `# Gottfried`) and code spans (eg. `` `Gottfried` ``)
+/// naming an entity in the journal or recent history.
+pub async fn find_mentions(output: &str, repository: &Repository) -> Vec<Thing> {
+    let headings = output
+        .lines()
+        .filter_map(|line| line.strip_prefix("# "))
+        .map(unescape);
+    let code_spans = output.split('`').skip(1).step_by(2).map(str::to_string);
+    let mut things: Vec<Thing> = Vec::new();
+
+    for name in headings.chain(code_spans) {
+        if let Ok(thing) = repository.get_by_name(name.trim()).await {
+            if !things.iter().any(|t| t.name() == thing.name()) {
+                things.push(thing);
+            }
+        }
+    }
+
+    things
+}
+
+/// Annotate the code spans in a block of rendered output that name an entity in the journal or
+/// recent history with the type of entity, eg. `` <span class="npc">`Gottfried`</span> ``.
+pub async fn annotate_mentions(output: &str, repository: &Repository) -> String {
+    let mut result = String::with_capacity(output.len());
+    let parts: Vec<&str> = output.split('`').collect();
+
+    for (i, part) in parts.iter().enumerate() {
+        if i % 2 == 0 {
+            result.push_str(part);
+        } else if i == parts.len() - 1 {
+            // An unmatched backtick doesn't begin a code span.
+            result.push('`');
+            result.push_str(part);
+        } else {
+            let span = format!("`{}`", part);
+
+            if let Ok(thing) = repository.get_by_name(part.trim()).await {
+                result.push_str(&Annotation::for_thing(&thing).annotate(&span).to_string());
+            } else {
+                result.push_str(&span);
+            }
+        }
+    }
+
+    result
+}
+
+/// Reverse the backslash escapes added by [`EscapedView`].
+fn unescape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => result.extend(chars.next()),
+            c => result.push(c),
+        }
+    }
+
+    result
+}
+
+/// Split the input around the first well-formed link token, returning the text before the token,
+/// the name it contains, and the text after it.
+fn next_token(input: &str) -> Option<(&str, &str, &str)> {
+    let mut offset = 0;
+
+    while let Some(start) = input[offset..].find('{').map(|i| i + offset) {
+        let inner_start = start + '{'.len_utf8();
+
+        if let Some(len) = input[inner_start..].find(['{', '}', '\n']) {
+            let inner_end = inner_start + len;
+            let name = &input[inner_start..inner_end];
+
+            if input[inner_end..].starts_with('}') && !name.is_empty() && name.trim() == name {
+                return Some((&input[..start], name, &input[inner_end + '}'.len_utf8()..]));
+            }
+        }
+
+        offset = inner_start;
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::storage::{Change, MemoryDataStore};
+    use crate::world::{Npc, Place};
+    use tokio_test::block_on;
+
+    #[test]
+    fn link_view_test() {
+        assert_eq!("{Gottfried}", LinkView::new("Gottfried").to_string());
+    }
+
+    #[test]
+    fn next_token_test() {
+        assert_eq!(
+            Some(("Hello ", "Gottfried", "!")),
+            next_token("Hello {Gottfried}!"),
+        );
+        assert_eq!(Some(("{ ", "Bree", "}")), next_token("{ {Bree}}"));
+        assert_eq!(None, next_token("{}"));
+        assert_eq!(None, next_token("{Potato\nJohnson}"));
+        assert_eq!(None, next_token("{Potato Johnson"));
+        assert_eq!(None, next_token("Potato Johnson"));
+    }
+
+    #[test]
+    fn render_links_test() {
+        let mut repository = Repository::new(MemoryDataStore::default());
+
+        block_on(
+            repository.modify(Change::Create {
+                thing: Npc {
+                    name: "Gottfried".into(),
+                    ..Default::default()
+                }
+                .into(),
+            }),
+        )
+        .unwrap();
+
+        block_on(
+            repository.modify(Change::CreateAndSave {
+                thing: Place {
+                    name: "Bree".into(),
+                    ..Default::default()
+                }
+                .into(),
+            }),
+        )
+        .unwrap();
+
+        assert_eq!(
+            "`Gottfried` of `Bree`, friend of Potato Johnson {}",
+            block_on(render_links(
+                "{gottfried} of {Bree}, friend of {Potato Johnson} {}",
+                &repository,
+            )),
+        );
+
+        assert_eq!(
+            "no links here",
+            block_on(render_links("no links here", &repository)),
+        );
+    }
+
+    #[test]
+    fn annotate_mentions_test() {
+        let mut repository = Repository::new(MemoryDataStore::default());
+
+        block_on(
+            repository.modify(Change::Create {
+                thing: Npc {
+                    name: "Gottfried".into(),
+                    ..Default::default()
+                }
+                .into(),
+            }),
+        )
+        .unwrap();
+
+        block_on(
+            repository.modify(Change::CreateAndSave {
+                thing: Place {
+                    name: "Bree".into(),
+                    ..Default::default()
+                }
+                .into(),
+            }),
+        )
+        .unwrap();
+
+        assert_eq!(
+            "<span class=\"npc\">`Gottfried`</span> is in <span class=\"place\">`Bree`</span>. Use `undo` to reverse this, or ` to",
+            block_on(annotate_mentions(
+                "`Gottfried` is in `Bree`. Use `undo` to reverse this, or ` to",
+                &repository,
+            )),
+        );
+        assert_eq!(
+            "no links here",
+            block_on(annotate_mentions("no links here", &repository)),
+        );
+    }
+
+    #[test]
+    fn unescape_test() {
+        assert_eq!(
+            "~delete Gottfried~ \\",
+            unescape(&EscapedView::new("~delete Gottfried~ \\").to_string()),
+        );
+    }
+
+    #[test]
+    fn find_mentions_test() {
+        let mut repository = Repository::new(MemoryDataStore::default());
+
+        block_on(
+            repository.modify(Change::Create {
+                thing: Npc {
+                    name: "Gottfried".into(),
+                    ..Default::default()
+                }
+                .into(),
+            }),
+        )
+        .unwrap();
+
+        block_on(
+            repository.modify(Change::CreateAndSave {
+                thing: Place {
+                    name: "Bree".into(),
+                    ..Default::default()
+                }
+                .into(),
+            }),
+        )
+        .unwrap();
+
+        let names: Vec<String> = block_on(find_mentions(
+            "# Bree\n\n`Gottfried` is in `bree`. Use `undo` to meet `Gottfried` again.",
+            &repository,
+        ))
+        .iter()
+        .map(|thing| thing.name().to_string())
+        .collect();
+
+        assert_eq!(vec!["Bree", "Gottfried"], names);
+        assert!(block_on(find_mentions("# Potato Johnson", &repository)).is_empty());
+    }
+}
@@ -1,16 +1,41 @@
 pub mod demographics;
+pub mod inventory;
 pub mod npc;
 pub mod place;
+pub mod status;
 
-pub use command::{ParsedThing, WorldCommand};
+pub(crate) use command::{append_unknown_words_notice, parse_equipment};
+pub use command::{npc_terms, place_terms, ParsedThing, WorldCommand};
+pub(crate) use connections::find_connections;
 pub use demographics::Demographics;
 pub use field::Field;
+pub(crate) use grammar::{count, list, render};
+pub use inventory::Inventory;
+pub use link::{annotate_mentions, find_mentions, render_links, LinkView};
 pub use npc::{Npc, NpcRelations};
-pub use place::{Place, PlaceRelations, Uuid as PlaceUuid};
+pub use party::{party_location, party_location_notice};
+pub use place::{
+    festival_notices, skipped_festival_notices, upcoming_festivals, Place, PlaceRelations,
+    Uuid as PlaceUuid,
+};
+pub use status::{expire_statuses, status_expiries, Statuses};
 pub use thing::{Thing, ThingRelations};
+pub use word::WordRegistry;
 
+mod chances;
 mod command;
+mod connections;
+mod contact;
+mod downtime;
 mod field;
+mod grammar;
+mod link;
+mod party;
+mod prep;
+mod random;
+mod rest;
+mod rumor;
+mod talk;
 mod thing;
 mod word;
 
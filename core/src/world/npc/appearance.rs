@@ -0,0 +1,665 @@
+use super::{Age, Npc, Occupation, Species};
+use crate::world::weighted_index_from_tuple;
+use initiative_macros::WordList;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, WordList)]
+#[serde(into = "&'static str", try_from = "&str")]
+pub enum Build {
+    Athletic,
+    Burly,
+    Gaunt,
+
+    #[alias = "brawny"]
+    Muscular,
+
+    #[alias = "plump"]
+    #[alias = "stout"]
+    Portly,
+
+    #[alias = "slim"]
+    #[alias = "thin"]
+    Slender,
+
+    Stocky,
+    Wiry,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, WordList)]
+#[serde(into = "&'static str", try_from = "&str")]
+pub enum Hair {
+    AuburnHaired,
+
+    #[alias = "hairless"]
+    Bald,
+
+    BlackHaired,
+
+    #[alias = "blond"]
+    #[alias = "blonde"]
+    #[alias = "blonde-haired"]
+    #[alias = "fair-haired"]
+    BlondHaired,
+
+    #[alias = "brunette"]
+    BrownHaired,
+
+    #[alias = "gray-haired"]
+    GreyHaired,
+
+    #[alias = "redheaded"]
+    RedHaired,
+
+    SilverHaired,
+    WhiteHaired,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, WordList)]
+#[serde(into = "&'static str", try_from = "&str")]
+pub enum Eyes {
+    #[term = "amber-eyed"]
+    Amber,
+
+    #[term = "blue-eyed"]
+    Blue,
+
+    #[term = "brown-eyed"]
+    Brown,
+
+    #[term = "gold-eyed"]
+    #[alias = "golden-eyed"]
+    Gold,
+
+    #[term = "green-eyed"]
+    Green,
+
+    #[term = "grey-eyed"]
+    #[alias = "gray-eyed"]
+    Grey,
+
+    #[term = "hazel-eyed"]
+    Hazel,
+
+    #[term = "red-eyed"]
+    Red,
+
+    #[term = "violet-eyed"]
+    Violet,
+}
+
+/// A distinguishing mark, something that would stand out in a witness's description.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, WordList)]
+#[serde(into = "&'static str", try_from = "&str")]
+pub enum Mark {
+    Birthmarked,
+
+    #[alias = "crooked-nosed"]
+    BrokenNosed,
+
+    #[alias = "freckly"]
+    Freckled,
+
+    #[alias = "limping"]
+    Lame,
+
+    OneEyed,
+    Pierced,
+    Pockmarked,
+    Scarred,
+    Tattooed,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, WordList)]
+#[serde(into = "&'static str", try_from = "&str")]
+pub enum Clothing {
+    #[alias = "gaudy"]
+    Flamboyant,
+
+    #[alias = "well-dressed"]
+    Fine,
+
+    #[alias = "drab"]
+    Plain,
+
+    Practical,
+
+    #[alias = "tattered"]
+    Ragged,
+
+    Robed,
+    Uniformed,
+}
+
+impl Build {
+    /// Dwarves tend to be stocky and elves slender, and the elderly are more often gaunt. Children
+    /// are never burly or muscular.
+    pub fn generate(rng: &mut impl Rng, species: Option<&Species>, age: Option<&Age>) -> Self {
+        let mut weights = match species {
+            Some(Species::Dwarf) => vec![
+                (Build::Stocky, 5),
+                (Build::Burly, 4),
+                (Build::Muscular, 3),
+                (Build::Portly, 3),
+                (Build::Wiry, 1),
+            ],
+            Some(Species::Elf) => vec![
+                (Build::Slender, 6),
+                (Build::Athletic, 3),
+                (Build::Wiry, 3),
+                (Build::Gaunt, 1),
+            ],
+            Some(Species::Gnome | Species::Halfling) => vec![
+                (Build::Portly, 4),
+                (Build::Slender, 3),
+                (Build::Wiry, 3),
+                (Build::Stocky, 2),
+                (Build::Athletic, 1),
+            ],
+            Some(Species::Dragonborn | Species::HalfOrc) => vec![
+                (Build::Muscular, 5),
+                (Build::Burly, 4),
+                (Build::Athletic, 2),
+                (Build::Stocky, 2),
+            ],
+            _ => vec![
+                (Build::Slender, 3),
+                (Build::Athletic, 2),
+                (Build::Muscular, 2),
+                (Build::Portly, 2),
+                (Build::Stocky, 2),
+                (Build::Wiry, 2),
+                (Build::Burly, 1),
+                (Build::Gaunt, 1),
+            ],
+        };
+
+        match age {
+            Some(Age::Infant | Age::Child | Age::Adolescent) => {
+                weights.retain(|(build, _)| !matches!(build, Build::Burly | Build::Muscular));
+                weights.push((Build::Slender, 2));
+            }
+            Some(Age::Elderly | Age::Geriatric) => {
+                weights.retain(|(build, _)| !matches!(build, Build::Athletic | Build::Muscular));
+                weights.push((Build::Gaunt, 3));
+            }
+            _ => {}
+        }
+
+        *weighted_index_from_tuple(rng, &weights)
+    }
+}
+
+impl Hair {
+    /// Hair greys with age, except among elves, whose hair turns silver or white. Dragonborn have
+    /// no hair at all.
+    pub fn generate(rng: &mut impl Rng, species: Option<&Species>, age: Option<&Age>) -> Self {
+        let mut weights = match species {
+            Some(Species::Dragonborn) => return Hair::Bald,
+            Some(Species::Dwarf) => vec![
+                (Hair::BlackHaired, 3),
+                (Hair::BrownHaired, 3),
+                (Hair::RedHaired, 3),
+                (Hair::AuburnHaired, 2),
+                (Hair::BlondHaired, 1),
+            ],
+            Some(Species::Elf) => vec![
+                (Hair::BlondHaired, 3),
+                (Hair::AuburnHaired, 2),
+                (Hair::BlackHaired, 2),
+                (Hair::SilverHaired, 2),
+                (Hair::BrownHaired, 1),
+                (Hair::WhiteHaired, 1),
+            ],
+            Some(Species::HalfOrc) => vec![
+                (Hair::BlackHaired, 5),
+                (Hair::BrownHaired, 2),
+                (Hair::Bald, 1),
+            ],
+            Some(Species::Tiefling) => vec![
+                (Hair::BlackHaired, 4),
+                (Hair::RedHaired, 2),
+                (Hair::BrownHaired, 1),
+                (Hair::SilverHaired, 1),
+            ],
+            _ => vec![
+                (Hair::BrownHaired, 4),
+                (Hair::BlackHaired, 3),
+                (Hair::BlondHaired, 2),
+                (Hair::AuburnHaired, 1),
+                (Hair::Bald, 1),
+                (Hair::RedHaired, 1),
+            ],
+        };
+
+        let elf = species == Some(&Species::Elf);
+
+        match age {
+            Some(Age::Infant) => {
+                weights.retain(|(hair, _)| hair != &Hair::Bald);
+                weights.push((Hair::Bald, 6));
+            }
+            Some(Age::Child | Age::Adolescent | Age::YoungAdult) => {
+                weights.retain(|(hair, _)| hair != &Hair::Bald);
+            }
+            Some(Age::MiddleAged) if !elf => weights.push((Hair::GreyHaired, 3)),
+            Some(Age::Elderly) if elf => weights = vec![(Hair::SilverHaired, 1)],
+            Some(Age::Elderly) => {
+                weights.push((Hair::GreyHaired, 12));
+                weights.push((Hair::WhiteHaired, 4));
+                weights.push((Hair::Bald, 3));
+            }
+            Some(Age::Geriatric) if elf => weights = vec![(Hair::WhiteHaired, 1)],
+            Some(Age::Geriatric) => {
+                weights = vec![
+                    (Hair::WhiteHaired, 5),
+                    (Hair::Bald, 3),
+                    (Hair::GreyHaired, 2),
+                ]
+            }
+            _ => {}
+        }
+
+        *weighted_index_from_tuple(rng, &weights)
+    }
+
+    /// A description of the hair, eg. "red".
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::AuburnHaired => "auburn",
+            Self::Bald => "none",
+            Self::BlackHaired => "black",
+            Self::BlondHaired => "blond",
+            Self::BrownHaired => "brown",
+            Self::GreyHaired => "grey",
+            Self::RedHaired => "red",
+            Self::SilverHaired => "silver",
+            Self::WhiteHaired => "white",
+        }
+    }
+}
+
+impl Eyes {
+    pub fn generate(rng: &mut impl Rng, species: Option<&Species>) -> Self {
+        const DRAGONBORN: &[(Eyes, usize)] = &[(Eyes::Amber, 3), (Eyes::Gold, 3), (Eyes::Red, 2)];
+        const ELF: &[(Eyes, usize)] = &[
+            (Eyes::Blue, 3),
+            (Eyes::Green, 3),
+            (Eyes::Grey, 2),
+            (Eyes::Violet, 2),
+            (Eyes::Amber, 1),
+            (Eyes::Hazel, 1),
+        ];
+        const HALF_ORC: &[(Eyes, usize)] = &[
+            (Eyes::Brown, 4),
+            (Eyes::Grey, 2),
+            (Eyes::Amber, 1),
+            (Eyes::Red, 1),
+        ];
+        const TIEFLING: &[(Eyes, usize)] = &[
+            (Eyes::Gold, 3),
+            (Eyes::Red, 3),
+            (Eyes::Amber, 2),
+            (Eyes::Violet, 1),
+        ];
+        const DEFAULT: &[(Eyes, usize)] = &[
+            (Eyes::Brown, 6),
+            (Eyes::Blue, 3),
+            (Eyes::Green, 2),
+            (Eyes::Hazel, 2),
+            (Eyes::Grey, 1),
+        ];
+
+        *weighted_index_from_tuple(
+            rng,
+            match species {
+                Some(Species::Dragonborn) => DRAGONBORN,
+                Some(Species::Elf) => ELF,
+                Some(Species::HalfOrc) => HALF_ORC,
+                Some(Species::Tiefling) => TIEFLING,
+                _ => DEFAULT,
+            },
+        )
+    }
+
+    /// The colour of the eyes, eg. "blue".
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Amber => "amber",
+            Self::Blue => "blue",
+            Self::Brown => "brown",
+            Self::Gold => "gold",
+            Self::Green => "green",
+            Self::Grey => "grey",
+            Self::Hazel => "hazel",
+            Self::Red => "red",
+            Self::Violet => "violet",
+        }
+    }
+}
+
+impl Mark {
+    /// Most people have nothing remarkable about them. Marks accumulate with age, and those in
+    /// violent lines of work are more likely to be scarred.
+    pub fn generate(
+        rng: &mut impl Rng,
+        age: Option<&Age>,
+        occupation: Option<&Occupation>,
+    ) -> Option<Self> {
+        let mut weights = match age {
+            Some(Age::Infant | Age::Child) => {
+                vec![
+                    (None, 16),
+                    (Some(Mark::Birthmarked), 2),
+                    (Some(Mark::Freckled), 2),
+                ]
+            }
+            _ => vec![
+                (None, 20),
+                (Some(Mark::Freckled), 3),
+                (Some(Mark::Scarred), 3),
+                (Some(Mark::Tattooed), 3),
+                (Some(Mark::Birthmarked), 2),
+                (Some(Mark::BrokenNosed), 2),
+                (Some(Mark::Pierced), 2),
+                (Some(Mark::Pockmarked), 2),
+                (Some(Mark::Lame), 1),
+                (Some(Mark::OneEyed), 1),
+            ],
+        };
+
+        match age {
+            Some(Age::Elderly | Age::Geriatric) => {
+                weights.push((Some(Mark::Lame), 3));
+                weights.push((Some(Mark::Scarred), 2));
+            }
+            Some(Age::Adolescent) => weights.push((None, 10)),
+            _ => {}
+        }
+
+        if let Some(Occupation::Guard | Occupation::Soldier | Occupation::Thief) = occupation {
+            weights.push((Some(Mark::Scarred), 5));
+            weights.push((Some(Mark::BrokenNosed), 2));
+            weights.push((Some(Mark::OneEyed), 1));
+        }
+
+        *weighted_index_from_tuple(rng, &weights)
+    }
+
+    /// A description of the mark that reads naturally after "with", eg. "with a crooked nose".
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Birthmarked => "a prominent birthmark",
+            Self::BrokenNosed => "a crooked nose",
+            Self::Freckled => "freckles",
+            Self::Lame => "a pronounced limp",
+            Self::OneEyed => "only one eye",
+            Self::Pierced => "several piercings",
+            Self::Pockmarked => "a pockmarked face",
+            Self::Scarred => "a prominent scar",
+            Self::Tattooed => "visible tattoos",
+        }
+    }
+}
+
+impl Clothing {
+    /// Clothing mostly depends on what the wearer does for a living.
+    pub fn generate(rng: &mut impl Rng, occupation: Option<&Occupation>) -> Self {
+        const NOBLE: &[(Clothing, usize)] = &[(Clothing::Fine, 4), (Clothing::Flamboyant, 2)];
+        const MERCHANT: &[(Clothing, usize)] = &[
+            (Clothing::Fine, 3),
+            (Clothing::Practical, 2),
+            (Clothing::Flamboyant, 1),
+        ];
+        const MILITARY: &[(Clothing, usize)] =
+            &[(Clothing::Uniformed, 4), (Clothing::Practical, 1)];
+        const RELIGIOUS: &[(Clothing, usize)] = &[(Clothing::Robed, 4), (Clothing::Plain, 1)];
+        const LABOR: &[(Clothing, usize)] = &[
+            (Clothing::Practical, 3),
+            (Clothing::Plain, 2),
+            (Clothing::Ragged, 1),
+        ];
+        const SERVANT: &[(Clothing, usize)] = &[(Clothing::Plain, 3), (Clothing::Uniformed, 1)];
+        const THIEF: &[(Clothing, usize)] = &[
+            (Clothing::Plain, 2),
+            (Clothing::Ragged, 2),
+            (Clothing::Flamboyant, 1),
+        ];
+        const TRADE: &[(Clothing, usize)] = &[
+            (Clothing::Plain, 2),
+            (Clothing::Practical, 2),
+            (Clothing::Fine, 1),
+        ];
+        const DEFAULT: &[(Clothing, usize)] = &[
+            (Clothing::Plain, 4),
+            (Clothing::Practical, 2),
+            (Clothing::Ragged, 2),
+            (Clothing::Fine, 1),
+            (Clothing::Flamboyant, 1),
+        ];
+
+        *weighted_index_from_tuple(
+            rng,
+            match occupation {
+                Some(Occupation::Noble) => NOBLE,
+                Some(Occupation::Merchant) => MERCHANT,
+                Some(Occupation::Guard | Occupation::Soldier) => MILITARY,
+                Some(
+                    Occupation::Acolyte
+                    | Occupation::Cultist
+                    | Occupation::Priest
+                    | Occupation::Scholar,
+                ) => RELIGIOUS,
+                Some(
+                    Occupation::Baker
+                    | Occupation::Blacksmith
+                    | Occupation::Butcher
+                    | Occupation::Carpenter
+                    | Occupation::Farmer
+                    | Occupation::Fisher
+                    | Occupation::Hunter
+                    | Occupation::Laborer
+                    | Occupation::Miner
+                    | Occupation::Sailor,
+                ) => LABOR,
+                Some(Occupation::Servant) => SERVANT,
+                Some(Occupation::Thief) => THIEF,
+                Some(Occupation::Healer | Occupation::Innkeeper | Occupation::Tailor) => TRADE,
+                None => DEFAULT,
+            },
+        )
+    }
+
+    /// A description of the clothing that reads naturally after "wearing", eg. "wearing robes".
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Flamboyant => "flamboyant clothes",
+            Self::Fine => "fine clothes",
+            Self::Plain => "plain clothes",
+            Self::Practical => "practical work clothes",
+            Self::Ragged => "ragged clothes",
+            Self::Robed => "robes",
+            Self::Uniformed => "a uniform",
+        }
+    }
+}
+
+/// Generate any appearance details that weren't specified, based on the NPC's species, age, and
+/// occupation. Infants don't get a build, distinguishing mark, or clothing.
+pub fn regenerate(rng: &mut impl Rng, npc: &mut Npc) {
+    let species = npc.species.value().copied();
+    let age = npc.age.value().copied();
+    let occupation = npc.occupation.value().copied();
+
+    npc.hair
+        .replace_with(|_| Hair::generate(rng, species.as_ref(), age.as_ref()));
+    npc.eyes
+        .replace_with(|_| Eyes::generate(rng, species.as_ref()));
+
+    if age == Some(Age::Infant) {
+        npc.build.clear();
+        npc.mark.clear();
+        npc.clothing.clear();
+        return;
+    }
+
+    npc.build
+        .replace_with(|_| Build::generate(rng, species.as_ref(), age.as_ref()));
+    npc.clothing
+        .replace_with(|_| Clothing::generate(rng, occupation.as_ref()));
+
+    if npc.mark.is_unlocked() {
+        match Mark::generate(rng, age.as_ref(), occupation.as_ref()) {
+            Some(mark) => npc.mark.replace(mark),
+            None => npc.mark.clear(),
+        }
+    }
+}
+
+impl fmt::Display for Build {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl fmt::Display for Hair {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl fmt::Display for Eyes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl fmt::Display for Mark {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl fmt::Display for Clothing {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::world::Field;
+    use rand::prelude::*;
+
+    #[test]
+    fn generate_test_species() {
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        let (mut stocky_dwarves, mut stocky_elves) = (0, 0);
+        for _ in 0..1000 {
+            if Build::generate(&mut rng, Some(&Species::Dwarf), Some(&Age::Adult)) == Build::Stocky
+            {
+                stocky_dwarves += 1;
+            }
+            if Build::generate(&mut rng, Some(&Species::Elf), Some(&Age::Adult)) == Build::Stocky {
+                stocky_elves += 1;
+            }
+        }
+        assert!((200..400).contains(&stocky_dwarves), "{}", stocky_dwarves);
+        assert_eq!(0, stocky_elves);
+
+        for _ in 0..100 {
+            assert_eq!(
+                Hair::Bald,
+                Hair::generate(&mut rng, Some(&Species::Dragonborn), Some(&Age::Adult)),
+            );
+            assert!(!matches!(
+                Eyes::generate(&mut rng, Some(&Species::Tiefling)),
+                Eyes::Blue | Eyes::Brown,
+            ));
+        }
+    }
+
+    #[test]
+    fn generate_test_age() {
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        for _ in 0..100 {
+            assert!(!matches!(
+                Build::generate(&mut rng, Some(&Species::HalfOrc), Some(&Age::Child)),
+                Build::Burly | Build::Muscular,
+            ));
+            assert!(matches!(
+                Hair::generate(&mut rng, Some(&Species::Human), Some(&Age::Geriatric)),
+                Hair::WhiteHaired | Hair::Bald | Hair::GreyHaired,
+            ));
+            assert_eq!(
+                Hair::SilverHaired,
+                Hair::generate(&mut rng, Some(&Species::Elf), Some(&Age::Elderly)),
+            );
+            assert!(matches!(
+                Mark::generate(&mut rng, Some(&Age::Child), None),
+                None | Some(Mark::Birthmarked | Mark::Freckled),
+            ));
+        }
+    }
+
+    #[test]
+    fn generate_test_occupation() {
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        for _ in 0..100 {
+            assert!(matches!(
+                Clothing::generate(&mut rng, Some(&Occupation::Noble)),
+                Clothing::Fine | Clothing::Flamboyant,
+            ));
+        }
+    }
+
+    #[test]
+    fn regenerate_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        let mut npc = Npc {
+            species: Species::Human.into(),
+            age: Age::Adult.into(),
+            hair: Field::new(Hair::RedHaired),
+            ..Default::default()
+        };
+        regenerate(&mut rng, &mut npc);
+        assert!(npc.build.is_some());
+        assert!(npc.eyes.is_some());
+        assert!(npc.clothing.is_some());
+        assert_eq!(Some(&Hair::RedHaired), npc.hair.value());
+
+        npc.age = Age::Infant.into();
+        regenerate(&mut rng, &mut npc);
+        assert!(npc.build.is_none());
+        assert!(npc.clothing.is_none());
+        assert!(npc.mark.is_none());
+    }
+
+    #[test]
+    fn from_str_test() {
+        assert_eq!(Ok(Build::Portly), "stout".parse());
+        assert_eq!(Ok(Hair::RedHaired), "Red-Haired".parse());
+        assert_eq!(Ok(Hair::BlondHaired), "blonde".parse());
+        assert_eq!(Ok(Eyes::Grey), "gray-eyed".parse());
+        assert_eq!(Ok(Mark::OneEyed), "one-eyed".parse());
+        assert_eq!(Ok(Clothing::Ragged), "tattered".parse());
+        assert_eq!(Err(()), "potato".parse::<Build>());
+    }
+
+    #[test]
+    fn serialize_deserialize_test() {
+        assert_eq!(
+            r#""red-haired""#,
+            serde_json::to_string(&Hair::RedHaired).unwrap(),
+        );
+
+        let value: Hair = serde_json::from_str(r#""red-haired""#).unwrap();
+        assert_eq!(Hair::RedHaired, value);
+    }
+}
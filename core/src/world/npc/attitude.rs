@@ -0,0 +1,178 @@
+use initiative_macros::WordList;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// An NPC's current attitude toward the party, from worst to best.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize, WordList)]
+#[serde(into = "&'static str", try_from = "&str")]
+pub enum Attitude {
+    Hostile,
+    Unfriendly,
+
+    #[alias = "neutral"]
+    Indifferent,
+
+    Friendly,
+    Helpful,
+}
+
+/// The outcome of a reaction roll: the dice rolled, the modifier applied for the NPC's existing
+/// attitude, and the resulting attitude.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Reaction {
+    pub dice: [u8; 2],
+    pub modifier: i8,
+    pub attitude: Attitude,
+}
+
+impl Attitude {
+    /// An NPC's existing attitude colours their reaction, so a friendly NPC is likelier to stay
+    /// friendly and a hostile one likelier to stay hostile.
+    pub fn reaction_modifier(attitude: Option<&Self>) -> i8 {
+        match attitude {
+            Some(Self::Hostile) => -4,
+            Some(Self::Unfriendly) => -2,
+            Some(Self::Indifferent) | None => 0,
+            Some(Self::Friendly) => 2,
+            Some(Self::Helpful) => 4,
+        }
+    }
+
+    /// The attitude resulting from a modified 2d6 reaction roll.
+    pub fn from_reaction_roll(total: i8) -> Self {
+        match total {
+            i8::MIN..=2 => Self::Hostile,
+            3..=5 => Self::Unfriendly,
+            6..=8 => Self::Indifferent,
+            9..=11 => Self::Friendly,
+            12.. => Self::Helpful,
+        }
+    }
+}
+
+impl Reaction {
+    /// Roll 2d6 for an NPC's reaction to the party, modified by their existing attitude.
+    pub fn roll(rng: &mut impl Rng, attitude: Option<&Attitude>) -> Self {
+        let dice = [rng.gen_range(1..=6), rng.gen_range(1..=6)];
+        let modifier = Attitude::reaction_modifier(attitude);
+
+        Self {
+            dice,
+            modifier,
+            attitude: Attitude::from_reaction_roll(
+                dice.iter().map(|&d| d as i8).sum::<i8>() + modifier,
+            ),
+        }
+    }
+
+    pub fn total(&self) -> i8 {
+        self.dice.iter().map(|&d| d as i8).sum::<i8>() + self.modifier
+    }
+}
+
+impl fmt::Display for Attitude {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl fmt::Display for Reaction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "`2d6` ({} + {})", self.dice[0], self.dice[1])?;
+
+        match self.modifier {
+            0 => {}
+            m if m > 0 => write!(f, " + {}", m)?,
+            m => write!(f, " - {}", -m)?,
+        }
+
+        write!(f, " = {}", self.total())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::prelude::*;
+
+    #[test]
+    fn from_reaction_roll_test() {
+        assert_eq!(Attitude::Hostile, Attitude::from_reaction_roll(-2));
+        assert_eq!(Attitude::Hostile, Attitude::from_reaction_roll(2));
+        assert_eq!(Attitude::Unfriendly, Attitude::from_reaction_roll(5));
+        assert_eq!(Attitude::Indifferent, Attitude::from_reaction_roll(7));
+        assert_eq!(Attitude::Friendly, Attitude::from_reaction_roll(9));
+        assert_eq!(Attitude::Helpful, Attitude::from_reaction_roll(12));
+        assert_eq!(Attitude::Helpful, Attitude::from_reaction_roll(16));
+    }
+
+    #[test]
+    fn roll_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        for _ in 0..100 {
+            let reaction = Reaction::roll(&mut rng, Some(&Attitude::Helpful));
+            assert_eq!(4, reaction.modifier);
+            assert!(reaction.attitude >= Attitude::Unfriendly, "{:?}", reaction);
+            assert_eq!(
+                Attitude::from_reaction_roll(reaction.total()),
+                reaction.attitude,
+            );
+
+            let reaction = Reaction::roll(&mut rng, None);
+            assert_eq!(0, reaction.modifier);
+            assert!(reaction.dice.iter().all(|d| (1..=6).contains(d)));
+        }
+    }
+
+    #[test]
+    fn display_test() {
+        assert_eq!(
+            "`2d6` (3 + 4) = 7",
+            Reaction {
+                dice: [3, 4],
+                modifier: 0,
+                attitude: Attitude::Indifferent,
+            }
+            .to_string(),
+        );
+        assert_eq!(
+            "`2d6` (6 + 5) + 2 = 13",
+            Reaction {
+                dice: [6, 5],
+                modifier: 2,
+                attitude: Attitude::Helpful,
+            }
+            .to_string(),
+        );
+        assert_eq!(
+            "`2d6` (1 + 1) - 4 = -2",
+            Reaction {
+                dice: [1, 1],
+                modifier: -4,
+                attitude: Attitude::Hostile,
+            }
+            .to_string(),
+        );
+        assert_eq!("indifferent", Attitude::Indifferent.to_string());
+    }
+
+    #[test]
+    fn from_str_test() {
+        assert_eq!(Ok(Attitude::Indifferent), "Neutral".parse());
+        assert_eq!(Ok(Attitude::Helpful), "helpful".parse());
+        assert_eq!(Err(()), "potato".parse::<Attitude>());
+    }
+
+    #[test]
+    fn serialize_deserialize_test() {
+        assert_eq!(
+            r#""unfriendly""#,
+            serde_json::to_string(&Attitude::Unfriendly).unwrap(),
+        );
+
+        let value: Attitude = serde_json::from_str(r#""unfriendly""#).unwrap();
+        assert_eq!(Attitude::Unfriendly, value);
+    }
+}
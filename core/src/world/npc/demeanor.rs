@@ -0,0 +1,64 @@
+use initiative_macros::WordList;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A character trait that colours how an NPC carries themself, whoever they're talking to.
+/// Unlike their attitude, it isn't specific to the party.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, WordList)]
+#[serde(into = "&'static str", try_from = "&str")]
+pub enum Demeanor {
+    Arrogant,
+    Cheerful,
+    Curious,
+
+    #[alias = "grouchy"]
+    Grumpy,
+
+    Jovial,
+    Lazy,
+
+    #[alias = "anxious"]
+    Nervous,
+
+    Pious,
+
+    #[alias = "timid"]
+    Shy,
+
+    Stern,
+    Suspicious,
+}
+
+impl fmt::Display for Demeanor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn from_str_test() {
+        assert_eq!(Ok(Demeanor::Grumpy), "Grouchy".parse());
+        assert_eq!(Ok(Demeanor::Shy), "shy".parse());
+        assert_eq!(Err(()), "potato".parse::<Demeanor>());
+    }
+
+    #[test]
+    fn display_test() {
+        assert_eq!("grumpy", Demeanor::Grumpy.to_string());
+    }
+
+    #[test]
+    fn serialize_deserialize_test() {
+        assert_eq!(
+            r#""suspicious""#,
+            serde_json::to_string(&Demeanor::Suspicious).unwrap(),
+        );
+
+        let value: Demeanor = serde_json::from_str(r#""suspicious""#).unwrap();
+        assert_eq!(Demeanor::Suspicious, value);
+    }
+}
@@ -162,13 +162,40 @@ impl GenerateSimple for Ethnicity {
 }
 
 impl Generate for Ethnicity {
-    fn gen_name(rng: &mut impl Rng, _age: &Age, gender: &Gender) -> String {
+    fn gen_name(rng: &mut impl Rng, age: &Age, gender: &Gender) -> String {
+        let fname = Self::gen_fname_simple(rng, gender);
+
         format!(
             "{} {}",
-            Self::gen_fname_simple(rng, gender),
+            Self::fname_for_age(rng, age, gender, fname),
             Self::gen_lname_simple(rng),
         )
     }
+
+    /// Dragonborn children go by a nickname describing their habits rather than a shortened name.
+    fn diminutive(rng: &mut impl Rng, _fname: &str) -> Option<String> {
+        Some(
+            [
+                "Climber",
+                "Pebble",
+                "Scaleshine",
+                "Sparkbreath",
+                "Tailchaser",
+                "Wingflap",
+            ]
+            .choose(rng)
+            .unwrap()
+            .to_string(),
+        )
+    }
+
+    fn archaic_fnames(gender: &Gender) -> &'static [(&'static str, usize)] {
+        match gender {
+            Gender::Feminine => &[("Sorathi", 1), ("Thavira", 1), ("Vyrnessa", 1)],
+            Gender::Masculine => &[("Kethrinax", 1), ("Szorvath", 1), ("Vorthalax", 1)],
+            _ => &[],
+        }
+    }
 }
 
 #[cfg(test)]
@@ -191,13 +191,37 @@ impl GenerateSimple for Ethnicity {
 }
 
 impl Generate for Ethnicity {
-    fn gen_name(rng: &mut impl Rng, _age: &Age, gender: &Gender) -> String {
+    fn gen_name(rng: &mut impl Rng, age: &Age, gender: &Gender) -> String {
+        let fname = Self::gen_fname_simple(rng, gender);
+
         format!(
             "{} {}",
-            Self::gen_fname_simple(rng, gender),
+            Self::fname_for_age(rng, age, gender, fname),
             Self::gen_lname_simple(rng),
         )
     }
+
+    fn diminutive_suffixes() -> &'static [(&'static str, usize)] {
+        &[("i", 2), ("li", 1)]
+    }
+
+    fn archaic_fnames(gender: &Gender) -> &'static [(&'static str, usize)] {
+        match gender {
+            Gender::Feminine => &[
+                ("Brunhild", 1),
+                ("Gunnlaug", 1),
+                ("Hildra", 1),
+                ("Sigrun", 1),
+            ],
+            Gender::Masculine => &[
+                ("Baldrek", 1),
+                ("Grimmar", 1),
+                ("Hrodgar", 1),
+                ("Thorgrum", 1),
+            ],
+            _ => &[],
+        }
+    }
 }
 
 #[cfg(test)]
@@ -212,11 +212,22 @@ impl Generate for Ethnicity {
                         Self::syllable_fname_last(),
                     )
                 }
-                _ => Self::gen_fname_simple(rng, gender),
+                _ => {
+                    let fname = Self::gen_fname_simple(rng, gender);
+                    Self::fname_for_age(rng, age, gender, fname)
+                }
             },
             Self::gen_lname_simple(rng),
         )
     }
+
+    fn archaic_fnames(gender: &Gender) -> &'static [(&'static str, usize)] {
+        match gender {
+            Gender::Feminine => &[("Aelwynne", 1), ("Caladriel", 1), ("Ithrien", 1)],
+            Gender::Masculine => &[("Aelthas", 1), ("Caelorn", 1), ("Ithilorn", 1)],
+            _ => &[],
+        }
+    }
 }
 
 #[cfg(test)]
@@ -183,13 +183,27 @@ impl GenerateSimple for Ethnicity {
 }
 
 impl Generate for Ethnicity {
-    fn gen_name(rng: &mut impl Rng, _age: &Age, gender: &Gender) -> String {
+    fn gen_name(rng: &mut impl Rng, age: &Age, gender: &Gender) -> String {
+        let fname = Self::gen_fname_simple(rng, gender);
+
         format!(
             "{} {}",
-            Self::gen_fname_simple(rng, gender),
+            Self::fname_for_age(rng, age, gender, fname),
             Self::gen_lname_simple(rng),
         )
     }
+
+    fn diminutive_suffixes() -> &'static [(&'static str, usize)] {
+        &[("y", 1), ("kin", 1), ("bit", 1)]
+    }
+
+    fn archaic_fnames(gender: &Gender) -> &'static [(&'static str, usize)] {
+        match gender {
+            Gender::Feminine => &[("Marvalda", 1), ("Tillibeth", 1), ("Winnifrell", 1)],
+            Gender::Masculine => &[("Bimbleton", 1), ("Fizzwick", 1), ("Oldrin", 1)],
+            _ => &[],
+        }
+    }
 }
 
 #[cfg(test)]
@@ -190,13 +190,27 @@ impl GenerateSimple for Ethnicity {
 }
 
 impl Generate for Ethnicity {
-    fn gen_name(rng: &mut impl Rng, _age: &Age, gender: &Gender) -> String {
+    fn gen_name(rng: &mut impl Rng, age: &Age, gender: &Gender) -> String {
+        let fname = Self::gen_fname_simple(rng, gender);
+
         format!(
             "{} {}",
-            Self::gen_fname_simple(rng, gender),
+            Self::fname_for_age(rng, age, gender, fname),
             Self::gen_lname_simple(rng),
         )
     }
+
+    fn diminutive_suffixes() -> &'static [(&'static str, usize)] {
+        &[("o", 2), ("y", 1), ("kin", 1)]
+    }
+
+    fn archaic_fnames(gender: &Gender) -> &'static [(&'static str, usize)] {
+        match gender {
+            Gender::Feminine => &[("Myrtle", 1), ("Petunia", 1), ("Primrose", 1)],
+            Gender::Masculine => &[("Bartholo", 1), ("Mungo", 1), ("Wilcome", 1)],
+            _ => &[],
+        }
+    }
 }
 
 #[cfg(test)]
@@ -195,13 +195,43 @@ impl GenerateSimple for Ethnicity {
 }
 
 impl Generate for Ethnicity {
-    fn gen_name(rng: &mut impl Rng, _age: &Age, gender: &Gender) -> String {
+    fn gen_name(rng: &mut impl Rng, age: &Age, gender: &Gender) -> String {
+        let fname = Self::gen_fname_simple(rng, gender);
+
         format!(
             "{} {}",
-            Self::gen_fname_simple(rng, gender),
+            Self::fname_for_age(rng, age, gender, fname),
             Self::gen_lname_simple(rng),
         )
     }
+
+    fn diminutive_suffixes() -> &'static [(&'static str, usize)] {
+        &[("y", 3), ("ie", 2)]
+    }
+
+    fn archaic_fnames(gender: &Gender) -> &'static [(&'static str, usize)] {
+        match gender {
+            Gender::Feminine => &[
+                ("Agatha", 1),
+                ("Beatrix", 1),
+                ("Edith", 1),
+                ("Ermengarde", 1),
+                ("Hilda", 1),
+                ("Matilda", 1),
+                ("Winifred", 1),
+            ],
+            Gender::Masculine => &[
+                ("Aldous", 1),
+                ("Bertram", 1),
+                ("Cuthbert", 1),
+                ("Edmund", 1),
+                ("Godric", 1),
+                ("Oswin", 1),
+                ("Wilfred", 1),
+            ],
+            _ => &[],
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,94 @@
+use super::{Age, Gender, Generate, GenerateFromWords, SurnameConvention};
+use rand::prelude::*;
+
+pub struct Ethnicity;
+
+impl GenerateFromWords for Ethnicity {
+    #[rustfmt::skip]
+    fn word_fname_f() -> &'static [(&'static str, usize)] {
+        &[
+            ("Bopha", 1), ("Channary", 1), ("Chenda", 1), ("Kolab", 1), ("Kunthea", 1),
+            ("Malis", 1), ("Maly", 1), ("Phalla", 1), ("Sophea", 1), ("Sreymom", 1), ("Sreypov", 1),
+            ("Thida", 1),
+        ]
+    }
+
+    #[rustfmt::skip]
+    fn word_fname_m() -> &'static [(&'static str, usize)] {
+        &[
+            ("Bunthoeun", 1), ("Dara", 1), ("Kosal", 1), ("Piseth", 1), ("Rithy", 1), ("Rotha", 1),
+            ("Sambath", 1), ("Samnang", 1), ("Sophal", 1), ("Vannak", 1), ("Veasna", 1),
+            ("Visal", 1), ("Vuthy", 1),
+        ]
+    }
+
+    #[rustfmt::skip]
+    fn word_fname() -> &'static [(&'static str, usize)] {
+        &[
+            ("Chanthy", 1), ("Rachana", 1), ("Sokha", 1), ("Sophy", 1), ("Sovann", 1), ("Vanna", 1),
+        ]
+    }
+
+    #[rustfmt::skip]
+    fn word_lname() -> &'static [(&'static str, usize)] {
+        &[
+            ("Chea", 1), ("Chhim", 1), ("Heng", 1), ("Keo", 1), ("Kim", 1), ("Ly", 1), ("Meas", 1),
+            ("Nhem", 1), ("Ouk", 1), ("Pen", 1), ("Prak", 1), ("Seng", 1), ("Sim", 1), ("Sok", 1),
+            ("Som", 1), ("Touch", 1), ("Yim", 1),
+        ]
+    }
+
+    fn surname_convention() -> SurnameConvention {
+        SurnameConvention::FamilyGiven
+    }
+}
+
+impl Generate for Ethnicity {
+    fn gen_name(rng: &mut impl Rng, age: &Age, gender: &Gender) -> String {
+        Self::gen_name_from_words(rng, age, gender)
+    }
+}
+
+#[cfg(test)]
+mod test_generate_for_ethnicity {
+    use super::*;
+    use crate::world::npc::ethnicity::{regenerate, Ethnicity};
+    use crate::world::Npc;
+
+    #[test]
+    fn gen_name_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let adult = Age::Adult;
+        let m = Gender::Masculine;
+        let f = Gender::Feminine;
+        let t = Gender::NonBinaryThey;
+
+        assert_eq!(
+            [
+                "Touch Rotha",
+                "Kim Vuthy",
+                "Ly Thida",
+                "Sim Sreymom",
+                "Keo Sophy",
+                "Touch Rachana",
+            ],
+            [
+                gen_name(&mut rng, &adult, &m),
+                gen_name(&mut rng, &adult, &m),
+                gen_name(&mut rng, &adult, &f),
+                gen_name(&mut rng, &adult, &f),
+                gen_name(&mut rng, &adult, &t),
+                gen_name(&mut rng, &adult, &t),
+            ],
+        );
+    }
+
+    fn gen_name(rng: &mut impl Rng, age: &Age, gender: &Gender) -> String {
+        let mut npc = Npc::default();
+        npc.gender.replace(*gender);
+        npc.age.replace(*age);
+        npc.ethnicity.replace(Ethnicity::Khmer);
+        regenerate(rng, &mut npc);
+        format!("{}", npc.name)
+    }
+}
@@ -0,0 +1,100 @@
+use super::{Age, Gender, Generate, GenerateFromWords, SurnameConvention};
+use rand::prelude::*;
+
+pub struct Ethnicity;
+
+impl GenerateFromWords for Ethnicity {
+    #[rustfmt::skip]
+    fn word_fname_f() -> &'static [(&'static str, usize)] {
+        &[
+            ("Anahera", 1), ("Aroha", 1), ("Hana", 1), ("Hine", 1), ("Kahurangi", 1), ("Kiri", 1),
+            ("Marama", 1), ("Mere", 1), ("Ngaio", 1), ("Pania", 1), ("Rangimarie", 1),
+        ]
+    }
+
+    #[rustfmt::skip]
+    fn word_fname_m() -> &'static [(&'static str, usize)] {
+        &[
+            ("Hemi", 1), ("Hone", 1), ("Ihaia", 1), ("Manaia", 1), ("Matiu", 1), ("Nikau", 1),
+            ("Pita", 1), ("Rawiri", 1), ("Tama", 1), ("Tamati", 1), ("Tane", 1), ("Wiremu", 1),
+        ]
+    }
+
+    #[rustfmt::skip]
+    fn word_fname() -> &'static [(&'static str, usize)] {
+        &[
+            ("Huia", 1), ("Kauri", 1), ("Moana", 1), ("Rangi", 1), ("Tui", 1), ("Whetu", 1),
+        ]
+    }
+
+    #[rustfmt::skip]
+    fn word_lname() -> &'static [(&'static str, usize)] {
+        &[
+            ("Henare", 1), ("Horomona", 1), ("Kereama", 1), ("Ngata", 1), ("Parata", 1),
+            ("Pomare", 1), ("Potae", 1), ("Rewi", 1), ("Tamihana", 1), ("Te Awa", 1),
+            ("Te Huia", 1), ("Te Rangi", 1), ("Tipene", 1), ("Waaka", 1),
+        ]
+    }
+
+    fn surname_convention() -> SurnameConvention {
+        SurnameConvention::GivenFamily
+    }
+}
+
+impl Generate for Ethnicity {
+    fn gen_name(rng: &mut impl Rng, age: &Age, gender: &Gender) -> String {
+        Self::gen_name_from_words(rng, age, gender)
+    }
+
+    fn archaic_fnames(gender: &Gender) -> &'static [(&'static str, usize)] {
+        match gender {
+            Gender::Feminine => &[("Huhana", 1), ("Mere", 1), ("Riria", 1)],
+            Gender::Masculine => &[("Heremaia", 1), ("Hoani", 1), ("Rapata", 1)],
+            _ => &[],
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_generate_for_ethnicity {
+    use super::*;
+    use crate::world::npc::ethnicity::{regenerate, Ethnicity};
+    use crate::world::Npc;
+
+    #[test]
+    fn gen_name_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let adult = Age::Adult;
+        let m = Gender::Masculine;
+        let f = Gender::Feminine;
+        let t = Gender::NonBinaryThey;
+
+        assert_eq!(
+            [
+                "Nikau Potae",
+                "Nikau Tipene",
+                "Rangimarie Tamihana",
+                "Kiri Pomare",
+                "Kauri Waaka",
+                "Huia Ngata",
+            ],
+            [
+                gen_name(&mut rng, &adult, &m),
+                gen_name(&mut rng, &adult, &m),
+                gen_name(&mut rng, &adult, &f),
+                gen_name(&mut rng, &adult, &f),
+                gen_name(&mut rng, &adult, &t),
+                gen_name(&mut rng, &adult, &t),
+            ],
+        );
+    }
+
+    fn gen_name(rng: &mut impl Rng, age: &Age, gender: &Gender) -> String {
+        let mut npc = Npc::default();
+        npc.gender.replace(*gender);
+        npc.age.replace(*age);
+        npc.ethnicity.replace(Ethnicity::Maori);
+        regenerate(rng, &mut npc);
+        format!("{}", npc.name)
+    }
+}
@@ -4,8 +4,12 @@ mod elvish;
 mod gnomish;
 mod halfling;
 mod human;
+mod khmer;
+mod maori;
 mod orcish;
+mod quechua;
 mod tiefling;
+mod yoruba;
 
 use super::{Age, Gender, Npc, Species};
 use crate::world::weighted_index_from_tuple;
@@ -25,6 +29,13 @@ pub enum Ethnicity {
     Halfling,
     Human,
     Tiefling,
+
+    Khmer,
+
+    #[alias = "māori"]
+    Maori,
+    Quechua,
+    Yoruba,
 }
 
 impl Ethnicity {
@@ -38,10 +49,26 @@ impl Ethnicity {
             Self::Orcish => Species::HalfOrc,
             Self::Halfling => Species::Halfling,
             Self::Tiefling => Species::Tiefling,
+            Self::Khmer | Self::Maori | Self::Quechua | Self::Yoruba => Species::Human,
         }
     }
 }
 
+/// How a full name is assembled for ethnicities whose names are drawn from lists of real names
+/// rather than generated from syllables.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum SurnameConvention {
+    /// The given name followed by the family name, eg. "Adebayo Ogunleye".
+    GivenFamily,
+
+    /// The family name followed by the given name, eg. "Sok Dara".
+    FamilyGiven,
+
+    /// The given name followed by the father's and mother's family names, eg. "Amaru Mamani
+    /// Quispe".
+    GivenPaternalMaternal,
+}
+
 trait Generate {
     fn regenerate(rng: &mut impl Rng, npc: &mut Npc) {
         if let (Some(gender), Some(age)) = (npc.gender.value(), npc.age.value()) {
@@ -50,6 +77,44 @@ trait Generate {
     }
 
     fn gen_name(rng: &mut impl Rng, age: &Age, gender: &Gender) -> String;
+
+    /// Endings added to the first syllable of a child's name to form the diminutive they go by,
+    /// eg. "y" turns "Tomas" into "Tommy". Children always go by their full name if there are none.
+    fn diminutive_suffixes() -> &'static [(&'static str, usize)] {
+        &[]
+    }
+
+    /// Old-fashioned given names, which elderly characters sometimes have in place of a generated
+    /// name.
+    fn archaic_fnames(_gender: &Gender) -> &'static [(&'static str, usize)] {
+        &[]
+    }
+
+    /// The diminutive that a child named `fname` goes by, if any.
+    fn diminutive(rng: &mut impl Rng, fname: &str) -> Option<String> {
+        if Self::diminutive_suffixes().is_empty() {
+            None
+        } else {
+            let suffix = *weighted_index_from_tuple(rng, Self::diminutive_suffixes());
+            Some(diminutive(fname, suffix))
+        }
+    }
+
+    /// Adapt a generated given name to the character's age. Half of children go by a diminutive,
+    /// and half of elderly characters have an old-fashioned name instead.
+    fn fname_for_age(rng: &mut impl Rng, age: &Age, gender: &Gender, fname: String) -> String {
+        match age {
+            Age::Infant | Age::Child if rng.gen_bool(0.5) => {
+                Self::diminutive(rng, &fname).unwrap_or(fname)
+            }
+            Age::Elderly | Age::Geriatric
+                if !Self::archaic_fnames(gender).is_empty() && rng.gen_bool(0.5) =>
+            {
+                weighted_index_from_tuple(rng, Self::archaic_fnames(gender)).to_string()
+            }
+            _ => fname,
+        }
+    }
 }
 
 trait GenerateSimple {
@@ -112,6 +177,39 @@ trait GenerateSimple {
     fn word_lname_last() -> &'static [(&'static str, usize)];
 }
 
+trait GenerateFromWords: Generate {
+    fn gen_name_from_words(rng: &mut impl Rng, age: &Age, gender: &Gender) -> String {
+        let fname = weighted_index_from_tuple(
+            rng,
+            match gender {
+                Gender::Feminine => Self::word_fname_f(),
+                Gender::Masculine => Self::word_fname_m(),
+                _ => Self::word_fname(),
+            },
+        )
+        .to_string();
+        let fname = Self::fname_for_age(rng, age, gender, fname);
+        let lname = weighted_index_from_tuple(rng, Self::word_lname());
+
+        match Self::surname_convention() {
+            SurnameConvention::GivenFamily => format!("{} {}", fname, lname),
+            SurnameConvention::FamilyGiven => format!("{} {}", lname, fname),
+            SurnameConvention::GivenPaternalMaternal => format!(
+                "{} {} {}",
+                fname,
+                lname,
+                weighted_index_from_tuple(rng, Self::word_lname()),
+            ),
+        }
+    }
+
+    fn word_fname_f() -> &'static [(&'static str, usize)];
+    fn word_fname_m() -> &'static [(&'static str, usize)];
+    fn word_fname() -> &'static [(&'static str, usize)];
+    fn word_lname() -> &'static [(&'static str, usize)];
+    fn surname_convention() -> SurnameConvention;
+}
+
 pub fn regenerate(rng: &mut impl Rng, npc: &mut Npc) {
     if let Some(ethnicity) = npc.ethnicity.value() {
         match ethnicity {
@@ -123,6 +221,10 @@ pub fn regenerate(rng: &mut impl Rng, npc: &mut Npc) {
             Ethnicity::Halfling => halfling::Ethnicity::regenerate(rng, npc),
             Ethnicity::Human => human::Ethnicity::regenerate(rng, npc),
             Ethnicity::Tiefling => tiefling::Ethnicity::regenerate(rng, npc),
+            Ethnicity::Khmer => khmer::Ethnicity::regenerate(rng, npc),
+            Ethnicity::Maori => maori::Ethnicity::regenerate(rng, npc),
+            Ethnicity::Quechua => quechua::Ethnicity::regenerate(rng, npc),
+            Ethnicity::Yoruba => yoruba::Ethnicity::regenerate(rng, npc),
         }
     }
 }
@@ -138,8 +240,41 @@ impl fmt::Display for Ethnicity {
             Self::Halfling => write!(f, "halfling"),
             Self::Human => write!(f, "human"),
             Self::Tiefling => write!(f, "tiefling"),
+            Self::Khmer => write!(f, "khmer"),
+            Self::Maori => write!(f, "māori"),
+            Self::Quechua => write!(f, "quechua"),
+            Self::Yoruba => write!(f, "yoruba"),
+        }
+    }
+}
+
+/// Shorten a name to its first syllable and add the suffix, doubling the final consonant before
+/// the English "-y" and "-ie" endings, eg. "Tomas" and "y" give "Tommy".
+fn diminutive(fname: &str, suffix: &str) -> String {
+    let is_vowel = |c: char| "aeiouyAEIOUY".contains(c);
+    let chars: Vec<char> = fname.chars().collect();
+
+    let mut end = chars
+        .iter()
+        .position(|c| is_vowel(*c))
+        .unwrap_or(chars.len());
+    while end < chars.len() && is_vowel(chars[end]) {
+        end += 1;
+    }
+
+    let mut result: String = chars[..end].iter().collect();
+    if let Some(&c) = chars
+        .get(end)
+        .filter(|c| c.is_alphabetic() && !is_vowel(**c))
+    {
+        result.push(c);
+        if suffix.starts_with('y') || suffix == "ie" {
+            result.push(c);
         }
     }
+
+    result.push_str(suffix);
+    result
 }
 
 fn gen_name(
@@ -180,6 +315,10 @@ mod test {
         assert_eq!(Species::Halfling, Ethnicity::Halfling.default_species());
         assert_eq!(Species::Human, Ethnicity::Human.default_species());
         assert_eq!(Species::Tiefling, Ethnicity::Tiefling.default_species());
+        assert_eq!(Species::Human, Ethnicity::Khmer.default_species());
+        assert_eq!(Species::Human, Ethnicity::Maori.default_species());
+        assert_eq!(Species::Human, Ethnicity::Quechua.default_species());
+        assert_eq!(Species::Human, Ethnicity::Yoruba.default_species());
     }
 
     #[test]
@@ -193,6 +332,46 @@ mod test {
         assert_eq!(Ethnicity::Elvish, value);
     }
 
+    #[test]
+    fn fname_for_age_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let gender = Gender::Feminine;
+        let fname = || "Rosalind".to_string();
+
+        for _ in 0..10 {
+            assert_eq!(
+                "Rosalind",
+                human::Ethnicity::fname_for_age(&mut rng, &Age::Adult, &gender, fname()),
+            );
+        }
+
+        let infant_names: Vec<String> = (0..10)
+            .map(|_| human::Ethnicity::fname_for_age(&mut rng, &Age::Infant, &gender, fname()))
+            .collect();
+        assert!(infant_names.iter().any(|s| s == "Rosalind"));
+        assert!(infant_names
+            .iter()
+            .any(|s| s.starts_with("Ros") && s != "Rosalind"));
+
+        let archaic_fnames = human::Ethnicity::archaic_fnames(&gender);
+        let geriatric_names: Vec<String> = (0..10)
+            .map(|_| human::Ethnicity::fname_for_age(&mut rng, &Age::Geriatric, &gender, fname()))
+            .collect();
+        assert!(geriatric_names.iter().any(|s| s == "Rosalind"));
+        assert!(geriatric_names
+            .iter()
+            .any(|s| archaic_fnames.iter().any(|(name, _)| name == s)));
+    }
+
+    #[test]
+    fn diminutive_test() {
+        assert_eq!("Tommy", diminutive("Tomas", "y"));
+        assert_eq!("Thori", diminutive("Thorgrim", "i"));
+        assert_eq!("Kitty", diminutive("Kit", "y"));
+        assert_eq!("Adkin", diminutive("Adebayo", "kin"));
+        assert_eq!("Bo", diminutive("Bo", ""));
+    }
+
     #[test]
     fn generate_name_test() {
         let mut rng = SmallRng::seed_from_u64(0);
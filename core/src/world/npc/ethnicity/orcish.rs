@@ -153,13 +153,27 @@ impl GenerateSimple for Ethnicity {
 }
 
 impl Generate for Ethnicity {
-    fn gen_name(rng: &mut impl Rng, _age: &Age, gender: &Gender) -> String {
+    fn gen_name(rng: &mut impl Rng, age: &Age, gender: &Gender) -> String {
+        let fname = Self::gen_fname_simple(rng, gender);
+
         format!(
             "{} {}",
-            Self::gen_fname_simple(rng, gender),
+            Self::fname_for_age(rng, age, gender, fname),
             Self::gen_lname_simple(rng),
         )
     }
+
+    fn diminutive_suffixes() -> &'static [(&'static str, usize)] {
+        &[("ug", 1), ("ak", 1)]
+    }
+
+    fn archaic_fnames(gender: &Gender) -> &'static [(&'static str, usize)] {
+        match gender {
+            Gender::Feminine => &[("Grazhak", 1), ("Ushbara", 1), ("Volga", 1)],
+            Gender::Masculine => &[("Krugmar", 1), ("Ulgrath", 1), ("Vorgash", 1)],
+            _ => &[],
+        }
+    }
 }
 
 #[cfg(test)]
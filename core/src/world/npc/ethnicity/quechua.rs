@@ -0,0 +1,105 @@
+use super::{Age, Gender, Generate, GenerateFromWords, SurnameConvention};
+use rand::prelude::*;
+
+pub struct Ethnicity;
+
+impl GenerateFromWords for Ethnicity {
+    #[rustfmt::skip]
+    fn word_fname_f() -> &'static [(&'static str, usize)] {
+        &[
+            ("Ch'aska", 1), ("Killa", 1), ("Qoyllur", 1), ("Sisa", 1), ("Sumaq", 1), ("Suyana", 1),
+            ("Urpi", 1),
+        ]
+    }
+
+    #[rustfmt::skip]
+    fn word_fname_m() -> &'static [(&'static str, usize)] {
+        &[
+            ("Amaru", 1), ("Atoq", 1), ("Kallpa", 1), ("Kusi", 1), ("Mayta", 1), ("Rumi", 1),
+            ("Sayri", 1), ("Sinchi", 1), ("Titu", 1), ("Waman", 1), ("Yupanki", 1),
+        ]
+    }
+
+    #[rustfmt::skip]
+    fn word_fname() -> &'static [(&'static str, usize)] {
+        &[
+            ("Illari", 1), ("Inti", 1), ("Mayu", 1), ("Nina", 1), ("Tamya", 1), ("Wayra", 1),
+        ]
+    }
+
+    #[rustfmt::skip]
+    fn word_lname() -> &'static [(&'static str, usize)] {
+        &[
+            ("Apaza", 1), ("Ccahuana", 1), ("Ccori", 1), ("Chambi", 1), ("Choque", 1),
+            ("Condori", 1), ("Huamán", 1), ("Huanca", 1), ("Layme", 1), ("Mamani", 1), ("Pari", 1),
+            ("Quispe", 1), ("Sullca", 1), ("Ticona", 1),
+        ]
+    }
+
+    fn surname_convention() -> SurnameConvention {
+        SurnameConvention::GivenPaternalMaternal
+    }
+}
+
+impl Generate for Ethnicity {
+    fn gen_name(rng: &mut impl Rng, age: &Age, gender: &Gender) -> String {
+        Self::gen_name_from_words(rng, age, gender)
+    }
+
+    /// Quechua forms diminutives with the suffix "-cha" on the whole name, eg. "Killacha".
+    fn diminutive(_rng: &mut impl Rng, fname: &str) -> Option<String> {
+        Some(format!("{}cha", fname))
+    }
+
+    fn archaic_fnames(gender: &Gender) -> &'static [(&'static str, usize)] {
+        match gender {
+            Gender::Feminine => &[("Asunta", 1), ("Felícitas", 1), ("Gregoria", 1)],
+            Gender::Masculine => &[("Eusebio", 1), ("Faustino", 1), ("Mariano", 1)],
+            _ => &[],
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_generate_for_ethnicity {
+    use super::*;
+    use crate::world::npc::ethnicity::{regenerate, Ethnicity};
+    use crate::world::Npc;
+
+    #[test]
+    fn gen_name_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let adult = Age::Adult;
+        let m = Gender::Masculine;
+        let f = Gender::Feminine;
+        let t = Gender::NonBinaryThey;
+
+        assert_eq!(
+            [
+                "Rumi Sullca Ticona",
+                "Sayri Huamán Condori",
+                "Suyana Chambi Ticona",
+                "Sumaq Apaza Chambi",
+                "Inti Choque Quispe",
+                "Tamya Layme Ccori",
+            ],
+            [
+                gen_name(&mut rng, &adult, &m),
+                gen_name(&mut rng, &adult, &m),
+                gen_name(&mut rng, &adult, &f),
+                gen_name(&mut rng, &adult, &f),
+                gen_name(&mut rng, &adult, &t),
+                gen_name(&mut rng, &adult, &t),
+            ],
+        );
+    }
+
+    fn gen_name(rng: &mut impl Rng, age: &Age, gender: &Gender) -> String {
+        let mut npc = Npc::default();
+        npc.gender.replace(*gender);
+        npc.age.replace(*age);
+        npc.ethnicity.replace(Ethnicity::Quechua);
+        regenerate(rng, &mut npc);
+        format!("{}", npc.name)
+    }
+}
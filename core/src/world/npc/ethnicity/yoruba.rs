@@ -0,0 +1,103 @@
+use super::{Age, Gender, Generate, GenerateFromWords, SurnameConvention};
+use rand::prelude::*;
+
+pub struct Ethnicity;
+
+impl GenerateFromWords for Ethnicity {
+    #[rustfmt::skip]
+    fn word_fname_f() -> &'static [(&'static str, usize)] {
+        &[
+            ("Abeni", 1), ("Adunni", 1), ("Bolanle", 1), ("Bunmi", 1), ("Folake", 1),
+            ("Funmilayo", 1), ("Iyabo", 1), ("Kemi", 1), ("Morenike", 1), ("Omolara", 1),
+            ("Sade", 1), ("Titilayo", 1), ("Toyin", 1), ("Yetunde", 1),
+        ]
+    }
+
+    #[rustfmt::skip]
+    fn word_fname_m() -> &'static [(&'static str, usize)] {
+        &[
+            ("Adebayo", 1), ("Adewale", 1), ("Akin", 1), ("Babatunde", 1), ("Femi", 1),
+            ("Gbenga", 1), ("Jide", 1), ("Kayode", 1), ("Kunle", 1), ("Olumide", 1), ("Segun", 1),
+            ("Tunde", 1), ("Wale", 1),
+        ]
+    }
+
+    #[rustfmt::skip]
+    fn word_fname() -> &'static [(&'static str, usize)] {
+        &[
+            ("Ayodele", 1), ("Ayomide", 1), ("Damilola", 1), ("Dayo", 1), ("Olamide", 1),
+            ("Oluwaseun", 1), ("Temitope", 1), ("Tobi", 1), ("Tolu", 1),
+        ]
+    }
+
+    #[rustfmt::skip]
+    fn word_lname() -> &'static [(&'static str, usize)] {
+        &[
+            ("Adeleke", 1), ("Adeyemi", 1), ("Afolabi", 1), ("Akinola", 1), ("Balogun", 1),
+            ("Bankole", 1), ("Fashola", 1), ("Ogunbiyi", 1), ("Ogundipe", 1), ("Ogunleye", 1),
+            ("Okunola", 1), ("Oladipo", 1), ("Olaniyan", 1), ("Olatunji", 1), ("Oyelaran", 1),
+        ]
+    }
+
+    fn surname_convention() -> SurnameConvention {
+        SurnameConvention::GivenFamily
+    }
+}
+
+impl Generate for Ethnicity {
+    fn gen_name(rng: &mut impl Rng, age: &Age, gender: &Gender) -> String {
+        Self::gen_name_from_words(rng, age, gender)
+    }
+
+    fn archaic_fnames(gender: &Gender) -> &'static [(&'static str, usize)] {
+        match gender {
+            Gender::Feminine => &[("Abike", 1), ("Ajoke", 1), ("Mojisola", 1)],
+            Gender::Masculine => &[("Adigun", 1), ("Ayinde", 1), ("Oladele", 1)],
+            _ => &[],
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_generate_for_ethnicity {
+    use super::*;
+    use crate::world::npc::ethnicity::{regenerate, Ethnicity};
+    use crate::world::Npc;
+
+    #[test]
+    fn gen_name_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let adult = Age::Adult;
+        let m = Gender::Masculine;
+        let f = Gender::Feminine;
+        let t = Gender::NonBinaryThey;
+
+        assert_eq!(
+            [
+                "Gbenga Oyelaran",
+                "Jide Olatunji",
+                "Yetunde Ogundipe",
+                "Iyabo Bankole",
+                "Tobi Akinola",
+                "Ayodele Olatunji",
+            ],
+            [
+                gen_name(&mut rng, &adult, &m),
+                gen_name(&mut rng, &adult, &m),
+                gen_name(&mut rng, &adult, &f),
+                gen_name(&mut rng, &adult, &f),
+                gen_name(&mut rng, &adult, &t),
+                gen_name(&mut rng, &adult, &t),
+            ],
+        );
+    }
+
+    fn gen_name(rng: &mut impl Rng, age: &Age, gender: &Gender) -> String {
+        let mut npc = Npc::default();
+        npc.gender.replace(*gender);
+        npc.age.replace(*age);
+        npc.ethnicity.replace(Ethnicity::Yoruba);
+        regenerate(rng, &mut npc);
+        format!("{}", npc.name)
+    }
+}
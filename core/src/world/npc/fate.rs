@@ -0,0 +1,57 @@
+use crate::time::Time;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// What has become of an NPC who no longer takes part in the campaign, and when it happened in
+/// game time. Such NPCs stay in the journal, but aren't drawn into rumors about other entries.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Fate {
+    Dead { time: Time },
+    Retired { time: Time },
+}
+
+impl Fate {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Dead { .. } => "dead",
+            Self::Retired { .. } => "retired",
+        }
+    }
+
+    pub fn time(&self) -> &Time {
+        match self {
+            Self::Dead { time } | Self::Retired { time } => time,
+        }
+    }
+}
+
+impl fmt::Display for Fate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn serialize_deserialize_test() {
+        let fate = Fate::Dead {
+            time: Time::try_new(3, 8, 0, 0).unwrap(),
+        };
+        let json = serde_json::to_string(&fate).unwrap();
+
+        assert_eq!(r#"{"dead":{"time":"3:08:00:00"}}"#, json);
+        assert_eq!(fate, serde_json::from_str(&json).unwrap());
+    }
+
+    #[test]
+    fn display_test() {
+        let time = Time::try_new(3, 8, 0, 0).unwrap();
+
+        assert_eq!("dead", Fate::Dead { time: time.clone() }.to_string());
+        assert_eq!("retired", Fate::Retired { time }.to_string());
+    }
+}
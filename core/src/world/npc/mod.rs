@@ -1,18 +1,34 @@
 pub use age::Age;
+pub use appearance::{Build, Clothing, Eyes, Hair, Mark};
+pub use attitude::{Attitude, Reaction};
+pub use demeanor::Demeanor;
 pub use ethnicity::Ethnicity;
+pub use fate::Fate;
 pub use gender::Gender;
+pub use occupation::Occupation;
+pub use pc::PlayerCharacter;
+pub use relationship::{ContactKind, Relationship, Relationships};
+pub use resources::{ordinal, Resource, Resources, Rest};
 pub use size::Size;
 pub use species::Species;
 pub use view::{DescriptionView, DetailsView, SummaryView};
 
 mod age;
+mod appearance;
+mod attitude;
+mod demeanor;
 mod ethnicity;
+mod fate;
 mod gender;
+mod occupation;
+mod pc;
+mod relationship;
+mod resources;
 mod size;
 mod species;
 mod view;
 
-use super::{Demographics, Field, Generate, Place, PlaceUuid};
+use super::{Demographics, Field, Generate, Inventory, Place, PlaceUuid, Statuses};
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 
@@ -29,8 +45,53 @@ pub struct Npc {
     pub species: Field<Species>,
     pub ethnicity: Field<Ethnicity>,
     pub location_uuid: Field<PlaceUuid>,
+    #[serde(default)]
+    pub resources: Field<Resources>,
+    #[serde(default)]
+    pub occupation: Field<Occupation>,
+    #[serde(default)]
+    pub inventory: Field<Inventory>,
+    /// The names of the SRD spells known to the character, in the order they were learned.
+    #[serde(default)]
+    pub spells: Field<Vec<String>>,
+    #[serde(default)]
+    pub statuses: Field<Statuses>,
+    #[serde(default)]
+    pub build: Field<Build>,
+    #[serde(default)]
+    pub hair: Field<Hair>,
+    #[serde(default)]
+    pub eyes: Field<Eyes>,
+    #[serde(default)]
+    pub mark: Field<Mark>,
+    #[serde(default)]
+    pub clothing: Field<Clothing>,
+    #[serde(default)]
+    pub attitude: Field<Attitude>,
+    #[serde(default)]
+    pub demeanor: Field<Demeanor>,
+    #[serde(default)]
+    pub fate: Field<Fate>,
+    #[serde(default)]
+    pub pc: Field<PlayerCharacter>,
+    #[serde(default)]
+    pub archived: Field<bool>,
+    #[serde(default)]
+    pub pinned: Field<bool>,
+
+    /// The name of a homebrew species, shown in place of the built-in one it's based on.
+    #[serde(default)]
+    pub homebrew: Field<String>,
+
+    /// The character's relationships with others, each of which is also recorded on the other
+    /// character.
+    #[serde(default)]
+    pub relationships: Field<Relationships>,
+
+    /// Other names the character goes by, which find them as well as their name does.
+    #[serde(default)]
+    pub aliases: Field<Vec<String>>,
     // pub home: Field<PlaceUuid>,
-    // pub occupation: Field<Role>,
     // pub languages: Field<Vec<String>>,
     // pub parents: Field<Vec<Uuid>>,
     // pub spouses: Field<Vec<Uuid>>,
@@ -41,6 +102,7 @@ pub struct Npc {
 #[derive(Debug, Default)]
 pub struct NpcRelations {
     pub location: Option<(Place, Option<Place>)>,
+    pub relationships: Vec<(Relationship, Npc)>,
 }
 
 impl Npc {
@@ -63,10 +125,185 @@ impl Npc {
             .unwrap_or(Gender::NonBinaryThey)
     }
 
+    /// A person emoji reflecting the NPC's age and gender, or an icon for their species if
+    /// `by_species` is set and their species is known.
+    pub fn get_emoji(&self, by_species: bool) -> &'static str {
+        if let Some(species) = self.species.value().filter(|_| by_species) {
+            return species.get_emoji();
+        }
+
+        match (self.age.value(), self.gender.value()) {
+            (Some(Age::Infant), _) => "\u{1f476}",
+            (Some(Age::Child | Age::Adolescent), Some(Gender::Feminine)) => "\u{1f467}",
+            (Some(Age::Child | Age::Adolescent), Some(Gender::Masculine)) => "\u{1f466}",
+            (Some(Age::Child | Age::Adolescent), _) => "\u{1f9d2}",
+            (Some(Age::Elderly | Age::Geriatric), Some(Gender::Feminine)) => "\u{1f475}",
+            (Some(Age::Elderly | Age::Geriatric), Some(Gender::Masculine)) => "\u{1f474}",
+            (Some(Age::Elderly | Age::Geriatric), _) => "\u{1f9d3}",
+            (_, Some(Gender::Feminine)) => "\u{1f469}",
+            (_, Some(Gender::Masculine)) => "\u{1f468}",
+            _ => "\u{1f9d1}",
+        }
+    }
+
+    /// The weight in pounds that the NPC can carry. NPCs don't have ability scores, so an average
+    /// Strength of 10 is assumed.
+    pub fn carrying_capacity(&self) -> u16 {
+        match self.size.value() {
+            Some(Size::Tiny { .. }) => 75,
+            _ => 150,
+        }
+    }
+
     pub fn get_words() -> &'static [&'static str] {
         &["character", "npc"][..]
     }
 
+    /// The fields that can be locked and unlocked by name, eg. `lock Gottfried's age`.
+    pub fn lockable_fields() -> &'static [&'static str] {
+        &[
+            "age",
+            "attitude",
+            "build",
+            "clothing",
+            "demeanor",
+            "ethnicity",
+            "eyes",
+            "gender",
+            "hair",
+            "mark",
+            "name",
+            "occupation",
+            "size",
+            "species",
+        ][..]
+    }
+
+    /// Is the field locked? Returns `None` if it isn't one of [`Npc::lockable_fields`].
+    pub fn is_field_locked(&self, field: &str) -> Option<bool> {
+        Some(match field {
+            "age" => self.age.is_locked(),
+            "attitude" => self.attitude.is_locked(),
+            "build" => self.build.is_locked(),
+            "clothing" => self.clothing.is_locked(),
+            "demeanor" => self.demeanor.is_locked(),
+            "ethnicity" => self.ethnicity.is_locked(),
+            "eyes" => self.eyes.is_locked(),
+            "gender" => self.gender.is_locked(),
+            "hair" => self.hair.is_locked(),
+            "mark" => self.mark.is_locked(),
+            "name" => self.name.is_locked(),
+            "occupation" => self.occupation.is_locked(),
+            "size" => self.size.is_locked(),
+            "species" => self.species.is_locked(),
+            _ => return None,
+        })
+    }
+
+    /// The lockable fields that are unlocked, and so free to be regenerated.
+    pub fn unlocked_fields(&self) -> Vec<&'static str> {
+        Self::lockable_fields()
+            .iter()
+            .copied()
+            .filter(|field| self.is_field_locked(field) == Some(false))
+            .collect()
+    }
+
+    /// Lock or unlock the field, returning whether it was locked beforehand, or `None` if it
+    /// isn't one of [`Npc::lockable_fields`].
+    pub fn set_field_locked(&mut self, field: &str, locked: bool) -> Option<bool> {
+        Some(match field {
+            "age" => {
+                self.age_years.set_locked(locked);
+                self.age.set_locked(locked)
+            }
+            "attitude" => self.attitude.set_locked(locked),
+            "build" => self.build.set_locked(locked),
+            "clothing" => self.clothing.set_locked(locked),
+            "demeanor" => self.demeanor.set_locked(locked),
+            "ethnicity" => self.ethnicity.set_locked(locked),
+            "eyes" => self.eyes.set_locked(locked),
+            "gender" => self.gender.set_locked(locked),
+            "hair" => self.hair.set_locked(locked),
+            "mark" => self.mark.set_locked(locked),
+            "name" => self.name.set_locked(locked),
+            "occupation" => self.occupation.set_locked(locked),
+            "size" => self.size.set_locked(locked),
+            "species" => self.species.set_locked(locked),
+            _ => return None,
+        })
+    }
+
+    /// The value of a field as it's shown to the user, or `None` if it isn't set or isn't one of
+    /// [`Npc::lockable_fields`].
+    pub fn display_field(&self, field: &str) -> Option<String> {
+        match field {
+            "age" => self
+                .age_years
+                .value()
+                .map(|age_years| format!("{} years", age_years))
+                .or_else(|| self.age.value().map(|age| age.to_string())),
+            "attitude" => self.attitude.value().map(|value| value.to_string()),
+            "build" => self.build.value().map(|value| value.to_string()),
+            "clothing" => self.clothing.value().map(|value| value.to_string()),
+            "demeanor" => self.demeanor.value().map(|value| value.to_string()),
+            "ethnicity" => self.ethnicity.value().map(|value| value.to_string()),
+            "eyes" => self.eyes.value().map(|value| value.to_string()),
+            "gender" => self.gender.value().map(|value| value.to_string()),
+            "hair" => self.hair.value().map(|value| value.to_string()),
+            "mark" => self.mark.value().map(|value| value.to_string()),
+            "name" => self.name.value().map(|value| value.to_string()),
+            "occupation" => self.occupation.value().map(|value| value.to_string()),
+            "size" => self.size.value().map(|value| value.to_string()),
+            "species" => self
+                .homebrew
+                .value()
+                .cloned()
+                .or_else(|| self.species.value().map(|value| value.to_string())),
+            _ => None,
+        }
+    }
+
+    /// An NPC with nothing but the given field, for use as a diff. Returns `None` if the field
+    /// isn't one of [`Npc::lockable_fields`].
+    pub fn field_diff(&self, field: &str) -> Option<Self> {
+        let mut diff = Self::default();
+
+        match field {
+            "age" => {
+                diff.age = self.age.value().cloned().into();
+                diff.age_years = self.age_years.value().cloned().into();
+            }
+            "attitude" => diff.attitude = self.attitude.value().cloned().into(),
+            "build" => diff.build = self.build.value().cloned().into(),
+            "clothing" => diff.clothing = self.clothing.value().cloned().into(),
+            "demeanor" => diff.demeanor = self.demeanor.value().cloned().into(),
+            "ethnicity" => diff.ethnicity = self.ethnicity.value().cloned().into(),
+            "eyes" => diff.eyes = self.eyes.value().cloned().into(),
+            "gender" => diff.gender = self.gender.value().cloned().into(),
+            "hair" => diff.hair = self.hair.value().cloned().into(),
+            "mark" => diff.mark = self.mark.value().cloned().into(),
+            "name" => diff.name = self.name.value().cloned().into(),
+            "occupation" => diff.occupation = self.occupation.value().cloned().into(),
+            "size" => diff.size = self.size.value().cloned().into(),
+            "species" => diff.species = self.species.value().cloned().into(),
+            _ => return None,
+        }
+
+        Some(diff)
+    }
+
+    /// True if the NPC was described by nothing but an occupation, and possibly a name.
+    pub fn is_only_occupation(&self) -> bool {
+        self.occupation.is_some()
+            && self
+                == &Npc {
+                    name: self.name.clone(),
+                    occupation: self.occupation.clone(),
+                    ..Default::default()
+                }
+    }
+
     pub fn lock_all(&mut self) {
         let Self {
             uuid: _,
@@ -78,6 +315,25 @@ impl Npc {
             species,
             ethnicity,
             location_uuid,
+            resources,
+            occupation,
+            inventory,
+            spells,
+            statuses,
+            build,
+            hair,
+            eyes,
+            mark,
+            clothing,
+            attitude,
+            demeanor,
+            fate,
+            pc,
+            archived,
+            pinned,
+            homebrew,
+            relationships,
+            aliases,
         } = self;
 
         name.lock();
@@ -88,6 +344,25 @@ impl Npc {
         species.lock();
         ethnicity.lock();
         location_uuid.lock();
+        resources.lock();
+        occupation.lock();
+        inventory.lock();
+        spells.lock();
+        statuses.lock();
+        build.lock();
+        hair.lock();
+        eyes.lock();
+        mark.lock();
+        clothing.lock();
+        attitude.lock();
+        demeanor.lock();
+        fate.lock();
+        pc.lock();
+        archived.lock();
+        pinned.lock();
+        homebrew.lock();
+        relationships.lock();
+        aliases.lock();
     }
 
     pub fn apply_diff(&mut self, diff: &mut Self) {
@@ -101,6 +376,25 @@ impl Npc {
             species,
             ethnicity,
             location_uuid,
+            resources,
+            occupation,
+            inventory,
+            spells,
+            statuses,
+            build,
+            hair,
+            eyes,
+            mark,
+            clothing,
+            attitude,
+            demeanor,
+            fate,
+            pc,
+            archived,
+            pinned,
+            homebrew,
+            relationships,
+            aliases,
         } = self;
 
         name.apply_diff(&mut diff.name);
@@ -111,6 +405,60 @@ impl Npc {
         species.apply_diff(&mut diff.species);
         ethnicity.apply_diff(&mut diff.ethnicity);
         location_uuid.apply_diff(&mut diff.location_uuid);
+        resources.apply_diff(&mut diff.resources);
+        occupation.apply_diff(&mut diff.occupation);
+        inventory.apply_diff(&mut diff.inventory);
+        spells.apply_diff(&mut diff.spells);
+        statuses.apply_diff(&mut diff.statuses);
+        build.apply_diff(&mut diff.build);
+        hair.apply_diff(&mut diff.hair);
+        eyes.apply_diff(&mut diff.eyes);
+        mark.apply_diff(&mut diff.mark);
+        clothing.apply_diff(&mut diff.clothing);
+        attitude.apply_diff(&mut diff.attitude);
+        demeanor.apply_diff(&mut diff.demeanor);
+        fate.apply_diff(&mut diff.fate);
+        pc.apply_diff(&mut diff.pc);
+        archived.apply_diff(&mut diff.archived);
+        pinned.apply_diff(&mut diff.pinned);
+        homebrew.apply_diff(&mut diff.homebrew);
+        relationships.apply_diff(&mut diff.relationships);
+        aliases.apply_diff(&mut diff.aliases);
+    }
+
+    /// A diff that merges the fields of a duplicate into this NPC, as with
+    /// [`Field::merged_from`]. The name, and whether the NPC is archived, are left as they are.
+    pub fn merge_diff(&self, other: &Self) -> Self {
+        Self {
+            uuid: None,
+            name: Field::default(),
+            gender: self.gender.merged_from(&other.gender),
+            age: self.age.merged_from(&other.age),
+            age_years: self.age_years.merged_from(&other.age_years),
+            size: self.size.merged_from(&other.size),
+            species: self.species.merged_from(&other.species),
+            ethnicity: self.ethnicity.merged_from(&other.ethnicity),
+            location_uuid: self.location_uuid.merged_from(&other.location_uuid),
+            resources: self.resources.merged_from(&other.resources),
+            occupation: self.occupation.merged_from(&other.occupation),
+            inventory: self.inventory.merged_from(&other.inventory),
+            spells: self.spells.merged_from(&other.spells),
+            statuses: self.statuses.merged_from(&other.statuses),
+            build: self.build.merged_from(&other.build),
+            hair: self.hair.merged_from(&other.hair),
+            eyes: self.eyes.merged_from(&other.eyes),
+            mark: self.mark.merged_from(&other.mark),
+            clothing: self.clothing.merged_from(&other.clothing),
+            attitude: self.attitude.merged_from(&other.attitude),
+            demeanor: self.demeanor.merged_from(&other.demeanor),
+            fate: self.fate.merged_from(&other.fate),
+            pc: self.pc.merged_from(&other.pc),
+            archived: Field::default(),
+            pinned: self.pinned.merged_from(&other.pinned),
+            homebrew: self.homebrew.merged_from(&other.homebrew),
+            relationships: self.relationships.merged_from(&other.relationships),
+            aliases: self.aliases.merged_from(&other.aliases),
+        }
     }
 }
 
@@ -143,6 +491,8 @@ impl Generate for Npc {
 
         species::regenerate(rng, self);
         ethnicity::regenerate(rng, self);
+        occupation::regenerate(rng, self);
+        appearance::regenerate(rng, self);
     }
 }
 
@@ -176,15 +526,23 @@ mod test {
         let npc = gandalf();
 
         assert_eq!(
-            r#"{"uuid":"00000000-0000-0000-0000-000000000000","name":"Gandalf the Grey","gender":"neuter","age":"geriatric","age_years":65535,"size":{"type":"Medium","height":72,"weight":200},"species":"human","ethnicity":"human","location_uuid":null}"#,
+            r#"{"uuid":"00000000-0000-0000-0000-000000000000","name":"Gandalf the Grey","gender":"neuter","age":"geriatric","age_years":65535,"size":{"type":"Medium","height":72,"weight":200},"species":"human","ethnicity":"human","location_uuid":null,"resources":null,"occupation":null,"inventory":null,"spells":null,"statuses":null,"build":null,"hair":null,"eyes":null,"mark":null,"clothing":null,"attitude":null,"demeanor":null,"fate":null,"pc":null,"archived":null,"pinned":null,"homebrew":null,"relationships":null,"aliases":null}"#,
             serde_json::to_string(&npc).unwrap()
         );
 
-        let value: Npc = serde_json::from_str(r#"{"uuid":"00000000-0000-0000-0000-000000000000","name":"Gandalf the Grey","gender":"neuter","age":"geriatric","age_years":65535,"size":{"type":"Medium","height":72,"weight":200},"species":"human","ethnicity":"human","location_uuid":null}"#).unwrap();
+        let value: Npc = serde_json::from_str(r#"{"uuid":"00000000-0000-0000-0000-000000000000","name":"Gandalf the Grey","gender":"neuter","age":"geriatric","age_years":65535,"size":{"type":"Medium","height":72,"weight":200},"species":"human","ethnicity":"human","location_uuid":null,"resources":null,"occupation":null,"inventory":null,"spells":null,"statuses":null,"build":null,"hair":null,"eyes":null,"mark":null,"clothing":null,"attitude":null,"demeanor":null,"fate":null,"pc":null,"archived":null,"pinned":null,"homebrew":null,"relationships":null,"aliases":null}"#).unwrap();
 
         assert_eq!(npc, value);
     }
 
+    #[test]
+    fn deserialize_test_without_resources() {
+        let value: Npc = serde_json::from_str(r#"{"uuid":"00000000-0000-0000-0000-000000000000","name":"Gandalf the Grey","gender":"neuter","age":"geriatric","age_years":65535,"size":{"type":"Medium","height":72,"weight":200},"species":"human","ethnicity":"human","location_uuid":null}"#).unwrap();
+
+        assert_eq!(Some(&"Gandalf the Grey".to_string()), value.name.value());
+        assert!(value.resources.is_none());
+    }
+
     #[test]
     fn apply_diff_test_no_change() {
         let mut npc = gandalf();
@@ -213,6 +571,32 @@ mod test {
         assert_eq!(empty_locked, diff);
     }
 
+    #[test]
+    fn field_diff_test() {
+        let npc = gandalf();
+
+        assert_eq!(
+            Some(Npc {
+                age: Age::Geriatric.into(),
+                age_years: u16::MAX.into(),
+                ..Default::default()
+            }),
+            npc.field_diff("age"),
+        );
+        assert_eq!(
+            Some(Npc {
+                name: "Gandalf the Grey".into(),
+                ..Default::default()
+            }),
+            npc.field_diff("name"),
+        );
+        assert_eq!(None, npc.field_diff("inventory"));
+
+        assert_eq!(Some("65535 years".to_string()), npc.display_field("age"));
+        assert_eq!(Some("human".to_string()), npc.display_field("species"));
+        assert_eq!(None, npc.display_field("potato"));
+    }
+
     fn gandalf() -> Npc {
         Npc {
             uuid: Some(uuid::Uuid::nil().into()),
@@ -228,6 +612,25 @@ mod test {
             species: Species::Human.into(),
             ethnicity: Ethnicity::Human.into(),
             location_uuid: None.into(),
+            resources: None.into(),
+            occupation: None.into(),
+            inventory: None.into(),
+            spells: None.into(),
+            statuses: None.into(),
+            build: None.into(),
+            hair: None.into(),
+            eyes: None.into(),
+            mark: None.into(),
+            clothing: None.into(),
+            attitude: None.into(),
+            demeanor: None.into(),
+            fate: None.into(),
+            pc: None.into(),
+            archived: None.into(),
+            pinned: None.into(),
+            homebrew: None.into(),
+            relationships: None.into(),
+            aliases: None.into(),
         }
     }
 
@@ -247,6 +650,25 @@ mod test {
                 species: Field::Locked(None),
                 ethnicity: Field::Locked(None),
                 location_uuid: Field::Locked(None),
+                resources: Field::Locked(None),
+                occupation: Field::Locked(None),
+                inventory: Field::Locked(None),
+                spells: Field::Locked(None),
+                statuses: Field::Locked(None),
+                build: Field::Locked(None),
+                hair: Field::Locked(None),
+                eyes: Field::Locked(None),
+                mark: Field::Locked(None),
+                clothing: Field::Locked(None),
+                attitude: Field::Locked(None),
+                demeanor: Field::Locked(None),
+                fate: Field::Locked(None),
+                pc: Field::Locked(None),
+                archived: Field::Locked(None),
+                pinned: Field::Locked(None),
+                homebrew: Field::Locked(None),
+                relationships: Field::Locked(None),
+                aliases: Field::Locked(None),
             },
             npc,
         );
@@ -0,0 +1,174 @@
+use super::{Age, Npc};
+use crate::world::weighted_index_from_tuple;
+use initiative_macros::WordList;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, WordList)]
+#[serde(into = "&'static str", try_from = "&str")]
+pub enum Occupation {
+    Acolyte,
+    Baker,
+
+    #[alias = "smith"]
+    Blacksmith,
+
+    Butcher,
+    Carpenter,
+    Cultist,
+
+    #[alias = "peasant"]
+    Farmer,
+
+    #[alias = "fisherman"]
+    #[alias = "fisherwoman"]
+    Fisher,
+
+    #[alias = "guardsman"]
+    #[alias = "guardswoman"]
+    #[alias = "watchman"]
+    Guard,
+
+    Healer,
+    Hunter,
+    Innkeeper,
+
+    #[alias = "labourer"]
+    Laborer,
+
+    #[alias = "trader"]
+    Merchant,
+
+    Miner,
+
+    #[alias = "aristocrat"]
+    #[alias = "nobleman"]
+    #[alias = "noblewoman"]
+    Noble,
+
+    #[alias = "cleric"]
+    Priest,
+
+    #[alias = "seaman"]
+    Sailor,
+
+    #[alias = "sage"]
+    Scholar,
+
+    Servant,
+    Soldier,
+    Tailor,
+
+    #[alias = "pickpocket"]
+    Thief,
+}
+
+impl Occupation {
+    /// Pick an occupation according to its prevalence in a typical medieval settlement, where most
+    /// people work the land and very few are nobility.
+    pub fn generate(rng: &mut impl Rng) -> Self {
+        const WEIGHTS: &[(Occupation, usize)] = &[
+            (Occupation::Farmer, 40),
+            (Occupation::Laborer, 15),
+            (Occupation::Servant, 8),
+            (Occupation::Fisher, 6),
+            (Occupation::Merchant, 6),
+            (Occupation::Soldier, 5),
+            (Occupation::Guard, 4),
+            (Occupation::Hunter, 3),
+            (Occupation::Miner, 3),
+            (Occupation::Sailor, 3),
+            (Occupation::Baker, 2),
+            (Occupation::Blacksmith, 2),
+            (Occupation::Butcher, 2),
+            (Occupation::Carpenter, 2),
+            (Occupation::Tailor, 2),
+            (Occupation::Thief, 2),
+            (Occupation::Acolyte, 1),
+            (Occupation::Cultist, 1),
+            (Occupation::Healer, 1),
+            (Occupation::Innkeeper, 1),
+            (Occupation::Noble, 1),
+            (Occupation::Priest, 1),
+            (Occupation::Scholar, 1),
+        ];
+
+        *weighted_index_from_tuple(rng, WEIGHTS)
+    }
+}
+
+/// Adults are assigned an occupation at random. Children don't have one unless it was specified
+/// explicitly.
+pub fn regenerate(rng: &mut impl Rng, npc: &mut Npc) {
+    match npc.age.value() {
+        Some(Age::Infant | Age::Child | Age::Adolescent) => npc.occupation.clear(),
+        _ => npc.occupation.replace_with(|_| Occupation::generate(rng)),
+    }
+}
+
+impl fmt::Display for Occupation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::world::Field;
+    use rand::prelude::*;
+
+    #[test]
+    fn generate_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut farmers = 0;
+
+        for _ in 0..1000 {
+            if Occupation::generate(&mut rng) == Occupation::Farmer {
+                farmers += 1;
+            }
+        }
+
+        assert!((300..500).contains(&farmers), "{}", farmers);
+    }
+
+    #[test]
+    fn regenerate_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        let mut npc = Npc {
+            age: Age::Adult.into(),
+            ..Default::default()
+        };
+        regenerate(&mut rng, &mut npc);
+        assert!(npc.occupation.is_some());
+
+        npc.age = Age::Child.into();
+        regenerate(&mut rng, &mut npc);
+        assert!(npc.occupation.is_none());
+
+        npc.occupation = Field::new(Occupation::Cultist);
+        regenerate(&mut rng, &mut npc);
+        assert_eq!(Some(&Occupation::Cultist), npc.occupation.value());
+    }
+
+    #[test]
+    fn from_str_test() {
+        assert_eq!(Ok(Occupation::Blacksmith), "blacksmith".parse());
+        assert_eq!(Ok(Occupation::Blacksmith), "Smith".parse());
+        assert_eq!(Ok(Occupation::Guard), "GUARDSMAN".parse());
+        assert_eq!(Err(()), "potato".parse::<Occupation>());
+    }
+
+    #[test]
+    fn serialize_deserialize_test() {
+        assert_eq!(
+            r#""innkeeper""#,
+            serde_json::to_string(&Occupation::Innkeeper).unwrap(),
+        );
+
+        let value: Occupation = serde_json::from_str(r#""innkeeper""#).unwrap();
+        assert_eq!(Occupation::Innkeeper, value);
+    }
+}
@@ -0,0 +1,241 @@
+use crate::utils::CaseInsensitiveStr;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The statistics tracked for a player character, which distinguish them from an NPC. Player
+/// characters are never generated, so everything here is provided by the user.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct PlayerCharacter {
+    pub player: Option<String>,
+    pub class: Option<String>,
+    pub level: Option<u8>,
+    pub armor_class: Option<u8>,
+    pub passive_perception: Option<u8>,
+}
+
+/// The class and level of a player character, followed by their player, eg. "level 5 ranger,
+/// played by Sam".
+pub struct PlayerCharacterView<'a>(&'a PlayerCharacter);
+
+impl PlayerCharacter {
+    /// Parse a comma-separated list of statistics, eg. "level 5 ranger, AC 16, passive perception
+    /// 14, played by Sam". The clauses can appear in any order, and any of them can be omitted.
+    pub fn parse_clauses(input: &str) -> Result<Self, ()> {
+        let mut pc = Self::default();
+
+        for clause in input.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            pc.parse_clause(clause)?;
+        }
+
+        Ok(pc)
+    }
+
+    fn parse_clause(&mut self, clause: &str) -> Result<(), ()> {
+        if let Some(player) = clause
+            .strip_prefix_ci("played by ")
+            .or_else(|| clause.strip_prefix_ci("player "))
+        {
+            self.player = Some(player.trim().to_string());
+        } else if let Some(ac) = clause
+            .strip_prefix_ci("ac ")
+            .or_else(|| clause.strip_prefix_ci("armor class "))
+        {
+            self.armor_class = Some(ac.trim().parse().map_err(|_| ())?);
+        } else if let Some(pp) = clause
+            .strip_prefix_ci("pp ")
+            .or_else(|| clause.strip_prefix_ci("passive perception "))
+        {
+            self.passive_perception = Some(pp.trim().parse().map_err(|_| ())?);
+        } else if let Some(rest) = clause.strip_prefix_ci("level ") {
+            // "level 5 ranger" or "level 5"
+            let (level, class) = rest.trim().split_once(' ').unwrap_or((rest.trim(), ""));
+            self.level = Some(level.parse().map_err(|_| ())?);
+
+            if !class.trim().is_empty() {
+                self.class = Some(class.trim().to_string());
+            }
+        } else if let Some((class, level)) = clause
+            .rsplit_once(' ')
+            .and_then(|(class, level)| Some((class, level.parse().ok()?)))
+        {
+            // "ranger 5"
+            self.class = Some(class.trim().to_string());
+            self.level = Some(level);
+        } else if clause
+            .chars()
+            .all(|c| c.is_alphabetic() || c.is_whitespace())
+        {
+            self.class = Some(clause.to_string());
+        } else {
+            return Err(());
+        }
+
+        Ok(())
+    }
+
+    /// Fill in any statistics that were provided in the other player character.
+    pub fn merge(&mut self, other: Self) {
+        let Self {
+            player,
+            class,
+            level,
+            armor_class,
+            passive_perception,
+        } = other;
+
+        if player.is_some() {
+            self.player = player;
+        }
+        if class.is_some() {
+            self.class = class;
+        }
+        if level.is_some() {
+            self.level = level;
+        }
+        if armor_class.is_some() {
+            self.armor_class = armor_class;
+        }
+        if passive_perception.is_some() {
+            self.passive_perception = passive_perception;
+        }
+    }
+
+    pub fn display(&self) -> PlayerCharacterView {
+        PlayerCharacterView(self)
+    }
+}
+
+impl<'a> fmt::Display for PlayerCharacterView<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let pc = self.0;
+
+        match (&pc.level, &pc.class) {
+            (Some(level), Some(class)) => write!(f, "level {} {}", level, class)?,
+            (Some(level), None) => write!(f, "level {} player character", level)?,
+            (None, Some(class)) => write!(f, "{}", class)?,
+            (None, None) => write!(f, "player character")?,
+        }
+
+        if let Some(player) = &pc.player {
+            write!(f, ", played by {}", player)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Written as the clauses accepted by [`PlayerCharacter::parse_clauses`].
+impl fmt::Display for PlayerCharacter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut clauses = Vec::new();
+
+        match (&self.level, &self.class) {
+            (Some(level), Some(class)) => clauses.push(format!("level {} {}", level, class)),
+            (Some(level), None) => clauses.push(format!("level {}", level)),
+            (None, Some(class)) => clauses.push(class.to_string()),
+            (None, None) => {}
+        }
+
+        if let Some(ac) = self.armor_class {
+            clauses.push(format!("AC {}", ac));
+        }
+        if let Some(pp) = self.passive_perception {
+            clauses.push(format!("passive perception {}", pp));
+        }
+        if let Some(player) = &self.player {
+            clauses.push(format!("played by {}", player));
+        }
+
+        write!(f, "{}", clauses.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_clauses_test() {
+        assert_eq!(
+            Ok(aragorn()),
+            PlayerCharacter::parse_clauses(
+                "level 5 ranger, AC 16, passive perception 14, played by Sam",
+            ),
+        );
+        assert_eq!(
+            Ok(aragorn()),
+            PlayerCharacter::parse_clauses("Played By Sam, ranger 5, pp 14, armor class 16"),
+        );
+        assert_eq!(
+            Ok(PlayerCharacter {
+                level: Some(3),
+                ..Default::default()
+            }),
+            PlayerCharacter::parse_clauses("level 3"),
+        );
+        assert_eq!(
+            Ok(PlayerCharacter {
+                class: Some("blood hunter".to_string()),
+                ..Default::default()
+            }),
+            PlayerCharacter::parse_clauses("blood hunter"),
+        );
+        assert_eq!(
+            Ok(PlayerCharacter::default()),
+            PlayerCharacter::parse_clauses("")
+        );
+
+        assert_eq!(Err(()), PlayerCharacter::parse_clauses("AC sixteen"));
+        assert_eq!(Err(()), PlayerCharacter::parse_clauses("level 300"));
+        assert_eq!(Err(()), PlayerCharacter::parse_clauses("+1 longsword"));
+    }
+
+    #[test]
+    fn merge_test() {
+        let mut pc = aragorn();
+        pc.merge(PlayerCharacter {
+            level: Some(6),
+            player: Some("Frodo".to_string()),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            PlayerCharacter {
+                level: Some(6),
+                player: Some("Frodo".to_string()),
+                ..aragorn()
+            },
+            pc,
+        );
+    }
+
+    #[test]
+    fn display_test() {
+        assert_eq!(
+            "level 5 ranger, AC 16, passive perception 14, played by Sam",
+            aragorn().to_string(),
+        );
+        assert_eq!(
+            Ok(aragorn()),
+            PlayerCharacter::parse_clauses(&aragorn().to_string()),
+        );
+        assert_eq!(
+            "level 5 ranger, played by Sam",
+            aragorn().display().to_string(),
+        );
+        assert_eq!(
+            "player character",
+            PlayerCharacter::default().display().to_string(),
+        );
+    }
+
+    fn aragorn() -> PlayerCharacter {
+        PlayerCharacter {
+            player: Some("Sam".to_string()),
+            class: Some("ranger".to_string()),
+            level: Some(5),
+            armor_class: Some(16),
+            passive_perception: Some(14),
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use super::Uuid;
+use initiative_macros::WordList;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The part a contact plays in a player character's life.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, WordList)]
+#[serde(into = "&'static str", try_from = "&str")]
+pub enum ContactKind {
+    Fence,
+
+    #[alias = "teacher"]
+    Mentor,
+
+    #[alias = "family"]
+    Relative,
+
+    #[alias = "nemesis"]
+    Rival,
+}
+
+/// One side of a relationship between two characters. Each side is stored with its own
+/// character, so that the relationship can be shown from either end.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(tag = "type")]
+pub enum Relationship {
+    /// The other character is this character's contact, eg. their mentor.
+    Contact { uuid: Uuid, kind: ContactKind },
+
+    /// This character is the other character's contact, with a reason for the party to seek them
+    /// out and something they're keeping to themself.
+    ContactOf {
+        uuid: Uuid,
+        kind: ContactKind,
+        hook: String,
+        secret: String,
+    },
+}
+
+/// The relationships of a character, in the order they were formed.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct Relationships(Vec<Relationship>);
+
+impl ContactKind {
+    /// How the contact is described in relation to the character, eg. "mentor to".
+    pub fn relation(&self) -> &'static str {
+        match self {
+            Self::Fence => "fence for",
+            Self::Mentor => "mentor to",
+            Self::Relative => "relative of",
+            Self::Rival => "rival of",
+        }
+    }
+}
+
+impl Relationship {
+    /// The other character in the relationship.
+    pub fn uuid(&self) -> &Uuid {
+        match self {
+            Self::Contact { uuid, .. } | Self::ContactOf { uuid, .. } => uuid,
+        }
+    }
+}
+
+impl Relationships {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Relationship> {
+        self.0.iter()
+    }
+
+    pub fn push(&mut self, relationship: Relationship) {
+        self.0.push(relationship);
+    }
+}
+
+impl From<Vec<Relationship>> for Relationships {
+    fn from(relationships: Vec<Relationship>) -> Self {
+        Self(relationships)
+    }
+}
+
+impl fmt::Display for ContactKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn from_str_test() {
+        assert_eq!(Ok(ContactKind::Mentor), "Teacher".parse());
+        assert_eq!(Ok(ContactKind::Fence), "fence".parse());
+        assert_eq!(Err(()), "potato".parse::<ContactKind>());
+    }
+
+    #[test]
+    fn serialize_deserialize_test() {
+        let relationships: Relationships = vec![
+            Relationship::Contact {
+                uuid: uuid::Uuid::nil().into(),
+                kind: ContactKind::Rival,
+            },
+            Relationship::ContactOf {
+                uuid: uuid::Uuid::nil().into(),
+                kind: ContactKind::Mentor,
+                hook: "Wants a favour.".to_string(),
+                secret: "Owes money.".to_string(),
+            },
+        ]
+        .into();
+
+        assert_eq!(
+            r#"[{"type":"Contact","uuid":"00000000-0000-0000-0000-000000000000","kind":"rival"},{"type":"ContactOf","uuid":"00000000-0000-0000-0000-000000000000","kind":"mentor","hook":"Wants a favour.","secret":"Owes money."}]"#,
+            serde_json::to_string(&relationships).unwrap(),
+        );
+
+        let value: Relationships =
+            serde_json::from_str(&serde_json::to_string(&relationships).unwrap()).unwrap();
+        assert_eq!(relationships, value);
+    }
+}
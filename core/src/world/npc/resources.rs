@@ -0,0 +1,387 @@
+use crate::time::Interval;
+use crate::utils::CaseInsensitiveStr;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Expendable resources belonging to a character, such as spell slots and limited-use features
+/// like Channel Divinity.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Resources {
+    /// Spell slots by level, so `spell_slots[0]` contains the 1st-level slots.
+    pub spell_slots: Vec<Pool>,
+    pub features: Vec<Feature>,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Pool {
+    pub max: u8,
+    pub used: u8,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Feature {
+    pub name: String,
+    pub uses: Pool,
+    pub recovery: Rest,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Rest {
+    Short,
+    Long,
+}
+
+/// Identifies a single resource, for when one is expended.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Resource {
+    SpellSlot(u8),
+    Feature(String),
+}
+
+pub struct ResourcesView<'a>(&'a Resources);
+
+impl Rest {
+    /// How long the rest takes.
+    pub fn interval(&self) -> Interval {
+        match self {
+            Self::Short => Interval::new(0, 1, 0, 0, 0),
+            Self::Long => Interval::new(0, 8, 0, 0, 0),
+        }
+    }
+}
+
+impl Resources {
+    pub fn is_empty(&self) -> bool {
+        self.spell_slots.iter().all(|pool| pool.max == 0) && self.features.is_empty()
+    }
+
+    pub fn set_spell_slots(&mut self, slots: &[u8]) {
+        self.spell_slots = slots
+            .iter()
+            .enumerate()
+            .map(|(i, &max)| Pool {
+                max,
+                used: self.spell_slots.get(i).map_or(0, |pool| pool.used.min(max)),
+            })
+            .collect();
+    }
+
+    /// Start tracking a limited-use feature, replacing any existing feature of the same name.
+    pub fn track_feature(&mut self, name: &str, max: u8, recovery: Rest) {
+        let used = if let Some(i) = self.features.iter().position(|f| f.name.eq_ci(name)) {
+            self.features.remove(i).uses.used.min(max)
+        } else {
+            0
+        };
+
+        self.features.push(Feature {
+            name: name.to_string(),
+            uses: Pool { max, used },
+            recovery,
+        });
+    }
+
+    pub fn get(&self, resource: &Resource) -> Option<&Pool> {
+        match resource {
+            Resource::SpellSlot(level) => (*level as usize)
+                .checked_sub(1)
+                .and_then(|i| self.spell_slots.get(i))
+                .filter(|pool| pool.max > 0),
+            Resource::Feature(name) => self
+                .features
+                .iter()
+                .find(|f| f.name.eq_ci(name))
+                .map(|f| &f.uses),
+        }
+    }
+
+    /// Expend a single use of a resource. Fails if the character doesn't have the resource or
+    /// if it has been exhausted.
+    pub fn expend(&mut self, resource: &Resource) -> Result<Pool, ()> {
+        let pool = match resource {
+            Resource::SpellSlot(level) => (*level as usize)
+                .checked_sub(1)
+                .and_then(|i| self.spell_slots.get_mut(i)),
+            Resource::Feature(name) => self
+                .features
+                .iter_mut()
+                .find(|f| f.name.eq_ci(name))
+                .map(|f| &mut f.uses),
+        }
+        .ok_or(())?;
+
+        if pool.used < pool.max {
+            pool.used += 1;
+            Ok(*pool)
+        } else {
+            Err(())
+        }
+    }
+
+    /// Recover resources as appropriate for the type of rest. A long rest recovers everything,
+    /// while a short rest only recovers features that specifically recharge on a short rest.
+    pub fn rest(&mut self, rest: Rest) {
+        if rest == Rest::Long {
+            self.spell_slots.iter_mut().for_each(|pool| pool.used = 0);
+        }
+
+        self.features
+            .iter_mut()
+            .filter(|feature| rest == Rest::Long || feature.recovery == Rest::Short)
+            .for_each(|feature| feature.uses.used = 0);
+    }
+
+    pub fn display(&self) -> ResourcesView {
+        ResourcesView(self)
+    }
+}
+
+impl Pool {
+    pub fn remaining(&self) -> u8 {
+        self.max.saturating_sub(self.used)
+    }
+}
+
+impl fmt::Display for Pool {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.remaining(), self.max)
+    }
+}
+
+impl fmt::Display for Rest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Short => write!(f, "short"),
+            Self::Long => write!(f, "long"),
+        }
+    }
+}
+
+impl FromStr for Rest {
+    type Err = ();
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        if raw.eq_ci("short") {
+            Ok(Self::Short)
+        } else if raw.eq_ci("long") {
+            Ok(Self::Long)
+        } else {
+            Err(())
+        }
+    }
+}
+
+impl fmt::Display for Resource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::SpellSlot(level) => write!(f, "slot {}", level),
+            Self::Feature(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+impl FromStr for Resource {
+    type Err = ();
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        if let Some(level) = raw.strip_prefix_ci("slot ") {
+            match level.trim().parse() {
+                Ok(level @ 1..=9) => Ok(Self::SpellSlot(level)),
+                _ => Err(()),
+            }
+        } else if raw.trim().is_empty() {
+            Err(())
+        } else {
+            Ok(Self::Feature(raw.trim().to_string()))
+        }
+    }
+}
+
+impl<'a> fmt::Display for ResourcesView<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let resources = self.0;
+        let mut lines = Vec::new();
+
+        let spell_slots: Vec<String> = resources
+            .spell_slots
+            .iter()
+            .enumerate()
+            .filter(|(_, pool)| pool.max > 0)
+            .map(|(i, pool)| format!("{} {}", ordinal(i as u8 + 1), pool))
+            .collect();
+
+        if !spell_slots.is_empty() {
+            lines.push(format!("**Spell Slots:** {}", spell_slots.join(", ")));
+        }
+
+        lines.extend(resources.features.iter().map(|feature| {
+            format!(
+                "**{}:** {} ({} rest)",
+                feature.name, feature.uses, feature.recovery,
+            )
+        }));
+
+        write!(f, "{}", lines.join("\\\n"))
+    }
+}
+
+pub fn ordinal(n: u8) -> String {
+    match (n % 10, n % 100) {
+        (1, 11) | (2, 12) | (3, 13) => format!("{}th", n),
+        (1, _) => format!("{}st", n),
+        (2, _) => format!("{}nd", n),
+        (3, _) => format!("{}rd", n),
+        _ => format!("{}th", n),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn set_spell_slots_test() {
+        let mut resources = Resources::default();
+        resources.set_spell_slots(&[4, 3, 2]);
+        resources.expend(&Resource::SpellSlot(1)).unwrap();
+        resources.expend(&Resource::SpellSlot(3)).unwrap();
+        resources.expend(&Resource::SpellSlot(3)).unwrap();
+
+        resources.set_spell_slots(&[4, 3, 1, 1]);
+
+        assert_eq!(
+            vec![
+                Pool { max: 4, used: 1 },
+                Pool { max: 3, used: 0 },
+                Pool { max: 1, used: 1 },
+                Pool { max: 1, used: 0 },
+            ],
+            resources.spell_slots,
+        );
+    }
+
+    #[test]
+    fn expend_test() {
+        let mut resources = Resources::default();
+        resources.set_spell_slots(&[1, 0]);
+        resources.track_feature("Channel Divinity", 1, Rest::Short);
+
+        assert_eq!(
+            Ok(Pool { max: 1, used: 1 }),
+            resources.expend(&Resource::SpellSlot(1)),
+        );
+        assert_eq!(Err(()), resources.expend(&Resource::SpellSlot(1)));
+        assert_eq!(Err(()), resources.expend(&Resource::SpellSlot(2)));
+        assert_eq!(Err(()), resources.expend(&Resource::SpellSlot(3)));
+        assert_eq!(Err(()), resources.expend(&Resource::SpellSlot(0)));
+
+        assert_eq!(
+            Ok(Pool { max: 1, used: 1 }),
+            resources.expend(&Resource::Feature("channel divinity".to_string())),
+        );
+        assert_eq!(
+            Err(()),
+            resources.expend(&Resource::Feature("Channel Divinity".to_string())),
+        );
+        assert_eq!(
+            Err(()),
+            resources.expend(&Resource::Feature("Wild Shape".to_string())),
+        );
+
+        assert_eq!(None, resources.get(&Resource::SpellSlot(2)));
+        assert_eq!(
+            Some(&Pool { max: 1, used: 1 }),
+            resources.get(&Resource::SpellSlot(1)),
+        );
+    }
+
+    #[test]
+    fn rest_test() {
+        let mut resources = Resources::default();
+        resources.set_spell_slots(&[2]);
+        resources.track_feature("Channel Divinity", 1, Rest::Short);
+        resources.track_feature("Wild Shape", 2, Rest::Long);
+
+        let slot = Resource::SpellSlot(1);
+        let channel_divinity = Resource::Feature("Channel Divinity".to_string());
+        let wild_shape = Resource::Feature("Wild Shape".to_string());
+
+        [&slot, &channel_divinity, &wild_shape]
+            .into_iter()
+            .for_each(|resource| {
+                resources.expend(resource).unwrap();
+            });
+
+        resources.rest(Rest::Short);
+        assert_eq!(1, resources.get(&slot).unwrap().remaining());
+        assert_eq!(1, resources.get(&channel_divinity).unwrap().remaining());
+        assert_eq!(1, resources.get(&wild_shape).unwrap().remaining());
+
+        resources.rest(Rest::Long);
+        assert_eq!(2, resources.get(&slot).unwrap().remaining());
+        assert_eq!(1, resources.get(&channel_divinity).unwrap().remaining());
+        assert_eq!(2, resources.get(&wild_shape).unwrap().remaining());
+    }
+
+    #[test]
+    fn resource_from_str_test() {
+        assert_eq!(Ok(Resource::SpellSlot(3)), "slot 3".parse());
+        assert_eq!(Ok(Resource::SpellSlot(9)), "SLOT 9".parse());
+        assert_eq!(Err(()), "slot 10".parse::<Resource>());
+        assert_eq!(Err(()), "slot 0".parse::<Resource>());
+        assert_eq!(
+            Ok(Resource::Feature("Wild Shape".to_string())),
+            "Wild Shape".parse(),
+        );
+        assert_eq!(Err(()), "".parse::<Resource>());
+    }
+
+    #[test]
+    fn display_test() {
+        let mut resources = Resources::default();
+        assert_eq!("", resources.display().to_string());
+
+        resources.set_spell_slots(&[4, 0, 2]);
+        resources.expend(&Resource::SpellSlot(1)).unwrap();
+        assert_eq!(
+            "**Spell Slots:** 1st 3/4, 3rd 2/2",
+            resources.display().to_string(),
+        );
+
+        resources.track_feature("Channel Divinity", 1, Rest::Short);
+        assert_eq!(
+            "**Spell Slots:** 1st 3/4, 3rd 2/2\\\n**Channel Divinity:** 1/1 (short rest)",
+            resources.display().to_string(),
+        );
+    }
+
+    #[test]
+    fn ordinal_test() {
+        assert_eq!(
+            vec!["1st", "2nd", "3rd", "4th", "11th", "12th", "13th", "21st", "22nd"],
+            [1, 2, 3, 4, 11, 12, 13, 21, 22]
+                .into_iter()
+                .map(ordinal)
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn serialize_deserialize_test() {
+        let mut resources = Resources::default();
+        resources.set_spell_slots(&[2]);
+        resources.track_feature("Channel Divinity", 1, Rest::Short);
+
+        assert_eq!(
+            r#"{"spell_slots":[{"max":2,"used":0}],"features":[{"name":"Channel Divinity","uses":{"max":1,"used":0},"recovery":"short"}]}"#,
+            serde_json::to_string(&resources).unwrap(),
+        );
+
+        assert_eq!(
+            resources,
+            serde_json::from_str(&serde_json::to_string(&resources).unwrap()).unwrap(),
+        );
+    }
+}
@@ -117,17 +117,57 @@ fn gen_height_weight(
 }
 
 impl Species {
+    pub const fn get_emoji(&self) -> &'static str {
+        match self {
+            Self::Dragonborn => "🐉",
+            Self::Dwarf => "⛏",
+            Self::Elf | Self::HalfElf => "🧝",
+            Self::Gnome => "🍄",
+            Self::HalfOrc => "🪓",
+            Self::Halfling => "🥧",
+            Self::Human => "🧑",
+            Self::Tiefling => "😈",
+        }
+    }
+
+    /// The age category of a member of this species who is `years` old.
+    pub fn age_from_years(&self, years: u16) -> Age {
+        match self {
+            Self::Dragonborn => dragonborn::Species::age_from_years(years),
+            Self::Dwarf => dwarf::Species::age_from_years(years),
+            Self::Elf => elf::Species::age_from_years(years),
+            Self::Gnome => gnome::Species::age_from_years(years),
+            Self::HalfElf => half_elf::Species::age_from_years(years),
+            Self::HalfOrc => half_orc::Species::age_from_years(years),
+            Self::Halfling => halfling::Species::age_from_years(years),
+            Self::Human => human::Species::age_from_years(years),
+            Self::Tiefling => tiefling::Species::age_from_years(years),
+        }
+    }
+
     pub fn default_ethnicity(&self) -> Ethnicity {
+        self.ethnicity_weights()[0].0
+    }
+
+    /// The relative likelihood of each ethnicity among members of this species, used when the
+    /// local demographics don't include the species at all. The first entry is the default.
+    pub fn ethnicity_weights(&self) -> &'static [(Ethnicity, u64)] {
         match self {
-            Self::Dragonborn => Ethnicity::Dragonborn,
-            Self::Dwarf => Ethnicity::Dwarvish,
-            Self::Elf => Ethnicity::Elvish,
-            Self::Gnome => Ethnicity::Gnomish,
-            Self::HalfElf => Ethnicity::Human,
-            Self::HalfOrc => Ethnicity::Orcish,
-            Self::Halfling => Ethnicity::Halfling,
-            Self::Human => Ethnicity::Human,
-            Self::Tiefling => Ethnicity::Tiefling,
+            Self::Dragonborn => &[(Ethnicity::Dragonborn, 1)],
+            Self::Dwarf => &[(Ethnicity::Dwarvish, 1)],
+            Self::Elf => &[(Ethnicity::Elvish, 1)],
+            Self::Gnome => &[(Ethnicity::Gnomish, 1)],
+            Self::HalfElf => &[(Ethnicity::Human, 2), (Ethnicity::Elvish, 1)],
+            Self::HalfOrc => &[(Ethnicity::Orcish, 1)],
+            Self::Halfling => &[(Ethnicity::Halfling, 1)],
+            Self::Human => &[
+                (Ethnicity::Human, 16),
+                (Ethnicity::Khmer, 1),
+                (Ethnicity::Maori, 1),
+                (Ethnicity::Quechua, 1),
+                (Ethnicity::Yoruba, 1),
+            ],
+            Self::Tiefling => &[(Ethnicity::Tiefling, 1)],
         }
     }
 }
@@ -253,6 +293,17 @@ mod test {
         assert_eq!(Ethnicity::Tiefling, Species::Tiefling.default_ethnicity());
     }
 
+    #[test]
+    fn ethnicity_weights_test() {
+        assert_eq!(
+            &[(Ethnicity::Dwarvish, 1)][..],
+            Species::Dwarf.ethnicity_weights(),
+        );
+        assert!(Species::Human
+            .ethnicity_weights()
+            .contains(&(Ethnicity::Yoruba, 1)));
+    }
+
     #[test]
     fn try_from_test() {
         assert_eq!(Ok(Species::Dragonborn), "dragonborn".parse());
@@ -272,6 +323,19 @@ mod test {
         assert_eq!("tiefling", format!("{}", Species::Tiefling));
     }
 
+    #[test]
+    fn get_emoji_test() {
+        assert_eq!("🐉", Species::Dragonborn.get_emoji());
+        assert_eq!("⛏", Species::Dwarf.get_emoji());
+        assert_eq!("🧝", Species::Elf.get_emoji());
+        assert_eq!("🍄", Species::Gnome.get_emoji());
+        assert_eq!("🧝", Species::HalfElf.get_emoji());
+        assert_eq!("🪓", Species::HalfOrc.get_emoji());
+        assert_eq!("🥧", Species::Halfling.get_emoji());
+        assert_eq!("🧑", Species::Human.get_emoji());
+        assert_eq!("😈", Species::Tiefling.get_emoji());
+    }
+
     #[test]
     fn serialize_deserialize_test() {
         assert_eq!("\"human\"", serde_json::to_string(&Species::Human).unwrap());
@@ -1,6 +1,10 @@
-use super::{Age, Gender, Npc, NpcRelations};
+use super::{Age, Fate, Npc, NpcRelations, Relationship};
+use crate::utils::{capitalize, CodeView, EscapedView};
+use crate::world::place::LocationView;
+use crate::world::{list, LinkView};
 use std::fmt;
 
+/// Formatting with the alternate flag (`{:#}`) omits the emoji.
 pub struct SummaryView<'a>(&'a Npc);
 
 pub struct DescriptionView<'a>(&'a Npc);
@@ -10,15 +14,29 @@ pub struct DetailsView<'a> {
     relations: NpcRelations,
 }
 
+/// The NPC's age, species, and ethnicity, eg. "elderly human".
+struct NounView<'a>(&'a Npc);
+
 fn write_summary_details(npc: &Npc, f: &mut fmt::Formatter) -> fmt::Result {
-    if let Some(age) = npc.age.value() {
-        age.fmt_with_species_ethnicity(npc.species.value(), npc.ethnicity.value(), f)?;
-    } else if let Some(species) = npc.species.value() {
-        write!(f, "{}", species)?;
-    } else if let Some(ethnicity) = npc.ethnicity.value() {
-        write!(f, "{} person", ethnicity)?;
+    if let Some(attitude) = npc.attitude.value() {
+        write!(f, "{} ", attitude)?;
+    }
+
+    if let Some(demeanor) = npc.demeanor.value() {
+        write!(f, "{} ", demeanor)?;
+    }
+
+    if let Some(occupation) = npc.occupation.value() {
+        // "elderly person" becomes "elderly blacksmith", while "elderly human" becomes "elderly
+        // human blacksmith".
+        let noun = NounView(npc).to_string();
+        if let Some(prefix) = noun.strip_suffix("person") {
+            write!(f, "{}{}", prefix, occupation)?;
+        } else {
+            write!(f, "{} {}", noun, occupation)?;
+        }
     } else {
-        write!(f, "person")?;
+        write!(f, "{}", NounView(npc))?;
     }
 
     if let Some(gender) = npc.gender.value() {
@@ -28,6 +46,131 @@ fn write_summary_details(npc: &Npc, f: &mut fmt::Formatter) -> fmt::Result {
     Ok(())
 }
 
+/// The NPC's fate and transient statuses as badges following the summary, eg. " [dead]" or
+/// " [poisoned]".
+fn write_statuses(npc: &Npc, f: &mut fmt::Formatter) -> fmt::Result {
+    npc.fate
+        .value()
+        .map(|fate| write!(f, " [{}]", fate))
+        .transpose()?;
+
+    npc.statuses
+        .value()
+        .filter(|statuses| !statuses.is_empty())
+        .map(|statuses| write!(f, " {}", statuses.display()))
+        .transpose()?;
+
+    Ok(())
+}
+
+/// The "Player Character" section of the details view, with whichever statistics have been
+/// provided.
+fn write_player_character(npc: &Npc, f: &mut fmt::Formatter) -> fmt::Result {
+    let pc = if let Some(pc) = npc.pc.value() {
+        pc
+    } else {
+        return Ok(());
+    };
+
+    let mut lines = Vec::new();
+
+    if let Some(class) = &pc.class {
+        lines.push(format!("**Class:** {}", class));
+    }
+
+    if let Some(level) = pc.level {
+        lines.push(format!("**Level:** {}", level));
+    }
+
+    if let Some(armor_class) = pc.armor_class {
+        lines.push(format!("**Armor Class:** {}", armor_class));
+    }
+
+    if let Some(passive_perception) = pc.passive_perception {
+        lines.push(format!("**Passive Perception:** {}", passive_perception));
+    }
+
+    if let Some(player) = &pc.player {
+        lines.push(format!("**Player:** {}", player));
+    }
+
+    if lines.is_empty() {
+        write!(f, "\n\n## Player Character")
+    } else {
+        write!(f, "\n\n## Player Character\n\n{}", lines.join("\\\n"))
+    }
+}
+
+/// The "Relationships" section of the details view, listing the character's contacts and whose
+/// contact they are. A contact's hook and secret are shown on their own entry.
+fn write_relationships(relations: &NpcRelations, f: &mut fmt::Formatter) -> fmt::Result {
+    let lines: Vec<String> = relations
+        .relationships
+        .iter()
+        .filter_map(|(relationship, other)| {
+            let name = LinkView::new(other.name.value()?);
+
+            Some(match relationship {
+                Relationship::Contact { kind, .. } => {
+                    format!("**{}:** {}", capitalize(kind.as_str()), name)
+                }
+                Relationship::ContactOf {
+                    kind, hook, secret, ..
+                } => format!(
+                    "**{}:** {}\\\n**Hook:** {}\\\n**Secret:** {}",
+                    capitalize(kind.relation()),
+                    name,
+                    hook,
+                    secret,
+                ),
+            })
+        })
+        .collect();
+
+    if lines.is_empty() {
+        Ok(())
+    } else {
+        write!(f, "\n\n## Relationships\n\n{}", lines.join("\\\n"))
+    }
+}
+
+/// The "Appearance" section of the details view, omitted entirely if nothing is known about the
+/// NPC's appearance other than their size.
+fn write_appearance(npc: &Npc, f: &mut fmt::Formatter) -> fmt::Result {
+    let mut lines = Vec::new();
+
+    if let Some(size) = npc.size.value() {
+        let (height_ft, height_in) = size.height_ft_in();
+        lines.push(format!("**Height:** {}'{}\"", height_ft, height_in));
+    }
+
+    if let Some(build) = npc.build.value() {
+        lines.push(format!("**Build:** {}", build));
+    }
+
+    if let Some(hair) = npc.hair.value() {
+        lines.push(format!("**Hair:** {}", hair.description()));
+    }
+
+    if let Some(eyes) = npc.eyes.value() {
+        lines.push(format!("**Eyes:** {}", eyes.description()));
+    }
+
+    if let Some(mark) = npc.mark.value() {
+        lines.push(format!("**Distinguishing Mark:** {}", mark.description()));
+    }
+
+    if let Some(clothing) = npc.clothing.value() {
+        lines.push(format!("**Clothing:** {}", clothing.description()));
+    }
+
+    if lines.len() > usize::from(npc.size.is_some()) {
+        write!(f, "\n\n## Appearance\n\n{}", lines.join("\\\n"))
+    } else {
+        Ok(())
+    }
+}
+
 impl<'a> SummaryView<'a> {
     pub fn new(npc: &'a Npc) -> Self {
         Self(npc)
@@ -46,44 +189,64 @@ impl<'a> DetailsView<'a> {
     }
 }
 
+impl<'a> fmt::Display for NounView<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let npc = self.0;
+
+        if let Some(homebrew) = npc.homebrew.value() {
+            match npc.age.value() {
+                Some(age @ (Age::Infant | Age::Child)) => write!(f, "{} {}", homebrew, age),
+                Some(age) => write!(f, "{} {}", age, homebrew),
+                None => write!(f, "{}", homebrew),
+            }
+        } else if let Some(age) = npc.age.value() {
+            age.fmt_with_species_ethnicity(npc.species.value(), npc.ethnicity.value(), f)
+        } else if let Some(species) = npc.species.value() {
+            write!(f, "{}", species)
+        } else if let Some(ethnicity) = npc.ethnicity.value() {
+            write!(f, "{} person", ethnicity)
+        } else {
+            write!(f, "person")
+        }
+    }
+}
+
 impl<'a> fmt::Display for SummaryView<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let npc = self.0;
         let has_details = npc.age.is_some()
             || npc.ethnicity.is_some()
             || npc.gender.is_some()
-            || npc.species.is_some();
-
-        write!(
-            f,
-            "{} ",
-            match (npc.age.value(), npc.gender.value()) {
-                (Some(Age::Infant), _) => '\u{1f476}',
-                (Some(Age::Child | Age::Adolescent), Some(Gender::Feminine)) => '\u{1f467}',
-                (Some(Age::Child | Age::Adolescent), Some(Gender::Masculine)) => '\u{1f466}',
-                (Some(Age::Child | Age::Adolescent), _) => '\u{1f9d2}',
-                (Some(Age::Elderly | Age::Geriatric), Some(Gender::Feminine)) => '\u{1f475}',
-                (Some(Age::Elderly | Age::Geriatric), Some(Gender::Masculine)) => '\u{1f474}',
-                (Some(Age::Elderly | Age::Geriatric), _) => '\u{1f9d3}',
-                (_, Some(Gender::Feminine)) => '\u{1f469}',
-                (_, Some(Gender::Masculine)) => '\u{1f468}',
-                _ => '\u{1f9d1}',
-            },
-        )?;
+            || npc.occupation.is_some()
+            || npc.species.is_some()
+            || npc.attitude.is_some()
+            || npc.demeanor.is_some();
+        let pc = npc.pc.value();
+
+        if !f.alternate() {
+            write!(f, "{} ", npc.get_emoji(false))?;
+        }
 
         if let Some(name) = npc.name.value() {
-            if has_details {
-                write!(f, "`{}` (", name)?;
-                write_summary_details(npc, f)?;
+            if has_details || pc.is_some() {
+                write!(f, "{} (", CodeView::new(name))?;
+                if has_details {
+                    write_summary_details(npc, f)?;
+                }
+                match (has_details, pc) {
+                    (true, Some(pc)) => write!(f, "; {}", pc.display())?,
+                    (false, Some(pc)) => write!(f, "{}", pc.display())?,
+                    (_, None) => {}
+                }
                 write!(f, ")")?;
             } else {
-                write!(f, "`{}`", name)?;
+                write!(f, "{}", CodeView::new(name))?;
             }
         } else {
             write_summary_details(npc, f)?;
         }
 
-        Ok(())
+        write_statuses(npc, f)
     }
 }
 
@@ -101,14 +264,30 @@ impl<'a> fmt::Display for DetailsView<'a> {
 
         npc.name
             .value()
-            .map(|name| write!(f, "# {}", name))
+            .map(|name| write!(f, "# {}", EscapedView::new(name)))
             .unwrap_or_else(|| write!(f, "# Unnamed NPC"))?;
 
         write!(f, "\n*")?;
         write_summary_details(npc, f)?;
         write!(f, "*")?;
+        write_statuses(npc, f)?;
+
+        npc.aliases
+            .value()
+            .filter(|aliases| !aliases.is_empty())
+            .map(|aliases| {
+                let aliases: Vec<String> = aliases
+                    .iter()
+                    .map(|alias| EscapedView::new(alias).to_string())
+                    .collect();
+                write!(f, "\n\n**Also known as:** {}", list(&aliases))
+            })
+            .transpose()?;
 
         match (npc.species.value(), npc.ethnicity.value()) {
+            _ if npc.homebrew.is_some() => {
+                write!(f, "\n\n**Species:** {}", npc.homebrew)?;
+            }
             (Some(species), Some(ethnicity)) if ethnicity != &species.default_ethnicity() => {
                 write!(f, "\n\n**Species:** {} ({})", species, ethnicity)?
             }
@@ -129,34 +308,93 @@ impl<'a> fmt::Display for DetailsView<'a> {
             .value()
             .map(|size| write!(f, "\\\n**Size:** {}", size))
             .transpose()?;
+        npc.fate
+            .value()
+            .map(|fate| match fate {
+                Fate::Dead { time } => write!(f, "\\\n**Died:** {}", time.display_long()),
+                Fate::Retired { time } => write!(f, "\\\n**Retired:** {}", time.display_long()),
+            })
+            .transpose()?;
 
         relations
             .location
             .as_ref()
             .map(|(parent, grandparent)| {
-                if let Some(grandparent) = grandparent {
-                    write!(
-                        f,
-                        "\\\n**Location:** {}, {}",
-                        parent.display_name(),
-                        grandparent.display_name(),
-                    )
-                } else {
-                    write!(f, "\\\n**Location:** {}", parent.display_summary())
-                }
+                write!(f, "\\\n**Location:** ")?;
+                fmt::Display::fmt(&LocationView::new(parent, grandparent.as_ref()), f)
             })
             .transpose()?;
 
+        write_player_character(npc, f)?;
+        write_relationships(relations, f)?;
+        write_appearance(npc, f)?;
+
+        npc.resources
+            .value()
+            .filter(|resources| !resources.is_empty())
+            .map(|resources| write!(f, "\n\n{}", resources.display()))
+            .transpose()?;
+
+        npc.inventory
+            .value()
+            .filter(|inventory| !inventory.is_empty())
+            .map(|inventory| {
+                write!(
+                    f,
+                    "\n\n{}\\\n**Load:** {} / {} lbs",
+                    inventory.display(),
+                    (inventory.weight() * 10.).round() / 10.,
+                    npc.carrying_capacity(),
+                )
+            })
+            .transpose()?;
+
+        // Spell names double as commands, so each one links to its rules text.
+        npc.spells
+            .value()
+            .filter(|spells| !spells.is_empty())
+            .map(|spells| {
+                write!(
+                    f,
+                    "\n\n**Spells Known:** {}",
+                    spells
+                        .iter()
+                        .map(|spell| format!("`{}`", spell))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                )
+            })
+            .transpose()?;
+
+        write_unlocked_fields(npc, f)?;
+
         write!(f, "\n\n</div>")?;
 
         Ok(())
     }
 }
 
+/// Journal entries are locked unless the user says otherwise, so only the exceptions are shown.
+fn write_unlocked_fields(npc: &Npc, f: &mut fmt::Formatter) -> fmt::Result {
+    let unlocked = npc.unlocked_fields();
+
+    if npc.uuid.is_some() && !unlocked.is_empty() {
+        write!(f, "\n\n**Unlocked:** {}", unlocked.join(", "))?;
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::world::npc::{Age, Ethnicity, Gender, Size, Species};
+    use crate::reference::Item as Equipment;
+    use crate::time::Time;
+    use crate::world::inventory::{Inventory, Item};
+    use crate::world::npc::{
+        Age, Attitude, Build, Clothing, ContactKind, Ethnicity, Eyes, Gender, Hair, Mark,
+        Occupation, PlayerCharacter, Size, Species,
+    };
     use crate::world::place::{Place, PlaceType};
     use crate::world::Field;
 
@@ -215,6 +453,50 @@ mod test {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn summary_view_test_alternate() {
+        assert_eq!("person", format!("{:#}", gen_npc(0).display_summary()));
+        assert_eq!(
+            "`Potato Johnson` (elderly human, he/him)",
+            format!(
+                "{:#}",
+                gen_npc(NAME | AGE | SPECIES | GENDER).display_summary()
+            ),
+        );
+    }
+
+    #[test]
+    fn summary_view_test_attitude() {
+        let mut npc = gen_npc(0);
+        npc.attitude = Attitude::Friendly.into();
+        assert_eq!("friendly person", format!("{:#}", npc.display_summary()));
+
+        let mut npc = gen_npc(NAME | AGE | SPECIES);
+        npc.attitude = Attitude::Hostile.into();
+        npc.occupation = Occupation::Blacksmith.into();
+        assert_eq!(
+            "`Potato Johnson` (hostile elderly human blacksmith)",
+            format!("{:#}", npc.display_summary()),
+        );
+    }
+
+    #[test]
+    fn description_view_test_occupation() {
+        [
+            ("blacksmith", 0),
+            ("elderly blacksmith", AGE),
+            ("human blacksmith", SPECIES),
+            ("elderly human blacksmith, he/him", AGE | SPECIES | GENDER),
+            ("elvish blacksmith", ETHNICITY),
+        ]
+        .into_iter()
+        .for_each(|(expected, bitmask)| {
+            let mut npc = gen_npc(bitmask);
+            npc.occupation = Occupation::Blacksmith.into();
+            assert_eq!(expected, npc.display_description().to_string());
+        });
+    }
+
     #[test]
     fn details_view_test_filled() {
         let mut npc = Npc::default();
@@ -245,6 +527,159 @@ mod test {
         );
     }
 
+    #[test]
+    fn details_view_test_inventory() {
+        let mut npc = Npc::default();
+        npc.name.replace("Gottfried".to_string());
+
+        let mut inventory = Inventory::default();
+        inventory.coins.gp = 15;
+        inventory.add_item(Item::equipment(&Equipment::Longsword, 1));
+        npc.inventory.replace(inventory);
+
+        assert_eq!(
+            r#"<div class="thing-box npc">
+
+# Gottfried
+*person*
+
+**Species:** N/A
+
+**Coins:** 15 gp\
+**Items:** Longsword\
+**Load:** 3.3 / 150 lbs
+
+</div>"#,
+            format!("{}", npc.display_details(NpcRelations::default())),
+        );
+    }
+
+    #[test]
+    fn details_view_test_fate() {
+        let mut npc = Npc::default();
+        npc.name.replace("Gottfried".to_string());
+        npc.fate.replace(Fate::Dead {
+            time: Time::try_new(3, 20, 0, 0).unwrap(),
+        });
+
+        assert_eq!("`Gottfried` [dead]", format!("{:#}", npc.display_summary()),);
+        assert_eq!(
+            r#"<div class="thing-box npc">
+
+# Gottfried
+*person* [dead]
+
+**Species:** N/A\
+**Died:** day 3 at 8:00:00 pm
+
+</div>"#,
+            format!("{}", npc.display_details(NpcRelations::default())),
+        );
+    }
+
+    #[test]
+    fn details_view_test_pc() {
+        let mut npc = Npc::default();
+        npc.name.replace("Aragorn".to_string());
+        npc.species.replace(Species::Human);
+        npc.pc.replace(PlayerCharacter {
+            player: Some("Sam".to_string()),
+            class: Some("ranger".to_string()),
+            level: Some(5),
+            armor_class: Some(16),
+            passive_perception: Some(14),
+        });
+
+        assert_eq!(
+            "`Aragorn` (human; level 5 ranger, played by Sam)",
+            format!("{:#}", npc.display_summary()),
+        );
+        assert_eq!(
+            r#"<div class="thing-box npc">
+
+# Aragorn
+*human*
+
+**Species:** human
+
+## Player Character
+
+**Class:** ranger\
+**Level:** 5\
+**Armor Class:** 16\
+**Passive Perception:** 14\
+**Player:** Sam
+
+</div>"#,
+            format!("{}", npc.display_details(NpcRelations::default())),
+        );
+
+        npc.species.clear();
+        assert_eq!(
+            "`Aragorn` (level 5 ranger, played by Sam)",
+            format!("{:#}", npc.display_summary()),
+        );
+    }
+
+    #[test]
+    fn details_view_test_spells() {
+        let mut npc = Npc::default();
+        npc.name.replace("Gottfried".to_string());
+        npc.spells
+            .replace(vec!["Fireball".to_string(), "Shield".to_string()]);
+
+        assert_eq!(
+            r#"<div class="thing-box npc">
+
+# Gottfried
+*person*
+
+**Species:** N/A
+
+**Spells Known:** `Fireball`, `Shield`
+
+</div>"#,
+            format!("{}", npc.display_details(NpcRelations::default())),
+        );
+    }
+
+    #[test]
+    fn details_view_test_appearance() {
+        let mut npc = Npc::default();
+        npc.name.replace("Potato Johnson".to_string());
+        npc.size.replace(Size::Medium {
+            height: 71,
+            weight: 140,
+        });
+        npc.build.replace(Build::Stocky);
+        npc.hair.replace(Hair::RedHaired);
+        npc.eyes.replace(Eyes::Blue);
+        npc.mark.replace(Mark::Scarred);
+        npc.clothing.replace(Clothing::Robed);
+
+        assert_eq!(
+            r#"<div class="thing-box npc">
+
+# Potato Johnson
+*person*
+
+**Species:** N/A\
+**Size:** 5'11", 140 lbs (medium)
+
+## Appearance
+
+**Height:** 5'11"\
+**Build:** stocky\
+**Hair:** red\
+**Eyes:** blue\
+**Distinguishing Mark:** a prominent scar\
+**Clothing:** robes
+
+</div>"#,
+            format!("{}", npc.display_details(NpcRelations::default())),
+        );
+    }
+
     #[test]
     fn details_view_test_species_ethnicity() {
         assert_eq!(
@@ -325,6 +760,7 @@ mod test {
                 },
                 None,
             )),
+            ..Default::default()
         };
 
         assert_eq!(
@@ -334,7 +770,7 @@ mod test {
 *person*
 
 **Species:** N/A\\
-**Location:** ⛰ `Mount Doom` (mountain)
+**Location:** ⛰ {Mount Doom} (mountain)
 
 </div>",
             format!("{}", DetailsView::new(&npc, relations)),
@@ -361,6 +797,7 @@ mod test {
                     ..Default::default()
                 }),
             )),
+            ..Default::default()
         };
 
         assert_eq!(
@@ -370,7 +807,62 @@ mod test {
 *person*
 
 **Species:** N/A\\
-**Location:** 🏨 `The Prancing Pony`, 🏘 `Bree`
+**Location:** 🏨 {The Prancing Pony}, 🏘 {Bree}
+
+</div>",
+            format!("{}", DetailsView::new(&npc, relations)),
+        );
+    }
+
+    #[test]
+    fn details_view_test_relationships() {
+        let npc = Npc {
+            name: "Gottfried".into(),
+            ..Default::default()
+        };
+
+        let relations = NpcRelations {
+            relationships: vec![
+                (
+                    Relationship::ContactOf {
+                        uuid: uuid::Uuid::nil().into(),
+                        kind: ContactKind::Mentor,
+                        hook: "Needs a favour.".to_string(),
+                        secret: "Owes money.".to_string(),
+                    },
+                    Npc {
+                        name: "Aragorn".into(),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    Relationship::Contact {
+                        uuid: uuid::Uuid::nil().into(),
+                        kind: ContactKind::Rival,
+                    },
+                    Npc {
+                        name: "Boromir".into(),
+                        ..Default::default()
+                    },
+                ),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            "<div class=\"thing-box npc\">
+
+# Gottfried
+*person*
+
+**Species:** N/A
+
+## Relationships
+
+**Mentor to:** {Aragorn}\\
+**Hook:** Needs a favour.\\
+**Secret:** Owes money.\\
+**Rival:** {Boromir}
 
 </div>",
             format!("{}", DetailsView::new(&npc, relations)),
@@ -0,0 +1,218 @@
+//! Where the party currently is. The location is a place in the journal, which new characters
+//! are placed in by default when they're created.
+
+use super::{Place, PlaceUuid, Thing};
+use crate::app::{AppMeta, CommandError};
+use crate::config::Config;
+use crate::locale::Message;
+use crate::storage::{Change, KeyValue};
+use crate::Uuid;
+
+/// The place in the journal where the party currently is, if any.
+pub async fn party_location(app_meta: &AppMeta) -> Option<Place> {
+    let uuid = app_meta
+        .repository
+        .get_key_value(&KeyValue::PartyLocation(None))
+        .await
+        .ok()
+        .and_then(KeyValue::party_location)?;
+
+    match app_meta.repository.get_by_uuid(uuid.as_ref()).await {
+        Ok(Thing::Place(place)) => Some(place),
+        _ => None,
+    }
+}
+
+/// A line noting where the party is, eg. "_The party is at `The Silver Eel`._", for the end of
+/// outputs where it's useful to know.
+pub async fn party_location_notice(app_meta: &AppMeta) -> Option<String> {
+    party_location(app_meta)
+        .await
+        .map(|place| format!("_The party is at `{}`._", place.name))
+}
+
+/// Place a newly created character where the party is, unless they've already been put somewhere.
+pub async fn place_with_party(thing: &mut Thing, app_meta: &AppMeta) {
+    if let Thing::Npc(npc) = thing {
+        if npc.location_uuid.is_none() {
+            if let Some(uuid) = party_location(app_meta).await.and_then(|place| place.uuid) {
+                npc.location_uuid = uuid.into();
+            }
+        }
+    }
+}
+
+/// Move the party to the named place, saving it to the journal first if necessary. If `travel`
+/// is set, the party is described as having travelled there rather than simply being there.
+pub async fn move_party(
+    name: &str,
+    travel: bool,
+    app_meta: &mut AppMeta,
+) -> Result<String, CommandError> {
+    let language = Config::load(app_meta).await.language;
+
+    let place = match app_meta.repository.get_by_name(name).await {
+        Ok(Thing::Place(place)) => place,
+        Ok(thing) => {
+            return Err(CommandError::unknown_entity(
+                &thing.name().to_string(),
+                format!("{} isn't a place.", thing.name()),
+            ))
+        }
+        Err(_) => {
+            return Err(CommandError::unknown_entity(
+                name,
+                Message::NoEntityNamed { name }.localize(language),
+            ))
+        }
+    };
+
+    let name = place.name.to_string();
+    let mut notices = Vec::new();
+
+    // Saving the place and moving the party are made together, so that `undo` reverses both at
+    // once and neither is made if the other fails.
+    let mut changes = Vec::new();
+
+    let uuid: PlaceUuid = if let Some(uuid) = place.uuid {
+        uuid
+    } else {
+        let uuid = Uuid::new_v4();
+
+        changes.push(Change::Save {
+            name: name.clone(),
+            uuid: Some(uuid),
+        });
+
+        notices.push(format!(
+            "_{} was automatically saved to your `journal` so that the party can be there._",
+            name,
+        ));
+
+        uuid.into()
+    };
+
+    changes.push(Change::SetKeyValue {
+        key_value: KeyValue::PartyLocation(Some(uuid)),
+    });
+
+    let saving = changes.len() > 1;
+
+    app_meta
+        .repository
+        .transaction(changes)
+        .await
+        .map_err(|_| {
+            CommandError::storage(if saving {
+                Message::CouldNotSave { name: &name }.localize(language)
+            } else {
+                Message::UnknownError.localize(language)
+            })
+        })?;
+
+    Ok(format!(
+        "{} `{}`. Use `undo` to reverse this.{}",
+        if travel {
+            "The party travels to"
+        } else {
+            "The party is now at"
+        },
+        name,
+        notices
+            .iter()
+            .map(|notice| format!("\\\n{}", notice))
+            .collect::<String>(),
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::storage::MemoryDataStore;
+    use crate::world::Npc;
+    use crate::Event;
+    use tokio_test::block_on;
+
+    #[test]
+    fn move_party_test() {
+        let mut app_meta = app_meta();
+
+        block_on(
+            app_meta.repository.modify(Change::CreateAndSave {
+                thing: Place {
+                    name: "The Silver Eel".into(),
+                    ..Default::default()
+                }
+                .into(),
+            }),
+        )
+        .unwrap();
+
+        block_on(
+            app_meta.repository.modify(Change::Create {
+                thing: Place {
+                    name: "Waterdeep".into(),
+                    ..Default::default()
+                }
+                .into(),
+            }),
+        )
+        .unwrap();
+
+        block_on(
+            app_meta.repository.modify(Change::CreateAndSave {
+                thing: Npc {
+                    name: "Gandalf".into(),
+                    ..Default::default()
+                }
+                .into(),
+            }),
+        )
+        .unwrap();
+
+        assert_eq!(None, block_on(party_location_notice(&app_meta)));
+
+        assert_eq!(
+            "The party is now at `The Silver Eel`. Use `undo` to reverse this.",
+            block_on(move_party("the silver eel", false, &mut app_meta)).unwrap(),
+        );
+        assert_eq!(
+            Some("_The party is at `The Silver Eel`._".to_string()),
+            block_on(party_location_notice(&app_meta)),
+        );
+
+        assert_eq!(
+            "The party travels to `Waterdeep`. Use `undo` to reverse this.\\\n_Waterdeep was automatically saved to your `journal` so that the party can be there._",
+            block_on(move_party("Waterdeep", true, &mut app_meta)).unwrap(),
+        );
+        assert_eq!(
+            Some("Waterdeep".to_string()),
+            block_on(party_location(&app_meta)).map(|place| place.name.to_string()),
+        );
+
+        block_on(app_meta.repository.undo());
+        assert_eq!(
+            Some("The Silver Eel".to_string()),
+            block_on(party_location(&app_meta)).map(|place| place.name.to_string()),
+        );
+
+        assert_eq!(
+            "Gandalf isn't a place.",
+            block_on(move_party("Gandalf", false, &mut app_meta))
+                .unwrap_err()
+                .message(),
+        );
+        assert_eq!(
+            "There is no entity named \"Mordor\".",
+            block_on(move_party("Mordor", false, &mut app_meta))
+                .unwrap_err()
+                .message(),
+        );
+    }
+
+    fn event_dispatcher(_event: Event) {}
+
+    fn app_meta() -> AppMeta {
+        AppMeta::new(MemoryDataStore::default(), &event_dispatcher)
+    }
+}
@@ -0,0 +1,605 @@
+//! Sensory details for describing a place at the table: what can be heard and smelled there, what
+//! is going on at the current time of day, and a set of audio cues that a frontend can map to the
+//! tracks of a soundboard.
+
+use super::{Place, PlaceType};
+use crate::time::TimeOfDay;
+use rand::prelude::*;
+use serde::Serialize;
+use std::fmt;
+
+/// The number of sounds and smells picked from a soundscape.
+const SOUNDS_SHOWN: usize = 3;
+const SMELLS_SHOWN: usize = 2;
+
+/// The atmosphere of a place at a given time of day, as shown by `ambience [name]` and passed to
+/// the frontend with [`Event::Ambience`](crate::Event::Ambience).
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Ambience {
+    pub name: String,
+    pub time_of_day: &'static str,
+    pub sounds: Vec<&'static str>,
+    pub smells: Vec<&'static str>,
+    pub activity: Option<&'static str>,
+
+    /// Short, stable identifiers (eg. `tavern-chatter`) for the frontend to match against its
+    /// soundboard. They don't change between calls for the same place and time of day.
+    pub cues: Vec<&'static str>,
+}
+
+/// Sensory details for a family of places. A soundscape without anything to say about a time of
+/// day leaves the activity out.
+struct Soundscape {
+    /// The kinds of place drawing from the soundscape, by their canonical term. The broad terms
+    /// `building`, `location`, `region`, and `vehicle` catch anything without a soundscape of its
+    /// own.
+    terms: &'static [&'static str],
+    sounds: &'static [&'static str],
+    smells: &'static [&'static str],
+    night: &'static [&'static str],
+    morning: &'static [&'static str],
+    midday: &'static [&'static str],
+    afternoon: &'static [&'static str],
+    evening: &'static [&'static str],
+    day_cues: &'static [&'static str],
+    night_cues: &'static [&'static str],
+}
+
+impl Soundscape {
+    fn activity(&self, time_of_day: TimeOfDay) -> &'static [&'static str] {
+        match time_of_day {
+            TimeOfDay::Night => self.night,
+            TimeOfDay::Morning => self.morning,
+            TimeOfDay::Midday => self.midday,
+            TimeOfDay::Afternoon => self.afternoon,
+            TimeOfDay::Evening => self.evening,
+        }
+    }
+
+    fn cues(&self, time_of_day: TimeOfDay) -> &'static [&'static str] {
+        if time_of_day == TimeOfDay::Night {
+            self.night_cues
+        } else {
+            self.day_cues
+        }
+    }
+}
+
+const SOUNDSCAPES: &[Soundscape] = &[
+    Soundscape {
+        terms: &["bar", "brewery", "club", "distillery", "inn", "restaurant"],
+        sounds: &[
+            "the clink of tankards",
+            "a burst of laughter from a corner table",
+            "the crackle of the hearth",
+            "a fiddle playing slightly out of tune",
+            "the scrape of a bench on the floorboards",
+        ],
+        smells: &["spilled ale", "wood smoke", "roasting meat", "pipe smoke"],
+        night: &["The last drinkers are being coaxed out into the night."],
+        morning: &["A server is scrubbing down the tables from last night."],
+        midday: &["Travellers are taking their midday meal at the long tables."],
+        afternoon: &["A few regulars nurse their drinks in the quiet."],
+        evening: &["The common room is packed, and someone is calling for a song."],
+        day_cues: &["tavern-chatter", "hearth-fire", "tavern-music"],
+        night_cues: &["hearth-fire", "creaking-wood"],
+    },
+    Soundscape {
+        terms: &[
+            "bakery",
+            "food-counter",
+            "general-store",
+            "imports-shop",
+            "market",
+            "specialty-shop",
+            "trading-post",
+        ],
+        sounds: &[
+            "merchants calling out their prices",
+            "coins changing hands",
+            "the rattle of a handcart",
+            "a dog barking at a pigeon",
+            "haggling in half a dozen accents",
+        ],
+        smells: &["fresh bread", "spices", "fruit on the turn", "damp canvas"],
+        night: &["The stalls are shuttered, and a watchman makes his rounds."],
+        morning: &["Merchants are setting out their wares for the day."],
+        midday: &["Shoppers crowd every counter."],
+        afternoon: &["Trade is slowing, and prices are starting to drop."],
+        evening: &["The last customers are hurried along so the doors can be closed."],
+        day_cues: &["market-crowd", "town-bustle"],
+        night_cues: &["town-night", "wind"],
+    },
+    Soundscape {
+        terms: &[
+            "armorer",
+            "blacksmith",
+            "forge",
+            "lumberyard",
+            "mill",
+            "shipyard",
+            "wainwright",
+            "weaponsmith",
+            "woodshop",
+        ],
+        sounds: &[
+            "the ring of a hammer on an anvil",
+            "the hiss of hot metal in water",
+            "the wheeze of the bellows",
+            "the rasp of a saw",
+            "a foreman shouting orders",
+        ],
+        smells: &["coal smoke", "hot iron", "fresh sawdust", "sweat and oil"],
+        night: &["The fires are banked, and the workshop is dark and still."],
+        morning: &["Apprentices are stoking the fires for the day's work."],
+        midday: &["The work is in full swing, and the noise carries down the street."],
+        afternoon: &["The craftsmen are finishing the day's orders."],
+        evening: &["The tools are being put away for the night."],
+        day_cues: &["smithy", "workshop"],
+        night_cues: &["embers", "town-night"],
+    },
+    Soundscape {
+        terms: &["abbey", "monastery", "shrine", "temple"],
+        sounds: &[
+            "a distant chant",
+            "the toll of a bell",
+            "whispered prayers",
+            "footsteps echoing on stone",
+        ],
+        smells: &[
+            "incense",
+            "candle wax",
+            "old stone",
+            "fresh flowers on the altar",
+        ],
+        night: &["A single candle burns before the altar."],
+        morning: &["The morning prayers are under way."],
+        midday: &["Pilgrims come and go with their offerings."],
+        afternoon: &["An acolyte is sweeping the steps."],
+        evening: &["The faithful are gathering for the evening service."],
+        day_cues: &["choir", "temple-bell"],
+        night_cues: &["temple-quiet", "candle"],
+    },
+    Soundscape {
+        terms: &["cemetery", "crypt", "dungeon", "mausoleum", "ruin", "tomb"],
+        sounds: &[
+            "water dripping somewhere out of sight",
+            "the skitter of rats",
+            "a draught moaning through a crack",
+            "stone settling with a groan",
+        ],
+        smells: &["damp earth", "mould", "rot", "stale air"],
+        night: &[],
+        morning: &[],
+        midday: &[],
+        afternoon: &[],
+        evening: &[],
+        day_cues: &["dungeon-drip", "eerie-wind"],
+        night_cues: &["dungeon-drip", "eerie-wind", "owl"],
+    },
+    Soundscape {
+        terms: &[
+            "academy",
+            "college",
+            "court",
+            "embassy",
+            "library",
+            "palace",
+            "school",
+            "university",
+        ],
+        sounds: &[
+            "the scratch of quills",
+            "hushed conversation",
+            "the rustle of pages",
+            "a door closing somewhere down the hall",
+        ],
+        smells: &["old paper", "ink", "beeswax polish"],
+        night: &["Only a night clerk is still at work by lamplight."],
+        morning: &["The halls fill as the day's business begins."],
+        midday: &["Every bench is taken."],
+        afternoon: &["A long afternoon session drags on."],
+        evening: &["The last of the staff are locking up."],
+        day_cues: &["indoor-murmur", "pages"],
+        night_cues: &["indoor-quiet", "clock"],
+    },
+    Soundscape {
+        terms: &[
+            "barracks",
+            "base",
+            "castle",
+            "citadel",
+            "fort",
+            "fortress",
+            "guardhouse",
+            "keep",
+            "prison",
+            "stronghold",
+            "tower",
+            "wall",
+        ],
+        sounds: &[
+            "boots marching in step",
+            "a sergeant barking orders",
+            "the clank of armour",
+            "a gate creaking on its hinges",
+        ],
+        smells: &["oiled leather", "horses", "cold stone"],
+        night: &["Sentries pace the walls, calling out the watch."],
+        morning: &["The garrison is drilling in the yard."],
+        midday: &["Soldiers queue for their rations."],
+        afternoon: &["The guard is changing."],
+        evening: &["Off-duty soldiers are dicing by the fire."],
+        day_cues: &["soldiers-drilling", "armour"],
+        night_cues: &["night-watch", "wind"],
+    },
+    Soundscape {
+        terms: &[
+            "camp", "capital", "city", "district", "outpost", "street", "town",
+        ],
+        sounds: &[
+            "cartwheels on cobbles",
+            "a street vendor's cry",
+            "children playing",
+            "church bells in the distance",
+            "the clatter of hooves",
+        ],
+        smells: &["chimney smoke", "horse dung", "baking bread"],
+        night: &["The streets are empty, save for the odd drunk and the watch."],
+        morning: &["The town is waking up, and shutters are opening."],
+        midday: &["The streets are crowded with people going about their business."],
+        afternoon: &["The streets have settled into a lazy afternoon."],
+        evening: &["Lamps are being lit, and people are heading home or to the taverns."],
+        day_cues: &["town-bustle"],
+        night_cues: &["town-night", "dogs-barking"],
+    },
+    Soundscape {
+        terms: &[
+            "beach",
+            "coastline",
+            "ferry",
+            "harbor",
+            "island",
+            "lighthouse",
+            "ocean",
+            "pier",
+            "reef",
+            "sea",
+            "ship",
+        ],
+        sounds: &[
+            "waves breaking",
+            "gulls crying overhead",
+            "rigging creaking in the wind",
+            "a bell buoy clanging",
+        ],
+        smells: &["salt spray", "tar", "fish", "seaweed"],
+        night: &["Lanterns bob on the dark water."],
+        morning: &["The fishing boats are heading out."],
+        midday: &["Crews are loading and unloading cargo."],
+        afternoon: &["The boats are coming back in with their catch."],
+        evening: &["Sailors are heading ashore for the night."],
+        day_cues: &["waves", "gulls", "harbor"],
+        night_cues: &["waves", "creaking-wood"],
+    },
+    Soundscape {
+        terms: &["forest", "grove", "jungle", "tree"],
+        sounds: &[
+            "birdsong",
+            "leaves rustling",
+            "a branch snapping underfoot",
+            "the drone of insects",
+        ],
+        smells: &["pine needles", "damp moss", "leaf litter"],
+        night: &["Something moves in the undergrowth, just out of sight."],
+        morning: &["Mist hangs between the trees."],
+        midday: &["Sunlight filters down through the canopy."],
+        afternoon: &["The air is warm and still under the trees."],
+        evening: &["The birds are settling down for the night."],
+        day_cues: &["forest-birds", "wind-in-trees"],
+        night_cues: &["forest-night", "owl"],
+    },
+    Soundscape {
+        terms: &["lake", "marsh", "river", "swamp"],
+        sounds: &[
+            "frogs croaking",
+            "water lapping at the bank",
+            "the buzz of midges",
+            "a fish breaking the surface",
+        ],
+        smells: &["stagnant water", "wet reeds", "mud"],
+        night: &["Will-o'-the-wisps flicker in the distance."],
+        morning: &["Mist rises from the water."],
+        midday: &["Dragonflies dart above the water."],
+        afternoon: &["The heat brings out the midges."],
+        evening: &["The frogs are starting their chorus."],
+        day_cues: &["water", "marsh"],
+        night_cues: &["water", "frogs"],
+    },
+    Soundscape {
+        terms: &["cave", "chasm", "mine", "rift"],
+        sounds: &[
+            "water dripping",
+            "the echo of your own footsteps",
+            "the flutter of bats",
+            "rocks shifting",
+        ],
+        smells: &["wet stone", "guano", "mineral dust"],
+        night: &[],
+        morning: &[],
+        midday: &[],
+        afternoon: &[],
+        evening: &[],
+        day_cues: &["cave", "dungeon-drip"],
+        night_cues: &["cave", "dungeon-drip"],
+    },
+    Soundscape {
+        terms: &[
+            "barrens",
+            "canyon",
+            "desert",
+            "glacier",
+            "hill",
+            "mesa",
+            "mountain",
+            "oasis",
+            "pass",
+            "plateau",
+            "ridge",
+            "tundra",
+            "wasteland",
+        ],
+        sounds: &[
+            "the wind howling",
+            "loose stones skittering",
+            "a hawk's cry",
+            "the crunch of footsteps",
+        ],
+        smells: &["dust", "cold air", "dry scrub"],
+        night: &["The cold sets in fast under the stars."],
+        morning: &["The first light creeps across the land."],
+        midday: &["The sun beats down."],
+        afternoon: &["Long shadows stretch across the ground."],
+        evening: &["The sky burns red as the sun goes down."],
+        day_cues: &["wind", "hawk"],
+        night_cues: &["wind", "wolves"],
+    },
+    Soundscape {
+        terms: &["airship", "caravan", "wagon"],
+        sounds: &[
+            "wheels creaking",
+            "the snap of canvas",
+            "harnesses jingling",
+            "the crew calling to one another",
+        ],
+        smells: &["horses", "tar", "canvas", "road dust"],
+        night: &["The camp is quiet, with a lookout posted."],
+        morning: &["The crew are making ready to move out."],
+        midday: &["The journey drags on under the sun."],
+        afternoon: &["The crew are looking for a place to stop for the night."],
+        evening: &["A cookfire is being lit."],
+        day_cues: &["wagon", "wind"],
+        night_cues: &["campfire", "crickets"],
+    },
+    Soundscape {
+        terms: &["building"],
+        sounds: &[
+            "muffled voices",
+            "footsteps on floorboards",
+            "a door banging",
+        ],
+        smells: &["wood smoke", "dust"],
+        night: &["Everyone inside seems to be asleep."],
+        morning: &[],
+        midday: &[],
+        afternoon: &[],
+        evening: &["Lamplight glows in the windows."],
+        day_cues: &["indoor-murmur"],
+        night_cues: &["indoor-quiet"],
+    },
+    Soundscape {
+        terms: &["location", "place", "region", "vehicle"],
+        sounds: &["the wind", "birdsong", "distant voices"],
+        smells: &["fresh air", "grass"],
+        night: &["It's dark and quiet."],
+        morning: &[],
+        midday: &[],
+        afternoon: &[],
+        evening: &[],
+        day_cues: &["outdoors"],
+        night_cues: &["crickets", "wind"],
+    },
+];
+
+impl Ambience {
+    /// Describe the atmosphere of a place at the given time of day, drawing on the most specific
+    /// soundscape available for its kind.
+    pub fn generate(place: &Place, time_of_day: TimeOfDay, rng: &mut impl Rng) -> Self {
+        let subtype = place.subtype.value().copied().unwrap_or_default();
+        let soundscape = soundscape(&subtype);
+
+        let mut sounds: Vec<_> = soundscape
+            .sounds
+            .choose_multiple(rng, SOUNDS_SHOWN)
+            .copied()
+            .collect();
+        sounds.sort_unstable();
+
+        let mut smells: Vec<_> = soundscape
+            .smells
+            .choose_multiple(rng, SMELLS_SHOWN)
+            .copied()
+            .collect();
+        smells.sort_unstable();
+
+        Self {
+            name: place.name.to_string(),
+            time_of_day: time_of_day_str(time_of_day),
+            sounds,
+            smells,
+            activity: soundscape.activity(time_of_day).choose(rng).copied(),
+            cues: soundscape.cues(time_of_day).to_vec(),
+        }
+    }
+}
+
+fn soundscape(subtype: &PlaceType) -> &'static Soundscape {
+    let category = match subtype {
+        PlaceType::Any => "place",
+        PlaceType::Building(_) => "building",
+        PlaceType::Location(_) => "location",
+        PlaceType::Region(_) => "region",
+        PlaceType::Vehicle(_) => "vehicle",
+    };
+
+    [subtype.as_str(), category]
+        .iter()
+        .find_map(|term| {
+            SOUNDSCAPES
+                .iter()
+                .find(|soundscape| soundscape.terms.contains(term))
+        })
+        .unwrap_or(&SOUNDSCAPES[SOUNDSCAPES.len() - 1])
+}
+
+fn time_of_day_str(time_of_day: TimeOfDay) -> &'static str {
+    match time_of_day {
+        TimeOfDay::Night => "night",
+        TimeOfDay::Morning => "morning",
+        TimeOfDay::Midday => "midday",
+        TimeOfDay::Afternoon => "afternoon",
+        TimeOfDay::Evening => "evening",
+    }
+}
+
+impl fmt::Display for Ambience {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "# Ambience at {}\n*{}*\n\n**Sounds:** {}\\\n**Smells:** {}",
+            self.name,
+            self.time_of_day,
+            self.sounds.join(", "),
+            self.smells.join(", "),
+        )?;
+
+        if let Some(activity) = self.activity {
+            write!(f, "\n\n{}", activity)?;
+        }
+
+        write!(f, "\n\n_Soundboard cues: {}_", self.cues.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn generate_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let inn = place("The Prancing Pony", "inn");
+
+        let ambience = Ambience::generate(&inn, TimeOfDay::Evening, &mut rng);
+        assert_eq!("The Prancing Pony", ambience.name);
+        assert_eq!("evening", ambience.time_of_day);
+        assert_eq!(SOUNDS_SHOWN, ambience.sounds.len());
+        assert_eq!(SMELLS_SHOWN, ambience.smells.len());
+        assert!(ambience
+            .sounds
+            .iter()
+            .all(|sound| SOUNDSCAPES[0].sounds.contains(sound)));
+        assert_eq!(SOUNDSCAPES[0].evening.first().copied(), ambience.activity);
+        assert_eq!(
+            vec!["tavern-chatter", "hearth-fire", "tavern-music"],
+            ambience.cues,
+        );
+
+        assert_eq!(
+            vec!["hearth-fire", "creaking-wood"],
+            Ambience::generate(&inn, TimeOfDay::Night, &mut rng).cues,
+        );
+    }
+
+    #[test]
+    fn soundscape_test() {
+        assert_eq!(
+            Some(&"smithy"),
+            soundscape(&"blacksmith".parse().unwrap()).day_cues.first(),
+        );
+        assert_eq!(
+            Some(&"indoor-murmur"),
+            soundscape(&"bathhouse".parse().unwrap()).day_cues.first(),
+        );
+        assert_eq!(
+            Some(&"outdoors"),
+            soundscape(&"kingdom".parse().unwrap()).day_cues.first(),
+        );
+        assert_eq!(
+            Some(&"outdoors"),
+            soundscape(&PlaceType::Any).day_cues.first(),
+        );
+    }
+
+    #[test]
+    fn soundscape_terms_test() {
+        SOUNDSCAPES
+            .iter()
+            .flat_map(|soundscape| soundscape.terms.iter())
+            .for_each(|term| {
+                assert!(term.parse::<PlaceType>().is_ok(), "{}", term);
+            });
+    }
+
+    #[test]
+    fn display_test() {
+        let ambience = Ambience {
+            name: "The Prancing Pony".to_string(),
+            time_of_day: "evening",
+            sounds: vec!["the clink of tankards", "the crackle of the hearth"],
+            smells: vec!["spilled ale"],
+            activity: Some("The common room is packed, and someone is calling for a song."),
+            cues: vec!["tavern-chatter", "hearth-fire"],
+        };
+
+        assert_eq!(
+            "# Ambience at The Prancing Pony
+*evening*
+
+**Sounds:** the clink of tankards, the crackle of the hearth\\
+**Smells:** spilled ale
+
+The common room is packed, and someone is calling for a song.
+
+_Soundboard cues: tavern-chatter, hearth-fire_",
+            ambience.to_string(),
+        );
+
+        assert_eq!(
+            "# Ambience at The Sunken Crypt
+*night*
+
+**Sounds:** water dripping\\
+**Smells:** mould
+
+_Soundboard cues: dungeon-drip_",
+            Ambience {
+                name: "The Sunken Crypt".to_string(),
+                time_of_day: "night",
+                sounds: vec!["water dripping"],
+                smells: vec!["mould"],
+                activity: None,
+                cues: vec!["dungeon-drip"],
+            }
+            .to_string(),
+        );
+    }
+
+    fn place(name: &str, subtype: &str) -> Place {
+        Place {
+            name: name.into(),
+            subtype: subtype.parse::<PlaceType>().unwrap().into(),
+            ..Default::default()
+        }
+    }
+}
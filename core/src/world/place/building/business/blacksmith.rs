@@ -1,17 +1,36 @@
-use crate::{
-    utils::pluralize,
-    world::{word::ListGenerator, Demographics, Place},
-};
+use crate::world::chances::{pick, ChanceTable, WeightTable};
+use crate::world::{grammar::plural, word::ListGenerator, Demographics, Place};
 use rand::distributions::WeightedIndex;
 use rand::prelude::*;
 
+const NAMES: WeightTable = &[
+    ("The [adjective] [symbol] of [tool] and [tool]", 1),
+    ("[Profession]s [shop]", 1),
+    ("The [adjective] [shop]", 1),
+    ("[tool] and [tool]", 1),
+    ("The [verb] [animal or enemy]", 1),
+];
+
 pub fn generate(place: &mut Place, rng: &mut impl Rng, _demographics: &Demographics) {
     place.name.replace_with(|_| name(rng));
 }
 
+pub fn chances() -> Vec<ChanceTable> {
+    vec![
+        ChanceTable::new("name", NAMES),
+        ChanceTable {
+            label: "[animal or enemy]",
+            outcomes: vec![
+                ("[animal]".to_string(), ANIMALS.len() as u32),
+                ("[enemy]".to_string(), ENEMIES.len() as u32),
+            ],
+        },
+    ]
+}
+
 fn name(rng: &mut impl Rng) -> String {
-    match rng.gen_range(0..5) {
-        0 => {
+    match pick(rng, NAMES) {
+        "The [adjective] [symbol] of [tool] and [tool]" => {
             let (tool1, tool2) = tool_tool(rng);
             format!(
                 "The {} {} of {} and {}",
@@ -21,16 +40,16 @@ fn name(rng: &mut impl Rng) -> String {
                 tool2
             )
         }
-        1 => {
-            let (profession, s) = pluralize(profession(rng));
-            format!("{}{} {}", profession, s, blacksmith_shop_name(rng))
+        "[Profession]s [shop]" => {
+            let profession = plural(profession(rng));
+            format!("{} {}", profession, blacksmith_shop_name(rng))
         }
-        2 => format!("The {} {}", adjective(rng), blacksmith_shop_name(rng)),
-        3 => {
+        "The [adjective] [shop]" => format!("The {} {}", adjective(rng), blacksmith_shop_name(rng)),
+        "[tool] and [tool]" => {
             let (tool1, tool2) = tool_tool(rng);
             format!("{} and {}", tool1, tool2)
         }
-        4 => format!("The {} {}", verb(rng), animal_or_enemy(rng)),
+        "The [verb] [animal or enemy]" => format!("The {} {}", verb(rng), animal_or_enemy(rng)),
         _ => unreachable!(),
     }
 }
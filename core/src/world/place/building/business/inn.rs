@@ -1,38 +1,64 @@
-use crate::utils::pluralize;
+use crate::world::chances::{pick, ChanceTable, WeightTable};
+use crate::world::grammar::plural;
 use crate::world::{word, word::ListGenerator, Demographics, Place};
 use rand::prelude::*;
 
+const NAMES: WeightTable = &[
+    ("The [thing]", 1),
+    ("[Profession]s Arms", 1),
+    ("[thing] and [thing]", 2),
+    ("The [adjective] [thing]", 1),
+    ("[number] [thing]s", 1),
+];
+
+const THINGS: WeightTable = &[
+    ("[animal]", 1),
+    ("[enemy]", 1),
+    ("[food]", 1),
+    ("[profession]", 1),
+    ("[symbol]", 1),
+];
+
 pub fn generate(place: &mut Place, rng: &mut impl Rng, _demographics: &Demographics) {
     place.name.replace_with(|_| name(rng));
+    place.menu.replace_with(|menu| {
+        let mut menu = menu.unwrap_or_default();
+        menu.regenerate(rng);
+        menu
+    });
+}
+
+pub fn chances() -> Vec<ChanceTable> {
+    vec![
+        ChanceTable::new("name", NAMES),
+        ChanceTable::new("[thing]", THINGS),
+    ]
 }
 
 fn name(rng: &mut impl Rng) -> String {
-    match rng.gen_range(0..6) {
-        0 => format!("The {}", thing(rng)),
-        1 => {
-            let (profession, s) = pluralize(word::profession(rng));
-            format!("{}{} Arms", profession, s)
-        }
-        2..=3 => {
+    match pick(rng, NAMES) {
+        "The [thing]" => format!("The {}", thing(rng)),
+        "[Profession]s Arms" => format!("{} Arms", plural(word::profession(rng))),
+        "[thing] and [thing]" => {
             let (thing1, thing2) = thing_thing(rng);
             format!("{} and {}", thing1, thing2)
         }
-        4 => format!("The {} {}", word::adjective(rng), thing(rng)),
-        5 => {
-            let (thing, s) = pluralize(thing(rng));
-            format!("{} {}{}", number(rng), thing, s)
+        "The [adjective] [thing]" => format!("The {} {}", word::adjective(rng), thing(rng)),
+        "[number] [thing]s" => {
+            let thing = plural(thing(rng));
+            format!("{} {}", number(rng), thing)
         }
         _ => unreachable!(),
     }
 }
 
 fn thing(rng: &mut impl Rng) -> &'static str {
-    match rng.gen_range(0..5) {
-        0 => word::animal(rng),
-        1 => word::enemy(rng),
-        2 => word::food(rng),
-        3 => word::profession(rng),
-        4 => word::symbol(rng),
+    match pick(rng, THINGS) {
+        "[animal]" => word::animal(rng),
+        "[enemy]" => word::enemy(rng),
+        "[food]" => word::food(rng),
+        "[profession]" => word::profession(rng),
+        "[symbol]" => word::symbol(rng),
         _ => unreachable!(),
     }
 }
@@ -40,12 +66,12 @@ fn thing(rng: &mut impl Rng) -> &'static str {
 fn thing_thing(rng: &mut impl Rng) -> (&'static str, &'static str) {
     // We're more likely to have two things in the same category.
     let (thing1, thing2) = if rng.gen_bool(0.5) {
-        match rng.gen_range(0..5) {
-            0 => (word::animal(rng), word::animal(rng)),
-            1 => (word::enemy(rng), word::enemy(rng)),
-            2 => (word::food(rng), word::food(rng)),
-            3 => (word::profession(rng), word::profession(rng)),
-            4 => (word::symbol(rng), word::symbol(rng)),
+        match pick(rng, THINGS) {
+            "[animal]" => (word::animal(rng), word::animal(rng)),
+            "[enemy]" => (word::enemy(rng), word::enemy(rng)),
+            "[food]" => (word::food(rng), word::food(rng)),
+            "[profession]" => (word::profession(rng), word::profession(rng)),
+            "[symbol]" => (word::symbol(rng), word::symbol(rng)),
             _ => unreachable!(),
         }
     } else {
@@ -2,6 +2,7 @@ mod blacksmith;
 mod inn;
 
 use super::BuildingType;
+use crate::world::chances::ChanceTable;
 use crate::world::place::{Place, PlaceType};
 use crate::world::Demographics;
 use initiative_macros::WordList;
@@ -64,6 +65,11 @@ pub enum BusinessType {
 }
 
 impl BusinessType {
+    /// Only inns and bars offer a menu.
+    pub const fn has_menu(&self) -> bool {
+        matches!(self, Self::Bar | Self::Inn)
+    }
+
     pub const fn get_emoji(&self) -> Option<&'static str> {
         match self {
             Self::Arena => Some("🏛"),
@@ -75,7 +81,7 @@ impl BusinessType {
             Self::Blacksmith | Self::Weaponsmith => Some("🗡"),
             Self::Brewery => Some("🍻"),
             Self::Casino => Some("🃏"),
-            Self::Club => Some(""),
+            Self::Club => Some("🎶"),
             Self::Distillery => Some("🥃"),
             Self::FightingPit => Some("⚔"),
             Self::FoodCounter => Some("🍲"),
@@ -112,9 +118,22 @@ pub fn generate(place: &mut Place, rng: &mut impl Rng, demographics: &Demographi
     if let Some(PlaceType::Building(BuildingType::Business(subtype))) = place.subtype.value() {
         #[allow(clippy::single_match)]
         match subtype {
+            BusinessType::Bar => place.menu.replace_with(|menu| {
+                let mut menu = menu.unwrap_or_default();
+                menu.regenerate(rng);
+                menu
+            }),
             BusinessType::Inn => inn::generate(place, rng, demographics),
             BusinessType::Blacksmith => blacksmith::generate(place, rng, demographics),
             _ => {}
         }
     }
 }
+
+pub fn chances(subtype: &BusinessType) -> Vec<ChanceTable> {
+    match subtype {
+        BusinessType::Inn => inn::chances(),
+        BusinessType::Blacksmith => blacksmith::chances(),
+        _ => Vec::new(),
+    }
+}
@@ -5,6 +5,7 @@ mod military;
 mod religious;
 mod travel;
 
+use crate::world::chances::ChanceTable;
 use crate::world::place::{Place, PlaceType};
 use crate::world::Demographics;
 use initiative_macros::WordList;
@@ -32,7 +33,7 @@ pub enum BuildingType {
 impl BuildingType {
     pub const fn get_emoji(&self) -> Option<&'static str> {
         match self {
-            Self::Any => None,
+            Self::Any => Some("🏢"),
             Self::Business(subtype) => subtype.get_emoji(),
             Self::Education(subtype) => subtype.get_emoji(),
             Self::Government(subtype) => subtype.get_emoji(),
@@ -54,3 +55,12 @@ pub fn generate(place: &mut Place, rng: &mut impl Rng, demographics: &Demographi
         }
     }
 }
+
+/// The tables behind the generator specific to a kind of building, if it has one.
+pub fn chances(subtype: &BuildingType) -> Vec<ChanceTable> {
+    match subtype {
+        BuildingType::Business(subtype) => business::chances(subtype),
+        BuildingType::Religious(subtype) => religious::chances(subtype),
+        _ => Vec::new(),
+    }
+}
@@ -3,6 +3,7 @@ use initiative_macros::WordList;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 
+use crate::world::chances::ChanceTable;
 use crate::world::{place::PlaceType, Demographics, Place};
 
 use super::BuildingType;
@@ -46,3 +47,10 @@ pub fn generate(place: &mut Place, rng: &mut impl Rng, demographics: &Demographi
         }
     }
 }
+
+pub fn chances(subtype: &ReligiousType) -> Vec<ChanceTable> {
+    match subtype {
+        ReligiousType::Shrine => shrine::chances(),
+        _ => Vec::new(),
+    }
+}
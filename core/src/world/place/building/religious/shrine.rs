@@ -1,23 +1,53 @@
-use crate::utils::pluralize;
+use crate::world::chances::{pick, ChanceTable, WeightTable};
+use crate::world::grammar::plural;
 use crate::world::{word, word::ListGenerator};
 use crate::world::{Demographics, Place};
 use rand::prelude::*;
 
+const NAMES: WeightTable = &[
+    ("The [descriptor] [place]", 4),
+    ("[place] of [deity]", 4),
+    ("Place Where the [animal]s [action]", 1),
+    ("[place] of the [number] [animal]s", 1),
+];
+
+const PLACES: WeightTable = &[("Shrine", 3), ("[building]", 2), ("[feature]", 1)];
+
+const DESCRIPTORS: WeightTable = &[("[adjective]", 2), ("[action]ing", 1)];
+
+const DEITIES: WeightTable = &[
+    ("the [person]", 2),
+    ("the [descriptor] [person]", 1),
+    ("the [animal]", 2),
+    ("the [descriptor] [animal]", 1),
+    ("[concept]", 3),
+    ("[descriptor] [concept]", 1),
+];
+
 pub fn generate(place: &mut Place, rng: &mut impl Rng, _demographics: &Demographics) {
     place.name.replace_with(|_| name(rng));
 }
 
+pub fn chances() -> Vec<ChanceTable> {
+    vec![
+        ChanceTable::new("name", NAMES),
+        ChanceTable::new("[place]", PLACES),
+        ChanceTable::new("[descriptor]", DESCRIPTORS),
+        ChanceTable::new("[deity]", DEITIES),
+    ]
+}
+
 fn name(rng: &mut impl Rng) -> String {
-    match rng.gen_range(0..10) {
-        0..=3 => format!("The {} {}", descriptor(rng), place(rng)),
-        4..=7 => format!("{} of {}", place(rng), deity(rng)),
-        8 => {
-            let (animal, s) = pluralize(word::animal(rng));
-            format!("Place Where the {}{} {}", animal, s, action(rng))
+    match pick(rng, NAMES) {
+        "The [descriptor] [place]" => format!("The {} {}", descriptor(rng), place(rng)),
+        "[place] of [deity]" => format!("{} of {}", place(rng), deity(rng)),
+        "Place Where the [animal]s [action]" => {
+            let animal = plural(word::animal(rng));
+            format!("Place Where the {} {}", animal, action(rng))
         }
-        9 => {
-            let (animal, s) = pluralize(word::animal(rng));
-            format!("{} of the {} {}{}", place(rng), number(rng), animal, s)
+        "[place] of the [number] [animal]s" => {
+            let animal = plural(word::animal(rng));
+            format!("{} of the {} {}", place(rng), number(rng), animal)
         }
         _ => unreachable!(),
     }
@@ -25,10 +55,10 @@ fn name(rng: &mut impl Rng) -> String {
 
 //place of worship can be a building or a natural feature
 fn place(rng: &mut impl Rng) -> &'static str {
-    match rng.gen_range(0..6) {
-        0..=2 => "Shrine",
-        3..=4 => building(rng),
-        5 => feature(rng),
+    match pick(rng, PLACES) {
+        "Shrine" => "Shrine",
+        "[building]" => building(rng),
+        "[feature]" => feature(rng),
         _ => unreachable!(),
     }
 }
@@ -52,9 +82,9 @@ fn feature(rng: &mut impl Rng) -> &'static str {
 
 //DESCRIPTOR can be an ADJECTIVE or an ACTION
 fn descriptor(rng: &mut impl Rng) -> String {
-    match rng.gen_range(0..3) {
-        0..=1 => word::adjective(rng).to_string(),
-        2 => gerund(action(rng)),
+    match pick(rng, DESCRIPTORS) {
+        "[adjective]" => word::adjective(rng).to_string(),
+        "[action]ing" => gerund(action(rng)),
         _ => unreachable!(),
     }
 }
@@ -93,13 +123,13 @@ fn number(rng: &mut impl Rng) -> &'static str {
 
 //DEITY can be PERSON, ANIMAL, or DIVINE CONCEPT
 fn deity(rng: &mut impl Rng) -> String {
-    match rng.gen_range(0..10) {
-        0..=1 => format!("the {}", word::person(rng)),
-        2 => format!("the {} {}", descriptor(rng), word::person(rng)),
-        3..=4 => format!("the {}", word::animal(rng)),
-        5 => format!("the {} {}", descriptor(rng), word::animal(rng)),
-        6..=8 => concept(rng).to_string(),
-        9 => format!("{} {}", descriptor(rng), concept(rng)),
+    match pick(rng, DEITIES) {
+        "the [person]" => format!("the {}", word::person(rng)),
+        "the [descriptor] [person]" => format!("the {} {}", descriptor(rng), word::person(rng)),
+        "the [animal]" => format!("the {}", word::animal(rng)),
+        "the [descriptor] [animal]" => format!("the {} {}", descriptor(rng), word::animal(rng)),
+        "[concept]" => concept(rng).to_string(),
+        "[descriptor] [concept]" => format!("{} {}", descriptor(rng), concept(rng)),
         _ => unreachable!(),
     }
 }
@@ -23,7 +23,7 @@ impl TravelType {
             Self::Ferry => Some("⛴"),
             Self::Gate => Some("🚪"),
             Self::Lighthouse | Self::Pier | Self::Shipyard => Some("⛵"),
-            Self::Portal => None,
+            Self::Portal => Some("🌀"),
         }
     }
 }
@@ -0,0 +1,566 @@
+//! Names and one-line descriptions for every kind of place, drawn from pools shared by similar
+//! kinds of place. Kinds of place with a generator of their own (eg. inns and shrines) replace
+//! the pooled name with something more specific.
+
+use super::{Place, PlaceType};
+use crate::utils::capitalize;
+use crate::world::chances::ChanceTable;
+use crate::world::grammar::with_article;
+use crate::world::word;
+use rand::prelude::*;
+
+/// Names and descriptions shared by a family of places. In the templates, `{kind}` is the kind of
+/// place (eg. "fighting pit"), and the other placeholders are random words: `{adjective}`,
+/// `{animal}`, `{direction}`, `{enemy}`, `{food}`, `{gem}`, `{person}`, `{profession}`, and
+/// `{symbol}`. A capitalized placeholder is rendered in title case, otherwise in lower case, and
+/// one preceded by "a" gets the appropriate article, eg. `{A kind}` for "An inn".
+struct Pool {
+    /// The kinds of place drawing from the pool, by their canonical term.
+    terms: &'static [&'static str],
+    names: &'static [&'static str],
+    descriptions: &'static [&'static str],
+}
+
+const POOLS: &[Pool] = &[
+    Pool {
+        terms: &["place", "building", "location", "region", "residence"],
+        names: &[
+            "The {Adjective} {Symbol}",
+            "{Direction} House",
+            "The {Person}'s Rest",
+            "{Animal} Hall",
+        ],
+        descriptions: &[
+            "An unremarkable {kind}, save for the {animal} carved above the door.",
+            "A weathered {kind} that the locals claim once belonged to {a profession}.",
+            "A quiet {kind} that nobody seems to remember being built.",
+        ],
+    },
+    Pool {
+        terms: &[
+            "bakery",
+            "bar",
+            "brewery",
+            "club",
+            "distillery",
+            "food-counter",
+            "inn",
+            "restaurant",
+            "spirits-shop",
+        ],
+        names: &[
+            "The {Adjective} {Food}",
+            "The {Animal} and {Food}",
+            "The {Adjective} {Animal}",
+            "{Person}'s {Kind}",
+        ],
+        descriptions: &[
+            "The smell of {food} hangs over this {kind}, and the regulars like it that way.",
+            "A cramped {kind} where {a profession} holds court most evenings.",
+            "A busy {kind} with a painted {animal} swinging over the door.",
+            "{A kind} famous for {a food} recipe that the owner swears is older than the town.",
+        ],
+    },
+    Pool {
+        terms: &[
+            "armorer",
+            "blacksmith",
+            "forge",
+            "furniture-shop",
+            "furrier",
+            "jeweller",
+            "lumberyard",
+            "mill",
+            "textiles-shop",
+            "wainwright",
+            "weaponsmith",
+            "woodshop",
+        ],
+        names: &[
+            "The {Adjective} {Symbol}",
+            "{Person}'s {Kind}",
+            "{Symbol} and {Symbol}",
+            "The {Gem} {Kind}",
+        ],
+        descriptions: &[
+            "The work in this {kind} is slow, careful, and priced accordingly.",
+            "A noisy {kind} whose apprentices seem to come and go with the seasons.",
+            "{A kind} with {a symbol} hung over the door as a sign of its craft.",
+        ],
+    },
+    Pool {
+        terms: &[
+            "business",
+            "general-store",
+            "guild-hall",
+            "imports-shop",
+            "magic-shop",
+            "pet-store",
+            "specialty-shop",
+            "stable",
+            "trading-post",
+            "warehouse",
+        ],
+        names: &[
+            "The {Adjective} {Symbol}",
+            "{Person}'s {Kind}",
+            "The {Gem} {Kind}",
+            "{Direction} {Kind}",
+        ],
+        descriptions: &[
+            "A cluttered {kind} where everything has a price and most prices are negotiable.",
+            "{A kind} whose owner keeps a ledger of every customer and every debt.",
+            "A well-kept {kind} with a painted {symbol} on its sign.",
+        ],
+    },
+    Pool {
+        terms: &["bank", "casino", "vault"],
+        names: &[
+            "The {Gem} {Kind}",
+            "The {Adjective} Coin",
+            "House of the {Symbol}",
+        ],
+        descriptions: &[
+            "{A kind} of heavy doors, quiet clerks, and watchful guards.",
+            "Fortunes are made and lost in this {kind}, mostly lost.",
+            "{A kind} rumoured to be in debt to something older than its owners.",
+        ],
+    },
+    Pool {
+        terms: &["arena", "bathhouse", "fighting-pit", "theater"],
+        names: &[
+            "The {Adjective} {Kind}",
+            "The {Animal}'s {Kind}",
+            "The {Gem} {Kind}",
+        ],
+        descriptions: &[
+            "A rowdy {kind} where the crowd is at least half the show.",
+            "A faded {kind} that has seen better days, and better audiences.",
+            "{A kind} patronized by nobles and gossips alike.",
+        ],
+    },
+    Pool {
+        terms: &["academy", "college", "library", "school", "university"],
+        names: &[
+            "The {Symbol} {Kind}",
+            "{Kind} of the {Adjective} {Symbol}",
+            "The {Person}'s {Kind}",
+        ],
+        descriptions: &[
+            "{A kind} of dusty halls and strict teachers.",
+            "{A kind} whose oldest texts are kept under lock and key.",
+            "{A kind} where students of every station learn side by side.",
+        ],
+    },
+    Pool {
+        terms: &["court", "embassy", "guardhouse", "palace", "prison"],
+        names: &[
+            "The {Adjective} {Kind}",
+            "The {Person}'s {Kind}",
+            "{Direction} {Kind}",
+        ],
+        descriptions: &[
+            "A stern {kind} flying the banners of those in power.",
+            "{A kind} where every corridor seems to hide a clerk, a guard, or a spy.",
+            "An imposing {kind} built to remind visitors who is in charge.",
+        ],
+    },
+    Pool {
+        terms: &[
+            "barracks",
+            "base",
+            "castle",
+            "citadel",
+            "fort",
+            "fortress",
+            "keep",
+            "stronghold",
+            "tower",
+        ],
+        names: &[
+            "{Direction} {Kind}",
+            "The {Adjective} {Kind}",
+            "{Kind} of the {Animal}",
+            "{Gem} {Kind}",
+        ],
+        descriptions: &[
+            "A grim {kind} whose walls bear the scars of old sieges.",
+            "A well-garrisoned {kind} watching over the roads.",
+            "A crumbling {kind} held by far fewer soldiers than it was built for.",
+        ],
+    },
+    Pool {
+        terms: &["abbey", "monastery", "shrine", "temple"],
+        names: &[
+            "{Kind} of the {Person}",
+            "The {Adjective} {Kind}",
+            "{Kind} of the {Animal}",
+        ],
+        descriptions: &[
+            "A peaceful {kind} where incense smoke drifts through the rafters.",
+            "{A kind} whose faithful leave offerings of {food} at the altar.",
+            "An austere {kind} tended by a handful of devoted followers.",
+        ],
+    },
+    Pool {
+        terms: &["cemetery", "crypt", "mausoleum", "tomb"],
+        names: &[
+            "The {Adjective} {Kind}",
+            "{Kind} of the {Person}",
+            "{Direction} {Kind}",
+        ],
+        descriptions: &[
+            "A silent {kind} where the grass grows long between the stones.",
+            "{A kind} said to be haunted by the ghost of {a profession}.",
+            "An old {kind} carved with the image of {a symbol}.",
+        ],
+    },
+    Pool {
+        terms: &[
+            "bridge",
+            "duty-house",
+            "ferry",
+            "gate",
+            "lighthouse",
+            "market",
+            "pier",
+            "portal",
+            "shipyard",
+        ],
+        names: &[
+            "{Direction} {Kind}",
+            "The {Adjective} {Kind}",
+            "The {Animal} {Kind}",
+            "{Person}'s {Kind}",
+        ],
+        descriptions: &[
+            "A busy {kind} where travellers trade news along with their coin.",
+            "{A kind} watched over by a bored-looking {profession}.",
+            "An old {kind} that has seen more travellers than anyone can count.",
+        ],
+    },
+    Pool {
+        terms: &[
+            "beach",
+            "canyon",
+            "cave",
+            "chasm",
+            "glacier",
+            "grove",
+            "hill",
+            "island",
+            "monolith",
+            "oasis",
+            "pass",
+            "peninsula",
+            "ridge",
+            "rift",
+            "river",
+            "tree",
+            "valley",
+        ],
+        names: &[
+            "{Adjective} {Kind}",
+            "The {Animal} {Kind}",
+            "{Direction} {Kind}",
+            "{Kind} of the {Enemy}",
+            "{Gem} {Kind}",
+        ],
+        descriptions: &[
+            "A remote {kind} where the cry of the {animal} is the only sound for miles.",
+            "{A kind} that the locals avoid after dark, muttering about {a enemy}.",
+            "A striking {kind} that travellers use as a landmark.",
+            "A windswept {kind}, beautiful and more than a little dangerous.",
+        ],
+    },
+    Pool {
+        terms: &[
+            "dungeon", "farm", "fountain", "garden", "harbor", "mine", "monument", "ruin",
+            "street", "wall",
+        ],
+        names: &[
+            "The {Adjective} {Kind}",
+            "{Person}'s {Kind}",
+            "{Kind} of the {Animal}",
+            "{Direction} {Kind}",
+        ],
+        descriptions: &[
+            "{A kind} that has stood here for as long as anyone remembers.",
+            "{A kind} that the locals give directions by.",
+            "A neglected {kind} that once knew better days.",
+        ],
+    },
+    Pool {
+        terms: &["camp", "capital", "city", "district", "outpost", "town"],
+        names: &[
+            "{Adjective} Hollow",
+            "{Animal} Crossing",
+            "{Direction} {Animal}",
+            "{Gem} Falls",
+            "{Person}'s Rest",
+        ],
+        descriptions: &[
+            "A bustling {kind} that grew up around the trade in {food}.",
+            "{A kind} whose people are wary of strangers but warm to friends.",
+            "A walled {kind} that has weathered more than one siege.",
+            "A sleepy {kind} where every {profession} knows everyone else's business.",
+        ],
+    },
+    Pool {
+        terms: &[
+            "archipelago",
+            "barrens",
+            "coastline",
+            "continent",
+            "desert",
+            "forest",
+            "jungle",
+            "lake",
+            "marsh",
+            "mesa",
+            "moor",
+            "mountain",
+            "ocean",
+            "plain",
+            "plateau",
+            "reef",
+            "sea",
+            "swamp",
+            "tundra",
+            "wasteland",
+            "world",
+        ],
+        names: &[
+            "The {Adjective} {Kind}",
+            "The {Direction} {Kind}",
+            "{Kind} of the {Enemy}",
+            "The {Gem} {Kind}",
+        ],
+        descriptions: &[
+            "A vast {kind} that few have crossed and fewer have mapped.",
+            "{A kind} where {a enemy} of legend is said to roam unchallenged.",
+            "{A kind} of hidden paths and forgotten places.",
+        ],
+    },
+    Pool {
+        terms: &[
+            "barony",
+            "city-state",
+            "confederation",
+            "country",
+            "county",
+            "domain",
+            "duchy",
+            "empire",
+            "kingdom",
+            "nation",
+            "principality",
+            "province",
+            "realm",
+            "territory",
+        ],
+        names: &[
+            "The {Adjective} {Kind}",
+            "The {Kind} of the {Symbol}",
+            "The {Direction} {Kind}",
+            "The {Kind} of {Gem}",
+        ],
+        descriptions: &[
+            "A proud {kind} whose banner bears {a symbol}.",
+            "{A kind} troubled by feuding nobles and empty coffers.",
+            "A prosperous {kind} that grew rich on {food} and trade.",
+        ],
+    },
+    Pool {
+        terms: &["vehicle", "airship", "caravan", "ship", "wagon"],
+        names: &[
+            "The {Adjective} {Animal}",
+            "The {Gem} {Symbol}",
+            "The {Person}'s {Symbol}",
+        ],
+        descriptions: &[
+            "{A kind} that has seen hard travel and carries the scars to prove it.",
+            "A sturdy {kind} crewed by folk who ask few questions.",
+            "{A kind} painted with {a animal} for luck.",
+        ],
+    },
+];
+
+/// Give the place a name and description from the pool for its kind, leaving any specified by
+/// the user untouched.
+pub fn generate(place: &mut Place, rng: &mut impl Rng) {
+    if let Some((subtype, pool)) = place
+        .subtype
+        .value()
+        .copied()
+        .and_then(|subtype| Some((subtype, pool(&subtype)?)))
+    {
+        let kind = subtype.as_str();
+        place
+            .name
+            .replace_with(|_| fill(pool.names.choose(rng).unwrap(), kind, rng));
+        place
+            .description
+            .replace_with(|_| fill(pool.descriptions.choose(rng).unwrap(), kind, rng));
+    }
+}
+
+/// The names and descriptions that a kind of place draws from, all equally likely.
+pub fn chances(subtype: &PlaceType) -> Vec<ChanceTable> {
+    pool(subtype)
+        .map(|pool| {
+            vec![
+                ChanceTable::uniform("name", pool.names),
+                ChanceTable::uniform("description", pool.descriptions),
+            ]
+        })
+        .unwrap_or_default()
+}
+
+fn pool(subtype: &PlaceType) -> Option<&'static Pool> {
+    let term = subtype.as_str();
+    POOLS.iter().find(|pool| pool.terms.contains(&term))
+}
+
+/// Fill the placeholders in a template. Unrecognized placeholders are left as they are.
+fn fill(template: &str, kind: &str, rng: &mut impl Rng) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some((start, end)) = rest
+        .find('{')
+        .and_then(|start| Some((start, start + rest[start..].find('}')?)))
+    {
+        output.push_str(&rest[..start]);
+
+        let placeholder = &rest[start + 1..end];
+        let (article, key) = match placeholder.split_once(' ') {
+            Some((article @ ("a" | "A"), key)) => (Some(article), key),
+            _ => (None, placeholder),
+        };
+
+        if let Some(value) = placeholder_value(key, kind, rng) {
+            let value = if key.starts_with(char::is_uppercase) {
+                title_case(&value)
+            } else {
+                value.to_lowercase()
+            };
+
+            match article {
+                Some("A") => output.push_str(&capitalize(&with_article(&value))),
+                Some(_) => output.push_str(&with_article(&value)),
+                None => output.push_str(&value),
+            }
+        } else {
+            output.push_str(&rest[start..=end]);
+        }
+
+        rest = &rest[end + 1..];
+    }
+
+    output.push_str(rest);
+    output
+}
+
+fn placeholder_value(key: &str, kind: &str, rng: &mut impl Rng) -> Option<String> {
+    let value = match key.to_lowercase().as_str() {
+        "kind" => return Some(kind.replace('-', " ")),
+        "adjective" => word::adjective(rng),
+        "animal" => word::animal(rng),
+        "direction" => word::cardinal_direction(rng),
+        "enemy" => word::enemy(rng),
+        "food" => word::food(rng),
+        "gem" => word::gem(rng),
+        "person" => word::person(rng),
+        "profession" => word::profession(rng),
+        "symbol" => word::symbol(rng),
+        _ => return None,
+    };
+
+    Some(value.to_string())
+}
+
+fn title_case(input: &str) -> String {
+    input
+        .split(' ')
+        .map(capitalize)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::world::Field;
+
+    #[test]
+    fn fill_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        assert_eq!(
+            "The Fighting Pit, a fighting pit",
+            fill("The {Kind}, a {kind}", "fighting-pit", &mut rng),
+        );
+        assert_eq!(
+            "An inn or an inn",
+            fill("{A kind} or {a kind}", "inn", &mut rng),
+        );
+        assert_eq!("A Fighting Pit", fill("{A Kind}", "fighting-pit", &mut rng),);
+        assert_eq!("{potato} and {", fill("{potato} and {", "inn", &mut rng),);
+
+        let output = fill("The {Adjective} {animal}", "inn", &mut rng);
+        let mut words = output.split(' ').skip(1);
+        assert!(words.next().unwrap().starts_with(char::is_uppercase));
+        assert!(words.next().unwrap().starts_with(char::is_lowercase));
+    }
+
+    #[test]
+    fn pools_test() {
+        for pool in POOLS {
+            assert!(!pool.names.is_empty(), "{:?}", pool.terms);
+            assert!(!pool.descriptions.is_empty(), "{:?}", pool.terms);
+
+            for term in pool.terms {
+                assert_eq!(
+                    Some(*term),
+                    term.parse::<PlaceType>().ok().map(|t| t.as_str()),
+                    "{}",
+                    term,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn generate_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        for term in PlaceType::get_words() {
+            let mut place = Place {
+                subtype: Field::Locked(term.parse().ok()),
+                ..Default::default()
+            };
+            generate(&mut place, &mut rng);
+
+            let name = place.name.value().expect(term);
+            let description = place.description.value().expect(term);
+            assert!(!name.contains('{'), "{}: {}", term, name);
+            assert!(!description.contains('{'), "{}: {}", term, description);
+        }
+    }
+
+    #[test]
+    fn generate_locked_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut place = Place {
+            subtype: Field::Locked("tomb".parse().ok()),
+            name: Field::Locked(Some("Ozymandias".to_string())),
+            ..Default::default()
+        };
+
+        generate(&mut place, &mut rng);
+
+        assert_eq!(Some(&"Ozymandias".to_string()), place.name.value());
+        assert!(place.description.is_some());
+    }
+}
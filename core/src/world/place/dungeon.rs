@@ -0,0 +1,904 @@
+use super::lair::Lair;
+use super::location::{LandmarkType, LocationType};
+use super::{Place, PlaceType};
+use crate::world::grammar::{count, with_article};
+use crate::world::word::ListGenerator;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A multi-room dungeon. The rooms are stored with the dungeon itself, so saving, editing, or
+/// undoing the dungeon always applies to every room at once.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Dungeon {
+    pub theme: Theme,
+    pub rooms: Vec<Room>,
+
+    /// Set if the dungeon was generated as the lair of a particular monster.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lair: Option<Lair>,
+}
+
+/// A single room of a dungeon. Rooms are numbered from 1 in the order they are stored, so that
+/// they can be shown with `room [number] of [dungeon]`.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct Room {
+    pub name: String,
+    pub feature: String,
+    pub inhabitants: Option<String>,
+    pub trap: Option<String>,
+    pub treasure: Option<String>,
+    pub boss: bool,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    Cavern,
+    Crypt,
+    Keep,
+    Mine,
+    Sewer,
+    Temple,
+
+    // The remaining themes are only used for the lairs of monsters, so that a black dragon lives in
+    // a swamp rather than a sewer.
+    Swamp,
+    Desert,
+    Forest,
+    Volcano,
+    Glacier,
+    Coast,
+    Peak,
+}
+
+/// The theme and an index of the rooms, eg. "~room 1~ Entrance".
+pub struct DungeonView<'a>(&'a Dungeon);
+
+/// A single room as shown by the `room` command.
+pub struct RoomView<'a> {
+    room: &'a Room,
+    number: usize,
+    dungeon_name: &'a str,
+}
+
+/// A monster that can lead a dungeon, with its challenge rating.
+type Boss = (&'static str, u8);
+
+pub fn generate(place: &mut Place, rng: &mut impl Rng) {
+    place.dungeon.replace_with(|_| Dungeon::generate(rng));
+
+    if let Some(theme) = place.dungeon.value().map(|dungeon| dungeon.theme) {
+        place.name.replace_with(|_| theme.name(rng));
+    }
+}
+
+impl Dungeon {
+    pub fn generate(rng: &mut impl Rng) -> Self {
+        let theme = Theme::generate(rng);
+        let (boss, boss_cr) = theme.boss(rng);
+        Self::generate_with_boss(rng, theme, boss, boss_cr)
+    }
+
+    /// Generate a dungeon of the given theme around a particular boss, as for a monster's lair.
+    pub fn generate_with_boss(rng: &mut impl Rng, theme: Theme, boss: &str, boss_cr: u8) -> Self {
+        let minion_cr = boss_cr / 2;
+
+        let mut areas: Vec<(&str, &str)> = theme.areas().to_vec();
+        areas.shuffle(rng);
+        areas.truncate(rng.gen_range(3..=6));
+
+        let (entrance_name, entrance_feature) = theme.entrance();
+        let mut rooms = vec![Room {
+            name: entrance_name.to_string(),
+            feature: entrance_feature.to_string(),
+            ..Default::default()
+        }];
+
+        rooms.extend(areas.into_iter().map(|(name, feature)| Room {
+            name: name.to_string(),
+            feature: feature.to_string(),
+            inhabitants: if rng.gen_bool(0.6) {
+                Some(count(rng.gen_range(1..=6), theme.minion(rng)))
+            } else {
+                None
+            },
+            trap: if rng.gen_bool(0.3) {
+                Some(trap(rng).to_string())
+            } else {
+                None
+            },
+            treasure: if rng.gen_bool(0.3) {
+                Some(format!(
+                    "{} (roll `loot cr {}`)",
+                    treasure_hook(rng),
+                    minion_cr,
+                ))
+            } else {
+                None
+            },
+            boss: false,
+        }));
+
+        let (lair_name, lair_feature) = theme.lair();
+        rooms.push(Room {
+            name: lair_name.to_string(),
+            feature: lair_feature.to_string(),
+            inhabitants: Some(with_article(boss)),
+            trap: None,
+            treasure: Some(format!(
+                "{} (roll `hoard cr {}`)",
+                treasure_hook(rng),
+                boss_cr,
+            )),
+            boss: true,
+        });
+
+        Self {
+            theme,
+            rooms,
+            lair: None,
+        }
+    }
+
+    pub fn display(&self) -> DungeonView {
+        DungeonView(self)
+    }
+}
+
+impl Room {
+    pub fn display<'a>(&'a self, number: usize, dungeon_name: &'a str) -> RoomView<'a> {
+        RoomView {
+            room: self,
+            number,
+            dungeon_name,
+        }
+    }
+}
+
+impl Place {
+    pub fn is_dungeon(&self) -> bool {
+        matches!(
+            self.subtype.value(),
+            Some(PlaceType::Location(LocationType::Landmark(
+                LandmarkType::Dungeon
+            ))),
+        )
+    }
+}
+
+impl Theme {
+    fn generate(rng: &mut impl Rng) -> Self {
+        match rng.gen_range(0..6) {
+            0 => Self::Cavern,
+            1 => Self::Crypt,
+            2 => Self::Keep,
+            3 => Self::Mine,
+            4 => Self::Sewer,
+            5 => Self::Temple,
+            _ => unreachable!(),
+        }
+    }
+
+    pub(super) fn name(&self, rng: &mut impl Rng) -> String {
+        let noun = ListGenerator(match self {
+            Self::Cavern => &["Caverns", "Deeps", "Grotto", "Warrens"],
+            Self::Crypt => &["Barrow", "Catacombs", "Crypt", "Tomb"],
+            Self::Keep => &["Fortress", "Hold", "Keep", "Tower"],
+            Self::Mine => &["Delvings", "Diggings", "Mine", "Shafts"],
+            Self::Sewer => &["Cisterns", "Sewers", "Undercity"],
+            Self::Temple => &["Sanctum", "Shrine", "Temple"],
+            Self::Swamp => &["Bog", "Fen", "Marsh", "Mire"],
+            Self::Desert => &["Dunes", "Ruins", "Sands", "Wastes"],
+            Self::Forest => &["Grove", "Tangle", "Thicket", "Wildwood"],
+            Self::Volcano => &["Caldera", "Forge", "Furnace", "Mountain"],
+            Self::Glacier => &["Glacier", "Icefall", "Rime", "Wastes"],
+            Self::Coast => &["Reef", "Sea Caves", "Shoals", "Tidecaves"],
+            Self::Peak => &["Aerie", "Crags", "Heights", "Spire"],
+        })
+        .gen(rng);
+
+        format!(
+            "The {} {}",
+            ListGenerator(&[
+                "Black",
+                "Crimson",
+                "Drowned",
+                "Forgotten",
+                "Forsaken",
+                "Hollow",
+                "Howling",
+                "Shattered",
+                "Silent",
+                "Sunken",
+                "Whispering",
+            ])
+            .gen(rng),
+            noun,
+        )
+    }
+
+    fn entrance(&self) -> (&'static str, &'static str) {
+        match self {
+            Self::Cavern => (
+                "Cave Mouth",
+                "A narrow opening in the hillside, half hidden by hanging roots.",
+            ),
+            Self::Crypt => (
+                "Sealed Door",
+                "A stone door carved with a warning, its seal long since broken.",
+            ),
+            Self::Keep => (
+                "Gatehouse",
+                "A crumbling gatehouse with a rusted portcullis stuck halfway open.",
+            ),
+            Self::Mine => (
+                "Adit",
+                "A timber-framed tunnel leading into the rock, rails running into the dark.",
+            ),
+            Self::Sewer => (
+                "Outflow Grate",
+                "A rusted grate over a culvert, wide enough to squeeze through.",
+            ),
+            Self::Temple => (
+                "Narthex",
+                "A pillared porch where defaced statues flank a pair of bronze doors.",
+            ),
+            Self::Swamp => (
+                "Drowned Causeway",
+                "A rotting causeway of logs, half sunk in brown water.",
+            ),
+            Self::Desert => (
+                "Buried Gate",
+                "The top of a stone archway, the rest of it swallowed by sand.",
+            ),
+            Self::Forest => (
+                "Thorn Wall",
+                "A hedge of thorns grown so thick that the only way through is a single tunnel.",
+            ),
+            Self::Volcano => (
+                "Smoking Vent",
+                "A fissure in the mountainside that breathes hot, sulphurous air.",
+            ),
+            Self::Glacier => (
+                "Ice Tunnel",
+                "A blue tunnel bored into the face of the glacier.",
+            ),
+            Self::Coast => (
+                "Tidal Cave",
+                "A cave mouth in the cliffs, reachable on foot only at low tide.",
+            ),
+            Self::Peak => (
+                "Windswept Ledge",
+                "A narrow ledge high on the cliff face, scattered with old bones.",
+            ),
+        }
+    }
+
+    fn lair(&self) -> (&'static str, &'static str) {
+        match self {
+            Self::Cavern => (
+                "Deep Cavern",
+                "A vast cavern around a black underground lake.",
+            ),
+            Self::Crypt => (
+                "Sepulchre",
+                "A vaulted chamber dominated by an ornate sarcophagus.",
+            ),
+            Self::Keep => (
+                "Great Hall",
+                "A long hall with a broken throne at the far end.",
+            ),
+            Self::Mine => (
+                "Lowest Gallery",
+                "A gallery where the miners broke into something older than the mine.",
+            ),
+            Self::Sewer => (
+                "Confluence",
+                "A domed chamber where several tunnels empty into a churning pool.",
+            ),
+            Self::Temple => (
+                "Inner Sanctum",
+                "A candlelit sanctum around an altar stained with old blood.",
+            ),
+            Self::Swamp => (
+                "Sunken Temple",
+                "The flooded ruin of a temple, its roof long since collapsed into the bog.",
+            ),
+            Self::Desert => (
+                "Sand-Filled Hall",
+                "A vast pillared hall, its floor a sea of drifting sand.",
+            ),
+            Self::Forest => (
+                "Heart of the Wood",
+                "A clearing beneath a colossal, ancient tree, poisonous flowers carpeting the ground.",
+            ),
+            Self::Volcano => (
+                "Magma Chamber",
+                "A cavern around a lake of glowing magma, the air shimmering with heat.",
+            ),
+            Self::Glacier => (
+                "Frozen Vault",
+                "A cavern of ice, with the shapes of frozen adventurers visible in the walls.",
+            ),
+            Self::Coast => (
+                "Drowned Grotto",
+                "A grotto flooded at high tide, its walls encrusted with shells and coral.",
+            ),
+            Self::Peak => (
+                "Summit Cave",
+                "A cave at the top of the mountain, open to the sky at one end.",
+            ),
+        }
+    }
+
+    fn areas(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::Cavern => &[
+                ("Bat Roost", "The ceiling is alive with rustling wings."),
+                ("Crystal Grotto", "Glittering crystals catch any light."),
+                ("Fungus Garden", "Pale mushrooms grow taller than a person."),
+                ("Narrow Squeeze", "The walls close in until you must crawl."),
+                ("Underground Stream", "Icy water rushes through a channel."),
+                (
+                    "Dripping Gallery",
+                    "Stalactites drip steadily onto the floor.",
+                ),
+                ("Bone Midden", "Gnawed bones are heaped against one wall."),
+            ],
+            Self::Crypt => &[
+                (
+                    "Ossuary",
+                    "Skulls and bones are stacked neatly along every wall.",
+                ),
+                (
+                    "Embalming Chamber",
+                    "Stone tables and dusty jars line the room.",
+                ),
+                ("Hall of Effigies", "Stone effigies of the dead stare down."),
+                (
+                    "Burial Niches",
+                    "Shrouded bodies rest in niches in the walls.",
+                ),
+                ("Mourners' Chapel", "Rotted pews face a cracked altar."),
+                ("Collapsed Gallery", "Half of the ceiling has fallen in."),
+                ("Flooded Vault", "Knee-deep black water covers the floor."),
+            ],
+            Self::Keep => &[
+                ("Barracks", "Rows of rotting bunks and an overturned table."),
+                ("Armory", "Empty weapon racks and a broken grindstone."),
+                ("Kitchen", "A cold hearth big enough to roast an ox."),
+                ("Dungeon Cells", "Rusted cells, one still locked."),
+                ("Chapel", "Faded banners hang above a simple altar."),
+                (
+                    "Collapsed Tower",
+                    "Rubble fills the stairwell to the upper floors.",
+                ),
+                ("Storeroom", "Split barrels and the smell of spoiled grain."),
+            ],
+            Self::Mine => &[
+                ("Cart Junction", "Overturned ore carts block the rails."),
+                ("Flooded Shaft", "A ladder descends into standing water."),
+                ("Foreman's Office", "A desk covered in ledgers and maps."),
+                (
+                    "Collapsed Tunnel",
+                    "Timbers have given way under tons of rock.",
+                ),
+                ("Gem Seam", "A vein of raw gems glints in the wall."),
+                (
+                    "Miners' Camp",
+                    "Bedrolls and cold campfires, abandoned in haste.",
+                ),
+                ("Smelting Chamber", "A cold furnace and a pile of slag."),
+            ],
+            Self::Sewer => &[
+                ("Sluice Gate", "A rusted gate holds back a wall of sewage."),
+                ("Cistern", "A deep pool of still, foul water."),
+                (
+                    "Smugglers' Cache",
+                    "Crates wrapped in oilcloth on a dry ledge.",
+                ),
+                (
+                    "Collapsed Culvert",
+                    "The tunnel is half blocked by fallen brick.",
+                ),
+                (
+                    "Fungus-Choked Tunnel",
+                    "Glowing fungus covers every surface.",
+                ),
+                (
+                    "Old Cellar",
+                    "The sewer has broken into a forgotten cellar.",
+                ),
+                ("Pump Room", "Great bronze pumps, silent and corroded."),
+            ],
+            Self::Temple => &[
+                ("Vestry", "Moth-eaten vestments hang from hooks."),
+                ("Scriptorium", "Desks piled with heretical texts."),
+                ("Hall of Idols", "Idols of a forgotten god line the walls."),
+                (
+                    "Ritual Chamber",
+                    "A summoning circle is chalked on the floor.",
+                ),
+                ("Acolytes' Cells", "Bare cells, each with a straw pallet."),
+                ("Reliquary", "Empty reliquaries, their contents stolen."),
+                ("Baptismal Pool", "A pool of dark water reflects no light."),
+            ],
+            Self::Swamp => &[
+                (
+                    "Sinking Ruin",
+                    "A tilted stone tower, slowly settling into the mud.",
+                ),
+                ("Reed Maze", "Reeds taller than a person hide every path."),
+                (
+                    "Rotting Boathouse",
+                    "Half-sunk boats and coils of rotten rope.",
+                ),
+                ("Stagnant Pool", "The water is black and utterly still."),
+                (
+                    "Bone-Strewn Island",
+                    "A hummock of dry land covered in bones.",
+                ),
+                (
+                    "Hanging Moss",
+                    "Curtains of grey moss hang from dead trees.",
+                ),
+            ],
+            Self::Desert => &[
+                (
+                    "Collapsed Colonnade",
+                    "Toppled pillars half buried in the sand.",
+                ),
+                (
+                    "Sandfall",
+                    "Sand pours steadily through a crack in the ceiling.",
+                ),
+                ("Tomb of Kings", "Painted sarcophagi stand open and empty."),
+                ("Dry Well", "A deep shaft, its bucket still hanging."),
+                ("Glass Field", "Sand fused into glass by lightning."),
+                (
+                    "Caravan Graveyard",
+                    "The bleached remains of camels and wagons.",
+                ),
+            ],
+            Self::Forest => &[
+                ("Strangling Vines", "Vines hang in loops from every branch."),
+                ("Fallen Giant", "A toppled tree as wide as a house."),
+                ("Toadstool Ring", "A perfect ring of red-capped mushrooms."),
+                ("Poisoned Spring", "A spring ringed with dead animals."),
+                (
+                    "Hunters' Snares",
+                    "Old traps hang from the trees, some occupied.",
+                ),
+                (
+                    "Overgrown Shrine",
+                    "A mossy shrine to a forgotten nature god.",
+                ),
+            ],
+            Self::Volcano => &[
+                ("Obsidian Gallery", "Black glass walls reflect a red glow."),
+                ("Lava Tube", "A smooth tunnel, still warm underfoot."),
+                ("Sulphur Pools", "Yellow pools bubble and reek."),
+                ("Ash Field", "Knee-deep ash muffles every footstep."),
+                (
+                    "Collapsed Forge",
+                    "The ruins of a dwarven forge, long abandoned.",
+                ),
+                (
+                    "Steam Vents",
+                    "Jets of scalding steam burst from the floor.",
+                ),
+            ],
+            Self::Glacier => &[
+                (
+                    "Crevasse Bridge",
+                    "A bridge of ice spans a bottomless crevasse.",
+                ),
+                (
+                    "Hall of Icicles",
+                    "Icicles hang like spears from the ceiling.",
+                ),
+                ("Frozen Waterfall", "A waterfall frozen mid-plunge."),
+                ("Snow Drift", "Deep snow hides the shape of the floor."),
+                ("Mammoth Graveyard", "Great tusks protrude from the ice."),
+                (
+                    "Meltwater Pool",
+                    "A pool of freezing water beneath a thin crust.",
+                ),
+            ],
+            Self::Coast => &[
+                (
+                    "Shipwreck",
+                    "The hull of a wrecked ship, wedged into the rocks.",
+                ),
+                ("Tide Pools", "Shallow pools full of scuttling creatures."),
+                (
+                    "Smugglers' Landing",
+                    "A rotten jetty and a stack of empty crates.",
+                ),
+                ("Kelp Forest", "Flooded passages choked with swaying kelp."),
+                (
+                    "Blowhole",
+                    "The sea booms and sprays through a gap in the rock.",
+                ),
+                ("Pearl Beds", "Oyster shells crunch underfoot."),
+            ],
+            Self::Peak => &[
+                ("Rope Bridge", "A fraying bridge over a sheer drop."),
+                ("Nesting Ledge", "Enormous nests of branches and bones."),
+                ("Wind Tunnel", "A howling gale blows through the passage."),
+                (
+                    "Abandoned Monastery",
+                    "Empty cells carved into the mountainside.",
+                ),
+                ("Frozen Stair", "Steps cut into the rock, slick with ice."),
+                ("Lookout", "A ledge with a view for a hundred miles."),
+            ],
+        }
+    }
+
+    pub(super) fn minion(&self, rng: &mut impl Rng) -> &'static str {
+        ListGenerator(match self {
+            Self::Cavern => &[
+                "giant bat",
+                "giant spider",
+                "grick",
+                "kobold",
+                "stirge",
+                "troglodyte",
+            ],
+            Self::Crypt => &["ghoul", "giant rat", "shadow", "skeleton", "zombie"],
+            Self::Keep => &["bandit", "goblin", "hobgoblin", "mastiff", "orc"],
+            Self::Mine => &[
+                "giant centipede",
+                "giant fire beetle",
+                "kobold",
+                "rust monster",
+                "zombie",
+            ],
+            Self::Sewer => &[
+                "crocodile",
+                "giant centipede",
+                "giant rat",
+                "gray ooze",
+                "thug",
+            ],
+            Self::Temple => &["acolyte", "cult fanatic", "cultist", "gargoyle", "guard"],
+            Self::Swamp => &[
+                "bullywug",
+                "crocodile",
+                "giant frog",
+                "kobold",
+                "lizardfolk",
+            ],
+            Self::Desert => &["giant scorpion", "gnoll", "jackal", "kobold", "dust mephit"],
+            Self::Forest => &[
+                "giant wasp",
+                "kobold",
+                "needle blight",
+                "twig blight",
+                "wolf",
+            ],
+            Self::Volcano => &["azer", "hell hound", "kobold", "magma mephit"],
+            Self::Glacier => &["ice mephit", "kobold", "polar bear", "winter wolf", "yeti"],
+            Self::Coast => &["giant crab", "merrow", "reef shark", "sahuagin"],
+            Self::Peak => &[
+                "aarakocra",
+                "giant eagle",
+                "griffon",
+                "hippogriff",
+                "kobold",
+            ],
+        })
+        .gen(rng)
+    }
+
+    fn boss(&self, rng: &mut impl Rng) -> Boss {
+        let bosses: &[Boss] = match self {
+            Self::Cavern => &[("basilisk", 3), ("ettin", 4), ("owlbear", 3), ("troll", 5)],
+            Self::Crypt => &[
+                ("mummy", 3),
+                ("vampire spawn", 5),
+                ("wight", 3),
+                ("wraith", 5),
+            ],
+            Self::Keep => &[
+                ("bandit captain", 2),
+                ("knight", 3),
+                ("ogre", 2),
+                ("veteran", 3),
+            ],
+            Self::Mine => &[
+                ("earth elemental", 5),
+                ("gelatinous cube", 2),
+                ("umber hulk", 5),
+                ("xorn", 5),
+            ],
+            Self::Sewer => &[("black pudding", 4), ("otyugh", 5), ("wererat", 2)],
+            Self::Temple => &[("mage", 6), ("medusa", 6), ("priest", 2)],
+            Self::Swamp => &[("green hag", 3), ("shambling mound", 5)],
+            Self::Desert => &[("lamia", 4), ("mummy", 3)],
+            Self::Forest => &[("green hag", 3), ("werewolf", 3)],
+            Self::Volcano => &[("fire giant", 9), ("salamander", 5)],
+            Self::Glacier => &[("frost giant", 8), ("young remorhaz", 5)],
+            Self::Coast => &[("sea hag", 2), ("water elemental", 5)],
+            Self::Peak => &[("chimera", 6), ("manticore", 3)],
+        };
+
+        bosses[rng.gen_range(0..bosses.len())]
+    }
+}
+
+fn trap(rng: &mut impl Rng) -> &'static str {
+    ListGenerator(&[
+        "A collapsing ceiling, triggered by a tripwire",
+        "A concealed pit lined with rusty spikes",
+        "A glyph of warding carved into the far door",
+        "A poison needle hidden in a lock",
+        "A pressure plate that looses poisoned darts",
+        "A scything blade across the doorway",
+        "A sliding wall that seals the room and floods it",
+    ])
+    .gen(rng)
+}
+
+fn treasure_hook(rng: &mut impl Rng) -> &'static str {
+    ListGenerator(&[
+        "A dead adventurer's pack",
+        "A locked, iron-banded chest",
+        "A loose flagstone hiding a pouch",
+        "A concealed niche in the wall",
+        "Offerings heaped before a shrine",
+        "A rotted strongbox under the rubble",
+    ])
+    .gen(rng)
+}
+
+impl fmt::Display for Theme {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Cavern => write!(f, "cavern"),
+            Self::Crypt => write!(f, "crypt"),
+            Self::Keep => write!(f, "ruined keep"),
+            Self::Mine => write!(f, "abandoned mine"),
+            Self::Sewer => write!(f, "sewer"),
+            Self::Temple => write!(f, "cult temple"),
+            Self::Swamp => write!(f, "swamp"),
+            Self::Desert => write!(f, "desert ruin"),
+            Self::Forest => write!(f, "forest"),
+            Self::Volcano => write!(f, "volcano"),
+            Self::Glacier => write!(f, "glacier"),
+            Self::Coast => write!(f, "sea cave"),
+            Self::Peak => write!(f, "mountain peak"),
+        }
+    }
+}
+
+impl<'a> fmt::Display for DungeonView<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let dungeon = self.0;
+
+        write!(f, "**Theme:** {}", dungeon.theme)?;
+
+        if let Some(lair) = &dungeon.lair {
+            write!(f, "\n\n{}", lair.display())?;
+        }
+
+        write!(f, "\n\n## Rooms\n")?;
+
+        for (i, room) in dungeon.rooms.iter().enumerate() {
+            write!(
+                f,
+                "{}~room {}~ {}",
+                if i == 0 { "\n" } else { "\\\n" },
+                i + 1,
+                room.name,
+            )?;
+
+            if room.boss {
+                write!(f, " (boss)")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> fmt::Display for RoomView<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Self {
+            room,
+            number,
+            dungeon_name,
+        } = self;
+
+        write!(
+            f,
+            "# Room {}: {}\n*{}*\n\n{}",
+            number, room.name, dungeon_name, room.feature,
+        )?;
+
+        let mut lines = Vec::new();
+
+        if let Some(inhabitants) = &room.inhabitants {
+            lines.push(format!(
+                "**{}:** {}",
+                if room.boss { "Boss" } else { "Inhabitants" },
+                inhabitants,
+            ));
+        }
+
+        if let Some(trap) = &room.trap {
+            lines.push(format!("**Trap:** {}", trap));
+        }
+
+        if let Some(treasure) = &room.treasure {
+            lines.push(format!("**Treasure:** {}", treasure));
+        }
+
+        if !lines.is_empty() {
+            write!(f, "\n\n{}", lines.join("\\\n"))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn generate_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        for _ in 0..20 {
+            let dungeon = Dungeon::generate(&mut rng);
+
+            assert!((5..=8).contains(&dungeon.rooms.len()), "{:?}", dungeon);
+            assert_eq!(1, dungeon.rooms.iter().filter(|room| room.boss).count());
+
+            let entrance = dungeon.rooms.first().unwrap();
+            assert!(entrance.inhabitants.is_none());
+            assert!(entrance.trap.is_none());
+            assert!(entrance.treasure.is_none());
+
+            let lair = dungeon.rooms.last().unwrap();
+            assert!(lair.boss);
+            assert!(lair.inhabitants.is_some());
+            assert!(lair.treasure.as_ref().unwrap().contains("`hoard cr "));
+        }
+    }
+
+    #[test]
+    fn generate_test_place() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut place = Place {
+            subtype: "dungeon".parse::<PlaceType>().ok().into(),
+            ..Default::default()
+        };
+
+        generate(&mut place, &mut rng);
+
+        assert!(place.is_dungeon());
+        assert!(place.dungeon.is_some());
+        assert!(place.name.value().unwrap().starts_with("The "));
+    }
+
+    #[test]
+    fn is_dungeon_test() {
+        [("dungeon", true), ("ruin", false), ("inn", false)]
+            .into_iter()
+            .for_each(|(subtype, is_dungeon)| {
+                let place = Place {
+                    subtype: subtype.parse::<PlaceType>().ok().into(),
+                    ..Default::default()
+                };
+                assert_eq!(is_dungeon, place.is_dungeon(), "{}", subtype);
+            });
+
+        assert!(!Place::default().is_dungeon());
+    }
+
+    #[test]
+    fn display_test() {
+        let dungeon = dungeon();
+
+        assert_eq!(
+            "**Theme:** crypt
+
+## Rooms
+
+~room 1~ Sealed Door\\
+~room 2~ Ossuary\\
+~room 3~ Sepulchre (boss)",
+            dungeon.display().to_string(),
+        );
+
+        assert_eq!(
+            "# Room 2: Ossuary
+*The Sunken Crypt*
+
+Skulls and bones are stacked neatly along every wall.
+
+**Inhabitants:** 3 skeletons\\
+**Trap:** A concealed pit lined with rusty spikes",
+            dungeon.rooms[1].display(2, "The Sunken Crypt").to_string(),
+        );
+
+        assert_eq!(
+            "# Room 3: Sepulchre
+*The Sunken Crypt*
+
+A vaulted chamber dominated by an ornate sarcophagus.
+
+**Boss:** a wight\\
+**Treasure:** A locked, iron-banded chest (roll `hoard cr 3`)",
+            dungeon.rooms[2].display(3, "The Sunken Crypt").to_string(),
+        );
+
+        assert_eq!(
+            "# Room 1: Sealed Door
+*The Sunken Crypt*
+
+A stone door carved with a warning, its seal long since broken.",
+            dungeon.rooms[0].display(1, "The Sunken Crypt").to_string(),
+        );
+    }
+
+    #[test]
+    fn serialize_deserialize_test() {
+        let dungeon = Dungeon {
+            theme: Theme::Sewer,
+            rooms: vec![Room {
+                name: "Cistern".to_string(),
+                feature: "A deep pool of still, foul water.".to_string(),
+                inhabitants: Some("2 giant rats".to_string()),
+                ..Default::default()
+            }],
+            lair: None,
+        };
+
+        assert_eq!(
+            r#"{"theme":"sewer","rooms":[{"name":"Cistern","feature":"A deep pool of still, foul water.","inhabitants":"2 giant rats","trap":null,"treasure":null,"boss":false}]}"#,
+            serde_json::to_string(&dungeon).unwrap(),
+        );
+
+        assert_eq!(
+            dungeon,
+            serde_json::from_str(r#"{"theme":"sewer","rooms":[{"name":"Cistern","feature":"A deep pool of still, foul water.","inhabitants":"2 giant rats"}]}"#)
+                .unwrap(),
+        );
+    }
+
+    fn dungeon() -> Dungeon {
+        Dungeon {
+            theme: Theme::Crypt,
+            rooms: vec![
+                Room {
+                    name: "Sealed Door".to_string(),
+                    feature: "A stone door carved with a warning, its seal long since broken."
+                        .to_string(),
+                    ..Default::default()
+                },
+                Room {
+                    name: "Ossuary".to_string(),
+                    feature: "Skulls and bones are stacked neatly along every wall.".to_string(),
+                    inhabitants: Some("3 skeletons".to_string()),
+                    trap: Some("A concealed pit lined with rusty spikes".to_string()),
+                    ..Default::default()
+                },
+                Room {
+                    name: "Sepulchre".to_string(),
+                    feature: "A vaulted chamber dominated by an ornate sarcophagus.".to_string(),
+                    inhabitants: Some("a wight".to_string()),
+                    trap: None,
+                    treasure: Some("A locked, iron-banded chest (roll `hoard cr 3`)".to_string()),
+                    boss: true,
+                },
+            ],
+            lair: None,
+        }
+    }
+}
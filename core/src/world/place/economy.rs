@@ -0,0 +1,298 @@
+//! The economy of a settlement: how wealthy it is, and what's in short supply. Together they raise
+//! or lower the price of SRD equipment bought there, or anywhere within it.
+
+use super::Place;
+use crate::app::AppMeta;
+use crate::reference::Item as Equipment;
+use crate::world::inventory::Coins;
+use crate::world::Thing;
+use initiative_macros::WordList;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// How far up the chain of locations to look for a place with an economy, eg. from a shop to its
+/// district to its city.
+const DEPTH_MAX: usize = 5;
+
+/// The word that clears a place's shortages, eg. `Waterdeep is well-supplied`.
+pub const WELL_SUPPLIED: &str = "well-supplied";
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, WordList)]
+#[serde(into = "&'static str", try_from = "&str")]
+pub enum Wealth {
+    #[alias = "impoverished"]
+    Poor,
+
+    #[alias = "average"]
+    Modest,
+
+    Prosperous,
+
+    #[alias = "rich"]
+    Wealthy,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, WordList)]
+#[serde(into = "&'static str", try_from = "&str")]
+pub enum Scarcity {
+    /// Everything is dearer far from the trade routes.
+    #[alias = "remote"]
+    Frontier,
+
+    SteelShortage,
+    TimberShortage,
+}
+
+/// The SRD equipment made mostly of steel.
+const STEEL_GOODS: &[&str] = &[
+    "battleaxe",
+    "breastplate",
+    "chain-mail",
+    "chain-shirt",
+    "dagger",
+    "flail",
+    "glaive",
+    "greataxe",
+    "greatsword",
+    "half-plate-armor",
+    "halberd",
+    "handaxe",
+    "lance",
+    "light-hammer",
+    "longsword",
+    "mace",
+    "morningstar",
+    "pike",
+    "plate-armor",
+    "rapier",
+    "ring-mail",
+    "scale-mail",
+    "scimitar",
+    "shortsword",
+    "sickle",
+    "splint-armor",
+    "trident",
+    "war-pick",
+    "warhammer",
+];
+
+/// The SRD equipment made mostly of wood.
+const TIMBER_GOODS: &[&str] = &[
+    "arrow",
+    "barrel",
+    "chest",
+    "club",
+    "crossbow-bolt",
+    "crossbow-hand",
+    "crossbow-heavy",
+    "crossbow-light",
+    "greatclub",
+    "javelin",
+    "ladder-10-foot",
+    "longbow",
+    "pole-10-foot",
+    "quarterstaff",
+    "shield",
+    "shortbow",
+    "spear",
+];
+
+impl Wealth {
+    /// The percentage by which prices are raised, or lowered if negative.
+    pub fn modifier(&self) -> i64 {
+        match self {
+            Self::Poor => -10,
+            Self::Modest => 0,
+            Self::Prosperous => 10,
+            Self::Wealthy => 25,
+        }
+    }
+}
+
+impl Scarcity {
+    /// The percentage by which the prices of the affected goods are raised.
+    fn percent(&self) -> i64 {
+        match self {
+            Self::Frontier => 20,
+            Self::SteelShortage | Self::TimberShortage => 50,
+        }
+    }
+
+    pub fn affects(&self, item: &Equipment) -> bool {
+        match self {
+            Self::Frontier => true,
+            Self::SteelShortage => STEEL_GOODS.contains(&item.get_index()),
+            Self::TimberShortage => TIMBER_GOODS.contains(&item.get_index()),
+        }
+    }
+
+    /// The percentage by which the price of `item` is raised.
+    pub fn modifier(&self, item: &Equipment) -> i64 {
+        if self.affects(item) {
+            self.percent()
+        } else {
+            0
+        }
+    }
+
+    /// The goods affected by the scarcity, eg. "steel goods".
+    fn goods(&self) -> &'static str {
+        match self {
+            Self::Frontier => "everything",
+            Self::SteelShortage => "steel goods",
+            Self::TimberShortage => "timber goods",
+        }
+    }
+}
+
+impl Place {
+    /// Does the place have an economy of its own, which affects the prices of things bought there?
+    pub fn has_economy(&self) -> bool {
+        self.wealth.is_some()
+            || self
+                .scarcity
+                .value()
+                .map_or(false, |scarcity| !scarcity.is_empty())
+    }
+
+    /// The percentage by which the price of `item` is raised here, or lowered if negative.
+    pub fn price_modifier(&self, item: &Equipment) -> i64 {
+        let wealth = self.wealth.value().map_or(0, Wealth::modifier);
+        let scarcity: i64 = self
+            .scarcity
+            .value()
+            .iter()
+            .flat_map(|scarcity| scarcity.iter())
+            .map(|scarcity| scarcity.modifier(item))
+            .sum();
+
+        (wealth + scarcity).max(-100)
+    }
+
+    /// The price of `item` here, given its `cost` in the SRD, rounded to the nearest copper piece.
+    pub fn local_price(&self, item: &Equipment, cost: Coins) -> Coins {
+        Coins::from_cp((cost.value_cp() * (100 + self.price_modifier(item)) as u64 + 50) / 100)
+    }
+
+    pub fn display_economy(&self) -> EconomyView {
+        EconomyView(self)
+    }
+}
+
+/// The place whose economy sets the prices at `place`: the place itself, or the nearest place
+/// containing it that has an economy.
+pub async fn economy_at(place: &Place, app_meta: &AppMeta) -> Option<Place> {
+    let mut place = place.clone();
+
+    for _ in 0..DEPTH_MAX {
+        if place.has_economy() {
+            return Some(place);
+        }
+
+        place = match app_meta
+            .repository
+            .get_by_uuid(place.location_uuid.value()?.as_ref())
+            .await
+        {
+            Ok(Thing::Place(parent)) => parent,
+            _ => return None,
+        };
+    }
+
+    None
+}
+
+/// The price modifiers of a place, eg. "+25% (wealthy), +50% on steel goods (steel shortage)".
+pub struct EconomyView<'a>(&'a Place);
+
+impl<'a> fmt::Display for EconomyView<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let place = self.0;
+        let mut modifiers = Vec::new();
+
+        if let Some(wealth) = place.wealth.value() {
+            modifiers.push(format!("{:+}% ({})", wealth.modifier(), wealth));
+        }
+
+        for scarcity in place.scarcity.value().iter().flat_map(|s| s.iter()) {
+            modifiers.push(format!(
+                "{:+}% on {} ({})",
+                scarcity.percent(),
+                scarcity.goods(),
+                scarcity.as_str().replace('-', " "),
+            ));
+        }
+
+        write!(f, "{}", modifiers.join(", "))
+    }
+}
+
+impl fmt::Display for Wealth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl fmt::Display for Scarcity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn price_modifier_test() {
+        let mut place = Place::default();
+        assert!(!place.has_economy());
+        assert_eq!(0, place.price_modifier(&Equipment::Longsword));
+
+        place.wealth = Wealth::Wealthy.into();
+        place.scarcity = vec![Scarcity::Frontier, Scarcity::SteelShortage].into();
+        assert!(place.has_economy());
+        assert_eq!(95, place.price_modifier(&Equipment::Longsword));
+        assert_eq!(45, place.price_modifier(&Equipment::Shield));
+
+        place.wealth = Wealth::Poor.into();
+        place.scarcity = Vec::new().into();
+        assert!(place.has_economy());
+        assert_eq!(-10, place.price_modifier(&Equipment::Shield));
+    }
+
+    #[test]
+    fn local_price_test() {
+        let place = Place {
+            scarcity: vec![Scarcity::Frontier].into(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            Coins::from_cp(1800),
+            place.local_price(&Equipment::Longsword, Coins::from_cp(1500)),
+        );
+        assert_eq!(
+            Coins::from_cp(1),
+            place.local_price(&Equipment::Longsword, Coins::from_cp(1)),
+        );
+    }
+
+    #[test]
+    fn display_test() {
+        let place = Place {
+            wealth: Wealth::Wealthy.into(),
+            scarcity: vec![Scarcity::Frontier, Scarcity::SteelShortage].into(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            "+25% (wealthy), +20% on everything (frontier), +50% on steel goods (steel shortage)",
+            place.display_economy().to_string(),
+        );
+
+        assert_eq!(Ok(Wealth::Wealthy), "rich".parse());
+        assert_eq!(Ok(Scarcity::SteelShortage), "steel-shortage".parse());
+        assert_eq!("timber-shortage", Scarcity::TimberShortage.to_string());
+    }
+}
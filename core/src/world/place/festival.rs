@@ -0,0 +1,393 @@
+use super::location::LocationType;
+use super::region::RegionType;
+use super::{Place, PlaceType};
+use crate::app::AppMeta;
+use crate::config::Config;
+use crate::time::Time;
+use crate::utils::CodeView;
+use crate::world::grammar::plural;
+use crate::world::{word, LinkView, Thing};
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::RangeInclusive;
+
+/// A festival celebrated once a year by a settlement or realm.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Festival {
+    pub name: String,
+
+    /// The day of the year on which the festival falls, counting from 1. If the configured year
+    /// is shorter than this, the date wraps around to the start of the year.
+    pub day: u16,
+
+    pub description: String,
+}
+
+/// A festival in the days ahead, as listed by the `festivals` command.
+pub struct Upcoming {
+    pub festival: Festival,
+    pub place_name: String,
+
+    /// The number of days until the festival, or 0 if it is today.
+    pub days: u16,
+}
+
+pub struct UpcomingView<'a>(&'a Upcoming);
+
+/// The kinds of festival that can be generated, with the days of a 365-day year on which each can
+/// fall.
+const FESTIVALS: &[(&str, &str, RangeInclusive<u16>)] = &[
+    (
+        "Midwinter Feast",
+        "Bonfires burn through the longest night while gifts are exchanged and mulled wine flows.",
+        350..=365,
+    ),
+    (
+        "Feast of First Thaw",
+        "The first running water of the year is blessed, and the winter stores are finished off.",
+        60..=80,
+    ),
+    (
+        "Planting Fair",
+        "Seed is blessed in the square, and farmers come from miles around to trade livestock.",
+        90..=120,
+    ),
+    (
+        "Blossom Festival",
+        "Streets are strewn with petals, and young couples are paired off in a dance.",
+        121..=150,
+    ),
+    (
+        "Midsummer Revels",
+        "A night of music, masks, and mischief under the shortest night of the year.",
+        170..=180,
+    ),
+    (
+        "Summer Games",
+        "Contests of archery, wrestling, and horsemanship draw champions and gamblers alike.",
+        190..=220,
+    ),
+    (
+        "Harvest Festival",
+        "The last sheaf is carried through the streets, followed by a great communal feast.",
+        250..=280,
+    ),
+    (
+        "Lantern Night",
+        "Lanterns are set afloat in memory of the dead, and no one speaks above a whisper.",
+        290..=310,
+    ),
+    (
+        "Founding Day",
+        "Parades and speeches mark the anniversary of the founding, whether or not anyone remembers it rightly.",
+        1..=365,
+    ),
+    (
+        "Market Fair",
+        "Merchants from distant lands fill the streets for a week of trading and haggling.",
+        1..=365,
+    ),
+];
+
+impl Festival {
+    /// Generate a handful of distinct festivals spread across the year.
+    pub fn generate_all(rng: &mut impl Rng) -> Vec<Self> {
+        let count = rng.gen_range(1..=3);
+        let mut festivals: Vec<Self> = FESTIVALS
+            .choose_multiple(rng, count)
+            .map(|(name, description, days)| Self {
+                name: name.to_string(),
+                day: rng.gen_range(days.clone()),
+                description: description.to_string(),
+            })
+            .collect();
+
+        if rng.gen_bool(0.5) {
+            let profession = word::profession(rng);
+
+            festivals.push(Self {
+                name: format!("Feast of the {}", plural(profession)),
+                day: rng.gen_range(1..=365),
+                description: format!(
+                    "The {} guild hosts a feast in honor of its patron, and its members parade in their finest.",
+                    profession.to_lowercase(),
+                ),
+            });
+        }
+
+        festivals.sort_by_key(|festival| festival.day);
+        festivals
+    }
+
+    /// The day of the year on which the festival falls in a year of the given length.
+    pub fn day_of_year(&self, year: u16) -> u16 {
+        (self.day.max(1) - 1) % year.max(1) + 1
+    }
+}
+
+impl Place {
+    /// Settlements and realms celebrate festivals; buildings and wilderness don't.
+    pub fn has_festivals(&self) -> bool {
+        matches!(
+            self.subtype.value(),
+            Some(
+                PlaceType::Location(LocationType::Settlement(_))
+                    | PlaceType::Region(RegionType::Political(_))
+            ),
+        )
+    }
+}
+
+/// The day of the year of a point in time, counting from 1.
+pub fn day_of_year(time: &Time, year: u16) -> u16 {
+    (i64::from(time.days()) - 1).rem_euclid(i64::from(year.max(1))) as u16 + 1
+}
+
+/// Every festival celebrated by places in the journal and recent entries, ordered by how soon it
+/// falls on or after the given time.
+pub async fn upcoming_festivals(now: &Time, app_meta: &AppMeta) -> Vec<Upcoming> {
+    let year = Config::load(app_meta).await.year;
+    let today = day_of_year(now, year);
+
+    let mut things = app_meta.repository.journal().await.unwrap_or_default();
+    things.extend(app_meta.repository.recent().cloned());
+
+    let mut upcoming: Vec<Upcoming> = things
+        .into_iter()
+        .filter(|thing| !thing.is_archived())
+        .filter_map(|thing| match thing {
+            Thing::Place(place) => Some(place),
+            Thing::Npc(_) => None,
+        })
+        .flat_map(|place| {
+            let place_name = place.name.to_string();
+
+            place
+                .festivals
+                .value()
+                .cloned()
+                .unwrap_or_default()
+                .into_iter()
+                .map(move |festival| Upcoming {
+                    days: (festival.day_of_year(year) + year - today) % year,
+                    festival,
+                    place_name: place_name.clone(),
+                })
+        })
+        .collect();
+
+    upcoming.sort_by(|a, b| {
+        a.days
+            .cmp(&b.days)
+            .then_with(|| a.festival.name.cmp(&b.festival.name))
+    });
+
+    upcoming
+}
+
+/// A notice for each festival falling on the day of the given time, eg. "_Today is the Harvest
+/// Festival in `Bree`._"
+pub async fn festival_notices(now: &Time, app_meta: &AppMeta) -> Vec<String> {
+    upcoming_festivals(now, app_meta)
+        .await
+        .into_iter()
+        .filter(|upcoming| upcoming.days == 0)
+        .map(|upcoming| {
+            format!(
+                "_Today is the {} in `{}`._",
+                upcoming.festival.name, upcoming.place_name,
+            )
+        })
+        .collect()
+}
+
+/// A notice for each festival that fell on a day passed over entirely when time moved on from
+/// `before` to `after`, eg. "_The Harvest Festival in `Bree` passed on day 3._" Each festival is
+/// mentioned once, however many years pass.
+pub async fn skipped_festival_notices(
+    before: &Time,
+    after: &Time,
+    app_meta: &AppMeta,
+) -> Vec<String> {
+    let skipped_days = i64::from(after.days()) - i64::from(before.days()) - 1;
+    if skipped_days < 1 {
+        return Vec::new();
+    }
+
+    let year = Config::load(app_meta).await.year;
+
+    upcoming_festivals(before, app_meta)
+        .await
+        .into_iter()
+        .filter_map(|upcoming| {
+            // A festival falling on the first day comes around again a year later.
+            let days = i64::from(if upcoming.days == 0 {
+                year
+            } else {
+                upcoming.days
+            });
+
+            (days <= skipped_days).then(|| {
+                format!(
+                    "_The {} in {} passed on day {}._",
+                    upcoming.festival.name,
+                    CodeView::new(&upcoming.place_name),
+                    i64::from(before.days()) + days,
+                )
+            })
+        })
+        .collect()
+}
+
+impl Upcoming {
+    pub fn display(&self) -> UpcomingView {
+        UpcomingView(self)
+    }
+}
+
+impl fmt::Display for Festival {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} (day {})", self.name, self.day)
+    }
+}
+
+impl<'a> fmt::Display for UpcomingView<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let upcoming = self.0;
+
+        match upcoming.days {
+            0 => write!(f, "Today")?,
+            1 => write!(f, "Tomorrow")?,
+            days => write!(f, "In {} days", days)?,
+        }
+
+        write!(
+            f,
+            ": **{}** in {}. {}",
+            upcoming.festival.name,
+            LinkView::new(&upcoming.place_name),
+            upcoming.festival.description,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::storage::{Change, MemoryDataStore};
+    use crate::world::Field;
+    use crate::Event;
+    use tokio_test::block_on;
+
+    #[test]
+    fn generate_all_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        for _ in 0..20 {
+            let festivals = Festival::generate_all(&mut rng);
+
+            assert!((1..=4).contains(&festivals.len()), "{:?}", festivals);
+            assert!(festivals.windows(2).all(|pair| pair[0].day <= pair[1].day));
+            assert!(festivals
+                .iter()
+                .all(|festival| (1..=365).contains(&festival.day)));
+        }
+    }
+
+    #[test]
+    fn day_of_year_test() {
+        let festival = Festival {
+            name: "Harvest Festival".to_string(),
+            day: 263,
+            description: String::new(),
+        };
+
+        assert_eq!(263, festival.day_of_year(365));
+        assert_eq!(3, festival.day_of_year(260));
+
+        assert_eq!(1, day_of_year(&Time::try_new(1, 0, 0, 0).unwrap(), 365));
+        assert_eq!(365, day_of_year(&Time::try_new(365, 0, 0, 0).unwrap(), 365));
+        assert_eq!(1, day_of_year(&Time::try_new(366, 0, 0, 0).unwrap(), 365));
+        assert_eq!(365, day_of_year(&Time::try_new(0, 0, 0, 0).unwrap(), 365));
+    }
+
+    #[test]
+    fn has_festivals_test() {
+        [
+            ("town", true),
+            ("city", true),
+            ("kingdom", true),
+            ("desert", false),
+            ("inn", false),
+        ]
+        .into_iter()
+        .for_each(|(subtype, has_festivals)| {
+            let place = Place {
+                subtype: subtype.parse::<PlaceType>().ok().into(),
+                ..Default::default()
+            };
+            assert_eq!(has_festivals, place.has_festivals(), "{}", subtype);
+        });
+    }
+
+    #[test]
+    fn upcoming_festivals_test() {
+        let mut app_meta = AppMeta::new(MemoryDataStore::default(), &event_dispatcher);
+
+        block_on(
+            app_meta.repository.modify(Change::Create {
+                thing: Place {
+                    name: "Bree".into(),
+                    festivals: Field::from(vec![
+                        Festival {
+                            name: "Harvest Festival".to_string(),
+                            day: 263,
+                            description: "A feast.".to_string(),
+                        },
+                        Festival {
+                            name: "Midwinter Feast".to_string(),
+                            day: 360,
+                            description: "A bonfire.".to_string(),
+                        },
+                    ]),
+                    ..Default::default()
+                }
+                .into(),
+            }),
+        )
+        .unwrap();
+
+        let upcoming = block_on(upcoming_festivals(
+            &Time::try_new(300, 0, 0, 0).unwrap(),
+            &app_meta,
+        ));
+
+        assert_eq!(
+            vec![
+                "In 60 days: **Midwinter Feast** in {Bree}. A bonfire.",
+                "In 328 days: **Harvest Festival** in {Bree}. A feast.",
+            ],
+            upcoming
+                .iter()
+                .map(|upcoming| upcoming.display().to_string())
+                .collect::<Vec<_>>(),
+        );
+
+        assert_eq!(
+            vec!["_Today is the Harvest Festival in `Bree`._"],
+            block_on(festival_notices(
+                &Time::try_new(263, 12, 0, 0).unwrap(),
+                &app_meta,
+            )),
+        );
+
+        assert!(block_on(festival_notices(
+            &Time::try_new(264, 0, 0, 0).unwrap(),
+            &app_meta
+        ))
+        .is_empty());
+    }
+
+    fn event_dispatcher(_event: Event) {}
+}
@@ -0,0 +1,168 @@
+//! A closing sentence for a place's description that depends on the time of day in the game
+//! world, so that an inn generated at 2am is quiet and shuttered while a market at noon bustles.
+
+use super::Place;
+use crate::time::TimeOfDay;
+use rand::prelude::*;
+
+/// Sentences for a family of places, by time of day. A time of day without any sentences leaves
+/// the description as it is.
+struct Pool {
+    /// The kinds of place drawing from the pool, by their canonical term.
+    terms: &'static [&'static str],
+    night: &'static [&'static str],
+    morning: &'static [&'static str],
+    midday: &'static [&'static str],
+    afternoon: &'static [&'static str],
+    evening: &'static [&'static str],
+}
+
+impl Pool {
+    fn sentences(&self, time_of_day: TimeOfDay) -> &'static [&'static str] {
+        match time_of_day {
+            TimeOfDay::Night => self.night,
+            TimeOfDay::Morning => self.morning,
+            TimeOfDay::Midday => self.midday,
+            TimeOfDay::Afternoon => self.afternoon,
+            TimeOfDay::Evening => self.evening,
+        }
+    }
+}
+
+const POOLS: &[Pool] = &[
+    Pool {
+        terms: &["bar", "brewery", "club", "distillery", "inn", "restaurant"],
+        night: &[
+            "At this hour the shutters are closed and the common room is quiet, save for the odd snore from upstairs.",
+            "The fire has burned down to embers, and only a single lamp still burns in the window.",
+        ],
+        morning: &[
+            "A yawning server is sweeping up the last of the previous night's mess.",
+            "The smell of porridge and fresh bread drifts out to the street.",
+        ],
+        midday: &["A handful of travellers are taking their midday meal at the long tables."],
+        afternoon: &["The place is all but empty, and the staff are taking the chance to rest."],
+        evening: &[
+            "The common room is filling up with the evening crowd, and someone has started on a fiddle.",
+            "Every table is taken, and the noise spills out into the street.",
+        ],
+    },
+    Pool {
+        terms: &[
+            "bakery",
+            "food-counter",
+            "general-store",
+            "imports-shop",
+            "market",
+            "specialty-shop",
+            "trading-post",
+        ],
+        night: &["The stalls are shuttered and dark, with only a watchman left to keep an eye on things."],
+        morning: &["The merchants are still setting out their wares for the day."],
+        midday: &[
+            "At this hour it bustles with shoppers haggling over every copper.",
+            "Buyers jostle at the counters, and the sellers can barely keep up.",
+        ],
+        afternoon: &["Business has slowed, and a few merchants are already counting their takings."],
+        evening: &["The last customers are being hurried along so that the doors can be closed for the night."],
+    },
+    Pool {
+        terms: &["abbey", "monastery", "shrine", "temple"],
+        night: &["A single candle burns before the altar, tended by a drowsy acolyte."],
+        morning: &["The morning prayers can be heard from the street."],
+        midday: &[],
+        afternoon: &[],
+        evening: &["The faithful are gathering for the evening service."],
+    },
+];
+
+/// Append a sentence suited to the time of day to the place's description, if the description
+/// hasn't been specified by the user and the kind of place has anything to say about it.
+pub fn apply_flavor(place: &mut Place, time_of_day: TimeOfDay, rng: &mut impl Rng) {
+    if place.description.is_locked() {
+        return;
+    }
+
+    let Some(sentence) = place
+        .subtype
+        .value()
+        .and_then(|subtype| {
+            let term = subtype.as_str();
+            POOLS.iter().find(|pool| pool.terms.contains(&term))
+        })
+        .and_then(|pool| pool.sentences(time_of_day).choose(rng))
+    else {
+        return;
+    };
+
+    place
+        .description
+        .replace_with(|description| match description {
+            Some(description) if !description.is_empty() => format!("{} {}", description, sentence),
+            _ => sentence.to_string(),
+        });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::world::place::PlaceType;
+    use crate::world::Field;
+
+    #[test]
+    fn apply_flavor_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let inn = Place {
+            subtype: "inn".parse::<PlaceType>().unwrap().into(),
+            description: Field::new_generated("A busy inn.".to_string()),
+            ..Default::default()
+        };
+
+        {
+            let mut place = inn.clone();
+            apply_flavor(&mut place, TimeOfDay::Night, &mut rng);
+
+            let description = place.description.value().unwrap();
+            assert!(description.starts_with("A busy inn. "), "{}", description);
+            assert!(
+                POOLS[0]
+                    .night
+                    .iter()
+                    .any(|sentence| description.ends_with(sentence)),
+                "{}",
+                description,
+            );
+        }
+
+        {
+            let mut place = inn.clone();
+            place.description.lock();
+            apply_flavor(&mut place, TimeOfDay::Night, &mut rng);
+            assert_eq!(inn.description.value(), place.description.value());
+        }
+
+        {
+            let mut place = Place {
+                subtype: "temple".parse::<PlaceType>().unwrap().into(),
+                ..Default::default()
+            };
+            apply_flavor(&mut place, TimeOfDay::Midday, &mut rng);
+            assert!(place.description.is_none());
+        }
+
+        {
+            let mut place = Place {
+                subtype: "market".parse::<PlaceType>().unwrap().into(),
+                ..Default::default()
+            };
+            apply_flavor(&mut place, TimeOfDay::Midday, &mut rng);
+            assert!(
+                POOLS[1]
+                    .midday
+                    .contains(&place.description.value().unwrap().as_str()),
+                "{:?}",
+                place.description,
+            );
+        }
+    }
+}
@@ -0,0 +1,413 @@
+use super::dungeon::{Dungeon, Theme};
+use super::{Place, PlaceType};
+use crate::reference::ChallengeRating;
+use crate::treasure::Treasure;
+use crate::utils::CaseInsensitiveStr;
+use crate::world::grammar::count;
+use initiative_reference::srd_5e;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The lair of a legendary monster, stored with the dungeon that it lives in. The lair actions are
+/// rolled when the lair is created so that they stay the same from one session to the next.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Lair {
+    pub monster: String,
+    pub challenge_rating: u8,
+
+    /// Homebrew monsters have a stat block to link to; SRD monsters don't yet.
+    #[serde(default)]
+    pub homebrew: bool,
+
+    pub lair_actions: Vec<String>,
+    pub wandering_groups: Vec<String>,
+}
+
+/// A monster that can be given a lair with `create lair for [monster]`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LairMonster {
+    name: String,
+    challenge_rating: u8,
+    theme: Theme,
+    homebrew: bool,
+}
+
+/// The lair actions and wandering groups, as shown at the top of the dungeon.
+pub struct LairView<'a>(&'a Lair);
+
+/// The number of lair actions rolled for each lair.
+const LAIR_ACTIONS: usize = 3;
+
+/// The number of groups of minions that wander the lair.
+const WANDERING_GROUPS: usize = 3;
+
+/// Words in a monster's name that suggest where it makes its lair, eg. a black dragon in a swamp.
+const THEME_KEYWORDS: &[(&str, Theme)] = &[
+    ("aboleth", Theme::Cavern),
+    ("androsphinx", Theme::Temple),
+    ("black", Theme::Swamp),
+    ("blue", Theme::Desert),
+    ("brass", Theme::Desert),
+    ("bronze", Theme::Coast),
+    ("copper", Theme::Peak),
+    ("gold", Theme::Peak),
+    ("green", Theme::Forest),
+    ("gynosphinx", Theme::Temple),
+    ("kraken", Theme::Coast),
+    ("lich", Theme::Crypt),
+    ("mummy", Theme::Crypt),
+    ("red", Theme::Volcano),
+    ("silver", Theme::Peak),
+    ("sphinx", Theme::Temple),
+    ("vampire", Theme::Keep),
+    ("white", Theme::Glacier),
+];
+
+impl LairMonster {
+    /// One of the legendary monsters of the SRD, eg. "adult black dragon".
+    pub fn srd(name: &str) -> Option<Self> {
+        srd_5e::monsters()
+            .ok()?
+            .into_iter()
+            .filter(|monster| monster.is_legendary())
+            .find(|monster| monster.name.eq_ci(name))
+            .map(|monster| {
+                let name = monster.name.to_lowercase();
+                Self {
+                    theme: theme_for(&name, Some(monster.monster_type())),
+                    challenge_rating: monster.challenge_rating() as u8,
+                    homebrew: false,
+                    name,
+                }
+            })
+    }
+
+    /// A monster added with `homebrew import`. Its type is used to choose a theme if its name
+    /// doesn't suggest one.
+    pub fn homebrew(name: &str, challenge_rating: u8, monster_type: Option<&str>) -> Self {
+        Self {
+            name: name.to_string(),
+            challenge_rating,
+            theme: theme_for(name, monster_type),
+            homebrew: true,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Lair {
+    pub fn display(&self) -> LairView {
+        LairView(self)
+    }
+}
+
+/// Turn the place into the lair of the given monster, with the monster's hoard rolled into the
+/// lair's inventory.
+pub fn generate(place: &mut Place, rng: &mut impl Rng, monster: &LairMonster) {
+    let LairMonster {
+        name,
+        challenge_rating,
+        theme,
+        homebrew,
+    } = monster;
+
+    let mut dungeon = Dungeon::generate_with_boss(rng, *theme, name, *challenge_rating);
+
+    if let Some(room) = dungeon.rooms.iter_mut().find(|room| room.boss) {
+        room.treasure = Some(format!("The {}'s hoard (see the lair's inventory)", name));
+    }
+
+    let save_dc = ChallengeRating::whole(*challenge_rating).save_dc();
+    let mut lair_actions: Vec<String> = lair_actions(*theme)
+        .iter()
+        .map(|action| {
+            action
+                .replace("{monster}", name)
+                .replace("{dc}", &save_dc.to_string())
+        })
+        .collect();
+    lair_actions.shuffle(rng);
+    lair_actions.truncate(LAIR_ACTIONS);
+
+    let wandering_groups = (0..WANDERING_GROUPS)
+        .map(|_| count(rng.gen_range(2..=6), theme.minion(rng)))
+        .collect();
+
+    dungeon.lair = Some(Lair {
+        monster: name.to_string(),
+        challenge_rating: *challenge_rating,
+        homebrew: *homebrew,
+        lair_actions,
+        wandering_groups,
+    });
+
+    place
+        .subtype
+        .replace("dungeon".parse::<PlaceType>().unwrap());
+    place.name.replace_with(|_| theme.name(rng));
+    place
+        .inventory
+        .replace(Treasure::hoard(rng, *challenge_rating).into_inventory());
+    place.dungeon.replace(dungeon);
+}
+
+fn theme_for(name: &str, monster_type: Option<&str>) -> Theme {
+    name.split_whitespace()
+        .find_map(|word| {
+            THEME_KEYWORDS
+                .iter()
+                .find(|(keyword, _)| keyword.eq_ci(word))
+                .map(|(_, theme)| *theme)
+        })
+        .unwrap_or_else(|| match monster_type.map(str::to_lowercase).as_deref() {
+            Some("undead") => Theme::Crypt,
+            Some("celestial") | Some("fiend") => Theme::Temple,
+            Some("giant") | Some("humanoid") => Theme::Keep,
+            Some("construct") | Some("elemental") => Theme::Mine,
+            Some("ooze") => Theme::Sewer,
+            _ => Theme::Cavern,
+        })
+}
+
+/// The lair actions that suit a theme. `{monster}` and `{dc}` are replaced by the name of the
+/// monster and the save DC expected at its challenge rating.
+fn lair_actions(theme: Theme) -> &'static [&'static str] {
+    match theme {
+        Theme::Cavern => &[
+            "Part of the ceiling collapses above a creature that the {monster} can see within 120 feet of it. The creature must succeed on a DC {dc} Dexterity saving throw or take 10 (3d6) bludgeoning damage and be knocked prone and buried.",
+            "The water in the lair surges. Each creature in the water within 90 feet of the {monster} must succeed on a DC {dc} Strength saving throw or be pulled up to 20 feet toward it.",
+            "Magical darkness fills a 20-foot-radius sphere centered on a point the {monster} can see within 120 feet of it, lasting until initiative count 20 on the next round.",
+            "Phantasmal whispers fill the caverns. Each creature within 60 feet of the {monster} must succeed on a DC {dc} Wisdom saving throw or be frightened of it until initiative count 20 on the next round.",
+        ],
+        Theme::Crypt => &[
+            "The {monster} calls on the dead. Up to three corpses within 60 feet of it rise as zombies under its control, crumbling to dust at initiative count 20 on the next round.",
+            "Necrotic energy lashes out at a creature the {monster} can see within 60 feet of it. The creature must succeed on a DC {dc} Constitution saving throw or take 10 (3d6) necrotic damage and be unable to regain hit points until initiative count 20 on the next round.",
+            "The {monster} rolls initiative for a spectral guardian, which appears within 30 feet of it and vanishes at initiative count 20 on the next round.",
+            "Every door in the lair slams shut and locks. Opening one requires a successful DC {dc} Strength check until initiative count 20 on the next round.",
+        ],
+        Theme::Keep => &[
+            "Mist billows through the halls, heavily obscuring a 20-foot-radius sphere centered on a point the {monster} can see until initiative count 20 on the next round.",
+            "Shadows grasp at a creature the {monster} can see within 60 feet of it. The creature must succeed on a DC {dc} Strength saving throw or be restrained until initiative count 20 on the next round.",
+            "A swarm of bats or rats boils out of the walls within 30 feet of the {monster}, acting on its initiative count until it is destroyed or the {monster} dismisses it.",
+            "The {monster} passes through a wall or floor as though it weren't there, moving up to 30 feet without provoking opportunity attacks.",
+        ],
+        Theme::Mine => &[
+            "The tunnel shakes. Each creature on the ground within 60 feet of the {monster} must succeed on a DC {dc} Dexterity saving throw or be knocked prone.",
+            "A shower of rubble falls on a 10-foot square the {monster} can see within 120 feet of it. Each creature there must succeed on a DC {dc} Dexterity saving throw or take 10 (3d6) bludgeoning damage.",
+            "A pocket of choking gas seeps from the rock, filling a 20-foot-radius sphere the {monster} can see. Each creature there must succeed on a DC {dc} Constitution saving throw or be poisoned until initiative count 20 on the next round.",
+            "The {monster} sinks into the stone and rises again at an unoccupied point it can see within 60 feet of it.",
+        ],
+        Theme::Sewer => &[
+            "Foul water surges through the tunnels. Each creature in the water within 60 feet of the {monster} must succeed on a DC {dc} Strength saving throw or be knocked prone and pushed 15 feet.",
+            "A cloud of stinking gas fills a 20-foot-radius sphere the {monster} can see. Each creature there must succeed on a DC {dc} Constitution saving throw or be poisoned until initiative count 20 on the next round.",
+            "A swarm of rats pours out of the drains within 30 feet of the {monster}, acting on its initiative count until it is destroyed.",
+            "The walls ooze with slime. The floor in a 20-foot square the {monster} can see becomes difficult terrain until initiative count 20 on the next round.",
+        ],
+        Theme::Temple => &[
+            "An unholy light flares around a creature the {monster} can see within 120 feet of it. The creature must succeed on a DC {dc} Wisdom saving throw or be blinded until initiative count 20 on the next round.",
+            "The {monster} gains the benefit of a spell of 3rd level or lower that it could cast as an action, without expending a spell slot.",
+            "Time warps around the {monster}. Each other creature within 60 feet of it must succeed on a DC {dc} Wisdom saving throw or be unable to take reactions until initiative count 20 on the next round.",
+            "The statues of the temple speak with one voice. Each creature of the {monster}'s choice within 60 feet of it must succeed on a DC {dc} Charisma saving throw or be frightened of it until initiative count 20 on the next round.",
+        ],
+        Theme::Swamp => &[
+            "Pools of water within 120 feet of the {monster} surge outward. Each creature of its choice standing in water must succeed on a DC {dc} Strength saving throw or be pulled underwater and restrained until initiative count 20 on the next round.",
+            "A cloud of swarming insects fills a 20-foot-radius sphere centered on a point the {monster} can see within 120 feet of it. Each creature in the cloud when it appears must succeed on a DC {dc} Constitution saving throw or take 10 (3d6) piercing damage.",
+            "Magical darkness spreads from a point the {monster} chooses within 60 feet of it, filling a 15-foot-radius sphere until initiative count 20 on the next round.",
+            "The mud within 60 feet of the {monster} churns, and the ground in a 20-foot square becomes difficult terrain until initiative count 20 on the next round.",
+        ],
+        Theme::Desert => &[
+            "A whirlwind of sand fills a 20-foot-radius sphere the {monster} can see within 120 feet of it. Each creature there must succeed on a DC {dc} Constitution saving throw or be blinded until initiative count 20 on the next round.",
+            "Part of the floor gives way into sinking sand. Each creature in a 20-foot square the {monster} can see must succeed on a DC {dc} Dexterity saving throw or sink up to its waist and be restrained.",
+            "Lightning arcs from the ceiling to a creature the {monster} can see within 120 feet of it. The creature must succeed on a DC {dc} Dexterity saving throw or take 10 (3d6) lightning damage.",
+            "A blistering wind blows through the lair. Each creature within 60 feet of the {monster} must succeed on a DC {dc} Constitution saving throw or gain a level of exhaustion.",
+        ],
+        Theme::Forest => &[
+            "Grasping roots and vines erupt in a 20-foot radius centered on a point the {monster} can see within 120 feet of it. Each creature there must succeed on a DC {dc} Strength saving throw or be restrained until initiative count 20 on the next round.",
+            "A wall of thorny brush springs up along a line of up to 60 feet that the {monster} can see, lasting until initiative count 20 on the next round. A creature moving through it takes 7 (2d6) piercing damage for every 5 feet it moves.",
+            "Poisonous pollen drifts through a 20-foot-radius sphere the {monster} can see. Each creature there must succeed on a DC {dc} Constitution saving throw or be poisoned until initiative count 20 on the next round.",
+            "An illusory double of the {monster} appears within 60 feet of it, lasting until initiative count 20 on the next round. Attacks against the {monster} have disadvantage while the double remains.",
+        ],
+        Theme::Volcano => &[
+            "Magma erupts from a point on the ground the {monster} can see within 120 feet of it, creating a 20-foot-high, 5-foot-radius geyser. Each creature in the geyser's area must succeed on a DC {dc} Dexterity saving throw or take 21 (6d6) fire damage.",
+            "A tremor shakes the lair in a 60-foot radius around the {monster}. Each other creature on the ground must succeed on a DC {dc} Dexterity saving throw or be knocked prone.",
+            "Volcanic gases form a cloud in a 20-foot-radius sphere centered on a point the {monster} can see within 120 feet of it. Each creature that starts its turn in the cloud must succeed on a DC {dc} Constitution saving throw or be poisoned until the end of its turn.",
+            "Red-hot rocks rain down on a 10-foot square the {monster} can see. Each creature there takes 10 (3d6) fire damage.",
+        ],
+        Theme::Glacier => &[
+            "Freezing fog fills a 20-foot-radius sphere centered on a point the {monster} can see within 120 feet of it. Each creature in the fog when it appears must succeed on a DC {dc} Constitution saving throw or take 10 (3d6) cold damage.",
+            "Jagged ice shards fall from the ceiling, striking up to three creatures underneath that the {monster} can see within 120 feet of it. Each must succeed on a DC {dc} Dexterity saving throw or take 10 (3d6) piercing damage.",
+            "A wall of ice springs up from the ground along a line of up to 60 feet that the {monster} can see. It lasts until the {monster} uses this lair action again or dies.",
+            "The floor within 60 feet of the {monster} becomes slick with ice. Each creature that moves across it must succeed on a DC {dc} Dexterity saving throw or fall prone.",
+        ],
+        Theme::Coast => &[
+            "A strong current moves through the lair. Each creature within 60 feet of the {monster} must succeed on a DC {dc} Strength saving throw or be pushed up to 60 feet away from it.",
+            "A wave crashes through a 20-foot-radius sphere the {monster} can see. Each creature there must succeed on a DC {dc} Strength saving throw or be knocked prone and take 7 (2d6) bludgeoning damage.",
+            "Thick fog rolls in from the sea, heavily obscuring a 20-foot-radius sphere centered on a point the {monster} can see until initiative count 20 on the next round.",
+            "Lightning flashes across the water, striking a creature the {monster} can see within 120 feet of it. The creature must succeed on a DC {dc} Dexterity saving throw or take 10 (3d6) lightning damage.",
+        ],
+        Theme::Peak => &[
+            "A gust of wind sweeps through the lair. Each creature within 60 feet of the {monster} must succeed on a DC {dc} Strength saving throw or be pushed 15 feet away from it and knocked prone.",
+            "Stones tumble down the slope onto a 10-foot square the {monster} can see. Each creature there must succeed on a DC {dc} Dexterity saving throw or take 10 (3d6) bludgeoning damage.",
+            "A sudden flurry of snow or cloud heavily obscures a 20-foot-radius sphere centered on a point the {monster} can see until initiative count 20 on the next round.",
+            "The {monster} reverses gravity in a 20-foot-radius, 100-foot-high cylinder it can see. Each creature there falls upward, landing again at initiative count 20 on the next round.",
+        ],
+    }
+}
+
+impl<'a> fmt::Display for LairView<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let lair = self.0;
+
+        if lair.homebrew {
+            write!(f, "**Lair of:** `{}`", lair.monster)?;
+        } else {
+            write!(f, "**Lair of:** {}", lair.monster)?;
+        }
+
+        write!(f, " (CR {})", lair.challenge_rating)?;
+
+        if !lair.lair_actions.is_empty() {
+            write!(
+                f,
+                "\n\n## Lair Actions\n\nOn initiative count 20 (losing initiative ties), the {} takes a lair action to cause one of the following effects. It can't use the same effect two rounds in a row.\n",
+                lair.monster,
+            )?;
+
+            for action in &lair.lair_actions {
+                write!(f, "\n* {}", action)?;
+            }
+        }
+
+        if !lair.wandering_groups.is_empty() {
+            write!(f, "\n\n## Wandering Groups\n")?;
+
+            for group in &lair.wandering_groups {
+                write!(f, "\n* {}", group)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn srd_test() {
+        assert_eq!(
+            Some(LairMonster {
+                name: "adult black dragon".to_string(),
+                challenge_rating: 14,
+                theme: Theme::Swamp,
+                homebrew: false,
+            }),
+            LairMonster::srd("Adult Black Dragon"),
+        );
+        assert_eq!(Theme::Crypt, LairMonster::srd("lich").unwrap().theme);
+        assert_eq!(None, LairMonster::srd("goblin"));
+    }
+
+    #[test]
+    fn homebrew_test() {
+        assert_eq!(
+            Theme::Volcano,
+            LairMonster::homebrew("Red Tyrant", 9, Some("dragon")).theme,
+        );
+        assert_eq!(
+            Theme::Crypt,
+            LairMonster::homebrew("Bone Lord", 9, Some("undead")).theme,
+        );
+        assert_eq!(
+            Theme::Cavern,
+            LairMonster::homebrew("Bog Lurker", 1, Some("monstrosity")).theme,
+        );
+        assert_eq!(
+            Theme::Cavern,
+            LairMonster::homebrew("Sacred Ooze", 1, None).theme,
+        );
+    }
+
+    #[test]
+    fn generate_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let monster = LairMonster::srd("adult black dragon").unwrap();
+        let mut place = Place::default();
+
+        generate(&mut place, &mut rng, &monster);
+
+        assert!(place.is_dungeon());
+        assert!(place.name.is_some());
+        assert!(place.inventory.is_some());
+
+        let dungeon = place.dungeon.value().unwrap();
+        assert_eq!(Theme::Swamp, dungeon.theme);
+
+        let boss = dungeon.rooms.last().unwrap();
+        assert!(boss.boss);
+        assert_eq!(Some("an adult black dragon"), boss.inhabitants.as_deref());
+
+        let lair = dungeon.lair.as_ref().unwrap();
+        assert_eq!(LAIR_ACTIONS, lair.lair_actions.len());
+        assert_eq!(WANDERING_GROUPS, lair.wandering_groups.len());
+        assert!(
+            lair.lair_actions
+                .iter()
+                .all(|action| !action.contains('{') && action.contains("the adult black dragon")),
+            "{:?}",
+            lair.lair_actions,
+        );
+    }
+
+    #[test]
+    fn display_test() {
+        let lair = Lair {
+            monster: "Bog Lurker".to_string(),
+            challenge_rating: 3,
+            homebrew: true,
+            lair_actions: vec!["The mud churns.".to_string()],
+            wandering_groups: vec!["3 bullywugs".to_string()],
+        };
+
+        assert_eq!(
+            "**Lair of:** `Bog Lurker` (CR 3)
+
+## Lair Actions
+
+On initiative count 20 (losing initiative ties), the Bog Lurker takes a lair action to cause one of the following effects. It can't use the same effect two rounds in a row.
+
+* The mud churns.
+
+## Wandering Groups
+
+* 3 bullywugs",
+            lair.display().to_string(),
+        );
+    }
+
+    #[test]
+    fn serialize_deserialize_test() {
+        let lair = Lair {
+            monster: "lich".to_string(),
+            challenge_rating: 21,
+            homebrew: false,
+            lair_actions: vec!["The dead rise.".to_string()],
+            wandering_groups: vec!["4 skeletons".to_string()],
+        };
+
+        assert_eq!(
+            r#"{"monster":"lich","challenge_rating":21,"homebrew":false,"lair_actions":["The dead rise."],"wandering_groups":["4 skeletons"]}"#,
+            serde_json::to_string(&lair).unwrap(),
+        );
+
+        assert_eq!(
+            lair,
+            serde_json::from_str(r#"{"monster":"lich","challenge_rating":21,"lair_actions":["The dead rise."],"wandering_groups":["4 skeletons"]}"#).unwrap(),
+        );
+    }
+}
@@ -1,7 +1,4 @@
-use crate::{
-    utils::pluralize,
-    world::{word, word::ListGenerator, Demographics, Place},
-};
+use crate::world::{grammar::plural, word, word::ListGenerator, Demographics, Place};
 use rand::prelude::*;
 
 pub fn generate(place: &mut Place, rng: &mut impl Rng, _demographics: &Demographics) {
@@ -21,8 +18,8 @@ fn name(rng: &mut impl Rng) -> String {
             beach_synonym(rng)
         ),
         5 => {
-            let (profession, s) = pluralize(word::profession(rng));
-            format!("{}{} {}", profession, s, beach_synonym(rng))
+            let profession = plural(word::profession(rng));
+            format!("{} {}", profession, beach_synonym(rng))
         }
         _ => unreachable!(),
     }
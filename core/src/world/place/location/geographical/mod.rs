@@ -1,5 +1,6 @@
 mod beach;
 mod canyon;
+mod river;
 
 use initiative_macros::WordList;
 use rand::Rng;
@@ -45,7 +46,7 @@ impl GeographicalType {
             Self::Island | Self::Peninsula => Some("🏝"),
             Self::Monolith => Some("🗿"),
             Self::Oasis => Some("🌴"),
-            Self::Cave | Self::Rift => None,
+            Self::Cave | Self::Rift => Some("🕳"),
         }
     }
 }
@@ -56,6 +57,7 @@ pub fn generate(place: &mut Place, rng: &mut impl Rng, demographics: &Demographi
         match subtype {
             GeographicalType::Beach => beach::generate(place, rng, demographics),
             GeographicalType::Canyon => canyon::generate(place, rng, demographics),
+            GeographicalType::River => river::generate(place, rng, demographics),
             _ => {}
         }
     }
@@ -0,0 +1,74 @@
+use crate::world::{word, word::ListGenerator, Demographics, Place};
+use rand::prelude::*;
+
+pub fn generate(place: &mut Place, rng: &mut impl Rng, _demographics: &Demographics) {
+    place.name.replace_with(|_| name(rng));
+}
+
+fn name(rng: &mut impl Rng) -> String {
+    match rng.gen_range(0..=5) {
+        0..=1 => format!("{} {}", thing(rng), river_synonym(rng)),
+        2 => format!("{} {}", word::adjective(rng), river_synonym(rng)),
+        3 => format!("{} {}", word::cardinal_direction(rng), river_synonym(rng)),
+        4 => format!("River {}", thing(rng)),
+        5 => format!("{}'s {}", word::person(rng), river_synonym(rng)),
+        _ => unreachable!(),
+    }
+}
+
+fn thing(rng: &mut impl Rng) -> &'static str {
+    match rng.gen_range(0..=7) {
+        0..=2 => word::animal(rng),
+        3 => word::enemy(rng),
+        4 => word::gem(rng),
+        5 => word::profession(rng),
+        6..=7 => word::symbol(rng),
+        _ => unreachable!(),
+    }
+}
+
+#[rustfmt::skip]
+fn river_synonym(rng: &mut impl Rng) -> &'static str {
+    ListGenerator(&[
+        "Beck", "Brook", "Creek", "Fork", "River", "River", "River", "Run", "Stream", "Water",
+    ]).gen(rng)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn name_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        assert_eq!(
+            [
+                "Hidden River",
+                "Ancestor's River",
+                "Red Creek",
+                "Column Creek",
+                "River Swan",
+                "Thirsty Beck",
+                "Silent Beck",
+                "Wasted Fork",
+                "Farrier Beck",
+                "River Hammer",
+                "Morose Fork",
+                "Red River",
+                "Cap Brook",
+                "River Cow",
+                "East River",
+                "Roc Beck",
+                "Sage's River",
+                "Silent Water",
+                "Empress's Run",
+                "East Creek",
+            ]
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<_>>(),
+            (0..20).map(|_| name(&mut rng)).collect::<Vec<String>>(),
+        );
+    }
+}
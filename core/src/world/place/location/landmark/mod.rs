@@ -1,9 +1,16 @@
 use initiative_macros::WordList;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 
+use crate::world::place::{dungeon, PlaceType};
+use crate::world::{Demographics, Place};
+
+use super::LocationType;
+
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, WordList)]
 #[serde(into = "&'static str", try_from = "&str")]
 pub enum LandmarkType {
+    Dungeon,
     Farm,
     Fountain,
     Garden,
@@ -19,6 +26,7 @@ pub enum LandmarkType {
 impl LandmarkType {
     pub const fn get_emoji(&self) -> Option<&'static str> {
         match self {
+            Self::Dungeon => Some("💀"),
             Self::Farm | Self::Garden => Some("🌱"),
             Self::Fountain => Some("⛲"),
             Self::Harbor => Some("⛵"),
@@ -30,3 +38,14 @@ impl LandmarkType {
         }
     }
 }
+
+pub fn generate(place: &mut Place, rng: &mut impl Rng, _demographics: &Demographics) {
+    #[allow(clippy::collapsible_match)]
+    if let Some(PlaceType::Location(LocationType::Landmark(subtype))) = place.subtype.value() {
+        #[allow(clippy::single_match)]
+        match subtype {
+            LandmarkType::Dungeon => dungeon::generate(place, rng),
+            _ => {}
+        }
+    }
+}
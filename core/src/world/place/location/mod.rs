@@ -2,6 +2,9 @@ mod geographical;
 mod landmark;
 mod settlement;
 
+pub use landmark::LandmarkType;
+pub use settlement::generate_name as generate_settlement_name;
+
 use initiative_macros::WordList;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
@@ -38,6 +41,8 @@ pub fn generate(place: &mut Place, rng: &mut impl Rng, demographics: &Demographi
         #[allow(clippy::single_match)]
         match subtype {
             LocationType::Geographical(_) => geographical::generate(place, rng, demographics),
+            LocationType::Landmark(_) => landmark::generate(place, rng, demographics),
+            LocationType::Settlement(_) => settlement::generate(place, rng, demographics),
             _ => {}
         }
     }
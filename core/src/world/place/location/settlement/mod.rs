@@ -1,4 +1,10 @@
+mod name;
+
+pub use name::generate_name;
+
+use crate::world::{Demographics, Place};
 use initiative_macros::WordList;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, WordList)]
@@ -30,3 +36,8 @@ impl SettlementType {
         }
     }
 }
+
+pub fn generate(place: &mut Place, rng: &mut impl Rng, demographics: &Demographics) {
+    let ethnicity = demographics.dominant_ethnicity();
+    place.name.replace_with(|_| generate_name(rng, &ethnicity));
+}
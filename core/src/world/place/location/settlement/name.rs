@@ -0,0 +1,280 @@
+use crate::world::npc::Ethnicity;
+use crate::world::weighted_index_from_tuple;
+use rand::prelude::*;
+
+/// The building blocks of settlement names in a region dominated by one ethnicity.
+struct Region {
+    /// Opening syllables of names made up entirely of syllables, eg. "Bran" in "Brannock".
+    syllable_first: &'static [(&'static str, usize)],
+    /// Closing syllables of names made up entirely of syllables, eg. "nock" in "Brannock".
+    syllable_last: &'static [(&'static str, usize)],
+    /// Whole words that begin a compound name, eg. "Ash" in "Ashford".
+    roots: &'static [(&'static str, usize)],
+    /// Endings of compound names, eg. "ford" in "Ashford".
+    suffixes: &'static [(&'static str, usize)],
+    /// Separate words preceding a name, eg. "Old" in "Old Ashford".
+    prefixes: &'static [(&'static str, usize)],
+}
+
+/// Generate the name of a settlement in a region dominated by the given ethnicity.
+pub fn generate_name(rng: &mut impl Rng, ethnicity: &Ethnicity) -> String {
+    let region = region(ethnicity);
+
+    match rng.gen_range(0..10) {
+        0..=5 => compound(rng, region),
+        6..=7 => syllables(rng, region),
+        8..=9 => {
+            let prefix = weighted_index_from_tuple(rng, region.prefixes);
+            let name = compound(rng, region);
+
+            if name.starts_with(prefix) {
+                name
+            } else {
+                format!("{} {}", prefix, name)
+            }
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn compound(rng: &mut impl Rng, region: &Region) -> String {
+    let root = weighted_index_from_tuple(rng, region.roots);
+    let suffix = weighted_index_from_tuple(rng, region.suffixes);
+    format!("{}{}", root, suffix)
+}
+
+fn syllables(rng: &mut impl Rng, region: &Region) -> String {
+    let first = weighted_index_from_tuple(rng, region.syllable_first);
+    let last = weighted_index_from_tuple(rng, region.syllable_last);
+    format!("{}{}", first, last)
+}
+
+fn region(ethnicity: &Ethnicity) -> &'static Region {
+    match ethnicity {
+        Ethnicity::Dragonborn => &DRAGONBORN,
+        Ethnicity::Dwarvish => &DWARVISH,
+        Ethnicity::Elvish => &ELVISH,
+        Ethnicity::Gnomish => &GNOMISH,
+        Ethnicity::Halfling => &HALFLING,
+        // Ethnicities drawn from the real world share the generic human place names rather than
+        // borrowing real place names.
+        Ethnicity::Human
+        | Ethnicity::Khmer
+        | Ethnicity::Maori
+        | Ethnicity::Quechua
+        | Ethnicity::Yoruba => &HUMAN,
+        Ethnicity::Orcish => &ORCISH,
+        Ethnicity::Tiefling => &TIEFLING,
+    }
+}
+
+#[rustfmt::skip]
+const DRAGONBORN: Region = Region {
+    syllable_first: &[
+        ("Ar", 2), ("Bala", 1), ("Dra", 3), ("Kri", 1), ("Mei", 1), ("Ra", 1), ("Sora", 1),
+        ("Thava", 1), ("Vor", 2), ("Yth", 1),
+    ],
+    syllable_last: &[
+        ("ath", 2), ("dar", 2), ("gorn", 1), ("iax", 1), ("rinn", 1), ("thar", 2), ("vys", 1),
+        ("xor", 1),
+    ],
+    roots: &[
+        ("Cinder", 1), ("Claw", 1), ("Ember", 2), ("Fang", 1), ("Flame", 2), ("Gold", 1),
+        ("Scale", 2), ("Storm", 1), ("Thunder", 1), ("Wyrm", 2),
+    ],
+    suffixes: &[
+        ("crest", 1), ("forge", 1), ("hold", 2), ("keep", 2), ("peak", 1), ("rock", 1),
+        ("spire", 2),
+    ],
+    prefixes: &[("Far", 1), ("Great", 1), ("High", 2), ("Old", 2), ("Red", 1)],
+};
+
+#[rustfmt::skip]
+const DWARVISH: Region = Region {
+    syllable_first: &[
+        ("Bar", 1), ("Bel", 1), ("Dun", 2), ("Dur", 2), ("Grim", 1), ("Gund", 1), ("Kar", 2),
+        ("Khaz", 2), ("Mor", 1), ("Thor", 1),
+    ],
+    syllable_last: &[
+        ("ak", 2), ("bek", 1), ("dul", 1), ("dum", 2), ("grim", 1), ("heim", 2), ("rak", 2),
+    ],
+    roots: &[
+        ("Anvil", 2), ("Coal", 1), ("Copper", 1), ("Deep", 1), ("Forge", 1), ("Gold", 1),
+        ("Granite", 1), ("Hammer", 2), ("Iron", 3), ("Stone", 3),
+    ],
+    suffixes: &[
+        ("delve", 2), ("forge", 1), ("gate", 1), ("hall", 2), ("hold", 3), ("mount", 1),
+    ],
+    prefixes: &[("Deep", 1), ("Great", 1), ("High", 1), ("Old", 2), ("Under", 1)],
+};
+
+#[rustfmt::skip]
+const ELVISH: Region = Region {
+    syllable_first: &[
+        ("Ael", 2), ("Ar", 1), ("Cel", 1), ("Eli", 1), ("Ith", 1), ("Lor", 2), ("Mith", 1),
+        ("Quel", 1), ("Sil", 2), ("Tha", 1),
+    ],
+    syllable_last: &[
+        ("adel", 1), ("anor", 2), ("ethil", 1), ("ion", 1), ("ith", 1), ("lond", 2),
+        ("oria", 1), ("thas", 1), ("wen", 1),
+    ],
+    roots: &[
+        ("Dawn", 1), ("Elder", 1), ("Glimmer", 1), ("Leaf", 1), ("Mist", 2), ("Moon", 2),
+        ("Silver", 2), ("Song", 1), ("Star", 2), ("Willow", 1),
+    ],
+    suffixes: &[
+        ("bough", 1), ("glade", 3), ("haven", 2), ("mere", 1), ("spire", 1), ("vale", 2),
+        ("wood", 2),
+    ],
+    prefixes: &[("Elder", 1), ("Fair", 2), ("High", 2), ("Twilight", 1)],
+};
+
+#[rustfmt::skip]
+const GNOMISH: Region = Region {
+    syllable_first: &[
+        ("Bim", 1), ("Dab", 1), ("Fizz", 1), ("Glim", 2), ("Nib", 1), ("Pip", 1), ("Quill", 1),
+        ("Tink", 2), ("Wob", 1), ("Zib", 1),
+    ],
+    syllable_last: &[
+        ("ble", 2), ("bit", 1), ("gle", 2), ("ket", 1), ("nook", 1), ("wick", 2), ("zle", 1),
+    ],
+    roots: &[
+        ("Cog", 2), ("Copper", 1), ("Gear", 2), ("Gem", 1), ("Lantern", 1), ("Puzzle", 1),
+        ("Spring", 1), ("Sprocket", 1), ("Tinker", 2), ("Whistle", 1),
+    ],
+    suffixes: &[
+        ("burrow", 2), ("dale", 1), ("hollow", 2), ("nook", 1), ("ton", 1), ("works", 2),
+    ],
+    prefixes: &[("Little", 2), ("Lower", 1), ("New", 1), ("Old", 1), ("Upper", 1)],
+};
+
+#[rustfmt::skip]
+const HALFLING: Region = Region {
+    syllable_first: &[
+        ("Bil", 1), ("Bram", 1), ("Bud", 2), ("Cot", 1), ("Fen", 1), ("Hob", 2), ("Mug", 1),
+        ("Pip", 1), ("Tum", 1), ("Wil", 1),
+    ],
+    syllable_last: &[
+        ("ble", 2), ("by", 2), ("den", 1), ("kin", 1), ("mer", 1), ("sey", 1), ("wort", 1),
+    ],
+    roots: &[
+        ("Apple", 2), ("Barley", 1), ("Bramble", 1), ("Clover", 2), ("Green", 2), ("Hazel", 1),
+        ("Honey", 1), ("Meadow", 1), ("Pudding", 1), ("Thistle", 1),
+    ],
+    suffixes: &[
+        ("bottom", 2), ("brook", 1), ("burrow", 2), ("dale", 1), ("field", 1), ("hill", 2),
+        ("shire", 1),
+    ],
+    prefixes: &[("Little", 2), ("Much", 1), ("Nether", 1), ("Over", 1), ("Under", 1)],
+};
+
+#[rustfmt::skip]
+const HUMAN: Region = Region {
+    syllable_first: &[
+        ("Al", 1), ("Bran", 2), ("Cal", 1), ("Dar", 1), ("Ed", 1), ("Har", 2), ("Kel", 1),
+        ("Mar", 2), ("Per", 1), ("Ros", 1), ("Tam", 1), ("Wel", 1),
+    ],
+    syllable_last: &[
+        ("ans", 1), ("cester", 2), ("don", 2), ("ick", 1), ("more", 1), ("nock", 1),
+        ("ridge", 2), ("wyn", 1),
+    ],
+    roots: &[
+        ("Ash", 2), ("Brook", 1), ("Cold", 1), ("Dun", 1), ("Elm", 1), ("Fair", 1), ("Hart", 1),
+        ("Kings", 2), ("Marsh", 1), ("Mill", 2), ("Oak", 2), ("Red", 1), ("Stone", 2),
+        ("Thorn", 1), ("Wolf", 1),
+    ],
+    suffixes: &[
+        ("bridge", 2), ("bury", 2), ("by", 1), ("field", 2), ("ford", 3), ("ham", 2),
+        ("ley", 2), ("mouth", 1), ("ton", 4), ("wick", 2), ("worth", 1),
+    ],
+    prefixes: &[
+        ("East", 1), ("Great", 1), ("Little", 1), ("Lower", 1), ("New", 2), ("North", 1),
+        ("Old", 2), ("South", 1), ("Upper", 1), ("West", 1),
+    ],
+};
+
+#[rustfmt::skip]
+const ORCISH: Region = Region {
+    syllable_first: &[
+        ("Dush", 1), ("Gor", 2), ("Grash", 1), ("Kruk", 1), ("Lug", 1), ("Mog", 2), ("Ruk", 1),
+        ("Thrak", 1), ("Ug", 1), ("Zug", 1),
+    ],
+    syllable_last: &[
+        ("ash", 2), ("bad", 1), ("gul", 1), ("mash", 1), ("nak", 2), ("rog", 1), ("uk", 2),
+        ("zog", 1),
+    ],
+    roots: &[
+        ("Ash", 1), ("Black", 2), ("Blood", 2), ("Bone", 2), ("Grim", 1), ("Iron", 1),
+        ("Red", 1), ("Scar", 1), ("Skull", 2), ("Tusk", 1),
+    ],
+    suffixes: &[
+        ("camp", 1), ("crag", 1), ("fang", 1), ("gash", 1), ("hold", 1), ("maw", 2), ("pit", 2),
+    ],
+    prefixes: &[("Black", 1), ("Broken", 2), ("Burnt", 1), ("Red", 1), ("Split", 1)],
+};
+
+#[rustfmt::skip]
+const TIEFLING: Region = Region {
+    syllable_first: &[
+        ("Ak", 1), ("Bel", 1), ("Dam", 1), ("Ka", 1), ("Mor", 2), ("Nem", 1), ("Orl", 1),
+        ("Vel", 1), ("Za", 1), ("Zar", 2),
+    ],
+    syllable_last: &[
+        ("akos", 1), ("enon", 1), ("ias", 1), ("ith", 2), ("mos", 1), ("oth", 2), ("thys", 1),
+        ("zel", 1),
+    ],
+    roots: &[
+        ("Ash", 1), ("Brim", 1), ("Cinder", 1), ("Dusk", 2), ("Gloom", 1), ("Night", 2),
+        ("Shadow", 1), ("Sorrow", 1), ("Thorn", 1),
+    ],
+    suffixes: &[
+        ("crossing", 1), ("fall", 2), ("gate", 2), ("hollow", 1), ("moor", 1), ("reach", 1),
+        ("vale", 1),
+    ],
+    prefixes: &[("Black", 1), ("Far", 1), ("Lost", 2), ("Lower", 1), ("Old", 1)],
+};
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn generate_name_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        let names: Vec<String> = (0..10)
+            .map(|_| generate_name(&mut rng, &Ethnicity::Human))
+            .collect();
+
+        assert_eq!(
+            vec![
+                "Wolfham",
+                "Old Kingston",
+                "Wolfmouth",
+                "Thornfield",
+                "Kelcester",
+                "Stoneford",
+                "West Stoneford",
+                "East Hartfield",
+                "Ashton",
+                "Kingston",
+            ],
+            names,
+        );
+    }
+
+    #[test]
+    fn generate_name_test_all_ethnicities() {
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        for word in Ethnicity::get_words() {
+            let ethnicity: Ethnicity = word.parse().unwrap();
+
+            for _ in 0..50 {
+                let name = generate_name(&mut rng, &ethnicity);
+                assert!(!name.is_empty(), "{}", ethnicity);
+                assert!(name.starts_with(char::is_uppercase), "{}", name);
+            }
+        }
+    }
+}
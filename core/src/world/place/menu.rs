@@ -0,0 +1,391 @@
+use super::building::BuildingType;
+use super::{Place, PlaceType};
+use crate::world::word::{self, ListGenerator};
+use crate::world::Field;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The fare offered by an inn or tavern. Prices are taken from the Food, Drink, and Lodging table
+/// of the SRD, according to the quality of the establishment.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct Menu {
+    pub quality: Field<Quality>,
+    pub drink: Field<MenuItem>,
+    pub dish: Field<MenuItem>,
+    pub room: Field<MenuItem>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct MenuItem {
+    pub name: String,
+
+    /// The price in copper pieces.
+    pub price: u32,
+}
+
+/// The lifestyle tiers of the SRD.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Quality {
+    Squalid,
+    Poor,
+    Modest,
+    Comfortable,
+    Wealthy,
+    Aristocratic,
+}
+
+pub struct MenuView<'a>(&'a Menu);
+
+struct PriceView(u32);
+
+impl Menu {
+    /// Generate any items that are missing or unlocked. Locked items are left untouched, but new
+    /// items are priced according to the quality of the establishment, even if it is locked.
+    pub fn regenerate(&mut self, rng: &mut impl Rng) {
+        self.quality.replace_with(|_| Quality::generate(rng));
+        let quality = self.quality.value().copied().unwrap_or(Quality::Modest);
+
+        self.drink.replace_with(|_| drink(rng, quality));
+        self.dish.replace_with(|_| dish(rng, quality));
+        self.room.replace_with(|_| room(quality));
+    }
+
+    pub fn lock_all(&mut self) {
+        let Self {
+            quality,
+            drink,
+            dish,
+            room,
+        } = self;
+
+        quality.lock();
+        drink.lock();
+        dish.lock();
+        room.lock();
+    }
+
+    pub fn display(&self) -> MenuView {
+        MenuView(self)
+    }
+}
+
+impl Place {
+    pub fn has_menu(&self) -> bool {
+        matches!(
+            self.subtype.value(),
+            Some(PlaceType::Building(BuildingType::Business(subtype))) if subtype.has_menu(),
+        )
+    }
+}
+
+impl Quality {
+    fn generate(rng: &mut impl Rng) -> Self {
+        match rng.gen_range(0..12) {
+            0 => Self::Squalid,
+            1..=3 => Self::Poor,
+            4..=7 => Self::Modest,
+            8..=9 => Self::Comfortable,
+            10 => Self::Wealthy,
+            11 => Self::Aristocratic,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl fmt::Display for Quality {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Squalid => write!(f, "squalid"),
+            Self::Poor => write!(f, "poor"),
+            Self::Modest => write!(f, "modest"),
+            Self::Comfortable => write!(f, "comfortable"),
+            Self::Wealthy => write!(f, "wealthy"),
+            Self::Aristocratic => write!(f, "aristocratic"),
+        }
+    }
+}
+
+impl fmt::Display for MenuItem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({})", self.name, PriceView(self.price))
+    }
+}
+
+impl fmt::Display for PriceView {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            cp if cp > 0 && cp % 100 == 0 => write!(f, "{} gp", cp / 100),
+            cp if cp > 0 && cp % 10 == 0 => write!(f, "{} sp", cp / 10),
+            cp => write!(f, "{} cp", cp),
+        }
+    }
+}
+
+impl<'a> fmt::Display for MenuView<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let menu = self.0;
+        let mut lines = Vec::new();
+
+        if let Some(quality) = menu.quality.value() {
+            lines.push(format!("**Quality:** {}", quality));
+        }
+
+        if let Some(drink) = menu.drink.value() {
+            lines.push(format!("**Signature Drink:** {}", drink));
+        }
+
+        if let Some(dish) = menu.dish.value() {
+            lines.push(format!("**House Dish:** {}", dish));
+        }
+
+        if let Some(room) = menu.room.value() {
+            lines.push(format!("**Lodging:** {} per night", room));
+        }
+
+        write!(f, "{}", lines.join("\\\n"))
+    }
+}
+
+fn drink(rng: &mut impl Rng, quality: Quality) -> MenuItem {
+    let adjective = match rng.gen_range(0..3) {
+        0 => word::adjective(rng),
+        1 => word::animal(rng),
+        2 => ListGenerator(&[
+            "Dragon's Breath",
+            "Dwarven",
+            "Elven",
+            "Frostbite",
+            "Goblin",
+            "Harvest",
+            "Honeyed",
+            "Smoked",
+            "Spiced",
+        ])
+        .gen(rng),
+        _ => unreachable!(),
+    };
+
+    match quality {
+        Quality::Squalid | Quality::Poor | Quality::Modest => MenuItem {
+            name: format!(
+                "Mug of {} {}",
+                adjective,
+                ListGenerator(&["Ale", "Bitter", "Cider", "Lager", "Mead", "Porter", "Stout"])
+                    .gen(rng),
+            ),
+            price: 4,
+        },
+        Quality::Comfortable | Quality::Wealthy => MenuItem {
+            name: format!(
+                "Pitcher of {} {}",
+                adjective,
+                ListGenerator(&["Claret", "Mulled Wine", "Red", "Rosé", "White"]).gen(rng),
+            ),
+            price: 20,
+        },
+        Quality::Aristocratic => MenuItem {
+            name: format!(
+                "Bottle of {} {}",
+                adjective,
+                ListGenerator(&["Brandy", "Claret", "Sparkling Wine", "Vintage"]).gen(rng),
+            ),
+            price: 1000,
+        },
+    }
+}
+
+fn dish(rng: &mut impl Rng, quality: Quality) -> MenuItem {
+    let main = ListGenerator(&[
+        "Beef", "Boar", "Chicken", "Eel", "Lentil", "Mushroom", "Mutton", "Pork", "Rabbit",
+        "Trout", "Turnip", "Venison",
+    ])
+    .gen(rng);
+
+    let name = match quality {
+        Quality::Squalid | Quality::Poor => format!(
+            "{} {}",
+            main,
+            ListGenerator(&["Gruel", "Porridge", "Pottage", "Soup"]).gen(rng),
+        ),
+        Quality::Modest | Quality::Comfortable => format!(
+            "{} {} {}",
+            ListGenerator(&["Hearty", "Peppered", "Salted", "Smoked", "Spiced"]).gen(rng),
+            main,
+            ListGenerator(&["Pasty", "Pie", "Sausages", "Skewers", "Stew"]).gen(rng),
+        ),
+        Quality::Wealthy | Quality::Aristocratic => format!(
+            "{} {} with {}",
+            ListGenerator(&["Braised", "Glazed", "Roasted", "Stuffed"]).gen(rng),
+            main,
+            ListGenerator(&["Black Truffles", "Figs", "Plum Sauce", "Saffron Rice"]).gen(rng),
+        ),
+    };
+
+    MenuItem {
+        name,
+        price: match quality {
+            Quality::Squalid => 3,
+            Quality::Poor => 6,
+            Quality::Modest => 30,
+            Quality::Comfortable => 50,
+            Quality::Wealthy => 80,
+            Quality::Aristocratic => 200,
+        },
+    }
+}
+
+fn room(quality: Quality) -> MenuItem {
+    let (name, price) = match quality {
+        Quality::Squalid => ("Common room floor", 7),
+        Quality::Poor => ("Common room cot", 10),
+        Quality::Modest => ("Shared room", 50),
+        Quality::Comfortable => ("Private room", 80),
+        Quality::Wealthy => ("Private suite", 200),
+        Quality::Aristocratic => ("Luxury suite", 400),
+    };
+
+    MenuItem {
+        name: name.to_string(),
+        price,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn regenerate_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut menu = Menu::default();
+        menu.regenerate(&mut rng);
+
+        assert!(menu.quality.is_some());
+        assert!(menu.drink.is_some());
+        assert!(menu.dish.is_some());
+        assert!(menu.room.is_some());
+    }
+
+    #[test]
+    fn regenerate_test_locked() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let drink = MenuItem {
+            name: "Mug of Dwarven Stout".to_string(),
+            price: 4,
+        };
+
+        let mut menu = Menu {
+            quality: Quality::Aristocratic.into(),
+            drink: drink.clone().into(),
+            ..Default::default()
+        };
+
+        for _ in 0..10 {
+            menu.regenerate(&mut rng);
+
+            assert_eq!(Some(&Quality::Aristocratic), menu.quality.value());
+            assert_eq!(Some(&drink), menu.drink.value());
+            assert_eq!(200, menu.dish.value().unwrap().price);
+            assert_eq!(
+                Some(&MenuItem {
+                    name: "Luxury suite".to_string(),
+                    price: 400,
+                }),
+                menu.room.value(),
+            );
+        }
+    }
+
+    #[test]
+    fn lock_all_test() {
+        let mut menu = Menu::default();
+        menu.lock_all();
+
+        assert_eq!(
+            Menu {
+                quality: Field::Locked(None),
+                drink: Field::Locked(None),
+                dish: Field::Locked(None),
+                room: Field::Locked(None),
+            },
+            menu,
+        );
+    }
+
+    #[test]
+    fn has_menu_test() {
+        [
+            ("inn", true),
+            ("tavern", true),
+            ("pub", true),
+            ("blacksmith", false),
+        ]
+        .into_iter()
+        .for_each(|(subtype, has_menu)| {
+            let place = Place {
+                subtype: subtype.parse::<PlaceType>().ok().into(),
+                ..Default::default()
+            };
+            assert_eq!(has_menu, place.has_menu(), "{}", subtype);
+        });
+
+        assert!(!Place::default().has_menu());
+    }
+
+    #[test]
+    fn display_test() {
+        let menu = Menu {
+            quality: Quality::Modest.into(),
+            drink: MenuItem {
+                name: "Mug of Dwarven Stout".to_string(),
+                price: 4,
+            }
+            .into(),
+            dish: MenuItem {
+                name: "Spiced Mutton Pie".to_string(),
+                price: 30,
+            }
+            .into(),
+            room: MenuItem {
+                name: "Luxury suite".to_string(),
+                price: 400,
+            }
+            .into(),
+        };
+
+        assert_eq!(
+            "**Quality:** modest\\
+**Signature Drink:** Mug of Dwarven Stout (4 cp)\\
+**House Dish:** Spiced Mutton Pie (3 sp)\\
+**Lodging:** Luxury suite (4 gp) per night",
+            menu.display().to_string(),
+        );
+
+        assert_eq!("", Menu::default().display().to_string());
+    }
+
+    #[test]
+    fn serialize_deserialize_test() {
+        let menu = Menu {
+            quality: Quality::Poor.into(),
+            room: room(Quality::Poor).into(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            r#"{"quality":"poor","drink":null,"dish":null,"room":{"name":"Common room cot","price":10}}"#,
+            serde_json::to_string(&menu).unwrap(),
+        );
+
+        assert_eq!(
+            menu,
+            serde_json::from_str(
+                r#"{"quality":"poor","room":{"name":"Common room cot","price":10}}"#
+            )
+            .unwrap(),
+        );
+    }
+}
@@ -1,11 +1,31 @@
-pub use view::{DescriptionView, DetailsView, NameView, SummaryView};
-
+pub use ambience::Ambience;
+pub use dungeon::Dungeon;
+pub use economy::{economy_at, Scarcity, Wealth, WELL_SUPPLIED};
+pub use festival::{festival_notices, skipped_festival_notices, upcoming_festivals, Festival};
+pub use flavor::apply_flavor;
+pub use lair::{generate as generate_lair, LairMonster};
+pub use location::generate_settlement_name;
+pub use menu::Menu;
+pub use region::generate_features;
+pub use vehicle::Vehicle;
+pub use view::{DescriptionView, DetailsView, LocationView, NameView, SummaryView};
+
+mod ambience;
 mod building;
+mod descriptor;
+mod dungeon;
+mod economy;
+mod festival;
+mod flavor;
+mod lair;
 mod location;
+mod menu;
 mod region;
+mod vehicle;
 mod view;
 
-use super::{Demographics, Field, Generate};
+use super::chances::{ChanceTable, Chances};
+use super::{Demographics, Field, Generate, Inventory, Statuses};
 use initiative_macros::WordList;
 use rand::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -21,13 +41,56 @@ pub struct Place {
 
     pub name: Field<String>,
     pub description: Field<String>,
+
+    #[serde(default)]
+    pub menu: Field<Menu>,
+
+    #[serde(default)]
+    pub dungeon: Field<Dungeon>,
+
+    #[serde(default)]
+    pub vehicle: Field<Vehicle>,
+
+    #[serde(default)]
+    pub festivals: Field<Vec<Festival>>,
+
+    #[serde(default)]
+    pub inventory: Field<Inventory>,
+
+    #[serde(default)]
+    pub statuses: Field<Statuses>,
+
+    #[serde(default)]
+    pub archived: Field<bool>,
+
+    #[serde(default)]
+    pub pinned: Field<bool>,
+
+    /// The name of a homebrew place type, shown in place of the built-in one it's based on.
+    #[serde(default)]
+    pub homebrew: Field<String>,
+
+    /// How wealthy the place is, which raises or lowers the prices of things bought there.
+    #[serde(default)]
+    pub wealth: Field<Wealth>,
+
+    /// What's in short supply, which raises the prices of the affected goods.
+    #[serde(default)]
+    pub scarcity: Field<Vec<Scarcity>>,
+
+    /// The name of the deity worshipped at a temple or shrine, from the campaign's pantheon.
+    #[serde(default)]
+    pub worship: Field<String>,
+
+    /// Other names the place goes by, which find it as well as its name does.
+    #[serde(default)]
+    pub aliases: Field<Vec<String>>,
     // pub architecture: Option<String>,
     // pub floors: Field<u8>,
     // pub owner: Field<Vec<NpcUuid>>,
     // pub staff: Field<Vec<NpcUuid>>,
     // pub occupants: Field<Vec<NpcUuid>>,
     // pub services: Option<String>,
-    // pub worship: Field<String>,
     // pub quality: something
     // pub price: something
 }
@@ -46,6 +109,7 @@ pub enum PlaceType {
     Building(building::BuildingType),
     Location(location::LocationType),
     Region(region::RegionType),
+    Vehicle(vehicle::VehicleType),
 }
 
 impl Place {
@@ -69,6 +133,86 @@ impl Place {
         &["place"][..]
     }
 
+    /// Is this a camp, town, city, or other place where people live?
+    pub fn is_settlement(&self) -> bool {
+        matches!(
+            self.subtype.value(),
+            Some(PlaceType::Location(location::LocationType::Settlement(_))),
+        )
+    }
+
+    /// The fields that can be locked and unlocked by name, eg. `lock The Silver Eel's menu`.
+    pub fn lockable_fields() -> &'static [&'static str] {
+        &[
+            "description",
+            "dungeon",
+            "festivals",
+            "menu",
+            "name",
+            "vehicle",
+        ][..]
+    }
+
+    /// Is the field locked? Returns `None` if it isn't one of [`Place::lockable_fields`].
+    pub fn is_field_locked(&self, field: &str) -> Option<bool> {
+        Some(match field {
+            "description" => self.description.is_locked(),
+            "dungeon" => self.dungeon.is_locked(),
+            "festivals" => self.festivals.is_locked(),
+            "menu" => self.menu.is_locked(),
+            "name" => self.name.is_locked(),
+            "vehicle" => self.vehicle.is_locked(),
+            _ => return None,
+        })
+    }
+
+    /// The lockable fields that are unlocked, and so free to be regenerated.
+    pub fn unlocked_fields(&self) -> Vec<&'static str> {
+        Self::lockable_fields()
+            .iter()
+            .copied()
+            .filter(|field| self.is_field_locked(field) == Some(false))
+            .collect()
+    }
+
+    /// Lock or unlock the field, returning whether it was locked beforehand, or `None` if it
+    /// isn't one of [`Place::lockable_fields`].
+    pub fn set_field_locked(&mut self, field: &str, locked: bool) -> Option<bool> {
+        Some(match field {
+            "description" => self.description.set_locked(locked),
+            "dungeon" => self.dungeon.set_locked(locked),
+            "festivals" => self.festivals.set_locked(locked),
+            "menu" => self.menu.set_locked(locked),
+            "name" => self.name.set_locked(locked),
+            "vehicle" => self.vehicle.set_locked(locked),
+            _ => return None,
+        })
+    }
+
+    /// The value of a field as it's shown to the user, or `None` if it isn't set or can't be
+    /// shown in a single line, as with menus and dungeons.
+    pub fn display_field(&self, field: &str) -> Option<String> {
+        match field {
+            "description" => self.description.value().cloned(),
+            "name" => self.name.value().cloned(),
+            _ => None,
+        }
+    }
+
+    /// A place with nothing but the given field, for use as a diff. Only fields that can be
+    /// shown with [`Place::display_field`] are supported.
+    pub fn field_diff(&self, field: &str) -> Option<Self> {
+        let mut diff = Self::default();
+
+        match field {
+            "description" => diff.description = self.description.value().cloned().into(),
+            "name" => diff.name = self.name.value().cloned().into(),
+            _ => return None,
+        }
+
+        Some(diff)
+    }
+
     pub fn lock_all(&mut self) {
         let Self {
             uuid: _,
@@ -76,12 +220,38 @@ impl Place {
             subtype,
             name,
             description,
+            menu,
+            dungeon,
+            vehicle,
+            festivals,
+            inventory,
+            statuses,
+            archived,
+            pinned,
+            homebrew,
+            wealth,
+            scarcity,
+            worship,
+            aliases,
         } = self;
 
         location_uuid.lock();
         subtype.lock();
         name.lock();
         description.lock();
+        menu.lock();
+        dungeon.lock();
+        vehicle.lock();
+        festivals.lock();
+        inventory.lock();
+        statuses.lock();
+        archived.lock();
+        pinned.lock();
+        homebrew.lock();
+        wealth.lock();
+        scarcity.lock();
+        worship.lock();
+        aliases.lock();
     }
 
     pub fn apply_diff(&mut self, diff: &mut Self) {
@@ -91,12 +261,63 @@ impl Place {
             subtype,
             name,
             description,
+            menu,
+            dungeon,
+            vehicle,
+            festivals,
+            inventory,
+            statuses,
+            archived,
+            pinned,
+            homebrew,
+            wealth,
+            scarcity,
+            worship,
+            aliases,
         } = self;
 
         location_uuid.apply_diff(&mut diff.location_uuid);
         subtype.apply_diff(&mut diff.subtype);
         name.apply_diff(&mut diff.name);
         description.apply_diff(&mut diff.description);
+        menu.apply_diff(&mut diff.menu);
+        dungeon.apply_diff(&mut diff.dungeon);
+        vehicle.apply_diff(&mut diff.vehicle);
+        festivals.apply_diff(&mut diff.festivals);
+        inventory.apply_diff(&mut diff.inventory);
+        statuses.apply_diff(&mut diff.statuses);
+        archived.apply_diff(&mut diff.archived);
+        pinned.apply_diff(&mut diff.pinned);
+        homebrew.apply_diff(&mut diff.homebrew);
+        wealth.apply_diff(&mut diff.wealth);
+        scarcity.apply_diff(&mut diff.scarcity);
+        worship.apply_diff(&mut diff.worship);
+        aliases.apply_diff(&mut diff.aliases);
+    }
+
+    /// A diff that merges the fields of a duplicate into this place, as with
+    /// [`Field::merged_from`]. The name, and whether the place is archived, are left as they are.
+    pub fn merge_diff(&self, other: &Self) -> Self {
+        Self {
+            uuid: None,
+            location_uuid: self.location_uuid.merged_from(&other.location_uuid),
+            subtype: self.subtype.merged_from(&other.subtype),
+            name: Field::default(),
+            description: self.description.merged_from(&other.description),
+            menu: self.menu.merged_from(&other.menu),
+            dungeon: self.dungeon.merged_from(&other.dungeon),
+            vehicle: self.vehicle.merged_from(&other.vehicle),
+            festivals: self.festivals.merged_from(&other.festivals),
+            inventory: self.inventory.merged_from(&other.inventory),
+            statuses: self.statuses.merged_from(&other.statuses),
+            archived: Field::default(),
+            pinned: self.pinned.merged_from(&other.pinned),
+            homebrew: self.homebrew.merged_from(&other.homebrew),
+            wealth: self.wealth.merged_from(&other.wealth),
+            scarcity: self.scarcity.merged_from(&other.scarcity),
+            worship: self.worship.merged_from(&other.worship),
+            aliases: self.aliases.merged_from(&other.aliases),
+        }
     }
 }
 
@@ -107,15 +328,22 @@ impl Generate for Place {
                 .replace_with(|_| PlaceType::generate(rng, demographics));
         }
 
+        descriptor::generate(self, rng);
+
         #[allow(clippy::collapsible_match)]
         if let Some(value) = self.subtype.value() {
             #[allow(clippy::single_match)]
             match value {
                 PlaceType::Building(_) => building::generate(self, rng, demographics),
                 PlaceType::Location(_) => location::generate(self, rng, demographics),
+                PlaceType::Vehicle(_) => vehicle::generate(self, rng, demographics),
                 _ => {}
             }
         }
+
+        if self.has_festivals() {
+            self.festivals.replace_with(|_| Festival::generate_all(rng));
+        }
     }
 }
 
@@ -126,6 +354,7 @@ impl PlaceType {
             Self::Building(subtype) => subtype.get_emoji(),
             Self::Location(subtype) => subtype.get_emoji(),
             Self::Region(subtype) => subtype.get_emoji(),
+            Self::Vehicle(subtype) => subtype.get_emoji(),
         } {
             emoji
         } else {
@@ -150,6 +379,24 @@ impl Generate for PlaceType {
     }
 }
 
+impl Chances for PlaceType {
+    /// The pooled name and description, with the name replaced by the tables of the kind of
+    /// place's own generator if it has one.
+    fn chances(&self) -> Vec<ChanceTable> {
+        let specific = match self {
+            PlaceType::Building(subtype) => building::chances(subtype),
+            _ => Vec::new(),
+        };
+
+        let mut tables: Vec<ChanceTable> = descriptor::chances(self)
+            .into_iter()
+            .filter(|table| !specific.iter().any(|t| t.label == table.label))
+            .collect();
+        tables.splice(0..0, specific);
+        tables
+    }
+}
+
 impl fmt::Display for PlaceType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.as_str())
@@ -178,6 +425,25 @@ mod test {
         );
     }
 
+    #[test]
+    fn chances_test() {
+        let labels = |term: &str| {
+            term.parse::<PlaceType>()
+                .unwrap()
+                .chances()
+                .into_iter()
+                .map(|table| table.label)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(vec!["name", "[thing]", "description"], labels("inn"));
+        assert_eq!(vec!["name", "description"], labels("bakery"));
+        assert_eq!(
+            vec!["name", "[place]", "[descriptor]", "[deity]", "description"],
+            labels("shrine"),
+        );
+    }
+
     #[test]
     fn default_test() {
         assert_eq!(PlaceType::Any, PlaceType::default());
@@ -224,11 +490,11 @@ mod test {
         let place = oaken_mermaid_inn();
 
         assert_eq!(
-            r#"{"uuid":"00000000-0000-0000-0000-000000000000","location_uuid":"00000000-0000-0000-0000-000000000000","subtype":"inn","name":"Oaken Mermaid Inn","description":"I am Mordenkainen"}"#,
+            r#"{"uuid":"00000000-0000-0000-0000-000000000000","location_uuid":"00000000-0000-0000-0000-000000000000","subtype":"inn","name":"Oaken Mermaid Inn","description":"I am Mordenkainen","menu":null,"dungeon":null,"vehicle":null,"festivals":null,"inventory":null,"statuses":null,"archived":null,"pinned":null,"homebrew":null,"wealth":null,"scarcity":null,"worship":null,"aliases":null}"#,
             serde_json::to_string(&place).unwrap(),
         );
 
-        let value: Place = serde_json::from_str(r#"{"uuid":"00000000-0000-0000-0000-000000000000","location_uuid":"00000000-0000-0000-0000-000000000000","subtype":"inn","name":"Oaken Mermaid Inn","description":"I am Mordenkainen"}"#).unwrap();
+        let value: Place = serde_json::from_str(r#"{"uuid":"00000000-0000-0000-0000-000000000000","location_uuid":"00000000-0000-0000-0000-000000000000","subtype":"inn","name":"Oaken Mermaid Inn","description":"I am Mordenkainen","menu":null,"dungeon":null,"vehicle":null,"festivals":null,"inventory":null,"statuses":null,"archived":null,"pinned":null,"homebrew":null,"wealth":null,"scarcity":null,"worship":null,"aliases":null}"#).unwrap();
 
         assert_eq!(place, value);
     }
@@ -273,6 +539,19 @@ mod test {
                 subtype: Field::Locked(None),
                 name: Field::Locked(None),
                 description: Field::Locked(None),
+                menu: Field::Locked(None),
+                dungeon: Field::Locked(None),
+                vehicle: Field::Locked(None),
+                festivals: Field::Locked(None),
+                inventory: Field::Locked(None),
+                statuses: Field::Locked(None),
+                archived: Field::Locked(None),
+                pinned: Field::Locked(None),
+                homebrew: Field::Locked(None),
+                wealth: Field::Locked(None),
+                scarcity: Field::Locked(None),
+                worship: Field::Locked(None),
+                aliases: Field::Locked(None),
             },
             place,
         );
@@ -293,6 +572,7 @@ mod test {
         let expect_words_emoji: Vec<(String, String)> = [
             ("abbey", "🙏"),
             ("academy", "🎓"),
+            ("airship", "🎈"),
             ("archipelago", "🏝"),
             ("arena", "🏛"),
             ("armorer", "🛡"),
@@ -306,30 +586,33 @@ mod test {
             ("bathhouse", "🛁"),
             ("beach", "🏖"),
             ("blacksmith", "🗡"),
+            ("boat", "⛵"),
             ("brewery", "🍻"),
             ("bridge", "🌉"),
-            ("building", "📍"),
+            ("building", "🏢"),
             ("business", "🪙"),
             ("camp", "🏕"),
             ("campsite", "🏕"),
             ("canyon", "🏞"),
             ("capital", "🏙"),
+            ("caravan", "🐪"),
             ("caravansary", "🏨"),
+            ("cart", "🐴"),
             ("casino", "🃏"),
             ("castle", "🏰"),
-            ("cave", "📍"),
-            ("cavern", "📍"),
+            ("cave", "🕳"),
+            ("cavern", "🕳"),
             ("cemetery", "🪦"),
             ("chasm", "🏞"),
             ("church", "🙏"),
             ("citadel", "🏰"),
             ("city", "🏙"),
             ("city-state", "👑"),
-            ("club", ""),
+            ("club", "🎶"),
             ("coastline", "🌊"),
             ("college", "🎓"),
             ("confederation", "👑"),
-            ("continent", "📍"),
+            ("continent", "🗺"),
             ("country", "👑"),
             ("county", "👑"),
             ("court", "🏰"),
@@ -339,6 +622,7 @@ mod test {
             ("district", "🏘"),
             ("domain", "👑"),
             ("duchy", "👑"),
+            ("dungeon", "💀"),
             ("duty-house", "🪙"),
             ("embassy", "🚩"),
             ("empire", "👑"),
@@ -387,16 +671,16 @@ mod test {
             ("manor", "🏠"),
             ("mansion", "🏠"),
             ("market", "🪙"),
-            ("marsh", "📍"),
+            ("marsh", "🐸"),
             ("mausoleum", "🪦"),
-            ("mesa", "📍"),
+            ("mesa", "🏜"),
             ("metropolis", "🏙"),
             ("mill", "🌾"),
             ("mine", "⚒"),
             ("monastery", "🙏"),
             ("monolith", "🗿"),
             ("monument", "🗽"),
-            ("moor", "📍"),
+            ("moor", "🌾"),
             ("mosque", "🙏"),
             ("mountain", "⛰"),
             ("nation", "👑"),
@@ -414,26 +698,28 @@ mod test {
             ("pet-store", "🐶"),
             ("pier", "⛵"),
             ("place", "📍"),
-            ("plain", "📍"),
-            ("plateau", "📍"),
-            ("portal", "📍"),
+            ("plain", "🌾"),
+            ("plateau", "⛰"),
+            ("portal", "🌀"),
             ("principality", "👑"),
             ("prison", "🛡"),
             ("province", "👑"),
             ("pub", "🍻"),
             ("quarter", "🏘"),
             ("realm", "👑"),
-            ("reef", "📍"),
+            ("reef", "🐠"),
             ("region", "👑"),
             ("region", "👑"),
             ("residence", "🏠"),
             ("restaurant", "🍽"),
             ("ridge", "⛰"),
-            ("rift", "📍"),
+            ("rift", "🕳"),
             ("river", "🏞"),
             ("ruin", "🏚"),
+            ("sailing-ship", "⛵"),
             ("school", "🎓"),
             ("sea", "🌊"),
+            ("ship", "⛵"),
             ("shipyard", "⛵"),
             ("shop", "🪙"),
             ("shrine", "🙏"),
@@ -445,7 +731,7 @@ mod test {
             ("store", "🪙"),
             ("street", "🏘"),
             ("stronghold", "🏰"),
-            ("swamp", "📍"),
+            ("swamp", "🐸"),
             ("synagogue", "🙏"),
             ("tavern", "🏨"),
             ("temple", "🙏"),
@@ -462,7 +748,9 @@ mod test {
             ("vale", "🏞"),
             ("valley", "🏞"),
             ("vault", "🏦"),
+            ("vehicle", "🛞"),
             ("village", "🏘"),
+            ("wagon", "🐴"),
             ("wainwright", "🪙"),
             ("wall", "🧱"),
             ("ward", "🏘"),
@@ -495,6 +783,19 @@ mod test {
 
             name: "Oaken Mermaid Inn".into(),
             description: "I am Mordenkainen".into(),
+            menu: None.into(),
+            dungeon: None.into(),
+            vehicle: None.into(),
+            festivals: None.into(),
+            inventory: None.into(),
+            statuses: None.into(),
+            archived: None.into(),
+            pinned: None.into(),
+            homebrew: None.into(),
+            wealth: None.into(),
+            scarcity: None.into(),
+            worship: None.into(),
+            aliases: None.into(),
         }
     }
 }
@@ -0,0 +1,330 @@
+use super::geography::GeographyType;
+use super::RegionType;
+use crate::world::place::PlaceType;
+use crate::world::{Demographics, Generate, LinkView, Place};
+use rand::prelude::*;
+
+/// How the places within a region relate to one another.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Layout {
+    /// Settlements strung along a river.
+    River,
+    /// Settlements on either side of a mountain range, linked by passes.
+    Mountains,
+    /// Landmarks hidden among the trees, with a settlement on the edge of the woods.
+    Forest,
+}
+
+impl Place {
+    /// Is the place a region whose settlements and landmarks are generated along with it?
+    pub fn has_features(&self) -> bool {
+        layout(self).is_some()
+    }
+}
+
+/// Generate the places within a region: settlements along a river, passes through a mountain
+/// range, or landmarks hidden in a forest. Each place is located within the region and
+/// described in relation to the others, so the region's `uuid` should be set beforehand.
+pub fn generate_features(
+    region: &Place,
+    rng: &mut impl Rng,
+    demographics: &Demographics,
+) -> Vec<Place> {
+    let layout = if let Some(layout) = layout(region) {
+        layout
+    } else {
+        return Vec::new();
+    };
+
+    let mut places = Vec::new();
+
+    match layout {
+        Layout::River => {
+            let capital = if matches!(
+                region.subtype.value(),
+                Some(PlaceType::Region(RegionType::Political(_))),
+            ) {
+                "city"
+            } else {
+                "town"
+            };
+
+            add(capital, region, &mut places, rng, demographics);
+            for _ in 0..rng.gen_range(1..=2) {
+                add("town", region, &mut places, rng, demographics);
+            }
+
+            let settlements: Vec<String> = places.iter().map(name).collect();
+            let river = add("river", region, &mut places, rng, demographics);
+
+            describe(
+                &mut places[river],
+                match &settlements[..] {
+                    [from, to] => format!(
+                        "It flows through {} from {} down to {}.",
+                        link(region),
+                        LinkView::new(from),
+                        LinkView::new(to),
+                    ),
+                    [from, past @ .., to] => format!(
+                        "It flows through {} from {}, past {}, and down to {}.",
+                        link(region),
+                        LinkView::new(from),
+                        past.iter()
+                            .map(|name| LinkView::new(name).to_string())
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                        LinkView::new(to),
+                    ),
+                    _ => unreachable!(),
+                },
+            );
+
+            let river_name = name(&places[river]);
+            for place in places.iter_mut().take(settlements.len()) {
+                describe(
+                    place,
+                    format!("It lies on the banks of {}.", LinkView::new(&river_name)),
+                );
+            }
+        }
+        Layout::Mountains => {
+            add("town", region, &mut places, rng, demographics);
+            add("outpost", region, &mut places, rng, demographics);
+
+            let settlements: Vec<String> = places.iter().map(name).collect();
+            let range = add("ridge", region, &mut places, rng, demographics);
+            describe(
+                &mut places[range],
+                format!("Its peaks run the length of {}.", link(region)),
+            );
+
+            let range_name = name(&places[range]);
+            for _ in 0..rng.gen_range(1..=2) {
+                let pass = add("pass", region, &mut places, rng, demographics);
+                describe(
+                    &mut places[pass],
+                    format!(
+                        "It crosses {}, linking {} and {}.",
+                        LinkView::new(&range_name),
+                        LinkView::new(&settlements[0]),
+                        LinkView::new(&settlements[1]),
+                    ),
+                );
+            }
+        }
+        Layout::Forest => {
+            let settlement = add("town", region, &mut places, rng, demographics);
+            describe(
+                &mut places[settlement],
+                format!("It stands on the edge of {}.", link(region)),
+            );
+
+            let count = rng.gen_range(2..=3);
+            for landmark in
+                ["cave", "grove", "monolith", "ruin", "tree"].choose_multiple(rng, count)
+            {
+                let landmark = add(landmark, region, &mut places, rng, demographics);
+                describe(
+                    &mut places[landmark],
+                    format!("It lies deep within {}.", link(region)),
+                );
+            }
+        }
+    }
+
+    places
+}
+
+fn layout(place: &Place) -> Option<Layout> {
+    match place.subtype.value() {
+        Some(PlaceType::Region(RegionType::Any | RegionType::Political(_))) => Some(Layout::River),
+        Some(PlaceType::Region(RegionType::Geography(subtype))) => match subtype {
+            GeographyType::Mountain => Some(Layout::Mountains),
+            GeographyType::Forest | GeographyType::Jungle => Some(Layout::Forest),
+            GeographyType::Coastline
+            | GeographyType::Marsh
+            | GeographyType::Moor
+            | GeographyType::Plain
+            | GeographyType::Plateau
+            | GeographyType::Swamp => Some(Layout::River),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Generate a place of the given type within the region, with a name that isn't already taken by
+/// another place in the region. Returns its index in `places`.
+fn add(
+    subtype: &str,
+    region: &Place,
+    places: &mut Vec<Place>,
+    rng: &mut impl Rng,
+    demographics: &Demographics,
+) -> usize {
+    let mut place = Place::default();
+
+    for _ in 0..10 {
+        place = Place {
+            location_uuid: region.uuid.clone().into(),
+            subtype: subtype.parse::<PlaceType>().ok().into(),
+            ..Default::default()
+        };
+        place.regenerate(rng, demographics);
+
+        if !places.iter().any(|other| other.name == place.name) {
+            break;
+        }
+    }
+
+    places.push(place);
+    places.len() - 1
+}
+
+/// Follow the place's description with a sentence relating it to its surroundings.
+fn describe(place: &mut Place, sentence: String) {
+    place
+        .description
+        .replace_with(|description| match description {
+            Some(description) => format!("{} {}", description, sentence),
+            None => sentence,
+        });
+}
+
+fn name(place: &Place) -> String {
+    place.name.to_string()
+}
+
+fn link(place: &Place) -> String {
+    LinkView::new(&name(place)).to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::world::place::Uuid;
+
+    #[test]
+    fn has_features_test() {
+        [
+            ("region", true),
+            ("kingdom", true),
+            ("mountain", true),
+            ("forest", true),
+            ("swamp", true),
+            ("ocean", false),
+            ("town", false),
+            ("inn", false),
+        ]
+        .into_iter()
+        .for_each(|(subtype, has_features)| {
+            assert_eq!(has_features, region(subtype).has_features(), "{}", subtype);
+        });
+    }
+
+    #[test]
+    fn generate_features_river_test() {
+        let region = region("kingdom");
+        let places = generate_features(
+            &region,
+            &mut SmallRng::seed_from_u64(0),
+            &Demographics::default(),
+        );
+
+        assert!(places.len() >= 3, "{:?}", places);
+        assert_eq!(Some("city"), subtype(&places[0]).as_deref());
+        assert_eq!(Some("river"), subtype(places.last().unwrap()).as_deref());
+
+        let (river, settlements) = places.split_last().unwrap();
+        for settlement in &settlements[1..] {
+            assert_eq!(Some("town"), subtype(settlement).as_deref());
+        }
+
+        for settlement in settlements {
+            assert!(
+                settlement
+                    .description
+                    .value()
+                    .unwrap()
+                    .ends_with(&format!(" It lies on the banks of {{{}}}.", river.name)),
+                "{:?}",
+                settlement,
+            );
+            assert!(
+                river
+                    .description
+                    .value()
+                    .unwrap()
+                    .contains(&format!("{{{}}}", settlement.name)),
+                "{:?}",
+                river,
+            );
+        }
+
+        assert!(places
+            .iter()
+            .all(|place| place.location_uuid == region.uuid.clone().into()));
+    }
+
+    #[test]
+    fn generate_features_mountains_test() {
+        let places = generate_features(
+            &region("mountain"),
+            &mut SmallRng::seed_from_u64(0),
+            &Demographics::default(),
+        );
+
+        assert_eq!(
+            ["town", "outpost", "ridge", "pass"],
+            places
+                .iter()
+                .take(4)
+                .map(|place| subtype(place).unwrap())
+                .collect::<Vec<_>>()[..],
+        );
+        assert!(places[3].description.value().unwrap().ends_with(&format!(
+            " It crosses {{{}}}, linking {{{}}} and {{{}}}.",
+            places[2].name, places[0].name, places[1].name,
+        )));
+    }
+
+    #[test]
+    fn generate_features_forest_test() {
+        let places = generate_features(
+            &region("forest"),
+            &mut SmallRng::seed_from_u64(0),
+            &Demographics::default(),
+        );
+
+        assert!((3..=4).contains(&places.len()), "{:?}", places);
+        assert!(places.iter().skip(1).all(|place| place
+            .description
+            .value()
+            .unwrap()
+            .ends_with(" It lies deep within {Mirkwood}.")));
+    }
+
+    #[test]
+    fn generate_features_none_test() {
+        assert!(generate_features(
+            &region("ocean"),
+            &mut SmallRng::seed_from_u64(0),
+            &Demographics::default(),
+        )
+        .is_empty());
+    }
+
+    fn region(subtype: &str) -> Place {
+        Place {
+            uuid: Some(Uuid::from(uuid::Uuid::nil())),
+            subtype: subtype.parse::<PlaceType>().ok().into(),
+            name: "Mirkwood".into(),
+            ..Default::default()
+        }
+    }
+
+    fn subtype(place: &Place) -> Option<String> {
+        place.subtype.value().map(|subtype| subtype.to_string())
+    }
+}
@@ -31,20 +31,16 @@ impl GeographyType {
     pub const fn get_emoji(&self) -> Option<&'static str> {
         match self {
             Self::Archipelago => Some("🏝"),
-            Self::Barrens | Self::Desert | Self::Wasteland => Some("🏜"),
+            Self::Barrens | Self::Desert | Self::Mesa | Self::Wasteland => Some("🏜"),
             Self::Coastline | Self::Lake | Self::Sea | Self::Ocean => Some("🌊"),
+            Self::Continent => Some("🗺"),
             Self::Forest | Self::Jungle => Some("🌳"),
-            Self::Mountain => Some("⛰"),
+            Self::Marsh | Self::Swamp => Some("🐸"),
+            Self::Moor | Self::Plain => Some("🌾"),
+            Self::Mountain | Self::Plateau => Some("⛰"),
+            Self::Reef => Some("🐠"),
             Self::Tundra => Some("❄"),
             Self::World => Some("🌐"),
-            Self::Continent
-            | Self::Marsh
-            | Self::Mesa
-            | Self::Moor
-            | Self::Plain
-            | Self::Plateau
-            | Self::Reef
-            | Self::Swamp => None,
         }
     }
 }
@@ -1,6 +1,9 @@
+mod features;
 mod geography;
 mod political;
 
+pub use features::generate_features;
+
 use initiative_macros::WordList;
 use serde::{Deserialize, Serialize};
 
@@ -17,7 +20,7 @@ pub enum RegionType {
 impl RegionType {
     pub const fn get_emoji(&self) -> Option<&'static str> {
         match self {
-            Self::Any => None,
+            Self::Any => Some("🗺"),
             Self::Geography(subtype) => subtype.get_emoji(),
             Self::Political(subtype) => subtype.get_emoji(),
         }
@@ -0,0 +1,348 @@
+use super::{Place, PlaceType};
+use crate::world::npc::Occupation;
+use crate::world::word::{self, ListGenerator};
+use crate::world::Demographics;
+use initiative_macros::WordList;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, WordList)]
+#[serde(into = "&'static str", try_from = "&str")]
+pub enum VehicleType {
+    #[term = "vehicle"]
+    Any,
+
+    Airship,
+    Caravan,
+
+    #[alias = "sailing-ship"]
+    #[alias = "boat"]
+    Ship,
+
+    #[alias = "cart"]
+    Wagon,
+}
+
+/// How fast and how much a vehicle can carry. Figures for ships and airships are taken from the
+/// Airborne and Waterborne Vehicles table of the SRD; caravans and wagons travel at a normal pace.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Vehicle {
+    /// The speed in miles per hour.
+    pub speed: u8,
+
+    /// The hours of travel per day. Ships and airships can travel around the clock with a crew
+    /// working in shifts, but caravans must stop to rest.
+    pub hours_per_day: u8,
+
+    pub crew: u16,
+    pub passengers: u16,
+
+    /// The cargo capacity in tons.
+    pub cargo: u16,
+}
+
+pub struct VehicleView<'a>(&'a Vehicle);
+
+impl VehicleType {
+    pub const fn get_emoji(&self) -> Option<&'static str> {
+        match self {
+            Self::Any => Some("🛞"),
+            Self::Airship => Some("🎈"),
+            Self::Caravan => Some("🐪"),
+            Self::Ship => Some("⛵"),
+            Self::Wagon => Some("🐴"),
+        }
+    }
+
+    /// The occupations of the notable crew members generated by the `crew` command, led by the
+    /// captain or caravan master.
+    pub const fn crew(&self) -> &'static [Occupation] {
+        match self {
+            Self::Any => &[],
+            Self::Airship => &[Occupation::Sailor, Occupation::Sailor, Occupation::Scholar],
+            Self::Caravan => &[
+                Occupation::Merchant,
+                Occupation::Guard,
+                Occupation::Guard,
+                Occupation::Laborer,
+            ],
+            Self::Ship => &[
+                Occupation::Sailor,
+                Occupation::Sailor,
+                Occupation::Sailor,
+                Occupation::Sailor,
+            ],
+            Self::Wagon => &[Occupation::Merchant],
+        }
+    }
+
+    fn generate(rng: &mut impl Rng) -> Self {
+        match rng.gen_range(0..4) {
+            0 => Self::Airship,
+            1 => Self::Caravan,
+            2 => Self::Ship,
+            3 => Self::Wagon,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl Vehicle {
+    fn generate(rng: &mut impl Rng, subtype: VehicleType) -> Self {
+        match subtype {
+            VehicleType::Airship => Self {
+                speed: 8,
+                hours_per_day: 24,
+                crew: 10,
+                passengers: 20,
+                cargo: 1,
+            },
+            VehicleType::Caravan => Self {
+                speed: 3,
+                hours_per_day: 8,
+                crew: rng.gen_range(6..=12),
+                passengers: rng.gen_range(0..=10),
+                cargo: rng.gen_range(2..=6),
+            },
+            VehicleType::Ship | VehicleType::Any => Self {
+                speed: 2,
+                hours_per_day: 24,
+                crew: 20,
+                passengers: 20,
+                cargo: 100,
+            },
+            VehicleType::Wagon => Self {
+                speed: 3,
+                hours_per_day: 8,
+                crew: 1,
+                passengers: rng.gen_range(0..=3),
+                cargo: 1,
+            },
+        }
+    }
+
+    /// The distance covered in a day of travel.
+    pub fn miles_per_day(&self) -> u16 {
+        u16::from(self.speed) * u16::from(self.hours_per_day)
+    }
+
+    pub fn display(&self) -> VehicleView {
+        VehicleView(self)
+    }
+}
+
+impl Place {
+    pub fn vehicle_type(&self) -> Option<VehicleType> {
+        match self.subtype.value() {
+            Some(PlaceType::Vehicle(subtype)) => Some(*subtype),
+            _ => None,
+        }
+    }
+}
+
+pub fn generate(place: &mut Place, rng: &mut impl Rng, _demographics: &Demographics) {
+    let subtype = match place.vehicle_type() {
+        Some(VehicleType::Any) | None => VehicleType::generate(rng),
+        Some(subtype) => subtype,
+    };
+
+    place.name.replace_with(|_| name(rng, subtype));
+    place
+        .vehicle
+        .replace_with(|_| Vehicle::generate(rng, subtype));
+}
+
+fn name(rng: &mut impl Rng, subtype: VehicleType) -> String {
+    match subtype {
+        VehicleType::Airship => match rng.gen_range(0..2) {
+            0 => format!(
+                "The {}{}",
+                ListGenerator(&["Cloud", "Sky", "Star", "Storm", "Sun", "Wind"]).gen(rng),
+                ListGenerator(&["chaser", "dancer", "piercer", "runner", "strider", "splitter"])
+                    .gen(rng),
+            ),
+            1 => format!("The {} {}", ship_adjective(rng), word::animal(rng)),
+            _ => unreachable!(),
+        },
+        VehicleType::Caravan => format!(
+            "The {} {} Caravan",
+            word::adjective(rng),
+            word::land_animal(rng),
+        ),
+        VehicleType::Ship | VehicleType::Any => match rng.gen_range(0..4) {
+            0..=1 => format!("The {} {}", ship_adjective(rng), ship_noun(rng)),
+            2 => format!("The {}'s {}", word::person(rng), ship_noun(rng)),
+            3 => format!(
+                "The {}",
+                ListGenerator(&[
+                    "Endeavour",
+                    "Fortune",
+                    "Perseverance",
+                    "Providence",
+                    "Resolve",
+                    "Revenge",
+                    "Triumph",
+                    "Valiant",
+                ])
+                .gen(rng),
+            ),
+            _ => unreachable!(),
+        },
+        VehicleType::Wagon => format!("The {}'s Wagon", word::profession(rng)),
+    }
+}
+
+fn ship_adjective(rng: &mut impl Rng) -> &'static str {
+    ListGenerator(&[
+        "Bold",
+        "Crimson",
+        "Dauntless",
+        "Golden",
+        "Gray",
+        "Merry",
+        "Restless",
+        "Salty",
+        "Silver",
+        "Swift",
+        "Wandering",
+        "Wicked",
+        "Windward",
+    ])
+    .gen(rng)
+}
+
+fn ship_noun(rng: &mut impl Rng) -> &'static str {
+    ListGenerator(&[
+        "Albatross",
+        "Dolphin",
+        "Gull",
+        "Heron",
+        "Kraken",
+        "Maiden",
+        "Mermaid",
+        "Serpent",
+        "Shark",
+        "Siren",
+        "Tempest",
+        "Wave",
+    ])
+    .gen(rng)
+}
+
+impl<'a> fmt::Display for VehicleView<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let vehicle = self.0;
+
+        write!(
+            f,
+            "**Speed:** {} mph ({} miles per day)\\\n**Crew:** {}\\\n**Passengers:** {}\\\n**Cargo:** {} {}",
+            vehicle.speed,
+            vehicle.miles_per_day(),
+            vehicle.crew,
+            vehicle.passengers,
+            vehicle.cargo,
+            if vehicle.cargo == 1 { "ton" } else { "tons" },
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn generate_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let demographics = Demographics::default();
+
+        [
+            ("airship", 8, 192),
+            ("caravan", 3, 24),
+            ("ship", 2, 48),
+            ("wagon", 3, 24),
+        ]
+        .into_iter()
+        .for_each(|(subtype, speed, miles_per_day)| {
+            let mut place = Place {
+                subtype: subtype.parse::<PlaceType>().ok().into(),
+                ..Default::default()
+            };
+            generate(&mut place, &mut rng, &demographics);
+
+            let vehicle = place.vehicle.value().unwrap();
+            assert_eq!(speed, vehicle.speed, "{}", subtype);
+            assert_eq!(miles_per_day, vehicle.miles_per_day(), "{}", subtype);
+            assert!(place.name.is_some(), "{}", subtype);
+        });
+    }
+
+    #[test]
+    fn generate_test_any() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut place = Place {
+            subtype: "vehicle".parse::<PlaceType>().ok().into(),
+            ..Default::default()
+        };
+        generate(&mut place, &mut rng, &Demographics::default());
+
+        assert!(place.name.is_some());
+        assert!(place.vehicle.is_some());
+        assert_eq!(Some(VehicleType::Any), place.vehicle_type());
+    }
+
+    #[test]
+    fn vehicle_type_test() {
+        assert_eq!(
+            Some(VehicleType::Ship),
+            Place {
+                subtype: "sailing-ship".parse::<PlaceType>().ok().into(),
+                ..Default::default()
+            }
+            .vehicle_type(),
+        );
+
+        assert_eq!(
+            None,
+            Place {
+                subtype: "inn".parse::<PlaceType>().ok().into(),
+                ..Default::default()
+            }
+            .vehicle_type(),
+        );
+    }
+
+    #[test]
+    fn display_test() {
+        let vehicle = Vehicle {
+            speed: 2,
+            hours_per_day: 24,
+            crew: 20,
+            passengers: 20,
+            cargo: 100,
+        };
+
+        assert_eq!(
+            "**Speed:** 2 mph (48 miles per day)\\
+**Crew:** 20\\
+**Passengers:** 20\\
+**Cargo:** 100 tons",
+            vehicle.display().to_string(),
+        );
+    }
+
+    #[test]
+    fn serialize_deserialize_test() {
+        let vehicle = Vehicle {
+            speed: 8,
+            hours_per_day: 24,
+            crew: 10,
+            passengers: 20,
+            cargo: 1,
+        };
+
+        let json = r#"{"speed":8,"hours_per_day":24,"crew":10,"passengers":20,"cargo":1}"#;
+        assert_eq!(json, serde_json::to_string(&vehicle).unwrap());
+        assert_eq!(vehicle, serde_json::from_str(json).unwrap());
+    }
+}
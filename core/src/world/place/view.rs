@@ -1,8 +1,12 @@
-use super::{Place, PlaceRelations, PlaceType};
+use super::{Place, PlaceRelations, PlaceType, WELL_SUPPLIED};
+use crate::utils::{CodeView, EscapedView};
+use crate::world::{list, LinkView};
 use std::fmt;
 
+/// Formatting with the alternate flag (`{:#}`) omits the emoji.
 pub struct NameView<'a>(&'a Place);
 
+/// Formatting with the alternate flag (`{:#}`) omits the emoji.
 pub struct SummaryView<'a>(&'a Place);
 
 pub struct DescriptionView<'a>(&'a Place);
@@ -12,6 +16,38 @@ pub struct DetailsView<'a> {
     relations: PlaceRelations,
 }
 
+/// Where something is located, eg. "🏨 {The Prancing Pony}, 🏘 {Bree}". Names are emitted as link
+/// tokens. Formatting with the alternate flag (`{:#}`) omits the emoji.
+pub struct LocationView<'a> {
+    parent: &'a Place,
+    grandparent: Option<&'a Place>,
+}
+
+fn write_name_link(place: &Place, f: &mut fmt::Formatter) -> fmt::Result {
+    if let Some(name) = place.name.value() {
+        if !f.alternate() {
+            write!(
+                f,
+                "{} ",
+                place.subtype.value().unwrap_or(&PlaceType::Any).get_emoji(),
+            )?;
+        }
+
+        write!(f, "{}", LinkView::new(name))
+    } else {
+        fmt::Display::fmt(&place.display_name(), f)
+    }
+}
+
+/// The place's type as shown to the user, which is its homebrew type if it has one.
+fn type_name(place: &Place, subtype: &PlaceType) -> String {
+    place
+        .homebrew
+        .value()
+        .cloned()
+        .unwrap_or_else(|| subtype.to_string())
+}
+
 impl<'a> NameView<'a> {
     pub fn new(place: &'a Place) -> Self {
         Self(place)
@@ -36,46 +72,124 @@ impl<'a> DetailsView<'a> {
     }
 }
 
+impl<'a> LocationView<'a> {
+    pub fn new(parent: &'a Place, grandparent: Option<&'a Place>) -> Self {
+        Self {
+            parent,
+            grandparent,
+        }
+    }
+}
+
 impl<'a> fmt::Display for NameView<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let place = self.0;
 
         if let Some(name) = place.name.value() {
-            write!(
-                f,
-                "{} `{}`",
-                place.subtype.value().unwrap_or(&PlaceType::Any).get_emoji(),
-                name,
-            )
+            if f.alternate() {
+                write!(f, "{}", CodeView::new(name))
+            } else {
+                write!(
+                    f,
+                    "{} {}",
+                    place.subtype.value().unwrap_or(&PlaceType::Any).get_emoji(),
+                    CodeView::new(name),
+                )
+            }
         } else {
             Ok(())
         }
     }
 }
 
+/// Transient statuses as badges following the summary, eg. " [on fire]".
+fn write_statuses(place: &Place, f: &mut fmt::Formatter) -> fmt::Result {
+    place
+        .statuses
+        .value()
+        .filter(|statuses| !statuses.is_empty())
+        .map(|statuses| write!(f, " {}", statuses.display()))
+        .transpose()?;
+
+    Ok(())
+}
+
 impl<'a> fmt::Display for SummaryView<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let place = self.0;
 
         match (place.subtype.value(), place.name.is_some()) {
-            (Some(subtype), true) => write!(f, "{} ({})", place.display_name(), subtype),
-            (Some(subtype), false) => write!(f, "{} {}", subtype.get_emoji(), subtype),
-            (None, true) => write!(f, "{} (place)", place.display_name()),
+            (Some(subtype), true) => {
+                fmt::Display::fmt(&place.display_name(), f)?;
+                write!(f, " ({})", type_name(place, subtype))
+            }
+            (Some(subtype), false) if f.alternate() => write!(f, "{}", type_name(place, subtype)),
+            (Some(subtype), false) => {
+                write!(f, "{} {}", subtype.get_emoji(), type_name(place, subtype),)
+            }
+            (None, true) => {
+                fmt::Display::fmt(&place.display_name(), f)?;
+                write!(f, " (place)")
+            }
+            (None, false) if f.alternate() => write!(f, "place"),
             (None, false) => write!(f, "{} place", PlaceType::Any.get_emoji()),
-        }
+        }?;
+
+        write_statuses(place, f)
     }
 }
 
 impl<'a> fmt::Display for DescriptionView<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if let Some(subtype) = self.0.subtype.value() {
-            write!(f, "{}", subtype)
+        let place = self.0;
+
+        if let Some(wealth) = place.wealth.value() {
+            write!(f, "{} ", wealth)?;
+        }
+
+        match place.scarcity.value() {
+            Some(scarcities) if scarcities.is_empty() => write!(f, "{} ", WELL_SUPPLIED)?,
+            Some(scarcities) => {
+                for scarcity in scarcities {
+                    write!(f, "{} ", scarcity)?;
+                }
+            }
+            None => {}
+        }
+
+        if let Some(subtype) = place.subtype.value() {
+            write!(f, "{}", type_name(place, subtype))
         } else {
             write!(f, "place")
         }
     }
 }
 
+impl<'a> fmt::Display for LocationView<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Self {
+            parent,
+            grandparent,
+        } = self;
+
+        if let Some(grandparent) = grandparent {
+            write_name_link(parent, f)?;
+            write!(f, ", ")?;
+            write_name_link(grandparent, f)
+        } else if parent.name.is_some() {
+            write_name_link(parent, f)?;
+
+            if let Some(subtype) = parent.subtype.value() {
+                write!(f, " ({})", type_name(parent, subtype))
+            } else {
+                write!(f, " (place)")
+            }
+        } else {
+            fmt::Display::fmt(&parent.display_summary(), f)
+        }
+    }
+}
+
 impl<'a> fmt::Display for DetailsView<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let Self { place, relations } = self;
@@ -85,44 +199,109 @@ impl<'a> fmt::Display for DetailsView<'a> {
         place
             .name
             .value()
-            .map(|name| write!(f, "# {}", name))
+            .map(|name| write!(f, "# {}", EscapedView::new(name)))
             .unwrap_or_else(|| write!(f, "# Unnamed {}", place.display_description()))?;
 
         write!(f, "\n*{}*", place.display_description())?;
+        write_statuses(place, f)?;
+
+        place
+            .aliases
+            .value()
+            .filter(|aliases| !aliases.is_empty())
+            .map(|aliases| {
+                let aliases: Vec<String> = aliases
+                    .iter()
+                    .map(|alias| EscapedView::new(alias).to_string())
+                    .collect();
+                write!(f, "\n\n**Also known as:** {}", list(&aliases))
+            })
+            .transpose()?;
 
         relations
             .location
             .as_ref()
             .map(|(parent, grandparent)| {
-                if let Some(grandparent) = grandparent {
-                    write!(
-                        f,
-                        "\n\n**Location:** {}, {}",
-                        parent.display_name(),
-                        grandparent.display_name(),
-                    )
-                } else {
-                    write!(f, "\n\n**Location:** {}", parent.display_summary())
-                }
+                write!(f, "\n\n**Location:** ")?;
+                fmt::Display::fmt(&LocationView::new(parent, grandparent.as_ref()), f)
             })
             .transpose()?;
 
         place
             .description
             .value()
-            .map(|description| write!(f, "\n\n{}", description))
+            .map(|description| write!(f, "\n\n{}", EscapedView::new(description)))
+            .transpose()?;
+
+        place
+            .dungeon
+            .value()
+            .map(|dungeon| write!(f, "\n\n{}", dungeon.display()))
+            .transpose()?;
+
+        place
+            .vehicle
+            .value()
+            .map(|vehicle| write!(f, "\n\n{}", vehicle.display()))
+            .transpose()?;
+
+        place
+            .festivals
+            .value()
+            .filter(|festivals| !festivals.is_empty())
+            .map(|festivals| {
+                write!(
+                    f,
+                    "\n\n**Festivals:** {}",
+                    festivals
+                        .iter()
+                        .map(|festival| festival.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                )
+            })
+            .transpose()?;
+
+        place
+            .worship
+            .value()
+            .map(|deity| write!(f, "\n\n**Worship:** {}", EscapedView::new(deity)))
+            .transpose()?;
+
+        if place.has_economy() {
+            write!(f, "\n\n**Prices:** {}", place.display_economy())?;
+        }
+
+        place
+            .inventory
+            .value()
+            .filter(|inventory| !inventory.is_empty())
+            .map(|inventory| write!(f, "\n\n{}", inventory.display()))
             .transpose()?;
 
+        write_unlocked_fields(place, f)?;
+
         write!(f, "\n\n</div>")?;
 
         Ok(())
     }
 }
 
+/// Journal entries are locked unless the user says otherwise, so only the exceptions are shown.
+fn write_unlocked_fields(place: &Place, f: &mut fmt::Formatter) -> fmt::Result {
+    let unlocked = place.unlocked_fields();
+
+    if place.uuid.is_some() && !unlocked.is_empty() {
+        write!(f, "\n\n**Unlocked:** {}", unlocked.join(", "))?;
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::world::place::PlaceType;
+    use crate::world::place::{PlaceType, Scarcity, Wealth};
 
     #[test]
     fn view_test_empty() {
@@ -343,7 +522,7 @@ I am Mordenkainen.
 # The Prancing Pony
 *inn*
 
-**Location:** 🏘 `Bree` (town)
+**Location:** 🏘 {Bree} (town)
 
 </div>",
             format!("{}", DetailsView::new(&place, relations)),
@@ -379,10 +558,114 @@ I am Mordenkainen.
 # The Prancing Pony
 *inn*
 
-**Location:** 🏘 `Bree`, 👑 `The Shire`
+**Location:** 🏘 {Bree}, 👑 {The Shire}
 
 </div>",
             format!("{}", DetailsView::new(&place, relations)),
         );
     }
+
+    #[test]
+    fn details_view_test_with_economy() {
+        let mut place = Place {
+            name: "Bree".into(),
+            subtype: "town".parse::<PlaceType>().unwrap().into(),
+            wealth: "rich".parse::<Wealth>().unwrap().into(),
+            scarcity: vec!["steel-shortage".parse::<Scarcity>().unwrap()].into(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            "wealthy steel-shortage town",
+            format!("{}", place.display_description()),
+        );
+        assert_eq!(
+            "<div class=\"thing-box place\">
+
+# Bree
+*wealthy steel-shortage town*
+
+**Prices:** +25% (wealthy), +50% on steel goods (steel shortage)
+
+</div>",
+            format!("{}", place.display_details(PlaceRelations::default())),
+        );
+
+        place.wealth = None.into();
+        place.scarcity = Vec::new().into();
+        assert_eq!(
+            "well-supplied town",
+            format!("{}", place.display_description()),
+        );
+        assert_eq!(
+            "<div class=\"thing-box place\">
+
+# Bree
+*well-supplied town*
+
+</div>",
+            format!("{}", place.display_details(PlaceRelations::default())),
+        );
+    }
+
+    #[test]
+    fn details_view_test_with_worship() {
+        let place = Place {
+            name: "The Glowing Altar".into(),
+            subtype: "shrine".parse::<PlaceType>().unwrap().into(),
+            worship: "Aurelos".into(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            "<div class=\"thing-box place\">
+
+# The Glowing Altar
+*shrine*
+
+**Worship:** Aurelos
+
+</div>",
+            format!("{}", place.display_details(PlaceRelations::default())),
+        );
+    }
+
+    #[test]
+    fn view_test_alternate() {
+        let place = Place {
+            name: "The Prancing Pony".into(),
+            subtype: "inn".parse::<PlaceType>().unwrap().into(),
+            ..Default::default()
+        };
+
+        assert_eq!("`The Prancing Pony`", format!("{:#}", place.display_name()));
+        assert_eq!(
+            "`The Prancing Pony` (inn)",
+            format!("{:#}", place.display_summary()),
+        );
+        assert_eq!("place", format!("{:#}", Place::default().display_summary()));
+
+        let relations = PlaceRelations {
+            location: Some((
+                Place {
+                    name: "Bree".into(),
+                    subtype: "town".parse::<PlaceType>().unwrap().into(),
+                    ..Default::default()
+                },
+                None,
+            )),
+        };
+
+        assert_eq!(
+            "<div class=\"thing-box place\">
+
+# The Prancing Pony
+*inn*
+
+**Location:** {Bree} (town)
+
+</div>",
+            format!("{:#}", DetailsView::new(&place, relations)),
+        );
+    }
 }
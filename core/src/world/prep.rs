@@ -0,0 +1,114 @@
+//! A checklist to look over before a session: what happened to the journal lately, what's coming
+//! up in game time, and what hasn't been saved yet.
+
+use super::{upcoming_festivals, LinkView, Thing};
+use crate::app::AppMeta;
+use crate::config::Config;
+use crate::storage::{KeyValue, TimelineEvent};
+use crate::time::{Interval, Time};
+
+/// The number of in-game days looked ahead by `prep` unless otherwise specified.
+pub const DEFAULT_PREP_DAYS: u16 = 7;
+
+/// The number of journal changes listed under "Recently".
+const RECENT_CHANGES_SHOWN: usize = 5;
+
+/// Assemble the session prep summary, looking ahead the given number of in-game days.
+pub async fn prep(days: u16, app_meta: &AppMeta) -> String {
+    let config = Config::load(app_meta).await;
+    let now = app_meta
+        .repository
+        .get_key_value(&KeyValue::Time(None))
+        .await
+        .ok()
+        .and_then(KeyValue::time)
+        .unwrap_or_default();
+
+    let mut output = format!(
+        "# Session prep\n\n_It is currently {}._",
+        config.display_time(&now)
+    );
+
+    output.push_str("\n\n## Recently\n");
+    let timeline = app_meta.repository.timeline().await.unwrap_or_default();
+    let changes: Vec<_> = timeline
+        .iter()
+        .filter(|entry| !matches!(entry.event, TimelineEvent::TimeJump { .. }))
+        .collect();
+
+    if changes.is_empty() {
+        output.push_str("\n*Nothing has been added to or removed from your journal yet.*");
+    } else {
+        changes
+            .iter()
+            .skip(changes.len().saturating_sub(RECENT_CHANGES_SHOWN))
+            .for_each(|entry| output.push_str(&format!("\n* {}", entry.display(&config))));
+    }
+
+    output.push_str(&format!(
+        "\n\n## Coming up in the next {} {}\n",
+        days,
+        if days == 1 { "day" } else { "days" },
+    ));
+    let mut scheduled: Vec<String> = upcoming_festivals(&now, app_meta)
+        .await
+        .iter()
+        .filter(|upcoming| upcoming.days <= days)
+        .map(|upcoming| upcoming.display().to_string())
+        .collect();
+
+    if let Some(until) = now.checked_add(&Interval::new_days(days.into())) {
+        let mut things = app_meta.repository.journal().await.unwrap_or_default();
+        things.extend(app_meta.repository.recent().cloned());
+
+        let mut expiring: Vec<(Time, String)> = things
+            .iter()
+            .filter(|thing| !thing.is_archived())
+            .flat_map(|thing| {
+                match thing {
+                    Thing::Npc(npc) => npc.statuses.value(),
+                    Thing::Place(place) => place.statuses.value(),
+                }
+                .into_iter()
+                .flat_map(|statuses| statuses.iter())
+                .filter_map(|status| status.expires.clone().map(|expires| (expires, status)))
+                .filter(|(expires, _)| expires <= &until)
+                .map(|(expires, status)| {
+                    let notice = format!(
+                        "**{}:** {} will no longer be {}.",
+                        config.display_time(&expires),
+                        LinkView::new(&thing.name().to_string()),
+                        status.name,
+                    );
+                    (expires, notice)
+                })
+                .collect::<Vec<_>>()
+            })
+            .collect();
+
+        expiring.sort_by(|(a, _), (b, _)| a.cmp(b));
+        scheduled.extend(expiring.into_iter().map(|(_, notice)| notice));
+    }
+
+    if scheduled.is_empty() {
+        output.push_str("\n*Nothing is scheduled.*");
+    } else {
+        scheduled
+            .iter()
+            .for_each(|line| output.push_str(&format!("\n* {}", line)));
+    }
+
+    output.push_str("\n\n## Unsaved\n");
+    let unsaved: Vec<&Thing> = app_meta.repository.recent().collect();
+
+    if unsaved.is_empty() {
+        output.push_str("\n*Everything has been saved to your journal.*");
+    } else {
+        unsaved
+            .iter()
+            .for_each(|thing| output.push_str(&format!("\n* {}", config.render_summary(thing))));
+        output.push_str("\n\n_Use `save [name]` to keep any of these in your `journal`._");
+    }
+
+    output
+}
@@ -0,0 +1,245 @@
+//! Picking an existing entry from the journal at random, for the moments when the party runs into
+//! "a merchant" or wanders into "a tavern" and it may as well be one the GM already knows.
+
+use super::grammar::{count, plural, with_article};
+use super::place::PlaceType;
+use super::{weighted_index_from_tuple, Field, ParsedThing, Thing, WorldCommand};
+use crate::app::{AppMeta, CommandAlias, CommandError};
+use crate::config::Config;
+use crate::locale::Message;
+use crate::Uuid;
+use std::collections::HashMap;
+
+/// Pinned entries are the ones the GM is keeping an eye on, so they come up more often.
+const PINNED_WEIGHT: usize = 3;
+
+/// How many places deep to look for something within a place, which also guards against a
+/// place that has somehow ended up inside itself.
+const MAX_DEPTH: usize = 10;
+
+/// Pick an entry from the journal that fits the description given by `template`, optionally
+/// limited to those within the named place. If nothing fits, offer to generate something that
+/// does.
+pub async fn pick_random(
+    template: Thing,
+    location: Option<String>,
+    app_meta: &mut AppMeta,
+) -> Result<String, CommandError> {
+    let config = Config::load(app_meta).await;
+
+    let location = if let Some(name) = location {
+        match app_meta.repository.get_by_name(&name).await {
+            Ok(Thing::Place(place)) => Some(place),
+            Ok(thing) => {
+                return Err(CommandError::unknown_entity(
+                    &thing.name().to_string(),
+                    format!("{} isn't a place.", thing.name()),
+                ))
+            }
+            Err(_) => {
+                return Err(CommandError::unknown_entity(
+                    &name,
+                    Message::NoEntityNamed { name: &name }.localize(config.language),
+                ))
+            }
+        }
+    } else {
+        None
+    };
+
+    let journal = app_meta.repository.journal().await.unwrap_or_default();
+
+    let parents: HashMap<&Uuid, &Uuid> = journal
+        .iter()
+        .filter_map(|thing| Some((thing.uuid()?, thing.location_uuid()?)))
+        .collect();
+
+    // Characters who are dead or retired aren't about to be run into, and the players' own
+    // characters aren't strangers to be picked.
+    let candidates: Vec<(&Thing, usize)> = journal
+        .iter()
+        .filter(|thing| !thing.is_archived() && thing.fate().is_none() && !thing.is_pc())
+        .filter(|thing| matches_template(thing, &template))
+        .filter(|thing| {
+            location.as_ref().map_or(true, |place| {
+                place
+                    .uuid
+                    .as_ref()
+                    .map_or(false, |uuid| is_within(thing, uuid.as_ref(), &parents))
+            })
+        })
+        .map(|thing| (thing, if thing.is_pinned() { PINNED_WEIGHT } else { 1 }))
+        .collect();
+
+    let description = template.display_description().to_string();
+    let within = location
+        .as_ref()
+        .map(|place| format!(" in {}", place.name))
+        .unwrap_or_default();
+
+    if candidates.is_empty() {
+        let mut diff = template;
+
+        if let Some(uuid) = location.as_ref().and_then(|place| place.uuid.clone()) {
+            match &mut diff {
+                Thing::Npc(npc) => npc.location_uuid = uuid.into(),
+                Thing::Place(place) => place.location_uuid = uuid.into(),
+            }
+        }
+
+        app_meta.command_aliases.insert(CommandAlias::literal(
+            "generate",
+            format!("generate {}", with_article(&description)),
+            WorldCommand::Create {
+                thing: Box::new(ParsedThing {
+                    thing: diff,
+                    unknown_words: Vec::new(),
+                    word_count: 0,
+                }),
+            }
+            .into(),
+        ));
+
+        return Err(CommandError::invalid_state(format!(
+            "There are no {}{} in your journal. Use ~generate~ to create one.",
+            plural(&description),
+            within,
+        )));
+    }
+
+    let thing = *weighted_index_from_tuple(&mut app_meta.rng, &candidates);
+    let relations = app_meta
+        .repository
+        .load_relations(thing)
+        .await
+        .unwrap_or_default();
+
+    let note = if candidates.len() == 1 {
+        format!("_The only {}{} in your journal._", description, within)
+    } else {
+        format!(
+            "_Picked at random from {}{} in your journal._",
+            count(candidates.len() as u32, &description),
+            within,
+        )
+    };
+
+    Ok(format!(
+        "{}\n\n{}",
+        config.render(thing.display_details(relations)),
+        note,
+    ))
+}
+
+/// Does the thing fit every part of the description that was given? Anything the description
+/// doesn't mention can be whatever it likes.
+fn matches_template(thing: &Thing, template: &Thing) -> bool {
+    fn field_matches<T: PartialEq>(template: &Field<T>, value: &Field<T>) -> bool {
+        template.value().map_or(true, |t| value.value() == Some(t))
+    }
+
+    match (thing, template) {
+        (Thing::Npc(npc), Thing::Npc(template)) => {
+            field_matches(&template.gender, &npc.gender)
+                && field_matches(&template.age, &npc.age)
+                && field_matches(&template.size, &npc.size)
+                && field_matches(&template.species, &npc.species)
+                && field_matches(&template.ethnicity, &npc.ethnicity)
+                && field_matches(&template.occupation, &npc.occupation)
+        }
+        (Thing::Place(place), Thing::Place(template)) => {
+            (template.subtype.value() == Some(&PlaceType::Any)
+                || field_matches(&template.subtype, &place.subtype))
+                && field_matches(&template.wealth, &place.wealth)
+        }
+        _ => false,
+    }
+}
+
+/// Is the thing somewhere inside the place, however many places deep?
+fn is_within(thing: &Thing, place_uuid: &Uuid, parents: &HashMap<&Uuid, &Uuid>) -> bool {
+    let mut current = thing.location_uuid();
+
+    for _ in 0..MAX_DEPTH {
+        match current {
+            Some(uuid) if uuid == place_uuid => return true,
+            Some(uuid) => current = parents.get(uuid).copied(),
+            None => return false,
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::world::npc::{Gender, Species};
+    use crate::world::{Npc, Place};
+
+    #[test]
+    fn matches_template_test() {
+        let dwarf = Thing::from(Npc {
+            species: Species::Dwarf.into(),
+            gender: Gender::Feminine.into(),
+            ..Default::default()
+        });
+        let inn = Thing::from(Place {
+            subtype: "inn".parse::<PlaceType>().ok().into(),
+            ..Default::default()
+        });
+
+        assert!(matches_template(&dwarf, &Npc::default().into()));
+        assert!(matches_template(
+            &dwarf,
+            &Npc {
+                species: Species::Dwarf.into(),
+                ..Default::default()
+            }
+            .into(),
+        ));
+        assert!(!matches_template(
+            &dwarf,
+            &Npc {
+                species: Species::Elf.into(),
+                ..Default::default()
+            }
+            .into(),
+        ));
+        assert!(!matches_template(&dwarf, &Place::default().into()));
+
+        assert!(matches_template(&inn, &inn));
+        assert!(matches_template(
+            &inn,
+            &Place {
+                subtype: PlaceType::Any.into(),
+                ..Default::default()
+            }
+            .into(),
+        ));
+        assert!(!matches_template(
+            &inn,
+            &Place {
+                subtype: "temple".parse::<PlaceType>().ok().into(),
+                ..Default::default()
+            }
+            .into(),
+        ));
+        assert!(!matches_template(&inn, &Npc::default().into()));
+    }
+
+    #[test]
+    fn is_within_test() {
+        let [town, inn, cellar] = [Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()];
+        let parents: HashMap<&Uuid, &Uuid> = [(&inn, &town), (&cellar, &inn)].into();
+        let npc = Thing::from(Npc {
+            location_uuid: crate::world::PlaceUuid::from(cellar).into(),
+            ..Default::default()
+        });
+
+        assert!(is_within(&npc, &cellar, &parents));
+        assert!(is_within(&npc, &town, &parents));
+        assert!(!is_within(&npc, &Uuid::new_v4(), &parents));
+        assert!(!is_within(&Npc::default().into(), &town, &parents));
+    }
+}
@@ -0,0 +1,174 @@
+//! Rests taken by the whole party at once: time moves on, everyone in the journal recovers their
+//! resources, and anything that was due to happen in the meantime is reported.
+
+use super::grammar::list;
+use super::npc::Rest;
+use super::{festival_notices, status_expiries, Npc, Thing};
+use crate::app::{AppMeta, CommandError};
+use crate::config::Config;
+use crate::storage::{Change, KeyValue};
+use crate::time::{Interval, Time};
+use crate::utils::capitalize;
+use rand::prelude::*;
+
+/// What might stumble across the party's camp during the night.
+const NIGHT_ENCOUNTERS: &[&str] = &[
+    "a pack of hungry wolves",
+    "a band of goblin raiders",
+    "a pair of desperate bandits",
+    "a wandering owlbear",
+    "a shambling zombie",
+    "a will-o'-wisp, bobbing just beyond the firelight",
+    "a giant spider descending from the trees",
+    "a lost traveller begging to share the fire",
+];
+
+/// The chance of a night encounter is one in this many.
+const NIGHT_ENCOUNTER_DIE: u8 = 6;
+
+/// Have the party take a rest, advancing time and restoring the resources of every active
+/// character in the journal. If `encounters` is set, a long rest can be interrupted by a night
+/// encounter, in which case time only advances to the moment of the interruption and nobody
+/// recovers anything.
+///
+/// All of the changes are batched, so a single `undo` reverses the whole rest.
+pub async fn party_rest(
+    rest: Rest,
+    encounters: bool,
+    app_meta: &mut AppMeta,
+) -> Result<String, CommandError> {
+    let config = Config::load(app_meta).await;
+    let now = app_meta
+        .repository
+        .get_key_value(&KeyValue::Time(None))
+        .await
+        .ok()
+        .and_then(KeyValue::time)
+        .unwrap_or_default();
+
+    let mut output = format!("# {} rest\n\n", capitalize(&rest.to_string()));
+
+    let interruption = if encounters && rest == Rest::Long {
+        let roll = app_meta.rng.gen_range(1..=NIGHT_ENCOUNTER_DIE);
+
+        if roll == 1 {
+            let hours = app_meta.rng.gen_range(1..8);
+            let encounter = NIGHT_ENCOUNTERS.choose(&mut app_meta.rng).unwrap();
+
+            output.push_str(&format!(
+                "_Night encounter roll: {} on a d{}._\n\n**{} hours into the rest, the camp is disturbed by {}!** The rest is interrupted, and nobody recovers anything.",
+                roll, NIGHT_ENCOUNTER_DIE, hours, encounter,
+            ));
+
+            Some(Interval::new_hours(hours))
+        } else {
+            output.push_str(&format!(
+                "_Night encounter roll: {} on a d{}. The night passes without incident._\n\n",
+                roll, NIGHT_ENCOUNTER_DIE,
+            ));
+
+            None
+        }
+    } else {
+        None
+    };
+
+    let time = now
+        .checked_add(interruption.as_ref().unwrap_or(&rest.interval()))
+        .ok_or_else(|| "Unable to advance time.".to_string())?;
+
+    let (rested, mut notices) =
+        rest_characters(rest, interruption.is_none(), &time, app_meta).await?;
+
+    if interruption.is_none() {
+        output.push_str(&format!(
+            "The party finishes a {} rest. It is now {}.",
+            rest,
+            config.display_time(&time),
+        ));
+
+        if !rested.is_empty() {
+            output.push_str(&format!("\n\n{} recovered their resources.", list(&rested)));
+        }
+    } else {
+        output.push_str(&format!(" It is now {}.", config.display_time(&time)));
+    }
+
+    if time.days() != now.days() {
+        notices.extend(festival_notices(&time, app_meta).await);
+    }
+
+    if !notices.is_empty() {
+        output.push_str("\n\n");
+        output.push_str(&notices.join("\\\n"));
+    }
+
+    output.push_str("\n\n_Use `undo` to reverse this._");
+
+    Ok(output)
+}
+
+/// Expire statuses, move time on, and (unless the rest was interrupted) restore resources,
+/// returning the names of the characters who recovered something and any status notices.
+async fn rest_characters(
+    rest: Rest,
+    recover: bool,
+    time: &Time,
+    app_meta: &mut AppMeta,
+) -> Result<(Vec<String>, Vec<String>), CommandError> {
+    // Statuses are expired before the time is changed, as with `+[time]`.
+    let (mut changes, notices) = status_expiries(time, app_meta).await;
+
+    changes.push(Change::SetKeyValue {
+        key_value: KeyValue::Time(Some(time.clone())),
+    });
+
+    let mut rested = Vec::new();
+
+    if recover {
+        let journal = app_meta.repository.journal().await.unwrap_or_default();
+
+        for thing in journal {
+            let Thing::Npc(npc) = &thing else {
+                continue;
+            };
+
+            if thing.is_archived() || thing.fate().is_some() {
+                continue;
+            }
+
+            let Some(resources) = npc.resources.value() else {
+                continue;
+            };
+
+            let mut recovered = resources.clone();
+            recovered.rest(rest);
+
+            if &recovered == resources {
+                continue;
+            }
+
+            changes.push(Change::Edit {
+                name: thing.name().to_string(),
+                uuid: thing.uuid().cloned(),
+                diff: Npc {
+                    resources: recovered.into(),
+                    ..Default::default()
+                }
+                .into(),
+            });
+
+            rested.push(format!("`{}`", thing.name()));
+        }
+    }
+
+    // The time, statuses, and resources change together, so that `undo` reverses them all at
+    // once and none of them change if any can't be saved.
+    app_meta
+        .repository
+        .transaction(changes)
+        .await
+        .map_err(|_| "Unable to advance time.".to_string())?;
+
+    Ok((rested, notices))
+}
@@ -0,0 +1,387 @@
+//! Tavern gossip about the characters and places in the journal, so that rumors tie into the
+//! campaign rather than being about strangers.
+
+use super::grammar::render;
+use super::npc::{Attitude, Gender};
+use super::{Demographics, Generate, Npc, Place, Thing};
+use crate::time::TimeOfDay;
+use crate::utils::capitalize;
+use rand::prelude::*;
+
+/// Stand-ins for the places and people a rumor mentions when the journal has none to offer.
+const PLACES: &[&str] = &[
+    "the old mill",
+    "the docks",
+    "the graveyard",
+    "the ruined watchtower",
+    "the temple",
+    "the crossroads",
+];
+
+const STRANGERS: &[&str] = &[
+    "a stranger in a grey cloak",
+    "a travelling tinker",
+    "one of the town guard",
+    "a half-drunk sailor",
+];
+
+const NPC_RUMORS: &[&str] = &[
+    "{name} has been seen slipping out of {place} after dark. Nobody knows what {they} {is|are} up to.",
+    "{name} owes money to half the town, if you believe the talk around {place}.",
+    "I heard {name} isn't who {they} {claims|claim} to be.",
+    "{name} was asking all sorts of questions about {place}. Wanted to know who comes and goes.",
+    "They say {name} came into some money recently. Paid for a round with a gold piece and didn't wait for change.",
+    "{name} had a shouting match with {other} the other day. Something about an old debt.",
+];
+
+const OCCUPATION_RUMORS: &[&str] = &[
+    "For {a} {occupation}, {name} keeps some strange hours.",
+    "{name} wasn't always {a} {occupation}, you know. Ask {them} what {they} did before.",
+];
+
+const STATUS_RUMORS: &[&str] = &["Have you heard? {name} is {status}, or so they say."];
+
+const HOSTILE_RUMORS: &[&str] = &[
+    "Watch yourselves around {name}. {They} {has|have} been saying nasty things about your lot.",
+];
+
+const FRIENDLY_RUMORS: &[&str] =
+    &["{name} speaks well of you, you know. Says you're the sort who gets things done."];
+
+const PLACE_RUMORS: &[&str] = &[
+    "Strange lights have been seen around {name} these past few nights.",
+    "The cellar under {name} goes deeper than it ought to, if you ask me.",
+    "Someone has been asking after {name}. Paying for answers in old coin, too.",
+    "{other} was asking for directions to {name}. Didn't look like the friendly sort.",
+];
+
+const DUNGEON_RUMORS: &[&str] = &[
+    "Treasure hunters went down into {name} a tenday ago. Only one came back, raving about {boss}.",
+    "Folk say {boss} has made its lair in the depths of {name}.",
+];
+
+const FESTIVAL_RUMORS: &[&str] = &[
+    "Everyone in {name} is already talking about {festival}. Should be quite the party this year.",
+];
+
+const VEHICLE_RUMORS: &[&str] =
+    &["The crew of {name} have been spending freely. Wherever they've been, it paid well."];
+
+/// What the teller says to lead into the rumor, depending on how busy the taproom is likely to be.
+fn openers(time_of_day: TimeOfDay) -> &'static [&'static str] {
+    match time_of_day {
+        TimeOfDay::Night => &[
+            "Keep your voice down, there aren't many of us left awake at this hour.",
+            "Pull up a stool. It's late enough now that nobody will overhear.",
+        ],
+        TimeOfDay::Morning => &["Bit early for gossip, but this one won't keep."],
+        TimeOfDay::Midday => &["With the midday crowd in, you hear all sorts."],
+        TimeOfDay::Afternoon => &["Slow afternoon, so here's something to pass the time."],
+        TimeOfDay::Evening => &["The evening crowd's been talking about nothing else."],
+    }
+}
+
+/// Generate a rumor about `about`, or else about a random entry in the journal. If the journal is
+/// empty, the rumor concerns a character or place invented on the spot.
+///
+/// Journal entries are named in backticks so that they can be looked up from the output. Half of
+/// the time, the rumor is led into with a remark suited to the time of day.
+pub fn generate(
+    rng: &mut impl Rng,
+    demographics: &Demographics,
+    journal: &[Thing],
+    about: Option<&Thing>,
+    time_of_day: TimeOfDay,
+) -> String {
+    let rumor = match about.or_else(|| journal.choose(rng)) {
+        Some(Thing::Npc(npc)) => npc_rumor(rng, npc, true, journal),
+        Some(Thing::Place(place)) => place_rumor(rng, place, true, journal),
+        None if rng.gen_bool(0.5) => {
+            let npc = Npc::generate(rng, demographics);
+            npc_rumor(rng, &npc, false, journal)
+        }
+        None => place_rumor(rng, &Place::default(), false, journal),
+    };
+
+    if rng.gen_bool(0.5) {
+        format!("{} {}", openers(time_of_day).choose(rng).unwrap(), rumor)
+    } else {
+        rumor
+    }
+}
+
+fn npc_rumor(rng: &mut impl Rng, npc: &Npc, in_journal: bool, journal: &[Thing]) -> String {
+    let name = display_name(npc.name.value().map(String::as_str), in_journal, rng);
+    let place = other_place(rng, journal);
+    let other = other_npc(rng, journal, npc.name.value());
+    let occupation = npc.occupation.value().map(|o| o.to_string());
+    let status = npc
+        .statuses
+        .value()
+        .and_then(|statuses| statuses.iter().collect::<Vec<_>>().choose(rng).copied())
+        .map(|status| status.name.to_lowercase());
+
+    let mut templates = NPC_RUMORS.to_vec();
+
+    if occupation.is_some() {
+        templates.extend_from_slice(OCCUPATION_RUMORS);
+    }
+
+    if status.is_some() {
+        templates.extend_from_slice(STATUS_RUMORS);
+    }
+
+    match npc.attitude.value() {
+        Some(Attitude::Hostile | Attitude::Unfriendly) => {
+            templates.extend_from_slice(HOSTILE_RUMORS)
+        }
+        Some(Attitude::Friendly | Attitude::Helpful) => {
+            templates.extend_from_slice(FRIENDLY_RUMORS)
+        }
+        _ => {}
+    }
+
+    capitalize(&render(
+        templates.choose(rng).unwrap(),
+        &npc.gender(),
+        &[
+            ("name", &name),
+            ("place", &place),
+            ("other", &other),
+            ("occupation", occupation.as_deref().unwrap_or_default()),
+            ("status", status.as_deref().unwrap_or_default()),
+        ],
+    ))
+}
+
+fn place_rumor(rng: &mut impl Rng, place: &Place, in_journal: bool, journal: &[Thing]) -> String {
+    let name = if in_journal {
+        display_name(place.name.value().map(String::as_str), true, rng)
+    } else {
+        other_place(rng, &[])
+    };
+    let other = other_npc(rng, journal, None);
+    let boss = place.dungeon.value().and_then(|dungeon| {
+        dungeon
+            .rooms
+            .iter()
+            .find(|room| room.boss)
+            .and_then(|room| room.inhabitants.clone())
+    });
+    let festival = place
+        .festivals
+        .value()
+        .and_then(|festivals| festivals.choose(rng))
+        .map(|festival| festival.name.clone());
+
+    let mut templates = PLACE_RUMORS.to_vec();
+
+    if boss.is_some() {
+        templates.extend_from_slice(DUNGEON_RUMORS);
+    }
+
+    if festival.is_some() {
+        templates.extend_from_slice(FESTIVAL_RUMORS);
+    }
+
+    if place.vehicle.is_some() {
+        templates.extend_from_slice(VEHICLE_RUMORS);
+    }
+
+    capitalize(&render(
+        templates.choose(rng).unwrap(),
+        &Gender::Neuter,
+        &[
+            ("name", &name),
+            ("other", &other),
+            ("boss", boss.as_deref().unwrap_or_default()),
+            ("festival", festival.as_deref().unwrap_or_default()),
+        ],
+    ))
+}
+
+fn display_name(name: Option<&str>, in_journal: bool, rng: &mut impl Rng) -> String {
+    match name {
+        Some(name) if in_journal => format!("`{}`", name),
+        Some(name) => name.to_string(),
+        None => STRANGERS.choose(rng).unwrap().to_string(),
+    }
+}
+
+/// A place from the journal to mention in passing, or a generic one if there are none.
+pub(super) fn other_place(rng: &mut impl Rng, journal: &[Thing]) -> String {
+    let places: Vec<&String> = journal
+        .iter()
+        .filter_map(|thing| match thing {
+            Thing::Place(place) => place.name.value(),
+            Thing::Npc(_) => None,
+        })
+        .collect();
+
+    places
+        .choose(rng)
+        .map(|name| format!("`{}`", name))
+        .unwrap_or_else(|| PLACES.choose(rng).unwrap().to_string())
+}
+
+/// A character from the journal to mention in passing, or a stranger if there are none.
+pub(super) fn other_npc(rng: &mut impl Rng, journal: &[Thing], except: Option<&String>) -> String {
+    let npcs: Vec<&String> = journal
+        .iter()
+        .filter_map(|thing| match thing {
+            Thing::Npc(npc) => npc.name.value(),
+            Thing::Place(_) => None,
+        })
+        .filter(|name| Some(*name) != except)
+        .collect();
+
+    npcs.choose(rng)
+        .map(|name| format!("`{}`", name))
+        .unwrap_or_else(|| STRANGERS.choose(rng).unwrap().to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::world::place::Dungeon;
+    use crate::world::status::Status;
+    use crate::world::Statuses;
+
+    #[test]
+    fn generate_npc_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let journal = [
+            Thing::Npc(Npc {
+                name: "Potato Johnson".into(),
+                gender: Gender::Feminine.into(),
+                ..Default::default()
+            }),
+            Thing::Place(Place {
+                name: "The Prancing Pony".into(),
+                ..Default::default()
+            }),
+        ];
+
+        for _ in 0..50 {
+            let rumor = generate(
+                &mut rng,
+                &Demographics::default(),
+                &journal,
+                Some(&journal[0]),
+                TimeOfDay::Morning,
+            );
+            assert!(rumor.contains("`Potato Johnson`"), "{}", rumor);
+            assert!(!rumor.contains('{'), "{}", rumor);
+            assert!(!rumor.contains("the old mill"), "{}", rumor);
+        }
+    }
+
+    #[test]
+    fn generate_place_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let dungeon = Dungeon::generate(&mut rng);
+        let boss = dungeon
+            .rooms
+            .iter()
+            .find(|room| room.boss)
+            .and_then(|room| room.inhabitants.clone())
+            .unwrap();
+        let place = Thing::Place(Place {
+            name: "The Sunken Vault".into(),
+            dungeon: dungeon.into(),
+            ..Default::default()
+        });
+
+        let rumors: Vec<String> = (0..50)
+            .map(|_| {
+                generate(
+                    &mut rng,
+                    &Demographics::default(),
+                    &[],
+                    Some(&place),
+                    TimeOfDay::Morning,
+                )
+            })
+            .collect();
+
+        assert!(rumors
+            .iter()
+            .all(|rumor| rumor.contains("`The Sunken Vault`")));
+        assert!(rumors.iter().any(|rumor| rumor.contains(&boss)));
+    }
+
+    #[test]
+    fn generate_empty_journal_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        for _ in 0..50 {
+            let rumor = generate(
+                &mut rng,
+                &Demographics::default(),
+                &[],
+                None,
+                TimeOfDay::Evening,
+            );
+            assert!(!rumor.is_empty());
+            assert!(!rumor.contains('`'), "{}", rumor);
+            assert!(!rumor.contains('{'), "{}", rumor);
+        }
+    }
+
+    #[test]
+    fn generate_status_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut statuses = Statuses::default();
+        statuses.add(Status {
+            name: "Poisoned".to_string(),
+            expires: None,
+        });
+        let npc = Thing::Npc(Npc {
+            name: "Potato Johnson".into(),
+            attitude: Attitude::Hostile.into(),
+            statuses: statuses.into(),
+            ..Default::default()
+        });
+
+        let rumors: Vec<String> = (0..100)
+            .map(|_| {
+                generate(
+                    &mut rng,
+                    &Demographics::default(),
+                    &[],
+                    Some(&npc),
+                    TimeOfDay::Morning,
+                )
+            })
+            .collect();
+
+        assert!(rumors
+            .iter()
+            .any(|rumor| rumor.contains("`Potato Johnson` is poisoned")));
+        assert!(rumors.iter().any(|rumor| rumor.contains("nasty things")));
+    }
+
+    #[test]
+    fn generate_time_of_day_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        let rumors: Vec<String> = (0..50)
+            .map(|_| {
+                generate(
+                    &mut rng,
+                    &Demographics::default(),
+                    &[],
+                    None,
+                    TimeOfDay::Night,
+                )
+            })
+            .collect();
+
+        assert!(rumors.iter().any(|rumor| openers(TimeOfDay::Night)
+            .iter()
+            .any(|o| rumor.starts_with(o))));
+        assert!(rumors.iter().all(|rumor| !openers(TimeOfDay::Midday)
+            .iter()
+            .any(|o| rumor.starts_with(o))));
+    }
+}
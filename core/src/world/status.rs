@@ -0,0 +1,286 @@
+use super::{Npc, Place, Thing};
+use crate::app::AppMeta;
+use crate::storage::Change;
+use crate::time::Time;
+use crate::utils::CaseInsensitiveStr;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Transient conditions affecting a character or place, such as "poisoned" or "on fire", which
+/// are kept apart from its permanent description.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Statuses(Vec<Status>);
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Status {
+    pub name: String,
+
+    /// The game time at which the status wears off, if it doesn't last indefinitely.
+    pub expires: Option<Time>,
+}
+
+/// Statuses that are understood without a duration: the SRD conditions along with other common
+/// states of being. Anything else must be given a duration, eg. "Gottfried is hunted for 3 days",
+/// so that a mistyped description isn't taken for a status.
+const KNOWN_STATUSES: &[&str] = &[
+    "asleep",
+    "bleeding",
+    "blessed",
+    "blinded",
+    "burning",
+    "charmed",
+    "concentrating",
+    "cursed",
+    "deafened",
+    "diseased",
+    "disguised",
+    "drunk",
+    "exhausted",
+    "flooded",
+    "frightened",
+    "grappled",
+    "hasted",
+    "hidden",
+    "incapacitated",
+    "invisible",
+    "on fire",
+    "paralyzed",
+    "petrified",
+    "poisoned",
+    "prone",
+    "raging",
+    "restrained",
+    "slowed",
+    "stunned",
+    "surprised",
+    "unconscious",
+    "under siege",
+    "wounded",
+];
+
+/// The names of the statuses in brackets, eg. "[poisoned] [on fire]".
+pub struct StatusesView<'a>(&'a Statuses);
+
+impl Status {
+    /// Whether the name is one of the statuses understood without a duration.
+    pub fn is_known(name: &str) -> bool {
+        name.in_ci(KNOWN_STATUSES)
+    }
+}
+
+impl Statuses {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Status> {
+        self.0.iter()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Status> {
+        self.0.iter().find(|status| status.name.eq_ci(name))
+    }
+
+    /// Add a status, replacing any existing status of the same name.
+    pub fn add(&mut self, status: Status) {
+        self.remove(&status.name);
+        self.0.push(status);
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<Status> {
+        self.0
+            .iter()
+            .position(|status| status.name.eq_ci(name))
+            .map(|i| self.0.remove(i))
+    }
+
+    /// Remove and return any statuses that have worn off by the given time.
+    pub fn expire(&mut self, now: &Time) -> Vec<Status> {
+        let (expired, remaining) = self
+            .0
+            .drain(..)
+            .partition(|status| matches!(&status.expires, Some(expires) if expires <= now));
+
+        self.0 = remaining;
+        expired
+    }
+
+    pub fn display(&self) -> StatusesView {
+        StatusesView(self)
+    }
+}
+
+impl<'a> fmt::Display for StatusesView<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, status) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+
+            write!(f, "[{}]", status.name)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Remove any statuses that have worn off by the given time from the characters and places in the
+/// journal and recent entries, returning a notice for each one.
+pub async fn expire_statuses(now: &Time, app_meta: &mut AppMeta) -> Vec<String> {
+    let mut notices = Vec::new();
+
+    for (change, expired) in expiries(now, app_meta).await {
+        if app_meta.repository.modify(change).await.is_ok() {
+            notices.extend(expired);
+        }
+    }
+
+    notices
+}
+
+/// The changes that would remove any statuses worn off by the given time, with a notice for each
+/// one, so that they can be made in a [`Repository::transaction`] with other changes.
+///
+/// [`Repository::transaction`]: crate::storage::Repository::transaction
+pub async fn status_expiries(now: &Time, app_meta: &AppMeta) -> (Vec<Change>, Vec<String>) {
+    let mut changes = Vec::new();
+    let mut notices = Vec::new();
+
+    for (change, expired) in expiries(now, app_meta).await {
+        changes.push(change);
+        notices.extend(expired);
+    }
+
+    (changes, notices)
+}
+
+/// An edit for each thing with statuses that have worn off by the given time, along with a notice
+/// for each status.
+async fn expiries(now: &Time, app_meta: &AppMeta) -> Vec<(Change, Vec<String>)> {
+    let mut things = app_meta.repository.journal().await.unwrap_or_default();
+    things.extend(app_meta.repository.recent().cloned());
+
+    let mut expiries = Vec::new();
+
+    for thing in things {
+        let mut statuses = match &thing {
+            Thing::Npc(npc) => npc.statuses.value(),
+            Thing::Place(place) => place.statuses.value(),
+        }
+        .cloned()
+        .unwrap_or_default();
+
+        let expired = statuses.expire(now);
+
+        if expired.is_empty() {
+            continue;
+        }
+
+        let diff = match &thing {
+            Thing::Npc(_) => Npc {
+                statuses: statuses.into(),
+                ..Default::default()
+            }
+            .into(),
+            Thing::Place(_) => Place {
+                statuses: statuses.into(),
+                ..Default::default()
+            }
+            .into(),
+        };
+
+        expiries.push((
+            Change::edit(&thing, diff),
+            expired
+                .iter()
+                .map(|status| format!("_{} is no longer {}._", thing.name(), status.name))
+                .collect(),
+        ));
+    }
+
+    expiries
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn add_remove_test() {
+        let mut statuses = Statuses::default();
+        assert!(statuses.is_empty());
+
+        statuses.add(status("poisoned", None));
+        statuses.add(status("on fire", None));
+        statuses.add(status("Poisoned", Some(time(2))));
+
+        assert_eq!(
+            vec![&status("on fire", None), &status("Poisoned", Some(time(2)))],
+            statuses.iter().collect::<Vec<_>>(),
+        );
+        assert_eq!(
+            Some(&status("Poisoned", Some(time(2)))),
+            statuses.get("POISONED"),
+        );
+
+        assert_eq!(Some(status("on fire", None)), statuses.remove("On Fire"));
+        assert_eq!(None, statuses.remove("on fire"));
+        assert_eq!(1, statuses.iter().count());
+    }
+
+    #[test]
+    fn expire_test() {
+        let mut statuses = Statuses::default();
+        statuses.add(status("poisoned", Some(time(2))));
+        statuses.add(status("on fire", None));
+        statuses.add(status("asleep", Some(time(3))));
+
+        assert!(statuses.expire(&time(1)).is_empty());
+        assert_eq!(
+            vec![status("poisoned", Some(time(2)))],
+            statuses.expire(&time(2)),
+        );
+        assert_eq!(
+            vec![status("asleep", Some(time(3)))],
+            statuses.expire(&time(4)),
+        );
+        assert_eq!(
+            vec![&status("on fire", None)],
+            statuses.iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn display_test() {
+        let mut statuses = Statuses::default();
+        assert_eq!("", statuses.display().to_string());
+
+        statuses.add(status("poisoned", Some(time(2))));
+        statuses.add(status("on fire", None));
+        assert_eq!("[poisoned] [on fire]", statuses.display().to_string());
+    }
+
+    #[test]
+    fn serialize_deserialize_test() {
+        let mut statuses = Statuses::default();
+        statuses.add(status("poisoned", Some(time(2))));
+        statuses.add(status("on fire", None));
+
+        let serialized =
+            r#"[{"name":"poisoned","expires":"2:00:00:00"},{"name":"on fire","expires":null}]"#;
+
+        assert_eq!(serialized, serde_json::to_string(&statuses).unwrap());
+        assert_eq!(statuses, serde_json::from_str(serialized).unwrap());
+    }
+
+    fn status(name: &str, expires: Option<Time>) -> Status {
+        Status {
+            name: name.to_string(),
+            expires,
+        }
+    }
+
+    fn time(days: i32) -> Time {
+        Time::try_new(days, 0, 0, 0).unwrap()
+    }
+}
@@ -0,0 +1,220 @@
+//! Material for improvising a conversation with a character: how they greet the party, what
+//! they're after, and a piece of gossip they can pass on, all drawn from the journal.
+
+use super::grammar::render;
+use super::npc::{Attitude, Demeanor};
+use super::rumor::{self, other_npc, other_place};
+use super::{Demographics, Npc, Thing};
+use crate::time::TimeOfDay;
+use rand::prelude::*;
+
+const WANTS: &[&str] = &[
+    "to settle an old debt with {other}",
+    "someone to find out what's really going on at {place}",
+    "to get away from {place} for a while, and quietly",
+    "news of {other}, who hasn't been seen for days",
+    "a letter delivered to {other}, no questions asked",
+    "to make {their} fortune, whatever it takes",
+    "a quiet drink and no trouble",
+];
+
+const HOSTILE_WANTS: &[&str] = &["the party gone, one way or another"];
+
+const FRIENDLY_WANTS: &[&str] =
+    &["to help the party however {they} can, for a small favour in return"];
+
+const STATUS_WANTS: &[&str] = &["to no longer be {status}"];
+
+fn greetings(attitude: Attitude) -> &'static [&'static str] {
+    match attitude {
+        Attitude::Hostile => &[
+            "You've got some nerve showing your faces here.",
+            "What do you want? Make it quick, before I call the guard.",
+        ],
+        Attitude::Unfriendly => &[
+            "Hmph. Can't say I'm glad to see you.",
+            "I suppose you'll be wanting something.",
+        ],
+        Attitude::Indifferent => &["Yes? What is it?", "Can I help you?"],
+        Attitude::Friendly => &[
+            "Well met, friends! Good to see you again.",
+            "Ah, it's you lot. Come, sit down.",
+        ],
+        Attitude::Helpful => &[
+            "My friends! Anything you need, just say the word.",
+            "Thank the gods you're here. What can I do for you?",
+        ],
+    }
+}
+
+fn manner(demeanor: &Demeanor) -> &'static str {
+    match demeanor {
+        Demeanor::Arrogant => "haughtily",
+        Demeanor::Cheerful => "cheerfully",
+        Demeanor::Curious => "with interest",
+        Demeanor::Grumpy => "grumpily",
+        Demeanor::Jovial => "with a hearty laugh",
+        Demeanor::Lazy => "without getting up",
+        Demeanor::Nervous => "nervously",
+        Demeanor::Pious => "with a blessing",
+        Demeanor::Shy => "quietly",
+        Demeanor::Stern => "sternly",
+        Demeanor::Suspicious => "warily",
+    }
+}
+
+/// Generate a dialogue hook for `npc`. What they want and know is drawn preferably from the
+/// entries `connected` to them, such as their location and the others there, and otherwise from
+/// the rest of the `journal`. Neither should include the character themself.
+pub fn generate(
+    rng: &mut impl Rng,
+    demographics: &Demographics,
+    npc: &Npc,
+    connected: &[Thing],
+    journal: &[Thing],
+    time_of_day: TimeOfDay,
+) -> String {
+    let attitude = npc
+        .attitude
+        .value()
+        .copied()
+        .unwrap_or(Attitude::Indifferent);
+    let nearby = if connected.is_empty() {
+        journal
+    } else {
+        connected
+    };
+
+    let greeting = greetings(attitude).choose(rng).unwrap();
+    let manner = npc.demeanor.value().map(manner);
+
+    let status = npc
+        .statuses
+        .value()
+        .and_then(|statuses| statuses.iter().collect::<Vec<_>>().choose(rng).copied())
+        .map(|status| status.name.to_lowercase());
+
+    let mut wants = WANTS.to_vec();
+
+    match attitude {
+        Attitude::Hostile => wants.extend_from_slice(HOSTILE_WANTS),
+        Attitude::Helpful => wants.extend_from_slice(FRIENDLY_WANTS),
+        _ => {}
+    }
+
+    if status.is_some() {
+        wants.extend_from_slice(STATUS_WANTS);
+    }
+
+    let wants = render(
+        wants.choose(rng).unwrap(),
+        &npc.gender(),
+        &[
+            ("other", &other_npc(rng, nearby, npc.name.value())),
+            ("place", &other_place(rng, nearby)),
+            ("status", status.as_deref().unwrap_or_default()),
+        ],
+    );
+
+    let subject = connected.choose(rng);
+    let knows = rumor::generate(rng, demographics, journal, subject, time_of_day);
+
+    format!(
+        "**Greets the party{}:** \"{}\"\\\n**Wants:** {}\\\n**Knows:** \"{}\"",
+        manner
+            .map(|manner| format!(" {}", manner))
+            .unwrap_or_default(),
+        greeting,
+        wants,
+        knows,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::world::npc::Gender;
+    use crate::world::Place;
+
+    #[test]
+    fn generate_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let npc = Npc {
+            name: "Gottfried".into(),
+            gender: Gender::Masculine.into(),
+            attitude: Attitude::Hostile.into(),
+            demeanor: Demeanor::Suspicious.into(),
+            ..Default::default()
+        };
+        let connected = [Thing::Place(Place {
+            name: "The Prancing Pony".into(),
+            ..Default::default()
+        })];
+        let journal = [
+            connected[0].clone(),
+            Thing::Npc(Npc {
+                name: "Potato Johnson".into(),
+                ..Default::default()
+            }),
+        ];
+
+        for _ in 0..50 {
+            let hook = generate(
+                &mut rng,
+                &Demographics::default(),
+                &npc,
+                &connected,
+                &journal,
+                TimeOfDay::Evening,
+            );
+
+            assert!(
+                hook.starts_with("**Greets the party warily:** \""),
+                "{}",
+                hook,
+            );
+            assert!(
+                greetings(Attitude::Hostile)
+                    .iter()
+                    .any(|greeting| hook.contains(greeting)),
+                "{}",
+                hook,
+            );
+            assert!(hook.contains("\"\\\n**Wants:** "), "{}", hook);
+            assert!(hook.contains("`The Prancing Pony`"), "{}", hook);
+            assert!(!hook.contains("`Gottfried`"), "{}", hook);
+            assert!(!hook.contains('{'), "{}", hook);
+        }
+    }
+
+    #[test]
+    fn generate_empty_journal_test() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let npc = Npc {
+            name: "Gottfried".into(),
+            ..Default::default()
+        };
+
+        for _ in 0..50 {
+            let hook = generate(
+                &mut rng,
+                &Demographics::default(),
+                &npc,
+                &[],
+                &[],
+                TimeOfDay::Morning,
+            );
+
+            assert!(hook.starts_with("**Greets the party:** \""), "{}", hook);
+            assert!(
+                greetings(Attitude::Indifferent)
+                    .iter()
+                    .any(|greeting| hook.contains(greeting)),
+                "{}",
+                hook,
+            );
+            assert!(!hook.contains('`'), "{}", hook);
+            assert!(!hook.contains('{'), "{}", hook);
+        }
+    }
+}
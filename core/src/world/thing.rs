@@ -1,7 +1,8 @@
 use super::{Demographics, Field, Generate, Npc, NpcRelations, Place, PlaceRelations};
+use crate::storage::{name_starts_with, names_match};
 use crate::world::command::ParsedThing;
-use crate::world::npc::{DetailsView as NpcDetailsView, Gender};
-use crate::world::place::DetailsView as PlaceDetailsView;
+use crate::world::npc::{DetailsView as NpcDetailsView, Fate, Gender};
+use crate::world::place::{DetailsView as PlaceDetailsView, PlaceType};
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
@@ -41,6 +42,33 @@ impl Thing {
         }
     }
 
+    /// The other names the thing goes by, if any.
+    pub fn aliases(&self) -> &[String] {
+        match self {
+            Thing::Place(place) => place.aliases.value(),
+            Thing::Npc(npc) => npc.aliases.value(),
+        }
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+    }
+
+    /// Does the thing go by the given name, either as its name or one of its aliases?
+    pub fn is_named(&self, name: &str) -> bool {
+        self.name().value().map_or(false, |s| names_match(s, name))
+            || self.aliases().iter().any(|alias| names_match(alias, name))
+    }
+
+    /// Does the thing's name, or one of its aliases, start with the given prefix?
+    pub fn is_named_starting_with(&self, prefix: &str) -> bool {
+        self.name()
+            .value()
+            .map_or(false, |s| name_starts_with(s, prefix))
+            || self
+                .aliases()
+                .iter()
+                .any(|alias| name_starts_with(alias, prefix))
+    }
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Thing::Place(..) => "place",
@@ -55,6 +83,43 @@ impl Thing {
         }
     }
 
+    /// The UUID of the place where the thing is located, if any.
+    pub fn location_uuid(&self) -> Option<&Uuid> {
+        match self {
+            Thing::Place(place) => place.location_uuid.value().map(|u| u.as_ref()),
+            Thing::Npc(npc) => npc.location_uuid.value().map(|u| u.as_ref()),
+        }
+    }
+
+    /// Has the thing been archived, setting it aside from the journal without deleting it?
+    pub fn is_archived(&self) -> bool {
+        match self {
+            Thing::Place(place) => place.archived.value() == Some(&true),
+            Thing::Npc(npc) => npc.archived.value() == Some(&true),
+        }
+    }
+
+    /// Has the thing been pinned to the top of the journal?
+    pub fn is_pinned(&self) -> bool {
+        match self {
+            Thing::Place(place) => place.pinned.value() == Some(&true),
+            Thing::Npc(npc) => npc.pinned.value() == Some(&true),
+        }
+    }
+
+    /// What has become of the thing, if it's a character who is no longer active.
+    pub fn fate(&self) -> Option<&Fate> {
+        match self {
+            Thing::Place(_) => None,
+            Thing::Npc(npc) => npc.fate.value(),
+        }
+    }
+
+    /// Is the thing a player character rather than an NPC?
+    pub fn is_pc(&self) -> bool {
+        matches!(self, Thing::Npc(npc) if npc.pc.is_some())
+    }
+
     pub fn set_uuid(&mut self, uuid: Uuid) {
         match self {
             Thing::Place(place) => {
@@ -88,6 +153,15 @@ impl Thing {
         }
     }
 
+    /// The emoji shown beside the thing in summaries: a person or species icon for characters
+    /// (see [`Npc::get_emoji`]), or an icon for the type of place.
+    pub fn get_emoji(&self, by_species: bool) -> &'static str {
+        match self {
+            Self::Npc(npc) => npc.get_emoji(by_species),
+            Self::Place(place) => place.subtype.value().unwrap_or(&PlaceType::Any).get_emoji(),
+        }
+    }
+
     pub fn place(&self) -> Option<&Place> {
         if let Self::Place(place) = self {
             Some(place)
@@ -96,11 +170,11 @@ impl Thing {
         }
     }
 
-    pub fn into_place(self) -> Result<Place, Thing> {
+    pub fn into_place(self) -> Result<Place, Box<Thing>> {
         if let Self::Place(place) = self {
             Ok(place)
         } else {
-            Err(self)
+            Err(Box::new(self))
         }
     }
 
@@ -112,11 +186,11 @@ impl Thing {
         }
     }
 
-    pub fn into_npc(self) -> Result<Npc, Thing> {
+    pub fn into_npc(self) -> Result<Npc, Box<Thing>> {
         if let Self::Npc(npc) = self {
             Ok(npc)
         } else {
-            Err(self)
+            Err(Box::new(self))
         }
     }
 
@@ -135,6 +209,42 @@ impl Thing {
         }
     }
 
+    /// The fields that can be locked and unlocked by name.
+    pub fn lockable_fields(&self) -> &'static [&'static str] {
+        match self {
+            Self::Npc(_) => Npc::lockable_fields(),
+            Self::Place(_) => Place::lockable_fields(),
+        }
+    }
+
+    pub fn is_field_locked(&self, field: &str) -> Option<bool> {
+        match self {
+            Self::Npc(npc) => npc.is_field_locked(field),
+            Self::Place(place) => place.is_field_locked(field),
+        }
+    }
+
+    pub fn set_field_locked(&mut self, field: &str, locked: bool) -> Option<bool> {
+        match self {
+            Self::Npc(npc) => npc.set_field_locked(field, locked),
+            Self::Place(place) => place.set_field_locked(field, locked),
+        }
+    }
+
+    pub fn display_field(&self, field: &str) -> Option<String> {
+        match self {
+            Self::Npc(npc) => npc.display_field(field),
+            Self::Place(place) => place.display_field(field),
+        }
+    }
+
+    pub fn field_diff(&self, field: &str) -> Option<Thing> {
+        match self {
+            Self::Npc(npc) => npc.field_diff(field).map(Thing::from),
+            Self::Place(place) => place.field_diff(field).map(Thing::from),
+        }
+    }
+
     pub fn lock_all(&mut self) {
         match self {
             Self::Npc(npc) => npc.lock_all(),
@@ -142,6 +252,16 @@ impl Thing {
         }
     }
 
+    /// A diff that merges the fields of a duplicate into this thing, or `None` if the duplicate
+    /// is a different kind of thing.
+    pub fn merge_diff(&self, other: &Self) -> Option<Self> {
+        match (self, other) {
+            (Self::Npc(npc), Self::Npc(other)) => Some(npc.merge_diff(other).into()),
+            (Self::Place(place), Self::Place(other)) => Some(place.merge_diff(other).into()),
+            _ => None,
+        }
+    }
+
     #[allow(clippy::result_unit_err)]
     pub fn try_apply_diff(&mut self, diff: &mut Self) -> Result<(), ()> {
         match (self, diff) {
@@ -212,6 +332,11 @@ impl FromStr for ParsedThing<Thing> {
                 .cmp(&parsed_place.unknown_words.len())
             {
                 Ordering::Less => Ok(parsed_npc.into_thing()),
+                // Some occupations double as businesses (eg. "blacksmith"). If that's the only
+                // thing we know about the NPC, assume that the business was meant.
+                Ordering::Equal if parsed_npc.thing.is_only_occupation() => {
+                    Ok(parsed_place.into_thing())
+                }
                 Ordering::Equal => Err(()),
                 Ordering::Greater => Ok(parsed_place.into_thing()),
             },
@@ -225,8 +350,8 @@ impl FromStr for ParsedThing<Thing> {
 impl<'a> fmt::Display for SummaryView<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self.0 {
-            Thing::Place(l) => write!(f, "{}", l.display_summary()),
-            Thing::Npc(n) => write!(f, "{}", n.display_summary()),
+            Thing::Place(l) => fmt::Display::fmt(&l.display_summary(), f),
+            Thing::Npc(n) => fmt::Display::fmt(&n.display_summary(), f),
         }
     }
 }
@@ -243,8 +368,8 @@ impl<'a> fmt::Display for DescriptionView<'a> {
 impl<'a> fmt::Display for DetailsView<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            DetailsView::Npc(view) => write!(f, "{}", view),
-            DetailsView::Place(view) => write!(f, "{}", view),
+            DetailsView::Npc(view) => fmt::Display::fmt(view, f),
+            DetailsView::Place(view) => fmt::Display::fmt(view, f),
         }
     }
 }
@@ -252,6 +377,7 @@ impl<'a> fmt::Display for DetailsView<'a> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::world::npc::{Age, Species};
 
     #[test]
     fn name_test() {
@@ -274,6 +400,26 @@ mod test {
         }
     }
 
+    #[test]
+    fn is_named_test() {
+        let thing = Thing::from(Place {
+            name: "The Yawning Portal".into(),
+            aliases: vec!["the Portal".to_string()].into(),
+            ..Default::default()
+        });
+
+        assert_eq!(&["the Portal".to_string()][..], thing.aliases());
+        assert!(thing.is_named("the yawning portal"));
+        assert!(thing.is_named("THE PORTAL"));
+        assert!(!thing.is_named("the"));
+
+        assert!(thing.is_named_starting_with("the yawn"));
+        assert!(thing.is_named_starting_with("the por"));
+        assert!(!thing.is_named_starting_with("portal"));
+
+        assert!(Thing::from(Npc::default()).aliases().is_empty());
+    }
+
     #[test]
     fn into_test() {
         assert!(matches!(Place::default().into(), Thing::Place(_)));
@@ -284,7 +430,7 @@ mod test {
     fn serialize_deserialize_test_place() {
         let thing = place();
         assert_eq!(
-            r#"{"type":"Place","uuid":null,"location_uuid":null,"subtype":null,"name":null,"description":null}"#,
+            r#"{"type":"Place","uuid":null,"location_uuid":null,"subtype":null,"name":null,"description":null,"menu":null,"dungeon":null,"vehicle":null,"festivals":null,"inventory":null,"statuses":null,"archived":null,"pinned":null,"homebrew":null,"wealth":null,"scarcity":null,"worship":null,"aliases":null}"#,
             serde_json::to_string(&thing).unwrap(),
         );
     }
@@ -293,7 +439,7 @@ mod test {
     fn serialize_deserialize_test_npc() {
         let thing = npc();
         assert_eq!(
-            r#"{"type":"Npc","uuid":null,"name":null,"gender":null,"age":null,"age_years":null,"size":null,"species":null,"ethnicity":null,"location_uuid":null}"#,
+            r#"{"type":"Npc","uuid":null,"name":null,"gender":null,"age":null,"age_years":null,"size":null,"species":null,"ethnicity":null,"location_uuid":null,"resources":null,"occupation":null,"inventory":null,"spells":null,"statuses":null,"build":null,"hair":null,"eyes":null,"mark":null,"clothing":null,"attitude":null,"demeanor":null,"fate":null,"pc":null,"archived":null,"pinned":null,"homebrew":null,"relationships":null,"aliases":null}"#,
             serde_json::to_string(&thing).unwrap(),
         );
     }
@@ -362,6 +508,27 @@ mod test {
         assert_eq!(Gender::Feminine, npc.gender());
     }
 
+    #[test]
+    fn get_emoji_test() {
+        assert_eq!("📍", place().get_emoji(false));
+        assert_eq!("🧑", npc().get_emoji(true));
+
+        let inn = Thing::Place(Place {
+            subtype: "inn".parse::<PlaceType>().unwrap().into(),
+            ..Default::default()
+        });
+        assert_eq!("🏨", inn.get_emoji(true));
+
+        let dwarf = Thing::Npc(Npc {
+            age: Age::Elderly.into(),
+            gender: Gender::Feminine.into(),
+            species: Species::Dwarf.into(),
+            ..Default::default()
+        });
+        assert_eq!("👵", dwarf.get_emoji(false));
+        assert_eq!("⛏", dwarf.get_emoji(true));
+    }
+
     #[test]
     fn lock_all_test_npc() {
         let mut npc = Npc::default();
@@ -380,6 +547,36 @@ mod test {
         assert_eq!(Thing::Place(place), thing);
     }
 
+    #[test]
+    fn from_str_test_occupation() {
+        assert!(matches!(
+            "blacksmith".parse::<ParsedThing<Thing>>().map(|t| t.thing),
+            Ok(Thing::Place(_)),
+        ));
+        assert!(matches!(
+            "a blacksmith named Hammer Time"
+                .parse::<ParsedThing<Thing>>()
+                .map(|t| t.thing),
+            Ok(Thing::Place(_)),
+        ));
+        assert!(matches!(
+            "npc blacksmith"
+                .parse::<ParsedThing<Thing>>()
+                .map(|t| t.thing),
+            Ok(Thing::Npc(_)),
+        ));
+        assert!(matches!(
+            "dwarf blacksmith"
+                .parse::<ParsedThing<Thing>>()
+                .map(|t| t.thing),
+            Ok(Thing::Npc(_)),
+        ));
+        assert!(matches!(
+            "cultist".parse::<ParsedThing<Thing>>().map(|t| t.thing),
+            Ok(Thing::Npc(_)),
+        ));
+    }
+
     fn place() -> Thing {
         Thing::Place(Place::default())
     }
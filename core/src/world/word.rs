@@ -1,115 +1,138 @@
+//! The pools of words that generators fill their templates from, eg. `animal` or `food`. The
+//! built-in pools are read from `data/words.json`, so contributors can extend them without
+//! touching the generators, and users can add to them with a table named after the pool, eg.
+//! `table add animal words: Capybara, Axolotl` (see [`Tables::words`](crate::table::Tables)).
+
 use rand::distributions::WeightedIndex;
 use rand::prelude::*;
 use rand::Rng;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+fn built_in() -> &'static HashMap<&'static str, Vec<&'static str>> {
+    static BUILT_IN: OnceLock<HashMap<&'static str, Vec<&'static str>>> = OnceLock::new();
+
+    BUILT_IN.get_or_init(|| {
+        serde_json::from_str(include_str!("../../../data/words.json"))
+            .expect("data/words.json is malformed")
+    })
+}
+
+thread_local! {
+    /// The registry in effect while [`WordRegistry::scope`] is running.
+    static ACTIVE: RefCell<WordRegistry> = RefCell::new(WordRegistry::default());
+
+    /// Words added by the user, kept for the life of the program so that generators can hand
+    /// them out as `&'static str` alongside the built-in words.
+    static INTERNED: RefCell<HashSet<&'static str>> = RefCell::new(HashSet::new());
+}
+
+/// Words added to the built-in pools, eg. from the user's tables.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct WordRegistry(HashMap<&'static str, Vec<&'static str>>);
 
-#[rustfmt::skip]
-const ADJECTIVES: &[&str] = &[
-    "Blue", "Bronze", "Brown", "Burgundy", "Driven", "Enchanted", "Gold", "Green", "Grey",
-    "Grouchy", "Hallowed", "Happy", "Hidden", "Hungry", "Jovial", "Lone", "Lost", "Lucky",
-    "Merry", "Moody", "Morose", "Orange", "Purple", "Red", "Silent", "Silver", "Thirsty",
-    "Wasted", "Wild",
-];
-
-#[rustfmt::skip]
-const LAND_ANIMALS: &[&str] = &[
-    "Antelope", "Ape", "Baboon", "Badger", "Bat", "Bear", "Beaver", "Bee", "Beetle", "Boar",
-    "Camel", "Cat", "Cow", "Deer", "Dog", "Donkey", "Dove", "Dragonfly", "Duck", "Eagle",
-    "Elephant", "Elk", "Ermine", "Fox", "Frog", "Goat", "Goose", "Hare", "Hart", "Hawk",
-    "Hedgehog", "Heron", "Herring", "Horse", "Hound", "Hyena", "Jackal", "Lamb", "Leopard",
-    "Lion", "Magpie", "Mole", "Owl", "Panther", "Peacock", "Phoenix", "Pony", "Porcupine",
-    "Rabbit", "Ram", "Rat", "Raven", "Salamander", "Scorpion", "Sheep", "Snake", "Spider",
-    "Squirrel", "Stag", "Stoat", "Stork", "Swan", "Tiger", "Toad", "Tortoise",
-    "Turkey", "Turtle", "Unicorn", "Vulture", "Weasel", "Wolf",
-];
-
-#[rustfmt::skip]
-const COASTAL_ANIMALS: &[&str] = &[
-    "Cormorant", "Crab", "Dolphin", "Herring", "Mermaid", "Octopus", "Osprey", "Otter",
-    "Pelican", "Perch", "Salmon", "Seagull", "Seal", "Shark", "Starfish", "Squid", "Whale",
-    "Whelk"
-];
-
-#[rustfmt::skip]
-const ENEMIES: &[&str] = &[
-    "Angel", "Bandit", "Brigand", "Centaur", "Chimera", "Demon", "Devil", "Dragon", "Fairy",
-    "Ghost", "Giant", "Goblin", "Gorgon", "Gremlin", "Hag", "Harpy", "Hydra", "Imp", "Kappa",
-    "Lich", "Manticore", "Minotaur", "Necromancer", "Oni", "Orc", "Peryton", "Pirate", "Roc",
-    "Satyr", "Seraph", "Siren", "Sorcerer", "Sphinx", "Thief", "Trickster", "Troll", "Unicorn",
-    "Vampire", "Werewolf", "Witch", "Wyvern", "Zombie",
-];
-
-#[rustfmt::skip]
-const FOOD: &[&str] = &[
-    "Barley", "Barrel", "Beef", "Beer", "Bread", "Cask", "Cheese", "Hop", "Keg", "Malt",
-    "Mead", "Meat", "Mutton", "Pint", "Pork", "Potatoes", "Rye", "Tun", "Veal", "Venison",
-    "Vine",
-];
-
-#[rustfmt::skip]
-const GEMS: &[&str] = &[
-    "Amber", "Agate", "Amethyst", "Aquamarine", "Beryl", "Citrine", "Diamond", "Emerald",
-    "Opal", "Quartz", "Sapphire", "Topaz"
-];
-
-#[rustfmt::skip]
-const PEOPLE: &[&str] = &[
-    "Father", "Mother", "Parent", "Sibling", "Hunter", "Emperor", "Empress", "Warrior",
-    "Sage", "Ancestor"
-];
-
-#[rustfmt::skip]
-const PROFESSIONS: &[&str] = &[
-    "Adventurer", "Baker", "Beggar", "Blacksmith", "Brewer", "Bricklayer", "Builder",
-    "Butcher", "Carpenter", "Conjurer", "Cooper", "Diviner", "Enchanter", "Evoker", "Farrier",
-    "Ferryman", "Fisherman", "Glazier", "Illusionist", "Knight", "Mage", "Magician", "Mason",
-    "Miller", "Plumber", "Porter", "Printer", "Roper", "Sailor", "Shipwright", "Smith",
-    "Soldier", "Waterman", "Warrior", "Wizard",
-];
-
-#[rustfmt::skip]
-const SYMBOLS: &[&str] = &[
-    "Abbey", "Anchor", "Anvil", "Arrow", "Axe", "Belfry", "Bell", "Book", "Buckle", "Cap",
-    "Castle", "Column", "Crescent", "Crown", "Drum", "Feather", "Foil", "Hammer", "Harp",
-    "Harrow", "Helmet", "Horseshoe", "Key", "Lance", "Lance", "Locket", "Mace", "Mill",
-    "Mitre", "Moon", "Nail", "Oar", "Phalactary", "Rake", "Rook", "Scale", "Sceptre", "Scythe",
-    "Ship", "Shovel", "Spear", "Spur", "Star", "Steeple", "Sun", "Sword", "Thunderbolt",
-    "Tower", "Trumpet", "Wand", "Wheel",
-];
+impl WordRegistry {
+    /// The names of the pools that can be added to.
+    pub fn pools() -> impl Iterator<Item = &'static str> {
+        let mut pools: Vec<_> = built_in().keys().copied().collect();
+        pools.sort_unstable();
+        pools.into_iter()
+    }
+
+    /// Add words to a pool, returning `false` if there's no such pool.
+    pub fn add<'a>(&mut self, pool: &str, words: impl IntoIterator<Item = &'a str>) -> bool {
+        let Some((pool, _)) = built_in().get_key_value(pool) else {
+            return false;
+        };
+
+        self.0
+            .entry(pool)
+            .or_default()
+            .extend(words.into_iter().map(intern));
+        true
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.values().all(Vec::is_empty)
+    }
+
+    /// Run a generator with these words added to the built-in pools. Nothing outside of `f` is
+    /// affected, so `f` should generate synchronously rather than awaiting anything.
+    pub fn scope<T>(&self, f: impl FnOnce() -> T) -> T {
+        let previous = ACTIVE.with(|active| active.replace(self.clone()));
+        let result = f();
+        ACTIVE.with(|active| active.replace(previous));
+        result
+    }
+}
+
+fn intern(word: &str) -> &'static str {
+    INTERNED.with(|interned| {
+        let mut interned = interned.borrow_mut();
+
+        if let Some(word) = interned.get(word) {
+            word
+        } else {
+            let word: &'static str = Box::leak(word.to_string().into_boxed_str());
+            interned.insert(word);
+            word
+        }
+    })
+}
+
+/// The number of words in a pool, including any added by the active registry.
+fn pool_len(pool: &str) -> usize {
+    built_in().get(pool).map_or(0, Vec::len)
+        + ACTIVE.with(|active| active.borrow().0.get(pool).map_or(0, Vec::len))
+}
+
+/// Pick a word from a pool. The built-in words come first, so that a given seed picks the same
+/// built-in word as ever when nothing has been added.
+fn pick(pool: &str, rng: &mut impl Rng) -> &'static str {
+    let built_in = &built_in()[pool];
+    let index = rng.gen_range(0..pool_len(pool));
+
+    built_in
+        .get(index)
+        .copied()
+        .unwrap_or_else(|| ACTIVE.with(|active| active.borrow().0[pool][index - built_in.len()]))
+}
 
 pub fn adjective(rng: &mut impl Rng) -> &'static str {
-    ListGenerator(ADJECTIVES).gen(rng)
+    pick("adjective", rng)
 }
 
 pub fn cardinal_direction(rng: &mut impl Rng) -> &'static str {
-    ListGenerator(&["North", "South", "East", "West"]).gen(rng)
+    pick("direction", rng)
 }
 
 pub fn enemy(rng: &mut impl Rng) -> &'static str {
-    ListGenerator(ENEMIES).gen(rng)
+    pick("enemy", rng)
 }
 
 pub fn food(rng: &mut impl Rng) -> &'static str {
-    ListGenerator(FOOD).gen(rng)
+    pick("food", rng)
 }
 
 pub fn gem(rng: &mut impl Rng) -> &'static str {
-    ListGenerator(GEMS).gen(rng)
+    pick("gem", rng)
 }
 
 pub fn person(rng: &mut impl Rng) -> &'static str {
-    ListGenerator(PEOPLE).gen(rng)
+    pick("person", rng)
 }
 
 pub fn profession(rng: &mut impl Rng) -> &'static str {
-    ListGenerator(PROFESSIONS).gen(rng)
+    pick("profession", rng)
 }
 
 pub fn symbol(rng: &mut impl Rng) -> &'static str {
-    ListGenerator(SYMBOLS).gen(rng)
+    pick("symbol", rng)
 }
 
 pub fn animal(rng: &mut impl Rng) -> &'static str {
-    let dist = WeightedIndex::new([LAND_ANIMALS.len(), COASTAL_ANIMALS.len()]).unwrap();
+    let dist = WeightedIndex::new([pool_len("animal"), pool_len("coastal animal")]).unwrap();
     match dist.sample(rng) {
         0 => land_animal(rng),
         1 => coastal_animal(rng),
@@ -118,11 +141,11 @@ pub fn animal(rng: &mut impl Rng) -> &'static str {
 }
 
 pub fn land_animal(rng: &mut impl Rng) -> &'static str {
-    ListGenerator(LAND_ANIMALS).gen(rng)
+    pick("animal", rng)
 }
 
 pub fn coastal_animal(rng: &mut impl Rng) -> &'static str {
-    ListGenerator(COASTAL_ANIMALS).gen(rng)
+    pick("coastal animal", rng)
 }
 
 pub struct ListGenerator(pub &'static [&'static str]);
@@ -132,3 +155,49 @@ impl ListGenerator {
         self.0[rng.gen_range(0..self.0.len())]
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn pools_test() {
+        assert_eq!(
+            vec![
+                "adjective",
+                "animal",
+                "coastal animal",
+                "direction",
+                "enemy",
+                "food",
+                "gem",
+                "person",
+                "profession",
+                "symbol",
+            ],
+            WordRegistry::pools().collect::<Vec<_>>(),
+        );
+
+        for pool in WordRegistry::pools() {
+            assert!(pool_len(pool) > 0, "{}", pool);
+        }
+    }
+
+    #[test]
+    fn scope_test() {
+        let mut words = WordRegistry::default();
+        assert!(words.add("gem", ["Moonstone"]));
+        assert!(!words.add("potato", ["Russet"]));
+
+        let built_in = pool_len("gem");
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        words.scope(|| {
+            assert_eq!(built_in + 1, pool_len("gem"));
+            assert!((0..100).any(|_| gem(&mut rng) == "Moonstone"));
+        });
+
+        assert_eq!(built_in, pool_len("gem"));
+        assert!((0..100).all(|_| gem(&mut rng) != "Moonstone"));
+    }
+}
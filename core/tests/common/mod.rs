@@ -1,5 +1,7 @@
-use initiative_core::app::AutocompleteSuggestion;
-use initiative_core::{app, App, BackupData, DataStore, Event, MemoryDataStore, NullDataStore};
+use initiative_core::app::{AuditedRng, AutocompleteSuggestion, CommandOutput, TraceHook};
+use initiative_core::{
+    app, App, BackupData, DataStore, Event, MaybeSync, MemoryDataStore, NullDataStore,
+};
 use tokio_test::block_on;
 
 pub fn get_name(output: &str) -> String {
@@ -28,7 +30,7 @@ pub fn sync_app_with_data_store(data_store: impl DataStore + 'static) -> SyncApp
     app
 }
 
-pub fn sync_app_with_dispatcher<F: Fn(Event)>(event_dispatcher: &'static F) -> SyncApp {
+pub fn sync_app_with_dispatcher<F: Fn(Event) + MaybeSync>(event_dispatcher: &'static F) -> SyncApp {
     let mut app = SyncApp::new(MemoryDataStore::default(), event_dispatcher);
     app.init();
     app
@@ -40,7 +42,7 @@ fn event_dispatcher(_event: Event) {}
 
 #[allow(dead_code)]
 impl SyncApp {
-    pub fn new<F: Fn(Event)>(
+    pub fn new<F: Fn(Event) + MaybeSync>(
         data_store: impl DataStore + 'static,
         event_dispatcher: &'static F,
     ) -> Self {
@@ -55,11 +57,43 @@ impl SyncApp {
         block_on(self.0.command(input))
     }
 
+    pub fn command_structured(&mut self, input: &str) -> CommandOutput {
+        block_on(self.0.command_structured(input))
+    }
+
     pub fn autocomplete(&self, input: &str) -> Vec<AutocompleteSuggestion> {
         block_on(self.0.autocomplete(input))
     }
 
+    pub fn subscribe(&mut self, hook: impl TraceHook + 'static) {
+        self.0.subscribe(hook)
+    }
+
+    pub fn set_clock(&mut self, clock: fn() -> u64) {
+        self.0.set_clock(clock)
+    }
+
+    pub fn set_seed(&mut self, seed: u64) {
+        self.0.set_rng(AuditedRng::from_seed(seed))
+    }
+
     pub fn bulk_import(&mut self, data: BackupData) -> Result<String, String> {
         block_on(self.0.bulk_import(data))
     }
+
+    pub fn import_homebrew(&mut self, data: serde_json::Value) -> Result<String, String> {
+        block_on(self.0.import_homebrew(data))
+    }
+
+    pub fn flush(&mut self) -> Result<(), String> {
+        block_on(self.0.flush())
+    }
+}
+
+/// Like a frontend being closed, anything still waiting to be saved is written when the app goes
+/// away.
+impl Drop for SyncApp {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
 }
@@ -18,3 +18,13 @@ fn it_works() {
 
     assert_ne!(app.command("roll 100d1000"), app.command("roll 100d1000"));
 }
+
+#[test]
+fn it_is_annotated() {
+    let mut app = sync_app();
+
+    assert_eq!(
+        Ok("<span class=\"roll\">[1] = **1**</span>".to_string()),
+        app.command_structured("roll d1").into_annotated_markdown(),
+    );
+}
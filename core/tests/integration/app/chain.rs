@@ -0,0 +1,128 @@
+use crate::common::sync_app;
+
+#[test]
+fn semicolons() {
+    let mut app = sync_app();
+
+    let output = app
+        .command("inn named Foo; npc named Alice; rename Alice to Carol")
+        .unwrap();
+    assert!(
+        output.starts_with("_Running `inn named Foo`._\n\n"),
+        "{}",
+        output
+    );
+    assert!(
+        output.contains("\n\n_Running `npc named Alice`._\n\n"),
+        "{}",
+        output
+    );
+    assert!(
+        output.contains("\n\n_Running `rename Alice to Carol`._\n\n"),
+        "{}",
+        output
+    );
+
+    app.command("Foo").unwrap();
+    app.command("Carol").unwrap();
+
+    assert_eq!(
+        "Successfully undid creating Foo, creating Alice, and editing Alice. Use `redo` to reverse this.",
+        app.command("undo")
+            .unwrap()
+            .lines()
+            .last()
+            .unwrap()
+            .trim_matches('_'),
+    );
+    assert!(app.command("Foo").is_err());
+    assert!(app.command("Alice").is_err());
+    assert!(app.command("Carol").is_err());
+
+    assert_eq!(
+        "Successfully redid creating Foo, creating Alice, and editing Carol. Use `undo` to reverse this.",
+        app.command("redo")
+            .unwrap()
+            .lines()
+            .last()
+            .unwrap()
+            .trim_matches('_'),
+    );
+    app.command("Foo").unwrap();
+    app.command("Carol").unwrap();
+}
+
+#[test]
+fn and() {
+    let mut app = sync_app();
+
+    let output = app.command("inn and npc").unwrap();
+    assert!(output.starts_with("_Running `inn`._\n\n"), "{}", output);
+    assert!(output.contains("\n\n_Running `npc`._\n\n"), "{}", output);
+
+    // Only split on "and" if the input doesn't make sense otherwise.
+    let output = app.command("inn named Foo and Sons").unwrap();
+    assert!(output.contains("# Foo and Sons\n"), "{}", output);
+
+    // Unless it joins commands that are unambiguous by themselves.
+    let output = app.command("create inn and create inn").unwrap();
+    assert!(
+        output.starts_with("_Running `create inn`._\n\n"),
+        "{}",
+        output
+    );
+    assert!(
+        output.contains("\n\n_Running `create inn`._\n\n"),
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn save_both() {
+    let mut app = sync_app();
+
+    let output = app
+        .command("create inn; create innkeeper; save both")
+        .unwrap();
+    assert!(
+        output.contains("\n\n_Running `save both`._\n\n"),
+        "{}",
+        output
+    );
+    assert!(output.contains(" were successfully saved."), "{}", output);
+
+    let journal = app.command("journal").unwrap();
+    assert!(journal.contains("## NPCs"), "{}", journal);
+    assert!(journal.contains("## Places"), "{}", journal);
+}
+
+#[test]
+fn failure() {
+    let mut app = sync_app();
+
+    let output = app
+        .command("inn named Foo; load Potato; npc named Alice")
+        .unwrap_err();
+    assert!(
+        output.ends_with("No matches for \"Potato\"\n\n_The remaining commands were not run, and the earlier ones were undone._"),
+        "{}",
+        output,
+    );
+
+    assert!(app.command("Foo").is_err());
+    assert!(app.command("Alice").is_err());
+    assert!(app.command("undo").is_err());
+}
+
+#[test]
+fn autocomplete() {
+    let app = sync_app();
+
+    assert_eq!(
+        Some("inn; about"),
+        app.autocomplete("inn; abo")
+            .first()
+            .map(|suggestion| suggestion.term.as_ref()),
+    );
+}
@@ -0,0 +1,61 @@
+use crate::common::{get_name, sync_app};
+use initiative_core::TraceEvent;
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn events_are_logged() {
+    let mut app = sync_app();
+
+    let output = app.command("npc").unwrap();
+    let name = get_name(&output);
+    app.command("potato").unwrap_err();
+
+    let output = app.command("debug events").unwrap();
+    assert!(output.starts_with("# Events\n\n"), "{}", output);
+    assert!(
+        output.contains(&format!(
+            "\n* parsed `npc` as `create person`\n* generated character {}\n* storage modified by creating {}\n",
+            name, name,
+        )),
+        "{}",
+        output,
+    );
+    assert!(
+        output.contains("\n* `potato` failed: Unknown command: \"potato\"\n"),
+        "{}",
+        output,
+    );
+    assert!(output.ends_with("\n* parsed `debug events`"), "{}", output);
+}
+
+#[test]
+fn events_are_sent_to_subscribers() {
+    let mut app = sync_app();
+    let events = Arc::new(Mutex::new(Vec::new()));
+
+    {
+        let events = events.clone();
+        app.subscribe(move |event: &TraceEvent| events.lock().unwrap().push(event.clone()));
+    }
+
+    app.command("date").unwrap();
+    app.command("save potato").unwrap_err();
+
+    assert_eq!(
+        vec![
+            TraceEvent::CommandParsed {
+                input: "date".to_string(),
+                command: "now".to_string(),
+            },
+            TraceEvent::CommandParsed {
+                input: "save potato".to_string(),
+                command: "save potato".to_string(),
+            },
+            TraceEvent::CommandFailed {
+                input: "save potato".to_string(),
+                error: "There is no entity named \"potato\".".to_string(),
+            },
+        ],
+        *events.lock().unwrap(),
+    );
+}
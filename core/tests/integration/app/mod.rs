@@ -1,8 +1,15 @@
 mod app;
+mod chain;
+mod events;
+mod page;
+mod rolls;
+#[cfg(feature = "sync")]
+mod threads;
 mod tutorial;
+mod wizard;
 
 use crate::common::{get_name, sync_app};
-use initiative_core::app::AutocompleteSuggestion;
+use initiative_core::app::{AutocompleteSuggestion, CommandError, CommandOutput};
 
 #[test]
 fn autocomplete_command() {
@@ -68,6 +75,38 @@ fn unknown() {
     );
 }
 
+#[test]
+fn unknown_suggests_commands() {
+    let mut app = sync_app();
+
+    let error = app.command("jurnal by type").unwrap_err();
+    assert!(
+        error.starts_with(
+            "Unknown command: \"jurnal by type\"\n\nDid you mean:\n\n* `journal`: list journal contents"
+        ),
+        "{}",
+        error,
+    );
+    assert!(
+        error.ends_with("\n\n_Type `?` for help with `journal`._"),
+        "{}",
+        error,
+    );
+
+    let output = app.command("?").unwrap();
+    assert!(
+        output.starts_with("# Help: journal\n\n* `journal`"),
+        "{}",
+        output
+    );
+    assert_eq!(output, app.command("help journal").unwrap());
+
+    assert_eq!(
+        "There is no help for \"potato\". See `help` for everything initiative.sh can do.",
+        app.command("help potato").unwrap_err(),
+    );
+}
+
 #[test]
 fn command_conflict_ambiguous() {
     assert_eq!(
@@ -94,3 +133,68 @@ fn command_conflict_other_meanings() {
         output,
     );
 }
+
+#[test]
+fn command_structured() {
+    let mut app = sync_app();
+
+    let output = app.command_structured("npc");
+    assert!(output.is_success(), "{:?}", output);
+
+    let body = output.body();
+    let name = get_name(&body.markdown);
+    assert_eq!(name, body.things[0].name().to_string());
+    assert!(
+        body.aliases.contains(&AutocompleteSuggestion::new(
+            "save",
+            format!("save {}", name)
+        )),
+        "{:?}",
+        body.aliases,
+    );
+
+    app.command("save").unwrap();
+    let output = app.command_structured("journal");
+    assert!(
+        output
+            .body()
+            .markdown
+            .contains(&format!("<span class=\"npc\">`{}`</span>", name)),
+        "{:?}",
+        output,
+    );
+    assert_eq!(output.clone().into_markdown(), app.command("journal"),);
+
+    let output = app.command_structured("potato");
+    assert!(matches!(output, CommandOutput::Failure(_)), "{:?}", output);
+    assert_eq!(Err(output.body().markdown.clone()), app.command("potato"));
+}
+
+#[test]
+fn command_structured_error() {
+    let mut app = sync_app();
+    let potato = 0..6;
+
+    assert_eq!(
+        Some(&CommandError::parse(
+            "Unknown command: \"potato\"",
+            vec![potato],
+        )),
+        app.command_structured("potato").error(),
+    );
+    assert_eq!(
+        Some(&CommandError::unknown_entity(
+            "Potato Johnson",
+            "No matches for \"Potato Johnson\"",
+        )),
+        app.command_structured("load Potato Johnson").error(),
+    );
+
+    app.command("elf named Gottfried").unwrap();
+    assert!(matches!(
+        app.command_structured("elf named Gottfried").error(),
+        Some(CommandError::NameConflict { name, .. }) if name == "Gottfried",
+    ));
+
+    assert_eq!(None, app.command_structured("Gottfried").error());
+}
@@ -0,0 +1,39 @@
+use crate::common::sync_app;
+
+#[test]
+fn long_output_is_paginated() {
+    let mut app = sync_app();
+    app.command("config page 10").unwrap();
+
+    let first_page = app.command("Open Game License").unwrap();
+    assert_eq!(10, first_page.lines().count());
+    assert!(
+        first_page.starts_with("# Open Game License Version 1.0a\n"),
+        "{}",
+        first_page,
+    );
+    assert!(
+        first_page.ends_with("\n\n_Page 1 of 14. Use ~next page~ to continue._"),
+        "{}",
+        first_page,
+    );
+
+    let output = app.command("next page").unwrap();
+    assert!(
+        output.ends_with(
+            "\n\n_Page 2 of 14. Use ~next page~ to continue or ~prev page~ to go back._"
+        ),
+        "{}",
+        output,
+    );
+
+    assert_eq!(first_page, app.command("prev page").unwrap());
+
+    let output = app.command("page 14").unwrap();
+    assert!(
+        output.ends_with("\n\n_Page 14 of 14. Use ~prev page~ to go back._"),
+        "{}",
+        output,
+    );
+    assert!(app.command("next page").is_err());
+}
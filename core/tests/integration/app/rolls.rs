@@ -0,0 +1,34 @@
+use crate::common::sync_app;
+
+#[test]
+fn rolls_are_logged() {
+    let mut app = sync_app();
+
+    let output = app.command("rolls").unwrap();
+    assert!(
+        output.starts_with("# Rolls\n\n_Random seed: "),
+        "{}",
+        output
+    );
+    assert!(
+        output.ends_with("\n\nNothing has been rolled yet."),
+        "{}",
+        output,
+    );
+
+    let roll = app.command("roll 8d6").unwrap();
+    app.command("about").unwrap();
+    app.command("npc").unwrap();
+
+    let output = app.command("rolls").unwrap();
+    let lines: Vec<&str> = output.lines().skip(4).collect();
+
+    assert_eq!(2, lines.len(), "{}", output);
+    assert!(
+        lines[0].starts_with(&format!("* `roll 8d6`: {}; ", roll)),
+        "{}",
+        output,
+    );
+    assert!(lines[0].ends_with(" random values"), "{}", output);
+    assert!(lines[1].starts_with("* `npc`: "), "{}", output);
+}
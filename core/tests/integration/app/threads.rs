@@ -0,0 +1,48 @@
+use crate::common::sync_app;
+use initiative_core::{app, Event, MemoryDataStore};
+use std::thread;
+
+fn event_dispatcher(_event: Event) {}
+
+#[test]
+fn app_can_move_between_threads() {
+    let mut app = sync_app();
+    app.command("inn named The Prancing Pony").unwrap();
+
+    let output = thread::spawn(move || app.command("The Prancing Pony").unwrap())
+        .join()
+        .unwrap();
+    assert!(output.contains("The Prancing Pony"), "{}", output);
+}
+
+#[test]
+fn apps_can_run_side_by_side() {
+    let handles: Vec<_> = ["Gandalf", "Saruman"]
+        .into_iter()
+        .map(|name| {
+            thread::spawn(move || {
+                let mut app = sync_app();
+                app.command(&format!("npc named {}", name)).unwrap();
+                app.command("journal").unwrap()
+            })
+        })
+        .collect();
+
+    let journals: Vec<_> = handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .collect();
+
+    assert!(journals[0].contains("Gandalf"), "{}", journals[0]);
+    assert!(!journals[0].contains("Saruman"), "{}", journals[0]);
+    assert!(journals[1].contains("Saruman"), "{}", journals[1]);
+    assert!(!journals[1].contains("Gandalf"), "{}", journals[1]);
+}
+
+#[test]
+fn commands_are_send() {
+    fn assert_send<T: Send>(_: &T) {}
+
+    let mut app = app(MemoryDataStore::default(), &event_dispatcher);
+    assert_send(&app.command("npc"));
+}
@@ -1,4 +1,7 @@
-use crate::common::{sync_app, sync_app_with_invalid_data_store};
+use crate::common::{
+    sync_app, sync_app_with_data_store, sync_app_with_invalid_data_store, SyncApp,
+};
+use initiative_core::MemoryDataStore;
 
 const TUTORIAL_STEPS: usize = 16;
 
@@ -256,3 +259,140 @@ fn restart_from_step(step: usize) {
         journal_output,
     );
 }
+
+#[test]
+fn chapters() {
+    let mut app = sync_app();
+
+    let output = app.command("tutorial combat").unwrap();
+    assert!(
+        output.starts_with("# Tutorial: SRD Reference"),
+        "{}",
+        output
+    );
+
+    let output = follow_to_conclusion(&mut app, first_command(&output));
+    assert!(output.contains("## Tutorial: Conclusion"), "{}", output);
+
+    let output = app.command("tutorial journal").unwrap();
+    assert!(output.starts_with("# Tutorial: The Journal"), "{}", output);
+    assert_eq!(
+        "The tutorial has been cancelled.",
+        app.command("cancel").unwrap().trim(),
+    );
+
+    let journal_output = app.command("journal").unwrap();
+    assert!(
+        journal_output.contains("Your journal is currently empty."),
+        "{}",
+        journal_output,
+    );
+}
+
+#[test]
+fn skip() {
+    let mut app = sync_app();
+
+    app.command("tutorial").unwrap();
+    app.command("next").unwrap();
+
+    let output = app.command("tutorial skip").unwrap();
+    assert!(output.starts_with("# Tutorial: The Journal"), "{}", output);
+
+    let output = app.command("tutorial skip").unwrap();
+    assert!(
+        output.starts_with("# Tutorial: SRD Reference"),
+        "{}",
+        output
+    );
+
+    let output = app.command("tutorial skip").unwrap();
+    assert!(output.starts_with("# Tutorial: Conclusion"), "{}", output);
+
+    assert!(app.command("cancel").is_err());
+
+    let journal_output = app.command("journal").unwrap();
+    assert!(
+        journal_output.contains("Your journal is currently empty."),
+        "{}",
+        journal_output,
+    );
+}
+
+#[test]
+fn exit_and_resume() {
+    let data_store = MemoryDataStore::default();
+
+    {
+        let mut app = sync_app_with_data_store(data_store.clone());
+
+        assert!(app.command("tutorial resume").is_err());
+
+        app.command("tutorial").unwrap();
+        app.command("next").unwrap();
+        app.command("inn").unwrap();
+        app.command("save").unwrap();
+
+        let output = app.command("exit").unwrap();
+        assert!(output.starts_with("You've left the tutorial"), "{}", output);
+
+        let output = app.command("about").unwrap();
+        assert!(!output.contains("# Tutorial"), "{}", output);
+        assert!(app.command("tutorial exit").is_err());
+    }
+
+    let mut app = sync_app_with_data_store(data_store);
+
+    let output = app.command("tutorial resume").unwrap();
+    assert!(
+        output.starts_with("# Tutorial: Generating Characters"),
+        "{}",
+        output,
+    );
+
+    let output = follow_to_conclusion(&mut app, first_command(&output));
+    assert!(output.contains("## Tutorial: Conclusion"), "{}", output);
+    assert!(app.command("tutorial resume").is_err());
+}
+
+#[test]
+fn resume_without_saved_things() {
+    let data_store = MemoryDataStore::default();
+
+    {
+        let mut app = sync_app_with_data_store(data_store.clone());
+        app.command("tutorial").unwrap();
+        app.command("next").unwrap();
+        app.command("inn").unwrap();
+        app.command("exit").unwrap();
+    }
+
+    let mut app = sync_app_with_data_store(data_store);
+
+    let output = app.command("tutorial resume").unwrap();
+    assert!(output.starts_with("# Tutorial: Introduction"), "{}", output);
+}
+
+fn first_command(output: &str) -> String {
+    output[output.find("# Tutorial").unwrap()..]
+        .split(&['`', '~'][..])
+        .nth(1)
+        .unwrap()
+        .to_string()
+}
+
+fn follow_to_conclusion(app: &mut SyncApp, mut command: String) -> String {
+    for _ in 0..TUTORIAL_STEPS {
+        println!("> {}\n", command);
+        let output = app.command(&command).unwrap();
+        println!("{}", output);
+
+        if output.contains("## Tutorial: Conclusion") {
+            return output;
+        }
+
+        command = first_command(&output);
+    }
+
+    panic!("Broke out of infinite loop!");
+}
@@ -0,0 +1,61 @@
+use crate::common::sync_app;
+
+#[test]
+fn npc() {
+    let mut app = sync_app();
+
+    let output = app.command("wizard npc").unwrap();
+    assert!(
+        output.starts_with("# New character\n\nWhat species"),
+        "{}",
+        output
+    );
+
+    let output = app.command("potato").unwrap_err().to_string();
+    assert!(
+        output.starts_with("\"potato\" is not a species that I know.\n\nWhat species"),
+        "{}",
+        output,
+    );
+
+    let output = app.command("elf").unwrap();
+    assert!(output.starts_with("How old"), "{}", output);
+
+    let output = app.command("elderly").unwrap();
+    assert!(output.starts_with("What is their name?"), "{}", output);
+
+    let output = app.command("mabel").unwrap();
+    assert!(output.contains("# Mabel\n*elderly elf "), "{}", output);
+    assert!(output.contains("added to your `journal`"), "{}", output);
+
+    app.command("Mabel").unwrap();
+    assert!(app.command("elf").is_ok());
+}
+
+#[test]
+fn npc_random() {
+    let mut app = sync_app();
+
+    app.command("wizard npc").unwrap();
+
+    let output = app.command("human child").unwrap();
+    assert!(output.starts_with("What is their name?"), "{}", output);
+
+    let output = app.command("random").unwrap();
+    assert!(output.contains("human child"), "{}", output);
+}
+
+#[test]
+fn cancel() {
+    let mut app = sync_app();
+
+    app.command("wizard npc").unwrap();
+    app.command("dwarf").unwrap();
+
+    assert_eq!(
+        "Stopped creating the character.",
+        app.command("cancel").unwrap(),
+    );
+    assert!(app.command("elderly").is_ok());
+    assert!(app.command("cancel").is_err());
+}
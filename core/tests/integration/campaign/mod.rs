@@ -0,0 +1,72 @@
+use crate::common::{sync_app, sync_app_with_data_store};
+use initiative_core::MemoryDataStore;
+
+#[test]
+fn campaigns_are_isolated() {
+    let mut app = sync_app();
+
+    app.command("inn named Moonbright").unwrap();
+    app.command("+1d").unwrap();
+
+    assert_eq!(
+        "Started a new campaign, **Curse of Strahd**. Use `campaign switch Default` to return to the previous one.",
+        app.command("campaign new Curse of Strahd").unwrap(),
+    );
+    assert_eq!(
+        "# Journal\n\n*Your journal is currently empty.*",
+        app.command("journal").unwrap(),
+    );
+    assert_eq!(
+        "It is currently day 1 at 8:00:00 am.",
+        app.command("now").unwrap(),
+    );
+    assert!(app.command("Moonbright").is_err());
+
+    app.command("inn named Vallaki").unwrap();
+
+    assert_eq!(
+        "Switched to the **Default** campaign.",
+        app.command("campaign switch default").unwrap(),
+    );
+    assert_eq!(
+        "It is currently day 2 at 8:00:00 am.",
+        app.command("now").unwrap(),
+    );
+    assert!(app.command("Moonbright").is_ok());
+    assert!(app.command("Vallaki").is_err());
+}
+
+#[test]
+fn campaigns_are_persisted() {
+    let data_store = MemoryDataStore::default();
+
+    {
+        let mut app = sync_app_with_data_store(data_store.clone());
+        app.command("inn named Moonbright").unwrap();
+        app.command("campaign new Curse of Strahd").unwrap();
+        app.command("inn named Vallaki").unwrap();
+    }
+
+    let mut app = sync_app_with_data_store(data_store);
+    assert_eq!(
+        "# Campaigns\n\n* `campaign switch Default`\n* **Curse of Strahd** (current)\n\n_Use `campaign new [name]` to start a new campaign._",
+        app.command("campaign").unwrap(),
+    );
+    assert!(app.command("Vallaki").is_ok());
+    assert!(app.command("Moonbright").is_err());
+}
+
+#[test]
+fn campaign_names_are_unique() {
+    let mut app = sync_app();
+    app.command("campaign new Curse of Strahd").unwrap();
+
+    assert_eq!(
+        Err("There is already a campaign named \"curse of strahd\".".to_string()),
+        app.command("campaign new curse of strahd"),
+    );
+    assert_eq!(
+        Err("There is already a campaign named \"default\".".to_string()),
+        app.command("campaign new default"),
+    );
+}
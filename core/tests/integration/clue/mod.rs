@@ -0,0 +1,37 @@
+use crate::common::sync_app;
+
+#[test]
+fn secrets_report_undelivered_clues() {
+    let mut app = sync_app();
+
+    app.command("npc named Captain Vell").unwrap();
+    app.command("secret add The mayor is a vampire").unwrap();
+    app.command("clue the mayor: Bloodless bodies in the crypt")
+        .unwrap();
+    app.command("clue the mayor: A letter to the Count from Captain Vell")
+        .unwrap();
+    app.command("clue the mayor: Garlic missing from the market")
+        .unwrap();
+
+    assert_eq!(
+        "# Secrets\n\n## Not yet found out\n\n* **The mayor is a vampire**: 0 of 3 clues delivered",
+        app.command("secrets").unwrap(),
+    );
+
+    assert!(app
+        .command("Captain Vell")
+        .unwrap()
+        .contains("\n\n**Clue (The mayor is a vampire):** A letter to the Count"));
+
+    app.command("deliver clue 2 of the mayor").unwrap();
+    assert_eq!(
+        "# Secrets\n\n## Clues delivered\n\n* **The mayor is a vampire**: 1 of 3 clues delivered",
+        app.command("secrets").unwrap(),
+    );
+
+    app.command("undo").unwrap();
+    assert_eq!(
+        "# Secrets\n\n## Not yet found out\n\n* **The mayor is a vampire**: 0 of 3 clues delivered",
+        app.command("secrets").unwrap(),
+    );
+}
@@ -0,0 +1,175 @@
+use crate::common::{sync_app, sync_app_with_data_store};
+use initiative_core::MemoryDataStore;
+
+#[test]
+fn config_is_initialized() {
+    let mut app = sync_app();
+
+    assert_eq!(
+        "# Configuration
+
+**Detail:** full\\
+**Emoji:** on\\
+**Icons:** person\\
+**Date format:** long\\
+**Show times:** in the game\\
+**Suggestions:** 10\\
+**Days per year:** 365\\
+**Dawn:** 6am\\
+**Dusk:** 6pm\\
+**Party Charisma:** +0\\
+**Save history:** off\\
+**Page size:** off\\
+**Autosave recent:** off\\
+**Language:** English
+
+_Use `config [setting] [value]` to change a setting, eg. `config emoji off`._",
+        app.command("config").unwrap(),
+    );
+}
+
+#[test]
+fn config_can_be_changed_and_undone() {
+    let mut app = sync_app();
+
+    assert_eq!(
+        "Configuration updated: `date short`. Use `undo` to reverse this.",
+        app.command("config date short").unwrap(),
+    );
+    assert_eq!("It is currently 1:08:00:00.", app.command("now").unwrap());
+
+    assert_eq!(
+        "Successfully undid changing the configuration. Use `redo` to reverse this.",
+        app.command("undo").unwrap(),
+    );
+    assert_eq!(
+        "It is currently day 1 at 8:00:00 am.",
+        app.command("now").unwrap(),
+    );
+}
+
+#[test]
+fn config_is_persisted() {
+    let data_store = MemoryDataStore::default();
+
+    {
+        let mut app = sync_app_with_data_store(data_store.clone());
+        app.command("config date short").unwrap();
+    }
+
+    let mut app = sync_app_with_data_store(data_store);
+    assert_eq!("It is currently 1:08:00:00.", app.command("now").unwrap());
+}
+
+#[test]
+fn config_detail_summary() {
+    let mut app = sync_app();
+    app.command("config detail summary").unwrap();
+
+    let output = app.command("inn named Foo").unwrap();
+    assert!(output.starts_with("🏨 `Foo` (inn)\n\n"), "{}", output);
+}
+
+#[test]
+fn config_emoji_off() {
+    let mut app = sync_app();
+    app.command("config emoji off").unwrap();
+    app.command("config detail summary").unwrap();
+
+    let output = app.command("inn named Foo").unwrap();
+    assert!(output.starts_with("`Foo` (inn)\n\n"), "{}", output);
+
+    let output = app.command("journal").unwrap();
+    assert!(
+        output.starts_with("# Journal\n\n## Places\n`Foo` (inn)\n\n"),
+        "{}",
+        output,
+    );
+}
+
+#[test]
+fn config_icons_species() {
+    let mut app = sync_app();
+    app.command("config icons species").unwrap();
+    app.command("config detail summary").unwrap();
+
+    let output = app.command("dwarf named Gottfried").unwrap();
+    assert!(output.starts_with("⛏ `Gottfried` ("), "{}", output);
+
+    let output = app.command("journal").unwrap();
+    assert!(output.contains("\n⛏ `Gottfried` ("), "{}", output);
+}
+
+#[test]
+fn config_suggestions() {
+    let mut app = sync_app();
+    app.command("config suggestions 3").unwrap();
+    app.command("inn").unwrap();
+
+    let output = app.command("more").unwrap();
+    assert!(output.contains("~3~"), "{}", output);
+    assert!(!output.contains("~4~"), "{}", output);
+}
+
+#[test]
+fn config_language_translates_messages() {
+    let mut app = sync_app();
+
+    assert_eq!(
+        "Configuration updated: `language es`. Use `undo` to reverse this.",
+        app.command("config language spanish").unwrap(),
+    );
+    assert_eq!(
+        r#"No hay ninguna entrada llamada "Potato Johnson"."#,
+        app.command("rename Potato Johnson to Spud Johnson")
+            .unwrap_err(),
+    );
+
+    app.command("tutorial").unwrap();
+    assert_eq!(
+        "El tutorial ha sido cancelado.\n",
+        app.command("cancel").unwrap(),
+    );
+
+    app.command("undo").unwrap();
+    assert_eq!(
+        r#"There is no entity named "Potato Johnson"."#,
+        app.command("rename Potato Johnson to Spud Johnson")
+            .unwrap_err(),
+    );
+}
+
+#[test]
+fn config_template_changes_summaries() {
+    let mut app = sync_app();
+
+    app.command("dwarf named Gottfried").unwrap();
+
+    assert_eq!(
+        "Summary template for `npc` updated. Use `undo` to reverse this.",
+        app.command("config template npc = \"{name} the {species}\"")
+            .unwrap(),
+    );
+
+    let output = app.command("journal").unwrap();
+    assert!(output.contains("`Gottfried` the dwarf"), "{}", output);
+
+    assert!(app
+        .command("config")
+        .unwrap()
+        .ends_with("## Summary templates\n\n**npc:** {name} the {species}"));
+
+    assert_eq!(
+        "\"mood\" isn't a field that can be shown in a summary.",
+        app.command("config template npc = {name} ({mood})")
+            .unwrap_err(),
+    );
+
+    assert_eq!(
+        "Successfully undid changing the summary templates. Use `redo` to reverse this.",
+        app.command("undo").unwrap(),
+    );
+
+    let output = app.command("journal").unwrap();
+    assert!(!output.contains("`Gottfried` the dwarf"), "{}", output);
+}
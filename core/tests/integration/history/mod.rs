@@ -0,0 +1,164 @@
+use crate::common::{sync_app, sync_app_with_data_store};
+use initiative_core::MemoryDataStore;
+
+#[test]
+fn history_is_recorded() {
+    let mut app = sync_app();
+
+    assert_eq!(
+        "There are no commands in the history yet.",
+        app.command("history").unwrap(),
+    );
+
+    app.command("inn named Moonbright").unwrap();
+    app.command("potato").unwrap_err();
+
+    assert_eq!(
+        "# History
+
+* `!1` `history`, day 1 at 8:00:00 am
+* `!2` `inn named Moonbright`, day 1 at 8:00:00 am
+* `!3` `potato` (failed), day 1 at 8:00:00 am
+
+_Use `!!` to repeat the last command or `![number]` to repeat an earlier one. Use `history save [name]` to save this session for `replay`._",
+        app.command("history").unwrap(),
+    );
+}
+
+#[test]
+fn history_shows_real_world_times() {
+    let mut app = sync_app();
+    app.set_clock(|| 1_700_000_000_000);
+
+    app.command("+1h").unwrap();
+    app.command("config when both").unwrap();
+
+    let output = app.command("history").unwrap();
+    assert!(
+        output.contains("\n* `!1` `+1h`, day 1 at 9:00:00 am (2023-11-14 22:13 UTC)\n"),
+        "{}",
+        output,
+    );
+
+    app.command("config when real").unwrap();
+    let output = app.command("history").unwrap();
+    assert!(
+        output.contains("\n* `!2` `config when both`, 2023-11-14 22:13 UTC\n"),
+        "{}",
+        output,
+    );
+}
+
+#[test]
+fn history_can_be_repeated() {
+    let mut app = sync_app();
+
+    assert_eq!(
+        "There are no commands in the history yet.",
+        app.command("!!").unwrap_err(),
+    );
+
+    app.command("now").unwrap();
+    app.command("+1h").unwrap();
+
+    assert_eq!(
+        "_Running `+1h`._\n\nIt is now day 1 at 10:00:00 am. Use `undo` to reverse.",
+        app.command("!!").unwrap(),
+    );
+    assert_eq!(
+        "_Running `now`._\n\nIt is currently day 1 at 10:00:00 am.",
+        app.command("!1").unwrap(),
+    );
+
+    // The expanded command is recorded, so `!!` repeats `now` rather than itself.
+    assert_eq!(
+        "_Running `now`._\n\nIt is currently day 1 at 10:00:00 am.",
+        app.command("!!").unwrap(),
+    );
+
+    assert_eq!(
+        "There is no command numbered 99 in the `history`.",
+        app.command("!99").unwrap_err(),
+    );
+}
+
+#[test]
+fn history_can_be_saved_and_replayed() {
+    let data_store = MemoryDataStore::default();
+
+    {
+        let mut app = sync_app_with_data_store(data_store.clone());
+
+        assert_eq!(
+            "There are no commands to save.",
+            app.command("history save session.log").unwrap_err(),
+        );
+
+        app.command("inn named Moonbright").unwrap();
+        app.command("+1d").unwrap();
+        app.command("potato").unwrap_err();
+        app.command("history").unwrap();
+
+        assert_eq!(
+            "Saved 2 commands as `session.log`. Use `replay session.log` to run them again.",
+            app.command("history save session.log").unwrap(),
+        );
+    }
+
+    let mut app = sync_app_with_data_store(data_store);
+
+    assert_eq!(
+        r#"There is no saved history named "potato"."#,
+        app.command("replay potato").unwrap_err(),
+    );
+
+    app.command("delete Moonbright").unwrap();
+
+    assert_eq!(
+        "Replayed 2 commands from `session.log`.",
+        app.command("replay session.log").unwrap(),
+    );
+    assert_eq!(
+        "It is currently day 3 at 8:00:00 am.",
+        app.command("now").unwrap(),
+    );
+    assert!(app.command("Moonbright").is_ok());
+
+    assert_eq!(
+        "Replayed 2 commands from `session.log`.
+
+! The following commands failed:
+
+* `inn named Moonbright`",
+        app.command("replay session.log").unwrap(),
+    );
+}
+
+#[test]
+fn history_is_persisted_if_enabled() {
+    let data_store = MemoryDataStore::default();
+
+    {
+        let mut app = sync_app_with_data_store(data_store.clone());
+        app.command("now").unwrap();
+    }
+
+    {
+        let mut app = sync_app_with_data_store(data_store.clone());
+        assert_eq!(
+            "There are no commands in the history yet.",
+            app.command("history").unwrap(),
+        );
+
+        app.command("config history on").unwrap();
+        app.command("now").unwrap();
+    }
+
+    let mut app = sync_app_with_data_store(data_store);
+    let output = app.command("history").unwrap();
+    assert!(
+        output.contains("* `!1` `config history on`, day 1 at 8:00:00 am\n* `!2` `now`, "),
+        "{}",
+        output,
+    );
+}
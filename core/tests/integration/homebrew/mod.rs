@@ -0,0 +1,88 @@
+mod reference;
+
+use crate::common::sync_app;
+
+#[test]
+fn homebrew_species_can_be_added_and_created() {
+    let mut app = sync_app();
+
+    let output = app
+        .command("species add warforged like dwarf with names like Anvil")
+        .unwrap();
+    assert_eq!(
+        "Added the **warforged** species. Use `create warforged` to use it, or `undo` to reverse this.",
+        output,
+    );
+
+    {
+        let output = app.command("create warforged").unwrap();
+        assert!(output.contains("# Anvil\n*"), "{}", output);
+        assert!(output.contains(" warforged"), "{}", output);
+        assert!(output.contains("**Species:** warforged"), "{}", output);
+    }
+
+    {
+        let output = app.command("adult warforged named Bolt").unwrap();
+        assert!(output.contains("# Bolt\n*adult warforged"), "{}", output);
+    }
+
+    {
+        let output = app.command("create potato warforged named Cog").unwrap();
+        assert!(
+            output.contains("\\> create **potato** warforged named Cog"),
+            "{}",
+            output,
+        );
+    }
+
+    assert_eq!(
+        "# Homebrew\n\n## Species\n\n* `warforged`: like dwarf, with names like Anvil",
+        app.command("homebrew").unwrap(),
+    );
+
+    assert_eq!(
+        "Removed the **warforged** species. Anything already created with it is unaffected. Use `undo` to reverse this.",
+        app.command("species remove warforged").unwrap(),
+    );
+    assert_eq!(
+        "Successfully undid changing the homebrew types. Use `redo` to reverse this.",
+        app.command("undo").unwrap(),
+    );
+    assert!(app.command("homebrew").unwrap().contains("warforged"));
+}
+
+#[test]
+fn homebrew_species_share_of_population() {
+    let mut app = sync_app();
+
+    app.command("species add kenku as 100% of the population")
+        .unwrap();
+
+    let output = app.command("npc").unwrap();
+    assert!(output.contains("**Species:** kenku"), "{}", output);
+
+    // A species asked for by name isn't replaced.
+    let output = app.command("elf").unwrap();
+    assert!(output.contains("**Species:** elf"), "{}", output);
+}
+
+#[test]
+fn homebrew_place_type_can_be_added_and_created() {
+    let mut app = sync_app();
+
+    app.command("place type add teahouse like inn").unwrap();
+
+    let output = app.command("teahouse named The Jade Cup").unwrap();
+    assert!(output.contains("# The Jade Cup\n*teahouse*"), "{}", output);
+
+    let output = app.command("journal by type").unwrap();
+    assert!(output.contains("## Teahouse"), "{}", output);
+
+    let output = app.command("journal where type = teahouse").unwrap();
+    assert!(output.contains("`The Jade Cup`"), "{}", output);
+
+    assert_eq!(
+        "\"inn\" is already built in.",
+        app.command("place type add inn like tavern").unwrap_err(),
+    );
+}
@@ -0,0 +1,192 @@
+use crate::common::{sync_app, sync_app_with_dispatcher};
+use initiative_core::Event;
+use serde_json::json;
+use std::cell::RefCell;
+
+#[test]
+fn homebrew_import_event() {
+    thread_local! {
+        static LAST_EVENT: RefCell<Option<Event>> = const { RefCell::new(None) };
+    }
+
+    fn event_dispatcher(event: Event) {
+        LAST_EVENT.with(|e| *e.borrow_mut() = Some(event));
+    }
+
+    let mut app = sync_app_with_dispatcher(&event_dispatcher);
+
+    assert_eq!(
+        "The file upload popup should appear momentarily. Please select a JSON file containing lists of `spells`, `equipment`, `magic-items`, or `monsters` in the format of the D&D 5e API.",
+        app.command("homebrew import").unwrap(),
+    );
+    assert!(matches!(
+        LAST_EVENT.with(|e| e.borrow_mut().take()),
+        Some(Event::ImportHomebrew),
+    ));
+}
+
+#[test]
+fn homebrew_content_is_imported_and_looked_up() {
+    let mut app = sync_app();
+
+    assert_eq!(
+        "Imported 1 spell and 1 monster. Use `homebrew list` to see your homebrew content, or `undo` to reverse this.",
+        app.import_homebrew(json!({"spells": [fireball()], "monsters": [bog_lurker()]}))
+            .unwrap(),
+    );
+
+    {
+        let output = app.command("Bog Lurker").unwrap();
+        assert!(
+            output.starts_with("# Bog Lurker\n*Large monstrosity, neutral evil*"),
+            "{}",
+            output,
+        );
+        assert!(
+            output.ends_with("*Bog Lurker is homebrew content. Use `homebrew list` to see the rest of your homebrew.*"),
+            "{}",
+            output,
+        );
+    }
+
+    assert_eq!(
+        vec![("Bog Lurker".to_string(), "homebrew monster".to_string())],
+        app.autocomplete("bog l")
+            .into_iter()
+            .map(|suggestion| (suggestion.term.to_string(), suggestion.summary.to_string()))
+            .collect::<Vec<_>>(),
+    );
+
+    // The homebrew version of a spell replaces the SRD version, which remains available with the
+    // "srd" prefix.
+    {
+        let output = app.command("fireball").unwrap();
+        assert!(output.contains("*9th-level evocation*"), "{}", output);
+        assert!(output.contains("`20d6`"), "{}", output);
+
+        let output = app.command("srd spell fireball").unwrap();
+        assert!(output.contains("*3rd-level evocation*"), "{}", output);
+    }
+
+    assert_eq!(
+        "# Homebrew\n\n## Spells\n\n* `Fireball` (9th-level evocation)\n\n## Monsters\n\n* `Bog Lurker` (large monstrosity, CR 2)",
+        app.command("homebrew list").unwrap(),
+    );
+
+    assert_eq!(
+        "Successfully undid importing homebrew content. Use `redo` to reverse this.",
+        app.command("undo").unwrap(),
+    );
+    assert!(app.command("bog lurker").is_err());
+}
+
+#[test]
+fn homebrew_import_errors() {
+    let mut app = sync_app();
+
+    assert_eq!(
+        "The spell \"Frostbolt\" couldn't be read: missing field `level`.",
+        app.import_homebrew(json!({"spells": [{"index": "frostbolt", "name": "Frostbolt"}]}))
+            .unwrap_err(),
+    );
+
+    assert_eq!(
+        "# Homebrew\n\n*You haven't added any homebrew yet. Use `species add [name] like [species]` or `place type add [name] like [place type]` to add a species or place type, eg. `species add warforged like dwarf`, or `homebrew import` to import spells, items, and monsters.*",
+        app.command("homebrew list").unwrap(),
+    );
+}
+
+#[test]
+fn homebrew_monster_can_be_scaled() {
+    let mut app = sync_app();
+    app.import_homebrew(json!({"monsters": [bog_lurker()]}))
+        .unwrap();
+
+    {
+        let output = app.command("scale bog lurker to cr 5").unwrap();
+        assert!(output.starts_with("# Bog Lurker\n"), "{}", output);
+        assert!(output.contains("**Challenge:** 5 (1,800 XP)"), "{}", output);
+        assert!(
+            output.ends_with(
+                "_Use `scale Bog Lurker to cr 5 as [name]` to save this as a homebrew monster._"
+            ),
+            "{}",
+            output,
+        );
+    }
+
+    {
+        let output = app
+            .command("scale bog lurker to cr 5 as Bog Tyrant")
+            .unwrap();
+        assert!(output.starts_with("# Bog Tyrant\n"), "{}", output);
+        assert!(
+            output.ends_with(
+                "_Bog Tyrant was saved as a homebrew monster. Use `undo` to reverse this._"
+            ),
+            "{}",
+            output,
+        );
+
+        let output = app.command("bog tyrant").unwrap();
+        assert!(output.contains("**Challenge:** 5 (1,800 XP)"), "{}", output);
+    }
+
+    assert_eq!(
+        "There is no monster named \"bog baron\".",
+        app.command("scale bog baron to cr 3").unwrap_err(),
+    );
+}
+
+fn fireball() -> serde_json::Value {
+    json!({
+        "index": "fireball",
+        "name": "Fireball",
+        "level": 9,
+        "school": {"index": "evocation", "name": "Evocation", "url": "/api/magic-schools/evocation"},
+        "casting_time": "1 action",
+        "range": "150 feet",
+        "components": ["V", "S"],
+        "duration": "Instantaneous",
+        "desc": ["A really big fireball deals 20d6 fire damage."],
+    })
+}
+
+#[test]
+fn homebrew_monster_has_lair() {
+    let mut app = sync_app();
+    app.import_homebrew(json!({"monsters": [bog_lurker()]}))
+        .unwrap();
+
+    let output = app.command("create lair for bog lurker").unwrap();
+    assert!(
+        output.contains("**Lair of:** `Bog Lurker` (CR 2)"),
+        "{}",
+        output,
+    );
+    assert!(output.contains("**Theme:** cavern"), "{}", output);
+    assert!(
+        output.contains("the Bog Lurker takes a lair action"),
+        "{}",
+        output,
+    );
+}
+
+fn bog_lurker() -> serde_json::Value {
+    json!({
+        "index": "bog-lurker",
+        "name": "Bog Lurker",
+        "size": "Large",
+        "type": "monstrosity",
+        "alignment": "neutral evil",
+        "armor_class": 14,
+        "hit_points": 59,
+        "strength": 18,
+        "dexterity": 9,
+        "constitution": 16,
+        "intelligence": 5,
+        "wisdom": 12,
+        "charisma": 6,
+        "challenge_rating": 2,
+    })
+}
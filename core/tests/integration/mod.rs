@@ -1,5 +1,20 @@
 mod app;
+mod campaign;
+mod clue;
+mod config;
+mod history;
+mod homebrew;
+mod portent;
 mod reference;
+mod religion;
+mod scene;
+mod session;
 mod storage;
+mod table;
+mod template;
+mod thread;
 mod time;
+mod treasure;
+mod treasury;
+mod war;
 mod world;
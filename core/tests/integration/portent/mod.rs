@@ -0,0 +1,46 @@
+use crate::common::sync_app;
+
+#[test]
+fn portents_are_shown_with_their_subject() {
+    let mut app = sync_app();
+
+    assert_eq!(
+        "# Portents\n\n*There are no curses, prophecies, or omens yet. Use `curse [name]`, `prophecy`, or `omen` to create one.*",
+        app.command("portents").unwrap(),
+    );
+
+    app.command("npc named Gimli").unwrap();
+
+    let output = app.command("prophecy about Gimli").unwrap();
+    assert!(output.starts_with("# Prophecy\n\n*"), "{}", output);
+    assert!(output.contains("Gimli"), "{}", output);
+    assert!(
+        output.ends_with(
+            "_The prophecy has been attached to `Gimli` in your `journal`. Use `undo` to remove it._"
+        ),
+        "{}",
+        output,
+    );
+
+    let output = app.command("Gimli").unwrap();
+    assert!(output.contains("\n\n**Prophecy:** "), "{}", output);
+
+    app.command("omen").unwrap();
+
+    let output = app.command("portents").unwrap();
+    assert!(
+        output.starts_with("# Portents\n\n* **Prophecy** on `Gimli`: "),
+        "{}",
+        output,
+    );
+    assert!(output.contains("\n* **Omen**: "), "{}", output);
+
+    assert_eq!(
+        "No matches for \"Legolas\"",
+        app.command("curse Legolas").unwrap_err(),
+    );
+
+    app.command("undo").unwrap();
+    app.command("undo").unwrap();
+    assert!(!app.command("Gimli").unwrap().contains("**Prophecy:**"));
+}
@@ -0,0 +1,79 @@
+use crate::common::sync_app;
+use initiative_core::app::AutocompleteSuggestion;
+
+#[test]
+fn wizard_5() {
+    let output = sync_app().command("wizard 5").unwrap();
+
+    assert!(
+        output.starts_with("# Wizard 5\n\n**Hit Die:** `d6`\\\n**Saving Throws:** INT, WIS\\\n**Proficiencies:** Daggers, Darts, Slings, Quarterstaffs, Crossbows, light\\\n**Proficiency Bonus:** +3\n\n## Features\n\n* **1st level:** "),
+        "{}",
+        output,
+    );
+    assert!(
+        output.contains("\n* **2nd level:** Arcane Tradition\n"),
+        "{}",
+        output
+    );
+    assert!(
+        output.contains(
+            "\n\n## Spellcasting\n\n| Level | Proficiency Bonus | Cantrips Known | 1st | 2nd | 3rd |\n|--:|--:|--:|--:|--:|--:|\n| 5th | +3 | 4 | 4 | 3 | 2 |\n\n",
+        ),
+        "{}",
+        output,
+    );
+    assert!(
+        output.ends_with("\n\n*Wizard 5 is Open Game Content subject to the `Open Game License`.*"),
+        "{}",
+        output,
+    );
+
+    assert_eq!(output, sync_app().command("srd class Wizard 5").unwrap());
+}
+
+#[test]
+fn wizard() {
+    let output = sync_app().command("wizard").unwrap();
+
+    assert!(
+        output.starts_with("# Wizard\n\n**Hit Die:** `d6`\\\n"),
+        "{}",
+        output
+    );
+    assert!(
+        output.contains("\n\n| Level | Proficiency Bonus | Features | Cantrips Known | 1st | 2nd | 3rd | 4th | 5th | 6th | 7th | 8th | 9th |\n"),
+        "{}",
+        output,
+    );
+    assert!(
+        output.contains(
+            "\n| 2nd | +2 | Arcane Tradition | 3 | 3 | — | — | — | — | — | — | — | — |\n"
+        ),
+        "{}",
+        output,
+    );
+}
+
+#[test]
+fn autocomplete() {
+    assert_eq!(
+        vec![AutocompleteSuggestion::new("Wizard", "SRD class")],
+        sync_app().autocomplete("wiz"),
+    );
+
+    assert_eq!(
+        vec![
+            AutocompleteSuggestion::new("Wizard 1", "SRD class"),
+            AutocompleteSuggestion::new("Wizard 10", "SRD class"),
+            AutocompleteSuggestion::new("Wizard 11", "SRD class"),
+            AutocompleteSuggestion::new("Wizard 12", "SRD class"),
+            AutocompleteSuggestion::new("Wizard 13", "SRD class"),
+            AutocompleteSuggestion::new("Wizard 14", "SRD class"),
+            AutocompleteSuggestion::new("Wizard 15", "SRD class"),
+            AutocompleteSuggestion::new("Wizard 16", "SRD class"),
+            AutocompleteSuggestion::new("Wizard 17", "SRD class"),
+            AutocompleteSuggestion::new("Wizard 18", "SRD class"),
+        ],
+        sync_app().autocomplete("wizard "),
+    );
+}
@@ -24,3 +24,21 @@ fn stunned() {
         sync_app().autocomplete("stunned"),
     );
 }
+
+#[test]
+fn conditions() {
+    let output = sync_app().command("conditions").unwrap();
+
+    assert!(
+        output.starts_with("# Conditions\n* `Blinded`\n* `Charmed`\n* `Deafened`\n"),
+        "{}",
+        output,
+    );
+    assert!(output.contains("\n* `Grappled`\n"), "{}", output);
+    assert_eq!(output, sync_app().command("srd conditions").unwrap());
+
+    assert_eq!(
+        vec![AutocompleteSuggestion::new("conditions", "SRD index")],
+        sync_app().autocomplete("conditions"),
+    );
+}
@@ -0,0 +1,57 @@
+use crate::common::sync_app;
+use initiative_core::app::AutocompleteSuggestion;
+
+#[test]
+fn sight_rot() {
+    let output = sync_app().command("Sight Rot").unwrap();
+
+    assert!(
+        output.starts_with(
+            "# Sight Rot\n\nThis painful infection causes bleeding from the eyes and eventually blinds the victim.\n\n",
+        ),
+        "{}",
+        output,
+    );
+    assert!(
+        output
+            .ends_with("\n\n*Sight Rot is Open Game Content subject to the `Open Game License`.*",),
+        "{}",
+        output,
+    );
+
+    assert_eq!(output, sync_app().command("srd disease sight rot").unwrap());
+
+    assert_eq!(
+        vec![AutocompleteSuggestion::new("Sight Rot", "SRD disease")],
+        sync_app().autocomplete("sight"),
+    );
+}
+
+#[test]
+fn cackle_fever_dice() {
+    let output = sync_app().command("cackle fever").unwrap();
+    assert!(
+        output.contains("Symptoms manifest `1d4` hours after infection"),
+        "{}",
+        output,
+    );
+}
+
+#[test]
+fn diseases() {
+    assert_eq!(
+        "\
+# Diseases
+* `Cackle Fever`
+* `Sewer Plague`
+* `Sight Rot`
+
+*This listing is Open Game Content subject to the `Open Game License`.*",
+        sync_app().command("diseases").unwrap(),
+    );
+
+    assert_eq!(
+        vec![AutocompleteSuggestion::new("diseases", "SRD index")],
+        sync_app().autocomplete("disea"),
+    );
+}
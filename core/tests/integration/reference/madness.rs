@@ -0,0 +1,63 @@
+use crate::common::sync_app;
+use initiative_core::app::AutocompleteSuggestion;
+
+#[test]
+fn madness() {
+    let output = sync_app().command("madness").unwrap();
+
+    assert!(
+        output.starts_with("# Madness\n\n***Going Mad.***"),
+        "{}",
+        output
+    );
+    assert!(
+        output.contains(
+            "subjected to an effect from the Short-Term Madness table for `1d10` minutes"
+        ),
+        "{}",
+        output,
+    );
+    assert_eq!(output, sync_app().command("srd madness Madness").unwrap());
+}
+
+#[test]
+fn short_term_madness() {
+    let output = sync_app().command("short-term madness").unwrap();
+
+    assert!(
+        output.contains("\n| 81–90 | The character is stunned. |\n"),
+        "{}",
+        output,
+    );
+    assert!(
+        output.ends_with(
+            "\n\n*Short-Term Madness is Open Game Content subject to the `Open Game License`.*",
+        ),
+        "{}",
+        output,
+    );
+
+    assert_eq!(
+        vec![AutocompleteSuggestion::new(
+            "Short-Term Madness",
+            "SRD madness",
+        )],
+        sync_app().autocomplete("short-term"),
+    );
+}
+
+#[test]
+fn autocomplete() {
+    assert_eq!(
+        vec![AutocompleteSuggestion::new(
+            "Indefinite Madness",
+            "SRD madness",
+        )],
+        sync_app().autocomplete("indefinite"),
+    );
+
+    assert_eq!(
+        vec![AutocompleteSuggestion::new("Madness", "SRD madness"),],
+        sync_app().autocomplete("madn"),
+    );
+}
@@ -0,0 +1,62 @@
+use crate::common::sync_app;
+use initiative_core::app::AutocompleteSuggestion;
+
+#[test]
+fn magic_items() {
+    let output = sync_app().command("magic items").unwrap();
+
+    assert!(output.starts_with("# Magic items\n* `"), "{}", output);
+    assert!(
+        output.contains("\n* `Rod of Rulership` (rare, requires attunement)\n"),
+        "{}",
+        output,
+    );
+    assert!(
+        output.contains("\n* `Deck of Many Things` (legendary)\n"),
+        "{}",
+        output,
+    );
+    assert!(output
+        .ends_with("\n\n*This listing is Open Game Content subject to the `Open Game License`.*"));
+
+    assert_eq!(output, sync_app().command("srd magic items").unwrap());
+}
+
+#[test]
+fn magic_items_by_rarity() {
+    let output = sync_app().command("magic items rare").unwrap();
+
+    assert!(output.starts_with("# Rare magic items\n* `"), "{}", output);
+    assert!(
+        output.contains("\n* `Rod of Rulership` (requires attunement)\n"),
+        "{}",
+        output,
+    );
+    assert!(!output.contains("`Deck of Many Things`"), "{}", output);
+
+    assert_eq!(output, sync_app().command("srd magic items rare").unwrap());
+    assert_eq!(output, sync_app().command("Magic Items Rare").unwrap());
+
+    assert!(sync_app()
+        .command("magic items very rare")
+        .unwrap()
+        .starts_with("# Very rare magic items\n"));
+
+    assert!(sync_app().command("magic items shiny").is_err());
+}
+
+#[test]
+fn autocomplete() {
+    assert_eq!(
+        vec![
+            AutocompleteSuggestion::new("magic items", "SRD index"),
+            AutocompleteSuggestion::new("magic items artifact", "SRD index by rarity"),
+            AutocompleteSuggestion::new("magic items common", "SRD index by rarity"),
+            AutocompleteSuggestion::new("magic items legendary", "SRD index by rarity"),
+            AutocompleteSuggestion::new("magic items rare", "SRD index by rarity"),
+            AutocompleteSuggestion::new("magic items uncommon", "SRD index by rarity"),
+            AutocompleteSuggestion::new("magic items very rare", "SRD index by rarity"),
+        ],
+        sync_app().autocomplete("magic items"),
+    );
+}
@@ -1,8 +1,15 @@
+mod class;
 mod condition;
+mod disease;
 mod item;
 mod item_category;
+mod madness;
 mod magic_item;
+mod magic_items;
+mod monster;
 mod open_game_license;
+mod scale;
+mod search;
 mod spell;
 mod spells;
 mod traits;
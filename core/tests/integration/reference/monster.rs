@@ -0,0 +1,26 @@
+use crate::common::sync_app;
+use initiative_core::app::AutocompleteSuggestion;
+
+#[test]
+fn srd_monster() {
+    let output = sync_app().command("srd monster goblin").unwrap();
+
+    assert!(output.starts_with("# Goblin\n"), "{}", output);
+    assert!(output.contains("**Challenge:** 1/4 (50 XP)"), "{}", output);
+    assert!(
+        output.ends_with("\n\n*Goblin is Open Game Content subject to the `Open Game License`.*"),
+        "{}",
+        output,
+    );
+}
+
+#[test]
+fn autocomplete() {
+    assert_eq!(
+        vec![AutocompleteSuggestion::new(
+            "srd monster Goblin",
+            "SRD monster",
+        )],
+        sync_app().autocomplete("srd monster gob"),
+    );
+}
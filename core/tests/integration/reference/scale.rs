@@ -0,0 +1,35 @@
+use crate::common::sync_app;
+
+#[test]
+fn srd_monster_can_be_scaled() {
+    let mut app = sync_app();
+
+    {
+        let output = app.command("scale goblin to cr 3").unwrap();
+        assert!(output.starts_with("# Goblin\n"), "{}", output);
+        assert!(output.contains("**Challenge:** 3 (700 XP)"), "{}", output);
+        assert!(
+            output.ends_with(
+                "_Use `scale Goblin to cr 3 as [name]` to save this as a homebrew monster._"
+            ),
+            "{}",
+            output,
+        );
+    }
+
+    {
+        let output = app.command("scale goblin to cr 3 as Goblin Boss").unwrap();
+        assert!(output.starts_with("# Goblin Boss\n"), "{}", output);
+
+        let output = app.command("goblin boss").unwrap();
+        assert!(output.contains("**Challenge:** 3 (700 XP)"), "{}", output);
+    }
+}
+
+#[test]
+fn challenge_rating_out_of_range() {
+    assert_eq!(
+        "\"100\" isn't a challenge rating. Challenge ratings range from 0 to 30, including 1/8, 1/4, and 1/2.",
+        sync_app().command("scale goblin to cr 100").unwrap_err(),
+    );
+}
@@ -0,0 +1,67 @@
+use crate::common::sync_app;
+use initiative_core::app::AutocompleteSuggestion;
+
+#[test]
+fn search() {
+    let output = sync_app().command("srd search fire").unwrap();
+
+    assert!(
+        output.starts_with("# Search results for \"fire\"\n\n## "),
+        "{}",
+        output,
+    );
+    assert!(
+        output.contains("\n\n## Spells\n\n* `Fire Bolt`\n"),
+        "{}",
+        output
+    );
+    assert!(output.contains("\n\n## Magic items\n\n"), "{}", output);
+    assert!(
+        output.ends_with(
+            "\n\n*This listing is Open Game Content subject to the `Open Game License`.*"
+        ),
+        "{}",
+        output,
+    );
+}
+
+#[test]
+fn search_monsters() {
+    let output = sync_app().command("srd search goblin").unwrap();
+
+    assert!(
+        output.contains("\n\n## Monsters\n\n* `srd monster Goblin`"),
+        "{}",
+        output,
+    );
+}
+
+#[test]
+fn search_ranks_names_first() {
+    let output = sync_app().command("srd search sight rot").unwrap();
+
+    assert!(
+        output.starts_with("# Search results for \"sight rot\"\n\n## Diseases\n\n* `Sight Rot`"),
+        "{}",
+        output,
+    );
+}
+
+#[test]
+fn search_no_results() {
+    assert_eq!(
+        Err("Nothing in the SRD matches \"xyzzy\".".to_string()),
+        sync_app().command("srd search xyzzy"),
+    );
+}
+
+#[test]
+fn autocomplete() {
+    assert_eq!(
+        vec![AutocompleteSuggestion::new(
+            "srd search [terms]",
+            "search the SRD by keyword",
+        )],
+        sync_app().autocomplete("srd se"),
+    );
+}
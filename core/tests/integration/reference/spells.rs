@@ -18,7 +18,47 @@ fn spells() {
     assert_eq!(322, output.lines().count(), "{}", output);
 
     assert_eq!(
-        vec![AutocompleteSuggestion::new("spells", "SRD index")],
+        vec![
+            AutocompleteSuggestion::new("spells", "SRD index"),
+            AutocompleteSuggestion::new("spells [class] [school] [level]", "filter SRD spells"),
+        ],
         sync_app().autocomplete("Spells"),
     );
 }
+
+#[test]
+fn spells_filtered() {
+    let output = sync_app().command("spells wizard 3").unwrap();
+    assert!(
+        output.starts_with(
+            "\
+# 3rd-level wizard spells
+
+| Name | Level | School |
+|---|--:|---|
+| `Animate Dead` | 3rd | Necromancy |
+",
+        ),
+        "{}",
+        output,
+    );
+    assert!(
+        output.contains("\n| `Fireball` | 3rd | Evocation |\n"),
+        "{}",
+        output
+    );
+    assert!(!output.contains("`Cure Wounds`"), "{}", output);
+    assert_eq!(output, sync_app().command("srd spells wizard 3").unwrap());
+
+    let output = sync_app().command("spells necromancy cantrips").unwrap();
+    assert!(
+        output.starts_with("# Necromancy cantrips\n\n| Name | Level | School |\n|---|--:|---|\n| `Chill Touch` | cantrip | Necromancy |"),
+        "{}",
+        output,
+    );
+
+    assert_eq!(
+        Err("There are no SRD spells matching \"paladin cantrips\".".to_string()),
+        sync_app().command("spells paladin cantrips"),
+    );
+}
@@ -0,0 +1,49 @@
+use crate::common::sync_app;
+
+#[test]
+fn temples_are_dedicated_to_the_pantheon() {
+    let mut app = sync_app();
+
+    app.command("shrine named The Lonely Stone").unwrap();
+    assert!(!app
+        .command("The Lonely Stone")
+        .unwrap()
+        .contains("**Worship:**"));
+
+    let output = app.command("create pantheon").unwrap();
+    assert!(output.starts_with("# Pantheon\n\n## "), "{}", output);
+
+    let output = app.command("shrine named The Glowing Altar").unwrap();
+    assert!(output.contains("\n\n**Worship:** "), "{}", output);
+
+    let output = app.command("create deity of war").unwrap();
+    let name = output
+        .strip_prefix("## ")
+        .and_then(|s| s.split(',').next())
+        .unwrap()
+        .to_string();
+    assert!(output.contains("\n*Domains: war"), "{}", output);
+
+    assert_eq!(
+        format!(
+            "`The Lonely Stone` is now dedicated to **{}**, {}. Use `undo` to reverse this.",
+            name,
+            output[name.len() + 5..].split('\n').next().unwrap(),
+        ),
+        app.command(&format!("dedicate the lonely stone to {}", name))
+            .unwrap(),
+    );
+    assert!(app
+        .command("The Lonely Stone")
+        .unwrap()
+        .contains(&format!("\n\n**Worship:** {}", name)));
+
+    let output = app.command("pantheon").unwrap();
+    assert!(output.contains(&format!("**{}**, ", name)), "{}", output);
+
+    app.command("undo").unwrap();
+    assert!(!app
+        .command("The Lonely Stone")
+        .unwrap()
+        .contains("**Worship:**"));
+}
@@ -0,0 +1,58 @@
+use crate::common::sync_app;
+
+#[test]
+fn scene_is_recapped() {
+    let mut app = sync_app();
+
+    app.command("session start").unwrap();
+
+    assert_eq!(
+        "Scene 1, \"Ambush at the bridge\", has started. Anything you create or roll until `scene end` will be part of it. Use `undo` to reverse this.",
+        app.command("scene start Ambush at the bridge").unwrap(),
+    );
+
+    app.command("+10m").unwrap();
+    app.command("npc named Gottfried").unwrap();
+    app.command("roll d20").unwrap();
+
+    let output = app.command("scene end").unwrap();
+    assert!(
+        output.starts_with(
+            "# Scene 1: Ambush at the bridge
+
+**Game time:** day 1 at 8:00:00 am to day 1 at 8:10:00 am (10 minutes)\\
+**Session:** `session 1`\\
+**Commands run:** 3
+
+## Added to the Journal
+
+`Gottfried`
+
+## Rolls
+
+* `roll d20`: "
+        ),
+        "{}",
+        output,
+    );
+    assert!(
+        output.ends_with(
+            "\n\n_The recap has been saved. Use `scene 1` to see it again, or `undo` to reverse this._"
+        ),
+        "{}",
+        output,
+    );
+
+    assert_eq!(
+        "# Scenes\n\n`scene 1`: Ambush at the bridge",
+        app.command("scenes").unwrap(),
+    );
+    assert_eq!(
+        output.split_once("\n\n_").unwrap().0,
+        app.command("scene 1").unwrap(),
+    );
+    assert_eq!(
+        "*No scene is in progress. Use `scene start [name]` to start one, or `scenes` to see the recaps of earlier scenes.*",
+        app.command("scene").unwrap(),
+    );
+}
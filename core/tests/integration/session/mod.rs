@@ -0,0 +1,47 @@
+use crate::common::sync_app;
+use std::cell::Cell;
+
+thread_local! {
+    static NOW: Cell<u64> = const { Cell::new(0) };
+}
+
+#[test]
+fn session_is_recapped() {
+    let mut app = sync_app();
+    app.set_clock(|| NOW.with(Cell::get));
+    NOW.with(|now| now.set(1_000_000));
+
+    assert_eq!(
+        "Session 1 has started. It's day 1 at 8:00:00 am in the game world. Use `session end` when you're done to get a recap, or `undo` to reverse this.",
+        app.command("session start").unwrap(),
+    );
+
+    app.command("+2h").unwrap();
+    app.command("inn named Moonbright").unwrap();
+
+    NOW.with(|now| now.set(1_000_000 + 9_900_000));
+
+    assert_eq!(
+        "# Session 1
+
+**Duration:** 2 hours, 45 minutes\\
+**Game time:** day 1 at 8:00:00 am to day 1 at 10:00:00 am (2 hours)\\
+**Commands run:** 2
+
+## Added to the Journal
+
+`Moonbright`
+
+_The recap has been saved. Use `session 1` to see it again, or `undo` to reverse this._",
+        app.command("session end").unwrap(),
+    );
+
+    assert_eq!(
+        "# Sessions\n\n`session 1` (2 hours, 45 minutes)",
+        app.command("sessions").unwrap(),
+    );
+    assert_eq!(
+        "*No session is in progress. Use `session start` to start one, or `sessions` to see the recaps of earlier sessions.*",
+        app.command("session").unwrap(),
+    );
+}
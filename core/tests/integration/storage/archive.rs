@@ -0,0 +1,93 @@
+use crate::common::sync_app;
+
+#[test]
+fn archive_hides_from_journal() {
+    let mut app = sync_app();
+    app.command("npc named Gottfried").unwrap();
+    app.command("npc named Heinrich").unwrap();
+
+    let output = app.command("archive Gottfried").unwrap();
+    assert!(output.starts_with("Gottfried was archived."), "{}", output);
+
+    let output = app.command("journal").unwrap();
+    assert!(!output.contains("Gottfried"), "{}", output);
+    assert!(output.contains("`Heinrich`"), "{}", output);
+    assert!(
+        output.ends_with("*1 archived entry is not shown. To list it, use `journal archived`.*"),
+        "{}",
+        output,
+    );
+
+    let output = app.command("journal archived").unwrap();
+    assert!(output.starts_with("# Archive"), "{}", output);
+    assert!(output.contains("`Gottfried`"), "{}", output);
+    assert!(!output.contains("Heinrich"), "{}", output);
+
+    assert_eq!(
+        "Gottfried is already archived.",
+        app.command("archive Gottfried").unwrap_err(),
+    );
+
+    let output = app.command("load Gottfried").unwrap();
+    assert!(
+        output.contains("_Gottfried is archived. Use `unarchive Gottfried` to restore"),
+        "{}",
+        output,
+    );
+
+    assert_eq!(
+        "Gottfried was restored to your journal. Use `undo` to reverse this.",
+        app.command("unarchive Gottfried").unwrap(),
+    );
+    assert!(app.command("journal").unwrap().contains("`Gottfried`"));
+
+    app.command("undo").unwrap();
+    assert!(!app.command("journal").unwrap().contains("Gottfried"));
+
+    assert_eq!(
+        "Heinrich is not archived.",
+        app.command("unarchive Heinrich").unwrap_err(),
+    );
+}
+
+#[test]
+fn archive_requires_a_saved_thing() {
+    let mut app = sync_app();
+    assert_eq!(
+        "There is no entity named \"Potato Johnson\".",
+        app.command("archive Potato Johnson").unwrap_err(),
+    );
+}
+
+#[test]
+fn delete_suggests_archive_for_connected_things() {
+    let mut app = sync_app();
+    let backup_data = serde_json::from_str(include_str!("export_import/v2.json")).unwrap();
+    app.bulk_import(backup_data).unwrap();
+
+    let output = app.command("delete Faman Halin").unwrap();
+    assert!(
+        output.starts_with("Faman Halin is connected to"),
+        "{}",
+        output,
+    );
+    assert!(output.contains("~archive~"), "{}", output);
+    assert!(output.contains("~delete~"), "{}", output);
+
+    let output = app.command("archive").unwrap();
+    assert!(
+        output.starts_with("Faman Halin was archived."),
+        "{}",
+        output
+    );
+    assert!(app.command("load Faman Halin").is_ok());
+
+    app.command("unarchive Faman Halin").unwrap();
+    assert!(app
+        .command("delete Faman Halin")
+        .unwrap()
+        .starts_with("Faman Halin is connected to"));
+    let output = app.command("delete").unwrap();
+    assert!(output.contains("was successfully deleted"), "{}", output);
+    assert!(app.command("load Faman Halin").is_err());
+}
@@ -0,0 +1,48 @@
+use crate::common::sync_app_with_data_store;
+use initiative_core::MemoryDataStore;
+
+#[test]
+fn recent_entries_are_restored_with_autosave() {
+    let data_store = MemoryDataStore::default();
+
+    let journal = {
+        let mut app = sync_app_with_data_store(data_store.clone());
+        app.command("config autosave on").unwrap();
+        app.command("npc").unwrap();
+        app.command("npc named Gottfried").unwrap();
+
+        let journal = app.command("journal").unwrap();
+        assert!(journal.contains("\n\n## Unsaved\n"), "{}", journal);
+        assert!(
+            journal.ends_with("*Unsaved entries are kept by autosave between sessions, but aren't part of your journal. To keep one, use `save [name]`.*"),
+            "{}",
+            journal,
+        );
+        journal
+    };
+
+    let mut app = sync_app_with_data_store(data_store);
+    assert_eq!(journal, app.command("journal").unwrap());
+}
+
+#[test]
+fn recent_entries_are_forgotten_without_autosave() {
+    let data_store = MemoryDataStore::default();
+
+    {
+        let mut app = sync_app_with_data_store(data_store.clone());
+        app.command("config autosave on").unwrap();
+        app.command("npc").unwrap();
+        app.command("config autosave off").unwrap();
+
+        let journal = app.command("journal").unwrap();
+        assert!(!journal.contains("Unsaved"), "{}", journal);
+    }
+
+    let mut app = sync_app_with_data_store(data_store);
+    app.command("config autosave on").unwrap();
+    assert_eq!(
+        "# Journal\n\n*Your journal is currently empty.*",
+        app.command("journal").unwrap(),
+    );
+}
@@ -1,4 +1,4 @@
-use crate::common::{sync_app, sync_app_with_invalid_data_store};
+use crate::common::{get_name, sync_app, sync_app_with_invalid_data_store};
 
 #[test]
 fn it_shows_a_message_when_empty() {
@@ -112,3 +112,110 @@ fn it_shows_alphabetized_results() {
 
     assert!(output_iter.next().is_none());
 }
+
+#[test]
+fn it_filters_by_query() {
+    let mut app = sync_app();
+
+    for (name, description, age) in [
+        ("Aerendyl", "elf", 250),
+        ("Caelynn", "elf", 80),
+        ("Gimli", "dwarf", 140),
+    ] {
+        app.command(&format!("{} named {}", description, name))
+            .unwrap();
+        app.command(&format!("{}'s age is {}", name, age)).unwrap();
+    }
+
+    assert_eq!(
+        "\
+# Journal
+
+*Entries where species = elf and age > 100*
+
+| Name | Species | Age |
+|---|---|---|
+| `Aerendyl` | elf | 250 years |
+
+*1 entry matches.*",
+        app.command("journal where species = elf and age > 100")
+            .unwrap(),
+    );
+
+    let output = app.command("journal where age >= 100").unwrap();
+    assert!(
+        output.contains(
+            "| `Aerendyl` | 250 years |\n\
+            | `Gimli` | 140 years |\n\n\
+            *2 entries match.*",
+        ),
+        "{}",
+        output,
+    );
+
+    let output = app.command("journal where species = gnome").unwrap();
+    assert!(output.contains("*No entries match."), "{}", output);
+}
+
+#[test]
+fn it_shows_sorted_and_grouped_results() {
+    let mut app = sync_app();
+
+    app.command("inn named The Silver Eel").unwrap();
+    app.command("elf named Aerendyl").unwrap();
+    app.command("+1d").unwrap();
+    app.command("dwarf named Gimli").unwrap();
+
+    let titles = |output: &str| -> Vec<String> {
+        output
+            .lines()
+            .filter_map(|line| line.strip_prefix("## "))
+            .map(|line| line.to_string())
+            .collect()
+    };
+
+    assert_eq!(
+        vec!["A", "G", "T"],
+        titles(&app.command("journal alphabetical").unwrap()),
+    );
+    assert_eq!(
+        vec!["NPCs", "Inn"],
+        titles(&app.command("journal by type").unwrap()),
+    );
+
+    let output = app.command("journal recent").unwrap();
+    assert_eq!(
+        vec!["Created on Day 2", "Created on Day 1"],
+        titles(&output),
+        "{}",
+        output,
+    );
+    assert!(
+        output.find("`Aerendyl`").unwrap() < output.find("`The Silver Eel`").unwrap(),
+        "{}",
+        output,
+    );
+
+    // Crew members are generated aboard their ship.
+    app.command("ship named The Wave").unwrap();
+    app.command("crew The Wave").unwrap();
+    let crew_member = get_name(&app.command("1").unwrap());
+    app.command(&format!("save {}", crew_member)).unwrap();
+
+    let output = app.command("journal by location").unwrap();
+    assert_eq!(
+        vec!["The Wave", "No Location"],
+        titles(&output),
+        "{}",
+        output,
+    );
+    assert!(
+        output
+            .split_once("## No Location")
+            .unwrap()
+            .0
+            .contains(&format!("`{}`", crew_member)),
+        "{}",
+        output,
+    );
+}
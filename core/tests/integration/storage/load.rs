@@ -1,5 +1,5 @@
 use crate::common::{get_name, sync_app, sync_app_with_data_store};
-use initiative_core::MemoryDataStore;
+use initiative_core::{MemoryDataStore, Thing};
 
 #[test]
 fn npc_can_be_loaded_from_storage() {
@@ -72,3 +72,105 @@ fn npc_can_be_loaded_from_storage_with_location() {
         app.command("Faman Halin").unwrap(),
     );
 }
+
+#[test]
+fn ambiguous_name_offers_disambiguation() {
+    let data_store = MemoryDataStore::default();
+
+    {
+        let mut things = data_store.things.borrow_mut();
+        [
+            r#"{"type":"Place","uuid":"00000000-0000-0000-0000-000000000001","location_uuid":null,"subtype":"inn","name":"The Raven","description":null}"#,
+            r#"{"type":"Npc","uuid":"00000000-0000-0000-0000-000000000002","name":"The Raven","gender":null,"age":null,"age_years":null,"size":null,"species":null,"ethnicity":null,"location_uuid":null}"#,
+        ]
+        .into_iter()
+        .for_each(|json| {
+            let thing: Thing = serde_json::from_str(json).unwrap();
+            things.insert(*thing.uuid().unwrap(), thing);
+        });
+    }
+
+    let mut app = sync_app_with_data_store(data_store);
+    app.init();
+
+    let output = app.command("The Raven").unwrap();
+    assert!(
+        output.starts_with("# There are several entries named \"The Raven\""),
+        "{}",
+        output,
+    );
+    assert!(output.contains("~1~ "), "{}", output);
+    assert!(output.contains("~2~ "), "{}", output);
+    assert!(
+        output.ends_with("_Type the number of the entry you want to load._"),
+        "{}",
+        output,
+    );
+
+    let first_output = app.command("1").unwrap();
+    app.command("The Raven").unwrap();
+    let second_output = app.command("2").unwrap();
+
+    assert!(
+        first_output.contains("class=\"thing-box npc\"")
+            != second_output.contains("class=\"thing-box npc\""),
+        "{}\n\n{}",
+        first_output,
+        second_output,
+    );
+    assert!(
+        first_output.contains("class=\"thing-box place\"")
+            != second_output.contains("class=\"thing-box place\""),
+        "{}\n\n{}",
+        first_output,
+        second_output,
+    );
+}
+
+#[test]
+fn load_lists_backlinks() {
+    let mut app = sync_app();
+    app.command("npc named Gottfried").unwrap();
+    app.command("inn named The Silver Eel").unwrap();
+
+    assert!(!app.command("Gottfried").unwrap().contains("Referenced by"));
+
+    app.command("The Silver Eel's description is run by Gottfried and his dog")
+        .unwrap();
+
+    let output = app.command("Gottfried").unwrap();
+    assert!(
+        output.contains("\n\n**Referenced by:** `The Silver Eel`"),
+        "{}",
+        output,
+    );
+
+    // The index is kept up to date as the journal changes.
+    app.command("The Silver Eel's description is a quiet spot")
+        .unwrap();
+    assert!(!app.command("Gottfried").unwrap().contains("Referenced by"));
+
+    app.command("undo").unwrap();
+    assert!(app.command("Gottfried").unwrap().contains("Referenced by"));
+}
+
+#[test]
+fn names_are_matched_regardless_of_case_accents_and_spacing() {
+    let mut app = sync_app();
+    app.command("npc named Éowyn").unwrap();
+
+    for input in [
+        "load   Éowyn  ",
+        "load eowyn",
+        "load  EOWYN",
+        "Éowyn  ",
+        "  eowyn",
+    ] {
+        let output = app.command(input).unwrap();
+        assert!(output.contains("Éowyn"), "{}: {}", input, output);
+    }
+
+    app.command("inn named The  Yawning Portal").unwrap();
+    let output = app.command("load the yawning   portal").unwrap();
+    assert!(output.contains("Yawning Portal"), "{}", output);
+}
@@ -0,0 +1,81 @@
+use crate::common::{get_name, sync_app};
+
+#[test]
+fn merge_combines_fields() {
+    let mut app = sync_app();
+    app.command("inn named The Silver Eel").unwrap();
+    app.command("The Silver Eel's description is a quiet spot")
+        .unwrap();
+
+    // A generated inn's fields are unlocked, so the saved entry's values take precedence.
+    let name = get_name(&app.command("inn").unwrap());
+
+    let output = app
+        .command(&format!("merge The Silver Eel into {}", name))
+        .unwrap();
+    assert!(
+        output.starts_with(&format!("The Silver Eel was merged into `{}`.\n\n", name,)),
+        "{}",
+        output,
+    );
+    assert!(output.contains(&format!("# {}", name)), "{}", output);
+    assert!(output.contains("a quiet spot"), "{}", output);
+    assert!(
+        output.ends_with("\n\n_Use `undo` to reverse this._"),
+        "{}",
+        output,
+    );
+
+    assert_eq!(
+        "No matches for \"The Silver Eel\"",
+        app.command("load The Silver Eel").unwrap_err(),
+    );
+
+    app.command("undo").unwrap();
+    assert!(app.command("load The Silver Eel").is_ok());
+    assert!(!app
+        .command(&format!("load {}", name))
+        .unwrap()
+        .contains("a quiet spot"));
+}
+
+#[test]
+fn merge_moves_occupants() {
+    let mut app = sync_app();
+    app.command("airship named The Windrunner").unwrap();
+    app.command("airship named Windrunner").unwrap();
+    app.command("crew The Windrunner").unwrap();
+    let crew_member = get_name(&app.command("1").unwrap());
+
+    let output = app.command("merge The Windrunner into Windrunner").unwrap();
+    assert!(
+        output.contains(&format!("`{}`", crew_member)) && output.contains(" now located there."),
+        "{}",
+        output,
+    );
+
+    let output = app.command(&format!("load {}", crew_member)).unwrap();
+    assert!(output.contains("`Windrunner`"), "{}", output);
+    assert!(!output.contains("`The Windrunner`"), "{}", output);
+}
+
+#[test]
+fn merge_errors() {
+    let mut app = sync_app();
+    app.command("npc named Gottfried").unwrap();
+    app.command("inn named The Silver Eel").unwrap();
+
+    assert_eq!(
+        "Gottfried can't be merged into itself.",
+        app.command("merge Gottfried into gottfried").unwrap_err(),
+    );
+    assert_eq!(
+        "Gottfried is a character and The Silver Eel is a place, so they can't be merged.",
+        app.command("merge Gottfried into The Silver Eel")
+            .unwrap_err(),
+    );
+    assert_eq!(
+        "There is no entity named \"Potato\".",
+        app.command("merge Potato into Gottfried").unwrap_err(),
+    );
+}
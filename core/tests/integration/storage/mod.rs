@@ -1,7 +1,13 @@
+mod archive;
+mod autosave;
 mod change;
 mod export_import;
 mod journal;
 mod load;
+mod merge;
+mod pin;
+mod report;
+mod stats;
 mod undo_redo;
 
 use crate::common::SyncApp;
@@ -0,0 +1,79 @@
+use crate::common::sync_app;
+
+#[test]
+fn pin_lists_first_in_journal() {
+    let mut app = sync_app();
+    app.command("npc named Aldous Huxley").unwrap();
+    app.command("npc named Gottfried Wolfsbane").unwrap();
+
+    assert_eq!(
+        "Gottfried Wolfsbane was pinned to the top of your journal, and can be loaded with just `Gottfried`. Use `undo` to reverse this.",
+        app.command("pin Gottfried Wolfsbane").unwrap(),
+    );
+
+    let output = app.command("journal").unwrap();
+    assert!(
+        output.starts_with("# Journal\n\n## Pinned\n")
+            && output.find("`Gottfried Wolfsbane`") < output.find("`Aldous Huxley`"),
+        "{}",
+        output,
+    );
+    assert_eq!(1, output.matches("Gottfried").count(), "{}", output);
+
+    let output = app.command("journal alphabetical").unwrap();
+    assert!(
+        output.starts_with("# Journal\n\n## Pinned\n")
+            && output.find("`Gottfried Wolfsbane`") < output.find("`Aldous Huxley`"),
+        "{}",
+        output,
+    );
+
+    assert_eq!(
+        "Gottfried Wolfsbane is already pinned.",
+        app.command("pin Gottfried Wolfsbane").unwrap_err(),
+    );
+
+    let output = app.command("unpin Gottfried Wolfsbane").unwrap();
+    assert_eq!(
+        "Gottfried Wolfsbane was unpinned. Use `undo` to reverse this.",
+        output,
+    );
+    assert!(!app.command("journal").unwrap().contains("## Pinned"));
+
+    app.command("undo").unwrap();
+    assert!(app.command("journal").unwrap().contains("## Pinned"));
+}
+
+#[test]
+fn pin_loads_by_first_name() {
+    let mut app = sync_app();
+    app.command("npc named Gottfried Wolfsbane").unwrap();
+
+    assert!(app.command("Gottfried").is_err());
+
+    app.command("pin Gottfried Wolfsbane").unwrap();
+
+    let output = app.command("Gottfried").unwrap();
+    assert!(output.contains("# Gottfried Wolfsbane"), "{}", output,);
+
+    let suggestions = app.autocomplete("gott");
+    assert!(
+        suggestions
+            .iter()
+            .any(|suggestion| suggestion.term == "Gottfried"
+                && suggestion.summary.ends_with(" (pinned)")),
+        "{:?}",
+        suggestions,
+    );
+}
+
+#[test]
+fn pin_requires_a_saved_thing() {
+    let mut app = sync_app();
+    app.command("npc").unwrap();
+
+    assert_eq!(
+        "There is no entity named \"Potato Johnson\".",
+        app.command("pin Potato Johnson").unwrap_err(),
+    );
+}
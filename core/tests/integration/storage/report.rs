@@ -0,0 +1,12 @@
+use crate::common::sync_app;
+
+#[test]
+fn report_without_problems() {
+    let mut app = sync_app();
+    app.command("npc named Gottfried").unwrap();
+
+    assert_eq!(
+        "# Storage report\n\n*No problems were found with your journal.*",
+        app.command("storage report").unwrap(),
+    );
+}
@@ -0,0 +1,38 @@
+use crate::common::sync_app;
+
+#[test]
+fn stats_world_summarizes_journal() {
+    let mut app = sync_app();
+
+    let output = app.command("stats world").unwrap();
+    assert!(
+        output.starts_with("# World statistics\n\n*Your journal is empty. Statistics will appear here once you've saved some characters and places.*"),
+        "{}",
+        output,
+    );
+
+    app.command("town named Dunwich").unwrap();
+    app.command("elf named Aerendyl").unwrap();
+    app.command("dwarf named Gimli").unwrap();
+
+    let output = app.command("STATS WORLD").unwrap();
+    assert!(
+        output.starts_with(
+            "# World statistics\n\n*3 journal entries: 2 characters and 1 place.*\n\n## Characters\n\n**Species:** 1 dwarf, 1 elf\\\n"
+        ),
+        "{}",
+        output,
+    );
+    assert!(
+        output.contains("\n\n## Places\n\n**Type:** 1 town\n\n"),
+        "{}",
+        output
+    );
+    assert!(
+        output.contains("\n\n## Settlements\n\nYour demographics expect "),
+        "{}",
+        output,
+    );
+    assert!(output.contains("\n* `Dunwich`: 0 residents"), "{}", output);
+    assert!(output.contains("\n\n## Gaps\n\n"), "{}", output);
+}
@@ -0,0 +1,92 @@
+use crate::common::{sync_app, sync_app_with_data_store};
+use initiative_core::MemoryDataStore;
+
+#[test]
+fn table_can_be_added_and_rolled() {
+    let mut app = sync_app();
+
+    assert_eq!(
+        "Added the **rumors** table. Use `roll on rumors` to roll on it, or `undo` to reverse this.",
+        app.command("table add rumors: 1-3 The mill is haunted, 4-6 Wolves, or worse")
+            .unwrap(),
+    );
+
+    let output = app.command("roll on Rumors").unwrap();
+    assert!(
+        output.ends_with(": The mill is haunted") || output.ends_with(": Wolves, or worse"),
+        "{}",
+        output,
+    );
+
+    assert_eq!(
+        "# Tables\n\n* `table rumors` (d6)",
+        app.command("tables").unwrap(),
+    );
+
+    assert_eq!(
+        "Successfully undid changing the tables. Use `redo` to reverse this.",
+        app.command("undo").unwrap(),
+    );
+    assert!(app.command("roll on rumors").is_err());
+}
+
+#[test]
+fn table_rejects_invalid_ranges() {
+    let mut app = sync_app();
+
+    assert_eq!(
+        "\"5-3 a\" doesn't begin with a valid number or range.",
+        app.command("table add t: 5-3 a").unwrap_err(),
+    );
+    assert_eq!(
+        "\"1-4294967295 a\" doesn't begin with a valid number or range.",
+        app.command("table add big: 1-4294967295 a").unwrap_err(),
+    );
+    assert!(app.command("roll on t").is_err());
+}
+
+#[test]
+fn roll_still_rolls_dice() {
+    let mut app = sync_app();
+    assert_eq!("[1] = **1**", app.command("roll 1d1").unwrap());
+}
+
+#[test]
+fn tables_are_persisted() {
+    let data_store = MemoryDataStore::default();
+
+    {
+        let mut app = sync_app_with_data_store(data_store.clone());
+        app.command("table add weather: Rain").unwrap();
+    }
+
+    let mut app = sync_app_with_data_store(data_store);
+    assert_eq!(
+        "**weather** (rolled 1 on a d1): Rain",
+        app.command("roll on weather").unwrap(),
+    );
+}
+
+#[test]
+fn tables_override_generators() {
+    let mut app = sync_app();
+    app.command("table add inn names: The Prancing Pony")
+        .unwrap();
+
+    let output = app.command("inn").unwrap();
+    assert!(output.contains("# The Prancing Pony"), "{}", output);
+}
+
+#[test]
+fn tables_add_to_word_pools() {
+    let mut app = sync_app();
+    app.set_seed(0);
+    app.command(&format!(
+        "table add food words: {}",
+        vec!["Quince"; 500].join(", "),
+    ))
+    .unwrap();
+
+    let output = app.command("create 10 inns").unwrap();
+    assert!(output.to_lowercase().contains("quince"), "{}", output);
+}
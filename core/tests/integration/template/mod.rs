@@ -0,0 +1,86 @@
+use crate::common::{sync_app, sync_app_with_data_store};
+use initiative_core::MemoryDataStore;
+
+#[test]
+fn template_can_be_saved_and_created() {
+    let mut app = sync_app();
+
+    assert_eq!(
+        "Saved the **city-guard** template. Use `create city-guard` to use it, or `undo` to reverse this.",
+        app.command("template save city-guard = human adult").unwrap(),
+    );
+
+    {
+        let output = app.command("create city-guard named Bob").unwrap();
+        assert!(output.contains("# Bob\n*adult human"), "{}", output);
+    }
+
+    {
+        let output = app.command("City-Guard").unwrap();
+        assert!(output.contains("*adult human"), "{}", output);
+    }
+
+    {
+        let output = app.command("create city-guard potato").unwrap();
+        assert!(output.contains("*adult human"), "{}", output);
+        assert!(
+            output.contains("\\> create city-guard **potato**"),
+            "{}",
+            output,
+        );
+    }
+
+    assert_eq!(
+        "# Templates\n\n* `create city-guard`: human adult",
+        app.command("templates").unwrap(),
+    );
+
+    assert_eq!(
+        "Deleted the **city-guard** template. Use `undo` to reverse this.",
+        app.command("template delete city-guard").unwrap(),
+    );
+    assert_eq!(
+        "Successfully undid changing the templates. Use `redo` to reverse this.",
+        app.command("undo").unwrap(),
+    );
+    assert!(app.command("templates").unwrap().contains("city-guard"));
+}
+
+#[test]
+fn template_reports_unknown_words() {
+    let mut app = sync_app();
+
+    let output = app
+        .command("template save city-guard = human adult chainmail")
+        .unwrap();
+    assert!(
+        output.starts_with("Saved the **city-guard** template."),
+        "{}",
+        output,
+    );
+    assert!(
+        output.contains("\\> human adult **chainmail**"),
+        "{}",
+        output,
+    );
+
+    assert_eq!(
+        "\"potato salad\" isn't a description of a character or place.",
+        app.command("template save spud = potato salad")
+            .unwrap_err(),
+    );
+}
+
+#[test]
+fn templates_are_persisted() {
+    let data_store = MemoryDataStore::default();
+
+    {
+        let mut app = sync_app_with_data_store(data_store.clone());
+        app.command("template save hostelry = inn").unwrap();
+    }
+
+    let mut app = sync_app_with_data_store(data_store);
+    let output = app.command("create hostelry").unwrap();
+    assert!(output.contains("\n*inn*\n"), "{}", output);
+}
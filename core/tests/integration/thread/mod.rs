@@ -0,0 +1,54 @@
+use crate::common::sync_app;
+
+#[test]
+fn threads_are_listed_by_last_touched() {
+    let mut app = sync_app();
+
+    app.command("npc named Captain Vell").unwrap();
+    app.command("thread add The cult is infiltrating the guard")
+        .unwrap();
+    app.command("thread add The baron's heir is missing")
+        .unwrap();
+
+    assert_eq!(
+        "`Captain Vell` is now involved in **The cult is infiltrating the guard**. Use `undo` to reverse this.",
+        app.command("thread the cult involves Captain Vell").unwrap(),
+    );
+
+    let output = app.command("threads").unwrap();
+    assert!(
+        output.starts_with("# Threads\n\n* **The cult is infiltrating the guard** (open), involving `Captain Vell`, _touched "),
+        "{}",
+        output,
+    );
+    assert!(
+        output.contains("\n* **The baron's heir is missing** (open), _touched "),
+        "{}",
+        output,
+    );
+
+    assert!(app
+        .command("Captain Vell")
+        .unwrap()
+        .contains("\n\n**Threads:** The cult is infiltrating the guard (open)"));
+
+    assert_eq!(
+        "**The baron's heir is missing** is now advancing. Use `undo` to reverse this.",
+        app.command("thread the baron is advancing").unwrap(),
+    );
+    assert!(app
+        .command("threads")
+        .unwrap()
+        .starts_with("# Threads\n\n* **The baron's heir is missing** (advancing)"));
+
+    app.command("undo").unwrap();
+    assert!(app
+        .command("threads")
+        .unwrap()
+        .starts_with("# Threads\n\n* **The cult is infiltrating the guard** (open)"));
+
+    assert_eq!(
+        "There is no thread called \"the dragon\". Use `threads` to list your threads.",
+        app.command("thread the dragon is resolved").unwrap_err(),
+    );
+}
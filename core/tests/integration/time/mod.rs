@@ -46,6 +46,48 @@ fn time_can_be_changed() {
     );
 }
 
+#[test]
+fn time_can_be_advanced_in_words() {
+    let mut app = sync_app();
+
+    assert_eq!(
+        "It is now day 8 at 8:00:00 am. Use `undo` to reverse.",
+        app.command("+1 week").unwrap(),
+    );
+
+    assert_eq!(
+        "It is now day 8 at 6:00:00 pm, 10 hours later. Use `undo` to reverse.",
+        app.command("advance to dusk").unwrap(),
+    );
+
+    assert_eq!(
+        "It is now day 9 at 8:00:00 am, 14 hours later. Use `undo` to reverse.",
+        app.command("next morning").unwrap(),
+    );
+
+    assert_eq!(
+        "It is now day 9 at 6:30:00 pm, 10 hours, 30 minutes later. Use `undo` to reverse.",
+        app.command("at 6:30pm").unwrap(),
+    );
+
+    app.command("config dawn 5am").unwrap();
+    assert_eq!(
+        "It is now day 10 at 5:00:00 am, 10 hours, 30 minutes later. Use `undo` to reverse.",
+        app.command("advance to dawn").unwrap(),
+    );
+}
+
+#[test]
+fn advancing_time_mentions_skipped_festivals() {
+    let mut app = sync_app();
+
+    app.command("config year 5").unwrap();
+    app.command("town named Bree").unwrap();
+
+    let output = app.command("+1 week").unwrap();
+    assert!(output.contains(" in `Bree` passed on day "), "{}", output);
+}
+
 #[test]
 fn time_is_persisted() {
     let data_store = MemoryDataStore::default();
@@ -70,3 +112,72 @@ fn time_is_persisted() {
         );
     }
 }
+
+#[test]
+fn timeline_lists_events_chronologically() {
+    let mut app = sync_app();
+
+    assert_eq!(
+        "# Timeline\n\n*The timeline is currently empty. Creating and deleting entries and advancing time will add events to it.*",
+        app.command("timeline").unwrap(),
+    );
+
+    app.command("elf named Gottfried").unwrap();
+    app.command("+8h").unwrap();
+    app.command("inn named The Prancing Pony").unwrap();
+    app.command("-1d").unwrap();
+    app.command("delete Gottfried").unwrap();
+
+    assert_eq!(
+        "# Timeline
+
+**day 0 at 4:00:00 pm:** deleted `Gottfried` (character)\\
+**day 1 at 8:00:00 am:** created `Gottfried` (character)\\
+**day 1 at 8:00:00 am:** time advanced to day 1 at 4:00:00 pm\\
+**day 1 at 4:00:00 pm:** created `The Prancing Pony` (place)\\
+**day 1 at 4:00:00 pm:** time rewound to day 0 at 4:00:00 pm",
+        app.command("timeline").unwrap(),
+    );
+}
+
+#[test]
+fn timeline_forgets_undone_events() {
+    let mut app = sync_app();
+
+    app.command("elf named Gottfried").unwrap();
+    app.command("+1d").unwrap();
+    app.command("undo").unwrap();
+
+    assert_eq!(
+        "# Timeline\n\n**day 1 at 8:00:00 am:** created `Gottfried` (character)",
+        app.command("timeline").unwrap(),
+    );
+
+    app.command("redo").unwrap();
+
+    assert_eq!(
+        "# Timeline
+
+**day 1 at 8:00:00 am:** created `Gottfried` (character)\\
+**day 1 at 8:00:00 am:** time advanced to day 2 at 8:00:00 am",
+        app.command("timeline").unwrap(),
+    );
+}
+
+#[test]
+fn timeline_is_persisted() {
+    let data_store = MemoryDataStore::default();
+
+    {
+        let mut app = sync_app_with_data_store(data_store.clone());
+        app.command("elf named Gottfried").unwrap();
+    }
+
+    let mut app = sync_app_with_data_store(data_store);
+    app.command("config date short").unwrap();
+
+    assert_eq!(
+        "# Timeline\n\n**1:08:00:00:** created `Gottfried` (character)",
+        app.command("timeline").unwrap(),
+    );
+}
@@ -0,0 +1,66 @@
+use crate::common::sync_app;
+
+#[test]
+fn loot_can_be_rolled() {
+    let mut app = sync_app();
+
+    let output = app.command("loot cr 1/2").unwrap();
+    assert!(
+        output.starts_with("# Individual Treasure (CR 0)\n\n**Coins:** "),
+        "{}",
+        output,
+    );
+    assert!(output.contains("\\\n**Total Value:** "), "{}", output);
+
+    let output = app.command("hoard cr 17").unwrap();
+    assert!(
+        output.starts_with("# Treasure Hoard (CR 17)\n\n**Coins:** "),
+        "{}",
+        output,
+    );
+}
+
+#[test]
+fn loot_can_be_given_to_a_character() {
+    let mut app = sync_app();
+
+    app.command("npc named Thorin").unwrap();
+    assert!(!app.command("Thorin").unwrap().contains("**Coins:**"));
+
+    let output = app.command("hoard cr 5 for thorin").unwrap();
+    assert!(
+        output.ends_with(
+            "_The treasure was added to the inventory of **Thorin**. Use `undo` to reverse this._"
+        ),
+        "{}",
+        output,
+    );
+
+    let output = app.command("Thorin").unwrap();
+    assert!(output.contains("\n\n**Coins:** "), "{}", output);
+
+    app.command("undo").unwrap();
+    assert!(!app.command("Thorin").unwrap().contains("**Coins:**"));
+}
+
+#[test]
+fn loot_can_be_given_to_a_place() {
+    let mut app = sync_app();
+
+    app.command("inn named Moonbright").unwrap();
+    app.command("loot cr 5 for Moonbright").unwrap();
+    app.command("loot cr 5 for Moonbright").unwrap();
+
+    let output = app.command("Moonbright").unwrap();
+    assert!(output.contains("\n\n**Coins:** "), "{}", output);
+}
+
+#[test]
+fn loot_requires_an_existing_recipient() {
+    let mut app = sync_app();
+
+    assert_eq!(
+        Err(r#"There is no character or place named "Thorin"."#.to_string()),
+        app.command("loot cr 5 for Thorin"),
+    );
+}
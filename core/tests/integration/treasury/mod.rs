@@ -0,0 +1,146 @@
+use crate::common::{sync_app, sync_app_with_data_store};
+use initiative_core::MemoryDataStore;
+
+#[test]
+fn coins_can_be_added_and_spent() {
+    let mut app = sync_app();
+
+    assert_eq!(
+        "# Treasury\n\n*The treasury is empty. Use `treasury add [amount]` to add coins, eg. `treasury add 150 gp`.*",
+        app.command("treasury").unwrap(),
+    );
+
+    assert_eq!(
+        "Added 150 gp to the treasury, which now holds 150 gp. Use `undo` to reverse this.",
+        app.command("treasury add 150 gp").unwrap(),
+    );
+
+    assert_eq!(
+        "Spent 2 sp, 1 pp from the treasury, which now holds 8 sp, 139 gp. Use `undo` to reverse this.",
+        app.command("treasury spend 1 pp 2 sp").unwrap(),
+    );
+
+    assert_eq!(
+        "# Treasury\n\n**Coins:** 8 sp, 139 gp\\\n**Total Value:** 8 sp, 139 gp",
+        app.command("treasury").unwrap(),
+    );
+
+    assert_eq!(
+        "The treasury only holds 8 sp, 139 gp, which isn't enough to spend 14 pp.",
+        app.command("treasury spend 14 pp").unwrap_err(),
+    );
+
+    assert_eq!(
+        "Successfully undid changing the treasury. Use `redo` to reverse this.",
+        app.command("undo").unwrap(),
+    );
+
+    assert_eq!(
+        "# Treasury\n\n**Coins:** 150 gp\\\n**Total Value:** 150 gp",
+        app.command("treasury").unwrap(),
+    );
+}
+
+#[test]
+fn equipment_can_be_bought() {
+    let mut app = sync_app();
+
+    assert_eq!(
+        "The treasury is empty, which isn't enough to buy Longsword ×2 for 30 gp.",
+        app.command("buy 2 longswords").unwrap_err(),
+    );
+
+    app.command("treasury add 5 pp").unwrap();
+
+    assert_eq!(
+        "Bought Longsword ×2 for 30 gp. The treasury now holds 2 pp. Use `undo` to reverse this.",
+        app.command("buy 2 longswords").unwrap(),
+    );
+
+    app.command("inn named Moonbright").unwrap();
+
+    assert_eq!(
+        "Moonbright doesn't have any Shield.",
+        app.command("buy a shield from Moonbright").unwrap_err(),
+    );
+}
+
+#[test]
+fn prices_follow_the_local_economy() {
+    let mut app = sync_app();
+
+    app.command("town named Waterdeep").unwrap();
+    app.command("Waterdeep is frontier").unwrap();
+    app.command("party is at Waterdeep").unwrap();
+    app.command("treasury add 5 pp").unwrap();
+
+    let output = app.command("Waterdeep").unwrap();
+    assert!(output.contains("\n*frontier town*\n"), "{}", output,);
+    assert!(
+        output.contains("\n\n**Prices:** +20% on everything (frontier)\n"),
+        "{}",
+        output,
+    );
+
+    let output = app.command("buy a longsword").unwrap();
+    assert!(
+        output.starts_with("Bought Longsword for 18 gp (+20% in **Waterdeep**)."),
+        "{}",
+        output,
+    );
+
+    let output = app.command("srd item longsword").unwrap();
+    assert!(
+        output.contains("\n\n**Price in `Waterdeep`:** 18 gp (+20%)"),
+        "{}",
+        output,
+    );
+
+    app.command("Waterdeep is well-supplied").unwrap();
+
+    let output = app.command("buy a longsword").unwrap();
+    assert!(
+        output.starts_with("Bought Longsword for 15 gp. "),
+        "{}",
+        output,
+    );
+}
+
+#[test]
+fn haggling_needs_a_stocked_shop() {
+    let mut app = sync_app();
+
+    assert_eq!(
+        "Configuration updated: `charisma +3`. Use `undo` to reverse this.",
+        app.command("config charisma 3").unwrap(),
+    );
+
+    app.command("inn named Moonbright").unwrap();
+
+    assert_eq!(
+        "Moonbright doesn't have any Shield.",
+        app.command("haggle a shield at Moonbright").unwrap_err(),
+    );
+
+    assert_eq!(
+        r#"There is no shop named "Ye Olde Shoppe"."#,
+        app.command("haggle a shield at Ye Olde Shoppe")
+            .unwrap_err(),
+    );
+}
+
+#[test]
+fn treasury_is_persisted() {
+    let data_store = MemoryDataStore::default();
+
+    {
+        let mut app = sync_app_with_data_store(data_store.clone());
+        app.command("treasury add 3 pp, 25 cp").unwrap();
+    }
+
+    let mut app = sync_app_with_data_store(data_store);
+    assert_eq!(
+        "# Treasury\n\n**Coins:** 25 cp, 3 pp\\\n**Total Value:** 5 cp, 2 sp, 30 gp",
+        app.command("treasury").unwrap(),
+    );
+}
@@ -0,0 +1,74 @@
+use crate::common::sync_app;
+
+#[test]
+fn armies_fight_over_territory() {
+    let mut app = sync_app();
+
+    assert_eq!(
+        "# Armies\n\n*There are no armies yet. Use `army [name] of [size] [quality]` to muster one, eg. `army The Iron Legion of 500 veterans led by Gimli`.*",
+        app.command("armies").unwrap(),
+    );
+
+    app.command("npc named Gimli").unwrap();
+    app.command("town named Bree").unwrap();
+
+    assert_eq!(
+        "**The Iron Legion** has been mustered: 500 veteran soldiers led by `Gimli`. Use `undo` to reverse this.",
+        app.command("army The Iron Legion of 500 veterans led by Gimli")
+            .unwrap(),
+    );
+    app.command("army The Black Host of 300 levies").unwrap();
+
+    let output = app
+        .command("battle the iron legion vs the black host at bree")
+        .unwrap();
+    assert!(output.starts_with("# Battle of Bree\n\n"), "{}", output);
+    assert!(
+        output.contains("**The Iron Legion:** `d20` ("),
+        "{}",
+        output,
+    );
+
+    let timeline = app.command("timeline").unwrap();
+    assert!(
+        timeline.contains(":** The Iron Legion defeated The Black Host at `Bree`")
+            || timeline.contains(":** The Black Host held off The Iron Legion at `Bree`")
+            || timeline.contains(
+                ":** The Iron Legion and The Black Host fought to a standstill at `Bree`"
+            ),
+        "{}",
+        timeline,
+    );
+
+    let armies = app.command("armies").unwrap();
+    assert!(armies.contains("\n\n## Recent Battles\n\n* "), "{}", armies);
+
+    app.command("undo").unwrap();
+
+    let armies = app.command("armies").unwrap();
+    assert!(
+        armies.contains("**The Iron Legion:** 500 veteran soldiers led by `Gimli`\\\n**The Black Host:** 300 levy soldiers"),
+        "{}",
+        armies,
+    );
+    assert!(!armies.contains("## Recent Battles"), "{}", armies);
+    assert!(
+        !app.command("timeline").unwrap().contains(" at `Bree`"),
+        "battle wasn't removed from the timeline",
+    );
+}
+
+#[test]
+fn armies_need_soldiers() {
+    let mut app = sync_app();
+
+    for input in ["army The Red of 0 levy", "army The Red of -10 levies"] {
+        assert_eq!(
+            Err("An army needs at least one soldier.".to_string()),
+            app.command(input),
+            "{}",
+            input,
+        );
+    }
+    assert!(app.command("disband The Red").is_err());
+}
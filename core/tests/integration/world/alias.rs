@@ -0,0 +1,89 @@
+use crate::common::sync_app;
+
+#[test]
+fn alias_finds_the_thing() {
+    let mut app = sync_app();
+
+    app.command("inn named The Yawning Portal").unwrap();
+
+    let output = app
+        .command("The Yawning Portal is also known as the Portal")
+        .unwrap();
+    assert!(
+        output.ends_with(
+            "_The Yawning Portal is now also known as the Portal. Use `undo` to reverse this._"
+        ),
+        "{}",
+        output,
+    );
+
+    {
+        let output = app.command("load the portal").unwrap();
+        assert!(output.contains("# The Yawning Portal"), "{}", output);
+        assert!(
+            output.contains("**Also known as:** the Portal"),
+            "{}",
+            output,
+        );
+    }
+
+    assert!(app
+        .autocomplete("load the por")
+        .iter()
+        .any(|suggestion| suggestion.term == "load The Yawning Portal"));
+
+    assert_eq!(
+        "The Yawning Portal is already known as the Portal.",
+        app.command("the yawning portal is also known as THE PORTAL")
+            .unwrap_err(),
+    );
+
+    app.command("undo").unwrap();
+    assert!(app.command("load the portal").is_err());
+}
+
+#[test]
+fn alias_must_not_be_in_use() {
+    let mut app = sync_app();
+
+    app.command("inn named The Yawning Portal").unwrap();
+    app.command("npc named Durnan").unwrap();
+
+    let output = app
+        .command("The Yawning Portal is also known as Durnan")
+        .unwrap_err();
+    assert!(
+        output.starts_with("That name is already in use by"),
+        "{}",
+        output
+    );
+    assert!(app.command("npc named the Portal").is_ok());
+    assert!(app
+        .command("The Yawning Portal is also known as the Portal")
+        .is_err());
+}
+
+#[test]
+fn alias_of_unknown_thing_names_the_owner() {
+    let mut app = sync_app();
+
+    app.command("npc named Éowyn").unwrap();
+
+    let output = app
+        .command("Shieldmaiden is also known as Éowyn")
+        .unwrap_err();
+    assert!(
+        output.starts_with(
+            "There is no entity named \"Shieldmaiden\". \"Éowyn\" is already in use by "
+        ),
+        "{}",
+        output,
+    );
+    assert!(output.contains("`Éowyn`"), "{}", output);
+
+    assert_eq!(
+        "There is no entity named \"Shieldmaiden\".",
+        app.command("Shieldmaiden is also known as Dernhelm")
+            .unwrap_err(),
+    );
+}
@@ -0,0 +1,83 @@
+use crate::common::{sync_app, sync_app_with_dispatcher};
+use initiative_core::app::AutocompleteSuggestion;
+use initiative_core::Event;
+use std::cell::RefCell;
+
+#[test]
+fn ambience_is_described() {
+    thread_local! {
+        static LAST_EVENT: RefCell<Option<Event>> = const { RefCell::new(None) };
+    }
+
+    fn event_dispatcher(event: Event) {
+        LAST_EVENT.with(|e| *e.borrow_mut() = Some(event));
+    }
+
+    let mut app = sync_app_with_dispatcher(&event_dispatcher);
+    app.command("inn named The Prancing Pony").unwrap();
+
+    let output = app.command("ambience the prancing pony").unwrap();
+    assert!(
+        output.starts_with("# Ambience at The Prancing Pony\n*morning*\n\n**Sounds:** "),
+        "{}",
+        output,
+    );
+    assert!(
+        output.contains("\n\nA server is scrubbing down the tables from last night."),
+        "{}",
+        output,
+    );
+    assert!(
+        output.ends_with("_Soundboard cues: tavern-chatter, hearth-fire, tavern-music_"),
+        "{}",
+        output,
+    );
+
+    let Some(Event::Ambience(ambience)) = LAST_EVENT.with(|e| e.borrow_mut().take()) else {
+        panic!("Expected an ambience event.");
+    };
+    assert_eq!("The Prancing Pony", ambience.name);
+    assert_eq!("morning", ambience.time_of_day);
+    assert_eq!(
+        vec!["tavern-chatter", "hearth-fire", "tavern-music"],
+        ambience.cues,
+    );
+
+    app.command("+16h").unwrap();
+    assert!(app
+        .command("ambience The Prancing Pony")
+        .unwrap()
+        .ends_with("_Soundboard cues: hearth-fire, creaking-wood_"));
+}
+
+#[test]
+fn ambience_requires_place() {
+    let mut app = sync_app();
+
+    app.command("npc named Gottfried").unwrap();
+
+    assert_eq!(
+        "Gottfried isn't a place.",
+        app.command("ambience Gottfried").unwrap_err(),
+    );
+    assert_eq!(
+        "There is no entity named \"The Red Dragon\".",
+        app.command("ambience The Red Dragon").unwrap_err(),
+    );
+}
+
+#[test]
+fn ambience_autocomplete() {
+    let mut app = sync_app();
+
+    app.command("inn named The Prancing Pony").unwrap();
+    app.command("npc named The Pale Man").unwrap();
+
+    assert_eq!(
+        vec![AutocompleteSuggestion::new(
+            "ambience The Prancing Pony",
+            "describe the atmosphere",
+        )],
+        app.autocomplete("ambience the"),
+    );
+}
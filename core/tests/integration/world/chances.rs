@@ -0,0 +1,24 @@
+use crate::common::sync_app;
+
+#[test]
+fn chances_shows_the_odds() {
+    let mut app = sync_app();
+
+    let output = app.command("chances tavern").unwrap();
+    assert!(
+        output.starts_with("# Chances: inn\n\n## name\n\n"),
+        "{}",
+        output
+    );
+    assert!(
+        output.contains("| [thing] and [thing] | 2 | 33.3% |"),
+        "{}",
+        output,
+    );
+    assert!(output.contains("## description"), "{}", output);
+
+    assert!(app
+        .autocomplete("chan")
+        .iter()
+        .any(|suggestion| suggestion.term == "chances [place type]"));
+}
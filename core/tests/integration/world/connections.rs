@@ -0,0 +1,35 @@
+use crate::common::sync_app;
+
+#[test]
+fn connections_follow_locations() {
+    let mut app = sync_app();
+    let backup_data =
+        serde_json::from_str(include_str!("../storage/export_import/v2.json")).unwrap();
+    app.bulk_import(backup_data).unwrap();
+
+    assert_eq!(
+        "# Connections of Faman Halin\n\n\
+        * `Faman Halin` → located in → `The Moody Conjurer`",
+        app.command("connections Faman Halin").unwrap(),
+    );
+    assert_eq!(
+        "# Connections of The Moody Conjurer\n\n\
+        * `The Moody Conjurer` → contains → `Faman Halin`",
+        app.command("connections the moody conjurer").unwrap(),
+    );
+}
+
+#[test]
+fn connections_without_any() {
+    let mut app = sync_app();
+    app.command("npc named Gottfried").unwrap();
+
+    assert_eq!(
+        "Gottfried has no known connections.",
+        app.command("connections Gottfried").unwrap(),
+    );
+    assert_eq!(
+        "There is no entity named \"Potato Johnson\".",
+        app.command("connections Potato Johnson").unwrap_err(),
+    );
+}
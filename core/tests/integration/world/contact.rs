@@ -0,0 +1,57 @@
+use crate::common::{get_name, sync_app};
+
+#[test]
+fn contacts_are_linked_to_player_characters() {
+    let mut app = sync_app();
+
+    app.command("pc Aragorn, level 5 ranger").unwrap();
+
+    let output = app.command("create mentor for aragorn").unwrap();
+    assert!(
+        output.contains("## Relationships\n\n**Mentor to:** `Aragorn`\\\n**Hook:** "),
+        "{}",
+        output,
+    );
+    assert!(output.contains("\\\n**Secret:** "), "{}", output);
+    assert!(
+        output.contains("has been automatically added to your `journal` as Aragorn's mentor."),
+        "{}",
+        output,
+    );
+
+    let mentor_name = get_name(&output);
+
+    {
+        let output = app.command("Aragorn").unwrap();
+        assert!(
+            output.contains(&format!(
+                "## Relationships\n\n**Mentor:** `{}`",
+                mentor_name,
+            )),
+            "{}",
+            output,
+        );
+    }
+
+    app.command("undo").unwrap();
+
+    assert!(!app.command("journal").unwrap().contains(&mentor_name));
+    assert!(!app.command("Aragorn").unwrap().contains("Relationships"));
+}
+
+#[test]
+fn contacts_are_for_player_characters() {
+    let mut app = sync_app();
+
+    app.command("npc named Gottfried").unwrap();
+
+    assert_eq!(
+        "Gottfried isn't a player character. Use `pc Gottfried` to make them one.",
+        app.command("create contact for Gottfried").unwrap_err(),
+    );
+
+    assert_eq!(
+        "There is no character named \"Boromir\".",
+        app.command("create rival for Boromir").unwrap_err(),
+    );
+}
@@ -49,9 +49,11 @@ fn create_npc_with_custom_attributes() {
     let mut app = sync_app();
 
     {
-        let output = app.command("Sue, a young enby dwarvish elf").unwrap();
+        let output = app
+            .command("Sue, a young enby dwarvish elf scholar")
+            .unwrap();
         assert!(
-            output.contains("# Sue\n*young adult elf, they/them*"),
+            output.contains("# Sue\n*young adult elf scholar, they/them*"),
             "{}",
             output,
         );
@@ -72,7 +74,7 @@ fn create_npc_with_custom_attributes() {
     {
         let output = app.command("a boy named sue").unwrap_err();
         assert_eq!(
-            "That name is already in use by 🧑 `Sue` (young adult elf, they/them).",
+            "That name is already in use by 🧑 `Sue` (young adult elf scholar, they/them).",
             output,
         );
     }
@@ -159,13 +161,15 @@ fn create_with_unknown_words() {
 }
 
 #[test]
-fn generate_location_with_no_name_generator() {
+fn generate_location_from_descriptor_pool() {
     let mut app = sync_app();
 
-    assert_eq!(
-        "There is no name generator implemented for that type. You must specify your own name using `kingdom named [name]`.",
-        app.command("kingdom").unwrap_err(),
-    );
+    {
+        let output = app.command("kingdom").unwrap();
+        assert!(output.contains("\n*kingdom*\n\nA"), "{}", output);
+        assert!(!output.contains("# Unnamed"), "{}", output);
+        assert!(!output.contains('{'), "{}", output);
+    }
 
     {
         let output = app.command("kingdom named Narnia").unwrap();
@@ -177,3 +181,118 @@ fn generate_location_with_no_name_generator() {
         assert!(output.contains("# Narnia"), "{}", output);
     }
 }
+
+#[test]
+fn create_npc_with_occupation() {
+    let mut app = sync_app();
+
+    {
+        let output = app.command("create npc blacksmith").unwrap();
+        assert!(output.contains("thing-box npc"), "{}", output);
+        assert!(output.contains(" blacksmith"), "{}", output);
+    }
+
+    {
+        let output = app.command("Gormo, an elderly dwarf cultist").unwrap();
+        assert!(
+            output.contains("# Gormo\n*elderly dwarf cultist, "),
+            "{}",
+            output,
+        );
+    }
+
+    {
+        let output = app.command("blacksmith").unwrap();
+        assert!(output.contains("thing-box place"), "{}", output);
+    }
+}
+
+#[test]
+fn create_npc_with_compound_description() {
+    let mut app = sync_app();
+
+    let output = app
+        .command("create middle-aged dwarvish woman blacksmith named Hilda who is grumpy")
+        .unwrap();
+
+    assert!(
+        output.contains("# Hilda\n*grumpy middle-aged dwarf blacksmith, she/her*"),
+        "{}",
+        output,
+    );
+    assert!(
+        output.contains("\n\n_Understood **middle-aged** as age, **dwarvish** as ethnicity, **woman** as gender and age, **blacksmith** as occupation, **Hilda** as name, and **grumpy** as trait._\n\n_Because you specified a name,"),
+        "{}",
+        output,
+    );
+
+    let output = app.command("elderly dwarf blacksmith").unwrap();
+    assert!(!output.contains("_Understood"), "{}", output);
+}
+
+#[test]
+fn create_npc_with_real_world_ethnicity() {
+    let mut app = sync_app();
+
+    for (input, ethnicity, names) in [
+        ("khmer man", "khmer", 2),
+        ("māori woman", "māori", 2),
+        ("quechua person", "quechua", 3),
+    ] {
+        let output = app.command(input).unwrap();
+        let name = get_name(&output);
+
+        assert!(
+            output.contains(&format!("**Species:** human ({})", ethnicity)),
+            "{}",
+            output,
+        );
+        assert!(name.split(' ').count() >= names, "{}", output);
+    }
+
+    let output = app.command("yoruba character named Adunni").unwrap();
+    assert!(output.contains("# Adunni\n*"), "{}", output);
+    assert!(output.contains("**Species:** human (yoruba)"), "{}", output);
+}
+
+#[test]
+fn create_place_with_time_of_day() {
+    let mut app = sync_app();
+
+    let output = app.command("market").unwrap();
+    assert!(
+        output.contains("merchants are still setting out their wares"),
+        "{}",
+        output,
+    );
+
+    app.command("+18h").unwrap();
+
+    let output = app.command("market").unwrap();
+    assert!(
+        output.contains("stalls are shuttered and dark"),
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn create_escapes_markup_in_names() {
+    let mut app = sync_app();
+
+    let output = app.command("npc named ~delete Gottfried~").unwrap();
+    assert!(
+        output.contains("\n# \\~delete Gottfried\\~\n"),
+        "{}",
+        output,
+    );
+
+    assert!(
+        output.contains("_Because you specified a name, \\~delete Gottfried\\~ has been"),
+        "{}",
+        output,
+    );
+
+    let output = app.command("journal").unwrap();
+    assert!(output.contains("`~delete Gottfried~`"), "{}", output);
+}
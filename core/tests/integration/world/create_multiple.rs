@@ -124,3 +124,92 @@ fn numeric_aliases() {
         generated_output,
     );
 }
+
+#[test]
+fn create_a_number_of_things() {
+    let mut app = sync_app();
+
+    let output = app.command("create three dwarves").unwrap();
+    assert!(output.starts_with("# 3 dwarves\n\n~1~ "), "{}", output);
+    assert_eq!(
+        3,
+        output.lines().filter(|line| line.starts_with('~')).count(),
+        "{}",
+        output,
+    );
+    assert_eq!(
+        3,
+        output
+            .lines()
+            .filter(|line| line.starts_with('~') && line.contains("dwarf"))
+            .count(),
+        "{}",
+        output,
+    );
+    assert!(
+        output.ends_with("_For even more suggestions, type ~more~._"),
+        "{}",
+        output,
+    );
+
+    let output = app.command("more").unwrap();
+    assert!(output.starts_with("# 3 dwarves\n\n"), "{}", output);
+
+    let output = app.command("create 2 inns").unwrap();
+    assert!(output.starts_with("# 2 inns\n\n"), "{}", output);
+
+    let output = app.command("four elderly elves").unwrap();
+    assert!(output.starts_with("# 4 elderly elves\n\n"), "{}", output);
+}
+
+#[test]
+fn create_a_group() {
+    let mut app = sync_app();
+
+    let output = app.command("create a party of halflings").unwrap();
+    assert!(output.starts_with("# 5 halflings\n\n"), "{}", output);
+    assert_eq!(
+        5,
+        output
+            .lines()
+            .filter(|line| line.starts_with('~') && line.contains("halfling"))
+            .count(),
+        "{}",
+        output,
+    );
+
+    let output = app.command("create 3 halflings").unwrap();
+    assert!(output.starts_with("# 3 halflings\n\n"), "{}", output);
+}
+
+#[test]
+fn create_a_group_from_a_template() {
+    let mut app = sync_app();
+
+    let error = app.command("create a party of goblins").unwrap_err();
+    assert!(
+        error.starts_with("Unknown command: \"create a party of goblins\"\n\n"),
+        "{}",
+        error,
+    );
+
+    app.command("template save goblin = halfling adult")
+        .unwrap();
+
+    let output = app.command("create a party of goblins").unwrap();
+    assert_eq!(
+        5,
+        output.lines().filter(|line| line.starts_with('~')).count(),
+        "{}",
+        output,
+    );
+    assert_eq!(
+        5,
+        output
+            .lines()
+            .filter(|line| line.starts_with('~') && line.contains(" halfling"))
+            .count(),
+        "{}",
+        output
+    );
+}
@@ -0,0 +1,61 @@
+use crate::common::sync_app;
+
+#[test]
+fn downtime_is_paid_for_from_the_treasury() {
+    let mut app = sync_app();
+
+    app.command("pc Aragorn, level 5 ranger").unwrap();
+
+    assert_eq!(
+        "# Downtime: Aragorn
+
+**Activity:** working\\
+**Days:** 5
+
+`Aragorn` practices their profession, earning 5 gp: enough to maintain a modest lifestyle.
+
+The treasury now holds 5 gp. It is now day 6 at 8:00:00 am.
+
+_Use `undo` to reverse this._",
+        app.command("downtime aragorn working 5 days").unwrap(),
+    );
+
+    assert_eq!(
+        "The treasury only holds 5 gp, which isn't enough to pay 10 gp for training.",
+        app.command("downtime Aragorn training 10 days")
+            .unwrap_err(),
+    );
+
+    {
+        let output = app.command("downtime Aragorn crafting a shield").unwrap();
+        assert!(
+            output.contains("`Aragorn` crafts Shield, spending 5 gp on raw materials."),
+            "{}",
+            output,
+        );
+        assert!(
+            output.contains("It is now day 8 at 8:00:00 am."),
+            "{}",
+            output
+        );
+    }
+
+    app.command("undo").unwrap();
+    assert_eq!(
+        "It is currently day 6 at 8:00:00 am.",
+        app.command("now").unwrap()
+    );
+}
+
+#[test]
+fn downtime_is_for_player_characters() {
+    let mut app = sync_app();
+
+    app.command("npc named Gottfried").unwrap();
+
+    assert_eq!(
+        "Gottfried isn't a player character. Use `pc Gottfried` to make them one.",
+        app.command("downtime Gottfried working 5 days")
+            .unwrap_err(),
+    );
+}
@@ -0,0 +1,121 @@
+use crate::common::{get_name, sync_app};
+
+#[test]
+fn dungeon_is_generated_with_rooms() {
+    let mut app = sync_app();
+
+    let output = app.command("dungeon").unwrap();
+    let name = get_name(&output);
+    assert!(output.contains("**Theme:** "), "{}", output);
+    assert!(output.contains("\n\n~room 1~ "), "{}", output);
+    assert!(output.contains(" (boss)"), "{}", output);
+
+    let room_1 = app.command("room 1").unwrap();
+    assert!(room_1.starts_with("# Room 1: "), "{}", room_1);
+    assert!(room_1.contains(&format!("\n*{}*\n\n", name)), "{}", room_1);
+    assert_eq!(
+        room_1,
+        app.command(&format!("room 1 of {}", name.to_lowercase()))
+            .unwrap(),
+    );
+
+    // The aliases survive viewing a room, so the rooms can be walked in order.
+    let room_2 = app.command("room 2").unwrap();
+    assert!(room_2.starts_with("# Room 2: "), "{}", room_2);
+}
+
+#[test]
+fn dungeon_rooms_are_persisted() {
+    let mut app = sync_app();
+
+    app.command("dungeon named The Sunken Crypt").unwrap();
+    let room_1 = app.command("room 1 of The Sunken Crypt").unwrap();
+
+    app.command("npc").unwrap();
+    assert_eq!(room_1, app.command("room 1 of The Sunken Crypt").unwrap(),);
+
+    let output = app.command("The Sunken Crypt").unwrap();
+    assert!(output.contains("**Theme:** "), "{}", output);
+}
+
+#[test]
+fn room_requires_dungeon() {
+    let mut app = sync_app();
+
+    app.command("dungeon named The Sunken Crypt").unwrap();
+    app.command("inn named The Prancing Pony").unwrap();
+
+    assert_eq!(
+        "The Prancing Pony isn't a dungeon.",
+        app.command("room 1 of The Prancing Pony").unwrap_err(),
+    );
+    assert_eq!(
+        "There is no entity named \"The Drowned Crypt\".",
+        app.command("room 1 of The Drowned Crypt").unwrap_err(),
+    );
+    assert!(app
+        .command("room 99 of The Sunken Crypt")
+        .unwrap_err()
+        .starts_with("The Sunken Crypt has no room 99. Its rooms are numbered 1 to "));
+}
+
+#[test]
+fn lair_is_generated_for_legendary_monster() {
+    let mut app = sync_app();
+
+    let output = app.command("create lair for adult black dragon").unwrap();
+    let name = get_name(&output);
+    assert!(output.contains("**Theme:** swamp"), "{}", output);
+    assert!(
+        output.contains("**Lair of:** adult black dragon (CR 14)"),
+        "{}",
+        output,
+    );
+    assert!(
+        output.contains("\n\n## Lair Actions\n\nOn initiative count 20 (losing initiative ties), the adult black dragon takes a lair action"),
+        "{}",
+        output,
+    );
+    assert!(output.contains("DC 18 "), "{}", output);
+    assert!(
+        output.contains("\n\n## Wandering Groups\n\n* "),
+        "{}",
+        output
+    );
+    assert!(output.contains("\n\n**Coins:** "), "{}", output);
+    assert!(
+        output.ends_with("_The lair of the adult black dragon has been automatically added to your `journal`, with its hoard in the lair's inventory. Use `undo` to remove it._"),
+        "{}",
+        output,
+    );
+
+    let boss = app.command(&format!("room 99 of {}", name)).unwrap_err();
+    let last_room = boss
+        .rsplit(' ')
+        .next()
+        .unwrap()
+        .trim_end_matches('.')
+        .to_string();
+    let output = app
+        .command(&format!("room {} of {}", last_room, name))
+        .unwrap();
+    assert!(
+        output.contains("**Boss:** an adult black dragon\\\n**Treasure:** The adult black dragon's hoard (see the lair's inventory)"),
+        "{}",
+        output,
+    );
+
+    assert!(app.command("journal").unwrap().contains(&name));
+    app.command("undo").unwrap();
+    assert!(app.command(&name).is_err());
+}
+
+#[test]
+fn lair_requires_known_monster() {
+    let mut app = sync_app();
+
+    assert_eq!(
+        "There's no lair for \"goblin\". Lairs can be created for legendary monsters like the adult black dragon, or for monsters added with `homebrew import`.",
+        app.command("create lair for goblin").unwrap_err(),
+    );
+}
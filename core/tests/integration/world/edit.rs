@@ -168,3 +168,81 @@ fn edit_with_unknown_words() {
         output,
     );
 }
+
+#[test]
+fn edit_npc_appearance() {
+    let mut app = sync_app();
+    app.command("dwarf named Gimli").unwrap();
+
+    let output = app
+        .command("Gimli is stocky red-haired blue-eyed scarred tattered")
+        .unwrap();
+    assert!(
+        output.contains(
+            "## Appearance\n\n\
+            **Height:** ",
+        ),
+        "{}",
+        output,
+    );
+    assert!(
+        output.contains(
+            "**Build:** stocky\\\n\
+            **Hair:** red\\\n\
+            **Eyes:** blue\\\n\
+            **Distinguishing Mark:** a prominent scar\\\n\
+            **Clothing:** ragged clothes",
+        ),
+        "{}",
+        output,
+    );
+}
+
+#[test]
+fn edit_single_field() {
+    let mut app = sync_app();
+    app.command("dwarf named Gimli").unwrap();
+    app.command("inn named The Silver Eel").unwrap();
+
+    {
+        let output = app.command("Gimli's age is 40y").unwrap();
+        assert!(output.contains("*adult dwarf"), "{}", output);
+        assert!(output.contains("**Age:** 40 years"), "{}", output);
+    }
+
+    {
+        let output = app.command("Gimli's species is elf").unwrap();
+        assert!(output.contains(" elf"), "{}", output);
+        assert!(output.contains("**Age:** 40 years"), "{}", output);
+    }
+
+    {
+        let output = app
+            .command("The Silver Eel's description is a dive where nobody asks questions")
+            .unwrap();
+        assert!(
+            output.contains("a dive where nobody asks questions"),
+            "{}",
+            output,
+        );
+    }
+
+    {
+        let output = app.command("Gimli's name is Glóin").unwrap();
+        assert!(output.contains("Gimli"), "{}", output);
+        assert!(app.command("Glóin").unwrap().contains("# Glóin"));
+    }
+
+    {
+        let output = app.command("Glóin's occupation is good baker").unwrap();
+        assert!(output.contains(" baker"), "{}", output);
+        assert!(
+            output.contains(
+                "\\> Glóin's occupation is **good** baker\\\n\
+                \u{a0}\u{a0}\u{a0}\u{a0}\u{a0}\u{a0}\u{a0}\u{a0}\u{a0}\u{a0}\u{a0}\u{a0}\u{a0}\u{a0}\u{a0}\u{a0}\u{a0}\u{a0}\u{a0}\u{a0}\u{a0}\u{a0}\u{a0}\u{a0}^^^^\\\n"
+            ),
+            "{}",
+            output,
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use crate::common::sync_app;
+
+#[test]
+fn characters_can_be_killed_and_retired() {
+    let mut app = sync_app();
+
+    app.command("elf named Gottfried").unwrap();
+    app.command("human named Sybil").unwrap();
+    app.command("+1d").unwrap();
+
+    {
+        let output = app.command("kill gottfried").unwrap();
+        assert!(output.contains("`Gottfried`"), "{}", output);
+        assert!(output.contains("[dead]"), "{}", output);
+        assert!(
+            output
+                .ends_with("_Gottfried died on day 2 at 8:00:00 am. Use `undo` to reverse this._"),
+            "{}",
+            output,
+        );
+    }
+
+    {
+        let output = app.command("Gottfried").unwrap();
+        assert!(
+            output.contains("**Died:** day 2 at 8:00:00 am"),
+            "{}",
+            output,
+        );
+    }
+
+    assert_eq!(
+        "Gottfried is already dead.",
+        app.command("retire Gottfried").unwrap_err(),
+    );
+
+    {
+        let output = app.command("retire Sybil").unwrap();
+        assert!(output.contains("[retired]"), "{}", output);
+    }
+
+    {
+        let output = app.command("journal").unwrap();
+        assert_eq!(2, output.matches("\n<s>").count(), "{}", output);
+        assert!(output.contains("[dead]</s>"), "{}", output);
+        assert!(output.contains("[retired]</s>"), "{}", output);
+    }
+
+    app.command("undo").unwrap();
+
+    {
+        let output = app.command("journal").unwrap();
+        assert_eq!(1, output.matches("\n<s>").count(), "{}", output);
+        assert!(output.contains("[dead]</s>"), "{}", output);
+        assert!(!output.contains("[retired]"), "{}", output);
+    }
+}
+
+#[test]
+fn only_characters_can_be_killed() {
+    let mut app = sync_app();
+
+    app.command("inn named Moonbright").unwrap();
+
+    assert_eq!(
+        "Moonbright isn't a character.",
+        app.command("kill Moonbright").unwrap_err(),
+    );
+    assert_eq!(
+        r#"There is no character named "Potato"."#,
+        app.command("retire Potato").unwrap_err(),
+    );
+}
+
+#[test]
+fn rumors_leave_out_dead_characters() {
+    let mut app = sync_app();
+
+    app.command("elf named Gottfried").unwrap();
+    app.command("kill Gottfried").unwrap();
+
+    for _ in 0..10 {
+        let output = app.command("rumor").unwrap();
+        assert!(!output.contains("Gottfried"), "{}", output);
+    }
+
+    let output = app.command("rumor about Gottfried").unwrap();
+    assert!(output.contains("`Gottfried`"), "{}", output);
+}
@@ -0,0 +1,69 @@
+use crate::common::sync_app;
+
+#[test]
+fn festivals_are_generated_with_settlements() {
+    let mut app = sync_app();
+
+    assert_eq!(
+        "There are no festivals coming up. Settlements and realms, such as a `town` or a `kingdom`, celebrate festivals of their own.",
+        app.command("festivals").unwrap(),
+    );
+
+    let output = app.command("town named Bree").unwrap();
+    assert!(output.contains("\n\n**Festivals:** "), "{}", output);
+
+    let output = app.command("festivals").unwrap();
+    assert!(
+        output.starts_with("# Upcoming festivals\n\n* "),
+        "{}",
+        output
+    );
+    assert!(output.contains(" in `Bree`. "), "{}", output);
+}
+
+#[test]
+fn advancing_time_onto_a_festival_mentions_it() {
+    let mut app = sync_app();
+
+    app.command("town named Bree").unwrap();
+
+    let output = app.command("festivals").unwrap();
+    let first = output.lines().nth(2).unwrap();
+    let (when, rest) = first.trim_start_matches("* ").split_once(": **").unwrap();
+    let name = rest.split_once("**").unwrap().0;
+
+    let days = match when {
+        "Today" => 365,
+        "Tomorrow" => 1,
+        when => when
+            .trim_start_matches("In ")
+            .trim_end_matches(" days")
+            .parse()
+            .unwrap(),
+    };
+
+    let output = app.command(&format!("+{}d", days)).unwrap();
+    assert!(
+        output.ends_with(&format!("\n\n_Today is the {} in `Bree`._", name)),
+        "{}",
+        output,
+    );
+
+    let output = app.command("+1h").unwrap();
+    assert!(!output.contains("_Today is the "), "{}", output);
+}
+
+#[test]
+fn festivals_follow_the_configured_year() {
+    let mut app = sync_app();
+
+    app.command("config year 1").unwrap();
+    app.command("kingdom named Arnor").unwrap();
+
+    let output = app.command("festivals").unwrap();
+    assert!(output.contains("\n* Today: **"), "{}", output);
+    assert!(!output.contains("Tomorrow"), "{}", output);
+
+    let output = app.command("+1d").unwrap();
+    assert!(output.contains("_Today is the "), "{}", output);
+}
@@ -0,0 +1,87 @@
+use crate::common::sync_app;
+
+#[test]
+fn equipment_can_be_given_and_taken() {
+    let mut app = sync_app();
+
+    app.command("elf named Gottfried").unwrap();
+
+    {
+        let output = app.command("give Gottfried 2 longswords").unwrap();
+        assert!(
+            output.contains("**Items:** Longsword ×2\\\n**Load:** 6 / 150 lbs"),
+            "{}",
+            output,
+        );
+        assert!(
+            output.ends_with("_Gottfried was given Longsword ×2. Use `undo` to reverse this._"),
+            "{}",
+            output,
+        );
+    }
+
+    {
+        let output = app.command("take a longsword from gottfried").unwrap();
+        assert!(output.contains("**Items:** Longsword\\\n"), "{}", output);
+        assert!(
+            output.ends_with("_Longsword was taken from Gottfried. Use `undo` to reverse this._"),
+            "{}",
+            output,
+        );
+    }
+
+    assert_eq!(
+        "Gottfried only has Longsword.",
+        app.command("take 2 longswords from Gottfried").unwrap_err(),
+    );
+    assert_eq!(
+        "Gottfried doesn't have any Shield.",
+        app.command("take a shield from Gottfried").unwrap_err(),
+    );
+
+    app.command("undo").unwrap();
+
+    {
+        let output = app.command("Gottfried").unwrap();
+        assert!(output.contains("**Items:** Longsword ×2"), "{}", output);
+    }
+}
+
+#[test]
+fn carrying_capacity_is_tracked() {
+    let mut app = sync_app();
+
+    app.command("elf named Gottfried").unwrap();
+
+    {
+        let output = app.command("give Gottfried 50 longswords").unwrap();
+        assert!(
+            !output.contains("exceeding a carrying capacity"),
+            "{}",
+            output
+        );
+    }
+
+    {
+        let output = app.command("give Gottfried a longsword").unwrap();
+        assert!(
+            output.ends_with(
+                "\n\n! Gottfried is now carrying 153 lbs, exceeding a carrying capacity of 150 lbs."
+            ),
+            "{}",
+            output,
+        );
+    }
+}
+
+#[test]
+fn equipment_can_only_be_given_to_characters() {
+    let mut app = sync_app();
+
+    app.command("inn named Moonbright").unwrap();
+
+    assert_eq!(
+        r#"There is no character named "Moonbright"."#,
+        app.command("give Moonbright a longsword").unwrap_err(),
+    );
+}
@@ -0,0 +1,92 @@
+use crate::common::sync_app;
+
+#[test]
+fn lock_and_unlock_fields() {
+    let mut app = sync_app();
+
+    app.command("npc named Gottfried").unwrap();
+
+    {
+        let output = app.command("unlock Gottfried's age").unwrap();
+        assert!(output.contains("\n\n**Unlocked:** age\n"), "{}", output);
+        assert!(
+            output.ends_with(
+                "_Gottfried's age is now unlocked and may change if it's regenerated. Use `undo` to reverse this._",
+            ),
+            "{}",
+            output,
+        );
+    }
+
+    {
+        let output = app.command("UNLOCK gottfried's Hair").unwrap();
+        assert!(
+            output.contains("\n\n**Unlocked:** age, hair\n"),
+            "{}",
+            output
+        );
+    }
+
+    {
+        let output = app.command("Gottfried").unwrap();
+        assert!(
+            output.contains("\n\n**Unlocked:** age, hair\n"),
+            "{}",
+            output
+        );
+    }
+
+    {
+        let output = app.command("lock Gottfried's age").unwrap();
+        assert!(output.contains("\n\n**Unlocked:** hair\n"), "{}", output);
+        assert!(
+            output.ends_with(
+                "_Gottfried's age is now locked and will be kept if it's regenerated. Use `undo` to reverse this._",
+            ),
+            "{}",
+            output,
+        );
+    }
+
+    {
+        let output = app.command("undo").unwrap();
+        assert!(
+            output.contains("\n\n**Unlocked:** age, hair\n"),
+            "{}",
+            output
+        );
+    }
+}
+
+#[test]
+fn lock_place_field() {
+    let mut app = sync_app();
+
+    app.command("inn named The Silver Eel").unwrap();
+
+    let output = app.command("unlock The Silver Eel's description").unwrap();
+    assert!(
+        output.contains("\n\n**Unlocked:** description\n"),
+        "{}",
+        output,
+    );
+}
+
+#[test]
+fn lock_errors() {
+    let mut app = sync_app();
+
+    app.command("npc named Gottfried").unwrap();
+
+    assert_eq!(
+        "There is no entity named \"Potato\".",
+        app.command("lock Potato's age").unwrap_err(),
+    );
+
+    let output = app.command("lock Gottfried's potato").unwrap_err();
+    assert!(
+        output.starts_with("Gottfried has no field called \"potato\". Its fields are `age`, "),
+        "{}",
+        output,
+    );
+}
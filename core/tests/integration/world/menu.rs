@@ -0,0 +1,78 @@
+use crate::common::{get_name, sync_app};
+use initiative_core::app::AutocompleteSuggestion;
+
+#[test]
+fn menu_is_generated_with_inn() {
+    let mut app = sync_app();
+
+    app.command("inn named The Prancing Pony").unwrap();
+
+    let output = app.command("menu The Prancing Pony").unwrap();
+    assert!(
+        output.starts_with("# Menu at The Prancing Pony\n\n**Quality:** "),
+        "{}",
+        output,
+    );
+    assert!(output.contains("\\\n**Signature Drink:** "), "{}", output);
+    assert!(output.contains("\\\n**House Dish:** "), "{}", output);
+    assert!(output.contains(" per night"), "{}", output);
+
+    assert_eq!(output, app.command("menu the prancing pony").unwrap());
+}
+
+#[test]
+fn menu_saves_recent_inn() {
+    let mut app = sync_app();
+
+    let name = get_name(&app.command("inn").unwrap());
+
+    let output = app.command(&format!("menu {}", name)).unwrap();
+    assert!(
+        output.ends_with(&format!(
+            "_{} was automatically saved to your `journal` to preserve its menu. Use `undo` to reverse this._",
+            name,
+        )),
+        "{}",
+        output,
+    );
+
+    assert!(output.starts_with(&app.command(&format!("menu {}", name)).unwrap()));
+}
+
+#[test]
+fn menu_requires_inn_or_bar() {
+    let mut app = sync_app();
+
+    app.command("blacksmith named The Anvil").unwrap();
+    app.command("pub named The Green Dragon").unwrap();
+
+    assert_eq!(
+        "The Anvil doesn't have a menu.",
+        app.command("menu the anvil").unwrap_err(),
+    );
+    assert_eq!(
+        "There is no entity named \"The Red Dragon\".",
+        app.command("menu The Red Dragon").unwrap_err(),
+    );
+
+    assert!(app
+        .command("menu The Green Dragon")
+        .unwrap()
+        .contains("**Signature Drink:** "));
+}
+
+#[test]
+fn menu_autocomplete() {
+    let mut app = sync_app();
+
+    app.command("inn named The Prancing Pony").unwrap();
+    app.command("blacksmith named The Anvil").unwrap();
+
+    assert_eq!(
+        vec![AutocompleteSuggestion::new(
+            "menu The Prancing Pony",
+            "show the menu",
+        )],
+        app.autocomplete("menu the"),
+    );
+}
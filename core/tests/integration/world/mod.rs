@@ -1,6 +1,33 @@
+mod alias;
+mod ambience;
+mod chances;
+mod connections;
+mod contact;
 mod create;
 mod create_multiple;
+mod downtime;
+mod dungeon;
 mod edit;
+mod fate;
+mod festival;
+mod inventory;
+mod lock;
+mod menu;
+mod name;
+mod party;
+mod pc;
+mod prep;
+mod random;
+mod reaction;
+mod region;
+mod rename;
+mod resources;
+mod rumor;
+mod spells;
+mod status;
+mod suggest;
+mod talk;
+mod vehicle;
 
 use crate::common::{get_name, sync_app};
 
@@ -90,6 +117,14 @@ fn generated_content_is_persisted() {
     // **Age:** 64 years\
     // **Size:** 5'7", 112 lbs (medium)
     //
+    // ## Appearance
+    //
+    // **Height:** 5'7"\
+    // **Build:** gaunt\
+    // **Hair:** grey\
+    // **Eyes:** brown\
+    // **Clothing:** plain clothes
+    //
     // _Sybil has not yet been saved. Use ~save~ to save her to your `journal`. For more
     // suggestions, type ~more~._
     let generated_output = app.command("npc").unwrap();
@@ -101,8 +136,12 @@ fn generated_content_is_persisted() {
         format!("# {}", name),
         persisted_output.lines().nth(2).unwrap(),
     );
+    // The appearance section varies in length, since not everyone has a distinguishing mark.
     assert_eq!(
-        12,
+        generated_output
+            .lines()
+            .filter(|line| !line.starts_with('_'))
+            .count(),
         generated_output
             .lines()
             .zip(persisted_output.lines())
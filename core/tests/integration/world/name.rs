@@ -0,0 +1,39 @@
+use crate::common::sync_app;
+
+#[test]
+fn name_suggests_settlement_names() {
+    let mut app = sync_app();
+
+    let output = app.command("name").unwrap();
+    assert!(
+        output.starts_with("# Settlement names (human)\n\n* "),
+        "{}",
+        output,
+    );
+    assert_eq!(12, output.lines().count(), "{}", output);
+
+    let output = app.command("name dwarvish").unwrap();
+    assert!(
+        output.starts_with("# Settlement names (dwarvish)\n\n* "),
+        "{}",
+        output,
+    );
+
+    let output = app.command("NAME ELF").unwrap();
+    assert!(
+        output.starts_with("# Settlement names (elvish)\n\n* "),
+        "{}",
+        output,
+    );
+}
+
+#[test]
+fn settlements_are_named() {
+    let mut app = sync_app();
+
+    for _ in 0..10 {
+        let output = app.command("town").unwrap();
+        assert!(!output.contains("\n# \n"), "{}", output);
+        assert!(output.contains("\n*town*\n"), "{}", output);
+    }
+}
@@ -0,0 +1,80 @@
+use crate::common::sync_app;
+
+#[test]
+fn party_location_is_set() {
+    let mut app = sync_app();
+
+    app.command("inn named The Silver Eel").unwrap();
+
+    assert_eq!(
+        "The party is now at `The Silver Eel`. Use `undo` to reverse this.",
+        app.command("party is at the silver eel").unwrap(),
+    );
+
+    let output = app.command("party").unwrap();
+    assert!(
+        output.ends_with("\n\n_The party is at `The Silver Eel`._"),
+        "{}",
+        output,
+    );
+
+    let output = app.command("now").unwrap();
+    assert!(
+        output.ends_with("\n\n_The party is at `The Silver Eel`._"),
+        "{}",
+        output,
+    );
+
+    // New characters are created where the party is.
+    let output = app.command("create elf").unwrap();
+    assert!(output.contains("`The Silver Eel`"), "{}", output);
+}
+
+#[test]
+fn travel_moves_the_party() {
+    let mut app = sync_app();
+
+    app.command("inn named The Silver Eel").unwrap();
+    app.command("party is at The Silver Eel").unwrap();
+    app.command("town named Waterdeep").unwrap();
+
+    assert_eq!(
+        "The party travels to `Waterdeep`. Use `undo` to reverse this.",
+        app.command("travel to Waterdeep").unwrap(),
+    );
+
+    let output = app.command("now").unwrap();
+    assert!(
+        output.ends_with("\n\n_The party is at `Waterdeep`._"),
+        "{}",
+        output,
+    );
+
+    assert_eq!(
+        "Successfully undid moving the party. Use `redo` to reverse this.",
+        app.command("undo").unwrap(),
+    );
+
+    let output = app.command("now").unwrap();
+    assert!(
+        output.ends_with("\n\n_The party is at `The Silver Eel`._"),
+        "{}",
+        output,
+    );
+}
+
+#[test]
+fn party_location_must_be_a_place() {
+    let mut app = sync_app();
+
+    app.command("npc named Gandalf").unwrap();
+
+    assert_eq!(
+        Err("Gandalf isn't a place.".to_string()),
+        app.command("travel to Gandalf"),
+    );
+    assert_eq!(
+        Err("There is no entity named \"Mordor\".".to_string()),
+        app.command("party is at Mordor"),
+    );
+}
@@ -0,0 +1,104 @@
+use crate::common::sync_app;
+
+#[test]
+fn player_characters_can_be_added_and_updated() {
+    let mut app = sync_app();
+
+    {
+        let output = app
+            .command("pc Aragorn, level 5 ranger, AC 16, passive perception 14, played by Sam")
+            .unwrap();
+        assert!(
+            output.contains("`Aragorn` (level 5 ranger, played by Sam)"),
+            "{}",
+            output,
+        );
+        assert!(
+            output.ends_with(
+                "_Aragorn was added to your journal as a player character. Use `undo` to reverse this._",
+            ),
+            "{}",
+            output,
+        );
+    }
+
+    app.command("pc aragorn, level 6").unwrap();
+
+    {
+        let output = app.command("Aragorn").unwrap();
+        assert!(
+            output.contains(
+                "## Player Character\n\n**Class:** ranger\\\n**Level:** 6\\\n**Armor Class:** 16\\\n**Passive Perception:** 14\\\n**Player:** Sam",
+            ),
+            "{}",
+            output,
+        );
+    }
+
+    app.command("undo").unwrap();
+
+    {
+        let output = app.command("Aragorn").unwrap();
+        assert!(output.contains("**Level:** 5"), "{}", output);
+    }
+}
+
+#[test]
+fn existing_characters_can_become_player_characters() {
+    let mut app = sync_app();
+
+    app.command("elf named Gottfried").unwrap();
+    app.command("pc Gottfried, wizard 3").unwrap();
+
+    {
+        let output = app.command("journal").unwrap();
+        assert!(output.contains("## Player Characters\n"), "{}", output);
+        assert!(!output.contains("## NPCs"), "{}", output);
+        assert!(output.contains("level 3 wizard)"), "{}", output);
+    }
+
+    app.command("inn named The Prancing Pony").unwrap();
+    assert_eq!(
+        "The Prancing Pony isn't a character.",
+        app.command("pc The Prancing Pony, level 1").unwrap_err(),
+    );
+}
+
+#[test]
+fn party_lists_player_characters() {
+    let mut app = sync_app();
+
+    assert_eq!(
+        "# Party\n\n*There are no player characters in your journal. To add one, use `pc [name]`.*",
+        app.command("party").unwrap(),
+    );
+
+    app.command("pc Aragorn, level 5 ranger, AC 16, passive perception 14, played by Sam")
+        .unwrap();
+    app.command("pc Boromir, fighter 4").unwrap();
+    app.command("elf named Gottfried").unwrap();
+
+    assert_eq!(
+        "# Party
+
+| Name | Class | Level | AC | Passive Perception | Player |
+|---|---|---|---|---|---|
+| `Aragorn` | ranger | 5 | 16 | 14 | Sam |
+| `Boromir` | fighter | 4 | - | - | - |
+
+*Average party level: 4.5*",
+        app.command("pcs").unwrap(),
+    );
+}
+
+#[test]
+fn player_characters_are_left_out_of_rumors() {
+    let mut app = sync_app();
+
+    app.command("pc Aragorn, level 5 ranger").unwrap();
+
+    for _ in 0..20 {
+        let output = app.command("rumor").unwrap();
+        assert!(!output.contains("Aragorn"), "{}", output);
+    }
+}
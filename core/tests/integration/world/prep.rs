@@ -0,0 +1,48 @@
+use crate::common::sync_app;
+
+#[test]
+fn prep_with_an_empty_journal() {
+    assert_eq!(
+        "# Session prep\n\n_It is currently day 1 at 8:00:00 am._\n\n## Recently\n\n*Nothing has been added to or removed from your journal yet.*\n\n## Coming up in the next 7 days\n\n*Nothing is scheduled.*\n\n## Unsaved\n\n*Everything has been saved to your journal.*",
+        sync_app().command("prep").unwrap(),
+    );
+}
+
+#[test]
+fn prep_lists_changes_schedule_and_unsaved_entries() {
+    let mut app = sync_app();
+
+    app.command("elf named Gottfried").unwrap();
+    app.command("Gottfried is poisoned for 2 days").unwrap();
+    app.command("Gottfried is cursed for 10 days").unwrap();
+    app.command("npc").unwrap();
+
+    let output = app.command("prep").unwrap();
+    assert!(
+        output.contains(
+            "\n\n## Recently\n\n* **day 1 at 8:00:00 am:** created `Gottfried` (character)\n"
+        ),
+        "{}",
+        output,
+    );
+    assert!(
+        output.contains("\n* **day 3 at 8:00:00 am:** `Gottfried` will no longer be poisoned."),
+        "{}",
+        output,
+    );
+    assert!(!output.contains("cursed"), "{}", output);
+    assert!(
+        output.ends_with("\n\n_Use `save [name]` to keep any of these in your `journal`._"),
+        "{}",
+        output,
+    );
+    assert!(output.contains("\n\n## Unsaved\n\n* "), "{}", output);
+
+    let output = app.command("prep 10 days").unwrap();
+    assert!(
+        output.contains("\n\n## Coming up in the next 10 days\n"),
+        "{}",
+        output,
+    );
+    assert!(output.contains("will no longer be cursed."), "{}", output);
+}
@@ -0,0 +1,50 @@
+use crate::common::sync_app;
+
+#[test]
+fn random_picks_from_the_journal() {
+    let mut app = sync_app();
+
+    app.command("inn named The Green Dragon").unwrap();
+    app.command("party is at The Green Dragon").unwrap();
+    app.command("dwarf named Gimli").unwrap();
+
+    {
+        let output = app.command("random dwarf in the green dragon").unwrap();
+        assert!(output.contains("# Gimli"), "{}", output);
+        assert!(
+            output.ends_with("_The only dwarf in The Green Dragon in your journal._"),
+            "{}",
+            output,
+        );
+    }
+
+    {
+        let output = app.command("random inn").unwrap();
+        assert!(output.contains("# The Green Dragon"), "{}", output);
+    }
+
+    assert!(app
+        .autocomplete("random dwarf in the gr")
+        .iter()
+        .any(|suggestion| suggestion.term == "random dwarf in The Green Dragon"));
+}
+
+#[test]
+fn random_offers_to_generate() {
+    let mut app = sync_app();
+
+    app.command("inn named The Green Dragon").unwrap();
+
+    assert_eq!(
+        "There are no elves in The Green Dragon in your journal. Use ~generate~ to create one.",
+        app.command("random elf in The Green Dragon").unwrap_err(),
+    );
+
+    let output = app.command("generate").unwrap();
+    assert!(output.contains("elf"), "{}", output);
+
+    assert_eq!(
+        "There is no entity named \"Greenest\".",
+        app.command("random elf in Greenest").unwrap_err(),
+    );
+}
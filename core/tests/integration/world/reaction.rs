@@ -0,0 +1,68 @@
+use crate::common::sync_app;
+
+#[test]
+fn reaction_is_rolled_and_saved() {
+    let mut app = sync_app();
+
+    app.command("elf named Gottfried").unwrap();
+
+    let output = app.command("reaction gottfried").unwrap();
+    assert!(output.contains("\n\n_Reaction roll: `2d6` ("), "{}", output);
+    assert!(
+        output.ends_with(" toward the party. Use `undo` to reverse this._"),
+        "{}",
+        output,
+    );
+
+    let attitude = [
+        "hostile",
+        "unfriendly",
+        "indifferent",
+        "friendly",
+        "helpful",
+    ]
+    .into_iter()
+    .find(|attitude| output.contains(&format!("Gottfried is {} toward", attitude)))
+    .unwrap();
+
+    let output = app.command("journal").unwrap();
+    assert!(
+        output.contains(&format!("`Gottfried` ({} ", attitude)),
+        "{}",
+        output,
+    );
+
+    app.command("undo").unwrap();
+    let output = app.command("Gottfried").unwrap();
+    assert!(!output.contains(attitude), "{}", output);
+}
+
+#[test]
+fn attitude_can_be_edited() {
+    let mut app = sync_app();
+
+    app.command("elf named Gottfried").unwrap();
+
+    let output = app.command("Gottfried is hostile").unwrap();
+    assert!(output.contains("*hostile "), "{}", output);
+
+    // Hostile characters are likely to remain hostile, but can never become helpful.
+    for _ in 0..10 {
+        let output = app.command("reaction Gottfried").unwrap();
+        assert!(output.contains(" - 4 = "), "{}", output);
+        assert!(!output.contains("is helpful toward"), "{}", output);
+        app.command("Gottfried is hostile").unwrap();
+    }
+}
+
+#[test]
+fn reaction_requires_a_character() {
+    let mut app = sync_app();
+
+    app.command("inn named Moonbright").unwrap();
+
+    assert_eq!(
+        r#"There is no character named "Moonbright"."#,
+        app.command("reaction Moonbright").unwrap_err(),
+    );
+}
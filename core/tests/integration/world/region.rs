@@ -0,0 +1,72 @@
+use crate::common::sync_app;
+
+#[test]
+fn regions_are_created_with_the_places_within_them() {
+    let mut app = sync_app();
+
+    let output = app.command("kingdom named Arnor").unwrap();
+    assert!(
+        output.contains("_Arnor has been automatically added to your `journal`, along with the places within it._"),
+        "{}",
+        output,
+    );
+    assert!(
+        output.contains("\n\n## Places within Arnor\n\n~1~ "),
+        "{}",
+        output
+    );
+    assert!(output.contains("(city)"), "{}", output);
+    assert!(output.contains("(river)"), "{}", output);
+
+    let river = app
+        .command("4")
+        .unwrap_or_else(|_| app.command("3").unwrap());
+    assert!(river.contains("*river*"), "{}", river);
+    assert!(
+        river.contains("It flows through `Arnor` from `"),
+        "{}",
+        river
+    );
+
+    let journal = app.command("journal").unwrap();
+    assert!(journal.contains("`Arnor`"), "{}", journal);
+}
+
+#[test]
+fn mountains_have_ranges_and_passes() {
+    let mut app = sync_app();
+
+    let output = app.command("mountain named Misty Mountains").unwrap();
+    assert!(output.contains("(ridge)"), "{}", output);
+    assert!(output.contains("(pass)"), "{}", output);
+
+    let pass = app.command("4").unwrap();
+    assert!(pass.contains("*pass*"), "{}", pass);
+    assert!(pass.contains("It crosses `"), "{}", pass);
+}
+
+#[test]
+fn oceans_are_created_alone() {
+    let mut app = sync_app();
+
+    let output = app.command("ocean named Belegaer").unwrap();
+    assert!(!output.contains("## Places within"), "{}", output);
+    assert!(
+        output.contains("Because you specified a name, Belegaer has been automatically added"),
+        "{}",
+        output,
+    );
+}
+
+#[test]
+fn regions_are_undone_with_the_places_within_them() {
+    let mut app = sync_app();
+
+    app.command("kingdom named Arnor").unwrap();
+    app.command("undo").unwrap();
+
+    let journal = app.command("journal").unwrap();
+    assert!(!journal.contains("`Arnor`"), "{}", journal);
+    assert!(!journal.contains("(city)"), "{}", journal);
+    assert!(!journal.contains("(river)"), "{}", journal);
+}
@@ -0,0 +1,119 @@
+use crate::common::{get_name, sync_app};
+use initiative_core::app::AutocompleteSuggestion;
+
+#[test]
+fn rename_npc() {
+    let mut app = sync_app();
+
+    app.command("man named Elvis").unwrap();
+
+    {
+        let output = app.command("rename Elvis to Aaron").unwrap();
+        assert!(output.contains("# Aaron"), "{}", output);
+        assert!(
+            output.ends_with(
+                "_Elvis was successfully renamed to Aaron. Use `undo` to reverse this._"
+            ),
+            "{}",
+            output,
+        );
+    }
+
+    {
+        let output = app.command("load Aaron").unwrap();
+        assert!(output.contains("# Aaron"), "{}", output);
+    }
+
+    {
+        let suggestions = app.autocomplete("rename aar");
+        assert_eq!(
+            vec![AutocompleteSuggestion::new(
+                "rename Aaron to [new name]",
+                "rename character",
+            )],
+            suggestions,
+        );
+    }
+
+    {
+        let output = app.command("Aaron").unwrap();
+        assert!(output.contains("# Aaron"), "{}", output);
+    }
+
+    assert_eq!(
+        "No matches for \"Elvis\"",
+        app.command("load Elvis").unwrap_err(),
+    );
+
+    {
+        let output = app.command("undo").unwrap();
+        assert!(output.contains("# Elvis"), "{}", output);
+    }
+}
+
+#[test]
+fn rename_unsaved_npc() {
+    let mut app = sync_app();
+
+    let name = get_name(&app.command("npc").unwrap());
+
+    let output = app
+        .command(&format!("rename {} to Potato Johnson", name))
+        .unwrap();
+    assert!(output.contains("# Potato Johnson"), "{}", output);
+    assert!(
+        output.ends_with(&format!(
+            "_{} was successfully renamed to Potato Johnson and automatically saved to your `journal`. Use `undo` to reverse this._",
+            name,
+        )),
+        "{}",
+        output,
+    );
+}
+
+#[test]
+fn rename_conflict() {
+    let mut app = sync_app();
+
+    app.command("man named Elvis").unwrap();
+    app.command("inn named Graceland").unwrap();
+
+    assert_eq!(
+        "That name is already in use by 🏨 `Graceland` (inn).",
+        app.command("rename Elvis to Graceland").unwrap_err(),
+    );
+
+    {
+        let output = app.command("rename Elvis to ELVIS").unwrap();
+        assert!(output.contains("# ELVIS"), "{}", output);
+    }
+
+    assert_eq!(
+        "There is no entity named \"Priscilla\".",
+        app.command("rename Priscilla to Lisa Marie").unwrap_err(),
+    );
+}
+
+#[test]
+fn rename_updates_aliases() {
+    let mut app = sync_app();
+
+    let output = app.command("create 3 inns").unwrap();
+    let name = output
+        .lines()
+        .find(|line| line.starts_with("~2~ "))
+        .and_then(|line| line.split('`').nth(1))
+        .unwrap()
+        .to_string();
+
+    app.command(&format!("rename {} to The Renamed Inn", name))
+        .unwrap();
+
+    let output = app.command("2").unwrap();
+    assert!(output.contains("# The Renamed Inn"), "{}", output);
+
+    assert!(app
+        .autocomplete("2")
+        .iter()
+        .any(|suggestion| suggestion.summary == "load The Renamed Inn"));
+}
@@ -0,0 +1,234 @@
+use crate::common::sync_app;
+
+#[test]
+fn spell_slots_can_be_used_and_recovered() {
+    let mut app = sync_app();
+
+    app.command("elf named Gottfried").unwrap();
+
+    {
+        let output = app.command("set slots 4 3 2 for Gottfried").unwrap();
+        assert!(
+            output.contains("**Spell Slots:** 1st 4/4, 2nd 3/3, 3rd 2/2"),
+            "{}",
+            output,
+        );
+        assert!(
+            output.ends_with("_Gottfried's spell slots were updated. Use `undo` to reverse this._"),
+            "{}",
+            output,
+        );
+    }
+
+    assert_eq!(
+        "Gottfried used a 3rd-level spell slot, leaving 1 of 2. Use `undo` to reverse this.",
+        app.command("use slot 3 for Gottfried").unwrap(),
+    );
+    assert_eq!(
+        "Gottfried used a 3rd-level spell slot, leaving 0 of 2. Use `undo` to reverse this.",
+        app.command("use slot 3 for gottfried").unwrap(),
+    );
+    assert_eq!(
+        "Gottfried has no 3rd-level spell slots remaining.",
+        app.command("use slot 3 for Gottfried").unwrap_err(),
+    );
+    assert_eq!(
+        "Gottfried doesn't have any 4th-level spell slots.",
+        app.command("use slot 4 for Gottfried").unwrap_err(),
+    );
+
+    {
+        let output = app.command("Gottfried").unwrap();
+        assert!(
+            output.contains("**Spell Slots:** 1st 4/4, 2nd 3/3, 3rd 0/2"),
+            "{}",
+            output,
+        );
+    }
+
+    {
+        let output = app.command("short rest Gottfried").unwrap();
+        assert!(output.contains("3rd 0/2"), "{}", output);
+        assert!(
+            output.ends_with("_Gottfried finished a short rest. It is now day 1 at 9:00:00 am._"),
+            "{}",
+            output,
+        );
+    }
+
+    {
+        let output = app.command("rest Gottfried").unwrap();
+        assert!(output.contains("3rd 2/2"), "{}", output);
+        assert!(
+            output.ends_with("_Gottfried finished a long rest. It is now day 1 at 5:00:00 pm._"),
+            "{}",
+            output,
+        );
+    }
+
+    assert_eq!(
+        "It is currently day 1 at 5:00:00 pm.",
+        app.command("now").unwrap(),
+    );
+}
+
+#[test]
+fn features_can_be_used_and_recovered() {
+    let mut app = sync_app();
+
+    app.command("human named Brother Cadfael").unwrap();
+
+    {
+        let output = app
+            .command("track 1 Channel Divinity per short rest for Brother Cadfael")
+            .unwrap();
+        assert!(
+            output.contains("**Channel Divinity:** 1/1 (short rest)"),
+            "{}",
+            output,
+        );
+    }
+
+    assert_eq!(
+        "Brother Cadfael used Channel Divinity, leaving 0 of 1. Use `undo` to reverse this.",
+        app.command("use channel divinity for Brother Cadfael")
+            .unwrap(),
+    );
+    assert_eq!(
+        "Brother Cadfael has no uses of Channel Divinity remaining.",
+        app.command("use channel divinity for Brother Cadfael")
+            .unwrap_err(),
+    );
+    assert_eq!(
+        "Brother Cadfael doesn't have Wild Shape.",
+        app.command("use Wild Shape for Brother Cadfael")
+            .unwrap_err(),
+    );
+
+    app.command("undo").unwrap();
+    assert!(app
+        .command("Brother Cadfael")
+        .unwrap()
+        .contains("**Channel Divinity:** 1/1 (short rest)"));
+
+    app.command("use Channel Divinity for Brother Cadfael")
+        .unwrap();
+
+    {
+        let output = app.command("short rest Brother Cadfael").unwrap();
+        assert!(
+            output.contains("**Channel Divinity:** 1/1 (short rest)"),
+            "{}",
+            output,
+        );
+    }
+}
+
+#[test]
+fn resources_require_a_character() {
+    let mut app = sync_app();
+
+    app.command("inn named Foo").unwrap();
+
+    assert_eq!(
+        "There is no character named \"Foo\".",
+        app.command("use slot 1 for Foo").unwrap_err(),
+    );
+    assert_eq!(
+        "There is no character named \"Potato Johnson\".",
+        app.command("rest Potato Johnson").unwrap_err(),
+    );
+}
+
+#[test]
+fn spell_slot_articles_agree_with_level() {
+    let mut app = sync_app();
+
+    app.command("elf named Gottfried").unwrap();
+    app.command("set slots 1 1 1 1 1 1 1 1 for Gottfried")
+        .unwrap();
+
+    assert_eq!(
+        "Gottfried used an 8th-level spell slot, leaving 0 of 1. Use `undo` to reverse this.",
+        app.command("use slot 8 for Gottfried").unwrap(),
+    );
+}
+
+#[test]
+fn party_rests() {
+    let mut app = sync_app();
+
+    app.command("elf named Gottfried").unwrap();
+    app.command("human named Brother Cadfael").unwrap();
+    app.command("set slots 2 for Gottfried").unwrap();
+    app.command("use slot 1 for Gottfried").unwrap();
+    app.command("track 1 Channel Divinity per short rest for Brother Cadfael")
+        .unwrap();
+    app.command("use channel divinity for Brother Cadfael")
+        .unwrap();
+    app.command("Gottfried is asleep for 1 hour").unwrap();
+
+    assert_eq!(
+        "# Short rest
+
+The party finishes a short rest. It is now day 1 at 9:00:00 am.
+
+`Brother Cadfael` recovered their resources.
+
+_Gottfried is no longer asleep._
+
+_Use `undo` to reverse this._",
+        app.command("rest short").unwrap(),
+    );
+
+    assert_eq!(
+        "# Long rest
+
+The party finishes a long rest. It is now day 1 at 5:00:00 pm.
+
+`Gottfried` recovered their resources.
+
+_Use `undo` to reverse this._",
+        app.command("rest long").unwrap(),
+    );
+
+    assert_eq!(
+        "Successfully undid changing the time and editing Gottfried. Use `redo` to reverse this.",
+        app.command("undo").unwrap(),
+    );
+    assert_eq!(
+        "It is currently day 1 at 9:00:00 am.",
+        app.command("now").unwrap(),
+    );
+
+    {
+        let output = app.command("Gottfried").unwrap();
+        assert!(output.contains("**Spell Slots:** 1st 1/2"), "{}", output);
+    }
+}
+
+#[test]
+fn party_rests_with_encounters() {
+    let mut app = sync_app();
+
+    let outputs: Vec<String> = (0..50)
+        .map(|_| app.command("rest long with encounters").unwrap())
+        .collect();
+
+    assert!(
+        outputs.iter().any(
+            |output| output.contains("_Night encounter roll: 1 on a d6._")
+                && output.contains("The rest is interrupted")
+        ),
+        "{:?}",
+        outputs,
+    );
+    assert!(
+        outputs.iter().any(
+            |output| output.contains("The night passes without incident.")
+                && output.contains("The party finishes a long rest.")
+        ),
+        "{:?}",
+        outputs,
+    );
+}
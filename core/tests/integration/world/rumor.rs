@@ -0,0 +1,41 @@
+use crate::common::sync_app;
+
+#[test]
+fn rumors_mention_the_journal() {
+    let mut app = sync_app();
+
+    app.command("elf named Gottfried").unwrap();
+
+    for _ in 0..10 {
+        let output = app.command("rumor").unwrap();
+        assert!(output.starts_with("# Rumor\n\n\""), "{}", output);
+        assert!(output.contains("`Gottfried`"), "{}", output);
+    }
+}
+
+#[test]
+fn rumors_can_be_about_an_entry() {
+    let mut app = sync_app();
+
+    app.command("elf named Gottfried").unwrap();
+    app.command("inn named Moonbright").unwrap();
+
+    for _ in 0..10 {
+        let output = app.command("rumor about moonbright").unwrap();
+        assert!(output.contains("`Moonbright`"), "{}", output);
+    }
+
+    assert_eq!(
+        r#"There is no entity named "Potato"."#,
+        app.command("rumor about Potato").unwrap_err(),
+    );
+}
+
+#[test]
+fn rumors_are_generated_without_a_journal() {
+    let mut app = sync_app();
+
+    let output = app.command("rumor").unwrap();
+    assert!(output.starts_with("# Rumor\n\n\""), "{}", output);
+    assert!(!output.contains('`'), "{}", output);
+}
@@ -0,0 +1,115 @@
+use crate::common::sync_app;
+use initiative_core::app::AutocompleteSuggestion;
+
+#[test]
+fn characters_can_learn_and_forget_spells() {
+    let mut app = sync_app();
+
+    app.command("elf named Gottfried").unwrap();
+
+    {
+        let output = app.command("Gottfried knows fireball, shield").unwrap();
+        assert!(
+            output.contains("\n\n**Spells Known:** `Fireball`, `Shield`"),
+            "{}",
+            output,
+        );
+        assert!(
+            output
+                .ends_with("_Gottfried learned Fireball and Shield. Use `undo` to reverse this._",),
+            "{}",
+            output,
+        );
+    }
+
+    {
+        let output = app.command("gottfried knows Shield and Light").unwrap();
+        assert!(
+            output.contains("**Spells Known:** `Fireball`, `Shield`, `Light`"),
+            "{}",
+            output,
+        );
+        assert!(
+            output.ends_with("_Gottfried learned Light. Use `undo` to reverse this._"),
+            "{}",
+            output,
+        );
+    }
+
+    assert_eq!(
+        "Gottfried already knows all of those spells.",
+        app.command("Gottfried knows Light").unwrap_err(),
+    );
+
+    {
+        let output = app.command("Gottfried forgets shield").unwrap();
+        assert!(
+            output.contains("**Spells Known:** `Fireball`, `Light`"),
+            "{}",
+            output,
+        );
+    }
+
+    assert_eq!(
+        "Gottfried doesn't know Shield.",
+        app.command("Gottfried forgets Shield").unwrap_err(),
+    );
+
+    app.command("undo").unwrap();
+    let output = app.command("Gottfried").unwrap();
+    assert!(
+        output.contains("**Spells Known:** `Fireball`, `Shield`, `Light`"),
+        "{}",
+        output,
+    );
+
+    // Spells link to their rules text.
+    let output = app.command("Fireball").unwrap();
+    assert!(output.starts_with("# Fireball"), "{}", output);
+}
+
+#[test]
+fn spells_are_autocompleted() {
+    let mut app = sync_app();
+
+    app.command("elf named Gottfried").unwrap();
+    app.command("Gottfried knows Fireball").unwrap();
+
+    let suggestions = app.autocomplete("Gottfried knows Fireball, shie");
+    assert!(
+        suggestions
+            .iter()
+            .any(|suggestion| suggestion.term == "Gottfried knows Fireball, Shield"),
+        "{:?}",
+        suggestions,
+    );
+
+    let suggestions = app.autocomplete("Gottfried knows fireb");
+    assert!(
+        suggestions
+            .iter()
+            .all(|suggestion| suggestion.term != "Gottfried knows Fireball"),
+        "{:?}",
+        suggestions,
+    );
+
+    assert_eq!(
+        vec![AutocompleteSuggestion::new(
+            "Gottfried forgets Fireball",
+            "forget a spell",
+        )],
+        app.autocomplete("Gottfried forgets f"),
+    );
+}
+
+#[test]
+fn only_characters_can_learn_spells() {
+    let mut app = sync_app();
+
+    app.command("inn named The Prancing Pony").unwrap();
+
+    assert_eq!(
+        "There is no character named \"The Prancing Pony\".",
+        app.command("The Prancing Pony knows Fireball").unwrap_err(),
+    );
+}
@@ -0,0 +1,149 @@
+use crate::common::sync_app;
+
+#[test]
+fn statuses_can_be_added_and_removed() {
+    let mut app = sync_app();
+
+    app.command("elf named Gottfried").unwrap();
+
+    {
+        let output = app.command("Gottfried is poisoned").unwrap();
+        assert!(
+            output.contains("Gottfried") && output.contains("[poisoned]"),
+            "{}",
+            output
+        );
+        assert!(
+            output.ends_with("_Gottfried is now poisoned. Use `undo` to reverse this._"),
+            "{}",
+            output,
+        );
+    }
+
+    {
+        let output = app.command("Gottfried is frightened").unwrap();
+        assert!(output.contains("[poisoned] [frightened]"), "{}", output);
+    }
+
+    {
+        let output = app.command("Gottfried").unwrap();
+        assert!(output.contains("[poisoned] [frightened]"), "{}", output);
+        assert!(!output.contains("**Description:**"), "{}", output);
+    }
+
+    {
+        let output = app.command("gottfried is no longer poisoned").unwrap();
+        assert!(output.contains("[frightened]"), "{}", output);
+        assert!(!output.contains("[poisoned]"), "{}", output);
+        assert!(
+            output.ends_with("_Gottfried is no longer poisoned. Use `undo` to reverse this._"),
+            "{}",
+            output,
+        );
+    }
+
+    assert_eq!(
+        "Gottfried isn't poisoned.",
+        app.command("Gottfried is no longer poisoned").unwrap_err(),
+    );
+
+    app.command("undo").unwrap();
+
+    {
+        let output = app.command("journal").unwrap();
+        assert!(output.contains("[poisoned] [frightened]"), "{}", output);
+    }
+}
+
+#[test]
+fn statuses_can_be_added_to_places() {
+    let mut app = sync_app();
+
+    app.command("inn named The Prancing Pony").unwrap();
+
+    let output = app.command("The Prancing Pony is on fire").unwrap();
+    assert!(output.contains("(inn) [on fire]"), "{}", output);
+    assert!(
+        output.ends_with("_The Prancing Pony is now on fire. Use `undo` to reverse this._"),
+        "{}",
+        output,
+    );
+}
+
+#[test]
+fn statuses_expire_as_time_passes() {
+    let mut app = sync_app();
+
+    app.command("elf named Gottfried").unwrap();
+    app.command("Gottfried is poisoned").unwrap();
+
+    {
+        let output = app.command("Gottfried is asleep for 1 hour").unwrap();
+        assert!(output.contains("[poisoned] [asleep]"), "{}", output);
+        assert!(
+            output.ends_with(
+                "_Gottfried is now asleep until day 1 at 9:00:00 am. Use `undo` to reverse this._",
+            ),
+            "{}",
+            output,
+        );
+    }
+
+    {
+        let output = app.command("+30m").unwrap();
+        assert!(!output.contains("no longer"), "{}", output);
+    }
+
+    {
+        let output = app.command("+30m").unwrap();
+        assert!(
+            output.ends_with("\n\n_Gottfried is no longer asleep._"),
+            "{}",
+            output,
+        );
+
+        let output = app.command("Gottfried").unwrap();
+        assert!(output.contains("[poisoned]"), "{}", output);
+        assert!(!output.contains("[asleep]"), "{}", output);
+    }
+
+    app.command("Gottfried is unconscious for 2 hours").unwrap();
+
+    {
+        let output = app.command("long rest Gottfried").unwrap();
+        assert!(
+            output.ends_with("\\\n_Gottfried is no longer unconscious._"),
+            "{}",
+            output,
+        );
+    }
+}
+
+#[test]
+fn statuses_must_be_known_or_timed() {
+    let mut app = sync_app();
+
+    app.command("elf named Gottfried").unwrap();
+
+    {
+        let output = app.command("Gottfried is a potato").unwrap_err();
+        assert!(
+            output.starts_with("Unknown command: \"Gottfried is a potato\""),
+            "{}",
+            output,
+        );
+    }
+
+    assert_eq!(
+        "A status has to last for some time, such as `for 1 hour`.",
+        app.command("Gottfried is poisoned for 0 minutes")
+            .unwrap_err(),
+    );
+
+    let output = app.command("Gottfried is hunted for 3 days").unwrap();
+    assert!(output.contains("[hunted]"), "{}", output);
+
+    let output = app.command("Gottfried").unwrap();
+    assert!(!output.contains("potato"), "{}", output);
+    assert!(!output.contains("[poisoned"), "{}", output);
+}
@@ -0,0 +1,75 @@
+use crate::common::sync_app;
+
+#[test]
+fn suggest_and_accept_description() {
+    let mut app = sync_app();
+
+    app.command("inn named The Silver Eel").unwrap();
+
+    let output = app.command("suggest The Silver Eel description").unwrap();
+    assert!(
+        output.starts_with("# Suggested description for The Silver Eel\n\n~1~ "),
+        "{}",
+        output,
+    );
+    assert!(output.contains("\\\n~3~ "), "{}", output);
+    assert!(
+        output.ends_with(
+            "_The Silver Eel's description hasn't been changed. Type the number of a suggestion to use it._",
+        ),
+        "{}",
+        output,
+    );
+
+    let suggestion = output
+        .lines()
+        .find_map(|line| line.strip_prefix("~2~ "))
+        .unwrap()
+        .trim_end_matches('\\')
+        .to_string();
+
+    let output = app.command("2").unwrap();
+    assert!(output.contains(&suggestion), "{}", output);
+    assert!(
+        output.ends_with("_The Silver Eel was successfully edited. Use `undo` to reverse this._"),
+        "{}",
+        output,
+    );
+
+    let output = app.command("undo").unwrap();
+    assert!(!output.contains(&suggestion), "{}", output);
+}
+
+#[test]
+fn suggest_npc_field() {
+    let mut app = sync_app();
+
+    app.command("elderly dwarf named Gottfried").unwrap();
+
+    let output = app.command("suggest Gottfried's hair").unwrap();
+    assert!(
+        output.starts_with("# Suggested hair for Gottfried\n\n~1~ "),
+        "{}",
+        output,
+    );
+
+    let output = app.command("1").unwrap();
+    assert!(output.contains("# Gottfried"), "{}", output);
+    assert!(output.contains("elderly dwarf"), "{}", output);
+}
+
+#[test]
+fn suggest_errors() {
+    let mut app = sync_app();
+
+    app.command("inn named The Silver Eel").unwrap();
+
+    assert_eq!(
+        "There is no entity named \"Potato\".",
+        app.command("suggest Potato description").unwrap_err(),
+    );
+    assert_eq!(
+        "There are no suggestions for The Silver Eel's menu. Suggestions are available for `description` and `name`.",
+        app.command("suggest The Silver Eel menu").unwrap_err(),
+    );
+}
@@ -0,0 +1,48 @@
+use crate::common::sync_app;
+
+#[test]
+fn talk_draws_on_the_journal() {
+    let mut app = sync_app();
+
+    app.command("inn named The Silver Eel").unwrap();
+    app.command("party is at The Silver Eel").unwrap();
+    app.command("npc named Gottfried").unwrap();
+    app.command("Gottfried is suspicious").unwrap();
+
+    let output = app.command("talk to Gottfried").unwrap();
+    assert!(
+        output.starts_with("# Talking to Gottfried\n\n**Greets the party warily:** \""),
+        "{}",
+        output,
+    );
+    assert!(output.contains("\\\n**Wants:** "), "{}", output);
+    assert!(output.contains("\\\n**Knows:** \""), "{}", output);
+    assert!(output.contains("`The Silver Eel`"), "{}", output);
+    assert!(
+        output.ends_with(
+            "\n\n_Gottfried hasn't formed an opinion of the party yet. Use `reaction Gottfried` to roll one._"
+        ),
+        "{}",
+        output,
+    );
+
+    app.command("Gottfried is friendly").unwrap();
+    let output = app.command("talk Gottfried").unwrap();
+    assert!(
+        output.ends_with("\n\n_Gottfried is friendly toward the party._"),
+        "{}",
+        output,
+    );
+}
+
+#[test]
+fn talk_requires_character() {
+    let mut app = sync_app();
+
+    app.command("inn named The Silver Eel").unwrap();
+
+    assert_eq!(
+        Err("There is no character named \"The Silver Eel\".".to_string()),
+        app.command("talk The Silver Eel"),
+    );
+}
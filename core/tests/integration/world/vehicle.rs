@@ -0,0 +1,68 @@
+use crate::common::{get_name, sync_app};
+
+#[test]
+fn vehicle_is_generated_with_capacity() {
+    let mut app = sync_app();
+
+    let output = app.command("ship").unwrap();
+    assert!(
+        output.contains("**Speed:** 2 mph (48 miles per day)\\\n"),
+        "{}",
+        output,
+    );
+    assert!(output.contains("\\\n**Cargo:** 100 tons"), "{}", output);
+
+    let output = app.command("caravan").unwrap();
+    assert!(
+        output.contains("**Speed:** 3 mph (24 miles per day)\\\n"),
+        "{}",
+        output,
+    );
+}
+
+#[test]
+fn crew_is_generated_aboard() {
+    let mut app = sync_app();
+
+    let name = get_name(&app.command("airship").unwrap());
+
+    let output = app.command(&format!("crew {}", name)).unwrap();
+    assert!(
+        output.starts_with(&format!("# Crew of {}\n\n_{} was automatically saved to your `journal` so that its crew can be aboard._\n\n~1~ ", name, name)),
+        "{}",
+        output,
+    );
+    assert!(output.contains("\\\n~3~ "), "{}", output);
+    assert!(!output.contains("~4~"), "{}", output);
+
+    let crew_member = app.command("1").unwrap();
+    assert!(
+        crew_member.contains(&format!("`{}`", name)),
+        "{}",
+        crew_member
+    );
+
+    // The same crew is shown the second time around.
+    let again = app.command(&format!("crew {}", name)).unwrap();
+    assert!(
+        output.ends_with(again.split_once("\n\n").unwrap().1),
+        "{}",
+        again
+    );
+}
+
+#[test]
+fn crew_requires_vehicle() {
+    let mut app = sync_app();
+
+    app.command("inn named The Prancing Pony").unwrap();
+
+    assert_eq!(
+        "The Prancing Pony doesn't have a crew.",
+        app.command("crew The Prancing Pony").unwrap_err(),
+    );
+    assert_eq!(
+        "There is no entity named \"The Salty Gull\".",
+        app.command("crew The Salty Gull").unwrap_err(),
+    );
+}
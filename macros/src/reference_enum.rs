@@ -7,6 +7,12 @@ struct Entry {
     name: String,
     aliases: Vec<String>,
     details: String,
+    rarity: Option<String>,
+    attunement: bool,
+    spell: Option<(u8, String, Vec<String>)>,
+    index: Option<String>,
+    weight: Option<f32>,
+    cost_cp: Option<u32>,
 }
 #[derive(Default)]
 struct EntryBuilder {
@@ -14,6 +20,12 @@ struct EntryBuilder {
     name: Option<String>,
     aliases: Vec<String>,
     details: Option<String>,
+    rarity: Option<String>,
+    attunement: bool,
+    spell: Option<(u8, String, Vec<String>)>,
+    index: Option<String>,
+    weight: Option<f32>,
+    cost_cp: Option<u32>,
 }
 
 impl EntryBuilder {
@@ -37,12 +49,48 @@ impl EntryBuilder {
         self
     }
 
+    fn with_rarity(mut self, rarity: Option<String>) -> Self {
+        self.rarity = rarity;
+        self
+    }
+
+    fn with_attunement(mut self, attunement: bool) -> Self {
+        self.attunement = attunement;
+        self
+    }
+
+    fn with_spell(mut self, level: u8, school: String, classes: Vec<String>) -> Self {
+        self.spell = Some((level, school, classes));
+        self
+    }
+
+    fn with_index(mut self, index: &str) -> Self {
+        self.index = Some(index.to_string());
+        self
+    }
+
+    fn with_weight(mut self, weight: Option<f32>) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    fn with_cost_cp(mut self, cost_cp: Option<u32>) -> Self {
+        self.cost_cp = cost_cp;
+        self
+    }
+
     fn into_entry(self) -> Result<Entry, ()> {
         Ok(Entry {
             ident: self.ident.ok_or(())?,
             name: self.name.ok_or(())?,
             aliases: self.aliases,
             details: self.details.ok_or(())?,
+            rarity: self.rarity,
+            attunement: self.attunement,
+            spell: self.spell,
+            index: self.index,
+            weight: self.weight,
+            cost_cp: self.cost_cp,
         })
     }
 }
@@ -51,6 +99,34 @@ pub fn run(input: TokenStream) -> Result<TokenStream, String> {
     let ident = parse_args(input)?;
 
     let entries: Vec<Entry> = match format!("{}", ident).as_str() {
+        "Class" => {
+            let levels = srd_5e::levels()?;
+            let mut result = Vec::new();
+
+            for class in srd_5e::classes()?.iter() {
+                result.push(
+                    EntryBuilder::default()
+                        .with_ident(&class.token())
+                        .with_name(class.name.to_owned())
+                        .with_details(&class.display_details(&levels))
+                        .into_entry()
+                        .unwrap(),
+                );
+
+                for level in class.levels(&levels) {
+                    result.push(
+                        EntryBuilder::default()
+                            .with_ident(&format!("{}{}", class.token(), level.level))
+                            .with_name(format!("{} {}", class.name, level.level))
+                            .with_details(&class.display_level(&levels, level.level))
+                            .into_entry()
+                            .unwrap(),
+                    );
+                }
+            }
+
+            result
+        }
         "Condition" => srd_5e::conditions()?
             .iter()
             .map(|condition| {
@@ -70,6 +146,9 @@ pub fn run(input: TokenStream) -> Result<TokenStream, String> {
                     .with_name(item.name())
                     .with_aliases(item.alt_name().into_iter().collect())
                     .with_details(&item.display_details())
+                    .with_index(item.index())
+                    .with_weight(item.weight())
+                    .with_cost_cp(item.cost_cp())
                     .into_entry()
                     .unwrap()
             })
@@ -139,6 +218,8 @@ pub fn run(input: TokenStream) -> Result<TokenStream, String> {
                     .with_ident(&item.token())
                     .with_name(item.name.to_owned())
                     .with_details(&item.display_details())
+                    .with_rarity(item.rarity())
+                    .with_attunement(item.requires_attunement())
                     .into_entry()
                     .unwrap()
             })
@@ -150,6 +231,7 @@ pub fn run(input: TokenStream) -> Result<TokenStream, String> {
                     .with_ident(&spell.token())
                     .with_name(spell.name.to_owned())
                     .with_details(&spell.display_details())
+                    .with_spell(spell.level(), spell.school(), spell.classes())
                     .into_entry()
                     .unwrap()
             })
@@ -199,6 +281,54 @@ pub fn run(input: TokenStream) -> Result<TokenStream, String> {
         quote! { #ident::#variant => #output }
     });
 
+    let get_spell = if ident == "Spell" {
+        let get_level_cases = entries.iter().map(|entry| {
+            let variant = &entry.ident;
+            let level = entry.spell.as_ref().map_or(0, |(level, _, _)| *level);
+            quote! { #ident::#variant => #level }
+        });
+
+        let get_school_cases = entries.iter().map(|entry| {
+            let variant = &entry.ident;
+            let school = entry
+                .spell
+                .as_ref()
+                .map_or("", |(_, school, _)| school.as_str());
+            quote! { #ident::#variant => #school }
+        });
+
+        let get_classes_cases = entries.iter().map(|entry| {
+            let variant = &entry.ident;
+            let classes = entry
+                .spell
+                .as_ref()
+                .map_or(&[][..], |(_, _, classes)| &classes[..]);
+            quote! { #ident::#variant => &[#(#classes),*] }
+        });
+
+        quote! {
+            pub fn get_level(&self) -> u8 {
+                match self {
+                    #(#get_level_cases),*
+                }
+            }
+
+            pub fn get_school(&self) -> &'static str {
+                match self {
+                    #(#get_school_cases),*
+                }
+            }
+
+            pub fn get_classes(&self) -> &'static [&'static str] {
+                match self {
+                    #(#get_classes_cases),*
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
     let get_list = if ident == "Spell" {
         let list_output: String = std::iter::once(format!("# {}s", ident))
             .chain(
@@ -217,6 +347,101 @@ pub fn run(input: TokenStream) -> Result<TokenStream, String> {
         quote! {}
     };
 
+    let get_rarity = if ident == "MagicItem" {
+        let variants = entries.iter().map(|entry| &entry.ident);
+        let get_rarity_cases = entries.iter().map(|entry| {
+            let variant = &entry.ident;
+            match &entry.rarity {
+                Some(rarity) => quote! { #ident::#variant => Some(#rarity) },
+                None => quote! { #ident::#variant => None },
+            }
+        });
+        let requires_attunement_cases = entries.iter().map(|entry| {
+            let variant = &entry.ident;
+            let attunement = entry.attunement;
+            quote! { #ident::#variant => #attunement }
+        });
+
+        quote! {
+            pub fn get_all() -> impl Iterator<Item = Self> {
+                [#(#ident::#variants),*].into_iter()
+            }
+
+            pub fn get_rarity(&self) -> Option<&'static str> {
+                match self {
+                    #(#get_rarity_cases),*
+                }
+            }
+
+            pub fn requires_attunement(&self) -> bool {
+                match self {
+                    #(#requires_attunement_cases),*
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let get_weight = if ident == "Item" {
+        let from_index_cases = entries.iter().filter_map(|entry| {
+            let variant = &entry.ident;
+            let index = entry.index.as_ref()?;
+            Some(quote! { #index => Some(#ident::#variant) })
+        });
+
+        let get_index_cases = entries.iter().map(|entry| {
+            let variant = &entry.ident;
+            let index = entry.index.as_deref().unwrap_or_default();
+            quote! { #ident::#variant => #index }
+        });
+
+        let get_weight_cases = entries.iter().map(|entry| {
+            let variant = &entry.ident;
+            match entry.weight {
+                Some(weight) => quote! { #ident::#variant => Some(#weight) },
+                None => quote! { #ident::#variant => None },
+            }
+        });
+
+        let get_cost_cp_cases = entries.iter().map(|entry| {
+            let variant = &entry.ident;
+            match entry.cost_cp {
+                Some(cost_cp) => quote! { #ident::#variant => Some(#cost_cp) },
+                None => quote! { #ident::#variant => None },
+            }
+        });
+
+        quote! {
+            pub fn from_index(index: &str) -> Option<Self> {
+                match index {
+                    #(#from_index_cases),*,
+                    _ => None,
+                }
+            }
+
+            pub fn get_index(&self) -> &'static str {
+                match self {
+                    #(#get_index_cases),*
+                }
+            }
+
+            pub fn get_weight(&self) -> Option<f32> {
+                match self {
+                    #(#get_weight_cases),*
+                }
+            }
+
+            pub fn get_cost_cp(&self) -> Option<u32> {
+                match self {
+                    #(#get_cost_cp_cases),*
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
     let words = entries.iter().flat_map(|entry| {
         let name = &entry.name;
         std::iter::once(quote! { #name, })
@@ -236,6 +461,12 @@ pub fn run(input: TokenStream) -> Result<TokenStream, String> {
 
             #get_list
 
+            #get_spell
+
+            #get_rarity
+
+            #get_weight
+
             pub fn get_name(&self) -> &'static str {
                 match self {
                     #(#get_name_cases),*
@@ -3,7 +3,8 @@
 //! reference type, such as spells or magic items.
 //!
 //! This serves as a dependency of the `initiative_macros` crate, specifically the `reference_enum`
-//! macro. As a result, it only runs at compile time.
+//! macro, which parses the bundled SRD data at compile time. The same structs are also used by
+//! `initiative_core` at runtime to read homebrew content provided by the user in the same format.
 
 pub mod srd_5e;
 
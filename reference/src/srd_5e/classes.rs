@@ -0,0 +1,346 @@
+use super::Reference;
+use serde::Deserialize;
+use std::fmt;
+
+#[derive(Debug, Deserialize)]
+pub struct Class {
+    index: String,
+    pub name: String,
+
+    hit_die: u8,
+
+    #[serde(default)]
+    proficiencies: Vec<Reference>,
+
+    #[serde(default)]
+    saving_throws: Vec<Reference>,
+}
+
+/// A row of a class table, listing the features and spell slots gained at a given level.
+#[derive(Debug, Deserialize)]
+pub struct Level {
+    pub level: u8,
+
+    #[serde(default)]
+    prof_bonus: Option<u8>,
+
+    #[serde(default)]
+    features: Vec<Reference>,
+
+    #[serde(default)]
+    spellcasting: Option<Spellcasting>,
+
+    class: Reference,
+
+    /// Subclasses have rows of their own, which aren't part of the class table.
+    #[serde(default)]
+    subclass: Option<Reference>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct Spellcasting {
+    cantrips_known: Option<u8>,
+    spells_known: Option<u8>,
+    spell_slots_level_1: Option<u8>,
+    spell_slots_level_2: Option<u8>,
+    spell_slots_level_3: Option<u8>,
+    spell_slots_level_4: Option<u8>,
+    spell_slots_level_5: Option<u8>,
+    spell_slots_level_6: Option<u8>,
+    spell_slots_level_7: Option<u8>,
+    spell_slots_level_8: Option<u8>,
+    spell_slots_level_9: Option<u8>,
+}
+
+pub enum Column {
+    CantripsKnown,
+    Features,
+    Level,
+    ProficiencyBonus,
+    SpellSlots(u8),
+    SpellsKnown,
+}
+
+pub struct DetailsView<'a> {
+    class: &'a Class,
+    levels: Vec<&'a Level>,
+}
+
+pub struct LevelView<'a> {
+    class: &'a Class,
+    levels: Vec<&'a Level>,
+    level: u8,
+}
+
+pub struct TableRowView<'a> {
+    level: &'a Level,
+    columns: &'a [Column],
+}
+
+impl Class {
+    pub fn token(&self) -> String {
+        crate::to_camel_case(&self.index)
+    }
+
+    /// The levels of this class from the full list of levels, in order.
+    pub fn levels<'a>(&self, levels: &'a [Level]) -> Vec<&'a Level> {
+        let mut levels: Vec<&Level> = levels
+            .iter()
+            .filter(|level| level.subclass.is_none() && level.class.index == self.index)
+            .collect();
+        levels.sort_by_key(|level| level.level);
+        levels
+    }
+
+    /// The class table, listing every level of the class.
+    pub fn display_details<'a>(&'a self, levels: &'a [Level]) -> DetailsView {
+        DetailsView {
+            class: self,
+            levels: self.levels(levels),
+        }
+    }
+
+    /// The features gained up to and including a given level, along with the row of the class
+    /// table for that level.
+    pub fn display_level<'a>(&'a self, levels: &'a [Level], level: u8) -> LevelView {
+        LevelView {
+            class: self,
+            levels: self.levels(levels),
+            level,
+        }
+    }
+
+    fn display_summary(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "**Hit Die:** d{}", self.hit_die)?;
+
+        if !self.saving_throws.is_empty() {
+            write!(
+                f,
+                "\\\n**Saving Throws:** {}",
+                self.saving_throws
+                    .iter()
+                    .map(|r| r.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            )?;
+        }
+
+        if !self.proficiencies.is_empty() {
+            write!(
+                f,
+                "\\\n**Proficiencies:** {}",
+                self.proficiencies
+                    .iter()
+                    .filter(|r| !r.index.starts_with("saving-throw-"))
+                    .map(|r| r.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Level {
+    pub fn display_table_row<'a>(&'a self, columns: &'a [Column]) -> TableRowView {
+        TableRowView {
+            level: self,
+            columns,
+        }
+    }
+
+    fn spell_slots(&self, spell_level: u8) -> Option<u8> {
+        let spellcasting = self.spellcasting.as_ref()?;
+
+        match spell_level {
+            1 => spellcasting.spell_slots_level_1,
+            2 => spellcasting.spell_slots_level_2,
+            3 => spellcasting.spell_slots_level_3,
+            4 => spellcasting.spell_slots_level_4,
+            5 => spellcasting.spell_slots_level_5,
+            6 => spellcasting.spell_slots_level_6,
+            7 => spellcasting.spell_slots_level_7,
+            8 => spellcasting.spell_slots_level_8,
+            9 => spellcasting.spell_slots_level_9,
+            _ => None,
+        }
+    }
+
+    fn feature_names(&self) -> String {
+        self.features
+            .iter()
+            .map(|r| r.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// The columns of a class table. Spellcasting columns are only included if at least one of the
+/// given levels has a nonzero value for them.
+fn columns(levels: &[&Level], include_features: bool) -> Vec<Column> {
+    let has_value = |get: &dyn Fn(&Level) -> Option<u8>| {
+        levels
+            .iter()
+            .any(|level| get(level).map_or(false, |value| value > 0))
+    };
+
+    let mut columns = vec![Column::Level, Column::ProficiencyBonus];
+
+    if include_features {
+        columns.push(Column::Features);
+    }
+
+    if has_value(&|level| level.spellcasting.as_ref()?.cantrips_known) {
+        columns.push(Column::CantripsKnown);
+    }
+
+    if has_value(&|level| level.spellcasting.as_ref()?.spells_known) {
+        columns.push(Column::SpellsKnown);
+    }
+
+    (1..=9)
+        .filter(|&spell_level| has_value(&|level| level.spell_slots(spell_level)))
+        .for_each(|spell_level| columns.push(Column::SpellSlots(spell_level)));
+
+    columns
+}
+
+fn ordinal(value: u8) -> String {
+    match value {
+        1 => "1st".to_string(),
+        2 => "2nd".to_string(),
+        3 => "3rd".to_string(),
+        n => format!("{}th", n),
+    }
+}
+
+fn write_table_header(f: &mut fmt::Formatter, columns: &[Column]) -> fmt::Result {
+    write!(f, "|")?;
+
+    columns
+        .iter()
+        .try_for_each(|column| write!(f, " {} |", column))?;
+    write!(f, "\n|")?;
+
+    columns.iter().try_for_each(|column| match column {
+        Column::Features => write!(f, "---|"),
+        _ => write!(f, "--:|"),
+    })
+}
+
+impl fmt::Display for Column {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::CantripsKnown => write!(f, "Cantrips Known"),
+            Self::Features => write!(f, "Features"),
+            Self::Level => write!(f, "Level"),
+            Self::ProficiencyBonus => write!(f, "Proficiency Bonus"),
+            Self::SpellSlots(spell_level) => write!(f, "{}", ordinal(*spell_level)),
+            Self::SpellsKnown => write!(f, "Spells Known"),
+        }
+    }
+}
+
+impl<'a> fmt::Display for TableRowView<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let level = self.level;
+
+        write!(f, "|")?;
+
+        self.columns.iter().try_for_each(|column| {
+            let value = match column {
+                Column::CantripsKnown => level.spellcasting.as_ref().and_then(|s| s.cantrips_known),
+                Column::Level => return write!(f, " {} |", ordinal(level.level)),
+                Column::Features => {
+                    return if level.features.is_empty() {
+                        write!(f, " — |")
+                    } else {
+                        write!(f, " {} |", level.feature_names())
+                    }
+                }
+                Column::ProficiencyBonus => {
+                    return match level.prof_bonus {
+                        Some(bonus) => write!(f, " +{} |", bonus),
+                        None => write!(f, " — |"),
+                    }
+                }
+                Column::SpellSlots(spell_level) => level.spell_slots(*spell_level),
+                Column::SpellsKnown => level.spellcasting.as_ref().and_then(|s| s.spells_known),
+            };
+
+            match value {
+                Some(value) if value > 0 => write!(f, " {} |", value),
+                _ => write!(f, " — |"),
+            }
+        })
+    }
+}
+
+impl<'a> fmt::Display for DetailsView<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let class = self.class;
+
+        write!(f, "# {}\n\n", class.name)?;
+        class.display_summary(f)?;
+
+        if !self.levels.is_empty() {
+            let columns = columns(&self.levels, true);
+
+            write!(f, "\n\n")?;
+            write_table_header(f, &columns)?;
+
+            self.levels
+                .iter()
+                .try_for_each(|level| write!(f, "\n{}", level.display_table_row(&columns)))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> fmt::Display for LevelView<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let class = self.class;
+        let current = self.levels.iter().find(|level| level.level == self.level);
+
+        write!(f, "# {} {}\n\n", class.name, self.level)?;
+        class.display_summary(f)?;
+
+        if let Some(bonus) = current.and_then(|level| level.prof_bonus) {
+            write!(f, "\\\n**Proficiency Bonus:** +{}", bonus)?;
+        }
+
+        let mut features = self
+            .levels
+            .iter()
+            .filter(|level| level.level <= self.level && !level.features.is_empty())
+            .peekable();
+
+        if features.peek().is_some() {
+            write!(f, "\n\n## Features\n")?;
+
+            features.try_for_each(|level| {
+                write!(
+                    f,
+                    "\n* **{} level:** {}",
+                    ordinal(level.level),
+                    level.feature_names(),
+                )
+            })?;
+        }
+
+        if let Some(level) = current {
+            let columns = columns(&[level], false);
+
+            if columns.len() > 2 {
+                write!(f, "\n\n## Spellcasting\n\n")?;
+                write_table_header(f, &columns)?;
+                write!(f, "\n{}", level.display_table_row(&columns))?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,5 +1,5 @@
-use super::{Column, Item, MagicItem};
-use crate::srd_5e::Reference;
+use super::{Column, Item};
+use crate::srd_5e::{MagicItem, Reference};
 use serde::Deserialize;
 use std::fmt;
 
@@ -106,6 +106,29 @@ impl Item {
         crate::to_camel_case(&self.index)
     }
 
+    pub fn index(&self) -> &str {
+        &self.index
+    }
+
+    /// The weight of the item in pounds, if it has one.
+    pub fn weight(&self) -> Option<f32> {
+        self.weight
+    }
+
+    /// The cost of the item in copper pieces, if it has one.
+    pub fn cost_cp(&self) -> Option<u32> {
+        let multiplier = match self.cost.unit.as_str() {
+            "cp" => 1.,
+            "sp" => 10.,
+            "ep" => 50.,
+            "gp" => 100.,
+            "pp" => 1000.,
+            _ => return None,
+        };
+
+        Some((self.cost.quantity * multiplier).round() as u32).filter(|&cost| cost > 0)
+    }
+
     pub fn display_table_row<'a>(&'a self, columns: &'a [Column]) -> TableRowView {
         TableRowView {
             item: self,
@@ -1,10 +1,8 @@
 pub use category::ItemCategory;
 pub use item::Item;
-pub use magic_item::MagicItem;
 
 mod category;
 mod item;
-mod magic_item;
 
 use std::fmt;
 
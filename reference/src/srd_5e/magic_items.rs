@@ -12,6 +12,14 @@ pub struct MagicItem {
 
     #[serde(default)]
     variants: Vec<Reference>,
+
+    #[serde(default)]
+    rarity: Option<Rarity>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Rarity {
+    name: String,
 }
 
 impl MagicItem {
@@ -30,6 +38,22 @@ impl MagicItem {
     pub fn has_variants(&self) -> bool {
         !self.variants.is_empty()
     }
+
+    /// The rarity of the item in lowercase, eg. "very rare". Items whose variants differ in
+    /// rarity, such as `Armor, +1, +2, or +3`, have a rarity of "varies".
+    pub fn rarity(&self) -> Option<String> {
+        self.rarity
+            .as_ref()
+            .map(|rarity| rarity.name.to_lowercase())
+    }
+
+    /// Whether a creature must attune to the item to use it, as noted in the first line of its
+    /// description, eg. "Rod, rare (requires attunement)".
+    pub fn requires_attunement(&self) -> bool {
+        self.desc
+            .first()
+            .map_or(false, |line| line.contains("requires attunement"))
+    }
 }
 
 pub struct SummaryView<'a>(&'a MagicItem);
@@ -1,15 +1,26 @@
+pub use classes::{Class, Level};
 pub use conditions::Condition;
-pub use equipment::{Item, ItemCategory, MagicItem};
+pub use equipment::{Item, ItemCategory};
+pub use magic_items::MagicItem;
+pub use monster::Monster;
 pub use spell::Spell;
 pub use std::fmt;
 pub use traits::Trait;
 
+mod classes;
 mod conditions;
 mod equipment;
+mod magic_items;
+mod monster;
 mod spell;
 mod traits;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+
+pub fn classes() -> Result<Vec<Class>, String> {
+    serde_json::from_str(include_str!("../../../data/srd_5e/src/5e-SRD-Classes.json"))
+        .map_err(|e| format!("{}", e))
+}
 
 pub fn conditions() -> Result<Vec<Condition>, String> {
     serde_json::from_str(include_str!(
@@ -32,6 +43,11 @@ pub fn item_categories() -> Result<Vec<ItemCategory>, String> {
     .map_err(|e| format!("{}", e))
 }
 
+pub fn levels() -> Result<Vec<Level>, String> {
+    serde_json::from_str(include_str!("../../../data/srd_5e/src/5e-SRD-Levels.json"))
+        .map_err(|e| format!("{}", e))
+}
+
 pub fn magic_items() -> Result<Vec<MagicItem>, String> {
     serde_json::from_str(include_str!(
         "../../../data/srd_5e/src/5e-SRD-Magic-Items.json",
@@ -44,6 +60,13 @@ pub fn magic_items() -> Result<Vec<MagicItem>, String> {
     .map_err(|e| format!("{}", e))
 }
 
+pub fn monsters() -> Result<Vec<Monster>, String> {
+    serde_json::from_str(include_str!(
+        "../../../data/srd_5e/src/5e-SRD-Monsters.json",
+    ))
+    .map_err(|e| format!("{}", e))
+}
+
 pub fn spells() -> Result<Vec<Spell>, String> {
     serde_json::from_str(include_str!("../../../data/srd_5e/src/5e-SRD-Spells.json"))
         .map_err(|e| format!("{}", e))
@@ -54,7 +77,7 @@ pub fn traits() -> Result<Vec<Trait>, String> {
         .map_err(|e| format!("{}", e))
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct Reference {
     index: String,
     name: String,
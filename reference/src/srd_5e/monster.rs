@@ -0,0 +1,515 @@
+use super::{write_text_block, Reference};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Monster {
+    index: String,
+    pub name: String,
+
+    size: String,
+
+    #[serde(rename = "type")]
+    monster_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    subtype: Option<String>,
+    alignment: String,
+
+    armor_class: ArmorClass,
+    hit_points: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hit_points_roll: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hit_dice: Option<String>,
+
+    #[serde(default)]
+    speed: BTreeMap<String, Value>,
+
+    strength: u8,
+    dexterity: u8,
+    constitution: u8,
+    intelligence: u8,
+    wisdom: u8,
+    charisma: u8,
+
+    #[serde(default)]
+    proficiencies: Vec<Proficiency>,
+
+    #[serde(default)]
+    damage_vulnerabilities: Vec<String>,
+
+    #[serde(default)]
+    damage_resistances: Vec<String>,
+
+    #[serde(default)]
+    damage_immunities: Vec<String>,
+
+    #[serde(default)]
+    condition_immunities: Vec<Reference>,
+
+    #[serde(default)]
+    senses: BTreeMap<String, Value>,
+
+    #[serde(default)]
+    languages: String,
+
+    challenge_rating: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    xp: Option<u32>,
+
+    #[serde(default)]
+    special_abilities: Vec<Ability>,
+
+    #[serde(default)]
+    actions: Vec<Ability>,
+
+    #[serde(default)]
+    reactions: Vec<Ability>,
+
+    #[serde(default)]
+    legendary_actions: Vec<Ability>,
+}
+
+/// The API has represented armor class both as a bare number and as a list of sources, eg.
+/// `[{"type": "natural", "value": 17}]`.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+enum ArmorClass {
+    Value(u8),
+    Sources(Vec<ArmorClassSource>),
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct ArmorClassSource {
+    #[serde(rename = "type")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source_type: Option<String>,
+    value: u8,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Proficiency {
+    value: i8,
+    proficiency: Reference,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Ability {
+    name: String,
+    desc: String,
+}
+
+pub struct SummaryView<'a>(&'a Monster);
+
+pub struct DetailsView<'a>(&'a Monster);
+
+impl Monster {
+    pub fn token(&self) -> String {
+        crate::to_camel_case(&self.index)
+    }
+
+    pub fn display_summary(&self) -> SummaryView {
+        SummaryView(self)
+    }
+
+    pub fn display_details(&self) -> DetailsView {
+        DetailsView(self)
+    }
+
+    /// The challenge rating as a number, eg. 0.25.
+    pub fn challenge_rating(&self) -> f32 {
+        self.challenge_rating
+    }
+
+    /// The creature type, eg. "dragon" or "undead".
+    pub fn monster_type(&self) -> &str {
+        &self.monster_type
+    }
+
+    /// Legendary monsters are the ones with legendary actions, and usually keep a lair.
+    pub fn is_legendary(&self) -> bool {
+        !self.legendary_actions.is_empty()
+    }
+
+    /// The challenge rating as written in a stat block, eg. "1/4" or "10".
+    pub fn challenge(&self) -> String {
+        match self.challenge_rating {
+            0.125 => "1/8".to_string(),
+            0.25 => "1/4".to_string(),
+            0.5 => "1/2".to_string(),
+            cr => format!("{}", cr),
+        }
+    }
+
+    fn get_size_type(&self) -> String {
+        let mut size_type = format!("{} {}", self.size, self.monster_type);
+
+        if let Some(subtype) = &self.subtype {
+            size_type.push_str(&format!(" ({})", subtype));
+        }
+
+        size_type
+    }
+
+    /// The proficiencies of a given kind, eg. "Saving Throw: ", formatted as "Con +6, Int +8".
+    fn get_proficiencies(&self, prefix: &str) -> Option<String> {
+        let proficiencies: Vec<String> = self
+            .proficiencies
+            .iter()
+            .filter_map(|p| {
+                p.proficiency
+                    .name
+                    .strip_prefix(prefix)
+                    .map(|name| format!("{} {:+}", capitalize_first(name), p.value))
+            })
+            .collect();
+
+        if proficiencies.is_empty() {
+            None
+        } else {
+            Some(proficiencies.join(", "))
+        }
+    }
+}
+
+impl<'a> fmt::Display for SummaryView<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let monster = self.0;
+
+        write!(
+            f,
+            "`{}` ({}, CR {})",
+            monster.name,
+            monster.get_size_type().to_lowercase(),
+            monster.challenge(),
+        )
+    }
+}
+
+impl<'a> fmt::Display for DetailsView<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let monster = self.0;
+
+        write!(
+            f,
+            "# {}\n*{}, {}*\n\n**Armor Class:** {}",
+            monster.name,
+            monster.get_size_type(),
+            monster.alignment,
+            monster.armor_class,
+        )?;
+
+        write!(f, "\\\n**Hit Points:** {}", monster.hit_points)?;
+        if let Some(roll) = monster
+            .hit_points_roll
+            .as_ref()
+            .or(monster.hit_dice.as_ref())
+        {
+            write!(f, " ({})", roll)?;
+        }
+
+        {
+            // The walking speed comes first and is written without a label.
+            let mut speeds: Vec<String> = monster
+                .speed
+                .get("walk")
+                .map(value_to_string)
+                .into_iter()
+                .collect();
+
+            monster
+                .speed
+                .iter()
+                .filter(|(mode, _)| mode.as_str() != "walk")
+                .for_each(|(mode, speed)| {
+                    speeds.push(format!("{} {}", mode, value_to_string(speed)))
+                });
+
+            if !speeds.is_empty() {
+                write!(f, "\\\n**Speed:** {}", speeds.join(", "))?;
+            }
+        }
+
+        write!(
+            f,
+            "\n\n| STR | DEX | CON | INT | WIS | CHA |\n|:-:|:-:|:-:|:-:|:-:|:-:|\n|",
+        )?;
+        [
+            monster.strength,
+            monster.dexterity,
+            monster.constitution,
+            monster.intelligence,
+            monster.wisdom,
+            monster.charisma,
+        ]
+        .iter()
+        .try_for_each(|&score| {
+            write!(f, " {} ({:+}) |", score, (score as i8 - 10).div_euclid(2))
+        })?;
+
+        let mut traits: Vec<(&str, String)> = Vec::new();
+
+        if let Some(saves) = monster.get_proficiencies("Saving Throw: ") {
+            traits.push(("Saving Throws", saves));
+        }
+        if let Some(skills) = monster.get_proficiencies("Skill: ") {
+            traits.push(("Skills", skills));
+        }
+        for (label, list) in [
+            ("Damage Vulnerabilities", &monster.damage_vulnerabilities),
+            ("Damage Resistances", &monster.damage_resistances),
+            ("Damage Immunities", &monster.damage_immunities),
+        ] {
+            if !list.is_empty() {
+                traits.push((label, list.join(", ")));
+            }
+        }
+        if !monster.condition_immunities.is_empty() {
+            traits.push((
+                "Condition Immunities",
+                monster
+                    .condition_immunities
+                    .iter()
+                    .map(|c| c.name.to_lowercase())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ));
+        }
+        if !monster.senses.is_empty() {
+            traits.push((
+                "Senses",
+                monster
+                    .senses
+                    .iter()
+                    .map(|(sense, value)| {
+                        if let Some(skill) = sense.strip_prefix("passive_") {
+                            format!(
+                                "passive {} {}",
+                                capitalize_first(skill),
+                                value_to_string(value)
+                            )
+                        } else {
+                            format!("{} {}", sense, value_to_string(value))
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ));
+        }
+        traits.push((
+            "Languages",
+            if monster.languages.is_empty() {
+                "—".to_string()
+            } else {
+                monster.languages.clone()
+            },
+        ));
+        traits.push((
+            "Challenge",
+            if let Some(xp) = monster.xp {
+                format!("{} ({} XP)", monster.challenge(), thousands(xp))
+            } else {
+                monster.challenge()
+            },
+        ));
+
+        let mut trait_iter = traits.iter();
+        if let Some((label, value)) = trait_iter.next() {
+            write!(f, "\n\n**{}:** {}", label, value)?;
+            trait_iter.try_for_each(|(label, value)| write!(f, "\\\n**{}:** {}", label, value))?;
+        }
+
+        write_abilities(f, &monster.special_abilities)?;
+
+        for (title, abilities) in [
+            ("Actions", &monster.actions),
+            ("Reactions", &monster.reactions),
+            ("Legendary Actions", &monster.legendary_actions),
+        ] {
+            if !abilities.is_empty() {
+                write!(f, "\n\n## {}", title)?;
+                write_abilities(f, abilities)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for ArmorClass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Value(value) => write!(f, "{}", value),
+            Self::Sources(sources) => {
+                let mut source_iter = sources.iter();
+
+                if let Some(source) = source_iter.next() {
+                    write!(f, "{}", source.value)?;
+
+                    match source.source_type.as_deref() {
+                        Some("dex") | None => {}
+                        Some(source_type) => write!(f, " ({} armor)", source_type)?,
+                    }
+                }
+
+                source_iter.try_for_each(|source| write!(f, ", {}", source.value))
+            }
+        }
+    }
+}
+
+fn write_abilities(f: &mut fmt::Formatter, abilities: &[Ability]) -> fmt::Result {
+    abilities.iter().try_for_each(|ability| {
+        write!(f, "\n\n***{}.*** ", ability.name)?;
+        write_text_block(
+            f,
+            &ability
+                .desc
+                .split('\n')
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| line.trim().to_string())
+                .collect::<Vec<_>>()[..],
+        )
+    })
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.to_string(),
+        value => value.to_string(),
+    }
+}
+
+fn capitalize_first(input: &str) -> String {
+    let mut chars = input.chars();
+
+    chars
+        .next()
+        .map(|c| {
+            c.to_uppercase()
+                .chain(chars.flat_map(char::to_lowercase))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn thousands(value: u32) -> String {
+    let digits = value.to_string();
+    let mut result = String::with_capacity(digits.len() + digits.len() / 3);
+
+    digits.chars().enumerate().for_each(|(i, c)| {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            result.push(',');
+        }
+        result.push(c);
+    });
+
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn display_test() {
+        let monster: Monster = serde_json::from_str(
+            r#"{
+                "index": "bog-lurker",
+                "name": "Bog Lurker",
+                "size": "Large",
+                "type": "monstrosity",
+                "subtype": null,
+                "alignment": "neutral evil",
+                "armor_class": [{"type": "natural", "value": 14}],
+                "hit_points": 59,
+                "hit_points_roll": "7d10+21",
+                "speed": {"walk": "20 ft.", "swim": "40 ft."},
+                "strength": 18,
+                "dexterity": 9,
+                "constitution": 16,
+                "intelligence": 5,
+                "wisdom": 12,
+                "charisma": 6,
+                "proficiencies": [
+                    {
+                        "value": 5,
+                        "proficiency": {
+                            "index": "saving-throw-con",
+                            "name": "Saving Throw: CON",
+                            "url": "/api/proficiencies/saving-throw-con"
+                        }
+                    },
+                    {
+                        "value": 3,
+                        "proficiency": {
+                            "index": "skill-stealth",
+                            "name": "Skill: Stealth",
+                            "url": "/api/proficiencies/skill-stealth"
+                        }
+                    }
+                ],
+                "damage_resistances": ["poison"],
+                "senses": {"darkvision": "60 ft.", "passive_perception": 11},
+                "languages": "",
+                "challenge_rating": 0.5,
+                "xp": 1100,
+                "special_abilities": [{"name": "Hold Breath", "desc": "It can hold its breath for 1 hour."}],
+                "actions": [{"name": "Bite", "desc": "Melee Weapon Attack: +6 to hit, reach 5 ft., one target. Hit: 13 (2d8 + 4) piercing damage."}]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            "`Bog Lurker` (large monstrosity, CR 1/2)",
+            monster.display_summary().to_string(),
+        );
+
+        assert_eq!(
+            "\
+# Bog Lurker
+*Large monstrosity, neutral evil*
+
+**Armor Class:** 14 (natural armor)\\
+**Hit Points:** 59 (7d10+21)\\
+**Speed:** 20 ft., swim 40 ft.
+
+| STR | DEX | CON | INT | WIS | CHA |
+|:-:|:-:|:-:|:-:|:-:|:-:|
+| 18 (+4) | 9 (-1) | 16 (+3) | 5 (-3) | 12 (+1) | 6 (-2) |
+
+**Saving Throws:** Con +5\\
+**Skills:** Stealth +3\\
+**Damage Resistances:** poison\\
+**Senses:** darkvision 60 ft., passive Perception 11\\
+**Languages:** —\\
+**Challenge:** 1/2 (1,100 XP)
+
+***Hold Breath.*** It can hold its breath for 1 hour.
+
+## Actions
+
+***Bite.*** Melee Weapon Attack: +6 to hit, reach 5 ft., one target. Hit: 13 (2d8 + 4) piercing damage.",
+            monster.display_details().to_string(),
+        );
+    }
+
+    #[test]
+    fn armor_class_test() {
+        let armor_class: ArmorClass = serde_json::from_str("13").unwrap();
+        assert_eq!("13", armor_class.to_string());
+
+        let armor_class: ArmorClass =
+            serde_json::from_str(r#"[{"type": "dex", "value": 13}]"#).unwrap();
+        assert_eq!("13", armor_class.to_string());
+    }
+
+    #[test]
+    fn thousands_test() {
+        assert_eq!("10", thousands(10));
+        assert_eq!("1,100", thousands(1100));
+        assert_eq!("155,000", thousands(155000));
+    }
+}
@@ -29,6 +29,9 @@ pub struct Spell {
 
     #[serde(default)]
     concentration: bool,
+
+    #[serde(default)]
+    classes: Vec<Reference>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -47,6 +50,21 @@ impl Spell {
         crate::to_camel_case(&self.index)
     }
 
+    /// The spell level, or 0 for a cantrip.
+    pub fn level(&self) -> u8 {
+        self.level
+    }
+
+    /// The school of magic in lowercase, eg. "necromancy".
+    pub fn school(&self) -> String {
+        self.school.name.to_lowercase()
+    }
+
+    /// The names of the classes that can learn the spell, eg. "Wizard".
+    pub fn classes(&self) -> Vec<String> {
+        self.classes.iter().map(|c| c.name.to_owned()).collect()
+    }
+
     pub fn display_summary(&self) -> SummaryView {
         SummaryView(self)
     }
@@ -1,3 +1,4 @@
+mod srd_5e_classes;
 mod srd_5e_conditions;
 mod srd_5e_item_categories;
 mod srd_5e_items;
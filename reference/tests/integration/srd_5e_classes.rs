@@ -0,0 +1,38 @@
+use initiative_reference::srd_5e::{classes, levels};
+
+#[test]
+fn fighter() {
+    let classes = classes().unwrap();
+    let levels = levels().unwrap();
+    let fighter = classes.iter().find(|c| c.name == "Fighter").unwrap();
+
+    assert_eq!(
+        (1..=20).collect::<Vec<u8>>(),
+        fighter
+            .levels(&levels)
+            .iter()
+            .map(|level| level.level)
+            .collect::<Vec<_>>(),
+    );
+
+    let output = format!("{}", fighter.display_level(&levels, 2));
+    assert!(
+        output.starts_with("# Fighter 2\n\n**Hit Die:** d10\\\n**Saving Throws:** STR, CON\\\n"),
+        "{}",
+        output,
+    );
+    assert!(
+        output.contains("\\\n**Proficiency Bonus:** +2\n\n## Features\n\n* **1st level:** "),
+        "{}",
+        output
+    );
+    assert!(!output.contains("## Spellcasting"), "{}", output);
+
+    let output = format!("{}", fighter.display_details(&levels));
+    assert!(
+        output
+            .contains("\n\n| Level | Proficiency Bonus | Features |\n|--:|--:|---|\n| 1st | +2 | "),
+        "{}",
+        output,
+    );
+}
@@ -101,6 +101,10 @@ fn shield() {
 **Weight:** 6 lbs",
         format!("{}", item.display_details()),
     );
+
+    assert_eq!("shield", item.index());
+    assert_eq!(Some(6.0), item.weight());
+    assert_eq!(Some(1000), item.cost_cp());
 }
 
 #[test]
@@ -13,6 +13,9 @@ fn deck_of_many_things() {
         format!("{}", magic_item.display_summary()),
     );
 
+    assert_eq!(Some("legendary".to_string()), magic_item.rarity());
+    assert!(!magic_item.requires_attunement());
+
     assert_eq!("\
 # Deck of Many Things
 
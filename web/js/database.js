@@ -4,6 +4,54 @@ import * as wasm from "initiative-web"
 
 const dexie = new Dexie("initiative")
 
+// Things and key-values belong to the campaign identified by the current namespace. The empty
+// namespace holds the data of the default campaign, including anything saved before campaigns
+// were introduced.
+let namespace = ""
+
+// Names are looked up by a key that ignores case, accents, and extra whitespace. New keys come
+// from `normalize` in the core crate; this is the same algorithm, for keying existing records.
+function nameKey(name) {
+  return (name ?? "")
+    .split(/\s+/)
+    .filter((word) => word !== "")
+    .map((word) => word.normalize("NFKD").replace(/\p{M}/gu, "").toLowerCase().replace(/ß/g, "ss"))
+    .join(" ")
+}
+
+// Unlocked fields are stored as `{ unlocked: value }`.
+function fieldValue(field) {
+  return field?.unlocked ?? field
+}
+
+dexie.version(10).stores({
+  things: "&uuid, name, *name_keys, namespace, type",
+  keyValue: "&key",
+}).upgrade((tx) => {
+  return tx.table("things").toCollection().modify((thing) => {
+    thing.name_keys = [fieldValue(thing.name), ...(fieldValue(thing.aliases) ?? [])].map(nameKey)
+    delete thing.name_key
+  })
+})
+
+dexie.version(9).stores({
+  things: "&uuid, name, name_key, namespace, type",
+  keyValue: "&key",
+}).upgrade((tx) => {
+  return tx.table("things").toCollection().modify((thing) => {
+    thing.name_key = nameKey(fieldValue(thing.name))
+  })
+})
+
+dexie.version(8).stores({
+  things: "&uuid, name, namespace, type",
+  keyValue: "&key",
+}).upgrade((tx) => {
+  return tx.table("things").toCollection().modify((thing) => {
+    thing.namespace = ""
+  })
+})
+
 dexie.version(7).stores({
   things: "&uuid, &name, type",
   keyValue: "&key",
@@ -120,47 +168,85 @@ export function health_check() {
   return !dexie.hasFailed()
 }
 
+export function set_namespace(value) {
+  namespace = value
+}
+
+function inNamespace(thing) {
+  return (thing.namespace ?? "") === namespace
+}
+
+function withoutNamespace(thing) {
+  if (thing) {
+    const result = { ...thing }
+    delete result.namespace
+    delete result.name_keys
+    return result
+  }
+}
+
+function namespacedKey(key) {
+  return namespace === "" ? key : `${namespace}/${key}`
+}
+
 export async function delete_thing_by_uuid(uuid) {
-  return dexie.things.delete(uuid)
+  const thing = await dexie.things.get({ uuid })
+
+  if (thing && inNamespace(thing)) {
+    return dexie.things.delete(uuid)
+  } else {
+    throw new Error(`No thing with UUID ${uuid} in the current campaign.`)
+  }
 }
 
 export async function get_all_the_things() {
-  return dexie.things.toArray()
+  return dexie.things
+    .where("namespace")
+    .equals(namespace)
+    .toArray()
+    .then((things) => things.map(withoutNamespace))
 }
 
 export async function get_thing_by_uuid(uuid) {
-  return dexie.things.get({ uuid })
+  return dexie.things
+    .get({ uuid })
+    .then((thing) => thing && inNamespace(thing) ? withoutNamespace(thing) : undefined)
 }
 
-export async function get_thing_by_name(name) {
+export async function get_thing_by_name(name_key) {
   return dexie.things
-    .where("name")
-    .equalsIgnoreCase(name)
+    .where("name_keys")
+    .equals(name_key)
+    .filter(inNamespace)
     .first()
+    .then(withoutNamespace)
 }
 
-export async function get_things_by_name_start(name, limit) {
+export async function get_things_by_name_start(name_key, limit) {
   return dexie.things
-    .where("name")
-    .startsWithIgnoreCase(name)
+    .where("name_keys")
+    .startsWith(name_key)
+    .distinct()
+    .filter(inNamespace)
     .limit(limit)
     .toArray()
+    .then((things) => things.map(withoutNamespace))
 }
 
-export async function save_thing(thing) {
-  return dexie.things.put(thing)
+export async function save_thing(thing, name_keys) {
+  return dexie.things.put({ ...thing, namespace, name_keys })
 }
 
 export async function set_value(key, value) {
-  return dexie.keyValue.put({key, value})
+  return dexie.keyValue.put({key: namespacedKey(key), value})
 }
 
 export async function get_value(key) {
-  return dexie.keyValue.get(key).then((v) => v?.value)
+  return dexie.keyValue.get(namespacedKey(key)).then((v) => v?.value)
 }
 
 export async function delete_value(key) {
-  return dexie.keyValue.delete(key)
+  return dexie.keyValue.delete(namespacedKey(key))
 }
 
 export async function export_database(data) {
@@ -18,6 +18,21 @@ wasm.initialize("terminal")
   .then((motd) => terminal.output(motd))
   .catch((err) => console.log(err))
 
+// Changes are saved in batches, so write whatever is outstanding every few seconds, and before the
+// page goes away.
+setInterval(() => wasm.flush().catch((err) => console.log(err)), 5000)
+
+document.addEventListener("visibilitychange", () => {
+  if (document.visibilityState === "hidden") {
+    wasm.flush().catch((err) => console.log(err))
+  }
+})
+
+window.addEventListener(
+  "pagehide",
+  () => wasm.flush().catch((err) => console.log(err)),
+)
+
 terminalElement.addEventListener(
   "initiative.export",
   async (event) => await export_database(event.detail),
@@ -39,6 +54,22 @@ terminalElement.addEventListener(
   ),
 )
 
+terminalElement.addEventListener(
+  "initiative.startHomebrewImport",
+  async (event) => await import_database(
+    async (data) => {
+      try {
+        terminal.output(await wasm.import_homebrew(data))
+      } catch (e) {
+        terminal.output("! " + e)
+      }
+    },
+    async (e) => {
+      terminal.output("! " + e)
+    }
+  ),
+)
+
 terminalElement.addEventListener(
   "initiative.command",
   async (event) => terminal.output(await wasm.command(event.detail.command)),
@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use initiative_core::{Thing, Uuid};
+use initiative_core::{normalize, normalize_prefix, Thing, Uuid};
 use wasm_bindgen::prelude::*;
 
 #[derive(Default)]
@@ -27,6 +27,15 @@ impl initiative_core::DataStore for DataStore {
     }
 
     async fn get_all_the_things(&self) -> Result<Vec<Thing>, ()> {
+        Ok(self
+            .get_all_the_thing_values()
+            .await?
+            .into_iter()
+            .filter_map(|value| serde_json::from_str(&value.to_string()).ok())
+            .collect())
+    }
+
+    async fn get_all_the_thing_values(&self) -> Result<Vec<serde_json::Value>, ()> {
         get_all_the_things()
             .await
             .map_err(|_| ())?
@@ -43,7 +52,7 @@ impl initiative_core::DataStore for DataStore {
     }
 
     async fn get_thing_by_name(&self, name: &str) -> Result<Option<Thing>, ()> {
-        get_thing_by_name(name)
+        get_thing_by_name(&normalize(name))
             .await
             .map_err(|_| ())?
             .into_serde()
@@ -55,18 +64,33 @@ impl initiative_core::DataStore for DataStore {
         name: &str,
         limit: Option<usize>,
     ) -> Result<Vec<Thing>, ()> {
-        get_things_by_name_start(name, limit.unwrap_or(usize::MAX))
-            .await
-            .map_err(|_| ())?
-            .into_serde()
-            .map_err(|_| ())
+        let values: Vec<serde_json::Value> =
+            get_things_by_name_start(&normalize_prefix(name), limit.unwrap_or(usize::MAX))
+                .await
+                .map_err(|_| ())?
+                .into_serde()
+                .map_err(|_| ())?;
+
+        Ok(values
+            .into_iter()
+            .filter_map(|value| serde_json::from_str(&value.to_string()).ok())
+            .collect())
     }
 
     async fn save_thing(&mut self, thing: &Thing) -> Result<(), ()> {
-        save_thing(JsValue::from_serde(thing).unwrap())
-            .await
-            .map(|_| ())
-            .map_err(|_| ())
+        // Things are looked up by any of their names, so each of them is indexed.
+        let name_keys: Vec<String> = std::iter::once(thing.name().to_string())
+            .chain(thing.aliases().iter().cloned())
+            .map(|name| normalize(&name))
+            .collect();
+
+        save_thing(
+            JsValue::from_serde(thing).unwrap(),
+            JsValue::from_serde(&name_keys).unwrap(),
+        )
+        .await
+        .map(|_| ())
+        .map_err(|_| ())
     }
 
     async fn set_value(&mut self, key: &str, value: &str) -> Result<(), ()> {
@@ -80,6 +104,10 @@ impl initiative_core::DataStore for DataStore {
     async fn delete_value(&mut self, key: &str) -> Result<(), ()> {
         delete_value(key).await.map(|_| ()).map_err(|_| ())
     }
+
+    fn set_namespace(&mut self, namespace: Option<&str>) {
+        set_namespace(namespace.unwrap_or_default());
+    }
 }
 
 #[wasm_bindgen(module = "/js/database.js")]
@@ -96,13 +124,13 @@ extern "C" {
     async fn get_thing_by_uuid(uuid: JsValue) -> Result<JsValue, JsValue>;
 
     #[wasm_bindgen(catch)]
-    async fn get_thing_by_name(name: &str) -> Result<JsValue, JsValue>;
+    async fn get_thing_by_name(name_key: &str) -> Result<JsValue, JsValue>;
 
     #[wasm_bindgen(catch)]
-    async fn get_things_by_name_start(name: &str, limit: usize) -> Result<JsValue, JsValue>;
+    async fn get_things_by_name_start(name_key: &str, limit: usize) -> Result<JsValue, JsValue>;
 
     #[wasm_bindgen(catch)]
-    async fn save_thing(thing: JsValue) -> Result<JsValue, JsValue>;
+    async fn save_thing(thing: JsValue, name_keys: JsValue) -> Result<JsValue, JsValue>;
 
     #[wasm_bindgen(catch)]
     async fn set_value(key: &str, value: &str) -> Result<JsValue, JsValue>;
@@ -112,4 +140,6 @@ extern "C" {
 
     #[wasm_bindgen(catch)]
     async fn delete_value(key: &str) -> Result<JsValue, JsValue>;
+
+    fn set_namespace(namespace: &str);
 }
@@ -22,8 +22,9 @@ pub async fn initialize(element_id: JsValue) -> String {
 pub async fn command(input: JsValue) -> JsValue {
     if let Some(input) = input.as_string() {
         app()
-            .command(&input)
+            .command_structured(&input)
             .await
+            .into_annotated_markdown()
             .unwrap_or_else(|e| format!("! {}", e))
             .into()
     } else {
@@ -31,6 +32,15 @@ pub async fn command(input: JsValue) -> JsValue {
     }
 }
 
+#[wasm_bindgen]
+pub async fn command_structured(input: JsValue) -> JsValue {
+    if let Some(input) = input.as_string() {
+        JsValue::from_serde(&app().command_structured(&input).await).unwrap()
+    } else {
+        JsValue::undefined()
+    }
+}
+
 #[wasm_bindgen]
 pub async fn autocomplete(input: JsValue) -> JsValue {
     if let Some(input) = input.as_string() {
@@ -52,6 +62,23 @@ pub async fn bulk_import(data: JsValue) -> Result<String, String> {
         .await
 }
 
+#[wasm_bindgen]
+pub async fn import_homebrew(data: JsValue) -> Result<String, String> {
+    app()
+        .import_homebrew(data.into_serde().map_err(|e| {
+            format!(
+                "The file you tried to import is not valid. The parser error was {}.",
+                e
+            )
+        })?)
+        .await
+}
+
+#[wasm_bindgen]
+pub async fn flush() -> Result<(), String> {
+    app().flush().await
+}
+
 fn event_dispatcher(event: core::Event) {
     let js_event = match event {
         core::Event::Export(data) => {
@@ -60,6 +87,12 @@ fn event_dispatcher(event: core::Event) {
             CustomEvent::new_with_event_init_dict("initiative.export", &init).unwrap()
         }
         core::Event::Import => CustomEvent::new("initiative.startImport").unwrap(),
+        core::Event::ImportHomebrew => CustomEvent::new("initiative.startHomebrewImport").unwrap(),
+        core::Event::Ambience(ambience) => {
+            let mut init = CustomEventInit::new();
+            init.detail(&JsValue::from_serde(&ambience).unwrap());
+            CustomEvent::new_with_event_init_dict("initiative.ambience", &init).unwrap()
+        }
     };
 
     get_root_element()
@@ -76,7 +109,9 @@ fn app() -> &'static mut core::app::App {
     unsafe {
         if APP.is_none() {
             let data_store = DataStore;
-            APP = Some(core::app(data_store, &event_dispatcher));
+            let mut app = core::app(data_store, &event_dispatcher);
+            app.set_clock(|| js_sys::Date::now() as u64);
+            APP = Some(app);
         }
 
         APP.as_mut().unwrap()